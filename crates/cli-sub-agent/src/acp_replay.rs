@@ -0,0 +1,78 @@
+//! `csa acp replay`: feed a captured ACP conversation back to an agent.
+//!
+//! Captures are written by ACP runs with `CSA_ACP_CAPTURE=1`. Replaying one
+//! against the agent that misbehaved, or against the mock built from the
+//! capture itself, reproduces a transport issue without rebuilding the
+//! environment it happened in.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use csa_acp::replay::{ReplayAgent, ReplayReport, replay_capture};
+
+use crate::cli::AcpCommands;
+
+/// Dispatch `csa acp <command>`.
+pub(crate) async fn handle_acp_command(cmd: AcpCommands) -> Result<()> {
+    match cmd {
+        AcpCommands::Replay {
+            capture,
+            timeout_secs,
+            json,
+            agent,
+        } => run_acp_replay(&capture, &agent, Duration::from_secs(timeout_secs), json).await,
+    }
+}
+
+async fn run_acp_replay(
+    capture: &Path,
+    agent: &[String],
+    timeout: Duration,
+    json: bool,
+) -> Result<()> {
+    let frames = csa_acp::capture::read_capture(capture)?;
+    let working_dir = std::env::current_dir().context("failed to resolve working directory")?;
+    let target = match agent.split_first() {
+        Some((command, args)) => ReplayAgent::Command {
+            command,
+            args,
+            working_dir: &working_dir,
+        },
+        None => ReplayAgent::Mock,
+    };
+    let report = replay_capture(&frames, target, timeout).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_report(&report));
+    }
+    if !report.matches_capture() {
+        bail!("replay diverged from {}", capture.display());
+    }
+    Ok(())
+}
+
+fn format_report(report: &ReplayReport) -> String {
+    let mut text = format!(
+        "Replayed {} client frame(s); the agent sent {} frame(s)\n",
+        report.sent,
+        report.received.len()
+    );
+    for key in &report.missing {
+        let _ = writeln!(text, "  missing: {key}");
+    }
+    for key in &report.mismatched {
+        let _ = writeln!(text, "  outcome differs: {key}");
+    }
+    if let Some(error) = &report.error {
+        let _ = writeln!(text, "  stopped early: {error}");
+    }
+    text
+}
+
+#[cfg(test)]
+#[path = "acp_replay_tests.rs"]
+mod tests;
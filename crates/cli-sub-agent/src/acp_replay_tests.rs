@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn format_report_lists_every_divergence() {
+    let report = ReplayReport {
+        sent: 3,
+        received: vec![serde_json::json!({ "id": 0, "result": {} })],
+        missing: vec!["response to id 1".to_string()],
+        mismatched: vec!["response to id 0".to_string()],
+        error: Some("failed to write frame 3: broken pipe".to_string()),
+    };
+
+    assert_eq!(
+        format_report(&report),
+        "Replayed 3 client frame(s); the agent sent 1 frame(s)\n\
+         \x20 missing: response to id 1\n\
+         \x20 outcome differs: response to id 0\n\
+         \x20 stopped early: failed to write frame 3: broken pipe\n"
+    );
+}
+
+#[test]
+fn format_report_of_a_matching_replay_is_one_line() {
+    let report = ReplayReport {
+        sent: 2,
+        ..ReplayReport::default()
+    };
+    assert_eq!(
+        format_report(&report),
+        "Replayed 2 client frame(s); the agent sent 0 frame(s)\n"
+    );
+}
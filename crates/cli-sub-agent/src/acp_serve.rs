@@ -0,0 +1,295 @@
+//! `csa acp-serve`: CSA as an ACP agent for editors such as Zed.
+//!
+//! Every ACP session is a CSA session created when the editor opens it, so
+//! each prompt turn resumes that session through the same routing, tier,
+//! slot and session pipeline as `csa run --session`. While a turn runs, new
+//! bytes of the session's `output.log` spool are streamed back to the editor.
+//! Unsandboxed `claude-code` turns share pooled agent processes across the
+//! sessions of the connection.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use csa_core::types::ToolName;
+use tokio::sync::mpsc;
+use tracing::info;
+
+use crate::pipeline::ConfigRefs;
+use crate::startup_env::StartupSubtreeEnv;
+
+/// How often the output spool is checked for new bytes during a turn.
+const SPOOL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Description given to CSA sessions opened by an ACP client.
+const ACP_SESSION_DESCRIPTION: &str = "acp-serve";
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AcpServeOptions {
+    pub(crate) tool: Option<ToolName>,
+    pub(crate) tier: Option<String>,
+}
+
+struct CsaServeBackend {
+    options: AcpServeOptions,
+    startup_env: StartupSubtreeEnv,
+    /// Project root of every session opened or loaded over this connection.
+    project_roots: RefCell<HashMap<String, PathBuf>>,
+}
+
+pub(crate) async fn run_acp_serve(
+    options: AcpServeOptions,
+    startup_env: &StartupSubtreeEnv,
+) -> Result<()> {
+    info!("Starting ACP agent server on stdio");
+    // Turns of all sessions on this connection run in this process, so they
+    // can share agent processes.
+    csa_executor::enable_acp_agent_pool();
+    let backend = CsaServeBackend {
+        options,
+        startup_env: startup_env.clone(),
+        project_roots: RefCell::new(HashMap::new()),
+    };
+    csa_acp::serve_stdio(backend)
+        .await
+        .context("ACP server stopped")
+}
+
+fn project_root_for(cwd: &Path) -> Result<PathBuf> {
+    let cwd = cwd
+        .to_str()
+        .with_context(|| format!("session cwd is not UTF-8: {}", cwd.display()))?;
+    crate::pipeline::determine_project_root(Some(cwd))
+}
+
+#[async_trait::async_trait(?Send)]
+impl csa_acp::AcpServeBackend for CsaServeBackend {
+    async fn new_session(&self, cwd: PathBuf) -> Result<String> {
+        let project_root = project_root_for(&cwd)?;
+        let session =
+            csa_session::create_session(&project_root, Some(ACP_SESSION_DESCRIPTION), None, None)?;
+        self.project_roots
+            .borrow_mut()
+            .insert(session.meta_session_id.clone(), project_root);
+        Ok(session.meta_session_id)
+    }
+
+    async fn load_session(&self, session_id: &str, cwd: PathBuf) -> Result<()> {
+        let project_root = project_root_for(&cwd)?;
+        csa_session::load_session(&project_root, session_id)
+            .with_context(|| format!("cannot load CSA session {session_id}"))?;
+        self.project_roots
+            .borrow_mut()
+            .insert(session_id.to_string(), project_root);
+        Ok(())
+    }
+
+    async fn prompt(
+        &self,
+        session_id: &str,
+        prompt: String,
+        output: mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let project_root = self
+            .project_roots
+            .borrow()
+            .get(session_id)
+            .cloned()
+            .with_context(|| format!("session {session_id} was not opened on this connection"))?;
+        self.run_turn(session_id, &project_root, &prompt, &output)
+            .await
+    }
+}
+
+impl CsaServeBackend {
+    async fn run_turn(
+        &self,
+        session_id: &str,
+        project_root: &Path,
+        prompt: &str,
+        output: &mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let effective_config = csa_config::EffectiveConfig::load(project_root)?;
+        let config = effective_config.project;
+        let global_config = effective_config.global;
+        let model_catalog = effective_config.model_catalog;
+
+        let (tool, model_spec, model) =
+            crate::run_helpers::resolve_tool_and_model(crate::run_helpers::RoutingRequest {
+                tool: self.options.tool,
+                tier: self.options.tier.as_deref(),
+                config: config.as_ref(),
+                global_config: Some(&global_config),
+                model_catalog: Some(&model_catalog),
+                tier_bypass_allowed: crate::run_helpers::tier_bypass_allowed(
+                    config.as_ref(),
+                    &global_config,
+                    false,
+                ),
+                tool_is_auto_resolved: self.options.tool.is_none(),
+                ..crate::run_helpers::RoutingRequest::new(project_root)
+            })?;
+        let executor = crate::pipeline::build_and_validate_executor(
+            &tool,
+            model_spec.as_deref(),
+            model.as_deref(),
+            None,
+            ConfigRefs {
+                project: config.as_ref(),
+                global: Some(&global_config),
+                model_catalog: Some(&model_catalog),
+            },
+            model_spec.is_none(),
+            false,
+            false,
+        )
+        .await?;
+
+        let slots_dir = csa_config::GlobalConfig::slots_dir()?;
+        let _slot = match csa_lock::slot::try_acquire_slot(
+            &slots_dir,
+            executor.tool_name(),
+            global_config.max_concurrent(executor.tool_name()),
+            Some(session_id),
+        )? {
+            csa_lock::slot::SlotAcquireResult::Acquired(slot) => slot,
+            csa_lock::slot::SlotAcquireResult::Exhausted(status) => {
+                return Err(
+                    anyhow::Error::new(csa_core::error::AppError::SlotExhausted {
+                        tool: executor.tool_name().to_string(),
+                        max: status.max_slots,
+                        alternatives: Vec::new(),
+                    })
+                    .context(format!(
+                        "all {} slots for '{}' are occupied; try again later",
+                        status.max_slots,
+                        executor.tool_name()
+                    )),
+                );
+            }
+        };
+
+        let extra_env = global_config.build_execution_env(
+            executor.tool_name(),
+            csa_config::ExecutionEnvOptions::from_no_failover(false),
+        );
+        let subtree_pin = crate::run_cmd_model_pin::resolve_subtree_model_pin(
+            model_spec.as_deref(),
+            false,
+            false,
+        );
+        let idle_timeout_seconds =
+            crate::pipeline::resolve_idle_timeout_seconds(config.as_ref(), None);
+        let initial_response_timeout_seconds =
+            crate::pipeline::resolve_initial_response_timeout_for_tool(
+                config.as_ref(),
+                None,
+                None,
+                executor.tool_name(),
+            );
+
+        let mut spool = SpoolTail::at_end(
+            csa_session::get_session_dir(project_root, session_id)?.join("output.log"),
+        );
+        let execution = crate::pipeline::execute_with_session(
+            &executor,
+            &tool,
+            prompt,
+            Some(session_id.to_string()),
+            false,
+            None,
+            None,
+            project_root,
+            config.as_ref(),
+            extra_env.as_ref(),
+            subtree_pin.as_ref(),
+            Some("run"),
+            self.options.tier.as_deref(),
+            None,
+            csa_process::StreamMode::BufferOnly,
+            idle_timeout_seconds,
+            initial_response_timeout_seconds,
+            None,
+            None,
+            Some(&global_config),
+            None,
+            crate::run_resource_overrides::RunResourceOverrides::inherited().for_child(),
+            false,
+            false,
+            &[],
+            &[],
+            None,
+            false,
+            &self.startup_env,
+        );
+        tokio::pin!(execution);
+        let result = loop {
+            tokio::select! {
+                result = &mut execution => break result?,
+                _ = tokio::time::sleep(SPOOL_POLL_INTERVAL) => spool.forward(output),
+            }
+        };
+        spool.forward(output);
+
+        if result.exit_code != 0 {
+            bail!(
+                "{} exited with code {}: {}",
+                executor.tool_name(),
+                result.exit_code,
+                result.summary
+            );
+        }
+        Ok(result.output)
+    }
+}
+
+/// Reads what was appended to a spool file since the last call.
+struct SpoolTail {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl SpoolTail {
+    /// Start after the current contents, which belong to earlier turns.
+    fn at_end(path: PathBuf) -> Self {
+        let offset = std::fs::metadata(&path).map_or(0, |meta| meta.len());
+        Self { path, offset }
+    }
+
+    fn read_new(&mut self) -> Option<String> {
+        let mut file = File::open(&self.path).ok()?;
+        let len = file.metadata().ok()?.len();
+        if len < self.offset {
+            // Rotated: the live file restarted from zero.
+            self.offset = 0;
+        }
+        if len == self.offset {
+            return None;
+        }
+        file.seek(SeekFrom::Start(self.offset)).ok()?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).ok()?;
+        // Hold back an incomplete UTF-8 sequence until the rest is written.
+        let valid = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(_) => bytes.len(),
+        };
+        self.offset += valid as u64;
+        (valid > 0).then(|| String::from_utf8_lossy(&bytes[..valid]).into_owned())
+    }
+
+    fn forward(&mut self, output: &mpsc::UnboundedSender<String>) {
+        if let Some(text) = self.read_new() {
+            let _ = output.send(text);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "acp_serve_tests.rs"]
+mod tests;
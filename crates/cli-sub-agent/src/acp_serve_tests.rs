@@ -0,0 +1,59 @@
+use super::*;
+use std::io::Write;
+
+fn append(path: &Path, bytes: &[u8]) {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .expect("open spool")
+        .write_all(bytes)
+        .expect("append spool");
+}
+
+#[test]
+fn spool_tail_skips_earlier_turns_and_reads_appended_text() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let path = tmp.path().join("output.log");
+    append(&path, b"previous turn\n");
+
+    let mut tail = SpoolTail::at_end(path.clone());
+    assert_eq!(tail.read_new(), None);
+
+    append(&path, b"hello ");
+    append(&path, b"world\n");
+    assert_eq!(tail.read_new().as_deref(), Some("hello world\n"));
+    assert_eq!(tail.read_new(), None);
+}
+
+#[test]
+fn spool_tail_waits_for_split_utf8_and_follows_rotation() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let path = tmp.path().join("output.log");
+    let mut tail = SpoolTail::at_end(path.clone());
+    assert_eq!(tail.read_new(), None, "missing spool reads nothing");
+
+    let snowman = "☃".as_bytes();
+    append(&path, &[b'a', snowman[0]]);
+    assert_eq!(tail.read_new().as_deref(), Some("a"));
+    append(&path, &snowman[1..]);
+    assert_eq!(tail.read_new().as_deref(), Some("☃"));
+
+    std::fs::write(&path, "new").expect("rotate spool");
+    assert_eq!(tail.read_new().as_deref(), Some("new"));
+}
+
+#[test]
+fn spool_tail_forwards_to_the_output_channel() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let path = tmp.path().join("output.log");
+    let mut tail = SpoolTail::at_end(path.clone());
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    tail.forward(&tx);
+    append(&path, b"chunk");
+    tail.forward(&tx);
+
+    assert_eq!(rx.try_recv().ok().as_deref(), Some("chunk"));
+    assert!(rx.try_recv().is_err());
+}
@@ -1,15 +1,21 @@
+use anyhow::Result;
 use csa_core::audit::AuditManifest;
+use serde::Serialize;
 use std::collections::BTreeMap;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+use crate::audit::skills::SkillDrift;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub(crate) struct ManifestDiff {
     pub new: Vec<String>,
     pub modified: Vec<String>,
     pub deleted: Vec<String>,
+    /// Only counted in serialized output (see [`DiffSummary`]).
+    #[serde(skip)]
     pub unchanged: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub(crate) struct DiffSummary {
     pub new: usize,
     pub modified: usize,
@@ -26,6 +32,34 @@ impl ManifestDiff {
             unchanged: self.unchanged.len(),
         }
     }
+
+    /// Whether any file is new, modified or deleted.
+    pub(crate) fn has_changes(&self) -> bool {
+        !(self.new.is_empty() && self.modified.is_empty() && self.deleted.is_empty())
+    }
+}
+
+/// `csa audit diff --format json` payload.
+#[derive(Debug, Serialize)]
+pub(crate) struct DiffReport<'a> {
+    #[serde(flatten)]
+    pub(crate) files: &'a ManifestDiff,
+    pub(crate) summary: DiffSummary,
+    /// True when files changed or, with `--skills`, any skill drifted.
+    pub(crate) changed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) skills: Option<&'a [SkillDrift]>,
+}
+
+impl<'a> DiffReport<'a> {
+    pub(crate) fn new(files: &'a ManifestDiff, skills: Option<&'a [SkillDrift]>) -> Self {
+        Self {
+            files,
+            summary: files.summary(),
+            changed: files.has_changes() || skills.is_some_and(|drift| !drift.is_empty()),
+            skills,
+        }
+    }
 }
 
 pub(crate) fn diff_manifest(
@@ -53,3 +87,31 @@ pub(crate) fn diff_manifest(
 
     diff
 }
+
+pub(crate) fn print_report_text(report: &DiffReport<'_>) {
+    for (label, paths) in [
+        ("new", &report.files.new),
+        ("modified", &report.files.modified),
+        ("deleted", &report.files.deleted),
+    ] {
+        for path in paths {
+            println!("{label:<9} {path}");
+        }
+    }
+    let summary = report.summary;
+    println!(
+        "{} new, {} modified, {} deleted, {} unchanged.",
+        summary.new, summary.modified, summary.deleted, summary.unchanged
+    );
+    if let Some(skills) = report.skills {
+        for drift in skills {
+            println!("[{}] {}: {}", drift.scope, drift.subject, drift.detail);
+        }
+        println!("{} skill drift issue(s).", skills.len());
+    }
+}
+
+pub(crate) fn print_report_json(report: &DiffReport<'_>) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(report)?);
+    Ok(())
+}
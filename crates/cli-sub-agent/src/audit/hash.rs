@@ -3,9 +3,14 @@ use sha2::{Digest, Sha256};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
 const BUFFER_SIZE: usize = 8 * 1024;
 
+/// Files modified this recently get no stat fingerprint: a second write in
+/// the same mtime tick would otherwise keep the stale hash.
+pub(crate) const RACY_WINDOW: Duration = Duration::from_secs(2);
+
 pub(crate) fn hash_file(path: &Path) -> Result<String> {
     let mut file = File::open(path)
         .with_context(|| format!("Failed to open file for hashing: {}", path.display()))?;
@@ -24,3 +29,35 @@ pub(crate) fn hash_file(path: &Path) -> Result<String> {
 
     Ok(format!("sha256:{:x}", hasher.finalize()))
 }
+
+/// `size:mtime_ns:ctime_ns:inode` fingerprint of `path`, or `None` when
+/// unavailable or too fresh to trust.
+///
+/// The ctime catches writes that restore the mtime (`touch -r`, `utimes`),
+/// which cannot set the ctime back.
+pub(crate) fn file_stat(path: &Path) -> Option<String> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime_ns = meta
+        .modified()
+        .ok()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    #[cfg(unix)]
+    let (ctime_ns, inode) = {
+        use std::os::unix::fs::MetadataExt;
+        let ctime_ns = u128::try_from(meta.ctime()).ok()? * 1_000_000_000
+            + u128::try_from(meta.ctime_nsec()).ok()?;
+        (ctime_ns, meta.ino())
+    };
+    #[cfg(not(unix))]
+    let (ctime_ns, inode) = (0, 0);
+    let now_ns = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    if now_ns.saturating_sub(mtime_ns.max(ctime_ns)) < RACY_WINDOW.as_nanos() {
+        return None;
+    }
+    Some(format!("{}:{mtime_ns}:{ctime_ns}:{inode}", meta.len()))
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result, bail};
 use csa_core::audit::{AuditManifest, AuditStatus};
+use rayon::prelude::*;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
@@ -23,16 +24,75 @@ pub(crate) fn manifest_path(root: &Path) -> PathBuf {
     root.join(io::DEFAULT_MANIFEST_PATH)
 }
 
-pub(crate) fn scan_and_hash(root: &Path, ignores: &[String]) -> Result<BTreeMap<String, String>> {
-    let mut current = BTreeMap::new();
-    let files = scan::scan_directory(root, ignores)?;
-    for relative in files {
-        let validated = security::validate_path(&relative, root)?;
-        let key = path_to_key(&relative);
-        let hash_value = hash::hash_file(&validated)?;
-        current.insert(key, hash_value);
+/// Hashes of a scanned tree; see [`scan_tree`].
+#[derive(Debug, Default)]
+pub(crate) struct TreeScan {
+    pub(crate) hashes: BTreeMap<String, String>,
+    /// Stat fingerprints taken before hashing, for files that have one.
+    pub(crate) stats: BTreeMap<String, String>,
+    /// Files whose hash was reused from the previous manifest.
+    pub(crate) reused: usize,
+}
+
+struct ScannedFile {
+    key: String,
+    hash: String,
+    stat: Option<String>,
+    reused: bool,
+}
+
+/// Scan `root` and hash its text files in parallel.
+///
+/// With `previous`, a file whose stat fingerprint matches its manifest entry
+/// keeps the recorded hash without being read. Only `audit diff
+/// --changed-only` passes one: anything that reports status or writes (and
+/// so re-signs) the manifest re-reads every file.
+pub(crate) fn scan_tree(
+    root: &Path,
+    ignores: &[String],
+    previous: Option<&AuditManifest>,
+) -> Result<TreeScan> {
+    let files = scan::walk_directory(root, ignores)?;
+    let scanned = files
+        .par_iter()
+        .map(|relative| -> Result<Option<ScannedFile>> {
+            let validated = security::validate_path(relative, root)?;
+            let key = path_to_key(relative);
+            // Stat before hashing, so a concurrent write leaves a stale stat
+            // (re-hashed next time) rather than a stale hash.
+            let stat = hash::file_stat(&validated);
+            if let Some(entry) = previous.and_then(|manifest| manifest.files.get(&key))
+                && stat.is_some()
+                && entry.stat == stat
+            {
+                return Ok(Some(ScannedFile {
+                    hash: entry.hash.clone(),
+                    key,
+                    stat,
+                    reused: true,
+                }));
+            }
+            if scan::is_binary_file(&validated)? {
+                return Ok(None);
+            }
+            Ok(Some(ScannedFile {
+                hash: hash::hash_file(&validated)?,
+                key,
+                stat,
+                reused: false,
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut tree = TreeScan::default();
+    for file in scanned.into_iter().flatten() {
+        if let Some(stat) = file.stat {
+            tree.stats.insert(file.key.clone(), stat);
+        }
+        tree.reused += usize::from(file.reused);
+        tree.hashes.insert(file.key, file.hash);
     }
-    Ok(current)
+    Ok(tree)
 }
 
 /// Returns `true` if the string contains glob metacharacters (`*`, `?`, `[`).
@@ -211,6 +271,7 @@ mod tests {
                     auditor: None,
                     approved_by: None,
                     approved_at: None,
+                    stat: None,
                 },
             );
         }
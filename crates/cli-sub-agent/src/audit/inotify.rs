@@ -0,0 +1,130 @@
+//! Minimal inotify wrapper for `csa audit watch` (directory watches only).
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::{CString, OsStr};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Completed writes, renames in/out, creations and deletions. `IN_MODIFY` is
+/// left out so a single save produces one event rather than one per write.
+const WATCH_MASK: u32 = libc::IN_CLOSE_WRITE
+    | libc::IN_MOVED_TO
+    | libc::IN_MOVED_FROM
+    | libc::IN_CREATE
+    | libc::IN_DELETE;
+
+const EVENT_BUFFER_SIZE: usize = 16 * 1024;
+
+pub(crate) struct DirWatcher {
+    fd: OwnedFd,
+    dirs: HashMap<i32, PathBuf>,
+}
+
+impl DirWatcher {
+    pub(crate) fn new() -> Result<Self> {
+        // SAFETY: inotify_init1 takes no pointers; the result is checked below.
+        let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC | libc::IN_NONBLOCK) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error()).context("Failed to initialize inotify");
+        }
+        // SAFETY: `fd` is a freshly created descriptor owned by nothing else.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        Ok(Self {
+            fd,
+            dirs: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn watch_dir(&mut self, dir: &Path) -> Result<()> {
+        let path = CString::new(dir.as_os_str().as_bytes())
+            .with_context(|| format!("Path contains a NUL byte: {}", dir.display()))?;
+        // SAFETY: `path` is a valid NUL-terminated string for the call's duration.
+        let wd = unsafe { libc::inotify_add_watch(self.fd.as_raw_fd(), path.as_ptr(), WATCH_MASK) };
+        if wd < 0 {
+            return Err(std::io::Error::last_os_error())
+                .with_context(|| format!("Failed to watch {}", dir.display()));
+        }
+        self.dirs.insert(wd, dir.to_path_buf());
+        Ok(())
+    }
+
+    pub(crate) fn watched_dirs(&self) -> usize {
+        self.dirs.len()
+    }
+
+    /// Wait up to `timeout` and return the paths touched since the last call.
+    pub(crate) fn wait(&mut self, timeout: Duration) -> Result<BTreeSet<PathBuf>> {
+        let mut changed = BTreeSet::new();
+        let mut poll_fd = libc::pollfd {
+            fd: self.fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout_ms = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+        // SAFETY: `poll_fd` is a single valid pollfd.
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) };
+        if ready < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::Interrupted {
+                return Ok(changed);
+            }
+            return Err(err).context("Failed to poll inotify");
+        }
+        if ready == 0 {
+            return Ok(changed);
+        }
+
+        let mut buffer = [0_u8; EVENT_BUFFER_SIZE];
+        loop {
+            // SAFETY: `buffer` is writable for its full length.
+            let read = unsafe {
+                libc::read(
+                    self.fd.as_raw_fd(),
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                )
+            };
+            if read < 0 {
+                let err = std::io::Error::last_os_error();
+                match err.kind() {
+                    std::io::ErrorKind::WouldBlock => break,
+                    std::io::ErrorKind::Interrupted => continue,
+                    _ => return Err(err).context("Failed to read inotify events"),
+                }
+            }
+            if read == 0 {
+                break;
+            }
+            self.collect_events(&buffer[..read as usize], &mut changed);
+        }
+        Ok(changed)
+    }
+
+    fn collect_events(&mut self, bytes: &[u8], changed: &mut BTreeSet<PathBuf>) {
+        let header = std::mem::size_of::<libc::inotify_event>();
+        let mut offset = 0;
+        while offset + header <= bytes.len() {
+            // SAFETY: the kernel only returns whole events; `read_unaligned`
+            // copes with the byte buffer's alignment.
+            let event: libc::inotify_event =
+                unsafe { std::ptr::read_unaligned(bytes[offset..].as_ptr().cast()) };
+            let name_start = offset + header;
+            let name_end = (name_start + event.len as usize).min(bytes.len());
+            if event.mask & libc::IN_IGNORED != 0 {
+                self.dirs.remove(&event.wd);
+            } else if let Some(dir) = self.dirs.get(&event.wd)
+                && name_end > name_start
+            {
+                let name = bytes[name_start..name_end]
+                    .split(|byte| *byte == 0)
+                    .next()
+                    .unwrap_or_default();
+                changed.insert(dir.join(OsStr::from_bytes(name)));
+            }
+            offset = name_end;
+        }
+    }
+}
@@ -3,15 +3,22 @@ use csa_core::audit::AuditManifest;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::audit::signing;
+
 pub(crate) const DEFAULT_MANIFEST_PATH: &str = ".csa/audit/manifest.toml";
 
 pub(crate) fn load(path: &Path) -> Result<AuditManifest> {
     if !path.exists() {
+        signing::verify_on_load(path, None)?;
         return Ok(AuditManifest::new("."));
     }
 
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read audit manifest: {}", path.display()));
+    // A signature mismatch is tampering, not corruption: never recover from it.
+    if let Ok(raw) = &content {
+        signing::verify_on_load(path, Some(raw.as_bytes()))?;
+    }
     let manifest = content.and_then(|raw| {
         toml::from_str::<AuditManifest>(&raw)
             .with_context(|| format!("Failed to parse audit manifest: {}", path.display()))
@@ -30,13 +37,14 @@ pub(crate) fn save(path: &Path, manifest: &AuditManifest) -> Result<()> {
         })?;
     }
 
+    let resign_key = signing::resign_key(path, manifest.meta.signing_key.is_some())?;
     let mut to_save = manifest.clone();
     to_save.meta.updated_at = chrono::Utc::now().to_rfc3339();
 
     let content =
         toml::to_string_pretty(&to_save).context("Failed to serialize audit manifest to TOML")?;
     let tmp_path = path.with_extension("tmp");
-    fs::write(&tmp_path, content)
+    fs::write(&tmp_path, &content)
         .with_context(|| format!("Failed to write temporary manifest: {}", tmp_path.display()))?;
     fs::rename(&tmp_path, path).with_context(|| {
         format!(
@@ -45,6 +53,12 @@ pub(crate) fn save(path: &Path, manifest: &AuditManifest) -> Result<()> {
             tmp_path.display()
         )
     })?;
+    if let Some(key) = resign_key {
+        signing::write_signature(
+            &signing::signature_path(path),
+            &signing::sign(&key, content.as_bytes()),
+        )?;
+    }
     Ok(())
 }
 
@@ -1,11 +1,16 @@
 pub(crate) mod diff;
 pub(crate) mod hash;
 pub(crate) mod helpers;
+#[cfg(target_os = "linux")]
+pub(crate) mod inotify;
 pub(crate) mod io;
 pub(crate) mod scan;
 pub(crate) mod security;
+pub(crate) mod signing;
+pub(crate) mod skills;
 pub(crate) mod status;
 pub(crate) mod topo;
+pub(crate) mod watch;
 
 #[cfg(test)]
 mod tests;
@@ -1,12 +1,32 @@
 use anyhow::{Context, Result};
 use ignore::WalkBuilder;
+use rayon::prelude::*;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Component, Path, PathBuf};
 
 const BINARY_SNIFF_BYTES: usize = 8 * 1024;
 
+/// Non-ignored text files under `root`, relative and sorted.
 pub(crate) fn scan_directory(root: &Path, extra_ignores: &[String]) -> Result<Vec<PathBuf>> {
+    let canonical_root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to canonicalize scan root: {}", root.display()))?;
+    let files = walk_directory(root, extra_ignores)?;
+    let binary = files
+        .par_iter()
+        .map(|relative| is_binary_file(&canonical_root.join(relative)))
+        .collect::<Result<Vec<bool>>>()?;
+    Ok(files
+        .into_iter()
+        .zip(binary)
+        .filter_map(|(relative, binary)| (!binary).then_some(relative))
+        .collect())
+}
+
+/// Like [`scan_directory`] but without the binary sniff, which callers may
+/// skip for files they already know.
+pub(crate) fn walk_directory(root: &Path, extra_ignores: &[String]) -> Result<Vec<PathBuf>> {
     let canonical_root = root
         .canonicalize()
         .with_context(|| format!("Failed to canonicalize scan root: {}", root.display()))?;
@@ -72,9 +92,6 @@ pub(crate) fn scan_directory(root: &Path, extra_ignores: &[String]) -> Result<Ve
         if contains_skipped_dir(&relative) || matches_extra_ignore(&relative, extra_ignores) {
             continue;
         }
-        if is_binary_file(&canonical_path)? {
-            continue;
-        }
 
         files.push(relative);
     }
@@ -84,7 +101,7 @@ pub(crate) fn scan_directory(root: &Path, extra_ignores: &[String]) -> Result<Ve
     Ok(files)
 }
 
-fn is_binary_file(path: &Path) -> Result<bool> {
+pub(crate) fn is_binary_file(path: &Path) -> Result<bool> {
     let mut file = File::open(path).with_context(|| {
         format!(
             "Failed to open file during binary sniff: {}",
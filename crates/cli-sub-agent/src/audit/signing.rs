@@ -0,0 +1,346 @@
+//! Ed25519 signatures over the audit manifest.
+//!
+//! Each manifest has its own signing key in the OS keyring (Secret Service
+//! on Linux, the Keychain on macOS); the signature sits beside the manifest as
+//! `manifest.toml.sig`, and the manifest names the key's fingerprint in
+//! `meta.signing_key`. Once a manifest has a key, [`io::load`] refuses it
+//! unsigned or with bytes that no longer match, and [`io::save`] re-signs after
+//! csa's own edits, so only rewrites made outside csa break the signature.
+//!
+//! [`io::load`]: crate::audit::io::load
+//! [`io::save`]: crate::audit::io::save
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Utc;
+use data_encoding::HEXLOWER;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+pub(crate) const ALGORITHM: &str = "ed25519";
+const KEYRING_SERVICE: &str = "cli-sub-agent";
+const KEYRING_USER: &str = "audit-manifest-signing-key";
+
+/// Contents of `manifest.toml.sig`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ManifestSignature {
+    pub(crate) algorithm: String,
+    /// Hex-encoded public key of the signer.
+    pub(crate) public_key: String,
+    /// Hex-encoded signature over the manifest file bytes.
+    pub(crate) signature: String,
+    pub(crate) signed_at: String,
+}
+
+pub(crate) fn signature_path(manifest_path: &Path) -> PathBuf {
+    let mut path = manifest_path.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+/// Short, stable identifier for a public key.
+pub(crate) fn fingerprint(key: &VerifyingKey) -> String {
+    let digest = Sha256::digest(key.as_bytes());
+    format!("{ALGORITHM}:{}", &HEXLOWER.encode(&digest)[..16])
+}
+
+pub(crate) fn sign(key: &SigningKey, manifest_bytes: &[u8]) -> ManifestSignature {
+    use ed25519_dalek::Signer;
+    ManifestSignature {
+        algorithm: ALGORITHM.to_string(),
+        public_key: HEXLOWER.encode(key.verifying_key().as_bytes()),
+        signature: HEXLOWER.encode(&key.sign(manifest_bytes).to_bytes()),
+        signed_at: Utc::now().to_rfc3339(),
+    }
+}
+
+/// Check `signature` against `manifest_bytes`, accepting only `trusted` as signer.
+pub(crate) fn verify(
+    signature: &ManifestSignature,
+    manifest_bytes: &[u8],
+    trusted: &VerifyingKey,
+) -> Result<()> {
+    if signature.algorithm != ALGORITHM {
+        bail!("Unsupported signature algorithm '{}'", signature.algorithm);
+    }
+    let signer = HEXLOWER
+        .decode(signature.public_key.as_bytes())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| anyhow!("Signature carries a malformed public key"))?;
+    if signer != *trusted {
+        bail!(
+            "Manifest was signed by {}, not by the keyring key {}",
+            fingerprint(&signer),
+            fingerprint(trusted)
+        );
+    }
+    let signature = HEXLOWER
+        .decode(signature.signature.as_bytes())
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or_else(|| anyhow!("Signature is malformed"))?;
+    trusted
+        .verify_strict(manifest_bytes, &signature)
+        .map_err(|_| anyhow!("Signature does not match the manifest contents"))
+}
+
+pub(crate) fn read_signature(path: &Path) -> Result<Option<ManifestSignature>> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Failed to read manifest signature: {}", path.display()));
+        }
+    };
+    toml::from_str(&raw)
+        .map(Some)
+        .with_context(|| format!("Failed to parse manifest signature: {}", path.display()))
+}
+
+pub(crate) fn write_signature(path: &Path, signature: &ManifestSignature) -> Result<()> {
+    let content =
+        toml::to_string_pretty(signature).context("Failed to serialize manifest signature")?;
+    let tmp_path = path.with_extension("sig.tmp");
+    fs::write(&tmp_path, content).with_context(|| {
+        format!(
+            "Failed to write temporary signature: {}",
+            tmp_path.display()
+        )
+    })?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to replace manifest signature: {}", path.display()))
+}
+
+/// Verify the manifest at `manifest_path` as it is loaded; `manifest_bytes`
+/// is `None` when the manifest does not exist.
+///
+/// A manifest that never had a signing key passes. Once it has one, a missing
+/// manifest, a missing signature or a signature that does not verify is an
+/// error. Only when the keyring cannot be read and neither a signature nor
+/// `meta.signing_key` says the manifest is signed does the check pass, with a
+/// warning.
+pub(crate) fn verify_on_load(manifest_path: &Path, manifest_bytes: Option<&[u8]>) -> Result<()> {
+    let signature = read_signature(&signature_path(manifest_path))?;
+    let declared_key = manifest_bytes.and_then(declared_signing_key);
+    let key = keyring_signing_key(manifest_path);
+    check_loaded(
+        manifest_path,
+        manifest_bytes,
+        signature.as_ref(),
+        declared_key.as_deref(),
+        key,
+    )
+}
+
+/// The `meta.signing_key` fingerprint named by the manifest, if any.
+fn declared_signing_key(manifest_bytes: &[u8]) -> Option<String> {
+    let manifest: toml::Table = std::str::from_utf8(manifest_bytes).ok()?.parse().ok()?;
+    let fingerprint = manifest.get("meta")?.get("signing_key")?.as_str()?;
+    Some(fingerprint.to_string())
+}
+
+fn check_loaded(
+    manifest_path: &Path,
+    manifest_bytes: Option<&[u8]>,
+    signature: Option<&ManifestSignature>,
+    declared_key: Option<&str>,
+    key: Result<Option<SigningKey>>,
+) -> Result<()> {
+    let path = manifest_path.display();
+    if manifest_bytes.is_none() && signature.is_some() {
+        bail!("Audit manifest {path} is missing but its signature is present");
+    }
+    let signed = signature.is_some() || declared_key.is_some();
+    let key = match key {
+        Ok(key) => key,
+        Err(err) if !signed => {
+            tracing::warn!(
+                error = %err,
+                path = %path,
+                "OS keyring unavailable; cannot tell whether the audit manifest must be signed"
+            );
+            return Ok(());
+        }
+        Err(err) => {
+            return Err(err).with_context(|| {
+                format!(
+                    "Audit manifest {path} is signed (key {}) but its signing key cannot be read",
+                    declared_key.unwrap_or("unknown")
+                )
+            });
+        }
+    };
+    let Some(manifest_bytes) = manifest_bytes else {
+        if key.is_some() {
+            bail!("Audit manifest {path} is missing but it has a signing key");
+        }
+        return Ok(());
+    };
+    match (signature, key) {
+        (None, None) if !signed => Ok(()),
+        (_, None) => bail!(
+            "Audit manifest {path} is signed but no signing key is in the OS keyring; \
+             review it, then run `csa audit sign` to sign it with a new key"
+        ),
+        (None, Some(_)) => bail!(
+            "Audit manifest {path} has a signing key but its signature is missing; it was \
+             changed outside csa. Review it, then run `csa audit sign` to accept the current contents"
+        ),
+        (Some(signature), Some(key)) => verify(signature, manifest_bytes, &key.verifying_key())
+            .with_context(|| {
+                format!(
+                    "Audit manifest {path} failed signature verification; it was changed outside csa. \
+                     Review it, then run `csa audit sign` to accept the current contents"
+                )
+            }),
+    }
+}
+
+/// Key to re-sign with after csa rewrites `manifest_path`, or `None` when the
+/// manifest has no signing key. Fails before anything is written if it is
+/// signed (or `declares_key`) but the key is gone.
+pub(crate) fn resign_key(manifest_path: &Path, declares_key: bool) -> Result<Option<SigningKey>> {
+    let signed = declares_key || signature_path(manifest_path).exists();
+    match keyring_signing_key(manifest_path) {
+        Ok(Some(key)) => Ok(Some(key)),
+        Ok(None) if signed => bail!(
+            "Audit manifest {} is signed but no signing key is in the OS keyring; \
+             run `csa audit sign` to sign it with a new key",
+            manifest_path.display()
+        ),
+        Ok(None) => Ok(None),
+        Err(err) if signed => Err(err),
+        Err(err) => {
+            tracing::warn!(error = %err, "OS keyring unavailable; audit manifest not re-signed");
+            Ok(None)
+        }
+    }
+}
+
+/// Keyring account holding the key for `manifest_path`. Keys are per manifest
+/// so signing one project's manifest does not demand signatures elsewhere.
+fn keyring_account(manifest_path: &Path) -> String {
+    let path = fs::canonicalize(manifest_path)
+        .or_else(|_| std::path::absolute(manifest_path))
+        .unwrap_or_else(|_| manifest_path.to_path_buf());
+    let digest = Sha256::digest(path.as_os_str().as_encoded_bytes());
+    format!("{KEYRING_USER}:{}", &HEXLOWER.encode(&digest)[..16])
+}
+
+fn keyring_entry(manifest_path: &Path) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, &keyring_account(manifest_path))
+        .context("Failed to open OS keyring entry")
+}
+
+/// The signing key for `manifest_path` stored in the OS keyring, if any.
+pub(crate) fn keyring_signing_key(manifest_path: &Path) -> Result<Option<SigningKey>> {
+    let secret = match keyring_entry(manifest_path)?.get_password() {
+        Ok(secret) => secret,
+        Err(keyring::Error::NoEntry) => return Ok(None),
+        Err(err) => return Err(err).context("Failed to read audit signing key from OS keyring"),
+    };
+    let bytes = HEXLOWER
+        .decode(secret.trim().as_bytes())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .context("Audit signing key in the OS keyring is malformed")?;
+    Ok(Some(SigningKey::from_bytes(&bytes)))
+}
+
+/// The signing key for `manifest_path`, generating and storing one on first use.
+pub(crate) fn keyring_signing_key_or_create(manifest_path: &Path) -> Result<SigningKey> {
+    if let Some(key) = keyring_signing_key(manifest_path)? {
+        return Ok(key);
+    }
+    let mut seed = [0_u8; 32];
+    getrandom::fill(&mut seed)
+        .map_err(|err| anyhow!("Failed to generate a new audit signing key: {err}"))?;
+    keyring_entry(manifest_path)?
+        .set_password(&HEXLOWER.encode(&seed))
+        .context("Failed to store audit signing key in OS keyring")?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &[u8] = b"[meta]\nversion = 1\n";
+
+    fn check(
+        bytes: Option<&[u8]>,
+        signature: Option<&ManifestSignature>,
+        key: Result<Option<SigningKey>>,
+    ) -> Result<()> {
+        check_loaded(Path::new("manifest.toml"), bytes, signature, None, key)
+    }
+
+    #[test]
+    fn manifests_without_a_key_load_unsigned() {
+        check(Some(MANIFEST), None, Ok(None)).expect("never signed");
+        check(None, None, Ok(None)).expect("never created");
+        check(Some(MANIFEST), None, Err(anyhow!("no secret service"))).expect("keyring down");
+    }
+
+    #[test]
+    fn manifests_with_a_key_must_carry_a_valid_signature() {
+        let key = SigningKey::from_bytes(&[7_u8; 32]);
+        let signature = sign(&key, MANIFEST);
+        check(Some(MANIFEST), Some(&signature), Ok(Some(key.clone()))).expect("valid");
+
+        let err = check(Some(MANIFEST), None, Ok(Some(key.clone()))).unwrap_err();
+        assert!(err.to_string().contains("signature is missing"), "{err}");
+        let err = check(None, None, Ok(Some(key.clone()))).unwrap_err();
+        assert!(err.to_string().contains("is missing"), "{err}");
+        let err = check(Some(b"[meta]\n"), Some(&signature), Ok(Some(key))).unwrap_err();
+        assert!(
+            err.to_string().contains("failed signature verification"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn signed_manifests_fail_without_their_key() {
+        let signature = sign(&SigningKey::from_bytes(&[7_u8; 32]), MANIFEST);
+        let err = check(Some(MANIFEST), Some(&signature), Ok(None)).unwrap_err();
+        assert!(err.to_string().contains("no signing key"), "{err}");
+        let err = check(Some(MANIFEST), Some(&signature), Err(anyhow!("locked"))).unwrap_err();
+        assert!(err.to_string().contains("cannot be read"), "{err}");
+    }
+
+    #[test]
+    fn manifests_naming_a_key_fail_without_a_signature_even_if_the_keyring_is_down() {
+        let declared = b"[meta]\nversion = 1\nsigning_key = \"ed25519:0123456789abcdef\"\n";
+        let fingerprint = declared_signing_key(declared).expect("declared key");
+        assert_eq!(fingerprint, "ed25519:0123456789abcdef");
+        assert_eq!(declared_signing_key(MANIFEST), None);
+
+        let load = |key| {
+            check_loaded(
+                Path::new("manifest.toml"),
+                Some(declared),
+                None,
+                Some(&fingerprint),
+                key,
+            )
+        };
+        let err = load(Err(anyhow!("no secret service"))).unwrap_err();
+        assert!(err.to_string().contains("cannot be read"), "{err}");
+        let err = load(Ok(None)).unwrap_err();
+        assert!(err.to_string().contains("no signing key"), "{err}");
+    }
+
+    #[test]
+    fn keyring_accounts_are_per_manifest() {
+        let a = keyring_account(Path::new("/repo-a/.csa/audit/manifest.toml"));
+        let b = keyring_account(Path::new("/repo-b/.csa/audit/manifest.toml"));
+        assert_ne!(a, b);
+        assert!(a.starts_with(KEYRING_USER), "{a}");
+    }
+}
@@ -0,0 +1,104 @@
+//! Skill coverage for `csa audit`: weave packages recorded from `weave.lock`,
+//! their global store checkouts, and the `.claude/skills`-style link trees.
+
+use anyhow::Result;
+use csa_core::audit::{AuditManifest, SkillEntry};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use weave::package::{SourceKind, load_project_lockfile};
+
+/// Git packages locked in the project's `weave.lock`.
+///
+/// Local-source packages have no commit to pin and are not recorded.
+pub(crate) fn locked_skills(root: &Path) -> BTreeMap<String, SkillEntry> {
+    load_project_lockfile(root)
+        .unwrap_or_default()
+        .package
+        .into_iter()
+        .filter(|pkg| pkg.source_kind == SourceKind::Git && !pkg.commit.is_empty())
+        .map(|pkg| {
+            (
+                pkg.name,
+                SkillEntry {
+                    repo: pkg.repo,
+                    commit: pkg.commit,
+                },
+            )
+        })
+        .collect()
+}
+
+/// One line of skill drift.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SkillDrift {
+    /// `lock`, `package` or `link`.
+    pub(crate) scope: &'static str,
+    /// Package name, or link path for `link`.
+    pub(crate) subject: String,
+    pub(crate) detail: String,
+}
+
+/// Differences between the commits recorded in the manifest and `weave.lock`.
+pub(crate) fn lock_drift(
+    recorded: &BTreeMap<String, SkillEntry>,
+    locked: &BTreeMap<String, SkillEntry>,
+) -> Vec<SkillDrift> {
+    let mut drift = Vec::new();
+    for (name, entry) in locked {
+        let detail = match recorded.get(name) {
+            None => format!(
+                "locked at {} but not recorded in the manifest",
+                entry.commit
+            ),
+            Some(previous) if previous.commit != entry.commit => format!(
+                "recorded at {}, weave.lock now pins {}",
+                previous.commit, entry.commit
+            ),
+            Some(previous) if previous.repo != entry.repo => format!(
+                "recorded from {}, weave.lock now points at {}",
+                previous.repo, entry.repo
+            ),
+            Some(_) => continue,
+        };
+        drift.push(SkillDrift {
+            scope: "lock",
+            subject: name.clone(),
+            detail,
+        });
+    }
+    for (name, entry) in recorded {
+        if !locked.contains_key(name) {
+            drift.push(SkillDrift {
+                scope: "lock",
+                subject: name.clone(),
+                detail: format!("recorded at {} but no longer locked", entry.commit),
+            });
+        }
+    }
+    drift
+}
+
+/// Lock, checkout and link drift for the project at `root`.
+pub(crate) fn skill_drift(root: &Path, manifest: &AuditManifest) -> Result<Vec<SkillDrift>> {
+    let store_root = weave::package::global_store_root()?;
+    let cache_root = weave::package::default_cache_root()?;
+
+    let mut drift = lock_drift(&manifest.skills, &locked_skills(root));
+    for package in weave::package::package_drift(root, &store_root, &cache_root)? {
+        drift.extend(package.issues.iter().map(|issue| SkillDrift {
+            scope: "package",
+            subject: package.name.clone(),
+            detail: issue.to_string(),
+        }));
+    }
+    for link in weave::package::link_drift(root, &store_root)? {
+        let subject = link.link.strip_prefix(root).unwrap_or(&link.link);
+        drift.push(SkillDrift {
+            scope: "link",
+            subject: subject.display().to_string(),
+            detail: link.issue.to_string(),
+        });
+    }
+    Ok(drift)
+}
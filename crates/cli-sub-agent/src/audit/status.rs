@@ -263,6 +263,7 @@ mod tests {
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
         manifest.files.insert(
@@ -274,6 +275,7 @@ mod tests {
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
         manifest.files.insert(
@@ -285,6 +287,7 @@ mod tests {
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
 
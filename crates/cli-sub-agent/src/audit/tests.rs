@@ -1,9 +1,16 @@
-use super::diff::diff_manifest;
+use super::diff::{DiffReport, ManifestDiff, diff_manifest};
 use super::hash::hash_file;
+use super::helpers::scan_tree;
 use super::io;
 use super::scan::scan_directory;
 use super::security::validate_path;
-use csa_core::audit::{AuditManifest, AuditStatus, FileEntry, ManifestMeta};
+use super::signing::{read_signature, sign, signature_path, verify, write_signature};
+use super::skills::{SkillDrift, lock_drift, locked_skills};
+use super::watch::{
+    IntegrityChange, IntegrityTracker, alert_hook_vars, build_alert, current_hash,
+    format_alert_text,
+};
+use csa_core::audit::{AuditManifest, AuditStatus, FileEntry, ManifestMeta, SkillEntry};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -101,6 +108,7 @@ fn test_diff_modified_file() {
             auditor: None,
             approved_by: None,
             approved_at: None,
+            stat: None,
         },
     );
     let mut current = BTreeMap::new();
@@ -125,6 +133,7 @@ fn test_diff_deleted_file() {
             auditor: None,
             approved_by: None,
             approved_at: None,
+            stat: None,
         },
     );
     let current = BTreeMap::new();
@@ -148,6 +157,7 @@ fn test_diff_unchanged_file() {
             auditor: Some("audit-bot".to_string()),
             approved_by: None,
             approved_at: None,
+            stat: None,
         },
     );
     let mut current = BTreeMap::new();
@@ -160,6 +170,47 @@ fn test_diff_unchanged_file() {
     assert!(diff.deleted.is_empty());
 }
 
+#[test]
+fn test_diff_report_json_lists_changes_and_counts_unchanged() {
+    let diff = ManifestDiff {
+        new: vec!["a.rs".to_string()],
+        modified: Vec::new(),
+        deleted: vec!["b.rs".to_string()],
+        unchanged: vec!["c.rs".to_string(), "d.rs".to_string()],
+    };
+    let value = serde_json::to_value(DiffReport::new(&diff, None)).expect("serialize");
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "new": ["a.rs"],
+            "modified": [],
+            "deleted": ["b.rs"],
+            "summary": {"new": 1, "modified": 0, "deleted": 1, "unchanged": 2},
+            "changed": true,
+        })
+    );
+}
+
+#[test]
+fn test_diff_report_changed_counts_skill_drift() {
+    let clean = ManifestDiff {
+        unchanged: vec!["c.rs".to_string()],
+        ..ManifestDiff::default()
+    };
+    assert!(!DiffReport::new(&clean, None).changed);
+    assert!(!DiffReport::new(&clean, Some(&[])).changed);
+
+    let drift = [SkillDrift {
+        scope: "lock",
+        subject: "pkg".to_string(),
+        detail: "recorded at a, weave.lock now pins b".to_string(),
+    }];
+    let report = DiffReport::new(&clean, Some(&drift));
+    assert!(report.changed);
+    let value = serde_json::to_value(&report).expect("serialize");
+    assert_eq!(value["skills"][0]["subject"], "pkg");
+}
+
 #[test]
 fn test_security_rejects_absolute() {
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -214,6 +265,7 @@ fn test_io_save_load_round_trip() {
             auditor: Some("audit-bot".to_string()),
             approved_by: None,
             approved_at: None,
+            stat: None,
         },
     );
 
@@ -225,8 +277,10 @@ fn test_io_save_load_round_trip() {
             updated_at: "2026-02-19T00:00:01Z".to_string(),
             last_scanned_at: Some("2026-02-19T00:00:02Z".to_string()),
             mirror_dir: None,
+            signing_key: None,
         },
         files,
+        skills: BTreeMap::new(),
     };
 
     io::save(&path, &manifest).expect("save should succeed");
@@ -240,3 +294,288 @@ fn test_io_save_load_round_trip() {
     // Ensure keys remain normalized for path lookups.
     assert!(loaded.files.contains_key(&to_key(Path::new("src/lib.rs"))));
 }
+
+fn tracked_manifest(root: &Path, key: &str) -> AuditManifest {
+    let mut manifest = AuditManifest::new(".");
+    manifest.files.insert(
+        key.to_string(),
+        FileEntry {
+            hash: hash_file(&root.join(key)).expect("hash tracked file"),
+            audit_status: AuditStatus::Approved,
+            blog_path: None,
+            auditor: None,
+            approved_by: None,
+            approved_at: None,
+            stat: None,
+        },
+    );
+    manifest
+}
+
+#[test]
+fn test_watch_tracker_reports_each_distinct_change_once() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path();
+    fs::write(root.join("lib.rs"), "fn audited() {}\n").expect("write tracked file");
+    let mut tracker = IntegrityTracker::new(&tracked_manifest(root, "lib.rs"), root);
+    assert_eq!(tracker.drifted(), 0);
+
+    fs::write(root.join("lib.rs"), "fn tampered() {}\n").expect("modify tracked file");
+    let (change, expected, actual) = tracker
+        .observe("lib.rs", current_hash(root, "lib.rs"))
+        .expect("modification is reported");
+    assert_eq!(change, IntegrityChange::Modified);
+    assert_ne!(Some(expected), actual);
+    assert_eq!(
+        tracker.observe("lib.rs", current_hash(root, "lib.rs")),
+        None
+    );
+    assert_eq!(tracker.drifted(), 1);
+
+    fs::write(root.join("lib.rs"), "fn audited() {}\n").expect("restore tracked file");
+    let (change, ..) = tracker
+        .observe("lib.rs", current_hash(root, "lib.rs"))
+        .expect("restore is reported");
+    assert_eq!(change, IntegrityChange::Restored);
+
+    fs::remove_file(root.join("lib.rs")).expect("delete tracked file");
+    let (change, _, actual) = tracker
+        .observe("lib.rs", current_hash(root, "lib.rs"))
+        .expect("deletion is reported");
+    assert_eq!((change, actual), (IntegrityChange::Deleted, None));
+    assert_eq!(tracker.observe("untracked.rs", None), None);
+}
+
+#[test]
+fn test_watch_alert_is_out_of_band_only_without_live_sessions() {
+    let alert = build_alert(
+        "src/lib.rs",
+        IntegrityChange::Modified,
+        "sha256:old".to_string(),
+        Some("sha256:new".to_string()),
+        Vec::new(),
+    );
+    assert!(alert.out_of_band);
+    assert!(format_alert_text(&alert).contains("ALERT out-of-band modified: src/lib.rs"));
+    let vars = alert_hook_vars(&alert, Path::new("/repo"));
+    assert_eq!(vars["actual_hash"], "sha256:new");
+    assert_eq!(vars["change"], "modified");
+
+    let attributed = build_alert(
+        "src/lib.rs",
+        IntegrityChange::Modified,
+        "sha256:old".to_string(),
+        Some("sha256:new".to_string()),
+        vec!["01JSESSION".to_string()],
+    );
+    assert!(!attributed.out_of_band);
+    assert!(format_alert_text(&attributed).ends_with("(during session 01JSESSION)"));
+    let restored = build_alert(
+        "src/lib.rs",
+        IntegrityChange::Restored,
+        "sha256:old".to_string(),
+        Some("sha256:old".to_string()),
+        Vec::new(),
+    );
+    assert!(!restored.out_of_band);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_inotify_dir_watcher_reports_rewritten_and_renamed_files() {
+    use super::inotify::DirWatcher;
+    use std::time::Duration;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path();
+    let mut watcher = DirWatcher::new().expect("inotify");
+    watcher.watch_dir(root).expect("watch root");
+
+    fs::write(root.join("a.txt"), "one").expect("write a");
+    fs::write(root.join("b.tmp"), "two").expect("write b");
+    fs::rename(root.join("b.tmp"), root.join("b.txt")).expect("rename b");
+
+    let changed = watcher.wait(Duration::from_secs(5)).expect("wait");
+    assert!(changed.contains(&root.join("a.txt")), "{changed:?}");
+    assert!(changed.contains(&root.join("b.txt")), "{changed:?}");
+    assert!(
+        watcher
+            .wait(Duration::from_millis(50))
+            .expect("wait")
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_manifest_signature_detects_tampering_and_foreign_keys() {
+    use ed25519_dalek::SigningKey;
+
+    let key = SigningKey::from_bytes(&[7_u8; 32]);
+    let manifest = b"[meta]\nversion = 1\n";
+    let signature = sign(&key, manifest);
+    assert_eq!(signature.algorithm, "ed25519");
+    verify(&signature, manifest, &key.verifying_key()).expect("own signature verifies");
+
+    let err = verify(&signature, b"[meta]\nversion = 2\n", &key.verifying_key()).unwrap_err();
+    assert!(err.to_string().contains("does not match"), "{err}");
+
+    let forger = SigningKey::from_bytes(&[9_u8; 32]);
+    let forged = sign(&forger, b"[meta]\nversion = 2\n");
+    let err = verify(&forged, b"[meta]\nversion = 2\n", &key.verifying_key()).unwrap_err();
+    assert!(err.to_string().contains("not by the keyring key"), "{err}");
+}
+
+#[test]
+fn test_manifest_signature_file_round_trip_and_missing_manifest_guard() {
+    use ed25519_dalek::SigningKey;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let manifest_path = tmp.path().join("manifest.toml");
+    let sig_path = signature_path(&manifest_path);
+    assert_eq!(sig_path, tmp.path().join("manifest.toml.sig"));
+    assert_eq!(read_signature(&sig_path).expect("read missing"), None);
+
+    let signature = sign(&SigningKey::from_bytes(&[1_u8; 32]), b"manifest");
+    write_signature(&sig_path, &signature).expect("write");
+    assert_eq!(
+        read_signature(&sig_path).expect("read"),
+        Some(signature.clone())
+    );
+
+    // Deleting a signed manifest must not silently yield an empty one.
+    let err = io::load(&manifest_path).unwrap_err();
+    assert!(err.to_string().contains("signature is present"), "{err}");
+}
+
+#[test]
+fn test_locked_skills_records_git_packages_from_weave_lock() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("weave.lock"),
+        r#"
+[[package]]
+name = "remote"
+repo = "https://github.com/user/remote.git"
+commit = "abcdef1234"
+
+[[package]]
+name = "mine"
+repo = ""
+commit = ""
+source_kind = "local"
+"#,
+    )
+    .expect("write lock");
+
+    let skills = locked_skills(tmp.path());
+    assert_eq!(
+        skills,
+        BTreeMap::from([(
+            "remote".to_string(),
+            SkillEntry {
+                repo: "https://github.com/user/remote.git".to_string(),
+                commit: "abcdef1234".to_string(),
+            },
+        )])
+    );
+    assert!(locked_skills(&tmp.path().join("missing")).is_empty());
+}
+
+#[test]
+fn test_lock_drift_reports_changed_added_and_removed_packages() {
+    let entry = |commit: &str| SkillEntry {
+        repo: "https://example.com/pkg.git".to_string(),
+        commit: commit.to_string(),
+    };
+    let recorded = BTreeMap::from([
+        ("same".to_string(), entry("aaaa")),
+        ("bumped".to_string(), entry("bbbb")),
+        ("dropped".to_string(), entry("cccc")),
+    ]);
+    let locked = BTreeMap::from([
+        ("same".to_string(), entry("aaaa")),
+        ("bumped".to_string(), entry("dddd")),
+        ("fresh".to_string(), entry("eeee")),
+    ]);
+
+    let drift: Vec<(String, String)> = lock_drift(&recorded, &locked)
+        .into_iter()
+        .map(|d| (d.subject, d.detail))
+        .collect();
+    assert_eq!(
+        drift,
+        vec![
+            (
+                "bumped".to_string(),
+                "recorded at bbbb, weave.lock now pins dddd".to_string()
+            ),
+            (
+                "fresh".to_string(),
+                "locked at eeee but not recorded in the manifest".to_string()
+            ),
+            (
+                "dropped".to_string(),
+                "recorded at cccc but no longer locked".to_string()
+            ),
+        ]
+    );
+    assert!(lock_drift(&locked, &locked).is_empty());
+}
+
+#[test]
+fn test_scan_tree_reuses_hash_only_while_stat_matches() {
+    use super::hash::RACY_WINDOW;
+    use std::time::Duration;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().canonicalize().expect("canonical root");
+    let settle = || std::thread::sleep(RACY_WINDOW + Duration::from_millis(100));
+    fs::write(root.join("old.rs"), "fn old() {}").expect("write old");
+    settle();
+    fs::write(root.join("fresh.rs"), "fn fresh() {}").expect("write fresh");
+
+    let first = scan_tree(&root, &[], None).expect("first scan");
+    assert_eq!(first.hashes.len(), 2);
+    assert_eq!(first.reused, 0);
+    // Files written within the racy window get no fingerprint.
+    assert!(first.stats.contains_key("old.rs"));
+    assert!(!first.stats.contains_key("fresh.rs"));
+
+    let mut manifest = AuditManifest::new(".");
+    for (key, hash) in &first.hashes {
+        manifest.files.insert(
+            key.clone(),
+            FileEntry {
+                // A sentinel hash proves the file was not re-read.
+                hash: format!("cached:{hash}"),
+                audit_status: AuditStatus::Pending,
+                blog_path: None,
+                auditor: None,
+                approved_by: None,
+                approved_at: None,
+                stat: first.stats.get(key).cloned(),
+            },
+        );
+    }
+
+    let second = scan_tree(&root, &[], Some(&manifest)).expect("second scan");
+    assert_eq!(second.reused, 1);
+    assert!(second.hashes["old.rs"].starts_with("cached:"));
+    assert_eq!(second.hashes["fresh.rs"], first.hashes["fresh.rs"]);
+
+    // Same size, mtime and inode: only the ctime reveals the rewrite.
+    let old = root.join("old.rs");
+    let mtime = fs::metadata(&old)
+        .and_then(|m| m.modified())
+        .expect("mtime");
+    fs::write(&old, "fn new() {}").expect("rewrite old");
+    fs::File::options()
+        .write(true)
+        .open(&old)
+        .and_then(|file| file.set_modified(mtime))
+        .expect("restore mtime");
+    settle();
+    let third = scan_tree(&root, &[], Some(&manifest)).expect("third scan");
+    assert_eq!(third.reused, 0);
+    assert_eq!(third.hashes["old.rs"], hash_file(&old).expect("hash"));
+}
@@ -0,0 +1,279 @@
+//! `csa audit watch`: re-hash manifest-tracked files as they change and alert
+//! when one is modified while no csa session is running in the project.
+
+use anyhow::Result;
+use chrono::Utc;
+use csa_core::audit::AuditManifest;
+use csa_core::types::OutputFormat;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::audit::hash;
+
+/// Upper bound on one blocking wait for filesystem events.
+#[cfg(target_os = "linux")]
+const WATCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// How a tracked file changed relative to the last hash seen for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum IntegrityChange {
+    Modified,
+    Deleted,
+    /// Content is back to the manifest hash.
+    Restored,
+}
+
+impl IntegrityChange {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Modified => "modified",
+            Self::Deleted => "deleted",
+            Self::Restored => "restored",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct IntegrityAlert {
+    pub(crate) timestamp: String,
+    pub(crate) path: String,
+    pub(crate) change: IntegrityChange,
+    pub(crate) expected_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) actual_hash: Option<String>,
+    /// Live csa sessions in the project when the change was seen.
+    pub(crate) active_sessions: Vec<String>,
+    /// Modified or deleted with no live csa session to attribute it to.
+    pub(crate) out_of_band: bool,
+}
+
+/// Manifest hashes plus the last hash seen per file, so each distinct change
+/// is reported once.
+pub(crate) struct IntegrityTracker {
+    expected: BTreeMap<String, String>,
+    last_seen: HashMap<String, Option<String>>,
+}
+
+impl IntegrityTracker {
+    /// Baseline from the files as they are now; drift that predates the
+    /// watch is counted by [`Self::drifted`] but not alerted.
+    pub(crate) fn new(manifest: &AuditManifest, root: &Path) -> Self {
+        let expected: BTreeMap<String, String> = manifest
+            .files
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.hash.clone()))
+            .collect();
+        let last_seen = expected
+            .keys()
+            .map(|key| (key.clone(), current_hash(root, key)))
+            .collect();
+        Self {
+            expected,
+            last_seen,
+        }
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &String> {
+        self.expected.keys()
+    }
+
+    pub(crate) fn tracks(&self, key: &str) -> bool {
+        self.expected.contains_key(key)
+    }
+
+    pub(crate) fn drifted(&self) -> usize {
+        self.expected
+            .iter()
+            .filter(|(key, hash)| self.last_seen.get(*key).and_then(Option::as_ref) != Some(*hash))
+            .count()
+    }
+
+    /// Record `actual` for `key`; returns the change when it differs from the
+    /// last hash seen.
+    pub(crate) fn observe(
+        &mut self,
+        key: &str,
+        actual: Option<String>,
+    ) -> Option<(IntegrityChange, String, Option<String>)> {
+        let expected = self.expected.get(key)?;
+        let previous = self.last_seen.insert(key.to_string(), actual.clone());
+        if previous.as_ref() == Some(&actual) {
+            return None;
+        }
+        let change = match actual.as_deref() {
+            None => IntegrityChange::Deleted,
+            Some(hash) if hash == expected => IntegrityChange::Restored,
+            Some(_) => IntegrityChange::Modified,
+        };
+        Some((change, expected.clone(), actual))
+    }
+}
+
+/// Hash of `root/key`, or `None` when it is missing or unreadable.
+pub(crate) fn current_hash(root: &Path, key: &str) -> Option<String> {
+    let path = root.join(key);
+    path.is_file()
+        .then(|| hash::hash_file(&path).ok())
+        .flatten()
+}
+
+pub(crate) fn build_alert(
+    path: &str,
+    change: IntegrityChange,
+    expected_hash: String,
+    actual_hash: Option<String>,
+    active_sessions: Vec<String>,
+) -> IntegrityAlert {
+    IntegrityAlert {
+        timestamp: Utc::now().to_rfc3339(),
+        path: path.to_string(),
+        out_of_band: active_sessions.is_empty() && change != IntegrityChange::Restored,
+        change,
+        expected_hash,
+        actual_hash,
+        active_sessions,
+    }
+}
+
+pub(crate) fn format_alert_text(alert: &IntegrityAlert) -> String {
+    let prefix = if alert.out_of_band {
+        "ALERT out-of-band "
+    } else {
+        ""
+    };
+    let mut line = format!(
+        "[{}] {prefix}{}: {}",
+        alert.timestamp,
+        alert.change.as_str(),
+        alert.path
+    );
+    if !alert.active_sessions.is_empty() {
+        line.push_str(&format!(
+            " (during session {})",
+            alert.active_sessions.join(", ")
+        ));
+    }
+    line
+}
+
+/// Template variables for the `audit_alert` hook.
+pub(crate) fn alert_hook_vars(alert: &IntegrityAlert, root: &Path) -> HashMap<String, String> {
+    HashMap::from([
+        ("path".to_string(), alert.path.clone()),
+        ("change".to_string(), alert.change.as_str().to_string()),
+        ("expected_hash".to_string(), alert.expected_hash.clone()),
+        (
+            "actual_hash".to_string(),
+            alert.actual_hash.clone().unwrap_or_default(),
+        ),
+        ("project_root".to_string(), root.display().to_string()),
+    ])
+}
+
+/// Active sessions of this project whose tool process is still alive.
+pub(crate) fn live_project_sessions(root: &Path) -> Vec<String> {
+    let Ok(sessions) = csa_session::list_sessions_readonly(root, None) else {
+        return Vec::new();
+    };
+    sessions
+        .into_iter()
+        .filter(|session| session.phase == csa_session::SessionPhase::Active)
+        .filter(|session| {
+            csa_session::get_session_dir(root, &session.meta_session_id)
+                .is_ok_and(|dir| csa_process::ToolLiveness::has_live_process(&dir))
+        })
+        .map(|session| session.meta_session_id)
+        .collect()
+}
+
+/// Watch until interrupted, printing one line (text) or JSON object per change.
+#[cfg(target_os = "linux")]
+pub(crate) fn run_watch(
+    root: &Path,
+    manifest: &AuditManifest,
+    format: OutputFormat,
+    fire_hooks: bool,
+) -> Result<()> {
+    use crate::audit::helpers::path_to_key;
+    use crate::audit::inotify::DirWatcher;
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    let mut tracker = IntegrityTracker::new(manifest, root);
+    let dirs: BTreeSet<PathBuf> = tracker
+        .keys()
+        .filter_map(|key| root.join(key).parent().map(Path::to_path_buf))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    let mut watcher = DirWatcher::new()?;
+    for dir in &dirs {
+        watcher.watch_dir(dir)?;
+    }
+    let hooks_config = fire_hooks.then(|| {
+        csa_hooks::load_hooks_config(
+            csa_session::get_session_root(root)
+                .ok()
+                .map(|session_root| session_root.join("hooks.toml"))
+                .as_deref(),
+            csa_hooks::global_hooks_path().as_deref(),
+            None,
+        )
+    });
+    eprintln!(
+        "Watching {} tracked files in {} directories ({} already differ from the manifest); \
+         press Ctrl-C to stop",
+        tracker.keys().count(),
+        watcher.watched_dirs(),
+        tracker.drifted()
+    );
+
+    loop {
+        let keys: Vec<String> = watcher
+            .wait(WATCH_POLL_INTERVAL)?
+            .iter()
+            .filter_map(|path| path.strip_prefix(root).ok())
+            .map(path_to_key)
+            .filter(|key| tracker.tracks(key))
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+        let sessions = live_project_sessions(root);
+        for key in keys {
+            let Some((change, expected, actual)) = tracker.observe(&key, current_hash(root, &key))
+            else {
+                continue;
+            };
+            let alert = build_alert(&key, change, expected, actual, sessions.clone());
+            match format {
+                OutputFormat::Text => println!("{}", format_alert_text(&alert)),
+                OutputFormat::Json => println!("{}", serde_json::to_string(&alert)?),
+            }
+            if !alert.out_of_band {
+                continue;
+            }
+            tracing::warn!(path = %alert.path, change = alert.change.as_str(), "Out-of-band edit to audited file");
+            if let Some(hooks_config) = &hooks_config
+                && let Err(err) = csa_hooks::run_hooks_for_event(
+                    csa_hooks::HookEvent::AuditAlert,
+                    hooks_config,
+                    &alert_hook_vars(&alert, root),
+                )
+            {
+                tracing::warn!("AuditAlert hook failed: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn run_watch(
+    _root: &Path,
+    _manifest: &AuditManifest,
+    _format: OutputFormat,
+    _fire_hooks: bool,
+) -> Result<()> {
+    anyhow::bail!("csa audit watch requires inotify and is only available on Linux")
+}
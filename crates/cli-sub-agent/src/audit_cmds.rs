@@ -7,15 +7,16 @@ use std::path::Path;
 
 use crate::audit::helpers::{
     canonical_root, compute_mirror_blog_path, current_root, expand_file_args, manifest_path,
-    parse_status, resolve_manifest_key, scan_and_hash, validate_mirror_dir,
+    parse_status, resolve_manifest_key, scan_tree, validate_mirror_dir,
 };
 use crate::audit::status::{
     build_status_rows, print_status_json, print_status_text, sort_rows, summarize_rows,
 };
-use crate::audit::{diff, io, security};
+use crate::audit::{diff, io, security, signing, skills, watch};
 use crate::cli::AuditCommands;
+use crate::startup_env::StartupSubtreeEnv;
 
-pub(crate) fn handle_audit(command: AuditCommands) -> Result<()> {
+pub(crate) fn handle_audit(command: AuditCommands, startup_env: &StartupSubtreeEnv) -> Result<()> {
     match command {
         AuditCommands::Init {
             root,
@@ -36,7 +37,22 @@ pub(crate) fn handle_audit(command: AuditCommands) -> Result<()> {
         } => handle_audit_update(files, status, auditor, blog_path, mirror_dir),
         AuditCommands::Approve { files, approved_by } => handle_audit_approve(files, approved_by),
         AuditCommands::Reset { files } => handle_audit_reset(files),
+        AuditCommands::Diff {
+            format,
+            skills,
+            changed_only,
+            fail_on_change,
+        } => {
+            let exit_code = handle_audit_diff(format, skills, changed_only, fail_on_change)?;
+            if exit_code != 0 {
+                crate::exit_current_process(exit_code);
+            }
+            Ok(())
+        }
         AuditCommands::Sync => handle_audit_sync(),
+        AuditCommands::Sign => handle_audit_sign(startup_env),
+        AuditCommands::Verify => handle_audit_verify(),
+        AuditCommands::Watch { format, no_hooks } => handle_audit_watch(format, no_hooks),
     }
 }
 
@@ -47,11 +63,12 @@ pub(crate) fn handle_audit_init(
 ) -> Result<()> {
     let scan_root = canonical_root(Path::new(&root))?;
     let mpath = manifest_path(&scan_root);
-    let file_hashes = scan_and_hash(&scan_root, &ignores)?;
+    let scan = scan_tree(&scan_root, &ignores, None)?;
 
     let mut manifest = AuditManifest::new(scan_root.display().to_string());
     manifest.meta.last_scanned_at = Some(Utc::now().to_rfc3339());
     manifest.meta.mirror_dir = mirror_dir.clone();
+    manifest.skills = skills::locked_skills(&scan_root);
 
     // Validate and create mirror directory if specified.
     if let Some(ref dir) = mirror_dir {
@@ -66,7 +83,8 @@ pub(crate) fn handle_audit_init(
         }
     }
 
-    for (path, hash_value) in file_hashes {
+    for (path, hash_value) in scan.hashes {
+        let stat = scan.stats.get(&path).cloned();
         manifest.files.insert(
             path,
             FileEntry {
@@ -76,6 +94,7 @@ pub(crate) fn handle_audit_init(
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat,
             },
         );
     }
@@ -97,7 +116,7 @@ pub(crate) fn handle_audit_status(
     let root = current_root()?;
     let mpath = manifest_path(&root);
     let manifest = io::load(&mpath)?;
-    let current_hashes = scan_and_hash(&root, &[])?;
+    let current_hashes = scan_tree(&root, &[], None)?.hashes;
     let manifest_diff = diff::diff_manifest(&manifest, &current_hashes);
 
     let modified_paths: BTreeSet<String> = manifest_diff.modified.into_iter().collect();
@@ -123,6 +142,41 @@ pub(crate) fn handle_audit_status(
     Ok(())
 }
 
+/// Returns the process exit code: 1 when `fail_on_change` is set and anything
+/// changed, otherwise 0.
+pub(crate) fn handle_audit_diff(
+    format: OutputFormat,
+    include_skills: bool,
+    changed_only: bool,
+    fail_on_change: bool,
+) -> Result<i32> {
+    let root = current_root()?;
+    let manifest = io::load(&manifest_path(&root))?;
+    // A full diff re-reads every file; `--changed-only` trusts unchanged stats.
+    let current_hashes = scan_tree(&root, &[], changed_only.then_some(&manifest))?.hashes;
+    let manifest_diff = diff::diff_manifest(&manifest, &current_hashes);
+    let skill_drift = include_skills
+        .then(|| skills::skill_drift(&root, &manifest))
+        .transpose()?;
+
+    let report = diff::DiffReport::new(&manifest_diff, skill_drift.as_deref());
+
+    match format {
+        OutputFormat::Text => diff::print_report_text(&report),
+        OutputFormat::Json => diff::print_report_json(&report)?,
+    }
+    Ok(i32::from(fail_on_change && report.changed))
+}
+
+pub(crate) fn handle_audit_watch(format: OutputFormat, no_hooks: bool) -> Result<()> {
+    let root = current_root()?;
+    let manifest = io::load(&manifest_path(&root))?;
+    if manifest.files.is_empty() {
+        anyhow::bail!("Audit manifest tracks no files; run `csa audit init` first");
+    }
+    watch::run_watch(&root, &manifest, format, !no_hooks)
+}
+
 pub(crate) fn handle_audit_approve(files: Vec<String>, approved_by: String) -> Result<()> {
     let root = current_root()?;
     let path = manifest_path(&root);
@@ -230,8 +284,10 @@ pub(crate) fn handle_audit_sync() -> Result<()> {
     let root = current_root()?;
     let path = manifest_path(&root);
     let mut manifest = io::load(&path)?;
-    let current_hashes = scan_and_hash(&root, &[])?;
-    let manifest_diff = diff::diff_manifest(&manifest, &current_hashes);
+    // The saved manifest is re-signed, so every hash is recomputed.
+    let scan = scan_tree(&root, &[], None)?;
+    let current_hashes = &scan.hashes;
+    let manifest_diff = diff::diff_manifest(&manifest, current_hashes);
     let summary = manifest_diff.summary();
 
     for new_path in &manifest_diff.new {
@@ -249,6 +305,7 @@ pub(crate) fn handle_audit_sync() -> Result<()> {
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
     }
@@ -272,7 +329,11 @@ pub(crate) fn handle_audit_sync() -> Result<()> {
     for deleted_path in &manifest_diff.deleted {
         manifest.files.remove(deleted_path);
     }
+    for (key, entry) in &mut manifest.files {
+        entry.stat = scan.stats.get(key).cloned();
+    }
 
+    manifest.skills = skills::locked_skills(&root);
     manifest.meta.last_scanned_at = Some(Utc::now().to_rfc3339());
     io::save(&path, &manifest)?;
 
@@ -283,6 +344,87 @@ pub(crate) fn handle_audit_sync() -> Result<()> {
     Ok(())
 }
 
+/// Sign the manifest as it is now. Only a human may vouch for it: inside a csa
+/// session an agent could otherwise re-sign its own edits.
+pub(crate) fn handle_audit_sign(startup_env: &StartupSubtreeEnv) -> Result<()> {
+    if let Some(session_id) = startup_env.session_id() {
+        anyhow::bail!(
+            "Refusing to sign the audit manifest from inside csa session {session_id}; \
+             run `csa audit sign` outside csa after reviewing the changes"
+        );
+    }
+    if startup_env.current_depth() > 0 {
+        anyhow::bail!(
+            "Refusing to sign the audit manifest from a csa sub-agent (depth {}); \
+             run `csa audit sign` outside csa after reviewing the changes",
+            startup_env.current_depth()
+        );
+    }
+    let root = current_root()?;
+    let path = manifest_path(&root);
+    let bytes = std::fs::read(&path).with_context(|| {
+        format!(
+            "Failed to read audit manifest {}; run `csa audit init` first",
+            path.display()
+        )
+    })?;
+    let mut manifest = ensure_manifest_matches_tree(&root, &path, &bytes)?;
+    let key = signing::keyring_signing_key_or_create(&path)?;
+    if let Some(previous) = signing::read_signature(&signing::signature_path(&path))?
+        && signing::verify(&previous, &bytes, &key.verifying_key()).is_err()
+    {
+        eprintln!("Replacing a signature that did not verify.");
+    }
+    let fingerprint = signing::fingerprint(&key.verifying_key());
+    manifest.meta.signing_key = Some(fingerprint.clone());
+    // `io::save` signs with the keyring key now that it exists.
+    io::save(&path, &manifest)?;
+    println!("Signed {} with {fingerprint}.", path.display());
+    Ok(())
+}
+
+/// Re-hash every recorded file so a signature never vouches for stale hashes.
+fn ensure_manifest_matches_tree(root: &Path, path: &Path, bytes: &[u8]) -> Result<AuditManifest> {
+    let manifest: AuditManifest = std::str::from_utf8(bytes)
+        .map_err(anyhow::Error::from)
+        .and_then(|raw| toml::from_str(raw).map_err(anyhow::Error::from))
+        .with_context(|| format!("Failed to parse audit manifest: {}", path.display()))?;
+    let current_hashes = scan_tree(root, &[], None)?.hashes;
+    let manifest_diff = diff::diff_manifest(&manifest, &current_hashes);
+    let stale: Vec<&str> = manifest_diff
+        .modified
+        .iter()
+        .chain(&manifest_diff.deleted)
+        .map(String::as_str)
+        .collect();
+    if !stale.is_empty() {
+        anyhow::bail!(
+            "Audit manifest is out of date for {}; run `csa audit sync` before signing",
+            stale.join(", ")
+        );
+    }
+    Ok(manifest)
+}
+
+pub(crate) fn handle_audit_verify() -> Result<()> {
+    let root = current_root()?;
+    let path = manifest_path(&root);
+    let signature = signing::read_signature(&signing::signature_path(&path))?
+        .ok_or_else(|| anyhow!("Audit manifest is not signed; run `csa audit sign`"))?;
+    let bytes = std::fs::read(&path)
+        .with_context(|| format!("Failed to read audit manifest: {}", path.display()))?;
+    let key = signing::keyring_signing_key(&path)?
+        .ok_or_else(|| anyhow!("No audit signing key in the OS keyring; cannot verify"))?;
+    signing::verify(&signature, &bytes, &key.verifying_key())
+        .with_context(|| format!("Audit manifest {} failed verification", path.display()))?;
+    println!(
+        "Audit manifest signature OK ({}, signed {}).",
+        signing::fingerprint(&key.verifying_key()),
+        signature.signed_at
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,7 +451,7 @@ mod tests {
         // Simulate handle_audit_init with mirror_dir.
         let scan_root = canonical_root(root).unwrap();
         let mpath = manifest_path(&scan_root);
-        let file_hashes = scan_and_hash(&scan_root, &[]).unwrap();
+        let file_hashes = scan_tree(&scan_root, &[], None).unwrap().hashes;
 
         let mut manifest = AuditManifest::new(scan_root.display().to_string());
         manifest.meta.last_scanned_at = Some(Utc::now().to_rfc3339());
@@ -325,6 +467,7 @@ mod tests {
                     auditor: None,
                     approved_by: None,
                     approved_at: None,
+                    stat: None,
                 },
             );
         }
@@ -9,16 +9,22 @@ use std::time::Instant;
 use tokio::task::JoinSet;
 use tracing::{error, info, warn};
 
-use crate::pipeline::{ConfigRefs, determine_project_root, execute_with_session};
+use crate::pipeline::{ConfigRefs, determine_project_root, execute_with_session_and_meta};
+use crate::run_helpers::parse_tool_name;
 use crate::run_resource_overrides::RunResourceOverrides;
 use crate::startup_env::StartupSubtreeEnv;
 use csa_config::ProjectConfig;
-use csa_core::types::ToolName;
+use csa_core::error::AppError;
+use csa_core::types::OutputFormat;
 use csa_resource::{ResourceGuard, ResourceLimits};
 
 #[path = "batch_catalog.rs"]
 mod batch_catalog;
-use batch_catalog::register_batch_model_specs;
+use batch_catalog::{register_batch_model_specs, resolve_batch_tiers};
+
+#[path = "batch_run.rs"]
+mod batch_run;
+pub(crate) use batch_run::handle_run_batch;
 
 include!("batch_types.rs");
 include!("batch_resource.rs");
@@ -31,8 +37,42 @@ pub(crate) async fn handle_batch(
     current_depth: u32,
     startup_env: &StartupSubtreeEnv,
 ) -> Result<()> {
+    let Some(batch) = load_batch(&file, cd.as_deref(), current_depth)? else {
+        return Ok(());
+    };
+
+    // If dry-run, print plan and exit
+    if dry_run {
+        print_execution_plan(&batch.plan, &batch.tasks);
+        return Ok(());
+    }
+
+    let results = execute_loaded_batch(batch, startup_env, None).await?;
+    print_summary(&results);
+
+    // Exit with non-zero if any task failed
+    let failed_count = results.iter().filter(|r| r.exit_code != 0).count();
+    if failed_count > 0 {
+        anyhow::bail!("{failed_count} tasks failed");
+    }
+
+    Ok(())
+}
+
+/// A parsed, validated batch file with its execution plan.
+struct LoadedBatch {
+    project_root: PathBuf,
+    config: Option<ProjectConfig>,
+    global_config: csa_config::GlobalConfig,
+    model_catalog: csa_config::EffectiveModelCatalog,
+    tasks: Vec<BatchTask>,
+    plan: ExecutionPlan,
+}
+
+/// Load, validate and plan a batch file. `None` when it has no tasks.
+fn load_batch(file: &str, cd: Option<&str>, current_depth: u32) -> Result<Option<LoadedBatch>> {
     // 1. Determine project root
-    let project_root = determine_project_root(cd.as_deref())?;
+    let project_root = determine_project_root(cd)?;
 
     // 2. Load one immutable model-sensitive snapshot for the whole command.
     let csa_config::EffectiveConfig {
@@ -57,7 +97,7 @@ pub(crate) async fn handle_batch(
     }
 
     // 4. Load and parse batch TOML file
-    let batch_path = PathBuf::from(&file);
+    let batch_path = PathBuf::from(file);
     if !batch_path.exists() {
         anyhow::bail!("Batch file not found: {file}");
     }
@@ -65,16 +105,17 @@ pub(crate) async fn handle_batch(
     let batch_content = std::fs::read_to_string(&batch_path)
         .with_context(|| format!("Failed to read batch file: {file}"))?;
 
-    let batch_config: BatchConfig = toml::from_str(&batch_content)
+    let mut batch_config: BatchConfig = toml::from_str(&batch_content)
         .with_context(|| format!("Failed to parse batch file: {file}"))?;
 
     if batch_config.tasks.is_empty() {
         warn!("No tasks found in batch file");
-        return Ok(());
+        return Ok(None);
     }
 
     // 5. Validate tasks
     validate_tasks(&batch_config.tasks)?;
+    resolve_batch_tiers(&mut batch_config.tasks, config.as_ref())?;
     register_batch_model_specs(
         &mut model_catalog,
         &batch_config.tasks,
@@ -85,43 +126,33 @@ pub(crate) async fn handle_batch(
     )?;
 
     // 6. Build execution plan
-    let execution_plan = build_execution_plan(&batch_config.tasks)?;
-
-    // 7. If dry-run, print plan and exit
-    if dry_run {
-        print_execution_plan(&execution_plan, &batch_config.tasks);
-        return Ok(());
-    }
-
-    // 8. Execute tasks
-    info!(
-        "Executing {} tasks from batch file",
-        batch_config.tasks.len()
-    );
-    let config = config.map(Arc::new);
-    let global_config = Arc::new(global_config);
-    let model_catalog = Arc::new(model_catalog);
-    let resource_overrides = RunResourceOverrides::inherited();
-    let batch_context = BatchExecutionContext {
-        project_root: &project_root,
+    let plan = build_execution_plan(&batch_config.tasks)?;
+    Ok(Some(LoadedBatch {
+        project_root,
         config,
         global_config,
         model_catalog,
-        resource_overrides,
+        tasks: batch_config.tasks,
+        plan,
+    }))
+}
+
+async fn execute_loaded_batch(
+    batch: LoadedBatch,
+    startup_env: &StartupSubtreeEnv,
+    run_mode: Option<BatchRunMode>,
+) -> Result<Vec<TaskResult>> {
+    info!("Executing {} tasks from batch file", batch.tasks.len());
+    let batch_context = BatchExecutionContext {
+        project_root: &batch.project_root,
+        config: batch.config.map(Arc::new),
+        global_config: Arc::new(batch.global_config),
+        model_catalog: Arc::new(batch.model_catalog),
+        resource_overrides: RunResourceOverrides::inherited(),
         startup_env,
+        run_mode,
     };
-    let results = execute_batch(&execution_plan, &batch_config.tasks, &batch_context).await?;
-
-    // 9. Print summary
-    print_summary(&results);
-
-    // 10. Exit with non-zero if any task failed
-    let failed_count = results.iter().filter(|r| r.exit_code != 0).count();
-    if failed_count > 0 {
-        anyhow::bail!("{failed_count} tasks failed");
-    }
-
-    Ok(())
+    execute_batch(&batch.plan, &batch.tasks, &batch_context).await
 }
 
 /// Validate batch tasks: check for duplicates, missing dependencies, cycles.
@@ -307,6 +338,16 @@ async fn execute_batch(
 
         for task_name in level {
             if let Some(task) = task_map.get(task_name.as_str()) {
+                if context.run_mode.is_some()
+                    && let Some(dependency) = failed_dependency(task, &results)
+                {
+                    warn!(
+                        "{} - Skipped: dependency '{}' failed",
+                        task.name, dependency
+                    );
+                    results.push(TaskResult::skipped(task, dependency));
+                    continue;
+                }
                 if task.mode == TaskMode::Parallel && level.len() > 1 {
                     parallel_tasks.push((*task).clone());
                 } else {
@@ -336,6 +377,7 @@ async fn execute_batch(
                     level: level_idx + 1,
                     seq: seq_idx + 1,
                     startup_env: context.startup_env,
+                    run_mode: context.run_mode.as_ref(),
                 },
             )
             .await;
@@ -346,6 +388,18 @@ async fn execute_batch(
     Ok(results)
 }
 
+/// First dependency of `task` that failed or was itself skipped.
+fn failed_dependency<'a>(task: &'a BatchTask, results: &[TaskResult]) -> Option<&'a str> {
+    task.depends_on
+        .iter()
+        .find(|dep| {
+            results
+                .iter()
+                .any(|result| result.name == **dep && result.exit_code != 0)
+        })
+        .map(String::as_str)
+}
+
 /// Execute parallel tasks concurrently using JoinSet.
 async fn execute_parallel_tasks(
     tasks: &[BatchTask],
@@ -364,6 +418,7 @@ async fn execute_parallel_tasks(
         let model_catalog = Arc::clone(&context.model_catalog);
         let resource_overrides = context.resource_overrides;
         let startup_env = startup_env.clone();
+        let run_mode = context.run_mode.clone();
 
         join_set.spawn(async move {
             execute_task(
@@ -378,6 +433,7 @@ async fn execute_parallel_tasks(
                     level,
                     seq: 0,
                     startup_env: &startup_env,
+                    run_mode: run_mode.as_ref(),
                 },
             )
             .await
@@ -410,6 +466,7 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
         level,
         seq,
         startup_env,
+        run_mode,
     } = context;
     let start = Instant::now();
     let task_label = if seq > 0 {
@@ -425,12 +482,7 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
         Ok(t) => t,
         Err(e) => {
             error!("{} - Failed to parse tool name: {}", task_label, e);
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!("Invalid tool name: {e}")),
-            };
+            return TaskResult::failed(task, start, format!("Invalid tool name: {e}"));
         }
     };
     let resolved_model = batch_catalog::resolve_batch_model(task, config);
@@ -439,35 +491,20 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
     if let Some(cfg) = config {
         if !cfg.is_tool_enabled(tool_name.as_str()) {
             error!("{} - Tool disabled in config", task_label);
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some("Tool disabled in config".to_string()),
-            };
+            return TaskResult::failed(task, start, "Tool disabled in config".to_string());
         }
 
         // Enforce tier whitelist: tool + model name
         if let Err(e) = cfg.enforce_tier_whitelist(tool_name.as_str(), None) {
             error!("{} - {}", task_label, e);
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!("{e}")),
-            };
+            return TaskResult::failed(task, start, format!("{e}"));
         }
         if let Err(e) = cfg.enforce_tier_model_name(
             tool_name.as_str(),
             crate::run_helpers::model_name_for_tier_validation(resolved_model.as_deref()),
         ) {
             error!("{} - {}", task_label, e);
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!("{e}")),
-            };
+            return TaskResult::failed(task, start, format!("{e}"));
         }
     }
 
@@ -491,12 +528,7 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
         Ok(executor) => executor,
         Err(error) => {
             error!("{} - Failed to build executor: {}", task_label, error);
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!("Failed to build executor: {error}")),
-            };
+            return TaskResult::failed(task, start, format!("Failed to build executor: {error}"));
         }
     };
 
@@ -505,12 +537,7 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
         && let Err(e) = guard.check_availability(executor.tool_name())
     {
         error!("{} - Resource check failed: {}", task_label, e);
-        return TaskResult {
-            name: task.name.clone(),
-            exit_code: 1,
-            duration_secs: start.elapsed().as_secs_f64(),
-            error: Some(format!("Resource check failed: {e}")),
-        };
+        return TaskResult::failed(task, start, format!("Resource check failed: {e}"));
     }
 
     let extra_env = global_config.build_execution_env(
@@ -537,69 +564,99 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
             executor.tool_name(),
         );
 
-    // Acquire global slot to enforce concurrency limit (fail-fast)
+    // Acquire global slot to enforce concurrency limit: fail fast for
+    // `csa batch`, wait for a free slot under `csa run --batch`.
     let max_concurrent = global_config.max_concurrent(executor.tool_name());
     let slots_dir = match csa_config::GlobalConfig::slots_dir() {
         Ok(d) => d,
         Err(e) => {
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!("Failed to resolve slots directory: {e}")),
-            };
+            return TaskResult::failed(
+                task,
+                start,
+                format!("Failed to resolve slots directory: {e}"),
+            );
         }
     };
-    let _slot_guard = match csa_lock::slot::try_acquire_slot(
-        &slots_dir,
-        executor.tool_name(),
-        max_concurrent,
-        None,
-    ) {
-        Ok(csa_lock::slot::SlotAcquireResult::Acquired(slot)) => slot,
-        Ok(csa_lock::slot::SlotAcquireResult::Exhausted(status)) => {
-            return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!(
+    let slot = match run_mode {
+        Some(mode) => {
+            let tool = executor.tool_name().to_string();
+            let timeout = mode.slot_wait_timeout;
+            match tokio::task::spawn_blocking(move || {
+                csa_lock::slot::acquire_slot_blocking(
+                    &slots_dir,
+                    &tool,
+                    max_concurrent,
+                    timeout,
+                    None,
+                )
+            })
+            .await
+            {
+                Ok(Ok(slot)) => Ok(slot),
+                Ok(Err(e)) => Err(e.context(format!(
+                    "Slot acquisition failed for '{}'",
+                    executor.tool_name()
+                ))),
+                Err(e) => Err(anyhow::anyhow!("Slot wait task failed: {e}")),
+            }
+        }
+        None => match csa_lock::slot::try_acquire_slot(
+            &slots_dir,
+            executor.tool_name(),
+            max_concurrent,
+            None,
+        ) {
+            Ok(csa_lock::slot::SlotAcquireResult::Acquired(slot)) => Ok(slot),
+            Ok(csa_lock::slot::SlotAcquireResult::Exhausted(status)) => {
+                Err(anyhow::Error::new(AppError::SlotExhausted {
+                    tool: executor.tool_name().to_string(),
+                    max: status.max_slots,
+                    alternatives: Vec::new(),
+                })
+                .context(format!(
                     "All {} slots for '{}' occupied ({}/{})",
                     max_concurrent,
                     executor.tool_name(),
                     status.occupied,
                     status.max_slots,
-                )),
-            };
-        }
-        Err(e) => {
+                )))
+            }
+            Err(e) => Err(e.context(format!(
+                "Slot acquisition failed for '{}'",
+                executor.tool_name()
+            ))),
+        },
+    };
+    let _slot_guard = match slot {
+        Ok(slot) => slot,
+        Err(error) => {
             return TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: start.elapsed().as_secs_f64(),
-                error: Some(format!(
-                    "Slot acquisition failed for '{}': {}",
-                    executor.tool_name(),
-                    e
-                )),
+                exit_code: crate::process_exit::exit_code_for_error(&error).code(),
+                ..TaskResult::failed(task, start, format!("{error:#}"))
             };
         }
     };
+    let parent = match run_mode {
+        Some(mode) => Some(mode.parent_session_id.clone()),
+        None => startup_env.session_id().map(ToOwned::to_owned),
+    };
 
     // Execute with ephemeral session (no persistent state)
-    let result = execute_with_session(
+    let result = execute_with_session_and_meta(
         &executor,
         &tool_name,
         &task.prompt,
-        None,                                            // session_arg: None (ephemeral)
-        false,                                           // fresh_spawn_preflight_override
-        Some(format!("batch: {}", task.name)),           // description
-        startup_env.session_id().map(ToOwned::to_owned), // parent
+        OutputFormat::Json,
+        None,                                  // session_arg: None (ephemeral)
+        false,                                 // fresh_spawn_preflight_override
+        Some(format!("batch: {}", task.name)), // description
+        parent,
         project_root,
         config,
         extra_env_ref,
         subtree_pin.as_ref(),
         Some("batch"),
-        None, // batch does not use tier-based selection
+        task.tier.as_deref(),
         None, // batch does not override context loading options
         csa_process::StreamMode::BufferOnly,
         idle_timeout_seconds,
@@ -622,7 +679,8 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
     let duration = start.elapsed().as_secs_f64();
 
     match result {
-        Ok(exec_result) => {
+        Ok(execution) => {
+            let exec_result = execution.execution;
             if exec_result.exit_code == 0 {
                 info!(
                     "{} - Completed successfully in {:.2}s",
@@ -637,34 +695,18 @@ async fn execute_task(task: &BatchTask, context: BatchTaskExecutionContext<'_>)
 
             TaskResult {
                 name: task.name.clone(),
+                tool: task.tool.clone(),
+                session_id: Some(execution.meta_session_id),
                 exit_code: exec_result.exit_code,
                 duration_secs: duration,
+                skipped: false,
                 error: None,
             }
         }
         Err(e) => {
             error!("{} - Execution error: {}", task_label, e);
-            TaskResult {
-                name: task.name.clone(),
-                exit_code: 1,
-                duration_secs: duration,
-                error: Some(e.to_string()),
-            }
-        }
-    }
-}
-
-/// Parse tool name string to ToolName enum.
-fn parse_tool_name(tool: &str) -> Result<ToolName> {
-    match tool {
-        "gemini-cli" | "gemini" => {
-            anyhow::bail!("{}", csa_core::types::removed_tool_error("gemini-cli"))
+            TaskResult::failed(task, start, e.to_string())
         }
-        "opencode" => Ok(ToolName::Opencode),
-        "codex" => Ok(ToolName::Codex),
-        "claude-code" => Ok(ToolName::ClaudeCode),
-        "antigravity-cli" => Ok(ToolName::AntigravityCli),
-        _ => anyhow::bail!("Unknown tool: {tool}"),
     }
 }
 
@@ -680,10 +722,14 @@ fn print_summary(results: &[TaskResult]) {
 
     let mut success_count = 0;
     let mut failed_count = 0;
+    let mut skipped_count = 0;
     let total_duration: f64 = results.iter().map(|r| r.duration_secs).sum();
 
     for result in results {
-        let status = if result.exit_code == 0 {
+        let status = if result.skipped {
+            skipped_count += 1;
+            "- SKIP"
+        } else if result.exit_code == 0 {
             success_count += 1;
             "✓ PASS"
         } else {
@@ -708,5 +754,8 @@ fn print_summary(results: &[TaskResult]) {
     println!("Total: {} tasks", results.len());
     println!("Success: {success_count}");
     println!("Failed: {failed_count}");
+    if skipped_count > 0 {
+        println!("Skipped: {skipped_count}");
+    }
     println!("Total duration: {total_duration:.2}s");
 }
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use csa_config::ProjectConfig;
 
 use super::BatchTask;
@@ -16,6 +16,60 @@ pub(super) fn resolve_batch_model(
     })
 }
 
+/// Fill in `tool` (and `model`, unless set) for tasks that select a tier,
+/// and canonicalize the tier name. A task without `tier` must name a tool.
+pub(super) fn resolve_batch_tiers(
+    tasks: &mut [BatchTask],
+    project_config: Option<&ProjectConfig>,
+) -> Result<()> {
+    for task in tasks {
+        let Some(selector) = task.tier.as_deref() else {
+            if task.tool.is_empty() {
+                anyhow::bail!("batch task '{}' must set `tool` or `tier`", task.name);
+            }
+            continue;
+        };
+        let config = project_config.with_context(|| {
+            format!(
+                "batch task '{}' selects tier '{selector}' but the project has no config",
+                task.name
+            )
+        })?;
+        let tier_name = config.resolve_tier_selector(selector).with_context(|| {
+            format!(
+                "batch task '{}' selects unknown tier '{selector}'",
+                task.name
+            )
+        })?;
+        let spec = config.tiers[&tier_name]
+            .models
+            .iter()
+            .find(|spec| {
+                let tool = spec.split('/').next().unwrap_or_default();
+                config.is_tool_enabled(tool) && (task.tool.is_empty() || task.tool == tool)
+            })
+            .with_context(|| {
+                format!(
+                    "batch task '{}': tier '{tier_name}' has no enabled model{}",
+                    task.name,
+                    if task.tool.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" for tool '{}'", task.tool)
+                    }
+                )
+            })?;
+        if task.tool.is_empty() {
+            task.tool = spec.split('/').next().unwrap_or_default().to_string();
+        }
+        if task.model.is_none() {
+            task.model = Some(spec.clone());
+        }
+        task.tier = Some(tier_name);
+    }
+    Ok(())
+}
+
 pub(super) fn register_batch_model_specs(
     catalog: &mut csa_config::EffectiveModelCatalog,
     tasks: &[BatchTask],
@@ -0,0 +1,142 @@
+//! `csa run --batch`: run a batch file as one session tree and write an
+//! aggregate JSON report into the shared parent session.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use csa_core::types::OutputFormat;
+use serde::Serialize;
+
+use super::{BatchRunMode, TaskResult, execute_loaded_batch, load_batch, print_summary};
+use crate::run_cmd_tool_selection::resolve_slot_wait_timeout_seconds;
+use crate::startup_env::StartupSubtreeEnv;
+
+/// Report file name inside the batch parent session directory.
+const BATCH_REPORT_FILE: &str = "batch-report.json";
+
+#[derive(Debug, Serialize)]
+struct BatchReport {
+    batch_file: String,
+    parent_session_id: String,
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+    succeeded: usize,
+    failed: usize,
+    skipped: usize,
+    tasks: Vec<BatchTaskReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchTaskReport {
+    name: String,
+    tool: String,
+    /// `succeeded`, `failed` or `skipped`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    exit_code: i32,
+    duration_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Handle `csa run --batch <FILE>`.
+pub(crate) async fn handle_run_batch(
+    file: String,
+    cd: Option<String>,
+    current_depth: u32,
+    startup_env: &StartupSubtreeEnv,
+    format: OutputFormat,
+) -> Result<()> {
+    let Some(batch) = load_batch(&file, cd.as_deref(), current_depth)? else {
+        return Ok(());
+    };
+    let project_root = batch.project_root.clone();
+    let slot_wait_timeout =
+        Duration::from_secs(resolve_slot_wait_timeout_seconds(batch.config.as_ref()));
+    let parent = csa_session::create_session(
+        &project_root,
+        Some(&format!("batch: {file}")),
+        startup_env.session_id(),
+        None,
+    )
+    .context("Failed to create batch parent session")?;
+    eprintln!("Batch parent session: {}", parent.meta_session_id);
+
+    let started_at = Utc::now();
+    let run_mode = BatchRunMode {
+        parent_session_id: parent.meta_session_id.clone(),
+        slot_wait_timeout,
+    };
+    let results = execute_loaded_batch(batch, startup_env, Some(run_mode)).await?;
+    let report = build_report(
+        &file,
+        &parent.meta_session_id,
+        started_at,
+        Utc::now(),
+        &results,
+    );
+
+    let report_path = csa_session::get_session_dir(&project_root, &parent.meta_session_id)?
+        .join(BATCH_REPORT_FILE);
+    let json = serde_json::to_string_pretty(&report)?;
+    std::fs::write(&report_path, &json)
+        .with_context(|| format!("Failed to write batch report: {}", report_path.display()))?;
+
+    match format {
+        OutputFormat::Json => println!("{json}"),
+        OutputFormat::Text => {
+            print_summary(&results);
+            println!("Report: {}", report_path.display());
+        }
+    }
+
+    let unsuccessful = report.failed + report.skipped;
+    if unsuccessful > 0 {
+        anyhow::bail!("{unsuccessful} tasks did not succeed");
+    }
+    Ok(())
+}
+
+fn build_report(
+    batch_file: &str,
+    parent_session_id: &str,
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+    results: &[TaskResult],
+) -> BatchReport {
+    let tasks: Vec<BatchTaskReport> = results
+        .iter()
+        .map(|result| BatchTaskReport {
+            name: result.name.clone(),
+            tool: result.tool.clone(),
+            status: if result.skipped {
+                "skipped"
+            } else if result.exit_code == 0 {
+                "succeeded"
+            } else {
+                "failed"
+            },
+            session_id: result.session_id.clone(),
+            exit_code: result.exit_code,
+            duration_secs: result.duration_secs,
+            error: result.error.clone(),
+        })
+        .collect();
+    let count = |status: &str| tasks.iter().filter(|task| task.status == status).count();
+    BatchReport {
+        batch_file: batch_file.to_string(),
+        parent_session_id: parent_session_id.to_string(),
+        started_at,
+        finished_at,
+        succeeded: count("succeeded"),
+        failed: count("failed"),
+        skipped: count("skipped"),
+        tasks,
+    }
+}
+
+#[cfg(test)]
+#[path = "batch_run_tests.rs"]
+mod tests;
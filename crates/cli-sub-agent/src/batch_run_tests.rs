@@ -0,0 +1,35 @@
+use super::*;
+
+fn result(name: &str, exit_code: i32, session_id: Option<&str>, skipped: bool) -> TaskResult {
+    TaskResult {
+        name: name.to_string(),
+        tool: "codex".to_string(),
+        session_id: session_id.map(str::to_string),
+        exit_code,
+        duration_secs: 1.5,
+        skipped,
+        error: (exit_code != 0).then(|| format!("{name} failed")),
+    }
+}
+
+#[test]
+fn build_report_counts_statuses_and_links_child_sessions() {
+    let started_at = Utc::now();
+    let results = vec![
+        result("build", 0, Some("01JBUILD"), false),
+        result("test", 1, Some("01JTEST"), false),
+        result("deploy", 1, None, true),
+    ];
+
+    let report = build_report("tasks.toml", "01JPARENT", started_at, started_at, &results);
+    assert_eq!((report.succeeded, report.failed, report.skipped), (1, 1, 1));
+
+    let json = serde_json::to_value(&report).expect("report serializes");
+    assert_eq!(json["parent_session_id"], "01JPARENT");
+    assert_eq!(json["tasks"][0]["status"], "succeeded");
+    assert_eq!(json["tasks"][0]["session_id"], "01JBUILD");
+    assert!(json["tasks"][0].get("error").is_none());
+    assert_eq!(json["tasks"][1]["status"], "failed");
+    assert_eq!(json["tasks"][2]["status"], "skipped");
+    assert!(json["tasks"][2].get("session_id").is_none());
+}
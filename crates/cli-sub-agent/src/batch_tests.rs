@@ -1,4 +1,5 @@
 use super::*;
+use csa_core::types::ToolName;
 
 #[path = "batch_catalog_tests.rs"]
 mod catalog_tests;
@@ -68,6 +69,7 @@ fn make_task(name: &str, tool: &str, depends_on: Vec<&str>) -> BatchTask {
     BatchTask {
         name: name.to_string(),
         tool: tool.to_string(),
+        tier: None,
         prompt: format!("do {name}"),
         mode: TaskMode::default(),
         depends_on: depends_on.into_iter().map(String::from).collect(),
@@ -271,6 +273,99 @@ fn detect_cycle_direct_cycle_detected() {
     assert!(result.is_err());
 }
 
+#[test]
+fn failed_dependency_finds_failed_or_skipped_dependency() {
+    let task = make_task("deploy", "codex", vec!["build", "test"]);
+    let start = Instant::now();
+    let passed = TaskResult {
+        exit_code: 0,
+        error: None,
+        ..TaskResult::failed(&make_task("build", "codex", vec![]), start, String::new())
+    };
+    assert_eq!(
+        failed_dependency(&task, std::slice::from_ref(&passed)),
+        None
+    );
+
+    let skipped = TaskResult::skipped(&make_task("test", "codex", vec!["lint"]), "lint");
+    assert!(skipped.skipped);
+    assert_eq!(skipped.exit_code, 1);
+    assert_eq!(failed_dependency(&task, &[passed, skipped]), Some("test"));
+}
+
+// --- tier resolution tests ---
+
+fn tier_config() -> ProjectConfig {
+    toml::from_str(
+        r#"
+[tools.opencode]
+enabled = false
+
+[tiers.tier-2-standard]
+description = "standard"
+models = ["opencode/google/gemini-2.5-pro/high", "codex/openai/gpt-5/medium", "claude-code/anthropic/sonnet/default"]
+"#,
+    )
+    .expect("tier config")
+}
+
+#[test]
+fn resolve_batch_tiers_picks_first_enabled_model_and_canonical_tier() {
+    let config = tier_config();
+    let mut tasks = vec![
+        BatchTask {
+            tier: Some("tier2".to_string()),
+            ..make_task("any", "", vec![])
+        },
+        BatchTask {
+            tier: Some("tier-2-standard".to_string()),
+            ..make_task("claude", "claude-code", vec![])
+        },
+        make_task("plain", "codex", vec![]),
+    ];
+
+    resolve_batch_tiers(&mut tasks, Some(&config)).expect("tiers resolve");
+    assert_eq!(tasks[0].tool, "codex");
+    assert_eq!(tasks[0].model.as_deref(), Some("codex/openai/gpt-5/medium"));
+    assert_eq!(tasks[0].tier.as_deref(), Some("tier-2-standard"));
+    assert_eq!(tasks[1].tool, "claude-code");
+    assert_eq!(
+        tasks[1].model.as_deref(),
+        Some("claude-code/anthropic/sonnet/default")
+    );
+    assert_eq!(tasks[2].model, None);
+}
+
+#[test]
+fn resolve_batch_tiers_rejects_missing_tool_and_unknown_tier() {
+    let config = tier_config();
+    let err = resolve_batch_tiers(&mut [make_task("bare", "", vec![])], Some(&config))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("must set `tool` or `tier`"), "{err}");
+
+    let mut tasks = [BatchTask {
+        tier: Some("tier9".to_string()),
+        ..make_task("unknown", "", vec![])
+    }];
+    let err = resolve_batch_tiers(&mut tasks, Some(&config))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unknown tier 'tier9'"), "{err}");
+
+    let mut tasks = [BatchTask {
+        tier: Some("tier2".to_string()),
+        ..make_task("disabled", "opencode", vec![])
+    }];
+    let err = resolve_batch_tiers(&mut tasks, Some(&config))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("no enabled model for tool 'opencode'"),
+        "{err}"
+    );
+}
+
 // --- BatchTask deserialization tests ---
 
 #[test]
@@ -312,6 +407,19 @@ model = "gpt-5"
     assert_eq!(config.tasks[1].model.as_deref(), Some("gpt-5"));
 }
 
+#[test]
+fn batch_config_deserialize_tier_without_tool() {
+    let toml_str = r#"
+[[tasks]]
+name = "review"
+tier = "tier-2-standard"
+prompt = "review the diff"
+"#;
+    let config: BatchConfig = toml::from_str(toml_str).unwrap();
+    assert!(config.tasks[0].tool.is_empty());
+    assert_eq!(config.tasks[0].tier.as_deref(), Some("tier-2-standard"));
+}
+
 #[test]
 fn batch_config_deserialize_invalid_toml_errors() {
     let toml_str = "this is not valid toml [[[";
@@ -10,9 +10,16 @@ struct BatchTask {
     /// Task name (unique identifier)
     name: String,
 
-    /// Tool to use (opencode, codex, claude-code)
+    /// Tool to use (opencode, codex, claude-code). May be omitted when `tier`
+    /// is set; filled in from the tier before execution.
+    #[serde(default)]
     tool: String,
 
+    /// Tier whose first enabled model runs this task (or the first model for
+    /// `tool` when both are set)
+    #[serde(default)]
+    tier: Option<String>,
+
     /// Task prompt
     prompt: String,
 
@@ -42,11 +49,42 @@ enum TaskMode {
 #[derive(Debug)]
 struct TaskResult {
     name: String,
+    tool: String,
+    /// Child session the task ran in; `None` when it failed before dispatch.
+    session_id: Option<String>,
     exit_code: i32,
     duration_secs: f64,
+    /// Not started because a dependency failed (`csa run --batch` only).
+    skipped: bool,
     error: Option<String>,
 }
 
+impl TaskResult {
+    fn failed(task: &BatchTask, start: Instant, error: String) -> Self {
+        Self {
+            name: task.name.clone(),
+            tool: task.tool.clone(),
+            session_id: None,
+            exit_code: 1,
+            duration_secs: start.elapsed().as_secs_f64(),
+            skipped: false,
+            error: Some(error),
+        }
+    }
+
+    fn skipped(task: &BatchTask, dependency: &str) -> Self {
+        Self {
+            skipped: true,
+            duration_secs: 0.0,
+            ..Self::failed(
+                task,
+                Instant::now(),
+                format!("dependency '{dependency}' did not succeed"),
+            )
+        }
+    }
+}
+
 struct BatchTaskExecutionContext<'a> {
     project_root: &'a Path,
     config: Option<&'a ProjectConfig>,
@@ -57,6 +95,7 @@ struct BatchTaskExecutionContext<'a> {
     level: usize,
     seq: usize,
     startup_env: &'a StartupSubtreeEnv,
+    run_mode: Option<&'a BatchRunMode>,
 }
 
 struct BatchExecutionContext<'a> {
@@ -66,4 +105,14 @@ struct BatchExecutionContext<'a> {
     model_catalog: Arc<csa_config::EffectiveModelCatalog>,
     resource_overrides: crate::run_resource_overrides::RunResourceOverrides,
     startup_env: &'a StartupSubtreeEnv,
+    run_mode: Option<BatchRunMode>,
+}
+
+/// Extra behaviour of `csa run --batch` over `csa batch`: tasks become
+/// children of one shared parent session, wait for a free tool slot instead
+/// of failing, and are skipped when a dependency did not succeed.
+#[derive(Debug, Clone)]
+struct BatchRunMode {
+    parent_session_id: String,
+    slot_wait_timeout: std::time::Duration,
 }
@@ -0,0 +1,445 @@
+//! `csa bench`: benchmark tools and tiers on a task suite.
+//!
+//! Every task of the suite runs `--repeat` times per `--tool`/`--tier`
+//! candidate. Each attempt gets a fresh session in a throwaway git worktree
+//! of HEAD, so attempts start from the same tree and never touch the live
+//! one. A run passes when the tool exits 0 and the task's `validate` command
+//! (`sh -c`, in the worktree) exits 0; a task's `setup` command runs in the
+//! worktree first. Each result is appended to the project's bench history
+//! (`csa_scheduler::bench_history`) as soon as its attempt finishes, and all
+//! of them are printed as a comparison table. `--history` prints the same
+//! table for the recorded history instead.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use csa_config::{EffectiveModelCatalog, GlobalConfig, ProjectConfig};
+use csa_core::types::{OutputFormat, ToolName};
+use csa_scheduler::{BenchHistory, BenchRecord, BenchSummary};
+use serde::Deserialize;
+
+use crate::cli::BenchArgs;
+use crate::pipeline::{ConfigRefs, determine_project_root};
+use crate::run_resource_overrides::RunResourceOverrides;
+use crate::run_worktree::IsolatedWorktree;
+use crate::startup_env::StartupSubtreeEnv;
+
+/// Task suite loaded from the suite TOML.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BenchSuite {
+    /// Name recorded in the history; defaults to the file stem.
+    #[serde(default)]
+    name: Option<String>,
+    tasks: Vec<BenchTask>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BenchTask {
+    name: String,
+    prompt: String,
+    /// Shell command run before every attempt, e.g. to reset fixtures.
+    #[serde(default)]
+    setup: Option<String>,
+    /// Shell command that must exit 0 for the attempt to pass.
+    #[serde(default)]
+    validate: Option<String>,
+    /// Wall-clock limit of the tool run.
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BenchCandidate {
+    Tool(ToolName),
+    Tier(String),
+}
+
+impl BenchCandidate {
+    fn label(&self) -> String {
+        match self {
+            Self::Tool(tool) => format!("tool:{}", tool.as_str()),
+            Self::Tier(tier) => format!("tier:{tier}"),
+        }
+    }
+}
+
+struct BenchContext<'a> {
+    project_root: &'a Path,
+    suite: &'a str,
+    config: Option<&'a ProjectConfig>,
+    global_config: &'a GlobalConfig,
+    model_catalog: &'a EffectiveModelCatalog,
+    startup_env: &'a StartupSubtreeEnv,
+}
+
+/// Handle `csa bench`.
+pub(crate) async fn handle_bench(
+    args: BenchArgs,
+    output_format: OutputFormat,
+    startup_env: &StartupSubtreeEnv,
+) -> Result<()> {
+    let project_root = determine_project_root(args.cd.as_deref())?;
+    if args.history {
+        let summaries = history_summaries(&project_root, args.suite.as_deref())?;
+        return print_summaries(&summaries, output_format);
+    }
+    let Some(suite_path) = args.suite.as_deref() else {
+        bail!("csa bench needs a task suite unless --history is given");
+    };
+    let suite = load_suite(suite_path)?;
+    let suite_name = suite_name(suite_path, &suite);
+    let candidates = bench_candidates(&args)?;
+    let effective_config = csa_config::EffectiveConfig::load(&project_root)?;
+    let context = BenchContext {
+        project_root: &project_root,
+        suite: &suite_name,
+        config: effective_config.project.as_ref(),
+        global_config: &effective_config.global,
+        model_catalog: &effective_config.model_catalog,
+        startup_env,
+    };
+
+    let mut records = Vec::new();
+    for candidate in &candidates {
+        for task in &suite.tasks {
+            for attempt in 1..=args.repeat {
+                eprintln!(
+                    "[csa-bench] {} {} ({attempt}/{})",
+                    candidate.label(),
+                    task.name,
+                    args.repeat
+                );
+                let record = run_bench_task(&context, candidate, task).await;
+                // Record each attempt right away; an interrupted bench keeps
+                // the runs it finished.
+                csa_scheduler::record_bench_results(&project_root, std::slice::from_ref(&record))
+                    .context("Failed to record bench result")?;
+                eprintln!(
+                    "[csa-bench] {} in {:.1}s{}",
+                    if record.passed { "PASS" } else { "FAIL" },
+                    record.latency_secs,
+                    record
+                        .error
+                        .as_deref()
+                        .map(|error| format!(": {error}"))
+                        .unwrap_or_default()
+                );
+                records.push(record);
+            }
+        }
+    }
+
+    print_summaries(&BenchHistory { records }.summaries(None), output_format)
+}
+
+/// Summaries of the project's bench history, limited to the suite at
+/// `suite_path` if given.
+fn history_summaries(project_root: &Path, suite_path: Option<&Path>) -> Result<Vec<BenchSummary>> {
+    let suite = match suite_path {
+        Some(path) => Some(suite_name(path, &load_suite(path)?)),
+        None => None,
+    };
+    Ok(csa_scheduler::load_bench_history(project_root)?.summaries(suite.as_deref()))
+}
+
+fn print_summaries(summaries: &[BenchSummary], output_format: OutputFormat) -> Result<()> {
+    match output_format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(summaries)?),
+        OutputFormat::Text => print!("{}", render_comparison(summaries)),
+    }
+    Ok(())
+}
+
+fn load_suite(path: &Path) -> Result<BenchSuite> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read bench suite: {}", path.display()))?;
+    let suite: BenchSuite = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse bench suite: {}", path.display()))?;
+    if suite.tasks.is_empty() {
+        bail!("Bench suite {} has no tasks", path.display());
+    }
+    let mut names = HashSet::new();
+    for task in &suite.tasks {
+        if !names.insert(task.name.as_str()) {
+            bail!("Duplicate bench task name: {}", task.name);
+        }
+    }
+    Ok(suite)
+}
+
+/// Suite name of the history records: the suite's `name`, else the file stem.
+fn suite_name(path: &Path, suite: &BenchSuite) -> String {
+    suite.name.clone().unwrap_or_else(|| {
+        path.file_stem().map_or_else(
+            || "bench".to_string(),
+            |stem| stem.to_string_lossy().into_owned(),
+        )
+    })
+}
+
+fn bench_candidates(args: &BenchArgs) -> Result<Vec<BenchCandidate>> {
+    let candidates: Vec<BenchCandidate> = args
+        .tools
+        .iter()
+        .map(|tool| BenchCandidate::Tool(*tool))
+        .chain(args.tiers.iter().cloned().map(BenchCandidate::Tier))
+        .collect();
+    if candidates.is_empty() {
+        bail!("csa bench needs at least one --tool or --tier to benchmark");
+    }
+    Ok(candidates)
+}
+
+/// Run one attempt of `task` with `candidate` in a fresh worktree; failures
+/// become failed records.
+async fn run_bench_task(
+    context: &BenchContext<'_>,
+    candidate: &BenchCandidate,
+    task: &BenchTask,
+) -> BenchRecord {
+    let mut record = BenchRecord {
+        recorded_at: Utc::now(),
+        suite: context.suite.to_string(),
+        task: task.name.clone(),
+        candidate: candidate.label(),
+        tool: match candidate {
+            BenchCandidate::Tool(tool) => tool.as_str().to_string(),
+            BenchCandidate::Tier(_) => String::new(),
+        },
+        model_spec: None,
+        passed: false,
+        latency_secs: 0.0,
+        total_tokens: None,
+        estimated_cost_usd: None,
+        session_id: None,
+        error: None,
+    };
+    let worktree = match IsolatedWorktree::create(context.project_root) {
+        Ok(worktree) => worktree,
+        Err(error) => {
+            record.error = Some(format!("{error:#}"));
+            return record;
+        }
+    };
+    // Keep the attempt's session with the project, not with the throwaway path.
+    csa_session::alias_session_root(&worktree.path, &worktree.project_root);
+    let writable = worktree.writable_git_paths().unwrap_or_else(|error| {
+        eprintln!("warning: worktree git dir stays read-only: {error:#}");
+        Vec::new()
+    });
+    run_attempt(
+        context,
+        candidate,
+        task,
+        &worktree.path,
+        &writable,
+        &mut record,
+    )
+    .await;
+    csa_session::remove_session_root_alias(&worktree.path);
+    worktree.remove();
+    record
+}
+
+async fn run_attempt(
+    context: &BenchContext<'_>,
+    candidate: &BenchCandidate,
+    task: &BenchTask,
+    run_root: &Path,
+    extra_writable: &[PathBuf],
+    record: &mut BenchRecord,
+) {
+    if let Some(setup) = &task.setup
+        && let Err(error) = run_shell(run_root, setup).await
+    {
+        record.error = Some(format!("setup failed: {error:#}"));
+        return;
+    }
+
+    let started = Instant::now();
+    let execution =
+        execute_candidate(context, candidate, task, run_root, extra_writable, record).await;
+    record.latency_secs = started.elapsed().as_secs_f64();
+    let execution = match execution {
+        Ok(execution) => execution,
+        Err(error) => {
+            record.error = Some(format!("{error:#}"));
+            return;
+        }
+    };
+    let usage = csa_session::load_session(run_root, &execution.meta_session_id)
+        .ok()
+        .and_then(|session| session.total_token_usage);
+    record.total_tokens = usage.as_ref().and_then(|usage| usage.total_tokens);
+    record.estimated_cost_usd = usage.and_then(|usage| usage.estimated_cost_usd);
+    record.session_id = Some(execution.meta_session_id);
+
+    if execution.execution.exit_code != 0 {
+        record.error = Some(format!(
+            "tool exited with code {}",
+            execution.execution.exit_code
+        ));
+        return;
+    }
+    match &task.validate {
+        Some(validate) => match run_shell(run_root, validate).await {
+            Ok(()) => record.passed = true,
+            Err(error) => record.error = Some(format!("validation failed: {error:#}")),
+        },
+        None => record.passed = true,
+    }
+}
+
+async fn execute_candidate(
+    context: &BenchContext<'_>,
+    candidate: &BenchCandidate,
+    task: &BenchTask,
+    run_root: &Path,
+    extra_writable: &[PathBuf],
+    record: &mut BenchRecord,
+) -> Result<crate::pipeline::SessionExecutionResult> {
+    let (tool, tier) = match candidate {
+        BenchCandidate::Tool(tool) => (Some(*tool), None),
+        BenchCandidate::Tier(tier) => (None, Some(tier.as_str())),
+    };
+    let (tool, model_spec, model) =
+        crate::run_helpers::resolve_tool_and_model(crate::run_helpers::RoutingRequest {
+            tool,
+            tier,
+            config: context.config,
+            global_config: Some(context.global_config),
+            model_catalog: Some(context.model_catalog),
+            tier_bypass_allowed: crate::run_helpers::tier_bypass_allowed(
+                context.config,
+                context.global_config,
+                false,
+            ),
+            ..crate::run_helpers::RoutingRequest::new(context.project_root)
+        })?;
+    record.tool = tool.as_str().to_string();
+    record.model_spec = model_spec.clone();
+
+    let executor = crate::pipeline::build_and_validate_executor(
+        &tool,
+        model_spec.as_deref(),
+        model.as_deref(),
+        None,
+        ConfigRefs {
+            project: context.config,
+            global: Some(context.global_config),
+            model_catalog: Some(context.model_catalog),
+        },
+        model_spec.is_none(),
+        false,
+        false,
+    )
+    .await?;
+    let _slot_guard = crate::pipeline::acquire_slot(&executor, context.global_config)?;
+
+    let extra_env = context.global_config.build_execution_env(
+        executor.tool_name(),
+        csa_config::ExecutionEnvOptions::default(),
+    );
+    // #1741: bench picks its own tool/model but still cascades an inherited pin.
+    let inherited_model_pin =
+        crate::run_cmd_model_pin::inherited_model_pin_from_startup(context.startup_env);
+    let subtree_pin =
+        crate::run_cmd_model_pin::inherited_subtree_model_pin(inherited_model_pin.as_ref());
+    let idle_timeout_seconds = crate::pipeline::resolve_idle_timeout_seconds(context.config, None);
+    let initial_response_timeout_seconds =
+        crate::pipeline::resolve_initial_response_timeout_for_tool(
+            context.config,
+            None,
+            None,
+            executor.tool_name(),
+        );
+
+    crate::pipeline::execute_with_session_and_meta(
+        &executor,
+        &tool,
+        &task.prompt,
+        OutputFormat::Json,
+        None,
+        false,
+        Some(format!(
+            "bench: {}/{} ({})",
+            context.suite,
+            task.name,
+            candidate.label()
+        )),
+        context.startup_env.session_id().map(ToOwned::to_owned),
+        run_root,
+        context.config,
+        extra_env.as_ref(),
+        subtree_pin.as_ref(),
+        Some("bench"),
+        tier,
+        None,
+        csa_process::StreamMode::BufferOnly,
+        idle_timeout_seconds,
+        initial_response_timeout_seconds,
+        task.timeout_secs.map(Duration::from_secs),
+        None,
+        Some(context.global_config),
+        None,
+        RunResourceOverrides::inherited().for_child(),
+        false,
+        false,
+        extra_writable,
+        &[],
+        None,
+        false,
+        context.startup_env,
+    )
+    .await
+}
+
+async fn run_shell(dir: &Path, command: &str) -> Result<()> {
+    let status = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .status()
+        .await
+        .with_context(|| format!("failed to spawn `{command}`"))?;
+    if !status.success() {
+        bail!("`{command}` exited with {status}");
+    }
+    Ok(())
+}
+
+fn render_comparison(summaries: &[BenchSummary]) -> String {
+    let mut out = format!(
+        "{:<24} {:<36} {:>7} {:>5} {:>9} {:>9} {:>9}\n",
+        "CANDIDATE", "MODEL", "PASSED", "RATE", "LATENCY", "TOKENS", "COST"
+    );
+    for summary in summaries {
+        let _ = writeln!(
+            out,
+            "{:<24} {:<36} {:>7} {:>4.0}% {:>8.1}s {:>9} {:>9}",
+            summary.candidate,
+            summary.model_spec.as_deref().unwrap_or(&summary.tool),
+            format!("{}/{}", summary.passed, summary.runs),
+            summary.pass_rate * 100.0,
+            summary.mean_latency_secs,
+            summary
+                .mean_total_tokens
+                .map_or_else(|| "-".to_string(), |tokens| tokens.to_string()),
+            summary
+                .total_cost_usd
+                .map_or_else(|| "-".to_string(), |cost| format!("${cost:.2}")),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "bench_cmd_tests.rs"]
+mod tests;
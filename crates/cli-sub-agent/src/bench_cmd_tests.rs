@@ -0,0 +1,152 @@
+use super::*;
+
+fn write_suite(dir: &Path, content: &str) -> std::path::PathBuf {
+    let path = dir.join("suite.toml");
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn load_suite_parses_tasks_and_rejects_invalid_suites() {
+    let dir = tempfile::tempdir().unwrap();
+    let suite = load_suite(&write_suite(
+        dir.path(),
+        r#"
+name = "smoke"
+
+[[tasks]]
+name = "fix-lint"
+prompt = "Fix the clippy warnings"
+setup = "git checkout -- ."
+validate = "cargo clippy -- -D warnings"
+timeout_secs = 600
+
+[[tasks]]
+name = "explain"
+prompt = "Explain src/lib.rs"
+"#,
+    ))
+    .unwrap();
+    assert_eq!(suite.name.as_deref(), Some("smoke"));
+    assert_eq!(suite.tasks.len(), 2);
+    assert_eq!(suite.tasks[0].timeout_secs, Some(600));
+    assert!(suite.tasks[1].validate.is_none());
+
+    let empty = load_suite(&write_suite(dir.path(), "tasks = []\n")).unwrap_err();
+    assert!(empty.to_string().contains("has no tasks"));
+
+    let duplicate = load_suite(&write_suite(
+        dir.path(),
+        "[[tasks]]\nname = \"a\"\nprompt = \"x\"\n[[tasks]]\nname = \"a\"\nprompt = \"y\"\n",
+    ))
+    .unwrap_err();
+    assert!(
+        duplicate
+            .to_string()
+            .contains("Duplicate bench task name: a")
+    );
+
+    assert!(
+        load_suite(&write_suite(
+            dir.path(),
+            "[[tasks]]\nname = \"a\"\nprompt = \"x\"\nvalidation = \"true\"\n",
+        ))
+        .is_err()
+    );
+}
+
+#[test]
+fn comparison_table_lists_every_candidate() {
+    let summaries = [
+        BenchSummary {
+            candidate: "tool:codex".to_string(),
+            tool: "codex".to_string(),
+            model_spec: Some("codex/openai/gpt-5/high".to_string()),
+            runs: 4,
+            passed: 3,
+            pass_rate: 0.75,
+            mean_latency_secs: 42.0,
+            mean_total_tokens: Some(12000),
+            total_cost_usd: Some(1.5),
+        },
+        BenchSummary {
+            candidate: "tier:fast".to_string(),
+            tool: "gemini-cli".to_string(),
+            model_spec: None,
+            runs: 4,
+            passed: 0,
+            pass_rate: 0.0,
+            mean_latency_secs: 3.0,
+            mean_total_tokens: None,
+            total_cost_usd: None,
+        },
+    ];
+    let table = render_comparison(&summaries);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("CANDIDATE"));
+    assert!(lines[1].contains("codex/openai/gpt-5/high"));
+    assert!(lines[1].contains("3/4"));
+    assert!(lines[1].contains("75%"));
+    assert!(lines[1].contains("42.0s"));
+    assert!(lines[1].contains("$1.50"));
+    assert!(lines[2].contains("gemini-cli"));
+    assert!(lines[2].contains("0/4"));
+}
+
+#[test]
+fn history_flag_replaces_the_suite_and_candidates() {
+    use clap::Parser;
+
+    let parse = |argv: &[&str]| crate::cli::Cli::try_parse_from(argv).map(|cli| cli.command);
+    assert!(matches!(
+        parse(&["csa", "bench", "--history"]),
+        Ok(crate::cli::Commands::Bench(BenchArgs {
+            history: true,
+            suite: None,
+            ..
+        }))
+    ));
+    assert!(parse(&["csa", "bench"]).is_err());
+    assert!(parse(&["csa", "bench", "--history", "--tool", "codex"]).is_err());
+}
+
+#[test]
+fn history_summaries_read_recorded_results_of_one_or_all_suites() {
+    let dir = tempfile::tempdir().unwrap();
+    let _state_home =
+        crate::test_env_lock::ScopedTestEnvVar::set("XDG_STATE_HOME", dir.path().join("state"));
+    let project_root = dir.path().join("project");
+    std::fs::create_dir_all(&project_root).unwrap();
+    assert!(history_summaries(&project_root, None).unwrap().is_empty());
+
+    let record = |suite: &str, candidate: &str| BenchRecord {
+        recorded_at: Utc::now(),
+        suite: suite.to_string(),
+        task: "task".to_string(),
+        candidate: candidate.to_string(),
+        tool: "codex".to_string(),
+        model_spec: None,
+        passed: true,
+        latency_secs: 1.0,
+        total_tokens: None,
+        estimated_cost_usd: None,
+        session_id: None,
+        error: None,
+    };
+    csa_scheduler::record_bench_results(
+        &project_root,
+        &[record("smoke", "tool:codex"), record("other", "tier:fast")],
+    )
+    .unwrap();
+
+    let all = history_summaries(&project_root, None).unwrap();
+    assert_eq!(all.len(), 2);
+    let suite = write_suite(
+        dir.path(),
+        "name = \"smoke\"\n[[tasks]]\nname = \"task\"\nprompt = \"x\"\n",
+    );
+    let smoke = history_summaries(&project_root, Some(&suite)).unwrap();
+    assert_eq!(smoke.len(), 1);
+    assert_eq!(smoke[0].candidate, "tool:codex");
+}
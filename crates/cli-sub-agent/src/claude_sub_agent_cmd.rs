@@ -1,10 +1,11 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use std::path::Path;
 use tracing::info;
 
 use crate::cli::ClaudeSubAgentArgs;
 use crate::startup_env::StartupSubtreeEnv;
 use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::error::AppError;
 use csa_core::types::{ToolArg, ToolName};
 
 pub(crate) async fn handle_claude_sub_agent(
@@ -17,7 +18,7 @@ pub(crate) async fn handle_claude_sub_agent(
     let Some((config, global_config, model_catalog, _project_completion_policy)) =
         crate::pipeline::load_and_validate(&project_root, current_depth)?
     else {
-        return Ok(1);
+        return Ok(csa_core::exit_code::ExitCode::ConfigError.code());
     };
 
     let inherited_trusted_pin = claude_sub_agent_inherited_trusted_pin(
@@ -177,7 +178,7 @@ fn resolve_claude_sub_agent_tool_and_model_with_catalog(
         request.project_config,
         request.global_config,
     )
-    .map_err(|e| anyhow!(e))?;
+    .map_err(AppError::config)?;
     let user_explicit_tool = matches!(resolved_arg_tool, Some(ToolArg::Specific(_)));
     let resolved_tool = if request.model_spec.is_some() && !user_explicit_tool {
         None
@@ -290,7 +291,7 @@ fn resolve_claude_tool(
     // CLI override is highest priority
     if let Some(tool_arg) = arg_tool {
         let resolved = resolve_tool_arg_alias(Some(tool_arg), project_config, global_config)
-            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .map_err(AppError::config)?
             .expect("Some(tool_arg) should remain Some after alias resolution");
         return match resolved {
             ToolArg::Specific(t) => Ok(t),
@@ -55,10 +55,44 @@ pub use cli_skill::*;
 #[path = "cli_verify.rs"]
 mod cli_verify;
 pub use cli_verify::*;
+#[path = "cli_top.rs"]
+mod cli_top;
+pub use cli_top::*;
+#[path = "cli_ps.rs"]
+mod cli_ps;
+pub use cli_ps::*;
+#[path = "cli_bench.rs"]
+mod cli_bench;
+pub use cli_bench::*;
+#[path = "cli_stats.rs"]
+mod cli_stats;
+pub use cli_stats::*;
+#[path = "cli_watch.rs"]
+mod cli_watch;
+pub use cli_watch::*;
+#[path = "cli_ui.rs"]
+mod cli_ui;
+pub use cli_ui::*;
+#[path = "cli_complete.rs"]
+mod cli_complete;
+pub use cli_complete::*;
+#[path = "cli_prompt.rs"]
+mod cli_prompt;
+pub use cli_prompt::*;
+#[path = "cli_worktree.rs"]
+mod cli_worktree;
+pub use cli_worktree::*;
+#[path = "cli_install_hooks.rs"]
+mod cli_install_hooks;
+pub use cli_install_hooks::*;
+#[path = "cli_export.rs"]
+mod cli_export;
+pub use cli_export::*;
 
 #[derive(Parser)]
 #[command(name = "csa", version = build_version())]
 #[command(about = "CLI Sub-Agent: Recursive Agent Container")]
+#[command(after_help = csa_core::exit_code::EXIT_CODES_HELP)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
@@ -225,8 +259,44 @@ pub enum AuditCommands {
         files: Vec<String>,
     },
 
+    /// Show files that changed since the last scan, without updating the manifest
+    Diff {
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Also report drift of weave packages and skill links from weave.lock
+        #[arg(long)]
+        skills: bool,
+
+        /// Only hash files whose size, mtime, ctime or inode changed since the last scan
+        #[arg(long)]
+        changed_only: bool,
+
+        /// Exit with status 1 when any file (or, with --skills, any skill) changed
+        #[arg(long)]
+        fail_on_change: bool,
+    },
+
     /// Reconcile manifest with filesystem state
     Sync,
+
+    /// Sign the manifest with the ed25519 key in the OS keyring (created on first use)
+    Sign,
+
+    /// Verify the manifest signature against the OS keyring key
+    Verify,
+
+    /// Watch manifest-tracked files and alert on edits made outside a running csa session
+    Watch {
+        /// Output format for change events (json prints one object per line)
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Do not fire the `audit_alert` hook for out-of-band edits
+        #[arg(long)]
+        no_hooks: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -323,6 +393,28 @@ pub enum SetupCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum AcpCommands {
+    /// Replay the client frames of an ACP capture (`CSA_ACP_CAPTURE=1`) against an agent
+    Replay {
+        /// Capture file, e.g. `<session-dir>/output/acp-capture.jsonl`
+        capture: PathBuf,
+
+        /// Seconds to wait for each captured agent request or response
+        #[arg(long, default_value_t = 30)]
+        timeout_secs: u64,
+
+        /// Print the replay report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Agent command and arguments after `--`; without one, a mock agent
+        /// answers with the captured agent frames
+        #[arg(last = true)]
+        agent: Vec<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum McpHubCommands {
     /// Start MCP Hub service
@@ -347,6 +439,11 @@ pub enum McpHubCommands {
         #[arg(long)]
         http_port: Option<u16>,
 
+        /// Client bearer tokens required by the HTTP endpoint
+        /// (default: ~/.config/cli-sub-agent/mcp-hub-tokens.toml if present)
+        #[arg(long, value_name = "PATH")]
+        http_tokens: Option<String>,
+
         /// Use systemd socket activation (Linux only)
         #[arg(long)]
         systemd_activation: bool,
@@ -357,6 +454,10 @@ pub enum McpHubCommands {
         /// Override hub socket path
         #[arg(long)]
         socket: Option<String>,
+
+        /// Show per-server and per-tool call counts, error rates and latency percentiles
+        #[arg(long)]
+        stats: bool,
     },
 
     /// Stop MCP Hub gracefully
@@ -366,10 +467,15 @@ pub enum McpHubCommands {
         socket: Option<String>,
     },
 
-    /// Regenerate mcp-hub routing-guide skill
+    /// Regenerate the mcp-hub routing-guide skill and per-tool skills
     GenSkill {
         /// Override hub socket path
         #[arg(long)]
         socket: Option<String>,
+
+        /// Update tool skills in place: keep notes below the generated section
+        /// and skills of servers that do not answer
+        #[arg(long)]
+        update: bool,
     },
 }
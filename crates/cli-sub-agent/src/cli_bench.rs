@@ -0,0 +1,36 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use std::path::PathBuf;
+
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+pub struct BenchArgs {
+    /// Task suite TOML: `[[tasks]]` with `name`, `prompt` and optional `validate`
+    #[arg(required_unless_present = "history")]
+    pub suite: Option<PathBuf>,
+
+    /// Tool to benchmark (repeatable)
+    #[arg(long = "tool", value_name = "TOOL", value_parser = super::parse_cli_tool_name)]
+    pub tools: Vec<csa_core::types::ToolName>,
+
+    /// Tier to benchmark (repeatable)
+    #[arg(long = "tier", value_name = "TIER")]
+    pub tiers: Vec<String>,
+
+    /// Runs of every task per tool or tier
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub repeat: u32,
+
+    /// Print the recorded results of earlier benches instead of running one
+    /// (only those of SUITE, if given)
+    #[arg(long, conflicts_with_all = ["tools", "tiers"])]
+    pub history: bool,
+
+    /// Autonomous mode flag (REQUIRED for root callers)
+    #[arg(long, value_name = "BOOL")]
+    pub sa_mode: Option<bool>,
+
+    /// Working directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+}
@@ -33,6 +33,25 @@ pub enum Commands {
         /// Read prompt from a file; use `-` or `/dev/stdin` for stdin.
         #[arg(long, value_name = "PATH", conflicts_with = "prompt")]
         prompt_file: Option<PathBuf>,
+        /// Run the tasks of a batch TOML file (prompt, tool/tier, depends_on) as
+        /// children of one parent session and write an aggregate JSON report
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["prompt", "prompt_flag", "prompt_file", "goal", "skill", "session", "last", "fork_from", "fork_last", "fork_from_caller"]
+        )]
+        batch: Option<String>,
+        /// Use the latest version of a saved prompt (`NAME` or `NAME@VERSION`);
+        /// the project library is searched before the global one
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["prompt", "prompt_flag", "prompt_file", "batch"]
+        )]
+        prompt_name: Option<String>,
+        /// Substitute `${KEY}` in the saved prompt (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE", requires = "prompt_name")]
+        vars: Vec<String>,
         /// Add prior review context to the prompt
         #[arg(long, value_name = "SESSION")]
         inline_context_from_review_session: Option<String>,
@@ -148,6 +167,10 @@ pub enum Commands {
         #[arg(long)]
         memory_query: Option<String>,
 
+        /// Print which memory entries were injected (or dropped) and why
+        #[arg(long, conflicts_with = "no_memory")]
+        explain_memory: bool,
+
         /// Force stdout streaming to stderr even in non-TTY/non-Text contexts
         #[arg(long, conflicts_with = "no_stream_stdout")]
         stream_stdout: bool,
@@ -213,6 +236,10 @@ pub enum Commands {
         /// Expose extra host paths to the filesystem sandbox as read-only binds.
         #[arg(long = "extra-readable", value_delimiter = ',', value_name = "PATH")]
         extra_readable: Vec<PathBuf>,
+
+        #[command(flatten)]
+        worktree: IsolatedWorktreeArgs,
+
         /// Deprecated no-op; daemon mode is the default.
         #[arg(long, hide = true)]
         daemon: bool,
@@ -273,6 +300,15 @@ pub enum Commands {
         /// Generate a fully-commented TOML template showing all options
         #[arg(long, conflicts_with = "full")]
         template: bool,
+
+        /// Start from a stack template (implies --full): rust, node, python, go
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            value_parser = ["rust", "node", "python", "go"],
+            conflicts_with = "template"
+        )]
+        from: Option<String>,
     },
 
     /// Garbage collect stale session artifacts
@@ -300,6 +336,12 @@ pub enum Commands {
     Doctor {
         #[command(subcommand)]
         subcommand: Option<DoctorSubcommand>,
+        /// Apply the fixes offered by remediable checks and print an action log
+        #[arg(long)]
+        fix: bool,
+        /// Preview the fixes `--fix` would apply without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Execute tasks from a batch file
@@ -318,9 +360,28 @@ pub enum Commands {
         dry_run: bool,
     },
 
+    /// Benchmark tools and tiers on a task suite
+    Bench(BenchArgs),
+
     /// Run as MCP server (JSON-RPC over stdio)
     McpServer,
 
+    /// Run as an ACP agent over stdio, for editors such as Zed
+    AcpServe {
+        /// Tool that answers prompts (default: tier/auto routing)
+        #[arg(long, value_parser = parse_cli_tool_name)]
+        tool: Option<csa_core::types::ToolName>,
+        /// Tier used to route prompts
+        #[arg(long)]
+        tier: Option<String>,
+    },
+
+    /// ACP developer tools
+    Acp {
+        #[command(subcommand)]
+        cmd: AcpCommands,
+    },
+
     /// Manage shared MCP Hub daemon
     McpHub {
         #[command(subcommand)]
@@ -416,6 +477,37 @@ pub enum Commands {
     /// Analyze workspace token health
     Health(HealthArgs),
 
+    /// Live resource dashboard for running sessions
+    Top(TopArgs),
+
+    /// List currently executing sessions
+    Ps(PsArgs),
+
+    /// Terminate a running session (SIGTERM, then SIGKILL after --grace seconds)
+    Kill(KillArgs),
+
+    /// Re-run a csa command whenever watched files change
+    Watch(WatchArgs),
+
+    /// Interactive session browser: genealogy tree, detail, live tail and actions
+    Ui(UiArgs),
+
+    /// Print a shell completion script (bash, zsh or fish)
+    Completions(CompletionsArgs),
+
+    /// Print completion candidates for the given words (used by completion scripts)
+    #[command(hide = true)]
+    Complete(CompleteArgs),
+
+    /// Historical usage statistics (sessions, tokens, cost) across projects
+    #[command(args_conflicts_with_subcommands = true)]
+    Stats {
+        #[command(subcommand)]
+        cmd: Option<StatsCommands>,
+        #[command(flatten)]
+        usage: StatsUsageArgs,
+    },
+
     /// Query AI tool conversation threads via xurl
     Xurl {
         #[command(subcommand)]
@@ -425,9 +517,18 @@ pub enum Commands {
     /// Recover main-agent context from recorded session history
     Recall(RecallArgs),
 
+    /// Save, list and reuse named prompts (`csa run --prompt-name`)
+    Prompt(PromptArgs),
+
     /// Manage CSA hooks
     Hooks {
         #[command(subcommand)]
         cmd: HooksCommands,
     },
+
+    /// Install git pre-commit/pre-push hooks that run `csa review` (or remove them)
+    InstallHooks(InstallHooksArgs),
+
+    /// Export shareable reports from session state
+    Export(ExportArgs),
 }
@@ -43,20 +43,12 @@ pub(crate) fn validate_return_to(value: &str) -> std::result::Result<String, Str
 pub(crate) fn parse_cli_tool_name(
     tool: &str,
 ) -> std::result::Result<csa_core::types::ToolName, String> {
-    if csa_core::types::is_removed_tool_name(tool) {
-        return Err(csa_core::types::removed_tool_error(tool));
-    }
-    match tool {
-        "opencode" => Ok(csa_core::types::ToolName::Opencode),
-        "codex" => Ok(csa_core::types::ToolName::Codex),
-        "claude-code" => Ok(csa_core::types::ToolName::ClaudeCode),
-        "openai-compat" => Ok(csa_core::types::ToolName::OpenaiCompat),
-        "hermes" => Ok(csa_core::types::ToolName::Hermes),
-        "antigravity-cli" => Ok(csa_core::types::ToolName::AntigravityCli),
-        "claude" => Ok(csa_core::types::ToolName::ClaudeCode),
-        "antigravity" => Ok(csa_core::types::ToolName::AntigravityCli),
-        _ => Err(format!(
-            "unknown tool '{tool}'. Valid values: opencode, codex, claude-code, openai-compat, hermes, antigravity-cli"
+    match csa_core::tool_registry::builtin_tool(tool) {
+        Some(builtin) if builtin.removed => Err(csa_core::types::removed_tool_error(tool)),
+        Some(builtin) => Ok(builtin.name),
+        None => Err(format!(
+            "unknown tool '{tool}'. Valid values: {}",
+            csa_core::types::supported_tool_list()
         )),
     }
 }
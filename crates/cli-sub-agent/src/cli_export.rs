@@ -0,0 +1,29 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+//! `csa export`: shareable artifacts built from session state.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub cmd: ExportCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// Write a self-contained HTML report for a session or review
+    Report {
+        /// Session ID or unique prefix
+        session: String,
+
+        /// Output file for the HTML report
+        #[arg(long, short = 'o', value_name = "PATH")]
+        out: PathBuf,
+
+        /// Working directory (defaults to CWD)
+        #[arg(long)]
+        cd: Option<String>,
+    },
+}
@@ -0,0 +1,46 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+//! `csa install-hooks`: git hooks that gate commits and pushes on `csa review`.
+
+use clap::{Args, ValueEnum};
+use csa_core::types::ReviewFailOn;
+
+/// Git hook managed by `csa install-hooks`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum GitHookKind {
+    /// Review the staged changes before each commit
+    PreCommit,
+    /// Review the pushed commits before each push
+    PrePush,
+}
+
+impl GitHookKind {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::PreCommit => "pre-commit",
+            Self::PrePush => "pre-push",
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct InstallHooksArgs {
+    /// Hook to manage (repeatable; default: pre-commit)
+    #[arg(long = "hook", value_enum, value_name = "HOOK")]
+    pub hooks: Vec<GitHookKind>,
+
+    /// Lowest finding severity that blocks the commit or push
+    #[arg(long, value_enum, value_name = "SEVERITY", default_value_t = ReviewFailOn::High)]
+    pub fail_on: ReviewFailOn,
+
+    /// Extra `csa review` arguments, e.g. "--tier tier-2-standard" (shell words)
+    #[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+    pub review_args: Option<String>,
+
+    /// Remove the CSA hooks and restore the hooks they chained
+    #[arg(long, conflicts_with = "review_args")]
+    pub uninstall: bool,
+
+    /// Repository directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+}
@@ -1,4 +1,6 @@
 // NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use std::path::PathBuf;
+
 use clap::{Subcommand, ValueEnum};
 
 #[derive(Subcommand, Debug)]
@@ -39,6 +41,12 @@ pub enum MemoryCommands {
         /// Comma-separated tags
         #[arg(long)]
         tags: Option<String>,
+        /// Review finding or todo ID this memory was derived from
+        #[arg(long)]
+        finding: Option<String>,
+        /// File the memory relates to (repeatable)
+        #[arg(long = "file")]
+        files: Vec<String>,
     },
     /// Show a specific memory entry by ID
     Show {
@@ -62,6 +70,35 @@ pub enum MemoryCommands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// List contradictory key/value facts (same key, different value)
+    Conflicts {
+        /// Mark all but the most recent fact for each conflicting key superseded
+        #[arg(long)]
+        resolve: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export memory entries to a portable, versioned JSONL file
+    Export {
+        /// Destination file
+        #[arg(long)]
+        out: PathBuf,
+        /// Only export entries of this project
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Import entries from a `csa memory export` file, preserving IDs
+    Import {
+        /// Export file to import
+        path: PathBuf,
+        /// Reassign imported entries to this project key
+        #[arg(long)]
+        project: Option<String>,
+        /// Preview the import without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Migrate legacy memory entries to another backend
     Migrate {
         /// Target memory backend
@@ -49,6 +49,12 @@ pub enum PlanCommands {
         #[arg(long)]
         chunked: bool,
 
+        /// Show each step's tool and rendered prompt and ask to approve, edit,
+        /// skip, or abort before running it; decisions go into the journal.
+        /// Implies --foreground.
+        #[arg(long, conflicts_with_all = ["dry_run", "chunked"])]
+        interactive: bool,
+
         /// Resume from a journal state file (path to journal JSON file)
         #[arg(long, conflicts_with = "file", conflicts_with = "pattern")]
         resume: Option<String>,
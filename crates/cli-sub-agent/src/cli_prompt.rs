@@ -0,0 +1,40 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+//! CLI subcommands for the saved prompt library.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+pub struct PromptArgs {
+    #[command(subcommand)]
+    pub cmd: PromptCommands,
+}
+
+#[derive(Subcommand)]
+pub enum PromptCommands {
+    /// Save a prompt under NAME as a new version (reads stdin unless --file is given)
+    Save {
+        /// Prompt name (letters, digits, `.`, `_`, `-`)
+        name: String,
+
+        /// Read the prompt from a file instead of stdin
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+
+        /// Save to the global library instead of the current project's
+        #[arg(long)]
+        global: bool,
+
+        /// Working directory (defaults to CWD)
+        #[arg(long)]
+        cd: Option<String>,
+    },
+
+    /// List saved prompts from the project and global libraries
+    List {
+        /// Working directory (defaults to CWD)
+        #[arg(long)]
+        cd: Option<String>,
+    },
+}
@@ -0,0 +1,32 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+pub struct PsArgs {
+    /// Show running sessions from every project, not just the current one
+    #[arg(long)]
+    pub all_projects: bool,
+
+    /// Working directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct KillArgs {
+    /// Session ID or prefix to kill (positional alternative to --session)
+    #[arg(conflicts_with = "session", required_unless_present = "session")]
+    pub session_id: Option<String>,
+
+    /// Session ID or prefix to kill
+    #[arg(long)]
+    pub session: Option<String>,
+
+    /// Seconds to wait after SIGTERM before sending SIGKILL
+    #[arg(long, value_name = "SECS", default_value_t = 10)]
+    pub grace: u64,
+
+    /// Working directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+}
@@ -2,15 +2,23 @@
 use std::path::PathBuf;
 
 use clap::{ArgGroup, ValueEnum};
-use csa_core::types::{ToolArg, ToolName};
+use csa_core::types::{ReviewFailOn, ToolArg, ToolName};
 use serde::{Deserialize, Serialize};
 
 use super::{Commands, parse_cli_tool_name, parse_model_spec_arg, parse_spec_path_arg};
 
+#[path = "cli_review_baseline.rs"]
+mod baseline_args;
 #[path = "cli_review_convergence.rs"]
 mod convergence_args;
 #[path = "cli_review_repair.rs"]
 mod repair_args;
+#[path = "cli_review_report.rs"]
+mod report_args;
+
+pub use baseline_args::{DEFAULT_REVIEW_BASELINE_PATH, ReviewBaselineCommand, ReviewCommand};
+pub use report_args::ReviewFormat;
+pub(crate) use report_args::normalize_review_format_args;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
@@ -83,9 +91,10 @@ impl std::fmt::Display for ReviewChunkingMode {
 }
 
 #[derive(clap::Args, Clone)]
+#[command(args_conflicts_with_subcommands = true)]
 #[command(group(
     ArgGroup::new("review_scope")
-        .args(["diff", "commit", "range", "files"])
+        .args(["diff", "staged", "commit", "range", "files"])
         .multiple(false)
 ))]
 #[command(group(
@@ -94,6 +103,9 @@ impl std::fmt::Display for ReviewChunkingMode {
         .multiple(false)
 ))]
 pub struct ReviewArgs {
+    #[command(subcommand)]
+    pub command: Option<ReviewCommand>,
+
     /// Produce a read-only convergence report for an explicit range.
     ///
     /// Add --discovery-only for the legacy discovery JSON, or --execute-completion to request
@@ -197,7 +209,7 @@ pub struct ReviewArgs {
     pub full_consistency: bool,
 
     /// Compare against branch (default: main)
-    #[arg(long, conflicts_with_all = ["diff", "commit", "range", "files"])]
+    #[arg(long, conflicts_with_all = ["diff", "staged", "commit", "range", "files"])]
     pub branch: Option<String>,
 
     /// Review one commit's diff (`<sha>^..<sha>`). `--base` is not accepted in this mode.
@@ -214,6 +226,49 @@ pub struct ReviewArgs {
     #[arg(long)]
     pub files: Option<String>,
 
+    /// Review only the staged changes (git diff --cached)
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Limit the reviewed diff to paths matching GLOB (repeatable; `**` crosses
+    /// directories, e.g. `crates/foo/**`). Applies to any scope except `--files`.
+    #[arg(long = "path", value_name = "GLOB", conflicts_with = "files")]
+    pub paths: Vec<String>,
+
+    /// Report format: text (default) or sarif (also accepts `--format sarif`).
+    ///
+    /// `sarif` runs in the foreground and prints the findings as a SARIF 2.1.0 log on stdout
+    /// for CI code scanning; the usual review report is written to stderr.
+    #[arg(long = "review-format", value_enum, default_value_t = ReviewFormat::Text)]
+    pub review_format: ReviewFormat,
+
+    /// Report findings recorded in this baseline as known and keep them out of the exit code.
+    ///
+    /// Without a value uses `.csa/review-baseline.toml`; relative paths resolve against the
+    /// project root. Create or refresh it with `csa review baseline update`.
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = DEFAULT_REVIEW_BASELINE_PATH
+    )]
+    pub baseline: Option<PathBuf>,
+
+    /// Derive the exit code from finding severities: fail on `critical`, `high` (and above) or
+    /// `any` finding. Defaults to `[review] fail_on`; runs in the foreground.
+    #[arg(long, value_enum, value_name = "SEVERITY")]
+    pub fail_on: Option<ReviewFailOn>,
+
+    /// Post the findings as review comments on GitHub PR N, updating the comments of an earlier
+    /// run, with the verdict as the review body. Uses the `[github]` auth of `gh`.
+    #[arg(long, value_name = "N")]
+    pub post_to_pr: Option<u64>,
+
+    /// GitHub Actions mode: review the pull request range from the `GITHUB_*` environment, emit
+    /// workflow annotations and a job summary, and exit by `--fail-on` (default `high`).
+    #[arg(long)]
+    pub ci: bool,
+
     /// Chunk large review diffs by module/crate before reviewer execution
     #[arg(long, value_enum, default_value_t = ReviewChunkingMode::Auto)]
     pub chunked_review: ReviewChunkingMode,
@@ -236,6 +291,18 @@ pub struct ReviewArgs {
     #[arg(long, conflicts_with_all = ["fix", "check_verdict"])]
     pub fix_finding: bool,
 
+    /// Apply the diff suggestions of a finished review session (`--session <ID>`).
+    ///
+    /// Reads the fenced ```diff blocks of the review report, asks before each hunk, applies the
+    /// accepted ones with `git apply`, runs the review quality gate as check command, and records
+    /// the outcome in the session's `output/applied-suggestions.json`.
+    #[arg(long, requires = "session", conflicts_with_all = ["fix", "fix_finding", "check_verdict"])]
+    pub apply_suggestions: bool,
+
+    /// Apply every suggested hunk without asking (with --apply-suggestions)
+    #[arg(short = 'y', long, requires = "apply_suggestions")]
+    pub yes: bool,
+
     /// Maximum fix iterations when --fix is enabled (default: 3)
     #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..))]
     pub max_rounds: u8,
@@ -278,6 +345,20 @@ pub struct ReviewArgs {
     )]
     pub consensus: String,
 
+    /// Accept a finding only when at least K reviewers flagged it (multi-reviewer mode).
+    /// Findings are matched by fid or by rule, file and nearby line; the verdict
+    /// then follows the accepted findings instead of the reviewers' votes.
+    #[arg(long, value_name = "K", value_parser = clap::value_parser!(u32).range(1..))]
+    pub min_agreement: Option<u32>,
+
+    /// Skip diff hunks that an earlier review at the same tier found clean (single reviewer).
+    /// Results are cached by file, hunk content hash and tier in the project state directory.
+    #[arg(
+        long,
+        conflicts_with_all = ["fix", "fix_finding", "check_verdict", "converge", "repair_only", "min_agreement"]
+    )]
+    pub hunk_cache: bool,
+
     /// Absolute wall-clock timeout in seconds (kills execution after N seconds when set)
     #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
     pub timeout: Option<u64>,
@@ -454,6 +535,44 @@ pub fn validate_review_args(args: &ReviewArgs) -> std::result::Result<(), clap::
         ));
     }
 
+    if let Some(path) = args
+        .paths
+        .iter()
+        .find(|path| path.trim().is_empty() || path.contains(','))
+    {
+        return Err(clap::Error::raw(
+            clap::error::ErrorKind::ValueValidation,
+            format!(
+                "--path {path:?} must be a non-empty glob without commas; repeat --path instead"
+            ),
+        ));
+    }
+
+    if let Some(min_agreement) = args.min_agreement {
+        if args.single {
+            return Err(clap::Error::raw(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--min-agreement needs multiple reviewers and conflicts with --single",
+            ));
+        }
+        if args.reviewers.is_some() && min_agreement > args.requested_reviewers() {
+            return Err(clap::Error::raw(
+                clap::error::ErrorKind::ValueValidation,
+                format!(
+                    "--min-agreement {min_agreement} exceeds --reviewers {}",
+                    args.requested_reviewers()
+                ),
+            ));
+        }
+    }
+
+    if args.hunk_cache && args.requested_reviewers() > 1 {
+        return Err(clap::Error::raw(
+            clap::error::ErrorKind::ArgumentConflict,
+            "--hunk-cache caches single-reviewer results and conflicts with --reviewers > 1",
+        ));
+    }
+
     if args.fix_finding && args.session.is_none() {
         return Err(clap::Error::raw(
             clap::error::ErrorKind::MissingRequiredArgument,
@@ -468,6 +587,15 @@ pub fn validate_review_args(args: &ReviewArgs) -> std::result::Result<(), clap::
         ));
     }
 
+    report_args::validate_run_report_args(args)?;
+
+    if args.apply_suggestions && (args.converge || args.repair_only) {
+        return Err(clap::Error::raw(
+            clap::error::ErrorKind::ArgumentConflict,
+            "--apply-suggestions applies a finished review and conflicts with --converge and --repair-only",
+        ));
+    }
+
     if args.fix_finding && args.requested_reviewers() > 1 {
         return Err(clap::Error::raw(
             clap::error::ErrorKind::ArgumentConflict,
@@ -0,0 +1,38 @@
+//! `csa review baseline` subcommands.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+
+/// Default baseline location, relative to the project root.
+pub const DEFAULT_REVIEW_BASELINE_PATH: &str = ".csa/review-baseline.toml";
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ReviewCommand {
+    /// Manage the baseline of accepted review findings
+    Baseline {
+        #[command(subcommand)]
+        command: ReviewBaselineCommand,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ReviewBaselineCommand {
+    /// Replace the baseline with the findings of review sessions
+    ///
+    /// Every finding of the given sessions (default: the latest review session)
+    /// becomes a known finding; baseline entries not among them are dropped.
+    Update {
+        /// Baseline file (relative paths resolve against the project root)
+        #[arg(long, value_name = "PATH", default_value = DEFAULT_REVIEW_BASELINE_PATH)]
+        baseline: PathBuf,
+
+        /// Review session whose findings to accept (repeatable; prefixes allowed)
+        #[arg(long, value_name = "SESSION_ID")]
+        session: Vec<String>,
+
+        /// Working directory
+        #[arg(long)]
+        cd: Option<String>,
+    },
+}
@@ -76,6 +76,10 @@ pub(super) fn validate(args: &ReviewArgs) -> std::result::Result<(), clap::Error
         Some("--session/--resume")
     } else if args.diff {
         Some("--diff")
+    } else if args.staged {
+        Some("--staged")
+    } else if !args.paths.is_empty() {
+        Some("--path")
     } else if args.branch.is_some() {
         Some("--branch")
     } else if args.commit.is_some() {
@@ -22,6 +22,8 @@ pub(super) fn validate_repair_only_args(args: &ReviewArgs) -> std::result::Resul
     } else if args.fix || args.fix_finding {
         Some("--fix/--fix-finding")
     } else if args.diff
+        || args.staged
+        || !args.paths.is_empty()
         || args.branch.is_some()
         || args.commit.is_some()
         || args.range.is_some()
@@ -0,0 +1,116 @@
+//! Flags that report one review run: `--format sarif`, `--baseline`, `--fail-on`,
+//! `--post-to-pr` and `--ci`.
+
+use std::ffi::OsString;
+
+use clap::ValueEnum;
+
+use super::ReviewArgs;
+
+/// Report format of `csa review` (`--format` is otherwise the global text/json flag).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    #[default]
+    Text,
+    /// SARIF 2.1.0 log of the review findings on stdout; the report goes to stderr.
+    Sarif,
+}
+
+pub(super) fn validate_run_report_args(args: &ReviewArgs) -> std::result::Result<(), clap::Error> {
+    let flag = if args.baseline.is_some() {
+        "--baseline"
+    } else if args.fail_on.is_some() {
+        "--fail-on"
+    } else if args.review_format == ReviewFormat::Sarif {
+        "--format sarif"
+    } else if args.post_to_pr.is_some() {
+        "--post-to-pr"
+    } else if args.ci {
+        "--ci"
+    } else {
+        return Ok(());
+    };
+    let conflicting = [
+        (args.fix, "--fix"),
+        (args.fix_finding, "--fix-finding"),
+        (args.apply_suggestions, "--apply-suggestions"),
+        (args.check_verdict, "--check-verdict"),
+        (args.converge, "--converge"),
+        (args.repair_only, "--repair-only"),
+    ];
+    if let Some((_, conflict)) = conflicting.iter().find(|(set, _)| *set) {
+        return Err(clap::Error::raw(
+            clap::error::ErrorKind::ArgumentConflict,
+            format!("{flag} reports one review run and conflicts with {conflict}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Rewrite `review --format sarif` to the review-local `--review-format sarif`.
+///
+/// The root command owns the global text/json `--format`, so `review` cannot define its own.
+/// Only the `sarif` value is rewritten; `--format json` keeps its global meaning.
+pub(crate) fn normalize_review_format_args(
+    args: impl IntoIterator<Item = OsString>,
+) -> Vec<OsString> {
+    let mut in_review = false;
+    let mut pending_format = false;
+    let mut normalized: Vec<OsString> = Vec::new();
+    for arg in args {
+        if pending_format {
+            pending_format = false;
+            if arg == "sarif"
+                && let Some(flag) = normalized.last_mut()
+            {
+                *flag = OsString::from("--review-format");
+            }
+        } else if in_review && arg == "--format" {
+            pending_format = true;
+        } else if in_review && arg == "--format=sarif" {
+            normalized.push(OsString::from("--review-format=sarif"));
+            continue;
+        } else if arg == "review" {
+            in_review = true;
+        }
+        normalized.push(arg);
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_review_format_args;
+    use std::ffi::OsString;
+
+    fn normalize_review(args: &[&str]) -> Vec<String> {
+        normalize_review_format_args(args.iter().map(OsString::from))
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn review_format_sarif_is_rewritten_to_the_review_flag() {
+        assert_eq!(
+            normalize_review(&["csa", "review", "--diff", "--format", "sarif"]),
+            ["csa", "review", "--diff", "--review-format", "sarif"]
+        );
+        assert_eq!(
+            normalize_review(&["csa", "review", "--format=sarif"]),
+            ["csa", "review", "--review-format=sarif"]
+        );
+    }
+
+    #[test]
+    fn global_format_values_are_left_alone() {
+        for args in [
+            ["csa", "review", "--format", "json"],
+            ["csa", "--format", "sarif", "review"],
+            ["csa", "run", "--format", "sarif"],
+        ] {
+            assert_eq!(normalize_review(&args), args);
+        }
+    }
+}
@@ -207,6 +207,29 @@ pub enum SessionCommands {
         cd: Option<String>,
     },
 
+    /// Show one session: metadata, genealogy, tools, tokens, sections, return packet, hooks
+    Show {
+        /// Session ID or prefix (positional alternative to --session)
+        #[arg(conflicts_with = "session")]
+        session_id: Option<String>,
+
+        /// Session ID or prefix
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Output as JSON instead of human-readable
+        #[arg(long)]
+        json: bool,
+
+        /// Dump a single output section by ID instead of the overview
+        #[arg(long)]
+        section: Option<String>,
+
+        /// Working directory
+        #[arg(long)]
+        cd: Option<String>,
+    },
+
     /// List artifacts in a session's output directory
     Artifacts {
         /// Session ID or prefix (positional alternative to --session)
@@ -338,6 +361,22 @@ pub enum SessionCommands {
         cd: Option<String>,
     },
 
+    /// Rebuild output sections, a partial return packet and result.toml from
+    /// the output.log spool of a run that died (e.g. SIGKILL)
+    Recover {
+        /// Session ID or prefix (positional alternative to --session)
+        #[arg(conflicts_with = "session")]
+        session_id: Option<String>,
+
+        /// Session ID or prefix
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Working directory
+        #[arg(long)]
+        cd: Option<String>,
+    },
+
     /// Kill a running daemon session (SIGTERM, then SIGKILL after grace period)
     Kill {
         /// Session ID to kill (positional alternative to --session)
@@ -0,0 +1,36 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use clap::{Args, Subcommand, ValueEnum};
+
+#[derive(Subcommand)]
+pub enum StatsCommands {
+    /// Show historical peak-memory estimates per tool, model and task kind
+    Memory {
+        /// Only show entries for this tool
+        #[arg(long)]
+        tool: Option<String>,
+
+        /// Half-life in days for weighting older runs
+        #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(u64).range(1..))]
+        half_life_days: u64,
+    },
+}
+
+/// Options of the usage report printed by `csa stats` without a subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct StatsUsageArgs {
+    /// Only count sessions created within this window (e.g. 12h, 7d, 30d)
+    #[arg(long, default_value = "30d")]
+    pub since: String,
+
+    /// Dimension to group sessions by
+    #[arg(long, value_enum, default_value_t = StatsGroupBy::Tool)]
+    pub by: StatsGroupBy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsGroupBy {
+    Tool,
+    Model,
+    Tier,
+    Project,
+}
@@ -0,0 +1,21 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+pub struct TopArgs {
+    /// Seconds between refreshes
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Print a single snapshot and exit (also used when stdout is not a terminal)
+    #[arg(long)]
+    pub once: bool,
+
+    /// Show live sessions from every project, not just the current one
+    #[arg(long)]
+    pub all_projects: bool,
+
+    /// Working directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+}
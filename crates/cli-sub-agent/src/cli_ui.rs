@@ -0,0 +1,13 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+pub struct UiArgs {
+    /// Seconds between refreshes of the session list and live tail
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Working directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+}
@@ -0,0 +1,32 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+#[command(
+    after_help = "Pass the csa command to run after `--`, for example: csa watch --on-change 'src/**' -- review --sa-mode false --diff"
+)]
+pub struct WatchArgs {
+    /// Project-relative glob whose file changes trigger a run (repeatable)
+    #[arg(long = "on-change", value_name = "GLOB", required = true)]
+    pub on_change: Vec<String>,
+
+    /// Milliseconds without further changes before a run starts
+    #[arg(long, default_value_t = 500, value_parser = clap::value_parser!(u64).range(50..))]
+    pub debounce_ms: u64,
+
+    /// Milliseconds between scans of the watched files
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(100..))]
+    pub poll_ms: u64,
+
+    /// Also run once at startup, before the first change
+    #[arg(long)]
+    pub initial: bool,
+
+    /// Working directory (defaults to CWD)
+    #[arg(long)]
+    pub cd: Option<String>,
+
+    /// csa command to run, supplied after `--`
+    #[arg(last = true, required = true, value_name = "CSA_ARG")]
+    pub command: Vec<String>,
+}
@@ -0,0 +1,27 @@
+// NOTE #1858: #[path]-included by tests; no `crate::`, no binary-only methods (dead_code).
+//! `csa run` flags for git worktree isolation.
+
+use clap::Args;
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct IsolatedWorktreeArgs {
+    /// Run the tool in a temporary git worktree of HEAD and record the resulting
+    /// diff in the session instead of editing the live working tree
+    #[arg(
+        long,
+        conflicts_with_all = ["session", "last", "fork_from", "fork_last", "fork_from_caller", "fork_call", "ephemeral", "batch"]
+    )]
+    pub isolated_worktree: bool,
+
+    /// Apply the worktree diff to the live working tree after the run
+    #[arg(
+        long,
+        requires = "isolated_worktree",
+        conflicts_with = "worktree_branch"
+    )]
+    pub worktree_apply: bool,
+
+    /// Commit the worktree changes to a new branch NAME after the run
+    #[arg(long, value_name = "NAME", requires = "isolated_worktree")]
+    pub worktree_branch: Option<String>,
+}
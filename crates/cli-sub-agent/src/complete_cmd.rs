@@ -0,0 +1,231 @@
+//! `csa completions` and the hidden `csa complete` helper behind it.
+//!
+//! The generated scripts hand the words typed so far to `csa complete`, which
+//! walks the clap command tree for subcommands and flags, and fills flag values
+//! from live state: session IDs for `--session`/`--fork-from`, runnable skills
+//! for `--skill`, and tool names plus configured aliases for `--tool`.
+
+use std::cmp::Reverse;
+use std::path::Path;
+
+use clap::{Arg, Command, CommandFactory, ValueEnum};
+use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::types::ToolName;
+
+use crate::cli::{Cli, CompleteArgs, CompletionShell, CompletionsArgs};
+
+const BASH_SCRIPT: &str = r#"_csa() {
+    local IFS=$'\n'
+    COMPREPLY=($(csa complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null))
+}
+complete -o default -F _csa csa
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef csa
+_csa() {
+    local -a candidates
+    candidates=(${(f)"$(csa complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)"})
+    (( ${#candidates} )) && compadd -a candidates || _files
+}
+if [ "$funcstack[1]" = "_csa" ]; then
+    _csa "$@"
+else
+    compdef _csa csa
+fi
+"#;
+
+const FISH_SCRIPT: &str = r#"function __csa_complete
+    set -l words (commandline -opc)
+    set -e words[1]
+    csa complete -- $words (commandline -ct) 2>/dev/null
+end
+complete -c csa -f -a '(__csa_complete)'
+"#;
+
+/// Live state a flag value is completed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueSource {
+    Session,
+    Skill,
+    Tool,
+}
+
+fn value_source(long: &str) -> Option<ValueSource> {
+    match long {
+        "session" | "fork-from" => Some(ValueSource::Session),
+        "skill" => Some(ValueSource::Skill),
+        "tool" => Some(ValueSource::Tool),
+        _ => None,
+    }
+}
+
+pub(crate) fn handle_completions(args: CompletionsArgs) {
+    print!("{}", completion_script(args.shell));
+}
+
+pub(crate) fn completion_script(shell: CompletionShell) -> &'static str {
+    match shell {
+        CompletionShell::Bash => BASH_SCRIPT,
+        CompletionShell::Zsh => ZSH_SCRIPT,
+        CompletionShell::Fish => FISH_SCRIPT,
+    }
+}
+
+/// Print one candidate per line. Failures to read state yield no candidates
+/// rather than an error, so a broken state dir never garbles the shell.
+pub(crate) fn handle_complete(args: CompleteArgs) {
+    let mut command = Cli::command();
+    command.build();
+    for candidate in complete_words(&command, &args.words, &live_values) {
+        println!("{candidate}");
+    }
+}
+
+fn live_values(source: ValueSource) -> Vec<String> {
+    let Ok(project_root) = crate::pipeline::determine_project_root(None) else {
+        return Vec::new();
+    };
+    match source {
+        ValueSource::Session => {
+            let mut sessions =
+                csa_session::list_sessions_readonly(&project_root, None).unwrap_or_default();
+            sessions.sort_by_key(|session| Reverse(session.last_accessed));
+            sessions
+                .into_iter()
+                .map(|session| session.meta_session_id)
+                .collect()
+        }
+        ValueSource::Skill => crate::skill_resolver::list_active_skill_sources(&project_root)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|skill| skill.name)
+            .collect(),
+        ValueSource::Tool => tool_names(&project_root),
+    }
+}
+
+fn tool_names(project_root: &Path) -> Vec<String> {
+    let mut names = vec!["auto".to_string(), "any-available".to_string()];
+    names.extend(
+        ToolName::value_variants()
+            .iter()
+            .filter_map(ToolName::to_possible_value)
+            .map(|value| value.get_name().to_string()),
+    );
+    if let Ok(global) = GlobalConfig::load() {
+        names.extend(global.tool_aliases.into_keys());
+    }
+    if let Ok(Some(project)) = ProjectConfig::load(project_root) {
+        names.extend(project.tool_aliases.into_keys());
+    }
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|name| seen.insert(name.clone()));
+    names
+}
+
+/// Candidates for the last of `words`, given the words typed before it.
+fn complete_words(
+    root: &Command,
+    words: &[String],
+    live: &impl Fn(ValueSource) -> Vec<String>,
+) -> Vec<String> {
+    let (current, typed) = match words.split_last() {
+        Some((current, typed)) => (current.as_str(), typed),
+        None => ("", &[][..]),
+    };
+
+    let mut command = root;
+    let mut pending_value: Option<&Arg> = None;
+    for word in typed {
+        if pending_value.is_some() {
+            // Bash splits `--flag=value` into `--flag`, `=`, `value`.
+            if word != "=" {
+                pending_value = None;
+            }
+            continue;
+        }
+        if let Some(arg) = flag_taking_value(command, word) {
+            pending_value = Some(arg);
+        } else if let Some(subcommand) = command.find_subcommand(word) {
+            command = subcommand;
+        }
+    }
+
+    if let Some(arg) = pending_value {
+        return with_prefix(arg_values(arg, live), current);
+    }
+    if let Some((flag, value)) = current.split_once('=')
+        && let Some(arg) = flag_taking_value(command, flag)
+    {
+        return with_prefix(arg_values(arg, live), value)
+            .into_iter()
+            .map(|value| format!("{flag}={value}"))
+            .collect();
+    }
+    let candidates = if current.starts_with('-') {
+        command
+            .get_arguments()
+            .filter(|arg| !arg.is_hide_set())
+            .filter_map(Arg::get_long)
+            .map(|long| format!("--{long}"))
+            .collect()
+    } else if command.has_subcommands() {
+        command
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect()
+    } else {
+        command
+            .get_positionals()
+            .flat_map(|arg| arg.get_possible_values())
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect()
+    };
+    with_prefix(candidates, current)
+}
+
+/// The argument behind `--long` or `-s` when it consumes the next word.
+fn flag_taking_value<'a>(command: &'a Command, word: &str) -> Option<&'a Arg> {
+    let arg = if let Some(long) = word.strip_prefix("--") {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(long))
+    } else {
+        let mut chars = word.strip_prefix('-')?.chars();
+        let short = chars.next().filter(|_| chars.next().is_none())?;
+        command
+            .get_arguments()
+            .find(|arg| arg.get_short() == Some(short))
+    }?;
+    arg.get_action().takes_values().then_some(arg)
+}
+
+/// Declared possible values win; free-form flags fall back to live state.
+fn arg_values(arg: &Arg, live: &impl Fn(ValueSource) -> Vec<String>) -> Vec<String> {
+    let possible: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    if !possible.is_empty() {
+        return possible;
+    }
+    arg.get_long()
+        .and_then(value_source)
+        .map(live)
+        .unwrap_or_default()
+}
+
+fn with_prefix(candidates: Vec<String>, prefix: &str) -> Vec<String> {
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "complete_cmd_tests.rs"]
+mod tests;
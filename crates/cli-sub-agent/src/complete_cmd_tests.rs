@@ -0,0 +1,51 @@
+use super::*;
+
+fn complete(words: &[&str]) -> Vec<String> {
+    let mut command = Cli::command();
+    command.build();
+    let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+    complete_words(&command, &words, &|source| match source {
+        ValueSource::Session => vec!["01AAA".to_string(), "01BBB".to_string()],
+        ValueSource::Skill => vec!["review".to_string(), "refactor".to_string()],
+        ValueSource::Tool => vec!["codex".to_string(), "claude-code".to_string()],
+    })
+}
+
+#[test]
+fn completes_subcommands_and_flags() {
+    let top_level = complete(&[""]);
+    assert!(top_level.contains(&"run".to_string()));
+    assert!(top_level.contains(&"completions".to_string()));
+    assert!(!top_level.contains(&"complete".to_string()));
+
+    assert_eq!(complete(&["sess"]), ["session"]);
+    let flags = complete(&["run", "--fork"]);
+    assert!(flags.contains(&"--fork-from".to_string()));
+    assert!(flags.contains(&"--fork-last".to_string()));
+}
+
+#[test]
+fn completes_flag_values_from_live_state() {
+    assert_eq!(complete(&["run", "--fork-from", "01A"]), ["01AAA"]);
+    assert_eq!(complete(&["run", "-s", ""]), ["01AAA", "01BBB"]);
+    assert_eq!(
+        complete(&["run", "--skill=re"]),
+        ["--skill=review", "--skill=refactor"]
+    );
+    assert_eq!(complete(&["run", "--tool", "=", "cl"]), ["claude-code"]);
+    // A value already given does not leave the next word in value position.
+    assert!(complete(&["run", "--tool", "codex", "--fork"]).contains(&"--fork-from".to_string()));
+}
+
+#[test]
+fn possible_values_take_precedence() {
+    assert_eq!(complete(&["session", "list", "--format", "j"]), ["json"]);
+    assert_eq!(complete(&["completions", "f"]), ["fish"]);
+}
+
+#[test]
+fn scripts_call_the_hidden_helper() {
+    for script in [BASH_SCRIPT, ZSH_SCRIPT, FISH_SCRIPT] {
+        assert!(script.contains("csa complete -- "));
+    }
+}
@@ -4,7 +4,6 @@ use anyhow::Result;
 use tracing::{error, warn};
 
 use csa_config::config::CURRENT_SCHEMA_VERSION;
-use csa_config::init::init_project;
 use csa_config::{GlobalConfig, ProjectConfig, validate_config};
 use csa_core::types::OutputFormat;
 
@@ -20,6 +19,9 @@ use display::{build_execution_toml, build_project_display_json, build_project_di
 #[path = "config_cmds_set.rs"]
 mod set;
 pub(crate) use set::handle_config_set;
+#[path = "config_cmds_init.rs"]
+mod init;
+pub(crate) use init::handle_init;
 
 pub(crate) fn handle_config_show(cd: Option<String>, format: OutputFormat) -> Result<()> {
     let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
@@ -63,7 +65,7 @@ pub(crate) fn handle_config_edit(cd: Option<String>) -> Result<()> {
     Ok(())
 }
 
-fn parse_editor_command(editor: &str) -> Result<(String, Vec<String>)> {
+pub(crate) fn parse_editor_command(editor: &str) -> Result<(String, Vec<String>)> {
     let mut parts = Vec::new();
     let mut current = String::new();
     let mut in_single_quotes = false;
@@ -119,44 +121,6 @@ fn parse_editor_command(editor: &str) -> Result<(String, Vec<String>)> {
     Ok((program.clone(), args.to_vec()))
 }
 
-pub(crate) fn handle_init(non_interactive: bool, full: bool, template: bool) -> Result<()> {
-    let project_root = crate::pipeline::determine_project_root(None)?;
-
-    if template {
-        return handle_init_template(&project_root);
-    }
-
-    // Default (no flags) = minimal; --full = old default with tool detection.
-    let minimal = !full;
-    let config = init_project(&project_root, non_interactive, minimal)?;
-    eprintln!(
-        "Initialized project configuration at: {}",
-        ProjectConfig::config_path(&project_root).display()
-    );
-    eprintln!("Project: {}", config.project.name);
-    if minimal {
-        eprintln!("  Mode: minimal (tools/tiers inherit from global config)");
-        eprintln!("  Use 'csa init --full' to auto-detect tools and generate tiers.");
-    }
-
-    // Generate global config if it doesn't exist
-    if let Ok(global_path) = GlobalConfig::config_path()
-        && !global_path.exists()
-    {
-        match GlobalConfig::save_default_template() {
-            Ok(path) => {
-                eprintln!("Generated global config template at: {}", path.display());
-                eprintln!("  Edit to configure API keys and concurrency limits.");
-            }
-            Err(e) => {
-                warn!("Failed to generate global config: {}", e);
-            }
-        }
-    }
-
-    Ok(())
-}
-
 /// Generate a fully-commented TOML template at `.csa/config.toml`.
 ///
 /// All sections are present but commented out, so the config file exists
@@ -0,0 +1,156 @@
+//! `csa init` wizard: shows what was probed and proposed, then asks before
+//! writing when run interactively.
+
+use std::io::{BufRead, IsTerminal, Write};
+
+use anyhow::Result;
+use tracing::warn;
+
+use csa_config::init::{InitPlan, StackTemplate, plan_init, write_init_plan};
+use csa_config::{GlobalConfig, ProjectConfig};
+
+pub(crate) fn handle_init(
+    non_interactive: bool,
+    full: bool,
+    template: bool,
+    from: Option<String>,
+) -> Result<()> {
+    let project_root = crate::pipeline::determine_project_root(None)?;
+
+    if template {
+        return super::handle_init_template(&project_root);
+    }
+
+    let stack = from
+        .as_deref()
+        .map(str::parse::<StackTemplate>)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+    // Default (no flags) = minimal; --full or --from = tool detection and tiers.
+    let minimal = !full && stack.is_none();
+    let plan = plan_init(&project_root, minimal, stack)?;
+    if !minimal {
+        for line in render_init_plan(&plan) {
+            eprintln!("{line}");
+        }
+        let interactive =
+            !non_interactive && std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+        if interactive && !confirm("Write this configuration? [Y/n] ")? {
+            eprintln!("Aborted; no configuration written.");
+            return Ok(());
+        }
+    }
+    write_init_plan(&project_root, &plan)?;
+
+    eprintln!(
+        "Initialized project configuration at: {}",
+        ProjectConfig::config_path(&project_root).display()
+    );
+    eprintln!("Project: {}", plan.config.project.name);
+    if minimal {
+        eprintln!("  Mode: minimal (tools/tiers inherit from global config)");
+        eprintln!("  Use 'csa init --full' to auto-detect tools and generate tiers.");
+    }
+
+    // Generate global config if it doesn't exist
+    if let Ok(global_path) = GlobalConfig::config_path()
+        && !global_path.exists()
+    {
+        match GlobalConfig::save_default_template() {
+            Ok(path) => {
+                eprintln!("Generated global config template at: {}", path.display());
+                eprintln!("  Edit to configure API keys and concurrency limits.");
+            }
+            Err(e) => {
+                warn!("Failed to generate global config: {}", e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Probe results and the proposed tiers and resource limits.
+fn render_init_plan(plan: &InitPlan) -> Vec<String> {
+    let mut lines = vec!["=== Detected tools ===".to_string()];
+    for probe in &plan.tools {
+        let enabled = plan
+            .config
+            .tools
+            .get(probe.tool)
+            .is_some_and(|tool| tool.enabled);
+        let status = match (&probe.path, enabled) {
+            (None, _) => "not installed".to_string(),
+            (Some(_), false) => "installed, disabled in global config".to_string(),
+            (Some(path), true) => format!(
+                "{} ({})",
+                probe.version.as_deref().unwrap_or("version unknown"),
+                path.display()
+            ),
+        };
+        lines.push(format!("  {:<12} {status}", probe.tool));
+        if !probe.models.is_empty() {
+            lines.push(format!(
+                "  {:<12} {} models available",
+                "",
+                probe.models.len()
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(match plan.host_memory_mb {
+        Some(mb) => format!("Host memory: {mb} MB"),
+        None => "Host memory: unknown (using default limits)".to_string(),
+    });
+    if let Some(template) = plan.template {
+        lines.push(format!("Template: {template}"));
+    }
+
+    lines.push(String::new());
+    lines.push("=== Proposed tiers ===".to_string());
+    let mut tiers: Vec<_> = plan.config.tiers.iter().collect();
+    tiers.sort_by_key(|(name, _)| name.as_str());
+    for (name, tier) in tiers {
+        lines.push(format!("  {name:<16} {}", tier.models.join(", ")));
+    }
+
+    let resources = &plan.config.resources;
+    lines.push(String::new());
+    lines.push("=== Proposed resources ===".to_string());
+    lines.push(format!(
+        "  min_free_memory_mb = {}",
+        resources.min_free_memory_mb
+    ));
+    lines.push(format!(
+        "  idle_timeout_seconds = {}",
+        resources.idle_timeout_seconds
+    ));
+    for (key, value) in [
+        ("memory_max_mb", resources.memory_max_mb),
+        ("node_heap_limit_mb", resources.node_heap_limit_mb),
+        ("disk_max_mb", resources.disk_max_mb),
+    ] {
+        if let Some(value) = value {
+            lines.push(format!("  {key} = {value}"));
+        }
+    }
+    lines.push(String::new());
+    lines
+}
+
+/// Ask a yes/no question on stderr; an empty answer means yes.
+fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "" | "y" | "yes"
+    ))
+}
+
+#[cfg(test)]
+#[path = "config_cmds_init_tests.rs"]
+mod tests;
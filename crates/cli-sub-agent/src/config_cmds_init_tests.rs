@@ -0,0 +1,39 @@
+use super::*;
+use csa_config::init::ToolProbe;
+
+#[test]
+fn render_init_plan_shows_probes_tiers_and_resources() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut plan = plan_init(dir.path(), false, Some(StackTemplate::Rust)).unwrap();
+    plan.host_memory_mb = Some(65536);
+    plan.tools = vec![
+        ToolProbe {
+            tool: "codex",
+            binary: "codex",
+            path: Some("/usr/bin/codex".into()),
+            version: Some("codex-cli 0.45.0".to_string()),
+            models: Vec::new(),
+        },
+        ToolProbe {
+            tool: "claude-code",
+            binary: "claude",
+            path: None,
+            version: None,
+            models: Vec::new(),
+        },
+    ];
+    plan.config.tools.get_mut("codex").unwrap().enabled = true;
+
+    let lines = render_init_plan(&plan);
+    assert!(lines.contains(&"  codex        codex-cli 0.45.0 (/usr/bin/codex)".to_string()));
+    assert!(lines.contains(&"  claude-code  not installed".to_string()));
+    assert!(lines.contains(&"Host memory: 65536 MB".to_string()));
+    assert!(lines.contains(&"Template: rust".to_string()));
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("  tier-3-complex "))
+    );
+    assert!(lines.contains(&"  idle_timeout_seconds = 600".to_string()));
+    assert!(lines.contains(&"  disk_max_mb = 20480".to_string()));
+}
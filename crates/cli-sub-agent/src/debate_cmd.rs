@@ -9,6 +9,7 @@ use crate::debate_cmd_resolve::{
     validate_debate_direct_tool_tier_restriction,
 };
 use crate::startup_env::StartupSubtreeEnv;
+use csa_core::error::AppError;
 use csa_core::types::{OutputFormat, ToolArg, ToolName};
 
 #[cfg(test)]
@@ -84,7 +85,7 @@ pub(crate) async fn handle_debate(
     let Some((config, global_config, model_catalog, _project_completion_policy)) =
         crate::pipeline::load_and_validate(&project_root, current_depth)?
     else {
-        return Ok(1);
+        return Ok(csa_core::exit_code::ExitCode::ConfigError.code());
     };
     // #1741: honor a pinned SA subtree's inherited model spec for `csa debate`
     // (see debate_cmd_subtree_pin::apply_subtree_pin).
@@ -327,7 +328,7 @@ fn resolve_debate_cli_tool(
         Ok(ToolArg::Alias(alias)) => {
             anyhow::bail!("BUG: unresolved debate --tool alias '{alias}' after alias resolution")
         }
-        Err(err) => anyhow::bail!("{err}"),
+        Err(err) => Err(AppError::config(err).into()),
     }
 }
 
@@ -2,7 +2,7 @@
 
 use anyhow::Result;
 use csa_config::{ProjectConfig, paths};
-use csa_core::types::{OutputFormat, PRIMARY_TOOL_NAMES};
+use csa_core::types::OutputFormat;
 use csa_resource::filesystem_sandbox::detect_filesystem_capability;
 use csa_resource::rlimit::current_rlimit_nproc;
 use csa_resource::sandbox::{ResourceCapability, detect_resource_capability, systemd_version};
@@ -10,8 +10,12 @@ use std::env;
 use std::path::{Path, PathBuf};
 use sysinfo::System;
 
+#[path = "doctor_checks.rs"]
+mod doctor_checks;
 #[path = "doctor_config.rs"]
 mod doctor_config;
+#[path = "doctor_fix.rs"]
+mod doctor_fix;
 #[path = "doctor_output_helpers.rs"]
 mod doctor_output_helpers;
 #[path = "doctor_resource.rs"]
@@ -23,6 +27,7 @@ mod doctor_sandbox;
 #[path = "doctor_tools.rs"]
 mod doctor_tools;
 use crate::install_provenance;
+use doctor_checks::{CheckInputs, DoctorCheck};
 use doctor_config::{
     inspect_doctor_effective_config_from, inspect_doctor_project_config_from,
     project_config_tool_lists, render_effective_config_lines, render_project_config_lines,
@@ -35,7 +40,9 @@ use doctor_sandbox::{
     build_filesystem_sandbox_json, print_filesystem_sandbox_status, print_git_hook_status,
     print_merge_guard_status, print_sandbox_status,
 };
-use doctor_tools::{check_tool_status, print_tool_availability, tool_status_json};
+use doctor_tools::{
+    check_tool_status, doctor_tool_names, print_tool_availability, tool_status_json,
+};
 
 #[cfg(test)]
 use doctor_config::load_doctor_project_config_from;
@@ -164,11 +171,17 @@ pub async fn run_doctor(format: OutputFormat) -> Result<()> {
 }
 
 /// Dispatch `csa doctor` / `csa doctor <subcommand>`.
+///
+/// `--fix` and `--dry-run` replace the diagnostic report with the
+/// remediation action log.
 pub async fn dispatch_doctor(
     format: OutputFormat,
     subcommand: Option<crate::cli::DoctorSubcommand>,
+    fix: bool,
+    dry_run: bool,
 ) -> Result<()> {
     match subcommand {
+        None if fix || dry_run => doctor_fix::run_doctor_fix(format, dry_run),
         None => run_doctor(format).await,
         Some(crate::cli::DoctorSubcommand::Install { target, artifact }) => {
             run_doctor_install(format, &target, artifact.as_deref())
@@ -221,13 +234,16 @@ async fn run_doctor_text_from(project_root: &Path) -> Result<()> {
     println!();
 
     println!("=== Tool Availability ===");
-    match effective_config_status.runtime_config() {
+    let tools = match effective_config_status.runtime_config() {
         Some(config) => print_tool_availability(Some(config)).await,
         None => match effective_config_status.tool_availability_error() {
-            Some(error) => print_tool_availability_error(&error),
+            Some(error) => {
+                print_tool_availability_error(&error);
+                Vec::new()
+            }
             None => print_tool_availability(None).await,
         },
-    }
+    };
     println!();
 
     println!("=== Project Config ===");
@@ -265,21 +281,49 @@ async fn run_doctor_text_from(project_root: &Path) -> Result<()> {
 
     println!("=== Install Provenance ===");
     print_install_provenance_status();
+    println!();
 
-    Ok(())
+    let checks = collect_checks(&project_config_status, &effective_config_status, &tools);
+    println!("=== Summary ===");
+    for line in doctor_checks::render_summary_lines(&checks) {
+        println!("{line}");
+    }
+
+    doctor_checks::ensure_no_failures(&checks)
 }
 
 /// Run diagnostics with JSON output.
 async fn run_doctor_json() -> Result<()> {
     let cwd = env::current_dir()?;
-    let result = build_doctor_json(&cwd);
+    let (result, checks) = build_doctor_json(&cwd);
 
     println!("{}", serde_json::to_string_pretty(&result)?);
 
-    Ok(())
+    doctor_checks::ensure_no_failures(&checks)
+}
+
+/// Classify the gathered diagnostics into the per-check report.
+fn collect_checks(
+    project_config: &DoctorProjectConfigStatus,
+    effective_config: &DoctorEffectiveConfigStatus,
+    tools: &[ToolStatus],
+) -> Vec<DoctorCheck> {
+    let state_dir = paths::state_dir();
+    let merge_guard = csa_hooks::detect_installed_guard();
+    doctor_checks::classify(&CheckInputs {
+        state_dir: state_dir.as_deref(),
+        project_config,
+        effective_config,
+        tools,
+        resource_sandbox: detect_resource_capability(),
+        filesystem_sandbox: detect_filesystem_capability(),
+        merge_guard: merge_guard.as_deref(),
+        install_current: install_provenance_snapshot().is_current(),
+    })
 }
 
-fn build_doctor_json(project_root: &Path) -> serde_json::Value {
+/// The additive JSON report plus the checks that decide the exit code.
+fn build_doctor_json(project_root: &Path) -> (serde_json::Value, Vec<DoctorCheck>) {
     let os = env::consts::OS;
     let arch = env::consts::ARCH;
     let version = env!("CARGO_PKG_VERSION");
@@ -290,30 +334,24 @@ fn build_doctor_json(project_root: &Path) -> serde_json::Value {
         .map(|d| d.display().to_string())
         .unwrap_or_default();
 
-    let tool_statuses: Vec<serde_json::Value> = match effective_config_status.runtime_config() {
-        Some(config) => PRIMARY_TOOL_NAMES
-            .iter()
-            .copied()
-            .map(|tool_name| {
-                let status = check_tool_status(tool_name, Some(config));
-                tool_status_json(&status)
-            })
+    let tools: Vec<ToolStatus> = match effective_config_status.runtime_config() {
+        Some(config) => doctor_tool_names()
+            .into_iter()
+            .map(|tool_name| check_tool_status(tool_name, Some(config)))
             .collect(),
         None => {
             if effective_config_status.tool_availability_error().is_some() {
                 Vec::new()
             } else {
-                PRIMARY_TOOL_NAMES
-                    .iter()
-                    .copied()
-                    .map(|tool_name| {
-                        let status = check_tool_status(tool_name, None);
-                        tool_status_json(&status)
-                    })
+                doctor_tool_names()
+                    .into_iter()
+                    .map(|tool_name| check_tool_status(tool_name, None))
                     .collect()
             }
         }
     };
+    let tool_statuses: Vec<serde_json::Value> = tools.iter().map(tool_status_json).collect();
+    let checks = collect_checks(&project_config_status, &effective_config_status, &tools);
 
     // Resource status
     let mut sys = System::new();
@@ -329,6 +367,11 @@ fn build_doctor_json(project_root: &Path) -> serde_json::Value {
             "systemd_version": systemd_version(),
             "user_scope": true,
         }),
+        ResourceCapability::CgroupDirect => serde_json::json!({
+            "capability": "CgroupDirect",
+            "delegated_root": csa_resource::cgroup_direct::delegated_root()
+                .map(|root| root.display().to_string()),
+        }),
         ResourceCapability::Setrlimit => serde_json::json!({
             "capability": "Setrlimit",
             "enforces": "pids_only",
@@ -357,6 +400,8 @@ fn build_doctor_json(project_root: &Path) -> serde_json::Value {
     let install_status = install_provenance_json();
 
     let result = serde_json::json!({
+        "status": doctor_checks::overall_status(&checks),
+        "checks": checks,
         "platform": {
             "os": os,
             "arch": arch,
@@ -378,7 +423,7 @@ fn build_doctor_json(project_root: &Path) -> serde_json::Value {
         "install": install_status,
     });
 
-    result
+    (result, checks)
 }
 
 /// Print platform information.
@@ -470,6 +515,10 @@ enum InstallDoctorSnapshot {
 }
 
 impl InstallDoctorSnapshot {
+    fn is_current(&self) -> bool {
+        matches!(self, Self::Report(report) if report.is_current())
+    }
+
     fn render_text(&self) -> String {
         match self {
             Self::Report(report) => report.diagnostic(),
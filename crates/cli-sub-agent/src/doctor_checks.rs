@@ -0,0 +1,237 @@
+//! Per-check classification for `csa doctor`.
+//!
+//! Every diagnostic area maps to one or more checks with a status of `ok`,
+//! `warn` or `fail`. Only `fail` marks a hard error (CSA cannot run a tool in
+//! this environment) and makes doctor exit non-zero; `warn` covers degraded
+//! but usable setups such as a missing sandbox or an optional tool.
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use csa_resource::filesystem_sandbox::FilesystemCapability;
+use csa_resource::sandbox::ResourceCapability;
+use serde::Serialize;
+
+use super::{DoctorEffectiveConfigStatus, DoctorProjectConfigStatus, ToolStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct DoctorCheck {
+    pub(super) id: String,
+    pub(super) status: CheckStatus,
+    pub(super) details: String,
+    /// Suggested remediation; `null` when the check passes.
+    pub(super) fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(id: impl Into<String>, details: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: CheckStatus::Ok,
+            details: details.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        id: impl Into<String>,
+        status: CheckStatus,
+        details: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            status,
+            details: details.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Already-gathered diagnostics the checks are derived from.
+pub(super) struct CheckInputs<'a> {
+    pub(super) state_dir: Option<&'a Path>,
+    pub(super) project_config: &'a DoctorProjectConfigStatus,
+    pub(super) effective_config: &'a DoctorEffectiveConfigStatus,
+    pub(super) tools: &'a [ToolStatus],
+    pub(super) resource_sandbox: ResourceCapability,
+    pub(super) filesystem_sandbox: FilesystemCapability,
+    pub(super) merge_guard: Option<&'a Path>,
+    pub(super) install_current: bool,
+}
+
+pub(super) fn classify(inputs: &CheckInputs<'_>) -> Vec<DoctorCheck> {
+    let mut checks = vec![
+        match inputs.state_dir {
+            Some(dir) => DoctorCheck::ok("state-dir", dir.display().to_string()),
+            None => DoctorCheck::problem(
+                "state-dir",
+                CheckStatus::Fail,
+                "cannot determine the CSA state directory",
+                "set HOME or XDG_STATE_HOME",
+            ),
+        },
+        match inputs.project_config {
+            DoctorProjectConfigStatus::Valid(_) => {
+                DoctorCheck::ok("project-config", ".csa/config.toml is valid")
+            }
+            DoctorProjectConfigStatus::Missing => DoctorCheck::problem(
+                "project-config",
+                CheckStatus::Warn,
+                "no .csa/config.toml; defaults apply",
+                "csa init",
+            ),
+            DoctorProjectConfigStatus::Invalid(error) => DoctorCheck::problem(
+                "project-config",
+                CheckStatus::Fail,
+                format!(".csa/config.toml is invalid: {error}"),
+                "fix the reported error, then run `csa config validate`",
+            ),
+        },
+    ];
+    if let DoctorEffectiveConfigStatus::Invalid(error) = inputs.effective_config {
+        checks.push(DoctorCheck::problem(
+            "effective-config",
+            CheckStatus::Fail,
+            format!("merged global and project config is invalid: {error}"),
+            "fix the reported error, then run `csa config validate`",
+        ));
+    }
+
+    checks.extend(inputs.tools.iter().map(tool_check));
+    // With an invalid effective config no tools were probed; that failure is
+    // already reported above.
+    if !inputs.tools.is_empty() {
+        checks.push(if inputs.tools.iter().any(ToolStatus::is_ready) {
+            let ready = inputs.tools.iter().filter(|tool| tool.is_ready()).count();
+            DoctorCheck::ok(
+                "tools",
+                format!("{ready}/{} tools ready", inputs.tools.len()),
+            )
+        } else {
+            DoctorCheck::problem(
+                "tools",
+                CheckStatus::Fail,
+                "no enabled tool has its runtime installed",
+                "install at least one tool listed above, or enable one in .csa/config.toml",
+            )
+        });
+    }
+
+    checks.push(match inputs.resource_sandbox {
+        ResourceCapability::None => DoctorCheck::problem(
+            "resource-sandbox",
+            CheckStatus::Warn,
+            "no resource isolation; memory and PID limits are not enforced",
+            "enable cgroup v2 delegation (systemd user session) or allow setrlimit",
+        ),
+        capability => DoctorCheck::ok("resource-sandbox", capability.to_string()),
+    });
+    checks.push(match inputs.filesystem_sandbox {
+        FilesystemCapability::None => DoctorCheck::problem(
+            "filesystem-sandbox",
+            CheckStatus::Warn,
+            "no filesystem isolation available",
+            "install bwrap and allow unprivileged user namespaces, or use a Landlock-capable kernel",
+        ),
+        capability => DoctorCheck::ok("filesystem-sandbox", capability.to_string()),
+    });
+    checks.push(match inputs.merge_guard {
+        Some(path) => DoctorCheck::ok("merge-guard", path.display().to_string()),
+        None => DoctorCheck::problem(
+            "merge-guard",
+            CheckStatus::Warn,
+            "merge guard not installed",
+            "csa hooks install-merge-guard",
+        ),
+    });
+    checks.push(if inputs.install_current {
+        DoctorCheck::ok("install", "PATH resolves to the intended install target")
+    } else {
+        DoctorCheck::problem(
+            "install",
+            CheckStatus::Warn,
+            "the csa on PATH is not the current intended install",
+            "run `just install`, or put the intended target first on PATH",
+        )
+    });
+    checks
+}
+
+fn tool_check(tool: &ToolStatus) -> DoctorCheck {
+    let id = format!("tool.{}", tool.name);
+    if !tool.config_enabled {
+        return DoctorCheck::ok(id, "disabled by config");
+    }
+    if tool.binary_available() {
+        let version = tool.version.as_deref().unwrap_or("version unknown");
+        return DoctorCheck::ok(id, format!("{} ({version})", tool.binary_name));
+    }
+    DoctorCheck::problem(
+        id,
+        CheckStatus::Warn,
+        format!("{} not found", tool.binary_name),
+        tool.hint
+            .clone()
+            .unwrap_or_else(|| format!("install {}", tool.binary_name)),
+    )
+}
+
+/// Worst status across `checks`; `ok` when there are none.
+pub(super) fn overall_status(checks: &[DoctorCheck]) -> CheckStatus {
+    checks
+        .iter()
+        .map(|check| check.status)
+        .max()
+        .unwrap_or(CheckStatus::Ok)
+}
+
+/// Text summary of the checks that did not pass.
+pub(super) fn render_summary_lines(checks: &[DoctorCheck]) -> Vec<String> {
+    let count = |status| checks.iter().filter(|check| check.status == status).count();
+    let mut lines = vec![format!(
+        "{} ok, {} warn, {} fail",
+        count(CheckStatus::Ok),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail)
+    )];
+    for check in checks
+        .iter()
+        .filter(|check| check.status != CheckStatus::Ok)
+    {
+        let label = match check.status {
+            CheckStatus::Fail => "FAIL",
+            _ => "WARN",
+        };
+        lines.push(format!("[{label}] {}: {}", check.id, check.details));
+        if let Some(fix) = &check.fix {
+            lines.push(format!("       Fix: {fix}"));
+        }
+    }
+    lines
+}
+
+/// Exit policy: only hard failures make `csa doctor` exit non-zero.
+pub(super) fn ensure_no_failures(checks: &[DoctorCheck]) -> Result<()> {
+    let failed: Vec<&str> = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .map(|check| check.id.as_str())
+        .collect();
+    if !failed.is_empty() {
+        bail!("doctor found failing checks: {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "doctor_checks_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use super::*;
+use crate::doctor::ToolAvailabilityState;
+
+fn tool(name: &'static str, config_enabled: bool, installed: bool) -> ToolStatus {
+    ToolStatus {
+        name,
+        config_enabled,
+        availability: if installed {
+            ToolAvailabilityState::Installed
+        } else {
+            ToolAvailabilityState::Missing
+        },
+        binary_name: name.to_string(),
+        version: installed.then(|| "1.0.0".to_string()),
+        hint: (!installed).then(|| format!("npm install -g {name}")),
+        transport: None,
+    }
+}
+
+fn healthy_inputs<'a>(
+    project_config: &'a DoctorProjectConfigStatus,
+    effective_config: &'a DoctorEffectiveConfigStatus,
+    tools: &'a [ToolStatus],
+) -> CheckInputs<'a> {
+    CheckInputs {
+        state_dir: Some(Path::new("/state/csa")),
+        project_config,
+        effective_config,
+        tools,
+        resource_sandbox: ResourceCapability::CgroupV2,
+        filesystem_sandbox: FilesystemCapability::Bwrap,
+        merge_guard: Some(Path::new("/hooks/merge-guard")),
+        install_current: true,
+    }
+}
+
+fn find<'a>(checks: &'a [DoctorCheck], id: &str) -> &'a DoctorCheck {
+    checks
+        .iter()
+        .find(|check| check.id == id)
+        .unwrap_or_else(|| panic!("missing check {id}: {checks:?}"))
+}
+
+#[test]
+fn missing_optional_tool_and_sandbox_only_warn() {
+    let project = DoctorProjectConfigStatus::Missing;
+    let effective = DoctorEffectiveConfigStatus::Defaults;
+    let tools = [tool("codex", true, true), tool("gemini-cli", true, false)];
+    let mut inputs = healthy_inputs(&project, &effective, &tools);
+    inputs.filesystem_sandbox = FilesystemCapability::None;
+    let checks = classify(&inputs);
+
+    assert_eq!(find(&checks, "tool.codex").status, CheckStatus::Ok);
+    let missing = find(&checks, "tool.gemini-cli");
+    assert_eq!(missing.status, CheckStatus::Warn);
+    assert_eq!(missing.fix.as_deref(), Some("npm install -g gemini-cli"));
+    assert_eq!(
+        find(&checks, "project-config").fix.as_deref(),
+        Some("csa init")
+    );
+    assert_eq!(
+        find(&checks, "filesystem-sandbox").status,
+        CheckStatus::Warn
+    );
+    assert_eq!(find(&checks, "tools").details, "1/2 tools ready");
+
+    assert_eq!(overall_status(&checks), CheckStatus::Warn);
+    assert!(ensure_no_failures(&checks).is_ok());
+}
+
+#[test]
+fn no_ready_tool_is_a_hard_failure() {
+    let project = DoctorProjectConfigStatus::Missing;
+    let effective = DoctorEffectiveConfigStatus::Defaults;
+    let tools = [tool("codex", false, true), tool("gemini-cli", true, false)];
+    let checks = classify(&healthy_inputs(&project, &effective, &tools));
+
+    assert_eq!(find(&checks, "tool.codex").details, "disabled by config");
+    assert_eq!(find(&checks, "tools").status, CheckStatus::Fail);
+    assert_eq!(overall_status(&checks), CheckStatus::Fail);
+    let error = ensure_no_failures(&checks).unwrap_err().to_string();
+    assert_eq!(error, "doctor found failing checks: tools");
+}
+
+#[test]
+fn invalid_config_fails_without_a_tools_aggregate() {
+    let project = DoctorProjectConfigStatus::Invalid("bad key".to_string());
+    let effective = DoctorEffectiveConfigStatus::Invalid("bad key".to_string());
+    let checks = classify(&healthy_inputs(&project, &effective, &[]));
+
+    assert_eq!(find(&checks, "project-config").status, CheckStatus::Fail);
+    assert_eq!(find(&checks, "effective-config").status, CheckStatus::Fail);
+    assert!(checks.iter().all(|check| check.id != "tools"));
+}
+
+#[test]
+fn checks_serialize_with_lowercase_status_and_null_fix() {
+    let check = DoctorCheck::ok("install", "current");
+    assert_eq!(
+        serde_json::to_value(&check).unwrap(),
+        serde_json::json!({
+            "id": "install",
+            "status": "ok",
+            "details": "current",
+            "fix": null,
+        })
+    );
+}
+
+#[test]
+fn summary_lists_non_ok_checks_with_fixes() {
+    let checks = [
+        DoctorCheck::ok("state-dir", "/state"),
+        DoctorCheck::problem(
+            "merge-guard",
+            CheckStatus::Warn,
+            "not installed",
+            "csa hooks install-merge-guard",
+        ),
+    ];
+    assert_eq!(
+        render_summary_lines(&checks),
+        [
+            "1 ok, 1 warn, 0 fail",
+            "[WARN] merge-guard: not installed",
+            "       Fix: csa hooks install-merge-guard",
+        ]
+    );
+}
@@ -0,0 +1,315 @@
+//! `csa doctor --fix` / `--dry-run`: remediation for problems doctor can
+//! repair on its own.
+//!
+//! Each check inspects one area and offers zero or more fixes. `--dry-run`
+//! lists them; `--fix` applies them in check order and logs every action.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use csa_config::{GlobalConfig, paths};
+use csa_core::types::OutputFormat;
+use serde::Serialize;
+use weave::check::DEFAULT_CHECK_DIRS;
+use weave::link::LinkScope;
+use weave::package::AuditIssue;
+
+use crate::cli::CompletionShell;
+use crate::complete_cmd::completion_script;
+use crate::gc::{extract_pid_from_lock, extract_slot_lock_info, is_process_alive};
+
+/// One fix offered by a check.
+struct Remediation {
+    action: String,
+    apply: Box<dyn FnOnce() -> Result<()>>,
+}
+
+impl Remediation {
+    fn new(action: impl Into<String>, apply: impl FnOnce() -> Result<()> + 'static) -> Self {
+        Self {
+            action: action.into(),
+            apply: Box::new(apply),
+        }
+    }
+}
+
+type Check = fn(&Path) -> Result<Vec<Remediation>>;
+
+/// Remediable checks, in the order their fixes are applied. Directories come
+/// first so later fixes can write into them.
+const CHECKS: &[(&str, Check)] = &[
+    ("state-dirs", missing_state_dirs),
+    ("stale-locks", stale_locks),
+    ("config-migrations", pending_migrations),
+    ("skill-links", broken_skill_links),
+    ("completions", outdated_completions),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ActionStatus {
+    Planned,
+    Applied,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+struct ActionRecord {
+    check: &'static str,
+    action: String,
+    status: ActionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+pub(super) fn run_doctor_fix(format: OutputFormat, dry_run: bool) -> Result<()> {
+    let project_root = std::env::current_dir().context("Failed to read current directory")?;
+    let log = remediate(&project_root, CHECKS, dry_run);
+    match format {
+        OutputFormat::Text => print!("{}", render_log(&log, dry_run)),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "dry_run": dry_run,
+                "actions": log,
+            }))?
+        ),
+    }
+    let failed = count(&log, ActionStatus::Failed);
+    if failed > 0 {
+        bail!("{failed} doctor fix(es) failed");
+    }
+    Ok(())
+}
+
+/// Run every check and, unless `dry_run`, apply what it offers. A check that
+/// cannot inspect its area is logged as a failed action; the rest still run.
+fn remediate(
+    project_root: &Path,
+    checks: &[(&'static str, Check)],
+    dry_run: bool,
+) -> Vec<ActionRecord> {
+    let mut log = Vec::new();
+    for &(check, inspect) in checks {
+        let remediations = match inspect(project_root) {
+            Ok(remediations) => remediations,
+            Err(err) => {
+                log.push(ActionRecord {
+                    check,
+                    action: "inspect".to_string(),
+                    status: ActionStatus::Failed,
+                    error: Some(format!("{err:#}")),
+                });
+                continue;
+            }
+        };
+        for remediation in remediations {
+            let (status, error) = if dry_run {
+                (ActionStatus::Planned, None)
+            } else {
+                match (remediation.apply)() {
+                    Ok(()) => (ActionStatus::Applied, None),
+                    Err(err) => (ActionStatus::Failed, Some(format!("{err:#}"))),
+                }
+            };
+            log.push(ActionRecord {
+                check,
+                action: remediation.action,
+                status,
+                error,
+            });
+        }
+    }
+    log
+}
+
+fn count(log: &[ActionRecord], status: ActionStatus) -> usize {
+    log.iter().filter(|record| record.status == status).count()
+}
+
+fn render_log(log: &[ActionRecord], dry_run: bool) -> String {
+    let mut out = String::from("=== Remediation ===\n");
+    if log.is_empty() {
+        out.push_str("Nothing to fix.\n");
+        return out;
+    }
+    for record in log {
+        let status = match record.status {
+            ActionStatus::Planned => "plan",
+            ActionStatus::Applied => "done",
+            ActionStatus::Failed => "FAIL",
+        };
+        let _ = writeln!(out, "[{status}] {}: {}", record.check, record.action);
+        if let Some(error) = &record.error {
+            let _ = writeln!(out, "       {error}");
+        }
+    }
+    let _ = if dry_run {
+        writeln!(
+            out,
+            "\n{} fix(es) planned; run `csa doctor --fix` to apply.",
+            count(log, ActionStatus::Planned)
+        )
+    } else {
+        writeln!(
+            out,
+            "\nApplied {} fix(es), {} failed.",
+            count(log, ActionStatus::Applied),
+            count(log, ActionStatus::Failed)
+        )
+    };
+    out
+}
+
+fn missing_state_dirs(_project_root: &Path) -> Result<Vec<Remediation>> {
+    let state_dir = paths::state_dir_write().context("Cannot determine the CSA state directory")?;
+    let dirs = [state_dir, GlobalConfig::slots_dir()?];
+    Ok(dirs
+        .into_iter()
+        .filter(|dir| !dir.is_dir())
+        .map(|dir| {
+            Remediation::new(format!("create missing {}", dir.display()), move || {
+                fs::create_dir_all(&dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))
+            })
+        })
+        .collect())
+}
+
+/// Session and tool-slot lock files whose recorded holder PID is gone.
+fn stale_locks(project_root: &Path) -> Result<Vec<Remediation>> {
+    let mut locks = Vec::new();
+    for session in csa_session::list_sessions_readonly(project_root, None).unwrap_or_default() {
+        if let Ok(session_dir) =
+            csa_session::get_session_dir(project_root, &session.meta_session_id)
+        {
+            locks.extend(dead_pid_locks(
+                &session_dir.join("locks"),
+                extract_pid_from_lock,
+            ));
+        }
+    }
+    let slots_dir = GlobalConfig::slots_dir()?;
+    for tool_dir in fs::read_dir(&slots_dir).into_iter().flatten().flatten() {
+        locks.extend(dead_pid_locks(&tool_dir.path(), |content| {
+            extract_slot_lock_info(content).map(|(pid, _, _)| pid)
+        }));
+    }
+    Ok(locks
+        .into_iter()
+        .map(|(path, pid)| {
+            let action = format!("remove lock held by dead PID {pid}: {}", path.display());
+            Remediation::new(action, move || {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))
+            })
+        })
+        .collect())
+}
+
+/// `*.lock` files in `dir` whose holder PID is no longer alive.
+fn dead_pid_locks(dir: &Path, holder_pid: impl Fn(&str) -> Option<u32>) -> Vec<(PathBuf, u32)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lock"))
+        .filter_map(|path| {
+            let pid = holder_pid(&fs::read_to_string(&path).ok()?)?;
+            (!is_process_alive(pid)).then_some((path, pid))
+        })
+        .collect()
+}
+
+fn pending_migrations(project_root: &Path) -> Result<Vec<Remediation>> {
+    let pending = crate::migrate_cmd::pending_migration_ids(project_root)?;
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+    let project_root = project_root.to_path_buf();
+    Ok(vec![Remediation::new(
+        format!("apply pending config migrations: {}", pending.join(", ")),
+        move || crate::migrate_cmd::apply_pending_migrations(&project_root),
+    )])
+}
+
+/// Broken skill symlinks are removed one by one, then the project's skills
+/// are re-linked so the removed entries come back pointing at live targets.
+fn broken_skill_links(project_root: &Path) -> Result<Vec<Remediation>> {
+    let dirs: Vec<PathBuf> = DEFAULT_CHECK_DIRS.iter().map(PathBuf::from).collect();
+    let mut remediations: Vec<Remediation> =
+        weave::check::check_symlinks(project_root, &dirs, false)?
+            .into_iter()
+            .flat_map(|result| result.issues)
+            .filter_map(|issue| match issue {
+                AuditIssue::BrokenSymlink { path, target } => Some(Remediation::new(
+                    format!(
+                        "remove broken skill link {} -> {}",
+                        path.display(),
+                        target.display()
+                    ),
+                    move || {
+                        fs::remove_file(&path)
+                            .with_context(|| format!("Failed to remove {}", path.display()))
+                    },
+                )),
+                _ => None,
+            })
+            .collect();
+    if !remediations.is_empty() {
+        let project_root = project_root.to_path_buf();
+        remediations.push(Remediation::new("re-link project skills", move || {
+            let report = weave::link::link_skills(&project_root, LinkScope::Project, false)?;
+            if report.has_errors() {
+                bail!("weave reported link errors; run `weave link sync` for details");
+            }
+            Ok(())
+        }));
+    }
+    Ok(remediations)
+}
+
+/// Installed completion scripts that no longer match this binary's output.
+/// Shells without an installed script are left alone.
+fn outdated_completions(_project_root: &Path) -> Result<Vec<Remediation>> {
+    let Some(base) = directories::BaseDirs::new() else {
+        return Ok(Vec::new());
+    };
+    let installed = [
+        (
+            CompletionShell::Bash,
+            base.data_dir().join("bash-completion/completions/csa"),
+        ),
+        (
+            CompletionShell::Zsh,
+            base.data_dir().join("zsh/site-functions/_csa"),
+        ),
+        (
+            CompletionShell::Fish,
+            base.config_dir().join("fish/completions/csa.fish"),
+        ),
+    ];
+    Ok(installed
+        .into_iter()
+        .filter_map(|(shell, path)| {
+            let script = completion_script(shell);
+            (fs::read_to_string(&path).ok()? != script).then(|| {
+                Remediation::new(
+                    format!("regenerate completion script {}", path.display()),
+                    move || {
+                        fs::write(&path, script)
+                            .with_context(|| format!("Failed to write {}", path.display()))
+                    },
+                )
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[path = "doctor_fix_tests.rs"]
+mod tests;
@@ -0,0 +1,86 @@
+use super::*;
+
+fn marker_path(project_root: &Path) -> PathBuf {
+    project_root.join("fixed")
+}
+
+fn offers_marker(project_root: &Path) -> Result<Vec<Remediation>> {
+    let marker = marker_path(project_root);
+    Ok(vec![Remediation::new("write marker", move || {
+        fs::write(&marker, "ok").context("write marker")
+    })])
+}
+
+fn offers_failing_fix(_project_root: &Path) -> Result<Vec<Remediation>> {
+    Ok(vec![Remediation::new("always fails", || bail!("boom"))])
+}
+
+fn cannot_inspect(_project_root: &Path) -> Result<Vec<Remediation>> {
+    bail!("state unreadable")
+}
+
+const TEST_CHECKS: &[(&str, Check)] = &[
+    ("marker", offers_marker),
+    ("failing", offers_failing_fix),
+    ("broken", cannot_inspect),
+];
+
+#[test]
+fn dry_run_plans_without_applying() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = remediate(dir.path(), TEST_CHECKS, true);
+
+    assert!(!marker_path(dir.path()).exists());
+    let statuses: Vec<_> = log.iter().map(|record| record.status).collect();
+    assert_eq!(
+        statuses,
+        [
+            ActionStatus::Planned,
+            ActionStatus::Planned,
+            ActionStatus::Failed
+        ]
+    );
+    assert_eq!(log[2].action, "inspect");
+    assert_eq!(log[2].error.as_deref(), Some("state unreadable"));
+
+    let rendered = render_log(&log, true);
+    assert!(rendered.contains("[plan] marker: write marker"));
+    assert!(rendered.contains("2 fix(es) planned"));
+}
+
+#[test]
+fn fix_applies_and_records_failures() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = remediate(dir.path(), TEST_CHECKS, false);
+
+    assert_eq!(fs::read_to_string(marker_path(dir.path())).unwrap(), "ok");
+    assert_eq!(log[0].status, ActionStatus::Applied);
+    assert_eq!(log[1].status, ActionStatus::Failed);
+    assert_eq!(log[1].error.as_deref(), Some("boom"));
+
+    let rendered = render_log(&log, false);
+    assert!(rendered.contains("[done] marker: write marker"));
+    assert!(rendered.contains("[FAIL] failing: always fails\n       boom"));
+    assert!(rendered.ends_with("Applied 1 fix(es), 2 failed.\n"));
+}
+
+#[test]
+fn empty_log_reports_nothing_to_fix() {
+    assert_eq!(
+        render_log(&[], false),
+        "=== Remediation ===\nNothing to fix.\n"
+    );
+}
+
+#[test]
+fn dead_pid_locks_skips_live_holders_and_other_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let live = format!("{{\"pid\": {}}}", std::process::id());
+    fs::write(dir.path().join("live.lock"), live).unwrap();
+    fs::write(dir.path().join("dead.lock"), "{\"pid\": 99999999}").unwrap();
+    fs::write(dir.path().join("dead.json"), "{\"pid\": 99999999}").unwrap();
+    fs::write(dir.path().join("garbage.lock"), "not json").unwrap();
+
+    let stale = dead_pid_locks(dir.path(), extract_pid_from_lock);
+    assert_eq!(stale, vec![(dir.path().join("dead.lock"), 99999999)]);
+}
@@ -15,6 +15,11 @@ pub(super) fn print_sandbox_status() {
             }
             println!("User scope:  supported");
         }
+        ResourceCapability::CgroupDirect => {
+            if let Some(root) = csa_resource::cgroup_direct::delegated_root() {
+                println!("Cgroup root: {} (delegated, no systemd)", root.display());
+            }
+        }
         ResourceCapability::Setrlimit => {
             println!("Enforces:    PID limit only (RLIMIT_NPROC)");
             match current_rlimit_nproc() {
@@ -45,6 +50,12 @@ pub(super) fn print_filesystem_sandbox_status() {
             let abi = csa_resource::landlock::detect_abi();
             println!("Landlock ABI: {abi:?}");
         }
+        FilesystemCapability::SandboxExec => {
+            println!(
+                "sandbox-exec: {}",
+                csa_resource::seatbelt::SANDBOX_EXEC_PATH
+            );
+        }
         FilesystemCapability::None => {
             println!("Warning:     No filesystem isolation available.");
             if let Some(ver) = bwrap_version() {
@@ -81,6 +92,12 @@ pub(super) fn build_filesystem_sandbox_json(fs_cap: FilesystemCapability) -> ser
                 "apparmor_userns_restricted": is_apparmor_userns_restricted(),
             })
         }
+        FilesystemCapability::SandboxExec => {
+            serde_json::json!({
+                "capability": "SandboxExec",
+                "sandbox_exec_path": csa_resource::seatbelt::SANDBOX_EXEC_PATH,
+            })
+        }
         FilesystemCapability::None => {
             serde_json::json!({
                 "capability": "None",
@@ -404,7 +404,7 @@ fn explicit_claude_code_cli_transport_reports_json_transport_details() {
 fn doctor_json_includes_install_provenance_surface() {
     let _env_lock = TEST_ENV_LOCK.blocking_lock();
     let td = tempfile::tempdir().expect("tempdir");
-    let report = build_doctor_json(td.path());
+    let (report, _checks) = build_doctor_json(td.path());
     let install = &report["install"];
     assert!(
         install.is_object(),
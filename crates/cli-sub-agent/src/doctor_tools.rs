@@ -4,11 +4,13 @@ use csa_core::types::PRIMARY_TOOL_NAMES;
 use csa_executor::{ClaudeCodeTransport, CodexRuntimeMetadata, CodexTransport};
 use std::process::Command;
 
-pub(super) async fn print_tool_availability(config: Option<&ProjectConfig>) {
-    let tools = PRIMARY_TOOL_NAMES;
+/// Print each tool's status and return the statuses for the check summary.
+pub(super) async fn print_tool_availability(config: Option<&ProjectConfig>) -> Vec<ToolStatus> {
+    let tools = doctor_tool_names();
 
     let mut ready_count = 0;
     let total_count = tools.len();
+    let mut statuses = Vec::with_capacity(total_count);
 
     for tool_name in tools.iter().copied() {
         let status = check_tool_status(tool_name, config);
@@ -16,10 +18,25 @@ pub(super) async fn print_tool_availability(config: Option<&ProjectConfig>) {
             ready_count += 1;
         }
         print_tool_status(&status);
+        statuses.push(status);
     }
 
     println!();
     println!("{ready_count}/{total_count} tools ready");
+    statuses
+}
+
+/// Primary built-in tools followed by tools registered in `[tool_registry]`.
+pub(super) fn doctor_tool_names() -> Vec<&'static str> {
+    PRIMARY_TOOL_NAMES
+        .iter()
+        .copied()
+        .chain(
+            csa_config::tool_registry()
+                .custom()
+                .map(|tool| tool.id.as_str()),
+        )
+        .collect()
 }
 
 pub(super) fn check_tool_status(
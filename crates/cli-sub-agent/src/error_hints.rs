@@ -36,19 +36,17 @@ const SANDBOX_FS_DENIAL_MARKERS: [&str; 7] = [
 ];
 
 pub fn suggest_fix(err: &Error) -> Option<String> {
-    for cause in err.chain() {
-        if let Some(app_err) = cause.downcast_ref::<AppError>() {
-            match app_err {
-                AppError::ToolNotInstalled(tool) => {
-                    if let Some(hint) = tool_install_hint(tool) {
-                        return Some(hint.to_string());
-                    }
+    if let Some(app_err) = crate::process_exit::app_error(err) {
+        match app_err {
+            AppError::ToolNotInstalled(tool) => {
+                if let Some(hint) = tool_install_hint(tool) {
+                    return Some(hint.to_string());
                 }
-                AppError::RateLimited { .. } => return Some(HINT_RATE_LIMIT.to_string()),
-                AppError::SlotExhausted { .. } => return Some(HINT_SLOT_EXHAUSTED.to_string()),
-                AppError::SessionNotFound(_) => return Some(HINT_SESSION_NOT_FOUND.to_string()),
-                _ => {}
             }
+            AppError::RateLimited { .. } => return Some(HINT_RATE_LIMIT.to_string()),
+            AppError::SlotExhausted { .. } => return Some(HINT_SLOT_EXHAUSTED.to_string()),
+            AppError::SessionNotFound(_) => return Some(HINT_SESSION_NOT_FOUND.to_string()),
+            _ => {}
         }
     }
 
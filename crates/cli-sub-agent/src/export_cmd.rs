@@ -0,0 +1,259 @@
+//! `csa export report`: self-contained HTML report for one session.
+//!
+//! The report collects the session summary, review findings, output sections,
+//! token/cost usage and the session's genealogy into a single HTML file with
+//! inline CSS and SVG, so it can be shared with people who do not use csa.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use csa_session::{
+    Finding, MetaSessionState, OutputSection, SessionResult, TokenUsage, read_all_sections,
+};
+
+use crate::cli::ExportCommands;
+use crate::review_session_findings::read_session_findings_or_fall_back;
+use crate::session_cmds::resolve_session_prefix_with_global_fallback;
+use crate::token_usage_display::display_total_tokens;
+
+#[path = "export_cmd_html.rs"]
+mod html;
+
+/// Descendant levels shown below the exported session in the genealogy graph.
+const MAX_DESCENDANT_DEPTH: usize = 3;
+
+/// Everything rendered into the HTML report.
+#[derive(Debug)]
+struct SessionReport {
+    session_id: String,
+    description: Option<String>,
+    project_path: String,
+    branch: Option<String>,
+    phase: String,
+    created_at: DateTime<Utc>,
+    last_accessed: DateTime<Utc>,
+    turn_count: u32,
+    csa_version: Option<String>,
+    result: Option<SessionResult>,
+    findings: Vec<Finding>,
+    sections: Vec<(OutputSection, String)>,
+    tools: BTreeMap<String, Option<TokenUsage>>,
+    token_usage: Option<TokenUsage>,
+    family: Vec<FamilyNode>,
+    generated_at: DateTime<Utc>,
+}
+
+/// One session in the genealogy graph, in depth-first display order.
+#[derive(Debug, Clone, PartialEq)]
+struct FamilyNode {
+    session_id: String,
+    /// Indent level relative to the oldest ancestor shown.
+    level: usize,
+    description: Option<String>,
+    phase: String,
+    forked: bool,
+    current: bool,
+    tokens: Option<u64>,
+    cost_usd: Option<f64>,
+}
+
+pub(crate) fn handle_export(cmd: ExportCommands) -> Result<()> {
+    match cmd {
+        ExportCommands::Report { session, out, cd } => handle_export_report(session, out, cd),
+    }
+}
+
+fn handle_export_report(session: String, out: PathBuf, cd: Option<String>) -> Result<()> {
+    let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
+    let resolved = resolve_session_prefix_with_global_fallback(&project_root, &session)?;
+    let session_dir = resolved.sessions_dir.join(&resolved.session_id);
+    let effective_root = resolved
+        .foreign_project_root
+        .as_deref()
+        .unwrap_or(&project_root);
+    let sessions = csa_session::list_sessions(effective_root, None).unwrap_or_else(|err| {
+        tracing::warn!(error = %err, "Failed to list sessions for the genealogy graph");
+        Vec::new()
+    });
+
+    let report = build_report(&resolved.session_id, &session_dir, &sessions)?;
+    std::fs::write(&out, html::render_report(&report))
+        .with_context(|| format!("failed to write {}", out.display()))?;
+    eprintln!(
+        "Wrote report for session {} to {}",
+        resolved.session_id,
+        out.display()
+    );
+    Ok(())
+}
+
+fn build_report(
+    session_id: &str,
+    session_dir: &Path,
+    sessions: &[MetaSessionState],
+) -> Result<SessionReport> {
+    let state_path = session_dir.join("state.toml");
+    let content = std::fs::read_to_string(&state_path)
+        .with_context(|| format!("failed to read {}", state_path.display()))?;
+    let state: MetaSessionState = toml::from_str(&content)
+        .with_context(|| format!("failed to parse {}", state_path.display()))?;
+
+    let mut findings = read_session_findings_or_fall_back(session_dir)
+        .unwrap_or_else(|err| {
+            tracing::warn!(error = %err, "Failed to read review findings");
+            None
+        })
+        .unwrap_or_default();
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+    let sections = read_all_sections(session_dir)
+        .unwrap_or_else(|err| {
+            tracing::warn!(error = %err, "Failed to read output sections");
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|(section, content)| {
+            section.id != csa_session::RETURN_PACKET_SECTION_ID && !content.trim().is_empty()
+        })
+        .collect();
+
+    Ok(SessionReport {
+        session_id: session_id.to_string(),
+        description: state.description.clone(),
+        project_path: state.project_path.clone(),
+        branch: state.branch.clone(),
+        phase: state.phase.to_string(),
+        created_at: state.created_at,
+        last_accessed: state.last_accessed,
+        turn_count: state.turn_count,
+        csa_version: state.csa_version.clone(),
+        result: load_result(session_dir),
+        findings,
+        sections,
+        tools: state
+            .tools
+            .iter()
+            .map(|(name, tool)| (name.clone(), tool.token_usage.clone()))
+            .collect(),
+        token_usage: state.total_token_usage.clone(),
+        family: family_tree(&state, sessions),
+        generated_at: Utc::now(),
+    })
+}
+
+fn load_result(session_dir: &Path) -> Option<SessionResult> {
+    let path = session_dir.join(csa_session::result::RESULT_FILE_NAME);
+    let content = std::fs::read_to_string(&path).ok()?;
+    toml::from_str(&content)
+        .map_err(|err| {
+            tracing::warn!(path = %path.display(), error = %err, "Failed to parse session result");
+        })
+        .ok()
+}
+
+/// Ancestors of `current` (oldest first), the session itself, then its
+/// descendants up to [`MAX_DESCENDANT_DEPTH`] levels, depth-first by creation.
+fn family_tree(current: &MetaSessionState, sessions: &[MetaSessionState]) -> Vec<FamilyNode> {
+    let by_id: HashMap<&str, &MetaSessionState> = sessions
+        .iter()
+        .map(|session| (session.meta_session_id.as_str(), session))
+        .collect();
+    let mut children: HashMap<&str, Vec<&MetaSessionState>> = HashMap::new();
+    for session in sessions {
+        if let Some(parent) = session.genealogy.parent_session_id.as_deref() {
+            children.entry(parent).or_default().push(session);
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|session| session.created_at);
+    }
+
+    let mut ancestors = Vec::new();
+    let mut next = current.genealogy.parent_session_id.as_deref();
+    while let Some(parent_id) = next {
+        // Guard against cycles in corrupted state.
+        if parent_id == current.meta_session_id
+            || ancestors
+                .iter()
+                .any(|node: &FamilyNode| node.session_id == parent_id)
+        {
+            break;
+        }
+        match by_id.get(parent_id) {
+            Some(parent) => {
+                ancestors.push(family_node(parent, 0, false));
+                next = parent.genealogy.parent_session_id.as_deref();
+            }
+            None => {
+                ancestors.push(FamilyNode {
+                    session_id: parent_id.to_string(),
+                    level: 0,
+                    description: None,
+                    phase: "unknown".to_string(),
+                    forked: false,
+                    current: false,
+                    tokens: None,
+                    cost_usd: None,
+                });
+                next = None;
+            }
+        }
+    }
+    ancestors.reverse();
+    for (level, node) in ancestors.iter_mut().enumerate() {
+        node.level = level;
+    }
+
+    let base = ancestors.len();
+    let mut nodes = ancestors;
+    nodes.push(family_node(current, base, true));
+    push_descendants(&mut nodes, &children, &current.meta_session_id, base + 1, 1);
+    nodes
+}
+
+fn push_descendants(
+    nodes: &mut Vec<FamilyNode>,
+    children: &HashMap<&str, Vec<&MetaSessionState>>,
+    parent_id: &str,
+    level: usize,
+    depth: usize,
+) {
+    if depth > MAX_DESCENDANT_DEPTH {
+        return;
+    }
+    for child in children.get(parent_id).into_iter().flatten() {
+        if nodes
+            .iter()
+            .any(|node| node.session_id == child.meta_session_id)
+        {
+            continue;
+        }
+        nodes.push(family_node(child, level, false));
+        push_descendants(
+            nodes,
+            children,
+            &child.meta_session_id,
+            level + 1,
+            depth + 1,
+        );
+    }
+}
+
+fn family_node(session: &MetaSessionState, level: usize, current: bool) -> FamilyNode {
+    let usage = session.total_token_usage.as_ref();
+    FamilyNode {
+        session_id: session.meta_session_id.clone(),
+        level,
+        description: session.description.clone(),
+        phase: session.phase.to_string(),
+        forked: session.genealogy.fork_of_session_id.is_some(),
+        current,
+        tokens: usage.and_then(display_total_tokens),
+        cost_usd: usage.and_then(|usage| usage.estimated_cost_usd),
+    }
+}
+
+#[cfg(test)]
+#[path = "export_cmd_tests.rs"]
+mod tests;
@@ -0,0 +1,438 @@
+//! HTML rendering for `csa export report`.
+//!
+//! The page has no external assets: styles are inlined and the usage charts and
+//! genealogy graph are inline SVG.
+
+use std::fmt::Write as _;
+
+use csa_session::{Severity, SeveritySummary, TokenUsage};
+
+use super::{FamilyNode, SessionReport};
+use crate::token_usage_display::display_total_tokens;
+
+const STYLE: &str = r#"
+body { font: 14px/1.5 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #1f2328; margin: 0; background: #f6f8fa; }
+main { max-width: 1040px; margin: 0 auto; padding: 24px; }
+h1 { font-size: 22px; margin: 0 0 4px; }
+h2 { font-size: 17px; margin: 0 0 12px; }
+section { background: #fff; border: 1px solid #d0d7de; border-radius: 6px; padding: 16px 20px; margin: 16px 0; }
+.subtitle { color: #59636e; margin: 0; }
+code, pre { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 12.5px; }
+pre { background: #f6f8fa; border-radius: 6px; padding: 12px; overflow-x: auto; white-space: pre-wrap; word-break: break-word; }
+dl { display: grid; grid-template-columns: max-content 1fr; gap: 4px 16px; margin: 0; }
+dt { color: #59636e; }
+dd { margin: 0; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 6px 8px; border-bottom: 1px solid #d0d7de; vertical-align: top; }
+th { background: #f6f8fa; }
+.badge { display: inline-block; padding: 0 8px; border-radius: 10px; color: #fff; font-size: 12px; font-weight: 600; }
+.sev-critical { background: #8b1a1a; }
+.sev-high { background: #d1242f; }
+.sev-medium { background: #bf8700; }
+.sev-low { background: #0969da; }
+tr.sev-critical td:first-child { border-left: 4px solid #8b1a1a; }
+tr.sev-high td:first-child { border-left: 4px solid #d1242f; }
+tr.sev-medium td:first-child { border-left: 4px solid #bf8700; }
+tr.sev-low td:first-child { border-left: 4px solid #0969da; }
+.muted { color: #59636e; }
+details { margin: 8px 0; }
+summary { cursor: pointer; font-weight: 600; }
+svg text { font-size: 12px; fill: #1f2328; }
+"#;
+
+/// Chart geometry shared by the bar charts.
+const CHART_WIDTH: usize = 720;
+const LABEL_WIDTH: usize = 180;
+const VALUE_WIDTH: usize = 110;
+const ROW_HEIGHT: usize = 26;
+
+const INPUT_COLOR: &str = "#54aeff";
+const OUTPUT_COLOR: &str = "#8250df";
+const COST_COLOR: &str = "#1a7f37";
+
+pub(super) fn render_report(report: &SessionReport) -> String {
+    let title = format!("csa report: {}", report.session_id);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<main>\n",
+        escape_html(&title)
+    );
+    let _ = writeln!(
+        out,
+        "<header><h1>{}</h1><p class=\"subtitle\"><code>{}</code></p></header>",
+        escape_html(report.description.as_deref().unwrap_or("Session report")),
+        escape_html(&report.session_id)
+    );
+    out.push_str(&render_summary(report));
+    out.push_str(&render_findings(report));
+    out.push_str(&render_usage(report));
+    out.push_str(&render_genealogy(&report.family));
+    out.push_str(&render_sections(report));
+    let _ = writeln!(
+        out,
+        "<footer class=\"muted\">Generated by csa {} at {}</footer>\n</main>\n</body>\n</html>",
+        escape_html(env!("CARGO_PKG_VERSION")),
+        report.generated_at.to_rfc3339()
+    );
+    out
+}
+
+fn render_summary(report: &SessionReport) -> String {
+    let mut rows: Vec<(&str, String)> = vec![
+        ("Project", report.project_path.clone()),
+        (
+            "Branch",
+            report.branch.clone().unwrap_or_else(|| "-".into()),
+        ),
+        (
+            "Phase",
+            format!("{} ({} turns)", report.phase, report.turn_count),
+        ),
+        ("Created", report.created_at.to_rfc3339()),
+        ("Last access", report.last_accessed.to_rfc3339()),
+        (
+            "CSA version",
+            report.csa_version.clone().unwrap_or_else(|| "-".into()),
+        ),
+    ];
+    if let Some(result) = &report.result {
+        let seconds = (result.completed_at - result.started_at)
+            .num_seconds()
+            .max(0);
+        rows.push((
+            "Result",
+            format!(
+                "{} (exit {}, tool {}, {}s)",
+                result.status, result.exit_code, result.tool, seconds
+            ),
+        ));
+        if !result.summary.is_empty() {
+            rows.push(("Summary", result.summary.clone()));
+        }
+    }
+    if let Some(usage) = &report.token_usage {
+        rows.push(("Tokens", usage_label(usage)));
+    }
+    let counts = SeveritySummary::from_findings(&report.findings);
+    rows.push((
+        "Findings",
+        format!(
+            "critical {}, high {}, medium {}, low {}",
+            counts.critical, counts.high, counts.medium, counts.low
+        ),
+    ));
+
+    let mut out = String::from("<section id=\"summary\">\n<h2>Summary</h2>\n<dl>\n");
+    for (label, value) in rows {
+        let _ = writeln!(
+            out,
+            "<dt>{}</dt><dd>{}</dd>",
+            escape_html(label),
+            escape_html(&value)
+        );
+    }
+    out.push_str("</dl>\n</section>\n");
+    out
+}
+
+fn render_findings(report: &SessionReport) -> String {
+    let mut out = format!(
+        "<section id=\"findings\">\n<h2>Findings ({})</h2>\n",
+        report.findings.len()
+    );
+    if report.findings.is_empty() {
+        out.push_str("<p class=\"muted\">No review findings recorded.</p>\n</section>\n");
+        return out;
+    }
+    out.push_str(
+        "<table>\n<thead><tr><th>Severity</th><th>Location</th><th>Rule</th>\
+         <th>Finding</th><th>Engine</th></tr></thead>\n<tbody>\n",
+    );
+    for finding in &report.findings {
+        let class = severity_class(&finding.severity);
+        let location = match finding.line {
+            Some(line) if line > 0 => format!("{}:{line}", finding.file),
+            _ => finding.file.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "<tr class=\"{class}\"><td><span class=\"badge {class}\">{}</span></td>\
+             <td><code>{}</code></td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+            severity_name(&finding.severity),
+            escape_html(&location),
+            escape_html(&finding.rule_id),
+            escape_html(&finding.summary),
+            escape_html(&finding.engine)
+        );
+    }
+    out.push_str("</tbody>\n</table>\n</section>\n");
+    out
+}
+
+fn render_usage(report: &SessionReport) -> String {
+    let mut out = String::from("<section id=\"usage\">\n<h2>Tokens and cost</h2>\n");
+    let token_rows: Vec<BarRow> = report
+        .tools
+        .iter()
+        .filter_map(|(tool, usage)| {
+            let usage = usage.as_ref()?;
+            let segments = match (usage.input_tokens, usage.output_tokens) {
+                (Some(input), Some(output)) => vec![(input, INPUT_COLOR), (output, OUTPUT_COLOR)],
+                _ => vec![(display_total_tokens(usage)?, INPUT_COLOR)],
+            };
+            Some(BarRow {
+                label: tool.clone(),
+                segments: segments.into_iter().map(|(v, c)| (v as f64, c)).collect(),
+                value_label: usage_tokens_label(usage),
+            })
+        })
+        .collect();
+    if token_rows.is_empty() {
+        out.push_str("<p class=\"muted\">No token usage recorded.</p>\n");
+    } else {
+        out.push_str("<h3>Tokens by tool</h3>\n");
+        let _ = writeln!(
+            out,
+            "<p class=\"muted\"><span style=\"color:{INPUT_COLOR}\">&#9632;</span> input \
+             <span style=\"color:{OUTPUT_COLOR}\">&#9632;</span> output</p>"
+        );
+        out.push_str(&bar_chart("Tokens by tool", &token_rows));
+    }
+
+    let cost_rows: Vec<BarRow> = report
+        .family
+        .iter()
+        .filter_map(|node| {
+            let cost = node.cost_usd?;
+            let marker = if node.current { " (this)" } else { "" };
+            Some(BarRow {
+                label: format!("{}{marker}", short_id(&node.session_id)),
+                segments: vec![(cost, COST_COLOR)],
+                value_label: format!("${cost:.4}"),
+            })
+        })
+        .collect();
+    if !cost_rows.is_empty() {
+        out.push_str("<h3>Estimated cost by session</h3>\n");
+        out.push_str(&bar_chart("Estimated cost by session", &cost_rows));
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+/// One horizontal bar; segments are stacked left to right.
+struct BarRow {
+    label: String,
+    segments: Vec<(f64, &'static str)>,
+    value_label: String,
+}
+
+fn bar_chart(title: &str, rows: &[BarRow]) -> String {
+    let max = rows
+        .iter()
+        .map(|row| row.segments.iter().map(|(value, _)| value).sum::<f64>())
+        .fold(0.0_f64, f64::max);
+    let bar_space = (CHART_WIDTH - LABEL_WIDTH - VALUE_WIDTH) as f64;
+    let height = rows.len() * ROW_HEIGHT + 8;
+    let mut out = format!(
+        "<svg role=\"img\" width=\"{CHART_WIDTH}\" height=\"{height}\" \
+         viewBox=\"0 0 {CHART_WIDTH} {height}\"><title>{}</title>\n",
+        escape_html(title)
+    );
+    for (index, row) in rows.iter().enumerate() {
+        let y = index * ROW_HEIGHT + 4;
+        let _ = writeln!(
+            out,
+            "<text x=\"0\" y=\"{}\">{}</text>",
+            y + 15,
+            escape_html(&row.label)
+        );
+        let mut x = LABEL_WIDTH as f64;
+        for (value, color) in &row.segments {
+            let width = if max > 0.0 {
+                value / max * bar_space
+            } else {
+                0.0
+            };
+            let _ = writeln!(
+                out,
+                "<rect x=\"{x:.1}\" y=\"{y}\" width=\"{width:.1}\" height=\"18\" fill=\"{color}\"/>"
+            );
+            x += width;
+        }
+        let _ = writeln!(
+            out,
+            "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+            x + 6.0,
+            y + 15,
+            escape_html(&row.value_label)
+        );
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+fn render_genealogy(family: &[FamilyNode]) -> String {
+    const NODE_HEIGHT: usize = 30;
+    const INDENT: usize = 28;
+    let mut out = String::from("<section id=\"genealogy\">\n<h2>Genealogy</h2>\n");
+    if family.len() <= 1 {
+        out.push_str("<p class=\"muted\">No parent or child sessions.</p>\n</section>\n");
+        return out;
+    }
+    let height = family.len() * NODE_HEIGHT + 4;
+    let _ = writeln!(
+        out,
+        "<svg role=\"img\" width=\"{CHART_WIDTH}\" height=\"{height}\" \
+         viewBox=\"0 0 {CHART_WIDTH} {height}\"><title>Session genealogy</title>"
+    );
+    for (index, node) in family.iter().enumerate() {
+        let x = node.level * INDENT + 4;
+        let y = index * NODE_HEIGHT + 2;
+        // Elbow connector from the nearest preceding node one level up.
+        if node.level > 0
+            && let Some(parent) = family[..index]
+                .iter()
+                .rposition(|other| other.level + 1 == node.level)
+        {
+            let px = family[parent].level * INDENT + 12;
+            let py = parent * NODE_HEIGHT + 2 + 22;
+            let _ = writeln!(
+                out,
+                "<path d=\"M{px} {py} V{} H{x}\" fill=\"none\" stroke=\"#8c959f\"{}/>",
+                y + 11,
+                if node.forked {
+                    " stroke-dasharray=\"4 3\""
+                } else {
+                    ""
+                }
+            );
+        }
+        let (fill, stroke) = if node.current {
+            ("#ddf4ff", "#0969da")
+        } else {
+            ("#ffffff", "#d0d7de")
+        };
+        let mut label = format!("{}  {}", short_id(&node.session_id), node.phase);
+        if let Some(tokens) = node.tokens {
+            let _ = write!(label, "  {} tok", format_count(tokens));
+        }
+        if let Some(cost) = node.cost_usd {
+            let _ = write!(label, "  ${cost:.4}");
+        }
+        if let Some(description) = node.description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = write!(label, "  {}", truncate(description, 60));
+        }
+        let width = CHART_WIDTH.saturating_sub(x + 4);
+        let _ = writeln!(
+            out,
+            "<g><title>{}</title><rect x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"22\" rx=\"4\" \
+             fill=\"{fill}\" stroke=\"{stroke}\"/><text x=\"{}\" y=\"{}\">{}</text></g>",
+            escape_html(&node.session_id),
+            x + 8,
+            y + 15,
+            escape_html(&label)
+        );
+    }
+    out.push_str("</svg>\n<p class=\"muted\">Dashed links are forks.</p>\n</section>\n");
+    out
+}
+
+fn render_sections(report: &SessionReport) -> String {
+    let mut out = String::from("<section id=\"output\">\n<h2>Output</h2>\n");
+    if report.sections.is_empty() {
+        out.push_str("<p class=\"muted\">No structured output recorded.</p>\n");
+    }
+    for (index, (section, content)) in report.sections.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "<details{}><summary>{} <span class=\"muted\">~{} tokens</span></summary>\n<pre>{}</pre>\n</details>",
+            if index == 0 { " open" } else { "" },
+            escape_html(&section.title),
+            section.token_estimate,
+            escape_html(content.trim_end())
+        );
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+fn usage_label(usage: &TokenUsage) -> String {
+    let mut label = usage_tokens_label(usage);
+    if let Some(cost) = usage.estimated_cost_usd {
+        let _ = write!(label, ", ~${cost:.4}");
+    }
+    label
+}
+
+fn usage_tokens_label(usage: &TokenUsage) -> String {
+    match (usage.input_tokens, usage.output_tokens) {
+        (Some(input), Some(output)) => {
+            format!("{} in / {} out", format_count(input), format_count(output))
+        }
+        _ => display_total_tokens(usage)
+            .map(|total| format!("{} total", format_count(total)))
+            .unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+fn severity_class(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "sev-critical",
+        Severity::High => "sev-high",
+        Severity::Medium => "sev-medium",
+        Severity::Low => "sev-low",
+    }
+}
+
+fn severity_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::High => "high",
+        Severity::Medium => "medium",
+        Severity::Low => "low",
+    }
+}
+
+fn format_count(value: u64) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, ch) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn short_id(session_id: &str) -> &str {
+    session_id.get(..11).unwrap_or(session_id)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    out.push_str("...");
+    out
+}
+
+pub(super) fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
@@ -0,0 +1,170 @@
+use super::*;
+use crate::test_review_findings::finding;
+use chrono::{Duration, TimeZone};
+use csa_session::{Severity, ToolState};
+
+const SESSION_ID: &str = "01JEXPORT000000000000000000";
+
+fn session(id: &str, parent: Option<&str>, minute: u32) -> MetaSessionState {
+    let created_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, minute, 0).unwrap();
+    let mut session = MetaSessionState {
+        meta_session_id: id.to_string(),
+        description: Some(format!("task {id}")),
+        created_at,
+        last_accessed: created_at + Duration::minutes(1),
+        ..MetaSessionState::default()
+    };
+    session.genealogy.parent_session_id = parent.map(str::to_string);
+    session
+}
+
+fn write_session(dir: &Path) {
+    let mut state = session(SESSION_ID, None, 0);
+    state.description = Some("review <auth> & sessions".to_string());
+    state.total_token_usage = Some(TokenUsage {
+        input_tokens: Some(12_000),
+        output_tokens: Some(3_400),
+        estimated_cost_usd: Some(0.25),
+        ..Default::default()
+    });
+    state.tools.insert(
+        "codex".to_string(),
+        ToolState {
+            provider_session_id: None,
+            last_action_summary: String::new(),
+            last_exit_code: 0,
+            updated_at: Utc::now(),
+            tool_version: None,
+            token_usage: state.total_token_usage.clone(),
+        },
+    );
+    std::fs::write(dir.join("state.toml"), toml::to_string(&state).unwrap()).unwrap();
+
+    let result = SessionResult {
+        status: "success".to_string(),
+        tool: "codex".to_string(),
+        summary: "2 findings".to_string(),
+        ..Default::default()
+    };
+    std::fs::write(
+        dir.join(csa_session::result::RESULT_FILE_NAME),
+        toml::to_string(&result).unwrap(),
+    )
+    .unwrap();
+
+    csa_session::persist_structured_output(
+        dir,
+        "<!-- CSA:SECTION:summary -->\nUse <b>escaped</b> output.\n<!-- CSA:SECTION:summary:END -->\n\
+         <!-- CSA:SECTION:return-packet -->\nstatus = \"Success\"\nexit_code = 0\n\
+         <!-- CSA:SECTION:return-packet:END -->\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn family_tree_lists_ancestors_session_and_descendants() {
+    let mut fork = session("01FORK000000", Some("01CURRENT000"), 4);
+    fork.genealogy.fork_of_session_id = Some("01CURRENT000".to_string());
+    fork.total_token_usage = Some(TokenUsage {
+        total_tokens: Some(900),
+        estimated_cost_usd: Some(0.01),
+        ..Default::default()
+    });
+    let current = session("01CURRENT000", Some("01PARENT0000"), 2);
+    let sessions = vec![
+        session("01ROOT000000", None, 0),
+        session("01PARENT0000", Some("01ROOT000000"), 1),
+        current.clone(),
+        fork,
+        session("01CHILD00000", Some("01CURRENT000"), 3),
+        session("01GRANDKID00", Some("01CHILD00000"), 5),
+        session("01SIBLING000", Some("01PARENT0000"), 6),
+    ];
+
+    let family = family_tree(&current, &sessions);
+    let rows: Vec<(&str, usize)> = family
+        .iter()
+        .map(|node| (node.session_id.as_str(), node.level))
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            ("01ROOT000000", 0),
+            ("01PARENT0000", 1),
+            ("01CURRENT000", 2),
+            ("01CHILD00000", 3),
+            ("01GRANDKID00", 4),
+            ("01FORK000000", 3),
+        ]
+    );
+    assert!(family[2].current);
+    assert!(family[5].forked);
+    assert_eq!(family[5].tokens, Some(900));
+    assert_eq!(family[5].cost_usd, Some(0.01));
+}
+
+#[test]
+fn family_tree_marks_missing_parent_and_stops_on_cycles() {
+    let current = session("01CURRENT000", Some("01GONE000000"), 0);
+    let family = family_tree(&current, std::slice::from_ref(&current));
+    assert_eq!(family.len(), 2);
+    assert_eq!(family[0].session_id, "01GONE000000");
+    assert_eq!(family[0].phase, "unknown");
+
+    let a = session("01A000000000", Some("01B000000000"), 0);
+    let b = session("01B000000000", Some("01A000000000"), 1);
+    let family = family_tree(&a, &[a.clone(), b]);
+    let ids: Vec<&str> = family.iter().map(|node| node.session_id.as_str()).collect();
+    assert_eq!(ids, ["01B000000000", "01A000000000"]);
+}
+
+#[test]
+fn build_report_skips_return_packet_section() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_session(tmp.path());
+
+    let report = build_report(SESSION_ID, tmp.path(), &[]).unwrap();
+    assert_eq!(report.result.as_ref().unwrap().summary, "2 findings");
+    let ids: Vec<&str> = report
+        .sections
+        .iter()
+        .map(|(section, _)| section.id.as_str())
+        .collect();
+    assert_eq!(ids, ["summary"]);
+    assert_eq!(report.family.len(), 1);
+    assert!(report.findings.is_empty());
+}
+
+#[test]
+fn rendered_report_is_self_contained_and_escaped() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_session(tmp.path());
+    let mut report = build_report(SESSION_ID, tmp.path(), &[]).unwrap();
+    report.findings = vec![
+        finding(Severity::Low, "FID", "src/b.rs", None, "rust.no-unwrap"),
+        Finding {
+            summary: "token <leak>".to_string(),
+            ..finding(
+                Severity::Critical,
+                "FID",
+                "src/a.rs",
+                Some(9),
+                "rust.no-unwrap",
+            )
+        },
+    ];
+
+    let page = html::render_report(&report);
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(!page.contains("<script"));
+    assert!(!page.contains("src=\"http"));
+    assert!(page.contains("<h1>review &lt;auth&gt; &amp; sessions</h1>"));
+    assert!(page.contains("Use &lt;b&gt;escaped&lt;/b&gt; output."));
+    assert!(page.contains("<span class=\"badge sev-critical\">critical</span>"));
+    assert!(page.contains("<code>src/a.rs:9</code>"));
+    assert!(page.contains("token &lt;leak&gt;"));
+    assert!(page.contains("critical 1, high 0, medium 0, low 1"));
+    assert!(page.contains("12,000 in / 3,400 out"));
+    assert!(page.contains("<title>Tokens by tool</title>"));
+    assert!(page.contains("No parent or child sessions."));
+}
@@ -7,20 +7,24 @@ use csa_config::{GcConfig, GlobalConfig};
 use csa_core::types::OutputFormat;
 use csa_resource::cleanup_orphan_scopes;
 use csa_session::{
-    MetaSessionState, SessionPhase, delete_session, get_session_dir, get_session_root,
+    GcCategory, GcReclaimed, MetaSessionState, SessionPhase, get_session_dir, get_session_root,
     list_sessions, list_sessions_readonly, save_session_in,
 };
 
 mod auto_gc;
 #[path = "gc_args.rs"]
 mod gc_args;
+mod policy;
 mod reaper;
 mod transcript;
 
+use auto_gc::handle_gc_global_filtered;
+pub(crate) use auto_gc::invalidate_state_dir_size_cache;
 #[cfg(test)]
-use auto_gc::discover_project_roots;
-pub(crate) use auto_gc::{handle_gc_global, invalidate_state_dir_size_cache};
+use auto_gc::{discover_project_roots, handle_gc_global};
 pub use gc_args::GcArgs;
+pub(crate) use policy::GcFilters;
+use policy::{SessionLocation, format_totals, reclaimed_json, remove_planned_sessions};
 pub(crate) use reaper::{AUTO_GC_REAP_RUNTIME_MAX_AGE_DAYS, reap_runtime_payloads_global};
 use reaper::{
     print_runtime_reap_summary, reap_runtime_payloads_in_root, require_runtime_reap_max_age,
@@ -70,6 +74,7 @@ pub(crate) fn should_skip_whole_session_delete(
     liveness_probe_mode: LivenessProbeMode,
 ) -> bool {
     session.phase == SessionPhase::Active
+        || csa_session::is_session_pinned(session_dir)
         || csa_process::ToolLiveness::has_live_process(session_dir)
         || csa_process::ToolLiveness::daemon_pid_is_alive(session_dir)
         || liveness_probe_mode.is_alive(session_dir)
@@ -89,19 +94,20 @@ pub(crate) fn handle_gc_args(
     format: OutputFormat,
     current_session_id: Option<&str>,
 ) -> Result<()> {
+    let filters = GcFilters::from_args(&args)?;
     if args.global {
-        handle_gc_global(
+        handle_gc_global_filtered(
             args.dry_run,
-            args.max_age_days,
             args.reap_runtime,
+            &filters,
             format,
             current_session_id,
         )
     } else {
-        handle_gc(
+        handle_gc_filtered(
             args.dry_run,
-            args.max_age_days,
             args.reap_runtime,
+            &filters,
             format,
             current_session_id,
             args.cd.as_deref(),
@@ -116,6 +122,28 @@ pub(crate) fn handle_gc(
     format: OutputFormat,
     current_session_id: Option<&str>,
     cd: Option<&str>,
+) -> Result<()> {
+    let filters = GcFilters {
+        max_age_days,
+        ..GcFilters::default()
+    };
+    handle_gc_filtered(
+        dry_run,
+        reap_runtime,
+        &filters,
+        format,
+        current_session_id,
+        cd,
+    )
+}
+
+pub(crate) fn handle_gc_filtered(
+    dry_run: bool,
+    reap_runtime: bool,
+    filters: &GcFilters,
+    format: OutputFormat,
+    current_session_id: Option<&str>,
+    cd: Option<&str>,
 ) -> Result<()> {
     let project_root = crate::pipeline::determine_project_root(cd)?;
     let session_root = get_session_root(&project_root)?;
@@ -127,12 +155,11 @@ pub(crate) fn handle_gc(
     let gc_config = GcConfig::load_for_project(&project_root)?;
     let now = chrono::Utc::now();
     let runtime_reap_max_age_days =
-        runtime_reap_max_age_days(reap_runtime, max_age_days, gc_config)?;
+        runtime_reap_max_age_days(reap_runtime, filters.max_age_days, gc_config)?;
+    let policy = filters.session_policy(reap_runtime);
 
     let mut stale_locks_removed = 0;
-    let mut empty_sessions_removed = 0;
-    let mut orphan_dirs_removed = 0;
-    let mut expired_sessions_removed = 0;
+    let mut reclaimed = GcReclaimed::default();
     let mut sessions_retired = 0u64;
     let mut orphan_scopes_cleaned = 0u64;
     let liveness_probe_mode = LivenessProbeMode::for_dry_run(dry_run);
@@ -141,7 +168,10 @@ pub(crate) fn handle_gc(
         eprintln!("[dry-run] No changes will be made.");
     }
 
-    for session in &sessions {
+    for session in sessions
+        .iter()
+        .filter(|session| policy.matches_tool(session))
+    {
         let session_dir = get_session_dir(&project_root, &session.meta_session_id)?;
         let locks_dir = session_dir.join("locks");
 
@@ -174,26 +204,12 @@ pub(crate) fn handle_gc(
             }
         }
 
+        // Empty sessions are removed below rather than retired.
         if session.tools.is_empty() {
-            if should_skip_whole_session_delete(session, &session_dir, liveness_probe_mode) {
-                info!(
-                    session = %session.meta_session_id,
-                    "Skipped whole-session delete for Active or live session"
-                );
-            } else if dry_run {
-                eprintln!(
-                    "[dry-run] Would remove empty session: {}",
-                    session.meta_session_id
-                );
-                empty_sessions_removed += 1;
-            } else if delete_session(&project_root, &session.meta_session_id).is_ok() {
-                empty_sessions_removed += 1;
-            }
             continue;
         }
 
         // Retire stale Active/Available sessions (>7 days since last access)
-        let age = now.signed_duration_since(session.last_accessed);
         if let Some(retirement) =
             stale_session_retirement_candidate(session, now, RETIRE_AFTER_DAYS)
         {
@@ -225,30 +241,17 @@ pub(crate) fn handle_gc(
                 }
             }
         }
-
-        if !reap_runtime
-            && let Some(days) = max_age_days
-            && age.num_days() > days as i64
-        {
-            if should_skip_whole_session_delete(session, &session_dir, liveness_probe_mode) {
-                info!(
-                    session = %session.meta_session_id,
-                    "Skipped expired whole-session delete for Active or live session"
-                );
-            } else if dry_run {
-                eprintln!(
-                    "[dry-run] Would remove expired session: {} (last accessed {} days ago)",
-                    session.meta_session_id,
-                    age.num_days()
-                );
-                expired_sessions_removed += 1;
-            } else if delete_session(&project_root, &session.meta_session_id).is_ok() {
-                info!("Removed expired session: {}", session.meta_session_id);
-                expired_sessions_removed += 1;
-            }
-        }
     }
 
+    remove_planned_sessions(
+        SessionLocation::Project(&project_root),
+        &sessions,
+        &policy,
+        dry_run,
+        liveness_probe_mode,
+        &mut reclaimed,
+    );
+
     let session_root = csa_session::get_session_root(&project_root)?;
     let rotation_path = session_root.join("rotation.toml");
     if rotation_path.exists() {
@@ -279,18 +282,22 @@ pub(crate) fn handle_gc(
                         "Skipped orphan-looking live session directory: {}",
                         session_dir.display()
                     );
-                } else if dry_run {
-                    eprintln!(
-                        "[dry-run] Would remove orphan directory: {}",
-                        session_dir.display()
-                    );
-                    orphan_dirs_removed += 1;
-                } else if fs::remove_dir_all(&session_dir).is_ok() {
-                    info!(
-                        "Removed orphan directory without state.toml: {}",
-                        session_dir.display()
-                    );
-                    orphan_dirs_removed += 1;
+                } else {
+                    let bytes = crate::preflight_state_dir::compute_state_dir_size(&session_dir)
+                        .unwrap_or(0);
+                    if dry_run {
+                        eprintln!(
+                            "[dry-run] Would remove orphan directory: {}",
+                            session_dir.display()
+                        );
+                        reclaimed.record(GcCategory::OrphanDirs, bytes);
+                    } else if fs::remove_dir_all(&session_dir).is_ok() {
+                        info!(
+                            "Removed orphan directory without state.toml: {}",
+                            session_dir.display()
+                        );
+                        reclaimed.record(GcCategory::OrphanDirs, bytes);
+                    }
                 }
             }
         }
@@ -313,7 +320,20 @@ pub(crate) fn handle_gc(
         })
         .transpose()?;
 
+    if let Some(stats) = runtime_reap_stats.as_ref() {
+        reclaimed.add(
+            GcCategory::RuntimePayloads,
+            stats.sessions_reaped,
+            stats.bytes_reclaimed,
+        );
+    }
+
     let transcript_stats = cleanup_project_transcripts(&session_root, gc_config, dry_run);
+    reclaimed.add(
+        GcCategory::Transcripts,
+        transcript_stats.files_removed,
+        transcript_stats.bytes_reclaimed,
+    );
 
     let review_gate_stats = crate::review_gate::gc_review_gate_markers(
         &project_root,
@@ -348,7 +368,9 @@ pub(crate) fn handle_gc(
         && let Ok(entries) = fs::read_dir(&slots_dir)
     {
         for entry in entries.flatten() {
-            if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+            if entry.file_type().is_ok_and(|ft| ft.is_dir())
+                && filters.matches_slot_tool(&entry.file_name())
+            {
                 let tool_dir = entry.path();
                 if let Ok(slot_entries) = fs::read_dir(&tool_dir) {
                     for slot_entry in slot_entries.flatten() {
@@ -450,8 +472,8 @@ pub(crate) fn handle_gc(
             let mut summary = serde_json::json!({
                 "dry_run": dry_run,
                 "stale_locks_removed": stale_locks_removed,
-                "empty_sessions_removed": empty_sessions_removed,
-                "orphan_dirs_removed": orphan_dirs_removed,
+                "empty_sessions_removed": reclaimed.get(GcCategory::EmptySessions).count,
+                "orphan_dirs_removed": reclaimed.get(GcCategory::OrphanDirs).count,
                 "sessions_retired": sessions_retired,
                 "transcripts_removed": transcript_stats.files_removed,
                 "transcript_bytes_reclaimed": transcript_stats.bytes_reclaimed,
@@ -460,9 +482,7 @@ pub(crate) fn handle_gc(
                 "orphan_scopes_cleaned": orphan_scopes_cleaned,
                 "review_gate_markers_removed": review_gate_stats.markers_removed,
             });
-            if !reap_runtime && max_age_days.is_some() {
-                summary["expired_sessions_removed"] = serde_json::json!(expired_sessions_removed);
-            }
+            reclaimed_json(&mut summary, &reclaimed, &policy)?;
             if let Some(runtime_reap_stats) = runtime_reap_stats.as_ref() {
                 summary["runtime_reap"] = serde_json::to_value(runtime_reap_stats)?;
             }
@@ -476,20 +496,35 @@ pub(crate) fn handle_gc(
                 if dry_run { "preview" } else { "complete" }
             );
             eprintln!("{prefix}  Stale locks removed: {stale_locks_removed}");
-            eprintln!("{prefix}  Empty sessions removed: {empty_sessions_removed}");
+            eprintln!(
+                "{prefix}  Empty sessions removed: {}",
+                format_totals(reclaimed.get(GcCategory::EmptySessions))
+            );
             if sessions_retired > 0 {
                 eprintln!("{prefix}  Sessions retired: {sessions_retired}");
             }
-            if !reap_runtime && max_age_days.is_some() {
-                eprintln!("{prefix}  Expired sessions removed: {expired_sessions_removed}");
+            if policy.max_age.is_some() {
+                eprintln!(
+                    "{prefix}  Expired sessions removed: {}",
+                    format_totals(reclaimed.get(GcCategory::ExpiredSessions))
+                );
+            }
+            if policy.max_total_size.is_some() {
+                eprintln!(
+                    "{prefix}  Over-budget sessions removed: {}",
+                    format_totals(reclaimed.get(GcCategory::OverBudgetSessions))
+                );
             }
             if let Some(runtime_reap_stats) = runtime_reap_stats.as_ref() {
                 print_runtime_reap_summary(prefix, runtime_reap_stats);
             }
-            eprintln!("{prefix}  Orphan directories removed: {orphan_dirs_removed}");
             eprintln!(
-                "{}  Transcript files removed: {} ({} bytes)",
-                prefix, transcript_stats.files_removed, transcript_stats.bytes_reclaimed
+                "{prefix}  Orphan directories removed: {}",
+                format_totals(reclaimed.get(GcCategory::OrphanDirs))
+            );
+            eprintln!(
+                "{prefix}  Transcript files removed: {}",
+                format_totals(reclaimed.get(GcCategory::Transcripts))
             );
             eprintln!("{prefix}  Stale slots cleaned: {stale_slots_cleaned}");
             if orphan_slots_cleaned > 0 {
@@ -502,6 +537,10 @@ pub(crate) fn handle_gc(
                     review_gate_stats.markers_removed
                 );
             }
+            eprintln!(
+                "{prefix}  Total reclaimed: {}",
+                crate::session_cmds::format_file_size(reclaimed.total_bytes())
+            );
         }
     }
 
@@ -526,7 +565,7 @@ pub(super) fn runtime_reap_max_age_days(
 }
 
 /// Extract PID from lock file JSON content (expects `{"pid": N}`).
-fn extract_pid_from_lock(json_content: &str) -> Option<u32> {
+pub(crate) fn extract_pid_from_lock(json_content: &str) -> Option<u32> {
     let v: serde_json::Value = serde_json::from_str(json_content).ok()?;
     let n = v.get("pid")?.as_u64()?;
     u32::try_from(n).ok()
@@ -536,7 +575,7 @@ fn extract_pid_from_lock(json_content: &str) -> Option<u32> {
 ///
 /// `session_id_is_null` is `true` when the `session_id` field is JSON `null` or absent.
 /// `acquired_at` is `None` when the field is absent or unparseable.
-fn extract_slot_lock_info(
+pub(crate) fn extract_slot_lock_info(
     json_content: &str,
 ) -> Option<(u32, bool, Option<chrono::DateTime<chrono::Utc>>)> {
     let v: serde_json::Value = serde_json::from_str(json_content).ok()?;
@@ -587,7 +626,7 @@ fn has_confirmed_sessions(dir: &Path) -> bool {
 }
 
 /// Check if a process is alive (cross-platform Unix).
-fn is_process_alive(pid: u32) -> bool {
+pub(crate) fn is_process_alive(pid: u32) -> bool {
     // SAFETY: signal 0 checks existence without sending a signal.
     // EPERM means process exists but we lack permission to signal it.
     let ret = unsafe { libc::kill(pid as libc::pid_t, 0) };
@@ -6,7 +6,10 @@ use tracing::{info, warn};
 use csa_config::GlobalConfig;
 use csa_core::types::OutputFormat;
 use csa_resource::cleanup_orphan_scopes;
-use csa_session::{list_sessions_from_root, list_sessions_from_root_readonly, save_session_in};
+use csa_session::{
+    GcCategory, GcReclaimed, list_sessions_from_root, list_sessions_from_root_readonly,
+    save_session_in,
+};
 
 use super::load_gc_config_for_sessions;
 use super::reaper::{
@@ -14,17 +17,33 @@ use super::reaper::{
     sessions_with_dry_run_retirements, stale_session_retirement_candidate,
 };
 use super::{
-    LivenessProbeMode, RETIRE_AFTER_DAYS, STATE_DIR_SIZE_CACHE_FILENAME, extract_pid_from_lock,
-    has_confirmed_sessions, is_orphan_session_dir, is_process_alive, runtime_reap_max_age_days,
-    should_skip_orphan_session_dir_delete, should_skip_whole_session_delete,
+    GcFilters, LivenessProbeMode, RETIRE_AFTER_DAYS, STATE_DIR_SIZE_CACHE_FILENAME,
+    SessionLocation, extract_pid_from_lock, format_totals, has_confirmed_sessions,
+    is_orphan_session_dir, is_process_alive, reclaimed_json, remove_planned_sessions,
+    runtime_reap_max_age_days, should_skip_orphan_session_dir_delete,
 };
 
-pub(crate) fn handle_gc_global(
+#[cfg(test)]
+pub(super) fn handle_gc_global(
     dry_run: bool,
     max_age_days: Option<u64>,
     reap_runtime: bool,
     format: OutputFormat,
     current_session_id: Option<&str>,
+) -> Result<()> {
+    let filters = GcFilters {
+        max_age_days,
+        ..GcFilters::default()
+    };
+    handle_gc_global_filtered(dry_run, reap_runtime, &filters, format, current_session_id)
+}
+
+pub(super) fn handle_gc_global_filtered(
+    dry_run: bool,
+    reap_runtime: bool,
+    filters: &GcFilters,
+    format: OutputFormat,
+    current_session_id: Option<&str>,
 ) -> Result<()> {
     let state_bases = csa_config::paths::state_dir_all_roots();
     if state_bases.is_empty() {
@@ -42,13 +61,10 @@ pub(crate) fn handle_gc_global(
     let mut runtime_reap_stats = super::RuntimeReapStats::default();
 
     let now = chrono::Utc::now();
+    let policy = filters.session_policy(reap_runtime);
     let mut total_stale_locks = 0u64;
-    let mut total_empty_sessions = 0u64;
-    let mut total_orphan_dirs = 0u64;
-    let mut total_expired_sessions = 0u64;
+    let mut reclaimed = GcReclaimed::default();
     let mut total_sessions_retired = 0u64;
-    let mut total_transcripts_removed = 0u64;
-    let mut total_transcript_bytes_reclaimed = 0u64;
     let mut projects_failed = 0u64;
     let liveness_probe_mode = LivenessProbeMode::for_dry_run(dry_run);
 
@@ -75,8 +91,10 @@ pub(crate) fn handle_gc_global(
             }
         };
 
-        let mut project_removed = 0usize;
-        for session in &sessions {
+        for session in sessions
+            .iter()
+            .filter(|session| policy.matches_tool(session))
+        {
             let session_dir = session_root.join("sessions").join(&session.meta_session_id);
             let locks_dir = session_dir.join("locks");
 
@@ -105,35 +123,12 @@ pub(crate) fn handle_gc_global(
                 }
             }
 
+            // Empty sessions are removed below rather than retired.
             if session.tools.is_empty() {
-                if should_skip_whole_session_delete(session, &session_dir, liveness_probe_mode) {
-                    info!(
-                        session = %session.meta_session_id,
-                        root = %session_root.display(),
-                        "Skipped whole-session delete for Active or live session"
-                    );
-                } else if dry_run {
-                    eprintln!(
-                        "[dry-run] Would remove empty session: {} (in {})",
-                        session.meta_session_id,
-                        session_root.display()
-                    );
-                    total_empty_sessions += 1;
-                    project_removed += 1;
-                } else if csa_session::delete_session_from_root(
-                    session_root,
-                    &session.meta_session_id,
-                )
-                .is_ok()
-                {
-                    total_empty_sessions += 1;
-                    project_removed += 1;
-                }
                 continue;
             }
 
             // Retire stale Active/Available sessions (>7 days since last access)
-            let age = now.signed_duration_since(session.last_accessed);
             if let Some(retirement) =
                 stale_session_retirement_candidate(session, now, RETIRE_AFTER_DAYS)
             {
@@ -170,43 +165,17 @@ pub(crate) fn handle_gc_global(
                     }
                 }
             }
-
-            if !reap_runtime
-                && let Some(days) = max_age_days
-                && age.num_days() > days as i64
-            {
-                if should_skip_whole_session_delete(session, &session_dir, liveness_probe_mode) {
-                    info!(
-                        session = %session.meta_session_id,
-                        root = %session_root.display(),
-                        "Skipped expired whole-session delete for Active or live session"
-                    );
-                } else if dry_run {
-                    eprintln!(
-                        "[dry-run] Would remove expired session: {} ({} days old, in {})",
-                        session.meta_session_id,
-                        age.num_days(),
-                        session_root.display()
-                    );
-                    total_expired_sessions += 1;
-                    project_removed += 1;
-                } else if csa_session::delete_session_from_root(
-                    session_root,
-                    &session.meta_session_id,
-                )
-                .is_ok()
-                {
-                    info!(
-                        "Removed expired session: {} (in {})",
-                        session.meta_session_id,
-                        session_root.display()
-                    );
-                    total_expired_sessions += 1;
-                    project_removed += 1;
-                }
-            }
         }
 
+        let project_removed = remove_planned_sessions(
+            SessionLocation::Root(session_root),
+            &sessions,
+            &policy,
+            dry_run,
+            liveness_probe_mode,
+            &mut reclaimed,
+        );
+
         let sessions_dir = session_root.join("sessions");
         let sessions_is_real_dir = sessions_dir.is_dir()
             && !fs::symlink_metadata(&sessions_dir)
@@ -221,15 +190,20 @@ pub(crate) fn handle_gc_global(
                             "Skipped orphan-looking live session directory: {}",
                             session_dir.display()
                         );
-                    } else if dry_run {
-                        eprintln!(
-                            "[dry-run] Would remove orphan directory: {}",
-                            session_dir.display()
-                        );
-                        total_orphan_dirs += 1;
-                    } else if fs::remove_dir_all(&session_dir).is_ok() {
-                        info!("Removed orphan directory: {}", session_dir.display());
-                        total_orphan_dirs += 1;
+                    } else {
+                        let bytes =
+                            crate::preflight_state_dir::compute_state_dir_size(&session_dir)
+                                .unwrap_or(0);
+                        if dry_run {
+                            eprintln!(
+                                "[dry-run] Would remove orphan directory: {}",
+                                session_dir.display()
+                            );
+                            reclaimed.record(GcCategory::OrphanDirs, bytes);
+                        } else if fs::remove_dir_all(&session_dir).is_ok() {
+                            info!("Removed orphan directory: {}", session_dir.display());
+                            reclaimed.record(GcCategory::OrphanDirs, bytes);
+                        }
                     }
                 }
             }
@@ -258,7 +232,7 @@ pub(crate) fn handle_gc_global(
 
         let project_gc_config = load_gc_config_for_sessions(session_root, &sessions);
         if let Some(days) =
-            runtime_reap_max_age_days(reap_runtime, max_age_days, project_gc_config)?
+            runtime_reap_max_age_days(reap_runtime, filters.max_age_days, project_gc_config)?
         {
             runtime_reap_enabled = true;
             let sessions_for_reap = if dry_run {
@@ -297,13 +271,21 @@ pub(crate) fn handle_gc_global(
         }
         let transcript_stats =
             super::cleanup_project_transcripts(session_root, project_gc_config, dry_run);
-        total_transcripts_removed =
-            total_transcripts_removed.saturating_add(transcript_stats.files_removed);
-        total_transcript_bytes_reclaimed =
-            total_transcript_bytes_reclaimed.saturating_add(transcript_stats.bytes_reclaimed);
+        reclaimed.add(
+            GcCategory::Transcripts,
+            transcript_stats.files_removed,
+            transcript_stats.bytes_reclaimed,
+        );
     }
 
     let runtime_reap_stats = runtime_reap_enabled.then_some(runtime_reap_stats);
+    if let Some(stats) = runtime_reap_stats.as_ref() {
+        reclaimed.add(
+            GcCategory::RuntimePayloads,
+            stats.sessions_reaped,
+            stats.bytes_reclaimed,
+        );
+    }
 
     let mut orphan_scopes_cleaned = 0u64;
     if dry_run {
@@ -332,7 +314,9 @@ pub(crate) fn handle_gc_global(
         && let Ok(entries) = fs::read_dir(&slots_dir)
     {
         for entry in entries.flatten() {
-            if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+            if entry.file_type().is_ok_and(|ft| ft.is_dir())
+                && filters.matches_slot_tool(&entry.file_name())
+            {
                 let tool_dir = entry.path();
                 if let Ok(slot_entries) = fs::read_dir(&tool_dir) {
                     for slot_entry in slot_entries.flatten() {
@@ -375,17 +359,15 @@ pub(crate) fn handle_gc_global(
                 "projects_scanned": project_roots.len(),
                 "projects_failed": projects_failed,
                 "stale_locks_removed": total_stale_locks,
-                "empty_sessions_removed": total_empty_sessions,
-                "orphan_dirs_removed": total_orphan_dirs,
+                "empty_sessions_removed": reclaimed.get(GcCategory::EmptySessions).count,
+                "orphan_dirs_removed": reclaimed.get(GcCategory::OrphanDirs).count,
                 "sessions_retired": total_sessions_retired,
-                "transcripts_removed": total_transcripts_removed,
-                "transcript_bytes_reclaimed": total_transcript_bytes_reclaimed,
+                "transcripts_removed": reclaimed.get(GcCategory::Transcripts).count,
+                "transcript_bytes_reclaimed": reclaimed.get(GcCategory::Transcripts).bytes,
                 "stale_slots_cleaned": stale_slots_cleaned,
                 "orphan_scopes_cleaned": orphan_scopes_cleaned,
             });
-            if !reap_runtime && max_age_days.is_some() {
-                summary["expired_sessions_removed"] = serde_json::json!(total_expired_sessions);
-            }
+            reclaimed_json(&mut summary, &reclaimed, &policy)?;
             if let Some(runtime_reap_stats) = runtime_reap_stats.as_ref() {
                 summary["runtime_reap"] = serde_json::to_value(runtime_reap_stats)?;
             }
@@ -403,22 +385,42 @@ pub(crate) fn handle_gc_global(
                 eprintln!("{prefix}  Projects failed: {projects_failed}");
             }
             eprintln!("{prefix}  Stale locks removed: {total_stale_locks}");
-            eprintln!("{prefix}  Empty sessions removed: {total_empty_sessions}");
+            eprintln!(
+                "{prefix}  Empty sessions removed: {}",
+                format_totals(reclaimed.get(GcCategory::EmptySessions))
+            );
             if total_sessions_retired > 0 {
                 eprintln!("{prefix}  Sessions retired: {total_sessions_retired}");
             }
-            if !reap_runtime && max_age_days.is_some() {
-                eprintln!("{prefix}  Expired sessions removed: {total_expired_sessions}");
+            if policy.max_age.is_some() {
+                eprintln!(
+                    "{prefix}  Expired sessions removed: {}",
+                    format_totals(reclaimed.get(GcCategory::ExpiredSessions))
+                );
+            }
+            if policy.max_total_size.is_some() {
+                eprintln!(
+                    "{prefix}  Over-budget sessions removed: {}",
+                    format_totals(reclaimed.get(GcCategory::OverBudgetSessions))
+                );
             }
             if let Some(runtime_reap_stats) = runtime_reap_stats.as_ref() {
                 print_runtime_reap_summary(prefix, runtime_reap_stats);
             }
-            eprintln!("{prefix}  Orphan directories removed: {total_orphan_dirs}");
             eprintln!(
-                "{prefix}  Transcript files removed: {total_transcripts_removed} ({total_transcript_bytes_reclaimed} bytes)"
+                "{prefix}  Orphan directories removed: {}",
+                format_totals(reclaimed.get(GcCategory::OrphanDirs))
+            );
+            eprintln!(
+                "{prefix}  Transcript files removed: {}",
+                format_totals(reclaimed.get(GcCategory::Transcripts))
             );
             eprintln!("{prefix}  Stale slots cleaned: {stale_slots_cleaned}");
             eprintln!("{prefix}  Orphan cgroup scopes cleaned: {orphan_scopes_cleaned}");
+            eprintln!(
+                "{prefix}  Total reclaimed: {}",
+                crate::session_cmds::format_file_size(reclaimed.total_bytes())
+            );
         }
     }
 
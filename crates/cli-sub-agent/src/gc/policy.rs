@@ -0,0 +1,213 @@
+//! Session filters (`--older-than`, `--tool`, `--max-total-size`) and the
+//! planned whole-session removal shared by project and `--global` gc.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use csa_session::{GcCategory, GcReclaimed, GcTotals, MetaSessionState, SessionGcPolicy};
+
+use super::{GcArgs, LivenessProbeMode, should_skip_whole_session_delete};
+use crate::session_cmds::format_file_size;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GcFilters {
+    pub(crate) max_age_days: Option<u64>,
+    pub(crate) older_than: Option<chrono::Duration>,
+    pub(crate) tool: Option<String>,
+    pub(crate) max_total_size: Option<u64>,
+}
+
+impl GcFilters {
+    pub(crate) fn from_args(args: &GcArgs) -> Result<Self> {
+        Ok(Self {
+            max_age_days: args.max_age_days,
+            older_than: args
+                .older_than
+                .as_deref()
+                .map(crate::session_cmds::parse_duration_filter)
+                .transpose()?,
+            tool: args.tool.clone(),
+            max_total_size: args.max_total_size,
+        })
+    }
+
+    /// With `--reap-runtime`, `--max-age-days` applies to runtime payloads
+    /// only, so whole sessions are not expired by age.
+    pub(super) fn session_policy(&self, reap_runtime: bool) -> SessionGcPolicy {
+        let max_age = if reap_runtime {
+            None
+        } else {
+            // `--max-age-days N` has always meant "more than N whole days".
+            self.older_than.or(self
+                .max_age_days
+                .map(|days| chrono::Duration::days(days as i64 + 1)))
+        };
+        SessionGcPolicy {
+            max_age,
+            tool: self.tool.clone(),
+            max_total_size: self.max_total_size,
+        }
+    }
+
+    /// Whether the slot directory for `tool` is in scope.
+    pub(super) fn matches_slot_tool(&self, tool: &std::ffi::OsStr) -> bool {
+        self.tool.as_deref().is_none_or(|filter| tool == filter)
+    }
+}
+
+/// Where a set of sessions lives: resolved through the project (per-project
+/// gc) or addressed directly under a session root (`--global`).
+#[derive(Debug, Clone, Copy)]
+pub(super) enum SessionLocation<'a> {
+    Project(&'a Path),
+    Root(&'a Path),
+}
+
+impl SessionLocation<'_> {
+    fn session_dir(self, session_id: &str) -> Result<PathBuf> {
+        match self {
+            Self::Project(project_root) => csa_session::get_session_dir(project_root, session_id),
+            Self::Root(session_root) => Ok(session_root.join("sessions").join(session_id)),
+        }
+    }
+
+    fn delete(self, session_id: &str) -> Result<()> {
+        match self {
+            Self::Project(project_root) => csa_session::delete_session(project_root, session_id),
+            Self::Root(session_root) => {
+                csa_session::delete_session_from_root(session_root, session_id)
+            }
+        }
+    }
+
+    fn describe(self, session_id: &str) -> String {
+        match self {
+            Self::Project(_) => session_id.to_string(),
+            Self::Root(session_root) => format!("{session_id} (in {})", session_root.display()),
+        }
+    }
+}
+
+/// Delete, or in dry-run list, the sessions `policy` selects and record the
+/// reclaimed bytes. Returns how many sessions were (or would be) removed.
+pub(super) fn remove_planned_sessions(
+    location: SessionLocation<'_>,
+    sessions: &[MetaSessionState],
+    policy: &SessionGcPolicy,
+    dry_run: bool,
+    liveness_probe_mode: LivenessProbeMode,
+    reclaimed: &mut GcReclaimed,
+) -> usize {
+    let session_dir = |session: &MetaSessionState| location.session_dir(&session.meta_session_id);
+    let removals = csa_session::plan_session_removals(
+        sessions,
+        policy,
+        chrono::Utc::now(),
+        |session| {
+            session_dir(session)
+                .and_then(|dir| crate::preflight_state_dir::compute_state_dir_size(&dir))
+                .unwrap_or(0)
+        },
+        |session| match session_dir(session) {
+            Ok(dir) => should_skip_whole_session_delete(session, &dir, liveness_probe_mode),
+            Err(_) => true,
+        },
+    );
+
+    let mut removed = 0;
+    for removal in removals {
+        let description = format!(
+            "{} session {} ({})",
+            removal_kind(removal.category),
+            location.describe(&removal.session_id),
+            format_file_size(removal.bytes)
+        );
+        if dry_run {
+            eprintln!("[dry-run] Would remove {description}");
+        } else if let Err(err) = location.delete(&removal.session_id) {
+            warn!(error = %err, "Failed to remove {description}");
+            continue;
+        } else {
+            info!("Removed {description}");
+        }
+        reclaimed.record(removal.category, removal.bytes);
+        removed += 1;
+    }
+    removed
+}
+
+fn removal_kind(category: GcCategory) -> &'static str {
+    match category {
+        GcCategory::EmptySessions => "empty",
+        GcCategory::ExpiredSessions => "expired",
+        GcCategory::OverBudgetSessions => "over-budget",
+        _ => "stale",
+    }
+}
+
+/// `count (size)` for one category of the text summary.
+pub(super) fn format_totals(totals: GcTotals) -> String {
+    format!("{} ({})", totals.count, format_file_size(totals.bytes))
+}
+
+/// Per-category JSON additions shared by project and global summaries.
+pub(super) fn reclaimed_json(
+    summary: &mut serde_json::Value,
+    reclaimed: &GcReclaimed,
+    policy: &SessionGcPolicy,
+) -> Result<()> {
+    if policy.max_age.is_some() {
+        summary["expired_sessions_removed"] =
+            serde_json::json!(reclaimed.get(GcCategory::ExpiredSessions).count);
+    }
+    if policy.max_total_size.is_some() {
+        summary["over_budget_sessions_removed"] =
+            serde_json::json!(reclaimed.get(GcCategory::OverBudgetSessions).count);
+    }
+    summary["reclaimed"] = serde_json::to_value(reclaimed)?;
+    summary["bytes_reclaimed"] = serde_json::json!(reclaimed.total_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GcFilters;
+    use clap::Parser;
+
+    #[test]
+    fn test_gc_filters_parse_age_tool_and_size_budget() {
+        let cli = crate::cli::Cli::try_parse_from([
+            "csa",
+            "gc",
+            "--older-than",
+            "12h",
+            "--tool",
+            "codex",
+            "--max-total-size",
+            "500M",
+        ])
+        .expect("gc filters should parse");
+        let crate::cli::Commands::Gc(args) = cli.command else {
+            panic!("expected gc command");
+        };
+        let filters = GcFilters::from_args(&args).unwrap();
+        let policy = filters.session_policy(false);
+        assert_eq!(policy.max_age, Some(chrono::Duration::hours(12)));
+        assert_eq!(policy.tool.as_deref(), Some("codex"));
+        assert_eq!(policy.max_total_size, Some(500 << 20));
+        assert!(filters.matches_slot_tool(std::ffi::OsStr::new("codex")));
+        assert!(!filters.matches_slot_tool(std::ffi::OsStr::new("gemini-cli")));
+
+        // `--max-age-days N` keeps meaning "more than N whole days".
+        let legacy = GcFilters {
+            max_age_days: Some(3),
+            ..GcFilters::default()
+        };
+        assert_eq!(
+            legacy.session_policy(false).max_age,
+            Some(chrono::Duration::days(4))
+        );
+        assert_eq!(legacy.session_policy(true).max_age, None);
+    }
+}
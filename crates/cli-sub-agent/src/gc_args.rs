@@ -15,6 +15,20 @@ pub struct GcArgs {
     #[arg(long, requires = "max_age_days")]
     pub reap_runtime: bool,
 
+    /// Delete sessions last accessed at least this long ago (e.g. 12h, 30d).
+    /// Finer-grained alternative to `--max-age-days`.
+    #[arg(long, value_name = "DURATION", conflicts_with_all = ["max_age_days", "reap_runtime"])]
+    pub older_than: Option<String>,
+
+    /// Only delete sessions that ran this tool, and only clean its slots
+    #[arg(long, value_name = "TOOL")]
+    pub tool: Option<String>,
+
+    /// After age-based removal, delete least recently used sessions until
+    /// each project's sessions fit in SIZE (e.g. 500M, 2G)
+    #[arg(long, value_name = "SIZE", value_parser = csa_session::parse_byte_size)]
+    pub max_total_size: Option<u64>,
+
     /// Scan all projects under ~/.local/state/cli-sub-agent/ (not just current project)
     #[arg(long)]
     pub global: bool,
@@ -1,5 +1,8 @@
+use std::path::Path;
+
 use anyhow::{Context, Result, bail};
 use csa_config::{MergedConfig, ProjectConfig};
+use tokio::io::AsyncWriteExt;
 
 pub(crate) fn resolve_gh_env(config: &MergedConfig) -> Option<(String, String)> {
     config
@@ -34,25 +37,11 @@ pub(crate) fn is_auth_error(stderr: &str) -> bool {
 /// loudly rather than feeding an empty issue body into the workflow.
 pub(crate) async fn fetch_issue_body(issue: u64) -> Result<String> {
     let cwd = std::env::current_dir().context("Failed to determine current directory")?;
-    let merged_config = ProjectConfig::load(&cwd)
-        .context("Failed to load project config while resolving GitHub auth")?;
-    let configured_env = merged_config.as_ref().and_then(resolve_gh_env).or_else(|| {
-        ProjectConfig::default_github_config_dir().map(|dir| ("GH_CONFIG_DIR".to_string(), dir))
-    });
-    fetch_issue_body_with_retry(issue, configured_env.as_ref()).await
-}
-
-async fn fetch_issue_body_with_retry(
-    issue: u64,
-    configured_env: Option<&(String, String)>,
-) -> Result<String> {
+    let configured_env = configured_gh_env(&cwd)?;
     let issue_arg = issue.to_string();
-    let mut command = tokio::process::Command::new("gh");
-    if let Some((key, value)) = configured_env {
-        command.env(key, value);
-    }
-    let output = command
-        .args([
+    let stdout = run_gh_with_retry(
+        &cwd,
+        &[
             "issue",
             "view",
             issue_arg.as_str(),
@@ -60,49 +49,87 @@ async fn fetch_issue_body_with_retry(
             "body",
             "-q",
             ".body",
-        ])
-        .output()
+        ],
+        None,
+        configured_env.as_ref(),
+    )
+    .await?;
+    Ok(stdout.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// `GH_CONFIG_DIR` of the project at `project_root`: `[github] config_dir`,
+/// else the default `~/.config/gh-aider` dir when it exists.
+pub(crate) fn configured_gh_env(project_root: &Path) -> Result<Option<(String, String)>> {
+    let merged_config = ProjectConfig::load(project_root)
+        .context("Failed to load project config while resolving GitHub auth")?;
+    Ok(merged_config.as_ref().and_then(resolve_gh_env).or_else(|| {
+        ProjectConfig::default_github_config_dir().map(|dir| ("GH_CONFIG_DIR".to_string(), dir))
+    }))
+}
+
+/// Run `gh <args>` in `cwd` with `configured_env`, feeding `input` on stdin,
+/// and return its stdout. On an auth error the command is retried once with
+/// default `gh` auth.
+pub(crate) async fn run_gh_with_retry(
+    cwd: &Path,
+    args: &[&str],
+    input: Option<&str>,
+    configured_env: Option<&(String, String)>,
+) -> Result<String> {
+    let command_line = format!("gh {}", args.join(" "));
+    let output = run_gh(cwd, args, input, configured_env, false)
         .await
-        .with_context(|| format!("Failed to run gh issue view {issue}"))?;
+        .with_context(|| format!("Failed to run {command_line}"))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         if configured_env.is_some() && is_auth_error(&stderr) {
-            let mut retry_command = tokio::process::Command::new("gh");
-            let retry_output = retry_command
-                .env_remove("GH_CONFIG_DIR")
-                .args([
-                    "issue",
-                    "view",
-                    issue_arg.as_str(),
-                    "--json",
-                    "body",
-                    "-q",
-                    ".body",
-                ])
-                .output()
+            let retry_output = run_gh(cwd, args, input, None, true)
                 .await
-                .with_context(|| {
-                    format!("Failed to retry gh issue view {issue} with default auth")
-                })?;
+                .with_context(|| format!("Failed to retry {command_line} with default auth"))?;
             if retry_output.status.success() {
-                return Ok(String::from_utf8_lossy(&retry_output.stdout)
-                    .trim_end_matches(['\r', '\n'])
-                    .to_string());
+                return Ok(String::from_utf8_lossy(&retry_output.stdout).into_owned());
             }
             let retry_stderr = String::from_utf8_lossy(&retry_output.stderr);
             bail!(
-                "gh issue view {issue} failed with configured auth: {}; retry with default auth also failed: {}",
+                "{command_line} failed with configured auth: {}; retry with default auth also failed: {}",
                 stderr.trim(),
                 retry_stderr.trim()
             );
         }
-        bail!("gh issue view {issue} failed: {}", stderr.trim());
+        bail!("{command_line} failed: {}", stderr.trim());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout)
-        .trim_end_matches(['\r', '\n'])
-        .to_string())
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// One `gh` invocation; `default_auth` drops any inherited `GH_CONFIG_DIR`.
+async fn run_gh(
+    cwd: &Path,
+    args: &[&str],
+    input: Option<&str>,
+    configured_env: Option<&(String, String)>,
+    default_auth: bool,
+) -> std::io::Result<std::process::Output> {
+    let mut command = tokio::process::Command::new("gh");
+    command.current_dir(cwd).args(args);
+    if let Some((key, value)) = configured_env {
+        command.env(key, value);
+    } else if default_auth {
+        command.env_remove("GH_CONFIG_DIR");
+    }
+    let Some(input) = input else {
+        return command.output().await;
+    };
+    command
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).await?;
+    }
+    child.wait_with_output().await
 }
 
 #[cfg(test)]
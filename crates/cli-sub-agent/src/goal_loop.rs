@@ -6,6 +6,7 @@ use anyhow::Result;
 use csa_core::types::{OutputFormat, ToolArg};
 
 use crate::run_resource_overrides::RunResourceOverrides;
+use crate::run_worktree::WorktreeOutcome;
 use crate::startup_env::StartupSubtreeEnv;
 
 pub(crate) struct GoalLoop {
@@ -104,6 +105,7 @@ pub(crate) struct GoalRunRequest {
     pub(crate) no_idle_timeout: bool,
     pub(crate) no_memory: bool,
     pub(crate) memory_query: Option<String>,
+    pub(crate) explain_memory: bool,
     pub(crate) current_depth: u32,
     pub(crate) output_format: OutputFormat,
     pub(crate) stream_mode: csa_process::StreamMode,
@@ -125,6 +127,8 @@ pub(crate) struct GoalRunRequest {
     pub(crate) extra_writable: Vec<PathBuf>,
     pub(crate) extra_readable: Vec<PathBuf>,
     pub(crate) startup_env: StartupSubtreeEnv,
+    /// `--isolated-worktree`: run in a temporary git worktree of HEAD.
+    pub(crate) isolated_worktree: Option<WorktreeOutcome>,
 }
 
 fn effective_require_commit(require_commit: bool, skill: Option<&str>) -> bool {
@@ -145,7 +149,15 @@ struct IterationResult {
     tokens_used: u64,
 }
 
-pub(crate) async fn handle_run_or_goal(request: GoalRunRequest) -> Result<i32> {
+pub(crate) async fn handle_run_or_goal(mut request: GoalRunRequest) -> Result<i32> {
+    if let Some(outcome) = request.isolated_worktree.take() {
+        return crate::run_worktree::run_in_isolated_worktree(request, outcome).await;
+    }
+    dispatch_run_or_goal(request).await
+}
+
+/// Run or goal-loop `request` in its own working directory.
+pub(crate) async fn dispatch_run_or_goal(request: GoalRunRequest) -> Result<i32> {
     if request.goal_criteria.is_some() {
         return handle_goal_run(request).await;
     }
@@ -189,6 +201,7 @@ pub(crate) async fn handle_run_or_goal(request: GoalRunRequest) -> Result<i32> {
         request.no_idle_timeout,
         request.no_memory,
         request.memory_query,
+        request.explain_memory,
         request.current_depth,
         request.output_format,
         request.stream_mode,
@@ -391,6 +404,7 @@ async fn run_goal_iteration(
         request.no_idle_timeout,
         request.no_memory,
         request.memory_query.clone(),
+        request.explain_memory,
         request.current_depth,
         request.output_format,
         request.stream_mode,
@@ -0,0 +1,255 @@
+//! `csa install-hooks`: gate commits and pushes on `csa review`.
+//!
+//! The hooks are plain shell scripts written into the repository's hooks
+//! directory (`git rev-parse --git-path hooks`, so `core.hooksPath` is honored).
+//! A pre-existing hook is moved aside to `<hook>.csa-chained` and run first with
+//! the same arguments and stdin; `--uninstall` moves it back.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use csa_core::types::ReviewFailOn;
+
+use crate::cli::{GitHookKind, InstallHooksArgs};
+
+const HOOK_MARKER: &str = "# Installed by: csa install-hooks";
+const CHAINED_SUFFIX: &str = ".csa-chained";
+
+const PRE_COMMIT_TEMPLATE: &str = r#"#!/bin/sh
+# Installed by: csa install-hooks (remove with `csa install-hooks --uninstall`)
+# Runs the hook this one replaced, then gates the commit on `csa review`.
+# Set CSA_SKIP_HOOKS=1 to bypass.
+
+chained="$(dirname "$0")/pre-commit.csa-chained"
+if [ -x "$chained" ]; then
+    "$chained" "$@" || exit $?
+fi
+
+@GUARDS@
+
+git diff --cached --quiet && exit 0
+CSA_REVIEW_HOOK=1 exec csa review --sa-mode false --staged --fail-on @FAIL_ON@@REVIEW_ARGS@ </dev/null
+"#;
+
+const PRE_PUSH_TEMPLATE: &str = r#"#!/bin/sh
+# Installed by: csa install-hooks (remove with `csa install-hooks --uninstall`)
+# Runs the hook this one replaced, then gates the push on `csa review`.
+# Set CSA_SKIP_HOOKS=1 to bypass.
+
+input=$(cat)
+chained="$(dirname "$0")/pre-push.csa-chained"
+if [ -x "$chained" ]; then
+    printf '%s\n' "$input" | "$chained" "$@" || exit $?
+fi
+
+@GUARDS@
+
+printf '%s\n' "$input" | while read -r local_ref local_sha remote_ref remote_sha; do
+    [ -n "$local_sha" ] || continue
+    case "$local_sha" in *[!0]*) ;; *) continue ;; esac
+    case "$remote_sha" in
+        *[!0]*) scope="--range $remote_sha..$local_sha" ;;
+        *) scope="" ;;
+    esac
+    # shellcheck disable=SC2086
+    CSA_REVIEW_HOOK=1 csa review --sa-mode false $scope --fail-on @FAIL_ON@@REVIEW_ARGS@ </dev/null || exit 1
+done || exit 1
+"#;
+
+const GUARDS: &str = r#"if [ -n "${CSA_SKIP_HOOKS:-}" ]; then
+    echo "csa: review hook skipped (CSA_SKIP_HOOKS)" >&2
+    exit 0
+fi
+# CSA executors run their own review gates, and `csa review` may run this hook
+# as its quality gate; do not review recursively.
+if [ -n "${CSA_REVIEW_HOOK:-}" ] || [ -n "${CSA_SESSION_ID:-}" ] || [ "${CSA_DEPTH:-0}" != "0" ]; then
+    exit 0
+fi
+if ! command -v csa >/dev/null 2>&1; then
+    echo "csa: not found on PATH; review hook skipped" >&2
+    exit 0
+fi"#;
+
+/// What `install_hook` / `uninstall_hook` did to one hook.
+#[derive(Debug, PartialEq, Eq)]
+enum HookChange {
+    Installed,
+    InstalledChained,
+    Updated,
+    Unchanged,
+    Removed,
+    RemovedRestored,
+    NotInstalled,
+    Foreign,
+}
+
+impl HookChange {
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::Installed => "installed",
+            Self::InstalledChained => "installed; existing hook chained",
+            Self::Updated => "updated",
+            Self::Unchanged => "already installed",
+            Self::Removed => "removed",
+            Self::RemovedRestored => "removed; chained hook restored",
+            Self::NotInstalled => "not installed",
+            Self::Foreign => "not installed by csa; left untouched",
+        }
+    }
+}
+
+pub(crate) fn handle_install_hooks(args: InstallHooksArgs) -> Result<()> {
+    let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+    let hooks_dir = git_hooks_dir(&project_root)?;
+    let mut hooks = args.hooks;
+    if hooks.is_empty() {
+        hooks = if args.uninstall {
+            vec![GitHookKind::PreCommit, GitHookKind::PrePush]
+        } else {
+            vec![GitHookKind::PreCommit]
+        };
+    }
+    hooks.sort();
+    hooks.dedup();
+
+    for hook in hooks {
+        let change = if args.uninstall {
+            uninstall_hook(&hooks_dir, hook)?
+        } else {
+            let script = render_hook(hook, args.fail_on, args.review_args.as_deref())?;
+            install_hook(&hooks_dir, hook, &script)?
+        };
+        eprintln!("{}: {}", hook.file_name(), change.describe());
+    }
+    Ok(())
+}
+
+fn render_hook(
+    hook: GitHookKind,
+    fail_on: ReviewFailOn,
+    review_args: Option<&str>,
+) -> Result<String> {
+    let review_args = review_args.map(str::trim).unwrap_or_default();
+    if review_args.contains(['\n', '\r']) {
+        bail!("--review-args must be a single line");
+    }
+    let template = match hook {
+        GitHookKind::PreCommit => PRE_COMMIT_TEMPLATE,
+        GitHookKind::PrePush => PRE_PUSH_TEMPLATE,
+    };
+    let review_args = if review_args.is_empty() {
+        String::new()
+    } else {
+        format!(" {review_args}")
+    };
+    Ok(template
+        .replace("@GUARDS@", GUARDS)
+        .replace("@FAIL_ON@", fail_on.as_str())
+        .replace("@REVIEW_ARGS@", &review_args))
+}
+
+fn install_hook(hooks_dir: &Path, hook: GitHookKind, script: &str) -> Result<HookChange> {
+    let path = hooks_dir.join(hook.file_name());
+    let chained = chained_path(hooks_dir, hook);
+    let change = match fs::read(&path) {
+        Ok(existing) if is_csa_hook(&existing) => {
+            if existing == script.as_bytes() {
+                return Ok(HookChange::Unchanged);
+            }
+            HookChange::Updated
+        }
+        Ok(_) => {
+            if chained.symlink_metadata().is_ok() {
+                bail!(
+                    "Cannot chain {}: {} already exists",
+                    path.display(),
+                    chained.display()
+                );
+            }
+            fs::rename(&path, &chained)
+                .with_context(|| format!("Failed to move {} aside", path.display()))?;
+            HookChange::InstalledChained
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => HookChange::Installed,
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+    fs::write(&path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&path, perms)?;
+    }
+    Ok(change)
+}
+
+fn uninstall_hook(hooks_dir: &Path, hook: GitHookKind) -> Result<HookChange> {
+    let path = hooks_dir.join(hook.file_name());
+    let removed = match fs::read(&path) {
+        Ok(existing) if is_csa_hook(&existing) => {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            true
+        }
+        Ok(_) => return Ok(HookChange::Foreign),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    let chained = chained_path(hooks_dir, hook);
+    if chained.symlink_metadata().is_err() {
+        return Ok(if removed {
+            HookChange::Removed
+        } else {
+            HookChange::NotInstalled
+        });
+    }
+    fs::rename(&chained, &path)
+        .with_context(|| format!("Failed to restore {}", chained.display()))?;
+    Ok(HookChange::RemovedRestored)
+}
+
+fn is_csa_hook(content: &[u8]) -> bool {
+    let marker = HOOK_MARKER.as_bytes();
+    content.windows(marker.len()).any(|window| window == marker)
+}
+
+fn chained_path(hooks_dir: &Path, hook: GitHookKind) -> PathBuf {
+    hooks_dir.join(format!("{}{CHAINED_SUFFIX}", hook.file_name()))
+}
+
+fn git_hooks_dir(project_root: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_root)
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "csa install-hooks must run inside a git repository: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if dir.is_absolute() {
+        dir
+    } else {
+        project_root.join(dir)
+    })
+}
+
+#[cfg(test)]
+#[path = "install_hooks_cmd_tests.rs"]
+mod tests;
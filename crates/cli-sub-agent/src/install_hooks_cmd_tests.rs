@@ -0,0 +1,141 @@
+use super::*;
+use tempfile::TempDir;
+
+fn script(hook: GitHookKind) -> String {
+    render_hook(hook, ReviewFailOn::High, None).unwrap()
+}
+
+fn run_hook(path: &Path, env: &[(&str, &str)]) -> i32 {
+    let mut cmd = Command::new("sh");
+    cmd.arg(path)
+        .env_remove("CSA_SESSION_ID")
+        .env_remove("CSA_DEPTH")
+        .env_remove("CSA_SKIP_HOOKS")
+        .env_remove("CSA_REVIEW_HOOK")
+        .stdin(std::process::Stdio::null());
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+    cmd.status().unwrap().code().unwrap()
+}
+
+#[test]
+fn render_hook_applies_severity_and_review_args() {
+    let pre_commit = render_hook(
+        GitHookKind::PreCommit,
+        ReviewFailOn::Critical,
+        Some(" --tier tier-2-standard "),
+    )
+    .unwrap();
+    assert!(pre_commit.contains(HOOK_MARKER));
+    assert!(
+        pre_commit.contains(
+            "csa review --sa-mode false --staged --fail-on critical --tier tier-2-standard"
+        )
+    );
+    assert!(pre_commit.contains("CSA_REVIEW_HOOK=1 exec csa review"));
+    assert!(!pre_commit.contains("@GUARDS@"));
+
+    let pre_push = script(GitHookKind::PrePush);
+    assert!(pre_push.contains("--fail-on high </dev/null"));
+    assert!(pre_push.contains("pre-push.csa-chained"));
+    assert!(render_hook(GitHookKind::PreCommit, ReviewFailOn::Any, Some("a\nb")).is_err());
+}
+
+#[test]
+fn install_is_idempotent_and_updates_config() {
+    let dir = TempDir::new().unwrap();
+    let hooks = dir.path().join("hooks");
+    assert_eq!(
+        install_hook(
+            &hooks,
+            GitHookKind::PreCommit,
+            &script(GitHookKind::PreCommit)
+        )
+        .unwrap(),
+        HookChange::Installed
+    );
+    assert_eq!(
+        install_hook(
+            &hooks,
+            GitHookKind::PreCommit,
+            &script(GitHookKind::PreCommit)
+        )
+        .unwrap(),
+        HookChange::Unchanged
+    );
+    let stricter = render_hook(GitHookKind::PreCommit, ReviewFailOn::Any, None).unwrap();
+    assert_eq!(
+        install_hook(&hooks, GitHookKind::PreCommit, &stricter).unwrap(),
+        HookChange::Updated
+    );
+    assert!(!chained_path(&hooks, GitHookKind::PreCommit).exists());
+    assert_eq!(
+        uninstall_hook(&hooks, GitHookKind::PreCommit).unwrap(),
+        HookChange::Removed
+    );
+    assert_eq!(
+        uninstall_hook(&hooks, GitHookKind::PreCommit).unwrap(),
+        HookChange::NotInstalled
+    );
+}
+
+#[test]
+fn existing_hook_is_chained_and_restored() {
+    let dir = TempDir::new().unwrap();
+    let hooks = dir.path().to_path_buf();
+    let original = "#!/bin/sh\nexit 3\n";
+    let hook_path = hooks.join("pre-commit");
+    fs::write(&hook_path, original).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    assert_eq!(
+        install_hook(
+            &hooks,
+            GitHookKind::PreCommit,
+            &script(GitHookKind::PreCommit)
+        )
+        .unwrap(),
+        HookChange::InstalledChained
+    );
+    assert_eq!(
+        fs::read_to_string(chained_path(&hooks, GitHookKind::PreCommit)).unwrap(),
+        original
+    );
+    // The chained hook runs first and its failure blocks the commit.
+    assert_eq!(run_hook(&hook_path, &[]), 3);
+
+    assert_eq!(
+        uninstall_hook(&hooks, GitHookKind::PreCommit).unwrap(),
+        HookChange::RemovedRestored
+    );
+    assert_eq!(fs::read_to_string(&hook_path).unwrap(), original);
+    assert!(!chained_path(&hooks, GitHookKind::PreCommit).exists());
+}
+
+#[test]
+fn hook_skips_review_when_bypassed_or_inside_csa() {
+    let dir = TempDir::new().unwrap();
+    let hooks = dir.path().to_path_buf();
+    install_hook(&hooks, GitHookKind::PrePush, &script(GitHookKind::PrePush)).unwrap();
+    let hook_path = hooks.join("pre-push");
+    assert_eq!(run_hook(&hook_path, &[("CSA_SKIP_HOOKS", "1")]), 0);
+    assert_eq!(run_hook(&hook_path, &[("CSA_DEPTH", "1")]), 0);
+    assert_eq!(run_hook(&hook_path, &[("CSA_REVIEW_HOOK", "1")]), 0);
+}
+
+#[test]
+fn foreign_hook_is_left_alone_on_uninstall() {
+    let dir = TempDir::new().unwrap();
+    let hooks = dir.path().to_path_buf();
+    fs::write(hooks.join("pre-push"), "#!/bin/sh\nexit 0\n").unwrap();
+    assert_eq!(
+        uninstall_hook(&hooks, GitHookKind::PrePush).unwrap(),
+        HookChange::Foreign
+    );
+    assert!(hooks.join("pre-push").exists());
+}
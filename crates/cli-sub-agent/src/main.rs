@@ -1,9 +1,14 @@
 use anyhow::Result;
 use clap::Parser;
+#[cfg(feature = "acp")]
+mod acp_replay;
+#[cfg(feature = "acp")]
+mod acp_serve;
 mod arch_cmd;
 mod audit;
 mod audit_cmds;
 mod batch;
+mod bench_cmd;
 mod bug_class;
 mod build_jobs_env;
 mod caller_hints_tests;
@@ -13,6 +18,7 @@ mod checklist_cmd;
 mod claude_sub_agent_cmd;
 mod cli;
 mod codex_transcript_filter;
+mod complete_cmd;
 mod config_cmds;
 mod daemon_caller_hints;
 mod daemon_launch_state;
@@ -29,12 +35,14 @@ mod error_marker_scan;
 mod error_report;
 mod eval_cmd;
 mod executor_csa_guard;
+mod export_cmd;
 mod failover_trace;
 mod gc;
 mod gh_env;
 mod goal_loop;
 mod hooks_cmd;
 mod hunt_cmd;
+mod install_hooks_cmd;
 mod install_provenance;
 #[cfg(test)]
 mod main_auto_weave_tests;
@@ -43,8 +51,11 @@ mod mcp_hub;
 mod mcp_server;
 mod memory_capture;
 mod memory_cmd;
+mod memory_injection_report;
 mod memory_migrate;
 mod memory_soft_limit_recovery_display;
+mod memory_transfer;
+mod memory_usage_stats;
 mod merge_cmd;
 mod mktsk_cmd;
 mod no_provider_launch;
@@ -56,18 +67,19 @@ mod pipeline_execute;
 mod pipeline_handoff;
 mod pipeline_jj_journal;
 mod pipeline_post_exec;
+mod pipeline_pressure_throttle;
 mod pipeline_project_key;
 mod pipeline_sandbox;
 mod pipeline_transcript;
 mod plan_cmd;
 mod plan_cmd_daemon;
 mod plan_cmd_journal;
-mod plan_condition;
 mod plan_display;
 mod preflight_state_dir;
 mod preflight_symlink;
 mod process_exit;
 mod process_tree;
+mod prompt_cmd;
 mod push_cmd;
 mod recall_cmd;
 mod require_commit_recovery_display;
@@ -97,6 +109,7 @@ mod run_cmd_tool_selection;
 mod run_helpers;
 mod run_helpers_branch_guard;
 mod run_resource_overrides;
+mod run_worktree;
 #[cfg(test)]
 mod sa_mode_tests;
 mod self_update;
@@ -110,9 +123,11 @@ mod session_display_alias;
 mod session_fix_finding_recovery;
 mod session_guard;
 mod session_kill_diagnostics;
+mod session_notify;
 mod session_observability;
 mod session_outcome;
 mod session_provider_quota;
+mod session_resource_report;
 mod session_result_publish;
 mod session_resume_handoff;
 mod session_summary_text;
@@ -125,14 +140,18 @@ mod skill_repo;
 mod skill_resolver;
 mod skill_run_cmd;
 mod startup_env;
+mod stats_cmd;
 mod stdout_write;
 #[cfg(any(feature = "parallel-tasks", test))]
 pub mod task_lock;
+mod template_vars;
 #[cfg(test)]
 mod test_bounded_command;
 #[cfg(test)]
 mod test_env_lock;
 #[cfg(test)]
+mod test_review_findings;
+#[cfg(test)]
 mod test_session_sandbox;
 mod tier_model_fallback;
 mod tiers_cmd;
@@ -145,12 +164,16 @@ mod todo_persist_cmd;
 mod todo_ref_cmd;
 mod token_usage_display;
 mod tool_version;
+mod top_cmd;
 mod triage_cmd;
+mod tui_terminal;
+mod ui_cmd;
 mod untracked_size;
 mod verdict_exit_code;
 mod verify_cmd;
 #[cfg(test)]
 mod version_check_recipe_tests;
+mod watch_cmd;
 mod worktree_lock_root;
 mod xurl_cmd;
 #[cfg(test)]
@@ -160,7 +183,7 @@ include!("review_round10_exact_tests.rs");
 #[cfg(test)]
 include!("debate_cmd_exact_tests.rs");
 use cli::{
-    Cli, Commands, ConfigCommands, McpHubCommands, SetupCommands, TiersCommands,
+    Cli, Commands, ConfigCommands, McpHubCommands, SetupCommands, StatsCommands, TiersCommands,
     validate_command_args,
 };
 use csa_core::types::OutputFormat;
@@ -170,6 +193,7 @@ use main_bootstrap::{
     check_weave_lock_version_alignment, link_bug_class_pipeline, maybe_auto_weave_upgrade,
     migrate_legacy_xdg_paths_if_needed, resolve_effective_min_timeout,
 };
+use pipeline::prompt_guard::emit_sa_mode_caller_guard;
 pub(crate) use process_exit::exit_current_process;
 use process_exit::report_daemon_error_or_exit_code;
 use sa_mode::apply_sa_mode_prompt_guard;
@@ -198,12 +222,8 @@ async fn async_main(wait_caller_identity: session_cmds::WaitCallerIdentity) {
     }
 
     if let Err(err) = run(wait_caller_identity).await {
-        eprintln!("{}", error_report::render_user_facing_error(&err));
-        if let Some(hint) = error_hints::suggest_fix(&err) {
-            eprintln!();
-            eprintln!("{hint}");
-        }
-        exit_current_process(1);
+        process_exit::report_error(&err);
+        exit_current_process(process_exit::exit_code_for_error(&err).code());
     }
 }
 
@@ -220,9 +240,14 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
         .try_init()
         .ok();
 
-    let cli = Cli::parse_from(cli::normalize_epic_format_args(std::env::args_os()));
+    let cli = Cli::parse_from(cli::normalize_review_format_args(
+        cli::normalize_epic_format_args(std::env::args_os()),
+    ));
     let output_format = cli.format;
     let text_output = matches!(output_format, OutputFormat::Text);
+    if matches!(output_format, OutputFormat::Json) {
+        process_exit::enable_json_errors();
+    }
     let command = cli.command;
     run_resource_overrides::initialize_inherited_resource_overrides(
         startup_env.internal_invocation(),
@@ -248,7 +273,25 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
 
     migrate_legacy_xdg_paths_if_needed();
 
+    if let Ok(global) = csa_config::GlobalConfig::load()
+        && global.metrics.enabled
+        && let Some(path) = csa_config::MetricsConfig::events_path()
+    {
+        if let Err(err) = csa_config::paths::create_session_state_dir() {
+            tracing::warn!(error = %err, "Failed to create the session state directory");
+        }
+        csa_core::metrics::enable_spool(path);
+    }
+
     match command {
+        Commands::Run {
+            batch: Some(file),
+            cd,
+            ..
+        } => {
+            batch::handle_run_batch(file, cd, current_depth, &startup_env, output_format).await?;
+            emit_sa_mode_caller_guard(sa_mode_active, current_depth, text_output);
+        }
         Commands::Run {
             tool,
             auto_route,
@@ -259,6 +302,8 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             goal,
             prompt_flag,
             prompt_file,
+            prompt_name,
+            vars,
             inline_context_from_review_session,
             session,
             last,
@@ -290,6 +335,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             no_idle_timeout,
             no_memory,
             memory_query,
+            explain_memory,
             stream_stdout,
             no_stream_stdout,
             no_error_marker_scan,
@@ -306,11 +352,16 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             allow_user_daemon_ipc,
             extra_writable,
             extra_readable,
+            worktree,
             daemon: _daemon,
             no_daemon,
             daemon_child,
             session_id,
+            batch: _,
         } => {
+            let prompt_flag =
+                prompt_cmd::resolve_run_prompt(prompt_name.as_deref(), &vars, cd.as_deref())?
+                    .or(prompt_flag);
             run_cmd_preflight::run_early_pre_daemon_checks(
                 run_cmd_preflight::EarlyPreDaemonChecks {
                     prompt_file: prompt_file.as_deref(),
@@ -333,7 +384,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
                     startup_env: &startup_env,
                 },
             )?;
-            let effective_no_daemon = no_daemon || goal.is_some();
+            let effective_no_daemon = no_daemon || goal.is_some() || worktree.isolated_worktree;
             let wait_hint_provider =
                 daemon_caller_hints::explicit_wait_provider_from_launch_routing(
                     model_spec.as_deref(),
@@ -408,6 +459,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
                 no_idle_timeout,
                 no_memory,
                 memory_query,
+                explain_memory,
                 current_depth,
                 output_format,
                 stream_mode,
@@ -427,15 +479,12 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
                 extra_writable,
                 extra_readable,
                 startup_env: startup_env.clone(),
+                isolated_worktree: run_worktree::WorktreeOutcome::from_args(&worktree),
             })
             .await;
             let exit_code = report_daemon_error_or_exit_code(result, &mut daemon_guard);
             // Post-session SA mode reminder so caller sees constraint before next action.
-            crate::pipeline::prompt_guard::emit_sa_mode_caller_guard(
-                sa_mode_active,
-                current_depth,
-                text_output,
-            );
+            emit_sa_mode_caller_guard(sa_mode_active, current_depth, text_output);
             daemon_guard.finalize();
             exit_current_process(exit_code);
         }
@@ -483,14 +532,15 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
         Commands::Push(args) => push_cmd::handle_push(args)?,
         Commands::Merge(args) => merge_cmd::handle_merge(args)?,
         Commands::Audit { command } => {
-            audit_cmds::handle_audit(command)?;
+            audit_cmds::handle_audit(command, &startup_env)?;
         }
         Commands::Init {
             non_interactive,
             full,
             template,
+            from,
         } => {
-            config_cmds::handle_init(non_interactive, full, template)?;
+            config_cmds::handle_init(non_interactive, full, template, from)?;
         }
         Commands::Gc(args) => gc::handle_gc_args(args, output_format, startup_env.session_id())?,
         Commands::Config { cmd } => match cmd {
@@ -543,9 +593,16 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
                 run_cmd_daemon::DaemonSpawnOptions::default()
             }
             .with_wait_hint_provider(wait_hint_provider);
+            // SARIF goes to the caller's stdout and `--fail-on`/`--ci` gate on
+            // the exit code, so those reviews stay in the foreground.
+            let sarif_output = args.review_format == cli::ReviewFormat::Sarif;
+            let foreground_report = sarif_output || args.fail_on.is_some() || args.ci;
+            // `csa review baseline ...` only edits the baseline file, and
+            // `--apply-suggestions` asks on the caller's terminal.
+            let local_command = args.command.is_some() || args.apply_suggestions;
             let mut daemon_guard = run_cmd_daemon::check_daemon_flags(
                 "review",
-                args.no_daemon || args.check_verdict,
+                args.no_daemon || args.check_verdict || foreground_report || local_command,
                 args.daemon_child,
                 &args.session_id,
                 args.cd.as_deref(),
@@ -554,10 +611,10 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             )?;
             let result = review_cmd::handle_review(args, current_depth, &startup_env).await;
             let exit_code = report_daemon_error_or_exit_code(result, &mut daemon_guard);
-            crate::pipeline::prompt_guard::emit_sa_mode_caller_guard(
+            emit_sa_mode_caller_guard(
                 sa_mode_active,
                 current_depth,
-                text_output,
+                text_output && !sarif_output && !local_command,
             );
             daemon_guard.finalize();
             exit_current_process(exit_code);
@@ -585,11 +642,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             let result =
                 debate_cmd::handle_debate(args, current_depth, output_format, &startup_env).await;
             let exit_code = report_daemon_error_or_exit_code(result, &mut daemon_guard);
-            crate::pipeline::prompt_guard::emit_sa_mode_caller_guard(
-                sa_mode_active,
-                current_depth,
-                text_output,
-            );
+            emit_sa_mode_caller_guard(sa_mode_active, current_depth, text_output);
             daemon_guard.finalize();
             exit_current_process(exit_code);
         }
@@ -601,25 +654,39 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
         } => {
             eval_cmd::handle_eval(project, days, json)?;
         }
-        Commands::Doctor { subcommand } => {
-            doctor::dispatch_doctor(output_format, subcommand).await?
-        }
-        Commands::Batch {
-            file,
-            sa_mode: _,
-            cd,
+        Commands::Doctor {
+            subcommand,
+            fix,
             dry_run,
+        } => doctor::dispatch_doctor(output_format, subcommand, fix, dry_run).await?,
+        Commands::Batch {
+            file, cd, dry_run, ..
         } => {
             batch::handle_batch(file, cd, dry_run, current_depth, &startup_env).await?;
-            crate::pipeline::prompt_guard::emit_sa_mode_caller_guard(
-                sa_mode_active,
-                current_depth,
-                text_output,
-            );
+            emit_sa_mode_caller_guard(sa_mode_active, current_depth, text_output);
+        }
+        Commands::Bench(args) => {
+            bench_cmd::handle_bench(args, output_format, &startup_env).await?;
+            emit_sa_mode_caller_guard(sa_mode_active, current_depth, text_output);
         }
         Commands::McpServer => {
             mcp_server::run_mcp_server(&startup_env, wait_caller_identity).await?;
         }
+        #[cfg(feature = "acp")]
+        Commands::AcpServe { tool, tier } => {
+            acp_serve::run_acp_serve(acp_serve::AcpServeOptions { tool, tier }, &startup_env)
+                .await?;
+        }
+        #[cfg(not(feature = "acp"))]
+        Commands::AcpServe { .. } => {
+            anyhow::bail!("csa was built without the `acp` feature; acp-serve is unavailable");
+        }
+        #[cfg(feature = "acp")]
+        Commands::Acp { cmd } => acp_replay::handle_acp_command(cmd).await?,
+        #[cfg(not(feature = "acp"))]
+        Commands::Acp { .. } => {
+            anyhow::bail!("csa was built without the `acp` feature; acp commands are unavailable");
+        }
         Commands::McpHub { cmd } => match cmd {
             McpHubCommands::Serve {
                 background,
@@ -627,6 +694,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
                 socket,
                 http_bind,
                 http_port,
+                http_tokens,
                 systemd_activation,
             } => {
                 mcp_hub::handle_serve_command(
@@ -635,18 +703,19 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
                     socket,
                     http_bind,
                     http_port,
+                    http_tokens,
                     systemd_activation,
                 )
                 .await?;
             }
-            McpHubCommands::Status { socket } => {
-                mcp_hub::handle_status_command(socket).await?;
+            McpHubCommands::Status { socket, stats } => {
+                mcp_hub::handle_status_command(socket, stats).await?;
             }
             McpHubCommands::Stop { socket } => {
                 mcp_hub::handle_stop_command(socket).await?;
             }
-            McpHubCommands::GenSkill { socket } => {
-                mcp_hub::handle_gen_skill_command(socket).await?;
+            McpHubCommands::GenSkill { socket, update } => {
+                mcp_hub::handle_gen_skill_command(socket, update).await?;
             }
         },
         Commands::Skill { cmd } => {
@@ -672,9 +741,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             }
         },
         Commands::Tiers { cmd } => match cmd {
-            TiersCommands::List { cd } => {
-                tiers_cmd::handle_tiers_list(cd, output_format)?;
-            }
+            TiersCommands::List { cd } => tiers_cmd::handle_tiers_list(cd, output_format)?,
         },
         Commands::Todo { cmd } => todo_dispatch_cmd::handle_todo_command(cmd, output_format)?,
         Commands::Checklist { command } => checklist_cmd::handle_checklist_command(command)?,
@@ -694,11 +761,7 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             let exit_code =
                 claude_sub_agent_cmd::handle_claude_sub_agent(args, current_depth, &startup_env)
                     .await?;
-            crate::pipeline::prompt_guard::emit_sa_mode_caller_guard(
-                sa_mode_active,
-                current_depth,
-                text_output,
-            );
+            emit_sa_mode_caller_guard(sa_mode_active, current_depth, text_output);
             exit_current_process(exit_code);
         }
         Commands::Tokuin { cmd } => cli::handle_tokuin(cmd)?,
@@ -707,9 +770,26 @@ async fn run(wait_caller_identity: session_cmds::WaitCallerIdentity) -> Result<(
             exit_current_process(exit_code);
         }
         Commands::Health(args) => cli::handle_health(args)?,
+        Commands::Top(args) => top_cmd::handle_top(args)?,
+        Commands::Ps(args) => top_cmd::handle_ps(args, output_format)?,
+        Commands::Kill(args) => top_cmd::handle_kill(args)?,
+        Commands::Watch(args) => watch_cmd::handle_watch(args).await?,
+        Commands::Ui(args) => ui_cmd::handle_ui(args)?,
+        Commands::Completions(args) => complete_cmd::handle_completions(args),
+        Commands::Complete(args) => complete_cmd::handle_complete(args),
+        Commands::Stats { cmd, usage } => match cmd {
+            Some(StatsCommands::Memory {
+                tool,
+                half_life_days,
+            }) => stats_cmd::handle_stats_memory(tool.as_deref(), half_life_days, output_format)?,
+            None => stats_cmd::handle_stats_usage(usage, output_format)?,
+        },
         Commands::Xurl { cmd } => xurl_cmd::handle_xurl(cmd, output_format)?,
         Commands::Recall(args) => recall_cmd::handle_recall(args.cmd)?,
+        Commands::Prompt(args) => prompt_cmd::handle_prompt(args.cmd, output_format)?,
         Commands::Hooks { cmd } => hooks_cmd::handle_hooks(cmd)?,
+        Commands::InstallHooks(args) => install_hooks_cmd::handle_install_hooks(args)?,
+        Commands::Export(args) => export_cmd::handle_export(args.cmd)?,
     }
 
     Ok(())
@@ -39,8 +39,11 @@ pub(crate) fn should_attempt_auto_weave_upgrade(command: &Commands) -> bool {
         // Review is a gate: stale weave.lock should warn, not rewrite the repo
         // before verdict artifacts are produced.
         Commands::Review(_) => false,
-        Commands::Debate(_) | Commands::Batch { .. } | Commands::Plan { .. } => true,
-        Commands::ClaudeSubAgent(_) | Commands::McpServer => true,
+        Commands::Debate(_)
+        | Commands::Batch { .. }
+        | Commands::Bench(_)
+        | Commands::Plan { .. } => true,
+        Commands::ClaudeSubAgent(_) | Commands::McpServer | Commands::AcpServe { .. } => true,
         _ => false,
     }
 }
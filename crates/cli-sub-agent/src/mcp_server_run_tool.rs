@@ -5,6 +5,7 @@ use serde_json::Value;
 use tempfile::TempDir;
 
 use crate::pipeline::{AdmittedExecutor, ConfigRefs, DispatchExecutor};
+pub(super) use crate::run_helpers::parse_tool_name;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct McpModelPinResolution {
@@ -296,10 +297,10 @@ pub(super) async fn handle_run_tool(
 
 fn is_tool_availability_error(error: &anyhow::Error) -> bool {
     error.chain().any(|cause| {
-        let message = cause.to_string();
-        message == "Failed to execute 'which' command"
-            || (message.starts_with("Tool '")
-                && message.ends_with(" is not installed or not in PATH"))
+        matches!(
+            cause.downcast_ref::<csa_core::error::AppError>(),
+            Some(csa_core::error::AppError::ToolNotInstalled(_))
+        ) || cause.to_string() == "Failed to execute 'which' command"
     })
 }
 
@@ -355,20 +356,6 @@ pub(super) fn resolve_mcp_model_pin(
     }
 }
 
-/// Parse tool name from string.
-pub(super) fn parse_tool_name(tool_str: &str) -> Result<ToolName> {
-    match tool_str {
-        "gemini-cli" | "gemini" => {
-            anyhow::bail!("{}", csa_core::types::removed_tool_error("gemini-cli"))
-        }
-        "opencode" => Ok(ToolName::Opencode),
-        "codex" => Ok(ToolName::Codex),
-        "claude-code" => Ok(ToolName::ClaudeCode),
-        "antigravity-cli" => Ok(ToolName::AntigravityCli),
-        _ => anyhow::bail!("Unknown tool: {tool_str}"),
-    }
-}
-
 pub(super) fn direct_entry_resolved_timeout(
     initial_response_timeout_seconds: Option<u64>,
 ) -> ResolvedTimeout {
@@ -7,12 +7,15 @@ use std::time::{Duration, Instant};
 use anyhow::{Context, Result};
 use csa_config::memory::MemoryConfig;
 use csa_memory::{
-    ApiClient, MemoryEntry, MemoryIndex, MemoryLlmClient, MemorySource, MemoryStore, NoopClient,
-    SearchResult,
+    ApiClient, MemoryEntry, MemoryIndex, MemoryLlmClient, MemoryProvenance, MemorySource,
+    MemoryStore, NoopClient, SearchResult,
 };
 use ulid::Ulid;
 
-const APP_NAME: &str = "cli-sub-agent";
+use crate::memory_injection_report::{
+    MemoryCandidateDecision, MemoryCandidateSource, MemoryInjectionReport,
+};
+
 const OUTPUT_TRUNCATE_CHARS: usize = 500;
 const OUTPUT_LOG_SUMMARY_READ_BYTES: u64 = 2 * 1024;
 const INJECT_MAX_RESULTS: usize = 5;
@@ -21,6 +24,8 @@ const INJECT_FALLBACK_TERMS: usize = 10;
 const INJECT_SNIPPET_MAX_CHARS: usize = 200;
 const MEMPAL_CONTEXT_TIMEOUT: Duration = Duration::from_secs(50);
 const MEMPAL_CONTEXT_MAX_ITEMS: &str = "5";
+/// Changed files recorded as provenance per captured entry.
+const PROVENANCE_MAX_FILES: usize = 20;
 
 /// Capture memory from a completed session.
 pub async fn capture_session_memory(
@@ -29,9 +34,10 @@ pub async fn capture_session_memory(
     project_key: Option<&str>,
     tool: Option<&str>,
     session_id: Option<&str>,
+    changed_paths: &[String],
 ) -> Result<()> {
     let memory_dir = resolve_memory_base_dir();
-    let store = MemoryStore::new(memory_dir.clone());
+    let store = MemoryStore::new(memory_dir.clone()).with_secret_policy(config.secret_policy);
     let index_dir = memory_dir.join("index");
 
     capture_session_memory_to_store(
@@ -40,6 +46,7 @@ pub async fn capture_session_memory(
         project_key,
         tool,
         session_id,
+        changed_paths,
         &store,
         &index_dir,
     )
@@ -52,6 +59,7 @@ async fn capture_session_memory_to_store(
     project_key: Option<&str>,
     tool: Option<&str>,
     session_id: Option<&str>,
+    changed_paths: &[String],
     store: &MemoryStore,
     index_dir: &Path,
 ) -> Result<()> {
@@ -86,11 +94,21 @@ async fn capture_session_memory_to_store(
         source: MemorySource::PostRun,
         valid_from: Some(now),
         valid_until: None,
+        provenance: MemoryProvenance {
+            files: changed_paths
+                .iter()
+                .take(PROVENANCE_MAX_FILES)
+                .cloned()
+                .collect(),
+            ..MemoryProvenance::default()
+        },
     };
 
+    // Screen once up front so the index and the JSONL store see the same text.
+    let entry = csa_memory::screen_entry(&entry, config.secret_policy)?;
     store.append(&entry)?;
 
-    match MemoryIndex::open(index_dir) {
+    match MemoryIndex::open_seeded(index_dir, config.index_backend, store) {
         Ok(index) => {
             if let Err(err) = index.index_entry(&entry) {
                 tracing::warn!(error = %err, "Failed to index memory entry");
@@ -116,11 +134,19 @@ pub(crate) fn build_memory_section(
     config: &MemoryConfig,
     prompt: &str,
     project_key: Option<&str>,
+    report: &mut MemoryInjectionReport,
 ) -> Option<String> {
     let memory_dir = resolve_memory_base_dir();
     let store = MemoryStore::new(memory_dir.clone());
     let index_dir = memory_dir.join("index");
-    build_memory_section_from_store(config, prompt, project_key, &store, &index_dir)
+    build_memory_section_from_store_with_report(
+        config,
+        prompt,
+        project_key,
+        &store,
+        &index_dir,
+        report,
+    )
 }
 
 pub fn build_memory_section_from_mempal(
@@ -249,22 +275,43 @@ fn run_mempal_context(
     }
 }
 
+#[cfg(test)]
 fn build_memory_section_from_store(
     config: &MemoryConfig,
     prompt: &str,
     project_key: Option<&str>,
     store: &MemoryStore,
     index_dir: &Path,
+) -> Option<String> {
+    let mut report = MemoryInjectionReport::new("legacy", config.inject_token_budget);
+    build_memory_section_from_store_with_report(
+        config,
+        prompt,
+        project_key,
+        store,
+        index_dir,
+        &mut report,
+    )
+}
+
+fn build_memory_section_from_store_with_report(
+    config: &MemoryConfig,
+    prompt: &str,
+    project_key: Option<&str>,
+    store: &MemoryStore,
+    index_dir: &Path,
+    report: &mut MemoryInjectionReport,
 ) -> Option<String> {
     let query: String = prompt.chars().take(INJECT_QUERY_MAX_CHARS).collect();
     if query.trim().is_empty() {
+        report.skip("empty memory query");
         return None;
     }
 
     // Retrieve more candidates from BM25, then apply project filter before limiting.
     // This prevents cross-project entries from consuming the top-k window.
     let bm25_fetch_limit = INJECT_MAX_RESULTS * 4;
-    let mut results = match MemoryIndex::open(index_dir) {
+    let mut results = match MemoryIndex::open_seeded(index_dir, config.index_backend, store) {
         Ok(index) => match index.search(&query, bm25_fetch_limit) {
             Ok(search_results) => search_results,
             Err(err) => {
@@ -277,6 +324,7 @@ fn build_memory_section_from_store(
             Vec::new()
         }
     };
+    let mut source = MemoryCandidateSource::Bm25;
 
     if let Some(project_name) = project_key {
         match store.load_all() {
@@ -286,7 +334,19 @@ fn build_memory_section_from_store(
                     .filter(|entry| entry.project.as_deref() == Some(project_name))
                     .map(|entry| entry.id.to_string())
                     .collect();
-                results.retain(|result| allowed_ids.contains(&result.entry_id));
+                results.retain(|result| {
+                    let allowed = allowed_ids.contains(&result.entry_id);
+                    if !allowed {
+                        report.record(
+                            &result.entry_id,
+                            result.score,
+                            source,
+                            snippet_tokens(&result.snippet),
+                            MemoryCandidateDecision::OtherProject,
+                        );
+                    }
+                    allowed
+                });
             }
             Err(err) => {
                 // Fail closed: if we can't load entries to verify project scope,
@@ -297,6 +357,15 @@ fn build_memory_section_from_store(
         }
     }
 
+    for result in results.iter().skip(INJECT_MAX_RESULTS) {
+        report.record(
+            &result.entry_id,
+            result.score,
+            source,
+            snippet_tokens(&result.snippet),
+            MemoryCandidateDecision::BelowCutoff,
+        );
+    }
     results.truncate(INJECT_MAX_RESULTS);
 
     if results.is_empty() {
@@ -306,6 +375,7 @@ fn build_memory_section_from_store(
             .collect::<Vec<_>>()
             .join(" ");
         if fallback_query.trim().is_empty() {
+            report.skip("no indexed matches and no fallback keywords");
             return None;
         }
 
@@ -314,6 +384,7 @@ fn build_memory_section_from_store(
             .map(regex::escape)
             .collect::<Vec<_>>()
             .join("|");
+        source = MemoryCandidateSource::Keyword;
         results = store
             .quick_search(&term_pattern)
             .unwrap_or_default()
@@ -333,40 +404,69 @@ fn build_memory_section_from_store(
     }
 
     if results.is_empty() {
+        report.skip("no matching memories");
         return None;
     }
 
     let mut section = String::from("\n<!-- CSA:MEMORY -->\n");
     section.push_str("The following are relevant memories from previous sessions:\n\n");
 
+    // Results arrive ranked best-first. Greedily keep every entry that still fits
+    // the budget so one oversized memory does not starve smaller, lower-ranked ones.
     let mut token_estimate = 0u32;
     let mut appended = 0usize;
     for result in &results {
         let snippet = result.snippet.replace('\n', " ");
         if snippet.trim().is_empty() {
+            report.record(
+                &result.entry_id,
+                result.score,
+                source,
+                0,
+                MemoryCandidateDecision::EmptySnippet,
+            );
             continue;
         }
 
         let mut snippet_for_output = snippet;
-        let mut entry_tokens = ((snippet_for_output.chars().count() as u32) / 4).max(1);
+        let mut entry_tokens = snippet_tokens(&snippet_for_output);
+        let mut decision = MemoryCandidateDecision::Injected;
         if token_estimate.saturating_add(entry_tokens) > config.inject_token_budget {
             if appended > 0 {
-                break;
+                report.record(
+                    &result.entry_id,
+                    result.score,
+                    source,
+                    entry_tokens,
+                    MemoryCandidateDecision::OverBudget,
+                );
+                continue;
             }
 
             let max_chars = (config.inject_token_budget.saturating_mul(4)) as usize;
-            if max_chars == 0 {
-                break;
-            }
-
             snippet_for_output = snippet_for_output.chars().take(max_chars).collect();
             if snippet_for_output.trim().is_empty() {
-                break;
+                report.record(
+                    &result.entry_id,
+                    result.score,
+                    source,
+                    entry_tokens,
+                    MemoryCandidateDecision::OverBudget,
+                );
+                continue;
             }
-            entry_tokens = ((snippet_for_output.chars().count() as u32) / 4).max(1);
+            entry_tokens = snippet_tokens(&snippet_for_output);
             if token_estimate.saturating_add(entry_tokens) > config.inject_token_budget {
-                break;
+                report.record(
+                    &result.entry_id,
+                    result.score,
+                    source,
+                    entry_tokens,
+                    MemoryCandidateDecision::OverBudget,
+                );
+                continue;
             }
+            decision = MemoryCandidateDecision::Truncated;
         }
 
         let short_id: String = result.entry_id.chars().take(8).collect();
@@ -378,6 +478,13 @@ fn build_memory_section_from_store(
         section.push_str(&format!("- [{display_id}] {}\n", snippet_for_output.trim()));
         token_estimate = token_estimate.saturating_add(entry_tokens);
         appended += 1;
+        report.record(
+            &result.entry_id,
+            result.score,
+            source,
+            entry_tokens,
+            decision,
+        );
     }
 
     if appended == 0 {
@@ -388,6 +495,11 @@ fn build_memory_section_from_store(
     Some(section)
 }
 
+/// Rough token estimate (~4 chars per token) used for budget accounting.
+fn snippet_tokens(snippet: &str) -> u32 {
+    ((snippet.chars().count() as u32) / 4).max(1)
+}
+
 fn read_session_summary(session_dir: &Path) -> Result<String> {
     let summary_path = session_dir.join("output").join("summary.txt");
     if summary_path.is_file() {
@@ -442,304 +554,9 @@ fn create_llm_client(config: &MemoryConfig) -> Box<dyn MemoryLlmClient> {
 }
 
 fn resolve_memory_base_dir() -> PathBuf {
-    if let Some(project_dirs) = directories::ProjectDirs::from("", "", APP_NAME) {
-        return project_dirs
-            .state_dir()
-            .unwrap_or_else(|| project_dirs.data_local_dir())
-            .join("memory");
-    }
-
-    if let Some(base_dirs) = directories::BaseDirs::new() {
-        return base_dirs
-            .home_dir()
-            .join(".local")
-            .join("state")
-            .join(APP_NAME)
-            .join("memory");
-    }
-
-    std::env::temp_dir()
-        .join(format!("{APP_NAME}-state"))
-        .join("memory")
+    csa_memory::default_memory_base_dir()
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    use chrono::Utc;
-    use std::io::Write as _;
-    use tempfile::tempdir;
-    use ulid::Ulid;
-
-    fn test_memory_config(auto_capture: bool) -> MemoryConfig {
-        MemoryConfig {
-            auto_capture,
-            ..MemoryConfig::default()
-        }
-    }
-
-    fn make_entry(id: &str, project: Option<&str>, content: &str) -> MemoryEntry {
-        MemoryEntry {
-            id: id.parse::<Ulid>().expect("valid ULID"),
-            timestamp: Utc::now(),
-            project: project.map(str::to_string),
-            tool: Some("codex".to_string()),
-            session_id: Some(format!("session-{id}")),
-            tags: vec!["test".to_string()],
-            content: content.to_string(),
-            facts: vec!["fact".to_string()],
-            source: MemorySource::Manual,
-            valid_from: None,
-            valid_until: None,
-        }
-    }
-
-    #[tokio::test]
-    async fn test_capture_with_noop_client() {
-        let session_dir = tempdir().expect("create temp session dir");
-        let memory_dir = tempdir().expect("create temp memory dir");
-        let output_path = session_dir.path().join("output.log");
-        fs::write(&output_path, "Session completed with actionable output.")
-            .expect("write output.log");
-
-        let store = MemoryStore::new(memory_dir.path().to_path_buf());
-        let index_dir = memory_dir.path().join("index");
-        capture_session_memory_to_store(
-            &test_memory_config(true),
-            session_dir.path(),
-            Some("test-project"),
-            Some("codex"),
-            Some("01ARZ3NDEKTSV4RRFFQ69G5FAV"),
-            &store,
-            &index_dir,
-        )
-        .await
-        .expect("capture should succeed");
-
-        let memories_path = memory_dir.path().join("memories.jsonl");
-        assert!(memories_path.is_file());
-
-        let entries = store.load_all().expect("load entries");
-        assert_eq!(entries.len(), 1);
-        assert_eq!(entries[0].project.as_deref(), Some("test-project"));
-        assert_eq!(entries[0].tool.as_deref(), Some("codex"));
-        assert!(!entries[0].facts.is_empty());
-    }
-
-    #[tokio::test]
-    async fn test_capture_disabled() {
-        let session_dir = tempdir().expect("create temp session dir");
-        let memory_dir = tempdir().expect("create temp memory dir");
-        let output_path = session_dir.path().join("output.log");
-        fs::write(&output_path, "This output should not be persisted.").expect("write output.log");
-
-        let store = MemoryStore::new(memory_dir.path().to_path_buf());
-        let index_dir = memory_dir.path().join("index");
-        capture_session_memory_to_store(
-            &test_memory_config(false),
-            session_dir.path(),
-            Some("test-project"),
-            Some("codex"),
-            Some("01ARZ3NDEKTSV4RRFFQ69G5FAV"),
-            &store,
-            &index_dir,
-        )
-        .await
-        .expect("capture should return ok when disabled");
-
-        assert!(!memory_dir.path().join("memories.jsonl").exists());
-    }
-
-    #[tokio::test]
-    async fn test_capture_generates_unique_entry_ids_for_same_session() {
-        let session_dir = tempdir().expect("create temp session dir");
-        let memory_dir = tempdir().expect("create temp memory dir");
-        let output_path = session_dir.path().join("output.log");
-        fs::write(
-            &output_path,
-            "Session output for duplicate-id regression test.",
-        )
-        .expect("write output.log");
-
-        let store = MemoryStore::new(memory_dir.path().to_path_buf());
-        let index_dir = memory_dir.path().join("index");
-        let session_id = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
-
-        capture_session_memory_to_store(
-            &test_memory_config(true),
-            session_dir.path(),
-            Some("test-project"),
-            Some("codex"),
-            Some(session_id),
-            &store,
-            &index_dir,
-        )
-        .await
-        .expect("first capture should succeed");
-
-        capture_session_memory_to_store(
-            &test_memory_config(true),
-            session_dir.path(),
-            Some("test-project"),
-            Some("codex"),
-            Some(session_id),
-            &store,
-            &index_dir,
-        )
-        .await
-        .expect("second capture should succeed");
-
-        let entries = store.load_all().expect("load entries");
-        assert_eq!(entries.len(), 2);
-        assert_ne!(
-            entries[0].id, entries[1].id,
-            "entry id must be unique even when session_id repeats"
-        );
-        assert!(
-            entries
-                .iter()
-                .all(|entry| entry.session_id.as_deref() == Some(session_id))
-        );
-    }
-
-    #[test]
-    fn test_read_session_summary_from_output() {
-        let session_dir = tempdir().expect("create temp session dir");
-        let output_dir = session_dir.path().join("output");
-        fs::create_dir_all(&output_dir).expect("create output dir");
-
-        let summary_path = output_dir.join("summary.txt");
-        fs::write(&summary_path, "preferred summary").expect("write summary");
-        fs::write(
-            session_dir.path().join("result.toml"),
-            "summary = \"fallback summary\"",
-        )
-        .expect("write result.toml");
-        fs::write(session_dir.path().join("output.log"), "fallback output")
-            .expect("write output.log");
-
-        let summary = read_session_summary(session_dir.path()).expect("read session summary");
-        assert_eq!(summary, "preferred summary");
-    }
-
-    #[test]
-    fn test_read_session_summary_bounds_output_log_read() {
-        let session_dir = tempdir().expect("create temp session dir");
-        let prefix = "a".repeat(OUTPUT_LOG_SUMMARY_READ_BYTES as usize);
-        let marker = "THIS_SUFFIX_MUST_NOT_BE_READ";
-        fs::write(
-            session_dir.path().join("output.log"),
-            format!("{prefix}{marker}"),
-        )
-        .expect("write output.log");
-
-        let summary = read_session_summary(session_dir.path()).expect("read session summary");
-        assert_eq!(summary.len(), OUTPUT_TRUNCATE_CHARS);
-        assert!(
-            !summary.contains(marker),
-            "summary reader should only inspect the bounded prefix"
-        );
-    }
-
-    #[test]
-    fn test_build_memory_section_empty() {
-        let memory_dir = tempdir().expect("create temp memory dir");
-        let store = MemoryStore::new(memory_dir.path().to_path_buf());
-        let index_dir = memory_dir.path().join("index");
-        let config = MemoryConfig {
-            inject: true,
-            ..MemoryConfig::default()
-        };
-
-        let section = build_memory_section_from_store(
-            &config,
-            "search for memory entries",
-            Some("test-project"),
-            &store,
-            &index_dir,
-        );
-        assert!(section.is_none());
-    }
-
-    #[test]
-    fn test_build_memory_section_with_entries() {
-        let memory_dir = tempdir().expect("create temp memory dir");
-        let store = MemoryStore::new(memory_dir.path().to_path_buf());
-        let index_dir = memory_dir.path().join("index");
-        let config = MemoryConfig {
-            inject: true,
-            inject_token_budget: 2000,
-            ..MemoryConfig::default()
-        };
-
-        let entry = make_entry(
-            "01ARZ3NDEKTSV4RRFFQ69G5FAV",
-            Some("test-project"),
-            "session fixed token budget handling for memory injection",
-        );
-        store.append(&entry).expect("append memory entry");
-        let index = MemoryIndex::open(&index_dir).expect("open memory index");
-        index.index_entry(&entry).expect("index memory entry");
-
-        let section = build_memory_section_from_store(
-            &config,
-            "token budget injection",
-            Some("test-project"),
-            &store,
-            &index_dir,
-        )
-        .expect("memory section should be generated");
-
-        assert!(section.contains("<!-- CSA:MEMORY -->"));
-        assert!(section.contains("<!-- CSA:MEMORY:END -->"));
-        assert!(section.contains("- [01ARZ3ND]"));
-        assert!(section.contains("token budget handling"));
-    }
-
-    #[test]
-    fn test_build_memory_section_token_budget() {
-        let memory_dir = tempdir().expect("create temp memory dir");
-        let store = MemoryStore::new(memory_dir.path().to_path_buf());
-        let index_dir = memory_dir.path().join("index");
-        let config = MemoryConfig {
-            inject: true,
-            inject_token_budget: 6,
-            ..MemoryConfig::default()
-        };
-
-        let entry_a = make_entry(
-            "01ARZ3NDEKTSV4RRFFQ69G5FAV",
-            Some("test-project"),
-            "alpha memory one short",
-        );
-        let entry_b = make_entry(
-            "01ARZ3NDEKTSV4RRFFQ69G5FAW",
-            Some("test-project"),
-            "alpha memory two short",
-        );
-        store.append(&entry_a).expect("append entry A");
-        store.append(&entry_b).expect("append entry B");
-        let index = MemoryIndex::open(&index_dir).expect("open memory index");
-        index
-            .rebuild(&[entry_a.clone(), entry_b.clone()])
-            .expect("index entries");
-
-        let section = build_memory_section_from_store(
-            &config,
-            "alpha memory",
-            Some("test-project"),
-            &store,
-            &index_dir,
-        )
-        .expect("memory section should be generated");
-
-        let bullet_count = section
-            .lines()
-            .filter(|line| line.starts_with("- ["))
-            .count();
-        assert_eq!(bullet_count, 1, "token budget should limit to one memory");
-    }
-
-    include!("memory_capture_mempal_tests.rs");
-}
+#[path = "memory_capture_tests.rs"]
+mod tests;
@@ -0,0 +1,281 @@
+use super::*;
+
+use chrono::Utc;
+use std::io::Write as _;
+use tempfile::tempdir;
+use ulid::Ulid;
+
+fn test_memory_config(auto_capture: bool) -> MemoryConfig {
+    MemoryConfig {
+        auto_capture,
+        ..MemoryConfig::default()
+    }
+}
+
+fn make_entry(id: &str, project: Option<&str>, content: &str) -> MemoryEntry {
+    MemoryEntry {
+        id: id.parse::<Ulid>().expect("valid ULID"),
+        timestamp: Utc::now(),
+        project: project.map(str::to_string),
+        tool: Some("codex".to_string()),
+        session_id: Some(format!("session-{id}")),
+        tags: vec!["test".to_string()],
+        content: content.to_string(),
+        facts: vec!["fact".to_string()],
+        source: MemorySource::Manual,
+        valid_from: None,
+        valid_until: None,
+        provenance: MemoryProvenance::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_capture_with_noop_client() {
+    let session_dir = tempdir().expect("create temp session dir");
+    let memory_dir = tempdir().expect("create temp memory dir");
+    let output_path = session_dir.path().join("output.log");
+    fs::write(&output_path, "Session completed with actionable output.").expect("write output.log");
+
+    let store = MemoryStore::new(memory_dir.path().to_path_buf());
+    let index_dir = memory_dir.path().join("index");
+    capture_session_memory_to_store(
+        &test_memory_config(true),
+        session_dir.path(),
+        Some("test-project"),
+        Some("codex"),
+        Some("01ARZ3NDEKTSV4RRFFQ69G5FAV"),
+        &["src/lib.rs".to_string()],
+        &store,
+        &index_dir,
+    )
+    .await
+    .expect("capture should succeed");
+
+    let memories_path = memory_dir.path().join("memories.jsonl");
+    assert!(memories_path.is_file());
+
+    let entries = store.load_all().expect("load entries");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].project.as_deref(), Some("test-project"));
+    assert_eq!(entries[0].tool.as_deref(), Some("codex"));
+    assert!(!entries[0].facts.is_empty());
+    assert_eq!(entries[0].provenance.files, vec!["src/lib.rs".to_string()]);
+}
+
+#[tokio::test]
+async fn test_capture_disabled() {
+    let session_dir = tempdir().expect("create temp session dir");
+    let memory_dir = tempdir().expect("create temp memory dir");
+    let output_path = session_dir.path().join("output.log");
+    fs::write(&output_path, "This output should not be persisted.").expect("write output.log");
+
+    let store = MemoryStore::new(memory_dir.path().to_path_buf());
+    let index_dir = memory_dir.path().join("index");
+    capture_session_memory_to_store(
+        &test_memory_config(false),
+        session_dir.path(),
+        Some("test-project"),
+        Some("codex"),
+        Some("01ARZ3NDEKTSV4RRFFQ69G5FAV"),
+        &["src/lib.rs".to_string()],
+        &store,
+        &index_dir,
+    )
+    .await
+    .expect("capture should return ok when disabled");
+
+    assert!(!memory_dir.path().join("memories.jsonl").exists());
+}
+
+#[tokio::test]
+async fn test_capture_generates_unique_entry_ids_for_same_session() {
+    let session_dir = tempdir().expect("create temp session dir");
+    let memory_dir = tempdir().expect("create temp memory dir");
+    let output_path = session_dir.path().join("output.log");
+    fs::write(
+        &output_path,
+        "Session output for duplicate-id regression test.",
+    )
+    .expect("write output.log");
+
+    let store = MemoryStore::new(memory_dir.path().to_path_buf());
+    let index_dir = memory_dir.path().join("index");
+    let session_id = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+
+    capture_session_memory_to_store(
+        &test_memory_config(true),
+        session_dir.path(),
+        Some("test-project"),
+        Some("codex"),
+        Some(session_id),
+        &[],
+        &store,
+        &index_dir,
+    )
+    .await
+    .expect("first capture should succeed");
+
+    capture_session_memory_to_store(
+        &test_memory_config(true),
+        session_dir.path(),
+        Some("test-project"),
+        Some("codex"),
+        Some(session_id),
+        &[],
+        &store,
+        &index_dir,
+    )
+    .await
+    .expect("second capture should succeed");
+
+    let entries = store.load_all().expect("load entries");
+    assert_eq!(entries.len(), 2);
+    assert_ne!(
+        entries[0].id, entries[1].id,
+        "entry id must be unique even when session_id repeats"
+    );
+    assert!(
+        entries
+            .iter()
+            .all(|entry| entry.session_id.as_deref() == Some(session_id))
+    );
+}
+
+#[test]
+fn test_read_session_summary_from_output() {
+    let session_dir = tempdir().expect("create temp session dir");
+    let output_dir = session_dir.path().join("output");
+    fs::create_dir_all(&output_dir).expect("create output dir");
+
+    let summary_path = output_dir.join("summary.txt");
+    fs::write(&summary_path, "preferred summary").expect("write summary");
+    fs::write(
+        session_dir.path().join("result.toml"),
+        "summary = \"fallback summary\"",
+    )
+    .expect("write result.toml");
+    fs::write(session_dir.path().join("output.log"), "fallback output").expect("write output.log");
+
+    let summary = read_session_summary(session_dir.path()).expect("read session summary");
+    assert_eq!(summary, "preferred summary");
+}
+
+#[test]
+fn test_read_session_summary_bounds_output_log_read() {
+    let session_dir = tempdir().expect("create temp session dir");
+    let prefix = "a".repeat(OUTPUT_LOG_SUMMARY_READ_BYTES as usize);
+    let marker = "THIS_SUFFIX_MUST_NOT_BE_READ";
+    fs::write(
+        session_dir.path().join("output.log"),
+        format!("{prefix}{marker}"),
+    )
+    .expect("write output.log");
+
+    let summary = read_session_summary(session_dir.path()).expect("read session summary");
+    assert_eq!(summary.len(), OUTPUT_TRUNCATE_CHARS);
+    assert!(
+        !summary.contains(marker),
+        "summary reader should only inspect the bounded prefix"
+    );
+}
+
+#[test]
+fn test_build_memory_section_empty() {
+    let memory_dir = tempdir().expect("create temp memory dir");
+    let store = MemoryStore::new(memory_dir.path().to_path_buf());
+    let index_dir = memory_dir.path().join("index");
+    let config = MemoryConfig {
+        inject: true,
+        ..MemoryConfig::default()
+    };
+
+    let section = build_memory_section_from_store(
+        &config,
+        "search for memory entries",
+        Some("test-project"),
+        &store,
+        &index_dir,
+    );
+    assert!(section.is_none());
+}
+
+#[test]
+fn test_build_memory_section_with_entries() {
+    let memory_dir = tempdir().expect("create temp memory dir");
+    let store = MemoryStore::new(memory_dir.path().to_path_buf());
+    let index_dir = memory_dir.path().join("index");
+    let config = MemoryConfig {
+        inject: true,
+        inject_token_budget: 2000,
+        ..MemoryConfig::default()
+    };
+
+    let entry = make_entry(
+        "01ARZ3NDEKTSV4RRFFQ69G5FAV",
+        Some("test-project"),
+        "session fixed token budget handling for memory injection",
+    );
+    store.append(&entry).expect("append memory entry");
+    let index = MemoryIndex::open(&index_dir).expect("open memory index");
+    index.index_entry(&entry).expect("index memory entry");
+
+    let section = build_memory_section_from_store(
+        &config,
+        "token budget injection",
+        Some("test-project"),
+        &store,
+        &index_dir,
+    )
+    .expect("memory section should be generated");
+
+    assert!(section.contains("<!-- CSA:MEMORY -->"));
+    assert!(section.contains("<!-- CSA:MEMORY:END -->"));
+    assert!(section.contains("- [01ARZ3ND]"));
+    assert!(section.contains("token budget handling"));
+}
+
+#[test]
+fn test_build_memory_section_token_budget() {
+    let memory_dir = tempdir().expect("create temp memory dir");
+    let store = MemoryStore::new(memory_dir.path().to_path_buf());
+    let index_dir = memory_dir.path().join("index");
+    let config = MemoryConfig {
+        inject: true,
+        inject_token_budget: 6,
+        ..MemoryConfig::default()
+    };
+
+    let entry_a = make_entry(
+        "01ARZ3NDEKTSV4RRFFQ69G5FAV",
+        Some("test-project"),
+        "alpha memory one short",
+    );
+    let entry_b = make_entry(
+        "01ARZ3NDEKTSV4RRFFQ69G5FAW",
+        Some("test-project"),
+        "alpha memory two short",
+    );
+    store.append(&entry_a).expect("append entry A");
+    store.append(&entry_b).expect("append entry B");
+    let index = MemoryIndex::open(&index_dir).expect("open memory index");
+    index
+        .rebuild(&[entry_a.clone(), entry_b.clone()])
+        .expect("index entries");
+
+    let section = build_memory_section_from_store(
+        &config,
+        "alpha memory",
+        Some("test-project"),
+        &store,
+        &index_dir,
+    )
+    .expect("memory section should be generated");
+
+    let bullet_count = section
+        .lines()
+        .filter(|line| line.starts_with("- ["))
+        .count();
+    assert_eq!(bullet_count, 1, "token budget should limit to one memory");
+}
+
+include!("memory_capture_mempal_tests.rs");
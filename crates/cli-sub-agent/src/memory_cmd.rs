@@ -1,19 +1,18 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Duration, NaiveDate, Utc};
-use csa_config::{GlobalConfig, MemoryConfig, ProjectConfig};
+use csa_config::{GlobalConfig, MemoryConfig, MemoryConflictPolicy, ProjectConfig};
 use csa_memory::{
-    ApiClient, MemoryEntry, MemoryFilter, MemoryIndex, MemoryLlmClient, MemorySource, MemoryStore,
-    execute_consolidation, plan_consolidation,
+    ApiClient, MemoryEntry, MemoryFilter, MemoryIndex, MemoryLlmClient, MemoryProvenance,
+    MemorySource, MemoryStore, detect_conflicts, execute_consolidation, plan_consolidation,
+    resolve_prefer_recent,
 };
 use ulid::Ulid;
 
 use crate::cli::MemoryCommands;
 
-const APP_NAME: &str = "cli-sub-agent";
-
 pub async fn handle_memory_command(command: MemoryCommands) -> Result<()> {
     match command {
         MemoryCommands::Search { query, limit, json } => handle_search(&query, limit, json),
@@ -24,11 +23,33 @@ pub async fn handle_memory_command(command: MemoryCommands) -> Result<()> {
             since,
             json,
         } => handle_list(project, tool, tag, since, json),
-        MemoryCommands::Add { content, tags } => handle_add(content, tags),
+        MemoryCommands::Add {
+            content,
+            tags,
+            finding,
+            files,
+        } => handle_add(
+            content,
+            tags,
+            MemoryProvenance {
+                finding_id: finding,
+                files,
+                ..MemoryProvenance::default()
+            },
+        ),
         MemoryCommands::Show { id } => handle_show(&id),
         MemoryCommands::Gc { days, dry_run } => handle_gc(days, dry_run),
         MemoryCommands::Reindex => handle_reindex(),
         MemoryCommands::Consolidate { dry_run } => handle_consolidate(dry_run).await,
+        MemoryCommands::Conflicts { resolve, json } => handle_conflicts(resolve, json),
+        MemoryCommands::Export { out, project } => {
+            crate::memory_transfer::export_memories(&memory_store(), &out, project)
+        }
+        MemoryCommands::Import {
+            path,
+            project,
+            dry_run,
+        } => handle_import(&path, project, dry_run),
         MemoryCommands::Migrate { to, dry_run, cd } => match to {
             crate::cli::MemoryMigrationTarget::Mempal => {
                 crate::memory_migrate::migrate_to_mempal(memory_store(), dry_run, cd)
@@ -108,6 +129,9 @@ fn handle_search(query: &str, limit: usize, json: bool) -> Result<()> {
             entry.project.as_deref().unwrap_or("-")
         );
         println!("   {}", truncate_chars(&entry.content, 80));
+        if let Some(provenance) = entry.provenance_summary() {
+            println!("   from: {}", truncate_chars(&provenance, 100));
+        }
         println!();
     }
 
@@ -172,7 +196,7 @@ fn handle_list(
     Ok(())
 }
 
-fn handle_add(content: String, tags: Option<String>) -> Result<()> {
+fn handle_add(content: String, tags: Option<String>, provenance: MemoryProvenance) -> Result<()> {
     let project_root = crate::pipeline::determine_project_root(None)?;
     let entry = MemoryEntry {
         id: Ulid::new(),
@@ -186,9 +210,11 @@ fn handle_add(content: String, tags: Option<String>) -> Result<()> {
         source: MemorySource::Manual,
         valid_from: None,
         valid_until: None,
+        provenance,
     };
 
-    let store = memory_store();
+    let store = screening_memory_store()?;
+    let entry = store.screen(&entry)?;
     store.append(&entry)?;
 
     if let Err(error) = open_memory_index().and_then(|index| index.index_entry(&entry)) {
@@ -228,6 +254,16 @@ fn handle_show(id_prefix: &str) -> Result<()> {
             .unwrap_or_else(|| "-".to_string())
     );
 
+    if let Some(finding_id) = &entry.provenance.finding_id {
+        println!("Finding: {finding_id}");
+    }
+    if !entry.provenance.files.is_empty() {
+        println!("Files: {}", entry.provenance.files.join(", "));
+    }
+    if !entry.provenance.derived_from.is_empty() {
+        println!("Derived From: {}", entry.provenance.derived_from.join(", "));
+    }
+
     if entry.tags.is_empty() {
         println!("Tags: -");
     } else {
@@ -273,7 +309,7 @@ fn handle_gc(days: u32, dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    memory_store().rewrite_all(&keep)?;
+    screening_memory_store()?.rewrite_all(&keep)?;
     open_memory_index()?.rebuild(&keep)?;
 
     println!(
@@ -287,14 +323,19 @@ fn handle_gc(days: u32, dry_run: bool) -> Result<()> {
 
 fn handle_reindex() -> Result<()> {
     let entries = memory_store().load_all()?;
-    open_memory_index()?.rebuild(&entries)?;
-    println!("Rebuilt memory index from {} entries.", entries.len());
+    let index = open_memory_index()?;
+    index.rebuild(&entries)?;
+    println!(
+        "Rebuilt {} memory index from {} entries.",
+        index.backend(),
+        entries.len()
+    );
     Ok(())
 }
 
 async fn handle_consolidate(dry_run: bool) -> Result<()> {
-    let store = memory_store();
     let config = load_memory_config()?;
+    let store = memory_store().with_secret_policy(config.secret_policy);
 
     if !config.llm.enabled || config.llm.base_url.is_empty() || config.llm.models.is_empty() {
         bail!(
@@ -320,6 +361,11 @@ async fn handle_consolidate(dry_run: bool) -> Result<()> {
         println!("  Entries before: {}", plan.total_before);
         println!("  Entries after:  {}", plan.total_after_estimate);
         println!("  Groups to merge: {}", plan.groups_to_merge.len());
+        println!(
+            "  Conflicting facts: {} (policy: {})",
+            plan.conflicts.len(),
+            config.conflict_policy
+        );
         for (idx, group) in plan.groups_to_merge.iter().enumerate() {
             let preview = truncate_chars(&group.merged_content_preview, 80);
             println!(
@@ -333,18 +379,94 @@ async fn handle_consolidate(dry_run: bool) -> Result<()> {
     }
 
     let index_dir = store.base_dir().join("index");
-    let index = MemoryIndex::open(&index_dir).ok();
+    let index = MemoryIndex::open_seeded(&index_dir, config.index_backend, &store).ok();
     let plan = execute_consolidation(
         &store,
         index.as_ref(),
         client.as_ref(),
         config.consolidation_threshold,
+        config.conflict_policy,
     )
     .await?;
     println!("Consolidation complete:");
     println!("  Entries before: {}", plan.total_before);
     println!("  Groups merged: {}", plan.groups_to_merge.len());
     println!("  Entries after (estimated): {}", plan.total_after_estimate);
+    if !plan.conflicts.is_empty() {
+        println!(
+            "  Conflicting facts: {} (policy: {})",
+            plan.conflicts.len(),
+            config.conflict_policy
+        );
+        if config.conflict_policy == MemoryConflictPolicy::Flag {
+            println!("  Review with `csa memory conflicts`.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_conflicts(resolve: bool, json: bool) -> Result<()> {
+    let store = screening_memory_store()?;
+    let mut entries = store.load_all()?;
+    let conflicts = detect_conflicts(&entries);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&conflicts)?);
+    } else if conflicts.is_empty() {
+        println!("No conflicting memory facts found.");
+    } else {
+        println!("Conflicting memory facts ({}):", conflicts.len());
+        for conflict in &conflicts {
+            println!();
+            println!(
+                "[{}] {}",
+                conflict.project.as_deref().unwrap_or("-"),
+                conflict.subject
+            );
+            for side in &conflict.sides {
+                println!(
+                    "  {}  {}  {}",
+                    short_id(&side.entry_id, 8),
+                    format_timestamp(side.timestamp),
+                    truncate_chars(&side.fact, 80)
+                );
+            }
+        }
+    }
+
+    if resolve && !conflicts.is_empty() {
+        let superseded = resolve_prefer_recent(&mut entries, &conflicts);
+        store.rewrite_all(&entries)?;
+        if let Err(error) = open_memory_index().and_then(|index| index.rebuild(&entries)) {
+            eprintln!(
+                "Warning: failed to rebuild memory index ({error}). Run `csa memory reindex`."
+            );
+        }
+        eprintln!(
+            "Resolved {} conflict(s); marked {superseded} stale fact(s) superseded.",
+            conflicts.len()
+        );
+    }
+    Ok(())
+}
+
+fn handle_import(path: &Path, project: Option<String>, dry_run: bool) -> Result<()> {
+    let store = screening_memory_store()?;
+    let imported = crate::memory_transfer::import_memories(&store, path, project, dry_run)?;
+    if dry_run || imported.is_empty() {
+        return Ok(());
+    }
+
+    let indexed = open_memory_index().and_then(|index| {
+        imported
+            .iter()
+            .try_for_each(|entry| index.index_entry(entry))
+    });
+    if let Err(error) = indexed {
+        bail!(
+            "memory entries imported but failed to update index: {error}. Run `csa memory reindex`."
+        );
+    }
     Ok(())
 }
 
@@ -359,6 +481,7 @@ fn handle_status() -> Result<()> {
     println!("  auto_capture: {}", config.auto_capture);
     println!("  inject:       {}", config.inject);
     println!("  inject_token_budget: {}", config.inject_token_budget);
+    println!("  index_backend: {}", config.index_backend);
     println!();
 
     match mempal_info {
@@ -461,30 +584,25 @@ fn memory_store() -> MemoryStore {
     MemoryStore::new(resolve_memory_base_dir())
 }
 
+/// Store for commands that write entries, screening them with the configured
+/// secret policy rather than the default.
+fn screening_memory_store() -> Result<MemoryStore> {
+    Ok(memory_store().with_secret_policy(load_memory_config()?.secret_policy))
+}
+
 fn open_memory_index() -> Result<MemoryIndex> {
-    MemoryIndex::open(&resolve_memory_base_dir().join("index"))
+    let backend = load_memory_config()
+        .map(|config| config.index_backend)
+        .unwrap_or_default();
+    MemoryIndex::open_seeded(
+        &resolve_memory_base_dir().join("index"),
+        backend,
+        &memory_store(),
+    )
 }
 
 fn resolve_memory_base_dir() -> PathBuf {
-    if let Some(project_dirs) = directories::ProjectDirs::from("", "", APP_NAME) {
-        return project_dirs
-            .state_dir()
-            .unwrap_or_else(|| project_dirs.data_local_dir())
-            .join("memory");
-    }
-
-    if let Some(base_dirs) = directories::BaseDirs::new() {
-        return base_dirs
-            .home_dir()
-            .join(".local")
-            .join("state")
-            .join(APP_NAME)
-            .join("memory");
-    }
-
-    std::env::temp_dir()
-        .join(format!("{APP_NAME}-state"))
-        .join("memory")
+    csa_memory::default_memory_base_dir()
 }
 
 fn short_id(id: &str, len: usize) -> String {
@@ -0,0 +1,222 @@
+//! Ranking transparency for memory injection (`csa run --explain-memory`).
+//!
+//! Every candidate considered for prompt injection is recorded together with
+//! the decision taken for it, so users can see which memories biased a prompt
+//! and which were dropped by the project filter or the token budget.
+
+use std::fmt::Write as _;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MemoryCandidateSource {
+    /// Ranked by the BM25 index.
+    Bm25,
+    /// Keyword fallback over the JSONL store (flat score).
+    Keyword,
+    /// Ranking delegated to the mempal backend.
+    Mempal,
+}
+
+impl MemoryCandidateSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Bm25 => "bm25",
+            Self::Keyword => "keyword",
+            Self::Mempal => "mempal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MemoryCandidateDecision {
+    Injected,
+    /// Injected after truncation to fit the remaining budget.
+    Truncated,
+    /// Dropped because it would exceed `memory.inject_token_budget`.
+    OverBudget,
+    /// Dropped because it belongs to a different project.
+    OtherProject,
+    /// Dropped because ranking produced more than the per-run result cap.
+    BelowCutoff,
+    EmptySnippet,
+}
+
+impl MemoryCandidateDecision {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Injected => "injected",
+            Self::Truncated => "injected (truncated to budget)",
+            Self::OverBudget => "dropped: over token budget",
+            Self::OtherProject => "dropped: other project",
+            Self::BelowCutoff => "dropped: below rank cutoff",
+            Self::EmptySnippet => "dropped: empty snippet",
+        }
+    }
+
+    pub(crate) fn is_injected(self) -> bool {
+        matches!(self, Self::Injected | Self::Truncated)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MemoryCandidate {
+    pub entry_id: String,
+    pub score: f32,
+    pub source: MemoryCandidateSource,
+    pub tokens: u32,
+    pub decision: MemoryCandidateDecision,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MemoryInjectionReport {
+    pub backend: String,
+    pub token_budget: u32,
+    pub tokens_used: u32,
+    pub candidates: Vec<MemoryCandidate>,
+    /// Reason injection was skipped entirely, if it was.
+    pub skipped: Option<String>,
+}
+
+impl MemoryInjectionReport {
+    pub(crate) fn new(backend: impl Into<String>, token_budget: u32) -> Self {
+        Self {
+            backend: backend.into(),
+            token_budget,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        entry_id: &str,
+        score: f32,
+        source: MemoryCandidateSource,
+        tokens: u32,
+        decision: MemoryCandidateDecision,
+    ) {
+        if decision.is_injected() {
+            self.tokens_used = self.tokens_used.saturating_add(tokens);
+        }
+        self.candidates.push(MemoryCandidate {
+            entry_id: entry_id.to_string(),
+            score,
+            source,
+            tokens,
+            decision,
+        });
+    }
+
+    pub(crate) fn skip(&mut self, reason: impl Into<String>) {
+        self.skipped = Some(reason.into());
+    }
+
+    pub(crate) fn injected_count(&self) -> usize {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.decision.is_injected())
+            .count()
+    }
+
+    /// Emit the report through tracing: one summary line plus a debug line per candidate.
+    pub(crate) fn log(&self) {
+        if let Some(reason) = &self.skipped {
+            tracing::debug!(backend = %self.backend, reason = %reason, "Memory injection skipped");
+            return;
+        }
+        tracing::info!(
+            backend = %self.backend,
+            candidates = self.candidates.len(),
+            injected = self.injected_count(),
+            tokens_used = self.tokens_used,
+            token_budget = self.token_budget,
+            "Memory injection ranking"
+        );
+        for candidate in &self.candidates {
+            tracing::debug!(
+                entry_id = %candidate.entry_id,
+                score = candidate.score,
+                source = candidate.source.as_str(),
+                tokens = candidate.tokens,
+                decision = candidate.decision.as_str(),
+                "Memory injection candidate"
+            );
+        }
+    }
+
+    /// Human-readable rendering printed to stderr for `--explain-memory`.
+    pub(crate) fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some(reason) = &self.skipped {
+            let _ = writeln!(
+                out,
+                "memory injection ({}): skipped: {reason}",
+                self.backend
+            );
+            return out;
+        }
+        let _ = writeln!(
+            out,
+            "memory injection ({}): {} of {} candidate(s) injected, ~{}/{} tokens",
+            self.backend,
+            self.injected_count(),
+            self.candidates.len(),
+            self.tokens_used,
+            self.token_budget
+        );
+        for (rank, candidate) in self.candidates.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  #{:<2} {} score={:.3} source={} tokens~{} -> {}",
+                rank + 1,
+                candidate.entry_id,
+                candidate.score,
+                candidate.source.as_str(),
+                candidate.tokens,
+                candidate.decision.as_str()
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_counts_only_injected_tokens() {
+        let mut report = MemoryInjectionReport::new("legacy", 100);
+        report.record(
+            "01A",
+            2.5,
+            MemoryCandidateSource::Bm25,
+            40,
+            MemoryCandidateDecision::Injected,
+        );
+        report.record(
+            "01B",
+            1.0,
+            MemoryCandidateSource::Bm25,
+            80,
+            MemoryCandidateDecision::OverBudget,
+        );
+
+        assert_eq!(report.tokens_used, 40);
+        assert_eq!(report.injected_count(), 1);
+        let rendered = report.render();
+        assert!(rendered.contains("1 of 2 candidate(s) injected, ~40/100 tokens"));
+        assert!(
+            rendered
+                .contains("01B score=1.000 source=bm25 tokens~80 -> dropped: over token budget")
+        );
+    }
+
+    #[test]
+    fn render_reports_skip_reason() {
+        let mut report = MemoryInjectionReport::new("legacy", 100);
+        report.skip("memory.inject is disabled");
+        assert_eq!(
+            report.render(),
+            "memory injection (legacy): skipped: memory.inject is disabled\n"
+        );
+    }
+}
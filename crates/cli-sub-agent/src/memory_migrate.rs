@@ -211,7 +211,7 @@ fn run_mempal_ingest(
 mod tests {
     use super::*;
     use chrono::Utc;
-    use csa_memory::MemorySource;
+    use csa_memory::{MemoryProvenance, MemorySource};
     use ulid::Ulid;
 
     #[test]
@@ -229,6 +229,7 @@ mod tests {
             source: MemorySource::PostRun,
             valid_from: None,
             valid_until: None,
+            provenance: MemoryProvenance::default(),
         };
 
         let payload = build_mempal_payload(&entry);
@@ -422,6 +422,7 @@ mod tests {
             memory_max_mb: Some(10000),
             soft_limit_percent: Some(90),
             scope_name: Some("csa-codex-01KW641KP78VR43SCKJVN6HGDN.scope".to_string()),
+            partial_results_saved: false,
         };
 
         let rendered = format_memory_soft_limit_recovery_lines_for_session(
@@ -470,6 +471,7 @@ mod tests {
             memory_max_mb: Some(10000),
             soft_limit_percent: Some(90),
             scope_name: None,
+            partial_results_saved: false,
         };
 
         let guidance = build_memory_soft_limit_recovery_guidance(
@@ -0,0 +1,62 @@
+//! `csa memory export` / `csa memory import`: portable JSONL knowledge bases.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csa_memory::{MemoryEntry, MemoryFilter, MemoryStore};
+
+pub fn export_memories(store: &MemoryStore, out: &Path, project: Option<String>) -> Result<()> {
+    let mut entries = store.list(MemoryFilter {
+        project,
+        ..MemoryFilter::default()
+    })?;
+    // `list` is newest-first; exports read more naturally in capture order.
+    entries.reverse();
+
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(out)
+        .with_context(|| format!("failed to create memory export: {}", out.display()))?;
+    let written = csa_memory::export_entries(&entries, &mut BufWriter::new(file))?;
+    println!(
+        "Exported {written} memory entries to {} (schema v{}).",
+        out.display(),
+        csa_memory::MEMORY_EXPORT_SCHEMA_VERSION
+    );
+    Ok(())
+}
+
+/// Import an export file and return the entries that were (or would be) added.
+pub fn import_memories(
+    store: &MemoryStore,
+    path: &Path,
+    project: Option<String>,
+    dry_run: bool,
+) -> Result<Vec<MemoryEntry>> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open memory export: {}", path.display()))?;
+    let (header, mut entries) = csa_memory::read_export(BufReader::new(file))
+        .with_context(|| format!("failed to read memory export: {}", path.display()))?;
+    if let Some(project) = project {
+        for entry in &mut entries {
+            entry.project = Some(project.clone());
+        }
+    }
+
+    let summary = csa_memory::import_entries(store, entries, dry_run)?;
+    println!(
+        "{} {} memory entries from {} (schema v{}, exported {}); skipped {} already present, {} expired.",
+        if dry_run { "Would import" } else { "Imported" },
+        summary.imported.len(),
+        path.display(),
+        header.schema_version,
+        header.exported_at.to_rfc3339(),
+        summary.skipped_existing,
+        summary.skipped_expired
+    );
+    Ok(summary.imported)
+}
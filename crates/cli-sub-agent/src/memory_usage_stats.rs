@@ -0,0 +1,59 @@
+//! Peak-memory history kept in `usage-stats.toml` under the current user's
+//! CSA state dir.
+//!
+//! Post-exec records each run's peak, plus peak VRAM for GPU tools, keyed by
+//! `(tool, model, task_kind)`; pre-spawn admission and `csa stats memory`
+//! read decayed P95 estimates back.
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use csa_resource::usage_stats::{DEFAULT_HALF_LIFE, USAGE_STATS_FILE};
+use csa_resource::{MemoryEstimate, UsageKey, UsageStats};
+use tracing::warn;
+
+pub(crate) fn usage_stats_path() -> Option<PathBuf> {
+    csa_config::paths::session_state_dir().map(|dir| dir.join(USAGE_STATS_FILE))
+}
+
+/// Stats key for a run; an absent task type is a plain `csa run`.
+pub(crate) fn usage_key(tool: &str, model: Option<&str>, task_type: Option<&str>) -> UsageKey {
+    UsageKey::new(tool, model, Some(task_type.unwrap_or("run")))
+}
+
+/// Best-effort: a failed write must never fail the run that produced it.
+pub(crate) fn record_peak_memory(key: UsageKey, peak_mb: u64, peak_vram_mb: Option<u64>) {
+    let Some(path) = usage_stats_path() else {
+        return;
+    };
+    if let Err(err) = csa_config::paths::create_session_state_dir()
+        .map_err(anyhow::Error::from)
+        .and_then(|()| {
+            UsageStats::record_to_file(&path, key.clone(), peak_mb, peak_vram_mb, SystemTime::now())
+        })
+    {
+        warn!(
+            key = %key,
+            path = %path.display(),
+            error = %err,
+            "Failed to record peak memory usage"
+        );
+    }
+}
+
+pub(crate) fn load_usage_stats() -> anyhow::Result<UsageStats> {
+    match usage_stats_path() {
+        Some(path) => UsageStats::load(&path),
+        None => Ok(UsageStats::default()),
+    }
+}
+
+pub(crate) fn historical_estimate(key: &UsageKey) -> Option<MemoryEstimate> {
+    match load_usage_stats() {
+        Ok(stats) => stats.estimate(key, SystemTime::now(), DEFAULT_HALF_LIFE),
+        Err(err) => {
+            warn!(key = %key, error = %err, "Failed to load memory usage stats");
+            None
+        }
+    }
+}
@@ -17,6 +17,44 @@ pub fn handle_migrate(dry_run: bool, status: bool) -> Result<()> {
     run_migrations(&project_dir, csa_version, weave_version, &registry, dry_run)
 }
 
+/// IDs of the migrations `csa migrate` would apply in `project_dir`.
+///
+/// Projects without a versioned weave.lock have nothing pending.
+pub(crate) fn pending_migration_ids(project_dir: &std::path::Path) -> Result<Vec<String>> {
+    let Some(lock) = csa_config::WeaveLock::load(project_dir)? else {
+        return Ok(Vec::new());
+    };
+    let Some(versions) = lock.versions() else {
+        return Ok(Vec::new());
+    };
+    let current: csa_config::Version = versions
+        .csa
+        .parse()
+        .with_context(|| format!("parsing lock version {:?}", versions.csa))?;
+    let csa_version = env!("CARGO_PKG_VERSION");
+    let target: csa_config::Version = csa_version
+        .parse()
+        .with_context(|| format!("parsing binary version {csa_version:?}"))?;
+    let registry = csa_config::default_registry();
+    Ok(registry
+        .pending(&current, &target, &lock.migrations.applied)
+        .into_iter()
+        .map(|migration| migration.id.clone())
+        .collect())
+}
+
+/// Apply pending migrations in `project_dir`, as `csa migrate` does.
+pub(crate) fn apply_pending_migrations(project_dir: &std::path::Path) -> Result<()> {
+    let version = env!("CARGO_PKG_VERSION");
+    run_migrations(
+        project_dir,
+        version,
+        version,
+        &csa_config::default_registry(),
+        false,
+    )
+}
+
 fn print_status(
     project_dir: &std::path::Path,
     csa_version: &str,
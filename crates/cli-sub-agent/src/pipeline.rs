@@ -13,6 +13,7 @@ use std::path::{Path, PathBuf};
 use tracing::{error, warn};
 
 use csa_config::{GlobalConfig, McpRegistry, ProjectConfig};
+use csa_core::error::AppError;
 use csa_core::types::ToolName;
 use csa_executor::{AcpMcpServerConfig, Executor};
 use csa_hooks::{HookEvent, run_hooks_for_event};
@@ -47,6 +48,7 @@ mod session_exec_failover;
 
 #[path = "pipeline_session_hooks.rs"]
 mod session_hooks;
+pub(crate) use session_hooks::build_project_hook_overrides;
 
 #[path = "pipeline_admitted_executor.rs"]
 mod admitted_executor;
@@ -66,12 +68,13 @@ pub(crate) mod lefthook_auto_install;
 #[allow(unused_imports)]
 pub(crate) use session_exec::{
     CleanRoomExecutionContract, CleanRoomExecutionLimits, execute_clean_room_session,
-    execute_with_session, execute_with_session_and_meta_with_parent_source,
+    execute_with_session, execute_with_session_and_meta,
+    execute_with_session_and_meta_with_parent_source,
 };
 #[cfg(test)]
 pub(crate) use session_exec::{
     CleanRoomSandboxInput, clean_room_execution_policy_effects, clean_room_runtime_prompt_for_test,
-    execute_with_session_and_meta, resolve_clean_room_sandbox_options_with_capabilities,
+    resolve_clean_room_sandbox_options_with_capabilities,
 };
 
 pub(crate) const DEFAULT_IDLE_TIMEOUT_SECONDS: u64 = 250;
@@ -475,7 +478,7 @@ pub(crate) async fn build_and_validate_executor(
             executor.install_hint(),
             executor.tool_name()
         );
-        anyhow::bail!("{e}");
+        return Err(e);
     }
     Ok(AdmittedExecutor::new(
         executor,
@@ -544,25 +547,34 @@ pub(crate) fn acquire_slot(
     executor: &Executor,
     global_config: &GlobalConfig,
 ) -> Result<csa_lock::slot::ToolSlot> {
-    let max_concurrent = global_config.max_concurrent(executor.tool_name());
+    acquire_tool_slot(executor.tool_name(), global_config)
+}
+
+/// [`acquire_slot`] by tool name, for tools without an [`Executor`] (plugins).
+pub(crate) fn acquire_tool_slot(
+    tool_name: &str,
+    global_config: &GlobalConfig,
+) -> Result<csa_lock::slot::ToolSlot> {
+    let max_concurrent = global_config.max_concurrent(tool_name);
     let slots_dir = GlobalConfig::slots_dir()?;
 
-    match csa_lock::slot::try_acquire_slot(&slots_dir, executor.tool_name(), max_concurrent, None) {
+    match csa_lock::slot::try_acquire_slot(&slots_dir, tool_name, max_concurrent, None) {
         Ok(csa_lock::slot::SlotAcquireResult::Acquired(slot)) => Ok(slot),
         Ok(csa_lock::slot::SlotAcquireResult::Exhausted(status)) => {
-            anyhow::bail!(
+            Err(anyhow::Error::new(AppError::SlotExhausted {
+                tool: tool_name.to_string(),
+                max: status.max_slots,
+                alternatives: Vec::new(),
+            })
+            .context(format!(
                 "All {} slots for '{}' occupied ({}/{}). Retry later, free slots with `csa gc`, or wait for an in-flight session to finish.",
                 max_concurrent,
-                executor.tool_name(),
+                tool_name,
                 status.occupied,
                 status.max_slots,
-            )
+            )))
         }
-        Err(e) => anyhow::bail!(
-            "Slot acquisition failed for '{}': {}",
-            executor.tool_name(),
-            e
-        ),
+        Err(e) => anyhow::bail!("Slot acquisition failed for '{}': {}", tool_name, e),
     }
 }
 
@@ -594,6 +606,8 @@ pub(crate) struct SessionExecutionResult {
 pub(crate) struct MemoryInjectionOptions {
     pub disabled: bool,
     pub query_override: Option<String>,
+    /// Print the memory injection report to stderr (`--explain-memory`).
+    pub explain: bool,
 }
 
 pub(crate) fn run_pipeline_hook(
@@ -656,13 +670,13 @@ pub(crate) fn capture_observational_hook_output(
 }
 
 pub(crate) fn determine_project_root(cd: Option<&str>) -> Result<PathBuf> {
-    let path = if let Some(cd_path) = cd {
-        PathBuf::from(cd_path)
-    } else {
-        std::env::current_dir()?
-    };
-
-    Ok(path.canonicalize()?)
+    if let Some(cd_path) = cd {
+        return PathBuf::from(cd_path).canonicalize().map_err(|err| {
+            anyhow::Error::new(AppError::ProjectRootNotFound)
+                .context(format!("--cd {cd_path}: {err}"))
+        });
+    }
+    Ok(std::env::current_dir()?.canonicalize()?)
 }
 
 #[cfg(test)]
@@ -6,6 +6,9 @@
 use std::collections::BTreeSet;
 use std::path::Path;
 
+use csa_core::transport_events::SessionEvent;
+use csa_session::{ChangedFile, FileAction};
+
 /// Fingerprint data extracted from a `GitWorkspaceSnapshot` for change detection.
 ///
 /// When status text is identical between pre/post snapshots, fingerprint
@@ -181,6 +184,43 @@ fn read_crate_name(cargo_toml_path: &Path) -> Option<String> {
     None
 }
 
+/// Files an ACP agent created or modified through CSA's client-side fs
+/// methods, relative to `project_root`. The first write of a path decides
+/// its action, so a file created and then edited stays an addition.
+pub(crate) fn client_fs_changed_files(
+    events: &[SessionEvent],
+    project_root: &Path,
+) -> Vec<ChangedFile> {
+    let mut changed: Vec<ChangedFile> = Vec::new();
+    for event in events {
+        let SessionEvent::FileAccess {
+            path,
+            operation,
+            status,
+        } = event
+        else {
+            continue;
+        };
+        let action = match operation.as_str() {
+            "create" => FileAction::Add,
+            "modify" => FileAction::Modify,
+            _ => continue,
+        };
+        let Ok(relative) = Path::new(path).strip_prefix(project_root) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().into_owned();
+        if status != "ok" || changed.iter().any(|known| known.path == relative) {
+            continue;
+        }
+        changed.push(ChangedFile {
+            path: relative,
+            action,
+        });
+    }
+    changed
+}
+
 /// Format changed paths as a JSON array string for the `{CHANGED_PATHS}` variable.
 pub(crate) fn format_changed_paths_json(paths: &[String]) -> String {
     serde_json::to_string(paths).unwrap_or_else(|_| "[]".to_string())
@@ -606,4 +646,37 @@ members = ["crates/*"]
     fn format_changed_crates_flags_empty() {
         assert_eq!(format_changed_crates_flags(&[]), "");
     }
+
+    #[test]
+    fn client_fs_changed_files_keeps_successful_writes_under_root() {
+        let access = |path: &str, operation: &str, status: &str| SessionEvent::FileAccess {
+            path: path.to_string(),
+            operation: operation.to_string(),
+            status: status.to_string(),
+        };
+        let events = [
+            access("/repo/src/new.rs", "create", "ok"),
+            access("/repo/src/new.rs", "modify", "ok"),
+            access("/repo/src/lib.rs", "read", "ok"),
+            access("/repo/src/main.rs", "modify", "ok"),
+            access("/repo/secret.txt", "modify", "denied"),
+            access("/elsewhere/a.rs", "create", "ok"),
+        ];
+
+        let changed = client_fs_changed_files(&events, Path::new("/repo"));
+
+        assert_eq!(
+            changed,
+            [
+                ChangedFile {
+                    path: "src/new.rs".to_string(),
+                    action: FileAction::Add,
+                },
+                ChangedFile {
+                    path: "src/main.rs".to_string(),
+                    action: FileAction::Modify,
+                },
+            ]
+        );
+    }
 }
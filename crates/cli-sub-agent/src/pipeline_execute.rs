@@ -55,6 +55,15 @@ pub(crate) async fn execute_transport_with_signal(
                 }
             };
             tokio::pin!(timeout_future);
+            let execution = executor.execute_with_transport(
+                effective_prompt,
+                tool_state,
+                session,
+                merged_env_ref,
+                execute_options,
+                session_config,
+            );
+            tokio::pin!(execution);
             tokio::select! {
                 _ = sigterm.recv() => {
                     warn!(
@@ -78,6 +87,7 @@ pub(crate) async fn execute_transport_with_signal(
                     ));
                 }
                 _ = sigint.recv() => {
+                    wind_down_interrupted_acp_turns(execution.as_mut(), session).await;
                     record_session_interruption_state(
                         project_root,
                         session,
@@ -114,14 +124,7 @@ pub(crate) async fn execute_transport_with_signal(
                         &summary,
                     ));
                 }
-                exec = executor.execute_with_transport(
-                    effective_prompt,
-                    tool_state,
-                    session,
-                    merged_env_ref,
-                    execute_options,
-                    session_config,
-                ) => exec,
+                exec = &mut execution => exec,
             }
         }
         #[cfg(not(unix))]
@@ -267,6 +270,29 @@ pub(crate) async fn execute_transport_with_signal(
     }
 }
 
+/// Give ACP turns in flight the chance to end with `session/cancel` before
+/// the interrupted run is torn down; agents that do not stop in time are
+/// signalled by the transport.
+#[cfg(unix)]
+async fn wind_down_interrupted_acp_turns<F: std::future::Future>(
+    execution: std::pin::Pin<&mut F>,
+    session: &MetaSessionState,
+) {
+    #[cfg(feature = "acp")]
+    if csa_executor::AcpTransport::interrupt_in_flight_prompts()
+        && tokio::time::timeout(csa_executor::AcpTransport::INTERRUPT_WAIT, execution)
+            .await
+            .is_err()
+    {
+        warn!(
+            session_id = %session.meta_session_id,
+            "Interrupted ACP turn did not end in time"
+        );
+    }
+    #[cfg(not(feature = "acp"))]
+    let _ = (execution, session);
+}
+
 pub(crate) async fn execute_clean_transport_with_signal(
     executor: &Executor,
     effective_prompt: &str,
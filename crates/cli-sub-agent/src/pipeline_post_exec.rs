@@ -55,10 +55,8 @@ mod result_sidecar;
 mod signal;
 // Re-exported privately so existing call sites (and the test submodule's
 // `use super::*`) reach these mechanical helpers unqualified.
-use helpers::{
-    is_codex_exec_initial_stall_summary, maybe_compress_tool_output, update_cumulative_tokens,
-    update_tool_state,
-};
+use helpers::{is_codex_exec_initial_stall_summary, maybe_compress_tool_output};
+pub(crate) use helpers::{update_cumulative_tokens, update_tool_state};
 use signal::record_signal_session_metadata;
 
 /// Process the results of tool execution: update session, persist artifacts, fire hooks.
@@ -109,6 +107,8 @@ pub(crate) async fn process_execution_result(
     // observed agent conversation turns; legacy transports leave it at `0` and
     // fall back to the historical `+= 1` per-invocation contract (#1438).
     session.turn_count = session.turn_count.saturating_add(ctx.turn_count.max(1));
+    // Recorded for `csa stats --by model`.
+    session.last_model = ctx.executor.model_override().map(str::to_string);
 
     let has_meaningful_reasoning_output =
         no_op::has_meaningful_reasoning_output(&token_usage, ctx.output_tokens);
@@ -127,6 +127,18 @@ pub(crate) async fn process_execution_result(
     );
     let classified_summary = result.summary.clone();
 
+    if let Some(peak_memory_mb) = result.peak_memory_mb {
+        crate::memory_usage_stats::record_peak_memory(
+            crate::memory_usage_stats::usage_key(
+                ctx.executor.tool_name(),
+                ctx.executor.model_override(),
+                ctx.task_type,
+            ),
+            peak_memory_mb,
+            result.peak_vram_mb,
+        );
+    }
+
     // Write structured result
     let execution_end_time = chrono::Utc::now();
     let mut session_result = SessionResult {
@@ -380,6 +392,12 @@ pub(crate) async fn process_execution_result(
         session.turn_count,
         &mut session_result,
     );
+    crate::session_resource_report::attach_resource_report(
+        &ctx.session_dir,
+        session,
+        result,
+        &mut session_result,
+    );
     if let Err(e) = crate::session_kill_diagnostics::save_result_with_signal_diagnostic(
         ctx.project_root,
         session,
@@ -478,6 +496,7 @@ pub(crate) async fn process_execution_result(
                     ctx.memory_project_key.as_deref(),
                     Some(ctx.executor.tool_name()),
                     Some(session.meta_session_id.as_str()),
+                    &ctx.changed_paths,
                 )
                 .await
                 {
@@ -492,6 +511,19 @@ pub(crate) async fn process_execution_result(
     if let Err(e) = run_hooks_for_event(HookEvent::SessionComplete, ctx.hooks_config, &hook_vars) {
         warn!("SessionComplete hook failed: {}", e);
     }
+    if crate::session_notify::pipeline_should_notify(session.genealogy.depth, ctx.task_type) {
+        crate::session_notify::notify_session_finished(
+            &session.meta_session_id,
+            ctx.executor.tool_name(),
+            ctx.project_root,
+            &session_result.status,
+            result.exit_code,
+            (session_result.completed_at - ctx.execution_start_time)
+                .num_seconds()
+                .max(0) as u64,
+        )
+        .await;
+    }
 
     if let Some(memory_config) = memory_config
         && matches!(
@@ -562,6 +594,11 @@ fn persist_output_sections(session_dir: &Path) {
     {
         warn!("Failed to persist structured output: {}", e);
     }
+    // Re-parsing output.log rebuilds the index, so re-add the partial return
+    // packet saved by a pre-kill memory checkpoint.
+    if let Err(e) = csa_session::merge_partial_return_packet(session_dir) {
+        warn!("Failed to merge partial return packet: {}", e);
+    }
 }
 
 #[path = "pipeline_post_exec_blocked.rs"]
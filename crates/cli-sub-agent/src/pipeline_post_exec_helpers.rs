@@ -73,7 +73,7 @@ pub(super) fn maybe_compress_tool_output(
 /// Record this turn's tool invocation into `session.tools`: refresh the
 /// provider session id, last summary/exit code, timestamp, and token usage,
 /// inserting a fresh [`ToolState`] when the tool is seen for the first time.
-pub(super) fn update_tool_state(
+pub(crate) fn update_tool_state(
     session: &mut MetaSessionState,
     tool_name: &str,
     provider_session_id: &Option<String>,
@@ -108,7 +108,7 @@ pub(super) fn update_tool_state(
 /// Fold this turn's `token_usage` into the session's cumulative totals and
 /// update token-budget tracking (advisory soft/hard threshold warnings).
 /// Missing per-field values must never zero out a previously recorded total.
-pub(super) fn update_cumulative_tokens(
+pub(crate) fn update_cumulative_tokens(
     session: &mut MetaSessionState,
     token_usage: Option<TokenUsage>,
 ) {
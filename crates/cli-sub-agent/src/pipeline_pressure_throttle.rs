@@ -0,0 +1,324 @@
+//! PSI-based adaptive throttling around a session run.
+//!
+//! Before spawning, a run waits (bounded by `psi_max_spawn_delay_seconds`)
+//! while host memory or CPU pressure is above the configured threshold.
+//! During the run, with `psi_pause_lowest_priority`, a monitor SIGSTOPs the
+//! tool's process group when this session is the most recently started of
+//! the live sessions, and SIGCONTs it once pressure subsides. Each action is
+//! returned as a [`PressureEvent`] for the session state.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use csa_config::ProjectConfig;
+use csa_process::ToolLiveness;
+use csa_resource::pressure::{PressureReading, PressureThresholds, wait_for_pressure_relief};
+use csa_session::{PressureAction, PressureEvent, SessionPhase};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+const DEFAULT_MAX_SPAWN_DELAY_SECS: u64 = 300;
+const PRESSURE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Present in a session dir while its tool is stopped, so other sessions'
+/// monitors skip it when choosing whom to pause next.
+const PRESSURE_PAUSED_MARKER: &str = ".pressure-paused";
+
+fn pressure_thresholds(config: Option<&ProjectConfig>) -> PressureThresholds {
+    let Some(config) = config else {
+        return PressureThresholds::default();
+    };
+    PressureThresholds {
+        memory_some_avg10: config.resources.psi_memory_threshold,
+        cpu_some_avg10: config.resources.psi_cpu_threshold,
+    }
+}
+
+/// Hold the spawn back while pressure is high. Returns the event to record
+/// when the spawn was delayed at all.
+pub(crate) async fn delay_spawn_for_pressure(
+    config: Option<&ProjectConfig>,
+) -> Option<PressureEvent> {
+    let thresholds = pressure_thresholds(config);
+    if !thresholds.is_enabled() {
+        return None;
+    }
+    let max_wait = Duration::from_secs(
+        config
+            .and_then(|cfg| cfg.resources.psi_max_spawn_delay_seconds)
+            .unwrap_or(DEFAULT_MAX_SPAWN_DELAY_SECS),
+    );
+    let mut first_sample = true;
+    let delay = wait_for_pressure_relief(thresholds, max_wait, PRESSURE_POLL_INTERVAL, || {
+        let reading = PressureReading::sample();
+        if std::mem::take(&mut first_sample)
+            && let Some(reason) = thresholds.exceeded(reading)
+        {
+            eprintln!(
+                "CSA: host pressure high ({reason}); delaying spawn up to {}s until it subsides.",
+                max_wait.as_secs()
+            );
+        }
+        reading
+    })
+    .await?;
+    crate::session_resource_report::record_queue_wait(delay.waited);
+
+    let action = if delay.timed_out {
+        crate::session_resource_report::record_limit_violation(format!(
+            "spawned despite host pressure: {}",
+            delay.reason
+        ));
+        warn!(
+            reason = %delay.reason,
+            waited_s = delay.waited.as_secs(),
+            "Spawning despite sustained host pressure"
+        );
+        PressureAction::SpawnDelayTimedOut
+    } else {
+        info!(
+            reason = %delay.reason,
+            waited_s = delay.waited.as_secs(),
+            "Host pressure subsided, spawning"
+        );
+        PressureAction::SpawnDelayed
+    };
+    Some(PressureEvent {
+        at: Utc::now(),
+        action,
+        reason: delay.reason,
+        duration_secs: Some(delay.waited.as_secs()),
+    })
+}
+
+/// Handle to a running pressure monitor. [`stop`](Self::stop) resumes the
+/// tool if it is paused and returns the recorded events.
+pub(crate) struct PressureMonitorHandle {
+    cancel_tx: watch::Sender<bool>,
+    join: Option<tokio::task::JoinHandle<Vec<PressureEvent>>>,
+}
+
+impl PressureMonitorHandle {
+    pub(crate) async fn stop(mut self) -> Vec<PressureEvent> {
+        let _ = self.cancel_tx.send(true);
+        match self.join.take() {
+            Some(join) => join.await.unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Drop for PressureMonitorHandle {
+    fn drop(&mut self) {
+        let _ = self.cancel_tx.send(true);
+    }
+}
+
+struct MonitorContext {
+    thresholds: PressureThresholds,
+    session_id: String,
+    session_dir: PathBuf,
+    /// Longest single pause; kept well under the idle timeout so a stopped
+    /// tool is not killed for producing no output.
+    max_pause: Duration,
+}
+
+/// Start the in-run monitor when pausing under pressure is enabled.
+pub(crate) fn start_pressure_monitor(
+    config: Option<&ProjectConfig>,
+    session_id: &str,
+    session_dir: &Path,
+    idle_timeout_seconds: u64,
+) -> Option<PressureMonitorHandle> {
+    let thresholds = pressure_thresholds(config);
+    let pause_enabled = config
+        .and_then(|cfg| cfg.resources.psi_pause_lowest_priority)
+        .unwrap_or(false);
+    if !pause_enabled || !thresholds.is_enabled() {
+        return None;
+    }
+    let context = MonitorContext {
+        thresholds,
+        session_id: session_id.to_string(),
+        session_dir: session_dir.to_path_buf(),
+        max_pause: Duration::from_secs(idle_timeout_seconds / 2).max(PRESSURE_POLL_INTERVAL),
+    };
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    let join = tokio::spawn(monitor_loop(context, cancel_rx));
+    Some(PressureMonitorHandle {
+        cancel_tx,
+        join: Some(join),
+    })
+}
+
+struct PausedTool {
+    groups: Vec<i32>,
+    since: Instant,
+}
+
+async fn monitor_loop(
+    context: MonitorContext,
+    mut cancel_rx: watch::Receiver<bool>,
+) -> Vec<PressureEvent> {
+    let mut events = Vec::new();
+    let mut paused: Option<PausedTool> = None;
+    let mut cooldown_until: Option<Instant> = None;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(PRESSURE_POLL_INTERVAL) => {}
+            result = cancel_rx.changed() => {
+                if result.is_err() || *cancel_rx.borrow() {
+                    if let Some(tool) = paused.take() {
+                        events.push(resume(&context, tool, "session run ended"));
+                    }
+                    return events;
+                }
+            }
+        }
+
+        let reading = PressureReading::sample();
+        if let Some(tool) = paused.take() {
+            if context.thresholds.subsided(reading) {
+                events.push(resume(&context, tool, "pressure subsided"));
+            } else if tool.since.elapsed() >= context.max_pause {
+                events.push(resume(&context, tool, "maximum pause reached"));
+                cooldown_until = Some(Instant::now() + context.max_pause);
+            } else {
+                paused = Some(tool);
+            }
+            continue;
+        }
+        if cooldown_until.is_some_and(|until| Instant::now() < until) {
+            continue;
+        }
+        let Some(reason) = context.thresholds.exceeded(reading) else {
+            continue;
+        };
+        let session_id = context.session_id.clone();
+        let is_lowest = tokio::task::spawn_blocking(move || {
+            live_sessions().is_ok_and(|live| is_lowest_priority(&session_id, &live))
+        })
+        .await
+        .unwrap_or(false);
+        if !is_lowest {
+            continue;
+        }
+        let groups = tool_process_groups(std::process::id());
+        if groups.is_empty() {
+            continue;
+        }
+        for &pgid in &groups {
+            signal_group(pgid, libc::SIGSTOP);
+        }
+        let _ = std::fs::write(context.session_dir.join(PRESSURE_PAUSED_MARKER), "");
+        warn!(session = %context.session_id, %reason, "Pausing session under host pressure");
+        events.push(PressureEvent {
+            at: Utc::now(),
+            action: PressureAction::Paused,
+            reason,
+            duration_secs: None,
+        });
+        paused = Some(PausedTool {
+            groups,
+            since: Instant::now(),
+        });
+    }
+}
+
+fn resume(context: &MonitorContext, tool: PausedTool, reason: &str) -> PressureEvent {
+    for &pgid in &tool.groups {
+        signal_group(pgid, libc::SIGCONT);
+    }
+    let _ = std::fs::remove_file(context.session_dir.join(PRESSURE_PAUSED_MARKER));
+    info!(session = %context.session_id, reason, "Resuming session paused under host pressure");
+    PressureEvent {
+        at: Utc::now(),
+        action: PressureAction::Resumed,
+        reason: reason.to_string(),
+        duration_secs: Some(tool.since.elapsed().as_secs()),
+    }
+}
+
+fn signal_group(pgid: i32, signal: libc::c_int) {
+    // SAFETY: kill(-pgid, sig) targets a process group led by our own child.
+    if unsafe { libc::kill(-pgid, signal) } != 0 {
+        warn!(
+            pgid,
+            signal,
+            error = %std::io::Error::last_os_error(),
+            "Failed to signal tool process group"
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LiveSession {
+    session_id: String,
+    created_at: DateTime<Utc>,
+    paused: bool,
+}
+
+fn live_sessions() -> anyhow::Result<Vec<LiveSession>> {
+    let sessions = csa_session::list_all_sessions_all_projects()?;
+    Ok(sessions
+        .into_iter()
+        .filter(|session| session.phase == SessionPhase::Active)
+        .filter_map(|session| {
+            let session_dir = csa_session::get_session_dir(
+                Path::new(&session.project_path),
+                &session.meta_session_id,
+            )
+            .ok()?;
+            ToolLiveness::has_live_process(&session_dir).then(|| LiveSession {
+                paused: session_dir.join(PRESSURE_PAUSED_MARKER).exists(),
+                session_id: session.meta_session_id,
+                created_at: session.created_at,
+            })
+        })
+        .collect())
+}
+
+/// The most recently started running session has the least work to lose,
+/// so it is paused first. The last running session is never paused.
+fn is_lowest_priority(session_id: &str, live: &[LiveSession]) -> bool {
+    let running: Vec<&LiveSession> = live.iter().filter(|session| !session.paused).collect();
+    running.len() >= 2
+        && running
+            .iter()
+            .max_by(|a, b| (a.created_at, &a.session_id).cmp(&(b.created_at, &b.session_id)))
+            .is_some_and(|newest| newest.session_id == session_id)
+}
+
+/// Process groups led by direct children of `parent_pid`: tools are spawned
+/// as group leaders, while helper children stay in CSA's own group.
+#[cfg(target_os = "linux")]
+fn tool_process_groups(parent_pid: u32) -> Vec<i32> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<i32>().ok())
+        .filter(|&pid| {
+            let Ok(stat) = std::fs::read_to_string(format!("/proc/{pid}/stat")) else {
+                return false;
+            };
+            let Some((_, rest)) = stat.rsplit_once(") ") else {
+                return false;
+            };
+            let mut fields = rest.split_whitespace().skip(1);
+            let ppid = fields.next().and_then(|field| field.parse::<u32>().ok());
+            let pgrp = fields.next().and_then(|field| field.parse::<i32>().ok());
+            ppid == Some(parent_pid) && pgrp == Some(pid)
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tool_process_groups(_parent_pid: u32) -> Vec<i32> {
+    Vec::new()
+}
+
+#[cfg(test)]
+#[path = "pipeline_pressure_throttle_tests.rs"]
+mod tests;
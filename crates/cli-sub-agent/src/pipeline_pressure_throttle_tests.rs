@@ -0,0 +1,71 @@
+use super::*;
+
+fn live(session_id: &str, minutes_ago: i64, paused: bool) -> LiveSession {
+    LiveSession {
+        session_id: session_id.to_string(),
+        created_at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+        paused,
+    }
+}
+
+#[test]
+fn newest_running_session_is_lowest_priority() {
+    let sessions = [live("old", 30, false), live("new", 1, false)];
+    assert!(is_lowest_priority("new", &sessions));
+    assert!(!is_lowest_priority("old", &sessions));
+}
+
+#[test]
+fn lone_or_already_paused_sessions_are_not_picked() {
+    assert!(!is_lowest_priority("only", &[live("only", 5, false)]));
+
+    // "new" is already stopped, so "mid" is next in line; "old" keeps running.
+    let sessions = [
+        live("old", 30, false),
+        live("mid", 10, false),
+        live("new", 1, true),
+    ];
+    assert!(is_lowest_priority("mid", &sessions));
+    assert!(!is_lowest_priority("new", &sessions));
+    assert!(!is_lowest_priority("old", &sessions));
+}
+
+#[test]
+fn thresholds_come_from_resources_config() {
+    assert!(!pressure_thresholds(None).is_enabled());
+
+    let mut config: ProjectConfig = toml::from_str(
+        "schema_version = 1\n[project]\nname = \"t\"\ncreated_at = \"2026-01-01T00:00:00Z\"\n",
+    )
+    .expect("minimal project config");
+    config.resources.psi_memory_threshold = Some(25.0);
+    let thresholds = pressure_thresholds(Some(&config));
+    assert_eq!(thresholds.memory_some_avg10, Some(25.0));
+    assert_eq!(thresholds.cpu_some_avg10, None);
+    assert!(delay_spawn_for_pressure(None).is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn tool_process_groups_finds_group_leading_children() {
+    use std::os::unix::process::CommandExt;
+
+    let mut leader = std::process::Command::new("sleep")
+        .arg("30")
+        .process_group(0)
+        .spawn()
+        .expect("spawn group leader");
+    let mut helper = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .expect("spawn helper");
+
+    let groups = tool_process_groups(std::process::id());
+    leader.kill().ok();
+    helper.kill().ok();
+    leader.wait().ok();
+    helper.wait().ok();
+
+    assert!(groups.contains(&(leader.id() as i32)));
+    assert!(!groups.contains(&(helper.id() as i32)));
+}
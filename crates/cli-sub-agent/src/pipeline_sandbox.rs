@@ -31,10 +31,14 @@ mod memory_override;
 #[path = "pipeline_sandbox_writable.rs"]
 mod writable_sources;
 use writable_sources::add_execution_env_writable_paths;
+#[path = "pipeline_sandbox_unsandboxed.rs"]
+mod unsandboxed;
+use unsandboxed::{network_mode, unsandboxed_network_error};
 
 pub(crate) use memory_balloon::maybe_inflate_balloon;
 #[cfg(test)]
 pub(crate) use memory_balloon::should_skip_balloon_prewarm;
+pub(crate) use unsandboxed::unsandboxed_run_error;
 
 /// Outcome of sandbox resolution — either enriched options or a fatal error string
 /// (for `Required` mode with no capability).
@@ -152,8 +156,8 @@ pub(crate) fn resolve_sandbox_options_with_overrides(
     resolve_sandbox_options_with_capability_source(
         input,
         resource_overrides,
-        csa_resource::detect_resource_capability,
-        csa_resource::detect_filesystem_capability,
+        || csa_resource::detect_sandbox_capability().resource,
+        || csa_resource::detect_sandbox_capability().filesystem,
     )
 }
 
@@ -354,6 +358,9 @@ fn resolve_sandbox_options_with_capability_source(
     ) {
         Ok(Some(enforcement)) => enforcement,
         Ok(None) => {
+            if let Some(message) = unsandboxed_network_error(cfg, tool_name) {
+                return SandboxResolution::RequiredButUnavailable(message);
+            }
             return SandboxResolution::Ok(Box::new(execute_options));
         }
         Err(message) => return SandboxResolution::RequiredButUnavailable(message),
@@ -366,6 +373,9 @@ fn resolve_sandbox_options_with_capability_source(
                  Set --memory-max-mb, resources.memory_max_mb, or tools.{tool_name}.memory_max_mb."
             ));
         }
+        if let Some(message) = unsandboxed_network_error(cfg, tool_name) {
+            return SandboxResolution::RequiredButUnavailable(message);
+        }
         info!(
             tool = %tool_name,
             enforcement = ?enforcement,
@@ -466,6 +476,9 @@ fn resolve_sandbox_options_with_capability_source(
         .with_resource_capability(resource_cap)
         .with_filesystem_capability(fs_cap)
         .with_resource_limits(Some(memory_max_mb), memory_swap_max_mb, pids_max)
+        .with_cpu_limits(cfg.resources.cpu_max_percent, cfg.resources.cpu_weight)
+        .with_disk_limit(cfg.resources.disk_max_mb)
+        .with_network(network_mode(cfg))
         .with_tool_defaults_and_state_dirs(
             tool_name,
             project_root,
@@ -51,7 +51,7 @@ pub(crate) fn capability_error_if_unenforced(
     has_run_memory_override: bool,
     resource_cap: ResourceCapability,
 ) -> Option<String> {
-    if !has_run_memory_override || resource_cap == ResourceCapability::CgroupV2 {
+    if !has_run_memory_override || resource_cap.is_cgroup() {
         return None;
     }
 
@@ -67,7 +67,7 @@ pub(crate) fn plan_error_if_unenforced(
     has_run_memory_override: bool,
     plan: &IsolationPlan,
 ) -> Option<String> {
-    if !has_run_memory_override || plan.resource == ResourceCapability::CgroupV2 {
+    if !has_run_memory_override || plan.resource.is_cgroup() {
         return None;
     }
 
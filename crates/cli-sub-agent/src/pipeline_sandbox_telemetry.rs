@@ -79,6 +79,7 @@ pub(crate) fn record_sandbox_telemetry(
     let capability = csa_resource::detect_resource_capability();
     let mode = match capability {
         csa_resource::ResourceCapability::CgroupV2 => "cgroup",
+        csa_resource::ResourceCapability::CgroupDirect => "cgroup-direct",
         csa_resource::ResourceCapability::Setrlimit => "rlimit",
         csa_resource::ResourceCapability::None => "none",
     };
@@ -88,6 +89,7 @@ pub(crate) fn record_sandbox_telemetry(
     let fs_mode = Some(match sandbox_context.isolation_plan.filesystem {
         csa_resource::FilesystemCapability::Bwrap => "bwrap".to_string(),
         csa_resource::FilesystemCapability::Landlock => "landlock".to_string(),
+        csa_resource::FilesystemCapability::SandboxExec => "sandbox-exec".to_string(),
         csa_resource::FilesystemCapability::None => "none".to_string(),
     });
 
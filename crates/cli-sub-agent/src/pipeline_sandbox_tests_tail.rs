@@ -540,3 +540,72 @@ enforcement_mode = "best-effort"
         "Bubblewrap plan should retain every explicit writable path"
     );
 }
+
+/// `resources.network = "none"` reaches the isolation plan, and a config that
+/// would skip the sandbox is refused instead of silently allowing network.
+#[test]
+fn test_network_none_propagates_and_fails_closed() {
+    let cfg = parse_project_config(
+        r#"
+[resources]
+network = "none"
+
+[tools.gemini-cli]
+enabled = true
+memory_max_mb = 2048
+"#,
+    );
+    let project_root = current_project_root();
+    let result = resolve_sandbox_options_with_capabilities(
+        SandboxResolveInput {
+            config: Some(&cfg),
+            tool_name: "gemini-cli",
+            session_id: "test-session",
+            project_root: &project_root,
+            stream_mode: StreamMode::BufferOnly,
+            idle_timeout_seconds: 120,
+            liveness_dead_seconds: 600,
+            initial_response_timeout_seconds: Some(120),
+            no_fs_sandbox: false,
+            allow_user_daemon_ipc: false,
+            readonly_project_root: false,
+            extra_writable: &[],
+            extra_readable: &[],
+            execution_env: None,
+        },
+        RunResourceOverrides::absent(),
+        csa_resource::ResourceCapability::Setrlimit,
+        csa_resource::FilesystemCapability::None,
+    );
+    let SandboxResolution::Ok(opts) = result else {
+        panic!("Expected SandboxResolution::Ok");
+    };
+    let ctx = opts.sandbox.as_ref().expect("Expected SandboxContext");
+    assert_eq!(ctx.isolation_plan.network, csa_resource::NetworkMode::None);
+
+    let cfg = parse_project_config(
+        r#"
+[resources]
+network = "none"
+enforcement_mode = "off"
+"#,
+    );
+    let result = resolve_sandbox_options(
+        Some(&cfg),
+        "gemini-cli",
+        "test-session",
+        &current_project_root(),
+        StreamMode::BufferOnly,
+        120,
+        600,
+        Some(120),
+        false,
+        false,
+        &[],
+        &[],
+    );
+    assert!(
+        matches!(&result, SandboxResolution::RequiredButUnavailable(message) if message.contains("resources.network")),
+        "network = \"none\" without a sandbox must be refused"
+    );
+}
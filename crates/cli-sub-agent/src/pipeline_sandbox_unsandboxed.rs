@@ -0,0 +1,38 @@
+use csa_config::ProjectConfig;
+
+pub(super) fn network_mode(cfg: &ProjectConfig) -> csa_resource::NetworkMode {
+    match cfg.resources.network.unwrap_or_default() {
+        csa_config::SandboxNetwork::Full => csa_resource::NetworkMode::Full,
+        csa_config::SandboxNetwork::None => csa_resource::NetworkMode::None,
+    }
+}
+
+/// `resources.network = "none"` is a guarantee, so refuse to run the tool
+/// when the resolved settings would skip the sandbox altogether.
+pub(super) fn unsandboxed_network_error(cfg: &ProjectConfig, tool_name: &str) -> Option<String> {
+    network_mode(cfg).is_isolated().then(|| {
+        format!(
+            "resources.network = \"none\" requires a sandboxed run, but sandboxing is disabled for tool '{tool_name}'. \
+             Set an enforcement_mode other than \"off\" and a memory_max_mb, or remove resources.network."
+        )
+    })
+}
+
+/// Why `tool_name` must not run outside the sandbox, for tools that never get
+/// one (executor plugins): resource or filesystem enforcement is `required`,
+/// or `resources.network = "none"` is set.
+pub(crate) fn unsandboxed_run_error(cfg: &ProjectConfig, tool_name: &str) -> Option<String> {
+    if cfg.tool_enforcement_mode(tool_name) == csa_config::EnforcementMode::Required {
+        return Some(format!(
+            "Sandbox enforcement is required for tool '{tool_name}', but it runs without a sandbox. \
+             Set tools.{tool_name}.enforcement_mode = \"best-effort\" or \"off\" to run it unisolated."
+        ));
+    }
+    if cfg.tool_fs_enforcement_mode(tool_name).as_deref() == Some("required") {
+        return Some(format!(
+            "Filesystem sandbox enforcement is required for tool '{tool_name}', but it runs without a sandbox. \
+             Set tools.{tool_name}.filesystem_sandbox.enforcement_mode = \"best-effort\" or \"off\" to run it unisolated."
+        ));
+    }
+    unsandboxed_network_error(cfg, tool_name)
+}
@@ -62,12 +62,10 @@ use clean_room::execute_clean_room_session_core;
 pub(crate) use clean_room::{
     CleanRoomSandboxInput, resolve_clean_room_sandbox_options_with_capabilities,
 };
-pub(crate) use session_exec_api::execute_with_session;
-#[cfg(test)]
-pub(crate) use session_exec_api::execute_with_session_and_meta;
 pub(crate) use session_exec_api::{
     CleanRoomExecutionContract, CleanRoomExecutionLimits, execute_clean_room_session,
 };
+pub(crate) use session_exec_api::{execute_with_session, execute_with_session_and_meta};
 
 #[cfg(test)]
 pub(crate) struct CleanRoomPolicyEffects {
@@ -137,9 +135,90 @@ pub(crate) async fn execute_with_session_and_meta_with_parent_source<
     error_marker_scan_override: Option<bool>,
     cli_no_hook_bypass_scan: bool,
     startup_env: &StartupSubtreeEnv,
+) -> Result<SessionExecutionResult> {
+    // Queue waits and the spawn-time usage snapshot belong to this run only.
+    crate::session_resource_report::scope_run_usage(execute_with_session_in_run_scope(
+        executor,
+        tool,
+        prompt,
+        output_format,
+        session_arg,
+        fresh_spawn_preflight_override,
+        description,
+        parent,
+        project_root,
+        config,
+        extra_env,
+        subtree_pin,
+        allow_git_push,
+        task_type,
+        tier_name,
+        context_load_options,
+        stream_mode,
+        idle_timeout_seconds,
+        initial_response_timeout_seconds,
+        wall_timeout,
+        memory_injection,
+        global_config,
+        pre_session_hook,
+        parent_session_source,
+        session_creation_mode,
+        resource_overrides,
+        no_fs_sandbox,
+        allow_user_daemon_ipc,
+        readonly_project_root,
+        extra_writable,
+        extra_readable,
+        error_marker_scan_override,
+        cli_no_hook_bypass_scan,
+        startup_env,
+    ))
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn execute_with_session_in_run_scope<D: DispatchExecutor + ?Sized>(
+    executor: &D,
+    tool: &ToolName,
+    prompt: &str,
+    output_format: OutputFormat,
+    session_arg: Option<String>,
+    fresh_spawn_preflight_override: bool,
+    description: Option<String>,
+    parent: Option<String>,
+    project_root: &Path,
+    config: Option<&ProjectConfig>,
+    extra_env: Option<&std::collections::HashMap<String, String>>,
+    // Trusted CSA-decided subtree model pin (#1741), carried outside generic
+    // `extra_env`. The executor applies it after env merges strip pin keys.
+    // `None` means CSA did not pin; never source this from request/config env.
+    subtree_pin: Option<&csa_core::env::SubtreeModelPin>,
+    allow_git_push: bool,
+    task_type: Option<&str>,
+    tier_name: Option<&str>,
+    context_load_options: Option<&csa_executor::ContextLoadOptions>,
+    stream_mode: csa_process::StreamMode,
+    idle_timeout_seconds: u64,
+    initial_response_timeout_seconds: Option<u64>,
+    wall_timeout: Option<Duration>,
+    memory_injection: Option<&MemoryInjectionOptions>,
+    global_config: Option<&GlobalConfig>,
+    pre_session_hook: Option<csa_hooks::PreSessionHookInvocation>,
+    parent_session_source: ParentSessionSource,
+    session_creation_mode: SessionCreationMode,
+    resource_overrides: RunResourceOverrides,
+    no_fs_sandbox: bool,
+    allow_user_daemon_ipc: bool,
+    readonly_project_root: bool,
+    extra_writable: &[PathBuf],
+    extra_readable: &[PathBuf],
+    error_marker_scan_override: Option<bool>,
+    cli_no_hook_bypass_scan: bool,
+    startup_env: &StartupSubtreeEnv,
 ) -> Result<SessionExecutionResult> {
     let dispatch_executor = executor;
     let executor = dispatch_executor.executor();
+    let run_timer = csa_core::metrics::RunTimer::start(executor.tool_name());
     let memory_project_key = resolve_memory_project_key(project_root, startup_env.project_root());
     let session_exec_bootstrap::SessionBootstrap {
         mut session,
@@ -237,7 +316,8 @@ pub(crate) async fn execute_with_session_and_meta_with_parent_source<
         &mut cleanup_guard,
         resource_overrides,
         task_type,
-    )?;
+    )
+    .await?;
     if let Some(ref budget) = session.token_budget {
         if budget.is_hard_exceeded() {
             let used = budget.used;
@@ -318,10 +398,20 @@ pub(crate) async fn execute_with_session_and_meta_with_parent_source<
         tool_state,
         execute_options,
         session_config,
+        mcp_hub_session: _mcp_hub_session,
         completion,
     } = runtime;
     let execution_start_time = completion.execution_start_time;
     dispatch_executor.emit_catalog_warning();
+    let pressure_monitor = crate::pipeline_pressure_throttle::start_pressure_monitor(
+        config,
+        &session.meta_session_id,
+        &session_dir,
+        idle_timeout_seconds,
+    );
+    let vram_monitor = config
+        .and_then(|cfg| cfg.tool_gpu_memory_mb(executor.tool_name()))
+        .and_then(|_| csa_resource::gpu::VramPeakMonitor::start(std::process::id()));
     let transport_result = crate::pipeline_execute::execute_transport_with_signal(
         executor,
         &effective_prompt,
@@ -335,12 +425,21 @@ pub(crate) async fn execute_with_session_and_meta_with_parent_source<
         execution_start_time,
         wall_timeout,
     )
-    .await
-    .with_context(|| format!("meta_session_id={}", session.meta_session_id))?;
+    .await;
+    if let Some(monitor) = pressure_monitor {
+        session.pressure_events.extend(monitor.stop().await);
+    }
+    let peak_vram_mb = match vram_monitor {
+        Some(monitor) => monitor.stop().await,
+        None => None,
+    };
+    let mut transport_result =
+        transport_result.with_context(|| format!("meta_session_id={}", session.meta_session_id))?;
+    transport_result.execution.peak_vram_mb = peak_vram_mb;
     if let Some(ref mut guard) = cleanup_guard {
         guard.defuse();
     }
-    session_exec_completion::complete_session_execution(
+    let result = session_exec_completion::complete_session_execution(
         session_exec_completion::CompletionInput {
             executor,
             tool,
@@ -359,5 +458,7 @@ pub(crate) async fn execute_with_session_and_meta_with_parent_source<
         },
         &mut session,
     )
-    .await
+    .await?;
+    run_timer.finish(result.execution.exit_code == 0);
+    Ok(result)
 }
@@ -184,7 +184,7 @@ pub(crate) async fn execute_clean_room_session(
         );
     }
     super::clean_room::validate_clean_room_sandbox_capability()?;
-    super::execute_clean_room_session_core(
+    crate::session_resource_report::scope_run_usage(super::execute_clean_room_session_core(
         admitted,
         tool,
         prompt,
@@ -192,7 +192,7 @@ pub(crate) async fn execute_clean_room_session(
         config,
         global_config,
         limits,
-    )
+    ))
     .await
 }
 
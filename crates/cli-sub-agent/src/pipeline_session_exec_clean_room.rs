@@ -347,7 +347,8 @@ pub(super) async fn execute_clean_room_session_core(
         &mut cleanup_guard,
         limits.resource_overrides(),
         None,
-    )?;
+    )
+    .await?;
     let default_global;
     let global = match global_config {
         Some(config) => config,
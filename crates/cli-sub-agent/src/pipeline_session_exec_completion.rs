@@ -85,6 +85,10 @@ pub(super) async fn complete_session_execution(
     let has_tool_calls = transport_result.metadata.has_tool_calls
         || transport_result.metadata.has_execute_tool_calls;
     let turn_count = transport_result.metadata.turn_count;
+    let client_fs_changes = crate::pipeline::changed_paths::client_fs_changed_files(
+        &transport_result.events,
+        input.project_root,
+    );
     let output_tokens = transport_result.metadata.output_tokens;
     let mut result = transport_result.execution;
     crate::pipeline_sandbox::check_sandbox_permission_errors(
@@ -250,6 +254,11 @@ pub(super) async fn complete_session_execution(
         );
         return Err(err).with_context(|| format!("meta_session_id={}", session.meta_session_id));
     }
+    if let Err(err) =
+        csa_session::add_return_packet_changed_files(input.session_dir, &client_fs_changes)
+    {
+        tracing::warn!(error = %err, "Failed to record client-side fs writes in return packet");
+    }
     Ok(SessionExecutionResult {
         execution: result,
         meta_session_id: session.meta_session_id.clone(),
@@ -712,26 +721,4 @@ mod tests {
             completed.execution.stderr_output
         );
     }
-
-    #[test]
-    fn fix_finding_terminal_guard_allows_dirty_side_effects_after_amend() {
-        let mut session = MetaSessionState::default();
-        session.task_context.task_type = Some(REVIEW_FIX_FINDING_TASK_TYPE.to_string());
-        let commit_guard = crate::run_cmd::PostRunCommitGuard {
-            workspace_mutated: true,
-            head_changed: true,
-            head_externally_raced: false,
-            changed_paths: vec!["tracked.txt".to_string()],
-        };
-        let mut result = csa_process::ExecutionResult {
-            exit_code: 0,
-            model_completed: Some(true),
-            ..Default::default()
-        };
-
-        apply_fix_finding_terminal_guard(&session, Some(true), Some(&commit_guard), &mut result);
-
-        assert_eq!(result.exit_code, 0);
-        assert!(result.csa_gate_failure.is_none());
-    }
 }
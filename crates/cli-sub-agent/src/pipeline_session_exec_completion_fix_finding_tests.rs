@@ -250,3 +250,25 @@ async fn completion_fails_fix_finding_reviewer_sub_session_when_no_amend_complet
             .contains("repo_side_effects=none_detected")
     );
 }
+
+#[test]
+fn fix_finding_terminal_guard_allows_dirty_side_effects_after_amend() {
+    let mut session = MetaSessionState::default();
+    session.task_context.task_type = Some(REVIEW_FIX_FINDING_TASK_TYPE.to_string());
+    let commit_guard = crate::run_cmd::PostRunCommitGuard {
+        workspace_mutated: true,
+        head_changed: true,
+        head_externally_raced: false,
+        changed_paths: vec!["tracked.txt".to_string()],
+    };
+    let mut result = csa_process::ExecutionResult {
+        exit_code: 0,
+        model_completed: Some(true),
+        ..Default::default()
+    };
+
+    apply_fix_finding_terminal_guard(&session, Some(true), Some(&commit_guard), &mut result);
+
+    assert_eq!(result.exit_code, 0);
+    assert!(result.csa_gate_failure.is_none());
+}
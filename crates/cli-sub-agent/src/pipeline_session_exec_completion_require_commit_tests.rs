@@ -89,6 +89,7 @@ fn register_memory_soft_limit_evidence(session_dir: &std::path::Path) {
         memory_max_mb: 12_288,
         soft_limit_percent: 70,
         scope_name: "csa-codex-01KTEST.scope".to_string(),
+        partial_results_saved: false,
     };
     csa_resource::memory_monitor::record_soft_limit_diagnostic_evidence(&diagnostic_path, &event);
 }
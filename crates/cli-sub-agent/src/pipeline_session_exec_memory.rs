@@ -4,6 +4,9 @@ use csa_config::{MemoryBackend, MemoryConfig};
 use tracing::{debug, info};
 
 use crate::memory_capture;
+use crate::memory_injection_report::{
+    MemoryCandidateDecision, MemoryCandidateSource, MemoryInjectionReport,
+};
 
 use super::MemoryInjectionOptions;
 
@@ -16,12 +19,26 @@ pub(super) fn append_memory_section(
     tool_name: &str,
     effective_prompt: &mut String,
 ) {
+    let explain = memory_injection.is_some_and(|opts| opts.explain);
     let memory_disabled =
         memory_injection.is_none() || memory_injection.is_some_and(|opts| opts.disabled);
     let Some(memory_cfg) = memory_cfg else {
+        emit_skip_explanation(explain, "memory", 0, "no memory configuration loaded");
         return;
     };
+    let backend = csa_memory::resolve_backend(memory_cfg.backend);
     if !memory_cfg.inject || memory_disabled {
+        let reason = if memory_disabled {
+            "disabled for this run"
+        } else {
+            "memory.inject is false"
+        };
+        emit_skip_explanation(
+            explain,
+            &backend.to_string(),
+            memory_cfg.inject_token_budget,
+            reason,
+        );
         return;
     }
     if csa_hooks::mempal_capture::tool_has_own_mempal(tool_name) {
@@ -29,15 +46,33 @@ pub(super) fn append_memory_section(
             tool = tool_name,
             "skipping mempal prompt injection for {tool_name} (has own integration)"
         );
+        emit_skip_explanation(
+            explain,
+            &backend.to_string(),
+            memory_cfg.inject_token_budget,
+            &format!("{tool_name} has its own mempal integration"),
+        );
         return;
     }
 
     let memory_query = memory_injection
         .and_then(|opts| opts.query_override.as_deref())
         .unwrap_or(raw_prompt);
-    if let Some(memory_section) =
-        build_memory_section_for_backend(memory_cfg, memory_query, memory_project_key, project_root)
-    {
+    let mut report =
+        MemoryInjectionReport::new(backend.to_string(), memory_cfg.inject_token_budget);
+    let memory_section = build_memory_section_for_resolved_backend(
+        backend,
+        memory_cfg,
+        memory_query,
+        memory_project_key,
+        project_root,
+        &mut report,
+    );
+    report.log();
+    if explain {
+        eprint!("{}", report.render());
+    }
+    if let Some(memory_section) = memory_section {
         info!(
             bytes = memory_section.len(),
             "Injecting memory context into prompt"
@@ -46,19 +81,13 @@ pub(super) fn append_memory_section(
     }
 }
 
-fn build_memory_section_for_backend(
-    memory_cfg: &MemoryConfig,
-    memory_query: &str,
-    memory_project_key: Option<&str>,
-    project_root: &Path,
-) -> Option<String> {
-    build_memory_section_for_resolved_backend(
-        csa_memory::resolve_backend(memory_cfg.backend),
-        memory_cfg,
-        memory_query,
-        memory_project_key,
-        project_root,
-    )
+fn emit_skip_explanation(explain: bool, backend: &str, token_budget: u32, reason: &str) {
+    if !explain {
+        return;
+    }
+    let mut report = MemoryInjectionReport::new(backend, token_budget);
+    report.skip(reason);
+    eprint!("{}", report.render());
 }
 
 fn build_memory_section_for_resolved_backend(
@@ -67,16 +96,34 @@ fn build_memory_section_for_resolved_backend(
     memory_query: &str,
     memory_project_key: Option<&str>,
     project_root: &Path,
+    report: &mut MemoryInjectionReport,
 ) -> Option<String> {
     match backend {
-        MemoryBackend::Mempal => memory_capture::build_memory_section_from_mempal(
+        MemoryBackend::Mempal => {
+            let section = memory_capture::build_memory_section_from_mempal(
+                memory_query,
+                project_root,
+                memory_cfg.inject_token_budget,
+            );
+            match &section {
+                // mempal ranks and formats internally; only the aggregate is observable.
+                Some(body) => report.record(
+                    "mempal-context",
+                    1.0,
+                    MemoryCandidateSource::Mempal,
+                    ((body.chars().count() as u32) / 4).max(1),
+                    MemoryCandidateDecision::Injected,
+                ),
+                None => report.skip("mempal returned no context"),
+            }
+            section
+        }
+        MemoryBackend::Legacy | MemoryBackend::Auto => memory_capture::build_memory_section(
+            memory_cfg,
             memory_query,
-            project_root,
-            memory_cfg.inject_token_budget,
+            memory_project_key,
+            report,
         ),
-        MemoryBackend::Legacy | MemoryBackend::Auto => {
-            memory_capture::build_memory_section(memory_cfg, memory_query, memory_project_key)
-        }
     }
 }
 
@@ -84,7 +131,7 @@ fn build_memory_section_for_resolved_backend(
 mod tests {
     use super::*;
     use crate::test_env_lock::{ScopedEnvVarRestore, TEST_ENV_LOCK};
-    use csa_memory::{MemoryEntry, MemorySource, MemoryStore};
+    use csa_memory::{MemoryEntry, MemoryProvenance, MemorySource, MemoryStore};
     use std::path::PathBuf;
     use tempfile::tempdir;
     use ulid::Ulid;
@@ -111,6 +158,7 @@ mod tests {
                 source: MemorySource::PostRun,
                 valid_from: Some(now),
                 valid_until: None,
+                provenance: MemoryProvenance::default(),
             })
             .expect("append legacy memory");
 
@@ -121,17 +169,21 @@ mod tests {
             ..MemoryConfig::default()
         };
 
+        let mut report = MemoryInjectionReport::new("legacy", config.inject_token_budget);
         let section = build_memory_section_for_resolved_backend(
             MemoryBackend::Legacy,
             &config,
             "legacy fallback context",
             Some("test-project"),
             temp.path(),
+            &mut report,
         )
         .expect("legacy memory section");
 
         assert!(section.contains("previous sessions"));
         assert!(section.contains("legacy memory fallback"));
         assert!(section.contains("<!-- CSA:MEMORY:END -->"));
+        assert_eq!(report.injected_count(), 1);
+        assert!(report.render().contains("01ARZ3NDEKTSV4RRFFQ69G5FAV"));
     }
 }
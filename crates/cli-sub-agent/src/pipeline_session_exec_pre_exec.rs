@@ -31,7 +31,7 @@ impl PipelinePreExecFailureDetails<'_> {
     }
 }
 
-pub(super) fn check_resources_before_spawn(
+pub(super) async fn check_resources_before_spawn(
     config: Option<&ProjectConfig>,
     executor: &Executor,
     project_root: &Path,
@@ -40,6 +40,9 @@ pub(super) fn check_resources_before_spawn(
     resource_overrides: RunResourceOverrides,
     task_type: Option<&str>,
 ) -> anyhow::Result<()> {
+    if let Some(event) = crate::pipeline_pressure_throttle::delay_spawn_for_pressure(config).await {
+        session.pressure_events.push(event);
+    }
     let mut resource_guard = ResourceGuard::new(ResourceLimits {
         min_free_memory_mb: resource_overrides.resolve_min_free_memory_mb(config),
     });
@@ -64,8 +67,17 @@ pub(super) fn check_resources_before_spawn(
             },
         ));
     }
-    let admission =
-        build_spawn_memory_admission(project_root, &session.meta_session_id, projected_spawn_mb);
+    let usage_key = crate::memory_usage_stats::usage_key(
+        executor.tool_name(),
+        executor.model_override(),
+        task_type,
+    );
+    let admission = build_spawn_memory_admission(
+        project_root,
+        &session.meta_session_id,
+        projected_spawn_mb,
+        &usage_key,
+    );
 
     if let Err(err) =
         resource_guard.check_availability_with_admission(executor.tool_name(), Some(admission))
@@ -84,6 +96,23 @@ pub(super) fn check_resources_before_spawn(
             },
         ));
     }
+    if let Some(gpu_memory_mb) = config.and_then(|cfg| cfg.tool_gpu_memory_mb(executor.tool_name()))
+        && let Err(err) = resource_guard.check_gpu_memory(executor.tool_name(), gpu_memory_mb)
+    {
+        return Err(persist_pipeline_pre_exec_failure(
+            project_root,
+            session,
+            executor.tool_name(),
+            err,
+            cleanup_guard,
+            Some("low_vram"),
+            PipelinePreExecFailureDetails {
+                config,
+                task_type,
+                resource_overrides,
+            },
+        ));
+    }
     if let Err(err) = crate::resource_admission::persist_spawn_memory_admission_ready(
         project_root,
         &session.meta_session_id,
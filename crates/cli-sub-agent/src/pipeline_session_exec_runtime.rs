@@ -1,4 +1,6 @@
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{Context, Result};
 use csa_config::ProjectConfig;
 use csa_hooks::{HookEvent, global_hooks_path, load_hooks_config};
 use csa_session::MetaSessionState;
@@ -176,15 +178,27 @@ async fn prepare_session_runtime_inner(
         .config
         .map(|cfg| cfg.session.tool_output_threshold_bytes)
         .unwrap_or(csa_config::DEFAULT_TOOL_OUTPUT_THRESHOLD_BYTES);
+    let acp_permissions = csa_core::acp_permissions::AcpPermissionPolicy {
+        config: input
+            .config
+            .map(|cfg| cfg.acp.permissions.clone())
+            .unwrap_or_default(),
+        allow_edit_existing_files: can_edit,
+        allow_write_new_files: can_write_new,
+        project_root: Some(input.project_root.to_path_buf()),
+    };
+    let mcp_hub_session =
+        open_mcp_hub_session(input.global_config, input.executor, input.project_root).await?;
     let session_config = Some(csa_executor::SessionConfig {
         mcp_servers,
-        mcp_proxy_socket: input
-            .global_config
-            .and_then(|gc| gc.mcp_proxy_socket.clone()),
+        mcp_proxy_socket: mcp_hub_session
+            .as_ref()
+            .map(|hub| hub.socket_path().to_string_lossy().into_owned()),
         tool_output_compaction: Some(csa_executor::ToolOutputCompactionConfig {
             sidecar_dir: input.session_dir.join("tool_outputs"),
             threshold_bytes: tool_output_threshold_bytes,
         }),
+        acp_permissions: (!acp_permissions.is_permissive()).then_some(acp_permissions),
         ..Default::default()
     });
     let mut merged_env =
@@ -382,6 +396,7 @@ async fn prepare_session_runtime_inner(
         tool_state,
         execute_options,
         session_config,
+        mcp_hub_session,
         completion: SessionCompletionPlan {
             merged_env,
             hooks_config,
@@ -472,3 +487,29 @@ mod tests {
         ));
     }
 }
+
+/// Open a hub session socket identifying this run's tool to `[mcp_filters]`.
+/// Without a running hub the agent gets its MCP servers directly; a hub that
+/// refuses the session fails the run rather than bypassing its filters.
+async fn open_mcp_hub_session(
+    global_config: Option<&csa_config::GlobalConfig>,
+    executor: &csa_executor::Executor,
+    project_root: &Path,
+) -> Result<Option<csa_mcp_hub::HubSession>> {
+    let Some(hub_socket) = global_config
+        .and_then(|config| config.mcp_proxy_socket.as_deref())
+        .map(Path::new)
+        .filter(|socket| socket.exists())
+    else {
+        return Ok(None);
+    };
+    csa_mcp_hub::open_session(hub_socket, executor.tool_name(), project_root)
+        .await
+        .map(Some)
+        .with_context(|| {
+            format!(
+                "failed to open an MCP hub session on {}; stop the hub or fix it to run without it",
+                hub_socket.display()
+            )
+        })
+}
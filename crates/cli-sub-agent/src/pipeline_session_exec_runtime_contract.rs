@@ -52,6 +52,8 @@ pub(super) struct SessionRuntimePlan {
     pub(super) tool_state: Option<ToolState>,
     pub(super) execute_options: ExecuteOptions,
     pub(super) session_config: Option<SessionConfig>,
+    /// Keeps the hub session socket in `session_config` open for the run.
+    pub(super) mcp_hub_session: Option<csa_mcp_hub::HubSession>,
     pub(super) completion: SessionCompletionPlan,
 }
 
@@ -442,6 +442,7 @@ mod tests {
                 memory_soft_limit_recovery: None,
                 post_exec_gate: None,
                 manager_fields: Default::default(),
+                resources: None,
             },
         )
         .expect("save result");
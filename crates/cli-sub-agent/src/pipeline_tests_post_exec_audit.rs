@@ -213,6 +213,7 @@ fn pre_execution_audit_baseline_returns_none_for_legacy_sessions_without_snapsho
         vcs_identity: None,
         identity_version: 2,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     };
 
     let executor = Executor::Codex {
@@ -280,6 +281,7 @@ fn pre_execution_audit_baseline_prefers_per_execution_snapshot() {
         vcs_identity: None,
         identity_version: 2,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     };
     let executor = Executor::Codex {
         model_override: None,
@@ -439,6 +441,7 @@ fn audit_failure_does_not_fail_execution() {
         vcs_identity: None,
         identity_version: 2,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     };
     let mut session_result = SessionResult {
         post_exec_gate: None,
@@ -563,6 +566,7 @@ fn reused_session_audit_uses_per_execution_baseline_not_session_creation() {
         vcs_identity: None,
         identity_version: 2,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     };
     let mut session_result = SessionResult {
         post_exec_gate: None,
@@ -666,6 +670,7 @@ fn writer_run_does_not_emit_repo_write_audit_artifact() {
         vcs_identity: None,
         identity_version: 2,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     };
     let mut session_result = SessionResult {
         post_exec_gate: None,
@@ -770,6 +775,7 @@ fn first_execution_falls_back_to_session_creation_baseline_when_per_exec_capture
         vcs_identity: None,
         identity_version: 2,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     };
     let mut session_result = SessionResult {
         post_exec_gate: None,
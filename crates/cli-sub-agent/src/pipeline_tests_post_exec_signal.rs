@@ -29,6 +29,7 @@ async fn process_execution_result_persists_signal_kill_hint() {
         memory_max_mb: 12_288,
         soft_limit_percent: 70,
         scope_name: "csa-codex-01KTEST.scope".to_string(),
+        partial_results_saved: false,
     };
     csa_resource::memory_monitor::record_soft_limit_diagnostic_evidence(
         &memory_registry_key,
@@ -553,6 +553,7 @@ async fn state_dir_cap_failure_overwrites_stale_result_for_resume() {
                 ),
                 ..Default::default()
             },
+            resources: None,
         },
     )
     .expect("seed stale result");
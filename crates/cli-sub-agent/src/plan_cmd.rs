@@ -11,10 +11,12 @@
 //! - Workflow variables from `--var KEY=VALUE` and `STEP_<id>_OUTPUT`
 //! - `${VAR}` substitution for CSA prompts, step tiers, and condition evaluation
 //! - `on_fail` handling: abort / skip / retry N
-//! - `condition` evaluation: `${VAR}` truthiness, `!(expr)`, `(a) && (b)`
-//! - Steps with `loop_var` are skipped with a warning (v2)
+//! - `condition` evaluation via `weave::condition`: `${VAR}` truthiness, `!`, `&&`, `||`,
+//!   comparisons, `exists(path)` and `env(NAME)`
+//! - Steps with `loop_var` or `repeat` (FOR/WHILE/UNTIL) are skipped with a warning (v2)
 
 use std::collections::HashMap;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
@@ -30,6 +32,7 @@ use crate::pipeline::determine_project_root;
 use crate::plan_display::{print_plan, print_summary};
 use crate::run_resource_overrides::RunResourceOverrides;
 use crate::startup_env::StartupSubtreeEnv;
+use crate::template_vars::{parse_var_assignments, substitute_vars, validate_variable_name};
 
 #[path = "plan_cmd_exec.rs"]
 mod plan_cmd_exec;
@@ -56,6 +59,10 @@ mod plan_cmd_tier_failover;
 
 #[path = "plan_cmd_child_diagnostics.rs"]
 mod plan_cmd_child_diagnostics;
+#[path = "plan_cmd_inputs.rs"]
+mod plan_cmd_inputs;
+#[path = "plan_cmd_interactive.rs"]
+mod plan_cmd_interactive;
 #[path = "plan_cmd_steps.rs"]
 mod plan_cmd_steps;
 #[cfg(test)]
@@ -63,9 +70,11 @@ mod plan_cmd_steps;
 mod plan_cmd_steps_test_helpers;
 #[cfg(test)]
 pub(crate) use plan_cmd_assignment::{
-    extract_output_assignment_markers, is_assignment_marker_key, should_inject_assignment_markers,
+    captured_output, extract_output_assignment_markers, is_assignment_marker_key,
+    should_inject_assignment_markers,
 };
 pub(crate) use plan_cmd_flow::shell_escape_for_command;
+pub(crate) use plan_cmd_inputs::resolve_inputs_before_spawn;
 pub(crate) use plan_cmd_repo::detect_effective_repo;
 #[cfg(test)]
 pub(crate) use plan_cmd_steps::resolve_step_tool;
@@ -81,9 +90,9 @@ pub(crate) use plan_cmd_steps_test_helpers::{
 // Re-exported here so the daemon dispatch (`crate::plan_cmd::PlanRunPipelineSource`)
 // and the in-module step/test submodules (`super::*`) keep their original paths.
 pub(crate) use crate::plan_cmd_journal::{
-    PLAN_JOURNAL_SCHEMA_VERSION, PlanRunJournal, PlanRunPipelineSource, apply_repo_fingerprint,
-    complete_pending_manual_step, detect_repo_fingerprint, load_plan_resume_context,
-    persist_plan_journal, plan_journal_path,
+    PLAN_JOURNAL_SCHEMA_VERSION, PlanRunJournal, PlanRunPipelineSource, StepApproval,
+    apply_repo_fingerprint, complete_pending_manual_step, detect_repo_fingerprint,
+    load_plan_resume_context, persist_plan_journal, plan_journal_path,
 };
 // Referenced only from the `#[cfg(test)]` submodules; gated to avoid an
 // unused-import error in non-test builds.
@@ -151,6 +160,8 @@ pub(crate) struct PlanRunArgs {
     pub model_spec_override: Option<String>,
     pub dry_run: bool,
     pub chunked: bool,
+    /// Ask for approval before each executing step (`--interactive`).
+    pub interactive: bool,
     pub resume: Option<String>,
     pub complete_manual_step: Option<usize>,
     pub cd: Option<String>,
@@ -182,6 +193,7 @@ pub(crate) async fn handle_plan_run(args: PlanRunArgs) -> Result<PlanRunOutcome>
         model_spec_override,
         dry_run,
         chunked,
+        interactive,
         resume,
         complete_manual_step,
         cd,
@@ -285,7 +297,7 @@ pub(crate) async fn handle_plan_run(args: PlanRunArgs) -> Result<PlanRunOutcome>
     };
 
     // 5. Parse --var KEY=VALUE into HashMap
-    let cli_variables = parse_variables(&vars, &plan)?;
+    let mut cli_variables = parse_variables(&vars, &plan)?;
 
     // 6. Dry-run: print plan and exit
     if dry_run {
@@ -293,6 +305,15 @@ pub(crate) async fn handle_plan_run(args: PlanRunArgs) -> Result<PlanRunOutcome>
         return Ok(PlanRunOutcome::default());
     }
 
+    if interactive && !std::io::stdin().is_terminal() {
+        bail!("--interactive asks before each step and needs a terminal on stdin");
+    }
+
+    // A resumed run takes its inputs from the journal.
+    if !explicit_resume {
+        plan_cmd_inputs::resolve_plan_inputs(&plan, &mut cli_variables)?;
+    }
+
     if let Some(step_id) = complete_manual_step {
         if !explicit_resume {
             bail!("--complete-manual-step requires --resume");
@@ -356,6 +377,7 @@ pub(crate) async fn handle_plan_run(args: PlanRunArgs) -> Result<PlanRunOutcome>
         .clone()
         .unwrap_or_else(|| pipeline_source.as_str().to_string());
     journal.completed_steps = resume_context.completed_steps.iter().copied().collect();
+    journal.repeat_iterations = resume_context.repeat_iterations.clone();
     apply_repo_fingerprint(&mut journal, &current_repo_fingerprint);
     persist_plan_journal(&journal_path, &journal)?;
     let mut run_ctx = PlanRunContext {
@@ -370,6 +392,7 @@ pub(crate) async fn handle_plan_run(args: PlanRunArgs) -> Result<PlanRunOutcome>
         journal_path: Some(&journal_path),
         resume_completed_steps: &resume_context.completed_steps,
         chunked,
+        interactive,
         no_fs_sandbox,
         resources,
         startup_env: &startup_env,
@@ -449,7 +472,7 @@ pub(crate) async fn handle_plan_run(args: PlanRunArgs) -> Result<PlanRunOutcome>
         });
     }
 
-    // 9. Warn about unsupported skips (loop_var)
+    // 9. Warn about unsupported skips (loop_var / repeat)
     let unsupported_skips = results
         .iter()
         .filter(|r| r.skipped && r.exit_code != 0)
@@ -576,45 +599,11 @@ fn parse_variables(cli_vars: &[String], plan: &ExecutionPlan) -> Result<HashMap<
     }
 
     // Override with CLI --var values
-    for entry in cli_vars {
-        let (key, value) = entry
-            .split_once('=')
-            .with_context(|| format!("Invalid --var format '{entry}': expected KEY=VALUE"))?;
-        validate_variable_name(key)?;
-        vars.insert(key.to_string(), value.to_string());
-    }
+    vars.extend(parse_var_assignments(cli_vars)?);
 
     Ok(vars)
 }
 
-/// Validate variable name format (`[A-Za-z_][A-Za-z0-9_]*`).
-fn validate_variable_name(name: &str) -> Result<()> {
-    let mut chars = name.chars();
-    let Some(first) = chars.next() else {
-        bail!("Invalid variable name '': must match [A-Za-z_][A-Za-z0-9_]*");
-    };
-
-    if !(first == '_' || first.is_ascii_alphabetic()) {
-        bail!("Invalid variable name '{name}': must match [A-Za-z_][A-Za-z0-9_]*");
-    }
-
-    if chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric()) {
-        Ok(())
-    } else {
-        bail!("Invalid variable name '{name}': must match [A-Za-z_][A-Za-z0-9_]*");
-    }
-}
-
-/// Substitute `${VAR}` placeholders in a workflow string.
-fn substitute_vars(template: &str, vars: &HashMap<String, String>) -> String {
-    let mut result = template.to_string();
-    for (key, value) in vars {
-        let placeholder = format!("${{{key}}}");
-        result = result.replace(&placeholder, value);
-    }
-    result
-}
-
 #[cfg(test)]
 #[path = "plan_cmd_tests.rs"]
 mod tests;
@@ -623,6 +612,10 @@ mod tests;
 #[path = "plan_cmd_tests_tail.rs"]
 mod tests_tail;
 
+#[cfg(test)]
+#[path = "plan_cmd_tests_control_flow.rs"]
+mod tests_control_flow;
+
 #[cfg(test)]
 #[path = "plan_cmd_tests_issue.rs"]
 mod tests_issue;
@@ -679,6 +672,18 @@ mod tests_commit;
 #[path = "plan_cmd_override_tests.rs"]
 mod override_tests;
 
+#[cfg(test)]
+#[path = "plan_cmd_output_tests.rs"]
+mod output_tests;
+
 #[cfg(test)]
 #[path = "plan_cmd_resource_inheritance_tests.rs"]
 mod resource_inheritance_tests;
+
+#[cfg(test)]
+#[path = "plan_cmd_parallel_tests.rs"]
+mod parallel_tests;
+
+#[cfg(test)]
+#[path = "plan_cmd_repeat_tests.rs"]
+mod repeat_tests;
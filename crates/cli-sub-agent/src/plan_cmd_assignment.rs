@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use weave::compiler::PlanStep;
+use weave::compiler::{OutputBinding, PlanStep};
 
 use super::validate_variable_name;
 
@@ -15,6 +15,38 @@ pub(crate) fn strip_assignment_marker_lines(output: &str) -> String {
         .join("\n")
 }
 
+/// Value bound by a step's `Output:` hint: the whole output, or the body of the
+/// Markdown section named by the binding. `None` when that section is absent.
+pub(crate) fn captured_output(binding: &OutputBinding, output: &str) -> Option<String> {
+    match &binding.section {
+        None => Some(output.to_string()),
+        Some(heading) => markdown_section(output, heading),
+    }
+}
+
+/// Lines under the first `#`-heading titled `heading` (case-insensitive), up
+/// to the next heading of the same or a higher level.
+fn markdown_section(output: &str, heading: &str) -> Option<String> {
+    let mut lines = output.lines();
+    let level = lines.by_ref().find_map(|line| {
+        let (level, title) = parse_heading(line)?;
+        title.eq_ignore_ascii_case(heading.trim()).then_some(level)
+    })?;
+    let body: Vec<&str> = lines
+        .take_while(|line| parse_heading(line).is_none_or(|(next, _)| next > level))
+        .collect();
+    Some(body.join("\n").trim().to_string())
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    let title = trimmed[level..].strip_prefix(' ')?;
+    (1..=6)
+        .contains(&level)
+        .then(|| (level, title.trim().trim_end_matches('#').trim_end()))
+}
+
 pub(crate) fn extract_output_assignment_markers(
     output: &str,
     allowlist: &HashSet<String>,
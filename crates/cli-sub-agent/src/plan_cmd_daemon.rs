@@ -65,6 +65,7 @@ pub(crate) async fn dispatch(
         tier,
         dry_run,
         chunked,
+        interactive,
         resume,
         complete_manual_step,
         cd,
@@ -77,7 +78,7 @@ pub(crate) async fn dispatch(
     } = cmd;
 
     if let Some(tier_name) = tier {
-        anyhow::bail!(
+        return Err(csa_core::error::AppError::config(format!(
             "`csa plan run` does not accept --tier because the plan runner executes \
              workflow steps and does not select a provider itself.\n\
              For dev2merge/mktd implementation routing, pass the workflow variable: \
@@ -85,7 +86,8 @@ pub(crate) async fn dispatch(
              For mktd plan-authoring sessions, use --var PLAN_TIER={tier_name}. \
              For one workflow step, put csa run --tier <name> in that step or use an \
              Implementation override: csa run --tier {tier_name} ..."
-        );
+        ))
+        .into());
     }
 
     // Resolve --issue once in the parent; daemon children receive forwarded vars.
@@ -124,6 +126,7 @@ pub(crate) async fn dispatch(
         model_spec_override: model_spec,
         dry_run,
         chunked,
+        interactive,
         resume,
         complete_manual_step,
         cd,
@@ -180,7 +183,8 @@ pub(crate) async fn dispatch_plan_run(
     }
 
     let needs_foreground = decide_needs_foreground(ForegroundDecisionInput {
-        foreground: input.foreground,
+        // Approval prompts need the caller's terminal.
+        foreground: input.foreground || plan_args.interactive,
         dry_run: plan_args.dry_run,
         chunked: plan_args.chunked,
         has_resume: plan_args.resume.is_some(),
@@ -189,7 +193,18 @@ pub(crate) async fn dispatch_plan_run(
     });
 
     if !needs_foreground {
-        spawn_and_exit(&plan_args, input.forwarded_args)?;
+        // The daemon child has no terminal, so ask for missing inputs here.
+        let mut plan_args = plan_args;
+        let answered = plan_cmd::resolve_inputs_before_spawn(&mut plan_args)?;
+        let forwarded_args = if answered.is_empty() {
+            input.forwarded_args
+        } else {
+            let base = input.forwarded_args.unwrap_or_else(|| {
+                build_forwarded_plan_args(&std::env::args().collect::<Vec<_>>())
+            });
+            Some(forwarded_args_with_vars(base, &answered))
+        };
+        spawn_and_exit(&plan_args, forwarded_args)?;
         unreachable!("plan daemon spawn returned without exiting");
     }
 
@@ -590,7 +605,9 @@ fn nested_session_env_present(startup_env: &StartupSubtreeEnv) -> bool {
 
 #[path = "plan_cmd_daemon_forwarding.rs"]
 mod forwarding;
-pub(crate) use forwarding::{build_forwarded_plan_args, forwarded_args_with_feature_input};
+pub(crate) use forwarding::{
+    build_forwarded_plan_args, forwarded_args_with_feature_input, forwarded_args_with_vars,
+};
 
 #[cfg(test)]
 #[path = "plan_cmd_daemon_completion_tests.rs"]
@@ -117,6 +117,23 @@ fn forwarded_args_with_issue_vars(
     forwarded
 }
 
+/// Append `--var` assignments to daemon-child args, ahead of any `--`
+/// positional separator.
+pub(crate) fn forwarded_args_with_vars(
+    mut base: Vec<String>,
+    assignments: &[String],
+) -> Vec<String> {
+    let insert_at = base
+        .iter()
+        .position(|token| token == "--")
+        .unwrap_or(base.len());
+    let vars = assignments
+        .iter()
+        .flat_map(|assignment| ["--var".to_string(), assignment.clone()]);
+    base.splice(insert_at..insert_at, vars);
+    base
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +185,28 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn answered_inputs_are_forwarded_before_double_dash() {
+        let base = vec![
+            "--pattern".to_string(),
+            "merge".to_string(),
+            "--".to_string(),
+            "literal".to_string(),
+        ];
+
+        let forwarded = forwarded_args_with_vars(base, &["TARGET_BRANCH=main".to_string()]);
+
+        assert_eq!(
+            forwarded,
+            vec![
+                "--pattern",
+                "merge",
+                "--var",
+                "TARGET_BRANCH=main",
+                "--",
+                "literal",
+            ]
+        );
+    }
 }
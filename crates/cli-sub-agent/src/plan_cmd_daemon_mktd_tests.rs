@@ -53,6 +53,7 @@ fn plan_daemon_args(project_root: &Path) -> PlanRunArgs {
         model_spec_override: None,
         dry_run: false,
         chunked: false,
+        interactive: false,
         resume: None,
         complete_manual_step: None,
         cd: Some(project_root.display().to_string()),
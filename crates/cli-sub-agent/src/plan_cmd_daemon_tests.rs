@@ -12,6 +12,7 @@ fn make_args() -> PlanRunArgs {
         model_spec_override: None,
         dry_run: false,
         chunked: false,
+        interactive: false,
         resume: None,
         complete_manual_step: None,
         cd: None,
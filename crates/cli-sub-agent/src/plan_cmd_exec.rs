@@ -6,6 +6,7 @@ use anyhow::{Context, Result, bail};
 use tracing::{info, warn};
 
 use csa_config::ProjectConfig;
+use csa_core::error::AppError;
 use csa_core::types::{OutputFormat, ToolName};
 
 use crate::codex_transcript_filter::{
@@ -364,13 +365,18 @@ pub(super) async fn execute_csa_step(
     ) {
         Ok(csa_lock::slot::SlotAcquireResult::Acquired(slot)) => slot,
         Ok(csa_lock::slot::SlotAcquireResult::Exhausted(status)) => {
-            bail!(
+            return Err(anyhow::Error::new(AppError::SlotExhausted {
+                tool: executor.tool_name().to_string(),
+                max: status.max_slots,
+                alternatives: Vec::new(),
+            })
+            .context(format!(
                 "All {} slots for '{}' occupied ({}/{})",
                 max_concurrent,
                 executor.tool_name(),
                 status.occupied,
                 status.max_slots,
-            );
+            )));
         }
         Err(e) => bail!(
             "Slot acquisition failed for '{}': {}",
@@ -0,0 +1,104 @@
+//! Required workflow inputs for `csa plan run`.
+//!
+//! Workflows declare typed inputs in their skill frontmatter (see
+//! `weave::inputs`). Before the first step runs, supplied values are checked
+//! against their types and required inputs without a value are either asked
+//! for on the terminal or reported as a `missing_inputs` JSON object on stdout.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{Context, Result, bail};
+use weave::compiler::{ExecutionPlan, VariableDecl, plan_from_toml};
+use weave::inputs::{MissingInputsReport, check_inputs, missing_inputs};
+
+use super::{PlanRunArgs, parse_variables, resolve_workflow_path};
+use crate::pipeline::determine_project_root;
+
+/// Check `values` against the plan's declared inputs and fill in missing
+/// required ones, returning the added `KEY=VALUE` assignments.
+///
+/// Without a terminal on stdin, prints a [`MissingInputsReport`] to stdout
+/// and fails instead of prompting.
+pub(super) fn resolve_plan_inputs(
+    plan: &ExecutionPlan,
+    values: &mut HashMap<String, String>,
+) -> Result<Vec<String>> {
+    check_inputs(plan, values)?;
+    let missing = missing_inputs(plan, values);
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !io::stdin().is_terminal() {
+        let report = MissingInputsReport::new(plan, &missing);
+        println!("{}", serde_json::to_string(&report)?);
+        let names: Vec<&str> = missing.iter().map(|decl| decl.name.as_str()).collect();
+        bail!(
+            "Workflow '{}' is missing required inputs: {}. Supply them with --var NAME=VALUE",
+            plan.name,
+            names.join(", ")
+        );
+    }
+    let answers = prompt_for_inputs(&missing, &mut io::stdin().lock(), &mut io::stderr())?;
+    let assignments = answers
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    values.extend(answers);
+    Ok(assignments)
+}
+
+/// Resolve inputs in the daemon parent, which still owns the terminal, and
+/// append the answers to `args.vars`. Returns the added assignments so they
+/// can be forwarded to the daemon child.
+pub(crate) fn resolve_inputs_before_spawn(args: &mut PlanRunArgs) -> Result<Vec<String>> {
+    let project_root = determine_project_root(args.cd.as_deref())?;
+    let path = resolve_workflow_path(&args.file, &args.pattern, &project_root)?;
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read workflow file: {}", path.display()))?;
+    let plan = plan_from_toml(&content)
+        .with_context(|| format!("Failed to parse workflow file: {}", path.display()))?;
+    let mut values = parse_variables(&args.vars, &plan)?;
+    let assignments = resolve_plan_inputs(&plan, &mut values)?;
+    args.vars.extend(assignments.iter().cloned());
+    Ok(assignments)
+}
+
+/// Ask for each missing input in turn, re-asking on empty or ill-typed
+/// answers. End of input fails.
+fn prompt_for_inputs(
+    missing: &[&VariableDecl],
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Vec<(String, String)>> {
+    let mut answers = Vec::with_capacity(missing.len());
+    for decl in missing {
+        let kind = decl.kind.unwrap_or_default();
+        if let Some(description) = &decl.description {
+            writeln!(output, "{}: {description}", decl.name)?;
+        }
+        let value = loop {
+            write!(output, "{} ({kind}): ", decl.name)?;
+            output.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                bail!("No value given for required input {}", decl.name);
+            }
+            let value = line.trim_end_matches(['\n', '\r']);
+            if value.is_empty() {
+                writeln!(output, "A value is required.")?;
+                continue;
+            }
+            match kind.check(value) {
+                Ok(()) => break value.to_string(),
+                Err(err) => writeln!(output, "Invalid value: {err}")?,
+            }
+        };
+        answers.push((decl.name.clone(), value));
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+#[path = "plan_cmd_inputs_tests.rs"]
+mod tests;
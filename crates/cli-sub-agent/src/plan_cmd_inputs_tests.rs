@@ -0,0 +1,62 @@
+use super::*;
+
+use weave::inputs::VariableType;
+
+fn decl(name: &str, kind: VariableType, description: Option<&str>) -> VariableDecl {
+    VariableDecl {
+        name: name.to_string(),
+        default: None,
+        kind: Some(kind),
+        description: description.map(str::to_string),
+        required: true,
+    }
+}
+
+fn prompt(decls: &[VariableDecl], answers: &str) -> (Result<Vec<(String, String)>>, String) {
+    let missing: Vec<&VariableDecl> = decls.iter().collect();
+    let mut output = Vec::new();
+    let result = prompt_for_inputs(&missing, &mut answers.as_bytes(), &mut output);
+    (result, String::from_utf8(output).expect("utf8"))
+}
+
+#[test]
+fn prompt_for_inputs_asks_for_each_input_with_its_type() {
+    let decls = [
+        decl("BRANCH", VariableType::String, Some("Branch to merge into")),
+        decl("RETRIES", VariableType::Int, None),
+    ];
+    let (answers, output) = prompt(&decls, "main\n3\n");
+    assert_eq!(
+        answers.expect("answers"),
+        [
+            ("BRANCH".to_string(), "main".to_string()),
+            ("RETRIES".to_string(), "3".to_string()),
+        ]
+    );
+    assert_eq!(
+        output,
+        "BRANCH: Branch to merge into\nBRANCH (string): RETRIES (int): "
+    );
+}
+
+#[test]
+fn prompt_for_inputs_reasks_on_empty_or_invalid_answers() {
+    let decls = [decl("DRY_RUN", VariableType::Bool, None)];
+    let (answers, output) = prompt(&decls, "\nmaybe\r\ntrue\n");
+    assert_eq!(
+        answers.expect("answers"),
+        [("DRY_RUN".to_string(), "true".to_string())]
+    );
+    assert!(output.contains("A value is required."), "{output}");
+    assert!(
+        output.contains("Invalid value: expected `true` or `false`, got 'maybe'"),
+        "{output}"
+    );
+}
+
+#[test]
+fn prompt_for_inputs_fails_at_end_of_input() {
+    let decls = [decl("BRANCH", VariableType::String, None)];
+    let err = prompt(&decls, "").0.unwrap_err();
+    assert!(err.to_string().contains("BRANCH"), "{err}");
+}
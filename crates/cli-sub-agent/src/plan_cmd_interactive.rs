@@ -0,0 +1,208 @@
+//! Approval gates for `csa plan run --interactive`.
+//!
+//! Before each step that would run a bash script or dispatch a CSA tool, the
+//! runner shows the step, its resolved tool and rendered prompt, and asks
+//! whether to approve, edit the prompt, skip, or abort. Steps that would not
+//! execute anyway (false condition, notes, includes, manual handoffs) pass
+//! without a question. Every decision is recorded in the run journal.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Context, Result, bail};
+use weave::compiler::PlanStep;
+
+use super::plan_cmd_exec::extract_bash_code_block;
+use super::plan_cmd_steps::PlanRunContext;
+use super::{
+    StepApproval, StepResult, StepTarget, apply_repo_fingerprint, detect_repo_fingerprint,
+    persist_plan_journal, resolve_step_tool_with_variables, substitute_vars,
+};
+use crate::plan_display::describe_step_target;
+
+/// Answer given at an approval gate.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum StepDecision {
+    Approve,
+    /// Run the step with this prompt instead of the rendered one.
+    Edit(String),
+    Skip,
+    Abort,
+}
+
+impl StepDecision {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approve => "approved",
+            Self::Edit(_) => "edited",
+            Self::Skip => "skipped",
+            Self::Abort => "aborted",
+        }
+    }
+}
+
+/// How the runner proceeds with a step after its gate.
+pub(super) enum Approval {
+    /// Execute the step, or the edited copy when present.
+    Run(Option<PlanStep>),
+    Skip,
+}
+
+/// Ask about `step` on the terminal and record the decision in the journal.
+///
+/// Aborting persists the journal with status `aborted` and returns an error.
+pub(super) fn approve_step(
+    step: &PlanStep,
+    vars: &HashMap<String, String>,
+    run_ctx: &mut PlanRunContext<'_>,
+) -> Result<Approval> {
+    let Some((tool, prompt)) = gated_step(step, vars, run_ctx) else {
+        return Ok(Approval::Run(None));
+    };
+    let decision = ask_step_decision(
+        step,
+        &tool,
+        &prompt,
+        &mut io::stdin().lock(),
+        &mut io::stderr(),
+        edit_in_editor,
+    )?;
+    run_ctx.journal.approvals.push(StepApproval {
+        step_id: step.id,
+        title: step.title.clone(),
+        decision: decision.as_str().to_string(),
+    });
+    match decision {
+        StepDecision::Approve => Ok(Approval::Run(None)),
+        StepDecision::Edit(prompt) => Ok(Approval::Run(Some(PlanStep {
+            prompt,
+            ..step.clone()
+        }))),
+        StepDecision::Skip => Ok(Approval::Skip),
+        StepDecision::Abort => {
+            let message = format!(
+                "Step {} ('{}') aborted at the approval gate",
+                step.id, step.title
+            );
+            run_ctx.journal.status = "aborted".to_string();
+            run_ctx.journal.last_error = Some(message.clone());
+            apply_repo_fingerprint(
+                run_ctx.journal,
+                &detect_repo_fingerprint(run_ctx.project_root),
+            );
+            if let Some(path) = run_ctx.journal_path {
+                persist_plan_journal(path, run_ctx.journal)?;
+            }
+            bail!(message)
+        }
+    }
+}
+
+/// Result of a step the user skipped at its gate.
+pub(super) fn skipped_by_user(step: &PlanStep) -> StepResult {
+    StepResult {
+        step_id: step.id,
+        title: step.title.clone(),
+        exit_code: 0,
+        duration_secs: 0.0,
+        skipped: true,
+        error: Some("skipped at approval gate".to_string()),
+        output: None,
+        session_id: None,
+        command: None,
+        stderr: None,
+    }
+}
+
+/// Resolved tool and rendered prompt of a step the runner would execute, or
+/// `None` when it would skip or hand the step off without running anything.
+fn gated_step(
+    step: &PlanStep,
+    vars: &HashMap<String, String>,
+    run_ctx: &PlanRunContext<'_>,
+) -> Option<(String, String)> {
+    if step.loop_var.is_some() {
+        return None;
+    }
+    if let Some(condition) = &step.condition
+        && !weave::condition::evaluate_condition_in(condition, vars, run_ctx.project_root)
+    {
+        return None;
+    }
+    let target = resolve_step_tool_with_variables(
+        step,
+        vars,
+        run_ctx.config,
+        run_ctx.tool_override,
+        run_ctx.model_spec_override,
+    );
+    let prompt = match &target {
+        Ok(StepTarget::DirectBash) => extract_bash_code_block(&step.prompt)
+            .unwrap_or(&step.prompt)
+            .to_string(),
+        Ok(StepTarget::CsaTool { .. }) => substitute_vars(&step.prompt, vars),
+        _ => return None,
+    };
+    Some((describe_step_target(&target), prompt))
+}
+
+/// Show the step and read `a`pprove, `e`dit, `s`kip, or `q` (abort).
+/// Unknown answers ask again; end of input aborts.
+fn ask_step_decision(
+    step: &PlanStep,
+    tool: &str,
+    prompt: &str,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    edit: impl Fn(&str) -> Result<String>,
+) -> Result<StepDecision> {
+    writeln!(output)?;
+    writeln!(output, "=== Step {}: {} ===", step.id, step.title)?;
+    writeln!(output, "Tool: {tool}")?;
+    if let Some(condition) = &step.condition {
+        writeln!(output, "Condition: {condition}")?;
+    }
+    writeln!(output, "--- prompt ---")?;
+    writeln!(output, "{}", prompt.trim_end())?;
+    writeln!(output, "--------------")?;
+    loop {
+        write!(output, "Run this step? [a]pprove/[e]dit/[s]kip/[q]abort ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(StepDecision::Abort);
+        }
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "a" | "approve" | "y" | "yes" => return Ok(StepDecision::Approve),
+            "e" | "edit" => return Ok(StepDecision::Edit(edit(prompt)?)),
+            "s" | "skip" => return Ok(StepDecision::Skip),
+            "q" | "quit" | "abort" => return Ok(StepDecision::Abort),
+            _ => writeln!(output, "Please answer a, e, s, or q.")?,
+        }
+    }
+}
+
+/// Open `$EDITOR` (default `vi`) on `text` and return the saved contents.
+fn edit_in_editor(text: &str) -> Result<String> {
+    let file = tempfile::Builder::new()
+        .prefix("csa-plan-step-")
+        .suffix(".md")
+        .tempfile()
+        .context("failed to create prompt file for editing")?;
+    std::fs::write(file.path(), text).context("failed to write prompt file for editing")?;
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let (program, args) = crate::config_cmds::parse_editor_command(&editor)?;
+    let status = std::process::Command::new(&program)
+        .args(args)
+        .arg(file.path())
+        .status()
+        .with_context(|| format!("failed to launch editor '{program}'"))?;
+    if !status.success() {
+        bail!("Editor exited with non-zero status: {status}");
+    }
+    std::fs::read_to_string(file.path()).context("failed to read edited prompt")
+}
+
+#[cfg(test)]
+#[path = "plan_cmd_interactive_tests.rs"]
+mod tests;
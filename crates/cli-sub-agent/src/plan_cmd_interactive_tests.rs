@@ -0,0 +1,85 @@
+use super::*;
+
+use weave::compiler::FailAction;
+
+fn step() -> PlanStep {
+    PlanStep {
+        id: 3,
+        title: "Deploy".to_string(),
+        tool: Some("bash".to_string()),
+        prompt: "```bash\n./deploy.sh\n```".to_string(),
+        tier: None,
+        depends_on: Vec::new(),
+        on_fail: FailAction::Abort,
+        condition: Some("${READY}".to_string()),
+        loop_var: None,
+        session: None,
+        workspace_access: None,
+        ..Default::default()
+    }
+}
+
+fn decide(answers: &str) -> (StepDecision, String) {
+    let mut output = Vec::new();
+    let decision = ask_step_decision(
+        &step(),
+        "bash (direct)",
+        "./deploy.sh\n",
+        &mut answers.as_bytes(),
+        &mut output,
+        |prompt| Ok(format!("{prompt}--dry-run\n")),
+    )
+    .expect("decision");
+    (decision, String::from_utf8(output).expect("utf8"))
+}
+
+#[test]
+fn ask_step_decision_shows_the_step_before_asking() {
+    let (decision, output) = decide("a\n");
+    assert_eq!(decision, StepDecision::Approve);
+    assert!(
+        output.contains(
+            "=== Step 3: Deploy ===\nTool: bash (direct)\nCondition: ${READY}\n\
+             --- prompt ---\n./deploy.sh\n--------------\n"
+        ),
+        "{output}"
+    );
+    assert!(output.ends_with("[a]pprove/[e]dit/[s]kip/[q]abort "));
+}
+
+#[test]
+fn ask_step_decision_maps_answers() {
+    assert_eq!(decide("s\n").0, StepDecision::Skip);
+    assert_eq!(decide("Q\n").0, StepDecision::Abort);
+    assert_eq!(
+        decide("edit\n").0,
+        StepDecision::Edit("./deploy.sh\n--dry-run\n".to_string())
+    );
+
+    let (decision, output) = decide("maybe\nyes\n");
+    assert_eq!(decision, StepDecision::Approve);
+    assert!(output.contains("Please answer a, e, s, or q."));
+}
+
+#[test]
+fn ask_step_decision_aborts_at_end_of_input() {
+    assert_eq!(decide("").0, StepDecision::Abort);
+}
+
+#[test]
+fn decisions_are_recorded_with_stable_names() {
+    let names: Vec<&str> = [
+        StepDecision::Approve,
+        StepDecision::Edit(String::new()),
+        StepDecision::Skip,
+        StepDecision::Abort,
+    ]
+    .iter()
+    .map(StepDecision::as_str)
+    .collect();
+    assert_eq!(names, ["approved", "edited", "skipped", "aborted"]);
+
+    let skipped = skipped_by_user(&step());
+    assert!(skipped.skipped);
+    assert_eq!(skipped.exit_code, 0);
+}
@@ -7,7 +7,7 @@
 //! from `plan_cmd` (`crate::plan_cmd`) so existing callers, the daemon
 //! dispatch, and the in-module test submodules keep their original paths.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{File, OpenOptions};
 use std::os::fd::AsRawFd;
 use std::path::{Path, PathBuf};
@@ -73,6 +73,22 @@ pub(crate) struct PlanRunJournal {
     pub(crate) repo_dirty: Option<bool>,
     #[serde(default = "RunResourceOverrides::absent")]
     pub(crate) resource_overrides: RunResourceOverrides,
+    /// Decisions taken at `--interactive` approval gates, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) approvals: Vec<StepApproval>,
+    /// Finished iterations of each `WHILE`/`UNTIL` block still repeating,
+    /// by block id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) repeat_iterations: BTreeMap<usize, u32>,
+}
+
+/// One approval-gate decision of an interactive plan run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StepApproval {
+    pub(crate) step_id: usize,
+    pub(crate) title: String,
+    /// `approved`, `edited`, `skipped`, or `aborted`.
+    pub(crate) decision: String,
 }
 
 impl PlanRunJournal {
@@ -93,6 +109,8 @@ impl PlanRunJournal {
             repo_head: None,
             repo_dirty: None,
             resource_overrides: RunResourceOverrides::absent(),
+            approvals: Vec::new(),
+            repeat_iterations: BTreeMap::new(),
         }
     }
 }
@@ -102,6 +120,7 @@ pub(crate) struct PlanResumeContext {
     pub(crate) completed_steps: HashSet<usize>,
     pub(crate) pipeline_source: Option<String>,
     pub(crate) resource_overrides: RunResourceOverrides,
+    pub(crate) repeat_iterations: BTreeMap<usize, u32>,
     pub(crate) resumed: bool,
 }
 
@@ -376,6 +395,7 @@ pub(crate) fn load_plan_resume_context(
             completed_steps: HashSet::new(),
             pipeline_source: None,
             resource_overrides: RunResourceOverrides::absent(),
+            repeat_iterations: BTreeMap::new(),
             resumed: false,
         });
     }
@@ -411,6 +431,7 @@ pub(crate) fn load_plan_resume_context(
             completed_steps: HashSet::new(),
             pipeline_source: None,
             resource_overrides: RunResourceOverrides::absent(),
+            repeat_iterations: BTreeMap::new(),
             resumed: false,
         });
     }
@@ -423,6 +444,7 @@ pub(crate) fn load_plan_resume_context(
             completed_steps: HashSet::new(),
             pipeline_source: None,
             resource_overrides: RunResourceOverrides::absent(),
+            repeat_iterations: BTreeMap::new(),
             resumed: false,
         });
     }
@@ -443,6 +465,7 @@ pub(crate) fn load_plan_resume_context(
             completed_steps: HashSet::new(),
             pipeline_source: None,
             resource_overrides: RunResourceOverrides::absent(),
+            repeat_iterations: BTreeMap::new(),
             resumed: false,
         });
     }
@@ -454,6 +477,7 @@ pub(crate) fn load_plan_resume_context(
             completed_steps: HashSet::new(),
             pipeline_source: None,
             resource_overrides: RunResourceOverrides::absent(),
+            repeat_iterations: BTreeMap::new(),
             resumed: false,
         });
     }
@@ -477,6 +501,7 @@ pub(crate) fn load_plan_resume_context(
         completed_steps: journal.completed_steps.into_iter().collect(),
         pipeline_source: Some(pipeline_source),
         resource_overrides,
+        repeat_iterations: journal.repeat_iterations,
         resumed: true,
     })
 }
@@ -0,0 +1,55 @@
+use super::*;
+use weave::compiler::{FailAction, PlanStep};
+
+#[tokio::test]
+async fn execute_plan_binds_output_section_to_named_variable() {
+    let tmp = tempfile::tempdir().unwrap();
+    let step = |id: usize, prompt: &str, output, condition: Option<&str>| PlanStep {
+        id,
+        title: format!("step {id}"),
+        tool: Some("bash".into()),
+        prompt: prompt.into(),
+        tier: None,
+        depends_on: vec![],
+        on_fail: FailAction::Abort,
+        condition: condition.map(str::to_string),
+        loop_var: None,
+        session: None,
+        workspace_access: None,
+        output,
+        ..Default::default()
+    };
+    let plan = ExecutionPlan {
+        name: "output-binding".into(),
+        description: String::new(),
+        variables: vec![],
+        steps: vec![
+            step(
+                1,
+                "```bash\nprintf '# Summary\\nall good\\n# Log\\nnoise\\n'\n```",
+                Some(weave::compiler::OutputBinding {
+                    var: "SUMMARY".into(),
+                    section: Some("Summary".into()),
+                }),
+                None,
+            ),
+            step(
+                2,
+                "```bash\nprintf '%s' \"${SUMMARY}\" > summary.txt\n```",
+                None,
+                Some("${SUMMARY}"),
+            ),
+        ],
+        ..Default::default()
+    };
+    let results = execute_plan(&plan, &HashMap::new(), tmp.path(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(
+        !results[1].skipped,
+        "bound variable must satisfy the condition"
+    );
+    let content = std::fs::read_to_string(tmp.path().join("summary.txt")).unwrap();
+    assert_eq!(content, "all good");
+}
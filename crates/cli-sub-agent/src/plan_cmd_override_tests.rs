@@ -29,6 +29,7 @@ fn resolve_step_tool_all_explicit_tools() {
             loop_var: None,
             session: None,
             workspace_access: None,
+            ..Default::default()
         };
         let target = resolve_step_tool(&step, None, None, None).unwrap();
         if expect_direct_bash {
@@ -84,6 +85,7 @@ async fn execute_step_tool_override_replaces_csa_tool() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     // Even with tool_override=claude-code, bash step must still run as bash
@@ -118,6 +120,7 @@ async fn execute_step_note_skips_without_dispatch() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
 
@@ -143,6 +146,7 @@ async fn execute_step_manual_returns_resumable_handoff() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
 
@@ -181,6 +185,7 @@ async fn execute_step_await_user_returns_instructions() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
 
@@ -220,6 +225,7 @@ fn tool_override_clears_model_spec() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let target = resolve_step_tool(&step, None, None, None).unwrap();
     // Without override: should be CsaTool with codex
@@ -271,6 +277,7 @@ fn tool_override_does_not_affect_weave_include() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let target = resolve_step_tool(&step, None, None, None).unwrap();
     // Simulate override: WeaveInclude must pass through unchanged
@@ -349,6 +356,7 @@ async fn execute_step_bash_captures_stdout_in_output() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let result = execute_step(&step, &vars, tmp.path(), None, None, None).await;
@@ -381,6 +389,7 @@ async fn execute_plan_injects_step_output_variables() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -394,8 +403,10 @@ async fn execute_plan_injects_step_output_variables() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -434,6 +445,7 @@ async fn execute_plan_skipped_step_injects_empty_output() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -447,8 +459,10 @@ async fn execute_plan_skipped_step_injects_empty_output() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -485,6 +499,7 @@ async fn execute_step_csa_empty_prompt_warns_without_panic() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let result = execute_step(&step, &vars, tmp.path(), None, None, None).await;
@@ -515,6 +530,7 @@ fn resolve_step_tool_respects_tool_override() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
 
     // Without override: should resolve to opencode as specified in step.tool
@@ -555,6 +571,7 @@ fn resolve_step_tool_respects_model_spec_override() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
 
     let tool_override = ToolName::Codex;
@@ -611,6 +628,7 @@ models = ["opencode/google/gemini-2.5-pro/high"]
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let model_spec_override = "codex/openai/gpt-5/high".to_string();
 
@@ -656,6 +674,7 @@ models = ["opencode/google/gemini-2.5-pro/high"]
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let mut vars = std::collections::HashMap::new();
     vars.insert("PLAN_TIER".to_string(), "tier-plan".to_string());
@@ -707,6 +726,7 @@ fn resolve_step_tool_model_spec_override_does_not_affect_bash() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let model_spec_override = "codex/openai/gpt-5/high".to_string();
 
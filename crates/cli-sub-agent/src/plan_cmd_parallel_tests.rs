@@ -0,0 +1,138 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use weave::compiler::{FailAction, ParallelGroup, PlanStep};
+
+fn bash_step(id: usize, script: String) -> PlanStep {
+    PlanStep {
+        id,
+        title: format!("step {id}"),
+        tool: Some("bash".into()),
+        prompt: format!("```bash\n{script}\n```"),
+        tier: None,
+        depends_on: vec![],
+        on_fail: FailAction::Abort,
+        condition: None,
+        loop_var: None,
+        session: None,
+        workspace_access: None,
+        ..Default::default()
+    }
+}
+
+fn group_plan(steps: Vec<PlanStep>, members: Vec<usize>, max: Option<u32>) -> ExecutionPlan {
+    ExecutionPlan {
+        name: "parallel".into(),
+        description: String::new(),
+        variables: vec![],
+        steps,
+        parallel_groups: vec![ParallelGroup {
+            id: 1,
+            steps: members,
+            max_concurrency: max,
+        }],
+    }
+}
+
+/// Run `plan` and return its results with the journal it left behind.
+async fn run_plan(plan: &ExecutionPlan, root: &Path) -> (Vec<StepResult>, PlanRunJournal) {
+    let workflow_path = root.join("workflow.toml");
+    let mut journal = PlanRunJournal::new(&plan.name, &workflow_path, HashMap::new());
+    let completed = HashSet::new();
+    let mut run_ctx = PlanRunContext {
+        project_root: root,
+        workflow_path: &workflow_path,
+        config: None,
+        global_config: super::test_global_config(),
+        model_catalog: super::test_model_catalog(),
+        tool_override: None,
+        model_spec_override: None,
+        journal: &mut journal,
+        journal_path: None,
+        resume_completed_steps: &completed,
+        chunked: false,
+        interactive: false,
+        no_fs_sandbox: false,
+        resources: RunResourceOverrides::absent(),
+        startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
+    };
+    let results = execute_plan_with_journal(plan, &HashMap::new(), &mut run_ctx)
+        .await
+        .unwrap();
+    (results, journal)
+}
+
+#[tokio::test]
+async fn execute_plan_runs_parallel_group_concurrently() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().display();
+    // Each step only succeeds once the other one has started.
+    let barrier = |id: usize, other: usize| {
+        bash_step(
+            id,
+            format!(
+                "touch '{dir}/started-{id}'\n\
+                 for _ in $(seq 100); do [ -e '{dir}/started-{other}' ] && exit 0; sleep 0.05; done\n\
+                 exit 1"
+            ),
+        )
+    };
+    let plan = group_plan(vec![barrier(1, 2), barrier(2, 1)], vec![1, 2], None);
+
+    let (results, _) = run_plan(&plan, tmp.path()).await;
+
+    assert_eq!(
+        results.iter().map(|r| r.step_id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert!(
+        results.iter().all(|r| r.exit_code == 0),
+        "parallel steps ran one after another"
+    );
+}
+
+#[tokio::test]
+async fn execute_plan_honours_parallel_max_concurrency() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().display();
+    // Each step fails if the other one is running at the same time.
+    let exclusive = |id: usize| {
+        bash_step(
+            id,
+            format!(
+                "[ -e '{dir}/running' ] && exit 1\n\
+                 touch '{dir}/running'\nsleep 0.3\nrm '{dir}/running'"
+            ),
+        )
+    };
+    let plan = group_plan(vec![exclusive(1), exclusive(2)], vec![1, 2], Some(1));
+
+    let (results, _) = run_plan(&plan, tmp.path()).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.exit_code == 0));
+}
+
+#[tokio::test]
+async fn aborting_parallel_step_journals_its_finished_siblings() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plan = group_plan(
+        vec![
+            bash_step(1, "exit 1".into()),
+            bash_step(2, "echo sibling-done".into()),
+            bash_step(3, "echo never".into()),
+        ],
+        vec![1, 2],
+        None,
+    );
+
+    let (results, journal) = run_plan(&plan, tmp.path()).await;
+
+    assert_eq!(
+        results.iter().map(|r| r.step_id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(journal.status, "failed");
+    assert_eq!(journal.completed_steps, vec![2]);
+    assert!(journal.vars["STEP_2_OUTPUT"].contains("sibling-done"));
+}
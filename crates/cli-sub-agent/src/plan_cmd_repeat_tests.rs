@@ -0,0 +1,66 @@
+use super::*;
+
+/// Compile `body` into a plan whose bash steps run in the test's tempdir.
+fn repeat_plan(body: &str) -> ExecutionPlan {
+    let document = format!("---\nname = \"repeat\"\n---\n{body}");
+    weave::compiler::compile(&weave::parser::parse_skill(&document).unwrap()).unwrap()
+}
+
+/// Bash step that counts its runs in `count` and reports the count as `${COUNT}`.
+const COUNT_STEP: &str = "## Count\nTool: bash\n```bash\nn=$(( $(cat count 2>/dev/null || echo 0) + 1 ))\necho \"$n\" > count\necho \"CSA_VAR:COUNT=$n\"\n```\n";
+
+fn step_ids(results: &[StepResult]) -> Vec<usize> {
+    results.iter().map(|result| result.step_id).collect()
+}
+
+#[tokio::test]
+async fn until_block_repeats_until_its_condition_holds() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plan = repeat_plan(&format!(
+        "## UNTIL ${{COUNT}} >= 3 MAX 5\n{COUNT_STEP}## ENDUNTIL\n## After\nTool: bash\n```bash\necho after\n```\n"
+    ));
+
+    let results = execute_plan(&plan, &HashMap::new(), tmp.path(), None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(step_ids(&results), vec![1, 1, 1, 2]);
+    assert!(results.iter().all(|result| result.exit_code == 0));
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("count")).unwrap(),
+        "3\n"
+    );
+}
+
+#[tokio::test]
+async fn while_block_with_a_false_condition_is_skipped() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plan = repeat_plan(&format!(
+        "## WHILE ${{COUNT}} < 2 MAX 5\n{COUNT_STEP}## ENDWHILE\n"
+    ));
+    let vars = HashMap::from([("COUNT".to_string(), "7".to_string())]);
+
+    let results = execute_plan(&plan, &vars, tmp.path(), None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(step_ids(&results), vec![1]);
+    assert!(results[0].skipped);
+    assert!(!tmp.path().join("count").exists());
+}
+
+#[tokio::test]
+async fn exhausted_repeat_guard_stops_the_plan_before_dependents() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plan = repeat_plan(&format!(
+        "## WHILE ${{COUNT}} < 100 MAX 2\n{COUNT_STEP}## ENDWHILE\n## After\nTool: bash\n```bash\ntouch after\n```\n"
+    ));
+    let vars = HashMap::from([("COUNT".to_string(), "0".to_string())]);
+
+    let results = execute_plan(&plan, &vars, tmp.path(), None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(step_ids(&results), vec![1, 1]);
+    assert!(!tmp.path().join("after").exists());
+}
@@ -27,6 +27,7 @@ csa review --range main...HEAD
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let startup_env = crate::startup_env::StartupSubtreeEnv::default();
@@ -80,6 +81,7 @@ csa review
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let startup_env = crate::startup_env::StartupSubtreeEnv::default();
 
@@ -132,6 +134,7 @@ async fn plan_retry_reuses_the_same_inherited_resource_snapshot() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let startup_env = crate::startup_env::StartupSubtreeEnv::default();
 
@@ -167,6 +170,7 @@ fn load_plan_resume_context_restores_explicit_resource_snapshot() {
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
     let journal_path = tmp.path().join("test.journal.json");
     let mut journal = PlanRunJournal::new("test", &workflow_path, HashMap::new());
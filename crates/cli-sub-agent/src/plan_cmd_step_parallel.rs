@@ -0,0 +1,60 @@
+use std::collections::{HashMap, HashSet};
+
+use tokio::sync::Semaphore;
+use weave::compiler::{ExecutionPlan, PlanStep};
+
+use super::{
+    StepExecutionContext, StepResult, execute_step_with_workflow, orchestrator_handoff_mode,
+};
+
+/// Pending members of the `## PARALLEL` group that `step` opens, with how many
+/// may run at once. `None` unless `step` is the first pending member of a
+/// group with at least two pending members, none of which hands off to the
+/// orchestrator (those stop the plan and must run one at a time).
+pub(super) fn pending_parallel_group<'p>(
+    plan: &'p ExecutionPlan,
+    step: &PlanStep,
+    completed_steps: &HashSet<usize>,
+) -> Option<(Vec<&'p PlanStep>, usize)> {
+    let group = plan
+        .parallel_groups
+        .iter()
+        .find(|group| group.steps.contains(&step.id))?;
+    let members: Vec<&PlanStep> = plan
+        .steps
+        .iter()
+        .filter(|member| group.steps.contains(&member.id) && !completed_steps.contains(&member.id))
+        .collect();
+    if members.len() < 2
+        || members[0].id != step.id
+        || members
+            .iter()
+            .any(|member| orchestrator_handoff_mode(member).is_some())
+    {
+        return None;
+    }
+    let limit = group
+        .max_concurrency
+        .map_or(members.len(), |max| (max as usize).max(1));
+    Some((members, limit))
+}
+
+/// Run `steps` against the same variables, at most `limit` at a time.
+/// Results come back in step order.
+pub(super) async fn execute_steps_concurrently(
+    steps: &[&PlanStep],
+    limit: usize,
+    variables: &HashMap<String, String>,
+    step_ctx: &StepExecutionContext<'_>,
+) -> Vec<StepResult> {
+    let semaphore = Semaphore::new(limit);
+    let runs = steps.iter().map(|step| {
+        let semaphore = &semaphore;
+        async move {
+            // The semaphore is never closed, so acquiring can only wait.
+            let _permit = semaphore.acquire().await.ok();
+            execute_step_with_workflow(step, variables, step_ctx).await
+        }
+    });
+    futures::future::join_all(runs).await
+}
@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use weave::compiler::{ExecutionPlan, PlanStep, RepeatSpec};
+use weave::parser::RepeatMode;
+
+use super::StepResult;
+
+/// What follows the last step of a `## WHILE`/`## UNTIL` block.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum RepeatOutcome {
+    /// Not the end of a block, or the block is done.
+    Continue,
+    /// Run the block again from this index into `plan.steps`.
+    Repeat { start: usize },
+    /// The condition still asks for another iteration after `max_iterations`.
+    Exhausted(String),
+}
+
+/// Index range of the steps of the `## WHILE`/`## UNTIL` block `spec`.
+fn block_range(plan: &ExecutionPlan, spec: &RepeatSpec) -> Option<RangeInclusive<usize>> {
+    let in_block = |step: &PlanStep| step.repeat.as_ref().is_some_and(|r| r.id == spec.id);
+    let start = plan.steps.iter().position(in_block)?;
+    let end = plan.steps.iter().rposition(in_block)?;
+    Some(start..=end)
+}
+
+/// Steps of the `## WHILE` block opened at `index` when its condition is
+/// false before the first iteration; they are skipped and the run continues
+/// after the block. `None` for any other step.
+pub(super) fn while_block_skipped<'p>(
+    plan: &'p ExecutionPlan,
+    index: usize,
+    vars: &HashMap<String, String>,
+    iterations: &BTreeMap<usize, u32>,
+    project_root: &Path,
+) -> Option<&'p [PlanStep]> {
+    let spec = plan.steps[index].repeat.as_ref()?;
+    let range = block_range(plan, spec)?;
+    if spec.mode != RepeatMode::While
+        || *range.start() != index
+        || iterations.contains_key(&spec.id)
+        || weave::condition::evaluate_condition_in(&spec.condition, vars, project_root)
+    {
+        return None;
+    }
+    eprintln!(
+        "[{}/{}] - SKIP (WHILE condition '{}' is false)",
+        plan.steps[index].id, plan.steps[index].title, spec.condition
+    );
+    Some(&plan.steps[range])
+}
+
+/// Result of a block step skipped because its `## WHILE` never ran.
+pub(super) fn skipped_by_while(step: &PlanStep) -> StepResult {
+    StepResult {
+        step_id: step.id,
+        title: step.title.clone(),
+        exit_code: 0,
+        duration_secs: 0.0,
+        skipped: true,
+        error: None,
+        output: None,
+        session_id: None,
+        command: None,
+        stderr: None,
+    }
+}
+
+/// Decide what follows the step at `index` once its outputs are in `vars`.
+///
+/// At the end of a block this counts the finished iteration in
+/// `iterations` (kept in the plan journal, so chunked and resumed runs keep
+/// counting) and checks the condition: `WHILE` repeats while it is true,
+/// `UNTIL` until it is true. Steps of a repeated block are removed from
+/// `completed_steps` so they run again.
+pub(super) fn after_step(
+    plan: &ExecutionPlan,
+    index: usize,
+    vars: &HashMap<String, String>,
+    iterations: &mut BTreeMap<usize, u32>,
+    completed_steps: &mut HashSet<usize>,
+    project_root: &Path,
+) -> RepeatOutcome {
+    let Some(spec) = plan.steps[index].repeat.as_ref() else {
+        return RepeatOutcome::Continue;
+    };
+    let Some(range) = block_range(plan, spec).filter(|range| *range.end() == index) else {
+        return RepeatOutcome::Continue;
+    };
+
+    let done = iterations.remove(&spec.id).unwrap_or(0) + 1;
+    let condition = weave::condition::evaluate_condition_in(&spec.condition, vars, project_root);
+    let again = match spec.mode {
+        RepeatMode::While => condition,
+        RepeatMode::Until => !condition,
+    };
+    let keyword = spec.mode.as_str().to_uppercase();
+    if !again {
+        eprintln!(
+            "{keyword} '{}' - DONE after {done} iteration(s)",
+            spec.condition
+        );
+        return RepeatOutcome::Continue;
+    }
+    if done >= spec.max_iterations {
+        return RepeatOutcome::Exhausted(format!(
+            "{keyword} block on '{}' did not finish within MAX {} iterations",
+            spec.condition, spec.max_iterations
+        ));
+    }
+
+    iterations.insert(spec.id, done);
+    for step in &plan.steps[range.clone()] {
+        completed_steps.remove(&step.id);
+    }
+    eprintln!(
+        "{keyword} '{}' - REPEAT (iteration {} of at most {})",
+        spec.condition,
+        done + 1,
+        spec.max_iterations
+    );
+    RepeatOutcome::Repeat {
+        start: *range.start(),
+    }
+}
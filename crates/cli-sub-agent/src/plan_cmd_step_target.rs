@@ -3,12 +3,14 @@ use std::collections::HashMap;
 use anyhow::{Result, bail};
 
 use csa_config::ProjectConfig;
+use csa_core::error::AppError;
 use csa_core::types::ToolName;
 use csa_executor::ModelSpec;
 use weave::compiler::PlanStep;
 use weave::parser::WorkspaceAccess;
 
 use super::substitute_vars;
+use crate::run_helpers::parse_tool_name;
 
 /// Resolved execution target for a plan step.
 /// Keeps direct shell execution separate from AI dispatch so `tool = "bash"` never falls through.
@@ -79,13 +81,6 @@ pub(crate) fn resolve_step_tool(
             "note" => return Ok(StepTarget::Note),
             "manual" => return Ok(StepTarget::Manual),
             "await-user" => return Ok(StepTarget::AwaitUser),
-            "gemini-cli" | "gemini" => {
-                bail!("{}", csa_core::types::removed_tool_error("gemini-cli"))
-            }
-            "antigravity-cli" => return Ok(StepTarget::csa(ToolName::AntigravityCli, None)),
-            "opencode" => return Ok(StepTarget::csa(ToolName::Opencode, None)),
-            "codex" => return Ok(StepTarget::csa(ToolName::Codex, None)),
-            "claude-code" => return Ok(StepTarget::csa(ToolName::ClaudeCode, None)),
             "csa" => {
                 if let Some(target) = resolve_csa_step_target(step, config)? {
                     return Ok(target);
@@ -93,12 +88,22 @@ pub(crate) fn resolve_step_tool(
                 return Ok(StepTarget::csa(ToolName::Codex, None));
             }
             "weave" => return Ok(StepTarget::WeaveInclude),
-            other => bail!(
-                "Unknown tool '{}' in step {} ('{}'). Known: bash, note, manual, await-user, opencode, codex, claude-code, csa, weave",
-                other,
-                step.id,
-                step.title
-            ),
+            other if csa_core::types::is_removed_tool_name(other) => {
+                bail!("{}", csa_core::types::removed_tool_error(other))
+            }
+            other => match ToolName::from_id(other) {
+                Some(tool) => return Ok(StepTarget::csa(tool, None)),
+                None => {
+                    return Err(AppError::config(format!(
+                        "Unknown tool '{}' in step {} ('{}'). Known: bash, note, manual, await-user, {}, csa, weave",
+                        other,
+                        step.id,
+                        step.title,
+                        csa_core::types::supported_tool_list()
+                    ))
+                    .into());
+                }
+            },
         }
     }
 
@@ -207,14 +212,3 @@ fn is_deterministic_step_tool(explicit_tool: Option<&str>) -> bool {
         Some("bash" | "note" | "manual" | "await-user" | "weave")
     )
 }
-
-fn parse_tool_name(tool: &str) -> Result<ToolName> {
-    match tool {
-        "gemini-cli" | "gemini" => bail!("{}", csa_core::types::removed_tool_error("gemini-cli")),
-        "opencode" => Ok(ToolName::Opencode),
-        "codex" => Ok(ToolName::Codex),
-        "claude-code" => Ok(ToolName::ClaudeCode),
-        "antigravity-cli" => Ok(ToolName::AntigravityCli),
-        other => bail!("Unknown tool: {other}"),
-    }
-}
@@ -12,7 +12,7 @@ use csa_hooks::format_next_step_directive;
 use weave::compiler::{ExecutionPlan, FailAction, PlanStep};
 
 use super::plan_cmd_assignment::{
-    extract_output_assignment_markers, should_inject_assignment_markers,
+    captured_output, extract_output_assignment_markers, should_inject_assignment_markers,
     strip_assignment_marker_lines,
 };
 use super::plan_cmd_exec::{StepExecutionOutcome, execute_bash_step, run_with_heartbeat};
@@ -20,6 +20,7 @@ use super::plan_cmd_flow::{
     OrchestratorHandoff, find_next_step, format_manual_step_resume_command,
     format_orchestrator_message, format_plan_resume_command, orchestrator_handoff_mode,
 };
+use super::plan_cmd_interactive::{Approval, approve_step, skipped_by_user};
 use super::plan_cmd_tier_failover::{TierFailoverParams, execute_csa_step_with_tier_failover};
 use super::{
     PlanRunJournal, apply_repo_fingerprint, detect_repo_fingerprint, persist_plan_journal,
@@ -30,11 +31,17 @@ use crate::startup_env::StartupSubtreeEnv;
 
 #[path = "plan_cmd_step_failure.rs"]
 mod step_failure;
+#[path = "plan_cmd_step_parallel.rs"]
+mod step_parallel;
+#[path = "plan_cmd_step_repeat.rs"]
+mod step_repeat;
 #[path = "plan_cmd_step_target.rs"]
 mod step_target;
 use step_failure::{
     describe_step_command, format_step_failure_error, serialize_step_result_json, stderr_tail,
 };
+use step_parallel::{execute_steps_concurrently, pending_parallel_group};
+use step_repeat::{RepeatOutcome, skipped_by_while};
 #[cfg(test)]
 pub(crate) use step_target::resolve_step_tool;
 pub(crate) use step_target::{
@@ -51,6 +58,8 @@ pub(super) async fn execute_plan_with_journal(
     let mut results = Vec::with_capacity(plan.steps.len());
     let mut vars = variables.clone();
     let mut completed_steps = run_ctx.resume_completed_steps.clone();
+    // Results of a parallel group that already ran, consumed in step order.
+    let mut group_results: HashMap<usize, StepResult> = HashMap::new();
     let assignment_marker_allowlist: HashSet<String> = plan
         .variables
         .iter()
@@ -69,7 +78,10 @@ pub(super) async fn execute_plan_with_journal(
         persist_plan_journal(path, run_ctx.journal)?;
     }
 
-    for step in &plan.steps {
+    let mut next = 0;
+    while let Some(step) = plan.steps.get(next) {
+        let index = next;
+        next += 1;
         if completed_steps.contains(&step.id) {
             eprintln!(
                 "[{}/{}] - RESUME-SKIP (already completed)",
@@ -78,23 +90,64 @@ pub(super) async fn execute_plan_with_journal(
             continue;
         }
 
-        let result = execute_step_with_workflow(
-            step,
+        if let Some(block) = step_repeat::while_block_skipped(
+            plan,
+            index,
             &vars,
-            &StepExecutionContext {
-                project_root: run_ctx.project_root,
-                workflow_path: run_ctx.workflow_path,
-                config: run_ctx.config,
-                global_config: run_ctx.global_config,
-                model_catalog: run_ctx.model_catalog,
-                tool_override: run_ctx.tool_override,
-                model_spec_override: run_ctx.model_spec_override,
-                no_fs_sandbox: run_ctx.no_fs_sandbox,
-                resources: run_ctx.resources,
-                startup_env: run_ctx.startup_env,
-            },
-        )
-        .await;
+            &run_ctx.journal.repeat_iterations,
+            run_ctx.project_root,
+        ) {
+            for member in block {
+                completed_steps.insert(member.id);
+                results.push(skipped_by_while(member));
+            }
+            next = index + block.len();
+            run_ctx.journal.completed_steps = completed_steps.iter().copied().collect();
+            if let Some(path) = run_ctx.journal_path {
+                persist_plan_journal(path, run_ctx.journal)?;
+            }
+            continue;
+        }
+
+        // Interactive runs approve and chunked runs report one step at a
+        // time, so both keep parallel groups sequential. Results of a group
+        // are recorded in step order below.
+        if group_results.is_empty()
+            && !run_ctx.interactive
+            && !run_ctx.chunked
+            && let Some((members, limit)) = pending_parallel_group(plan, step, &completed_steps)
+        {
+            info!(
+                "Running {} parallel steps, up to {} at a time",
+                members.len(),
+                limit
+            );
+            let results =
+                execute_steps_concurrently(&members, limit, &vars, &run_ctx.step_context()).await;
+            group_results.extend(results.into_iter().map(|result| (result.step_id, result)));
+        }
+
+        let result = match group_results.remove(&step.id) {
+            Some(result) => result,
+            None => {
+                let approval = if run_ctx.interactive {
+                    approve_step(step, &vars, run_ctx)?
+                } else {
+                    Approval::Run(None)
+                };
+                match &approval {
+                    Approval::Skip => skipped_by_user(step),
+                    Approval::Run(edited) => {
+                        execute_step_with_workflow(
+                            edited.as_ref().unwrap_or(step),
+                            &vars,
+                            &run_ctx.step_context(),
+                        )
+                        .await
+                    }
+                }
+            }
+        };
         let orchestrator_handoff = if result.skipped {
             None
         } else {
@@ -102,23 +155,13 @@ pub(super) async fn execute_plan_with_journal(
         };
         let is_failure = !result.skipped && result.exit_code != 0;
 
-        // Inject successful step output for later steps.
-        let var_key = format!("STEP_{}_OUTPUT", result.step_id);
-        let raw_output = result.output.as_deref().unwrap_or("").to_string();
-        let assignment_markers = if !is_failure && should_inject_assignment_markers(step) {
-            extract_output_assignment_markers(&raw_output, &assignment_marker_allowlist)
-        } else {
-            Vec::new()
-        };
-        // Strip CSA_VAR lines from downstream output.
-        let var_value = strip_assignment_marker_lines(&raw_output);
-        vars.insert(var_key, var_value);
-        let session_var_key = format!("STEP_{}_SESSION", result.step_id);
-        let session_var_value = result.session_id.as_deref().unwrap_or("").to_string();
-        vars.insert(session_var_key, session_var_value);
-        for (key, value) in assignment_markers {
-            vars.insert(key, value);
-        }
+        inject_step_outputs(
+            step,
+            &result,
+            is_failure,
+            &mut vars,
+            &assignment_marker_allowlist,
+        );
         let is_manual_handoff = matches!(
             orchestrator_handoff,
             Some(OrchestratorHandoff::ManualResume)
@@ -128,6 +171,26 @@ pub(super) async fn execute_plan_with_journal(
             // Manual handoff only prints instructions, so explicit resume must replay it.
             completed_steps.insert(step.id);
         }
+        let step_aborts = is_failure
+            && matches!(
+                step.on_fail,
+                FailAction::Abort | FailAction::Retry(_) | FailAction::Delegate(_)
+            );
+        let mut exhausted_repeat = None;
+        if !step_aborts {
+            match step_repeat::after_step(
+                plan,
+                index,
+                &vars,
+                &mut run_ctx.journal.repeat_iterations,
+                &mut completed_steps,
+                run_ctx.project_root,
+            ) {
+                RepeatOutcome::Continue => {}
+                RepeatOutcome::Repeat { start } => next = start,
+                RepeatOutcome::Exhausted(message) => exhausted_repeat = Some(message),
+            }
+        }
         run_ctx.journal.vars = vars.clone();
         run_ctx.journal.completed_steps = completed_steps.iter().copied().collect();
         apply_repo_fingerprint(
@@ -197,23 +260,39 @@ pub(super) async fn execute_plan_with_journal(
             eprintln!("{}", format_next_step_directive(&cmd, required));
         }
 
-        let abort = is_failure
-            && matches!(
-                step.on_fail,
-                FailAction::Abort | FailAction::Retry(_) | FailAction::Delegate(_)
-            );
         results.push(result);
 
-        if abort {
-            error!(
-                "Step {} ('{}') failed (on_fail={:?}) — stopping workflow",
-                step.id, step.title, step.on_fail
-            );
+        if step_aborts || exhausted_repeat.is_some() {
+            let last_error = exhausted_repeat.unwrap_or_else(|| {
+                format!(
+                    "Step {} ('{}') failed with on_fail={:?}",
+                    step.id, step.title, step.on_fail
+                )
+            });
+            error!("{last_error} — stopping workflow");
+            // Later members of this step's parallel group have already run;
+            // journal them so --resume does not run the successful ones again.
+            for member in &plan.steps {
+                let Some(sibling) = group_results.remove(&member.id) else {
+                    continue;
+                };
+                let sibling_failed = !sibling.skipped && sibling.exit_code != 0;
+                inject_step_outputs(
+                    member,
+                    &sibling,
+                    sibling_failed,
+                    &mut vars,
+                    &assignment_marker_allowlist,
+                );
+                if !sibling_failed {
+                    completed_steps.insert(member.id);
+                }
+                results.push(sibling);
+            }
+            run_ctx.journal.vars = vars.clone();
+            run_ctx.journal.completed_steps = completed_steps.iter().copied().collect();
             run_ctx.journal.status = "failed".to_string();
-            run_ctx.journal.last_error = Some(format!(
-                "Step {} ('{}') failed with on_fail={:?}",
-                step.id, step.title, step.on_fail
-            ));
+            run_ctx.journal.last_error = Some(last_error);
             apply_repo_fingerprint(
                 run_ctx.journal,
                 &detect_repo_fingerprint(run_ctx.project_root),
@@ -228,6 +307,49 @@ pub(super) async fn execute_plan_with_journal(
     Ok(results)
 }
 
+/// Inject `result`'s output, output binding, session and `CSA_VAR`
+/// assignments into `vars` for later steps.
+fn inject_step_outputs(
+    step: &PlanStep,
+    result: &StepResult,
+    is_failure: bool,
+    vars: &mut HashMap<String, String>,
+    assignment_marker_allowlist: &HashSet<String>,
+) {
+    // Inject successful step output for later steps.
+    let var_key = format!("STEP_{}_OUTPUT", result.step_id);
+    let raw_output = result.output.as_deref().unwrap_or("").to_string();
+    let assignment_markers = if !is_failure && should_inject_assignment_markers(step) {
+        extract_output_assignment_markers(&raw_output, assignment_marker_allowlist)
+    } else {
+        Vec::new()
+    };
+    // Strip CSA_VAR lines from downstream output.
+    let var_value = strip_assignment_marker_lines(&raw_output);
+    if let Some(binding) = &step.output {
+        let captured = captured_output(binding, &var_value).unwrap_or_else(|| {
+            if !result.skipped {
+                warn!(
+                    "[{}/{}] - Output section '{}' not found; binding ${{{}}} to empty",
+                    step.id,
+                    step.title,
+                    binding.section.as_deref().unwrap_or_default(),
+                    binding.var
+                );
+            }
+            String::new()
+        });
+        vars.insert(binding.var.clone(), captured);
+    }
+    vars.insert(var_key, var_value);
+    let session_var_key = format!("STEP_{}_SESSION", result.step_id);
+    let session_var_value = result.session_id.as_deref().unwrap_or("").to_string();
+    vars.insert(session_var_key, session_var_value);
+    for (key, value) in assignment_markers {
+        vars.insert(key, value);
+    }
+}
+
 pub(crate) async fn execute_step_with_workflow(
     step: &PlanStep,
     variables: &HashMap<String, String>,
@@ -240,7 +362,8 @@ pub(crate) async fn execute_step_with_workflow(
     // Evaluate condition: skip step when condition evaluates to false.
     // Steps whose condition is true (or absent) proceed to execution.
     if let Some(ref condition) = step.condition {
-        let condition_met = crate::plan_condition::evaluate_condition(condition, variables);
+        let condition_met =
+            weave::condition::evaluate_condition_in(condition, variables, step_ctx.project_root);
         if !condition_met {
             info!(
                 "{} - SKIP (condition '{}' evaluated to false)",
@@ -55,6 +55,7 @@ pub(crate) async fn execute_plan(
         journal_path: None,
         resume_completed_steps: &completed,
         chunked: false,
+        interactive: false,
         no_fs_sandbox: false,
         resources: crate::run_resource_overrides::RunResourceOverrides::absent(),
         startup_env: &startup_env,
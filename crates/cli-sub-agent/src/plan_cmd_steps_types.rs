@@ -31,11 +31,29 @@ pub(super) struct PlanRunContext<'a> {
     pub(super) journal_path: Option<&'a Path>,
     pub(super) resume_completed_steps: &'a HashSet<usize>,
     pub(super) chunked: bool,
+    pub(super) interactive: bool,
     pub(super) no_fs_sandbox: bool,
     pub(super) resources: RunResourceOverrides,
     pub(super) startup_env: &'a StartupSubtreeEnv,
 }
 
+impl PlanRunContext<'_> {
+    fn step_context(&self) -> StepExecutionContext<'_> {
+        StepExecutionContext {
+            project_root: self.project_root,
+            workflow_path: self.workflow_path,
+            config: self.config,
+            global_config: self.global_config,
+            model_catalog: self.model_catalog,
+            tool_override: self.tool_override,
+            model_spec_override: self.model_spec_override,
+            no_fs_sandbox: self.no_fs_sandbox,
+            resources: self.resources,
+            startup_env: self.startup_env,
+        }
+    }
+}
+
 pub(crate) struct StepExecutionContext<'a> {
     pub(crate) project_root: &'a Path,
     pub(crate) workflow_path: &'a Path,
@@ -127,8 +127,12 @@ fn load_plan_resume_context_reads_running_journal_with_explicit_resume() {
         variables: vec![VariableDecl {
             name: "FEATURE".into(),
             default: Some("default".into()),
+            kind: None,
+            description: None,
+            required: false,
         }],
         steps: vec![],
+        ..Default::default()
     };
 
     let journal_path = tmp.path().join("test.journal.json");
@@ -147,6 +151,8 @@ fn load_plan_resume_context_reads_running_journal_with_explicit_resume() {
         repo_head: Some("abc123".to_string()),
         repo_dirty: Some(false),
         resource_overrides: RunResourceOverrides::absent(),
+        approvals: Vec::new(),
+        repeat_iterations: Default::default(),
     };
     write_plan_journal_without_lock(&journal_path, &journal);
 
@@ -182,6 +188,7 @@ fn load_plan_resume_context_preserves_cli_alias_pipeline_source() {
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let journal_path = tmp.path().join("dev2merge.journal.json");
@@ -197,6 +204,8 @@ fn load_plan_resume_context_preserves_cli_alias_pipeline_source() {
         repo_head: Some("abc123".to_string()),
         repo_dirty: Some(false),
         resource_overrides: RunResourceOverrides::absent(),
+        approvals: Vec::new(),
+        repeat_iterations: Default::default(),
     };
     persist_plan_journal(&journal_path, &journal).unwrap();
 
@@ -221,6 +230,7 @@ fn load_plan_resume_context_clears_stale_running_journal_without_explicit_resume
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let journal_path = tmp.path().join("test.journal.json");
@@ -236,6 +246,8 @@ fn load_plan_resume_context_clears_stale_running_journal_without_explicit_resume
         repo_head: Some("abc123".to_string()),
         repo_dirty: Some(false),
         resource_overrides: RunResourceOverrides::absent(),
+        approvals: Vec::new(),
+        repeat_iterations: Default::default(),
     };
     write_plan_journal_without_lock(&journal_path, &journal);
 
@@ -263,6 +275,7 @@ fn load_plan_resume_context_refuses_locked_running_journal_without_explicit_resu
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let journal_path = tmp.path().join("test.journal.json");
@@ -278,6 +291,8 @@ fn load_plan_resume_context_refuses_locked_running_journal_without_explicit_resu
         repo_head: Some("abc123".to_string()),
         repo_dirty: Some(false),
         resource_overrides: RunResourceOverrides::absent(),
+        approvals: Vec::new(),
+        repeat_iterations: Default::default(),
     };
     write_plan_journal_without_lock(&journal_path, &journal);
     let _held_lock = hold_plan_journal_lock(&journal_path);
@@ -322,6 +337,7 @@ fn load_plan_resume_context_requires_explicit_resume_for_manual_handoff() {
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let journal_path = tmp.path().join("test.journal.json");
@@ -337,6 +353,8 @@ fn load_plan_resume_context_requires_explicit_resume_for_manual_handoff() {
         repo_head: Some("abc123".to_string()),
         repo_dirty: Some(false),
         resource_overrides: RunResourceOverrides::absent(),
+        approvals: Vec::new(),
+        repeat_iterations: Default::default(),
     };
     persist_plan_journal(&journal_path, &journal).unwrap();
 
@@ -373,6 +391,7 @@ fn load_plan_resume_context_rejects_awaiting_user_journal_even_with_explicit_res
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let journal_path = tmp.path().join("test.journal.json");
@@ -388,6 +407,8 @@ fn load_plan_resume_context_rejects_awaiting_user_journal_even_with_explicit_res
         repo_head: Some("abc123".to_string()),
         repo_dirty: Some(false),
         resource_overrides: RunResourceOverrides::absent(),
+        approvals: Vec::new(),
+        repeat_iterations: Default::default(),
     };
     persist_plan_journal(&journal_path, &journal).unwrap();
 
@@ -437,13 +458,20 @@ fn parse_variables_uses_defaults() {
             VariableDecl {
                 name: "FOO".into(),
                 default: Some("bar".into()),
+                kind: None,
+                description: None,
+                required: false,
             },
             VariableDecl {
                 name: "BAZ".into(),
                 default: None,
+                kind: None,
+                description: None,
+                required: false,
             },
         ],
         steps: vec![],
+        ..Default::default()
     };
 
     let vars = parse_variables(&[], &plan).unwrap();
@@ -459,8 +487,12 @@ fn parse_variables_cli_overrides_default() {
         variables: vec![VariableDecl {
             name: "FOO".into(),
             default: Some("default".into()),
+            kind: None,
+            description: None,
+            required: false,
         }],
         steps: vec![],
+        ..Default::default()
     };
 
     let vars = parse_variables(&["FOO=override".into()], &plan).unwrap();
@@ -474,6 +506,7 @@ fn parse_variables_rejects_invalid_format() {
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let err = parse_variables(&["NO_EQUALS_SIGN".into()], &plan);
@@ -487,6 +520,7 @@ fn parse_variables_rejects_invalid_variable_name() {
         description: String::new(),
         variables: vec![],
         steps: vec![],
+        ..Default::default()
     };
 
     let err = parse_variables(&["BAD-NAME=value".into()], &plan);
@@ -589,6 +623,7 @@ fn should_inject_assignment_markers_only_for_bash_steps() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let codex_step = PlanStep {
         id: 2,
@@ -602,6 +637,7 @@ fn should_inject_assignment_markers_only_for_bash_steps() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let tier_only_step = PlanStep {
         id: 3,
@@ -615,6 +651,7 @@ fn should_inject_assignment_markers_only_for_bash_steps() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
 
     assert!(should_inject_assignment_markers(&bash_step));
@@ -636,6 +673,7 @@ fn step_readonly_project_root_follows_workspace_access_contract() {
         loop_var: None,
         session: None,
         workspace_access: Some(WorkspaceAccess::ReadOnly),
+        ..Default::default()
     };
 
     assert!(step_readonly_project_root(&step));
@@ -676,77 +714,3 @@ fn truncate_long_string() {
     assert_eq!(result.len(), 13); // 10 chars + "..."
     assert!(result.ends_with("..."));
 }
-
-#[test]
-fn resolve_step_tool_explicit_bash_returns_direct_bash() {
-    let step = PlanStep {
-        id: 1,
-        title: "test".into(),
-        tool: Some("bash".into()),
-        prompt: String::new(),
-        tier: None,
-        depends_on: vec![],
-        on_fail: FailAction::Abort,
-        condition: None,
-        loop_var: None,
-        session: None,
-        workspace_access: None,
-    };
-    let target = resolve_step_tool(&step, None, None, None).unwrap();
-    assert!(
-        matches!(target, StepTarget::DirectBash),
-        "tool=bash must resolve to DirectBash, not a CSA tool"
-    );
-}
-
-#[test]
-fn resolve_step_tool_explicit_codex() {
-    let step = PlanStep {
-        id: 1,
-        title: "test".into(),
-        tool: Some("codex".into()),
-        prompt: String::new(),
-        tier: None,
-        depends_on: vec![],
-        on_fail: FailAction::Abort,
-        condition: None,
-        loop_var: None,
-        session: None,
-        workspace_access: None,
-    };
-    let target = resolve_step_tool(&step, None, None, None).unwrap();
-    assert!(matches!(
-        target,
-        StepTarget::CsaTool {
-            tool_name: ToolName::Codex,
-            ..
-        }
-    ));
-}
-
-#[test]
-fn resolve_step_tool_fallback_no_config() {
-    let step = PlanStep {
-        id: 1,
-        title: "test".into(),
-        tool: None,
-        prompt: String::new(),
-        tier: None,
-        depends_on: vec![],
-        on_fail: FailAction::Abort,
-        condition: None,
-        loop_var: None,
-        session: None,
-        workspace_access: None,
-    };
-    let target = resolve_step_tool(&step, None, None, None).unwrap();
-    assert!(matches!(
-        target,
-        StepTarget::CsaTool {
-            tool_name: ToolName::Codex,
-            ..
-        }
-    ));
-}
-
-include!("plan_cmd_tests_step_target_tail.rs");
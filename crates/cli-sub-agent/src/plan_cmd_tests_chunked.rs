@@ -26,6 +26,7 @@ fn make_chunked_run_ctx<'a>(
         journal_path,
         resume_completed_steps,
         chunked,
+        interactive: false,
         no_fs_sandbox: false,
         resources: RunResourceOverrides::absent(),
         startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
@@ -52,6 +53,7 @@ async fn execute_plan_chunked_single_step() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -65,8 +67,10 @@ async fn execute_plan_chunked_single_step() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -114,6 +118,7 @@ async fn execute_plan_chunked_resume() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -127,6 +132,7 @@ async fn execute_plan_chunked_resume() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 3,
@@ -140,8 +146,10 @@ async fn execute_plan_chunked_resume() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -262,6 +270,7 @@ async fn execute_plan_chunked_skips_condition_false_and_runs_next() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -275,8 +284,10 @@ async fn execute_plan_chunked_skips_condition_false_and_runs_next() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -331,6 +342,7 @@ async fn execute_plan_chunked_resume_skips_condition_false_no_infinite_loop() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -344,6 +356,7 @@ async fn execute_plan_chunked_resume_skips_condition_false_no_infinite_loop() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 3,
@@ -357,8 +370,10 @@ async fn execute_plan_chunked_resume_skips_condition_false_no_infinite_loop() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
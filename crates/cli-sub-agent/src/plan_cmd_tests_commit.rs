@@ -142,6 +142,7 @@ async fn execute_step_csa_nested_plan_uses_fresh_child_session() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
 
     let vars = HashMap::new();
@@ -442,6 +443,7 @@ async fn commit_workflow_test_gate_aborts_before_following_steps() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -455,8 +457,10 @@ async fn commit_workflow_test_gate_aborts_before_following_steps() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let vars = HashMap::from([("PATH".to_string(), patched_path)]);
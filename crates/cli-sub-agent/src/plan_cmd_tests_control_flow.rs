@@ -0,0 +1,103 @@
+use super::*;
+use weave::compiler::{FailAction, PlanStep};
+
+#[test]
+fn captured_output_binds_full_output_or_named_section() {
+    let output =
+        "preamble\n## Findings\n- leak in foo.rs\n### Detail\nstack\n## Verdict ##\nFAIL\n";
+    let binding = |section: Option<&str>| weave::compiler::OutputBinding {
+        var: "FINDINGS".to_string(),
+        section: section.map(str::to_string),
+    };
+
+    assert_eq!(
+        captured_output(&binding(None), output).as_deref(),
+        Some(output)
+    );
+    assert_eq!(
+        captured_output(&binding(Some("findings")), output).as_deref(),
+        Some("- leak in foo.rs\n### Detail\nstack")
+    );
+    assert_eq!(
+        captured_output(&binding(Some("Verdict")), output).as_deref(),
+        Some("FAIL")
+    );
+    assert_eq!(captured_output(&binding(Some("Missing")), output), None);
+}
+
+#[test]
+fn resolve_step_tool_explicit_bash_returns_direct_bash() {
+    let step = PlanStep {
+        id: 1,
+        title: "test".into(),
+        tool: Some("bash".into()),
+        prompt: String::new(),
+        tier: None,
+        depends_on: vec![],
+        on_fail: FailAction::Abort,
+        condition: None,
+        loop_var: None,
+        session: None,
+        workspace_access: None,
+        ..Default::default()
+    };
+    let target = resolve_step_tool(&step, None, None, None).unwrap();
+    assert!(
+        matches!(target, StepTarget::DirectBash),
+        "tool=bash must resolve to DirectBash, not a CSA tool"
+    );
+}
+
+#[test]
+fn resolve_step_tool_explicit_codex() {
+    let step = PlanStep {
+        id: 1,
+        title: "test".into(),
+        tool: Some("codex".into()),
+        prompt: String::new(),
+        tier: None,
+        depends_on: vec![],
+        on_fail: FailAction::Abort,
+        condition: None,
+        loop_var: None,
+        session: None,
+        workspace_access: None,
+        ..Default::default()
+    };
+    let target = resolve_step_tool(&step, None, None, None).unwrap();
+    assert!(matches!(
+        target,
+        StepTarget::CsaTool {
+            tool_name: ToolName::Codex,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn resolve_step_tool_fallback_no_config() {
+    let step = PlanStep {
+        id: 1,
+        title: "test".into(),
+        tool: None,
+        prompt: String::new(),
+        tier: None,
+        depends_on: vec![],
+        on_fail: FailAction::Abort,
+        condition: None,
+        loop_var: None,
+        session: None,
+        workspace_access: None,
+        ..Default::default()
+    };
+    let target = resolve_step_tool(&step, None, None, None).unwrap();
+    assert!(matches!(
+        target,
+        StepTarget::CsaTool {
+            tool_name: ToolName::Codex,
+            ..
+        }
+    ));
+}
+
+include!("plan_cmd_tests_step_target_tail.rs");
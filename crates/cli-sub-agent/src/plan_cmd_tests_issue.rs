@@ -16,6 +16,7 @@ async fn execute_step_bash_receives_issue_number_variable() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::from([(ISSUE_NUMBER_VAR.to_string(), "1663".to_string())]);
     let tmp = tempfile::tempdir().unwrap();
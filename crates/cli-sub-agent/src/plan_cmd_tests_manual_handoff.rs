@@ -22,6 +22,7 @@ fn manual_handoff_plan() -> ExecutionPlan {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -35,8 +36,10 @@ fn manual_handoff_plan() -> ExecutionPlan {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     }
 }
 
@@ -123,6 +126,7 @@ fn plan_run_args(project_root: &Path, workflow_path: &Path) -> PlanRunArgs {
         model_spec_override: None,
         dry_run: false,
         chunked: false,
+        interactive: false,
         resume: None,
         complete_manual_step: None,
         cd: Some(project_root.display().to_string()),
@@ -156,6 +160,7 @@ async fn execute_plan_stops_after_manual_handoff() {
         journal_path: Some(&journal_path),
         resume_completed_steps: &completed,
         chunked: false,
+        interactive: false,
         no_fs_sandbox: false,
         resources: RunResourceOverrides::absent(),
         startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
@@ -370,6 +375,7 @@ async fn execute_plan_resume_replays_manual_handoff_step() {
             journal_path: Some(&journal_path),
             resume_completed_steps: &completed,
             chunked: false,
+            interactive: false,
             no_fs_sandbox: false,
             resources: RunResourceOverrides::absent(),
             startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
@@ -408,6 +414,7 @@ async fn execute_plan_resume_replays_manual_handoff_step() {
         journal_path: Some(&journal_path),
         resume_completed_steps: &resumed_completed,
         chunked: false,
+        interactive: false,
         no_fs_sandbox: false,
         resources: RunResourceOverrides::absent(),
         startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
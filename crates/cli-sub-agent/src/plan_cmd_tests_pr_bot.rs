@@ -26,6 +26,7 @@ async fn execute_step_with_workflow_exposes_runtime_paths_to_bash() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
 
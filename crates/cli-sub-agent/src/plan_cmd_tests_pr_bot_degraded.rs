@@ -20,6 +20,7 @@ async fn execute_pr_bot_local_review_accepts_same_sha_native_bypass_evidence() {
         description: String::new(),
         variables,
         steps,
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -59,6 +60,7 @@ async fn execute_pr_bot_local_review_rejects_stale_native_bypass_evidence() {
         description: String::new(),
         variables,
         steps,
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -91,6 +93,7 @@ async fn execute_pr_bot_local_review_rejects_review_check_skip_audit_log() {
         description: String::new(),
         variables,
         steps,
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -123,6 +126,7 @@ async fn execute_pr_bot_bot_unavailable_gate_accepts_native_bypass_evidence() {
         description: String::new(),
         variables,
         steps,
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -178,6 +182,7 @@ async fn execute_pr_bot_post_fix_fallback_accepts_native_bypass_evidence() {
         description: String::new(),
         variables,
         steps,
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -235,8 +240,10 @@ async fn execute_pr_bot_degraded_local_fallback_records_rationale_and_reaches_me
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -306,6 +313,7 @@ async fn execute_pr_bot_degraded_local_fallback_still_blocks_high_findings() {
         description: String::new(),
         variables,
         steps,
+        ..Default::default()
     };
 
     let results = execute_plan(&plan, &vars, tmp.path(), None, None)
@@ -18,6 +18,7 @@ async fn execute_step_failure_reports_stderr_tail() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -69,6 +70,7 @@ async fn execute_step_failure_reports_stdout_tail() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
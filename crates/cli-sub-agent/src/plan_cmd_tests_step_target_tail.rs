@@ -12,6 +12,7 @@ fn resolve_step_tool_weave_returns_include_marker() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let target = resolve_step_tool(&step, None, None, None).unwrap();
     assert!(matches!(target, StepTarget::WeaveInclude));
@@ -31,6 +32,7 @@ fn resolve_step_tool_unknown_tool_errors() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     assert!(resolve_step_tool(&step, None, None, None).is_err());
 }
@@ -22,6 +22,7 @@ async fn execute_step_skips_when_condition_is_false() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -44,6 +45,7 @@ async fn execute_step_runs_when_condition_is_true() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let mut vars = HashMap::new();
     vars.insert("FLAG".into(), "yes".into());
@@ -71,6 +73,7 @@ async fn execute_step_skips_loop_with_nonzero_exit() {
         }),
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -93,6 +96,7 @@ async fn execute_step_skips_weave_include() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -117,6 +121,7 @@ async fn execute_step_bash_runs_code_block() {
         loop_var: None,
         session: None,
         workspace_access: None,
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -179,6 +184,7 @@ async fn execute_plan_stops_for_await_user() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -192,8 +198,10 @@ async fn execute_plan_stops_for_await_user() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -214,6 +222,7 @@ async fn execute_plan_stops_for_await_user() {
         journal_path: Some(&journal_path),
         resume_completed_steps: &completed,
         chunked: false,
+        interactive: false,
         no_fs_sandbox: false,
         resources: RunResourceOverrides::absent(),
         startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
@@ -248,6 +257,7 @@ async fn execute_plan_continues_after_skipped_await_user_step() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -261,8 +271,10 @@ async fn execute_plan_continues_after_skipped_await_user_step() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -283,6 +295,7 @@ async fn execute_plan_continues_after_skipped_await_user_step() {
         journal_path: Some(&journal_path),
         resume_completed_steps: &completed,
         chunked: false,
+        interactive: false,
         no_fs_sandbox: false,
         resources: RunResourceOverrides::absent(),
         startup_env: &crate::startup_env::EMPTY_STARTUP_SUBTREE_ENV,
@@ -321,6 +334,7 @@ async fn execute_plan_skips_false_condition_cleanly() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -334,8 +348,10 @@ async fn execute_plan_skips_false_condition_cleanly() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -374,7 +390,9 @@ async fn execute_plan_runs_true_condition_steps() {
             loop_var: None,
             session: None,
             workspace_access: None,
+            ..Default::default()
         }],
+        ..Default::default()
     };
     let mut vars = HashMap::new();
     vars.insert("FLAG".into(), "yes".into());
@@ -395,6 +413,9 @@ async fn execute_plan_allows_prefixed_marker_to_drive_next_condition() {
         variables: vec![VariableDecl {
             name: "FLAG".into(),
             default: None,
+            kind: None,
+            description: None,
+            required: false,
         }],
         steps: vec![
             PlanStep {
@@ -409,6 +430,7 @@ async fn execute_plan_allows_prefixed_marker_to_drive_next_condition() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -422,8 +444,10 @@ async fn execute_plan_allows_prefixed_marker_to_drive_next_condition() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
@@ -451,6 +475,9 @@ async fn execute_plan_does_not_inject_markers_from_failed_steps() {
         variables: vec![VariableDecl {
             name: "FLAG".into(),
             default: None,
+            kind: None,
+            description: None,
+            required: false,
         }],
         steps: vec![
             PlanStep {
@@ -465,6 +492,7 @@ async fn execute_plan_does_not_inject_markers_from_failed_steps() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
             PlanStep {
                 id: 2,
@@ -478,8 +506,10 @@ async fn execute_plan_does_not_inject_markers_from_failed_steps() {
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let vars = HashMap::new();
     let tmp = tempfile::tempdir().unwrap();
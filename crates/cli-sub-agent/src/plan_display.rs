@@ -29,23 +29,9 @@ pub(crate) fn print_plan(
 
     println!("Steps ({}):", plan.steps.len());
     for step in &plan.steps {
-        let tool_info = match resolve_step_tool_with_variables(step, variables, config, None, None)
-        {
-            Ok(StepTarget::DirectBash) => "bash (direct)".into(),
-            Ok(StepTarget::WeaveInclude) => "weave (include)".into(),
-            Ok(StepTarget::Note) => "note (non-executable)".into(),
-            Ok(StepTarget::Manual) => "manual (orchestrator-required)".into(),
-            Ok(StepTarget::AwaitUser) => "await-user (user-action required)".into(),
-            Ok(StepTarget::CsaTool {
-                tool_name,
-                model_spec,
-                ..
-            }) => match model_spec {
-                Some(s) => format!("{} ({})", tool_name.as_str(), s),
-                None => tool_name.as_str().to_string(),
-            },
-            Err(e) => format!("<error: {e}>"),
-        };
+        let tool_info = describe_step_target(&resolve_step_tool_with_variables(
+            step, variables, config, None, None,
+        ));
 
         let on_fail = match &step.on_fail {
             FailAction::Abort => "abort",
@@ -59,6 +45,14 @@ pub(crate) fn print_plan(
             step.loop_var
                 .as_ref()
                 .map(|l| format!("FOR {}", l.variable)),
+            step.repeat.as_ref().map(|r| {
+                format!(
+                    "{} {} (max {})",
+                    r.mode.as_str().to_uppercase(),
+                    r.condition,
+                    r.max_iterations
+                )
+            }),
         ];
         let flag_str: Vec<String> = flags.into_iter().flatten().collect();
         let flag_display = if flag_str.is_empty() {
@@ -75,6 +69,34 @@ pub(crate) fn print_plan(
             "     -> sets ${{STEP_{}_OUTPUT}} for subsequent steps",
             step.id
         );
+        if let Some(binding) = &step.output {
+            match &binding.section {
+                Some(section) => {
+                    println!("     -> sets ${{{}}} from section '{section}'", binding.var)
+                }
+                None => println!("     -> sets ${{{}}}", binding.var),
+            }
+        }
+    }
+}
+
+/// Human-readable execution target, as shown by `--dry-run` and `--interactive`.
+pub(crate) fn describe_step_target(target: &anyhow::Result<StepTarget>) -> String {
+    match target {
+        Ok(StepTarget::DirectBash) => "bash (direct)".into(),
+        Ok(StepTarget::WeaveInclude) => "weave (include)".into(),
+        Ok(StepTarget::Note) => "note (non-executable)".into(),
+        Ok(StepTarget::Manual) => "manual (orchestrator-required)".into(),
+        Ok(StepTarget::AwaitUser) => "await-user (user-action required)".into(),
+        Ok(StepTarget::CsaTool {
+            tool_name,
+            model_spec,
+            ..
+        }) => match model_spec {
+            Some(s) => format!("{} ({})", tool_name.as_str(), s),
+            None => tool_name.as_str().to_string(),
+        },
+        Err(e) => format!("<error: {e}>"),
     }
 }
 
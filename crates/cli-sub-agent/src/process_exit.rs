@@ -1,6 +1,16 @@
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::Result;
+use csa_core::error::AppError;
+use csa_core::exit_code::ExitCode;
+
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Also report failures as a JSON document on stdout; set from `--format json`.
+pub(crate) fn enable_json_errors() {
+    JSON_ERRORS.store(true, Ordering::Relaxed);
+}
 
 pub(crate) fn report_daemon_error_or_exit_code(
     result: Result<i32>,
@@ -9,18 +19,68 @@ pub(crate) fn report_daemon_error_or_exit_code(
     match result {
         Ok(code) => code,
         Err(err) => {
-            let rendered = crate::error_report::render_user_facing_error(&err);
-            eprintln!("{rendered}");
-            if let Some(hint) = crate::error_hints::suggest_fix(&err) {
-                eprintln!();
-                eprintln!("{hint}");
-            }
+            let rendered = report_error(&err);
             daemon_guard.finalize();
-            exit_current_process_with_reason(1, Some(&rendered));
+            exit_current_process_with_reason(exit_code_for_error(&err).code(), Some(&rendered));
         }
     }
 }
 
+/// Print a failed command's error and fix hint to stderr, plus
+/// [`error_json`] on stdout in JSON mode. Returns the rendered text.
+pub(crate) fn report_error(err: &anyhow::Error) -> String {
+    let rendered = crate::error_report::render_user_facing_error(err);
+    eprintln!("{rendered}");
+    if let Some(hint) = crate::error_hints::suggest_fix(err) {
+        eprintln!();
+        eprintln!("{hint}");
+    }
+    if JSON_ERRORS.load(Ordering::Relaxed) {
+        println!("{}", error_json(err));
+    }
+    rendered
+}
+
+/// The typed [`AppError`] behind a failure, whether it is the root error, a
+/// source further down the chain, or attached as context.
+pub(crate) fn app_error(err: &anyhow::Error) -> Option<&AppError> {
+    err.downcast_ref::<AppError>().or_else(|| {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<AppError>())
+    })
+}
+
+/// `{"error": {...}}` document for a failed command. Typed errors carry
+/// their `kind` and structured `context`; anything else is `unclassified`.
+pub(crate) fn error_json(err: &anyhow::Error) -> serde_json::Value {
+    let error = match app_error(err) {
+        Some(app_err) => app_err.to_json(),
+        None => serde_json::json!({
+            "kind": "unclassified",
+            "message": format!("{err:#}"),
+            "exit_code": exit_code_for_error(err).code(),
+        }),
+    };
+    serde_json::json!({ "error": error })
+}
+
+/// Classify a failed command into the CLI exit code taxonomy by the typed
+/// [`AppError`] anywhere in its chain. Untyped errors are internal errors.
+pub(crate) fn exit_code_for_error(err: &anyhow::Error) -> ExitCode {
+    app_error(err).map_or(ExitCode::InternalError, AppError::exit_code)
+}
+
+/// `csa run` exit code for a finished tool run. Timeouts (124) and signal
+/// exits (128+N) pass through; any other non-zero tool exit is a tool
+/// failure. The tool's own code stays in the session result.
+pub(crate) fn run_exit_code(tool_exit_code: i32) -> i32 {
+    match tool_exit_code {
+        0 => ExitCode::Success.code(),
+        124 | 129..=255 => tool_exit_code,
+        _ => ExitCode::ToolFailure.code(),
+    }
+}
+
 pub(crate) fn exit_current_process(exit_code: i32) -> ! {
     exit_current_process_with_reason(exit_code, None)
 }
@@ -31,3 +91,7 @@ fn exit_current_process_with_reason(exit_code: i32, reason: Option<&str>) -> ! {
     crate::session_cmds_daemon::persist_daemon_completion_from_env_with_reason(exit_code, reason);
     std::process::exit(exit_code);
 }
+
+#[cfg(test)]
+#[path = "process_exit_tests.rs"]
+mod tests;
@@ -0,0 +1,110 @@
+use super::*;
+
+#[test]
+fn typed_app_errors_use_their_own_category() {
+    let err = anyhow::Error::new(AppError::SlotExhausted {
+        tool: "codex".into(),
+        max: 2,
+        alternatives: Vec::new(),
+    })
+    .context("Failed to resume session");
+    assert_eq!(exit_code_for_error(&err), ExitCode::SlotExhausted);
+
+    let err = anyhow::Error::new(AppError::ToolNotInstalled("codex".into()));
+    assert_eq!(exit_code_for_error(&err), ExitCode::ConfigError);
+}
+
+#[test]
+fn typed_errors_attached_as_context_are_found() {
+    let err = anyhow::anyhow!("peak_memory_mb=unknown")
+        .context(AppError::ProtocolError {
+            tool: "claude-code".into(),
+            message: "sandboxed ACP: ACP initialization failed: timeout".into(),
+        })
+        .context("meta_session_id=01KTESTSESSIONABCDE123456");
+    assert_eq!(exit_code_for_error(&err), ExitCode::ToolFailure);
+
+    let json = error_json(&err);
+    assert_eq!(json["error"]["kind"], "protocol_error");
+    assert_eq!(json["error"]["context"]["tool"], "claude-code");
+    assert_eq!(json["error"]["exit_code"], ExitCode::ToolFailure.code());
+}
+
+#[test]
+fn untyped_errors_are_reported_as_unclassified() {
+    let err = anyhow::anyhow!("Unknown tool: bogus").context("Failed to route run");
+    let json = error_json(&err);
+    assert_eq!(json["error"]["kind"], "unclassified");
+    assert_eq!(
+        json["error"]["message"],
+        "Failed to route run: Unknown tool: bogus"
+    );
+    assert_eq!(json["error"]["exit_code"], ExitCode::InternalError.code());
+}
+
+#[test]
+fn lock_slot_and_config_errors_are_classified() {
+    let cases = [
+        (
+            anyhow::Error::new(AppError::LockHeld {
+                lock_path: "/tmp/x.lock".into(),
+            }),
+            ExitCode::LockContention,
+        ),
+        (
+            anyhow::Error::new(AppError::LockHeld {
+                lock_path: "/tmp/x.lock".into(),
+            })
+            .context("concurrent write session blocked by 01JWRITER"),
+            ExitCode::LockContention,
+        ),
+        (
+            anyhow::Error::new(AppError::SlotExhausted {
+                tool: "codex".into(),
+                max: 2,
+                alternatives: Vec::new(),
+            })
+            .context("Timed out waiting for slot 'codex' after 300s"),
+            ExitCode::SlotExhausted,
+        ),
+        (
+            AppError::config("Unknown tool: bogus-tool-xyz").into(),
+            ExitCode::ConfigError,
+        ),
+        (
+            AppError::config("Direct --tool is blocked when tiers are configured.").into(),
+            ExitCode::ConfigError,
+        ),
+    ];
+    for (err, expected) in cases {
+        assert_eq!(exit_code_for_error(&err), expected, "{err:#}");
+    }
+}
+
+#[test]
+fn untyped_errors_are_not_guessed_from_loose_words() {
+    for message in [
+        "Session locked by PID 4242 (lock_path: /tmp/x.lock, pid_status: alive)",
+        "Unknown tool: bogus-tool-xyz",
+        "codex: 429 Too Many Requests",
+        "All 2 slots for 'codex' are occupied",
+        "Failed to parse config .csa/config.toml",
+        "retrying after HTTP 429 from a mirror while parsing the config cache",
+    ] {
+        let err = anyhow::anyhow!(message.to_string());
+        assert_eq!(
+            exit_code_for_error(&err),
+            ExitCode::InternalError,
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn run_exit_code_keeps_timeouts_and_signals() {
+    assert_eq!(run_exit_code(0), 0);
+    assert_eq!(run_exit_code(1), ExitCode::ToolFailure.code());
+    assert_eq!(run_exit_code(3), ExitCode::ToolFailure.code());
+    assert_eq!(run_exit_code(124), 124);
+    assert_eq!(run_exit_code(143), 143);
+}
@@ -0,0 +1,384 @@
+//! Saved prompt library (`csa prompt save|list`, `csa run --prompt-name`).
+//!
+//! Prompts live in the state dir, per project under the project's session root
+//! and globally under `<state dir>/prompts/`. Each save writes a new immutable
+//! `<name>/v<N>.md`; runs use the latest version unless `NAME@N` pins one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use csa_core::types::OutputFormat;
+use serde::Serialize;
+
+use crate::cli::PromptCommands;
+use crate::stdout_write::{write_stdout, write_stdout_line};
+use crate::template_vars::{parse_var_assignments, substitute_vars, unresolved_placeholders};
+
+const PROMPTS_DIR_NAME: &str = "prompts";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum PromptScope {
+    Project,
+    Global,
+}
+
+#[derive(Debug, Serialize)]
+struct SavedPrompt {
+    name: String,
+    version: u32,
+    path: PathBuf,
+    /// True when the content matched the latest version and nothing was written.
+    unchanged: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct PromptEntry {
+    name: String,
+    scope: PromptScope,
+    latest_version: u32,
+    versions: usize,
+    updated_at: Option<DateTime<Utc>>,
+    /// A project prompt with the same name takes precedence at run time.
+    shadowed: bool,
+}
+
+/// One prompt directory in a library.
+#[derive(Debug)]
+struct StoredPrompt {
+    name: String,
+    versions: Vec<u32>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+pub(crate) fn handle_prompt(cmd: PromptCommands, format: OutputFormat) -> Result<()> {
+    match cmd {
+        PromptCommands::Save {
+            name,
+            file,
+            global,
+            cd,
+        } => {
+            let content = match &file {
+                Some(path) => std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read prompt file: {}", path.display()))?,
+                None => {
+                    let mut buf = String::new();
+                    std::io::stdin()
+                        .read_to_string(&mut buf)
+                        .context("Failed to read prompt from stdin")?;
+                    buf
+                }
+            };
+            let scope = if global {
+                PromptScope::Global
+            } else {
+                PromptScope::Project
+            };
+            let root = prompts_root(scope, cd.as_deref())?;
+            let saved = save_prompt_in(&root, &name, &content)?;
+            if matches!(format, OutputFormat::Json) {
+                write_stdout_line(&serde_json::to_string_pretty(&saved)?)?;
+            } else if saved.unchanged {
+                eprintln!(
+                    "Prompt '{name}' unchanged (v{}): {}",
+                    saved.version,
+                    saved.path.display()
+                );
+            } else {
+                eprintln!(
+                    "Saved prompt '{name}' v{}: {}",
+                    saved.version,
+                    saved.path.display()
+                );
+            }
+            Ok(())
+        }
+        PromptCommands::List { cd } => {
+            let project = list_prompts_in(&prompts_root(PromptScope::Project, cd.as_deref())?)?;
+            let global = list_prompts_in(&prompts_root(PromptScope::Global, None)?)?;
+            let entries = merge_listings(project, global);
+            if matches!(format, OutputFormat::Json) {
+                write_stdout_line(&serde_json::to_string_pretty(&entries)?)?;
+            } else {
+                write_stdout(&render_listing(&entries))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Resolve `csa run --prompt-name` into prompt text with `--var` values applied.
+pub(crate) fn resolve_run_prompt(
+    prompt_name: Option<&str>,
+    vars: &[String],
+    cd: Option<&str>,
+) -> Result<Option<String>> {
+    let Some(spec) = prompt_name else {
+        return Ok(None);
+    };
+    let (name, version) = parse_prompt_spec(spec)?;
+    let vars = parse_var_assignments(vars)?;
+    let roots = [
+        prompts_root(PromptScope::Project, cd)?,
+        prompts_root(PromptScope::Global, None)?,
+    ];
+    for root in &roots {
+        if let Some((_, content)) = load_prompt_in(root, name, version)? {
+            return Ok(Some(render_prompt(name, &content, &vars)));
+        }
+    }
+    match version {
+        Some(version) => bail!("Saved prompt '{name}' has no version {version}"),
+        None => bail!("No saved prompt named '{name}' (see `csa prompt list`)"),
+    }
+}
+
+fn render_prompt(name: &str, content: &str, vars: &HashMap<String, String>) -> String {
+    let rendered = substitute_vars(content, vars);
+    let missing = unresolved_placeholders(&rendered);
+    if !missing.is_empty() {
+        eprintln!(
+            "warning: prompt '{name}' has unset variables: {} (pass --var KEY=VALUE)",
+            missing.join(", ")
+        );
+    }
+    rendered
+}
+
+fn prompts_root(scope: PromptScope, cd: Option<&str>) -> Result<PathBuf> {
+    match scope {
+        PromptScope::Project => {
+            let project_root = crate::pipeline::determine_project_root(cd)?;
+            Ok(csa_session::get_session_root(&project_root)?.join(PROMPTS_DIR_NAME))
+        }
+        PromptScope::Global => Ok(csa_config::paths::state_dir_write()
+            .unwrap_or_else(csa_config::paths::state_dir_fallback)
+            .join(PROMPTS_DIR_NAME)),
+    }
+}
+
+/// Split `NAME[@VERSION]`.
+fn parse_prompt_spec(spec: &str) -> Result<(&str, Option<u32>)> {
+    let (name, version) = match spec.rsplit_once('@') {
+        Some((name, version)) => {
+            let version = version
+                .trim_start_matches('v')
+                .parse::<u32>()
+                .ok()
+                .filter(|version| *version > 0)
+                .with_context(|| format!("Invalid prompt version in '{spec}'"))?;
+            (name, Some(version))
+        }
+        None => (spec, None),
+    };
+    validate_prompt_name(name)?;
+    Ok((name, version))
+}
+
+fn validate_prompt_name(name: &str) -> Result<()> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if !valid {
+        bail!("Invalid prompt name '{name}': must match [A-Za-z0-9][A-Za-z0-9._-]*");
+    }
+    Ok(())
+}
+
+fn save_prompt_in(root: &Path, name: &str, content: &str) -> Result<SavedPrompt> {
+    validate_prompt_name(name)?;
+    if content.trim().is_empty() {
+        bail!("Refusing to save empty prompt '{name}'");
+    }
+    let dir = root.join(name);
+    if let Some((version, latest)) = load_prompt_in(root, name, None)?
+        && latest == content
+    {
+        return Ok(SavedPrompt {
+            name: name.to_string(),
+            version,
+            path: version_path(&dir, version),
+            unchanged: true,
+        });
+    }
+
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create prompt directory: {}", dir.display()))?;
+    let mut version = prompt_versions(&dir)?.last().copied().unwrap_or(0) + 1;
+    loop {
+        let path = version_path(&dir, version);
+        // `create_new` keeps concurrent saves from overwriting each other's version.
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())
+                    .with_context(|| format!("Failed to write prompt: {}", path.display()))?;
+                return Ok(SavedPrompt {
+                    name: name.to_string(),
+                    version,
+                    path,
+                    unchanged: false,
+                });
+            }
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => version += 1,
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to create prompt: {}", path.display()));
+            }
+        }
+    }
+}
+
+/// Load `version` (or the latest) of `name`; `None` when it does not exist.
+fn load_prompt_in(root: &Path, name: &str, version: Option<u32>) -> Result<Option<(u32, String)>> {
+    let dir = root.join(name);
+    let versions = prompt_versions(&dir)?;
+    let selected = match version {
+        Some(version) => versions.contains(&version).then_some(version),
+        None => versions.last().copied(),
+    };
+    let Some(selected) = selected else {
+        return Ok(None);
+    };
+    let path = version_path(&dir, selected);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read prompt: {}", path.display()))?;
+    Ok(Some((selected, content)))
+}
+
+fn list_prompts_in(root: &Path) -> Result<Vec<StoredPrompt>> {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Failed to read prompt library: {}", root.display()));
+        }
+    };
+    let mut prompts = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if validate_prompt_name(&name).is_err() || !entry.path().is_dir() {
+            continue;
+        }
+        let versions = prompt_versions(&entry.path())?;
+        let Some(&latest) = versions.last() else {
+            continue;
+        };
+        let updated_at = std::fs::metadata(version_path(&entry.path(), latest))
+            .and_then(|meta| meta.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        prompts.push(StoredPrompt {
+            name,
+            versions,
+            updated_at,
+        });
+    }
+    Ok(prompts)
+}
+
+fn merge_listings(project: Vec<StoredPrompt>, global: Vec<StoredPrompt>) -> Vec<PromptEntry> {
+    let project_names: Vec<String> = project.iter().map(|prompt| prompt.name.clone()).collect();
+    let mut merged = BTreeMap::new();
+    for (scope, listing) in [
+        (PromptScope::Project, project),
+        (PromptScope::Global, global),
+    ] {
+        for prompt in listing {
+            let shadowed = scope == PromptScope::Global && project_names.contains(&prompt.name);
+            merged.insert(
+                (prompt.name.clone(), scope),
+                PromptEntry {
+                    latest_version: prompt.versions.last().copied().unwrap_or(0),
+                    versions: prompt.versions.len(),
+                    name: prompt.name,
+                    scope,
+                    updated_at: prompt.updated_at,
+                    shadowed,
+                },
+            );
+        }
+    }
+    merged.into_values().collect()
+}
+
+fn render_listing(entries: &[PromptEntry]) -> String {
+    if entries.is_empty() {
+        return "No saved prompts. Save one with `csa prompt save <name>`.\n".to_string();
+    }
+    let mut out = format!(
+        "{:<28} {:<8} {:>7}  {:<20}\n",
+        "NAME", "SCOPE", "LATEST", "UPDATED"
+    );
+    for entry in entries {
+        let scope = match entry.scope {
+            PromptScope::Project => "project",
+            PromptScope::Global => "global",
+        };
+        let updated = entry
+            .updated_at
+            .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<28} {:<8} {:>7}  {:<20}{}\n",
+            entry.name,
+            scope,
+            format!("v{}", entry.latest_version),
+            updated,
+            if entry.shadowed {
+                " (shadowed by project)"
+            } else {
+                ""
+            }
+        ));
+    }
+    out
+}
+
+/// Sorted version numbers stored in a prompt directory.
+fn prompt_versions(dir: &Path) -> Result<Vec<u32>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read prompt: {}", dir.display()));
+        }
+    };
+    let mut versions: Vec<u32> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix('v')?
+                .strip_suffix(".md")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    versions.sort_unstable();
+    Ok(versions)
+}
+
+fn version_path(dir: &Path, version: u32) -> PathBuf {
+    dir.join(format!("v{version}.md"))
+}
+
+#[cfg(test)]
+#[path = "prompt_cmd_tests.rs"]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+
+#[test]
+fn save_prompt_creates_versions_and_skips_identical_content() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let first = save_prompt_in(tmp.path(), "review", "Review ${TARGET}\n").unwrap();
+    assert_eq!(first.version, 1);
+    assert!(!first.unchanged);
+
+    let same = save_prompt_in(tmp.path(), "review", "Review ${TARGET}\n").unwrap();
+    assert_eq!(same.version, 1);
+    assert!(same.unchanged);
+
+    let second = save_prompt_in(tmp.path(), "review", "Review ${TARGET} twice\n").unwrap();
+    assert_eq!(second.version, 2);
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("review/v1.md")).unwrap(),
+        "Review ${TARGET}\n"
+    );
+
+    let (version, latest) = load_prompt_in(tmp.path(), "review", None).unwrap().unwrap();
+    assert_eq!(version, 2);
+    assert_eq!(latest, "Review ${TARGET} twice\n");
+    let (_, pinned) = load_prompt_in(tmp.path(), "review", Some(1))
+        .unwrap()
+        .unwrap();
+    assert_eq!(pinned, "Review ${TARGET}\n");
+    assert!(
+        load_prompt_in(tmp.path(), "review", Some(3))
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        load_prompt_in(tmp.path(), "missing", None)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn save_prompt_rejects_bad_names_and_empty_content() {
+    let tmp = tempfile::tempdir().unwrap();
+    for name in ["", "../escape", "a/b", ".hidden"] {
+        assert!(save_prompt_in(tmp.path(), name, "text").is_err(), "{name}");
+    }
+    assert!(save_prompt_in(tmp.path(), "blank", "  \n").is_err());
+}
+
+#[test]
+fn parse_prompt_spec_accepts_optional_version() {
+    assert_eq!(parse_prompt_spec("review").unwrap(), ("review", None));
+    assert_eq!(parse_prompt_spec("review@3").unwrap(), ("review", Some(3)));
+    assert_eq!(parse_prompt_spec("review@v2").unwrap(), ("review", Some(2)));
+    assert!(parse_prompt_spec("review@0").is_err());
+    assert!(parse_prompt_spec("review@latest").is_err());
+}
+
+#[test]
+fn listing_marks_global_prompts_shadowed_by_project() {
+    let project = tempfile::tempdir().unwrap();
+    let global = tempfile::tempdir().unwrap();
+    save_prompt_in(project.path(), "review", "project review").unwrap();
+    save_prompt_in(global.path(), "review", "global review").unwrap();
+    save_prompt_in(global.path(), "triage", "v1").unwrap();
+    save_prompt_in(global.path(), "triage", "v2").unwrap();
+
+    let entries = merge_listings(
+        list_prompts_in(project.path()).unwrap(),
+        list_prompts_in(global.path()).unwrap(),
+    );
+    let summary: Vec<(&str, PromptScope, u32, bool)> = entries
+        .iter()
+        .map(|e| (e.name.as_str(), e.scope, e.latest_version, e.shadowed))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("review", PromptScope::Project, 1, false),
+            ("review", PromptScope::Global, 1, true),
+            ("triage", PromptScope::Global, 2, false),
+        ]
+    );
+
+    let text = render_listing(&entries);
+    assert!(text.contains("(shadowed by project)"));
+    assert!(render_listing(&[]).starts_with("No saved prompts"));
+}
+
+#[test]
+fn render_prompt_substitutes_vars() {
+    let vars = parse_var_assignments(&["TARGET=src/lib.rs".into()]).unwrap();
+    assert_eq!(
+        render_prompt("review", "Review ${TARGET} for ${FOCUS}", &vars),
+        "Review src/lib.rs for ${FOCUS}"
+    );
+    assert_eq!(
+        unresolved_placeholders("a ${FOCUS} ${FOCUS} ${1x} ${B}"),
+        vec!["FOCUS".to_string(), "B".to_string()]
+    );
+}
@@ -349,6 +349,7 @@ mod tests {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "range:main...HEAD".to_string(),
+            path_filters: Vec::new(),
             exit_code: 0,
             fix_attempted: false,
             fix_rounds: 0,
@@ -3,9 +3,9 @@ use std::{io, path::Path};
 use anyhow::Context;
 use chrono::{DateTime, TimeDelta, Utc};
 use csa_config::ProjectConfig;
-use csa_resource::{ResourceGuard, ResourceLimits, SpawnMemoryAdmission};
+use csa_resource::{ResourceGuard, ResourceLimits, SpawnMemoryAdmission, UsageKey};
 use csa_session::{MetaSessionState, SandboxInfo, SessionPhase, SessionTreeMemorySampler};
-use tracing::warn;
+use tracing::{info, warn};
 
 use crate::run_resource_overrides::RunResourceOverrides;
 
@@ -172,6 +172,7 @@ pub(crate) fn build_spawn_memory_admission(
     project_root: &Path,
     current_session_id: &str,
     projected_spawn_mb: u64,
+    usage_key: &UsageKey,
 ) -> SpawnMemoryAdmission {
     let active = match csa_session::list_all_sessions_all_projects() {
         Ok(sessions) => aggregate_active_session_memory(
@@ -190,12 +191,26 @@ pub(crate) fn build_spawn_memory_admission(
         }
     };
 
+    let historical = crate::memory_usage_stats::historical_estimate(usage_key);
+    if let Some(estimate) = &historical {
+        info!(
+            key = %usage_key,
+            estimate_key = %estimate.key,
+            historical_p95_mb = estimate.p95_mb,
+            samples = estimate.sample_count,
+            effective_weight = estimate.effective_weight,
+            projected_spawn_mb,
+            "Historical memory estimate for spawn admission"
+        );
+    }
+
     SpawnMemoryAdmission {
         projected_spawn_mb,
         active_session_rss_mb: active.sampled_rss_mb,
         active_session_projected_mb: active.projected_mb,
         active_session_count: active.active_count,
         sampled_session_count: active.sampled_count,
+        historical_p95_mb: historical.map(|estimate| estimate.p95_mb),
     }
 }
 
@@ -1,4 +1,4 @@
-use csa_resource::{IsolationPlan, ResourceCapability, memory_policy};
+use csa_resource::{IsolationPlan, memory_policy};
 
 const REVIEWER_SUB_SESSION_TASK_TYPE: &str = "reviewer_sub_session";
 const WRITER_TASK_TYPE: &str = "run";
@@ -200,7 +200,7 @@ fn memory_soft_limit_admission_denial(
     let session_kind = CodexSoftLimitAdmissionKind::from_task_type(task_type)?;
 
     let plan = isolation_plan?;
-    if plan.resource != ResourceCapability::CgroupV2 {
+    if !plan.resource.is_cgroup() {
         return None;
     }
 
@@ -230,7 +230,7 @@ fn memory_soft_limit_admission_denial(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use csa_resource::FilesystemCapability;
+    use csa_resource::{FilesystemCapability, ResourceCapability};
 
     fn isolation_plan(
         tool_resource: ResourceCapability,
@@ -247,6 +247,10 @@ mod tests {
             memory_max_mb,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: true,
             project_root: None,
             soft_limit_percent,
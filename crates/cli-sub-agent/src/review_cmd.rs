@@ -25,16 +25,22 @@ use tracing::{debug, error, warn};
 pub(crate) mod output;
 pub(crate) use output::clean_detection::detect_bounded_clean_verdict_token;
 use output::{is_worktree_submodule, persist_review_result_exit_code};
+#[path = "review_cmd_apply_suggestions.rs"]
+mod apply_suggestions;
 #[path = "review_cmd_artifact_consistency.rs"]
 mod artifact_consistency;
 #[path = "review_cmd_artifact_parse.rs"]
 mod artifact_parse;
+#[path = "review_cmd_baseline.rs"]
+mod baseline;
 #[path = "review_cmd_bug_class.rs"]
 mod bug_class_pipeline;
 #[path = "review_cmd_check_verdict.rs"]
 mod check_verdict;
 #[path = "review_cmd_chunking.rs"]
 mod chunking;
+#[path = "review_cmd_ci.rs"]
+mod ci;
 #[path = "review_cmd_completion_policy.rs"]
 mod completion_policy;
 #[path = "review_cmd_depth.rs"]
@@ -45,8 +51,12 @@ mod diff_size;
 mod dirty_tree;
 #[path = "review_cmd_execute.rs"]
 mod execute;
+#[path = "review_cmd_fail_on.rs"]
+mod fail_on;
 #[path = "review_cmd_failure_post.rs"]
 mod failure_post;
+#[path = "review_cmd_finding_agreement.rs"]
+mod finding_agreement;
 #[path = "review_cmd_findings_toml.rs"]
 mod findings_toml;
 #[path = "review_cmd_fix.rs"]
@@ -57,6 +67,10 @@ mod fix_finding;
 mod flow;
 #[path = "review_cmd_gate.rs"]
 mod gate;
+#[path = "review_cmd_hunk_cache.rs"]
+mod hunk_cache;
+#[path = "review_cmd_infrastructure_notes.rs"]
+mod infrastructure_notes;
 #[path = "review_cmd_mempal.rs"]
 mod mempal;
 #[path = "review_cmd_multi.rs"]
@@ -65,6 +79,8 @@ mod multi;
 mod multi_repo_write_audit;
 #[path = "review_cmd_parent_artifacts.rs"]
 mod parent_artifacts;
+#[path = "review_cmd_post_pr.rs"]
+mod post_pr;
 #[path = "review_cmd_post_review.rs"]
 mod post_review;
 #[path = "review_cmd_preflight.rs"]
@@ -75,6 +91,8 @@ mod prior_rounds;
 mod prose_findings;
 #[path = "review_cmd_prose_resolution.rs"]
 mod prose_resolution;
+#[path = "review_cmd_report.rs"]
+mod report;
 #[path = "review_cmd_resolve.rs"]
 mod resolve;
 #[path = "review_cmd_result.rs"]
@@ -83,6 +101,10 @@ mod result_handling;
 mod review_convergence;
 #[path = "review_cmd_reviewers.rs"]
 mod reviewers;
+#[path = "review_cmd_run_findings.rs"]
+mod run_findings;
+#[path = "review_cmd_sarif.rs"]
+mod sarif;
 #[path = "review_cmd_session_fix.rs"]
 mod session_fix;
 #[path = "review_cmd_subtree_pin.rs"]
@@ -100,7 +122,7 @@ use bug_class_pipeline::{maybe_extract_recurring_bug_class_skills, resolve_revie
 pub(crate) use check_verdict::check_review_verdict_for_target;
 #[cfg(test)]
 use execute::execute_review;
-use execute::{compute_diff_fingerprint, execute_review_with_tier_filter};
+use execute::{compute_diff_fingerprint_for_paths, execute_review_with_tier_filter};
 #[cfg(test)]
 #[rustfmt::skip]
 pub(crate) use flow::{ execute_review_for_tests, persist_review_sidecars_if_session_exists, should_run_fix_loop };
@@ -108,7 +130,8 @@ pub(crate) use flow::{ execute_review_for_tests, persist_review_sidecars_if_sess
 use flow::persist_review_sidecars_if_session_exists_with_diff_size;
 #[cfg(not(test))]
 use flow::{persist_review_sidecars_if_session_exists_with_diff_size, should_run_fix_loop};
-use post_review::{build_post_review_output, emit_post_review_output, review_scope_is_cumulative};
+pub(crate) use post_review::review_scope_is_cumulative;
+use post_review::{build_post_review_output, emit_post_review_output};
 use prior_rounds::load_prior_rounds_section_or_persist_error;
 #[cfg(test)]
 use resolve::build_review_instruction;
@@ -127,7 +150,10 @@ use reviewers::resolve_effective_reviewer_selection_for_args;
 #[rustfmt::skip]
 pub(crate) use { fix::persist_fix_final_artifacts_for_tests, output::persist_review_verdict_for_tests };
 
-pub(crate) use execute::compute_diff_fingerprint as compute_review_diff_fingerprint;
+pub(crate) use execute::{
+    compute_diff_fingerprint as compute_review_diff_fingerprint,
+    compute_diff_fingerprint_for_paths as compute_review_diff_fingerprint_for_paths,
+};
 
 #[path = "review_cmd_handle.rs"]
 mod handle;
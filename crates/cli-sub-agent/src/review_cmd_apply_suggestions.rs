@@ -0,0 +1,334 @@
+//! `csa review --apply-suggestions`: apply a review's suggested patches.
+//!
+//! Reviewers may propose concrete fixes as fenced ```diff blocks in their
+//! report. This mode collects those hunks from a finished review session,
+//! asks before applying each one (unless `--yes`), applies the accepted hunks
+//! with `git apply`, runs the project's check command (the review quality
+//! gate), and records the outcome in `output/applied-suggestions.json`.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use csa_core::exit_code::ExitCode;
+use serde::Serialize;
+
+use crate::cli::ReviewArgs;
+
+#[path = "review_cmd_apply_suggestions_parse.rs"]
+mod parse;
+pub(super) use parse::diff_path;
+use parse::{SuggestedHunk, extract_suggested_hunks};
+
+const APPLIED_SUGGESTIONS_FILE: &str = "applied-suggestions.json";
+const APPLIED_SUGGESTIONS_SCHEMA_VERSION: u32 = 1;
+const REVIEW_REPORT_FILE: &str = "review-report.md";
+
+#[derive(Debug, Serialize)]
+struct AppliedSuggestions {
+    schema_version: u32,
+    session_id: String,
+    timestamp: DateTime<Utc>,
+    applied: Vec<HunkRecord>,
+    declined: Vec<HunkRecord>,
+    failed: Vec<HunkRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check: Option<CheckRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct HunkRecord {
+    file: String,
+    header: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl HunkRecord {
+    fn new(hunk: &SuggestedHunk, error: Option<String>) -> Self {
+        Self {
+            file: hunk.file().to_string(),
+            header: hunk.header.clone(),
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CheckRecord {
+    command: String,
+    passed: bool,
+}
+
+/// Apply the suggested patches of `--session` and run the check command.
+///
+/// Exits 2 ([`ExitCode::ToolFailure`]) when an accepted hunk does not apply or
+/// the check fails, so a failed fix is not mistaken for review findings.
+pub(super) async fn handle_apply_suggestions(args: ReviewArgs, current_depth: u32) -> Result<i32> {
+    let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+    let session_ref = args
+        .session
+        .as_deref()
+        .context("--apply-suggestions requires --session <review-session-id>")?;
+    let session_id = csa_session::resolve_fork_source(&project_root, session_ref)
+        .with_context(|| {
+            format!("failed to resolve --session {session_ref} for --apply-suggestions")
+        })?
+        .meta_session_id;
+    let session_dir = csa_session::get_session_dir(&project_root, &session_id)
+        .with_context(|| format!("failed to resolve review session dir for {session_id}"))?;
+
+    let hunks = extract_suggested_hunks(&review_report_texts(&session_dir)?);
+    if hunks.is_empty() {
+        println!("No suggested patches in review session {session_id}.");
+        return Ok(0);
+    }
+
+    let stdin = io::stdin();
+    let is_terminal = stdin.is_terminal();
+    let accepted = confirm_hunks(
+        &hunks,
+        args.yes,
+        is_terminal,
+        &mut stdin.lock(),
+        &mut io::stdout(),
+    )?;
+
+    let mut record = AppliedSuggestions {
+        schema_version: APPLIED_SUGGESTIONS_SCHEMA_VERSION,
+        session_id: session_id.clone(),
+        timestamp: Utc::now(),
+        applied: Vec::new(),
+        declined: Vec::new(),
+        failed: Vec::new(),
+        check: None,
+    };
+    for (hunk, accepted) in hunks.iter().zip(accepted) {
+        if !accepted {
+            record.declined.push(HunkRecord::new(hunk, None));
+            continue;
+        }
+        match git_apply(&project_root, &hunk.to_patch()) {
+            Ok(()) => {
+                println!("Applied {} {}", hunk.file(), hunk.header);
+                record.applied.push(HunkRecord::new(hunk, None));
+            }
+            Err(error) => {
+                eprintln!("Failed to apply {} {}: {error:#}", hunk.file(), hunk.header);
+                record
+                    .failed
+                    .push(HunkRecord::new(hunk, Some(format!("{error:#}"))));
+            }
+        }
+    }
+
+    if !record.applied.is_empty() {
+        record.check = run_check_command(&project_root, current_depth).await?;
+        match &record.check {
+            Some(check) if check.passed => println!("Check passed: {}", check.command),
+            Some(check) => println!("Check FAILED: {}", check.command),
+            None => println!("No check command configured or detected; skipped the check."),
+        }
+    }
+    let record_path = write_record(&session_dir, &record)?;
+    println!(
+        "{} applied, {} declined, {} failed; recorded in {}",
+        record.applied.len(),
+        record.declined.len(),
+        record.failed.len(),
+        record_path.display()
+    );
+
+    Ok(outcome_exit_code(&record).code())
+}
+
+fn outcome_exit_code(record: &AppliedSuggestions) -> ExitCode {
+    let check_failed = record.check.as_ref().is_some_and(|check| !check.passed);
+    if record.failed.is_empty() && !check_failed {
+        ExitCode::Success
+    } else {
+        ExitCode::ToolFailure
+    }
+}
+
+/// Review reports of the session and its reviewers, then the session output.
+fn review_report_texts(session_dir: &Path) -> Result<Vec<String>> {
+    let mut report_paths = vec![session_dir.join(REVIEW_REPORT_FILE)];
+    let mut reviewer_dirs: Vec<PathBuf> = std::fs::read_dir(session_dir)
+        .with_context(|| format!("failed to read {}", session_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("reviewer-"))
+        })
+        .collect();
+    reviewer_dirs.sort();
+    report_paths.extend(reviewer_dirs.iter().map(|dir| dir.join(REVIEW_REPORT_FILE)));
+
+    let mut texts: Vec<String> = report_paths
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect();
+    texts.extend(
+        csa_session::read_all_sections(session_dir)?
+            .into_iter()
+            .map(|(_, content)| content),
+    );
+    Ok(texts)
+}
+
+/// Ask about each hunk; `y` applies it, `a` applies it and the rest, `q`
+/// declines it and the rest. Anything else declines the hunk.
+fn confirm_hunks(
+    hunks: &[SuggestedHunk],
+    yes: bool,
+    is_terminal: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Vec<bool>> {
+    if yes {
+        return Ok(vec![true; hunks.len()]);
+    }
+    if !is_terminal {
+        bail!(
+            "--apply-suggestions asks before each of the {} suggested hunks; \
+             pass --yes to apply them without a terminal",
+            hunks.len()
+        );
+    }
+    let mut accepted = Vec::with_capacity(hunks.len());
+    let mut remaining: Option<bool> = None;
+    for (index, hunk) in hunks.iter().enumerate() {
+        if let Some(answer) = remaining {
+            accepted.push(answer);
+            continue;
+        }
+        writeln!(output, "[{}/{}] {}", index + 1, hunks.len(), hunk.file())?;
+        writeln!(output, "{}", hunk.header)?;
+        for line in &hunk.lines {
+            writeln!(output, "{line}")?;
+        }
+        write!(output, "Apply this hunk? [y/N/a/q] ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            remaining = Some(false);
+            accepted.push(false);
+            continue;
+        }
+        let answer = answer.trim().to_ascii_lowercase();
+        match answer.as_str() {
+            "y" | "yes" => accepted.push(true),
+            "a" | "all" => {
+                remaining = Some(true);
+                accepted.push(true);
+            }
+            "q" | "quit" => {
+                remaining = Some(false);
+                accepted.push(false);
+            }
+            _ => accepted.push(false),
+        }
+    }
+    Ok(accepted)
+}
+
+/// Apply one hunk to the working tree; hunk line counts are recomputed.
+fn git_apply(project_root: &Path, patch: &str) -> Result<()> {
+    let mut child = Command::new("git")
+        .args(["apply", "--recount", "--whitespace=nowarn", "-"])
+        .current_dir(project_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run git apply")?;
+    child
+        .stdin
+        .take()
+        .context("git apply stdin unavailable")?
+        .write_all(patch.as_bytes())
+        .context("failed to pass the patch to git apply")?;
+    let output = child
+        .wait_with_output()
+        .context("failed to wait for git apply")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Run the review quality gate as the project's check command, like the
+/// `--fix` loop does after each round. `None` when no gate ran.
+async fn run_check_command(project_root: &Path, current_depth: u32) -> Result<Option<CheckRecord>> {
+    let effective_config = csa_config::EffectiveConfig::load(project_root)?;
+    let review_config = effective_config
+        .project
+        .as_ref()
+        .and_then(|config| config.review.as_ref());
+    let global_config = effective_config.global;
+    let gate_steps = global_config.review.effective_gate_steps();
+    let gate_timeout = review_config
+        .map(|review| review.gate_timeout_secs)
+        .unwrap_or_else(csa_config::ReviewConfig::default_gate_timeout);
+    let gate_mode = &global_config.review.gate_mode;
+
+    if gate_steps.is_empty() {
+        let gate_command = review_config.and_then(|review| review.gate_command.as_deref());
+        let result = crate::pipeline::gate::evaluate_quality_gate(
+            project_root,
+            gate_command,
+            gate_timeout,
+            gate_mode,
+            current_depth,
+            None,
+        )
+        .await?;
+        return Ok((!result.skipped).then(|| CheckRecord {
+            passed: result.passed(),
+            command: result.command,
+        }));
+    }
+
+    let pipeline_result = crate::pipeline::gate::evaluate_quality_gates(
+        project_root,
+        &gate_steps,
+        gate_timeout,
+        gate_mode,
+        current_depth,
+        None,
+    )
+    .await?;
+    let commands: Vec<&str> = pipeline_result
+        .steps
+        .iter()
+        .filter(|step| !step.skipped)
+        .map(|step| step.command.as_str())
+        .collect();
+    if commands.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(CheckRecord {
+        command: commands.join(" && "),
+        passed: pipeline_result.passed,
+    }))
+}
+
+fn write_record(session_dir: &Path, record: &AppliedSuggestions) -> Result<PathBuf> {
+    let output_dir = session_dir.join("output");
+    std::fs::create_dir_all(&output_dir)
+        .with_context(|| format!("failed to create {}", output_dir.display()))?;
+    let path = output_dir.join(APPLIED_SUGGESTIONS_FILE);
+    let content =
+        serde_json::to_string_pretty(record).context("failed to serialize applied suggestions")?;
+    std::fs::write(&path, content)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+#[path = "review_cmd_apply_suggestions_tests.rs"]
+mod tests;
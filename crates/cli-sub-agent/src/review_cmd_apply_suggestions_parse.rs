@@ -0,0 +1,166 @@
+//! Extract unified-diff hunks from fenced ```diff blocks in review reports.
+//!
+//! Reviewers write the patches by hand, so hunk line counts are not trusted
+//! (`git apply --recount` recomputes them) and a bare `@@` header is accepted.
+
+const DIFF_FENCE_LANGUAGES: &[&str] = &["diff", "patch", "udiff"];
+/// Header for a bare `@@`: `git apply` anchors hunks starting at line 1 to
+/// the top of the file, any later start line lets it search for the context.
+const BARE_HUNK_HEADER: &str = "@@ -2 +2 @@";
+const BARE_NEW_FILE_HUNK_HEADER: &str = "@@ -0,0 +1 @@";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SuggestedHunk {
+    /// Path before the change; `None` when the hunk creates the file.
+    pub(super) old_path: Option<String>,
+    /// Path after the change; `None` when the hunk deletes the file.
+    pub(super) new_path: Option<String>,
+    pub(super) header: String,
+    pub(super) lines: Vec<String>,
+}
+
+impl SuggestedHunk {
+    pub(super) fn file(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Single-hunk patch accepted by `git apply`.
+    pub(super) fn to_patch(&self) -> String {
+        let side = |prefix: &str, path: &Option<String>| match path {
+            Some(path) => format!("{prefix}/{path}"),
+            None => "/dev/null".to_string(),
+        };
+        let header = if self.header.starts_with("@@ -") {
+            self.header.as_str()
+        } else if self.old_path.is_none() {
+            BARE_NEW_FILE_HUNK_HEADER
+        } else {
+            BARE_HUNK_HEADER
+        };
+        let mut patch = format!(
+            "--- {}\n+++ {}\n{header}\n",
+            side("a", &self.old_path),
+            side("b", &self.new_path)
+        );
+        for line in &self.lines {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        patch
+    }
+}
+
+/// Hunks of every diff block in `texts`, without duplicates.
+pub(super) fn extract_suggested_hunks(texts: &[String]) -> Vec<SuggestedHunk> {
+    let mut hunks = Vec::new();
+    for text in texts {
+        for block in diff_blocks(text) {
+            for hunk in parse_diff_block(&block) {
+                if !hunks.contains(&hunk) {
+                    hunks.push(hunk);
+                }
+            }
+        }
+    }
+    hunks
+}
+
+fn diff_blocks(text: &str) -> Vec<Vec<&str>> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        match current.take() {
+            Some(block) if trimmed.starts_with("```") => blocks.push(block),
+            Some(mut block) => {
+                block.push(line);
+                current = Some(block);
+            }
+            None => {
+                if let Some(info) = trimmed.strip_prefix("```") {
+                    let language = info.split_whitespace().next().unwrap_or_default();
+                    if DIFF_FENCE_LANGUAGES
+                        .iter()
+                        .any(|known| language.eq_ignore_ascii_case(known))
+                    {
+                        current = Some(Vec::new());
+                    }
+                }
+            }
+        }
+    }
+    blocks
+}
+
+fn parse_diff_block(lines: &[&str]) -> Vec<SuggestedHunk> {
+    let mut hunks = Vec::new();
+    let mut paths: Option<(Option<String>, Option<String>)> = None;
+    let mut current: Option<SuggestedHunk> = None;
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        let next_is_new_path = lines
+            .get(index)
+            .is_some_and(|next| next.starts_with("+++ "));
+        if let Some(old) = line.strip_prefix("--- ")
+            && next_is_new_path
+        {
+            finish_hunk(&mut hunks, current.take());
+            let new = &lines[index]["+++ ".len()..];
+            index += 1;
+            paths = Some((diff_path(old), diff_path(new)));
+        } else if line.starts_with("@@") {
+            finish_hunk(&mut hunks, current.take());
+            current = paths.clone().map(|(old_path, new_path)| SuggestedHunk {
+                old_path,
+                new_path,
+                header: line.trim_end().to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(hunk) = current.as_mut() {
+            if line.is_empty() {
+                // Editors and models strip the space of empty context lines.
+                hunk.lines.push(" ".to_string());
+            } else if line.starts_with([' ', '+', '-', '\\']) {
+                hunk.lines.push(line.to_string());
+            } else {
+                finish_hunk(&mut hunks, current.take());
+            }
+        }
+    }
+    finish_hunk(&mut hunks, current);
+    hunks
+}
+
+fn finish_hunk(hunks: &mut Vec<SuggestedHunk>, hunk: Option<SuggestedHunk>) {
+    let Some(mut hunk) = hunk else {
+        return;
+    };
+    while hunk.lines.last().is_some_and(|line| line.trim().is_empty()) {
+        hunk.lines.pop();
+    }
+    if hunk.lines.iter().any(|line| line.starts_with(['+', '-'])) {
+        hunks.push(hunk);
+    }
+}
+
+/// Repository-relative path of a `---`/`+++` header, `None` for `/dev/null`.
+pub(in crate::review_cmd) fn diff_path(raw: &str) -> Option<String> {
+    let path = raw.split('\t').next().unwrap_or_default().trim();
+    if path == "/dev/null" || path.is_empty() {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(csa_session::normalize_path(path))
+}
+
+#[cfg(test)]
+#[path = "review_cmd_apply_suggestions_parse_tests.rs"]
+mod tests;
@@ -0,0 +1,112 @@
+use super::*;
+
+fn texts(text: &str) -> Vec<String> {
+    vec![text.to_string()]
+}
+
+#[test]
+fn extracts_hunks_from_diff_fences_only() {
+    let report = "\
+## Findings
+1. [P1] unwrap on user input (`src/lib.rs:2`)
+
+```rust
+--- a/src/ignored.rs
++++ b/src/ignored.rs
+@@ -1 +1 @@
+-a
++b
+```
+
+## Suggested Patches
+```diff
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@ fn parse()
+ fn parse(input: &str) -> u32 {
+-    input.parse().unwrap()
++    input.parse().unwrap_or_default()
+
+@@ -10,2 +10,3 @@
+ }
++// trailing
+```
+";
+    let hunks = extract_suggested_hunks(&texts(report));
+    assert_eq!(hunks.len(), 2);
+    assert_eq!(hunks[0].file(), "src/lib.rs");
+    assert_eq!(hunks[0].header, "@@ -1,3 +1,3 @@ fn parse()");
+    assert_eq!(
+        hunks[0].lines,
+        [
+            " fn parse(input: &str) -> u32 {",
+            "-    input.parse().unwrap()",
+            "+    input.parse().unwrap_or_default()",
+        ]
+    );
+    assert_eq!(hunks[1].lines, [" }", "+// trailing"]);
+    assert_eq!(
+        hunks[0].to_patch(),
+        "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,3 @@ fn parse()\n fn parse(input: &str) -> u32 {\n-    input.parse().unwrap()\n+    input.parse().unwrap_or_default()\n"
+    );
+}
+
+#[test]
+fn removed_lines_that_look_like_headers_stay_in_the_hunk() {
+    let report = "\
+```patch
+--- src/notes.md
++++ src/notes.md
+@@ -1,2 +1,1 @@
+ # Notes
+--- old rule
+```
+";
+    let hunks = extract_suggested_hunks(&texts(report));
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[0].old_path.as_deref(), Some("src/notes.md"));
+    assert_eq!(hunks[0].lines, [" # Notes", "--- old rule"]);
+}
+
+#[test]
+fn new_files_and_bare_headers_produce_applicable_patches() {
+    let report = "\
+```diff
+--- /dev/null
++++ b/docs/new.md
+@@
++hello
+```
+";
+    let hunks = extract_suggested_hunks(&texts(report));
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[0].old_path, None);
+    assert_eq!(hunks[0].file(), "docs/new.md");
+    assert_eq!(
+        hunks[0].to_patch(),
+        "--- /dev/null\n+++ b/docs/new.md\n@@ -0,0 +1 @@\n+hello\n"
+    );
+}
+
+#[test]
+fn skips_hunks_without_file_or_changes_and_duplicates() {
+    let block = "\
+```diff
+@@ -1 +1 @@
+-orphan
++hunk
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -1,1 +1,1 @@
+ context only
+@@ -5,1 +5,1 @@
+-old
++new
+```
+";
+    let hunks = extract_suggested_hunks(&[block.to_string(), block.to_string()]);
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[0].header, "@@ -5,1 +5,1 @@");
+}
@@ -0,0 +1,192 @@
+use super::*;
+
+fn hunk(file: &str, lines: &[&str]) -> SuggestedHunk {
+    SuggestedHunk {
+        old_path: Some(file.to_string()),
+        new_path: Some(file.to_string()),
+        header: "@@ -1,2 +1,2 @@".to_string(),
+        lines: lines.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn confirm_hunks_follows_answers() {
+    let hunks = vec![
+        hunk("a.rs", &["-a", "+b"]),
+        hunk("b.rs", &["-a", "+b"]),
+        hunk("c.rs", &["-a", "+b"]),
+        hunk("d.rs", &["-a", "+b"]),
+    ];
+    let mut output = Vec::new();
+    let accepted = confirm_hunks(&hunks, false, true, &mut "y\n\nq\n".as_bytes(), &mut output)
+        .expect("confirm");
+    assert_eq!(accepted, [true, false, false, false]);
+    let output = String::from_utf8(output).expect("utf8");
+    assert!(output.contains("[1/4] a.rs\n@@ -1,2 +1,2 @@\n-a\n+b\nApply this hunk? [y/N/a/q] "));
+    assert!(!output.contains("[4/4]"), "q stops asking");
+
+    let accepted = confirm_hunks(
+        &hunks,
+        false,
+        true,
+        &mut "n\na\n".as_bytes(),
+        &mut Vec::new(),
+    )
+    .expect("confirm");
+    assert_eq!(accepted, [false, true, true, true]);
+
+    let accepted = confirm_hunks(&hunks, false, true, &mut "y\n".as_bytes(), &mut Vec::new())
+        .expect("confirm");
+    assert_eq!(
+        accepted,
+        [true, false, false, false],
+        "EOF declines the rest"
+    );
+}
+
+#[test]
+fn confirm_hunks_needs_yes_without_a_terminal() {
+    let hunks = vec![hunk("a.rs", &["-a", "+b"])];
+    let error = confirm_hunks(&hunks, false, false, &mut "y\n".as_bytes(), &mut Vec::new())
+        .expect_err("non-interactive without --yes");
+    assert!(error.to_string().contains("pass --yes"));
+
+    let mut output = Vec::new();
+    let accepted =
+        confirm_hunks(&hunks, true, false, &mut "".as_bytes(), &mut output).expect("confirm");
+    assert_eq!(accepted, [true]);
+    assert!(output.is_empty());
+}
+
+#[test]
+fn git_apply_applies_moved_hunks_and_reports_conflicts() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    git(root, &["init", "-q"]);
+    std::fs::write(
+        root.join("lib.rs"),
+        "// header\nfn a() {\n    x.unwrap();\n}\n",
+    )
+    .expect("write");
+
+    // Reviewer line numbers and counts are off; --recount and context search fix them.
+    let suggested = SuggestedHunk {
+        header: "@@ -10,2 +10,2 @@".to_string(),
+        ..hunk(
+            "lib.rs",
+            &[
+                " fn a() {",
+                "-    x.unwrap();",
+                "+    x.unwrap_or_default();",
+                " }",
+            ],
+        )
+    };
+    git_apply(root, &suggested.to_patch()).expect("apply");
+    assert_eq!(
+        std::fs::read_to_string(root.join("lib.rs")).expect("read"),
+        "// header\nfn a() {\n    x.unwrap_or_default();\n}\n"
+    );
+
+    let error = git_apply(root, &suggested.to_patch()).expect_err("already applied");
+    assert!(!error.to_string().is_empty());
+
+    let bare = SuggestedHunk {
+        header: "@@".to_string(),
+        ..hunk("lib.rs", &["-// header", "+// Header", " fn a() {"])
+    };
+    git_apply(root, &bare.to_patch()).expect("apply bare hunk");
+    assert!(
+        std::fs::read_to_string(root.join("lib.rs"))
+            .expect("read")
+            .starts_with("// Header\n")
+    );
+}
+
+#[test]
+fn review_report_texts_reads_reviewer_reports_and_output_sections() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let session_dir = dir.path();
+    std::fs::create_dir_all(session_dir.join("reviewer-2")).expect("mkdir");
+    std::fs::create_dir_all(session_dir.join("reviewer-1")).expect("mkdir");
+    std::fs::write(session_dir.join(REVIEW_REPORT_FILE), "parent").expect("write");
+    std::fs::write(
+        session_dir.join("reviewer-2").join(REVIEW_REPORT_FILE),
+        "two",
+    )
+    .expect("write");
+    std::fs::write(
+        session_dir.join("reviewer-1").join(REVIEW_REPORT_FILE),
+        "one",
+    )
+    .expect("write");
+
+    let texts = review_report_texts(session_dir).expect("texts");
+    assert_eq!(texts, ["parent", "one", "two"]);
+}
+
+#[test]
+fn write_record_stores_outcome_in_session_output() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let suggested = hunk("a.rs", &["-a", "+b"]);
+    let record = AppliedSuggestions {
+        schema_version: APPLIED_SUGGESTIONS_SCHEMA_VERSION,
+        session_id: "01TESTAPPLYSUGGESTIONS000".to_string(),
+        timestamp: Utc::now(),
+        applied: vec![HunkRecord::new(&suggested, None)],
+        declined: Vec::new(),
+        failed: vec![HunkRecord::new(
+            &suggested,
+            Some("patch does not apply".to_string()),
+        )],
+        check: Some(CheckRecord {
+            command: "just pre-commit".to_string(),
+            passed: true,
+        }),
+    };
+
+    let path = write_record(dir.path(), &record).expect("write");
+    assert_eq!(
+        path,
+        dir.path().join("output").join(APPLIED_SUGGESTIONS_FILE)
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).expect("read")).expect("json");
+    assert_eq!(json["applied"][0]["file"], "a.rs");
+    assert!(json["applied"][0].get("error").is_none());
+    assert_eq!(json["failed"][0]["error"], "patch does not apply");
+    assert_eq!(json["check"]["passed"], true);
+    assert_eq!(outcome_exit_code(&record), ExitCode::ToolFailure);
+}
+
+#[test]
+fn failed_check_is_a_tool_failure_not_review_findings() {
+    let suggested = hunk("a.rs", &["-a", "+b"]);
+    let mut record = AppliedSuggestions {
+        schema_version: APPLIED_SUGGESTIONS_SCHEMA_VERSION,
+        session_id: "01TESTAPPLYSUGGESTIONS000".to_string(),
+        timestamp: Utc::now(),
+        applied: vec![HunkRecord::new(&suggested, None)],
+        declined: Vec::new(),
+        failed: Vec::new(),
+        check: Some(CheckRecord {
+            command: "just pre-commit".to_string(),
+            passed: true,
+        }),
+    };
+    assert_eq!(outcome_exit_code(&record), ExitCode::Success);
+
+    record.check.as_mut().expect("check").passed = false;
+    assert_eq!(outcome_exit_code(&record), ExitCode::ToolFailure);
+}
@@ -0,0 +1,285 @@
+//! `csa review --baseline`: accepted findings that do not fail a review.
+//!
+//! The baseline (`.csa/review-baseline.toml` by default) lists findings the
+//! team has accepted. A finding of a new review is known when its stable
+//! FindingId (fid-v1) is in the baseline, or when an entry for the same file
+//! and rule carries the same anchor hash: the hash of the source lines around
+//! the finding, so accepted findings survive unrelated edits that move them.
+//! A finding whose severity rose above the accepted entry's is new either way.
+//! Reviewer-local ids such as `f1` in `findings.toml` never match on their own.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use csa_session::{Finding, FindingId, ReviewSessionMeta, Severity, anchor_hash};
+use serde::{Deserialize, Serialize};
+
+use super::run_findings::{ReviewFindings, all_sessions_decided, collect_session_findings};
+use super::sarif::severity_label;
+use crate::cli::{ReviewBaselineCommand, ReviewCommand};
+
+const BASELINE_VERSION: u32 = 1;
+/// Source lines hashed on each side of a finding's line.
+const ANCHOR_CONTEXT_LINES: usize = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct BaselineEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fid: Option<String>,
+    file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    anchor: String,
+    severity: Severity,
+    rule_id: String,
+    summary: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BaselineFile {
+    version: u32,
+    #[serde(default)]
+    findings: Vec<BaselineEntry>,
+}
+
+#[derive(Debug)]
+pub(super) struct ReviewBaseline {
+    path: PathBuf,
+    entries: Vec<BaselineEntry>,
+}
+
+impl ReviewBaseline {
+    /// Load the baseline at `path` (relative to `project_root` unless absolute).
+    pub(super) fn load(project_root: &Path, path: &Path) -> Result<Self> {
+        let path = project_root.join(path);
+        let content = std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "failed to read review baseline {}; create it with `csa review baseline update`",
+                path.display()
+            )
+        })?;
+        let file: BaselineFile = toml::from_str(&content)
+            .with_context(|| format!("failed to parse review baseline {}", path.display()))?;
+        if file.version > BASELINE_VERSION {
+            bail!(
+                "review baseline {} has version {}; this csa supports up to {BASELINE_VERSION}",
+                path.display(),
+                file.version
+            );
+        }
+        Ok(Self {
+            path,
+            entries: file.findings,
+        })
+    }
+
+    /// Split `findings` into those in the baseline and new ones.
+    pub(super) fn split(&self, project_root: &Path, findings: Vec<Finding>) -> ReviewFindings {
+        let (known, new) = findings.into_iter().partition(|finding| {
+            let entry = entry_for(project_root, finding);
+            self.entries.iter().any(|known| entry.matches(known))
+        });
+        ReviewFindings {
+            new,
+            known,
+            baseline_applied: true,
+        }
+    }
+
+    /// Text section listing known and new findings.
+    pub(super) fn report(&self, findings: &ReviewFindings, verdict_waived: bool) -> String {
+        let mut report = format!(
+            "===== Review Baseline =====\nbaseline: {} ({} accepted)\nknown: {} (excluded from the exit code)\n",
+            self.path.display(),
+            self.entries.len(),
+            findings.known.len()
+        );
+        for finding in &findings.known {
+            push_finding_line(&mut report, finding);
+        }
+        let _ = writeln!(report, "new: {}", findings.new.len());
+        for finding in &findings.new {
+            push_finding_line(&mut report, finding);
+        }
+        if verdict_waived {
+            report.push_str("all findings are in the baseline; exiting 0\n");
+        }
+        report
+    }
+}
+
+impl BaselineEntry {
+    fn matches(&self, known: &BaselineEntry) -> bool {
+        if severity_rank(&self.severity) > severity_rank(&known.severity) {
+            return false;
+        }
+        if self.fid.is_some() && self.fid == known.fid {
+            return true;
+        }
+        self.file == known.file && self.rule_id == known.rule_id && self.anchor == known.anchor
+    }
+}
+
+fn severity_rank(severity: &Severity) -> u8 {
+    severity.clone() as u8
+}
+
+fn push_finding_line(report: &mut String, finding: &Finding) {
+    let location = match finding.line {
+        Some(line) => format!("{}:{line}", finding.file),
+        None => finding.file.clone(),
+    };
+    let summary = finding.summary.lines().next().unwrap_or_default();
+    let _ = writeln!(
+        report,
+        "- [{}] {location} {}: {summary}",
+        severity_label(&finding.severity),
+        finding.rule_id
+    );
+}
+
+/// Baseline entry describing `finding`, with its anchor hash.
+fn entry_for(project_root: &Path, finding: &Finding) -> BaselineEntry {
+    let file = csa_session::normalize_path(&finding.file);
+    BaselineEntry {
+        fid: FindingId::parse(&finding.fid).map(FindingId::into_inner),
+        anchor: finding_anchor(project_root, &file, finding),
+        file,
+        line: finding.line,
+        severity: finding.severity.clone(),
+        rule_id: finding.rule_id.clone(),
+        summary: finding.summary.clone(),
+    }
+}
+
+/// Hash of the source lines around the finding, or of its summary when the
+/// finding has no readable line.
+fn finding_anchor(project_root: &Path, file: &str, finding: &Finding) -> String {
+    let source = finding
+        .line
+        .filter(|line| *line > 0)
+        .and_then(|line| Some((line, std::fs::read_to_string(project_root.join(file)).ok()?)));
+    if let Some((line, source)) = source {
+        let lines: Vec<&str> = source.lines().collect();
+        let index = line as usize - 1;
+        if index < lines.len() {
+            let start = index.saturating_sub(ANCHOR_CONTEXT_LINES);
+            let end = (index + ANCHOR_CONTEXT_LINES + 1).min(lines.len());
+            return anchor_hash(&lines[start..end]);
+        }
+    }
+    anchor_hash(&[finding.summary.as_str()])
+}
+
+/// Exit code once known findings no longer count.
+///
+/// A failed review passes when it has findings, all of them are known, and
+/// every review session reached a pass/fail decision; reviews that were
+/// unavailable or uncertain keep their exit code.
+pub(super) fn exit_code_with_baseline(
+    project_root: &Path,
+    session_ids: &[String],
+    findings: &ReviewFindings,
+    exit_code: i32,
+) -> i32 {
+    if exit_code != 1 || findings.known.is_empty() || !findings.new.is_empty() {
+        return exit_code;
+    }
+    if all_sessions_decided(project_root, session_ids) {
+        0
+    } else {
+        exit_code
+    }
+}
+
+/// `csa review baseline ...`
+pub(super) fn handle_baseline_command(command: ReviewCommand) -> Result<i32> {
+    match command {
+        ReviewCommand::Baseline {
+            command:
+                ReviewBaselineCommand::Update {
+                    baseline,
+                    session,
+                    cd,
+                },
+        } => {
+            let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
+            update_baseline(&project_root, &baseline, &session)?;
+            Ok(0)
+        }
+    }
+}
+
+fn update_baseline(project_root: &Path, baseline: &Path, sessions: &[String]) -> Result<()> {
+    let session_ids = if sessions.is_empty() {
+        vec![latest_review_session(project_root)?.context(
+            "no review session found; run `csa review` first or pass --session <SESSION_ID>",
+        )?]
+    } else {
+        let sessions_dir = csa_session::get_session_root(project_root)?.join("sessions");
+        sessions
+            .iter()
+            .map(|prefix| csa_session::resolve_session_prefix(&sessions_dir, prefix))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut entries: Vec<BaselineEntry> = Vec::new();
+    for finding in collect_session_findings(project_root, &session_ids) {
+        let entry = entry_for(project_root, &finding);
+        if !entries.iter().any(|existing| entry.matches(existing)) {
+            entries.push(entry);
+        }
+    }
+    let previous = ReviewBaseline::load(project_root, baseline)
+        .map(|previous| previous.entries)
+        .unwrap_or_default();
+    let added = entries
+        .iter()
+        .filter(|entry| !previous.iter().any(|known| entry.matches(known)))
+        .count();
+    let dropped = previous
+        .iter()
+        .filter(|known| !entries.iter().any(|entry| entry.matches(known)))
+        .count();
+
+    let path = project_root.join(baseline);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let file = BaselineFile {
+        version: BASELINE_VERSION,
+        findings: entries,
+    };
+    let content = toml::to_string_pretty(&file).context("failed to serialize review baseline")?;
+    std::fs::write(&path, content)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    println!(
+        "wrote {}: {} accepted findings from {} ({added} added, {dropped} dropped)",
+        path.display(),
+        file.findings.len(),
+        session_ids.join(", ")
+    );
+    Ok(())
+}
+
+/// Most recent session with review metadata.
+fn latest_review_session(project_root: &Path) -> Result<Option<String>> {
+    let sessions = csa_session::list_sessions(project_root, None)?;
+    Ok(sessions
+        .into_iter()
+        .filter_map(|session| {
+            let session_dir =
+                csa_session::get_session_dir(project_root, &session.meta_session_id).ok()?;
+            let raw = std::fs::read_to_string(session_dir.join("review_meta.json")).ok()?;
+            let meta: ReviewSessionMeta = serde_json::from_str(&raw).ok()?;
+            Some((meta.timestamp, session.meta_session_id))
+        })
+        .max()
+        .map(|(_, session_id)| session_id))
+}
+
+#[cfg(test)]
+#[path = "review_cmd_baseline_tests.rs"]
+mod tests;
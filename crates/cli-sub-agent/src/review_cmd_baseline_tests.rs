@@ -0,0 +1,155 @@
+use super::*;
+
+const STABLE_FID: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn finding(fid: &str, file: &str, line: Option<u32>, summary: &str) -> Finding {
+    Finding {
+        summary: summary.to_string(),
+        ..crate::test_review_findings::finding(Severity::High, fid, file, line, "rust.no-unwrap")
+    }
+}
+
+fn write_baseline(project_root: &Path, entries: Vec<BaselineEntry>) -> ReviewBaseline {
+    let content = toml::to_string_pretty(&BaselineFile {
+        version: BASELINE_VERSION,
+        findings: entries,
+    })
+    .expect("serialize baseline");
+    std::fs::write(project_root.join("baseline.toml"), content).expect("write baseline");
+    ReviewBaseline::load(project_root, Path::new("baseline.toml")).expect("load baseline")
+}
+
+#[test]
+fn known_findings_match_by_stable_fid_or_anchor() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src")).expect("mkdir");
+    std::fs::write(root.join("src/lib.rs"), "fn a() {\n    x.unwrap();\n}\n").expect("write");
+
+    let by_anchor = finding("f1", "src/lib.rs", Some(2), "unwrap on x");
+    let by_fid = finding(STABLE_FID, "src/other.rs", None, "old summary");
+    let baseline = write_baseline(
+        root,
+        vec![entry_for(root, &by_anchor), entry_for(root, &by_fid)],
+    );
+
+    // The accepted unwrap moved down two lines and the fid finding was reworded.
+    std::fs::write(
+        root.join("src/lib.rs"),
+        "// header\n\nfn a() {\n    x.unwrap();\n}\n",
+    )
+    .expect("write");
+    let findings = baseline.split(
+        root,
+        vec![
+            finding("f1", "./src/lib.rs", Some(4), "unwrap on x"),
+            finding(STABLE_FID, "src/other.rs", None, "new summary"),
+            finding("f1", "src/lib.rs", Some(1), "unwrap on y"),
+        ],
+    );
+
+    assert!(findings.baseline_applied);
+    assert_eq!(findings.known.len(), 2);
+    assert_eq!(findings.new.len(), 1);
+    assert_eq!(findings.new[0].summary, "unwrap on y");
+}
+
+#[test]
+fn anchor_matches_need_the_same_rule_and_no_higher_severity() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src")).expect("mkdir");
+    std::fs::write(root.join("src/lib.rs"), "fn a() {\n    x.unwrap();\n}\n").expect("write");
+    let accepted = finding(STABLE_FID, "src/lib.rs", Some(2), "unwrap on x");
+    let baseline = write_baseline(root, vec![entry_for(root, &accepted)]);
+
+    let mut other_rule = finding("f1", "src/lib.rs", Some(2), "unwrap on x");
+    other_rule.rule_id = "rust.panic-path".to_string();
+    let mut escalated = finding("f2", "src/lib.rs", Some(2), "unwrap on x");
+    escalated.severity = Severity::Critical;
+    let mut escalated_fid = accepted.clone();
+    escalated_fid.severity = Severity::Critical;
+    let mut downgraded = finding("f3", "src/lib.rs", Some(2), "unwrap on x");
+    downgraded.severity = Severity::Low;
+
+    let findings = baseline.split(root, vec![other_rule, escalated, escalated_fid, downgraded]);
+    assert_eq!(findings.known.len(), 1);
+    assert_eq!(findings.known[0].fid, "f3");
+    assert_eq!(findings.new.len(), 3);
+}
+
+#[test]
+fn reviewer_local_ids_do_not_match_on_their_own() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    let baseline = write_baseline(
+        root,
+        vec![entry_for(root, &finding("f1", "src/a.rs", None, "first"))],
+    );
+    assert_eq!(
+        entry_for(root, &finding("f1", "src/a.rs", None, "first")).fid,
+        None
+    );
+
+    let findings = baseline.split(root, vec![finding("f1", "src/a.rs", None, "second")]);
+    assert!(findings.known.is_empty());
+    assert_eq!(findings.new.len(), 1);
+}
+
+#[test]
+fn load_reports_missing_and_newer_baselines() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    let missing = ReviewBaseline::load(root, Path::new("missing.toml")).expect_err("missing");
+    assert!(format!("{missing:#}").contains("csa review baseline update"));
+
+    std::fs::write(root.join("future.toml"), "version = 2\n").expect("write");
+    let newer = ReviewBaseline::load(root, Path::new("future.toml")).expect_err("newer");
+    assert!(format!("{newer:#}").contains("version 2"));
+}
+
+#[test]
+fn report_lists_known_and_new_findings() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    let baseline = write_baseline(root, Vec::new());
+    let findings = ReviewFindings {
+        new: vec![finding("f2", "src/b.rs", Some(7), "new issue\ndetails")],
+        known: vec![finding("f1", "src/a.rs", None, "accepted issue")],
+        baseline_applied: true,
+    };
+
+    let report = baseline.report(&findings, false);
+    assert!(report.starts_with("===== Review Baseline =====\n"));
+    assert!(report.contains("known: 1 (excluded from the exit code)\n"));
+    assert!(report.contains("- [high] src/a.rs rust.no-unwrap: accepted issue\n"));
+    assert!(report.contains("new: 1\n- [high] src/b.rs:7 rust.no-unwrap: new issue\n"));
+    assert!(!report.contains("exiting 0"));
+    assert!(baseline.report(&findings, true).contains("exiting 0"));
+}
+
+#[test]
+fn exit_code_is_kept_unless_every_finding_is_known() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    let known_only = ReviewFindings {
+        known: vec![finding("f1", "src/a.rs", None, "accepted")],
+        baseline_applied: true,
+        ..ReviewFindings::default()
+    };
+    let with_new = ReviewFindings {
+        new: vec![finding("f2", "src/b.rs", None, "new")],
+        known: known_only.known.clone(),
+        baseline_applied: true,
+    };
+
+    assert_eq!(exit_code_with_baseline(root, &[], &known_only, 0), 0);
+    assert_eq!(exit_code_with_baseline(root, &[], &known_only, 2), 2);
+    assert_eq!(exit_code_with_baseline(root, &[], &with_new, 1), 1);
+    assert_eq!(
+        exit_code_with_baseline(root, &[], &ReviewFindings::default(), 1),
+        1
+    );
+    // Without a persisted pass/fail decision the review may not have run.
+    assert_eq!(exit_code_with_baseline(root, &[], &known_only, 1), 1);
+}
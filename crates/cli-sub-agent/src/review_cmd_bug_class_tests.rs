@@ -181,6 +181,7 @@ fn review_iterations_increment_from_prior_review_meta_on_same_branch() {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "base:main".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -226,6 +227,7 @@ fn review_iterations_do_not_undercount_after_more_than_ten_prior_reviews() {
                 failure_reason: None,
                 tool: "codex".to_string(),
                 scope: "base:main".to_string(),
+                path_filters: Vec::new(),
                 exit_code: 1,
                 fix_attempted: false,
                 fix_rounds: 0,
@@ -278,6 +280,7 @@ fn review_iterations_use_max_prior_value_instead_of_most_recent_session() {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "base:main".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -311,6 +314,7 @@ fn review_iterations_use_max_prior_value_instead_of_most_recent_session() {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "base:main".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -384,6 +388,7 @@ fn bug_class_loader_collapses_multi_reviewer_sessions_into_one_logical_review()
                 failure_reason: None,
                 tool: "codex".to_string(),
                 scope: "base:main".to_string(),
+                path_filters: Vec::new(),
                 exit_code: 1,
                 fix_attempted: false,
                 fix_rounds: 0,
@@ -470,6 +475,7 @@ fn bug_class_loader_skips_parent_consolidated_artifact_to_avoid_false_promotion(
                 failure_reason: None,
                 tool: "codex".to_string(),
                 scope: "base:main".to_string(),
+                path_filters: Vec::new(),
                 exit_code: 1,
                 fix_attempted: false,
                 fix_rounds: 0,
@@ -531,6 +537,7 @@ fn bug_class_loader_keeps_sessions_with_review_meta_even_if_consolidated_exists(
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "base:main".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -577,6 +584,7 @@ fn bug_class_loader_reads_exact_consolidated_path_written_by_consensus_writer()
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "base:main".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -132,7 +132,7 @@ pub(crate) fn check_review_verdict_for_session(
         return Ok(None);
     };
 
-    if meta.head_sha != head_sha || meta.scope != required_scope {
+    if meta.head_sha != head_sha || !meta.covers_scope(required_scope) {
         debug!(
             session_id = %resolved.session_id,
             meta_head_sha = %meta.head_sha,
@@ -249,7 +249,7 @@ pub(crate) fn check_review_verdict_for_target(
             );
             continue;
         };
-        if meta.head_sha != head_sha || meta.scope != required_scope {
+        if meta.head_sha != head_sha || !meta.covers_scope(required_scope) {
             debug!(
                 session_id = %session.meta_session_id,
                 meta_head_sha = %meta.head_sha,
@@ -406,7 +406,7 @@ fn check_review_verdict_marker(
         );
         return Ok(None);
     };
-    if meta.head_sha != head_sha || meta.scope != required_scope {
+    if meta.head_sha != head_sha || !meta.covers_scope(required_scope) {
         debug!(
             session_id = %marker.session_id,
             meta_head_sha = %meta.head_sha,
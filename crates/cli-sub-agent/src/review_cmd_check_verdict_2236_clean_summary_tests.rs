@@ -226,6 +226,7 @@ fn write_failed_empty_artifact_review_session(
             failure_reason: None,
             tool: "codex".to_string(),
             scope: REQUIRED_FULL_DIFF_SCOPE.to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -463,6 +464,7 @@ fn issue_2393_existing_fail_meta_without_artifact_recovers_exact_head_pass_summa
             failure_reason: None,
             tool: "codex".to_string(),
             scope: REQUIRED_FULL_DIFF_SCOPE.to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -84,6 +84,7 @@ fn write_review_session(
         failure_reason: failure_reason.map(str::to_string),
         tool: "codex".to_string(),
         scope: REQUIRED_FULL_DIFF_SCOPE.to_string(),
+        path_filters: Vec::new(),
         exit_code: if decision == ReviewDecision::Pass {
             0
         } else {
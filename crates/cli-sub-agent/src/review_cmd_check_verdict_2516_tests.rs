@@ -44,6 +44,7 @@ fn issue_2516_check_verdict_rejects_pass_json_with_findings_toml() {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: REQUIRED_FULL_DIFF_SCOPE.to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
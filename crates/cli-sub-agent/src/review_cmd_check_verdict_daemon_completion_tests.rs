@@ -219,6 +219,7 @@ fn recovered_artifact_only_pass_keeps_original_timestamp_so_newer_fail_blocks_ga
         failure_reason: None,
         tool: "codex".to_string(),
         scope: REQUIRED_FULL_DIFF_SCOPE.to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: false,
         fix_rounds: 0,
@@ -87,6 +87,7 @@ fn write_review_session_with_description(
         failure_reason: None,
         tool: "codex".to_string(),
         scope: scope.to_string(),
+        path_filters: Vec::new(),
         exit_code: if decision == ReviewDecision::Pass {
             0
         } else {
@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::io::Write;
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
@@ -77,6 +78,10 @@ pub(super) struct ReviewChunk {
     pub(super) pathspecs: Vec<String>,
     pub(super) changed_lines: usize,
     pub(super) estimated_tokens: usize,
+    /// More changed lines than `max_changed_lines_per_chunk`: a single large
+    /// file, or chunks merged to stay within `max_chunks`.
+    #[serde(default)]
+    pub(super) over_budget: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -118,6 +123,7 @@ pub(super) struct ChunkedReviewContext<'a> {
     pub(super) review_mode: &'a str,
     pub(super) current_depth: u32,
     pub(super) startup_env: &'a StartupSubtreeEnv,
+    pub(super) report: &'a mut super::report::ReviewReport,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -140,6 +146,8 @@ struct ChunkAuditEntry {
     pathspecs: Vec<String>,
     changed_lines: usize,
     estimated_tokens: usize,
+    #[serde(default)]
+    over_budget: bool,
     session_id: String,
     verdict: String,
 }
@@ -182,6 +190,14 @@ impl ReviewChunkingConfig {
     pub(super) fn concurrency(&self) -> usize {
         self.max_concurrency.max(1)
     }
+
+    /// Chunk reviews run at once: `max_concurrency`, capped by the tool's
+    /// `max_concurrent` slots so chunks queue instead of failing on a full slot.
+    pub(super) fn concurrency_for_slots(&self, max_concurrent_slots: u32) -> usize {
+        self.concurrency()
+            .min(usize::try_from(max_concurrent_slots).unwrap_or(usize::MAX))
+            .max(1)
+    }
 }
 
 impl ReviewChunkPlan {
@@ -201,6 +217,7 @@ pub(super) fn should_bypass_chunking(
 pub(super) fn plan_review_chunks(
     project_root: &Path,
     scope: &str,
+    path_filters: &[String],
     diff_size: Option<&ReviewDiffSize>,
     config: &ReviewChunkingConfig,
 ) -> Result<Option<ReviewChunkPlan>> {
@@ -208,7 +225,7 @@ pub(super) fn plan_review_chunks(
         return Ok(None);
     };
 
-    let files = collect_review_chunk_files(project_root, scope)
+    let files = collect_review_chunk_files(project_root, scope, path_filters)
         .with_context(|| format!("failed to collect changed files for review scope {scope}"))?;
     if files.len() <= 1 {
         return Ok(None);
@@ -233,7 +250,11 @@ pub(super) async fn run_chunked_review(ctx: ChunkedReviewContext<'_>) -> Result<
         ctx.startup_env,
         ctx.project_root,
     )?;
-    let diff_fingerprint = super::execute::compute_diff_fingerprint(ctx.project_root, ctx.scope);
+    let diff_fingerprint = super::execute::compute_diff_fingerprint_for_paths(
+        ctx.project_root,
+        ctx.scope,
+        &ctx.args.paths,
+    );
     super::parent_artifacts::clear_multi_reviewer_artifact_dirs(
         ctx.plan.chunk_count().saturating_add(1),
         &parent_startup_env,
@@ -246,9 +267,23 @@ pub(super) async fn run_chunked_review(ctx: ChunkedReviewContext<'_>) -> Result<
         "Running chunked review"
     );
 
+    for chunk in ctx.plan.chunks.iter().filter(|chunk| chunk.over_budget) {
+        eprintln!(
+            "chunked review: chunk {} ({}) has {} changed lines, over the {}-line chunk budget; \
+             its reviewer may not see the whole chunk",
+            chunk.id,
+            chunk.group,
+            chunk.changed_lines,
+            ctx.chunking_config.max_changed_lines_per_chunk
+        );
+    }
+
     let chunk_tools = vec![ctx.tool; ctx.plan.chunk_count()];
     let mut join_set = JoinSet::new();
-    let semaphore = Arc::new(Semaphore::new(ctx.chunking_config.concurrency()));
+    let semaphore = Arc::new(Semaphore::new(
+        ctx.chunking_config
+            .concurrency_for_slots(ctx.global_config.max_concurrent(ctx.tool.as_str())),
+    ));
     for chunk in ctx.plan.chunks.clone() {
         let semaphore = Arc::clone(&semaphore);
         let reviewer_index = chunk.id.saturating_sub(1);
@@ -443,6 +478,7 @@ pub(super) async fn run_chunked_review(ctx: ChunkedReviewContext<'_>) -> Result<
             review_iterations,
             diff_fingerprint: diff_fingerprint.clone(),
             review_mode: Some(ctx.review_mode),
+            path_filters: &ctx.args.paths,
         },
         super::diff_size::ReviewDiffReport {
             diff_size: ctx.diff_size,
@@ -458,11 +494,13 @@ pub(super) async fn run_chunked_review(ctx: ChunkedReviewContext<'_>) -> Result<
         all_reviewers_unavailable,
         head_sha: &head_sha,
         scope: ctx.scope,
+        path_filters: &ctx.args.paths,
         run_review_mode: Some(ctx.review_mode),
         review_iterations,
         diff_fingerprint: diff_fingerprint.clone(),
         diff_size: ctx.diff_size,
         large_diff_warning: ctx.large_diff_warning,
+        finding_consensus: None,
     };
     if let Err(err) = super::parent_artifacts::write_multi_reviewer_consensus_artifacts(
         consensus_artifacts,
@@ -477,6 +515,11 @@ pub(super) async fn run_chunked_review(ctx: ChunkedReviewContext<'_>) -> Result<
     let fail_closed = outcomes
         .iter()
         .any(|outcome| !outcome.produced_usable_verdict());
+    if let Err(err) =
+        write_chunked_review_findings(ctx.project_root, &parent_startup_env, &ctx.plan, &outcomes)
+    {
+        warn!(error = %err, "Failed to write combined chunked-review findings");
+    }
     if let Err(err) = write_chunked_review_audit(
         ctx.project_root,
         &parent_startup_env,
@@ -489,18 +532,20 @@ pub(super) async fn run_chunked_review(ctx: ChunkedReviewContext<'_>) -> Result<
         warn!(error = %err, "Failed to write chunked-review audit artifact");
     }
 
-    print_reviewer_outcomes(&outcomes);
-    println!(
+    print_reviewer_outcomes(&outcomes, ctx.report)?;
+    writeln!(
+        ctx.report,
         "===== Chunked Review =====\nchunks: {}\nsynthesis: {}\nfinal_decision: {final_verdict}",
         ctx.plan.chunk_count(),
         chunks_all_usable
-    );
+    )?;
 
     let review_session_ids = outcomes
         .iter()
         .filter(|outcome| outcome.produced_usable_verdict())
         .map(|outcome| outcome.session_id.clone())
         .collect::<Vec<_>>();
+    ctx.report.record_sessions(&review_session_ids);
     super::bug_class_pipeline::maybe_extract_recurring_bug_class_skills(
         ctx.project_root,
         &review_session_ids,
@@ -517,6 +562,10 @@ use planning::*;
 mod synthesis;
 use synthesis::*;
 
+#[path = "review_cmd_chunking_provenance.rs"]
+mod provenance;
+use provenance::*;
+
 #[cfg(test)]
 #[path = "review_cmd_chunking_tests.rs"]
 mod tests;
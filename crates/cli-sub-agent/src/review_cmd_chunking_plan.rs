@@ -25,11 +25,12 @@ pub(super) fn activation_reason(
 pub(super) fn collect_review_chunk_files(
     project_root: &Path,
     scope: &str,
+    path_filters: &[String],
 ) -> Result<Vec<ReviewChunkFile>> {
-    let mut files = collect_numstat_files(project_root, scope)?;
-    apply_name_status(project_root, scope, &mut files)?;
+    let mut files = collect_numstat_files(project_root, scope, path_filters)?;
+    apply_name_status(project_root, scope, path_filters, &mut files)?;
     if scope == "uncommitted" {
-        append_untracked_files(project_root, &mut files)?;
+        append_untracked_files(project_root, path_filters, &mut files)?;
     }
     files.sort_by(|left, right| left.path.cmp(&right.path));
     files.dedup_by(|left, right| left.path == right.path);
@@ -39,17 +40,25 @@ pub(super) fn collect_review_chunk_files(
 pub(super) fn collect_numstat_files(
     project_root: &Path,
     scope: &str,
+    path_filters: &[String],
 ) -> Result<Vec<ReviewChunkFile>> {
-    let output = run_git(project_root, &git_diff_args(scope, "--numstat"))?;
+    let output = run_git(
+        project_root,
+        &git_diff_args(scope, "--numstat", path_filters),
+    )?;
     Ok(parse_numstat_output(&output))
 }
 
 pub(super) fn apply_name_status(
     project_root: &Path,
     scope: &str,
+    path_filters: &[String],
     files: &mut [ReviewChunkFile],
 ) -> Result<()> {
-    let output = run_git(project_root, &git_diff_args(scope, "--name-status"))?;
+    let output = run_git(
+        project_root,
+        &git_diff_args(scope, "--name-status", path_filters),
+    )?;
     let statuses = parse_name_status_output(&output);
     for file in files {
         if let Some(status) = statuses.get(&file.path) {
@@ -61,15 +70,15 @@ pub(super) fn apply_name_status(
 
 pub(super) fn append_untracked_files(
     project_root: &Path,
+    path_filters: &[String],
     files: &mut Vec<ReviewChunkFile>,
 ) -> Result<()> {
-    let output = run_git(
-        project_root,
-        &["ls-files", "--others", "--exclude-standard"]
-            .into_iter()
-            .map(String::from)
-            .collect::<Vec<_>>(),
-    )?;
+    let mut args = ["ls-files", "--others", "--exclude-standard"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    crate::review_cmd::resolve::push_path_filter_pathspecs(&mut args, path_filters);
+    let output = run_git(project_root, &args)?;
     let existing = files
         .iter()
         .map(|file| file.path.clone())
@@ -106,9 +115,10 @@ pub(super) fn run_git(project_root: &Path, args: &[String]) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
-pub(super) fn git_diff_args(scope: &str, mode_flag: &str) -> Vec<String> {
+pub(super) fn git_diff_args(scope: &str, mode_flag: &str, path_filters: &[String]) -> Vec<String> {
     let mut args = match scope {
         "uncommitted" => vec!["diff".to_string(), "HEAD".to_string()],
+        "staged" => vec!["diff".to_string(), "--cached".to_string()],
         _ if scope.starts_with("range:") => vec![
             "diff".to_string(),
             scope.trim_start_matches("range:").to_string(),
@@ -138,6 +148,7 @@ pub(super) fn git_diff_args(scope: &str, mode_flag: &str) -> Vec<String> {
     args.insert(insert_at, mode_flag.to_string());
     args.insert(insert_at + 1, "-M".to_string());
     args.insert(insert_at + 2, "--no-color".to_string());
+    crate::review_cmd::resolve::push_path_filter_pathspecs(&mut args, path_filters);
     args
 }
 
@@ -230,7 +241,7 @@ pub(super) fn plan_review_chunks_from_files(
     let chunks = chunks
         .into_iter()
         .enumerate()
-        .map(|(idx, files)| build_chunk(idx + 1, files))
+        .map(|(idx, files)| build_chunk(idx + 1, files, config.max_changed_lines_per_chunk))
         .collect::<Vec<_>>();
     let total_changed_lines = chunks.iter().map(|chunk| chunk.changed_lines).sum();
     let total_files = chunks.iter().map(|chunk| chunk.files.len()).sum();
@@ -304,7 +315,11 @@ pub(super) fn cap_chunk_count(
     chunks
 }
 
-pub(super) fn build_chunk(id: usize, files: Vec<ReviewChunkFile>) -> ReviewChunk {
+pub(super) fn build_chunk(
+    id: usize,
+    files: Vec<ReviewChunkFile>,
+    max_changed_lines: usize,
+) -> ReviewChunk {
     let changed_lines = changed_lines(&files);
     let pathspecs = files.iter().map(|file| file.path.clone()).collect();
     let group = summarize_chunk_group(&files);
@@ -315,6 +330,7 @@ pub(super) fn build_chunk(id: usize, files: Vec<ReviewChunkFile>) -> ReviewChunk
         files,
         pathspecs,
         changed_lines,
+        over_budget: changed_lines > max_changed_lines,
     }
 }
 
@@ -0,0 +1,142 @@
+//! Combined findings of a chunked review with per-chunk provenance.
+//!
+//! `output/chunked-review-findings.json` is a `ReviewArtifact` holding the
+//! consolidated findings of every chunk and the synthesis review, plus a
+//! `provenance` list, parallel to `findings`, naming the chunk reviews that
+//! reported each finding.
+
+use super::*;
+
+use csa_session::review_artifact::{Finding, SeveritySummary};
+
+use crate::review_consensus::{are_related_findings, consolidate_findings};
+
+pub(super) const CHUNKED_REVIEW_FINDINGS_FILE: &str = "chunked-review-findings.json";
+const SYNTHESIS_GROUP: &str = "synthesis";
+
+/// Where one consolidated finding came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct ChunkFindingProvenance {
+    pub(super) fid: String,
+    pub(super) sources: Vec<ChunkFindingSource>,
+}
+
+/// A chunk review (or the synthesis review) that reported the finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct ChunkFindingSource {
+    /// `None` for the synthesis review.
+    pub(super) chunk_id: Option<usize>,
+    pub(super) group: String,
+    pub(super) session_id: String,
+}
+
+#[derive(Serialize)]
+struct ChunkedReviewFindings<'a> {
+    #[serde(flatten)]
+    artifact: &'a ReviewArtifact,
+    provenance: &'a [ChunkFindingProvenance],
+}
+
+/// Consolidate the artifacts of the chunk and synthesis reviews and attribute
+/// every consolidated finding to the reviews that reported it.
+///
+/// A `fid` is only meaningful within the review that assigned it, so findings
+/// are deduplicated by `fid` per review and merged across reviews only when
+/// they are related (same rule and file, nearby lines). Each merged finding
+/// keeps the indices of the reviews it came from; `provenance[i]` describes
+/// `findings[i]`.
+pub(super) fn combine_chunk_artifacts(
+    plan: &ReviewChunkPlan,
+    reviews: Vec<(&ReviewerOutcome, ReviewArtifact)>,
+    session_id: &str,
+) -> (ReviewArtifact, Vec<ChunkFindingProvenance>) {
+    let mut merged: Vec<(Finding, BTreeSet<usize>)> = Vec::new();
+    for (review_index, (_, artifact)) in reviews.iter().enumerate() {
+        for finding in consolidate_findings(artifact.findings.clone()) {
+            match merged
+                .iter_mut()
+                .find(|(existing, _)| are_related_findings(existing, &finding))
+            {
+                Some((existing, sources)) => {
+                    if finding.severity > existing.severity {
+                        *existing = finding;
+                    }
+                    sources.insert(review_index);
+                }
+                None => merged.push((finding, BTreeSet::from([review_index]))),
+            }
+        }
+    }
+    merged.sort_by(|(left, _), (right, _)| {
+        right
+            .severity
+            .cmp(&left.severity)
+            .then_with(|| left.fid.cmp(&right.fid))
+    });
+
+    let source = |review_index: usize| {
+        let outcome = reviews[review_index].0;
+        let chunk = plan
+            .chunks
+            .iter()
+            .find(|chunk| chunk.id == outcome.reviewer_index + 1);
+        ChunkFindingSource {
+            chunk_id: chunk.map(|chunk| chunk.id),
+            group: chunk.map_or_else(|| SYNTHESIS_GROUP.to_string(), |chunk| chunk.group.clone()),
+            session_id: outcome.session_id.clone(),
+        }
+    };
+    let provenance = merged
+        .iter()
+        .map(|(finding, sources)| ChunkFindingProvenance {
+            fid: finding.fid.clone(),
+            sources: sources.iter().map(|&index| source(index)).collect(),
+        })
+        .collect();
+    let findings: Vec<Finding> = merged.into_iter().map(|(finding, _)| finding).collect();
+    let artifact = ReviewArtifact {
+        severity_summary: SeveritySummary::from_findings(&findings),
+        findings,
+        review_mode: reviews
+            .iter()
+            .find_map(|(_, artifact)| artifact.review_mode.clone()),
+        schema_version: "1.0".to_string(),
+        session_id: session_id.to_string(),
+        timestamp: chrono::Utc::now(),
+    };
+    (artifact, provenance)
+}
+
+/// Write `output/chunked-review-findings.json` next to the chunked-review audit.
+/// Reviews without a readable findings artifact contribute nothing.
+pub(super) fn write_chunked_review_findings(
+    project_root: &Path,
+    startup_env: &StartupSubtreeEnv,
+    plan: &ReviewChunkPlan,
+    outcomes: &[ReviewerOutcome],
+) -> Result<()> {
+    let parent_session_dir = startup_env.session_dir().map(Path::new);
+    let reviews = outcomes
+        .iter()
+        .filter_map(|outcome| {
+            load_chunk_artifact(project_root, parent_session_dir, outcome)
+                .ok()
+                .map(|artifact| (outcome, artifact))
+        })
+        .collect();
+    let (artifact, provenance) = combine_chunk_artifacts(
+        plan,
+        reviews,
+        startup_env.session_id().unwrap_or("chunked-review"),
+    );
+    let output_dir = audit_session_dir(project_root, startup_env, outcomes).join("output");
+    fs::create_dir_all(&output_dir)
+        .with_context(|| format!("failed to create {}", output_dir.display()))?;
+    let payload = serde_json::to_vec_pretty(&ChunkedReviewFindings {
+        artifact: &artifact,
+        provenance: &provenance,
+    })
+    .context("failed to serialize chunked-review findings")?;
+    let path = output_dir.join(CHUNKED_REVIEW_FINDINGS_FILE);
+    fs::write(&path, payload).with_context(|| format!("failed to write {}", path.display()))
+}
@@ -114,6 +114,12 @@ Report only findings with in-chunk file/line evidence. Do not report cross-chunk
         group = chunk.group,
         changed_lines = chunk.changed_lines,
     );
+    if chunk.over_budget {
+        prompt.push_str(
+            "\n\nThis chunk is over the per-chunk size budget. Read its diff one pathspec at a time \
+             and review every file; do not skip the parts of the diff that do not fit at once.",
+        );
+    }
     crate::review_design_anchor::append_design_anchor(&mut prompt);
     prompt
 }
@@ -327,6 +333,7 @@ pub(super) fn write_chunked_review_audit(
                     pathspecs: chunk.pathspecs.clone(),
                     changed_lines: chunk.changed_lines,
                     estimated_tokens: chunk.estimated_tokens,
+                    over_budget: chunk.over_budget,
                     session_id: outcome.session_id.clone(),
                     verdict: outcome.verdict.to_string(),
                 })
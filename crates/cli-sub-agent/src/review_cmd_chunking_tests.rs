@@ -1,4 +1,5 @@
 use csa_session::ReviewDiffSize;
+use csa_session::review_artifact::{Finding, Severity};
 
 use super::*;
 
@@ -87,6 +88,29 @@ fn planner_caps_total_chunks_without_dropping_files() {
 
     assert_eq!(plan.chunk_count(), 12);
     assert_eq!(plan.total_files, 20);
+    // Every chunk is over the 700-line budget, whether merged or a single file.
+    assert!(plan.chunks.iter().all(|chunk| chunk.over_budget));
+}
+
+#[test]
+fn chunks_within_the_line_budget_are_not_over_budget() {
+    let config = ReviewChunkingConfig {
+        mode: ReviewChunkingMode::Always,
+        ..ReviewChunkingConfig::default()
+    };
+    let plan = plan_review_chunks_from_files(
+        "range:main...HEAD",
+        Some(&large_diff_size(2, 1_000)),
+        vec![
+            file("crates/alpha/src/lib.rs", 300),
+            file("crates/beta/src/lib.rs", 700),
+        ],
+        ReviewChunkActivationReason::Always,
+        &config,
+    );
+
+    assert_eq!(plan.chunk_count(), 2);
+    assert!(plan.chunks.iter().all(|chunk| !chunk.over_budget));
 }
 
 #[test]
@@ -148,6 +172,9 @@ fn config_helpers_and_bypass_predicate_match_cli_semantics() {
 
     assert_eq!(config.mode, ReviewChunkingMode::Always);
     assert_eq!(config.concurrency(), 3);
+    assert_eq!(config.concurrency_for_slots(2), 2);
+    assert_eq!(config.concurrency_for_slots(8), 3);
+    assert_eq!(config.concurrency_for_slots(0), 1);
     assert!(should_bypass_chunking(
         ReviewChunkingMode::Off,
         false,
@@ -358,6 +385,7 @@ fn plan_review_chunks_collects_uncommitted_git_scope() {
     let plan = plan_review_chunks(
         temp.path(),
         "uncommitted",
+        &[],
         Some(&large_diff_size(2, 2)),
         &config,
     )
@@ -387,3 +415,166 @@ fn run_git_test(project_root: &std::path::Path, args: &[&str]) {
         String::from_utf8_lossy(&output.stderr)
     );
 }
+
+fn two_crate_plan() -> ReviewChunkPlan {
+    let config = ReviewChunkingConfig {
+        mode: ReviewChunkingMode::Always,
+        ..ReviewChunkingConfig::default()
+    };
+    plan_review_chunks_from_files(
+        "range:main...HEAD",
+        Some(&large_diff_size(2, 800)),
+        vec![
+            file("crates/alpha/src/lib.rs", 400),
+            file("crates/beta/src/lib.rs", 400),
+        ],
+        ReviewChunkActivationReason::Always,
+        &config,
+    )
+}
+
+fn chunk_outcome(reviewer_index: usize) -> ReviewerOutcome {
+    ReviewerOutcome {
+        reviewer_index,
+        tool: ToolName::Codex,
+        session_id: format!("01REVIEW{reviewer_index}"),
+        output: "FAIL\n".to_string(),
+        exit_code: 1,
+        verdict: HAS_ISSUES,
+        diagnostic: None,
+    }
+}
+
+fn chunk_finding(fid: &str, file: &str, severity: Severity) -> Finding {
+    Finding {
+        severity,
+        fid: fid.to_string(),
+        file: file.to_string(),
+        line: Some(10),
+        rule_id: fid.to_string(),
+        summary: format!("{fid} summary"),
+        engine: "reviewer".to_string(),
+    }
+}
+
+fn chunk_artifact(session_id: &str, findings: Vec<Finding>) -> ReviewArtifact {
+    ReviewArtifact {
+        severity_summary: csa_session::review_artifact::SeveritySummary::from_findings(&findings),
+        findings,
+        review_mode: None,
+        schema_version: "1.0".to_string(),
+        session_id: session_id.to_string(),
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+#[test]
+fn combined_chunk_artifact_records_which_chunks_reported_each_finding() {
+    let plan = two_crate_plan();
+    let outcomes = [chunk_outcome(0), chunk_outcome(1), chunk_outcome(2)];
+    let reviews = vec![
+        (
+            &outcomes[0],
+            chunk_artifact(
+                "01REVIEW0",
+                vec![chunk_finding(
+                    "alpha-bug",
+                    "crates/alpha/src/lib.rs",
+                    Severity::High,
+                )],
+            ),
+        ),
+        (
+            &outcomes[1],
+            chunk_artifact(
+                "01REVIEW1",
+                vec![chunk_finding(
+                    "beta-bug",
+                    "crates/beta/src/lib.rs",
+                    Severity::Low,
+                )],
+            ),
+        ),
+        (
+            &outcomes[2],
+            chunk_artifact(
+                "01REVIEW2",
+                vec![chunk_finding(
+                    "alpha-bug",
+                    "crates/alpha/src/lib.rs",
+                    Severity::Medium,
+                )],
+            ),
+        ),
+    ];
+
+    let (combined, provenance) = combine_chunk_artifacts(&plan, reviews, "01PARENT");
+
+    assert_eq!(combined.session_id, "01PARENT");
+    assert_eq!(combined.findings.len(), 2);
+    let alpha = provenance
+        .iter()
+        .find(|entry| entry.fid == "alpha-bug")
+        .expect("alpha provenance");
+    assert_eq!(
+        alpha.sources,
+        vec![
+            ChunkFindingSource {
+                chunk_id: Some(1),
+                group: "crates/alpha".to_string(),
+                session_id: "01REVIEW0".to_string(),
+            },
+            ChunkFindingSource {
+                chunk_id: None,
+                group: "synthesis".to_string(),
+                session_id: "01REVIEW2".to_string(),
+            },
+        ]
+    );
+    let beta = provenance
+        .iter()
+        .find(|entry| entry.fid == "beta-bug")
+        .expect("beta provenance");
+    assert_eq!(beta.sources.len(), 1);
+    assert_eq!(beta.sources[0].chunk_id, Some(2));
+}
+
+#[test]
+fn combined_chunk_artifact_keeps_colliding_fids_of_different_chunks_apart() {
+    let plan = two_crate_plan();
+    let outcomes = [chunk_outcome(0), chunk_outcome(1)];
+    let reviews = vec![
+        (
+            &outcomes[0],
+            chunk_artifact(
+                "01REVIEW0",
+                vec![chunk_finding(
+                    "F1",
+                    "crates/alpha/src/lib.rs",
+                    Severity::High,
+                )],
+            ),
+        ),
+        (
+            &outcomes[1],
+            chunk_artifact(
+                "01REVIEW1",
+                vec![chunk_finding("F1", "crates/beta/src/lib.rs", Severity::Low)],
+            ),
+        ),
+    ];
+
+    let (combined, provenance) = combine_chunk_artifacts(&plan, reviews, "01PARENT");
+
+    assert_eq!(combined.findings.len(), 2);
+    assert_eq!(provenance.len(), 2);
+    for (finding, entry) in combined.findings.iter().zip(&provenance) {
+        assert_eq!(entry.sources.len(), 1);
+        let expected_chunk = if finding.file.starts_with("crates/alpha") {
+            1
+        } else {
+            2
+        };
+        assert_eq!(entry.sources[0].chunk_id, Some(expected_chunk));
+    }
+}
@@ -0,0 +1,241 @@
+//! `csa review --ci`: GitHub Actions defaults and reporting.
+//!
+//! Without an explicit scope the review covers the pull request (or pushed)
+//! range named by the workflow environment. Once the run's findings are known,
+//! each new finding becomes a workflow annotation and a markdown table is
+//! appended to the job summary. The exit code comes from `--fail-on`.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csa_core::types::ReviewFailOn;
+use csa_session::{Finding, Severity, SeveritySummary, normalize_path};
+use tracing::warn;
+
+use super::run_findings::ReviewFindings;
+use super::sarif::severity_label;
+use crate::cli::ReviewArgs;
+
+const ZERO_SHA: &str = "0000000000000000000000000000000000000000";
+
+/// What the CI report needs from the review arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CiReport {
+    pub(super) scope: String,
+}
+
+/// Fill in the CI scope and `--fail-on` default when `--ci` is set.
+pub(super) fn apply_ci_defaults(args: &mut ReviewArgs) -> Option<CiReport> {
+    if !args.ci {
+        return None;
+    }
+    args.no_daemon = true;
+    args.fail_on.get_or_insert(ReviewFailOn::High);
+    if !has_explicit_scope(args) {
+        match ci_range(|key| std::env::var(key).ok()) {
+            Some(range) => args.range = Some(range),
+            None => warn!(
+                "--ci: no pull request or push range in the environment; using the default scope"
+            ),
+        }
+    }
+    Some(CiReport {
+        scope: scope_label(args),
+    })
+}
+
+fn has_explicit_scope(args: &ReviewArgs) -> bool {
+    args.diff
+        || args.staged
+        || args.branch.is_some()
+        || args.commit.is_some()
+        || args.range.is_some()
+        || args.files.is_some()
+}
+
+/// Review range from the GitHub Actions environment: the pull request base
+/// from the event payload or `GITHUB_BASE_REF`, else the pushed range.
+pub(super) fn ci_range(env: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let event = env("GITHUB_EVENT_PATH")
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
+    let event_str = |pointer: &str| {
+        event
+            .as_ref()
+            .and_then(|event| event.pointer(pointer))
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    if let Some(base) = event_str("/pull_request/base/sha") {
+        return Some(format!("{base}...HEAD"));
+    }
+    if let Some(base_ref) = env("GITHUB_BASE_REF").filter(|value| !value.is_empty()) {
+        return Some(format!("origin/{base_ref}...HEAD"));
+    }
+    event_str("/before")
+        .filter(|before| before != ZERO_SHA)
+        .map(|before| format!("{before}..HEAD"))
+}
+
+fn scope_label(args: &ReviewArgs) -> String {
+    if let Some(range) = &args.range {
+        range.clone()
+    } else if let Some(commit) = &args.commit {
+        format!("commit {commit}")
+    } else if let Some(files) = &args.files {
+        format!("files {files}")
+    } else if args.staged {
+        "staged changes".to_string()
+    } else if args.diff {
+        "uncommitted changes".to_string()
+    } else {
+        format!(
+            "branch diff against {}",
+            args.branch.as_deref().unwrap_or("the base branch")
+        )
+    }
+}
+
+/// Write the annotations to the review report and append the job summary for
+/// the run.
+///
+/// With `--format sarif` the report is stderr, where the runner also picks up
+/// workflow commands.
+pub(super) fn report(
+    ci: &CiReport,
+    findings: &ReviewFindings,
+    fail_on: ReviewFailOn,
+    exit_code: i32,
+    output: &mut impl std::io::Write,
+) -> Result<()> {
+    for finding in &findings.new {
+        writeln!(output, "{}", annotation(finding))?;
+    }
+    if let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY").filter(|path| !path.is_empty()) {
+        append_summary(
+            Path::new(&path),
+            &job_summary(ci, findings, fail_on, exit_code),
+        )?;
+    }
+    Ok(())
+}
+
+fn append_summary(path: &Path, summary: &str) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open job summary {}", path.display()))?;
+    file.write_all(summary.as_bytes())
+        .with_context(|| format!("failed to write job summary {}", path.display()))
+}
+
+/// `::error file=...,line=...,title=...::message` for one finding.
+pub(super) fn annotation(finding: &Finding) -> String {
+    let level = match finding.severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "notice",
+    };
+    let mut properties = Vec::new();
+    if let Some(file) = finding_file(finding) {
+        properties.push(format!("file={}", escape_property(&file)));
+        if let Some(line) = finding.line.filter(|line| *line > 0) {
+            properties.push(format!("line={line}"));
+        }
+    }
+    let title = if finding.rule_id.trim().is_empty() {
+        format!("csa review ({})", severity_label(&finding.severity))
+    } else {
+        format!(
+            "csa review ({}): {}",
+            severity_label(&finding.severity),
+            finding.rule_id
+        )
+    };
+    properties.push(format!("title={}", escape_property(&title)));
+    format!(
+        "::{level} {}::{}",
+        properties.join(","),
+        escape_data(&finding.summary)
+    )
+}
+
+/// Markdown block appended to `$GITHUB_STEP_SUMMARY`.
+pub(super) fn job_summary(
+    ci: &CiReport,
+    findings: &ReviewFindings,
+    fail_on: ReviewFailOn,
+    exit_code: i32,
+) -> String {
+    let summary = SeveritySummary::from_findings(&findings.new);
+    let result = match exit_code {
+        0 => "passed".to_string(),
+        1 => format!("blocking findings at `fail_on={fail_on}`"),
+        code => format!("review did not complete (exit {code})"),
+    };
+    let mut out = String::from("### csa review\n\n");
+    let _ = writeln!(out, "- **Result:** {result}");
+    let _ = writeln!(out, "- **Scope:** `{}`", ci.scope);
+    let _ = writeln!(
+        out,
+        "- **Findings:** critical {}, high {}, medium {}, low {}",
+        summary.critical, summary.high, summary.medium, summary.low
+    );
+    if !findings.new.is_empty() {
+        out.push_str("\n| Severity | Location | Finding |\n| --- | --- | --- |\n");
+        for finding in &findings.new {
+            let location = match (finding_file(finding), finding.line) {
+                (Some(file), Some(line)) if line > 0 => format!("`{file}:{line}`"),
+                (Some(file), _) => format!("`{file}`"),
+                (None, _) => "-".to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                severity_label(&finding.severity),
+                location,
+                escape_cell(&finding.summary)
+            );
+        }
+    }
+    if !findings.known.is_empty() {
+        let _ = writeln!(
+            out,
+            "\n{} finding(s) recorded in the baseline are not listed.",
+            findings.known.len()
+        );
+    }
+    out.push('\n');
+    out
+}
+
+fn finding_file(finding: &Finding) -> Option<String> {
+    let file = finding.file.trim();
+    (!file.is_empty() && file != "<unknown>").then(|| normalize_path(file))
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn escape_cell(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", " ")
+        .replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+#[path = "review_cmd_ci_tests.rs"]
+mod tests;
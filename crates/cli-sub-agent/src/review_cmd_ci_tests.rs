@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use super::*;
+
+fn finding(severity: Severity, file: &str, line: Option<u32>, summary: &str) -> Finding {
+    Finding {
+        summary: summary.to_string(),
+        ..crate::test_review_findings::finding(severity, "FID", file, line, "rust.no-unwrap")
+    }
+}
+
+fn env_of(vars: HashMap<&'static str, String>) -> impl Fn(&str) -> Option<String> {
+    move |key| vars.get(key).cloned()
+}
+
+#[test]
+fn ci_range_prefers_pull_request_base_sha() {
+    let dir = tempfile::tempdir().unwrap();
+    let event = dir.path().join("event.json");
+    std::fs::write(
+        &event,
+        r#"{"pull_request":{"base":{"sha":"abc123"},"head":{"sha":"def456"}}}"#,
+    )
+    .unwrap();
+    let env = env_of(HashMap::from([
+        ("GITHUB_EVENT_PATH", event.display().to_string()),
+        ("GITHUB_BASE_REF", "main".to_string()),
+    ]));
+    assert_eq!(ci_range(env).as_deref(), Some("abc123...HEAD"));
+}
+
+#[test]
+fn ci_range_falls_back_to_base_ref_then_push_range() {
+    let env = env_of(HashMap::from([("GITHUB_BASE_REF", "dev".to_string())]));
+    assert_eq!(ci_range(env).as_deref(), Some("origin/dev...HEAD"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let event = dir.path().join("event.json");
+    std::fs::write(&event, r#"{"before":"1111111","after":"2222222"}"#).unwrap();
+    let env = env_of(HashMap::from([(
+        "GITHUB_EVENT_PATH",
+        event.display().to_string(),
+    )]));
+    assert_eq!(ci_range(env).as_deref(), Some("1111111..HEAD"));
+
+    std::fs::write(&event, format!(r#"{{"before":"{ZERO_SHA}"}}"#)).unwrap();
+    let env = env_of(HashMap::from([(
+        "GITHUB_EVENT_PATH",
+        event.display().to_string(),
+    )]));
+    assert_eq!(ci_range(env), None);
+    assert_eq!(ci_range(|_| None), None);
+}
+
+#[test]
+fn annotation_maps_severity_and_escapes_values() {
+    assert_eq!(
+        annotation(&finding(
+            Severity::High,
+            "./src/lib.rs",
+            Some(12),
+            "50% wrong,\nsee: docs"
+        )),
+        "::error file=src/lib.rs,line=12,title=csa review (high)%3A rust.no-unwrap::50%25 wrong,%0Asee: docs"
+    );
+    let medium = annotation(&finding(Severity::Medium, "", Some(3), "m"));
+    assert!(medium.starts_with("::warning title="), "{medium}");
+    assert!(!medium.contains("line="), "{medium}");
+    assert!(
+        annotation(&finding(Severity::Low, "a.rs", None, "l"))
+            .starts_with("::notice file=a.rs,title=")
+    );
+}
+
+#[test]
+fn job_summary_lists_new_findings_and_result() {
+    let findings = ReviewFindings {
+        new: vec![finding(Severity::High, "src/a.rs", Some(7), "bad | pipe")],
+        known: vec![finding(Severity::Low, "src/b.rs", None, "old")],
+        baseline_applied: true,
+    };
+    let ci = CiReport {
+        scope: "abc...HEAD".to_string(),
+    };
+    let summary = job_summary(&ci, &findings, ReviewFailOn::High, 1);
+    assert!(summary.contains("**Result:** blocking findings at `fail_on=high`"));
+    assert!(summary.contains("**Scope:** `abc...HEAD`"));
+    assert!(summary.contains("critical 0, high 1, medium 0, low 0"));
+    assert!(summary.contains("| high | `src/a.rs:7` | bad \\| pipe |"));
+    assert!(summary.contains("1 finding(s) recorded in the baseline"));
+
+    let clean = job_summary(&ci, &ReviewFindings::default(), ReviewFailOn::High, 0);
+    assert!(clean.contains("**Result:** passed"));
+    assert!(!clean.contains("| Severity |"));
+}
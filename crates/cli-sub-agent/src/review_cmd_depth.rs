@@ -1,4 +1,5 @@
 use std::collections::BTreeSet;
+use std::ffi::OsStr;
 use std::path::Path;
 use std::process::Stdio;
 use std::time::Duration;
@@ -7,6 +8,8 @@ use tokio::process::Command;
 
 use crate::cli::{ReviewArgs, ReviewDepth, ReviewMode};
 
+use super::resolve::push_path_filter_pathspecs;
+
 const GIT_COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
 const MAX_RISK_DIFF_BYTES: usize = 200_000;
 pub(super) const REVIEW_HISTORY_MAX_CHARS: usize = 2_000;
@@ -89,7 +92,7 @@ pub(super) async fn resolve_review_depth_for_project(
         };
     }
 
-    let risk_signals = collect_review_diff_text(project_root, scope)
+    let risk_signals = collect_review_diff_text(project_root, scope, &args.paths)
         .await
         .map(|diff| risky_signals_from_diff(&diff))
         .unwrap_or_default();
@@ -109,54 +112,59 @@ fn assessment_from_risk_signals(risk_signals: Vec<RiskyDiffSignal>) -> ReviewDep
     }
 }
 
-async fn collect_review_diff_text(project_root: &Path, scope: &str) -> Option<String> {
-    if scope == "uncommitted" {
-        return run_git_stdout(
-            project_root,
-            &["diff", "HEAD", "--no-color", "--unified=0"],
-            MAX_RISK_DIFF_BYTES,
-        )
-        .await;
-    }
-
-    if let Some(range) = scope.strip_prefix("range:") {
-        return run_git_stdout(
-            project_root,
-            &["diff", "--no-color", "--unified=0", range],
-            MAX_RISK_DIFF_BYTES,
-        )
-        .await;
-    }
-
-    if let Some(base) = scope.strip_prefix("base:") {
-        let range = format!("{base}...HEAD");
-        return run_git_stdout(
-            project_root,
-            &["diff", "--no-color", "--unified=0", &range],
-            MAX_RISK_DIFF_BYTES,
-        )
-        .await;
-    }
-
-    if let Some(commit) = scope.strip_prefix("commit:") {
-        return run_git_stdout(
-            project_root,
-            &["show", "--no-color", "--unified=0", commit],
-            MAX_RISK_DIFF_BYTES,
-        )
-        .await;
-    }
-
-    if let Some(pathspec) = scope.strip_prefix("files:") {
-        return run_git_stdout(
-            project_root,
-            &["diff", "--no-color", "--unified=0", "--", pathspec],
-            MAX_RISK_DIFF_BYTES,
-        )
-        .await;
-    }
-
-    None
+async fn collect_review_diff_text(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+) -> Option<String> {
+    let mut args: Vec<String> = if scope == "uncommitted" {
+        vec![
+            "diff".into(),
+            "HEAD".into(),
+            "--no-color".into(),
+            "--unified=0".into(),
+        ]
+    } else if scope == "staged" {
+        vec![
+            "diff".into(),
+            "--cached".into(),
+            "--no-color".into(),
+            "--unified=0".into(),
+        ]
+    } else if let Some(range) = scope.strip_prefix("range:") {
+        vec![
+            "diff".into(),
+            "--no-color".into(),
+            "--unified=0".into(),
+            range.into(),
+        ]
+    } else if let Some(base) = scope.strip_prefix("base:") {
+        vec![
+            "diff".into(),
+            "--no-color".into(),
+            "--unified=0".into(),
+            format!("{base}...HEAD"),
+        ]
+    } else if let Some(commit) = scope.strip_prefix("commit:") {
+        vec![
+            "show".into(),
+            "--no-color".into(),
+            "--unified=0".into(),
+            commit.into(),
+        ]
+    } else if let Some(pathspec) = scope.strip_prefix("files:") {
+        vec![
+            "diff".into(),
+            "--no-color".into(),
+            "--unified=0".into(),
+            "--".into(),
+            pathspec.into(),
+        ]
+    } else {
+        return None;
+    };
+    push_path_filter_pathspecs(&mut args, path_filters);
+    run_git_stdout(project_root, &args, MAX_RISK_DIFF_BYTES).await
 }
 
 pub(super) fn risky_signals_from_diff(diff: &str) -> Vec<RiskyDiffSignal> {
@@ -216,8 +224,9 @@ fn line_has_wildcard_true_arm(line: &str) -> bool {
 pub(super) async fn collect_bounded_regression_context(
     project_root: &Path,
     scope: &str,
+    path_filters: &[String],
 ) -> Option<String> {
-    let changed_files = collect_changed_files(project_root, scope).await;
+    let changed_files = collect_changed_files(project_root, scope, path_filters).await;
     if changed_files.is_empty() {
         return None;
     }
@@ -251,55 +260,55 @@ pub(super) async fn collect_bounded_regression_context(
     format_bounded_review_history(&entries, REVIEW_HISTORY_MAX_CHARS)
 }
 
-async fn collect_changed_files(project_root: &Path, scope: &str) -> Vec<String> {
-    let mut files = BTreeSet::new();
-    if scope == "uncommitted" {
-        insert_name_only_output(
-            &mut files,
-            run_git_stdout(project_root, &["diff", "--name-only", "HEAD"], 50_000).await,
-        );
-        insert_name_only_output(
-            &mut files,
-            run_git_stdout(
-                project_root,
-                &["ls-files", "--others", "--exclude-standard"],
-                50_000,
-            )
-            .await,
-        );
+async fn collect_changed_files(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+) -> Vec<String> {
+    let mut commands: Vec<Vec<String>> = if scope == "uncommitted" {
+        vec![
+            vec!["diff".into(), "--name-only".into(), "HEAD".into()],
+            vec![
+                "ls-files".into(),
+                "--others".into(),
+                "--exclude-standard".into(),
+            ],
+        ]
+    } else if scope == "staged" {
+        vec![vec!["diff".into(), "--name-only".into(), "--cached".into()]]
     } else if let Some(range) = scope.strip_prefix("range:") {
-        insert_name_only_output(
-            &mut files,
-            run_git_stdout(project_root, &["diff", "--name-only", range], 50_000).await,
-        );
+        vec![vec!["diff".into(), "--name-only".into(), range.into()]]
     } else if let Some(base) = scope.strip_prefix("base:") {
-        let range = format!("{base}...HEAD");
-        insert_name_only_output(
-            &mut files,
-            run_git_stdout(project_root, &["diff", "--name-only", &range], 50_000).await,
-        );
+        vec![vec![
+            "diff".into(),
+            "--name-only".into(),
+            format!("{base}...HEAD"),
+        ]]
     } else if let Some(commit) = scope.strip_prefix("commit:") {
-        insert_name_only_output(
-            &mut files,
-            run_git_stdout(
-                project_root,
-                &["diff-tree", "--no-commit-id", "--name-only", "-r", commit],
-                50_000,
-            )
-            .await,
-        );
+        vec![vec![
+            "diff-tree".into(),
+            "--no-commit-id".into(),
+            "--name-only".into(),
+            "-r".into(),
+            commit.into(),
+        ]]
     } else if let Some(pathspec) = scope.strip_prefix("files:") {
-        insert_name_only_output(
-            &mut files,
-            run_git_stdout(
-                project_root,
-                &["diff", "--name-only", "--", pathspec],
-                50_000,
-            )
-            .await,
-        );
-    }
+        vec![vec![
+            "diff".into(),
+            "--name-only".into(),
+            "--".into(),
+            pathspec.into(),
+        ]]
+    } else {
+        Vec::new()
+    };
 
+    let mut files = BTreeSet::new();
+    for args in &mut commands {
+        push_path_filter_pathspecs(args, path_filters);
+        let output = run_git_stdout(project_root, args.as_slice(), 50_000).await;
+        insert_name_only_output(&mut files, output);
+    }
     files.into_iter().collect()
 }
 
@@ -349,7 +358,11 @@ pub(super) fn format_bounded_review_history(
     (!rendered.is_empty()).then_some(rendered)
 }
 
-async fn run_git_stdout(project_root: &Path, args: &[&str], max_bytes: usize) -> Option<String> {
+async fn run_git_stdout(
+    project_root: &Path,
+    args: &[impl AsRef<OsStr>],
+    max_bytes: usize,
+) -> Option<String> {
     let mut command = Command::new("git");
     command
         .args(args)
@@ -458,7 +471,7 @@ mod tests {
         )
         .expect("modify file");
 
-        let context = collect_bounded_regression_context(temp.path(), "uncommitted")
+        let context = collect_bounded_regression_context(temp.path(), "uncommitted", &[])
             .await
             .expect("regression context");
 
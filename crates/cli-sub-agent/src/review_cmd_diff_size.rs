@@ -1,4 +1,5 @@
 use std::collections::BTreeSet;
+use std::ffi::OsStr;
 use std::path::{Component, Path};
 use std::process::Command;
 
@@ -7,6 +8,8 @@ use csa_session::state::{ReviewSessionMeta, write_review_meta};
 use csa_session::{ReviewDiffSize, ReviewVerdictArtifact, write_review_verdict};
 use tracing::{debug, warn};
 
+use super::resolve::push_path_filter_pathspecs;
+
 // #1841 cross-dimension breadth-first blocking enumeration lives in its own file
 // so this #1645 diff-size module stays within the per-module token budget.
 #[path = "review_cmd_enumeration_mode.rs"]
@@ -37,12 +40,16 @@ impl LargeDiffWarning {
     }
 }
 
-pub(super) fn compute_review_diff_size(project_root: &Path, scope: &str) -> Option<ReviewDiffSize> {
-    if scope == "uncommitted" {
+pub(super) fn compute_review_diff_size(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+) -> Option<ReviewDiffSize> {
+    if scope == "uncommitted" && path_filters.is_empty() {
         return compute_uncommitted_review_diff_size(project_root);
     }
 
-    let diff = collect_review_diff_payload(project_root, scope)?;
+    let diff = collect_review_diff_payload(project_root, scope, path_filters)?;
     Some(diff_size_from_payload(&diff))
 }
 
@@ -318,35 +325,45 @@ fn insert_large_diff_warning_fields(
     }
 }
 
-fn collect_review_diff_payload(project_root: &Path, scope: &str) -> Option<Vec<u8>> {
+fn collect_review_diff_payload(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+) -> Option<Vec<u8>> {
     if scope == "uncommitted" {
-        return collect_uncommitted_diff_payload(project_root);
-    }
-
-    if let Some(range) = scope.strip_prefix("range:") {
-        return run_git(project_root, &["diff", "--no-color", range]);
+        return collect_uncommitted_diff_payload(project_root, path_filters);
     }
 
-    if let Some(base) = scope.strip_prefix("base:") {
+    let mut args: Vec<String> = if scope == "staged" {
+        vec!["diff".into(), "--cached".into(), "--no-color".into()]
+    } else if let Some(range) = scope.strip_prefix("range:") {
+        vec!["diff".into(), "--no-color".into(), range.into()]
+    } else if let Some(base) = scope.strip_prefix("base:") {
         let merge_base = run_git(project_root, &["merge-base", "HEAD", base])?;
         let merge_base = String::from_utf8(merge_base).ok()?;
         let merge_base = merge_base.trim();
         if merge_base.is_empty() {
             return None;
         }
-        let diff_range = format!("{merge_base}...HEAD");
-        return run_git(project_root, &["diff", "--no-color", &diff_range]);
-    }
-
-    if let Some(commit) = scope.strip_prefix("commit:") {
-        return run_git(project_root, &["show", "--no-color", commit]);
-    }
-
-    if let Some(pathspec) = scope.strip_prefix("files:") {
-        return run_git(project_root, &["diff", "--no-color", "--", pathspec]);
-    }
-
-    None
+        vec![
+            "diff".into(),
+            "--no-color".into(),
+            format!("{merge_base}...HEAD"),
+        ]
+    } else if let Some(commit) = scope.strip_prefix("commit:") {
+        vec!["show".into(), "--no-color".into(), commit.into()]
+    } else if let Some(pathspec) = scope.strip_prefix("files:") {
+        vec![
+            "diff".into(),
+            "--no-color".into(),
+            "--".into(),
+            pathspec.into(),
+        ]
+    } else {
+        return None;
+    };
+    push_path_filter_pathspecs(&mut args, path_filters);
+    run_git(project_root, &args)
 }
 
 fn compute_uncommitted_review_diff_size(project_root: &Path) -> Option<ReviewDiffSize> {
@@ -381,16 +398,64 @@ fn merge_untracked_diff_size(
     size.notes.extend(untracked.notes);
 }
 
-fn collect_uncommitted_diff_payload(project_root: &Path) -> Option<Vec<u8>> {
-    run_git(project_root, &["diff", "HEAD", "--no-color"])
+/// Unified diff of `scope` limited to `path_filters`, as the reviewer collects it.
+pub(super) fn review_diff_text(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+) -> Option<String> {
+    let diff = collect_review_diff_payload(project_root, scope, path_filters)?;
+    Some(String::from_utf8_lossy(&diff).into_owned())
 }
 
-fn run_git(project_root: &Path, args: &[&str]) -> Option<Vec<u8>> {
-    let output = Command::new("git")
-        .args(args)
-        .current_dir(project_root)
-        .output()
+/// `git diff HEAD` of the working tree, untracked files included. They are
+/// added intent-to-add to a copy of the index, so the real index is untouched.
+fn collect_uncommitted_diff_payload(
+    project_root: &Path,
+    path_filters: &[String],
+) -> Option<Vec<u8>> {
+    let index_dir = tempfile::Builder::new()
+        .prefix("csa-review-index-")
+        .tempdir()
         .ok()?;
+    let index = index_dir.path().join("index");
+    let real_index = run_git(project_root, &["rev-parse", "--git-path", "index"])?;
+    let real_index = project_root.join(String::from_utf8_lossy(&real_index).trim());
+    if real_index.exists() {
+        std::fs::copy(&real_index, &index).ok()?;
+    }
+
+    let mut add_args = vec![
+        "add".to_string(),
+        "--all".to_string(),
+        "--intent-to-add".to_string(),
+    ];
+    push_path_filter_pathspecs(&mut add_args, path_filters);
+    run_git_with_index(project_root, &add_args, Some(&index))?;
+    let mut diff_args = vec![
+        "diff".to_string(),
+        "HEAD".to_string(),
+        "--no-color".to_string(),
+    ];
+    push_path_filter_pathspecs(&mut diff_args, path_filters);
+    run_git_with_index(project_root, &diff_args, Some(&index))
+}
+
+fn run_git(project_root: &Path, args: &[impl AsRef<OsStr>]) -> Option<Vec<u8>> {
+    run_git_with_index(project_root, args, None)
+}
+
+fn run_git_with_index(
+    project_root: &Path,
+    args: &[impl AsRef<OsStr>],
+    index: Option<&Path>,
+) -> Option<Vec<u8>> {
+    let mut command = Command::new("git");
+    command.args(args).current_dir(project_root);
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command.output().ok()?;
     output.status.success().then_some(output.stdout)
 }
 
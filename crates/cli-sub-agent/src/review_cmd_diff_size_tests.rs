@@ -46,6 +46,7 @@ fn review_meta(session_id: &str) -> ReviewSessionMeta {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -120,7 +121,7 @@ fn committed_range_diff_size_counts_only_committed_git_diff() {
         .expect("write untracked file");
 
     let size =
-        compute_review_diff_size(repo.path(), "range:base...HEAD").expect("compute diff size");
+        compute_review_diff_size(repo.path(), "range:base...HEAD", &[]).expect("compute diff size");
 
     assert_eq!(size.files, 1);
     assert_eq!(size.changed_lines, 1);
@@ -133,7 +134,8 @@ fn uncommitted_diff_size_counts_untracked_files() {
     let repo = setup_diff_size_git_repo();
     std::fs::write(repo.path().join("new.txt"), "one\ntwo\nthree\n").expect("write untracked file");
 
-    let size = compute_review_diff_size(repo.path(), "uncommitted").expect("compute diff size");
+    let size =
+        compute_review_diff_size(repo.path(), "uncommitted", &[]).expect("compute diff size");
 
     // The uncommitted path now includes untracked files (#1818): the new file
     // is one of three exact lines, with no estimated/capped note.
@@ -154,7 +156,8 @@ fn uncommitted_diff_size_counts_overlapping_staged_and_unstaged_edit_once() {
     run_git_command(repo.path(), &["add", "tracked.txt"]);
     std::fs::write(&tracked_path, "final\n").expect("write unstaged version");
 
-    let size = compute_review_diff_size(repo.path(), "uncommitted").expect("compute diff size");
+    let size =
+        compute_review_diff_size(repo.path(), "uncommitted", &[]).expect("compute diff size");
 
     assert_eq!(size.files, 1);
     assert_eq!(size.changed_lines, 2);
@@ -284,3 +287,57 @@ fn large_diff_warn_lines_zero_disables_auto_escalation_signal() {
 
     assert!(warning.is_none());
 }
+
+#[test]
+fn staged_diff_size_honors_path_filters() {
+    let repo = setup_diff_size_git_repo();
+    std::fs::create_dir_all(repo.path().join("crates/foo/src")).expect("create crate dir");
+    std::fs::write(repo.path().join("crates/foo/src/lib.rs"), "a\nb\n").expect("write lib");
+    std::fs::write(repo.path().join("tracked.txt"), "baseline\nstaged\n").expect("write tracked");
+    run_git_command(repo.path(), &["add", "."]);
+    std::fs::write(repo.path().join("tracked.txt"), "unstaged\n").expect("write unstaged");
+
+    let size = compute_review_diff_size(repo.path(), "staged", &[]).expect("compute diff size");
+    assert_eq!(size.files, 2);
+    assert_eq!(size.changed_lines, 3, "unstaged edits are excluded");
+
+    let size = compute_review_diff_size(repo.path(), "staged", &["crates/foo/**".to_string()])
+        .expect("compute diff size");
+    assert_eq!(size.files, 1);
+    assert_eq!(size.changed_lines, 2);
+}
+
+#[test]
+fn uncommitted_review_diff_text_includes_untracked_files_without_staging_them() {
+    let repo = setup_diff_size_git_repo();
+    std::fs::write(repo.path().join("tracked.txt"), "changed\n").expect("write tracked");
+    std::fs::create_dir_all(repo.path().join("src")).expect("create src dir");
+    std::fs::write(repo.path().join("src/new.rs"), "fn new() {}\n").expect("write untracked");
+    std::fs::write(repo.path().join("other.txt"), "other\n").expect("write untracked");
+
+    let diff = review_diff_text(repo.path(), "uncommitted", &[]).expect("diff text");
+    assert!(diff.contains("+changed"));
+    assert!(diff.contains("+fn new() {}"));
+    assert!(diff.contains("+other"));
+
+    let diff = review_diff_text(repo.path(), "uncommitted", &["src/**".to_string()])
+        .expect("filtered diff text");
+    assert!(diff.contains("+fn new() {}"));
+    assert!(!diff.contains("+other"));
+    assert!(!diff.contains("+changed"));
+
+    let status = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(repo.path())
+        .output()
+        .expect("git status");
+    let status = String::from_utf8_lossy(&status.stdout);
+    assert!(
+        status.contains("?? other.txt"),
+        "real index was modified: {status}"
+    );
+    assert!(
+        status.contains("?? src/"),
+        "real index was modified: {status}"
+    );
+}
@@ -87,6 +87,7 @@ fn review_meta(session_id: &str, head_sha: &str) -> ReviewSessionMeta {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
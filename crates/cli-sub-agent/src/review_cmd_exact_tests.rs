@@ -148,6 +148,7 @@ fn exact_test_make_review_meta(
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "diff".to_string(),
+        path_filters: Vec::new(),
         exit_code: if decision == ReviewDecision::Pass { 0 } else { 1 },
         fix_attempted: true,
         fix_rounds: 1,
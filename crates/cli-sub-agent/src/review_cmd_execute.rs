@@ -808,17 +808,30 @@ pub(crate) async fn execute_review_with_tier_filter(
 /// invocations produce the same diff content (e.g., revert-then-revert),
 /// the second can reuse the first review's result.
 pub(crate) fn compute_diff_fingerprint(project_root: &Path, scope: &str) -> Option<String> {
+    compute_diff_fingerprint_for_paths(project_root, scope, &[])
+}
+
+/// [`compute_diff_fingerprint`] of the part of the diff that matches the
+/// `--path` globs `path_filters`.
+pub(crate) fn compute_diff_fingerprint_for_paths(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+) -> Option<String> {
     use sha2::{Digest, Sha256};
 
-    let diff_args: Vec<&str> = if scope == "uncommitted" {
-        vec!["diff", "HEAD"]
+    let mut diff_args: Vec<String> = if scope == "uncommitted" {
+        vec!["diff".into(), "HEAD".into()]
+    } else if scope == "staged" {
+        vec!["diff".into(), "--cached".into()]
     } else if let Some(range) = scope.strip_prefix("range:") {
-        vec!["diff", range]
+        vec!["diff".into(), range.into()]
     } else if let Some(base) = scope.strip_prefix("base:") {
-        vec!["diff", base]
+        vec!["diff".into(), base.into()]
     } else {
         return None;
     };
+    super::resolve::push_path_filter_pathspecs(&mut diff_args, path_filters);
 
     let output = std::process::Command::new("git")
         .args(&diff_args)
@@ -126,6 +126,7 @@ async fn execute_review_falls_back_to_next_tier_model_and_persists_routing_metad
         failure_reason: result.failure_reason.clone(),
         tool: result.executed_tool.as_str().to_string(),
         scope: "uncommitted".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
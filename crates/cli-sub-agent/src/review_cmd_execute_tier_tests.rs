@@ -379,6 +379,7 @@ async fn execute_review_advances_tier_fallback_when_explicit_tool_and_tier() {
         failure_reason: result.failure_reason.clone(),
         tool: result.executed_tool.as_str().to_string(),
         scope: "uncommitted".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -0,0 +1,87 @@
+//! `csa review --fail-on`: the exit code as a severity threshold.
+//!
+//! The verdict of a reviewer is a judgement call; with a threshold the exit
+//! code comes from the severity summary of the run's findings instead, so the
+//! same findings always produce the same exit code. Known baseline findings
+//! do not count. Reviews that did not reach a pass/fail decision keep their
+//! exit code, and so does a failed review that persisted no findings.
+
+use std::path::Path;
+
+use anyhow::Result;
+use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::types::ReviewFailOn;
+use csa_session::SeveritySummary;
+
+use super::run_findings::{ReviewFindings, all_sessions_decided};
+use crate::cli::ReviewArgs;
+
+/// Fill in `[review] fail_on` when the flag is absent and the command runs a
+/// review; fix, verdict-check and convergence modes keep their own exit codes.
+pub(super) fn apply_configured_fail_on(args: &mut ReviewArgs) -> Result<()> {
+    if args.fail_on.is_some()
+        || args.fix
+        || args.fix_finding
+        || args.check_verdict
+        || args.converge
+        || args.repair_only
+    {
+        return Ok(());
+    }
+    let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+    let project_config = ProjectConfig::load(&project_root)?;
+    args.fail_on = resolve_fail_on(project_config.as_ref(), &GlobalConfig::load()?);
+    Ok(())
+}
+
+pub(super) fn resolve_fail_on(
+    project_config: Option<&ProjectConfig>,
+    global_config: &GlobalConfig,
+) -> Option<ReviewFailOn> {
+    project_config
+        .and_then(|config| config.review.as_ref())
+        .and_then(|review| review.fail_on)
+        .or(global_config.review.fail_on)
+}
+
+pub(super) fn fails_on(fail_on: ReviewFailOn, summary: &SeveritySummary) -> bool {
+    match fail_on {
+        ReviewFailOn::Critical => summary.critical > 0,
+        ReviewFailOn::High => summary.critical + summary.high > 0,
+        ReviewFailOn::Any => summary.critical + summary.high + summary.medium + summary.low > 0,
+    }
+}
+
+/// 1 when a new finding reaches `fail_on`, 0 otherwise.
+pub(super) fn exit_code_with_fail_on(
+    project_root: &Path,
+    session_ids: &[String],
+    findings: &ReviewFindings,
+    fail_on: ReviewFailOn,
+    exit_code: i32,
+) -> i32 {
+    let no_findings = findings.new.is_empty() && findings.known.is_empty();
+    if (exit_code != 0 && no_findings) || !all_sessions_decided(project_root, session_ids) {
+        return exit_code;
+    }
+    i32::from(fails_on(
+        fail_on,
+        &SeveritySummary::from_findings(&findings.new),
+    ))
+}
+
+pub(super) fn format_fail_on_line(
+    fail_on: ReviewFailOn,
+    findings: &ReviewFindings,
+    exit_code: i32,
+) -> String {
+    let summary = SeveritySummary::from_findings(&findings.new);
+    format!(
+        "fail_on={fail_on}: critical={} high={} medium={} low={} -> exit {exit_code}",
+        summary.critical, summary.high, summary.medium, summary.low
+    )
+}
+
+#[cfg(test)]
+#[path = "review_cmd_fail_on_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+use csa_session::{Finding, Severity};
+
+use super::*;
+
+fn finding(severity: Severity) -> Finding {
+    crate::test_review_findings::finding(severity, "f1", "src/lib.rs", Some(1), "rust.no-unwrap")
+}
+
+fn summary(severities: &[Severity]) -> SeveritySummary {
+    let findings: Vec<Finding> = severities.iter().cloned().map(finding).collect();
+    SeveritySummary::from_findings(&findings)
+}
+
+#[test]
+fn fails_on_counts_findings_at_or_above_the_threshold() {
+    let high = summary(&[Severity::High, Severity::Low]);
+    assert!(!fails_on(ReviewFailOn::Critical, &high));
+    assert!(fails_on(ReviewFailOn::High, &high));
+    assert!(fails_on(ReviewFailOn::Any, &high));
+
+    let low = summary(&[Severity::Medium, Severity::Low]);
+    assert!(!fails_on(ReviewFailOn::High, &low));
+    assert!(fails_on(ReviewFailOn::Any, &low));
+
+    assert!(fails_on(
+        ReviewFailOn::Critical,
+        &summary(&[Severity::Critical])
+    ));
+    assert!(!fails_on(ReviewFailOn::Any, &summary(&[])));
+}
+
+#[test]
+fn exit_code_is_kept_without_decided_sessions_or_persisted_findings() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    let low_only = ReviewFindings {
+        new: vec![finding(Severity::Low)],
+        ..ReviewFindings::default()
+    };
+
+    // Without a persisted pass/fail decision the review may not have run.
+    assert_eq!(
+        exit_code_with_fail_on(root, &[], &low_only, ReviewFailOn::High, 1),
+        1
+    );
+    assert_eq!(
+        exit_code_with_fail_on(root, &[], &ReviewFindings::default(), ReviewFailOn::Any, 1),
+        1
+    );
+}
+
+#[test]
+fn fail_on_line_reports_new_findings_and_exit_code() {
+    let findings = ReviewFindings {
+        new: vec![finding(Severity::High), finding(Severity::Low)],
+        known: vec![finding(Severity::Critical)],
+        baseline_applied: true,
+    };
+    assert_eq!(
+        format_fail_on_line(ReviewFailOn::High, &findings, 1),
+        "fail_on=high: critical=0 high=1 medium=0 low=1 -> exit 1"
+    );
+}
+
+#[test]
+fn resolve_fail_on_prefers_project_config_over_global() {
+    let global: GlobalConfig =
+        toml::from_str("[review]\nfail_on = \"any\"\n").expect("parse global config");
+    assert_eq!(resolve_fail_on(None, &global), Some(ReviewFailOn::Any));
+
+    let project: ProjectConfig =
+        toml::from_str("schema_version = 1\n[review]\nfail_on = \"critical\"\n")
+            .expect("parse project config");
+    assert_eq!(
+        resolve_fail_on(Some(&project), &global),
+        Some(ReviewFailOn::Critical)
+    );
+
+    assert_eq!(resolve_fail_on(None, &GlobalConfig::default()), None);
+}
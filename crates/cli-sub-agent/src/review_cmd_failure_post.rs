@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::path::Path;
 
 use csa_core::types::{ReviewDecision, ToolName};
@@ -25,7 +26,10 @@ pub(super) struct NonFixFailureContext<'a> {
     pub(super) review_session_ids: &'a [String],
 }
 
-pub(super) fn handle_non_fix_failure(ctx: NonFixFailureContext<'_>) -> i32 {
+pub(super) fn handle_non_fix_failure(
+    ctx: NonFixFailureContext<'_>,
+    report: &mut impl Write,
+) -> std::io::Result<i32> {
     let route = super::post_review::build_fix_finding_route(
         ctx.result,
         ctx.initial_tool,
@@ -38,7 +42,8 @@ pub(super) fn handle_non_fix_failure(ctx: NonFixFailureContext<'_>) -> i32 {
         ctx.review_meta,
         ctx.sanitized,
         Some(&route),
-    );
+        report,
+    )?;
     if should_accumulate_findings(&ctx) {
         crate::review_findings::accumulate_findings(ctx.project_root, ctx.sanitized);
     }
@@ -53,14 +58,18 @@ pub(super) fn handle_non_fix_failure(ctx: NonFixFailureContext<'_>) -> i32 {
         ],
         ctx.project_root,
     );
-    let output =
-        super::post_review::build_post_review_output(&hook_output, ctx.decision, ctx.scope);
-    super::post_review::emit_post_review_output(&output);
+    let output = super::post_review::build_post_review_output(
+        &hook_output,
+        ctx.decision,
+        ctx.scope,
+        &ctx.review_meta.path_filters,
+    );
+    super::post_review::emit_post_review_output(&output, report)?;
     super::bug_class_pipeline::maybe_extract_recurring_bug_class_skills(
         ctx.project_root,
         ctx.review_session_ids,
     );
-    ctx.effective_exit_code
+    Ok(ctx.effective_exit_code)
 }
 
 fn should_accumulate_findings(ctx: &NonFixFailureContext<'_>) -> bool {
@@ -0,0 +1,230 @@
+//! Per-finding reviewer agreement for `csa review --min-agreement <K>`.
+//!
+//! Every reviewer's findings are grouped with the other reviewers' findings on
+//! the same issue: the same file and rule, on lines that lie within two lines
+//! of each other. Finding IDs are chosen by each reviewer and never group
+//! findings on their own. The grouping sorts the reports first, so it does not
+//! depend on the order reviewers finished in. A group is accepted only
+//! when at least K distinct reviewers flagged it, and the consensus verdict is
+//! derived from the accepted findings instead of the reviewers' votes, so a
+//! finding raised by a single model no longer fails the review on its own.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csa_session::review_artifact::{Finding, ReviewArtifact, SeveritySummary};
+use serde::Serialize;
+
+use crate::review_consensus::{CLEAN, HAS_ISSUES};
+
+pub(super) const FINDING_AGREEMENT_FILE: &str = "finding-agreement.json";
+const FINDING_AGREEMENT_SCHEMA_VERSION: u32 = 1;
+/// Reports of one file and rule this many lines apart share a line range.
+const LINE_TOLERANCE: u32 = 2;
+
+/// One issue and the 1-based indices of the reviewers that flagged it.
+#[derive(Debug, Clone)]
+pub(super) struct AgreedFinding {
+    /// Highest-severity report of the issue.
+    pub(super) finding: Finding,
+    pub(super) reviewers: BTreeSet<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct FindingConsensus {
+    pub(super) min_agreement: usize,
+    pub(super) reviewers: usize,
+    pub(super) findings: Vec<AgreedFinding>,
+    review_mode: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FindingAgreementReport<'a> {
+    schema_version: u32,
+    min_agreement: usize,
+    reviewers: usize,
+    verdict: &'static str,
+    findings: Vec<FindingAgreementRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct FindingAgreementRecord<'a> {
+    #[serde(flatten)]
+    finding: &'a Finding,
+    reviewers: &'a BTreeSet<usize>,
+    agreement: usize,
+    accepted: bool,
+}
+
+impl FindingConsensus {
+    /// Group the findings of `(reviewer_index, artifact)` pairs by issue:
+    /// normalized file, rule and line range.
+    pub(super) fn from_reviewer_artifacts(
+        reviewer_artifacts: &[(usize, ReviewArtifact)],
+        reviewers: usize,
+        min_agreement: usize,
+    ) -> Self {
+        let mut by_file_and_rule: BTreeMap<(String, String), Vec<(usize, &Finding)>> =
+            BTreeMap::new();
+        for (reviewer_index, artifact) in reviewer_artifacts {
+            for finding in &artifact.findings {
+                by_file_and_rule
+                    .entry(issue_key(finding))
+                    .or_default()
+                    .push((*reviewer_index, finding));
+            }
+        }
+
+        let mut findings: Vec<AgreedFinding> = Vec::new();
+        for mut reports in by_file_and_rule.into_values() {
+            // Reports without a line sort first and form one range of their own.
+            reports.sort_by_key(|(reviewer_index, finding)| (finding.line, *reviewer_index));
+            let mut range_end: Option<Option<u32>> = None;
+            for (reviewer_index, finding) in reports {
+                let extends_range = match (range_end, finding.line) {
+                    (Some(None), None) => true,
+                    (Some(Some(end)), Some(line)) => line - end <= LINE_TOLERANCE,
+                    _ => false,
+                };
+                range_end = Some(finding.line);
+                match findings.last_mut().filter(|_| extends_range) {
+                    Some(agreed) => {
+                        agreed.reviewers.insert(reviewer_index);
+                        if finding.severity > agreed.finding.severity {
+                            agreed.finding = finding.clone();
+                        }
+                    }
+                    None => findings.push(AgreedFinding {
+                        finding: finding.clone(),
+                        reviewers: BTreeSet::from([reviewer_index]),
+                    }),
+                }
+            }
+        }
+        findings.sort_by(|left, right| {
+            right
+                .finding
+                .severity
+                .cmp(&left.finding.severity)
+                .then_with(|| left.finding.fid.cmp(&right.finding.fid))
+        });
+        Self {
+            min_agreement,
+            reviewers,
+            findings,
+            review_mode: reviewer_artifacts
+                .iter()
+                .find_map(|(_, artifact)| artifact.review_mode.clone()),
+        }
+    }
+
+    pub(super) fn is_accepted(&self, agreed: &AgreedFinding) -> bool {
+        agreed.reviewers.len() >= self.min_agreement
+    }
+
+    pub(super) fn accepted(&self) -> impl Iterator<Item = &AgreedFinding> {
+        self.findings
+            .iter()
+            .filter(|agreed| self.is_accepted(agreed))
+    }
+
+    /// `HAS_ISSUES` when an accepted finding is blocking, `CLEAN` otherwise.
+    pub(super) fn verdict(&self) -> &'static str {
+        if self
+            .accepted()
+            .any(|agreed| super::parent_artifacts::is_blocking_severity(&agreed.finding.severity))
+        {
+            HAS_ISSUES
+        } else {
+            CLEAN
+        }
+    }
+
+    /// Consolidated review artifact holding only the accepted findings.
+    pub(super) fn accepted_artifact(&self, session_id: &str) -> ReviewArtifact {
+        let findings: Vec<Finding> = self
+            .accepted()
+            .map(|agreed| agreed.finding.clone())
+            .collect();
+        ReviewArtifact {
+            severity_summary: SeveritySummary::from_findings(&findings),
+            findings,
+            review_mode: self.review_mode.clone(),
+            schema_version: "1.0".to_string(),
+            session_id: session_id.to_string(),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    /// Write every finding with its agreement to `output/finding-agreement.json`.
+    pub(super) fn write(&self, session_dir: &Path) -> Result<()> {
+        let report = FindingAgreementReport {
+            schema_version: FINDING_AGREEMENT_SCHEMA_VERSION,
+            min_agreement: self.min_agreement,
+            reviewers: self.reviewers,
+            verdict: self.verdict(),
+            findings: self
+                .findings
+                .iter()
+                .map(|agreed| FindingAgreementRecord {
+                    finding: &agreed.finding,
+                    reviewers: &agreed.reviewers,
+                    agreement: agreed.reviewers.len(),
+                    accepted: self.is_accepted(agreed),
+                })
+                .collect(),
+        };
+        let output_dir = session_dir.join("output");
+        std::fs::create_dir_all(&output_dir)
+            .with_context(|| format!("failed to create {}", output_dir.display()))?;
+        let path = output_dir.join(FINDING_AGREEMENT_FILE);
+        let content = serde_json::to_string_pretty(&report)
+            .context("failed to serialize finding agreement")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Console lines for the `===== Consensus =====` block.
+    pub(super) fn summary_lines(&self) -> Vec<String> {
+        let accepted = self.accepted().count();
+        let mut lines = vec![format!(
+            "finding_agreement: {accepted} of {} findings flagged by >= {} of {} reviewers",
+            self.findings.len(),
+            self.min_agreement,
+            self.reviewers
+        )];
+        for agreed in &self.findings {
+            lines.push(format!(
+                "- [{}/{}] {} {}{} {} ({})",
+                agreed.reviewers.len(),
+                self.reviewers,
+                if self.is_accepted(agreed) {
+                    "accepted"
+                } else {
+                    "dropped"
+                },
+                agreed.finding.file,
+                agreed
+                    .finding
+                    .line
+                    .map(|line| format!(":{line}"))
+                    .unwrap_or_default(),
+                agreed.finding.summary,
+                agreed.finding.rule_id,
+            ));
+        }
+        lines
+    }
+}
+
+/// File (without `./`, with `/` separators) and lowercased rule of a finding.
+fn issue_key(finding: &Finding) -> (String, String) {
+    let file = finding.file.trim().replace('\\', "/");
+    let file = file.trim_start_matches("./").to_string();
+    (file, finding.rule_id.trim().to_ascii_lowercase())
+}
+
+#[cfg(test)]
+#[path = "review_cmd_finding_agreement_tests.rs"]
+mod tests;
@@ -0,0 +1,190 @@
+use super::*;
+use crate::test_review_findings::finding;
+use csa_session::review_artifact::Severity;
+
+fn artifact(findings: Vec<Finding>) -> ReviewArtifact {
+    ReviewArtifact {
+        severity_summary: SeveritySummary::from_findings(&findings),
+        findings,
+        review_mode: Some("diff".to_string()),
+        schema_version: "1.0".to_string(),
+        session_id: "01REVIEWERSESSION000000000".to_string(),
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+fn three_reviewer_artifacts() -> Vec<(usize, ReviewArtifact)> {
+    vec![
+        (
+            1,
+            artifact(vec![
+                finding(Severity::Medium, "A", "src/lib.rs", Some(10), "unwrap"),
+                finding(Severity::High, "B", "src/io.rs", Some(3), "path-traversal"),
+            ]),
+        ),
+        (
+            2,
+            artifact(vec![
+                // Same issue under a different fid, two lines away.
+                finding(Severity::High, "C", "src/lib.rs", Some(12), "unwrap"),
+                // The same reviewer reporting it twice still counts once.
+                finding(Severity::Medium, "A", "src/lib.rs", Some(10), "unwrap"),
+            ]),
+        ),
+        (
+            3,
+            artifact(vec![finding(
+                Severity::Low,
+                "D",
+                "src/main.rs",
+                Some(1),
+                "naming",
+            )]),
+        ),
+    ]
+}
+
+#[test]
+fn groups_findings_by_file_rule_and_line_range() {
+    let consensus = FindingConsensus::from_reviewer_artifacts(&three_reviewer_artifacts(), 3, 2);
+
+    let agreement: Vec<(&str, Vec<usize>)> = consensus
+        .findings
+        .iter()
+        .map(|agreed| {
+            (
+                agreed.finding.fid.as_str(),
+                agreed.reviewers.iter().copied().collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        agreement,
+        [("B", vec![1]), ("C", vec![1, 2]), ("D", vec![3])],
+        "highest severity represents a group, sorted by severity"
+    );
+}
+
+#[test]
+fn grouping_ignores_fids_and_reviewer_order() {
+    let reports = |first: usize, second: usize| {
+        vec![
+            (
+                first,
+                artifact(vec![
+                    finding(Severity::High, "X", "./src/lib.rs", Some(14), "Unwrap"),
+                    finding(Severity::High, "Y", "src/lib.rs", Some(40), "unwrap"),
+                ]),
+            ),
+            (
+                second,
+                artifact(vec![
+                    finding(Severity::High, "X", "src/lib.rs", Some(90), "unwrap"),
+                    finding(Severity::Medium, "Z", "src/lib.rs", Some(12), "unwrap"),
+                ]),
+            ),
+        ]
+    };
+    let groups = |artifacts: Vec<(usize, ReviewArtifact)>| {
+        let consensus = FindingConsensus::from_reviewer_artifacts(&artifacts, 2, 2);
+        let mut groups: Vec<(Option<u32>, Vec<usize>)> = consensus
+            .findings
+            .iter()
+            .map(|agreed| {
+                (
+                    agreed.finding.line,
+                    agreed.reviewers.iter().copied().collect(),
+                )
+            })
+            .collect();
+        groups.sort();
+        groups
+    };
+
+    let expected = vec![
+        (Some(14), vec![1, 2]),
+        (Some(40), vec![1]),
+        (Some(90), vec![2]),
+    ];
+    assert_eq!(
+        groups(reports(1, 2)),
+        expected,
+        "same fid at line 90 stays apart"
+    );
+    let mut reversed = reports(1, 2);
+    reversed.reverse();
+    assert_eq!(groups(reversed), expected);
+}
+
+#[test]
+fn verdict_follows_findings_that_reach_min_agreement() {
+    let artifacts = three_reviewer_artifacts();
+
+    let consensus = FindingConsensus::from_reviewer_artifacts(&artifacts, 3, 2);
+    let accepted: Vec<&str> = consensus
+        .accepted()
+        .map(|agreed| agreed.finding.fid.as_str())
+        .collect();
+    assert_eq!(accepted, ["C"]);
+    assert_eq!(consensus.verdict(), HAS_ISSUES);
+
+    let consensus = FindingConsensus::from_reviewer_artifacts(&artifacts, 3, 3);
+    assert_eq!(consensus.accepted().count(), 0);
+    assert_eq!(
+        consensus.verdict(),
+        CLEAN,
+        "single-reviewer findings are dropped"
+    );
+
+    let low_only = vec![
+        (
+            1,
+            artifact(vec![finding(Severity::Low, "D", "a.rs", Some(1), "naming")]),
+        ),
+        (
+            2,
+            artifact(vec![finding(Severity::Low, "D", "a.rs", Some(1), "naming")]),
+        ),
+    ];
+    let consensus = FindingConsensus::from_reviewer_artifacts(&low_only, 2, 2);
+    assert_eq!(consensus.accepted().count(), 1);
+    assert_eq!(
+        consensus.verdict(),
+        CLEAN,
+        "accepted low findings do not block"
+    );
+}
+
+#[test]
+fn accepted_artifact_and_report_record_agreement() {
+    let consensus = FindingConsensus::from_reviewer_artifacts(&three_reviewer_artifacts(), 3, 2);
+
+    let accepted = consensus.accepted_artifact("01PARENTSESSION00000000000");
+    assert_eq!(accepted.findings.len(), 1);
+    assert_eq!(accepted.severity_summary.high, 1);
+    assert_eq!(accepted.review_mode.as_deref(), Some("diff"));
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    consensus.write(dir.path()).expect("write");
+    let path = dir.path().join("output").join(FINDING_AGREEMENT_FILE);
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).expect("read")).expect("json");
+    assert_eq!(json["min_agreement"], 2);
+    assert_eq!(json["reviewers"], 3);
+    assert_eq!(json["verdict"], HAS_ISSUES);
+    assert_eq!(json["findings"][1]["fid"], "C");
+    assert_eq!(json["findings"][1]["reviewers"], serde_json::json!([1, 2]));
+    assert_eq!(json["findings"][1]["agreement"], 2);
+    assert_eq!(json["findings"][1]["accepted"], true);
+    assert_eq!(json["findings"][0]["accepted"], false);
+
+    let lines = consensus.summary_lines();
+    assert_eq!(
+        lines[0],
+        "finding_agreement: 1 of 3 findings flagged by >= 2 of 3 reviewers"
+    );
+    assert_eq!(
+        lines[2],
+        "- [2/3] accepted src/lib.rs:12 C summary (unwrap)"
+    );
+}
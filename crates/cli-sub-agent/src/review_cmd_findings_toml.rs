@@ -2,9 +2,10 @@ use std::fs;
 use std::path::Path;
 
 use csa_session::state::ReviewSessionMeta;
-use csa_session::{FindingsFile, write_findings_toml};
+use csa_session::{FindingsFile, InfrastructureNote, write_findings_toml};
 use tracing::{debug, warn};
 
+use crate::review_cmd::infrastructure_notes::split_review_channels;
 use crate::review_cmd::output::extract_review_text;
 use crate::review_cmd::prose_findings::{
     findings_file_from_explicit_findings_sections, findings_file_from_prose,
@@ -173,11 +174,32 @@ pub(in crate::review_cmd) fn review_explicitly_states_no_findings(text: &str) ->
 /// review_contains_prose_fail_conclusion`]). Sharing one loader keeps their source
 /// sets identical so a FAIL verdict can never survive in a place one consults but
 /// the other ignores — the root cause of the #1675 review rounds (a verdict in
-/// `details`, then `output.log`, that the detector did not scan). Returns `None`
-/// when no review text can be located.
+/// `details`, then `output.log`, that the detector did not scan). Tool
+/// diagnostics (quota errors, hook output) are split off into the infrastructure
+/// channel ([`load_infrastructure_notes`]) and never reach either consumer.
+/// Returns `None` when no review text can be located.
 pub(in crate::review_cmd) fn load_canonical_review_text(
     session_dir: &Path,
 ) -> Result<Option<String>, anyhow::Error> {
+    let Some(raw_text) = load_raw_review_text(session_dir)? else {
+        return Ok(None);
+    };
+    let reviewer_text = split_review_channels(&raw_text).reviewer_text;
+    Ok((!reviewer_text.trim().is_empty()).then_some(reviewer_text))
+}
+
+/// Infrastructure notes of the review prose of a session; empty when no review
+/// text can be read.
+pub(in crate::review_cmd) fn load_infrastructure_notes(
+    session_dir: &Path,
+) -> Vec<InfrastructureNote> {
+    match load_raw_review_text(session_dir) {
+        Ok(Some(raw_text)) => split_review_channels(&raw_text).infrastructure_notes,
+        _ => Vec::new(),
+    }
+}
+
+fn load_raw_review_text(session_dir: &Path) -> Result<Option<String>, anyhow::Error> {
     let mut review_texts = Vec::new();
     let mut latest_summary = None;
     let mut latest_details = None;
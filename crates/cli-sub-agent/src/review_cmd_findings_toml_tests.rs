@@ -29,6 +29,7 @@ fn make_review_meta(session_id: &str) -> ReviewSessionMeta {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "diff".to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: false,
         fix_rounds: 0,
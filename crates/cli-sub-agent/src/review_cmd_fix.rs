@@ -76,6 +76,7 @@ pub(crate) struct FixLoopContext<'a> {
     pub diff_report: super::diff_size::ReviewDiffReport<'a>,
     pub project_root: &'a Path,
     pub scope: String,
+    pub path_filters: Vec<String>,
     pub decision: String,
     pub verdict: String,
     /// Review mode that produced this verdict ("standard" or "red-team"), #1817.
@@ -278,6 +279,7 @@ pub(crate) async fn run_fix_loop(ctx: FixLoopContext<'_>) -> Result<i32> {
                 failure_reason: None,
                 tool: ctx.effective_tool.to_string(),
                 scope: ctx.scope.clone(),
+                path_filters: ctx.path_filters.clone(),
                 exit_code: 0,
                 fix_attempted: true,
                 fix_rounds: u32::from(round),
@@ -323,6 +325,7 @@ pub(crate) async fn run_fix_loop(ctx: FixLoopContext<'_>) -> Result<i32> {
         failure_reason: None,
         tool: ctx.effective_tool.to_string(),
         scope: ctx.scope,
+        path_filters: ctx.path_filters,
         exit_code: 1,
         fix_attempted: true,
         fix_rounds: u32::from(ctx.max_rounds),
@@ -465,13 +468,16 @@ fn persist_fix_final_artifacts_with_current_output(
         diff_report.diff_size,
     );
     if outcome.reached_genuine_clean_convergence() {
-        crate::review_gate::maybe_write_review_gate_marker(
-            project_root,
-            &final_meta.head_sha,
-            &final_meta.session_id,
-            &final_meta.scope,
-            final_meta.review_mode.as_deref(),
-        );
+        // A review limited by `--path` filters does not clear the whole scope.
+        if final_meta.path_filters.is_empty() {
+            crate::review_gate::maybe_write_review_gate_marker(
+                project_root,
+                &final_meta.head_sha,
+                &final_meta.session_id,
+                &final_meta.scope,
+                final_meta.review_mode.as_deref(),
+            );
+        }
     } else {
         remove_review_gate_marker_for_head(project_root, &final_meta);
     }
@@ -28,6 +28,7 @@ fn make_clean_review_meta(session_id: &str) -> ReviewSessionMeta {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "diff".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: true,
         fix_rounds: 1,
@@ -85,7 +85,7 @@ pub(crate) async fn handle_fix_finding(
     let Some((config, global_config, model_catalog, _project_completion_policy)) =
         crate::pipeline::load_and_validate(project_root, current_depth)?
     else {
-        return Ok(1);
+        return Ok(csa_core::exit_code::ExitCode::ConfigError.code());
     };
     let session_ref = args
         .session
@@ -221,6 +221,7 @@ mod tests {
 
     fn fix_finding_prompt_args() -> ReviewArgs {
         ReviewArgs {
+            command: None,
             converge: false,
             discovery_only: false,
             execute_completion: false,
@@ -245,9 +246,17 @@ mod tests {
             commit: None,
             range: None,
             files: None,
+            staged: false,
+            paths: Vec::new(),
+            review_format: crate::cli::ReviewFormat::Text,
+            baseline: None,
+            fail_on: None,
+            post_to_pr: None,
             chunked_review: crate::cli::ReviewChunkingMode::Auto,
             fix: false,
             fix_finding: true,
+            apply_suggestions: false,
+            yes: false,
             max_rounds: 3,
             review_mode: None,
             depth: crate::cli::ReviewDepth::Standard,
@@ -257,6 +266,8 @@ mod tests {
             reviewers: None,
             single: false,
             consensus: "majority".to_string(),
+            min_agreement: None,
+            hunk_cache: false,
             cd: None,
             timeout: None,
             idle_timeout: None,
@@ -64,6 +64,7 @@ fn failed_review_meta(session_id: &str) -> ReviewSessionMeta {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: false,
         fix_rounds: 0,
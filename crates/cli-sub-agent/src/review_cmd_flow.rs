@@ -106,13 +106,16 @@ pub(super) fn persist_review_sidecars_if_session_exists_with_diff_size(
             persisted_review_verdict_exit_code(project_root, persistable_session_id)
         });
     if verdict_exit_code == 0 {
-        crate::review_gate::maybe_write_review_gate_marker(
-            project_root,
-            &final_meta.head_sha,
-            persistable_session_id,
-            &final_meta.scope,
-            final_meta.review_mode.as_deref(),
-        );
+        // A review limited by `--path` filters does not clear the whole scope.
+        if final_meta.path_filters.is_empty() {
+            crate::review_gate::maybe_write_review_gate_marker(
+                project_root,
+                &final_meta.head_sha,
+                persistable_session_id,
+                &final_meta.scope,
+                final_meta.review_mode.as_deref(),
+            );
+        }
     } else {
         crate::review_gate::remove_review_gate_marker_for_head(
             project_root,
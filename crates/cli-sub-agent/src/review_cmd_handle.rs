@@ -1,10 +1,42 @@
+use super::report::{ReviewReport, ReviewRun};
 use super::*;
+use std::io::Write;
 
-async fn handle_review_inner(
+pub(super) async fn handle_review_inner(
+    args: ReviewArgs,
+    current_depth: u32,
+    startup_env: &StartupSubtreeEnv,
+) -> Result<ReviewRun> {
+    let started_at = std::time::Instant::now();
+    let cd = args.cd.clone();
+    let notifies = current_depth == 0 && !args.check_verdict;
+    let mut report = ReviewReport::for_args(&args);
+    let result = run_review(args, current_depth, startup_env, &mut report).await;
+    // Finished reviews notify with their verdict below; this covers reviews
+    // that fail before producing one.
+    if notifies && result.is_err() {
+        let project_root = crate::pipeline::determine_project_root(cd.as_deref())
+            .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        crate::session_notify::notify_session_finished(
+            startup_env.session_id().unwrap_or("review"),
+            "review",
+            &project_root,
+            "error",
+            csa_core::exit_code::ExitCode::InternalError.code(),
+            started_at.elapsed().as_secs(),
+        )
+        .await;
+    }
+    result.map(|exit_code| report.finish(exit_code))
+}
+
+async fn run_review(
     mut args: ReviewArgs,
     current_depth: u32,
     startup_env: &StartupSubtreeEnv,
+    report: &mut ReviewReport,
 ) -> Result<i32> {
+    let review_started_at = std::time::Instant::now();
     let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
     if args.check_verdict {
         return check_verdict::handle_check_verdict(&project_root, &args);
@@ -24,7 +56,7 @@ async fn handle_review_inner(
     let Some((config, global_config, model_catalog, project_completion_policy)) =
         crate::pipeline::load_and_validate(&project_root, current_depth)?
     else {
-        return Ok(1);
+        return Ok(csa_core::exit_code::ExitCode::ConfigError.code());
     };
     if args.repair_only {
         return review_convergence::run_repair(review_convergence::RepairContext::new(
@@ -95,8 +127,9 @@ async fn handle_review_inner(
     let scope = derive_scope_for_project(&args, &project_root);
     let depth_assessment =
         depth::resolve_review_depth_for_project(&args, &project_root, &scope).await;
-    let regression_context = depth::collect_bounded_regression_context(&project_root, &scope).await;
-    let diff = diff_size::compute_review_diff_size(&project_root, &scope);
+    let regression_context =
+        depth::collect_bounded_regression_context(&project_root, &scope, &args.paths).await;
+    let diff = diff_size::compute_review_diff_size(&project_root, &scope, &args.paths);
     let large_warn = diff_size::warn_if_large_diff(diff.as_ref(), config.as_ref(), &global_config);
     let mode = if args.fix {
         "review-and-fix"
@@ -151,6 +184,7 @@ async fn handle_review_inner(
             review_depth: depth_assessment.depth,
             review_depth_auto_escalation: depth_assessment.auto_escalation_summary(),
             regression_context: regression_context.as_deref(),
+            path_filters: &args.paths,
         },
     );
 
@@ -254,15 +288,50 @@ async fn handle_review_inner(
         &model_catalog,
     );
     let reviewers = reviewer_selection.reviewers;
+    if args.min_agreement.is_some() && reviewers < 2 {
+        anyhow::bail!(
+            "--min-agreement counts agreement across reviewers; run with --reviewers <N> (N > 1)"
+        );
+    }
+    if args.hunk_cache && reviewers > 1 {
+        anyhow::bail!("--hunk-cache caches single-reviewer results; run with --single");
+    }
+    let hunk_cache_plan = if args.hunk_cache {
+        let tier = hunk_cache::tier_key(
+            resolved_tier_name.as_deref(),
+            resolved_model_spec.as_deref(),
+            tool,
+        );
+        hunk_cache::plan_or_warn(&project_root, &scope, &args.paths, tier)
+    } else {
+        None
+    };
+    if let Some(section) = hunk_cache_plan
+        .as_ref()
+        .and_then(|plan| plan.prompt_section())
+    {
+        prompt.push_str("\n\n");
+        prompt.push_str(&section);
+    }
     let explicit_tool_with_failover =
         (selection.direct_tool_requested && tier_active && !execution_no_failover).then_some(tool);
 
-    let explicit_multi_reviewer = args.reviewers.is_some() && args.requested_reviewers() > 1;
+    // Chunking would bypass the per-finding agreement of --min-agreement.
+    let explicit_multi_reviewer = (args.reviewers.is_some() && args.requested_reviewers() > 1)
+        || args.min_agreement.is_some();
+    // Chunked reviews do not consult the hunk cache.
     if !explicit_multi_reviewer
+        && !args.hunk_cache
         && !chunking::should_bypass_chunking(args.chunked_review, args.fix, args.session.is_some())
     {
         let chunking_config = chunking::ReviewChunkingConfig::for_args(args.chunked_review);
-        match chunking::plan_review_chunks(&project_root, &scope, diff.as_ref(), &chunking_config) {
+        match chunking::plan_review_chunks(
+            &project_root,
+            &scope,
+            &args.paths,
+            diff.as_ref(),
+            &chunking_config,
+        ) {
             Ok(Some(chunk_plan)) => {
                 return chunking::run_chunked_review(chunking::ChunkedReviewContext {
                     args: &args,
@@ -296,6 +365,7 @@ async fn handle_review_inner(
                     review_mode: review_mode.as_str(),
                     current_depth,
                     startup_env,
+                    report,
                 })
                 .await;
             }
@@ -403,10 +473,11 @@ async fn handle_review_inner(
         let decision = resolved.decision;
         let auth_prompt_failure = resolved.auth_prompt_failure;
         let failure_reason = resolved.failure_reason;
-        print!(
+        write!(
+            report,
             "{}",
             diff_size::add_review_diff_size_line(&sanitized, diff.as_ref())
-        );
+        )?;
         debug!(verdict, decision = %decision, empty_output, "Review verdict (legacy + four-value)");
         let review_iterations = result
             .persistable_session_id
@@ -421,7 +492,8 @@ async fn handle_review_inner(
             .collect::<Vec<_>>();
 
         let effective_exit_code = resolved.effective_exit_code;
-        let diff_fingerprint = compute_diff_fingerprint(&project_root, &scope);
+        let diff_fingerprint =
+            compute_diff_fingerprint_for_paths(&project_root, &scope, &args.paths);
         let fix_attempted = should_run_fix_loop(args.fix, decision);
         let review_meta = ReviewSessionMeta {
             session_id: result.execution.meta_session_id.clone(),
@@ -435,6 +507,7 @@ async fn handle_review_inner(
             failure_reason,
             tool: result.executed_tool.to_string(),
             scope: scope.clone(),
+            path_filters: args.paths.clone(),
             exit_code: effective_exit_code,
             fix_attempted,
             fix_rounds: 0,
@@ -455,6 +528,19 @@ async fn handle_review_inner(
             persist_review_result_exit_code(&project_root, session_id, effective_exit_code);
             diff_size::persist_review_diff_size_headers(&project_root, session_id, diff.as_ref());
         }
+        let notify_finished = async |verdict: &str, exit_code: i32| {
+            if current_depth == 0 {
+                crate::session_notify::notify_session_finished(
+                    &review_meta.session_id,
+                    &review_meta.tool,
+                    &project_root,
+                    verdict,
+                    exit_code,
+                    review_started_at.elapsed().as_secs(),
+                )
+                .await;
+            }
+        };
         if verdict != CLEAN {
             dirty_tree::maybe_emit_dirty_tree_hint(
                 &project_root,
@@ -468,8 +554,16 @@ async fn handle_review_inner(
             result.persistable_session_id.as_deref(),
             result.executed_tool.as_str(),
         );
-        let is_cumulative_review = review_scope_is_cumulative(&scope);
+        report.record_sessions(&review_session_ids);
+        if let (Some(plan), Some(session_id)) = (
+            hunk_cache_plan.as_ref(),
+            result.persistable_session_id.as_deref(),
+        ) {
+            hunk_cache::record_or_warn(&project_root, plan, session_id, decision);
+        }
+        let is_cumulative_review = review_scope_is_cumulative(&scope, &args.paths);
         if !should_run_fix_loop(args.fix, decision) {
+            notify_finished(verdict, effective_exit_code).await;
             return Ok(failure_post::handle_non_fix_failure(
                 failure_post::NonFixFailureContext {
                     project_root: &project_root,
@@ -490,7 +584,8 @@ async fn handle_review_inner(
                     is_cumulative_review,
                     review_session_ids: &review_session_ids,
                 },
-            ));
+                report,
+            )?);
         }
 
         let effective_fix_tool = result.executed_tool;
@@ -505,6 +600,7 @@ async fn handle_review_inner(
             &project_root,
             &review_session_ids,
         ) {
+            notify_finished(verdict, effective_exit_code).await;
             return Ok(effective_exit_code);
         }
         let scope_for_hook = scope.clone();
@@ -538,6 +634,7 @@ async fn handle_review_inner(
             },
             project_root: &project_root,
             scope,
+            path_filters: args.paths.clone(),
             decision: decision.as_str().to_string(),
             verdict: verdict.to_string(),
             review_mode: Some(review_mode.as_str().to_string()),
@@ -569,14 +666,18 @@ async fn handle_review_inner(
                 ReviewDecision::Fail
             },
             &scope_for_hook,
+            &args.paths,
         );
         if fix_passed {
-            emit_post_review_output(&post_review_output);
+            emit_post_review_output(&post_review_output, report)?;
         } else if !is_cumulative_review {
             crate::review_findings::accumulate_findings(&project_root, &sanitized);
         }
 
         maybe_extract_recurring_bug_class_skills(&project_root, &review_session_ids);
+        if let Ok(exit_code) = &fix_exit_code {
+            notify_finished(if fix_passed { CLEAN } else { verdict }, *exit_code).await;
+        }
         return fix_exit_code;
     }
     multi::run_multi_reviewer_review(multi::MultiReviewerReviewContext {
@@ -607,6 +708,7 @@ async fn handle_review_inner(
         current_session_id: startup_env.session_id(),
         current_depth,
         startup_env,
+        report,
     })
     .await
 }
@@ -1,14 +1,25 @@
 use super::*;
 
 pub(crate) async fn handle_review(
-    args: ReviewArgs,
+    mut args: ReviewArgs,
     current_depth: u32,
     startup_env: &StartupSubtreeEnv,
 ) -> Result<i32> {
+    if let Some(command) = args.command.take() {
+        return baseline::handle_baseline_command(command);
+    }
+    if args.apply_suggestions {
+        return apply_suggestions::handle_apply_suggestions(args, current_depth).await;
+    }
+    fail_on::apply_configured_fail_on(&mut args)?;
+    if run_findings::reports_run_findings(&args) {
+        return run_findings::handle_review_reporting_findings(args, current_depth, startup_env)
+            .await;
+    }
     let convergence = args.converge;
     let execute_completion = args.execute_completion;
     match handle_review_inner(args, current_depth, startup_env).await {
-        Ok(exit_code) => Ok(exit_code),
+        Ok(run) => Ok(run.exit_code),
         Err(error) if convergence && execute_completion => {
             review_convergence::emit_completion_setup_block("execution_setup_failure", &error)
         }
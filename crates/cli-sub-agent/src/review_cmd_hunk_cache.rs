@@ -0,0 +1,351 @@
+//! `csa review --hunk-cache`: skip diff hunks an earlier review already cleared.
+//!
+//! Every hunk of the review diff is keyed by (file path, hash of the hunk
+//! lines, review tier). After a review reaches a pass/fail decision its
+//! findings are attributed to the hunks by file and line and stored under those
+//! keys in the project state directory. The next review at the same tier hands
+//! the reviewer the diff without the hunks whose cached result has no findings,
+//! so only hunks that changed, or that had findings, are read and reviewed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use csa_core::types::{ReviewDecision, ToolName};
+use csa_session::Finding;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use super::apply_suggestions::diff_path;
+
+const HUNK_CACHE_FILE: &str = "review-hunk-cache.json";
+const HUNK_CACHE_SCHEMA_VERSION: u32 = 1;
+/// Oldest entries are dropped beyond this many.
+const MAX_HUNK_CACHE_ENTRIES: usize = 5_000;
+
+/// One `@@` hunk of the review diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ReviewHunk {
+    pub(super) file: String,
+    pub(super) header: String,
+    /// First and last line of the hunk in the new file.
+    pub(super) new_lines: (u32, u32),
+    /// SHA-256 of the hunk lines, without the header's line numbers.
+    pub(super) hash: String,
+    /// Lines of the file's diff before its first hunk (`diff --git`, `---`, ...).
+    file_header: String,
+    /// The hunk as it appears in the diff, header included.
+    text: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct HunkCacheEntry {
+    file: String,
+    hunk_hash: String,
+    tier: String,
+    findings: Vec<Finding>,
+    session_id: String,
+    reviewed_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct HunkCacheFile {
+    schema_version: u32,
+    entries: Vec<HunkCacheEntry>,
+}
+
+/// Hunks of one review and which of them the cache clears.
+#[derive(Debug)]
+pub(super) struct HunkCachePlan {
+    cache_path: PathBuf,
+    tier: String,
+    hunks: Vec<ReviewHunk>,
+    reused: Vec<bool>,
+}
+
+/// Cache key of the reviewer: the tier, else the model spec, else the tool.
+pub(super) fn tier_key(tier: Option<&str>, model_spec: Option<&str>, tool: ToolName) -> String {
+    match (tier, model_spec) {
+        (Some(tier), _) => format!("tier:{tier}"),
+        (None, Some(model_spec)) => format!("model:{model_spec}"),
+        (None, None) => format!("tool:{tool}"),
+    }
+}
+
+/// Split a unified diff into hunks.
+pub(super) fn parse_diff_hunks(diff: &str) -> Vec<ReviewHunk> {
+    let mut hunks = Vec::new();
+    let mut old_file: Option<String> = None;
+    let mut file: Option<String> = None;
+    let mut file_header = String::new();
+    let mut current: Option<(String, (u32, u32), Vec<&str>)> = None;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            finish_hunk(&mut hunks, file.as_deref(), &file_header, current.take());
+            old_file = None;
+            file = None;
+            file_header.clear();
+        } else if line.starts_with("@@") {
+            finish_hunk(&mut hunks, file.as_deref(), &file_header, current.take());
+            if let Some(new_lines) = new_line_range(line) {
+                current = Some((line.to_string(), new_lines, Vec::new()));
+            }
+            continue;
+        } else if let Some((_, _, lines)) = current.as_mut() {
+            lines.push(line);
+            continue;
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            old_file = diff_path(path);
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            file = diff_path(path).or_else(|| old_file.clone());
+        }
+        file_header.push_str(line);
+        file_header.push('\n');
+    }
+    finish_hunk(&mut hunks, file.as_deref(), &file_header, current);
+    hunks
+}
+
+fn finish_hunk(
+    hunks: &mut Vec<ReviewHunk>,
+    file: Option<&str>,
+    file_header: &str,
+    hunk: Option<(String, (u32, u32), Vec<&str>)>,
+) {
+    let (Some(file), Some((header, new_lines, lines))) = (file, hunk) else {
+        return;
+    };
+    let mut hasher = Sha256::new();
+    let mut text = format!("{header}\n");
+    for line in lines {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+        text.push_str(line);
+        text.push('\n');
+    }
+    hunks.push(ReviewHunk {
+        file: file.to_string(),
+        header,
+        new_lines,
+        hash: format!("sha256:{:x}", hasher.finalize()),
+        file_header: file_header.to_string(),
+        text,
+    });
+}
+
+/// `(start, end)` of the `+start,count` part of a hunk header. A pure deletion
+/// (`count` 0) covers the line it follows.
+fn new_line_range(header: &str) -> Option<(u32, u32)> {
+    let range = header
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match range.split_once(',') {
+        Some((start, count)) => (start.parse::<u32>().ok()?, count.parse::<u32>().ok()?),
+        None => (range.parse::<u32>().ok()?, 1),
+    };
+    Some((start, start + count.max(1) - 1))
+}
+
+/// Findings of `findings` located in `hunk`; a finding without a line counts
+/// for every hunk of its file.
+fn hunk_findings(hunk: &ReviewHunk, findings: &[Finding]) -> Vec<Finding> {
+    findings
+        .iter()
+        .filter(|finding| csa_session::normalize_path(&finding.file) == hunk.file)
+        .filter(|finding| {
+            finding
+                .line
+                .is_none_or(|line| (hunk.new_lines.0..=hunk.new_lines.1).contains(&line))
+        })
+        .cloned()
+        .collect()
+}
+
+fn load_cache(path: &Path) -> Result<HunkCacheFile> {
+    match std::fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str::<HunkCacheFile>(&raw)
+            .with_context(|| format!("failed to parse {}", path.display()))
+            .map(|cache| {
+                if cache.schema_version == HUNK_CACHE_SCHEMA_VERSION {
+                    cache
+                } else {
+                    HunkCacheFile::default()
+                }
+            }),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(HunkCacheFile::default()),
+        Err(error) => Err(error).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+impl HunkCachePlan {
+    /// Look up the hunks of `diff` in the cache at `cache_path`.
+    pub(super) fn load(cache_path: PathBuf, tier: String, diff: &str) -> Result<Self> {
+        let cache = load_cache(&cache_path)?;
+        let cleared: BTreeMap<(&str, &str), bool> = cache
+            .entries
+            .iter()
+            .filter(|entry| entry.tier == tier)
+            .map(|entry| {
+                (
+                    (entry.file.as_str(), entry.hunk_hash.as_str()),
+                    entry.findings.is_empty(),
+                )
+            })
+            .collect();
+        let hunks = parse_diff_hunks(diff);
+        let reused = hunks
+            .iter()
+            .map(|hunk| {
+                cleared
+                    .get(&(hunk.file.as_str(), hunk.hash.as_str()))
+                    .copied()
+                    .unwrap_or(false)
+            })
+            .collect();
+        Ok(Self {
+            cache_path,
+            tier,
+            hunks,
+            reused,
+        })
+    }
+
+    pub(super) fn reused_count(&self) -> usize {
+        self.reused.iter().filter(|reused| **reused).count()
+    }
+
+    pub(super) fn summary_line(&self) -> String {
+        format!(
+            "hunk cache: reusing {} of {} diff hunks reviewed clean at {}",
+            self.reused_count(),
+            self.hunks.len(),
+            self.tier
+        )
+    }
+
+    /// Reviewer instruction carrying the diff without the cached hunks,
+    /// `None` when none are cached.
+    pub(super) fn prompt_section(&self) -> Option<String> {
+        let reused = self.reused_count();
+        if reused == 0 {
+            return None;
+        }
+        let mut diff = String::new();
+        let mut last_file_header: Option<&str> = None;
+        for (hunk, reused) in self.hunks.iter().zip(&self.reused) {
+            if *reused {
+                continue;
+            }
+            if last_file_header != Some(hunk.file_header.as_str()) {
+                diff.push_str(&hunk.file_header);
+                last_file_header = Some(&hunk.file_header);
+            }
+            diff.push_str(&hunk.text);
+        }
+        Some(format!(
+            "Hunk cache: {reused} of {} diff hunks are unchanged since a review with the same \
+             tier reported no findings in them and are left out of the diff below. Review this \
+             diff instead of collecting the diff for the scope; read other code only as context \
+             for it.\n<review-diff inert=\"true\">\n{diff}</review-diff>",
+            self.hunks.len()
+        ))
+    }
+
+    /// Store the result of a decided review for every hunk of the plan.
+    pub(super) fn record(
+        &self,
+        session_id: &str,
+        decision: ReviewDecision,
+        findings: Option<&[Finding]>,
+    ) -> Result<()> {
+        let findings = match (decision, findings) {
+            (ReviewDecision::Pass, findings) => findings.unwrap_or_default(),
+            (ReviewDecision::Fail, Some(findings)) => findings,
+            // Undecided reviews, and failures without findings to attribute,
+            // say nothing about individual hunks.
+            _ => return Ok(()),
+        };
+        let mut cache = load_cache(&self.cache_path)?;
+        cache.entries.retain(|entry| {
+            entry.tier != self.tier
+                || !self
+                    .hunks
+                    .iter()
+                    .any(|hunk| hunk.file == entry.file && hunk.hash == entry.hunk_hash)
+        });
+        let reviewed_at = Utc::now();
+        cache
+            .entries
+            .extend(self.hunks.iter().map(|hunk| HunkCacheEntry {
+                file: hunk.file.clone(),
+                hunk_hash: hunk.hash.clone(),
+                tier: self.tier.clone(),
+                findings: hunk_findings(hunk, findings),
+                session_id: session_id.to_string(),
+                reviewed_at,
+            }));
+        if cache.entries.len() > MAX_HUNK_CACHE_ENTRIES {
+            cache.entries.sort_by_key(|entry| entry.reviewed_at);
+            let excess = cache.entries.len() - MAX_HUNK_CACHE_ENTRIES;
+            cache.entries.drain(..excess);
+        }
+        cache.schema_version = HUNK_CACHE_SCHEMA_VERSION;
+        if let Some(parent) = self.cache_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string(&cache).context("failed to serialize hunk cache")?;
+        std::fs::write(&self.cache_path, content)
+            .with_context(|| format!("failed to write {}", self.cache_path.display()))
+    }
+}
+
+/// Plan for the review diff of `scope` limited to `path_filters`, or `None`
+/// when it cannot be read.
+pub(super) fn plan_or_warn(
+    project_root: &Path,
+    scope: &str,
+    path_filters: &[String],
+    tier: String,
+) -> Option<HunkCachePlan> {
+    let diff = super::diff_size::review_diff_text(project_root, scope, path_filters)?;
+    let plan = csa_session::get_session_root(project_root)
+        .and_then(|root| HunkCachePlan::load(root.join(HUNK_CACHE_FILE), tier, &diff));
+    match plan {
+        Ok(plan) => {
+            eprintln!("{}", plan.summary_line());
+            Some(plan)
+        }
+        Err(error) => {
+            warn!(error = %error, "Review hunk cache unavailable; reviewing every hunk");
+            None
+        }
+    }
+}
+
+/// Record the findings of review session `session_id` in the cache.
+pub(super) fn record_or_warn(
+    project_root: &Path,
+    plan: &HunkCachePlan,
+    session_id: &str,
+    decision: ReviewDecision,
+) {
+    let result = csa_session::get_session_dir(project_root, session_id)
+        .and_then(|session_dir| {
+            crate::review_session_findings::read_session_findings_or_fall_back(&session_dir)
+        })
+        .and_then(|findings| plan.record(session_id, decision, findings.as_deref()));
+    if let Err(error) = result {
+        warn!(
+            session_id = %session_id,
+            error = %error,
+            "Failed to update the review hunk cache"
+        );
+    }
+}
+
+#[cfg(test)]
+#[path = "review_cmd_hunk_cache_tests.rs"]
+mod tests;
@@ -0,0 +1,140 @@
+use csa_session::Severity;
+
+use super::*;
+use crate::test_review_findings::finding;
+
+const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {
+-    x.unwrap();
++    x.unwrap_or_default();
+ }
+@@ -20,2 +20,3 @@ fn b() {
+ fn c() {
++    y();
+ }
+diff --git a/src/old.rs b/src/old.rs
+deleted file mode 100644
+--- a/src/old.rs
++++ /dev/null
+@@ -1,1 +0,0 @@
+-fn old() {}
+";
+
+#[test]
+fn parse_diff_hunks_keys_hunks_by_file_and_content() {
+    let hunks = parse_diff_hunks(DIFF);
+    assert_eq!(hunks.len(), 3);
+    assert_eq!(hunks[0].file, "src/lib.rs");
+    assert_eq!(hunks[0].header, "@@ -1,3 +1,3 @@");
+    assert_eq!(hunks[0].new_lines, (1, 3));
+    assert_eq!(hunks[1].new_lines, (20, 22));
+    assert_eq!(hunks[2].file, "src/old.rs");
+    assert_eq!(hunks[2].new_lines, (0, 0));
+
+    // Moving a hunk keeps its hash; editing it does not.
+    let moved = parse_diff_hunks(&DIFF.replace("@@ -20,2 +20,3 @@", "@@ -40,2 +40,3 @@"));
+    assert_eq!(moved[1].hash, hunks[1].hash);
+    let edited = parse_diff_hunks(&DIFF.replace("+    y();", "+    z();"));
+    assert_ne!(edited[1].hash, hunks[1].hash);
+    assert_eq!(edited[0].hash, hunks[0].hash);
+}
+
+#[test]
+fn recorded_clean_hunks_are_reused_at_the_same_tier_only() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let cache_path = dir.path().join("state").join(HUNK_CACHE_FILE);
+    let load = |tier: &str, diff: &str| {
+        HunkCachePlan::load(cache_path.clone(), tier.to_string(), diff).expect("load plan")
+    };
+
+    let plan = load("tier:standard", DIFF);
+    assert_eq!(plan.reused_count(), 0);
+    assert_eq!(plan.prompt_section(), None);
+
+    // A finding on line 2 keeps the first hunk out of the cache.
+    let findings = [finding(
+        Severity::High,
+        "f1",
+        "./src/lib.rs",
+        Some(2),
+        "rust.no-unwrap",
+    )];
+    plan.record("01SESSION", ReviewDecision::Fail, Some(&findings))
+        .expect("record");
+
+    let plan = load("tier:standard", DIFF);
+    assert_eq!(plan.reused, [false, true, true]);
+    assert_eq!(
+        plan.summary_line(),
+        "hunk cache: reusing 2 of 3 diff hunks reviewed clean at tier:standard"
+    );
+    let section = plan.prompt_section().expect("section");
+    assert!(
+        section.starts_with("Hunk cache: 2 of 3 diff hunks"),
+        "{section}"
+    );
+    // Only the hunk with a finding is left in the diff handed to the reviewer.
+    let diff = section
+        .split_once("<review-diff inert=\"true\">\n")
+        .map(|(_, diff)| diff)
+        .expect("diff");
+    assert_eq!(
+        diff,
+        "diff --git a/src/lib.rs b/src/lib.rs\n\
+         index 1111111..2222222 100644\n\
+         --- a/src/lib.rs\n\
+         +++ b/src/lib.rs\n\
+         @@ -1,3 +1,3 @@\n \
+         fn a() {\n\
+         -    x.unwrap();\n\
+         +    x.unwrap_or_default();\n \
+         }\n\
+         </review-diff>"
+    );
+
+    assert_eq!(load("tier:audit", DIFF).reused_count(), 0);
+
+    // A passing re-review clears the fixed hunk.
+    plan.record("01SESSION2", ReviewDecision::Pass, None)
+        .expect("record");
+    assert_eq!(load("tier:standard", DIFF).reused_count(), 3);
+}
+
+#[test]
+fn undecided_reviews_and_failures_without_findings_are_not_recorded() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let cache_path = dir.path().join(HUNK_CACHE_FILE);
+    let plan =
+        HunkCachePlan::load(cache_path.clone(), "tool:codex".to_string(), DIFF).expect("load plan");
+
+    plan.record("01SESSION", ReviewDecision::Unavailable, None)
+        .expect("record");
+    plan.record("01SESSION", ReviewDecision::Fail, None)
+        .expect("record");
+    assert!(!cache_path.exists());
+}
+
+#[test]
+fn tier_key_prefers_tier_then_model_spec_then_tool() {
+    assert_eq!(
+        tier_key(
+            Some("standard"),
+            Some("codex/openai/gpt-5/high"),
+            ToolName::Codex
+        ),
+        "tier:standard"
+    );
+    assert_eq!(
+        tier_key(None, Some("codex/openai/gpt-5/high"), ToolName::Codex),
+        "model:codex/openai/gpt-5/high"
+    );
+    assert_eq!(
+        tier_key(None, None, ToolName::ClaudeCode),
+        "tool:claude-code"
+    );
+}
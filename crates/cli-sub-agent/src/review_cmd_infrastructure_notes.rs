@@ -0,0 +1,135 @@
+//! Split review output into the findings channel and infrastructure notes.
+//!
+//! The canonical review text unions the reviewer's sections with the raw tool
+//! output, which also carries provider errors and hook output. Such lines say
+//! nothing about the code: they are removed before findings extraction and
+//! verdict detection, and recorded as `infrastructure_notes` of the verdict.
+
+use csa_core::gemini::{PERMANENT_QUOTA_EXHAUSTION_PATTERNS, RATE_LIMIT_PATTERNS};
+use csa_session::{InfrastructureNote, InfrastructureNoteKind};
+
+const MAX_INFRASTRUCTURE_NOTES: usize = 20;
+const MAX_NOTE_CHARS: usize = 300;
+
+/// Line starts of tool diagnostics, matched case-sensitively as the tools print
+/// them: `Error: 429 ...` (gemini-cli), `API Error: 401 ...` (claude-code),
+/// `ERROR: ...` / `stream error: ...` (codex) and JSON error envelopes. A quota
+/// or auth keyword alone is not enough, since reviewers also write about rate
+/// limiters and auth code.
+const DIAGNOSTIC_PREFIXES: &[&str] = &[
+    "Error: ",
+    "ERROR: ",
+    "error: ",
+    "fatal: ",
+    "API Error: ",
+    "[API Error: ",
+    "stream error: ",
+    "{\"error\"",
+];
+const QUOTA_PATTERNS: &[&str] = &["quota", "rate limit", "rate_limit", "usage limit"];
+const AUTH_PATTERNS: &[&str] = &[
+    "unauthorized",
+    "invalid api key",
+    "invalid_api_key",
+    "authentication failed",
+    "not logged in",
+    "http 401",
+    "http 403",
+];
+/// gemini-cli: `MCP issues detected. Run /mcp list for status.`
+const TOOL_ERROR_PREFIXES: &[&str] = &["MCP issues detected. "];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct ReviewChannels {
+    /// Review text without infrastructure lines.
+    pub(super) reviewer_text: String,
+    pub(super) infrastructure_notes: Vec<InfrastructureNote>,
+}
+
+/// Separate infrastructure lines of `text` from the reviewer's content. Lines
+/// inside code fences (such as the `findings.toml` block) always stay.
+pub(super) fn split_review_channels(text: &str) -> ReviewChannels {
+    let mut channels = ReviewChannels::default();
+    let mut in_code_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_fence = !in_code_fence;
+        }
+        let kind = (!in_code_fence)
+            .then(|| classify_infrastructure_line(line))
+            .flatten();
+        let Some(kind) = kind else {
+            channels.reviewer_text.push_str(line);
+            channels.reviewer_text.push('\n');
+            continue;
+        };
+        let message: String = line.trim().chars().take(MAX_NOTE_CHARS).collect();
+        let note = InfrastructureNote { kind, message };
+        if channels.infrastructure_notes.len() < MAX_INFRASTRUCTURE_NOTES
+            && !channels.infrastructure_notes.contains(&note)
+        {
+            channels.infrastructure_notes.push(note);
+        }
+    }
+    channels
+}
+
+pub(super) fn classify_infrastructure_line(line: &str) -> Option<InfrastructureNoteKind> {
+    let trimmed = line.trim();
+    if trimmed.starts_with("[csa-hook]") {
+        return Some(InfrastructureNoteKind::Hook);
+    }
+    if TOOL_ERROR_PREFIXES
+        .iter()
+        .any(|prefix| trimmed.starts_with(prefix))
+    {
+        return Some(InfrastructureNoteKind::ToolError);
+    }
+    let line = trimmed
+        .strip_prefix("[stderr] ")
+        .or_else(|| trimmed.strip_prefix("[stdout] "))
+        .unwrap_or(trimmed);
+    if !(DIAGNOSTIC_PREFIXES
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+        || is_http_status_line(line))
+    {
+        return None;
+    }
+    let lower = line.to_ascii_lowercase();
+    if mentions_source_location(&lower) {
+        return None;
+    }
+    let matches_any = |patterns: &[&str]| patterns.iter().any(|pattern| lower.contains(pattern));
+    if matches_any(RATE_LIMIT_PATTERNS)
+        || matches_any(PERMANENT_QUOTA_EXHAUSTION_PATTERNS)
+        || matches_any(QUOTA_PATTERNS)
+    {
+        Some(InfrastructureNoteKind::Quota)
+    } else if matches_any(AUTH_PATTERNS) {
+        Some(InfrastructureNoteKind::Auth)
+    } else {
+        None
+    }
+}
+
+/// `HTTP 401 Invalid API key`: a status line as printed by HTTP clients.
+fn is_http_status_line(line: &str) -> bool {
+    line.strip_prefix("HTTP ").is_some_and(|rest| {
+        let code = rest.split(|ch: char| !ch.is_ascii_digit()).next();
+        code.is_some_and(|code| code.len() == 3)
+    })
+}
+
+/// Whether the line points at code (`src/lib.rs:12`), as findings do.
+fn mentions_source_location(lower: &str) -> bool {
+    lower.split_whitespace().any(|token| {
+        token.split_once(':').is_some_and(|(path, rest)| {
+            path.contains('.') && rest.starts_with(|ch: char| ch.is_ascii_digit())
+        })
+    })
+}
+
+#[cfg(test)]
+#[path = "review_cmd_infrastructure_notes_tests.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+use super::*;
+
+#[test]
+fn diagnostics_are_split_off_and_findings_stay() {
+    let text = "\
+## Findings
+- [high] src/lib.rs:12 unwrap on user input
+Error: 429 Too Many Requests: quota exceeded for model gemini-2.5-pro
+[csa-hook] post-review hook output: exit 0
+Error: 429 Too Many Requests: quota exceeded for model gemini-2.5-pro
+[stderr] Error: Unauthorized (HTTP 401)
+MCP issues detected. Run /mcp list for status.
+Verdict: FAIL
+";
+    let channels = split_review_channels(text);
+
+    assert_eq!(
+        channels.reviewer_text,
+        "## Findings\n- [high] src/lib.rs:12 unwrap on user input\nVerdict: FAIL\n"
+    );
+    let kinds: Vec<_> = channels
+        .infrastructure_notes
+        .iter()
+        .map(|note| note.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            InfrastructureNoteKind::Quota,
+            InfrastructureNoteKind::Hook,
+            InfrastructureNoteKind::Auth,
+            InfrastructureNoteKind::ToolError,
+        ]
+    );
+    assert_eq!(
+        channels.infrastructure_notes[0].message,
+        "Error: 429 Too Many Requests: quota exceeded for model gemini-2.5-pro"
+    );
+}
+
+#[test]
+fn reviewer_prose_about_quotas_and_auth_is_not_a_diagnostic() {
+    for line in [
+        "The rate limiter returns 429 without a Retry-After header.",
+        "Error handling in src/quota.rs:40 ignores quota exceeded responses.",
+        "Warning: src/auth.rs:12 maps unauthorized to 500",
+        "- [medium] invalid api key is logged in plain text",
+        "Error handling ignores rate limit responses from the upstream API.",
+        "Errors: quota exceeded responses are retried forever.",
+        "Warning: retries never stop when the quota exceeded flag is set.",
+        "Status 429 is mapped to a generic failure.",
+        "HTTP clients here treat 401 unauthorized as retryable.",
+        "Reviewers should run /mcp list when MCP issues detected in CI.",
+    ] {
+        assert_eq!(classify_infrastructure_line(line), None, "{line}");
+    }
+}
+
+#[test]
+fn tool_diagnostic_formats_are_recognised() {
+    for (line, kind) in [
+        (
+            "API Error: 429 {\"type\":\"rate_limit_error\"}",
+            InfrastructureNoteKind::Quota,
+        ),
+        (
+            "ERROR: You've hit your usage limit.",
+            InfrastructureNoteKind::Quota,
+        ),
+        (
+            "stream error: 429 Too Many Requests; retrying 1/5 in 200ms",
+            InfrastructureNoteKind::Quota,
+        ),
+        ("HTTP 401 Invalid API key", InfrastructureNoteKind::Auth),
+        (
+            "{\"error\":{\"code\":401,\"message\":\"Unauthorized\"}}",
+            InfrastructureNoteKind::Auth,
+        ),
+    ] {
+        assert_eq!(classify_infrastructure_line(line), Some(kind), "{line}");
+    }
+}
+
+#[test]
+fn fenced_blocks_are_never_split() {
+    let text = "\
+```text
+Error: quota exceeded for project demo
+```
+Error: quota exceeded
+";
+    let channels = split_review_channels(text);
+    assert!(
+        channels
+            .reviewer_text
+            .contains("```text\nError: quota exceeded for project demo\n```")
+    );
+    assert_eq!(channels.infrastructure_notes.len(), 1);
+    assert_eq!(
+        channels.infrastructure_notes[0].message,
+        "Error: quota exceeded"
+    );
+}
@@ -19,7 +19,7 @@ use crate::review_routing::{
 use super::bug_class_pipeline::{
     maybe_extract_recurring_bug_class_skills, resolve_review_iterations,
 };
-use super::execute::{compute_diff_fingerprint, execute_review_with_tier_filter};
+use super::execute::{compute_diff_fingerprint_for_paths, execute_review_with_tier_filter};
 use super::output::ReviewerOutcome;
 use super::output::{
     GEMINI_AUTH_PROMPT_STATUS_REASON, persist_review_verdict_artifact, print_reviewer_outcomes,
@@ -32,6 +32,7 @@ use super::result_handling::{
 use super::reviewers::resolve_multi_reviewer_pool_with_catalog;
 use csa_session::ReviewDiffSize;
 use csa_session::state::ReviewSessionMeta;
+use std::io::Write;
 use std::path::Path;
 
 impl ReviewArgs {
@@ -78,15 +79,25 @@ pub(super) struct MultiReviewerReviewContext<'a> {
     pub current_session_id: Option<&'a str>,
     pub current_depth: u32,
     pub startup_env: &'a crate::startup_env::StartupSubtreeEnv,
+    pub report: &'a mut super::report::ReviewReport,
 }
 
 pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_>) -> Result<i32> {
+    let started_at = std::time::Instant::now();
     if ctx.args.fix {
         anyhow::bail!("--fix is not supported when --reviewers > 1");
     }
     if ctx.args.session.is_some() {
         anyhow::bail!("--session is only supported when --reviewers=1");
     }
+    if let Some(min_agreement) = ctx.args.min_agreement
+        && min_agreement as usize > ctx.reviewers
+    {
+        anyhow::bail!(
+            "--min-agreement {min_agreement} exceeds the {} reviewers of this run",
+            ctx.reviewers
+        );
+    }
 
     let consensus_strategy = parse_consensus_strategy(&ctx.args.consensus)?;
     let reviewer_pool = resolve_multi_reviewer_pool_with_catalog(
@@ -257,7 +268,8 @@ pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_
         .map(|outcome| resolve_review_iterations(ctx.project_root, &outcome.session_id))
         .unwrap_or(1);
     let head_sha = csa_session::detect_git_head(ctx.project_root).unwrap_or_default();
-    let diff_fingerprint = compute_diff_fingerprint(ctx.project_root, ctx.scope);
+    let diff_fingerprint =
+        compute_diff_fingerprint_for_paths(ctx.project_root, ctx.scope, &ctx.args.paths);
     persist_multi_review_sidecars(
         ctx.project_root,
         parent_startup_env.session_dir().map(Path::new),
@@ -268,6 +280,7 @@ pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_
             review_iterations,
             diff_fingerprint: diff_fingerprint.clone(),
             review_mode: Some(ctx.args.effective_review_mode().as_str()),
+            path_filters: &ctx.args.paths,
         },
         super::diff_size::ReviewDiffReport {
             diff_size: ctx.diff_size,
@@ -294,11 +307,26 @@ pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_
         && outcomes
             .iter()
             .all(|outcome| outcome.verdict == UNAVAILABLE);
-    let final_verdict = super::multi_repo_write_audit::final_verdict_for_multi_review(
-        all_reviewers_unavailable,
-        repo_write_audit_blocked,
-        &consensus_result,
-    );
+    let finding_consensus = match ctx.args.min_agreement {
+        Some(min_agreement) => load_finding_consensus_or_warn(
+            ctx.project_root,
+            ctx.reviewers,
+            &outcomes,
+            &parent_startup_env,
+            min_agreement as usize,
+        ),
+        None => None,
+    };
+    let final_verdict = match &finding_consensus {
+        Some(consensus) if !all_reviewers_unavailable && !repo_write_audit_blocked => {
+            consensus.verdict()
+        }
+        _ => super::multi_repo_write_audit::final_verdict_for_multi_review(
+            all_reviewers_unavailable,
+            repo_write_audit_blocked,
+            &consensus_result,
+        ),
+    };
     let agreement = agreement_level(&consensus_result);
     let consensus_artifacts = super::parent_artifacts::MultiReviewerConsensusArtifacts {
         project_root: ctx.project_root,
@@ -308,12 +336,14 @@ pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_
         all_reviewers_unavailable,
         head_sha: &head_sha,
         scope: ctx.scope,
+        path_filters: &ctx.args.paths,
         // Parent consensus uses run-level mode even when reviewer artifacts lack it (#1817).
         run_review_mode: Some(ctx.args.effective_review_mode().as_str()),
         review_iterations,
         diff_fingerprint: diff_fingerprint.clone(),
         diff_size: ctx.diff_size,
         large_diff_warning: ctx.large_diff_warning,
+        finding_consensus: finding_consensus.as_ref(),
     };
     if let Err(err) = super::parent_artifacts::write_multi_reviewer_consensus_artifacts(
         consensus_artifacts,
@@ -325,29 +355,37 @@ pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_
         );
     }
 
-    print_reviewer_outcomes(&outcomes);
+    print_reviewer_outcomes(&outcomes, ctx.report)?;
 
-    println!(
+    writeln!(
+        ctx.report,
         "===== Consensus =====\nstrategy: {}\nconsensus_reached: {}\nagreement_level: {:.0}%\nfinal_decision: {final_verdict}\nindividual_verdicts:",
         consensus_strategy_label(consensus_result.strategy_used),
         consensus_result.consensus_reached,
         agreement * 100.0,
-    );
+    )?;
     for outcome in &outcomes {
         if excluded_from_consensus.contains(&outcome.reviewer_index) {
-            println!(
+            writeln!(
+                ctx.report,
                 "- reviewer {} ({}) => {} (excluded_from_consensus: permanent quota unavailable)",
                 outcome.reviewer_index + 1,
                 outcome.tool,
                 outcome.verdict
-            );
+            )?;
         } else {
-            println!(
+            writeln!(
+                ctx.report,
                 "- reviewer {} ({}) => {}",
                 outcome.reviewer_index + 1,
                 outcome.tool,
                 outcome.verdict
-            );
+            )?;
+        }
+    }
+    if let Some(consensus) = &finding_consensus {
+        for line in consensus.summary_lines() {
+            writeln!(ctx.report, "{line}")?;
         }
     }
 
@@ -355,8 +393,58 @@ pub(super) async fn run_multi_reviewer_review(ctx: MultiReviewerReviewContext<'_
         .iter()
         .map(|outcome| outcome.session_id.clone())
         .collect::<Vec<_>>();
+    ctx.report.record_sessions(&review_session_ids);
     maybe_extract_recurring_bug_class_skills(ctx.project_root, &review_session_ids);
-    Ok(multi_reviewer_exit_code(final_verdict))
+    let exit_code = multi_reviewer_exit_code(final_verdict);
+    if ctx.current_depth == 0
+        && let Some(session_id) = parent_startup_env
+            .session_id()
+            .or(review_session_ids.first().map(String::as_str))
+    {
+        crate::session_notify::notify_session_finished(
+            session_id,
+            &format!("{} reviewers", ctx.reviewers),
+            ctx.project_root,
+            final_verdict,
+            exit_code,
+            started_at.elapsed().as_secs(),
+        )
+        .await;
+    }
+    Ok(exit_code)
+}
+
+/// `--min-agreement` per-finding consensus, or `None` to keep the vote-based
+/// verdict when reviewer findings are missing or unreadable.
+fn load_finding_consensus_or_warn(
+    project_root: &Path,
+    reviewers: usize,
+    outcomes: &[ReviewerOutcome],
+    startup_env: &crate::startup_env::StartupSubtreeEnv,
+    min_agreement: usize,
+) -> Option<super::finding_agreement::FindingConsensus> {
+    match super::parent_artifacts::load_finding_consensus(
+        project_root,
+        reviewers,
+        outcomes,
+        startup_env,
+        min_agreement,
+    ) {
+        Ok(Some(consensus)) => Some(consensus),
+        Ok(None) => {
+            warn!(
+                "A HAS_ISSUES reviewer persisted no findings; --min-agreement falls back to the reviewer-vote consensus"
+            );
+            None
+        }
+        Err(err) => {
+            warn!(
+                error = %err,
+                "Failed to load reviewer findings; --min-agreement falls back to the reviewer-vote consensus"
+            );
+            None
+        }
+    }
 }
 
 fn parent_startup_env_for_multi_review(
@@ -422,6 +510,7 @@ pub(super) struct ReviewRunMeta<'a> {
     pub(super) review_iterations: u32,
     pub(super) diff_fingerprint: Option<String>,
     pub(super) review_mode: Option<&'a str>,
+    pub(super) path_filters: &'a [String],
 }
 
 pub(super) fn persist_multi_review_sidecars(
@@ -455,6 +544,7 @@ pub(super) fn persist_multi_review_sidecars(
             failure_reason: None,
             tool: outcome.tool.as_str().to_string(),
             scope: scope.to_string(),
+            path_filters: run_meta.path_filters.to_vec(),
             exit_code: outcome.exit_code,
             fix_attempted: false,
             fix_rounds: 0,
@@ -183,6 +183,7 @@ fn repo_write_audit_blocks_clean_multi_reviewer_majority_and_parent_verdict() {
             review_iterations: 1,
             diff_fingerprint: None,
             review_mode: Some("standard"),
+            path_filters: &[],
         },
         super::super::diff_size::ReviewDiffReport {
             diff_size: None,
@@ -252,6 +253,7 @@ fn repo_write_audit_blocks_clean_multi_reviewer_majority_and_parent_verdict() {
             diff_fingerprint: None,
             diff_size: None,
             large_diff_warning: None,
+            finding_consensus: None,
         },
         &startup_env_for_parent_session(&parent_dir, parent_session_id),
     )
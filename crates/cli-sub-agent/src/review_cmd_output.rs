@@ -126,6 +126,8 @@ pub(super) fn persist_review_verdict_artifact(
                 }
             };
             apply_review_meta_to_artifact(&mut artifact, meta);
+            artifact.infrastructure_notes =
+                super::findings_toml::load_infrastructure_notes(&session_dir);
             attach_no_provider_launch_diagnostic(&session_dir, meta, &mut artifact);
             if let Err(error) = enforce_final_verdict_consistency(&session_dir, &mut artifact) {
                 warn!(
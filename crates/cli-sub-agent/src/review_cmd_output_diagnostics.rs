@@ -1,6 +1,7 @@
 use crate::review_consensus::{CLEAN, HAS_ISSUES};
 use csa_core::gemini::RATE_LIMIT_PATTERNS;
 use csa_core::types::ToolName;
+use std::io::Write;
 
 #[derive(Debug, Clone)]
 pub(in crate::review_cmd) struct ReviewerOutcome {
@@ -19,21 +20,26 @@ impl ReviewerOutcome {
     }
 }
 
-pub(in crate::review_cmd) fn print_reviewer_outcomes(outcomes: &[ReviewerOutcome]) {
+pub(in crate::review_cmd) fn print_reviewer_outcomes(
+    outcomes: &[ReviewerOutcome],
+    report: &mut impl Write,
+) -> std::io::Result<()> {
     for o in outcomes {
         let r = o.reviewer_index + 1;
-        println!(
+        writeln!(
+            report,
             "===== Reviewer {r} ({}) | verdict={} | exit_code={} =====",
             o.tool, o.verdict, o.exit_code
-        );
+        )?;
         if let Some(ref d) = o.diagnostic {
             eprintln!("[csa-review] Reviewer {r} tool failure: {d}");
         }
-        print!("{}", o.output);
+        write!(report, "{}", o.output)?;
         if !o.output.ends_with('\n') {
-            println!();
+            writeln!(report)?;
         }
     }
+    Ok(())
 }
 
 /// Detect known tool-level diagnostic messages that indicate the review tool
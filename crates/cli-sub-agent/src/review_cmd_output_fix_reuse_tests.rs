@@ -28,6 +28,7 @@ fn make_review_meta(
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "diff".to_string(),
+        path_filters: Vec::new(),
         exit_code: if decision == ReviewDecision::Pass {
             0
         } else {
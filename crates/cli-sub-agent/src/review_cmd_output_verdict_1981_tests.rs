@@ -26,6 +26,7 @@ fn make_review_meta_with_decision(
         failure_reason: None,
         tool: ToolName::Codex.as_str().to_string(),
         scope: "diff".to_string(),
+        path_filters: Vec::new(),
         exit_code: match decision {
             ReviewDecision::Pass | ReviewDecision::Skip => 0,
             ReviewDecision::Fail | ReviewDecision::Uncertain | ReviewDecision::Unavailable => 1,
@@ -24,6 +24,7 @@ use super::diff_size::{
     LargeDiffWarning, ReviewDiffReport, apply_large_diff_warning,
     write_review_meta_with_diff_report,
 };
+use super::finding_agreement::FindingConsensus;
 use super::output::ReviewerOutcome;
 
 #[path = "review_cmd_parent_verdict.rs"]
@@ -38,6 +39,8 @@ pub(super) struct MultiReviewerConsensusArtifacts<'a> {
     pub(super) all_reviewers_unavailable: bool,
     pub(super) head_sha: &'a str,
     pub(super) scope: &'a str,
+    /// `--path` globs the reviewed diff was limited to.
+    pub(super) path_filters: &'a [String],
     /// Run-level review mode (`effective_review_mode`), recorded on the parent
     /// meta/verdict/marker so the `--check-verdict` mode filter matches even when
     /// the per-reviewer artifacts carry none (#1817).
@@ -46,6 +49,9 @@ pub(super) struct MultiReviewerConsensusArtifacts<'a> {
     pub(super) diff_fingerprint: Option<String>,
     pub(super) diff_size: Option<&'a ReviewDiffSize>,
     pub(super) large_diff_warning: Option<LargeDiffWarning>,
+    /// Per-finding agreement (`--min-agreement`); when set, the parent artifacts
+    /// keep only its accepted findings and follow `final_verdict` directly.
+    pub(super) finding_consensus: Option<&'a FindingConsensus>,
 }
 
 pub(super) async fn clear_multi_reviewer_artifact_dirs(
@@ -133,14 +139,34 @@ fn load_multi_reviewer_artifacts(
     reviewers: usize,
     outcomes: &[ReviewerOutcome],
 ) -> Result<(Vec<ReviewArtifact>, BTreeSet<usize>)> {
+    let (reviewer_artifacts, persisted_indices) =
+        load_indexed_reviewer_artifacts(project_root, Some(output_dir), reviewers, outcomes)?;
+    Ok((
+        reviewer_artifacts
+            .into_iter()
+            .map(|(_, artifact)| artifact)
+            .collect(),
+        persisted_indices,
+    ))
+}
+
+/// Reviewer artifacts paired with their 1-based reviewer index.
+fn load_indexed_reviewer_artifacts(
+    project_root: &Path,
+    output_dir: Option<&Path>,
+    reviewers: usize,
+    outcomes: &[ReviewerOutcome],
+) -> Result<(Vec<(usize, ReviewArtifact)>, BTreeSet<usize>)> {
     let mut reviewer_artifacts = Vec::new();
     let mut persisted_indices = BTreeSet::new();
     for reviewer_index in 1..=reviewers {
-        let mut artifact_paths = vec![
-            output_dir
-                .join(format!("reviewer-{reviewer_index}"))
-                .join("review-findings.json"),
-        ];
+        let mut artifact_paths: Vec<PathBuf> = output_dir
+            .map(|dir| {
+                dir.join(format!("reviewer-{reviewer_index}"))
+                    .join("review-findings.json")
+            })
+            .into_iter()
+            .collect();
         if let Some(outcome) = outcomes
             .iter()
             .find(|outcome| outcome.reviewer_index + 1 == reviewer_index)
@@ -161,7 +187,7 @@ fn load_multi_reviewer_artifacts(
             let content = fs::read_to_string(&artifact_path)
                 .with_context(|| format!("failed to read {}", artifact_path.display()))?;
             let artifact = parse_reviewer_artifact(&artifact_path, &content)?;
-            reviewer_artifacts.push(artifact);
+            reviewer_artifacts.push((reviewer_index, artifact));
             persisted_indices.insert(reviewer_index);
             break;
         }
@@ -169,6 +195,30 @@ fn load_multi_reviewer_artifacts(
     Ok((reviewer_artifacts, persisted_indices))
 }
 
+/// Per-finding agreement over the persisted reviewer artifacts (`--min-agreement`).
+///
+/// `None` when a reviewer that voted `HAS_ISSUES` persisted no structured findings:
+/// its dissent cannot be counted per finding, so the vote-based verdict stands (#1659).
+pub(super) fn load_finding_consensus(
+    project_root: &Path,
+    reviewers: usize,
+    outcomes: &[ReviewerOutcome],
+    startup_env: &StartupSubtreeEnv,
+    min_agreement: usize,
+) -> Result<Option<FindingConsensus>> {
+    let parent_dir = resolve_parent_session(startup_env).map(|(session_dir, _)| session_dir);
+    let (reviewer_artifacts, persisted_indices) =
+        load_indexed_reviewer_artifacts(project_root, parent_dir.as_deref(), reviewers, outcomes)?;
+    if !dissenting_findings_persisted(outcomes, &persisted_indices) {
+        return Ok(None);
+    }
+    Ok(Some(FindingConsensus::from_reviewer_artifacts(
+        &reviewer_artifacts,
+        reviewers,
+        min_agreement,
+    )))
+}
+
 /// Whether every reviewer that voted `HAS_ISSUES` persisted a structured findings
 /// artifact. When false, at least one dissenting reviewer's findings never reached
 /// disk (e.g. quota/auth failure forced a non-zero exit before structured output was
@@ -209,6 +259,7 @@ pub(super) fn write_multi_reviewer_parent_artifacts(
         run_review_mode,
         None,
         None,
+        None,
     )
 }
 
@@ -224,21 +275,32 @@ fn write_multi_reviewer_parent_artifacts_with_diff_size(
     run_review_mode: Option<&str>,
     diff_size: Option<&ReviewDiffSize>,
     large_diff_warning: Option<LargeDiffWarning>,
+    finding_consensus: Option<&FindingConsensus>,
 ) -> Result<()> {
     let Some((session_dir, session_id)) = resolve_parent_session(startup_env) else {
         return Ok(());
     };
-    let (reviewer_artifacts, persisted_indices) =
-        load_multi_reviewer_artifacts(project_root, &session_dir, reviewers, outcomes)?;
-    let dissent_findings_persisted = dissenting_findings_persisted(outcomes, &persisted_indices);
-    let consolidated = build_consolidated_artifact(reviewer_artifacts, &session_id);
-    let parent_decision = parent_review_decision(
-        &consolidated,
-        final_verdict,
-        outcomes,
-        all_reviewers_unavailable,
-        dissent_findings_persisted,
-    );
+    let (consolidated, parent_decision) = if let Some(consensus) = finding_consensus {
+        consensus.write(&session_dir)?;
+        (
+            consensus.accepted_artifact(&session_id),
+            consensus_review_decision(final_verdict),
+        )
+    } else {
+        let (reviewer_artifacts, persisted_indices) =
+            load_multi_reviewer_artifacts(project_root, &session_dir, reviewers, outcomes)?;
+        let dissent_findings_persisted =
+            dissenting_findings_persisted(outcomes, &persisted_indices);
+        let consolidated = build_consolidated_artifact(reviewer_artifacts, &session_id);
+        let parent_decision = parent_review_decision(
+            &consolidated,
+            final_verdict,
+            outcomes,
+            all_reviewers_unavailable,
+            dissent_findings_persisted,
+        );
+        (consolidated, parent_decision)
+    };
     let parent_verdict = parent_legacy_verdict(parent_decision, final_verdict);
     let parent_artifact = parent_artifact_for_decision(&consolidated, parent_decision);
     write_consolidated_artifact(&parent_artifact, &session_dir)?;
@@ -270,6 +332,7 @@ fn write_multi_reviewer_parent_artifacts_with_diff_size(
             &meta.verdict,
             outcomes.first().map(|o| o.session_id.as_str()),
             &meta.scope,
+            &meta.path_filters,
             meta.review_mode.as_deref(),
         );
     }
@@ -285,6 +348,7 @@ pub(super) fn write_multi_reviewer_consensus_artifacts(
     let final_review_meta = parent_consensus_review_meta(
         ctx.head_sha,
         ctx.scope,
+        ctx.path_filters,
         ctx.final_verdict,
         ctx.review_iterations,
         ctx.diff_fingerprint.clone(),
@@ -301,6 +365,7 @@ pub(super) fn write_multi_reviewer_consensus_artifacts(
         ctx.run_review_mode,
         ctx.diff_size,
         ctx.large_diff_warning,
+        ctx.finding_consensus,
     )?;
     if final_review_meta.is_none() {
         write_standalone_consensus_review_artifacts(&ctx)?;
@@ -314,18 +379,31 @@ pub(super) fn write_standalone_consensus_review_artifacts(
     let Some((target, session_dir)) = resolve_standalone_consensus_carrier(ctx)? else {
         return Ok(None);
     };
-    let (reviewer_artifacts, persisted_indices) =
-        load_multi_reviewer_artifacts(ctx.project_root, &session_dir, ctx.reviewers, ctx.outcomes)?;
-    let dissent_findings_persisted =
-        dissenting_findings_persisted(ctx.outcomes, &persisted_indices);
-    let consolidated = build_consolidated_artifact(reviewer_artifacts, &target.session_id);
-    let decision = parent_review_decision(
-        &consolidated,
-        ctx.final_verdict,
-        ctx.outcomes,
-        ctx.all_reviewers_unavailable,
-        dissent_findings_persisted,
-    );
+    let (consolidated, decision) = if let Some(consensus) = ctx.finding_consensus {
+        consensus.write(&session_dir)?;
+        (
+            consensus.accepted_artifact(&target.session_id),
+            consensus_review_decision(ctx.final_verdict),
+        )
+    } else {
+        let (reviewer_artifacts, persisted_indices) = load_multi_reviewer_artifacts(
+            ctx.project_root,
+            &session_dir,
+            ctx.reviewers,
+            ctx.outcomes,
+        )?;
+        let dissent_findings_persisted =
+            dissenting_findings_persisted(ctx.outcomes, &persisted_indices);
+        let consolidated = build_consolidated_artifact(reviewer_artifacts, &target.session_id);
+        let decision = parent_review_decision(
+            &consolidated,
+            ctx.final_verdict,
+            ctx.outcomes,
+            ctx.all_reviewers_unavailable,
+            dissent_findings_persisted,
+        );
+        (consolidated, decision)
+    };
     let verdict = parent_legacy_verdict(decision, ctx.final_verdict);
     let artifact = parent_artifact_for_decision(&consolidated, decision);
     write_consolidated_artifact(&artifact, &session_dir)?;
@@ -343,6 +421,7 @@ pub(super) fn write_standalone_consensus_review_artifacts(
         failure_reason: None,
         tool: "consensus".to_string(),
         scope: ctx.scope.to_string(),
+        path_filters: ctx.path_filters.to_vec(),
         exit_code: if decision.is_clean() { 0 } else { 1 },
         fix_attempted: false,
         fix_rounds: 0,
@@ -373,6 +452,7 @@ pub(super) fn write_standalone_consensus_review_artifacts(
         &meta.verdict,
         Some(&target.session_id),
         &meta.scope,
+        &meta.path_filters,
         meta.review_mode.as_deref(),
     );
     Ok(Some(target.session_id.clone()))
@@ -396,6 +476,7 @@ fn resolve_standalone_consensus_carrier<'a>(
 pub(super) fn parent_consensus_review_meta(
     head_sha: &str,
     scope: &str,
+    path_filters: &[String],
     final_verdict: &str,
     review_iterations: u32,
     diff_fingerprint: Option<String>,
@@ -415,6 +496,7 @@ pub(super) fn parent_consensus_review_meta(
         failure_reason: None,
         tool: "consensus".to_string(),
         scope: scope.to_string(),
+        path_filters: path_filters.to_vec(),
         exit_code: if decision == ReviewDecision::Pass {
             0
         } else {
@@ -575,7 +657,7 @@ fn review_decision_from_produced_outcomes(outcomes: &[ReviewerOutcome]) -> Optio
     saw_produced.then_some(ReviewDecision::Pass)
 }
 
-fn is_blocking_severity(severity: &Severity) -> bool {
+pub(super) fn is_blocking_severity(severity: &Severity) -> bool {
     matches!(
         severity,
         Severity::Critical | Severity::High | Severity::Medium
@@ -0,0 +1,277 @@
+//! `csa review --post-to-pr <N>`: publish the run's findings on a GitHub PR.
+//!
+//! Findings on a line of the PR diff become review comments on that line;
+//! the others are listed in the review body next to the verdict. Every
+//! comment carries a hidden marker keyed by the finding, so a re-review edits
+//! the comment it posted before instead of adding a duplicate. GitHub access
+//! goes through `gh` with the project's configured auth (see `gh_env`).
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csa_session::{Finding, FindingId, SeveritySummary, anchor_hash};
+use serde::{Deserialize, Serialize};
+
+use super::hunk_cache::parse_diff_hunks;
+use super::run_findings::ReviewFindings;
+use super::sarif::severity_label;
+use crate::gh_env::{configured_gh_env, run_gh_with_retry};
+
+const FINDING_MARKER_PREFIX: &str = "<!-- csa-review-finding:";
+
+/// Review comment of a PR, as listed by the GitHub API.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(super) struct PrComment {
+    pub(super) id: u64,
+    pub(super) body: String,
+}
+
+/// New inline comment of the review.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(super) struct NewPrComment {
+    pub(super) path: String,
+    pub(super) line: u32,
+    pub(super) side: &'static str,
+    pub(super) body: String,
+}
+
+/// What to send to GitHub for one run.
+#[derive(Debug, Default)]
+pub(super) struct PrReviewPlan {
+    /// Earlier comments of the same findings, with their new body.
+    pub(super) updates: Vec<(u64, String)>,
+    pub(super) comments: Vec<NewPrComment>,
+    pub(super) body: String,
+}
+
+#[derive(Serialize)]
+struct PrReviewRequest<'a> {
+    commit_id: &'a str,
+    event: &'static str,
+    body: &'a str,
+    comments: &'a [NewPrComment],
+}
+
+/// Stable key of a finding across runs: its FindingId, else a hash of its
+/// file, rule and line.
+pub(super) fn finding_key(finding: &Finding) -> String {
+    match FindingId::parse(&finding.fid) {
+        Some(fid) => fid.into_inner(),
+        None => {
+            let line = finding
+                .line
+                .map(|line| line.to_string())
+                .unwrap_or_default();
+            anchor_hash(&[
+                csa_session::normalize_path(&finding.file).as_str(),
+                finding.rule_id.as_str(),
+                line.as_str(),
+            ])
+        }
+    }
+}
+
+fn finding_marker(finding: &Finding) -> String {
+    format!("{FINDING_MARKER_PREFIX} {} -->", finding_key(finding))
+}
+
+fn comment_body(finding: &Finding) -> String {
+    format!(
+        "**[{}] {}**\n\n{}\n\n{}",
+        severity_label(&finding.severity),
+        finding.rule_id,
+        finding.summary.trim(),
+        finding_marker(finding)
+    )
+}
+
+/// Verdict label of the run's exit code.
+fn verdict_label(exit_code: i32) -> &'static str {
+    match exit_code {
+        0 => "PASS",
+        1 => "FAIL",
+        _ => "UNAVAILABLE",
+    }
+}
+
+/// Plan the review of `findings` against the PR diff `pr_diff` and the
+/// comments already on the PR.
+pub(super) fn plan_pr_review(
+    findings: &ReviewFindings,
+    pr_diff: &str,
+    existing: &[PrComment],
+    exit_code: i32,
+) -> PrReviewPlan {
+    let hunks = parse_diff_hunks(pr_diff);
+    let existing: HashMap<&str, &PrComment> = existing
+        .iter()
+        .filter_map(|comment| {
+            let start = comment.body.find(FINDING_MARKER_PREFIX)?;
+            let marker = &comment.body[start..];
+            let end = marker.find("-->")? + "-->".len();
+            Some((&marker[..end], comment))
+        })
+        .collect();
+
+    let mut plan = PrReviewPlan::default();
+    let mut outside_diff = Vec::new();
+    for finding in &findings.new {
+        let path = csa_session::normalize_path(&finding.file);
+        let in_diff = finding.line.filter(|line| {
+            hunks.iter().any(|hunk| {
+                hunk.file == path && (hunk.new_lines.0..=hunk.new_lines.1).contains(line)
+            })
+        });
+        let body = comment_body(finding);
+        match existing.get(finding_marker(finding).as_str()) {
+            Some(comment) => {
+                if comment.body != body {
+                    plan.updates.push((comment.id, body));
+                }
+            }
+            None => match in_diff {
+                Some(line) => plan.comments.push(NewPrComment {
+                    path,
+                    line,
+                    side: "RIGHT",
+                    body,
+                }),
+                None => outside_diff.push(finding),
+            },
+        }
+    }
+
+    let summary = SeveritySummary::from_findings(&findings.new);
+    plan.body = format!(
+        "**CSA review: {}** (exit {exit_code})\n\ncritical={} high={} medium={} low={}",
+        verdict_label(exit_code),
+        summary.critical,
+        summary.high,
+        summary.medium,
+        summary.low
+    );
+    if !findings.known.is_empty() {
+        let _ = write!(
+            plan.body,
+            "; {} known finding(s) from the baseline not posted",
+            findings.known.len()
+        );
+    }
+    if !outside_diff.is_empty() {
+        plan.body.push_str("\n\nFindings outside the PR diff:");
+        for finding in outside_diff {
+            let location = match finding.line {
+                Some(line) => format!("{}:{line}", finding.file),
+                None => finding.file.clone(),
+            };
+            let _ = write!(
+                plan.body,
+                "\n- [{}] `{location}` {}: {}",
+                severity_label(&finding.severity),
+                finding.rule_id,
+                finding.summary.lines().next().unwrap_or_default()
+            );
+        }
+    }
+    plan
+}
+
+/// Post the run's findings and verdict to PR `pr`.
+pub(super) async fn post_review_to_pr(
+    project_root: &Path,
+    pr: u64,
+    findings: &ReviewFindings,
+    exit_code: i32,
+) -> Result<String> {
+    let configured_env = configured_gh_env(project_root)?;
+    let gh = |args: Vec<String>, input: Option<String>| {
+        let configured_env = configured_env.clone();
+        async move {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            run_gh_with_retry(
+                project_root,
+                &args,
+                input.as_deref(),
+                configured_env.as_ref(),
+            )
+            .await
+        }
+    };
+    let pr_path = format!("repos/{{owner}}/{{repo}}/pulls/{pr}");
+
+    let head = gh(
+        vec![
+            "api".into(),
+            pr_path.clone(),
+            "--jq".into(),
+            ".head.sha".into(),
+        ],
+        None,
+    )
+    .await?;
+    let pr_diff = gh(vec!["pr".into(), "diff".into(), pr.to_string()], None).await?;
+    let listed = gh(
+        vec![
+            "api".into(),
+            "--paginate".into(),
+            format!("{pr_path}/comments"),
+            "--jq".into(),
+            ".[] | {id, body}".into(),
+        ],
+        None,
+    )
+    .await?;
+    let existing = listed
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<PrComment>)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("failed to parse the PR review comments listed by gh")?;
+
+    let plan = plan_pr_review(findings, &pr_diff, &existing, exit_code);
+    for (id, body) in &plan.updates {
+        let request = serde_json::json!({ "body": body }).to_string();
+        gh(
+            vec![
+                "api".into(),
+                "--method".into(),
+                "PATCH".into(),
+                format!("repos/{{owner}}/{{repo}}/pulls/comments/{id}"),
+                "--input".into(),
+                "-".into(),
+            ],
+            Some(request),
+        )
+        .await?;
+    }
+    let request = serde_json::to_string(&PrReviewRequest {
+        commit_id: head.trim(),
+        event: "COMMENT",
+        body: &plan.body,
+        comments: &plan.comments,
+    })
+    .context("failed to serialize the PR review")?;
+    gh(
+        vec![
+            "api".into(),
+            "--method".into(),
+            "POST".into(),
+            format!("{pr_path}/reviews"),
+            "--input".into(),
+            "-".into(),
+        ],
+        Some(request),
+    )
+    .await?;
+    Ok(format!(
+        "post_to_pr=#{pr}: {} new comment(s), {} updated",
+        plan.comments.len(),
+        plan.updates.len()
+    ))
+}
+
+#[cfg(test)]
+#[path = "review_cmd_post_pr_tests.rs"]
+mod tests;
@@ -0,0 +1,130 @@
+use csa_session::Severity;
+
+use super::*;
+
+const PR_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,3 +10,4 @@ fn a() {
+ let x = 1;
++let y = x.unwrap();
+ let z = 2;
+ }
+";
+
+fn finding(file: &str, line: Option<u32>, rule_id: &str) -> Finding {
+    Finding {
+        summary: "unwrap on user input".to_string(),
+        ..crate::test_review_findings::finding(Severity::High, "f1", file, line, rule_id)
+    }
+}
+
+fn new_findings(findings: Vec<Finding>) -> ReviewFindings {
+    ReviewFindings {
+        new: findings,
+        ..ReviewFindings::default()
+    }
+}
+
+#[test]
+fn findings_in_the_diff_become_line_comments_and_others_go_to_the_body() {
+    let findings = new_findings(vec![
+        finding("./src/lib.rs", Some(11), "rust.no-unwrap"),
+        finding("src/lib.rs", Some(40), "rust.dead-code"),
+        finding("src/main.rs", None, "rust.style"),
+    ]);
+    let plan = plan_pr_review(&findings, PR_DIFF, &[], 1);
+
+    assert!(plan.updates.is_empty());
+    assert_eq!(plan.comments.len(), 1);
+    assert_eq!(plan.comments[0].path, "src/lib.rs");
+    assert_eq!(plan.comments[0].line, 11);
+    assert_eq!(plan.comments[0].side, "RIGHT");
+    assert!(
+        plan.comments[0].body.starts_with(
+            "**[high] rust.no-unwrap**\n\nunwrap on user input\n\n<!-- csa-review-finding: "
+        ),
+        "{}",
+        plan.comments[0].body
+    );
+    assert!(
+        plan.body
+            .starts_with("**CSA review: FAIL** (exit 1)\n\ncritical=0 high=3 medium=0 low=0"),
+        "{}",
+        plan.body
+    );
+    assert!(
+        plan.body
+            .contains("\n- [high] `src/lib.rs:40` rust.dead-code: ")
+    );
+    assert!(plan.body.contains("\n- [high] `src/main.rs` rust.style: "));
+}
+
+#[test]
+fn re_review_updates_the_earlier_comment_of_a_finding() {
+    let first = new_findings(vec![finding("src/lib.rs", Some(11), "rust.no-unwrap")]);
+    let posted = plan_pr_review(&first, PR_DIFF, &[], 1).comments[0]
+        .body
+        .clone();
+    let existing = [
+        PrComment {
+            id: 7,
+            body: posted.clone(),
+        },
+        PrComment {
+            id: 8,
+            body: "human comment".to_string(),
+        },
+    ];
+
+    let unchanged = plan_pr_review(&first, PR_DIFF, &existing, 1);
+    assert!(unchanged.comments.is_empty());
+    assert!(unchanged.updates.is_empty());
+
+    let mut reworded = finding("src/lib.rs", Some(11), "rust.no-unwrap");
+    reworded.summary = "unwrap panics on malformed input".to_string();
+    let plan = plan_pr_review(&new_findings(vec![reworded]), PR_DIFF, &existing, 1);
+    assert!(plan.comments.is_empty());
+    assert_eq!(plan.updates.len(), 1);
+    assert_eq!(plan.updates[0].0, 7);
+    assert!(
+        plan.updates[0]
+            .1
+            .contains("unwrap panics on malformed input")
+    );
+}
+
+#[test]
+fn finding_key_prefers_the_stable_finding_id() {
+    let mut stable = finding("src/lib.rs", Some(11), "rust.no-unwrap");
+    stable.fid = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string();
+    assert_eq!(finding_key(&stable), "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+    let local = finding("./src/lib.rs", Some(11), "rust.no-unwrap");
+    assert_eq!(
+        finding_key(&local),
+        finding_key(&finding("src/lib.rs", Some(11), "rust.no-unwrap"))
+    );
+    assert_ne!(
+        finding_key(&local),
+        finding_key(&finding("src/lib.rs", Some(12), "rust.no-unwrap"))
+    );
+}
+
+#[test]
+fn clean_runs_post_a_pass_verdict_and_count_known_findings() {
+    let findings = ReviewFindings {
+        new: Vec::new(),
+        known: vec![finding("src/lib.rs", Some(11), "rust.no-unwrap")],
+        baseline_applied: true,
+    };
+    let plan = plan_pr_review(&findings, PR_DIFF, &[], 0);
+    assert!(plan.comments.is_empty());
+    assert_eq!(
+        plan.body,
+        "**CSA review: PASS** (exit 0)\n\ncritical=0 high=0 medium=0 low=0; \
+         1 known finding(s) from the baseline not posted"
+    );
+}
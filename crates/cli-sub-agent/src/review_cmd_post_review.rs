@@ -1,4 +1,5 @@
 use std::fs;
+use std::io::{self, Write};
 use std::path::Path;
 
 use csa_core::types::{ReviewDecision, ToolName};
@@ -93,19 +94,20 @@ pub(super) fn build_fix_finding_route(
     }
 }
 
-pub(super) fn emit_post_review_output(output: &str) {
+pub(super) fn emit_post_review_output(output: &str, report: &mut impl Write) -> io::Result<()> {
     let trimmed = output.trim_end();
     if trimmed.is_empty() {
-        return;
+        return Ok(());
     }
 
     // Daemon-mode callers typically observe completion via `csa session wait`,
     // which streams stdout.log only. Mirror the directive there so the normal
     // daemon path can consume it mechanically without tailing stderr.log.
     if std::env::var_os("CSA_DAEMON_SESSION_ID").is_some() {
-        println!("{trimmed}");
+        writeln!(report, "{trimmed}")?;
     }
     eprintln!("{trimmed}");
+    Ok(())
 }
 
 pub(super) fn build_review_failure_suggestion(
@@ -151,15 +153,16 @@ pub(super) fn emit_review_failure_suggestion(
     session_id: &str,
     preceding_output: &str,
     route: Option<&ReviewFailureFixFindingRoute>,
-) {
+    report: &mut impl Write,
+) -> io::Result<()> {
     let Some(suggestion) = build_review_failure_suggestion(decision, session_id, route) else {
-        return;
+        return Ok(());
     };
     if !preceding_output.is_empty() && !preceding_output.ends_with('\n') {
-        println!();
+        writeln!(report)?;
     }
-    print!("{suggestion}");
-    println!("{}", build_review_failure_caller_hint(session_id));
+    write!(report, "{suggestion}")?;
+    writeln!(report, "{}", build_review_failure_caller_hint(session_id))
 }
 
 pub(super) fn build_review_failure_fix_finding_unavailable_explanation(
@@ -226,12 +229,13 @@ pub(super) fn suggest_review_failure_fix(
     meta: &ReviewSessionMeta,
     preceding_output: &str,
     route: Option<&ReviewFailureFixFindingRoute>,
-) {
+    report: &mut impl Write,
+) -> io::Result<()> {
     let decision = meta
         .decision
         .parse::<ReviewDecision>()
         .unwrap_or(ReviewDecision::Uncertain);
-    emit_review_failure_suggestion(decision, &meta.session_id, preceding_output, route);
+    emit_review_failure_suggestion(decision, &meta.session_id, preceding_output, route, report)?;
     if build_review_failure_suggestion(decision, &meta.session_id, route).is_none()
         && let Some(explanation) = build_review_failure_fix_finding_unavailable_explanation(
             decision,
@@ -240,11 +244,12 @@ pub(super) fn suggest_review_failure_fix(
         )
     {
         if !preceding_output.is_empty() && !preceding_output.ends_with('\n') {
-            println!();
+            writeln!(report)?;
         }
-        println!("{explanation}");
+        writeln!(report, "{explanation}")?;
     }
     persist_review_failure_suggestion(project_root, meta, route);
+    Ok(())
 }
 
 pub(super) fn build_review_failure_caller_hint(session_id: &str) -> String {
@@ -263,13 +268,14 @@ pub(super) fn build_post_review_output(
     captured_output: &str,
     decision: ReviewDecision,
     scope: &str,
+    path_filters: &[String],
 ) -> String {
     let trimmed = captured_output.trim_end();
     if csa_hooks::parse_next_step_directive(trimmed).is_some() {
         return trimmed.to_string();
     }
 
-    let Some(directive) = synthesize_post_review_next_step(decision, scope) else {
+    let Some(directive) = synthesize_post_review_next_step(decision, scope, path_filters) else {
         return trimmed.to_string();
     };
 
@@ -280,8 +286,12 @@ pub(super) fn build_post_review_output(
     }
 }
 
-fn synthesize_post_review_next_step(decision: ReviewDecision, scope: &str) -> Option<String> {
-    if decision == ReviewDecision::Pass && review_scope_is_cumulative(scope) {
+fn synthesize_post_review_next_step(
+    decision: ReviewDecision,
+    scope: &str,
+    path_filters: &[String],
+) -> Option<String> {
+    if decision == ReviewDecision::Pass && review_scope_is_cumulative(scope, path_filters) {
         return Some(csa_hooks::format_next_step_directive(
             POST_REVIEW_PR_BOT_CMD,
             true,
@@ -290,8 +300,10 @@ fn synthesize_post_review_next_step(decision: ReviewDecision, scope: &str) -> Op
     None
 }
 
-pub(crate) fn review_scope_is_cumulative(scope: &str) -> bool {
-    scope.starts_with("base:") || scope.starts_with("range:")
+/// Whether the review covered the whole branch diff; a review limited by
+/// `--path` filters never does.
+pub(crate) fn review_scope_is_cumulative(scope: &str, path_filters: &[String]) -> bool {
+    path_filters.is_empty() && (scope.starts_with("base:") || scope.starts_with("range:"))
 }
 
 #[cfg(test)]
@@ -434,6 +446,7 @@ mod tests {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "range:main...HEAD".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
@@ -490,6 +503,7 @@ mod tests {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "range:main...HEAD".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
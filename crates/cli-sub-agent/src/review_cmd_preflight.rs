@@ -14,7 +14,7 @@ pub(crate) fn validate_before_session(
     args: &ReviewArgs,
     startup_env: &StartupSubtreeEnv,
 ) -> Result<()> {
-    if args.check_verdict {
+    if args.check_verdict || args.command.is_some() || args.apply_suggestions {
         return Ok(());
     }
 
@@ -256,6 +256,11 @@ fn validate_host_memory_before_session(
         project_root,
         REVIEW_PREFLIGHT_SESSION_ID,
         projected_spawn_mb,
+        &crate::memory_usage_stats::usage_key(
+            tool.as_str(),
+            None,
+            Some(REVIEWER_SUB_SESSION_TASK_TYPE),
+        ),
     );
     resource_guard
         .check_availability_with_admission(tool.as_str(), Some(admission))
@@ -159,6 +159,7 @@ fn persist_review_pre_exec_unavailable_sidecars(
         failure_reason: Some(failure_reason.clone()),
         tool: tool_name.to_string(),
         scope: super::resolve::derive_scope_for_project(args, project_root),
+        path_filters: args.paths.clone(),
         exit_code: crate::verdict_exit_code::exit_code_from_review_decision(
             ReviewDecision::Unavailable,
         ),
@@ -0,0 +1,94 @@
+//! Report of one `csa review` run.
+//!
+//! The review paths (single, multi-reviewer, chunked) write their report to a
+//! [`ReviewReport`] and record the review sessions they produced in it. With
+//! `--format sarif` the report goes to stderr so stdout carries only the log.
+
+use std::io::{self, Write};
+
+use crate::cli::{ReviewArgs, ReviewFormat};
+
+/// Output sink of a review run.
+#[derive(Debug)]
+pub(super) struct ReviewReport {
+    to_stderr: bool,
+    session_ids: Vec<String>,
+}
+
+/// What a review run returns to its caller.
+#[derive(Debug)]
+pub(super) struct ReviewRun {
+    pub(super) exit_code: i32,
+    /// Review sessions whose findings belong to the run.
+    pub(super) session_ids: Vec<String>,
+}
+
+impl ReviewReport {
+    pub(super) fn for_args(args: &ReviewArgs) -> Self {
+        Self {
+            to_stderr: args.review_format == ReviewFormat::Sarif,
+            session_ids: Vec::new(),
+        }
+    }
+
+    pub(super) fn record_sessions(&mut self, session_ids: &[String]) {
+        self.session_ids.extend(session_ids.iter().cloned());
+    }
+
+    pub(super) fn finish(self, exit_code: i32) -> ReviewRun {
+        ReviewRun {
+            exit_code,
+            session_ids: self.session_ids,
+        }
+    }
+}
+
+impl Write for ReviewReport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.to_stderr {
+            io::stderr().write(buf)
+        } else {
+            io::stdout().write(buf)
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.to_stderr {
+            io::stderr().flush()
+        } else {
+            io::stdout().flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::cli::{Cli, Commands};
+
+    fn review_args(argv: &[&str]) -> ReviewArgs {
+        let argv = ["csa", "review"].iter().chain(argv);
+        match Cli::try_parse_from(argv)
+            .expect("review CLI args should parse")
+            .command
+        {
+            Commands::Review(args) => args,
+            _ => panic!("expected review subcommand"),
+        }
+    }
+
+    #[test]
+    fn sarif_report_goes_to_stderr_and_keeps_recorded_sessions() {
+        let mut report = ReviewReport::for_args(&review_args(&["--review-format", "sarif"]));
+        assert!(report.to_stderr);
+        report.record_sessions(&["01A".to_string()]);
+        report.record_sessions(&["01B".to_string(), "01C".to_string()]);
+
+        let run = report.finish(1);
+        assert_eq!(run.exit_code, 1);
+        assert_eq!(run.session_ids, ["01A", "01B", "01C"]);
+        assert!(!ReviewReport::for_args(&review_args(&[])).to_stderr);
+    }
+}
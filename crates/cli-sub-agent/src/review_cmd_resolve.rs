@@ -315,14 +315,18 @@ Reason: CSA enforces heterogeneity in auto mode and will not fall back."
 /// 1. `--range <from>...<to>` → "range:<from>...<to>"
 /// 2. `--files <pathspec>`    → "files:<pathspec>"
 /// 3. `--commit <sha>`        → "commit:<sha>"
-/// 4. `--diff`                → "uncommitted"
-/// 5. default                 → "base:<branch>" (branch defaults to "main")
+/// 4. `--staged`              → "staged"
+/// 5. `--diff`                → "uncommitted"
+/// 6. default                 → "base:<branch>" (branch defaults to "main")
+///
+/// `--path <glob>` filters are kept apart from the scope and recorded in
+/// `ReviewSessionMeta::path_filters`.
 #[path = "review_cmd_resolve_scope.rs"]
 mod scope;
 #[cfg(test)]
 pub(crate) use scope::derive_scope;
 pub(crate) use scope::{
-    derive_scope_for_project, review_scope_allows_auto_discovery,
+    derive_scope_for_project, push_path_filter_pathspecs, review_scope_allows_auto_discovery,
     validate_single_parent_commit_scope,
 };
 
@@ -367,6 +371,7 @@ pub(crate) fn build_review_instruction(
         context,
         None,
         None,
+        &[],
     );
     crate::review_design_anchor::append_design_anchor(&mut instruction);
     instruction
@@ -380,6 +385,7 @@ fn build_review_instruction_without_design_anchor(
     context: Option<&ResolvedReviewContext>,
     project_root: Option<&Path>,
     pattern: Option<&ResolvedPattern>,
+    path_filters: &[String],
 ) -> String {
     let mut instruction = format!(
         "{ANTI_RECURSION_PREAMBLE}Use the csa-review skill. scope={scope}, mode={mode}, security_mode={security_mode}, review_mode={review_mode}. Emit exactly one final verdict token: PASS, FAIL, SKIP, UNCERTAIN, or UNAVAILABLE."
@@ -396,6 +402,9 @@ fn build_review_instruction_without_design_anchor(
             instruction.push_str(&render_spec_review_context(spec));
         }
     }
+    if !path_filters.is_empty() {
+        instruction.push_str(&format!("\npath_filters={}", path_filters.join(",")));
+    }
     if let (Some(project_root), Some(pattern)) = (project_root, pattern) {
         let skill_source_dir = pattern.skill_source_dir("csa-review");
         let mut parts = vec![instruction];
@@ -432,6 +441,7 @@ pub(crate) fn build_review_instruction_for_project(
         context,
         Some(project_root),
         options.resolved_pattern,
+        options.path_filters,
     );
     let consistency_scope = if options.full_consistency {
         "touched-files"
@@ -498,6 +508,8 @@ pub(crate) struct ReviewProjectPromptOptions<'a> {
     pub(crate) review_depth: ReviewDepth,
     pub(crate) review_depth_auto_escalation: Option<String>,
     pub(crate) regression_context: Option<&'a str>,
+    /// `--path` globs the reviewed diff is limited to.
+    pub(crate) path_filters: &'a [String],
 }
 
 fn append_review_depth_metadata(
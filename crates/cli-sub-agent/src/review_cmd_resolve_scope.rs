@@ -15,6 +15,9 @@ pub(crate) fn derive_scope(args: &ReviewArgs) -> String {
     if let Some(ref commit) = args.commit {
         return format!("commit:{commit}");
     }
+    if args.staged {
+        return "staged".to_string();
+    }
     if args.diff {
         return "uncommitted".to_string();
     }
@@ -35,6 +38,18 @@ pub(crate) fn derive_scope_for_project(args: &ReviewArgs, project_root: &Path) -
     derive_diff_scope_for_project(project_root)
 }
 
+/// Append `--path` filters to git diff arguments as glob pathspecs, so `**`
+/// matches across directories.
+pub(crate) fn push_path_filter_pathspecs(args: &mut Vec<String>, filters: &[String]) {
+    if filters.is_empty() {
+        return;
+    }
+    if !args.iter().any(|arg| arg == "--") {
+        args.push("--".to_string());
+    }
+    args.extend(filters.iter().map(|filter| format!(":(glob){filter}")));
+}
+
 pub(crate) fn validate_single_parent_commit_scope(
     project_root: &Path,
     commit: &str,
@@ -184,7 +199,8 @@ fn run_command_with_timeout(command: &mut Command, timeout: Duration) -> Option<
 }
 
 pub(crate) fn review_scope_allows_auto_discovery(args: &ReviewArgs) -> bool {
-    args.range.is_some() || (!args.diff && args.commit.is_none() && args.files.is_none())
+    args.range.is_some()
+        || (!args.diff && !args.staged && args.commit.is_none() && args.files.is_none())
 }
 
 #[cfg(all(test, unix))]
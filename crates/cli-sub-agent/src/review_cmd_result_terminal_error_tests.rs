@@ -103,6 +103,7 @@ fn terminal_tool_error_reason_persists_through_real_sidecars() {
         failure_reason: resolved.failure_reason.clone(),
         tool: ToolName::ClaudeCode.to_string(),
         scope: "uncommitted".to_string(),
+        path_filters: Vec::new(),
         exit_code: resolved.effective_exit_code,
         fix_attempted: false,
         fix_rounds: 0,
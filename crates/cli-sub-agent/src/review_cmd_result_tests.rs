@@ -317,6 +317,7 @@ fn mock_pass_review_persists_pass_review_meta() {
         failure_reason: None,
         tool: ToolName::Codex.to_string(),
         scope: "uncommitted".to_string(),
+        path_filters: Vec::new(),
         exit_code: resolved.effective_exit_code,
         fix_attempted: false,
         fix_rounds: 0,
@@ -0,0 +1,163 @@
+//! Findings of one `csa review` run, for `--format sarif`, `--baseline`,
+//! `--fail-on`, `--post-to-pr` and `--ci`.
+//!
+//! The review run returns the review sessions its paths (single,
+//! multi-reviewer, chunked) produced. The findings of those sessions are then
+//! split against the baseline, reported, checked against the severity
+//! threshold, printed as SARIF, and posted to the PR.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csa_core::types::ReviewDecision;
+use csa_session::{Finding, FindingId, ReviewVerdictArtifact};
+use tracing::warn;
+
+use super::baseline::ReviewBaseline;
+use super::report::ReviewReport;
+use crate::cli::{ReviewArgs, ReviewFormat};
+use crate::review_session_findings::read_session_findings_or_fall_back;
+use crate::startup_env::StartupSubtreeEnv;
+
+/// Findings of a run, split by the baseline.
+#[derive(Debug, Default)]
+pub(super) struct ReviewFindings {
+    /// Findings not in the baseline (all findings without one).
+    pub(super) new: Vec<Finding>,
+    /// Findings recorded in the baseline.
+    pub(super) known: Vec<Finding>,
+    pub(super) baseline_applied: bool,
+}
+
+pub(super) fn reports_run_findings(args: &ReviewArgs) -> bool {
+    args.review_format == ReviewFormat::Sarif
+        || args.baseline.is_some()
+        || args.fail_on.is_some()
+        || args.post_to_pr.is_some()
+        || args.ci
+}
+
+/// Run the review, then apply the baseline and the severity threshold, emit
+/// the CI annotations, print the SARIF log and post the findings to the PR.
+///
+/// In SARIF mode the review report goes to stderr so stdout carries only the
+/// log.
+pub(super) async fn handle_review_reporting_findings(
+    mut args: ReviewArgs,
+    current_depth: u32,
+    startup_env: &StartupSubtreeEnv,
+) -> Result<i32> {
+    let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+    let baseline = args
+        .baseline
+        .as_deref()
+        .map(|path| ReviewBaseline::load(&project_root, path))
+        .transpose()?;
+    let ci = super::ci::apply_ci_defaults(&mut args);
+    let sarif = args.review_format == ReviewFormat::Sarif;
+    let fail_on = args.fail_on;
+    let post_to_pr = args.post_to_pr;
+    let mut report = ReviewReport::for_args(&args);
+    let run = super::handle::handle_review_inner(args, current_depth, startup_env).await?;
+    let mut exit_code = run.exit_code;
+    let session_ids = run.session_ids;
+    let findings = collect_session_findings(&project_root, &session_ids);
+    let findings = match &baseline {
+        Some(baseline) => {
+            let findings = baseline.split(&project_root, findings);
+            let baseline_exit_code = super::baseline::exit_code_with_baseline(
+                &project_root,
+                &session_ids,
+                &findings,
+                exit_code,
+            );
+            write!(
+                report,
+                "{}",
+                baseline.report(&findings, baseline_exit_code != exit_code)
+            )?;
+            exit_code = baseline_exit_code;
+            findings
+        }
+        None => ReviewFindings {
+            new: findings,
+            ..ReviewFindings::default()
+        },
+    };
+    if let Some(fail_on) = fail_on {
+        let fail_on_exit_code = super::fail_on::exit_code_with_fail_on(
+            &project_root,
+            &session_ids,
+            &findings,
+            fail_on,
+            exit_code,
+        );
+        let line = super::fail_on::format_fail_on_line(fail_on, &findings, fail_on_exit_code);
+        writeln!(report, "{line}")?;
+        exit_code = fail_on_exit_code;
+    }
+    if let (Some(ci), Some(fail_on)) = (&ci, fail_on) {
+        super::ci::report(ci, &findings, fail_on, exit_code, &mut report)?;
+    }
+    if sarif {
+        super::sarif::print_sarif(&findings)?;
+    }
+    if let Some(pr) = post_to_pr {
+        let line = super::post_pr::post_review_to_pr(&project_root, pr, &findings, exit_code)
+            .await
+            .with_context(|| format!("failed to post review findings to PR #{pr}"))?;
+        eprintln!("{line}");
+    }
+    Ok(exit_code)
+}
+
+/// Whether every session reached a pass/fail decision; unavailable,
+/// uncertain, and skipped reviews did not.
+pub(super) fn all_sessions_decided(project_root: &Path, session_ids: &[String]) -> bool {
+    !session_ids.is_empty()
+        && session_ids.iter().all(|session_id| {
+            matches!(
+                persisted_review_decision(project_root, session_id),
+                Some(ReviewDecision::Pass | ReviewDecision::Fail)
+            )
+        })
+}
+
+fn persisted_review_decision(project_root: &Path, session_id: &str) -> Option<ReviewDecision> {
+    let session_dir = csa_session::get_session_dir(project_root, session_id).ok()?;
+    let raw =
+        std::fs::read_to_string(session_dir.join("output").join("review-verdict.json")).ok()?;
+    serde_json::from_str::<ReviewVerdictArtifact>(&raw)
+        .ok()
+        .map(|artifact| artifact.decision)
+}
+
+/// Findings of `session_ids`, without the duplicates reviewers agree on.
+pub(super) fn collect_session_findings(
+    project_root: &Path,
+    session_ids: &[String],
+) -> Vec<Finding> {
+    let mut seen = HashSet::new();
+    let mut findings = Vec::new();
+    for session_id in session_ids {
+        let session_findings = csa_session::get_session_dir(project_root, session_id)
+            .and_then(|session_dir| read_session_findings_or_fall_back(&session_dir));
+        match session_findings {
+            Ok(Some(session_findings)) => {
+                findings.extend(session_findings.into_iter().filter(|finding| {
+                    // Reviewer-local ids (`f1`) repeat across sessions.
+                    FindingId::parse(&finding.fid).is_none() || seen.insert(finding.fid.clone())
+                }))
+            }
+            Ok(None) => {}
+            Err(error) => warn!(
+                session_id = %session_id,
+                error = %error,
+                "Failed to read review findings"
+            ),
+        }
+    }
+    findings
+}
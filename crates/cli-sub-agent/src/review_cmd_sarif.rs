@@ -0,0 +1,139 @@
+//! `csa review --format sarif`: review findings as a SARIF 2.1.0 log.
+//!
+//! The review report goes to stderr in SARIF mode (see `ReviewReport`), so the
+//! log is the only thing written to stdout.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use csa_session::{Finding, Severity, normalize_path};
+use serde_json::{Value, json};
+
+use super::run_findings::ReviewFindings;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_TOOL_NAME: &str = "csa-review";
+const UNKNOWN_FILE: &str = "<unknown>";
+const FALLBACK_RULE_ID: &str = "csa.review";
+
+/// Print the SARIF log of `findings` to stdout.
+pub(super) fn print_sarif(findings: &ReviewFindings) -> Result<()> {
+    let log = render_sarif(findings);
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &log).context("failed to write SARIF log")?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+/// Build a SARIF 2.1.0 log with one run holding `findings`.
+///
+/// With a baseline, known findings are `unchanged` and the others `new`.
+fn render_sarif(findings: &ReviewFindings) -> Value {
+    let all = || findings.new.iter().chain(&findings.known);
+    let mut rule_index = BTreeMap::new();
+    for finding in all() {
+        let next = rule_index.len();
+        rule_index.entry(rule_id(finding)).or_insert(next);
+    }
+    let mut rules: Vec<(&str, usize)> = rule_index.iter().map(|(id, i)| (*id, *i)).collect();
+    rules.sort_by_key(|(_, index)| *index);
+    let rules: Vec<Value> = rules
+        .into_iter()
+        .map(|(id, _)| json!({ "id": id, "shortDescription": { "text": id } }))
+        .collect();
+
+    let results: Vec<Value> = all()
+        .enumerate()
+        .map(|(index, finding)| {
+            let rule = rule_id(finding);
+            let mut result = json!({
+                "ruleId": rule,
+                "ruleIndex": rule_index[rule],
+                "level": sarif_level(&finding.severity),
+                "message": { "text": finding.summary },
+                "partialFingerprints": { "csaFindingId/v1": finding.fid },
+                "properties": {
+                    "severity": severity_label(&finding.severity),
+                    "engine": finding.engine,
+                },
+            });
+            if let Some(location) = sarif_location(finding) {
+                result["locations"] = json!([location]);
+            }
+            if findings.baseline_applied {
+                let state = if index < findings.new.len() {
+                    "new"
+                } else {
+                    "unchanged"
+                };
+                result["baselineState"] = json!(state);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": SARIF_TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn rule_id(finding: &Finding) -> &str {
+    if finding.rule_id.trim().is_empty() {
+        FALLBACK_RULE_ID
+    } else {
+        &finding.rule_id
+    }
+}
+
+fn sarif_level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+pub(super) fn severity_label(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::High => "high",
+        Severity::Medium => "medium",
+        Severity::Low => "low",
+    }
+}
+
+/// Physical location relative to the repository root, or `None` when the
+/// finding has no file.
+fn sarif_location(finding: &Finding) -> Option<Value> {
+    let file = finding.file.trim();
+    if file.is_empty() || file == UNKNOWN_FILE {
+        return None;
+    }
+    let mut location = json!({
+        "artifactLocation": {
+            "uri": normalize_path(file),
+            "uriBaseId": "%SRCROOT%",
+        }
+    });
+    if let Some(line) = finding.line.filter(|line| *line > 0) {
+        location["region"] = json!({ "startLine": line });
+    }
+    Some(json!({ "physicalLocation": location }))
+}
+
+#[cfg(test)]
+#[path = "review_cmd_sarif_tests.rs"]
+mod tests;
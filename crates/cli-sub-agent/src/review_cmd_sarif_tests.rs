@@ -0,0 +1,112 @@
+use super::*;
+use crate::test_review_findings::finding;
+
+fn new_findings(new: Vec<Finding>) -> ReviewFindings {
+    ReviewFindings {
+        new,
+        ..ReviewFindings::default()
+    }
+}
+
+#[test]
+fn sarif_log_maps_severity_location_and_fingerprint() {
+    let log = render_sarif(&new_findings(vec![
+        finding(
+            Severity::Critical,
+            "FIDCRIT",
+            "./src\\lib.rs",
+            Some(42),
+            "rust.no-unwrap",
+        ),
+        finding(Severity::Medium, "FIDMED", "src/a.rs", None, "rust.naming"),
+        finding(
+            Severity::Low,
+            "FIDLOW",
+            "src/b.rs",
+            Some(3),
+            "rust.no-unwrap",
+        ),
+    ]));
+
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "csa-review");
+    let rules = run["tool"]["driver"]["rules"].as_array().expect("rules");
+    let rule_ids: Vec<&str> = rules.iter().filter_map(|r| r["id"].as_str()).collect();
+    assert_eq!(rule_ids, ["rust.no-unwrap", "rust.naming"]);
+
+    let results = run["results"].as_array().expect("results");
+    assert_eq!(results.len(), 3);
+    let critical = &results[0];
+    assert_eq!(critical["ruleId"], "rust.no-unwrap");
+    assert_eq!(critical["ruleIndex"], 0);
+    assert_eq!(critical["level"], "error");
+    assert_eq!(critical["message"]["text"], "FIDCRIT summary");
+    assert_eq!(
+        critical["partialFingerprints"]["csaFindingId/v1"],
+        "FIDCRIT"
+    );
+    assert_eq!(critical["properties"]["severity"], "critical");
+    let location = &critical["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+    assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+    assert_eq!(location["region"]["startLine"], 42);
+
+    assert_eq!(results[1]["level"], "warning");
+    assert_eq!(results[1]["ruleIndex"], 1);
+    assert!(
+        results[1]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none(),
+        "findings without a line anchor have no region"
+    );
+    assert_eq!(results[2]["level"], "note");
+    assert_eq!(results[2]["ruleIndex"], 0);
+    assert!(
+        critical.get("baselineState").is_none(),
+        "baselineState is only set when a baseline was applied"
+    );
+}
+
+#[test]
+fn baseline_marks_known_findings_unchanged() {
+    let log = render_sarif(&ReviewFindings {
+        new: vec![finding(Severity::High, "FIDNEW", "src/a.rs", Some(1), "r")],
+        known: vec![finding(Severity::Low, "FIDOLD", "src/b.rs", Some(2), "r")],
+        baseline_applied: true,
+    });
+    let results = log["runs"][0]["results"].as_array().expect("results");
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[0]["partialFingerprints"]["csaFindingId/v1"],
+        "FIDNEW"
+    );
+    assert_eq!(results[0]["baselineState"], "new");
+    assert_eq!(
+        results[1]["partialFingerprints"]["csaFindingId/v1"],
+        "FIDOLD"
+    );
+    assert_eq!(results[1]["baselineState"], "unchanged");
+}
+
+#[test]
+fn findings_without_file_or_rule_stay_valid() {
+    let log = render_sarif(&new_findings(vec![finding(
+        Severity::High,
+        "FIDHIGH",
+        "<unknown>",
+        None,
+        " ",
+    )]));
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "csa.review");
+    assert_eq!(result["level"], "error");
+    assert!(result.get("locations").is_none());
+}
+
+#[test]
+fn empty_review_renders_an_empty_run() {
+    let log = render_sarif(&ReviewFindings::default());
+    assert_eq!(log["runs"][0]["results"], json!([]));
+    assert_eq!(log["runs"][0]["tool"]["driver"]["rules"], json!([]));
+}
@@ -186,7 +186,8 @@ pub(crate) fn setup_git_repo() -> TempDir {
 
 #[test]
 fn build_post_review_output_synthesizes_builtin_directive_for_clean_cumulative_review() {
-    let output = build_post_review_output("", csa_core::types::ReviewDecision::Pass, "base:main");
+    let output =
+        build_post_review_output("", csa_core::types::ReviewDecision::Pass, "base:main", &[]);
     let directive = csa_hooks::parse_next_step_directive(&output).expect("directive");
     assert_eq!(
         directive.cmd.as_deref(),
@@ -201,6 +202,7 @@ fn build_post_review_output_does_not_synthesize_for_files_scope() {
         "",
         csa_core::types::ReviewDecision::Pass,
         "files:crates/csa-hooks/src/",
+        &[],
     );
     assert!(output.is_empty());
 }
@@ -212,6 +214,7 @@ fn build_post_review_output_preserves_existing_directive_without_duplication() {
         existing,
         csa_core::types::ReviewDecision::Pass,
         "range:main...HEAD",
+        &[],
     );
     assert_eq!(output, existing);
 }
@@ -28,6 +28,7 @@ fn build_review_instruction_for_project_injects_review_checklist() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -62,6 +63,7 @@ fn build_review_instruction_for_project_omits_checklist_when_missing() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -94,3 +94,172 @@ fn review_cli_rejects_prompt_without_fix_finding() {
         "{err}"
     );
 }
+
+#[test]
+fn review_cli_parses_apply_suggestions_flags() {
+    let args = parse_review_args(&[
+        "csa",
+        "review",
+        "--apply-suggestions",
+        "--session",
+        "01HREVIEWSESSION0000000000",
+        "-y",
+    ]);
+
+    assert!(args.apply_suggestions);
+    assert!(args.yes);
+    assert_eq!(args.session.as_deref(), Some("01HREVIEWSESSION0000000000"));
+}
+
+#[test]
+fn review_cli_rejects_apply_suggestions_without_session_or_with_fix() {
+    let err = parse_or_validate_review_error(&["csa", "review", "--apply-suggestions"]);
+    assert!(err.to_string().contains("--session"), "{err}");
+
+    let err = parse_or_validate_review_error(&[
+        "csa",
+        "review",
+        "--apply-suggestions",
+        "--fix",
+        "--session",
+        "01HREVIEWSESSION0000000000",
+    ]);
+    assert!(err.to_string().contains("--fix"), "{err}");
+
+    let err = parse_or_validate_review_error(&["csa", "review", "--diff", "--yes"]);
+    assert!(err.to_string().contains("--apply-suggestions"), "{err}");
+}
+
+#[test]
+fn review_cli_parses_min_agreement() {
+    let args = parse_review_args(&[
+        "csa",
+        "review",
+        "--diff",
+        "--reviewers",
+        "3",
+        "--min-agreement",
+        "2",
+    ]);
+
+    assert_eq!(args.reviewers, Some(3));
+    assert_eq!(args.min_agreement, Some(2));
+}
+
+#[test]
+fn review_cli_rejects_min_agreement_above_reviewers_or_with_single() {
+    let err = parse_or_validate_review_error(&[
+        "csa",
+        "review",
+        "--diff",
+        "--reviewers",
+        "2",
+        "--min-agreement",
+        "3",
+    ]);
+    assert!(err.to_string().contains("exceeds --reviewers 2"), "{err}");
+
+    let err = parse_or_validate_review_error(&[
+        "csa",
+        "review",
+        "--diff",
+        "--single",
+        "--min-agreement",
+        "2",
+    ]);
+    assert!(err.to_string().contains("--single"), "{err}");
+
+    let err = parse_or_validate_review_error(&["csa", "review", "--diff", "--min-agreement", "0"]);
+    assert!(err.to_string().contains("--min-agreement"), "{err}");
+}
+
+#[test]
+fn review_cli_parses_staged_scope_with_path_filters() {
+    let args = parse_review_args(&[
+        "csa",
+        "review",
+        "--staged",
+        "--path",
+        "crates/foo/**",
+        "--path",
+        "docs/*.md",
+    ]);
+
+    assert!(args.staged);
+    assert_eq!(args.paths, ["crates/foo/**", "docs/*.md"]);
+    assert_eq!(super::resolve::derive_scope(&args), "staged");
+}
+
+#[test]
+fn review_cli_rejects_conflicting_scope_and_invalid_path_filters() {
+    let err = parse_or_validate_review_error(&["csa", "review", "--staged", "--diff"]);
+    assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+    let err =
+        parse_or_validate_review_error(&["csa", "review", "--files", "src/", "--path", "src/*.rs"]);
+    assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+    let err = parse_or_validate_review_error(&["csa", "review", "--diff", "--path", "a,b"]);
+    assert!(err.to_string().contains("--path"), "{err}");
+}
+
+#[test]
+fn review_cli_parses_fail_on_and_rejects_it_with_fix() {
+    let args = parse_review_args(&["csa", "review", "--diff", "--fail-on", "high"]);
+    assert_eq!(args.fail_on, Some(csa_core::types::ReviewFailOn::High));
+    assert_eq!(
+        parse_review_args(&["csa", "review", "--diff"]).fail_on,
+        None
+    );
+
+    let err = parse_or_validate_review_error(&["csa", "review", "--diff", "--fail-on", "medium"]);
+    assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+
+    let err =
+        parse_or_validate_review_error(&["csa", "review", "--diff", "--fix", "--fail-on", "any"]);
+    assert!(
+        err.to_string()
+            .contains("--fail-on reports one review run and conflicts with --fix"),
+        "{err}"
+    );
+}
+
+#[test]
+fn review_cli_parses_hunk_cache_for_single_reviewer_runs_only() {
+    let args = parse_review_args(&["csa", "review", "--range", "main...HEAD", "--hunk-cache"]);
+    assert!(args.hunk_cache);
+
+    let err = parse_or_validate_review_error(&["csa", "review", "--diff", "--fix", "--hunk-cache"]);
+    assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+    let err = parse_or_validate_review_error(&[
+        "csa",
+        "review",
+        "--diff",
+        "--reviewers",
+        "2",
+        "--hunk-cache",
+    ]);
+    assert!(err.to_string().contains("--hunk-cache"), "{err}");
+}
+
+#[test]
+fn review_cli_parses_post_to_pr_and_rejects_it_with_fix() {
+    let args = parse_review_args(&[
+        "csa",
+        "review",
+        "--range",
+        "main...HEAD",
+        "--post-to-pr",
+        "42",
+    ]);
+    assert_eq!(args.post_to_pr, Some(42));
+
+    let err =
+        parse_or_validate_review_error(&["csa", "review", "--diff", "--fix", "--post-to-pr", "42"]);
+    assert!(
+        err.to_string()
+            .contains("--post-to-pr reports one review run and conflicts with --fix"),
+        "{err}"
+    );
+}
@@ -155,6 +155,7 @@ async fn handle_review_fix_loop_uses_effective_fallback_tool() {
         },
         project_root: project_dir.path(),
         scope: "files:tracked.txt".to_string(),
+        path_filters: Vec::new(),
         decision: ReviewDecision::Fail.as_str().to_string(),
         verdict: "HAS_ISSUES".to_string(),
         review_mode: None,
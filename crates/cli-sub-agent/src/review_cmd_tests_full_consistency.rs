@@ -41,6 +41,7 @@ fn test_build_review_instruction_no_diff_content_default() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -82,6 +83,7 @@ fn test_build_review_instruction_audit_depth_metadata() {
             regression_context: Some(
                 "Recent commit history for changed files (regression context):\n- src/lib.rs:\n  abc1234 fix old bug",
             ),
+            path_filters: &[],
         },
     );
 
@@ -113,6 +115,7 @@ fn test_build_review_instruction_full_consistency() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -46,6 +46,7 @@ fn make_review_meta(session_id: &str, decision: &str, review_iterations: u32) ->
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "uncommitted".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -98,6 +99,7 @@ fn create_mock_review_session(
         vcs_identity: None,
         identity_version: 1,
         fork_call_timestamps: Vec::new(),
+        ..Default::default()
     })
     .expect("write mock session state");
     write_review_meta(
@@ -126,6 +128,7 @@ fn build_review_instruction_for_project_contains_design_preference_anchor() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -151,6 +154,7 @@ fn build_review_instruction_for_project_contains_same_class_site_sweep_anchor()
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -218,6 +222,7 @@ fn count_prior_reviews_zero_omits_iteration_block() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -258,6 +263,7 @@ fn count_prior_reviews_one_injects_iteration_two() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -157,6 +157,7 @@ fn review_verdict_carries_no_provider_launch_diagnostic() {
         failure_reason: Some("reviewer provider did not launch".to_string()),
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: false,
         fix_rounds: 0,
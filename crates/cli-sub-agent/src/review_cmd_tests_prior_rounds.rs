@@ -59,6 +59,7 @@ invariant = "lock-losing resume cannot mutate metadata of winning session"
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -157,6 +158,7 @@ fn build_review_instruction_for_project_without_prior_rounds_flag_leaves_section
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -1,4 +1,4 @@
-use super::super::super::resolve::derive_scope_for_project;
+use super::super::super::resolve::{derive_scope_for_project, push_path_filter_pathspecs};
 use super::super::*;
 
 fn setup_git_repo_on_main() -> tempfile::TempDir {
@@ -65,3 +65,41 @@ fn derive_scope_for_project_keeps_uncommitted_scope_on_clean_main() {
         "uncommitted"
     );
 }
+
+#[test]
+fn derive_scope_for_project_keeps_path_filters_out_of_the_scope() {
+    let temp = setup_git_repo_on_main();
+    let args = ReviewArgs {
+        staged: true,
+        paths: vec!["crates/foo/**".to_string(), "docs/*.md".to_string()],
+        ..default_review_args()
+    };
+
+    assert_eq!(derive_scope_for_project(&args, temp.path()), "staged");
+}
+
+#[test]
+fn path_filtered_reviews_are_not_cumulative() {
+    let filters = ["crates/foo/**".to_string()];
+    assert!(review_scope_is_cumulative("base:main", &[]));
+    assert!(!review_scope_is_cumulative("base:main", &filters));
+
+    let output = build_post_review_output(
+        "",
+        csa_core::types::ReviewDecision::Pass,
+        "range:main...HEAD",
+        &filters,
+    );
+    assert!(output.is_empty());
+}
+
+#[test]
+fn path_filters_become_glob_pathspecs_after_a_single_separator() {
+    let mut args = vec!["diff".to_string(), "main...HEAD".to_string()];
+    push_path_filter_pathspecs(&mut args, &["crates/foo/**".to_string()]);
+    assert_eq!(args, ["diff", "main...HEAD", "--", ":(glob)crates/foo/**"]);
+
+    let mut args = vec!["diff".to_string(), "--".to_string(), "src".to_string()];
+    push_path_filter_pathspecs(&mut args, &["src/*.rs".to_string()]);
+    assert_eq!(args, ["diff", "--", "src", ":(glob)src/*.rs"]);
+}
@@ -350,6 +350,34 @@ fn test_build_review_instruction_with_context() {
     assert!(result.contains("context=/path/to/TODO.md"));
 }
 
+#[test]
+fn test_build_review_instruction_lists_path_filters_outside_scope() {
+    let project_dir = tempdir().unwrap();
+    let path_filters = ["crates/foo/**".to_string(), "docs/*.md".to_string()];
+
+    let (result, _) = build_review_instruction_for_project(
+        "staged",
+        "review-only",
+        "auto",
+        ReviewMode::Standard,
+        None,
+        project_dir.path(),
+        resolve::ReviewProjectPromptOptions {
+            project_config: None,
+            resolved_pattern: None,
+            prior_rounds_section: None,
+            current_session_id: None,
+            full_consistency: false,
+            review_depth: crate::cli::ReviewDepth::Standard,
+            review_depth_auto_escalation: None,
+            regression_context: None,
+            path_filters: &path_filters,
+        },
+    );
+    assert!(result.contains("scope=staged,"));
+    assert!(result.contains("\npath_filters=crates/foo/**,docs/*.md"));
+}
+
 #[test]
 fn test_build_review_instruction_fix_mode() {
     let result = build_review_instruction(
@@ -407,6 +435,7 @@ fn test_build_review_instruction_for_project_includes_rust_profile() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -434,6 +463,7 @@ fn test_build_review_instruction_for_project_includes_unknown_profile_for_empty_
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -146,6 +146,7 @@ fn build_review_instruction_for_project_injects_bundled_pattern_without_repo_loc
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -193,6 +194,7 @@ fn build_review_instruction_for_project_injects_repo_local_pattern() {
             review_depth: crate::cli::ReviewDepth::Standard,
             review_depth_auto_escalation: None,
             regression_context: None,
+            path_filters: &[],
         },
     );
 
@@ -470,7 +470,7 @@ pub(crate) fn merge_related_findings(findings: Vec<Finding>) -> Vec<Finding> {
     merged
 }
 
-fn are_related_findings(left: &Finding, right: &Finding) -> bool {
+pub(crate) fn are_related_findings(left: &Finding, right: &Finding) -> bool {
     if left.rule_id != right.rule_id || left.file != right.file {
         return false;
     }
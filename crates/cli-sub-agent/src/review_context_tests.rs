@@ -261,6 +261,7 @@ fn make_review_meta(session_id: &str, decision: &str, iters: u32) -> ReviewSessi
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "base:main".to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: false,
         fix_rounds: 0,
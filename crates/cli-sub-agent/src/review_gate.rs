@@ -184,19 +184,25 @@ pub(crate) fn write_review_gate_marker(
 /// Write a gate marker when `verdict == "CLEAN"`.
 ///
 /// Any other final verdict invalidates the marker for the current branch/head so
-/// a stale clean marker cannot outlive a later failing review publication.
+/// a stale clean marker cannot outlive a later failing review publication. A
+/// clean review limited by `--path` filters does not cover the whole scope and
+/// writes no marker.
 pub(crate) fn maybe_write_gate_marker_for_clean(
     project_root: &Path,
     head_sha: &str,
     verdict: &str,
     first_session_id: Option<&str>,
     scope: &str,
+    path_filters: &[String],
     review_mode: Option<&str>,
 ) {
     if verdict != "CLEAN" {
         remove_review_gate_marker_for_head(project_root, head_sha, first_session_id);
         return;
     }
+    if !path_filters.is_empty() {
+        return;
+    }
     if let Some(sid) = first_session_id {
         maybe_write_review_gate_marker(project_root, head_sha, sid, scope, review_mode);
     }
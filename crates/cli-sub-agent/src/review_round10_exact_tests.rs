@@ -172,6 +172,7 @@ fn prior_round_context_prefers_session_findings_over_root() {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "base:main".to_string(),
+            path_filters: Vec::new(),
             exit_code: 1,
             fix_attempted: false,
             fix_rounds: 0,
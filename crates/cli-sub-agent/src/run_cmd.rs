@@ -159,6 +159,7 @@ impl SubagentRunConfig {
             false,
             false,
             None,
+            false,
             self.current_depth,
             self.output_format,
             self.stream_mode,
@@ -145,7 +145,7 @@ async fn ri(request: RunLoopRequest<'_>, g: &mut Cg) -> Result<RunLoopCompletion
                 Ok(child_slot) => _slot_guard = Some(child_slot),
                 Err(e) => {
                     eprintln!("{e}");
-                    return Ok(Exit(1));
+                    return Ok(Exit(crate::process_exit::exit_code_for_error(&e).code()));
                 }
             }
         }
@@ -268,6 +268,8 @@ async fn ri(request: RunLoopRequest<'_>, g: &mut Cg) -> Result<RunLoopCompletion
                 ),
                 exit_code,
                 peak_memory_mb: None,
+                peak_vram_mb: None,
+                child_usage: None,
                 model_completed: Some(false),
                 terminal_reason: Some("timeout".to_owned()),
                 raw_process_exit_code: Some(exit_code),
@@ -451,6 +453,8 @@ async fn ri(request: RunLoopRequest<'_>, g: &mut Cg) -> Result<RunLoopCompletion
                     ),
                     exit_code,
                     peak_memory_mb: None,
+                    peak_vram_mb: None,
+                    child_usage: None,
                     model_completed: Some(false),
                     terminal_reason: Some("timeout".to_owned()),
                     raw_process_exit_code: Some(exit_code),
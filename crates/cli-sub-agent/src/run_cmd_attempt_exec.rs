@@ -9,6 +9,7 @@ use tempfile::TempDir;
 use tracing::info;
 
 use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::error::AppError;
 use csa_core::types::{OutputFormat, ToolName};
 use csa_executor::ResolvedTimeout;
 
@@ -390,8 +391,9 @@ async fn execute_persistent(
             }
         }
         Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("Session locked by PID")
+            let lock_contention = crate::process_exit::app_error(&e)
+                .filter(|app_err| matches!(app_err, AppError::LockContention { .. }));
+            if let Some(lock_contention) = lock_contention
                 && matches!(output_format, OutputFormat::Json)
             {
                 cleanup_pre_created_fork_session(pre_created_fork_session_id, project_root);
@@ -399,10 +401,11 @@ async fn execute_persistent(
                     "error": "session_locked",
                     "session_id": effective_session_arg.unwrap_or_else(|| "(new)".to_string()),
                     "tool": current_tool.as_str(),
-                    "message": error_msg
+                    "message": e.to_string(),
+                    "lock": lock_contention.to_json()["context"],
                 });
                 println!("{}", serde_json::to_string_pretty(&json_error)?);
-                AttemptExecution::Exit(1)
+                AttemptExecution::Exit(csa_core::exit_code::ExitCode::LockContention.code())
             } else {
                 AttemptExecution::Finished {
                     result: Box::new(Err(e)),
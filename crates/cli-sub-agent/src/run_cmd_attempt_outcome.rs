@@ -22,6 +22,7 @@ use crate::run_cmd_post::{
     ErrorRateLimitFailoverRequest, RateLimitAction, RateLimitFailoverRequest,
     detect_permanent_tool_exhaustion_result, evaluate_error_rate_limit_failover_with_catalog,
     evaluate_rate_limit_failover_with_catalog, is_permanent_tool_exhaustion_error,
+    rate_limited_error,
 };
 use crate::run_cmd_tool_selection::take_next_runtime_fallback_tool;
 
@@ -282,17 +283,45 @@ pub(super) fn handle_attempt_error(
         }
         RateLimitAction::ExhaustedFailovers { reason } => {
             cleanup_pre_created_fork_session(pre_created_fork_session_id, request.project_root);
-            Ok(AttemptErrorAction::Error(
-                error.context(format!("tier failover unavailable: {reason}")),
-            ))
+            let error = error.context(format!("tier failover unavailable: {reason}"));
+            Ok(AttemptErrorAction::Error(with_rate_limit_cause(
+                error,
+                &request,
+                &full_error_chain,
+            )))
         }
         RateLimitAction::NoRateLimit => {
             cleanup_pre_created_fork_session(pre_created_fork_session_id, request.project_root);
-            Ok(AttemptErrorAction::Error(error))
+            Ok(AttemptErrorAction::Error(with_rate_limit_cause(
+                error,
+                &request,
+                &full_error_chain,
+            )))
         }
     }
 }
 
+/// Type an attempt error the executor left untyped as
+/// [`AppError::RateLimited`](csa_core::error::AppError::RateLimited) when its
+/// chain is a provider rate limit that failover did not absorb.
+fn with_rate_limit_cause(
+    error: anyhow::Error,
+    request: &AttemptErrorRequest<'_>,
+    full_error_chain: &str,
+) -> anyhow::Error {
+    if crate::process_exit::app_error(&error).is_some() {
+        return error;
+    }
+    match rate_limited_error(
+        request.tool_name,
+        full_error_chain,
+        request.current_model_spec,
+    ) {
+        Some(rate_limited) => error.context(rate_limited),
+        None => error,
+    }
+}
+
 pub(super) enum PostAttemptAction {
     Retry(AttemptRetryAction),
     Break(Option<Vec<String>>),
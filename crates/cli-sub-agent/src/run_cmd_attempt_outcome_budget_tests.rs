@@ -77,4 +77,72 @@ mod tests {
                 .contains("Issue token budget exhausted; stopping retry loop (used=10, max=10).")
         );
     }
+
+    #[test]
+    fn unabsorbed_rate_limit_error_is_reported_as_typed_rate_limit() {
+        let project_root = tempfile::tempdir().expect("project root");
+        let global_config = GlobalConfig::default();
+        let model_catalog = csa_config::EffectiveModelCatalog::shipped()
+            .expect("shipped model catalog must be valid");
+        let mut tried_tools = Vec::new();
+        let mut tried_specs = Vec::new();
+        let mut runtime_fallback_candidates = Vec::new();
+        let mut runtime_fallback_attempts = 0;
+        let mut fallback_chain: csa_scheduler::FallbackChain = Vec::new();
+        let mut pre_created_fork_session_id = None;
+
+        let action = handle_attempt_error(
+            anyhow::anyhow!("ACP transport failed: HTTP 429 Too Many Requests")
+                .context("meta_session_id=01KTESTSESSIONABCDE123456"),
+            AttemptErrorRequest {
+                run_timeout_seconds: None,
+                project_root: project_root.path(),
+                is_fork: false,
+                current_tool: ToolName::ClaudeCode,
+                skill: None,
+                output_format: OutputFormat::Json,
+                executed_session_id: None,
+                effective_session_arg: None,
+                runtime_fallback_enabled: false,
+                max_runtime_fallback_attempts: 0,
+                tool_name: ToolName::ClaudeCode.as_str(),
+                current_model_spec: None,
+                attempts: 1,
+                max_failover_attempts: 3,
+                tier_auto_select: false,
+                failover_on_crash_enabled: true,
+                resolved_tier_name: None,
+                tier_failover_tool_filter: None,
+                ephemeral: false,
+                prompt_text: "do work",
+                config: None,
+                global_config: &global_config,
+                model_catalog: &model_catalog,
+                task_needs_edit: None,
+                attempt_elapsed: Duration::ZERO,
+            },
+            AttemptErrorState {
+                tried_tools: &mut tried_tools,
+                tried_specs: &mut tried_specs,
+                runtime_fallback_candidates: &mut runtime_fallback_candidates,
+                runtime_fallback_attempts: &mut runtime_fallback_attempts,
+                fallback_chain: &mut fallback_chain,
+                pre_created_fork_session_id: &mut pre_created_fork_session_id,
+            },
+        )
+        .expect("attempt error evaluation should succeed");
+
+        let AttemptErrorAction::Error(error) = action else {
+            panic!("an unabsorbed rate limit fails the run");
+        };
+        let json = crate::process_exit::error_json(&error);
+        assert_eq!(json["error"]["kind"], "rate_limited");
+        assert_eq!(json["error"]["context"]["tool"], "claude-code");
+        assert_eq!(json["error"]["exit_code"], 2);
+        assert_eq!(crate::process_exit::exit_code_for_error(&error).code(), 2);
+        assert!(
+            format!("{error:#}").contains("HTTP 429 Too Many Requests"),
+            "the tool's own message stays in the chain"
+        );
+    }
 }
@@ -1,10 +1,11 @@
 //! Slot acquisition helpers for the `csa run` attempt loop.
 
 use std::path::Path;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::exit_code::ExitCode;
 use csa_core::types::{ToolName, ToolSelectionStrategy};
 use csa_lock::slot::{
     SlotAcquireResult, ToolSlot, acquire_slot_blocking, format_slot_diagnostic, slot_usage,
@@ -119,6 +120,7 @@ pub(super) fn acquire_attempt_slot(
                 );
                 let timeout =
                     Duration::from_secs(resolve_slot_wait_timeout_seconds(request.config));
+                let wait_started = Instant::now();
                 let slot = acquire_slot_blocking(
                     request.slots_dir,
                     request.tool_name,
@@ -126,6 +128,7 @@ pub(super) fn acquire_attempt_slot(
                     timeout,
                     request.session_arg,
                 )?;
+                crate::session_resource_report::record_queue_wait(wait_started.elapsed());
                 info!(
                     tool = %request.tool_name,
                     slot = slot.slot_index(),
@@ -142,7 +145,7 @@ pub(super) fn acquire_attempt_slot(
                         request.tool_name
                     );
                 }
-                Ok(AttemptSlotOutcome::Exit(1))
+                Ok(AttemptSlotOutcome::Exit(ExitCode::SlotExhausted.code()))
             }
         }
     }
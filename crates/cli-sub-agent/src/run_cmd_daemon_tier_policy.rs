@@ -44,10 +44,10 @@ pub(crate) fn validate_run_tier_policy_before_daemon_spawn(
         return Ok(());
     }
 
-    anyhow::bail!(
-        "{}",
-        crate::run_helpers::format_run_direct_tool_tier_policy_error(&config)
+    Err(csa_core::error::AppError::config(
+        crate::run_helpers::format_run_direct_tool_tier_policy_error(&config),
     )
+    .into())
 }
 
 #[cfg(test)]
@@ -33,6 +33,8 @@ use crate::run_helpers_branch_guard::{
     BranchGuardRuntime, evaluate_and_emit_refusal, observe_branch_state,
 };
 use crate::startup_env::StartupSubtreeEnv;
+#[path = "run_cmd_execute_plugin.rs"]
+mod plugin;
 #[path = "run_cmd_execute_post_exec_gate.rs"]
 mod post_exec_gate;
 #[path = "run_cmd_execute_resume_tier.rs"]
@@ -51,6 +53,7 @@ mod run_output;
 mod skill_resume;
 #[path = "run_cmd_execute_tier_guard.rs"]
 mod tier_guard;
+use plugin::{PluginRunRequest, resolve_plugin_route, run_plugin_route};
 use post_exec_gate::{
     PostExecGateApplyOptions, apply_post_exec_gate_after_success_with_runner,
     execute_post_exec_gate_command,
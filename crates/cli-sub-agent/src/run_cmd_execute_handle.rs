@@ -38,6 +38,7 @@ pub(crate) async fn handle_run(
     no_idle_timeout: bool,
     no_memory: bool,
     memory_query: Option<String>,
+    explain_memory: bool,
     current_depth: u32,
     output_format: OutputFormat,
     stream_mode: StreamMode,
@@ -128,7 +129,7 @@ pub(crate) async fn handle_run(
     let Some((mut config, mut global_config, model_catalog, _project_completion_policy)) =
         pipeline::load_and_validate(&project_root, current_depth)?
     else {
-        return Ok(1);
+        return Ok(csa_core::exit_code::ExitCode::ConfigError.code());
     };
     let caller_fork_resolution = if fork_from_caller {
         let resolved = resolve_fork_from_caller(config.as_ref());
@@ -316,6 +317,31 @@ pub(crate) async fn handle_run(
         pre_exec: pre_exec_error,
     })?;
 
+    if let Some(route) = resolve_plugin_route(
+        skill_res.tool.as_ref(),
+        effective_tier.as_deref(),
+        config.as_ref(),
+    )? {
+        return run_plugin_route(PluginRunRequest {
+            route,
+            project_root: &project_root,
+            config: config.as_ref(),
+            global_config: &global_config,
+            startup_env: &startup_env,
+            prompt: &prompt_text,
+            model: model.as_deref(),
+            thinking: thinking.as_deref(),
+            session_arg: session_arg.as_deref(),
+            is_fork,
+            description: pre_exec_description,
+            parent: pre_exec_parent,
+            timeout_seconds: resolve_run_timeout_seconds(timeout, skill.as_deref()),
+            output_format,
+            stream_mode,
+        })
+        .await;
+    }
+
     warn_if_tier_without_tool(tier.as_deref(), user_explicit_tool);
 
     let tool_strategy = resolve_run_tool_strategy(
@@ -441,6 +467,9 @@ pub(crate) async fn handle_run(
     }
 
     let effective_session_arg = if is_fork { None } else { session_arg.clone() };
+    if let Some(session) = effective_session_arg.as_deref() {
+        startup_env.ensure_not_in_lineage(&project_root, session)?;
+    }
 
     emit_reusable_session_hint(
         &project_root,
@@ -466,6 +495,7 @@ pub(crate) async fn handle_run(
     let memory_injection = pipeline::MemoryInjectionOptions {
         disabled: no_memory,
         query_override: memory_query,
+        explain: explain_memory,
     };
 
     let loop_strategy = if user_model_spec_explicit {
@@ -473,7 +503,7 @@ pub(crate) async fn handle_run(
     } else {
         strategy
     };
-    let loop_completion = execute_run_loop(RunLoopRequest {
+    let run_loop = execute_run_loop(RunLoopRequest {
         strategy: loop_strategy,
         initial_tool: resolved_tool,
         initial_model_spec: resolved_model_spec,
@@ -533,8 +563,9 @@ pub(crate) async fn handle_run(
         extra_readable,
         branch_guard,
         startup_env: &startup_env,
-    })
-    .await?;
+    });
+    // One run context across attempts, so slot waits reach the report.
+    let loop_completion = crate::session_resource_report::scope_run_usage(run_loop).await?;
 
     let loop_outcome = match loop_completion {
         RunLoopCompletion::Exit(exit_code) => return Ok(exit_code),
@@ -611,13 +642,16 @@ pub(crate) async fn handle_run(
         write_fallback_chain_to_result_toml(&project_root, sid, &loop_outcome.fallback_chain);
     }
 
+    let idle_timeout =
+        super::run_output::idle_timeout_error(&result, current_tool.as_str(), idle_timeout_seconds);
     emit_run_result_output(
         &project_root,
         output_format,
         session_id,
         &result,
         warning.as_ref(),
+        idle_timeout.as_ref(),
     )?;
 
-    Ok(result.exit_code)
+    Ok(crate::process_exit::run_exit_code(result.exit_code))
 }
@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use anyhow::Result;
+use csa_core::error::AppError;
 use csa_core::types::OutputFormat;
 use csa_process::ExecutionResult;
 
@@ -31,12 +32,26 @@ pub(super) fn enrich_ephemeral_signal_diagnostics(result: &mut ExecutionResult)
     result.stderr_output.push('\n');
 }
 
+/// Typed error for a run the idle watchdog cut short, reported as `error` in
+/// the JSON output. `idle_timeout_seconds` is `u64::MAX` when disabled.
+pub(super) fn idle_timeout_error(
+    result: &ExecutionResult,
+    tool: &str,
+    idle_timeout_seconds: u64,
+) -> Option<AppError> {
+    (result.terminal_reason.as_deref() == Some("idle_timeout")).then(|| AppError::IdleTimeout {
+        tool: tool.to_string(),
+        idle_secs: (idle_timeout_seconds != u64::MAX).then_some(idle_timeout_seconds),
+    })
+}
+
 pub(super) fn emit_run_result_output(
     project_root: &Path,
     output_format: OutputFormat,
     executed_session_id: Option<&str>,
     result: &ExecutionResult,
     large_diff_warning: Option<&csa_session::LargeDiffWarningReport>,
+    failure: Option<&AppError>,
 ) -> Result<()> {
     match output_format {
         OutputFormat::Text => {
@@ -73,7 +88,11 @@ pub(super) fn emit_run_result_output(
             }
         }
         OutputFormat::Json => {
-            let json = render_run_json_output(result, large_diff_warning)?;
+            let resources = executed_session_id
+                .and_then(|sid| csa_session::load_result(project_root, sid).ok().flatten())
+                .and_then(|session_result| session_result.resources);
+            let json =
+                render_run_json_output(result, large_diff_warning, resources.as_ref(), failure)?;
             println!("{json}");
         }
     }
@@ -84,8 +103,20 @@ pub(super) fn emit_run_result_output(
 pub(super) fn render_run_json_output(
     result: &ExecutionResult,
     large_diff_warning: Option<&csa_session::LargeDiffWarningReport>,
+    resources: Option<&csa_session::SessionResourceReport>,
+    failure: Option<&AppError>,
 ) -> Result<String> {
     let mut value = serde_json::to_value(result)?;
+    if let Some(failure) = failure
+        && let serde_json::Value::Object(fields) = &mut value
+    {
+        fields.insert("error".to_string(), failure.to_json());
+    }
+    if let Some(resources) = resources
+        && let serde_json::Value::Object(fields) = &mut value
+    {
+        fields.insert("resources".to_string(), serde_json::to_value(resources)?);
+    }
     if let Some(warning) = large_diff_warning
         && let serde_json::Value::Object(fields) = &mut value
     {
@@ -299,7 +330,7 @@ Implemented and committed.
             approx_diff_tokens: 18_000,
         };
 
-        let rendered = render_run_json_output(&result, Some(&warning))
+        let rendered = render_run_json_output(&result, Some(&warning), None, None)
             .expect("run JSON output should serialize");
         let json: serde_json::Value =
             serde_json::from_str(&rendered).expect("run JSON output should parse");
@@ -317,6 +348,31 @@ Implemented and committed.
         assert!(block.contains("<!-- CSA:LARGE_DIFF_WARNING:END -->"));
     }
 
+    #[test]
+    fn run_json_output_reports_idle_timeouts_as_typed_errors() {
+        let result = ExecutionResult {
+            exit_code: 137,
+            terminal_reason: Some("idle_timeout".to_string()),
+            ..Default::default()
+        };
+        let failure = idle_timeout_error(&result, "codex", 300);
+
+        let rendered = render_run_json_output(&result, None, None, failure.as_ref())
+            .expect("run JSON output should serialize");
+        let json: serde_json::Value =
+            serde_json::from_str(&rendered).expect("run JSON output should parse");
+
+        assert_eq!(json["error"]["kind"], "idle_timeout");
+        assert_eq!(json["error"]["context"]["tool"], "codex");
+        assert_eq!(json["error"]["context"]["idle_secs"], 300);
+        assert!(
+            idle_timeout_error(&ExecutionResult::default(), "codex", 300).is_none(),
+            "completed runs carry no error"
+        );
+        let disabled = idle_timeout_error(&result, "codex", u64::MAX).expect("idle timeout");
+        assert!(disabled.to_json()["context"].get("idle_secs").is_none());
+    }
+
     #[test]
     fn run_json_output_omits_large_diff_warning_when_absent() {
         let result = ExecutionResult {
@@ -326,12 +382,37 @@ Implemented and committed.
             ..Default::default()
         };
 
-        let rendered =
-            render_run_json_output(&result, None).expect("run JSON output should serialize");
+        let rendered = render_run_json_output(&result, None, None, None)
+            .expect("run JSON output should serialize");
         let json: serde_json::Value =
             serde_json::from_str(&rendered).expect("run JSON output should parse");
 
         assert!(json.get("large_diff_warning").is_none());
         assert!(json.get("large_diff_warning_block").is_none());
     }
+
+    #[test]
+    fn run_json_output_includes_session_resources() {
+        let result = ExecutionResult::default();
+        let resources = csa_session::SessionResourceReport {
+            peak_rss_mb: Some(512),
+            sandbox_mode: Some("cgroup".to_string()),
+            queue_wait_secs: 7,
+            limit_violations: vec!["disk budget exceeded: 2 GiB".to_string()],
+            ..Default::default()
+        };
+
+        let rendered = render_run_json_output(&result, None, Some(&resources), None)
+            .expect("run JSON output should serialize");
+        let json: serde_json::Value =
+            serde_json::from_str(&rendered).expect("run JSON output should parse");
+
+        assert_eq!(json["resources"]["peak_rss_mb"], 512);
+        assert_eq!(json["resources"]["sandbox_mode"], "cgroup");
+        assert_eq!(json["resources"]["queue_wait_secs"], 7);
+        assert_eq!(
+            json["resources"]["limit_violations"][0],
+            "disk budget exceeded: 2 GiB"
+        );
+    }
 }
@@ -0,0 +1,357 @@
+//! `csa run` for executor plugins (`[tools.<name>] plugin = true`).
+//!
+//! Plugins bypass the built-in executor pipeline but share its slots,
+//! session state, `output.log` spool, `result.toml` and run hooks. They never
+//! run inside the sandbox, so a config that requires one refuses them.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::error::AppError;
+use csa_core::types::{OutputFormat, ToolArg};
+use csa_executor::{PluginExecutor, PluginRequest};
+use csa_hooks::{HookEvent, HooksConfig};
+use csa_process::{ExecutionResult, StreamMode};
+use csa_session::SessionResult;
+
+use crate::pipeline::run_pipeline_hook;
+use crate::pipeline_post_exec::{update_cumulative_tokens, update_tool_state};
+use crate::startup_env::StartupSubtreeEnv;
+
+/// Result summaries are capped like the built-in executors' summaries.
+const PLUGIN_SUMMARY_MAX_CHARS: usize = 200;
+
+/// A run routed to an executor plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PluginRoute {
+    pub(super) name: String,
+    /// Tier model spec that selected the plugin, when routed through a tier.
+    pub(super) model_spec: Option<String>,
+}
+
+impl PluginRoute {
+    /// Model and thinking budget from the `<plugin>/<provider>/<model>/<budget>` spec.
+    fn spec_model_and_thinking(&self) -> (Option<&str>, Option<&str>) {
+        let Some(spec) = self.model_spec.as_deref() else {
+            return (None, None);
+        };
+        let mut parts = spec.splitn(4, '/').skip(2);
+        let model = parts.next().filter(|model| !model.is_empty());
+        let thinking = parts
+            .next()
+            .filter(|budget| !budget.is_empty() && *budget != "default");
+        (model, thinking)
+    }
+}
+
+/// Decide whether this run goes to an executor plugin.
+///
+/// `--tool <plugin>` selects the plugin directly (taking its model from the
+/// tier when one lists it). Without an explicit tool, the active tier routes
+/// to a plugin when the first usable entry in `models` is a plugin spec;
+/// disabled or uninstalled plugins are skipped, and a built-in spec listed
+/// first keeps the built-in pipeline.
+pub(super) fn resolve_plugin_route(
+    tool: Option<&ToolArg>,
+    tier: Option<&str>,
+    config: Option<&ProjectConfig>,
+) -> Result<Option<PluginRoute>> {
+    resolve_plugin_route_with(tool, tier, config, plugin_installed)
+}
+
+fn resolve_plugin_route_with(
+    tool: Option<&ToolArg>,
+    tier: Option<&str>,
+    config: Option<&ProjectConfig>,
+    installed: impl Fn(&str) -> bool,
+) -> Result<Option<PluginRoute>> {
+    let Some(config) = config else {
+        return Ok(None);
+    };
+    let tier_models = tier
+        .and_then(|tier| config.tiers.get(tier))
+        .map(|tier| tier.models.as_slice())
+        .unwrap_or_default();
+    let spec_tool = |spec: &str| spec.split('/').next().unwrap_or_default().to_string();
+
+    match tool {
+        Some(ToolArg::Alias(name)) if config.is_plugin_tool(name) => {
+            config.enforce_tool_enabled(name, false)?;
+            Ok(Some(PluginRoute {
+                name: name.clone(),
+                model_spec: tier_models
+                    .iter()
+                    .find(|spec| spec_tool(spec) == *name)
+                    .cloned(),
+            }))
+        }
+        None | Some(ToolArg::Auto) | Some(ToolArg::AnyAvailable) => {
+            for spec in tier_models {
+                let name = spec_tool(spec);
+                if !config.is_plugin_tool(&name) {
+                    return Ok(None);
+                }
+                if config.is_tool_enabled(&name) && installed(&name) {
+                    return Ok(Some(PluginRoute {
+                        name,
+                        model_spec: Some(spec.clone()),
+                    }));
+                }
+            }
+            Ok(None)
+        }
+        Some(_) => Ok(None),
+    }
+}
+
+fn plugin_installed(name: &str) -> bool {
+    csa_config::plugin_executable_path(name).is_some_and(|path| path.is_file())
+}
+
+pub(super) struct PluginRunRequest<'a> {
+    pub(super) route: PluginRoute,
+    pub(super) project_root: &'a Path,
+    pub(super) config: Option<&'a ProjectConfig>,
+    pub(super) global_config: &'a GlobalConfig,
+    pub(super) startup_env: &'a StartupSubtreeEnv,
+    pub(super) prompt: &'a str,
+    pub(super) model: Option<&'a str>,
+    pub(super) thinking: Option<&'a str>,
+    pub(super) session_arg: Option<&'a str>,
+    pub(super) is_fork: bool,
+    pub(super) description: Option<&'a str>,
+    pub(super) parent: Option<&'a str>,
+    pub(super) timeout_seconds: Option<u64>,
+    pub(super) output_format: OutputFormat,
+    pub(super) stream_mode: StreamMode,
+}
+
+/// Run `request.route` under a tool slot and record the turn in the session.
+pub(super) async fn run_plugin_route(request: PluginRunRequest<'_>) -> Result<i32> {
+    let name = request.route.name.as_str();
+    if request.is_fork {
+        anyhow::bail!("Executor plugin '{name}' does not support forking sessions");
+    }
+    let executable = csa_config::plugin_executable_path(name)
+        .context("Cannot resolve the user config directory for executor plugins")?;
+    if !executable.is_file() {
+        anyhow::bail!(
+            "Executor plugin '{name}' is registered but {} does not exist",
+            executable.display()
+        );
+    }
+    ensure_unsandboxed_run_allowed(request.config, name)?;
+    let plugin = PluginExecutor::new(name, executable);
+    let _slot = crate::pipeline::acquire_tool_slot(name, request.global_config)?;
+
+    let (mut session, resume_session_id) = match request.session_arg {
+        Some(session_ref) => {
+            request
+                .startup_env
+                .ensure_not_in_lineage(request.project_root, session_ref)?;
+            let resolved =
+                csa_session::resolve_resume_session(request.project_root, session_ref, name)?;
+            let session =
+                csa_session::load_session(request.project_root, &resolved.meta_session_id)?;
+            (session, resolved.provider_session_id)
+        }
+        None => (
+            csa_session::create_session(
+                request.project_root,
+                request.description,
+                request.parent,
+                Some(name),
+            )?,
+            None,
+        ),
+    };
+    tracing::info!("Executing in session: {}", session.meta_session_id);
+    let session_dir = csa_session::get_session_dir(request.project_root, &session.meta_session_id)?;
+    let spool_path = session_dir.join("output.log");
+    let mut spool = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&spool_path)
+        .with_context(|| format!("Failed to open {}", spool_path.display()))?;
+
+    let hooks_config = load_run_hooks(request.project_root, request.config);
+    let mut hook_vars = run_hook_variables(request.project_root, &session_dir, &session, name);
+    run_pipeline_hook(HookEvent::PreRun, &hooks_config, &hook_vars)?;
+
+    let (spec_model, spec_thinking) = request.route.spec_model_and_thinking();
+    let plugin_request = PluginRequest {
+        prompt: request.prompt,
+        model: request.model.or(spec_model),
+        thinking: request.thinking.or(spec_thinking),
+        resume_session_id: resume_session_id.as_deref(),
+        working_dir: request.project_root,
+        timeout: request.timeout_seconds.map(Duration::from_secs),
+    };
+    let started_at = Utc::now();
+    let tee = request.stream_mode == StreamMode::TeeToStderr;
+    let outcome = plugin
+        .execute(&plugin_request, &plugin.session_env(&session), |chunk| {
+            // The spool is what `csa session recover` rebuilds from; keep
+            // writing it even if a chunk fails to land.
+            let _ = spool.write_all(chunk.as_bytes());
+            if tee {
+                eprint!("{chunk}");
+            }
+        })
+        .await?;
+    drop(spool);
+    let completed_at = Utc::now();
+
+    let summary = truncate_summary(
+        outcome
+            .summary
+            .as_deref()
+            .filter(|summary| !summary.trim().is_empty())
+            .or_else(|| {
+                outcome
+                    .output
+                    .lines()
+                    .rev()
+                    .find(|line| !line.trim().is_empty())
+            })
+            .unwrap_or_default(),
+    );
+    let execution = ExecutionResult {
+        output: outcome.output.clone(),
+        stderr_output: outcome.stderr.clone(),
+        summary: summary.clone(),
+        exit_code: outcome.exit_code,
+        ..Default::default()
+    };
+
+    update_tool_state(
+        &mut session,
+        name,
+        &outcome.provider_session_id,
+        &execution,
+        &outcome.token_usage,
+    );
+    update_cumulative_tokens(&mut session, outcome.token_usage.clone());
+    session.turn_count = session.turn_count.saturating_add(1);
+    session.last_accessed = completed_at;
+    csa_session::save_session(&session)?;
+
+    if let Err(err) = csa_session::persist_structured_output_from_file(&session_dir, &spool_path) {
+        tracing::warn!(error = %err, "Failed to index executor plugin output");
+    }
+    let status = if outcome.timed_out {
+        "timeout"
+    } else if outcome.exit_code == 0 {
+        "success"
+    } else {
+        "failure"
+    };
+    csa_session::save_result(
+        request.project_root,
+        &session.meta_session_id,
+        &SessionResult {
+            status: status.to_string(),
+            exit_code: outcome.exit_code,
+            summary,
+            tool: name.to_string(),
+            started_at,
+            completed_at,
+            ..Default::default()
+        },
+    )?;
+
+    hook_vars.insert("exit_code".to_string(), outcome.exit_code.to_string());
+    run_pipeline_hook(HookEvent::PostRun, &hooks_config, &hook_vars)?;
+    if let Err(err) =
+        csa_hooks::run_hooks_for_event(HookEvent::SessionComplete, &hooks_config, &hook_vars)
+    {
+        tracing::warn!("SessionComplete hook failed: {err}");
+    }
+
+    super::run_output::emit_run_result_output(
+        request.project_root,
+        request.output_format,
+        Some(&session.meta_session_id),
+        &execution,
+        None,
+        None,
+    )?;
+    Ok(crate::process_exit::run_exit_code(outcome.exit_code))
+}
+
+/// Refuse a plugin run when the config requires a sandbox for it; otherwise
+/// say that resource limits configured for it are not applied.
+fn ensure_unsandboxed_run_allowed(config: Option<&ProjectConfig>, name: &str) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if let Some(message) = crate::pipeline_sandbox::unsandboxed_run_error(config, name) {
+        return Err(AppError::SandboxFailure {
+            tool: name.to_string(),
+            message,
+        }
+        .into());
+    }
+    if config.tool_enforcement_mode(name) != csa_config::EnforcementMode::Off {
+        tracing::warn!(
+            tool = name,
+            "Executor plugins run without the sandbox; resource limits are not applied"
+        );
+    }
+    Ok(())
+}
+
+/// Run hooks for the `run` task, as the built-in pipeline loads them.
+fn load_run_hooks(project_root: &Path, config: Option<&ProjectConfig>) -> HooksConfig {
+    let project_overrides = crate::pipeline::build_project_hook_overrides(config, Some("run"));
+    csa_hooks::load_hooks_config(
+        csa_session::get_session_root(project_root)
+            .ok()
+            .map(|root| root.join("hooks.toml"))
+            .as_deref(),
+        csa_hooks::global_hooks_path().as_deref(),
+        project_overrides.as_ref(),
+    )
+}
+
+fn run_hook_variables(
+    project_root: &Path,
+    session_dir: &Path,
+    session: &csa_session::MetaSessionState,
+    tool: &str,
+) -> HashMap<String, String> {
+    let sessions_root = session_dir.parent().unwrap_or(session_dir);
+    HashMap::from([
+        ("session_id".to_string(), session.meta_session_id.clone()),
+        ("session_dir".to_string(), session_dir.display().to_string()),
+        (
+            "sessions_root".to_string(),
+            sessions_root.display().to_string(),
+        ),
+        ("tool".to_string(), tool.to_string()),
+        (
+            "project_root".to_string(),
+            project_root.display().to_string(),
+        ),
+        ("CHANGED_PATHS".to_string(), "[]".to_string()),
+        ("CHANGED_CRATES".to_string(), String::new()),
+        ("CHANGED_CRATES_FLAGS".to_string(), String::new()),
+    ])
+}
+
+fn truncate_summary(summary: &str) -> String {
+    let summary = summary.trim();
+    match summary.char_indices().nth(PLUGIN_SUMMARY_MAX_CHARS) {
+        Some((idx, _)) => format!("{}...", &summary[..idx]),
+        None => summary.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "run_cmd_execute_plugin_tests.rs"]
+mod tests;
@@ -0,0 +1,135 @@
+use super::*;
+
+fn config(toml_src: &str) -> ProjectConfig {
+    toml::from_str(toml_src).expect("test config should parse")
+}
+
+const PLUGIN_FIRST: &str = r#"
+[tools.local-llm]
+plugin = true
+
+[tools.offline]
+plugin = true
+
+[tiers.quick]
+description = "quick"
+models = [
+    "offline/none/tiny/default",
+    "local-llm/ollama/qwen3-coder/high",
+    "codex/openai/gpt-5.5/xhigh",
+]
+"#;
+
+fn route(name: &str, spec: Option<&str>) -> Option<PluginRoute> {
+    Some(PluginRoute {
+        name: name.to_string(),
+        model_spec: spec.map(str::to_string),
+    })
+}
+
+#[test]
+fn tier_routes_to_first_installed_plugin_before_builtins() {
+    let cfg = config(PLUGIN_FIRST);
+    let installed = |name: &str| name == "local-llm";
+
+    let selected = resolve_plugin_route_with(None, Some("quick"), Some(&cfg), installed).unwrap();
+    assert_eq!(
+        selected,
+        route("local-llm", Some("local-llm/ollama/qwen3-coder/high"))
+    );
+    assert_eq!(
+        selected.unwrap().spec_model_and_thinking(),
+        (Some("qwen3-coder"), Some("high"))
+    );
+
+    // Nothing installed: the built-in spec takes over.
+    assert_eq!(
+        resolve_plugin_route_with(None, Some("quick"), Some(&cfg), |_| false).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn builtin_listed_first_keeps_builtin_pipeline() {
+    let cfg = config(
+        r#"
+[tools.local-llm]
+plugin = true
+
+[tiers.quick]
+description = "quick"
+models = ["codex/openai/gpt-5.5/xhigh", "local-llm/ollama/qwen3-coder/default"]
+"#,
+    );
+    assert_eq!(
+        resolve_plugin_route_with(None, Some("quick"), Some(&cfg), |_| true).unwrap(),
+        None
+    );
+    assert_eq!(
+        resolve_plugin_route_with(
+            Some(&ToolArg::Specific(csa_core::types::ToolName::Codex)),
+            Some("quick"),
+            Some(&cfg),
+            |_| true
+        )
+        .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn explicit_plugin_tool_uses_tier_model_and_respects_enabled() {
+    let cfg = config(PLUGIN_FIRST);
+    let tool = ToolArg::Alias("local-llm".to_string());
+    let selected =
+        resolve_plugin_route_with(Some(&tool), Some("quick"), Some(&cfg), |_| false).unwrap();
+    assert_eq!(
+        selected,
+        route("local-llm", Some("local-llm/ollama/qwen3-coder/high"))
+    );
+    assert_eq!(
+        resolve_plugin_route_with(Some(&tool), None, Some(&cfg), |_| false).unwrap(),
+        route("local-llm", None)
+    );
+
+    let disabled = config("[tools.local-llm]\nplugin = true\nenabled = false\n");
+    assert!(resolve_plugin_route_with(Some(&tool), None, Some(&disabled), |_| true).is_err());
+
+    let unknown = ToolArg::Alias("not-a-plugin".to_string());
+    assert_eq!(
+        resolve_plugin_route_with(Some(&unknown), None, Some(&cfg), |_| true).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn summary_truncation_is_char_safe() {
+    assert_eq!(truncate_summary("  done  "), "done");
+    let long = "é".repeat(PLUGIN_SUMMARY_MAX_CHARS + 5);
+    let truncated = truncate_summary(&long);
+    assert!(truncated.ends_with("..."));
+    assert_eq!(truncated.chars().count(), PLUGIN_SUMMARY_MAX_CHARS + 3);
+}
+
+#[test]
+fn plugins_are_refused_when_a_sandbox_is_required() {
+    let allowed = config("[tools.local-llm]\nplugin = true\n");
+    assert!(ensure_unsandboxed_run_allowed(Some(&allowed), "local-llm").is_ok());
+    assert!(ensure_unsandboxed_run_allowed(None, "local-llm").is_ok());
+
+    for required in [
+        "[tools.local-llm]\nplugin = true\nenforcement_mode = \"required\"\n",
+        "[tools.local-llm]\nplugin = true\n[tools.local-llm.filesystem_sandbox]\nenforcement_mode = \"required\"\n",
+        "[tools.local-llm]\nplugin = true\n[resources]\nnetwork = \"none\"\n",
+    ] {
+        let err = ensure_unsandboxed_run_allowed(Some(&config(required)), "local-llm")
+            .expect_err(required);
+        assert!(
+            matches!(
+                err.downcast_ref::<AppError>(),
+                Some(AppError::SandboxFailure { tool, .. }) if tool == "local-llm"
+            ),
+            "{err:#}"
+        );
+    }
+}
@@ -122,6 +122,7 @@ async fn run_preflight_fixture(project_root: &Path, no_preflight: bool) -> anyho
         false,
         false,
         None,
+        false,
         0,
         OutputFormat::Text,
         csa_process::StreamMode::BufferOnly,
@@ -191,6 +192,7 @@ async fn handle_run_persists_model_spec_tier_bypass_pre_exec_result() {
         false,
         false,
         None,
+        false,
         0,
         OutputFormat::Text,
         csa_process::StreamMode::BufferOnly,
@@ -367,6 +369,7 @@ async fn handle_run_fails_fast_when_worktree_write_lock_is_held() {
         false,
         false,
         None,
+        false,
         0,
         OutputFormat::Text,
         csa_process::StreamMode::BufferOnly,
@@ -456,6 +459,7 @@ async fn handle_run_does_not_persist_result_for_non_conflict_pre_exec_error() {
         false,
         false,
         None,
+        false,
         0,
         OutputFormat::Text,
         csa_process::StreamMode::BufferOnly,
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::error::AppError;
 use csa_core::types::{ToolArg, ToolName, ToolSelectionStrategy};
 use weave::parser::AgentConfig;
 
@@ -49,7 +50,7 @@ pub(super) fn resolve_run_tool_strategy(
     let resolved_tool_arg = tool_arg
         .unwrap_or(ToolArg::Auto)
         .resolve_alias(tool_aliases)
-        .map_err(anyhow::Error::msg)?;
+        .map_err(AppError::config)?;
     let tier_failover_tool_filter = resolve_tier_failover_tool_filter(
         user_explicit_tool,
         active_tier,
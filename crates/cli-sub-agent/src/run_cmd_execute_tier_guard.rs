@@ -3,6 +3,7 @@ use std::path::Path;
 use anyhow::Result;
 
 use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::error::AppError;
 
 use super::routing::{RunModelSelectionFlags, enforce_run_tier_bypass_gate};
 
@@ -56,7 +57,7 @@ pub(super) fn enforce_direct_tool_tier_guard(ctx: DirectToolTierGuardCtx<'_>) ->
     let cfg = ctx
         .config
         .expect("tiers_configured should imply project config is present");
-    let err = anyhow::anyhow!(crate::run_helpers::format_run_direct_tool_tier_policy_error(cfg));
+    let err = AppError::config(crate::run_helpers::format_run_direct_tool_tier_policy_error(cfg));
     Err(ctx.pre_exec.persist(ctx.effective_tier, err))
 }
 
@@ -372,7 +372,14 @@ pub(crate) fn fork_call_slot_handoff(
                     .collect();
                 let all_usage = slot_usage(slots_dir, &all_tools_ref);
                 let diag_msg = format_slot_diagnostic(tool_name_str, &status, &all_usage);
-                anyhow::bail!("fork-call child slot exhausted: {diag_msg}");
+                return Err(
+                    anyhow::Error::new(csa_core::error::AppError::SlotExhausted {
+                        tool: tool_name_str.to_string(),
+                        max: status.max_slots,
+                        alternatives: Vec::new(),
+                    })
+                    .context(format!("fork-call child slot exhausted: {diag_msg}")),
+                );
             }
         }
     };
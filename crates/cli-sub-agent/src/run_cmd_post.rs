@@ -26,7 +26,7 @@ pub(crate) use failover::{
     ErrorRateLimitFailoverRequest, RateLimitAction, RateLimitFailoverRequest,
     detect_permanent_tool_exhaustion_result, detect_permanent_tool_exhaustion_text,
     evaluate_error_rate_limit_failover_with_catalog, evaluate_rate_limit_failover_with_catalog,
-    format_tool_exhausted_summary, is_permanent_tool_exhaustion_error,
+    format_tool_exhausted_summary, is_permanent_tool_exhaustion_error, rate_limited_error,
 };
 #[cfg(test)]
 pub(crate) use failover::{
@@ -26,7 +26,7 @@ use failover_detection::{
 };
 pub(crate) use failover_detection::{
     detect_permanent_tool_exhaustion_result, detect_permanent_tool_exhaustion_text,
-    format_tool_exhausted_summary, is_permanent_tool_exhaustion_error,
+    format_tool_exhausted_summary, is_permanent_tool_exhaustion_error, rate_limited_error,
 };
 
 /// Outcome of rate-limit failover evaluation.
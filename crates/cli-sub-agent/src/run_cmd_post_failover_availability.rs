@@ -132,6 +132,10 @@ pub(super) fn decide_available_failover(
         ) {
             crate::run_helpers::ToolBinaryAvailability::Available { .. } => {
                 let tool = crate::run_helpers::parse_tool_name(&new_tool)?;
+                csa_core::metrics::record(&csa_core::metrics::MetricEvent::Failover {
+                    from: failed_tool.to_string(),
+                    to: new_tool,
+                });
                 return Ok(RateLimitAction::Retry {
                     new_tool: tool,
                     new_model_spec: Some(new_model_spec),
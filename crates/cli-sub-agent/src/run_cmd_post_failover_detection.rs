@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use csa_core::error::AppError;
 use tracing::warn;
 
 #[derive(Clone, Copy)]
@@ -152,6 +153,29 @@ pub(super) fn detect_transport_error_failover_signal(
     })
 }
 
+/// Typed [`AppError::RateLimited`] for a failed attempt whose error chain is a
+/// provider rate limit or quota error, so JSON output and the exit code report
+/// it as one. Crash, auth and overload signals also drive failover but are not
+/// rate limits, so they stay untyped.
+pub(crate) fn rate_limited_error(
+    tool_name_str: &str,
+    error_message: &str,
+    current_model_spec: Option<&str>,
+) -> Option<AppError> {
+    detect_transport_error_failover_signal(tool_name_str, error_message, current_model_spec)
+        .filter(|signal| matches!(signal.kind, TransportErrorFailoverKind::RateLimit))
+        .filter(|signal| {
+            matches!(
+                signal.reason.as_str(),
+                "HTTP 429" | "QUOTA_EXHAUSTED" | "RESOURCE_EXHAUSTED" | "codex_429_retry_exhausted"
+            )
+        })
+        .map(|signal| AppError::RateLimited {
+            tool: tool_name_str.to_string(),
+            message: format!("{} (matched '{}')", signal.reason, signal.matched_pattern),
+        })
+}
+
 pub(super) fn allows_init_failure_failover(
     tool_name: &str,
     reason: &str,
@@ -40,6 +40,7 @@ fn test_session(
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
@@ -134,6 +134,46 @@ fn test_cli_memory_migrate_mempal_parses() {
     }
 }
 
+#[test]
+fn test_cli_memory_export_import_parse() {
+    let cli = try_parse_cli(&["csa", "memory", "export", "--out", "mem.jsonl"]).unwrap();
+    match cli.command {
+        crate::cli::Commands::Memory {
+            command: crate::cli::MemoryCommands::Export { out, project },
+        } => {
+            assert_eq!(out, std::path::PathBuf::from("mem.jsonl"));
+            assert!(project.is_none());
+        }
+        _ => panic!("expected memory export command"),
+    }
+
+    let cli = try_parse_cli(&[
+        "csa",
+        "memory",
+        "import",
+        "mem.jsonl",
+        "--project",
+        "team-kb",
+        "--dry-run",
+    ])
+    .unwrap();
+    match cli.command {
+        crate::cli::Commands::Memory {
+            command:
+                crate::cli::MemoryCommands::Import {
+                    path,
+                    project,
+                    dry_run,
+                },
+        } => {
+            assert_eq!(path, std::path::PathBuf::from("mem.jsonl"));
+            assert_eq!(project.as_deref(), Some("team-kb"));
+            assert!(dry_run);
+        }
+        _ => panic!("expected memory import command"),
+    }
+}
+
 #[test]
 fn test_cli_fork_from_conflicts_with_session() {
     let result = try_parse_cli(&[
@@ -504,6 +544,16 @@ fn test_cli_memory_query_flag_parses() {
     }
 }
 
+#[test]
+fn test_cli_explain_memory_flag_parses() {
+    let cli = try_parse_cli(&["csa", "run", "--explain-memory", "prompt"]).unwrap();
+    match cli.command {
+        crate::cli::Commands::Run { explain_memory, .. } => assert!(explain_memory),
+        _ => panic!("expected Run command"),
+    }
+    assert!(try_parse_cli(&["csa", "run", "--explain-memory", "--no-memory", "prompt"]).is_err());
+}
+
 #[test]
 fn test_cli_timeout_flag_parses() {
     let cli = try_parse_cli(&["csa", "run", "--timeout", "600", "prompt"]).unwrap();
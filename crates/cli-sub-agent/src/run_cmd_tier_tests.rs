@@ -154,6 +154,7 @@ async fn handle_run_persists_result_for_direct_tool_tier_rejection() {
         false,
         false,
         None,
+        false,
         0,
         OutputFormat::Text,
         csa_process::StreamMode::BufferOnly,
@@ -309,6 +310,7 @@ async fn handle_run_reuses_matching_session_tier_for_direct_tool() {
         false,
         false,
         None,
+        false,
         0,
         OutputFormat::Text,
         csa_process::StreamMode::BufferOnly,
@@ -181,6 +181,7 @@ fn memory_soft_limit_require_commit_with_mm_status_records_commit_only_recovery(
         memory_max_mb: Some(10000),
         soft_limit_percent: Some(90),
         scope_name: Some("csa-codex-01KW641KP78VR43SCKJVN6HGDN.scope".to_string()),
+        partial_results_saved: false,
     });
     csa_session::save_result(root, &session.meta_session_id, &session_result)
         .expect("result should be saved");
@@ -267,6 +268,7 @@ fn memory_soft_limit_require_commit_with_staged_work_fails_closed_for_writer() {
         memory_max_mb: Some(10000),
         soft_limit_percent: Some(90),
         scope_name: Some("csa-codex-01KW641KP78VR43SCKJVN6HGDN.scope".to_string()),
+        partial_results_saved: false,
     });
     csa_session::save_result(root, &session.meta_session_id, &session_result)
         .expect("result should be saved");
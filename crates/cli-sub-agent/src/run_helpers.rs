@@ -3,6 +3,7 @@
 use anyhow::Result;
 
 use csa_config::ProjectConfig;
+use csa_core::error::AppError;
 use csa_core::types::ToolName;
 use csa_executor::ModelSpec;
 
@@ -133,7 +134,7 @@ pub(crate) fn validate_direct_tool_tier_restriction(
     let mut available: Vec<&str> = cfg.tiers.keys().map(|k| k.as_str()).collect();
     available.sort_unstable();
     let alias_hint = cfg.format_tier_aliases();
-    anyhow::bail!(
+    Err(AppError::config(format!(
         "Direct --tool is restricted when tiers are configured. \
          Use --tier <name> to specify which tier's model/thinking config to use, \
          or use --tier <name> --tool <tool> to prefer a tool inside that tier. \
@@ -142,7 +143,8 @@ pub(crate) fn validate_direct_tool_tier_restriction(
          [tier_policy].allow_force_bypass = true in the global CSA config. \
          Available tiers: [{}]{alias_hint}",
         available.join(", ")
-    );
+    ))
+    .into())
 }
 
 pub(crate) fn format_run_direct_tool_tier_policy_error(cfg: &ProjectConfig) -> String {
@@ -1,6 +1,7 @@
 use anyhow::Result;
 
 use csa_config::GlobalConfig;
+use csa_core::error::AppError;
 use csa_core::types::ToolName;
 
 /// Check if a prompt is a context compress/compact command.
@@ -11,16 +12,10 @@ pub(crate) fn is_compress_command(prompt: &str) -> bool {
 
 /// Parse a tool name string to ToolName enum.
 pub(crate) fn parse_tool_name(name: &str) -> Result<ToolName> {
-    match name {
-        "gemini-cli" | "gemini" => anyhow::bail!("{}", csa_core::types::removed_tool_error(name)),
-        "opencode" => Ok(ToolName::Opencode),
-        "codex" => Ok(ToolName::Codex),
-        "claude-code" => Ok(ToolName::ClaudeCode),
-        "openai-compat" => Ok(ToolName::OpenaiCompat),
-        "hermes" => Ok(ToolName::Hermes),
-        "antigravity-cli" => Ok(ToolName::AntigravityCli),
-        _ => anyhow::bail!("Unknown tool: {name}"),
+    if csa_core::types::is_removed_tool_name(name) {
+        anyhow::bail!("{}", csa_core::types::removed_tool_error(name));
     }
+    ToolName::from_id(name).ok_or_else(|| AppError::config(format!("Unknown tool: {name}")).into())
 }
 
 /// Truncate a string to max_len characters, adding "..." if truncated.
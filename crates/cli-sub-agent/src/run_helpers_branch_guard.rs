@@ -4,7 +4,9 @@ use anyhow::Result;
 use csa_config::{GlobalConfig, ProjectConfig};
 use csa_core::vcs::VcsKind;
 
-pub(crate) const BRANCH_GUARD_EXIT_CODE: i32 = 2;
+/// A refusal is a policy (configuration) error. This was 2 before the exit
+/// code taxonomy gave 2 to tool failures.
+pub(crate) const BRANCH_GUARD_EXIT_CODE: i32 = csa_core::exit_code::ExitCode::ConfigError.code();
 
 const HARDCODED_PROTECTED_BRANCHES: &[&str] = &["main", "master", "dev", "develop"];
 
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use csa_config::{GlobalConfig, ProjectConfig};
+use csa_core::error::AppError;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub(crate) struct TierBypassGateFlags {
@@ -38,7 +39,7 @@ pub(crate) fn enforce_tier_bypass_gate(ctx: TierBypassGateCtx<'_>) -> Result<()>
     let mut tier_names: Vec<&str> = cfg.tiers.keys().map(String::as_str).collect();
     tier_names.sort_unstable();
     let aliases = cfg.format_tier_aliases();
-    anyhow::bail!(
+    Err(AppError::config(format!(
         "Tier bypass is disabled because [tiers] are configured. \
          Use --tier <name> to select a configured tier. \
          Available tiers: [{}]{aliases}. \
@@ -50,7 +51,8 @@ pub(crate) fn enforce_tier_bypass_gate(ctx: TierBypassGateCtx<'_>) -> Result<()>
          Refused flags: {}.",
         tier_names.join(", "),
         refused_flags.join(", ")
-    );
+    ))
+    .into())
 }
 
 pub(crate) fn tier_bypass_allowed(
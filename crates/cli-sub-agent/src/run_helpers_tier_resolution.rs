@@ -50,6 +50,10 @@ pub(crate) fn evaluate_tier_models_with_catalog(
             continue;
         }
         let tool_str = parts[0];
+        // Executor plugins are routed before built-in tier resolution.
+        if config.is_plugin_tool(tool_str) {
+            continue;
+        }
         let Ok(tool) = parse_tool_name(tool_str) else {
             excluded.push(TierModelExclusion {
                 model_spec: spec.clone(),
@@ -82,14 +82,15 @@ pub(crate) fn resolved_tool_binary_name(
     config: Option<&ProjectConfig>,
 ) -> Option<&'static str> {
     match tool_name {
-        "gemini-cli" => Some("gemini"),
-        "opencode" => Some("opencode"),
         "codex" => Some(resolved_codex_transport(config).runtime_binary_name()),
         "claude-code" => Some(resolved_claude_code_transport(config).runtime_binary_name()),
-        "hermes" => Some("hermes"),
-        "antigravity-cli" => Some("antigravity"),
-        "openai-compat" => None,
-        _ => None,
+        // Removed tools keep their binary so diagnostics can still name it.
+        _ => match csa_core::tool_registry::builtin_tool_by_id(tool_name) {
+            Some(builtin) => builtin.binary,
+            None => csa_config::tool_registry()
+                .get(tool_name)
+                .and_then(|tool| tool.binary.as_deref()),
+        },
     }
 }
 
@@ -235,6 +236,11 @@ pub(crate) fn tool_binary_availability(
             "claude-code" => Cow::Borrowed(resolved_claude_code_transport(config).install_hint()),
             _ => Cow::Borrowed(
                 install_hint_for_known_tool(tool_name)
+                    .or_else(|| {
+                        csa_config::tool_registry()
+                            .get(tool_name)
+                            .and_then(|tool| tool.install_hint.as_deref())
+                    })
                     .unwrap_or("Install the tool and ensure it is on PATH"),
             ),
         };
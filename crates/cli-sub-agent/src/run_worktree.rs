@@ -0,0 +1,338 @@
+//! `csa run --isolated-worktree`: run the tool in a throwaway git worktree.
+//!
+//! The worktree is a detached checkout of HEAD under the temp dir. After the
+//! run every change (including untracked files) is captured as one binary diff
+//! against the base commit, recorded in the session as `worktree.diff` plus
+//! `worktree.toml`, and then left for review, applied to the live tree, or
+//! committed to a new branch. The worktree itself is always removed.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use crate::cli::IsolatedWorktreeArgs;
+use crate::goal_loop::GoalRunRequest;
+
+pub(crate) const WORKTREE_DIFF_FILE: &str = "worktree.diff";
+pub(crate) const WORKTREE_RECORD_FILE: &str = "worktree.toml";
+
+/// What happens to the worktree diff once the run finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WorktreeOutcome {
+    /// Keep the diff in the session and print how to apply it.
+    Review,
+    /// Apply the diff to the live working tree (unstaged).
+    Apply,
+    /// Commit the changes to a new branch with this name.
+    Branch(String),
+}
+
+impl WorktreeOutcome {
+    pub(crate) fn from_args(args: &IsolatedWorktreeArgs) -> Option<Self> {
+        if !args.isolated_worktree {
+            return None;
+        }
+        Some(match (&args.worktree_branch, args.worktree_apply) {
+            (Some(branch), _) => Self::Branch(branch.clone()),
+            (None, true) => Self::Apply,
+            (None, false) => Self::Review,
+        })
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Review => "review",
+            Self::Apply => "apply",
+            Self::Branch(_) => "branch",
+        }
+    }
+}
+
+/// Contents of `worktree.toml` in the session directory.
+#[derive(Debug, Serialize)]
+struct WorktreeRecord {
+    worktree_path: PathBuf,
+    project_root: PathBuf,
+    base_commit: String,
+    mode: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    /// Whether `--worktree-apply` or `--worktree-branch` succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delivered: Option<bool>,
+    changed: bool,
+    diff_stat: String,
+    diff_file: String,
+}
+
+/// A detached checkout of HEAD under the temp dir, also used by `csa bench`.
+pub(crate) struct IsolatedWorktree {
+    pub(crate) project_root: PathBuf,
+    pub(crate) path: PathBuf,
+    base_commit: String,
+}
+
+impl IsolatedWorktree {
+    pub(crate) fn create(project_root: &Path) -> Result<Self> {
+        let toplevel = git_stdout(project_root, &["rev-parse", "--show-toplevel"])
+            .context("an isolated worktree requires a git repository")?;
+        let project_root = PathBuf::from(toplevel);
+        let base_commit = git_stdout(&project_root, &["rev-parse", "HEAD"])
+            .context("an isolated worktree requires at least one commit")?;
+        let path = std::env::temp_dir().join(format!(
+            "csa-worktree-{}",
+            ulid::Ulid::new().to_string().to_lowercase()
+        ));
+        let path_arg = path.to_string_lossy().into_owned();
+        git_stdout(
+            &project_root,
+            &["worktree", "add", "--detach", &path_arg, &base_commit],
+        )
+        .with_context(|| format!("Failed to create git worktree at {}", path.display()))?;
+        // Sessions store the canonical path; keep the two comparable.
+        let path = path.canonicalize().unwrap_or(path);
+        Ok(Self {
+            project_root,
+            path,
+            base_commit,
+        })
+    }
+
+    /// Parts of the main repository's git dir the tool has to write to: the
+    /// worktree's own admin dir (`worktrees/<name>`: HEAD, index, logs) and
+    /// the object store. Branches and other refs stay read-only.
+    pub(crate) fn writable_git_paths(&self) -> Result<Vec<PathBuf>> {
+        let admin_dir = git_stdout(&self.path, &["rev-parse", "--absolute-git-dir"])?;
+        let objects = git_stdout(&self.path, &["rev-parse", "--git-path", "objects"])?;
+        Ok(vec![PathBuf::from(admin_dir), self.path.join(objects)])
+    }
+
+    /// Stage everything and return `(binary diff, shortstat)` against the base.
+    fn capture_diff(&self) -> Result<(Vec<u8>, String)> {
+        git_stdout(&self.path, &["add", "-A"])?;
+        let diff = git_output(
+            &self.path,
+            &["diff", "--cached", "--binary", &self.base_commit],
+        )?;
+        let stat = git_stdout(
+            &self.path,
+            &["diff", "--cached", "--shortstat", &self.base_commit],
+        )?;
+        Ok((diff, stat))
+    }
+
+    /// Commit the staged changes (if any) and point a new branch at the result,
+    /// so commits made by the tool itself are kept as well.
+    fn commit_to_branch(&self, branch: &str, message: &str) -> Result<()> {
+        git_stdout(&self.path, &["check-ref-format", "--branch", branch])
+            .with_context(|| format!("Invalid branch name '{branch}'"))?;
+        let staged = !Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["diff", "--cached", "--quiet"])
+            .status()
+            .context("Failed to run git diff")?
+            .success();
+        if staged {
+            git_stdout(
+                &self.path,
+                &["commit", "--quiet", "--no-verify", "-m", message],
+            )?;
+        }
+        git_stdout(&self.path, &["branch", branch, "HEAD"])?;
+        Ok(())
+    }
+
+    pub(crate) fn remove(&self) {
+        let path_arg = self.path.to_string_lossy().into_owned();
+        if let Err(err) = git_stdout(
+            &self.project_root,
+            &["worktree", "remove", "--force", &path_arg],
+        ) {
+            eprintln!(
+                "warning: failed to remove isolated worktree {}: {err:#}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// Run `request` inside a fresh worktree and deliver the resulting diff.
+pub(crate) async fn run_in_isolated_worktree(
+    mut request: GoalRunRequest,
+    outcome: WorktreeOutcome,
+) -> Result<i32> {
+    let project_root = crate::pipeline::determine_project_root(request.cd.as_deref())?;
+    let worktree = IsolatedWorktree::create(&project_root)?;
+    eprintln!(
+        "csa run: isolated worktree {} (base {})",
+        worktree.path.display(),
+        short_sha(&worktree.base_commit)
+    );
+
+    request.cd = Some(worktree.path.to_string_lossy().into_owned());
+    match worktree.writable_git_paths() {
+        Ok(paths) => request.extra_writable.extend(paths),
+        Err(err) => eprintln!("warning: worktree git dir stays read-only: {err:#}"),
+    }
+    // Keep the run's sessions with the project, not with the throwaway path.
+    csa_session::alias_session_root(&worktree.path, &worktree.project_root);
+
+    let result = crate::goal_loop::dispatch_run_or_goal(request).await;
+    if let Err(err) = finish_worktree(&worktree, &outcome) {
+        eprintln!("warning: isolated worktree diff was not delivered: {err:#}");
+    }
+    csa_session::remove_session_root_alias(&worktree.path);
+    worktree.remove();
+    result
+}
+
+fn finish_worktree(worktree: &IsolatedWorktree, outcome: &WorktreeOutcome) -> Result<()> {
+    let (diff, diff_stat) = worktree.capture_diff()?;
+    let session_id = latest_worktree_session(worktree);
+    let record_dir = match &session_id {
+        Some(id) => csa_session::get_session_dir(&worktree.project_root, id)?,
+        // No session (e.g. the run failed before creating one): keep the diff next
+        // to the project's sessions so it is not lost with the worktree.
+        None => csa_session::get_session_root(&worktree.project_root)?
+            .join("worktrees")
+            .join(worktree.path.file_name().unwrap_or_default()),
+    };
+    std::fs::create_dir_all(&record_dir)
+        .with_context(|| format!("Failed to create {}", record_dir.display()))?;
+    let diff_path = record_dir.join(WORKTREE_DIFF_FILE);
+    std::fs::write(&diff_path, &diff)
+        .with_context(|| format!("Failed to write {}", diff_path.display()))?;
+
+    let changed = !diff.is_empty();
+    let delivered = match outcome {
+        WorktreeOutcome::Review => None,
+        WorktreeOutcome::Apply | WorktreeOutcome::Branch(_) if !changed => None,
+        WorktreeOutcome::Apply => Some(report_delivery(
+            apply_diff(&worktree.project_root, &diff_path),
+            "applied worktree diff to the working tree",
+        )),
+        WorktreeOutcome::Branch(branch) => {
+            let message = format!(
+                "csa run {}",
+                session_id.as_deref().unwrap_or("(isolated worktree)")
+            );
+            Some(report_delivery(
+                worktree.commit_to_branch(branch, &message),
+                &format!("committed worktree changes to branch '{branch}'"),
+            ))
+        }
+    };
+
+    let record = WorktreeRecord {
+        worktree_path: worktree.path.clone(),
+        project_root: worktree.project_root.clone(),
+        base_commit: worktree.base_commit.clone(),
+        mode: outcome.label(),
+        branch: match outcome {
+            WorktreeOutcome::Branch(branch) => Some(branch.clone()),
+            _ => None,
+        },
+        delivered,
+        changed,
+        diff_stat: diff_stat.clone(),
+        diff_file: WORKTREE_DIFF_FILE.to_string(),
+    };
+    let record_path = record_dir.join(WORKTREE_RECORD_FILE);
+    std::fs::write(&record_path, toml::to_string_pretty(&record)?)
+        .with_context(|| format!("Failed to write {}", record_path.display()))?;
+
+    if !changed {
+        eprintln!("csa run: isolated worktree has no changes");
+    } else {
+        eprintln!("csa run: worktree diff: {diff_stat}");
+        eprintln!("  diff: {}", diff_path.display());
+        if *outcome == WorktreeOutcome::Review || delivered == Some(false) {
+            eprintln!(
+                "  apply: git -C {} apply {}",
+                worktree.project_root.display(),
+                diff_path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn report_delivery(result: Result<()>, done: &str) -> bool {
+    match result {
+        Ok(()) => {
+            eprintln!("csa run: {done}");
+            true
+        }
+        Err(err) => {
+            eprintln!("warning: {err:#}");
+            false
+        }
+    }
+}
+
+fn apply_diff(project_root: &Path, diff_path: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_root)
+        .arg("apply")
+        .arg(diff_path)
+        .output()
+        .context("Failed to run git apply")?;
+    if !output.status.success() {
+        bail!(
+            "git apply failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// The shallowest, most recent session created in the worktree. Those are
+/// stored with the project's sessions but keep the worktree as project path.
+fn latest_worktree_session(worktree: &IsolatedWorktree) -> Option<String> {
+    csa_session::list_sessions(&worktree.project_root, None)
+        .ok()?
+        .into_iter()
+        .filter(|session| Path::new(&session.project_path).starts_with(&worktree.path))
+        .min_by(|a, b| {
+            a.genealogy
+                .depth
+                .cmp(&b.genealogy.depth)
+                .then(b.created_at.cmp(&a.created_at))
+        })
+        .map(|session| session.meta_session_id)
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(12)]
+}
+
+fn git_stdout(dir: &Path, args: &[&str]) -> Result<String> {
+    Ok(String::from_utf8_lossy(&git_output(dir, args)?)
+        .trim()
+        .to_string())
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+#[path = "run_worktree_tests.rs"]
+mod tests;
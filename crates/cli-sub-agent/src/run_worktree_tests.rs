@@ -0,0 +1,148 @@
+use super::*;
+use crate::cli::{Cli, Commands};
+use clap::Parser;
+use tempfile::TempDir;
+
+fn parse_outcome(args: &[&str]) -> Result<Option<WorktreeOutcome>, clap::Error> {
+    match Cli::try_parse_from(args)?.command {
+        Commands::Run { worktree, .. } => Ok(WorktreeOutcome::from_args(&worktree)),
+        _ => panic!("expected run command"),
+    }
+}
+
+fn run_git(repo: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .expect("git command should execute");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn init_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    run_git(dir.path(), &["init", "-q"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    run_git(dir.path(), &["config", "user.name", "Test"]);
+    std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+    run_git(dir.path(), &["add", "."]);
+    run_git(dir.path(), &["commit", "-q", "-m", "init"]);
+    dir
+}
+
+#[test]
+fn worktree_flags_select_outcome() {
+    assert_eq!(parse_outcome(&["csa", "run", "x"]).unwrap(), None);
+    assert_eq!(
+        parse_outcome(&["csa", "run", "--isolated-worktree", "x"]).unwrap(),
+        Some(WorktreeOutcome::Review)
+    );
+    assert_eq!(
+        parse_outcome(&["csa", "run", "--isolated-worktree", "--worktree-apply", "x"]).unwrap(),
+        Some(WorktreeOutcome::Apply)
+    );
+    assert_eq!(
+        parse_outcome(&[
+            "csa",
+            "run",
+            "--isolated-worktree",
+            "--worktree-branch",
+            "csa/try",
+            "x"
+        ])
+        .unwrap(),
+        Some(WorktreeOutcome::Branch("csa/try".to_string()))
+    );
+}
+
+#[test]
+fn worktree_flags_reject_invalid_combinations() {
+    assert!(parse_outcome(&["csa", "run", "--worktree-apply", "x"]).is_err());
+    assert!(
+        parse_outcome(&[
+            "csa",
+            "run",
+            "--isolated-worktree",
+            "--worktree-apply",
+            "--worktree-branch",
+            "b",
+            "x"
+        ])
+        .is_err()
+    );
+    assert!(parse_outcome(&["csa", "run", "--isolated-worktree", "--last", "x"]).is_err());
+    assert!(parse_outcome(&["csa", "run", "--isolated-worktree", "--ephemeral", "x"]).is_err());
+}
+
+#[test]
+fn captured_diff_includes_edits_and_untracked_files() {
+    let repo = init_repo();
+    let worktree = IsolatedWorktree::create(repo.path()).unwrap();
+    std::fs::write(worktree.path.join("a.txt"), "two\n").unwrap();
+    std::fs::write(worktree.path.join("new.txt"), "fresh\n").unwrap();
+
+    let (diff, stat) = worktree.capture_diff().unwrap();
+    let diff = String::from_utf8(diff).unwrap();
+    assert!(diff.contains("+two"));
+    assert!(diff.contains("new.txt"));
+    assert!(stat.contains("2 files changed"));
+
+    // The live tree is untouched until the diff is applied.
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("a.txt")).unwrap(),
+        "one\n"
+    );
+    let scratch = TempDir::new().unwrap();
+    let diff_path = scratch.path().join("worktree.diff");
+    std::fs::write(&diff_path, &diff).unwrap();
+    apply_diff(&worktree.project_root, &diff_path).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("new.txt")).unwrap(),
+        "fresh\n"
+    );
+
+    worktree.remove();
+    assert!(!worktree.path.exists());
+}
+
+#[test]
+fn commit_to_branch_keeps_changes_on_new_branch() {
+    let repo = init_repo();
+    let worktree = IsolatedWorktree::create(repo.path()).unwrap();
+    std::fs::write(worktree.path.join("a.txt"), "branch\n").unwrap();
+    worktree.capture_diff().unwrap();
+    worktree
+        .commit_to_branch("csa/isolated", "csa run test")
+        .unwrap();
+    assert!(worktree.commit_to_branch("bad..name", "x").is_err());
+    worktree.remove();
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo.path())
+        .args(["show", "csa/isolated:a.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "branch\n");
+}
+
+#[test]
+fn only_the_worktree_admin_dir_and_objects_are_writable() {
+    let repo = init_repo();
+    let worktree = IsolatedWorktree::create(repo.path()).unwrap();
+    let git_dir = repo.path().canonicalize().unwrap().join(".git");
+
+    let paths = worktree.writable_git_paths().unwrap();
+    let paths: Vec<PathBuf> = paths.iter().map(|p| p.canonicalize().unwrap()).collect();
+    worktree.remove();
+
+    assert_eq!(paths.len(), 2);
+    assert!(paths[0].starts_with(git_dir.join("worktrees")), "{paths:?}");
+    assert_eq!(paths[1], git_dir.join("objects"));
+    assert!(!paths.contains(&git_dir));
+}
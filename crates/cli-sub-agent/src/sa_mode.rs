@@ -9,6 +9,7 @@ fn command_name_for_sa_mode(command: &Commands) -> Option<&'static str> {
         Commands::Review(_) => Some("review"),
         Commands::Debate(_) => Some("debate"),
         Commands::Batch { .. } => Some("batch"),
+        Commands::Bench(_) => Some("bench"),
         Commands::Plan {
             cmd: PlanCommands::Run { .. },
         } => Some("plan run"),
@@ -20,10 +21,17 @@ fn command_name_for_sa_mode(command: &Commands) -> Option<&'static str> {
 pub(crate) fn command_sa_mode_arg(command: &Commands) -> Option<Option<bool>> {
     match command {
         Commands::Run { sa_mode, .. } => Some(*sa_mode),
-        Commands::Review(args) if args.check_verdict => None,
+        Commands::Review(args)
+            if args.check_verdict || args.command.is_some() || args.apply_suggestions =>
+        {
+            None
+        }
+        // CI jobs have no calling agent to guard.
+        Commands::Review(args) if args.ci => Some(args.sa_mode.or(Some(false))),
         Commands::Review(args) => Some(args.sa_mode),
         Commands::Debate(args) => Some(args.sa_mode),
         Commands::Batch { sa_mode, .. } => Some(*sa_mode),
+        Commands::Bench(args) => Some(args.sa_mode),
         Commands::Plan {
             cmd: PlanCommands::Run { sa_mode, .. },
         } => Some(*sa_mode),
@@ -49,6 +49,27 @@ mod tests {
             _ => panic!("expected batch command"),
         }
 
+        let bench_cli = Cli::try_parse_from([
+            "csa",
+            "bench",
+            "suite.toml",
+            "--tool",
+            "codex",
+            "--tier",
+            "fast",
+            "--sa-mode",
+            "false",
+        ])
+        .expect("bench cli should parse");
+        match bench_cli.command {
+            Commands::Bench(args) => {
+                assert_eq!(args.sa_mode, Some(false));
+                assert_eq!(args.tiers, ["fast"]);
+                assert_eq!(args.repeat, 1);
+            }
+            _ => panic!("expected bench command"),
+        }
+
         let plan_cli =
             Cli::try_parse_from(["csa", "plan", "run", "flow.toml", "--sa-mode", "false"])
                 .expect("plan run cli should parse");
@@ -206,6 +227,16 @@ mod tests {
         restore_env_var("CSA_INTERNAL_INVOCATION", original_internal);
     }
 
+    #[test]
+    fn validate_sa_mode_defaults_to_false_for_review_ci() {
+        let cli = Cli::try_parse_from(["csa", "review", "--ci"]).expect("cli parse should pass");
+        assert!(!crate::validate_sa_mode(&cli.command, 0, false).expect("--ci needs no sa-mode"));
+
+        let cli = Cli::try_parse_from(["csa", "review", "--ci", "--sa-mode", "true"])
+            .expect("cli parse should pass");
+        assert!(crate::validate_sa_mode(&cli.command, 0, false).unwrap());
+    }
+
     #[test]
     fn validate_sa_mode_rejects_forged_depth_without_internal_marker() {
         let _env_lock = SA_MODE_ENV_LOCK.lock().expect("sa-mode env lock poisoned");
@@ -26,9 +26,10 @@ pub(crate) use resolve::{
 mod list;
 use list::{
     filter_sessions_by_csa_version, format_elapsed, format_started_at, resolve_session_status,
-    select_sessions_for_list, select_sessions_for_list_all_projects, session_created_at,
-    session_outcome_indicator, session_to_json, truncate_with_ellipsis,
+    select_sessions_for_list, select_sessions_for_list_all_projects, session_outcome_indicator,
+    session_to_json, truncate_with_ellipsis,
 };
+pub(crate) use list::{format_compact_duration, session_created_at};
 #[cfg(test)]
 use list::{is_session_stale_for_test, status_from_phase_and_result};
 
@@ -40,7 +41,8 @@ pub(crate) use reconcile::{
     ensure_terminal_result_for_dead_active_session_with_before_write,
 };
 pub(crate) use reconcile::{
-    ensure_terminal_result_for_dead_active_session, retire_if_dead_with_result,
+    ensure_terminal_result_for_dead_active_session, persist_session_state_atomically,
+    retire_if_dead_with_result,
 };
 
 #[path = "session_cmds_compress.rs"]
@@ -59,10 +61,18 @@ pub(crate) use logs::{
 mod observe;
 pub(crate) use observe::{handle_session_peek, handle_session_stats};
 
+#[path = "session_cmds_show.rs"]
+mod show;
+pub(crate) use show::handle_session_show;
+
+#[path = "session_cmds_recover.rs"]
+mod recover;
+pub(crate) use recover::handle_session_recover;
+
 /// Parse a human-friendly duration string (e.g., "1h", "30m", "2d") into
 /// a `chrono::Duration`. Supports `s` (seconds), `m` (minutes), `h` (hours),
 /// and `d` (days).
-fn parse_duration_filter(s: &str) -> Result<chrono::Duration> {
+pub(crate) fn parse_duration_filter(s: &str) -> Result<chrono::Duration> {
     let s = s.trim();
     if s.is_empty() {
         anyhow::bail!("Duration string cannot be empty");
@@ -454,7 +464,7 @@ pub(crate) fn handle_session_clean(
             ) {
                 info!(
                     session = %session.meta_session_id,
-                    "Skipped session clean delete for Active or live session"
+                    "Skipped session clean delete for Active, pinned or live session"
                 );
             } else if dry_run {
                 eprintln!(
@@ -577,7 +587,7 @@ pub(crate) use crate::session_cmds_daemon::handle_session_wait;
 pub(crate) use crate::session_cmds_daemon::{
     SessionWaitOutputMode, WaitCallerIdentity, handle_session_attach,
     handle_session_attach_with_prompt, handle_session_kill, handle_session_wait_for_mcp,
-    handle_session_wait_with_options,
+    handle_session_wait_with_options, kill_session,
 };
 
 #[cfg(test)]
@@ -22,6 +22,9 @@ use attach::{
 };
 use attach::{resolve_attach_terminal_exit, wait_for_attach_live_output_path};
 
+#[path = "session_cmds_daemon_kill.rs"]
+mod kill;
+pub(crate) use kill::{handle_session_kill, kill_session};
 #[path = "session_cmds_daemon_wait.rs"]
 mod wait;
 
@@ -74,12 +77,6 @@ struct UnpushedCommitsRecoveryPacket {
     recovery_command: String,
 }
 
-/// Check whether a daemon PID is still running.
-fn is_pid_alive(pid: u32) -> bool {
-    // SAFETY: kill(pid, 0) is a standard POSIX liveness probe.
-    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
-}
-
 fn session_has_terminal_process(session_dir: &Path) -> bool {
     csa_process::ToolLiveness::has_live_process(session_dir)
         || csa_process::ToolLiveness::daemon_pid_is_alive(session_dir)
@@ -521,101 +518,6 @@ fn build_attach_resume_args(
     ]
 }
 
-/// Kill a session with SIGTERM, then SIGKILL after a 5-second grace period if needed.
-///
-/// Resolution order for the target PID (#1118 part C):
-/// 1. Live daemon leader from `daemon.pid` (matches PID + start-time).
-/// 2. Stale `daemon.pid` → bail (refuses to signal a potentially reused PID).
-/// 3. Inline (non-daemon) tool process from session lock files in `locks/`.
-///    The lock-holding tool process is its own session leader (spawned via
-///    `setsid`), so `kill(-pid, SIG)` propagates to the whole process group
-///    just like the daemon path.
-pub(crate) fn handle_session_kill(session: String, cd: Option<String>) -> Result<()> {
-    let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
-    let resolved = resolve_session_prefix_with_global_fallback(&project_root, &session)?;
-    let session_dir = resolved.sessions_dir.join(&resolved.session_id);
-
-    let (pid, kind) = if let Some(pid) =
-        csa_process::ToolLiveness::daemon_pid_for_signal(&session_dir)
-    {
-        (pid, "daemon")
-    } else if let Some(stale_pid) = read_daemon_pid(&session_dir) {
-        anyhow::bail!(
-            "Stored daemon PID {} for session {} no longer matches a live session process; refusing to signal a potentially reused PID",
-            stale_pid,
-            resolved.session_id,
-        );
-    } else if let Some(pid) = csa_process::ToolLiveness::live_process_pid(&session_dir) {
-        (pid, "inline")
-    } else {
-        anyhow::bail!(
-            "No live PID found for session {} — session has neither a daemon.pid file nor a live tool lock file in {}/locks/",
-            resolved.session_id,
-            session_dir.display(),
-        );
-    };
-
-    if pid <= 1 {
-        anyhow::bail!(
-            "Refusing to kill PID {} — invalid PID (would target init or caller's process group)",
-            pid,
-        );
-    }
-
-    if !is_pid_alive(pid) {
-        eprintln!(
-            "Session {} (PID {}) is already dead",
-            resolved.session_id, pid,
-        );
-        return Ok(());
-    }
-
-    // Send SIGTERM to the process group (negative PID).
-    eprintln!(
-        "Sending SIGTERM to {} session {} (PID {})...",
-        kind, resolved.session_id, pid,
-    );
-    // SAFETY: kill(-pid, SIGTERM) sends to the entire process group.
-    let pgid = -(pid as libc::pid_t);
-    let rc = unsafe { libc::kill(pgid, libc::SIGTERM) };
-    if rc != 0 {
-        let err = std::io::Error::last_os_error();
-        eprintln!("Warning: SIGTERM failed for PID {pid}: {err}");
-    }
-
-    // Grace period: wait up to 5 seconds for clean shutdown.
-    for _ in 0..50 {
-        if !is_pid_alive(pid) {
-            eprintln!("Session {} terminated", resolved.session_id);
-            return Ok(());
-        }
-        std::thread::sleep(std::time::Duration::from_millis(100));
-    }
-
-    // Force kill.
-    eprintln!(
-        "Session {} still alive after 5s, sending SIGKILL...",
-        resolved.session_id,
-    );
-    // SAFETY: kill(-pid, SIGKILL) force-kills the entire process group.
-    let rc = unsafe { libc::kill(pgid, libc::SIGKILL) };
-    if rc != 0 {
-        let err = std::io::Error::last_os_error();
-        eprintln!("Warning: SIGKILL failed for PID {pid}: {err}");
-    }
-    // Wait for reaping.
-    std::thread::sleep(std::time::Duration::from_millis(500));
-    if is_pid_alive(pid) {
-        anyhow::bail!(
-            "Failed to kill session {} (PID {})",
-            resolved.session_id,
-            pid
-        );
-    }
-    eprintln!("Session {} killed", resolved.session_id);
-    Ok(())
-}
-
 #[cfg(test)]
 #[path = "session_cmds_daemon_attach_proptest.rs"]
 mod session_cmds_daemon_attach_proptest;
@@ -0,0 +1,155 @@
+//! Terminating a running session's process group (`csa session kill`,
+//! `csa kill`).
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use csa_session::MetaSessionState;
+use tracing::warn;
+
+use super::read_daemon_pid;
+use crate::session_cmds::{
+    persist_session_state_atomically, resolve_session_prefix_with_global_fallback,
+};
+
+/// Grace period `csa session kill` gives a session before SIGKILL.
+const DEFAULT_KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// `termination_reason` recorded for sessions killed on request.
+const KILLED_TERMINATION_REASON: &str = "killed";
+
+/// Check whether a daemon PID is still running.
+fn is_pid_alive(pid: u32) -> bool {
+    // SAFETY: kill(pid, 0) is a standard POSIX liveness probe.
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+/// Kill a session with SIGTERM, then SIGKILL after a 5-second grace period if needed.
+pub(crate) fn handle_session_kill(session: String, cd: Option<String>) -> Result<()> {
+    kill_session(session, cd, DEFAULT_KILL_GRACE)
+}
+
+/// Kill a session with SIGTERM, then SIGKILL once `grace` has elapsed, and
+/// record [`KILLED_TERMINATION_REASON`] in its state.
+///
+/// Resolution order for the target PID (#1118 part C):
+/// 1. Live daemon leader from `daemon.pid` (matches PID + start-time).
+/// 2. Stale `daemon.pid` → bail (refuses to signal a potentially reused PID).
+/// 3. Inline (non-daemon) tool process from session lock files in `locks/`.
+///    The lock-holding tool process is its own session leader (spawned via
+///    `setsid`), so `kill(-pid, SIG)` propagates to the whole process group
+///    just like the daemon path.
+pub(crate) fn kill_session(session: String, cd: Option<String>, grace: Duration) -> Result<()> {
+    let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
+    let resolved = resolve_session_prefix_with_global_fallback(&project_root, &session)?;
+    let session_dir = resolved.sessions_dir.join(&resolved.session_id);
+
+    let (pid, kind) = if let Some(pid) =
+        csa_process::ToolLiveness::daemon_pid_for_signal(&session_dir)
+    {
+        (pid, "daemon")
+    } else if let Some(stale_pid) = read_daemon_pid(&session_dir) {
+        anyhow::bail!(
+            "Stored daemon PID {} for session {} no longer matches a live session process; refusing to signal a potentially reused PID",
+            stale_pid,
+            resolved.session_id,
+        );
+    } else if let Some(pid) = csa_process::ToolLiveness::live_process_pid(&session_dir) {
+        (pid, "inline")
+    } else {
+        anyhow::bail!(
+            "No live PID found for session {} — session has neither a daemon.pid file nor a live tool lock file in {}/locks/",
+            resolved.session_id,
+            session_dir.display(),
+        );
+    };
+
+    if pid <= 1 {
+        anyhow::bail!(
+            "Refusing to kill PID {} — invalid PID (would target init or caller's process group)",
+            pid,
+        );
+    }
+
+    if !is_pid_alive(pid) {
+        eprintln!(
+            "Session {} (PID {}) is already dead",
+            resolved.session_id, pid,
+        );
+        return Ok(());
+    }
+
+    // Send SIGTERM to the process group (negative PID).
+    eprintln!(
+        "Sending SIGTERM to {} session {} (PID {})...",
+        kind, resolved.session_id, pid,
+    );
+    // SAFETY: kill(-pid, SIGTERM) sends to the entire process group.
+    let pgid = -(pid as libc::pid_t);
+    let rc = unsafe { libc::kill(pgid, libc::SIGTERM) };
+    if rc != 0 {
+        let err = std::io::Error::last_os_error();
+        eprintln!("Warning: SIGTERM failed for PID {pid}: {err}");
+    }
+
+    // Grace period: wait for clean shutdown.
+    let deadline = Instant::now() + grace;
+    while Instant::now() < deadline {
+        if !is_pid_alive(pid) {
+            eprintln!("Session {} terminated", resolved.session_id);
+            record_killed(&session_dir);
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    // Force kill.
+    eprintln!(
+        "Session {} still alive after {}s, sending SIGKILL...",
+        resolved.session_id,
+        grace.as_secs(),
+    );
+    // SAFETY: kill(-pid, SIGKILL) force-kills the entire process group.
+    let rc = unsafe { libc::kill(pgid, libc::SIGKILL) };
+    if rc != 0 {
+        let err = std::io::Error::last_os_error();
+        eprintln!("Warning: SIGKILL failed for PID {pid}: {err}");
+    }
+    // Wait for reaping.
+    std::thread::sleep(Duration::from_millis(500));
+    if is_pid_alive(pid) {
+        anyhow::bail!(
+            "Failed to kill session {} (PID {})",
+            resolved.session_id,
+            pid
+        );
+    }
+    eprintln!("Session {} killed", resolved.session_id);
+    record_killed(&session_dir);
+    Ok(())
+}
+
+/// Best-effort: a session that is still initializing has no state yet.
+fn record_killed(session_dir: &Path) {
+    if let Err(err) = record_termination_reason(session_dir, KILLED_TERMINATION_REASON) {
+        warn!(
+            session_dir = %session_dir.display(),
+            error = %err,
+            "Failed to record termination reason for killed session"
+        );
+    }
+}
+
+/// Runs after the process group is gone, so it overwrites whatever reason
+/// the dying tool recorded for the signal it received.
+fn record_termination_reason(session_dir: &Path, reason: &str) -> Result<()> {
+    let state_path = session_dir.join("state.toml");
+    let contents = fs::read_to_string(&state_path)
+        .with_context(|| format!("Failed to read {}", state_path.display()))?;
+    let mut session: MetaSessionState = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", state_path.display()))?;
+    session.termination_reason = Some(reason.to_string());
+    persist_session_state_atomically(session_dir, &session)
+}
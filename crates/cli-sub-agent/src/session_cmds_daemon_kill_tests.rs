@@ -61,4 +61,6 @@ fn handle_session_kill_accepts_legacy_stderr_pid() {
         !status.success(),
         "legacy daemon process should be terminated by session kill"
     );
+    let killed = csa_session::load_session(project, &session_id).expect("load killed session");
+    assert_eq!(killed.termination_reason.as_deref(), Some("killed"));
 }
@@ -291,7 +291,11 @@ fn recover_or_persist_review_meta_from_verdict(
         && review_meta_matches_verdict(meta, verdict)
     {
         let Some(diff_fingerprint) = meta.diff_fingerprint.clone().or_else(|| {
-            crate::review_cmd::compute_review_diff_fingerprint(project_root, &meta.scope)
+            crate::review_cmd::compute_review_diff_fingerprint_for_paths(
+                project_root,
+                &meta.scope,
+                &meta.path_filters,
+            )
         }) else {
             return existing_meta;
         };
@@ -354,9 +358,18 @@ fn review_meta_from_verdict(
         .map(|meta| meta.scope.clone())
         .filter(|scope| !scope.trim().is_empty())
         .unwrap_or_else(|| canonical_review_scope(session));
+    let path_filters = previous_meta
+        .map(|meta| meta.path_filters.clone())
+        .unwrap_or_default();
     let diff_fingerprint = previous_meta
         .and_then(|meta| meta.diff_fingerprint.clone())
-        .or_else(|| crate::review_cmd::compute_review_diff_fingerprint(project_root, &scope));
+        .or_else(|| {
+            crate::review_cmd::compute_review_diff_fingerprint_for_paths(
+                project_root,
+                &scope,
+                &path_filters,
+            )
+        });
     csa_session::ReviewSessionMeta {
         session_id: session.meta_session_id.clone(),
         head_sha: previous_meta
@@ -379,6 +392,7 @@ fn review_meta_from_verdict(
             .filter(|tool| !tool.trim().is_empty() && tool != "unknown")
             .unwrap_or(fallback_tool),
         scope,
+        path_filters,
         exit_code: crate::verdict_exit_code::exit_code_from_review_decision(decision),
         fix_attempted: previous_meta.is_some_and(|meta| meta.fix_attempted),
         fix_rounds: previous_meta.map_or(0, |meta| meta.fix_rounds),
@@ -452,6 +466,7 @@ fn persist_review_no_result_meta(
         failure_reason: Some(diagnostic.to_string()),
         tool: tool.to_string(),
         scope: canonical_review_scope(session),
+        path_filters: Vec::new(),
         exit_code: crate::verdict_exit_code::exit_code_from_review_decision(
             ReviewDecision::Unavailable,
         ),
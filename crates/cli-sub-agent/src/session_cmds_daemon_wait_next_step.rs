@@ -65,7 +65,8 @@ pub(crate) fn synthesized_wait_next_step(session_dir: &Path) -> Result<Option<St
     if !review_meta.accepts_clean_review_verdict(verdict.decision) {
         return Ok(None);
     }
-    if !(review_meta.scope.starts_with("base:") || review_meta.scope.starts_with("range:")) {
+    if !crate::review_cmd::review_scope_is_cumulative(&review_meta.scope, &review_meta.path_filters)
+    {
         return Ok(None);
     }
 
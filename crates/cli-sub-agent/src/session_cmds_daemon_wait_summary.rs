@@ -433,6 +433,9 @@ fn format_kill_diagnostics(diagnostics: &csa_session::KillDiagnosticReport) -> S
     if let Some(scope_name) = diagnostics.scope_name.as_deref() {
         parts.push(format!("scope_name={scope_name}"));
     }
+    if diagnostics.partial_results_saved {
+        parts.push("partial_results_saved=true".to_string());
+    }
     parts.join(", ")
 }
 
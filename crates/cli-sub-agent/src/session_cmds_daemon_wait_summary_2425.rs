@@ -25,6 +25,7 @@ fn write_review_sidecars(
             failure_reason: failure_reason.map(str::to_string),
             tool: "codex".to_string(),
             scope: "range:main...HEAD".to_string(),
+            path_filters: Vec::new(),
             exit_code: if decision == ReviewDecision::Pass {
                 0
             } else {
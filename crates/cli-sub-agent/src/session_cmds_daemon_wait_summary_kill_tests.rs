@@ -9,6 +9,7 @@ fn memory_report() -> csa_session::KillDiagnosticReport {
         memory_max_mb: Some(10000),
         soft_limit_percent: Some(90),
         scope_name: Some("csa-codex-01KW641KP78VR43SCKJVN6HGDN.scope".to_string()),
+        partial_results_saved: false,
     }
 }
 
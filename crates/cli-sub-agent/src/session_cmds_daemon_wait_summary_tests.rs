@@ -307,6 +307,7 @@ fn compact_summary_labels_fix_loop_noop_from_review_meta() {
         require_commit_recovery: None,
         memory_soft_limit_recovery: None,
         manager_fields: Default::default(),
+        resources: None,
     };
 
     let summary = render_wait_result_summary(temp.path(), "01TESTWAITNOOP", &result);
@@ -441,6 +442,7 @@ fn compact_summary_includes_writer_uncommitted_warning() {
         require_commit_recovery: None,
         memory_soft_limit_recovery: None,
         manager_fields: Default::default(),
+        resources: None,
     };
 
     let summary = render_wait_result_summary(temp.path(), "01TESTWAITDIRTY", &result);
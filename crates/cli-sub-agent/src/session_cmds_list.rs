@@ -53,7 +53,7 @@ pub(super) fn truncate_with_ellipsis(input: &str, max_chars: usize) -> String {
     format!("{}...", &input[..end])
 }
 
-pub(super) fn session_created_at(session: &MetaSessionState) -> DateTime<Utc> {
+pub(crate) fn session_created_at(session: &MetaSessionState) -> DateTime<Utc> {
     session
         .created_at
         .with_timezone(&Utc)
@@ -67,7 +67,7 @@ pub(super) fn format_started_at(created_at: DateTime<Utc>) -> String {
         .to_string()
 }
 
-pub(super) fn format_compact_duration(duration: Duration) -> String {
+pub(crate) fn format_compact_duration(duration: Duration) -> String {
     let secs = duration.num_seconds().max(0);
     let days = secs / 86_400;
     let hours = (secs % 86_400) / 3_600;
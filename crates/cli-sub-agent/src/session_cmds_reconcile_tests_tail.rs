@@ -44,6 +44,33 @@ fn make_result(status: &str, exit_code: i32) -> SessionResult {
     }
 }
 
+fn clean_review_meta(
+    session_id: &str,
+    timestamp: chrono::DateTime<Utc>,
+) -> csa_session::ReviewSessionMeta {
+    csa_session::ReviewSessionMeta {
+        session_id: session_id.to_string(),
+        head_sha: "deadbeef".to_string(),
+        decision: "pass".to_string(),
+        verdict: "CLEAN".to_string(),
+        status_reason: None,
+        routed_to: None,
+        primary_failure: None,
+        failure_reason: None,
+        tool: "codex".to_string(),
+        scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
+        exit_code: 0,
+        fix_attempted: false,
+        fix_rounds: 0,
+        review_iterations: 1,
+        timestamp,
+        diff_fingerprint: None,
+        review_mode: None,
+        fix_convergence: None,
+    }
+}
+
 fn run_git(dir: &std::path::Path, args: &[&str]) {
     let mut command = std::process::Command::new("git");
     command.args(args).current_dir(dir);
@@ -481,30 +508,7 @@ fn late_real_result_already_exists_cleans_up_reconcile_owned_sidecar() {
     tail_backdate_tree(&session_dir, 120);
 
     let now = chrono::Utc::now();
-    csa_session::write_review_meta(
-        &session_dir,
-        &csa_session::ReviewSessionMeta {
-            session_id: session_id.clone(),
-            head_sha: "deadbeef".to_string(),
-            decision: "pass".to_string(),
-            verdict: "CLEAN".to_string(),
-            status_reason: None,
-            routed_to: None,
-            primary_failure: None,
-            failure_reason: None,
-            tool: "codex".to_string(),
-            scope: "range:main...HEAD".to_string(),
-            exit_code: 0,
-            fix_attempted: false,
-            fix_rounds: 0,
-            review_iterations: 1,
-            timestamp: now,
-            diff_fingerprint: None,
-            review_mode: None,
-            fix_convergence: None,
-        },
-    )
-    .unwrap();
+    csa_session::write_review_meta(&session_dir, &clean_review_meta(&session_id, now)).unwrap();
     fs::create_dir_all(session_dir.join("output")).unwrap();
     fs::write(
         session_dir.join("output").join("review-verdict.json"),
@@ -588,30 +592,7 @@ fn retire_if_dead_with_result_preserves_pr_bot_handoff_for_real_results() {
     )
     .unwrap();
 
-    csa_session::write_review_meta(
-        &session_dir,
-        &csa_session::ReviewSessionMeta {
-            session_id: session_id.clone(),
-            head_sha: "deadbeef".to_string(),
-            decision: "pass".to_string(),
-            verdict: "CLEAN".to_string(),
-            status_reason: None,
-            routed_to: None,
-            primary_failure: None,
-            failure_reason: None,
-            tool: "codex".to_string(),
-            scope: "range:main...HEAD".to_string(),
-            exit_code: 0,
-            fix_attempted: false,
-            fix_rounds: 0,
-            review_iterations: 1,
-            timestamp: now,
-            diff_fingerprint: None,
-            review_mode: None,
-            fix_convergence: None,
-        },
-    )
-    .unwrap();
+    csa_session::write_review_meta(&session_dir, &clean_review_meta(&session_id, now)).unwrap();
     fs::create_dir_all(session_dir.join("output")).unwrap();
     fs::write(
         session_dir.join("output").join("review-verdict.json"),
@@ -0,0 +1,226 @@
+//! `csa session recover`: rebuild structured output from the `output.log`
+//! spool of a run that died without finishing (e.g. SIGKILL).
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use csa_session::{
+    MetaSessionState, OutputIndex, PhaseEvent, RETURN_PACKET_SECTION_ID, ReturnPacket,
+    ReturnStatus, SessionPhase, SessionResult,
+};
+
+use super::reconcile::with_reconcile_lock;
+use super::{persist_session_state_atomically, resolve_session_prefix_with_global_fallback};
+use crate::session_result_publish::publish_result_file_if_absent;
+
+/// `termination_reason` recorded for sessions rebuilt from their spool.
+const RECOVERED_TERMINATION_REASON: &str = "recovered (incomplete)";
+
+/// Cap on the synthesized summary, matching the result summary budget.
+const RECOVERED_SUMMARY_MAX_CHARS: usize = 2_000;
+
+const RECOVERED_ERROR_CONTEXT: &str = "Recovered from output.log after the run ended without a result; \
+     sections may be truncated and later work may be missing.";
+
+/// What `recover_session_dir` rebuilt.
+#[derive(Debug, PartialEq, Eq)]
+struct RecoveryReport {
+    sections: Vec<String>,
+    total_lines: usize,
+    return_packet_synthesized: bool,
+    result_synthesized: bool,
+    summary: String,
+}
+
+pub(crate) fn handle_session_recover(session: String, cd: Option<String>) -> Result<()> {
+    let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
+    let resolved = resolve_session_prefix_with_global_fallback(&project_root, &session)?;
+    let session_dir = resolved.sessions_dir.join(&resolved.session_id);
+    if csa_process::ToolLiveness::is_alive(&session_dir) {
+        anyhow::bail!(
+            "Session {} is still running; only dead sessions can be recovered",
+            resolved.session_id
+        );
+    }
+
+    let report = with_reconcile_lock(&session_dir, || recover_session_dir(&session_dir))?
+        .ok_or_else(|| {
+            anyhow!(
+                "Session {} is being reconciled by another csa process; retry shortly",
+                resolved.session_id
+            )
+        })?;
+
+    eprintln!(
+        "Recovered session {} from output.log ({} lines): sections {}",
+        resolved.session_id,
+        report.total_lines,
+        report.sections.join(", ")
+    );
+    if report.return_packet_synthesized {
+        eprintln!("  synthesized a partial return packet");
+    }
+    if report.result_synthesized {
+        eprintln!("  wrote result.toml");
+    }
+    eprintln!("  marked session as {RECOVERED_TERMINATION_REASON}");
+    println!("{}", report.summary);
+    Ok(())
+}
+
+/// Parse `output.log` into sections, regenerate `output/index.toml`, add a
+/// partial return packet and a `result.toml` where missing, and mark the
+/// session as recovered.
+fn recover_session_dir(session_dir: &Path) -> Result<RecoveryReport> {
+    let spool = session_dir.join("output.log");
+    if !spool.is_file() {
+        anyhow::bail!(
+            "No output spool to recover: {} does not exist",
+            spool.display()
+        );
+    }
+    let state_path = session_dir.join("state.toml");
+    let contents = fs::read_to_string(&state_path)
+        .with_context(|| format!("Failed to read {}", state_path.display()))?;
+    let mut session: MetaSessionState = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", state_path.display()))?;
+    let result_path = session_dir.join(csa_session::result::RESULT_FILE_NAME);
+    if let Some(result) = read_result(&result_path)
+        && result.status == "success"
+    {
+        anyhow::bail!(
+            "Session {} completed successfully; nothing to recover",
+            session.meta_session_id
+        );
+    }
+
+    let mut index = csa_session::persist_structured_output_from_file(session_dir, &spool)
+        .context("Failed to parse output.log into sections")?;
+    let summary = recovered_summary(session_dir, &index);
+
+    let return_packet_synthesized = if has_usable_return_packet(session_dir, &mut index)? {
+        false
+    } else {
+        let packet = ReturnPacket {
+            status: ReturnStatus::Failure,
+            summary: summary.clone(),
+            error_context: Some(RECOVERED_ERROR_CONTEXT.to_string()),
+            ..Default::default()
+        };
+        csa_session::write_partial_return_packet(session_dir, &packet)?;
+        csa_session::merge_partial_return_packet(session_dir)?
+    };
+
+    let now = Utc::now();
+    let result = SessionResult {
+        status: "failure".to_string(),
+        exit_code: 1,
+        summary: format!("{RECOVERED_TERMINATION_REASON}: {summary}"),
+        tool: session
+            .tools
+            .iter()
+            .max_by_key(|(_, state)| state.updated_at)
+            .map(|(tool, _)| tool.clone())
+            .unwrap_or_else(|| "unknown".to_string()),
+        started_at: std::cmp::min(session.last_accessed, now),
+        completed_at: now,
+        ..Default::default()
+    };
+    let result_contents =
+        toml::to_string_pretty(&result).context("Failed to serialize recovered result")?;
+    let result_synthesized =
+        publish_result_file_if_absent(&result_path, &result_contents, "recovered result.toml")?
+            == crate::session_result_publish::ResultFilePublishOutcome::Created;
+
+    session.termination_reason = Some(RECOVERED_TERMINATION_REASON.to_string());
+    session.last_accessed = now;
+    if session.phase != SessionPhase::Retired
+        && session.apply_phase_event(PhaseEvent::Retired).is_err()
+    {
+        session.phase = SessionPhase::Retired;
+    }
+    persist_session_state_atomically(session_dir, &session)?;
+
+    let index = csa_session::load_output_index(session_dir)?.unwrap_or(index);
+    Ok(RecoveryReport {
+        sections: index
+            .sections
+            .into_iter()
+            .map(|section| section.id)
+            .collect(),
+        total_lines: index.total_lines,
+        return_packet_synthesized,
+        result_synthesized,
+        summary,
+    })
+}
+
+fn read_result(path: &Path) -> Option<SessionResult> {
+    let contents = fs::read_to_string(path).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+/// The tool's own `summary` section when it got that far, otherwise the last
+/// lines of the spool.
+fn recovered_summary(session_dir: &Path, index: &OutputIndex) -> String {
+    let from_section = index
+        .sections
+        .iter()
+        .any(|section| section.id == "summary")
+        .then(|| {
+            csa_session::read_section(session_dir, "summary")
+                .ok()
+                .flatten()
+        })
+        .flatten()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty());
+    let summary = from_section.unwrap_or_else(|| {
+        crate::pipeline_post_exec::build_fallback_result_summary(
+            session_dir,
+            "Run ended without a summary",
+        )
+    });
+    truncate_chars(&summary, RECOVERED_SUMMARY_MAX_CHARS)
+}
+
+/// A return packet cut off mid-write does not parse; drop it from the index so
+/// the partial packet can take its place.
+fn has_usable_return_packet(session_dir: &Path, index: &mut OutputIndex) -> Result<bool> {
+    if !index
+        .sections
+        .iter()
+        .any(|section| section.id == RETURN_PACKET_SECTION_ID)
+    {
+        return Ok(false);
+    }
+    // `parse_return_packet` never fails (it substitutes an error packet), so
+    // check the canonical TOML form directly.
+    let complete = csa_session::read_section(session_dir, RETURN_PACKET_SECTION_ID)?
+        .is_some_and(|content| toml::from_str::<ReturnPacket>(&content).is_ok());
+    if complete {
+        return Ok(true);
+    }
+
+    index
+        .sections
+        .retain(|section| section.id != RETURN_PACKET_SECTION_ID);
+    let index_path = session_dir.join("output").join("index.toml");
+    let index_toml = toml::to_string_pretty(index).context("Failed to serialize output index")?;
+    fs::write(&index_path, index_toml)
+        .with_context(|| format!("Failed to write {}", index_path.display()))?;
+    Ok(false)
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}...", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "session_cmds_recover_tests.rs"]
+mod tests;
@@ -0,0 +1,111 @@
+use super::*;
+
+fn write_session(session_dir: &Path, output_log: &str) -> MetaSessionState {
+    let session = MetaSessionState {
+        meta_session_id: "01JRECOVER0000000000000000".to_string(),
+        ..Default::default()
+    };
+    fs::write(
+        session_dir.join("state.toml"),
+        toml::to_string(&session).unwrap(),
+    )
+    .unwrap();
+    fs::write(session_dir.join("output.log"), output_log).unwrap();
+    session
+}
+
+fn load_state(session_dir: &Path) -> MetaSessionState {
+    toml::from_str(&fs::read_to_string(session_dir.join("state.toml")).unwrap()).unwrap()
+}
+
+#[test]
+fn recover_rebuilds_sections_and_replaces_truncated_return_packet() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    write_session(
+        dir,
+        "<!-- CSA:SECTION:summary -->\nFixed the parser; tests still running.\n\
+         <!-- CSA:SECTION:summary:END -->\n\
+         <!-- CSA:SECTION:details -->\nstep 1 done\nstep 2 in progress\n\
+         <!-- CSA:SECTION:return-packet -->\nstatus = \"Succ",
+    );
+
+    let report = recover_session_dir(dir).unwrap();
+
+    assert_eq!(
+        report.sections,
+        ["summary", "details", RETURN_PACKET_SECTION_ID]
+    );
+    assert!(report.return_packet_synthesized);
+    assert!(report.result_synthesized);
+    assert_eq!(report.summary, "Fixed the parser; tests still running.");
+
+    let packet = csa_session::parse_return_packet(
+        &csa_session::read_section(dir, RETURN_PACKET_SECTION_ID)
+            .unwrap()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(packet.status, ReturnStatus::Failure);
+    assert_eq!(packet.summary, report.summary);
+    assert!(
+        csa_session::read_section(dir, "details")
+            .unwrap()
+            .unwrap()
+            .contains("step 2 in progress")
+    );
+
+    let result = read_result(&dir.join(csa_session::result::RESULT_FILE_NAME)).unwrap();
+    assert_eq!(result.status, "failure");
+    assert!(result.summary.starts_with("recovered (incomplete): Fixed"));
+    let state = load_state(dir);
+    assert_eq!(
+        state.termination_reason.as_deref(),
+        Some(RECOVERED_TERMINATION_REASON)
+    );
+    assert_eq!(state.phase, SessionPhase::Retired);
+}
+
+#[test]
+fn recover_without_markers_summarizes_spool_tail_and_keeps_existing_result() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    write_session(dir, "compiling\nrunning tests\n");
+    let existing = "status = \"signal\"\nexit_code = 137\nsummary = \"killed\"\ntool = \"codex\"\n\
+                    started_at = \"2026-01-01T00:00:00Z\"\ncompleted_at = \"2026-01-01T00:01:00Z\"\n";
+    fs::write(dir.join(csa_session::result::RESULT_FILE_NAME), existing).unwrap();
+
+    let report = recover_session_dir(dir).unwrap();
+
+    assert_eq!(report.sections, ["full", RETURN_PACKET_SECTION_ID]);
+    assert!(!report.result_synthesized);
+    assert!(
+        report.summary.contains("running tests"),
+        "{}",
+        report.summary
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join(csa_session::result::RESULT_FILE_NAME)).unwrap(),
+        existing
+    );
+}
+
+#[test]
+fn recover_refuses_successful_sessions_and_missing_spool() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    write_session(dir, "done\n");
+    fs::write(
+        dir.join(csa_session::result::RESULT_FILE_NAME),
+        "status = \"success\"\nexit_code = 0\nsummary = \"ok\"\ntool = \"codex\"\n\
+         started_at = \"2026-01-01T00:00:00Z\"\ncompleted_at = \"2026-01-01T00:01:00Z\"\n",
+    )
+    .unwrap();
+    let err = recover_session_dir(dir).unwrap_err();
+    assert!(err.to_string().contains("nothing to recover"), "{err}");
+    assert!(!dir.join("output").exists());
+
+    fs::remove_file(dir.join("output.log")).unwrap();
+    let err = recover_session_dir(dir).unwrap_err();
+    assert!(err.to_string().contains("No output spool"), "{err}");
+}
@@ -31,7 +31,7 @@ pub(crate) fn resolve_session_prefix_with_fallback(
 }
 
 pub(crate) fn legacy_sessions_dir_from_primary_root(primary_root: &Path) -> Option<PathBuf> {
-    let primary_state_dir = paths::state_dir_write()?;
+    let primary_state_dir = paths::session_state_dir()?;
     let legacy_state_dir = paths::legacy_state_dir()?;
     let relative_root = primary_root.strip_prefix(primary_state_dir).ok()?;
     let legacy_root = legacy_state_dir.join(relative_root);
@@ -219,7 +219,7 @@ fn infer_foreign_project_root_from_session_dir(session_dir: &Path) -> Option<Pat
 
 fn candidate_state_dirs() -> Vec<PathBuf> {
     let mut dirs = Vec::new();
-    if let Some(dir) = paths::state_dir_write() {
+    if let Some(dir) = paths::session_state_dir() {
         dirs.push(dir);
     }
     if let Some(dir) = paths::legacy_state_dir() {
@@ -124,6 +124,7 @@ fn memory_soft_limit_result() -> SessionResultView {
                 memory_max_mb: Some(10000),
                 soft_limit_percent: Some(90),
                 scope_name: Some("csa-codex-01KTEST.scope".to_string()),
+                partial_results_saved: false,
             }),
             memory_soft_limit_recovery: Some(csa_session::MemorySoftLimitRecoveryDiagnostic {
                 outcome: "dirty_or_staged_changes".to_string(),
@@ -26,6 +26,7 @@ fn build_result_json_payload_includes_kill_diagnostics() {
                 memory_max_mb: Some(12_288),
                 soft_limit_percent: Some(70),
                 scope_name: Some("csa-codex-01KTEST.scope".to_string()),
+                partial_results_saved: false,
             }),
             ..Default::default()
         },
@@ -551,6 +551,7 @@ fn build_result_json_payload_includes_review_iterations() {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: true,
         fix_rounds: 1,
@@ -0,0 +1,326 @@
+//! `csa session show`: consolidated read-only view of one session.
+//!
+//! Gathers state, genealogy, tool states, token usage, the structured output
+//! index, the session's own return packet and hook results (post-exec gate
+//! report and warnings from `result.toml`) into a single report.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use csa_core::types::OutputFormat;
+use csa_session::{
+    MetaSessionState, OutputIndex, PostExecGateReport, ReturnPacket, SessionPhase, SessionResult,
+    TokenUsage, ToolState,
+};
+use serde::Serialize;
+
+use crate::stdout_write::{write_stdout, write_stdout_line};
+use crate::token_usage_display::compact_token_usage;
+
+#[derive(Debug, Serialize)]
+struct SessionShowReport {
+    session_id: String,
+    session_dir: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    project_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    phase: SessionPhase,
+    created_at: DateTime<Utc>,
+    last_accessed: DateTime<Utc>,
+    turn_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    csa_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    termination_reason: Option<String>,
+    genealogy: GenealogyView,
+    tools: BTreeMap<String, ToolState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_usage: Option<TokenUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<ResultView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_index: Option<OutputIndex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    return_packet: Option<ReturnPacket>,
+    hooks: HookResults,
+}
+
+#[derive(Debug, Serialize)]
+struct GenealogyView {
+    depth: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fork_of_session_id: Option<String>,
+    children: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResultView {
+    status: String,
+    exit_code: i32,
+    tool: String,
+    summary: String,
+    started_at: DateTime<Utc>,
+    completed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct HookResults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    post_exec_gate: Option<PostExecGateReport>,
+    warnings: Vec<String>,
+}
+
+pub(crate) fn handle_session_show(
+    session: String,
+    json: bool,
+    section: Option<String>,
+    cd: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let json = json || matches!(format, OutputFormat::Json);
+    if let Some(section) = section {
+        // Same lookup and output as `csa session result --section`.
+        return super::handle_session_result(
+            session,
+            json,
+            cd,
+            super::StructuredOutputOpts {
+                section: Some(section),
+                ..Default::default()
+            },
+        );
+    }
+
+    let project_root = crate::pipeline::determine_project_root(cd.as_deref())?;
+    let resolved = super::resolve_session_prefix_with_global_fallback(&project_root, &session)?;
+    let session_dir = resolved.sessions_dir.join(&resolved.session_id);
+    let effective_root = resolved
+        .foreign_project_root
+        .as_deref()
+        .unwrap_or(&project_root);
+    let children = csa_session::find_children(effective_root, &resolved.session_id)
+        .unwrap_or_else(|err| {
+            tracing::warn!(session_id = %resolved.session_id, error = %err, "Failed to scan child sessions");
+            Vec::new()
+        });
+
+    let report = build_show_report(&resolved.session_id, &session_dir, children)?;
+    if json {
+        write_stdout_line(&serde_json::to_string_pretty(&report)?)?;
+    } else {
+        write_stdout(&render_show_text(&report))?;
+    }
+    Ok(())
+}
+
+fn build_show_report(
+    session_id: &str,
+    session_dir: &Path,
+    children: Vec<String>,
+) -> Result<SessionShowReport> {
+    let state_path = session_dir.join("state.toml");
+    let content = std::fs::read_to_string(&state_path)
+        .with_context(|| format!("failed to read {}", state_path.display()))?;
+    let state: MetaSessionState = toml::from_str(&content)
+        .with_context(|| format!("failed to parse {}", state_path.display()))?;
+
+    let result = load_result(session_dir);
+    let hooks = result
+        .as_ref()
+        .map(|result| HookResults {
+            post_exec_gate: result.post_exec_gate.clone(),
+            warnings: result.warnings.clone(),
+        })
+        .unwrap_or_default();
+    let return_packet =
+        csa_session::read_section(session_dir, csa_session::RETURN_PACKET_SECTION_ID)
+            .ok()
+            .flatten()
+            .and_then(|content| csa_session::parse_return_packet(&content).ok());
+
+    Ok(SessionShowReport {
+        session_id: session_id.to_string(),
+        session_dir: session_dir.to_path_buf(),
+        description: state.description,
+        project_path: state.project_path,
+        branch: state.branch,
+        phase: state.phase,
+        created_at: state.created_at,
+        last_accessed: state.last_accessed,
+        turn_count: state.turn_count,
+        csa_version: state.csa_version,
+        termination_reason: state.termination_reason,
+        genealogy: GenealogyView {
+            depth: state.genealogy.depth,
+            parent_session_id: state.genealogy.parent_session_id,
+            fork_of_session_id: state.genealogy.fork_of_session_id,
+            children,
+        },
+        tools: state.tools.into_iter().collect(),
+        token_usage: state.total_token_usage,
+        result: result.map(|result| ResultView {
+            status: result.status,
+            exit_code: result.exit_code,
+            tool: result.tool,
+            summary: result.summary,
+            started_at: result.started_at,
+            completed_at: result.completed_at,
+        }),
+        output_index: csa_session::load_output_index(session_dir).ok().flatten(),
+        return_packet,
+        hooks,
+    })
+}
+
+fn load_result(session_dir: &Path) -> Option<SessionResult> {
+    let path = session_dir.join(csa_session::result::RESULT_FILE_NAME);
+    let content = std::fs::read_to_string(&path).ok()?;
+    toml::from_str(&content)
+        .map_err(|err| {
+            tracing::warn!(path = %path.display(), error = %err, "Failed to parse session result");
+        })
+        .ok()
+}
+
+fn render_show_text(report: &SessionShowReport) -> String {
+    let mut out = format!("Session: {}\n", report.session_id);
+    let mut field = |label: &str, value: &str| out.push_str(&format!("  {label:<14}{value}\n"));
+    field("Description:", report.description.as_deref().unwrap_or("-"));
+    field("Project:", &report.project_path);
+    field("Branch:", report.branch.as_deref().unwrap_or("-"));
+    field(
+        "Phase:",
+        &format!("{} ({} turns)", report.phase, report.turn_count),
+    );
+    field("Created:", &report.created_at.to_rfc3339());
+    field("Last access:", &report.last_accessed.to_rfc3339());
+    field("CSA version:", report.csa_version.as_deref().unwrap_or("-"));
+    if let Some(reason) = &report.termination_reason {
+        field("Terminated:", reason);
+    }
+    field("Directory:", &report.session_dir.display().to_string());
+
+    let genealogy = &report.genealogy;
+    out.push_str("\nGenealogy:\n");
+    out.push_str(&format!("  Depth:        {}\n", genealogy.depth));
+    out.push_str(&format!(
+        "  Parent:       {}\n",
+        genealogy.parent_session_id.as_deref().unwrap_or("(root)")
+    ));
+    if let Some(fork_of) = &genealogy.fork_of_session_id {
+        out.push_str(&format!("  Fork of:      {fork_of}\n"));
+    }
+    out.push_str(&format!(
+        "  Children:     {}\n",
+        if genealogy.children.is_empty() {
+            "none".to_string()
+        } else {
+            genealogy.children.join(", ")
+        }
+    ));
+
+    out.push_str("\nTools:\n");
+    if report.tools.is_empty() {
+        out.push_str("  none\n");
+    }
+    for (name, tool) in &report.tools {
+        out.push_str(&format!(
+            "  {name:<12} exit={:<4} tokens={}  updated={}\n",
+            tool.last_exit_code,
+            compact_token_usage(tool.token_usage.as_ref()),
+            tool.updated_at.to_rfc3339()
+        ));
+        if let Some(provider_session) = &tool.provider_session_id {
+            out.push_str(&format!("    provider session: {provider_session}\n"));
+        }
+        if !tool.last_action_summary.is_empty() {
+            out.push_str(&format!("    last action: {}\n", tool.last_action_summary));
+        }
+    }
+    out.push_str(&format!(
+        "\nTokens: {}\n",
+        compact_token_usage(report.token_usage.as_ref())
+    ));
+
+    if let Some(result) = &report.result {
+        out.push_str(&format!(
+            "\nResult: {} (exit {}, tool {})\n",
+            result.status, result.exit_code, result.tool
+        ));
+        out.push_str(&format!(
+            "  {} -> {}\n",
+            result.started_at.to_rfc3339(),
+            result.completed_at.to_rfc3339()
+        ));
+        if !result.summary.is_empty() {
+            out.push_str(&format!("  {}\n", result.summary));
+        }
+    }
+
+    match &report.output_index {
+        Some(index) => {
+            out.push_str(&format!(
+                "\nSections (~{} tokens, {} lines):\n",
+                index.total_tokens, index.total_lines
+            ));
+            for section in &index.sections {
+                out.push_str(&format!(
+                    "  {:<20} ~{:>6} tok  {}\n",
+                    section.id, section.token_estimate, section.title
+                ));
+            }
+        }
+        None => out.push_str("\nSections: none\n"),
+    }
+
+    if let Some(packet) = &report.return_packet {
+        out.push_str(&format!(
+            "\nReturn packet: {:?} (exit {})\n",
+            packet.status, packet.exit_code
+        ));
+        if !packet.summary.is_empty() {
+            out.push_str(&format!("  {}\n", packet.summary));
+        }
+        if !packet.changed_files.is_empty() {
+            out.push_str(&format!(
+                "  Changed files: {}\n",
+                packet.changed_files.len()
+            ));
+        }
+        for action in &packet.next_actions {
+            out.push_str(&format!("  Next: {action}\n"));
+        }
+        if let Some(error) = &packet.error_context {
+            out.push_str(&format!("  Error: {error}\n"));
+        }
+    }
+
+    out.push_str("\nHooks:\n");
+    match &report.hooks.post_exec_gate {
+        Some(gate) => {
+            out.push_str(&format!(
+                "  Post-exec gate: `{}` exited {}",
+                gate.gate_command, gate.exit_code
+            ));
+            if let Some(step) = &gate.failing_step {
+                out.push_str(&format!(" (failing step: {step})"));
+            }
+            out.push('\n');
+        }
+        None => out.push_str("  Post-exec gate: passed or not run\n"),
+    }
+    for warning in &report.hooks.warnings {
+        out.push_str(&format!("  Warning: {warning}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "session_cmds_show_tests.rs"]
+mod tests;
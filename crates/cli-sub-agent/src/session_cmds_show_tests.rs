@@ -0,0 +1,136 @@
+use super::*;
+use csa_session::{Genealogy, ReturnStatus};
+
+const SESSION_ID: &str = "01JSHOW0000000000000000000";
+
+fn write_session(dir: &Path) {
+    let mut state = MetaSessionState {
+        meta_session_id: SESSION_ID.to_string(),
+        project_path: "/work/project".to_string(),
+        description: Some("fix flaky test".to_string()),
+        genealogy: Genealogy {
+            parent_session_id: Some("01JPARENT000000000000000000".to_string()),
+            depth: 1,
+            ..Default::default()
+        },
+        total_token_usage: Some(TokenUsage {
+            input_tokens: Some(1_000),
+            output_tokens: Some(250),
+            estimated_cost_usd: Some(0.5),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    state.tools.insert(
+        "codex".to_string(),
+        ToolState {
+            provider_session_id: Some("thread-1".to_string()),
+            last_action_summary: "patched parser".to_string(),
+            last_exit_code: 0,
+            updated_at: Utc::now(),
+            tool_version: None,
+            token_usage: None,
+        },
+    );
+    std::fs::write(dir.join("state.toml"), toml::to_string(&state).unwrap()).unwrap();
+
+    let result = SessionResult {
+        status: "success".to_string(),
+        tool: "codex".to_string(),
+        summary: "done".to_string(),
+        warnings: vec!["post-run hook exited 1".to_string()],
+        post_exec_gate: Some(PostExecGateReport {
+            gate_command: "just pre-commit".to_string(),
+            exit_code: 100,
+            failing_step: Some("just clippy".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    std::fs::write(
+        dir.join(csa_session::result::RESULT_FILE_NAME),
+        toml::to_string(&result).unwrap(),
+    )
+    .unwrap();
+
+    csa_session::persist_structured_output(
+        dir,
+        "<!-- CSA:SECTION:summary -->\nParser fixed.\n<!-- CSA:SECTION:summary:END -->\n\
+         <!-- CSA:SECTION:return-packet -->\nstatus = \"Success\"\nexit_code = 0\n\
+         summary = \"Parser fixed\"\nnext_actions = [\"add fuzz test\"]\n\
+         <!-- CSA:SECTION:return-packet:END -->\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn build_show_report_collects_all_session_facets() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_session(tmp.path());
+
+    let report = build_show_report(SESSION_ID, tmp.path(), vec!["01JCHILD".to_string()]).unwrap();
+    assert_eq!(report.description.as_deref(), Some("fix flaky test"));
+    assert_eq!(report.genealogy.depth, 1);
+    assert_eq!(report.genealogy.children, vec!["01JCHILD".to_string()]);
+    assert_eq!(report.tools["codex"].last_action_summary, "patched parser");
+    assert_eq!(report.result.as_ref().unwrap().status, "success");
+
+    let index = report.output_index.as_ref().unwrap();
+    let ids: Vec<&str> = index.sections.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["summary", "return-packet"]);
+
+    let packet = report.return_packet.as_ref().unwrap();
+    assert_eq!(packet.status, ReturnStatus::Success);
+    assert_eq!(packet.next_actions, vec!["add fuzz test".to_string()]);
+
+    assert_eq!(report.hooks.warnings.len(), 1);
+    assert_eq!(report.hooks.post_exec_gate.as_ref().unwrap().exit_code, 100);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["genealogy"]["children"][0], "01JCHILD");
+    assert_eq!(json["token_usage"]["input_tokens"], 1_000);
+}
+
+#[test]
+fn build_show_report_tolerates_missing_result_and_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let state = MetaSessionState {
+        meta_session_id: SESSION_ID.to_string(),
+        ..Default::default()
+    };
+    std::fs::write(
+        tmp.path().join("state.toml"),
+        toml::to_string(&state).unwrap(),
+    )
+    .unwrap();
+
+    let report = build_show_report(SESSION_ID, tmp.path(), Vec::new()).unwrap();
+    assert!(report.result.is_none());
+    assert!(report.output_index.is_none());
+    assert!(report.return_packet.is_none());
+
+    let text = render_show_text(&report);
+    assert!(text.contains("Parent:       (root)"));
+    assert!(text.contains("Children:     none"));
+    assert!(text.contains("Sections: none"));
+    assert!(text.contains("Post-exec gate: passed or not run"));
+}
+
+#[test]
+fn render_show_text_lists_sections_packet_and_hooks() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_session(tmp.path());
+    let report = build_show_report(SESSION_ID, tmp.path(), Vec::new()).unwrap();
+
+    let text = render_show_text(&report);
+    assert!(text.starts_with(&format!("Session: {SESSION_ID}\n")));
+    assert!(text.contains("Parent:       01JPARENT000000000000000000"));
+    assert!(text.contains("codex        exit=0"));
+    assert!(text.contains("last action: patched parser"));
+    assert!(text.contains("Tokens: 1250tok"));
+    assert!(text.contains("  summary "));
+    assert!(text.contains("Return packet: Success (exit 0)"));
+    assert!(text.contains("Next: add fuzz test"));
+    assert!(text.contains("`just pre-commit` exited 100 (failing step: just clippy)"));
+    assert!(text.contains("Warning: post-run hook exited 1"));
+}
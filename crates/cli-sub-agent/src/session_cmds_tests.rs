@@ -415,6 +415,7 @@ fn sample_session_state() -> MetaSessionState {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
@@ -87,6 +87,7 @@ fn sample_fork_session() -> MetaSessionState {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
@@ -16,6 +16,7 @@ fn write_clean_review_meta(session_dir: &Path, session_id: &str, tool: &str) {
         failure_reason: None,
         tool: tool.to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -194,6 +195,7 @@ fn issue_1971_wait_uses_generated_fail_verdict_for_pass_result() {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -16,6 +16,7 @@ fn write_clean_review_meta(session_dir: &Path, session_id: &str, tool: &str) {
         failure_reason: None,
         tool: tool.to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -110,6 +111,7 @@ fn issue_1978_wait_uses_generated_fail_verdict_for_blocking_summary_without_fail
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -267,6 +267,7 @@ fn daemon_completion_review_artifact_recovery_prefers_non_pass_verdict_over_stal
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "range:main...HEAD".to_string(),
+            path_filters: Vec::new(),
             exit_code: 0,
             fix_attempted: false,
             fix_rounds: 0,
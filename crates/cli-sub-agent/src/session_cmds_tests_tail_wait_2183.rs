@@ -18,6 +18,7 @@ fn write_clean_review_meta(session_dir: &Path, session_id: &str) {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -138,6 +138,16 @@ pub(crate) fn dispatch(
                 },
             )?;
         }
+        SessionCommands::Show {
+            session_id,
+            session,
+            json,
+            section,
+            cd,
+        } => {
+            let sid = resolve_session_id(session_id, session)?;
+            session_cmds::handle_session_show(sid, json, section, cd, output_format)?;
+        }
         SessionCommands::Artifacts {
             session_id,
             session,
@@ -204,6 +214,14 @@ pub(crate) fn dispatch(
             let _ = std::io::stderr().flush();
             std::process::exit(exit_code);
         }
+        SessionCommands::Recover {
+            session_id,
+            session,
+            cd,
+        } => {
+            let sid = resolve_session_id(session_id, session)?;
+            session_cmds::handle_session_recover(sid, cd)?;
+        }
         SessionCommands::Kill {
             session_id,
             session,
@@ -319,7 +337,7 @@ fn wait_provider_error(
          Do not omit this flag; provider can change mid-session when the user switches models.\n\
          <!-- CSA:CALLER_HINT action=\"select_wait_provider\" rule=\"Derive the caller model provider dynamically on every wait; pass --model-provider with a configured provider_ttls key.\" -->",
     );
-    anyhow::anyhow!(message)
+    csa_core::error::AppError::config(message).into()
 }
 
 fn resolve_session_wait_memory_warn_mb(cli_override: Option<u64>, cd: Option<&str>) -> Option<u64> {
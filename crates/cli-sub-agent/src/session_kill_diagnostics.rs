@@ -4,7 +4,9 @@
 use std::path::Path;
 
 use anyhow::{Context, Result};
-use csa_resource::memory_monitor::{MEMORY_SOFT_LIMIT_KILL_HINT, MemorySoftLimitKillDiagnostic};
+use csa_resource::memory_monitor::{
+    MEMORY_SOFT_LIMIT_KILL_HINT, MemorySoftLimitKillDiagnostic, OOM_PARTIAL_RESULTS_LABEL,
+};
 use csa_session::{
     KillDiagnosticReport, MetaSessionState, SessionResult, SignalResultMetadata, save_result,
     save_result_with_signal_metadata,
@@ -137,6 +139,9 @@ impl KillDiagnostic {
             details.push(format!("memory_max_mb={}", event.memory_max_mb));
             details.push(format!("soft_limit_percent={}", event.soft_limit_percent));
             details.push(format!("scope_name={}", event.scope_name));
+            if event.partial_results_saved {
+                details.push("partial_results_saved=true".to_string());
+            }
             return details;
         }
         if let Some(child) = &self.child_timeout {
@@ -191,7 +196,19 @@ impl KillDiagnostic {
         details
     }
 
+    fn partial_results_saved(&self) -> bool {
+        self.memory_soft_limit
+            .as_ref()
+            .is_some_and(|event| event.partial_results_saved)
+    }
+
     pub(crate) fn stderr_line(&self) -> Option<String> {
+        if self.partial_results_saved() {
+            return Some(format!(
+                "CSA diagnostic: {OOM_PARTIAL_RESULTS_LABEL} ({}). CSA's memory monitor saved the output sections parsed so far and a partial return packet, then sent SIGTERM at the configured soft limit; continue from the saved sections, or increase resources.memory_max_mb or tools.<tool>.memory_max_mb before redispatching.",
+                self.detail_parts().join(", ")
+            ));
+        }
         let hint = match self.hint {
             KillHint::Earlyoom => "earlyoom",
             KillHint::MemoryPressure => "memory pressure",
@@ -253,10 +270,19 @@ impl KillDiagnostic {
                 memory_max_mb: Some(event.memory_max_mb),
                 soft_limit_percent: Some(event.soft_limit_percent),
                 scope_name: Some(event.scope_name.clone()),
+                partial_results_saved: event.partial_results_saved,
             })
     }
 }
 
+/// Memory soft-limit kill recorded for this session's run started at `started_at`.
+pub(crate) fn memory_soft_limit_event(
+    session_dir: &Path,
+    started_at: &chrono::DateTime<chrono::Utc>,
+) -> Option<MemorySoftLimitKillDiagnostic> {
+    read_memory_soft_limit_diagnostic(session_dir, Some(started_at))
+}
+
 #[cfg(test)]
 pub(crate) fn diagnose_signal_kill(
     exit_code: i32,
@@ -43,6 +43,7 @@ mod tests {
             memory_max_mb: 12_288,
             soft_limit_percent: 70,
             scope_name: "csa-codex-01KTEST.scope".to_string(),
+            partial_results_saved: false,
         }
     }
 
@@ -118,4 +119,33 @@ mod tests {
             Some("memory_soft_limit")
         );
     }
+
+    #[test]
+    fn checkpointed_soft_limit_reports_partial_results_saved() {
+        let event = MemorySoftLimitKillDiagnostic {
+            partial_results_saved: true,
+            ..memory_soft_limit_event()
+        };
+        let diagnostic = diagnose_signal_kill_with_events(
+            143,
+            Some("signal"),
+            Some(event),
+            None,
+            None,
+            KillSignalObservations::default,
+        )
+        .expect("signal exit should produce diagnostic");
+
+        let line = diagnostic.stderr_line().expect("stderr line");
+        assert!(
+            line.starts_with("CSA diagnostic: out-of-memory (partial results saved) ("),
+            "{line}"
+        );
+        assert!(line.contains("partial_results_saved=true"), "{line}");
+        assert!(
+            diagnostic
+                .result_report()
+                .is_some_and(|report| report.partial_results_saved)
+        );
+    }
 }
@@ -517,6 +517,7 @@ fn memory_soft_limit_event() -> csa_resource::memory_monitor::MemorySoftLimitKil
         memory_max_mb: 12_288,
         soft_limit_percent: 70,
         scope_name: "csa-codex-01KTEST.scope".to_string(),
+        partial_results_saved: false,
     }
 }
 
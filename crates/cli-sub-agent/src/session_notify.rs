@@ -0,0 +1,50 @@
+//! Completion notifications for top-level sessions (`[hooks.notify]`).
+
+use std::path::Path;
+
+use csa_hooks::SessionNotification;
+
+/// Task types that notify from their own command with a review verdict
+/// instead of from the session pipeline.
+const SELF_NOTIFYING_TASK_TYPES: &[&str] = &["review", "reviewer_sub_session"];
+
+/// Whether the session pipeline should notify for this session.
+pub(crate) fn pipeline_should_notify(depth: u32, task_type: Option<&str>) -> bool {
+    depth == 0 && !task_type.is_some_and(|task_type| SELF_NOTIFYING_TASK_TYPES.contains(&task_type))
+}
+
+/// Send a completion notification when `[hooks.notify]` is configured.
+///
+/// `exit_code == 0` counts as success. Never fails; target errors are logged.
+/// Targets wait on their subprocess, so they run on the blocking pool.
+pub(crate) async fn notify_session_finished(
+    session_id: &str,
+    tool: &str,
+    project_root: &Path,
+    verdict: &str,
+    exit_code: i32,
+    duration_secs: u64,
+) {
+    let Some(config) = csa_hooks::load_global_notify_hook_config() else {
+        return;
+    };
+    let notification = SessionNotification {
+        session_id: session_id.to_string(),
+        tool: tool.to_string(),
+        project_root: project_root.display().to_string(),
+        succeeded: exit_code == 0,
+        verdict: verdict.to_string(),
+        exit_code,
+        duration_secs,
+    };
+    let dispatch = tokio::task::spawn_blocking(move || {
+        csa_hooks::dispatch_session_notification(&config, &notification);
+    });
+    if let Err(error) = dispatch.await {
+        tracing::warn!(error = %error, "Completion notification task failed");
+    }
+}
+
+#[cfg(test)]
+#[path = "session_notify_tests.rs"]
+mod tests;
@@ -0,0 +1,11 @@
+use super::*;
+
+#[test]
+fn pipeline_notifies_only_for_root_non_review_sessions() {
+    assert!(pipeline_should_notify(0, Some("run")));
+    assert!(pipeline_should_notify(0, Some("debate")));
+    assert!(pipeline_should_notify(0, None));
+    assert!(!pipeline_should_notify(1, Some("run")));
+    assert!(!pipeline_should_notify(0, Some("review")));
+    assert!(!pipeline_should_notify(0, Some("reviewer_sub_session")));
+}
@@ -140,6 +140,7 @@ fn recover_legacy_plain_pass_review_sidecars_for_session(
         failure_reason: None,
         tool,
         scope,
+        path_filters: Vec::new(),
         exit_code: crate::verdict_exit_code::exit_code_from_review_decision(decision),
         fix_attempted: false,
         fix_rounds: 0,
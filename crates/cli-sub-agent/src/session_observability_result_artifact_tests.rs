@@ -280,6 +280,7 @@ fn issue_2440_clean_pass_repair_does_not_override_summary_gate() {
             failure_reason: None,
             tool: "codex".to_string(),
             scope: "range:main...HEAD".to_string(),
+            path_filters: Vec::new(),
             exit_code: 0,
             fix_attempted: false,
             fix_rounds: 0,
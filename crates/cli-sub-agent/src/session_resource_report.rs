@@ -0,0 +1,170 @@
+//! Per-session `resources.toml`: peak RSS, CPU, block I/O, sandbox mode,
+//! queue wait and limit violations of one run.
+//!
+//! Queue waits and pre-spawn violations happen before the session pipeline
+//! owns any per-run state, so they are accumulated in a task-local run
+//! context ([`scope_run_usage`]) and drained when the report is attached in
+//! post-exec.  Recording outside a run context is a no-op, so nothing carries
+//! over from one run to the next.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+
+use csa_resource::child_usage::ChildUsage;
+use csa_session::{
+    MetaSessionState, PressureAction, RESOURCE_REPORT_FILE_NAME, SessionArtifact,
+    SessionResourceReport, SessionResult,
+};
+use tracing::warn;
+
+/// Stderr prefix of a disk budget breach reported by the transport.
+const DISK_BUDGET_NOTICE_PREFIX: &str = "disk budget exceeded:";
+
+#[derive(Debug, Default)]
+struct PendingRunUsage {
+    queue_wait: Duration,
+    violations: Vec<String>,
+}
+
+tokio::task_local! {
+    static RUN_USAGE: RefCell<PendingRunUsage>;
+}
+
+/// Run `future` with its own run context, or inside the caller's when one is
+/// already open (`csa run` opens it before waiting for a tool slot).
+pub(crate) async fn scope_run_usage<F: Future>(future: F) -> F::Output {
+    if RUN_USAGE.try_with(|_| ()).is_ok() {
+        future.await
+    } else {
+        RUN_USAGE
+            .scope(RefCell::new(PendingRunUsage::default()), future)
+            .await
+    }
+}
+
+fn with_pending<T: Default>(f: impl FnOnce(&mut PendingRunUsage) -> T) -> T {
+    RUN_USAGE
+        .try_with(|pending| f(&mut pending.borrow_mut()))
+        .unwrap_or_default()
+}
+
+/// Add time spent waiting for a tool slot or for host pressure to subside.
+pub(crate) fn record_queue_wait(waited: Duration) {
+    with_pending(|pending| pending.queue_wait += waited);
+}
+
+/// Note a limit hit before the tool was spawned.
+pub(crate) fn record_limit_violation(detail: String) {
+    with_pending(|pending| pending.violations.push(detail));
+}
+
+/// Build the report, write `resources.toml` and attach it to `session_result`.
+pub(crate) fn attach_resource_report(
+    session_dir: &Path,
+    session: &MetaSessionState,
+    execution: &csa_process::ExecutionResult,
+    session_result: &mut SessionResult,
+) {
+    let pending = with_pending(std::mem::take);
+    let mut violations = pending.violations;
+    violations.extend(run_violations(
+        session_dir,
+        session,
+        &execution.stderr_output,
+        session_result,
+    ));
+    let report = build_report(
+        session,
+        execution.peak_memory_mb,
+        execution.peak_vram_mb,
+        execution.child_usage,
+        pending.queue_wait,
+        violations,
+        session_result,
+    );
+
+    if let Err(err) = csa_session::write_resource_report(session_dir, &report) {
+        warn!(
+            session = %session.meta_session_id,
+            error = %err,
+            "Failed to write session resource report"
+        );
+    } else if !session_result
+        .artifacts
+        .iter()
+        .any(|artifact| artifact.path == RESOURCE_REPORT_FILE_NAME)
+    {
+        session_result
+            .artifacts
+            .push(SessionArtifact::new(RESOURCE_REPORT_FILE_NAME));
+    }
+    session_result.resources = Some(report);
+}
+
+fn build_report(
+    session: &MetaSessionState,
+    peak_rss_mb: Option<u64>,
+    peak_vram_mb: Option<u64>,
+    child_usage: Option<ChildUsage>,
+    queue_wait: Duration,
+    limit_violations: Vec<String>,
+    session_result: &SessionResult,
+) -> SessionResourceReport {
+    let sandbox = session.sandbox_info.as_ref();
+    SessionResourceReport {
+        peak_rss_mb,
+        peak_vram_mb,
+        cpu_seconds: child_usage.map(|usage| usage.cpu_seconds()),
+        io_read_bytes: child_usage.and_then(|usage| usage.read_bytes),
+        io_write_bytes: child_usage.and_then(|usage| usage.write_bytes),
+        sandbox_mode: sandbox.map(|info| info.mode.clone()),
+        filesystem_sandbox: sandbox.and_then(|info| info.filesystem_mode.clone()),
+        queue_wait_secs: queue_wait.as_secs(),
+        wall_secs: (session_result.completed_at - session_result.started_at)
+            .num_seconds()
+            .max(0) as u64,
+        limit_violations,
+    }
+}
+
+/// Limits hit while the tool was running.
+fn run_violations(
+    session_dir: &Path,
+    session: &MetaSessionState,
+    stderr: &str,
+    session_result: &SessionResult,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+    if let Some(event) = crate::session_kill_diagnostics::memory_soft_limit_event(
+        session_dir,
+        &session_result.started_at,
+    ) {
+        violations.push(format!(
+            "memory soft limit: {} MB >= {} MB ({}% of {} MB)",
+            event.current_mb, event.threshold_mb, event.soft_limit_percent, event.memory_max_mb
+        ));
+    }
+    violations.extend(
+        stderr
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with(DISK_BUDGET_NOTICE_PREFIX))
+            .map(str::to_string),
+    );
+    violations.extend(
+        session
+            .pressure_events
+            .iter()
+            .filter(|event| {
+                event.action == PressureAction::Paused && event.at >= session_result.started_at
+            })
+            .map(|event| format!("paused under host pressure: {}", event.reason)),
+    );
+    violations
+}
+
+#[cfg(test)]
+#[path = "session_resource_report_tests.rs"]
+mod tests;
@@ -0,0 +1,156 @@
+use super::*;
+use chrono::Utc;
+use csa_process::ExecutionResult;
+use csa_session::{
+    ContextStatus, Genealogy, PressureEvent, SandboxInfo, SessionPhase, TaskContext,
+};
+use std::collections::HashMap;
+
+fn sample_session_state() -> MetaSessionState {
+    MetaSessionState {
+        meta_session_id: "01HTEST000000000000000000".to_string(),
+        description: None,
+        project_path: "/tmp".to_string(),
+        branch: None,
+        created_at: Utc::now(),
+        last_accessed: Utc::now(),
+        csa_version: None,
+        genealogy: Genealogy::default(),
+        tools: HashMap::new(),
+        context_status: ContextStatus::default(),
+        total_token_usage: None,
+        phase: SessionPhase::Active,
+        task_context: TaskContext::default(),
+        turn_count: 0,
+        token_budget: None,
+        sandbox_info: Some(SandboxInfo {
+            mode: "cgroup".to_string(),
+            memory_max_mb: Some(4096),
+            filesystem_mode: Some("bwrap".to_string()),
+            readonly_project_root: None,
+            resource_resolution: None,
+        }),
+        termination_reason: None,
+        is_seed_candidate: false,
+        git_head_at_creation: None,
+        pre_session_porcelain: None,
+        last_return_packet: None,
+        change_id: None,
+        spec_id: None,
+        fork_call_timestamps: Vec::new(),
+        vcs_identity: None,
+        identity_version: 1,
+        ..Default::default()
+    }
+}
+
+fn pressure_event(action: PressureAction, minutes_ago: i64, reason: &str) -> PressureEvent {
+    PressureEvent {
+        at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+        action,
+        reason: reason.to_string(),
+        duration_secs: None,
+    }
+}
+
+#[test]
+fn attach_resource_report_writes_file_and_sets_result() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let mut session = sample_session_state();
+    session.pressure_events = vec![
+        pressure_event(PressureAction::Paused, 60, "earlier run"),
+        pressure_event(
+            PressureAction::Paused,
+            1,
+            "memory some avg10=40.0% >= 20.0%",
+        ),
+        pressure_event(PressureAction::Resumed, 1, "pressure subsided"),
+    ];
+    let now = Utc::now();
+    let mut session_result = SessionResult {
+        started_at: now - chrono::Duration::seconds(90),
+        completed_at: now,
+        ..Default::default()
+    };
+    let disk_notice =
+        "disk budget exceeded: 2100 MB written (limit 2048 MB) under /tmp; process killed";
+    let execution = ExecutionResult {
+        stderr_output: format!("warning: slow\n  {disk_notice}\n"),
+        peak_memory_mb: Some(1536),
+        peak_vram_mb: Some(20_480),
+        ..Default::default()
+    };
+
+    attach_resource_report(tmp.path(), &session, &execution, &mut session_result);
+
+    let report = session_result.resources.clone().expect("report attached");
+    assert_eq!(report.peak_rss_mb, Some(1536));
+    assert_eq!(report.peak_vram_mb, Some(20_480));
+    assert_eq!(report.sandbox_mode.as_deref(), Some("cgroup"));
+    assert_eq!(report.filesystem_sandbox.as_deref(), Some("bwrap"));
+    assert_eq!(report.wall_secs, 90);
+    assert_eq!(
+        report.limit_violations,
+        [
+            disk_notice,
+            "paused under host pressure: memory some avg10=40.0% >= 20.0%",
+        ]
+    );
+    assert!(
+        session_result
+            .artifacts
+            .iter()
+            .any(|artifact| artifact.path == RESOURCE_REPORT_FILE_NAME)
+    );
+
+    let written = std::fs::read_to_string(tmp.path().join(RESOURCE_REPORT_FILE_NAME))
+        .expect("resources.toml written");
+    let parsed: SessionResourceReport = toml::from_str(&written).expect("parse resources.toml");
+    assert_eq!(parsed, report);
+}
+
+#[test]
+fn build_report_without_sandbox_or_usage_leaves_fields_empty() {
+    let mut session = sample_session_state();
+    session.sandbox_info = None;
+    let session_result = SessionResult::default();
+
+    let report = build_report(
+        &session,
+        None,
+        None,
+        None,
+        Duration::from_secs(42),
+        Vec::new(),
+        &session_result,
+    );
+
+    assert_eq!(report.queue_wait_secs, 42);
+    assert!(report.sandbox_mode.is_none());
+    assert!(report.cpu_seconds.is_none());
+    assert!(report.io_read_bytes.is_none());
+    assert!(report.limit_violations.is_empty());
+}
+
+#[tokio::test]
+async fn run_usage_is_scoped_to_one_run() {
+    // Outside a run context nothing is recorded.
+    record_queue_wait(Duration::from_secs(5));
+
+    let first = scope_run_usage(async {
+        record_queue_wait(Duration::from_secs(3));
+        // A nested pipeline call shares the enclosing run context.
+        scope_run_usage(async {
+            record_limit_violation("spawned despite host pressure".to_string());
+        })
+        .await;
+        with_pending(std::mem::take)
+    })
+    .await;
+    assert_eq!(first.queue_wait, Duration::from_secs(3));
+    assert_eq!(first.violations, ["spawned despite host pressure"]);
+
+    let second = scope_run_usage(async { with_pending(std::mem::take) }).await;
+    assert_eq!(second.queue_wait, Duration::ZERO);
+    assert!(second.violations.is_empty());
+}
@@ -19,9 +19,11 @@
 use anyhow::{Context, Result, bail};
 use csa_config::paths;
 use std::path::{Path, PathBuf};
+use tracing::warn;
 
 use weave::package::{self, SourceKind};
 use weave::parser::{AgentConfig, SkillConfig, parse_skill_config};
+use weave::requirements::{Unmet, configured_mcp_servers, parse_requirements};
 
 use crate::skill_repo::sanitize_skill_md;
 
@@ -64,7 +66,49 @@ pub(crate) fn resolve_skill(name: &str, project_root: &Path) -> Result<ResolvedS
     }
 
     let candidates = search_paths(name, project_root);
-    resolve_skill_from_candidates(name, &candidates)
+    let skill = resolve_skill_from_candidates(name, &candidates)?;
+    check_requirements(
+        name,
+        &skill,
+        env!("CARGO_PKG_VERSION"),
+        &configured_mcp_servers(project_root),
+    )?;
+    Ok(skill)
+}
+
+/// Fail before the run starts when the skill's frontmatter requires an
+/// executable or a csa release this machine lacks. Missing MCP servers only
+/// warn: the tool may be configured with them outside csa.
+fn check_requirements(
+    name: &str,
+    skill: &ResolvedSkill,
+    csa_version: &str,
+    mcp_servers: &[String],
+) -> Result<()> {
+    let requirements = parse_requirements(&skill.skill_md).with_context(|| {
+        format!(
+            "skill '{name}': invalid requirements in {}",
+            skill.dir.join("SKILL.md").display()
+        )
+    })?;
+    let (advisory, fatal): (Vec<_>, Vec<_>) = requirements
+        .unmet(csa_version, mcp_servers)
+        .into_iter()
+        .partition(|unmet| matches!(unmet, Unmet::McpServer(_)));
+    for unmet in &advisory {
+        warn!(skill = name, "{unmet}; {}", unmet.hint());
+    }
+    if fatal.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "skill '{name}' cannot run on this machine:\n{}",
+        fatal
+            .iter()
+            .map(|unmet| format!("  - {unmet}\n    hint: {}", unmet.hint()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
 }
 
 fn resolve_skill_from_candidates(name: &str, candidates: &[PathBuf]) -> Result<ResolvedSkill> {
@@ -126,6 +126,52 @@ fn resolve_skill_from_csa_skills() {
     assert!(resolved.dir.ends_with(".csa/skills/my-skill"));
 }
 
+#[test]
+fn resolve_skill_fails_early_when_a_required_tool_is_missing() {
+    let tmp = TempDir::new().unwrap();
+    make_skill_dir(
+        tmp.path(),
+        ".csa/skills/needs-tool",
+        "---\nname: needs-tool\nrequires-tools: [sh, csa-no-such-tool]\n---\n# Needs Tool\n",
+        None,
+    );
+
+    let err = resolve_skill("needs-tool", tmp.path()).unwrap_err();
+    let message = format!("{err:#}");
+    assert!(
+        message.contains("skill 'needs-tool' cannot run on this machine"),
+        "{message}"
+    );
+    assert!(
+        message.contains("requires `csa-no-such-tool`, which is not on PATH"),
+        "{message}"
+    );
+    assert!(
+        message.contains("hint: install `csa-no-such-tool`"),
+        "{message}"
+    );
+    assert!(!message.contains("`sh`"), "{message}");
+}
+
+#[test]
+fn check_requirements_fails_on_old_csa_but_only_warns_for_mcp_servers() {
+    let skill = ResolvedSkill {
+        dir: PathBuf::from("/skills/notes"),
+        skill_md: "---\nrequires-mcp-servers: [github]\nmin-csa-version: 0.2.0\n---\n".to_string(),
+        config: None,
+    };
+
+    assert!(check_requirements("notes", &skill, "0.2.0", &[]).is_ok());
+    let err = check_requirements("notes", &skill, "0.1.9", &[]).unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.contains("requires csa >= 0.2.0, but this is 0.1.9"),
+        "{message}"
+    );
+    assert!(message.contains("csa self-update"), "{message}");
+    assert!(!message.contains("github"), "{message}");
+}
+
 #[test]
 fn resolve_skill_from_global_store() {
     let tmp = TempDir::new().unwrap();
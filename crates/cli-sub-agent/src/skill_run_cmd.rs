@@ -89,6 +89,7 @@ pub(crate) async fn handle_skill_run(
         no_idle_timeout: false,
         no_memory: false,
         memory_query: None,
+        explain_memory: false,
         current_depth,
         output_format,
         stream_mode: csa_process::StreamMode::BufferOnly,
@@ -108,6 +109,7 @@ pub(crate) async fn handle_skill_run(
         extra_writable: vec![],
         extra_readable: vec![],
         startup_env,
+        isolated_worktree: None,
     })
     .await
 }
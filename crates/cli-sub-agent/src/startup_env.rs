@@ -1,10 +1,12 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use csa_core::env::{
     CSA_DEPTH_ENV_KEY, CSA_FORCE_IGNORE_TIER_SETTING_ENV_KEY, CSA_INTERNAL_INVOCATION_ENV_KEY,
-    CSA_MODEL_SPEC_ENV_KEY, CSA_NO_FAILOVER_ENV_KEY, CSA_PARENT_SESSION_DIR_ENV_KEY,
-    CSA_PARENT_SESSION_ENV_KEY, CSA_PATTERN_INTERNAL_ENV_KEY, CSA_PROJECT_ROOT_ENV_KEY,
-    CSA_SESSION_DIR_ENV_KEY, CSA_SESSION_ID_ENV_KEY, STARTUP_SUBTREE_ENV_KEYS,
+    CSA_LINEAGE_ENV_KEY, CSA_MODEL_SPEC_ENV_KEY, CSA_NO_FAILOVER_ENV_KEY,
+    CSA_PARENT_SESSION_DIR_ENV_KEY, CSA_PARENT_SESSION_ENV_KEY, CSA_PATTERN_INTERNAL_ENV_KEY,
+    CSA_PROJECT_ROOT_ENV_KEY, CSA_SESSION_DIR_ENV_KEY, CSA_SESSION_ID_ENV_KEY,
+    STARTUP_SUBTREE_ENV_KEYS,
 };
 
 const CSA_CHILD_CONTRACT_ENV_KEYS: &[&str] = &[
@@ -30,8 +32,11 @@ pub(crate) struct StartupSubtreeEnv {
     model_spec: Option<String>,
     force_ignore_tier_setting: bool,
     no_failover: bool,
+    /// Session chain (root first) from the verified `CSA_LINEAGE` token.
+    lineage_chain: Vec<String>,
     raw_session_id: Option<String>,
     raw_depth: Option<String>,
+    raw_lineage: Option<String>,
     raw_project_root: Option<String>,
     raw_session_dir: Option<String>,
     raw_parent_session: Option<String>,
@@ -67,8 +72,10 @@ pub(crate) static EMPTY_STARTUP_SUBTREE_ENV: StartupSubtreeEnv = StartupSubtreeE
     model_spec: None,
     force_ignore_tier_setting: false,
     no_failover: false,
+    lineage_chain: Vec::new(),
     raw_session_id: None,
     raw_depth: None,
+    raw_lineage: None,
     raw_project_root: None,
     raw_session_dir: None,
     raw_parent_session: None,
@@ -90,12 +97,40 @@ impl StartupSubtreeEnv {
         if let Ok(value) = std::env::var(CSA_PATTERN_INTERNAL_ENV_KEY) {
             values.insert(CSA_PATTERN_INTERNAL_ENV_KEY, value);
         }
-        Self::from_values(values)
+        Self::from_values(values).with_verified_lineage()
+    }
+
+    /// Reconcile `CSA_DEPTH` with the signed lineage token (or, without one,
+    /// the spawning session's genealogy) so a spoofed or lost depth cannot
+    /// reset recursion accounting.
+    fn with_verified_lineage(mut self) -> Self {
+        let session_dir = self.session_dir.as_ref().map(PathBuf::from).or_else(|| {
+            let session_id = self.session_id.as_deref()?;
+            let project_root = self.project_root.as_deref()?;
+            csa_session::get_session_dir(Path::new(project_root), session_id).ok()
+        });
+        let inherited = csa_session::verify_inherited_lineage(
+            self.raw_lineage.as_deref(),
+            self.depth,
+            self.session_id.as_deref(),
+            session_dir.as_deref(),
+        );
+        // Tracing is not initialized yet at startup capture.
+        if let Some(warning) = inherited.warning {
+            eprintln!("warning: {warning}");
+        }
+        if inherited.lineage.depth != self.depth {
+            self.depth = inherited.lineage.depth;
+            self.raw_depth = Some(self.depth.to_string());
+        }
+        self.lineage_chain = inherited.lineage.chain;
+        self
     }
 
     pub(crate) fn from_values(values: HashMap<&'static str, String>) -> Self {
         let raw_session_id = values.get(CSA_SESSION_ID_ENV_KEY).cloned();
         let raw_depth = values.get(CSA_DEPTH_ENV_KEY).cloned();
+        let raw_lineage = values.get(CSA_LINEAGE_ENV_KEY).cloned();
         let raw_project_root = values.get(CSA_PROJECT_ROOT_ENV_KEY).cloned();
         let raw_session_dir = values.get(CSA_SESSION_DIR_ENV_KEY).cloned();
         let raw_parent_session = values.get(CSA_PARENT_SESSION_ENV_KEY).cloned();
@@ -136,8 +171,10 @@ impl StartupSubtreeEnv {
             model_spec: non_empty(raw_model_spec.as_ref()),
             force_ignore_tier_setting,
             no_failover,
+            lineage_chain: Vec::new(),
             raw_session_id,
             raw_depth,
+            raw_lineage,
             raw_project_root,
             raw_session_dir,
             raw_parent_session,
@@ -163,6 +200,36 @@ impl StartupSubtreeEnv {
         self.session_id.as_deref()
     }
 
+    /// Refuse to resume `session` when it is this process's own ancestor: the
+    /// run would loop back into its own subtree. A prefix must first resolve
+    /// to exactly one session of `project_root`, so a short prefix shared by
+    /// an ancestor does not block an unrelated session.
+    pub(crate) fn ensure_not_in_lineage(
+        &self,
+        project_root: &Path,
+        session: &str,
+    ) -> anyhow::Result<()> {
+        if self.lineage_chain.is_empty() {
+            return Ok(());
+        }
+        let session = session.trim().to_ascii_uppercase();
+        let in_lineage = |id: &str| self.lineage_chain.iter().any(|ancestor| ancestor == id);
+        let session_id =
+            if in_lineage(&session) || csa_session::validate_session_id(&session).is_ok() {
+                session
+            } else {
+                crate::session_cmds::resolve_session_prefix_with_fallback(project_root, &session)?
+                    .session_id
+            };
+        if in_lineage(&session_id) {
+            anyhow::bail!(
+                "refusing to resume session {session_id} from inside its own subtree (lineage: {})",
+                self.lineage_chain.join(" > ")
+            );
+        }
+        Ok(())
+    }
+
     pub(crate) fn with_current_session(
         mut self,
         session_id: impl AsRef<str>,
@@ -222,6 +289,7 @@ impl StartupSubtreeEnv {
     pub(crate) fn to_child_env_vars(&self) -> Vec<(String, String)> {
         let mut vars = self.to_csa_child_contract_env_vars();
         self.push_child_env_var(&mut vars, CSA_DEPTH_ENV_KEY, &self.raw_depth);
+        self.push_child_env_var(&mut vars, CSA_LINEAGE_ENV_KEY, &self.raw_lineage);
         self.push_child_env_var(&mut vars, CSA_PROJECT_ROOT_ENV_KEY, &self.raw_project_root);
         self.push_child_env_var(
             &mut vars,
@@ -334,3 +334,89 @@ fn startup_capture_preserves_subtree_keys_in_process_env() {
         Ok("1")
     );
 }
+
+#[test]
+fn verified_lineage_raises_lowered_depth_and_blocks_resuming_ancestors() {
+    let tmp = tempfile::tempdir().unwrap();
+    for (id, parent, depth) in [("01KROOT", None, 0), ("01KCHILD", Some("01KROOT"), 1)] {
+        let dir = tmp.path().join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        let parent = parent
+            .map(|parent| format!("parent_session_id = \"{parent}\"\n"))
+            .unwrap_or_default();
+        std::fs::write(
+            dir.join("state.toml"),
+            format!("[genealogy]\n{parent}depth = {depth}\n"),
+        )
+        .unwrap();
+    }
+    let child_dir = tmp.path().join("01KCHILD");
+    let values = HashMap::from([
+        (CSA_SESSION_ID_ENV_KEY, "01KCHILD".to_string()),
+        (CSA_DEPTH_ENV_KEY, "0".to_string()),
+        (CSA_SESSION_DIR_ENV_KEY, child_dir.display().to_string()),
+    ]);
+
+    let startup = StartupSubtreeEnv::from_values(values).with_verified_lineage();
+
+    assert_eq!(startup.current_depth(), 2);
+    assert!(
+        startup
+            .to_child_env_vars()
+            .contains(&(CSA_DEPTH_ENV_KEY.to_string(), "2".to_string()))
+    );
+    let err = startup
+        .ensure_not_in_lineage(tmp.path(), "01kroot")
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("(lineage: 01KROOT > 01KCHILD)"),
+        "{err}"
+    );
+    assert!(
+        startup
+            .ensure_not_in_lineage(tmp.path(), "01ARZ3NDEKTSV4RRFFQ69G5FAV")
+            .is_ok()
+    );
+}
+
+#[test]
+fn lineage_check_resolves_prefixes_to_exactly_one_session() {
+    let tmp = tempfile::tempdir().unwrap();
+    let _state_dir = crate::test_env_lock::ScopedTestEnvVar::set(
+        csa_config::paths::STATE_DIR_ENV,
+        tmp.path().join("state"),
+    );
+    let ancestor = "01KAAAAAAAAAAAAAAAAAAAAAAA";
+    let sibling = "01KBBBBBBBBBBBBBBBBBBBBBBB";
+    let sessions_dir = csa_session::get_session_root(tmp.path())
+        .unwrap()
+        .join("sessions");
+    for id in [ancestor, sibling] {
+        std::fs::create_dir_all(sessions_dir.join(id)).unwrap();
+    }
+    let startup = StartupSubtreeEnv {
+        lineage_chain: vec![ancestor.to_string()],
+        ..StartupSubtreeEnv::default()
+    };
+
+    let err = startup
+        .ensure_not_in_lineage(tmp.path(), "01ka")
+        .unwrap_err();
+    assert!(err.to_string().contains("refusing to resume"), "{err}");
+    assert!(startup.ensure_not_in_lineage(tmp.path(), "01KB").is_ok());
+    let err = startup
+        .ensure_not_in_lineage(tmp.path(), "01K")
+        .unwrap_err();
+    assert!(err.to_string().contains("Ambiguous"), "{err}");
+}
+
+#[test]
+fn lineage_token_is_reemitted_to_daemon_children() {
+    let values = HashMap::from([(CSA_LINEAGE_ENV_KEY, "v1.1.01KA.abc".to_string())]);
+    let startup = StartupSubtreeEnv::from_values(values);
+    assert!(
+        startup
+            .to_child_env_vars()
+            .contains(&(CSA_LINEAGE_ENV_KEY.to_string(), "v1.1.01KA.abc".to_string()))
+    );
+}
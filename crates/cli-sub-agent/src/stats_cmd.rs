@@ -0,0 +1,92 @@
+//! `csa stats`: usage report, and `csa stats memory`: decayed peak-memory
+//! estimates from past runs.
+
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use csa_core::types::OutputFormat;
+use csa_resource::MemoryEstimate;
+
+#[path = "stats_cmd_usage.rs"]
+mod usage;
+pub(crate) use usage::handle_stats_usage;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Handle `csa stats memory`.
+pub(crate) fn handle_stats_memory(
+    tool: Option<&str>,
+    half_life_days: u64,
+    format: OutputFormat,
+) -> Result<()> {
+    let stats = crate::memory_usage_stats::load_usage_stats()?;
+    let half_life = Duration::from_secs(half_life_days.saturating_mul(SECS_PER_DAY));
+    let estimates: Vec<MemoryEstimate> = stats
+        .estimates(SystemTime::now(), half_life)
+        .into_iter()
+        .filter(|estimate| tool.is_none_or(|tool| estimate.key.tool == tool))
+        .collect();
+
+    match format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&estimates_json(&estimates))?
+            )
+        }
+        OutputFormat::Text => print!("{}", render_estimates(&estimates)),
+    }
+    Ok(())
+}
+
+fn estimates_json(estimates: &[MemoryEstimate]) -> serde_json::Value {
+    serde_json::Value::Array(
+        estimates
+            .iter()
+            .map(|estimate| {
+                serde_json::json!({
+                    "tool": estimate.key.tool,
+                    "model": estimate.key.model,
+                    "task_kind": estimate.key.task_kind,
+                    "p95_mb": estimate.p95_mb,
+                    "last_peak_mb": estimate.last_peak_mb,
+                    "samples": estimate.sample_count,
+                    "effective_weight": estimate.effective_weight,
+                    "vram_p95_mb": estimate.vram_p95_mb,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn render_estimates(estimates: &[MemoryEstimate]) -> String {
+    if estimates.is_empty() {
+        return "No memory usage recorded yet.\n".to_string();
+    }
+    let mut out = format!(
+        "{:<14} {:<24} {:<20} {:>8} {:>9} {:>7} {:>7} {:>9}\n",
+        "TOOL", "MODEL", "TASK", "P95", "LAST", "RUNS", "WEIGHT", "VRAM_P95"
+    );
+    for estimate in estimates {
+        let vram = estimate
+            .vram_p95_mb
+            .map(|mb| format!("{mb}MB"))
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<14} {:<24} {:<20} {:>6}MB {:>7}MB {:>7} {:>7.2} {:>9}\n",
+            estimate.key.tool,
+            estimate.key.model.as_deref().unwrap_or("-"),
+            estimate.key.task_kind.as_deref().unwrap_or("-"),
+            estimate.p95_mb,
+            estimate.last_peak_mb,
+            estimate.sample_count,
+            estimate.effective_weight,
+            vram,
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "stats_cmd_tests.rs"]
+mod tests;
@@ -0,0 +1,53 @@
+use super::*;
+use csa_resource::UsageKey;
+
+fn estimate(tool: &str, model: Option<&str>, p95_mb: u64) -> MemoryEstimate {
+    MemoryEstimate {
+        key: UsageKey::new(tool, model, Some("run")),
+        p95_mb,
+        sample_count: 4,
+        effective_weight: 2.5,
+        last_peak_mb: p95_mb - 100,
+        vram_p95_mb: None,
+    }
+}
+
+#[test]
+fn render_estimates_lists_one_row_per_key() {
+    let rendered = render_estimates(&[
+        estimate("gemini-cli", Some("gemini-2.5-flash"), 700),
+        estimate("gemini-cli", Some("gemini-2.5-pro"), 4_100),
+    ]);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("TOOL"));
+    assert!(lines[1].contains("gemini-2.5-flash"));
+    assert!(lines[1].contains("700MB"));
+    assert!(lines[2].contains("4100MB"));
+    assert!(lines[2].contains("2.50"));
+
+    assert_eq!(render_estimates(&[]), "No memory usage recorded yet.\n");
+}
+
+#[test]
+fn estimates_json_keeps_missing_model_as_null() {
+    let json = estimates_json(&[estimate("codex", None, 1_500)]);
+    assert_eq!(json[0]["tool"], "codex");
+    assert!(json[0]["model"].is_null());
+    assert_eq!(json[0]["task_kind"], "run");
+    assert_eq!(json[0]["p95_mb"], 1_500);
+}
+
+#[test]
+fn vram_estimate_is_shown_when_recorded() {
+    let mut local = estimate("openai-compat", Some("qwen3-32b"), 2_000);
+    local.vram_p95_mb = Some(21_500);
+    let rendered = render_estimates(&[local.clone(), estimate("codex", None, 1_500)]);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert!(lines[0].ends_with("VRAM_P95"));
+    assert!(lines[1].ends_with("21500MB"));
+    assert!(lines[2].ends_with('-'));
+
+    let json = estimates_json(&[local]);
+    assert_eq!(json[0]["vram_p95_mb"], 21_500);
+}
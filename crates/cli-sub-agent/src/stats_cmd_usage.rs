@@ -0,0 +1,242 @@
+//! `csa stats`: session, token and cost usage across all projects.
+//!
+//! Reads every project's session state (`state.toml`) and result
+//! (`result.toml`), keeps the sessions created within `--since`, and
+//! aggregates them per `--by` group.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::Utc;
+use csa_core::types::OutputFormat;
+use csa_session::{MetaSessionState, SessionResult};
+use serde::Serialize;
+
+use crate::cli::{StatsGroupBy, StatsUsageArgs};
+
+const UNKNOWN_GROUP: &str = "-";
+/// Exit-code bucket of sessions that have no result yet.
+const NO_RESULT: &str = "running";
+
+/// What one session contributes to the report.
+#[derive(Debug, Clone, Default)]
+struct UsageSample {
+    group: String,
+    exit_code: Option<i32>,
+    duration_secs: Option<i64>,
+    input_tokens: u64,
+    output_tokens: u64,
+    total_tokens: u64,
+    cost_usd: f64,
+    failover_events: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct UsageBucket {
+    key: String,
+    sessions: usize,
+    /// Sessions per exit code, `running` for sessions without a result.
+    exit_codes: BTreeMap<String, usize>,
+    input_tokens: u64,
+    output_tokens: u64,
+    total_tokens: u64,
+    estimated_cost_usd: f64,
+    failover_events: usize,
+    /// Mean run time of the sessions that have a result.
+    avg_duration_secs: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct UsageReport {
+    since: String,
+    group_by: &'static str,
+    total: UsageBucket,
+    groups: Vec<UsageBucket>,
+}
+
+/// Handle `csa stats`.
+pub(crate) fn handle_stats_usage(args: StatsUsageArgs, format: OutputFormat) -> Result<()> {
+    let cutoff = Utc::now() - crate::session_cmds::parse_duration_filter(&args.since)?;
+    let mut seen = HashSet::new();
+    let mut samples = Vec::new();
+    for (session_root, _) in csa_session::list_all_project_session_roots()? {
+        let sessions = match csa_session::list_sessions_from_root_readonly(&session_root) {
+            Ok(sessions) => sessions,
+            Err(err) => {
+                tracing::debug!(
+                    root = %session_root.display(),
+                    error = %err,
+                    "Skipping project root with unreadable sessions"
+                );
+                continue;
+            }
+        };
+        for session in sessions {
+            if session.created_at < cutoff || !seen.insert(session.meta_session_id.clone()) {
+                continue;
+            }
+            let session_dir = session_root.join("sessions").join(&session.meta_session_id);
+            let result = load_result(&session_dir);
+            samples.push(usage_sample(&session, result.as_ref(), args.by));
+        }
+    }
+
+    let report = build_report(args.since, args.by, &samples);
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => print!("{}", render_report(&report)),
+    }
+    Ok(())
+}
+
+/// Best effort: a missing or unparsable result counts as "no result yet".
+fn load_result(session_dir: &Path) -> Option<SessionResult> {
+    let content =
+        std::fs::read_to_string(session_dir.join(csa_session::result::RESULT_FILE_NAME)).ok()?;
+    toml::from_str(&content).ok()
+}
+
+fn usage_sample(
+    session: &MetaSessionState,
+    result: Option<&SessionResult>,
+    by: StatsGroupBy,
+) -> UsageSample {
+    let group = match by {
+        StatsGroupBy::Tool => result
+            .map(|result| result.tool.as_str())
+            .filter(|tool| !tool.trim().is_empty())
+            .or_else(|| match session.tools.len() {
+                1 => session.tools.keys().next().map(String::as_str),
+                _ => None,
+            })
+            .map(str::to_string),
+        StatsGroupBy::Model => session.last_model.clone(),
+        StatsGroupBy::Tier => session.task_context.tier_name.clone(),
+        StatsGroupBy::Project => Some(session.project_path.clone()).filter(|path| !path.is_empty()),
+    }
+    .unwrap_or_else(|| UNKNOWN_GROUP.to_string());
+    let usage = session.total_token_usage.as_ref();
+    let failover_events = result.map_or(0, |result| match &result.fallback_chain {
+        Some(chain) => chain.len(),
+        None => usize::from(result.fallback_tool.is_some()),
+    });
+
+    UsageSample {
+        group,
+        exit_code: result.map(|result| result.exit_code),
+        duration_secs: result.map(|result| {
+            (result.completed_at - result.started_at)
+                .num_seconds()
+                .max(0)
+        }),
+        input_tokens: usage.and_then(|usage| usage.input_tokens).unwrap_or(0),
+        output_tokens: usage.and_then(|usage| usage.output_tokens).unwrap_or(0),
+        total_tokens: usage.and_then(|usage| usage.total_tokens).unwrap_or(0),
+        cost_usd: usage
+            .and_then(|usage| usage.estimated_cost_usd)
+            .unwrap_or(0.0),
+        failover_events,
+    }
+}
+
+fn build_report(since: String, by: StatsGroupBy, samples: &[UsageSample]) -> UsageReport {
+    let mut groups: BTreeMap<&str, Vec<&UsageSample>> = BTreeMap::new();
+    for sample in samples {
+        groups
+            .entry(sample.group.as_str())
+            .or_default()
+            .push(sample);
+    }
+    let mut groups: Vec<UsageBucket> = groups
+        .into_iter()
+        .map(|(key, samples)| build_bucket(key, samples))
+        .collect();
+    groups.sort_by(|a, b| b.sessions.cmp(&a.sessions).then_with(|| a.key.cmp(&b.key)));
+
+    UsageReport {
+        since,
+        group_by: match by {
+            StatsGroupBy::Tool => "tool",
+            StatsGroupBy::Model => "model",
+            StatsGroupBy::Tier => "tier",
+            StatsGroupBy::Project => "project",
+        },
+        total: build_bucket("total", samples.iter().collect()),
+        groups,
+    }
+}
+
+fn build_bucket(key: &str, samples: Vec<&UsageSample>) -> UsageBucket {
+    let mut bucket = UsageBucket {
+        key: key.to_string(),
+        sessions: samples.len(),
+        ..UsageBucket::default()
+    };
+    let mut durations = Vec::new();
+    for sample in samples {
+        let exit_code = sample
+            .exit_code
+            .map_or_else(|| NO_RESULT.to_string(), |code| code.to_string());
+        *bucket.exit_codes.entry(exit_code).or_default() += 1;
+        bucket.input_tokens += sample.input_tokens;
+        bucket.output_tokens += sample.output_tokens;
+        bucket.total_tokens += sample.total_tokens;
+        bucket.estimated_cost_usd += sample.cost_usd;
+        bucket.failover_events += sample.failover_events;
+        durations.extend(sample.duration_secs);
+    }
+    if !durations.is_empty() {
+        bucket.avg_duration_secs =
+            Some(durations.iter().sum::<i64>() as f64 / durations.len() as f64);
+    }
+    bucket
+}
+
+fn render_report(report: &UsageReport) -> String {
+    if report.total.sessions == 0 {
+        return format!("No sessions in the last {}.\n", report.since);
+    }
+    let mut out = format!(
+        "Sessions created in the last {}, by {}\n\n{:<32} {:>8} {:<24} {:>12} {:>12} {:>10} {:>9} {:>9}\n",
+        report.since,
+        report.group_by,
+        report.group_by.to_ascii_uppercase(),
+        "SESSIONS",
+        "EXIT CODES",
+        "IN TOKENS",
+        "OUT TOKENS",
+        "COST",
+        "FAILOVERS",
+        "AVG TIME"
+    );
+    for bucket in report.groups.iter().chain(std::iter::once(&report.total)) {
+        let exit_codes = bucket
+            .exit_codes
+            .iter()
+            .map(|(code, count)| format!("{code}:{count}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let avg_time = bucket
+            .avg_duration_secs
+            .map_or_else(|| "-".to_string(), |secs| format!("{secs:.0}s"));
+        let _ = writeln!(
+            out,
+            "{:<32} {:>8} {:<24} {:>12} {:>12} {:>10} {:>9} {:>9}",
+            bucket.key,
+            bucket.sessions,
+            exit_codes,
+            bucket.input_tokens,
+            bucket.output_tokens,
+            format!("${:.2}", bucket.estimated_cost_usd),
+            bucket.failover_events,
+            avg_time,
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "stats_cmd_usage_tests.rs"]
+mod tests;
@@ -0,0 +1,106 @@
+use super::*;
+use chrono::Duration;
+use csa_session::{TokenUsage, ToolState};
+
+fn session(tool: &str, tier: Option<&str>, tokens: Option<(u64, u64, f64)>) -> MetaSessionState {
+    let mut session = MetaSessionState {
+        meta_session_id: "01J00000000000000000000000".to_string(),
+        project_path: "/work/app".to_string(),
+        ..MetaSessionState::default()
+    };
+    session.tools.insert(
+        tool.to_string(),
+        ToolState {
+            provider_session_id: None,
+            last_action_summary: String::new(),
+            last_exit_code: 0,
+            updated_at: Utc::now(),
+            tool_version: None,
+            token_usage: None,
+        },
+    );
+    session.task_context.tier_name = tier.map(str::to_string);
+    session.total_token_usage = tokens.map(|(input, output, cost)| TokenUsage {
+        input_tokens: Some(input),
+        output_tokens: Some(output),
+        total_tokens: Some(input + output),
+        estimated_cost_usd: Some(cost),
+        ..TokenUsage::default()
+    });
+    session
+}
+
+fn result(tool: &str, exit_code: i32, duration_secs: i64) -> SessionResult {
+    let started_at = Utc::now();
+    SessionResult {
+        tool: tool.to_string(),
+        exit_code,
+        started_at,
+        completed_at: started_at + Duration::seconds(duration_secs),
+        ..SessionResult::default()
+    }
+}
+
+#[test]
+fn samples_group_by_the_requested_dimension() {
+    let codex = session("codex", Some("tier-2"), Some((1_000, 200, 0.5)));
+    let mut fallback = result("claude-code", 0, 30);
+    fallback.fallback_tool = Some("claude-code".to_string());
+
+    let by_tool = usage_sample(&codex, Some(&fallback), StatsGroupBy::Tool);
+    assert_eq!(by_tool.group, "claude-code");
+    assert_eq!(by_tool.exit_code, Some(0));
+    assert_eq!(by_tool.duration_secs, Some(30));
+    assert_eq!(by_tool.failover_events, 1);
+    assert_eq!((by_tool.input_tokens, by_tool.total_tokens), (1_000, 1_200));
+
+    assert_eq!(
+        usage_sample(&codex, None, StatsGroupBy::Tool).group,
+        "codex"
+    );
+    assert_eq!(
+        usage_sample(&codex, None, StatsGroupBy::Tier).group,
+        "tier-2"
+    );
+    assert_eq!(usage_sample(&codex, None, StatsGroupBy::Model).group, "-");
+    assert_eq!(
+        usage_sample(&codex, None, StatsGroupBy::Project).group,
+        "/work/app"
+    );
+}
+
+#[test]
+fn report_aggregates_groups_and_total() {
+    let codex = session("codex", None, Some((1_000, 200, 0.5)));
+    let gemini = session("gemini-cli", None, None);
+    let samples = [
+        usage_sample(&codex, Some(&result("codex", 0, 10)), StatsGroupBy::Tool),
+        usage_sample(&codex, Some(&result("codex", 1, 30)), StatsGroupBy::Tool),
+        usage_sample(&gemini, None, StatsGroupBy::Tool),
+    ];
+
+    let report = build_report("7d".to_string(), StatsGroupBy::Tool, &samples);
+    assert_eq!(report.total.sessions, 3);
+    assert_eq!(report.total.total_tokens, 2_400);
+    assert_eq!(report.total.estimated_cost_usd, 1.0);
+    assert_eq!(report.groups.len(), 2);
+    let codex_bucket = &report.groups[0];
+    assert_eq!(codex_bucket.key, "codex");
+    assert_eq!(codex_bucket.exit_codes["0"], 1);
+    assert_eq!(codex_bucket.exit_codes["1"], 1);
+    assert_eq!(codex_bucket.avg_duration_secs, Some(20.0));
+    assert_eq!(report.groups[1].exit_codes[NO_RESULT], 1);
+    assert_eq!(report.groups[1].avg_duration_secs, None);
+
+    let rendered = render_report(&report);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], "Sessions created in the last 7d, by tool");
+    assert!(lines[2].starts_with("TOOL"));
+    assert!(lines[3].starts_with("codex"));
+    assert!(lines[3].contains("0:1 1:1"));
+    assert!(lines[3].contains("$1.00"));
+    assert!(lines[5].starts_with("total"));
+
+    let empty = build_report("30d".to_string(), StatsGroupBy::Tier, &[]);
+    assert_eq!(render_report(&empty), "No sessions in the last 30d.\n");
+}
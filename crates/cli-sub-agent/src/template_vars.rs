@@ -0,0 +1,68 @@
+//! `${VAR}` template variables shared by workflow plans and saved prompts.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, bail};
+
+/// Parse one `--var KEY=VALUE` argument.
+pub(crate) fn parse_var_assignment(entry: &str) -> Result<(String, String)> {
+    let (key, value) = entry
+        .split_once('=')
+        .with_context(|| format!("Invalid --var format '{entry}': expected KEY=VALUE"))?;
+    validate_variable_name(key)?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parse a list of `--var KEY=VALUE` arguments; later entries win.
+pub(crate) fn parse_var_assignments(entries: &[String]) -> Result<HashMap<String, String>> {
+    entries
+        .iter()
+        .map(|entry| parse_var_assignment(entry))
+        .collect()
+}
+
+/// Validate variable name format (`[A-Za-z_][A-Za-z0-9_]*`).
+pub(crate) fn validate_variable_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        bail!("Invalid variable name '': must match [A-Za-z_][A-Za-z0-9_]*");
+    };
+
+    if !(first == '_' || first.is_ascii_alphabetic()) {
+        bail!("Invalid variable name '{name}': must match [A-Za-z_][A-Za-z0-9_]*");
+    }
+
+    if chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric()) {
+        Ok(())
+    } else {
+        bail!("Invalid variable name '{name}': must match [A-Za-z_][A-Za-z0-9_]*");
+    }
+}
+
+/// Substitute `${VAR}` placeholders in a template string.
+pub(crate) fn substitute_vars(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut result = template.to_string();
+    for (key, value) in vars {
+        let placeholder = format!("${{{key}}}");
+        result = result.replace(&placeholder, value);
+    }
+    result
+}
+
+/// Names of `${VAR}` placeholders left in `text`, in order of first use.
+pub(crate) fn unresolved_placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        if validate_variable_name(name).is_ok() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    names
+}
@@ -0,0 +1,23 @@
+//! Review finding fixture shared by the review and export tests.
+
+use csa_session::{Finding, Severity};
+
+/// A `reviewer` finding summarised as `"<fid> summary"`. Tests that depend on
+/// the summary override it with struct update syntax.
+pub(crate) fn finding(
+    severity: Severity,
+    fid: &str,
+    file: &str,
+    line: Option<u32>,
+    rule_id: &str,
+) -> Finding {
+    Finding {
+        severity,
+        fid: fid.to_string(),
+        file: file.to_string(),
+        line,
+        rule_id: rule_id.to_string(),
+        summary: format!("{fid} summary"),
+        engine: "reviewer".to_string(),
+    }
+}
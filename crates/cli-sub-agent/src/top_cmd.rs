@@ -0,0 +1,413 @@
+//! `csa top`: live resource dashboard for running sessions.
+//!
+//! Each refresh lists `Active` sessions that still own a live process (daemon
+//! leader from `daemon.pid` or an inline tool holding a session lock), samples
+//! the process group's RSS and the process tree's CPU time the same way the
+//! memory monitor and liveness watchdog do, and shows global slot occupancy
+//! per tool. Interactive mode runs full-screen in raw mode; `--once`, or a
+//! stdout/stdin that is not a terminal, prints a single snapshot.
+
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use csa_config::GlobalConfig;
+use csa_lock::slot::{SlotStatus, slot_usage};
+use csa_process::ToolLiveness;
+use csa_session::{MetaSessionState, SessionPhase};
+use ratatui::widgets::Paragraph;
+
+use crate::cli::TopArgs;
+use crate::session_cmds::{format_compact_duration, format_file_size, session_created_at};
+use crate::tui_terminal::{Key, RawTerminal};
+
+#[path = "top_cmd_ps.rs"]
+mod ps;
+pub(crate) use ps::{handle_kill, handle_ps};
+
+/// Session files whose mtime marks the most recent observable tool activity.
+const ACTIVITY_FILES: &[&str] = &[
+    "stdout.log",
+    "output.log",
+    "stderr.log",
+    "output/acp-events.jsonl",
+];
+
+/// Delay between the two samples of a one-shot snapshot so CPU% has a baseline.
+const ONE_SHOT_CPU_WINDOW: Duration = Duration::from_millis(500);
+
+/// Width of the session ID column; enough to disambiguate ULID prefixes.
+const SESSION_ID_WIDTH: usize = 12;
+
+#[derive(Debug, Clone)]
+struct SessionRow {
+    session_id: String,
+    tool: String,
+    pid: u32,
+    rss_bytes: Option<u64>,
+    cpu_percent: Option<f64>,
+    elapsed: chrono::Duration,
+    idle: Option<chrono::Duration>,
+    paused: bool,
+    description: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct TopSnapshot {
+    sessions: Vec<SessionRow>,
+    slots: Vec<SlotStatus>,
+}
+
+/// Process-tree CPU ticks from the previous refresh, keyed by root PID.
+#[derive(Debug, Default)]
+struct CpuSampler {
+    previous: HashMap<u32, (u64, Instant)>,
+}
+
+impl CpuSampler {
+    fn sample(&mut self, pid: u32, now: Instant) -> Option<f64> {
+        // Only Linux reports real tick counts; elsewhere the tree probe is a
+        // liveness check that always yields zero.
+        if !cfg!(target_os = "linux") {
+            return None;
+        }
+        let ticks = csa_process::process_tree_cpu_ticks(pid)?;
+        let (previous_ticks, previous_at) = self.previous.insert(pid, (ticks, now))?;
+        let wall_secs = now.duration_since(previous_at).as_secs_f64();
+        cpu_percent(
+            ticks.saturating_sub(previous_ticks),
+            wall_secs,
+            clock_ticks_per_second(),
+        )
+    }
+
+    fn retain(&mut self, live_pids: &HashSet<u32>) {
+        self.previous.retain(|pid, _| live_pids.contains(pid));
+    }
+}
+
+/// Interactive state that survives refreshes.
+#[derive(Debug, Default)]
+struct TopApp {
+    selected: usize,
+    /// PIDs this dashboard already sent SIGTERM; a second kill escalates.
+    terminated: HashSet<u32>,
+    /// PIDs this dashboard stopped, for platforms without `/proc` state.
+    paused: HashSet<u32>,
+    status: Option<String>,
+}
+
+pub(crate) fn handle_top(args: TopArgs) -> Result<()> {
+    let project_root = if args.all_projects {
+        None
+    } else {
+        Some(crate::pipeline::determine_project_root(args.cd.as_deref())?)
+    };
+    let mut sampler = CpuSampler::default();
+    let interactive =
+        !args.once && std::io::stdout().is_terminal() && std::io::stdin().is_terminal();
+
+    if !interactive {
+        collect_snapshot(project_root.as_deref(), &mut sampler, &HashSet::new())?;
+        std::thread::sleep(ONE_SHOT_CPU_WINDOW);
+        let snapshot = collect_snapshot(project_root.as_deref(), &mut sampler, &HashSet::new())?;
+        print!("{}", render(&snapshot, None, None));
+        return Ok(());
+    }
+
+    run_interactive(
+        project_root.as_deref(),
+        Duration::from_secs(args.interval),
+        &mut sampler,
+    )
+}
+
+fn run_interactive(
+    project_root: Option<&Path>,
+    interval: Duration,
+    sampler: &mut CpuSampler,
+) -> Result<()> {
+    let mut terminal = RawTerminal::enter()?;
+    let mut app = TopApp::default();
+    let mut snapshot = collect_snapshot(project_root, sampler, &app.paused)?;
+    let mut next_refresh = Instant::now() + interval;
+
+    loop {
+        app.selected = app.selected.min(snapshot.sessions.len().saturating_sub(1));
+        let text = render(&snapshot, Some(app.selected), app.status.as_deref());
+        terminal.draw(|frame| frame.render_widget(Paragraph::new(text), frame.area()))?;
+
+        let timeout = next_refresh.saturating_duration_since(Instant::now());
+        let mut refresh_now = false;
+        match terminal.read_key(timeout)? {
+            Some(key) if key.is_quit() => break,
+            Some(key) if key.is_up() => app.selected = app.selected.saturating_sub(1),
+            Some(key) if key.is_down() => app.selected = app.selected.saturating_add(1),
+            Some(Key::Char('x' | 'X')) => {
+                if let Some(row) = snapshot.sessions.get(app.selected) {
+                    app.status = Some(kill_session(row, &mut app.terminated));
+                    refresh_now = true;
+                }
+            }
+            Some(Key::Char('p' | 'P')) => {
+                if let Some(row) = snapshot.sessions.get(app.selected) {
+                    app.status = Some(toggle_pause(row, &mut app.paused));
+                    refresh_now = true;
+                }
+            }
+            Some(_) | None => {}
+        }
+
+        if refresh_now || Instant::now() >= next_refresh {
+            snapshot = collect_snapshot(project_root, sampler, &app.paused)?;
+            next_refresh = Instant::now() + interval;
+        }
+    }
+    Ok(())
+}
+
+fn collect_snapshot(
+    project_root: Option<&Path>,
+    sampler: &mut CpuSampler,
+    paused: &HashSet<u32>,
+) -> Result<TopSnapshot> {
+    let sessions = match project_root {
+        Some(root) => csa_session::list_sessions_readonly(root, None)?,
+        None => csa_session::list_all_sessions_all_projects()?,
+    };
+
+    let now = Utc::now();
+    let sampled_at = Instant::now();
+    let mut rows = Vec::new();
+    for session in sessions
+        .iter()
+        .filter(|session| session.phase == SessionPhase::Active)
+    {
+        let Ok(session_dir) = csa_session::get_session_dir(
+            Path::new(&session.project_path),
+            &session.meta_session_id,
+        ) else {
+            continue;
+        };
+        let Some(pid) = ToolLiveness::daemon_pid_for_signal(&session_dir)
+            .or_else(|| ToolLiveness::live_process_pid(&session_dir))
+        else {
+            continue;
+        };
+        rows.push(SessionRow {
+            session_id: session.meta_session_id.clone(),
+            tool: session_tool(session, &session_dir),
+            pid,
+            rss_bytes: csa_resource::process_memory::process_group_rss_bytes(pid as i32),
+            cpu_percent: sampler.sample(pid, sampled_at),
+            elapsed: now - session_created_at(session),
+            idle: last_activity(&session_dir).map(|at| now - at),
+            paused: process_is_stopped(pid).unwrap_or_else(|| paused.contains(&pid)),
+            description: session.description.clone(),
+        });
+    }
+    rows.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+    sampler.retain(&rows.iter().map(|row| row.pid).collect());
+
+    Ok(TopSnapshot {
+        sessions: rows,
+        slots: current_slot_usage(),
+    })
+}
+
+fn current_slot_usage() -> Vec<SlotStatus> {
+    let (Ok(config), Ok(slots_dir)) = (GlobalConfig::load(), GlobalConfig::slots_dir()) else {
+        return Vec::new();
+    };
+    slot_usage(&slots_dir, &config.all_tool_slots())
+}
+
+/// Tool currently running in the session: the holder of a lock in `locks/`,
+/// falling back to the most recently updated tool state.
+fn session_tool(session: &MetaSessionState, session_dir: &Path) -> String {
+    let lock_tool = std::fs::read_dir(session_dir.join("locks"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lock"))
+        .find_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            (!stem.starts_with('.')).then(|| stem.to_string())
+        });
+    lock_tool
+        .or_else(|| {
+            session
+                .tools
+                .iter()
+                .max_by_key(|(_, state)| state.updated_at)
+                .map(|(tool, _)| tool.clone())
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn last_activity(session_dir: &Path) -> Option<DateTime<Utc>> {
+    ACTIVITY_FILES
+        .iter()
+        .filter_map(|name| std::fs::metadata(session_dir.join(name)).ok())
+        .filter_map(|metadata| metadata.modified().ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+}
+
+fn render(snapshot: &TopSnapshot, selected: Option<usize>, status: Option<&str>) -> String {
+    let mut lines = Vec::new();
+    let count = snapshot.sessions.len();
+    let noun = if count == 1 { "session" } else { "sessions" };
+    if selected.is_some() {
+        lines.push(format!(
+            "csa top - {count} live {noun}   [up/down] select  [x] kill  [p] pause/resume  [q] quit"
+        ));
+    } else {
+        lines.push(format!("csa top - {count} live {noun}"));
+    }
+
+    let slots = snapshot
+        .slots
+        .iter()
+        .map(|slot| format!("{} {}/{}", slot.tool_name, slot.occupied, slot.max_slots))
+        .collect::<Vec<_>>();
+    lines.push(if slots.is_empty() {
+        "slots: unavailable".to_string()
+    } else {
+        format!("slots: {}", slots.join("  "))
+    });
+    lines.push(String::new());
+    lines.push(format!(
+        "  {:<SESSION_ID_WIDTH$}  {:<12}  {:>7}  {:>9}  {:>6}  {:>7}  {:>7}  {:<7}  DESCRIPTION",
+        "SESSION", "TOOL", "PID", "RSS", "CPU%", "ELAPSED", "IDLE", "STATE"
+    ));
+
+    for (index, row) in snapshot.sessions.iter().enumerate() {
+        let marker = if selected == Some(index) { '>' } else { ' ' };
+        let session_id: String = row.session_id.chars().take(SESSION_ID_WIDTH).collect();
+        lines.push(format!(
+            "{marker} {session_id:<SESSION_ID_WIDTH$}  {:<12}  {:>7}  {:>9}  {:>6}  {:>7}  {:>7}  {:<7}  {}",
+            row.tool,
+            row.pid,
+            row.rss_bytes
+                .map(format_file_size)
+                .unwrap_or_else(|| "-".to_string()),
+            row.cpu_percent
+                .map(|percent| format!("{percent:.1}"))
+                .unwrap_or_else(|| "-".to_string()),
+            format_compact_duration(row.elapsed),
+            row.idle
+                .map(format_compact_duration)
+                .unwrap_or_else(|| "-".to_string()),
+            if row.paused { "paused" } else { "running" },
+            row.description.as_deref().unwrap_or(""),
+        ));
+    }
+    if snapshot.sessions.is_empty() {
+        lines.push("  (no running sessions)".to_string());
+    }
+    if let Some(status) = status {
+        lines.push(String::new());
+        lines.push(status.to_string());
+    }
+
+    let mut frame = lines.join("\n");
+    frame.push('\n');
+    frame
+}
+
+/// SIGTERM the session's process group; a second request escalates to SIGKILL.
+fn kill_session(row: &SessionRow, terminated: &mut HashSet<u32>) -> String {
+    let escalate = terminated.contains(&row.pid);
+    let (signal, name) = if escalate {
+        (libc::SIGKILL, "SIGKILL")
+    } else {
+        (libc::SIGTERM, "SIGTERM")
+    };
+    match signal_process_group(row.pid, signal) {
+        Ok(()) => {
+            terminated.insert(row.pid);
+            // A stopped group cannot act on SIGTERM until it is resumed.
+            let _ = signal_process_group(row.pid, libc::SIGCONT);
+            format!(
+                "Sent {name} to session {} (PID {}){}",
+                row.session_id,
+                row.pid,
+                if escalate {
+                    ""
+                } else {
+                    "; press x again to SIGKILL"
+                }
+            )
+        }
+        Err(err) => format!("Failed to signal session {}: {err}", row.session_id),
+    }
+}
+
+/// SIGSTOP or SIGCONT the session's process group depending on its state.
+fn toggle_pause(row: &SessionRow, paused: &mut HashSet<u32>) -> String {
+    let (signal, verb) = if row.paused {
+        (libc::SIGCONT, "Resumed")
+    } else {
+        (libc::SIGSTOP, "Paused")
+    };
+    match signal_process_group(row.pid, signal) {
+        Ok(()) => {
+            if row.paused {
+                paused.remove(&row.pid);
+            } else {
+                paused.insert(row.pid);
+            }
+            format!("{verb} session {} (PID {})", row.session_id, row.pid)
+        }
+        Err(err) => format!("Failed to signal session {}: {err}", row.session_id),
+    }
+}
+
+fn signal_process_group(pid: u32, signal: libc::c_int) -> std::io::Result<()> {
+    // SAFETY: getpgrp has no preconditions.
+    let own_group = u32::try_from(unsafe { libc::getpgrp() }).unwrap_or(0);
+    if pid <= 1 || pid == own_group {
+        return Err(std::io::Error::other(format!(
+            "refusing to signal process group {pid}"
+        )));
+    }
+    // SAFETY: kill(-pid, sig) signals the session's process group; session
+    // tools are spawned with setsid so the PID is also the group ID.
+    let rc = unsafe { libc::kill(-(pid as libc::pid_t), signal) };
+    if rc == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_is_stopped(pid: u32) -> Option<bool> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let state = stat.rsplit_once(") ")?.1.chars().next()?;
+    Some(matches!(state, 'T' | 't'))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_is_stopped(_pid: u32) -> Option<bool> {
+    None
+}
+
+fn clock_ticks_per_second() -> f64 {
+    // SAFETY: sysconf has no preconditions.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 { ticks as f64 } else { 100.0 }
+}
+
+fn cpu_percent(delta_ticks: u64, wall_secs: f64, ticks_per_second: f64) -> Option<f64> {
+    (wall_secs > 0.0).then(|| delta_ticks as f64 / ticks_per_second / wall_secs * 100.0)
+}
+
+#[cfg(test)]
+#[path = "top_cmd_tests.rs"]
+mod tests;
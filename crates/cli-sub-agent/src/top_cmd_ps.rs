@@ -0,0 +1,196 @@
+//! `csa ps` and `csa kill`: one-shot listing and termination of running
+//! sessions.
+//!
+//! A session is running when its daemon leader or lock-holding tool process
+//! is alive, or when a process still holds a global tool slot for it. Slot
+//! holders are found through their `flock`, which the kernel drops on exit,
+//! so they catch sessions whose state is not (yet) readable.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use csa_config::GlobalConfig;
+use csa_core::types::OutputFormat;
+use csa_lock::slot::{HeldSlot, held_slots};
+use csa_process::ToolLiveness;
+use csa_session::{MetaSessionState, SessionPhase};
+use serde::Serialize;
+
+use super::{SESSION_ID_WIDTH, session_tool};
+use crate::cli::{KillArgs, PsArgs};
+use crate::session_cmds::{format_compact_duration, session_created_at};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProcessSource {
+    /// Detached daemon leader from `daemon.pid`.
+    Daemon,
+    /// Inline tool process holding a session lock.
+    Inline,
+    /// Holder of a global tool slot with no live session process found.
+    Slot,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PsRow {
+    session_id: Option<String>,
+    tool: String,
+    pid: u32,
+    source: ProcessSource,
+    started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Owner in a shared state directory, when recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+}
+
+pub(crate) fn handle_ps(args: PsArgs, format: OutputFormat) -> Result<()> {
+    let (sessions, project_scoped) = if args.all_projects {
+        (csa_session::list_all_sessions_all_projects()?, false)
+    } else {
+        let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+        (
+            csa_session::list_sessions_readonly(&project_root, None)?,
+            true,
+        )
+    };
+    let slots = GlobalConfig::slots_dir()
+        .map(|dir| held_slots(&dir))
+        .unwrap_or_default();
+    let rows = collect_rows(&sessions, &slots, project_scoped, |session| {
+        let session_dir = csa_session::get_session_dir(
+            Path::new(&session.project_path),
+            &session.meta_session_id,
+        )
+        .ok()?;
+        let (pid, source) = match ToolLiveness::daemon_pid_for_signal(&session_dir) {
+            Some(pid) => (pid, ProcessSource::Daemon),
+            None => (
+                ToolLiveness::live_process_pid(&session_dir)?,
+                ProcessSource::Inline,
+            ),
+        };
+        Some((pid, source, session_tool(session, &session_dir)))
+    });
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+        OutputFormat::Text => print!("{}", render_rows(&rows, !project_scoped, Utc::now())),
+    }
+    Ok(())
+}
+
+pub(crate) fn handle_kill(args: KillArgs) -> Result<()> {
+    let session = args
+        .session_id
+        .or(args.session)
+        .ok_or_else(|| anyhow::anyhow!("Missing session ID"))?;
+    crate::session_cmds::kill_session(session, args.cd, Duration::from_secs(args.grace))
+}
+
+/// Live Active sessions first, then slot holders not already listed. With
+/// `project_scoped`, slots are only listed for sessions of that project.
+fn collect_rows(
+    sessions: &[MetaSessionState],
+    slots: &[HeldSlot],
+    project_scoped: bool,
+    mut live_process: impl FnMut(&MetaSessionState) -> Option<(u32, ProcessSource, String)>,
+) -> Vec<PsRow> {
+    let mut rows = Vec::new();
+    for session in sessions
+        .iter()
+        .filter(|session| session.phase == SessionPhase::Active)
+    {
+        if let Some((pid, source, tool)) = live_process(session) {
+            rows.push(PsRow {
+                session_id: Some(session.meta_session_id.clone()),
+                tool,
+                pid,
+                source,
+                started_at: session_created_at(session),
+                project: Some(session.project_path.clone()),
+                description: session.description.clone(),
+                user: session.user.clone(),
+            });
+        }
+    }
+    rows.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+
+    let listed: HashSet<Option<String>> = rows.iter().map(|row| row.session_id.clone()).collect();
+    for slot in slots {
+        if slot.session_id.is_some() && listed.contains(&slot.session_id) {
+            continue;
+        }
+        let session = slot.session_id.as_deref().and_then(|id| {
+            sessions
+                .iter()
+                .find(|session| session.meta_session_id == id)
+        });
+        if project_scoped && session.is_none() {
+            continue;
+        }
+        rows.push(PsRow {
+            session_id: slot.session_id.clone(),
+            tool: slot.tool_name.clone(),
+            pid: slot.pid,
+            source: ProcessSource::Slot,
+            started_at: slot.acquired_at,
+            project: session.map(|session| session.project_path.clone()),
+            description: session.and_then(|session| session.description.clone()),
+            user: slot.user.clone(),
+        });
+    }
+    rows
+}
+
+fn render_rows(rows: &[PsRow], show_project: bool, now: DateTime<Utc>) -> String {
+    if rows.is_empty() {
+        return "No running sessions.\n".to_string();
+    }
+    let mut out = format!(
+        "{:<SESSION_ID_WIDTH$}  {:<12} {:>8}  {:<7} {:>8}  {}\n",
+        "SESSION",
+        "TOOL",
+        "PID",
+        "SOURCE",
+        "ELAPSED",
+        if show_project {
+            "PROJECT"
+        } else {
+            "DESCRIPTION"
+        }
+    );
+    for row in rows {
+        let session_id = row.session_id.as_deref().unwrap_or("-");
+        let source = match row.source {
+            ProcessSource::Daemon => "daemon",
+            ProcessSource::Inline => "inline",
+            ProcessSource::Slot => "slot",
+        };
+        let last = if show_project {
+            row.project.as_deref()
+        } else {
+            row.description.as_deref()
+        };
+        out.push_str(&format!(
+            "{:<SESSION_ID_WIDTH$}  {:<12} {:>8}  {:<7} {:>8}  {}\n",
+            &session_id[..session_id.len().min(SESSION_ID_WIDTH)],
+            row.tool,
+            row.pid,
+            source,
+            format_compact_duration(now - row.started_at),
+            last.unwrap_or("-")
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "top_cmd_ps_tests.rs"]
+mod tests;
@@ -0,0 +1,89 @@
+use super::*;
+
+fn session(id: &str, phase: SessionPhase) -> MetaSessionState {
+    MetaSessionState {
+        meta_session_id: id.to_string(),
+        project_path: "/work/project".to_string(),
+        description: Some(format!("task {id}")),
+        phase,
+        ..Default::default()
+    }
+}
+
+fn slot(tool: &str, pid: u32, session_id: Option<&str>) -> HeldSlot {
+    HeldSlot {
+        tool_name: tool.to_string(),
+        slot_index: 0,
+        pid,
+        session_id: session_id.map(str::to_string),
+        acquired_at: Utc::now(),
+        user: None,
+    }
+}
+
+#[test]
+fn collect_rows_merges_live_sessions_and_unlisted_slot_holders() {
+    let sessions = vec![
+        session("01JLIVE", SessionPhase::Active),
+        session("01JSTARTING", SessionPhase::Active),
+        session("01JRETIRED", SessionPhase::Retired),
+    ];
+    let slots = vec![
+        slot("codex", 100, Some("01JLIVE")),
+        slot("codex", 200, Some("01JSTARTING")),
+        slot("gemini-cli", 300, None),
+        slot("opencode", 400, Some("01JOTHERPROJECT")),
+    ];
+    let live = |session: &MetaSessionState| {
+        (session.meta_session_id == "01JLIVE")
+            .then(|| (100, ProcessSource::Daemon, "codex".to_string()))
+    };
+
+    let rows = collect_rows(&sessions, &slots, true, live);
+    let listed: Vec<_> = rows
+        .iter()
+        .map(|row| (row.session_id.as_deref(), row.pid, row.source.clone()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            (Some("01JLIVE"), 100, ProcessSource::Daemon),
+            (Some("01JSTARTING"), 200, ProcessSource::Slot),
+        ]
+    );
+    assert_eq!(rows[1].description.as_deref(), Some("task 01JSTARTING"));
+
+    let rows = collect_rows(&sessions, &slots, false, live);
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[2].session_id, None);
+    assert_eq!(rows[3].project, None);
+}
+
+#[test]
+fn render_rows_shows_table_or_empty_notice() {
+    let now = Utc::now();
+    assert_eq!(render_rows(&[], false, now), "No running sessions.\n");
+
+    let rows = vec![PsRow {
+        session_id: Some("01JABCDEFGHJKMNPQRSTVWXYZ0".to_string()),
+        tool: "codex".to_string(),
+        pid: 4242,
+        source: ProcessSource::Inline,
+        started_at: now - chrono::Duration::seconds(3_720),
+        project: Some("/work/project".to_string()),
+        description: Some("fix flaky test".to_string()),
+        user: None,
+    }];
+    let table = render_rows(&rows, false, now);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("SESSION"));
+    assert!(lines[0].ends_with("DESCRIPTION"));
+    assert!(lines[1].starts_with("01JABCDEFGHJ  codex"));
+    assert!(lines[1].contains("4242"));
+    assert!(lines[1].contains("inline"));
+    assert!(lines[1].contains("1h2m"));
+    assert!(lines[1].ends_with("fix flaky test"));
+
+    let table = render_rows(&rows, true, now);
+    assert!(table.lines().nth(1).unwrap().ends_with("/work/project"));
+}
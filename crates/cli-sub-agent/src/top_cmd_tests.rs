@@ -0,0 +1,88 @@
+use super::*;
+
+fn row(session_id: &str, paused: bool) -> SessionRow {
+    SessionRow {
+        session_id: session_id.to_string(),
+        tool: "codex".to_string(),
+        pid: 4242,
+        rss_bytes: Some(512 * 1024 * 1024),
+        cpu_percent: Some(12.5),
+        elapsed: chrono::Duration::seconds(3_720),
+        idle: Some(chrono::Duration::seconds(5)),
+        paused,
+        description: Some("fix flaky test".to_string()),
+    }
+}
+
+#[test]
+fn render_lists_sessions_slots_and_selection() {
+    let snapshot = TopSnapshot {
+        sessions: vec![
+            row("01JABCDEFGHJKMNPQRSTVWXYZ0", false),
+            row("01JZZZZZZZZZZZZZZZZZZZZZZ1", true),
+        ],
+        slots: vec![SlotStatus {
+            tool_name: "codex".to_string(),
+            max_slots: 3,
+            occupied: 2,
+        }],
+    };
+
+    let frame = render(&snapshot, Some(1), Some("Paused session"));
+    let lines: Vec<&str> = frame.lines().collect();
+    assert!(lines[0].starts_with("csa top - 2 live sessions"));
+    assert_eq!(lines[1], "slots: codex 2/3");
+    assert!(lines[4].starts_with("  01JABCDEFGHJ  codex"));
+    assert!(lines[4].contains("512.0 MB"));
+    assert!(lines[4].contains("12.5"));
+    assert!(lines[4].contains("1h2m"));
+    assert!(lines[4].contains("running"));
+    assert!(lines[5].starts_with("> 01JZZZZZZZZZ"));
+    assert!(lines[5].contains("paused"));
+    assert_eq!(lines.last(), Some(&"Paused session"));
+}
+
+#[test]
+fn render_reports_empty_state() {
+    let frame = render(&TopSnapshot::default(), None, None);
+    assert!(frame.contains("slots: unavailable"));
+    assert!(frame.contains("(no running"));
+    assert!(!frame.contains("[q] quit"));
+}
+
+#[test]
+fn cpu_percent_scales_ticks_by_wall_time() {
+    assert_eq!(cpu_percent(50, 1.0, 100.0), Some(50.0));
+    assert_eq!(cpu_percent(400, 2.0, 100.0), Some(200.0));
+    assert_eq!(cpu_percent(10, 0.0, 100.0), None);
+}
+
+#[test]
+fn session_tool_prefers_lock_holder_over_tool_state() {
+    let tmp = tempfile::tempdir().unwrap();
+    let session = MetaSessionState::default();
+    assert_eq!(session_tool(&session, tmp.path()), "-");
+
+    std::fs::create_dir_all(tmp.path().join("locks")).unwrap();
+    std::fs::write(tmp.path().join("locks/.wait.lock"), "").unwrap();
+    std::fs::write(tmp.path().join("locks/claude-code.lock"), "{}").unwrap();
+    assert_eq!(session_tool(&session, tmp.path()), "claude-code");
+}
+
+#[test]
+fn last_activity_uses_newest_log_mtime() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(last_activity(tmp.path()).is_none());
+
+    std::fs::write(tmp.path().join("stderr.log"), "warn").unwrap();
+    let activity = last_activity(tmp.path()).expect("stderr.log mtime");
+    assert!(Utc::now() - activity < chrono::Duration::seconds(60));
+}
+
+#[test]
+fn signal_process_group_refuses_init_and_own_group() {
+    assert!(signal_process_group(1, 0).is_err());
+    // SAFETY: getpgrp has no preconditions.
+    let own_group = unsafe { libc::getpgrp() } as u32;
+    assert!(signal_process_group(own_group, 0).is_err());
+}
@@ -0,0 +1,121 @@
+//! Full-screen terminal for the interactive commands (`csa top`, `csa ui`):
+//! a ratatui terminal on the crossterm backend in raw mode on the alternate
+//! screen. The previous terminal state is restored on drop, including on early
+//! returns through `?`.
+
+use std::io::Stdout;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::{Frame, Terminal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Key {
+    Up,
+    Down,
+    Tab,
+    Enter,
+    Esc,
+    Backspace,
+    /// Ctrl-C, which raw mode delivers as a key instead of a signal.
+    Interrupt,
+    Char(char),
+    Other,
+}
+
+impl Key {
+    /// `q`, Esc or Ctrl-C.
+    pub(crate) fn is_quit(self) -> bool {
+        matches!(self, Key::Char('q' | 'Q') | Key::Esc | Key::Interrupt)
+    }
+
+    /// Up arrow or `k`.
+    pub(crate) fn is_up(self) -> bool {
+        matches!(self, Key::Up | Key::Char('k'))
+    }
+
+    /// Down arrow or `j`.
+    pub(crate) fn is_down(self) -> bool {
+        matches!(self, Key::Down | Key::Char('j'))
+    }
+}
+
+pub(crate) struct RawTerminal {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl RawTerminal {
+    /// Enable raw mode, enter the alternate screen and hide the cursor.
+    pub(crate) fn enter() -> Result<Self> {
+        crossterm::terminal::enable_raw_mode().context("Failed to enable raw mode")?;
+        // Constructed before anything else can fail so `Drop` undoes raw mode.
+        let mut terminal = Self {
+            terminal: Terminal::new(CrosstermBackend::new(std::io::stdout()))
+                .context("Failed to open terminal")?,
+        };
+        crossterm::execute!(terminal.terminal.backend_mut(), EnterAlternateScreen)
+            .context("Failed to enter the alternate screen")?;
+        terminal
+            .terminal
+            .hide_cursor()
+            .context("Failed to hide cursor")?;
+        Ok(terminal)
+    }
+
+    /// Terminal height in rows, when the terminal reports one.
+    pub(crate) fn height(&self) -> Option<usize> {
+        self.terminal
+            .size()
+            .ok()
+            .map(|size| usize::from(size.height))
+            .filter(|rows| *rows > 0)
+    }
+
+    /// Redraw the screen with `render`.
+    pub(crate) fn draw(&mut self, render: impl FnOnce(&mut Frame)) -> Result<()> {
+        self.terminal
+            .draw(render)
+            .context("Failed to draw to terminal")?;
+        Ok(())
+    }
+
+    /// Wait up to `timeout` for a key press.
+    pub(crate) fn read_key(&self, timeout: Duration) -> Result<Option<Key>> {
+        if !event::poll(timeout).context("Failed to poll terminal input")? {
+            return Ok(None);
+        }
+        match event::read().context("Failed to read terminal input")? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => Ok(Some(key_from_event(key))),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = self.terminal.show_cursor();
+        let _ = crossterm::execute!(self.terminal.backend_mut(), LeaveAlternateScreen);
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+pub(crate) fn key_from_event(key: KeyEvent) -> Key {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Interrupt,
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => Key::Char(c),
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Backspace => Key::Backspace,
+        _ => Key::Other,
+    }
+}
+
+#[cfg(test)]
+#[path = "tui_terminal_tests.rs"]
+mod tests;
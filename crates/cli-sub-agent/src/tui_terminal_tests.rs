@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn key_from_event_maps_navigation_and_characters() {
+    let key = |code| key_from_event(KeyEvent::new(code, KeyModifiers::NONE));
+    assert_eq!(key(KeyCode::Up), Key::Up);
+    assert!(key(KeyCode::Char('j')).is_down());
+    assert!(key(KeyCode::Char('k')).is_up());
+    assert_eq!(key(KeyCode::Tab), Key::Tab);
+    assert_eq!(key(KeyCode::Enter), Key::Enter);
+    assert_eq!(key(KeyCode::Char('P')), Key::Char('P'));
+    assert!(key(KeyCode::Char('q')).is_quit());
+    assert!(key(KeyCode::Esc).is_quit());
+    assert_eq!(key(KeyCode::Right), Key::Other);
+
+    let ctrl_c = key_from_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    assert_eq!(ctrl_c, Key::Interrupt);
+    assert!(ctrl_c.is_quit());
+}
@@ -0,0 +1,314 @@
+//! `csa ui`: interactive session browser.
+//!
+//! The left pane lists the project's sessions as a genealogy tree. The right
+//! pane shows either the selected session's detail (metadata, result, output
+//! sections, return packet, review findings) or a live tail of its
+//! `output.log`; Tab switches between them. Actions fork, re-run, pin and
+//! delete the selected session. Everything is read through the csa-session
+//! APIs; fork and re-run spawn `csa run` so they behave exactly like the CLI.
+
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use csa_process::ToolLiveness;
+use csa_session::{FindingsFile, MetaSessionState, RETURN_PACKET_SECTION_ID, SessionPhase};
+
+use crate::cli::UiArgs;
+use crate::tui_terminal::{Key, RawTerminal};
+
+#[path = "ui_cmd_view.rs"]
+mod view;
+use view::{Pane, SessionDetail};
+
+/// Interactive state that survives refreshes.
+#[derive(Debug, Default)]
+struct UiApp {
+    selected: usize,
+    pane: Pane,
+    status: Option<String>,
+    /// Session armed for deletion by a first `d`; a second `d` deletes it.
+    pending_delete: Option<String>,
+    /// Prompt being typed for a fork, shown in place of the status line.
+    fork_prompt: Option<ForkPrompt>,
+}
+
+#[derive(Debug)]
+struct ForkPrompt {
+    session_id: String,
+    text: String,
+}
+
+pub(crate) fn handle_ui(args: UiArgs) -> Result<()> {
+    if !(std::io::stdout().is_terminal() && std::io::stdin().is_terminal()) {
+        bail!("csa ui needs an interactive terminal; use `csa session list --tree` instead");
+    }
+    let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+    let interval = Duration::from_secs(args.interval);
+
+    let mut terminal = RawTerminal::enter()?;
+    let mut app = UiApp::default();
+    let (mut sessions, mut pinned) = load_sessions(&project_root)?;
+    let mut next_refresh = Instant::now() + interval;
+
+    loop {
+        let rows = view::build_tree(&sessions, &pinned);
+        app.selected = app.selected.min(rows.len().saturating_sub(1));
+        let current = rows.get(app.selected).and_then(|row| {
+            sessions
+                .iter()
+                .find(|session| session.meta_session_id == row.session_id)
+        });
+        let height = terminal.height();
+        let right = match current {
+            Some(session) => right_pane(&project_root, session, app.pane, height),
+            None => vec!["(no sessions)".to_string()],
+        };
+        let status = match &app.fork_prompt {
+            Some(fork) => Some(format!(
+                "Fork {} with prompt: {}",
+                fork.session_id, fork.text
+            )),
+            None => app.status.clone(),
+        };
+        terminal.draw(|frame| {
+            view::render_frame(
+                frame,
+                &rows,
+                app.selected,
+                &right,
+                app.pane,
+                status.as_deref(),
+            )
+        })?;
+
+        let timeout = next_refresh.saturating_duration_since(Instant::now());
+        let key = terminal.read_key(timeout)?;
+        if key.is_some() && !matches!(key, Some(Key::Char('d' | 'D'))) {
+            app.pending_delete = None;
+        }
+        let mut refresh_now = false;
+        match key {
+            key if app.fork_prompt.is_some() => {
+                refresh_now = edit_fork_prompt(&project_root, &mut app, key);
+            }
+            Some(key) if key.is_quit() => break,
+            Some(key) if key.is_up() => app.selected = app.selected.saturating_sub(1),
+            Some(key) if key.is_down() => app.selected = app.selected.saturating_add(1),
+            Some(Key::Tab) => {
+                app.pane = match app.pane {
+                    Pane::Detail => Pane::Tail,
+                    Pane::Tail => Pane::Detail,
+                }
+            }
+            Some(Key::Char(action)) => {
+                if let Some(session) = current {
+                    app.status = handle_action(&project_root, session, action, &mut app)?;
+                    refresh_now = true;
+                }
+            }
+            Some(_) | None => {}
+        }
+
+        if refresh_now || Instant::now() >= next_refresh {
+            (sessions, pinned) = load_sessions(&project_root)?;
+            next_refresh = Instant::now() + interval;
+        }
+    }
+    Ok(())
+}
+
+/// The project's sessions plus the IDs of the pinned ones.
+fn load_sessions(project_root: &Path) -> Result<(Vec<MetaSessionState>, HashSet<String>)> {
+    let sessions = csa_session::list_sessions_readonly(project_root, None)?;
+    let pinned = sessions
+        .iter()
+        .filter(|session| {
+            csa_session::get_session_dir(project_root, &session.meta_session_id)
+                .is_ok_and(|dir| csa_session::is_session_pinned(&dir))
+        })
+        .map(|session| session.meta_session_id.clone())
+        .collect();
+    Ok((sessions, pinned))
+}
+
+fn right_pane(
+    project_root: &Path,
+    session: &MetaSessionState,
+    pane: Pane,
+    height: Option<usize>,
+) -> Vec<String> {
+    let session_dir = match csa_session::get_session_dir(project_root, &session.meta_session_id) {
+        Ok(dir) => dir,
+        Err(err) => return vec![format!("Session directory unavailable: {err}")],
+    };
+    match pane {
+        Pane::Detail => {
+            view::detail_lines(session, &load_detail(project_root, session, &session_dir))
+        }
+        Pane::Tail => match std::fs::read_to_string(session_dir.join("output.log")) {
+            Ok(content) => view::tail_lines(&content, height.unwrap_or(30).saturating_sub(3)),
+            Err(_) => vec!["(no output.log yet)".to_string()],
+        },
+    }
+}
+
+/// Best effort: unreadable artifacts are left out of the detail pane.
+fn load_detail(
+    project_root: &Path,
+    session: &MetaSessionState,
+    session_dir: &Path,
+) -> SessionDetail {
+    let return_packet = csa_session::read_section(session_dir, RETURN_PACKET_SECTION_ID)
+        .ok()
+        .flatten()
+        .and_then(|content| csa_session::parse_return_packet(&content).ok());
+    let findings = std::fs::read_to_string(session_dir.join("output").join("findings.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<FindingsFile>(&content).ok())
+        .map(|file| file.findings)
+        .unwrap_or_default();
+    SessionDetail {
+        pinned: csa_session::is_session_pinned(session_dir),
+        live: ToolLiveness::has_live_process(session_dir),
+        result: csa_session::load_result(project_root, &session.meta_session_id)
+            .ok()
+            .flatten(),
+        sections: csa_session::load_output_index(session_dir)
+            .ok()
+            .flatten()
+            .map(|index| index.sections)
+            .unwrap_or_default(),
+        return_packet,
+        findings,
+    }
+}
+
+/// Feed `key` to the fork prompt being typed; returns whether a fork started.
+fn edit_fork_prompt(project_root: &Path, app: &mut UiApp, key: Option<Key>) -> bool {
+    let Some(mut fork) = app.fork_prompt.take() else {
+        return false;
+    };
+    match key {
+        Some(Key::Char(c)) => fork.text.push(c),
+        Some(Key::Backspace) => {
+            fork.text.pop();
+        }
+        Some(Key::Esc | Key::Interrupt) => {
+            app.status = Some("Fork cancelled".to_string());
+            return false;
+        }
+        Some(Key::Enter) => {
+            let prompt = fork.text.trim();
+            if prompt.is_empty() {
+                app.status = Some("Fork cancelled".to_string());
+                return false;
+            }
+            app.status = Some(spawn_csa_run(
+                project_root,
+                &["--fork-from", fork.session_id.as_str(), "--prompt", prompt],
+            ));
+            return true;
+        }
+        Some(_) | None => {}
+    }
+    app.fork_prompt = Some(fork);
+    false
+}
+
+/// Run the action bound to `key` and return the status line to show.
+fn handle_action(
+    project_root: &Path,
+    session: &MetaSessionState,
+    key: char,
+    app: &mut UiApp,
+) -> Result<Option<String>> {
+    let session_id = &session.meta_session_id;
+    let session_dir = csa_session::get_session_dir(project_root, session_id)?;
+    let status = match key.to_ascii_lowercase() {
+        'f' => {
+            app.fork_prompt = Some(ForkPrompt {
+                session_id: session_id.clone(),
+                text: String::new(),
+            });
+            return Ok(app.status.clone());
+        }
+        'r' => {
+            let prompt_file = session_dir.join("input").join("prompt.txt");
+            let Some(tool) = session
+                .tools
+                .iter()
+                .max_by_key(|(_, state)| state.updated_at)
+                .map(|(tool, _)| tool)
+            else {
+                return Ok(Some(format!("Session {session_id} has no recorded tool")));
+            };
+            if !prompt_file.is_file() {
+                return Ok(Some(format!("Session {session_id} has no recorded prompt")));
+            }
+            let prompt_file = prompt_file.to_string_lossy();
+            let mut args = vec!["--tool", tool.as_str(), "--prompt-file", &*prompt_file];
+            // Tier policy rejects `--tool` alone once tiers are configured.
+            if let Some(tier) = &session.task_context.tier_name {
+                args.extend(["--tier", tier.as_str()]);
+            }
+            spawn_csa_run(project_root, &args)
+        }
+        'p' => {
+            let pin = !csa_session::is_session_pinned(&session_dir);
+            csa_session::set_session_pinned(&session_dir, pin)?;
+            format!(
+                "{} session {session_id}",
+                if pin { "Pinned" } else { "Unpinned" }
+            )
+        }
+        'd' => {
+            if session.phase == SessionPhase::Active || ToolLiveness::has_live_process(&session_dir)
+            {
+                format!("Refusing to delete Active or live session {session_id}")
+            } else if app.pending_delete.as_deref() == Some(session_id) {
+                app.pending_delete = None;
+                csa_session::delete_session(project_root, session_id)?;
+                format!("Deleted session {session_id}")
+            } else {
+                app.pending_delete = Some(session_id.clone());
+                format!("Press d again to delete session {session_id}")
+            }
+        }
+        _ => return Ok(app.status.clone()),
+    };
+    Ok(Some(status))
+}
+
+/// Start `csa run` (daemonized) in the project and report its first line.
+fn spawn_csa_run(project_root: &Path, args: &[&str]) -> String {
+    let output = std::env::current_exe()
+        .context("Failed to locate the csa binary")
+        .and_then(|csa| {
+            Command::new(csa)
+                .args(["run", "--sa-mode", "false"])
+                .args(args)
+                .current_dir(project_root)
+                .stdin(Stdio::null())
+                .output()
+                .context("Failed to spawn csa run")
+        });
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .map_or_else(|| "Started csa run".to_string(), str::to_string),
+        Ok(output) => format!(
+            "csa run failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(err) => format!("{err:#}"),
+    }
+}
+
+#[cfg(test)]
+#[path = "ui_cmd_tests.rs"]
+mod tests;
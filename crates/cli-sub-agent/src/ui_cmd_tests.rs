@@ -0,0 +1,103 @@
+use super::view::*;
+use chrono::{Duration, TimeZone, Utc};
+use csa_session::{MetaSessionState, ReturnPacket, ReturnStatus};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use std::collections::HashSet;
+
+fn session(id: &str, parent: Option<&str>, minute: u32) -> MetaSessionState {
+    let created_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, minute, 0).unwrap();
+    let mut session = MetaSessionState {
+        meta_session_id: id.to_string(),
+        description: Some(format!("task {id}")),
+        created_at,
+        last_accessed: created_at + Duration::minutes(1),
+        ..MetaSessionState::default()
+    };
+    session.genealogy.parent_session_id = parent.map(str::to_string);
+    session
+}
+
+#[test]
+fn tree_nests_children_under_parents_and_marks_pins() {
+    let sessions = vec![
+        session("01OLDROOT000", None, 0),
+        session("01CHILD2000", Some("01OLDROOT000"), 3),
+        session("01CHILD1000", Some("01OLDROOT000"), 2),
+        session("01GRANDKID00", Some("01CHILD1000"), 4),
+        session("01NEWROOT000", None, 5),
+        session("01ORPHAN0000", Some("01GONE000000"), 1),
+    ];
+    let pinned = HashSet::from(["01CHILD1000".to_string()]);
+
+    let rows = build_tree(&sessions, &pinned);
+    let ids: Vec<&str> = rows.iter().map(|row| row.session_id.as_str()).collect();
+    assert_eq!(
+        ids,
+        [
+            "01NEWROOT000",
+            "01ORPHAN0000",
+            "01OLDROOT000",
+            "01CHILD1000",
+            "01GRANDKID00",
+            "01CHILD2000",
+        ]
+    );
+    assert!(rows[2].label.starts_with(" 01OLDROOT00 active"));
+    assert!(rows[3].label.starts_with("  *01CHILD1000"));
+    assert!(rows[4].label.starts_with("     01GRANDKID0"));
+    assert!(rows[4].label.ends_with("task 01GRANDKID00"));
+}
+
+#[test]
+fn detail_lists_return_packet_and_pin_state() {
+    let session = session("01ROOT000000", None, 0);
+    let detail = SessionDetail {
+        pinned: true,
+        return_packet: Some(ReturnPacket {
+            status: ReturnStatus::Success,
+            exit_code: 0,
+            summary: "done".to_string(),
+            next_actions: vec!["merge".to_string()],
+            ..ReturnPacket::default()
+        }),
+        ..SessionDetail::default()
+    };
+
+    let lines = detail_lines(&session, &detail);
+    assert_eq!(lines[1], "Phase    active [pinned]");
+    assert!(lines.contains(&"Return packet: Success (exit 0)".to_string()));
+    assert!(lines.contains(&"  next: merge".to_string()));
+}
+
+#[test]
+fn frame_scrolls_to_selection_and_fits_width() {
+    let rows: Vec<TreeRow> = (0..10)
+        .map(|index| TreeRow {
+            session_id: index.to_string(),
+            label: format!("row {index}"),
+        })
+        .collect();
+    let right = tail_lines("a\nb\nc\nd", 2);
+    assert_eq!(right, ["c", "d"]);
+
+    let mut terminal = Terminal::new(TestBackend::new(50, 6)).unwrap();
+    terminal
+        .draw(|frame| render_frame(frame, &rows, 7, &right, Pane::Tail, Some("status")))
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    let lines: Vec<String> = (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    assert!(lines[0].starts_with("csa ui - 10 sessions [tail]"));
+    assert_eq!(lines[1], " row 4              │c");
+    assert_eq!(lines[2], " row 5              │d");
+    assert_eq!(lines[4], ">row 7              │");
+    assert_eq!(lines[5], "status");
+}
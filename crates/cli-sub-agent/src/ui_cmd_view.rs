@@ -0,0 +1,268 @@
+//! Layout for `csa ui`: genealogy tree rows, the detail pane and the ratatui
+//! two-pane frame. Everything here is computed from already-loaded data.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use csa_session::{
+    MetaSessionState, OutputSection, ReturnPacket, ReviewFinding, SessionPhase, SessionResult,
+};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListState, Paragraph};
+
+use crate::session_cmds::{format_compact_duration, session_created_at};
+
+/// Characters of the ULID shown in the tree; enough to disambiguate prefixes.
+const SHORT_ID_WIDTH: usize = 11;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum Pane {
+    #[default]
+    Detail,
+    Tail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TreeRow {
+    pub(super) session_id: String,
+    pub(super) label: String,
+}
+
+/// Everything the detail pane shows for the selected session.
+#[derive(Debug, Default)]
+pub(super) struct SessionDetail {
+    pub(super) pinned: bool,
+    pub(super) live: bool,
+    pub(super) result: Option<SessionResult>,
+    pub(super) sections: Vec<OutputSection>,
+    pub(super) return_packet: Option<ReturnPacket>,
+    pub(super) findings: Vec<ReviewFinding>,
+}
+
+/// Flatten sessions into depth-first genealogy order.
+///
+/// Roots (no parent, or a parent that is no longer on disk) are listed newest
+/// first; children follow their parent oldest first, indented by depth.
+pub(super) fn build_tree(sessions: &[MetaSessionState], pinned: &HashSet<String>) -> Vec<TreeRow> {
+    let known: HashSet<&str> = sessions
+        .iter()
+        .map(|session| session.meta_session_id.as_str())
+        .collect();
+    let mut children: HashMap<&str, Vec<&MetaSessionState>> = HashMap::new();
+    let mut roots = Vec::new();
+    for session in sessions {
+        match session.genealogy.parent_session_id.as_deref() {
+            Some(parent) if known.contains(parent) && parent != session.meta_session_id => {
+                children.entry(parent).or_default().push(session)
+            }
+            _ => roots.push(session),
+        }
+    }
+    roots.sort_by_key(|session| std::cmp::Reverse(session_created_at(session)));
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|session| session_created_at(session));
+    }
+
+    let mut rows = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(&MetaSessionState, usize)> = roots
+        .into_iter()
+        .rev()
+        .map(|session| (session, 0))
+        .collect();
+    while let Some((session, depth)) = stack.pop() {
+        if !visited.insert(session.meta_session_id.as_str()) {
+            continue;
+        }
+        rows.push(TreeRow {
+            session_id: session.meta_session_id.clone(),
+            label: tree_label(session, depth, pinned.contains(&session.meta_session_id)),
+        });
+        if let Some(siblings) = children.get(session.meta_session_id.as_str()) {
+            stack.extend(siblings.iter().rev().map(|child| (*child, depth + 1)));
+        }
+    }
+    rows
+}
+
+fn tree_label(session: &MetaSessionState, depth: usize, pinned: bool) -> String {
+    let short_id: String = session
+        .meta_session_id
+        .chars()
+        .take(SHORT_ID_WIDTH)
+        .collect();
+    let tool = session
+        .tools
+        .iter()
+        .max_by_key(|(_, state)| state.updated_at)
+        .map_or("-", |(tool, _)| tool.as_str());
+    format!(
+        "{}{}{short_id} {:<9} {tool} {}",
+        "  ".repeat(depth),
+        if pinned { '*' } else { ' ' },
+        phase_label(&session.phase),
+        session.description.as_deref().unwrap_or(""),
+    )
+}
+
+fn phase_label(phase: &SessionPhase) -> &'static str {
+    match phase {
+        SessionPhase::Active => "active",
+        SessionPhase::Available => "available",
+        SessionPhase::Retired => "retired",
+        SessionPhase::ToolExhausted => "exhausted",
+    }
+}
+
+/// Lines of the detail pane for `session`.
+pub(super) fn detail_lines(session: &MetaSessionState, detail: &SessionDetail) -> Vec<String> {
+    let mut lines = vec![
+        format!("Session  {}", session.meta_session_id),
+        format!(
+            "Phase    {}{}{}",
+            phase_label(&session.phase),
+            if detail.live { " (live)" } else { "" },
+            if detail.pinned { " [pinned]" } else { "" },
+        ),
+        format!(
+            "Age      {}",
+            format_compact_duration(Utc::now() - session_created_at(session))
+        ),
+        format!("Turns    {}", session.turn_count),
+    ];
+    if let Some(description) = &session.description {
+        lines.push(format!("Desc     {description}"));
+    }
+    if let Some(branch) = &session.branch {
+        lines.push(format!("Branch   {branch}"));
+    }
+    if let Some(parent) = &session.genealogy.parent_session_id {
+        lines.push(format!("Parent   {parent}"));
+    }
+    if let Some(usage) = &session.total_token_usage {
+        lines.push(format!(
+            "Tokens   {} in / {} out{}",
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+            usage
+                .estimated_cost_usd
+                .map(|cost| format!(" (${cost:.2})"))
+                .unwrap_or_default(),
+        ));
+    }
+    if let Some(result) = &detail.result {
+        lines.push(format!(
+            "Result   {} (exit {}, {})",
+            result.status, result.exit_code, result.tool
+        ));
+        if !result.summary.is_empty() {
+            lines.push(format!("         {}", result.summary));
+        }
+    }
+
+    if !detail.sections.is_empty() {
+        lines.push(String::new());
+        lines.push("Sections".to_string());
+        lines.extend(detail.sections.iter().map(|section| {
+            format!(
+                "  {:<20} lines {}-{} (~{} tokens)",
+                section.id, section.line_start, section.line_end, section.token_estimate
+            )
+        }));
+    }
+    if let Some(packet) = &detail.return_packet {
+        lines.push(String::new());
+        lines.push(format!(
+            "Return packet: {:?} (exit {})",
+            packet.status, packet.exit_code
+        ));
+        if !packet.summary.is_empty() {
+            lines.push(format!("  {}", packet.summary));
+        }
+        if !packet.changed_files.is_empty() {
+            lines.push(format!("  {} changed file(s)", packet.changed_files.len()));
+        }
+        lines.extend(
+            packet
+                .next_actions
+                .iter()
+                .map(|action| format!("  next: {action}")),
+        );
+    }
+    if !detail.findings.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Findings ({})", detail.findings.len()));
+        lines.extend(detail.findings.iter().map(|finding| {
+            let location = finding
+                .file_ranges
+                .first()
+                .map(|range| format!(" {}:{}", range.path, range.start))
+                .unwrap_or_default();
+            format!(
+                "  [{:?}]{location} {}",
+                finding.severity, finding.description
+            )
+        }));
+    }
+    lines
+}
+
+/// The last `count` lines of `content`.
+pub(super) fn tail_lines(content: &str, count: usize) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Draw the full-screen frame: tree on the left, `right` on the right.
+pub(super) fn render_frame(
+    frame: &mut Frame,
+    rows: &[TreeRow],
+    selected: usize,
+    right: &[String],
+    pane: Pane,
+    status: Option<&str>,
+) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let left_width = (body.width * 2 / 5).min(60);
+    let [left, right_area] =
+        Layout::horizontal([Constraint::Length(left_width), Constraint::Min(0)]).areas(body);
+
+    frame.render_widget(
+        Paragraph::new(format!(
+            "csa ui - {} sessions [{}]   [up/down] select  [tab] detail/tail  [f] fork  [r] re-run  [p] pin  [d] delete  [q] quit",
+            rows.len(),
+            match pane {
+                Pane::Detail => "detail",
+                Pane::Tail => "tail",
+            }
+        )),
+        header,
+    );
+    let tree = List::new(rows.iter().map(|row| row.label.as_str()))
+        .highlight_symbol(">")
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut tree_state = ListState::default().with_selected((!rows.is_empty()).then_some(selected));
+    frame.render_stateful_widget(tree, left, &mut tree_state);
+    frame.render_widget(
+        Paragraph::new(
+            right
+                .iter()
+                .map(|line| Line::raw(line.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .block(Block::new().borders(Borders::LEFT)),
+        right_area,
+    );
+    frame.render_widget(Paragraph::new(status.unwrap_or("")), footer);
+}
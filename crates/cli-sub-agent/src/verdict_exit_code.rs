@@ -1,24 +1,25 @@
+use csa_core::exit_code::ExitCode;
 use csa_core::types::ReviewDecision;
 
-pub(crate) const INFRASTRUCTURE_FAILURE_EXIT_CODE: i32 = 2;
+pub(crate) const INFRASTRUCTURE_FAILURE_EXIT_CODE: i32 = ExitCode::ToolFailure.code();
 
 pub(crate) fn exit_code_from_review_decision(decision: ReviewDecision) -> i32 {
     match decision {
-        ReviewDecision::Pass => 0,
+        ReviewDecision::Pass => ExitCode::Success.code(),
         ReviewDecision::Fail
         | ReviewDecision::Skip
         | ReviewDecision::Uncertain
-        | ReviewDecision::Unavailable => 1,
+        | ReviewDecision::Unavailable => ExitCode::ReviewFindings.code(),
     }
 }
 
 pub(crate) fn exit_code_from_debate_verdict(verdict: &str, decision: Option<&str>) -> i32 {
     if token_is_success(decision) || token_is_success(Some(verdict)) {
-        return 0;
+        return ExitCode::Success.code();
     }
 
     if token_is_failure(decision) || token_is_failure(Some(verdict)) {
-        return 1;
+        return ExitCode::ReviewFindings.code();
     }
 
     INFRASTRUCTURE_FAILURE_EXIT_CODE
@@ -0,0 +1,279 @@
+//! `csa watch`: re-run a csa command whenever watched files change.
+//!
+//! Every `--poll-ms` the project tree (honouring `.gitignore`) is scanned for
+//! files matching an `--on-change` glob. `.git`, `.csa/state` and any CSA
+//! state directory inside the project are never scanned, so the runs' own
+//! session and state writes cannot trigger further runs. A file that appears,
+//! disappears or changes size or mtime opens a debounce window; the command
+//! runs once a scan taken `--debounce-ms` after the last change finds the
+//! watched files unchanged. A change during a run
+//! cancels it (SIGTERM to its process group, SIGKILL after a grace period) so
+//! the next run sees the latest tree. Runs inherit stdout/stderr, so their
+//! results stream to the terminal, and `run`, `review` and `debate` are kept
+//! in the foreground with `--no-daemon`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{Context, Result, bail};
+use ignore::WalkBuilder;
+use tokio::process::{Child, Command};
+
+use crate::cli::WatchArgs;
+
+/// Time a cancelled run gets to clean up after SIGTERM before SIGKILL.
+const CANCEL_GRACE: Duration = Duration::from_secs(5);
+
+/// Project-relative directory where csa keeps per-project state.
+const PROJECT_STATE_DIR: &str = ".csa/state";
+
+/// Subcommands that daemonize unless told otherwise.
+const DAEMON_SUBCOMMANDS: &[&str] = &["run", "review", "debate"];
+
+/// Size and mtime of every watched file, keyed by project-relative path.
+type WatchSnapshot = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+pub(crate) async fn handle_watch(args: WatchArgs) -> Result<()> {
+    let project_root = crate::pipeline::determine_project_root(args.cd.as_deref())?;
+    let patterns = compile_patterns(&args.on_change)?;
+    let command = watch_command_args(&args.command)?;
+    let csa_binary = std::env::current_exe().context("Failed to detect csa binary path")?;
+    let debounce = Duration::from_millis(args.debounce_ms);
+    let poll = Duration::from_millis(args.poll_ms);
+    let ignored = ignored_state_dirs(&project_root);
+
+    let mut snapshot = scan_watched_files(&project_root, &patterns, &ignored);
+    eprintln!(
+        "[csa-watch] watching {} file(s) matching {}; running `csa {}` on change (Ctrl-C to stop)",
+        snapshot.len(),
+        args.on_change.join(", "),
+        command.join(" ")
+    );
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut runs = 0usize;
+    let mut running: Option<Child> = None;
+    let mut changed_at = args.initial.then(Instant::now);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => {
+                if let Some(child) = running.take() {
+                    cancel_run(child).await;
+                }
+                eprintln!("[csa-watch] stopped after {runs} run(s)");
+                return Ok(());
+            }
+            status = wait_run(&mut running) => {
+                running = None;
+                eprintln!("[csa-watch] run #{runs} {}", describe_exit(status));
+            }
+            _ = tokio::time::sleep(next_scan_in(poll, debounce, changed_at)) => {
+                let next = scan_watched_files(&project_root, &patterns, &ignored);
+                let changed = changed_paths(&snapshot, &next);
+                if !changed.is_empty() {
+                    snapshot = next;
+                    changed_at = Some(Instant::now());
+                    eprintln!("[csa-watch] changed: {}", summarize_paths(&changed));
+                    if let Some(child) = running.take() {
+                        eprintln!("[csa-watch] cancelling run #{runs}");
+                        cancel_run(child).await;
+                    }
+                }
+                if running.is_none() && changed_at.is_some_and(|at| at.elapsed() >= debounce) {
+                    changed_at = None;
+                    runs += 1;
+                    eprintln!("[csa-watch] run #{runs}: csa {}", command.join(" "));
+                    running = Some(spawn_run(&csa_binary, &project_root, &command)?);
+                }
+            }
+        }
+    }
+}
+
+/// Wait before the next scan: the poll interval, shortened so a pending
+/// debounce window is checked as soon as it ends.
+fn next_scan_in(poll: Duration, debounce: Duration, changed_at: Option<Instant>) -> Duration {
+    changed_at.map_or(poll, |at| {
+        poll.min(debounce.saturating_sub(at.elapsed()))
+            .max(Duration::from_millis(10))
+    })
+}
+
+/// Absolute directories a scan skips: the project's `.csa/state` and every
+/// CSA state root that lies inside the project (e.g. via `CSA_STATE_DIR`).
+fn ignored_state_dirs(project_root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_root.join(PROJECT_STATE_DIR)];
+    for root in csa_config::paths::state_dir_all_roots() {
+        let root = root.canonicalize().unwrap_or(root);
+        if root.starts_with(project_root) {
+            dirs.push(root);
+        }
+    }
+    dirs
+}
+
+fn compile_patterns(globs: &[String]) -> Result<Vec<glob::Pattern>> {
+    globs
+        .iter()
+        .map(|raw| {
+            glob::Pattern::new(raw.trim_start_matches("./"))
+                .with_context(|| format!("Invalid --on-change glob: {raw}"))
+        })
+        .collect()
+}
+
+/// Arguments of the watched run: `csa watch` itself is refused and
+/// daemonizing subcommands get `--no-daemon` so their output streams here.
+fn watch_command_args(command: &[String]) -> Result<Vec<String>> {
+    let Some(subcommand) = command.first() else {
+        bail!("csa watch needs a command after `--`, for example `-- review --diff`");
+    };
+    if subcommand == "watch" {
+        bail!("csa watch cannot watch another `csa watch`");
+    }
+    let mut args = command.to_vec();
+    if DAEMON_SUBCOMMANDS.contains(&subcommand.as_str())
+        && !command.iter().any(|arg| arg == "--no-daemon")
+    {
+        args.insert(1, "--no-daemon".to_string());
+    }
+    Ok(args)
+}
+
+fn matches_any(patterns: &[glob::Pattern], relative: &Path) -> bool {
+    // `*` stays within one directory; `**` crosses them.
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    patterns
+        .iter()
+        .any(|pattern| pattern.matches_path_with(relative, options))
+}
+
+fn scan_watched_files(
+    project_root: &Path,
+    patterns: &[glob::Pattern],
+    ignored: &[PathBuf],
+) -> WatchSnapshot {
+    let mut builder = WalkBuilder::new(project_root);
+    builder.hidden(false);
+    let ignored = ignored.to_vec();
+    builder.filter_entry(move |entry| {
+        entry.file_name() != ".git" && !ignored.iter().any(|dir| entry.path() == dir)
+    });
+
+    let mut snapshot = WatchSnapshot::new();
+    for entry in builder.build().flatten() {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(project_root) else {
+            continue;
+        };
+        if !matches_any(patterns, relative) {
+            continue;
+        }
+        // A file deleted mid-scan simply drops out until the next scan.
+        if let Ok(metadata) = entry.metadata() {
+            snapshot.insert(
+                relative.to_path_buf(),
+                (metadata.len(), metadata.modified().ok()),
+            );
+        }
+    }
+    snapshot
+}
+
+/// Paths added, removed or modified between two scans.
+fn changed_paths(previous: &WatchSnapshot, next: &WatchSnapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = next
+        .iter()
+        .filter(|(path, stamp)| previous.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(
+        previous
+            .keys()
+            .filter(|path| !next.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+fn summarize_paths(paths: &[PathBuf]) -> String {
+    const SHOWN: usize = 3;
+    let mut summary = paths
+        .iter()
+        .take(SHOWN)
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if paths.len() > SHOWN {
+        summary.push_str(&format!(" (+{} more)", paths.len() - SHOWN));
+    }
+    summary
+}
+
+fn spawn_run(csa_binary: &Path, project_root: &Path, command: &[String]) -> Result<Child> {
+    let mut cmd = Command::new(csa_binary);
+    cmd.args(command)
+        .current_dir(project_root)
+        .stdin(Stdio::null());
+    // Own process group: cancelling reaches the run's descendants, and the
+    // terminal's Ctrl-C reaches only the watcher, which then cancels the run.
+    #[cfg(unix)]
+    cmd.process_group(0);
+    cmd.spawn()
+        .with_context(|| format!("Failed to spawn `csa {}`", command.join(" ")))
+}
+
+async fn wait_run(running: &mut Option<Child>) -> std::io::Result<ExitStatus> {
+    match running {
+        Some(child) => child.wait().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn cancel_run(mut child: Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: the leader is not reaped yet, so its PID still anchors the
+        // process group created by `process_group(0)` at spawn.
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGTERM);
+        }
+        if tokio::time::timeout(CANCEL_GRACE, child.wait())
+            .await
+            .is_ok()
+        {
+            return;
+        }
+        // SAFETY: the wait timed out, so the leader is still unreaped.
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    let _ = child.kill().await;
+}
+
+fn describe_exit(status: std::io::Result<ExitStatus>) -> String {
+    match status {
+        Ok(status) => match status.code() {
+            Some(code) => format!("exited with code {code}"),
+            None => "was terminated by a signal".to_string(),
+        },
+        Err(error) => format!("could not be awaited: {error}"),
+    }
+}
+
+#[cfg(test)]
+#[path = "watch_cmd_tests.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+use super::*;
+
+fn args(raw: &[&str]) -> Vec<String> {
+    raw.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn daemonizing_subcommands_run_in_the_foreground() {
+    assert_eq!(
+        watch_command_args(&args(&["review", "--diff"])).unwrap(),
+        args(&["review", "--no-daemon", "--diff"])
+    );
+    assert_eq!(
+        watch_command_args(&args(&["run", "--no-daemon", "fix lint"])).unwrap(),
+        args(&["run", "--no-daemon", "fix lint"])
+    );
+    assert_eq!(
+        watch_command_args(&args(&["audit", "status"])).unwrap(),
+        args(&["audit", "status"])
+    );
+    assert!(watch_command_args(&args(&["watch", "--on-change", "src/**"])).is_err());
+    assert!(watch_command_args(&[]).is_err());
+}
+
+#[test]
+fn scans_report_added_modified_and_removed_watched_files() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src/nested")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), "fn a() {}").unwrap();
+    std::fs::write(root.join("src/nested/mod.rs"), "fn b() {}").unwrap();
+    std::fs::write(root.join("README.md"), "readme").unwrap();
+    let patterns = compile_patterns(&args(&["./src/**", "*.toml"])).unwrap();
+
+    let first = scan_watched_files(root, &patterns, &[]);
+    assert_eq!(
+        first.keys().cloned().collect::<Vec<_>>(),
+        [
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/nested/mod.rs")
+        ]
+    );
+    assert!(changed_paths(&first, &scan_watched_files(root, &patterns, &[])).is_empty());
+
+    std::fs::write(root.join("src/lib.rs"), "fn a() { b() }").unwrap();
+    std::fs::remove_file(root.join("src/nested/mod.rs")).unwrap();
+    std::fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+    std::fs::write(root.join("README.md"), "changed readme").unwrap();
+    let changed = changed_paths(&first, &scan_watched_files(root, &patterns, &[]));
+    assert_eq!(
+        changed,
+        [
+            PathBuf::from("Cargo.toml"),
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/nested/mod.rs")
+        ]
+    );
+    assert_eq!(summarize_paths(&changed[..2]), "Cargo.toml, src/lib.rs");
+}
+
+#[test]
+fn invalid_globs_are_rejected() {
+    let error = compile_patterns(&args(&["src/[**"])).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Invalid --on-change glob: src/[**")
+    );
+}
+
+#[test]
+fn state_directories_are_not_scanned() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    std::fs::create_dir_all(root.join(".csa/state/plan")).unwrap();
+    std::fs::create_dir_all(root.join("state/sessions/01ABC")).unwrap();
+    std::fs::write(root.join(".csa/state/plan/journal.json"), "{}").unwrap();
+    std::fs::write(root.join("state/sessions/01ABC/state.toml"), "").unwrap();
+    std::fs::write(root.join(".csa/config.toml"), "").unwrap();
+    let patterns = compile_patterns(&args(&["**"])).unwrap();
+
+    let mut ignored = ignored_state_dirs(root);
+    ignored.push(root.join("state"));
+    let scanned = scan_watched_files(root, &patterns, &ignored);
+    assert_eq!(
+        scanned.keys().cloned().collect::<Vec<_>>(),
+        [PathBuf::from(".csa/config.toml")]
+    );
+}
+
+#[test]
+fn scans_wait_for_the_end_of_the_debounce_window() {
+    let poll = Duration::from_millis(1000);
+    let debounce = Duration::from_millis(300);
+    assert_eq!(next_scan_in(poll, debounce, None), poll);
+    let pending = next_scan_in(poll, debounce, Some(Instant::now()));
+    assert!(pending <= debounce && pending > Duration::from_millis(200));
+    let elapsed = Instant::now() - Duration::from_secs(5);
+    assert_eq!(
+        next_scan_in(poll, debounce, Some(elapsed)),
+        Duration::from_millis(10)
+    );
+}
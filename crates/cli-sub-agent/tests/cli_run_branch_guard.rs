@@ -146,7 +146,7 @@ fn init_csa_project(tmp: &Path) {
 }
 
 fn assert_branch_guard_refused(output: &Output) {
-    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(output.status.code(), Some(78));
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stdout_without_guards = strip_caller_sa_guard_blocks(&stdout);
     assert!(
@@ -193,7 +193,7 @@ fn strip_caller_sa_guard_blocks(text: &str) -> String {
 }
 
 fn assert_branch_guard_allowed_to_later_error(output: &Output) {
-    assert_ne!(output.status.code(), Some(2));
+    assert_ne!(output.status.code(), Some(78));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
         !stderr.contains("refusing to run on protected branch"),
@@ -6,8 +6,8 @@
 // stderr.log and daemon-completion.toml MUST be written even on error paths.
 //
 // Two scenarios:
-//   A. Ok(non-zero) — handle_run returns Ok(1) without Err propagation.
-//      daemon-completion.toml must still be written with exit_code=1.
+//   A. Ok(non-zero) — handle_run returns Ok(78) without Err propagation.
+//      daemon-completion.toml must still be written with exit_code=78.
 //   B. Err() propagation — handle_run returns Err(e) which is caught by
 //      report_daemon_error_or_exit_code (issue #574 fix).
 //      stderr.log must contain the error message and daemon-completion.toml
@@ -89,7 +89,7 @@ fn test_session_id(suffix: &str) -> String {
 // Scenario A: Ok(non-zero) return path
 //
 // When CSA_DEPTH exceeds max_depth, load_and_validate returns Ok(None) and
-// handle_run returns Ok(1). This tests that daemon-completion.toml is written
+// handle_run returns Ok(78). This tests that daemon-completion.toml is written
 // even on the non-error exit path with a non-zero exit code.
 // ---------------------------------------------------------------------------
 
@@ -110,7 +110,7 @@ fn daemon_child_ok_nonzero_writes_completion_toml() {
     fs::create_dir_all(&session_dir).expect("create session dir");
 
     // Run as daemon-child with CSA_DEPTH=100 (exceeds default max_depth=5).
-    // handle_run will call load_and_validate → None → return Ok(1).
+    // handle_run will call load_and_validate → None → return Ok(78) (config error).
     let output = csa_cmd(tmp.path())
         .args([
             "run",
@@ -131,11 +131,11 @@ fn daemon_child_ok_nonzero_writes_completion_toml() {
         .output()
         .expect("failed to run csa");
 
-    // Process should exit with code 1 (not hang or crash).
+    // Process should exit with code 78 (not hang or crash).
     assert_eq!(
         output.status.code(),
-        Some(1),
-        "expected exit code 1, got {:?}\nstdout: {}\nstderr: {}",
+        Some(78),
+        "expected exit code 78 (config error), got {:?}\nstdout: {}\nstderr: {}",
         output.status.code(),
         String::from_utf8_lossy(&output.stdout),
         String::from_utf8_lossy(&output.stderr),
@@ -150,11 +150,11 @@ fn daemon_child_ok_nonzero_writes_completion_toml() {
     );
 
     let (exit_code, status) = read_daemon_completion(&session_dir);
-    assert_eq!(exit_code, 1, "exit_code should be 1");
-    // status_from_exit_code(1) = "failure" (0 = "success", 137/143 = "signal", else = "failure").
+    assert_eq!(exit_code, 78, "exit_code should be 78");
+    // status_from_exit_code(78) = "failure" (0 = "success", 137/143 = "signal", else = "failure").
     assert_eq!(
         status, "failure",
-        "status should be 'failure' for exit code 1"
+        "status should be 'failure' for exit code 78"
     );
 }
 
@@ -216,11 +216,11 @@ fn daemon_child_err_path_captures_stderr_and_writes_completion() {
         .output()
         .expect("failed to spawn csa");
 
-    // Must exit with code 1 (not hang, not crash with signal).
+    // Must exit with code 78 (not hang, not crash with signal).
     assert_eq!(
         child.status.code(),
-        Some(1),
-        "expected exit code 1, got {:?}\nstdout: {}\nstderr: {}",
+        Some(78),
+        "expected exit code 78 (config error), got {:?}\nstdout: {}\nstderr: {}",
         child.status.code(),
         String::from_utf8_lossy(&child.stdout),
         String::from_utf8_lossy(&child.stderr),
@@ -235,10 +235,10 @@ fn daemon_child_err_path_captures_stderr_and_writes_completion() {
     );
 
     let (exit_code, status) = read_daemon_completion(&session_dir);
-    assert_eq!(exit_code, 1, "exit_code should be 1");
+    assert_eq!(exit_code, 78, "exit_code should be 78");
     assert_eq!(
         status, "failure",
-        "status should be 'failure' for exit code 1"
+        "status should be 'failure' for exit code 78"
     );
 
     // --- Check stderr.log ---
@@ -313,7 +313,7 @@ fn daemon_child_review_err_writes_completion() {
     // check_daemon_flags succeeds (daemon_child=true, session_id present).
     // install_daemon_stderr_rotation may fail (cd=/nonexistent), which is best-effort.
     // handle_review calls determine_project_root(Some("/nonexistent")) → Err.
-    // report_daemon_error_or_exit_code catches Err → eprintln! → finalize → exit(1).
+    // report_daemon_error_or_exit_code catches Err → eprintln! → finalize → exit(64).
     let output = csa_cmd(tmp.path())
         .args([
             "review",
@@ -333,11 +333,11 @@ fn daemon_child_review_err_writes_completion() {
         .output()
         .expect("failed to run csa review");
 
-    // Must exit cleanly with code 1.
+    // Must exit cleanly with code 64.
     assert_eq!(
         output.status.code(),
-        Some(1),
-        "expected exit code 1, got {:?}\nstdout: {}\nstderr: {}",
+        Some(64),
+        "expected exit code 64 (usage error), got {:?}\nstdout: {}\nstderr: {}",
         output.status.code(),
         String::from_utf8_lossy(&output.stdout),
         String::from_utf8_lossy(&output.stderr),
@@ -352,10 +352,10 @@ fn daemon_child_review_err_writes_completion() {
     );
 
     let (exit_code, status) = read_daemon_completion(&session_dir);
-    assert_eq!(exit_code, 1, "exit_code should be 1");
+    assert_eq!(exit_code, 64, "exit_code should be 64");
     assert_eq!(
         status, "failure",
-        "status should be 'failure' for exit code 1"
+        "status should be 'failure' for exit code 64"
     );
 
     // The error message should mention the nonexistent path.
@@ -611,44 +611,6 @@ fn run_help_shows_tool_options() {
     assert!(stdout.contains("--model"));
 }
 
-#[test]
-fn mcp_hub_serve_parse_with_background_and_socket() {
-    let cli = Cli::try_parse_from([
-        "csa",
-        "mcp-hub",
-        "serve",
-        "--background",
-        "--socket",
-        "/tmp/cli-sub-agent-1000/mcp-hub.sock",
-    ])
-    .expect("mcp-hub serve args should parse");
-
-    match cli.command {
-        Commands::McpHub {
-            cmd:
-                McpHubCommands::Serve {
-                    background,
-                    foreground,
-                    socket,
-                    http_bind,
-                    http_port,
-                    systemd_activation,
-                },
-        } => {
-            assert!(background);
-            assert!(!foreground);
-            assert_eq!(
-                socket.as_deref(),
-                Some("/tmp/cli-sub-agent-1000/mcp-hub.sock")
-            );
-            assert!(http_bind.is_none());
-            assert!(http_port.is_none());
-            assert!(!systemd_activation);
-        }
-        _ => panic!("expected mcp-hub serve subcommand"),
-    }
-}
-
 #[test]
 fn mcp_hub_gen_skill_parse_with_socket() {
     let cli = Cli::try_parse_from([
@@ -662,12 +624,13 @@ fn mcp_hub_gen_skill_parse_with_socket() {
 
     match cli.command {
         Commands::McpHub {
-            cmd: McpHubCommands::GenSkill { socket },
+            cmd: McpHubCommands::GenSkill { socket, update },
         } => {
             assert_eq!(
                 socket.as_deref(),
                 Some("/tmp/cli-sub-agent-1000/mcp-hub.sock")
             );
+            assert!(!update);
         }
         _ => panic!("expected mcp-hub gen-skill subcommand"),
     }
@@ -1300,8 +1263,8 @@ fn run_direct_tool_tier_rejection_surfaces_cause_and_session_id() {
 
     assert_eq!(
         output.status.code(),
-        Some(1),
-        "expected exit code 1, got {:?}\nstdout: {}\nstderr: {}",
+        Some(78),
+        "expected exit code 78, got {:?}\nstdout: {}\nstderr: {}",
         output.status.code(),
         String::from_utf8_lossy(&output.stdout),
         String::from_utf8_lossy(&output.stderr),
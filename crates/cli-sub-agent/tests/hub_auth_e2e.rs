@@ -0,0 +1,85 @@
+#[path = "../src/cli.rs"]
+mod cli_defs;
+#[path = "../src/gc_args.rs"]
+mod gc;
+
+use clap::Parser;
+use cli_defs::{Cli, Commands, McpHubCommands, validate_command_args};
+
+#[test]
+fn mcp_hub_serve_parse_with_background_and_socket() {
+    let cli = Cli::try_parse_from([
+        "csa",
+        "mcp-hub",
+        "serve",
+        "--background",
+        "--socket",
+        "/tmp/cli-sub-agent-1000/mcp-hub.sock",
+    ])
+    .expect("mcp-hub serve args should parse");
+    validate_command_args(&cli.command, 1800).expect("mcp-hub serve args should validate");
+
+    match cli.command {
+        Commands::McpHub {
+            cmd:
+                McpHubCommands::Serve {
+                    background,
+                    foreground,
+                    socket,
+                    http_bind,
+                    http_port,
+                    http_tokens,
+                    systemd_activation,
+                },
+        } => {
+            assert!(background);
+            assert!(!foreground);
+            assert_eq!(
+                socket.as_deref(),
+                Some("/tmp/cli-sub-agent-1000/mcp-hub.sock")
+            );
+            assert!(http_bind.is_none());
+            assert!(http_port.is_none());
+            assert!(http_tokens.is_none());
+            assert!(!systemd_activation);
+        }
+        _ => panic!("expected mcp-hub serve subcommand"),
+    }
+}
+
+#[test]
+fn mcp_hub_serve_parse_with_per_client_http_tokens() {
+    let cli = Cli::try_parse_from([
+        "csa",
+        "mcp-hub",
+        "serve",
+        "--http-port",
+        "0",
+        "--http-tokens",
+        "/etc/csa/mcp-hub-tokens.toml",
+    ])
+    .expect("mcp-hub serve --http-tokens should parse");
+
+    match cli.command {
+        Commands::McpHub {
+            cmd:
+                McpHubCommands::Serve {
+                    http_port,
+                    http_tokens,
+                    ..
+                },
+        } => {
+            assert_eq!(http_port, Some(0));
+            assert_eq!(http_tokens.as_deref(), Some("/etc/csa/mcp-hub-tokens.toml"));
+        }
+        _ => panic!("expected mcp-hub serve subcommand"),
+    }
+}
+
+#[test]
+fn mcp_hub_serve_rejects_http_tokens_without_path() {
+    let err = Cli::try_parse_from(["csa", "mcp-hub", "serve", "--http-tokens"])
+        .err()
+        .expect("--http-tokens requires a PATH value");
+    assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+}
@@ -70,7 +70,7 @@ fn wait_without_provider_fails_closed_before_session_lookup() {
     let output = run_wait(tmp.path(), &[]);
     let stderr = stderr(&output);
 
-    assert_eq!(output.status.code(), Some(1), "{stderr}");
+    assert_eq!(output.status.code(), Some(78), "{stderr}");
     assert!(
         stderr.contains("csa session wait requires --model-provider <key>"),
         "{stderr}"
@@ -94,7 +94,7 @@ fn run_direct_tool_tier_rejection_before_daemon_does_not_start_session() {
 
     assert_eq!(
         output.status.code(),
-        Some(1),
+        Some(78),
         "expected synchronous policy rejection, got {:?}\nstdout: {}\nstderr: {}",
         output.status.code(),
         String::from_utf8_lossy(&output.stdout),
@@ -151,7 +151,7 @@ fn plan_run_tier_rejection_mentions_supported_routing_mechanisms() {
 
     assert_eq!(
         output.status.code(),
-        Some(1),
+        Some(78),
         "expected actionable plan-run tier rejection, got {:?}\nstdout: {}\nstderr: {}",
         output.status.code(),
         String::from_utf8_lossy(&output.stdout),
@@ -0,0 +1,339 @@
+//! Recording of the JSON-RPC frames exchanged with an ACP agent.
+//!
+//! When capture is on, every newline-delimited frame written to the agent's
+//! stdin or read from its stdout is appended to a JSONL file. Each frame is
+//! stored with its direction and timestamps. Credentials are redacted before
+//! anything reaches the disk: `env` and `headers` values, and string fields
+//! whose name suggests a secret. The file is private to the user (mode 0600),
+//! since frames carry whole prompts, and is written by a background task so
+//! the protocol path never waits on the disk. [`crate::replay`] feeds such a
+//! file back to an agent.
+
+use std::{
+    cell::RefCell,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader},
+    path::Path,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufWriter, ReadBuf},
+    sync::{mpsc, oneshot},
+};
+use tracing::warn;
+
+const REDACTED: &str = "<redacted>";
+
+/// Field names whose string values are never written to a capture.
+const SENSITIVE_KEY_PARTS: &[&str] = &[
+    "api_key",
+    "api-key",
+    "apikey",
+    "authorization",
+    "cookie",
+    "credential",
+    "passwd",
+    "password",
+    "private_key",
+    "secret",
+    "token",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameDirection {
+    /// Written by the client to the agent's stdin.
+    ClientToAgent,
+    /// Read by the client from the agent's stdout.
+    AgentToClient,
+}
+
+/// One line of a capture file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedFrame {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Milliseconds since the capture started.
+    pub elapsed_ms: u64,
+    pub direction: FrameDirection,
+    /// The redacted frame, or the raw line as a string when it is not JSON.
+    pub frame: Value,
+}
+
+/// Read a capture file written by [`AcpConnection::capture_frames`].
+///
+/// [`AcpConnection::capture_frames`]: crate::AcpConnection::capture_frames
+pub fn read_capture(path: &Path) -> anyhow::Result<Vec<CapturedFrame>> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open ACP capture {}", path.display()))?;
+    let mut frames = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line =
+            line.with_context(|| format!("failed to read ACP capture {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let frame = serde_json::from_str(&line).with_context(|| {
+            format!("invalid frame on line {} of {}", index + 1, path.display())
+        })?;
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+/// Replace credentials in a JSON-RPC frame with a placeholder.
+pub fn redact_frame(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if key == "env" || key == "headers" {
+                    redact_variables(entry);
+                } else if entry.is_string()
+                    && SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+                {
+                    *entry = Value::String(REDACTED.to_string());
+                } else {
+                    redact_frame(entry);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_frame),
+        _ => {}
+    }
+}
+
+/// ACP passes variables and headers as `[{ "name", "value" }]`; plain maps
+/// are handled as well.
+fn redact_variables(value: &mut Value) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                match item.get_mut("value") {
+                    Some(entry) => *entry = Value::String(REDACTED.to_string()),
+                    None => *item = Value::String(REDACTED.to_string()),
+                }
+            }
+        }
+        Value::Object(map) => {
+            for entry in map.values_mut() {
+                *entry = Value::String(REDACTED.to_string());
+            }
+        }
+        Value::Null => {}
+        other => *other = Value::String(REDACTED.to_string()),
+    }
+}
+
+enum CaptureMessage {
+    Line(String),
+    Flush(oneshot::Sender<()>),
+}
+
+/// An open capture file, shared by both directions of a connection.
+#[derive(Clone)]
+pub(crate) struct FrameCapture {
+    lines: mpsc::UnboundedSender<CaptureMessage>,
+    started: Instant,
+}
+
+/// Capture of a connection; `None` while capture is off.
+pub(crate) type SharedCapture = Rc<RefCell<Option<FrameCapture>>>;
+
+impl FrameCapture {
+    /// Append to `path`, creating it and its parent directories as needed.
+    /// Must be called inside a Tokio runtime, which runs the writer.
+    pub(crate) fn create(path: &Path) -> io::Result<Self> {
+        let runtime = tokio::runtime::Handle::try_current().map_err(io::Error::other)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = options.open(path)?;
+        // An existing capture keeps its mode on open.
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        Ok(Self::spawn_writer(&runtime, file))
+    }
+
+    fn spawn_writer(runtime: &tokio::runtime::Handle, file: File) -> Self {
+        let (lines, mut receiver) = mpsc::unbounded_channel();
+        runtime.spawn(async move {
+            let mut file = BufWriter::new(tokio::fs::File::from_std(file));
+            while let Some(message) = receiver.recv().await {
+                let mut flushed = Vec::new();
+                let mut next = Some(message);
+                // Write what is queued, then flush once the queue is empty.
+                while let Some(message) = next {
+                    match message {
+                        CaptureMessage::Line(line) => {
+                            if let Err(error) = file.write_all(line.as_bytes()).await {
+                                warn!(%error, "failed to write ACP frame capture");
+                            }
+                        }
+                        CaptureMessage::Flush(done) => flushed.push(done),
+                    }
+                    next = receiver.try_recv().ok();
+                }
+                if let Err(error) = file.flush().await {
+                    warn!(%error, "failed to write ACP frame capture");
+                }
+                for done in flushed {
+                    let _ = done.send(());
+                }
+            }
+        });
+        Self {
+            lines,
+            started: Instant::now(),
+        }
+    }
+
+    /// Wait until every frame recorded so far is on disk.
+    #[cfg(test)]
+    pub(crate) async fn flush(&self) {
+        let (done, flushed) = oneshot::channel();
+        if self.lines.send(CaptureMessage::Flush(done)).is_ok() {
+            let _ = flushed.await;
+        }
+    }
+
+    fn record(&self, direction: FrameDirection, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        let mut frame =
+            serde_json::from_str(line).unwrap_or_else(|_| Value::String(line.to_string()));
+        redact_frame(&mut frame);
+
+        let record = CapturedFrame {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_millis() as u64),
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            direction,
+            frame,
+        };
+        match serde_json::to_string(&record) {
+            Ok(json) => {
+                let _ = self.lines.send(CaptureMessage::Line(json + "\n"));
+            }
+            Err(error) => warn!(%error, "failed to write ACP frame capture"),
+        }
+    }
+}
+
+/// Splits the bytes seen in one direction into frames.
+struct FrameLines {
+    direction: FrameDirection,
+    capture: SharedCapture,
+    pending: Vec<u8>,
+}
+
+impl FrameLines {
+    fn observe(&mut self, bytes: &[u8]) {
+        let capture = self.capture.borrow();
+        let Some(capture) = capture.as_ref() else {
+            self.pending.clear();
+            return;
+        };
+        self.pending.extend_from_slice(bytes);
+        while let Some(end) = self.pending.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            capture.record(self.direction, &line);
+        }
+    }
+}
+
+/// The agent's stdout, recording every frame read from it.
+pub(crate) struct CapturingReader<R> {
+    inner: R,
+    lines: FrameLines,
+}
+
+impl<R> CapturingReader<R> {
+    pub(crate) fn new(inner: R, capture: SharedCapture) -> Self {
+        Self {
+            inner,
+            lines: FrameLines {
+                direction: FrameDirection::AgentToClient,
+                capture,
+                pending: Vec::new(),
+            },
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CapturingReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            this.lines.observe(&buf.filled()[before..]);
+        }
+        poll
+    }
+}
+
+/// The agent's stdin, recording every frame written to it.
+pub(crate) struct CapturingWriter<W> {
+    inner: W,
+    lines: FrameLines,
+}
+
+impl<W> CapturingWriter<W> {
+    pub(crate) fn new(inner: W, capture: SharedCapture) -> Self {
+        Self {
+            inner,
+            lines: FrameLines {
+                direction: FrameDirection::ClientToAgent,
+                capture,
+                pending: Vec::new(),
+            },
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for CapturingWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = poll {
+            this.lines.observe(&buf[..written]);
+        }
+        poll
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+#[path = "capture_tests.rs"]
+mod tests;
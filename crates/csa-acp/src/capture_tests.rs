@@ -0,0 +1,104 @@
+use super::*;
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[test]
+fn redact_frame_hides_env_headers_and_secret_fields() {
+    let mut frame = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "session/new",
+        "params": {
+            "cwd": "/repo",
+            "mcpServers": [{
+                "name": "hub",
+                "command": "mcp-hub",
+                "env": [{ "name": "GITHUB_TOKEN", "value": "ghp_secret" }],
+                "headers": [{ "name": "Authorization", "value": "Bearer abc" }],
+            }],
+            "_meta": { "apiKey": "sk-123", "usage": { "totalTokens": 42 } },
+        },
+    });
+
+    redact_frame(&mut frame);
+
+    let server = &frame["params"]["mcpServers"][0];
+    assert_eq!(server["env"][0]["name"], "GITHUB_TOKEN");
+    assert_eq!(server["env"][0]["value"], REDACTED);
+    assert_eq!(server["headers"][0]["value"], REDACTED);
+    assert_eq!(server["command"], "mcp-hub");
+    assert_eq!(frame["params"]["_meta"]["apiKey"], REDACTED);
+    assert_eq!(frame["params"]["_meta"]["usage"]["totalTokens"], 42);
+    assert_eq!(frame["params"]["cwd"], "/repo");
+}
+
+#[tokio::test]
+async fn both_directions_are_recorded_once_capture_is_on() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("output/acp-capture.jsonl");
+    let capture: SharedCapture = Rc::default();
+    let (client_io, mut agent_io) = tokio::io::duplex(1024);
+    let (reader, writer) = tokio::io::split(client_io);
+    let mut reader = CapturingReader::new(reader, capture.clone());
+    let mut writer = CapturingWriter::new(writer, capture.clone());
+
+    writer.write_all(b"{\"skipped\":true}\n").await.unwrap();
+    *capture.borrow_mut() = Some(FrameCapture::create(&path).expect("create capture"));
+    writer
+        .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":0,\"method\":\"initialize\"}\n")
+        .await
+        .unwrap();
+    agent_io
+        .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":0,")
+        .await
+        .unwrap();
+    agent_io
+        .write_all(b"\"result\":{}}\nnot json\n")
+        .await
+        .unwrap();
+    drop(agent_io);
+    let mut incoming = String::new();
+    reader.read_to_string(&mut incoming).await.unwrap();
+    let recorder = capture.borrow().clone().expect("capture on");
+    recorder.flush().await;
+
+    let frames = read_capture(&path).expect("read capture");
+    let summary: Vec<_> = frames
+        .iter()
+        .map(|frame| (frame.direction, frame.frame.clone()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                FrameDirection::ClientToAgent,
+                json!({ "jsonrpc": "2.0", "id": 0, "method": "initialize" }),
+            ),
+            (
+                FrameDirection::AgentToClient,
+                json!({ "jsonrpc": "2.0", "id": 0, "result": {} }),
+            ),
+            (FrameDirection::AgentToClient, json!("not json")),
+        ]
+    );
+    assert!(frames.iter().all(|frame| frame.timestamp_ms > 0));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn capture_files_are_private_to_the_user() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("acp-capture.jsonl");
+    std::fs::write(&path, "").expect("create");
+    std::fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).expect("chmod");
+
+    let capture = FrameCapture::create(&path).expect("create capture");
+    capture.record(FrameDirection::ClientToAgent, b"{\"id\":1}\n");
+    capture.flush().await;
+
+    let mode = fs::metadata(&path).expect("metadata").permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(read_capture(&path).expect("read capture").len(), 1);
+}
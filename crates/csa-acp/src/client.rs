@@ -1,11 +1,24 @@
 use std::time::Instant;
-use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    rc::Rc,
+};
 
+use crate::client_fs::{FsOperation, FsSandbox, FsScope, SharedFsSandbox, access_event};
+use crate::permissions::{PermissionSubject, SharedPermissionEngine, select_outcome};
+use crate::terminal::SharedTerminals;
 use crate::tool_output_compaction::ToolOutputCompactionState;
 use agent_client_protocol::{
-    Client, ContentBlock, ContentChunk, RequestPermissionOutcome, RequestPermissionRequest,
+    Client, ClientCapabilities, ContentBlock, ContentChunk, CreateTerminalRequest,
+    CreateTerminalResponse, FileSystemCapability, KillTerminalCommandRequest,
+    KillTerminalCommandResponse, ReadTextFileRequest, ReadTextFileResponse, ReleaseTerminalRequest,
+    ReleaseTerminalResponse, RequestPermissionOutcome, RequestPermissionRequest,
     RequestPermissionResponse, SelectedPermissionOutcome, SessionNotification, SessionUpdate,
-    ToolCallContent, ToolCallUpdateFields,
+    TerminalId, TerminalOutputRequest, TerminalOutputResponse, ToolCallContent,
+    ToolCallUpdateFields, WaitForTerminalExitRequest, WaitForTerminalExitResponse,
+    WriteTextFileRequest, WriteTextFileResponse,
 };
 
 /// Maximum bytes retained in the tail text buffer; shared with `csa-process::output_helpers`.
@@ -139,6 +152,21 @@ pub enum SessionEvent {
         output: String,
     },
     PlanUpdate(String),
+    /// An agent permission request and the policy decision taken on it.
+    PermissionRequest {
+        id: String,
+        title: String,
+        kind: String,
+        paths: Vec<String>,
+        decision: String,
+    },
+    /// A file read or write the agent made through the client, with its
+    /// outcome (`ok`, `denied` or `error`).
+    FileAccess {
+        path: String,
+        operation: String,
+        status: String,
+    },
     Other(String),
 }
 
@@ -151,6 +179,8 @@ pub(crate) fn event_counts_as_initial_response(event: &SessionEvent) -> bool {
             | SessionEvent::ToolCallStarted { .. }
             | SessionEvent::ToolCallCompleted { .. }
             | SessionEvent::ToolCallOutput { .. }
+            | SessionEvent::PermissionRequest { .. }
+            | SessionEvent::FileAccess { .. }
     )
 }
 
@@ -253,11 +283,12 @@ impl SessionEventStore {
             SessionEvent::AgentMessage(_) => {
                 self.turn_count = self.turn_count.saturating_add(1);
             }
-            SessionEvent::ToolCallOutput { .. } => {
+            SessionEvent::ToolCallOutput { .. } | SessionEvent::FileAccess { .. } => {
                 self.has_tool_calls = true;
             }
             SessionEvent::AgentThought(_)
             | SessionEvent::ToolCallCompleted { .. }
+            | SessionEvent::PermissionRequest { .. }
             | SessionEvent::Other(_) => {}
         }
     }
@@ -286,12 +317,57 @@ pub(crate) type SharedEvents = Rc<RefCell<SessionEventStore>>;
 pub(crate) type SharedActivity = Rc<RefCell<Instant>>;
 pub(crate) type SharedToolOutputCompactor = Rc<RefCell<Option<ToolOutputCompactionState>>>;
 
+/// Event sink of one session multiplexed over a shared connection.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionChannels {
+    pub(crate) events: SharedEvents,
+    pub(crate) last_activity: SharedActivity,
+    pub(crate) last_meaningful_activity: SharedActivity,
+    pub(crate) tool_output_compactor: SharedToolOutputCompactor,
+}
+
+impl SessionChannels {
+    pub(crate) fn new() -> Self {
+        Self {
+            events: Rc::new(RefCell::new(SessionEventStore::default())),
+            last_activity: Rc::new(RefCell::new(Instant::now())),
+            last_meaningful_activity: Rc::new(RefCell::new(Instant::now())),
+            tool_output_compactor: Rc::new(RefCell::new(None)),
+        }
+    }
+}
+
+/// Capabilities advertised in `initialize`: client-side fs and terminals.
+/// File writes are only offered when the agent's sandbox lets it write.
+pub(crate) fn client_capabilities(sandbox: Option<&FsSandbox>) -> ClientCapabilities {
+    ClientCapabilities::new()
+        .fs(FileSystemCapability::new()
+            .read_text_file(true)
+            .write_text_file(sandbox.is_none_or(FsSandbox::allows_writes)))
+        .terminal(true)
+}
+
+fn unknown_terminal(id: &TerminalId) -> agent_client_protocol::Error {
+    agent_client_protocol::Error::invalid_params().data(format!("unknown terminal {}", id.0))
+}
+
+/// Provider session ID -> channels. Notifications for a session without a
+/// route go to the connection-wide channels.
+pub(crate) type SharedSessionRoutes = Rc<RefCell<HashMap<String, SessionChannels>>>;
+
 #[derive(Debug, Clone)]
 pub(crate) struct AcpClient {
     events: SharedEvents,
     last_activity: SharedActivity,
     last_meaningful_activity: SharedActivity,
     tool_output_compactor: SharedToolOutputCompactor,
+    session_routes: SharedSessionRoutes,
+    permission_engine: SharedPermissionEngine,
+    /// Root for client-side fs requests when no policy names a project root.
+    fs_root: Option<PathBuf>,
+    /// Filesystem sandbox of the agent; fs requests may not leave it.
+    fs_sandbox: SharedFsSandbox,
+    terminals: SharedTerminals,
 }
 
 impl AcpClient {
@@ -320,9 +396,55 @@ impl AcpClient {
             last_activity,
             last_meaningful_activity,
             tool_output_compactor,
+            session_routes: Rc::default(),
+            permission_engine: Rc::default(),
+            fs_root: None,
+            fs_sandbox: Rc::default(),
+            terminals: Rc::default(),
         }
     }
 
+    /// Share `routes` with the connection that registers multiplexed sessions.
+    pub(crate) fn with_session_routes(mut self, routes: SharedSessionRoutes) -> Self {
+        self.session_routes = routes;
+        self
+    }
+
+    /// Share the permission policy the connection installs per run.
+    pub(crate) fn with_permission_engine(mut self, engine: SharedPermissionEngine) -> Self {
+        self.permission_engine = engine;
+        self
+    }
+
+    /// Confine client-side fs writes to `root` unless the permission policy
+    /// names a project root.
+    pub(crate) fn with_fs_root(mut self, root: PathBuf) -> Self {
+        self.fs_root = Some(root);
+        self
+    }
+
+    /// Share the filesystem sandbox the connection records once the agent
+    /// is spawned.
+    pub(crate) fn with_fs_sandbox(mut self, sandbox: SharedFsSandbox) -> Self {
+        self.fs_sandbox = sandbox;
+        self
+    }
+
+    /// Share the terminals the connection configures with its launch spec.
+    pub(crate) fn with_terminals(mut self, terminals: SharedTerminals) -> Self {
+        self.terminals = terminals;
+        self
+    }
+
+    /// Event store of `session_id`, or the connection-wide one when the
+    /// session is not multiplexed.
+    fn events_for_session(&self, session_id: &str) -> SharedEvents {
+        self.session_routes
+            .borrow()
+            .get(session_id)
+            .map_or_else(|| self.events.clone(), |channels| channels.events.clone())
+    }
+
     fn chunk_to_text(chunk: &ContentChunk) -> String {
         match &chunk.content {
             ContentBlock::Text(text) => text.text.clone(),
@@ -426,8 +548,11 @@ impl AcpClient {
         }
     }
 
-    fn update_to_event_for_session(&self, update: SessionUpdate) -> Option<SessionEvent> {
-        let mut compactor_ref = self.tool_output_compactor.borrow_mut();
+    fn update_to_event_for_session(
+        compactor: &SharedToolOutputCompactor,
+        update: SessionUpdate,
+    ) -> Option<SessionEvent> {
+        let mut compactor_ref = compactor.borrow_mut();
         Self::update_to_event_with_compactor(update, compactor_ref.as_mut())
     }
 }
@@ -473,17 +598,133 @@ impl Client for AcpClient {
         &self,
         args: RequestPermissionRequest,
     ) -> agent_client_protocol::Result<RequestPermissionResponse> {
-        let outcome = args
-            .options
-            .first()
-            .map(|first| {
-                RequestPermissionOutcome::Selected(SelectedPermissionOutcome::new(
-                    first.option_id.clone(),
-                ))
-            })
-            .unwrap_or(RequestPermissionOutcome::Cancelled);
+        let engine = self.permission_engine.borrow();
+        let Some(engine) = engine.as_ref() else {
+            let outcome = args
+                .options
+                .first()
+                .map(|first| {
+                    RequestPermissionOutcome::Selected(SelectedPermissionOutcome::new(
+                        first.option_id.clone(),
+                    ))
+                })
+                .unwrap_or(RequestPermissionOutcome::Cancelled);
+            return Ok(RequestPermissionResponse::new(outcome));
+        };
+
+        let subject = PermissionSubject::from_request(&args);
+        let decision = engine.decide(&subject);
+        tracing::debug!(
+            title = %subject.title,
+            decision = decision.as_str(),
+            "ACP permission request decided by policy"
+        );
+        self.events_for_session(&args.session_id.0)
+            .borrow_mut()
+            .push(subject.into_event(decision));
+        Ok(RequestPermissionResponse::new(select_outcome(
+            &args.options,
+            decision,
+        )))
+    }
+
+    async fn read_text_file(
+        &self,
+        args: ReadTextFileRequest,
+    ) -> agent_client_protocol::Result<ReadTextFileResponse> {
+        let engine = self.permission_engine.borrow();
+        let sandbox = self.fs_sandbox.borrow();
+        let scope =
+            FsScope::new(self.fs_root.as_deref(), engine.as_ref()).with_sandbox(sandbox.as_ref());
+        let path = scope.resolve(&args.path);
+        let result = scope.read_text(&path, args.line, args.limit);
+        self.events_for_session(&args.session_id.0)
+            .borrow_mut()
+            .push(access_event(&path, FsOperation::Read, &result));
+        result
+            .map(ReadTextFileResponse::new)
+            .map_err(|error| error.into_rpc_error(&path))
+    }
+
+    async fn write_text_file(
+        &self,
+        args: WriteTextFileRequest,
+    ) -> agent_client_protocol::Result<WriteTextFileResponse> {
+        let engine = self.permission_engine.borrow();
+        let sandbox = self.fs_sandbox.borrow();
+        let scope =
+            FsScope::new(self.fs_root.as_deref(), engine.as_ref()).with_sandbox(sandbox.as_ref());
+        let path = scope.resolve(&args.path);
+        let operation = FsScope::write_operation(&path);
+        let result = scope.write_text(&path, operation, &args.content);
+        tracing::debug!(
+            path = %path.display(),
+            operation = operation.as_str(),
+            ok = result.is_ok(),
+            "ACP client-side file write"
+        );
+        self.events_for_session(&args.session_id.0)
+            .borrow_mut()
+            .push(access_event(&path, operation, &result));
+        result
+            .map(|()| WriteTextFileResponse::new())
+            .map_err(|error| error.into_rpc_error(&path))
+    }
+
+    async fn create_terminal(
+        &self,
+        args: CreateTerminalRequest,
+    ) -> agent_client_protocol::Result<CreateTerminalResponse> {
+        let events = self.events_for_session(&args.session_id.0);
+        let id = self.terminals.create(args, events).await.map_err(|error| {
+            agent_client_protocol::Error::internal_error().data(format!("{error:#}"))
+        })?;
+        Ok(CreateTerminalResponse::new(id))
+    }
+
+    async fn terminal_output(
+        &self,
+        args: TerminalOutputRequest,
+    ) -> agent_client_protocol::Result<TerminalOutputResponse> {
+        let (output, truncated, exit_status) = self
+            .terminals
+            .output(&args.terminal_id.0)
+            .ok_or_else(|| unknown_terminal(&args.terminal_id))?;
+        let mut response = TerminalOutputResponse::new(output, truncated);
+        response.exit_status = exit_status;
+        Ok(response)
+    }
+
+    async fn wait_for_terminal_exit(
+        &self,
+        args: WaitForTerminalExitRequest,
+    ) -> agent_client_protocol::Result<WaitForTerminalExitResponse> {
+        let exit_status = self
+            .terminals
+            .wait_for_exit(&args.terminal_id.0)
+            .await
+            .ok_or_else(|| unknown_terminal(&args.terminal_id))?;
+        Ok(WaitForTerminalExitResponse::new(exit_status))
+    }
+
+    async fn kill_terminal_command(
+        &self,
+        args: KillTerminalCommandRequest,
+    ) -> agent_client_protocol::Result<KillTerminalCommandResponse> {
+        if !self.terminals.kill(&args.terminal_id.0) {
+            return Err(unknown_terminal(&args.terminal_id));
+        }
+        Ok(KillTerminalCommandResponse::new())
+    }
 
-        Ok(RequestPermissionResponse::new(outcome))
+    async fn release_terminal(
+        &self,
+        args: ReleaseTerminalRequest,
+    ) -> agent_client_protocol::Result<ReleaseTerminalResponse> {
+        if !self.terminals.release(&args.terminal_id.0) {
+            return Err(unknown_terminal(&args.terminal_id));
+        }
+        Ok(ReleaseTerminalResponse::new())
     }
 
     async fn session_notification(
@@ -494,11 +735,31 @@ impl Client for AcpClient {
         // Idle-timeout remains broad: any ACP session notification counts
         // as transport liveness, even when suppressed from collected output.
         *self.last_activity.borrow_mut() = now;
-        if let Some(event) = self.update_to_event_for_session(args.update) {
+        let route = self
+            .session_routes
+            .borrow()
+            .get(&*args.session_id.0)
+            .cloned();
+        let (events, last_meaningful_activity, compactor) = match &route {
+            Some(channels) => {
+                *channels.last_activity.borrow_mut() = now;
+                (
+                    &channels.events,
+                    &channels.last_meaningful_activity,
+                    &channels.tool_output_compactor,
+                )
+            }
+            None => (
+                &self.events,
+                &self.last_meaningful_activity,
+                &self.tool_output_compactor,
+            ),
+        };
+        if let Some(event) = Self::update_to_event_for_session(compactor, args.update) {
             if event_counts_as_initial_response(&event) {
-                *self.last_meaningful_activity.borrow_mut() = now;
+                *last_meaningful_activity.borrow_mut() = now;
             }
-            self.events.borrow_mut().push(event);
+            events.borrow_mut().push(event);
         }
         Ok(())
     }
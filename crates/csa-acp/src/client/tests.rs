@@ -461,3 +461,41 @@ fn streaming_metadata_cache_hit_ratio_returns_none_when_input_tokens_zero() {
     };
     assert!(metadata.cache_hit_ratio().is_none());
 }
+
+#[tokio::test]
+async fn test_session_notification_routes_registered_sessions_to_their_channels() {
+    use agent_client_protocol::{Client, SessionNotification};
+
+    use super::{SessionChannels, SharedSessionRoutes};
+
+    let events = Rc::new(RefCell::new(SessionEventStore::default()));
+    let routes: SharedSessionRoutes = Rc::default();
+    let routed = SessionChannels::new();
+    routes
+        .borrow_mut()
+        .insert("routed-session".to_string(), routed.clone());
+    let client = AcpClient::new(
+        Rc::clone(&events),
+        Rc::new(RefCell::new(Instant::now())),
+        Rc::new(RefCell::new(Instant::now())),
+    )
+    .with_session_routes(Rc::clone(&routes));
+
+    for (session_id, text) in [("routed-session", "mine"), ("other-session", "theirs")] {
+        let chunk = ContentChunk::new(ContentBlock::Text(TextContent::new(text)));
+        let notification =
+            SessionNotification::new(session_id, SessionUpdate::AgentMessageChunk(chunk));
+        client.session_notification(notification).await.unwrap();
+    }
+
+    let routed_events = routed.events.borrow().events();
+    assert!(matches!(
+        routed_events.as_slice(),
+        [SessionEvent::AgentMessage(text)] if text == "mine"
+    ));
+    let shared_events = events.borrow().events();
+    assert!(matches!(
+        shared_events.as_slice(),
+        [SessionEvent::AgentMessage(text)] if text == "theirs"
+    ));
+}
@@ -0,0 +1,260 @@
+//! Client side of the ACP `fs/read_text_file` and `fs/write_text_file`
+//! methods.
+//!
+//! Agents that see the `fs` client capability read and write files through
+//! CSA instead of touching the disk themselves. Writes are confined to the
+//! project root, which also holds for runs without the filesystem sandbox,
+//! and the permission policy's `auto_deny` globs and file restrictions apply
+//! to both methods. Under a filesystem sandbox, requests are also held to
+//! what the sandbox lets the agent reach itself: reads to the plan's project
+//! root, writable and readable paths, writes to its writable paths. Every
+//! request leaves a `FileAccess` session event, so a run keeps an audit trail
+//! of the files its agent touched.
+
+use std::{
+    cell::RefCell,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+
+use csa_resource::{filesystem_sandbox::FilesystemCapability, isolation_plan::IsolationPlan};
+
+use crate::{client::SessionEvent, permissions::PermissionEngine};
+
+/// The filesystem sandbox of the connection's agent, once it was spawned.
+pub(crate) type SharedFsSandbox = Rc<RefCell<Option<FsSandbox>>>;
+
+/// The paths a filesystem-sandboxed agent can reach on its own.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FsSandbox {
+    readable: Vec<PathBuf>,
+    writable: Vec<PathBuf>,
+}
+
+impl FsSandbox {
+    /// `None` when `plan` has no filesystem sandbox.
+    pub(crate) fn from_plan(plan: &IsolationPlan) -> Option<Self> {
+        if plan.filesystem == FilesystemCapability::None {
+            return None;
+        }
+        let writable: Vec<PathBuf> = plan
+            .writable_paths
+            .iter()
+            .filter(|path| !plan.readonly_project_root || plan.project_root.as_ref() != Some(*path))
+            .cloned()
+            .collect();
+        let readable = plan
+            .project_root
+            .iter()
+            .chain(&plan.writable_paths)
+            .chain(&plan.readable_paths)
+            .cloned()
+            .collect();
+        Some(Self { readable, writable })
+    }
+
+    /// Whether the agent can write anywhere in the sandbox.
+    pub(crate) fn allows_writes(&self) -> bool {
+        !self.writable.is_empty()
+    }
+
+    fn can_read(&self, path: &Path) -> bool {
+        self.readable.iter().any(|root| is_within(root, path))
+    }
+
+    fn can_write(&self, path: &Path) -> bool {
+        self.writable.iter().any(|root| is_within(root, path))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FsOperation {
+    Read,
+    Create,
+    Modify,
+}
+
+impl FsOperation {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Create => "create",
+            Self::Modify => "modify",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum FsError {
+    Denied(&'static str),
+    Io(io::Error),
+}
+
+impl FsError {
+    fn status(&self) -> &'static str {
+        match self {
+            Self::Denied(_) => "denied",
+            Self::Io(_) => "error",
+        }
+    }
+
+    pub(crate) fn into_rpc_error(self, path: &Path) -> agent_client_protocol::Error {
+        match self {
+            Self::Denied(reason) => agent_client_protocol::Error::invalid_params()
+                .data(format!("{}: {reason}", path.display())),
+            Self::Io(error) => agent_client_protocol::Error::internal_error()
+                .data(format!("{}: {error}", path.display())),
+        }
+    }
+}
+
+/// The event recording one fs request and how it ended.
+pub(crate) fn access_event<T>(
+    path: &Path,
+    operation: FsOperation,
+    result: &Result<T, FsError>,
+) -> SessionEvent {
+    SessionEvent::FileAccess {
+        path: path.display().to_string(),
+        operation: operation.as_str().to_string(),
+        status: result
+            .as_ref()
+            .map_or_else(FsError::status, |_| "ok")
+            .to_string(),
+    }
+}
+
+/// What the agent may reach through the client during one run.
+pub(crate) struct FsScope<'a> {
+    root: Option<&'a Path>,
+    engine: Option<&'a PermissionEngine>,
+    sandbox: Option<&'a FsSandbox>,
+}
+
+impl<'a> FsScope<'a> {
+    /// The policy's project root takes precedence over the agent's working
+    /// directory.
+    pub(crate) fn new(working_dir: Option<&'a Path>, engine: Option<&'a PermissionEngine>) -> Self {
+        let root = engine
+            .and_then(PermissionEngine::project_root)
+            .or(working_dir);
+        Self {
+            root,
+            engine,
+            sandbox: None,
+        }
+    }
+
+    /// Hold requests to what the agent's filesystem sandbox allows.
+    pub(crate) fn with_sandbox(mut self, sandbox: Option<&'a FsSandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// `path` made absolute against the root, with `.` and `..` removed.
+    pub(crate) fn resolve(&self, path: &Path) -> PathBuf {
+        normalize(&self.root.unwrap_or(Path::new("")).join(path))
+    }
+
+    pub(crate) fn write_operation(path: &Path) -> FsOperation {
+        if path.exists() {
+            FsOperation::Modify
+        } else {
+            FsOperation::Create
+        }
+    }
+
+    /// Read `path`, optionally only `limit` lines from the 1-based `line`.
+    pub(crate) fn read_text(
+        &self,
+        path: &Path,
+        line: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<String, FsError> {
+        if self.sandbox.is_some_and(|sandbox| !sandbox.can_read(path)) {
+            return Err(FsError::Denied(
+                "path is not readable in the agent's sandbox",
+            ));
+        }
+        if let Some(reason) = self.engine.and_then(|engine| engine.file_read_denial(path)) {
+            return Err(FsError::Denied(reason));
+        }
+        let content = fs::read_to_string(path).map_err(FsError::Io)?;
+        Ok(slice_lines(&content, line, limit))
+    }
+
+    pub(crate) fn write_text(
+        &self,
+        path: &Path,
+        operation: FsOperation,
+        content: &str,
+    ) -> Result<(), FsError> {
+        let root = self
+            .root
+            .ok_or(FsError::Denied("no project root to confine writes to"))?;
+        if !is_within(root, path) {
+            return Err(FsError::Denied("path is outside the project root"));
+        }
+        if self.sandbox.is_some_and(|sandbox| !sandbox.can_write(path)) {
+            return Err(FsError::Denied(
+                "path is not writable in the agent's sandbox",
+            ));
+        }
+        if let Some(reason) = self
+            .engine
+            .and_then(|engine| engine.file_write_denial(path, operation == FsOperation::Create))
+        {
+            return Err(FsError::Denied(reason));
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(FsError::Io)?;
+        }
+        fs::write(path, content).map_err(FsError::Io)
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Whether `path` stays inside `root`, following symlinks along the part of
+/// `path` that already exists.
+fn is_within(root: &Path, path: &Path) -> bool {
+    let root = normalize(root);
+    if !path.starts_with(&root) {
+        return false;
+    }
+    let Ok(canonical_root) = root.canonicalize() else {
+        return false;
+    };
+    path.ancestors()
+        .find_map(|existing| existing.canonicalize().ok())
+        .is_some_and(|canonical| canonical.starts_with(&canonical_root))
+}
+
+fn slice_lines(content: &str, line: Option<u32>, limit: Option<u32>) -> String {
+    if line.is_none() && limit.is_none() {
+        return content.to_string();
+    }
+    let start = line.map_or(0, |line| line.saturating_sub(1) as usize);
+    let lines = content.split_inclusive('\n').skip(start);
+    match limit {
+        Some(limit) => lines.take(limit as usize).collect(),
+        None => lines.collect(),
+    }
+}
+
+#[cfg(test)]
+#[path = "client_fs_tests.rs"]
+mod tests;
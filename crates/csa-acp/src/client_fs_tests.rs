@@ -0,0 +1,181 @@
+use super::*;
+use csa_core::acp_permissions::{AcpPermissionPolicy, AcpPermissionsConfig};
+
+fn engine(root: &Path, auto_deny: &[&str], allow_write_new_files: bool) -> PermissionEngine {
+    PermissionEngine::new(AcpPermissionPolicy {
+        config: AcpPermissionsConfig {
+            auto_deny: auto_deny.iter().map(ToString::to_string).collect(),
+            ..AcpPermissionsConfig::default()
+        },
+        allow_edit_existing_files: true,
+        allow_write_new_files,
+        project_root: Some(root.to_path_buf()),
+    })
+}
+
+#[test]
+fn writes_are_confined_to_the_project_root() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let outside = tempfile::tempdir().expect("tempdir");
+    let scope = FsScope::new(Some(root.path()), None);
+
+    let inside = scope.resolve(Path::new("src/./new/../lib.rs"));
+    assert_eq!(inside, root.path().join("src/lib.rs"));
+    assert_eq!(FsScope::write_operation(&inside), FsOperation::Create);
+    scope
+        .write_text(&inside, FsOperation::Create, "fn main() {}\n")
+        .expect("write inside root");
+    assert_eq!(FsScope::write_operation(&inside), FsOperation::Modify);
+
+    let escaped = scope.resolve(Path::new("../escape.txt"));
+    assert!(matches!(
+        scope.write_text(&escaped, FsOperation::Create, "x"),
+        Err(FsError::Denied(_))
+    ));
+    let absolute = outside.path().join("file.txt");
+    assert!(matches!(
+        scope.write_text(&absolute, FsOperation::Create, "x"),
+        Err(FsError::Denied(_))
+    ));
+    assert!(!absolute.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn writes_through_symlinks_leaving_the_root_are_denied() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let outside = tempfile::tempdir().expect("tempdir");
+    std::os::unix::fs::symlink(outside.path(), root.path().join("link")).expect("symlink");
+    let scope = FsScope::new(Some(root.path()), None);
+
+    let path = scope.resolve(Path::new("link/file.txt"));
+    assert!(matches!(
+        scope.write_text(&path, FsOperation::Create, "x"),
+        Err(FsError::Denied(_))
+    ));
+    assert!(!outside.path().join("file.txt").exists());
+}
+
+#[test]
+fn policy_restrictions_apply_to_reads_and_writes() {
+    let root = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(root.path().join("secrets")).expect("mkdir");
+    std::fs::write(root.path().join("secrets/key"), "k").expect("write");
+    let engine = engine(root.path(), &["secrets/**"], false);
+    let scope = FsScope::new(None, Some(&engine));
+
+    let secret = scope.resolve(Path::new("secrets/key"));
+    assert!(matches!(
+        scope.read_text(&secret, None, None),
+        Err(FsError::Denied(_))
+    ));
+    let new_file = scope.resolve(Path::new("notes.md"));
+    assert!(matches!(
+        scope.write_text(&new_file, FsOperation::Create, "x"),
+        Err(FsError::Denied(_))
+    ));
+}
+
+#[test]
+fn reads_honour_line_and_limit() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let path = root.path().join("a.txt");
+    std::fs::write(&path, "one\ntwo\nthree\n").expect("write");
+    let scope = FsScope::new(Some(root.path()), None);
+
+    assert_eq!(
+        scope.read_text(&path, None, None).expect("read"),
+        "one\ntwo\nthree\n"
+    );
+    assert_eq!(
+        scope.read_text(&path, Some(2), Some(1)).expect("read"),
+        "two\n"
+    );
+    assert_eq!(
+        scope.read_text(&path, None, Some(2)).expect("read"),
+        "one\ntwo\n"
+    );
+    assert!(matches!(
+        scope.read_text(&root.path().join("missing"), None, None),
+        Err(FsError::Io(_))
+    ));
+}
+
+#[test]
+fn access_events_record_the_outcome() {
+    let path = Path::new("/repo/src/lib.rs");
+    let ok: Result<(), FsError> = Ok(());
+    let denied: Result<(), FsError> = Err(FsError::Denied("no"));
+    for (result, expected) in [(ok, "ok"), (denied, "denied")] {
+        match access_event(path, FsOperation::Modify, &result) {
+            SessionEvent::FileAccess {
+                path,
+                operation,
+                status,
+            } => {
+                assert_eq!(path, "/repo/src/lib.rs");
+                assert_eq!(operation, "modify");
+                assert_eq!(status, expected);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+}
+
+fn sandbox_plan(root: &Path, readonly_project_root: bool) -> IsolationPlan {
+    IsolationPlan {
+        resource: csa_resource::sandbox::ResourceCapability::None,
+        filesystem: FilesystemCapability::Bwrap,
+        writable_paths: vec![root.to_path_buf()],
+        readable_paths: Vec::new(),
+        env_overrides: Default::default(),
+        degraded_reasons: Vec::new(),
+        memory_max_mb: None,
+        memory_swap_max_mb: None,
+        pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
+        readonly_project_root,
+        user_daemon_ipc: false,
+        project_root: Some(root.to_path_buf()),
+        soft_limit_percent: None,
+        memory_monitor_interval_seconds: None,
+    }
+}
+
+#[test]
+fn sandboxed_requests_stay_within_the_plan() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let outside = tempfile::tempdir().expect("tempdir");
+    let foreign = outside.path().join("notes.txt");
+    std::fs::write(&foreign, "x").expect("write");
+    let sandbox = FsSandbox::from_plan(&sandbox_plan(root.path(), false)).expect("sandbox");
+    let scope = FsScope::new(Some(root.path()), None).with_sandbox(Some(&sandbox));
+
+    assert!(matches!(
+        scope.read_text(&foreign, None, None),
+        Err(FsError::Denied(_))
+    ));
+    let inside = scope.resolve(Path::new("a.txt"));
+    scope
+        .write_text(&inside, FsOperation::Create, "a")
+        .expect("write inside the sandbox");
+    assert_eq!(scope.read_text(&inside, None, None).expect("read"), "a");
+
+    let readonly = FsSandbox::from_plan(&sandbox_plan(root.path(), true)).expect("sandbox");
+    assert!(!readonly.allows_writes());
+    let scope = FsScope::new(Some(root.path()), None).with_sandbox(Some(&readonly));
+    assert!(matches!(
+        scope.write_text(&inside, FsOperation::Modify, "b"),
+        Err(FsError::Denied(_))
+    ));
+    assert_eq!(scope.read_text(&inside, None, None).expect("read"), "a");
+
+    let unsandboxed = IsolationPlan {
+        filesystem: FilesystemCapability::None,
+        ..sandbox_plan(root.path(), true)
+    };
+    assert!(FsSandbox::from_plan(&unsandboxed).is_none());
+}
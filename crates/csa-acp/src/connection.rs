@@ -1,14 +1,15 @@
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     path::{Path, PathBuf},
     rc::Rc,
     time::{Duration, Instant},
 };
 
 use agent_client_protocol::{
-    Agent, ClientSideConnection, InitializeRequest, LoadSessionRequest, NewSessionRequest,
-    PromptRequest, ProtocolVersion, SessionId, StopReason,
+    Agent, CancelNotification, ClientSideConnection, InitializeRequest, LoadSessionRequest,
+    NewSessionRequest, PromptRequest, ProtocolVersion, SessionId, StopReason,
 };
+use csa_core::acp_permissions::AcpPermissionPolicy;
 #[cfg(test)]
 use csa_process::SpoolRotator;
 use csa_process::{
@@ -42,11 +43,24 @@ pub use connection_spawn::{AcpConnectionOptions, AcpSandboxRequest, AcpSpawnRequ
 pub(crate) mod connection_fork;
 pub use connection_fork::{CliForkResult, fork_session_via_cli};
 
+#[path = "connection_mux.rs"]
+mod connection_mux;
+
+#[path = "connection_reconnect.rs"]
+mod connection_reconnect;
+use connection_reconnect::RespawnSpec;
+pub use connection_reconnect::{DEFAULT_MAX_RECONNECTS, PromptAttempt, resume_prompt};
+
 use crate::{
+    capture::{FrameCapture, SharedCapture},
     client::{
-        SessionEvent, SharedActivity, SharedEvents, SharedToolOutputCompactor, StreamingMetadata,
+        SessionChannels, SessionEvent, SharedActivity, SharedEvents, SharedSessionRoutes,
+        SharedToolOutputCompactor, StreamingMetadata,
     },
+    client_fs::SharedFsSandbox,
     error::{AcpError, AcpResult},
+    permissions::{PermissionEngine, SharedPermissionEngine},
+    terminal::SharedTerminals,
     tool_output_compaction::ToolOutputCompactionConfig,
 };
 
@@ -93,10 +107,30 @@ pub struct AcpConnection {
     last_activity: SharedActivity,
     last_meaningful_activity: SharedActivity,
     tool_output_compactor: SharedToolOutputCompactor,
+    /// Per-session channels of sessions multiplexed over this connection.
+    session_routes: SharedSessionRoutes,
+    /// Policy consulted by the client for `session/request_permission`.
+    permission_engine: SharedPermissionEngine,
+    /// Filesystem sandbox the client holds fs requests to.
+    fs_sandbox: SharedFsSandbox,
+    /// Terminals the agent opened through the client.
+    terminals: SharedTerminals,
+    /// Where the JSON-RPC frames of the agent's stdio are recorded, if anywhere.
+    frame_capture: SharedCapture,
     stderr_buf: Rc<RefCell<String>>,
     default_working_dir: PathBuf,
     init_timeout: Duration,
     termination_grace_period: Duration,
+    /// Whether the agent advertised `session/load` in its initialize response.
+    load_session_supported: Cell<bool>,
+    /// How to start an identical agent process after a crash; `None` for
+    /// connections assembled from parts.
+    respawn: Option<RespawnSpec>,
+    /// Sandbox of a respawned process; the original spawn's handle is owned
+    /// by the caller.
+    reconnect_sandbox: Option<AcpSandboxHandle>,
+    reconnects: u32,
+    max_reconnects: u32,
 }
 
 impl AcpConnection {
@@ -117,6 +151,11 @@ impl AcpConnection {
         last_activity: SharedActivity,
         last_meaningful_activity: SharedActivity,
         tool_output_compactor: SharedToolOutputCompactor,
+        session_routes: SharedSessionRoutes,
+        permission_engine: SharedPermissionEngine,
+        fs_sandbox: SharedFsSandbox,
+        terminals: SharedTerminals,
+        frame_capture: SharedCapture,
         stderr_buf: Rc<RefCell<String>>,
         default_working_dir: PathBuf,
         options: AcpConnectionOptions,
@@ -129,17 +168,58 @@ impl AcpConnection {
             last_activity,
             last_meaningful_activity,
             tool_output_compactor,
+            session_routes,
+            permission_engine,
+            fs_sandbox,
+            terminals,
+            frame_capture,
             stderr_buf,
             default_working_dir,
             init_timeout: options.init_timeout,
             termination_grace_period: options.termination_grace_period,
+            load_session_supported: Cell::new(false),
+            respawn: None,
+            reconnect_sandbox: None,
+            reconnects: 0,
+            max_reconnects: options.max_reconnects,
+        }
+    }
+
+    /// Record how the agent was spawned, for reconnects and for the
+    /// terminal commands it starts.
+    fn set_respawn(&mut self, spec: RespawnSpec) {
+        self.terminals.set_launch(spec.terminal_launch());
+        *self.fs_sandbox.borrow_mut() = spec.fs_sandbox();
+        self.respawn = Some(spec);
+    }
+
+    /// Decide the agent's permission requests with `policy`; `None` grants
+    /// the first offered option.
+    pub fn set_permission_policy(&self, policy: Option<AcpPermissionPolicy>) {
+        *self.permission_engine.borrow_mut() = policy.map(PermissionEngine::new);
+    }
+
+    /// Append every JSON-RPC frame exchanged with the agent to `path`, see
+    /// [`crate::capture`]. Call before [`Self::initialize`] to record the
+    /// whole conversation. A capture that cannot be opened is logged and
+    /// skipped; it never fails the run.
+    pub fn capture_frames(&self, path: &Path) {
+        match FrameCapture::create(path) {
+            Ok(capture) => *self.frame_capture.borrow_mut() = Some(capture),
+            Err(error) => tracing::warn!(
+                path = %path.display(),
+                %error,
+                "failed to open ACP frame capture"
+            ),
         }
     }
 
     pub async fn initialize(&self) -> AcpResult<()> {
         self.ensure_process_running().await?;
 
-        let request = InitializeRequest::new(ProtocolVersion::LATEST);
+        let request = InitializeRequest::new(ProtocolVersion::LATEST).client_capabilities(
+            crate::client::client_capabilities(self.fs_sandbox.borrow().as_ref()),
+        );
         let result = self
             .local_set
             .run_until(async {
@@ -151,7 +231,11 @@ impl AcpConnection {
             .await;
 
         match result {
-            Some(Ok(_response)) => Ok(()),
+            Some(Ok(response)) => {
+                self.load_session_supported
+                    .set(response.agent_capabilities.load_session);
+                Ok(())
+            }
             Some(Err(err)) => {
                 let stderr = self.stderr();
                 Err(AcpError::InitializationFailed(format!(
@@ -330,14 +414,25 @@ impl AcpConnection {
     ) -> AcpResult<PromptResult> {
         self.ensure_process_running().await?;
 
-        self.events.borrow_mut().clear();
-        *self.tool_output_compactor.borrow_mut() = io
+        // A multiplexed session has channels of its own; a timeout then
+        // cancels only its turn instead of killing the shared process.
+        let routed = self.session_channels(session_id);
+        let multiplexed = routed.is_some();
+        let SessionChannels {
+            events,
+            last_activity,
+            last_meaningful_activity,
+            tool_output_compactor,
+        } = routed.unwrap_or_else(|| self.default_channels());
+
+        events.borrow_mut().clear();
+        *tool_output_compactor.borrow_mut() = io
             .tool_output_compaction
             .clone()
             .map(ToolOutputCompactionConfig::into_state);
         let now = Instant::now();
-        *self.last_activity.borrow_mut() = now;
-        *self.last_meaningful_activity.borrow_mut() = now;
+        *last_activity.borrow_mut() = now;
+        *last_meaningful_activity.borrow_mut() = now;
         let execution_start = Instant::now();
         let heartbeat_interval = resolve_heartbeat_interval();
         let mut last_heartbeat = execution_start;
@@ -356,7 +451,11 @@ impl AcpConnection {
         enum PromptOutcome<T> {
             Completed(T),
             IdleTimeout,
+            /// Interrupted, and the agent did not end the turn after `session/cancel`.
+            CancelTimeout,
         }
+        let mut interrupt = crate::interrupt::subscribe();
+        let mut cancel_deadline: Option<Instant> = None;
         let outcome = self
             .local_set
             .run_until(async {
@@ -366,7 +465,7 @@ impl AcpConnection {
                     tokio::select! {
                         response = &mut prompt_future => {
                             let _ = stream_new_agent_messages(
-                                &self.events,
+                                &events,
                                 &mut processed_event_count,
                                 io.stream_stdout_to_stderr,
                                 &mut output_spool,
@@ -376,9 +475,18 @@ impl AcpConnection {
                             );
                             break PromptOutcome::Completed(response);
                         }
+                        _ = interrupt.changed(), if cancel_deadline.is_none() => {
+                            tracing::info!(session_id, "prompt interrupted; sending session/cancel");
+                            let notification =
+                                CancelNotification::new(SessionId::new(session_id.to_string()));
+                            if let Err(error) = self.connection.cancel(notification).await {
+                                tracing::warn!(%error, "session/cancel failed");
+                            }
+                            cancel_deadline = Some(Instant::now() + crate::interrupt::CANCEL_GRACE);
+                        }
                         _ = tokio::time::sleep(Duration::from_millis(200)) => {
                             let saw_progress_this_poll = stream_new_agent_messages(
-                                &self.events,
+                                &events,
                                 &mut processed_event_count,
                                 io.stream_stdout_to_stderr,
                                 &mut output_spool,
@@ -392,7 +500,7 @@ impl AcpConnection {
                             if process_tree_made_cpu_progress(process_activity.as_mut()) {
                                 let now = Instant::now();
                                 // CPU progress is a liveness signal, not an initial-response signal.
-                                *self.last_activity.borrow_mut() = now;
+                                *last_activity.borrow_mut() = now;
                             }
                             let (effective_timeout, timeout_phase, last_relevant_activity) =
                                 if !saw_initial_response_event {
@@ -401,13 +509,13 @@ impl AcpConnection {
                                         (
                                             irt,
                                             TimeoutPhase::InitialResponse,
-                                            *self.last_meaningful_activity.borrow(),
+                                            *last_meaningful_activity.borrow(),
                                         )
                                     } else {
-                                        (idle_timeout, TimeoutPhase::Idle, *self.last_activity.borrow())
+                                        (idle_timeout, TimeoutPhase::Idle, *last_activity.borrow())
                                     }
                                 } else {
-                                    (idle_timeout, TimeoutPhase::Idle, *self.last_activity.borrow())
+                                    (idle_timeout, TimeoutPhase::Idle, *last_activity.borrow())
                                 };
                             maybe_emit_heartbeat(
                                 heartbeat_interval,
@@ -417,6 +525,9 @@ impl AcpConnection {
                                 effective_timeout,
                                 timeout_phase,
                             );
+                            if cancel_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                                break PromptOutcome::CancelTimeout;
+                            }
                             if last_relevant_activity.elapsed() >= effective_timeout {
                                 break PromptOutcome::IdleTimeout;
                             }
@@ -427,7 +538,7 @@ impl AcpConnection {
             .await;
 
         let _ = stream_new_agent_messages(
-            &self.events,
+            &events,
             &mut processed_event_count,
             io.stream_stdout_to_stderr,
             &mut output_spool,
@@ -435,7 +546,7 @@ impl AcpConnection {
             &mut stdout_line_buf,
             &mut thought_line_buf,
         );
-        self.tool_output_compactor.borrow_mut().take();
+        tool_output_compactor.borrow_mut().take();
         if let Some(writer) = output_spool.take() {
             match writer.finalize() {
                 Ok(plan) => {
@@ -451,10 +562,10 @@ impl AcpConnection {
         // Return the retained tail only.  Total event counts and command/tool
         // metadata are tracked incrementally in `StreamingMetadata`.
         {
-            let events_ref = self.events.borrow();
+            let events_ref = events.borrow();
             metadata.sync_from_store(&events_ref);
         }
-        let events = self.events.borrow_mut().take_events();
+        let events = events.borrow_mut().take_events();
         let output = collect_agent_output(&mut metadata);
         match outcome {
             PromptOutcome::Completed(Ok(response)) => Ok(PromptResult {
@@ -469,7 +580,11 @@ impl AcpConnection {
                 Err(AcpError::PromptFailed(format!("{err}{stderr_detail}")))
             }
             PromptOutcome::IdleTimeout => {
-                let _ = self.kill().await;
+                if multiplexed {
+                    let _ = self.cancel_session(session_id).await;
+                } else {
+                    let _ = self.kill().await;
+                }
                 let exit_reason =
                     if !saw_initial_response_event && initial_response_timeout.is_some() {
                         "initial_response_timeout"
@@ -484,6 +599,22 @@ impl AcpConnection {
                     metadata,
                 })
             }
+            PromptOutcome::CancelTimeout => {
+                tracing::warn!(
+                    session_id,
+                    "agent did not end the cancelled turn in time; signalling it"
+                );
+                if !multiplexed {
+                    let _ = self.kill().await;
+                }
+                Ok(PromptResult {
+                    output,
+                    events,
+                    exit_reason: Some("cancelled".to_string()),
+                    timed_out: false,
+                    metadata,
+                })
+            }
         }
     }
 
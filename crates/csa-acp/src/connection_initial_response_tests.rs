@@ -352,6 +352,11 @@ async fn build_test_connection(
         last_activity,
         last_meaningful_activity,
         Rc::new(RefCell::new(None)),
+        Rc::default(),
+        Rc::default(),
+        Rc::default(),
+        Rc::default(),
+        Rc::default(),
         stderr_buf,
         std::env::current_dir().expect("cwd"),
         AcpConnectionOptions {
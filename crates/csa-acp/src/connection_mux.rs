@@ -0,0 +1,58 @@
+//! Several ACP sessions over one agent process.
+//!
+//! The agent tags every `session/update` with its session ID, so each
+//! registered session gets its own event store and activity clocks and
+//! concurrent prompts on other sessions never see its output. Sessions that
+//! were not registered share the connection-wide channels.
+
+use agent_client_protocol::{Agent, CancelNotification, SessionId};
+
+use super::{AcpConnection, SessionChannels};
+use crate::error::{AcpError, AcpResult};
+
+impl AcpConnection {
+    /// Give `session_id` channels of its own for prompts over this connection.
+    pub(crate) fn register_session(&self, session_id: &str) {
+        self.session_routes
+            .borrow_mut()
+            .insert(session_id.to_string(), SessionChannels::new());
+    }
+
+    pub(crate) fn unregister_session(&self, session_id: &str) {
+        self.session_routes.borrow_mut().remove(session_id);
+    }
+
+    /// Number of sessions currently registered on this connection.
+    pub fn multiplexed_sessions(&self) -> usize {
+        self.session_routes.borrow().len()
+    }
+
+    pub(crate) fn session_channels(&self, session_id: &str) -> Option<SessionChannels> {
+        self.session_routes.borrow().get(session_id).cloned()
+    }
+
+    pub(crate) fn default_channels(&self) -> SessionChannels {
+        SessionChannels {
+            events: self.events.clone(),
+            last_activity: self.last_activity.clone(),
+            last_meaningful_activity: self.last_meaningful_activity.clone(),
+            tool_output_compactor: self.tool_output_compactor.clone(),
+        }
+    }
+
+    /// Whether the agent process is still alive.
+    pub fn is_running(&self) -> bool {
+        matches!(self.child.borrow_mut().try_wait(), Ok(None))
+    }
+
+    /// Cancel the in-flight turn of one session with `session/cancel`,
+    /// leaving the process and its other sessions running.
+    pub async fn cancel_session(&self, session_id: &str) -> AcpResult<()> {
+        self.ensure_process_running().await?;
+        let notification = CancelNotification::new(SessionId::new(session_id.to_string()));
+        self.local_set
+            .run_until(self.connection.cancel(notification))
+            .await
+            .map_err(|err| AcpError::PromptFailed(format!("session/cancel failed: {err}")))
+    }
+}
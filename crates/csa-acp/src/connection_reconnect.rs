@@ -0,0 +1,332 @@
+//! Reconnecting to an agent whose process died mid-turn.
+//!
+//! A crashed agent closes stdout, so the in-flight `session/prompt` fails
+//! with a transport error. When the agent advertised `session/load`, an
+//! identical process is respawned, the provider session is loaded back and
+//! the interrupted turn is sent again with a note that it is a resume. Exits
+//! by SIGKILL or SIGTERM are left alone: those come from the OOM killer,
+//! CSA's own resource limits, or the user.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    time::{Duration, Instant},
+};
+
+use csa_resource::isolation_plan::IsolationPlan;
+use tracing::{info, warn};
+
+use super::{
+    AcpConnection, AcpConnectionOptions, AcpSandboxHandle, AcpSandboxRequest, AcpSpawnRequest,
+    PromptIoOptions, PromptResult, connection_status::exit_signal,
+};
+use crate::{
+    client_fs::FsSandbox,
+    error::{AcpError, AcpResult},
+    terminal::{TerminalLaunch, TerminalSandbox},
+};
+
+/// Default for [`AcpConnectionOptions::max_reconnects`].
+pub const DEFAULT_MAX_RECONNECTS: u32 = 2;
+
+/// How long to wait for the child to be reaped after the transport closed.
+const EXIT_REAP_TIMEOUT: Duration = Duration::from_millis(500);
+
+const SIGTERM: i32 = 15;
+const SIGKILL: i32 = 9;
+
+/// Owned copy of the spawn request, replayed to start a replacement process.
+#[derive(Debug, Clone)]
+pub(crate) struct RespawnSpec {
+    command: String,
+    args: Vec<String>,
+    working_dir: PathBuf,
+    env: HashMap<String, String>,
+    options: AcpConnectionOptions,
+    sandbox: Option<RespawnSandbox>,
+}
+
+#[derive(Debug, Clone)]
+struct RespawnSandbox {
+    isolation_plan: IsolationPlan,
+    tool_name: String,
+    session_id: String,
+    env_overrides: Option<HashMap<String, String>>,
+}
+
+impl RespawnSpec {
+    pub(crate) fn new(
+        request: AcpSpawnRequest<'_>,
+        sandbox: Option<&AcpSandboxRequest<'_>>,
+    ) -> Self {
+        Self {
+            command: request.command.to_string(),
+            args: request.args.to_vec(),
+            working_dir: request.working_dir.to_path_buf(),
+            env: request.env.clone(),
+            options: request.options,
+            sandbox: sandbox.map(|sandbox| RespawnSandbox {
+                isolation_plan: sandbox.isolation_plan.clone(),
+                tool_name: sandbox.tool_name.to_string(),
+                session_id: sandbox.session_id.to_string(),
+                env_overrides: sandbox.env_overrides.cloned(),
+            }),
+        }
+    }
+
+    pub(crate) fn terminal_launch(&self) -> TerminalLaunch {
+        TerminalLaunch {
+            working_dir: self.working_dir.clone(),
+            env: AcpConnection::merge_sandbox_env(
+                &self.env,
+                self.sandbox
+                    .as_ref()
+                    .and_then(|sandbox| sandbox.env_overrides.as_ref()),
+            ),
+            sandbox: self.sandbox.as_ref().map(|sandbox| TerminalSandbox {
+                isolation_plan: sandbox.isolation_plan.clone(),
+                tool_name: sandbox.tool_name.clone(),
+                session_id: sandbox.session_id.clone(),
+            }),
+        }
+    }
+
+    pub(crate) fn fs_sandbox(&self) -> Option<FsSandbox> {
+        self.sandbox
+            .as_ref()
+            .and_then(|sandbox| FsSandbox::from_plan(&sandbox.isolation_plan))
+    }
+
+    async fn spawn(&self, attempt: u32) -> AcpResult<(AcpConnection, Option<AcpSandboxHandle>)> {
+        let request = AcpSpawnRequest {
+            command: &self.command,
+            args: &self.args,
+            working_dir: &self.working_dir,
+            env: &self.env,
+            options: self.options,
+        };
+        let Some(sandbox) = &self.sandbox else {
+            let conn = AcpConnection::spawn_with_options(
+                request.command,
+                request.args,
+                request.working_dir,
+                request.env,
+                request.options,
+            )
+            .await?;
+            return Ok((conn, None));
+        };
+        // The original scope is still owned (and eventually stopped) by the
+        // caller's handle, so the replacement gets a scope of its own.
+        let scope_session_id = format!("{}-reconnect-{attempt}", sandbox.session_id);
+        let (conn, handle) = AcpConnection::spawn_sandboxed(
+            request,
+            Some(AcpSandboxRequest {
+                isolation_plan: &sandbox.isolation_plan,
+                tool_name: &sandbox.tool_name,
+                session_id: &scope_session_id,
+                env_overrides: sandbox.env_overrides.as_ref(),
+            }),
+        )
+        .await?;
+        Ok((conn, Some(handle)))
+    }
+}
+
+/// Whether an agent that exited with `status` may be respawned.
+pub(crate) fn is_reconnectable_exit(status: ExitStatus) -> bool {
+    !matches!(exit_signal(status), Some(SIGKILL | SIGTERM))
+}
+
+/// Outcome of [`AcpConnection::prompt_or_respawn`].
+#[derive(Debug)]
+pub enum PromptAttempt {
+    /// The turn ended.
+    Finished(PromptResult),
+    /// The agent crashed and was respawned; the turn has to be resent.
+    Respawned,
+}
+
+/// Prompt text for re-sending a turn that was cut off by a crash.
+pub fn resume_prompt(original: &str) -> String {
+    format!(
+        "[CSA] The agent process crashed during the previous turn and was restarted \
+         with this session reloaded. Continue that turn from where it stopped without \
+         repeating completed work. The original request follows.\n\n{original}"
+    )
+}
+
+impl AcpConnection {
+    /// Number of times this connection has respawned its agent.
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnects
+    }
+
+    /// Exit status of an agent whose transport closed, waiting briefly for
+    /// the process to be reaped. `None` while the process is still running.
+    async fn exited_status(&self) -> Option<ExitStatus> {
+        let deadline = Instant::now() + EXIT_REAP_TIMEOUT;
+        loop {
+            if let Ok(Some(status)) = self.child.borrow_mut().try_wait() {
+                return Some(status);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// Respawn the agent and load `session_id` into the new process.
+    ///
+    /// Fails with [`AcpError::ReconnectFailed`] when the connection cannot be
+    /// respawned, the agent does not support `session/load`, or the new
+    /// process cannot load the session. `self` is left untouched on failure.
+    pub async fn reconnect(
+        &mut self,
+        session_id: &str,
+        working_dir: Option<&Path>,
+    ) -> AcpResult<()> {
+        self.try_reconnect(session_id, working_dir)
+            .await
+            .map_err(AcpError::ReconnectFailed)
+    }
+
+    async fn try_reconnect(
+        &mut self,
+        session_id: &str,
+        working_dir: Option<&Path>,
+    ) -> Result<(), String> {
+        let Some(spec) = self.respawn.clone() else {
+            return Err("connection was not spawned from a command CSA can restart".to_string());
+        };
+        if !self.load_session_supported.get() {
+            return Err(format!(
+                "agent does not advertise session/load, so session {session_id} cannot be resumed"
+            ));
+        }
+
+        let attempt = self.reconnects + 1;
+        let (mut fresh, sandbox) = spec
+            .spawn(attempt)
+            .await
+            .map_err(|error| format!("respawn attempt {attempt} failed: {error}"))?;
+        // The respawned agent's frames go to the same capture.
+        *fresh.frame_capture.borrow_mut() = self.frame_capture.borrow().clone();
+        let loaded = match fresh.initialize().await {
+            Ok(()) => fresh.load_session(session_id, working_dir).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = loaded {
+            let _ = fresh.kill().await;
+            return Err(format!(
+                "respawned agent could not load session {session_id}: {error}"
+            ));
+        }
+
+        fresh.reconnects = attempt;
+        fresh.max_reconnects = self.max_reconnects;
+        fresh.reconnect_sandbox = sandbox;
+        *fresh.permission_engine.borrow_mut() = self.permission_engine.borrow_mut().take();
+        // Reap whatever is left of the crashed process group before dropping it.
+        let _ = self.kill().await;
+        *self = fresh;
+        info!(
+            session_id,
+            attempt, "ACP agent respawned and session reloaded"
+        );
+        Ok(())
+    }
+
+    /// Like [`Self::prompt_with_io`], but when the agent crashes mid-turn it
+    /// is reconnected and the turn resumed, up to the connection's
+    /// `max_reconnects`.
+    pub async fn prompt_with_reconnect(
+        &mut self,
+        session_id: &str,
+        text: &str,
+        idle_timeout: Duration,
+        initial_response_timeout: Option<Duration>,
+        io: PromptIoOptions<'_>,
+    ) -> AcpResult<PromptResult> {
+        let mut turn = text.to_string();
+        loop {
+            match self
+                .prompt_or_respawn(
+                    session_id,
+                    &turn,
+                    idle_timeout,
+                    initial_response_timeout,
+                    io.clone(),
+                )
+                .await?
+            {
+                PromptAttempt::Finished(result) => return Ok(result),
+                PromptAttempt::Respawned => turn = resume_prompt(text),
+            }
+        }
+    }
+
+    /// One attempt of [`Self::prompt_with_reconnect`]: when the agent crashes
+    /// mid-turn it is respawned and [`PromptAttempt::Respawned`] returned, so
+    /// the caller can re-point anything that watches the agent process (see
+    /// [`Self::respawned_sandbox`]) before resending the turn with
+    /// [`resume_prompt`].
+    pub async fn prompt_or_respawn(
+        &mut self,
+        session_id: &str,
+        text: &str,
+        idle_timeout: Duration,
+        initial_response_timeout: Option<Duration>,
+        io: PromptIoOptions<'_>,
+    ) -> AcpResult<PromptAttempt> {
+        let error = match self
+            .prompt_with_io(session_id, text, idle_timeout, initial_response_timeout, io)
+            .await
+        {
+            Ok(result) => return Ok(PromptAttempt::Finished(result)),
+            Err(error) => error,
+        };
+        let Some(status) = self.exited_status().await else {
+            return Err(error);
+        };
+        if !is_reconnectable_exit(status) {
+            return Err(error);
+        }
+        if self.reconnects >= self.max_reconnects {
+            warn!(
+                session_id,
+                reconnects = self.reconnects,
+                error = %error,
+                "ACP agent crashed again; reconnect budget exhausted"
+            );
+            return Err(error);
+        }
+
+        warn!(
+            session_id,
+            attempt = self.reconnects + 1,
+            max_reconnects = self.max_reconnects,
+            error = %error,
+            "ACP agent crashed mid-turn; reconnecting to resume the turn"
+        );
+        if let Err(reconnect_error) = self.try_reconnect(session_id, None).await {
+            return Err(AcpError::ReconnectFailed(format!(
+                "{reconnect_error}; original error: {error}"
+            )));
+        }
+        Ok(PromptAttempt::Respawned)
+    }
+
+    /// Sandbox of the respawned agent; `None` until a sandboxed connection
+    /// reconnected. The original sandbox stays with the caller that spawned
+    /// the connection.
+    pub fn respawned_sandbox(&self) -> Option<&AcpSandboxHandle> {
+        self.reconnect_sandbox.as_ref()
+    }
+}
+
+#[cfg(test)]
+#[path = "connection_reconnect_tests.rs"]
+mod tests;
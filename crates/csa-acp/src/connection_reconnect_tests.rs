@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use super::*;
+
+#[cfg(unix)]
+#[test]
+fn exits_by_csa_or_oom_signals_are_not_reconnected() {
+    use std::os::unix::process::ExitStatusExt;
+
+    assert!(is_reconnectable_exit(ExitStatus::from_raw(1 << 8)));
+    assert!(is_reconnectable_exit(ExitStatus::from_raw(11)));
+    assert!(is_reconnectable_exit(ExitStatus::from_raw(6)));
+    assert!(!is_reconnectable_exit(ExitStatus::from_raw(SIGKILL)));
+    assert!(!is_reconnectable_exit(ExitStatus::from_raw(SIGTERM)));
+}
+
+#[test]
+fn resume_prompt_keeps_the_original_request() {
+    let prompt = resume_prompt("fix the failing test");
+    assert!(prompt.starts_with("[CSA] The agent process crashed"));
+    assert!(prompt.ends_with("\n\nfix the failing test"));
+}
+
+#[test]
+fn respawn_spec_gives_sandboxed_respawns_a_separate_scope() {
+    let env = HashMap::from([("PATH".to_string(), "/usr/bin".to_string())]);
+    let args = vec!["--acp".to_string()];
+    let plan = IsolationPlan {
+        resource: csa_resource::sandbox::ResourceCapability::Setrlimit,
+        filesystem: csa_resource::FilesystemCapability::None,
+        writable_paths: vec![PathBuf::from("/tmp")],
+        readable_paths: Vec::new(),
+        env_overrides: HashMap::new(),
+        degraded_reasons: Vec::new(),
+        memory_max_mb: Some(2048),
+        memory_swap_max_mb: None,
+        pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
+        readonly_project_root: false,
+        user_daemon_ipc: false,
+        project_root: None,
+        soft_limit_percent: None,
+        memory_monitor_interval_seconds: None,
+    };
+    let spec = RespawnSpec::new(
+        AcpSpawnRequest {
+            command: "agent",
+            args: &args,
+            working_dir: Path::new("/tmp"),
+            env: &env,
+            options: AcpConnectionOptions::default(),
+        },
+        Some(&AcpSandboxRequest {
+            isolation_plan: &plan,
+            tool_name: "codex",
+            session_id: "01HSESSION",
+            env_overrides: None,
+        }),
+    );
+
+    assert_eq!(spec.command, "agent");
+    assert_eq!(spec.args, args);
+    assert_eq!(spec.env, env);
+    assert_eq!(spec.options.max_reconnects, DEFAULT_MAX_RECONNECTS);
+    let sandbox = spec.sandbox.expect("sandbox kept");
+    assert_eq!(sandbox.tool_name, "codex");
+    assert_eq!(sandbox.session_id, "01HSESSION");
+    assert_eq!(sandbox.isolation_plan.memory_max_mb, Some(2048));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn reconnect_without_session_load_support_explains_why() {
+    let args = vec!["-c".to_string(), "exit 3".to_string()];
+    let mut connection = AcpConnection::spawn_with_options(
+        "sh",
+        &args,
+        Path::new("/tmp"),
+        &HashMap::new(),
+        AcpConnectionOptions {
+            termination_grace_period: Duration::ZERO,
+            ..AcpConnectionOptions::default()
+        },
+    )
+    .await
+    .expect("spawn sh");
+    assert!(connection.respawn.is_some());
+
+    let err = connection
+        .reconnect("sess-1", None)
+        .await
+        .expect_err("agent never advertised session/load");
+    assert!(matches!(err, AcpError::ReconnectFailed(_)));
+    assert!(
+        err.to_string()
+            .contains("does not advertise session/load, so session sess-1 cannot be resumed")
+    );
+    assert_eq!(connection.reconnect_count(), 0);
+    assert!(connection.respawned_sandbox().is_none());
+}
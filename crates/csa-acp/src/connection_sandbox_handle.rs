@@ -8,19 +8,28 @@
 ///   On drop, the guard calls `systemctl --user stop <scope>`, sending
 ///   `SIGTERM` to all processes in the scope.
 ///
+/// - **`CgroupDirect`**: The ACP process runs in a CSA-created cgroup that is
+///   killed and removed on drop.
+///
 /// - **`Rlimit`**: `RLIMIT_NPROC` was applied in the child's `pre_exec`.
 ///   This is a marker variant indicating rlimit-based PID isolation is active.
 ///
 /// - **`Bwrap`**: Bubblewrap filesystem sandbox is active.
 ///
+/// - **`SandboxExec`**: macOS Seatbelt filesystem sandbox is active.
+///
 /// - **`None`**: No sandbox active.
 pub enum AcpSandboxHandle {
     /// cgroup scope guard -- dropped to stop the scope.
     Cgroup(csa_resource::cgroup::CgroupScopeGuard),
+    /// Directly managed cgroup guard -- dropped to kill and remove the cgroup.
+    CgroupDirect(csa_resource::cgroup_direct::DirectCgroupGuard),
     /// Bubblewrap filesystem sandbox is active.
     Bwrap,
     /// Landlock LSM filesystem sandbox is active.
     Landlock,
+    /// macOS Seatbelt (`sandbox-exec`) filesystem sandbox is active.
+    SandboxExec,
     /// `RLIMIT_NPROC` was applied in child via `pre_exec`.
     Rlimit,
     /// No sandbox active.
@@ -30,8 +39,8 @@ pub enum AcpSandboxHandle {
 impl AcpSandboxHandle {
     /// Check if the OOM killer was triggered in the sandbox scope.
     ///
-    /// Only meaningful for the [`Cgroup`](Self::Cgroup) variant; returns
-    /// `false` for all others.  Must be called **before** the handle is
+    /// Only meaningful for the cgroup variants; returns `false` for all
+    /// others.  Must be called **before** the handle is
     /// dropped, as the cgroup scope is stopped on drop.
     pub fn check_oom_killed(&self) -> bool {
         self.check_oom_killed_with_signal(None)
@@ -42,6 +51,7 @@ impl AcpSandboxHandle {
     pub fn check_oom_killed_with_signal(&self, exit_signal: Option<i32>) -> bool {
         match self {
             Self::Cgroup(guard) => guard.check_oom_killed_with_signal(exit_signal),
+            Self::CgroupDirect(guard) => guard.check_oom_killed_with_signal(exit_signal),
             _ => false,
         }
     }
@@ -59,6 +69,7 @@ impl AcpSandboxHandle {
     pub fn oom_diagnosis_with_signal(&self, exit_signal: Option<i32>) -> Option<String> {
         match self {
             Self::Cgroup(guard) => guard.oom_diagnosis_with_signal(exit_signal),
+            Self::CgroupDirect(guard) => guard.oom_diagnosis_with_signal(exit_signal),
             _ => None,
         }
     }
@@ -70,6 +81,17 @@ impl AcpSandboxHandle {
     pub fn memory_peak_mb(&self) -> Option<u64> {
         match self {
             Self::Cgroup(guard) => guard.memory_peak_mb(),
+            Self::CgroupDirect(guard) => guard.memory_peak_mb(),
+            _ => None,
+        }
+    }
+
+    /// CPU time and block I/O of the cgroup scope; like
+    /// [`Self::memory_peak_mb`], only available before the handle is dropped.
+    pub fn usage(&self) -> Option<csa_resource::child_usage::ChildUsage> {
+        match self {
+            Self::Cgroup(guard) => guard.usage(),
+            Self::CgroupDirect(guard) => guard.usage(),
             _ => None,
         }
     }
@@ -78,6 +100,20 @@ impl AcpSandboxHandle {
     pub fn scope_name(&self) -> Option<&str> {
         match self {
             Self::Cgroup(guard) => Some(guard.scope_name()),
+            Self::CgroupDirect(guard) => Some(guard.scope_name()),
+            _ => None,
+        }
+    }
+
+    /// The `cgroup.procs` file of the agent's cgroup, for commands that have
+    /// to run under the agent's limits.
+    pub fn cgroup_procs_path(&self) -> Option<std::ffi::CString> {
+        match self {
+            Self::Cgroup(guard) => {
+                let procs = guard.cgroup_dir()?.join("cgroup.procs");
+                std::ffi::CString::new(procs.into_os_string().into_encoded_bytes()).ok()
+            }
+            Self::CgroupDirect(guard) => Some(guard.procs_path().to_owned()),
             _ => None,
         }
     }
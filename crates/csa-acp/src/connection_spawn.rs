@@ -22,11 +22,15 @@ use csa_resource::isolation_plan::IsolationPlan;
 use csa_resource::sandbox::ResourceCapability;
 
 use crate::{
-    client::{AcpClient, SessionEventStore, trim_tail_buffer},
+    capture::{CapturingReader, CapturingWriter, SharedCapture},
+    client::{AcpClient, SessionEventStore, SharedSessionRoutes, trim_tail_buffer},
+    client_fs::SharedFsSandbox,
     error::{AcpError, AcpResult},
+    permissions::SharedPermissionEngine,
+    terminal::SharedTerminals,
 };
 
-use super::{AcpConnection, AcpSandboxHandle};
+use super::{AcpConnection, AcpSandboxHandle, RespawnSpec};
 
 fn append_stderr_tail(stderr_buf: &mut String, chunk: &str) {
     stderr_buf.push_str(chunk);
@@ -39,6 +43,9 @@ pub struct AcpConnectionOptions {
     pub init_timeout: Duration,
     /// Grace period between SIGTERM and SIGKILL for forced termination.
     pub termination_grace_period: Duration,
+    /// How many times a crashed agent is respawned to resume its session
+    /// over the lifetime of the connection.
+    pub max_reconnects: u32,
 }
 
 impl Default for AcpConnectionOptions {
@@ -46,6 +53,7 @@ impl Default for AcpConnectionOptions {
         Self {
             init_timeout: Duration::from_secs(120),
             termination_grace_period: Duration::from_secs(5),
+            max_reconnects: super::DEFAULT_MAX_RECONNECTS,
         }
     }
 }
@@ -74,10 +82,11 @@ struct PreparedSandboxCommand {
     effective_env: HashMap<String, String>,
     landlock_paths: Option<Vec<PathBuf>>,
     has_bwrap: bool,
+    has_seatbelt: bool,
 }
 
 impl AcpConnection {
-    fn merge_sandbox_env(
+    pub(crate) fn merge_sandbox_env(
         base_env: &HashMap<String, String>,
         sandbox_env_overrides: Option<&HashMap<String, String>>,
     ) -> HashMap<String, String> {
@@ -110,12 +119,13 @@ impl AcpConnection {
         sandbox: &AcpSandboxRequest<'_>,
     ) -> PreparedSandboxCommand {
         let plan = sandbox.isolation_plan;
-        let effective_env = Self::merge_sandbox_env(request.env, sandbox.env_overrides);
+        let mut effective_env = Self::merge_sandbox_env(request.env, sandbox.env_overrides);
 
         // --- Filesystem axis: optionally wrap the command with bwrap ---
         // Landlock paths are captured here and applied in pre_exec later,
         // since Landlock operates on the calling thread (not via a wrapper binary).
         let mut landlock_paths: Option<Vec<PathBuf>> = None;
+        let mut has_seatbelt = false;
 
         let (effective_command, effective_args, has_bwrap) = match plan.filesystem {
             FilesystemCapability::Bwrap => {
@@ -140,6 +150,35 @@ impl AcpConnection {
                     (request.command.to_owned(), request.args.to_vec(), false)
                 }
             }
+            FilesystemCapability::SandboxExec => {
+                let seatbelt_plan = Self::merged_bwrap_isolation_plan(plan, sandbox.env_overrides);
+                if let Some(seatbelt_cmd) = csa_resource::seatbelt::from_isolation_plan(
+                    &seatbelt_plan,
+                    request.command,
+                    request.args,
+                ) {
+                    let program = seatbelt_cmd.get_program().to_string_lossy().to_string();
+                    let args: Vec<String> = seatbelt_cmd
+                        .get_args()
+                        .map(|a| a.to_string_lossy().to_string())
+                        .collect();
+                    // Unlike bwrap's --setenv, plan env travels on the wrapper itself.
+                    effective_env.extend(seatbelt_cmd.get_envs().filter_map(|(key, value)| {
+                        Some((
+                            key.to_string_lossy().into_owned(),
+                            value?.to_string_lossy().into_owned(),
+                        ))
+                    }));
+                    debug!("wrapped ACP command with sandbox-exec filesystem sandbox");
+                    has_seatbelt = true;
+                    (program, args, false)
+                } else {
+                    warn!(
+                        "sandbox-exec requested but from_isolation_plan returned None; proceeding without"
+                    );
+                    (request.command.to_owned(), request.args.to_vec(), false)
+                }
+            }
             FilesystemCapability::Landlock => {
                 debug!("Landlock filesystem isolation will be applied in pre_exec");
                 // Filter out project_root when readonly_project_root is set,
@@ -167,6 +206,7 @@ impl AcpConnection {
             effective_env,
             landlock_paths,
             has_bwrap,
+            has_seatbelt,
         }
     }
 
@@ -196,7 +236,18 @@ impl AcpConnection {
         options: AcpConnectionOptions,
     ) -> AcpResult<Self> {
         let cmd = Self::build_cmd(command, args, working_dir, env);
-        Self::spawn_with_cmd(cmd, working_dir, options).await
+        let mut conn = Self::spawn_with_cmd(cmd, working_dir, options).await?;
+        conn.set_respawn(RespawnSpec::new(
+            AcpSpawnRequest {
+                command,
+                args,
+                working_dir,
+                env,
+                options,
+            },
+            None,
+        ));
+        Ok(conn)
     }
 
     /// Spawn an ACP process with optional dual-axis isolation.
@@ -207,6 +258,8 @@ impl AcpConnection {
     /// ## Resource axis (`plan.resource`)
     ///
     /// - **CgroupV2**: Launched inside a systemd transient scope.
+    /// - **CgroupDirect**: Joins a CSA-created child of the delegated cgroup
+    ///   subtree from `pre_exec` (containers without systemd).
     /// - **Setrlimit**: `RLIMIT_NPROC` applied via `pre_exec`.
     /// - **None**: OOM score adjustment as last resort.
     ///
@@ -214,9 +267,18 @@ impl AcpConnection {
     ///
     /// - **Bwrap**: The ACP binary is wrapped with `bwrap(1)` via
     ///   [`csa_resource::bwrap::from_isolation_plan()`].
-    /// - **Landlock**: Reserved for Phase C (no-op).
+    /// - **SandboxExec** (macOS): The ACP binary is wrapped with
+    ///   `sandbox-exec(1)` via [`csa_resource::seatbelt::from_isolation_plan()`].
+    /// - **Landlock**: Rules applied in `pre_exec` so the child may only
+    ///   write to the plan's writable paths (project root, session dir,
+    ///   tool config dirs).  Used when bwrap is unavailable, e.g. on hosts
+    ///   without user namespaces or systemd.
     /// - **None**: No filesystem isolation.
     ///
+    /// `plan.network == NetworkMode::None` is honoured on every path:
+    /// `unshare --net` inside a cgroup scope, `--unshare-net`, `(deny network*)`
+    /// in the Seatbelt profile, or a pre-exec network namespace.
+    ///
     /// When `sandbox` is `None`, behavior is identical to [`Self::spawn`].
     ///
     /// Returns the connection and an [`AcpSandboxHandle`] that must be kept
@@ -224,6 +286,17 @@ impl AcpConnection {
     pub async fn spawn_sandboxed(
         request: AcpSpawnRequest<'_>,
         sandbox: Option<AcpSandboxRequest<'_>>,
+    ) -> AcpResult<(Self, AcpSandboxHandle)> {
+        let respawn = RespawnSpec::new(request, sandbox.as_ref());
+        let (mut conn, handle) = Self::spawn_sandboxed_once(request, sandbox).await?;
+        conn.set_respawn(respawn);
+        conn.terminals.set_agent_cgroup(handle.cgroup_procs_path());
+        Ok((conn, handle))
+    }
+
+    async fn spawn_sandboxed_once(
+        request: AcpSpawnRequest<'_>,
+        sandbox: Option<AcpSandboxRequest<'_>>,
     ) -> AcpResult<(Self, AcpSandboxHandle)> {
         let Some(sandbox) = sandbox else {
             let conn = Self::spawn_with_options(
@@ -244,7 +317,10 @@ impl AcpConnection {
             effective_env,
             mut landlock_paths,
             has_bwrap,
+            has_seatbelt,
         } = Self::prepare_sandbox_command(request, &sandbox);
+        // bwrap and the Seatbelt profile already cut the network; otherwise do it in pre_exec.
+        let private_network = plan.network.is_isolated() && !has_bwrap && !has_seatbelt;
 
         // --- Resource axis: apply resource isolation ---
         match plan.resource {
@@ -262,6 +338,15 @@ impl AcpConnection {
                     memory_max_mb: plan.memory_max_mb.unwrap_or(4096),
                     memory_swap_max_mb: plan.memory_swap_max_mb,
                     pids_max: plan.pids_max.or(Some(512)),
+                    cpu_max_percent: plan.cpu_max_percent,
+                    cpu_weight: plan.cpu_weight,
+                    // The scope runs the command under `unshare --net` unless
+                    // bwrap or Seatbelt already cut the network.
+                    network: if private_network {
+                        plan.network
+                    } else {
+                        csa_resource::NetworkMode::Full
+                    },
                 };
                 let scope_cmd = csa_resource::cgroup::create_scope_command_with_env(
                     sandbox.tool_name,
@@ -308,6 +393,68 @@ impl AcpConnection {
                 );
                 Ok((conn, AcpSandboxHandle::Cgroup(guard)))
             }
+            ResourceCapability::CgroupDirect => {
+                let root =
+                    csa_resource::cgroup_direct::prepare_delegated_root().map_err(|error| {
+                        AcpError::ConfigError(format!(
+                            "delegated cgroup v2 subtree is no longer usable: {error:#}"
+                        ))
+                    })?;
+                let cgroup_config = csa_resource::cgroup::SandboxConfig {
+                    memory_max_mb: plan.memory_max_mb.unwrap_or(4096),
+                    memory_swap_max_mb: plan.memory_swap_max_mb,
+                    pids_max: plan.pids_max.or(Some(512)),
+                    cpu_max_percent: plan.cpu_max_percent,
+                    cpu_weight: plan.cpu_weight,
+                    network: plan.network,
+                };
+                let guard = csa_resource::cgroup_direct::DirectCgroupGuard::create(
+                    &root,
+                    sandbox.tool_name,
+                    sandbox.session_id,
+                    &cgroup_config,
+                )
+                .map_err(|error| AcpError::ConfigError(format!("{error:#}")))?;
+
+                let mut cmd = Self::build_cmd_base(
+                    &effective_command,
+                    &effective_args,
+                    request.working_dir,
+                    &effective_env,
+                );
+
+                // Join the cgroup before entering a user namespace or applying
+                // Landlock, either of which would deny the cgroup.procs write.
+                // SAFETY: setsid(), open/write/close and Landlock syscalls are
+                //         async-signal-safe and run before exec.
+                #[cfg(unix)]
+                {
+                    let procs_path = guard.procs_path().to_owned();
+                    let ll_paths = landlock_paths.take();
+                    unsafe {
+                        cmd.pre_exec(move || {
+                            libc::setsid();
+                            csa_resource::cgroup_direct::join_cgroup(&procs_path)?;
+                            if private_network {
+                                csa_resource::network::enter_private_network()?;
+                            }
+                            if let Some(ref paths) = ll_paths {
+                                csa_resource::apply_landlock_rules(paths)
+                                    .map_err(std::io::Error::other)?;
+                            }
+                            Ok(())
+                        });
+                    }
+                }
+
+                let conn =
+                    Self::spawn_with_cmd_raw(cmd, request.working_dir, request.options).await?;
+                debug!(
+                    cgroup = %guard.scope_name(),
+                    "ACP process spawned inside directly managed cgroup"
+                );
+                Ok((conn, AcpSandboxHandle::CgroupDirect(guard)))
+            }
             ResourceCapability::Setrlimit => {
                 let mut cmd = Self::build_cmd_base(
                     &effective_command,
@@ -322,12 +469,18 @@ impl AcpConnection {
                 {
                     let rlimit_memory = plan.memory_max_mb.unwrap_or(0);
                     let rlimit_pids = plan.pids_max.map(u64::from);
+                    let cpu_max_percent = plan.cpu_max_percent;
                     let ll_paths = landlock_paths.take();
                     unsafe {
                         cmd.pre_exec(move || {
                             libc::setsid();
+                            if private_network {
+                                csa_resource::network::enter_private_network()?;
+                            }
                             csa_resource::rlimit::apply_rlimits(rlimit_memory, rlimit_pids)
                                 .map_err(std::io::Error::other)?;
+                            csa_resource::rlimit::apply_cpu_priority(cpu_max_percent)
+                                .map_err(std::io::Error::other)?;
                             if let Some(ref paths) = ll_paths {
                                 csa_resource::apply_landlock_rules(paths)
                                     .map_err(std::io::Error::other)?;
@@ -345,6 +498,8 @@ impl AcpConnection {
                     conn,
                     if has_bwrap {
                         AcpSandboxHandle::Bwrap
+                    } else if has_seatbelt {
+                        AcpSandboxHandle::SandboxExec
                     } else if has_landlock {
                         AcpSandboxHandle::Landlock
                     } else {
@@ -354,8 +509,8 @@ impl AcpConnection {
             }
             ResourceCapability::None => {
                 let has_landlock = landlock_paths.is_some();
-                if has_bwrap || has_landlock {
-                    // Filesystem sandbox active but no resource isolation.
+                if has_bwrap || has_seatbelt || has_landlock || private_network {
+                    // Filesystem or network sandbox active but no resource isolation.
                     let mut cmd = Self::build_cmd_base(
                         &effective_command,
                         &effective_args,
@@ -371,6 +526,9 @@ impl AcpConnection {
                         unsafe {
                             cmd.pre_exec(move || {
                                 libc::setsid();
+                                if private_network {
+                                    csa_resource::network::enter_private_network()?;
+                                }
                                 csa_resource::rlimit::apply_oom_score_adj()
                                     .map_err(std::io::Error::other)?;
                                 if let Some(ref paths) = ll_paths {
@@ -386,8 +544,12 @@ impl AcpConnection {
                         Self::spawn_with_cmd_raw(cmd, request.working_dir, request.options).await?;
                     let handle = if has_bwrap {
                         AcpSandboxHandle::Bwrap
-                    } else {
+                    } else if has_seatbelt {
+                        AcpSandboxHandle::SandboxExec
+                    } else if has_landlock {
                         AcpSandboxHandle::Landlock
+                    } else {
+                        AcpSandboxHandle::None
                     };
                     Ok((conn, handle))
                 } else {
@@ -502,18 +664,28 @@ impl AcpConnection {
         let last_activity = Rc::new(RefCell::new(Instant::now()));
         let last_meaningful_activity = Rc::new(RefCell::new(Instant::now()));
         let tool_output_compactor = Rc::new(RefCell::new(None));
+        let session_routes: SharedSessionRoutes = Rc::default();
+        let permission_engine: SharedPermissionEngine = Rc::default();
+        let fs_sandbox: SharedFsSandbox = Rc::default();
+        let terminals: SharedTerminals = Rc::default();
+        let frame_capture: SharedCapture = Rc::default();
         let client = AcpClient::new_with_tool_output_compactor(
             events.clone(),
             last_activity.clone(),
             last_meaningful_activity.clone(),
             tool_output_compactor.clone(),
-        );
+        )
+        .with_session_routes(session_routes.clone())
+        .with_permission_engine(permission_engine.clone())
+        .with_fs_root(working_dir.to_path_buf())
+        .with_fs_sandbox(fs_sandbox.clone())
+        .with_terminals(terminals.clone());
         let stderr_buf = Rc::new(RefCell::new(String::new()));
 
         let connection = local_set
             .run_until(async {
-                let outgoing = stdin.compat_write();
-                let incoming = stdout.compat();
+                let outgoing = CapturingWriter::new(stdin, frame_capture.clone()).compat_write();
+                let incoming = CapturingReader::new(stdout, frame_capture.clone()).compat();
                 let (conn, io_task) = agent_client_protocol::ClientSideConnection::new(
                     client,
                     outgoing,
@@ -565,6 +737,11 @@ impl AcpConnection {
             last_activity,
             last_meaningful_activity,
             tool_output_compactor,
+            session_routes,
+            permission_engine,
+            fs_sandbox,
+            terminals,
+            frame_capture,
             stderr_buf,
             working_dir.to_path_buf(),
             options,
@@ -114,6 +114,9 @@ fn merge_sandbox_env_scrubs_subtree_contract_from_env_overrides() {
         memory_max_mb: 4096,
         memory_swap_max_mb: None,
         pids_max: Some(512),
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: csa_resource::NetworkMode::Full,
     };
     let scope_cmd = csa_resource::cgroup::create_scope_command_with_env(
         "gemini-cli",
@@ -271,6 +274,10 @@ fn prepare_sandbox_command_merges_runtime_env_overrides_into_bwrap_invocation()
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: Some(PathBuf::from("/project")),
@@ -383,6 +390,10 @@ fn prepare_sandbox_command_scrubs_subtree_contract_from_bwrap_env_overrides() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: Some(PathBuf::from("/project")),
@@ -11,14 +11,14 @@ pub(crate) fn process_exited_error(status: ExitStatus, stderr: String) -> AcpErr
 }
 
 #[cfg(unix)]
-fn exit_signal(status: ExitStatus) -> Option<i32> {
+pub(crate) fn exit_signal(status: ExitStatus) -> Option<i32> {
     use std::os::unix::process::ExitStatusExt;
 
     status.signal()
 }
 
 #[cfg(not(unix))]
-fn exit_signal(_status: ExitStatus) -> Option<i32> {
+pub(crate) fn exit_signal(_status: ExitStatus) -> Option<i32> {
     None
 }
 
@@ -175,6 +175,30 @@ pub(crate) fn stream_new_agent_messages_with_tool_output_compaction(
                 }
                 spool_chunk(output_spool, rendered.as_bytes(), metadata);
             }
+            SessionEvent::PermissionRequest {
+                title, decision, ..
+            } => {
+                let msg = format!("[permission] {title} -> {decision}\n");
+                if stream_stdout_to_stderr {
+                    flush_remaining_buf(stdout_line_buf, "[stdout] ");
+                    flush_remaining_buf(thought_line_buf, "[thought] ");
+                    eprint!("{msg}");
+                }
+                spool_chunk(output_spool, msg.as_bytes(), metadata);
+            }
+            SessionEvent::FileAccess {
+                path,
+                operation,
+                status,
+            } => {
+                let msg = format!("[fs] {operation} {path} -> {status}\n");
+                if stream_stdout_to_stderr {
+                    flush_remaining_buf(stdout_line_buf, "[stdout] ");
+                    flush_remaining_buf(thought_line_buf, "[thought] ");
+                    eprint!("{msg}");
+                }
+                spool_chunk(output_spool, msg.as_bytes(), metadata);
+            }
             SessionEvent::Other(payload) => {
                 let msg = format!("[other] {payload}\n");
                 if stream_stdout_to_stderr {
@@ -21,6 +21,8 @@ pub enum AcpError {
         signal: Option<i32>,
         stderr: String,
     },
+    #[error("ACP reconnect failed: {0}")]
+    ReconnectFailed(String),
     #[error("Session fork failed: {0}")]
     ForkFailed(String),
     #[error("ACP subprocess spawn failed: {0}")]
@@ -29,6 +31,23 @@ pub enum AcpError {
     ConfigError(String),
 }
 
+impl AcpError {
+    /// Whether the agent broke the ACP exchange itself (connection, handshake,
+    /// session or prompt), as opposed to crashing, failing to spawn, or being
+    /// misconfigured.
+    pub fn is_protocol_error(&self) -> bool {
+        matches!(
+            self,
+            Self::ConnectionFailed(_)
+                | Self::InitializationFailed(_)
+                | Self::SessionFailed(_)
+                | Self::PromptFailed(_)
+                | Self::ReconnectFailed(_)
+                | Self::ForkFailed(_)
+        )
+    }
+}
+
 /// Format the exit reason: either "code N" or "killed by signal S (NAME)".
 fn format_exit_info(code: i32, signal: Option<i32>) -> String {
     if let Some(sig) = signal {
@@ -97,6 +116,31 @@ mod tests {
         assert!(err.source().is_none());
     }
 
+    #[test]
+    fn test_protocol_errors_exclude_crashes_and_spawn_failures() {
+        assert!(AcpError::InitializationFailed("timeout".to_string()).is_protocol_error());
+        assert!(AcpError::PromptFailed("bad frame".to_string()).is_protocol_error());
+        assert!(
+            !AcpError::ProcessExited {
+                code: 1,
+                signal: None,
+                stderr: String::new(),
+            }
+            .is_protocol_error()
+        );
+        assert!(!AcpError::from(io::Error::other("enoent")).is_protocol_error());
+        assert!(!AcpError::ConfigError("bad".to_string()).is_protocol_error());
+    }
+
+    #[test]
+    fn test_reconnect_failed_display() {
+        let err = AcpError::ReconnectFailed("agent does not advertise session/load".to_string());
+        assert_eq!(
+            err.to_string(),
+            "ACP reconnect failed: agent does not advertise session/load"
+        );
+    }
+
     #[test]
     fn test_process_exited_without_stderr() {
         let err = AcpError::ProcessExited {
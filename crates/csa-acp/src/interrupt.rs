@@ -0,0 +1,32 @@
+//! Process-wide interruption of in-flight prompt turns.
+//!
+//! SIGINT applies to every turn the process runs, so it is broadcast here
+//! rather than threaded through each transport. A turn that sees an
+//! interrupt sends `session/cancel` and waits up to [`CANCEL_GRACE`] for the
+//! agent to end it. Only an agent that does not stop in time is signalled.
+
+use std::{sync::OnceLock, time::Duration};
+
+use tokio::sync::watch;
+
+/// How long an interrupted turn may take to end after `session/cancel`.
+pub const CANCEL_GRACE: Duration = Duration::from_secs(5);
+
+static INTERRUPTS: OnceLock<watch::Sender<u64>> = OnceLock::new();
+
+fn interrupts() -> &'static watch::Sender<u64> {
+    INTERRUPTS.get_or_init(|| watch::channel(0).0)
+}
+
+/// Ask every prompt turn in flight to cancel and return how many there
+/// were. Turns started later are not affected.
+pub fn interrupt_prompts() -> usize {
+    let interrupts = interrupts();
+    interrupts.send_modify(|generation| *generation += 1);
+    interrupts.receiver_count()
+}
+
+/// Receiver that changes on the next [`interrupt_prompts`].
+pub(crate) fn subscribe() -> watch::Receiver<u64> {
+    interrupts().subscribe()
+}
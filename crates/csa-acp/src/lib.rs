@@ -1,9 +1,17 @@
+pub mod capture;
 pub mod client;
+mod client_fs;
 pub mod connection;
 pub mod error;
+pub mod interrupt;
 pub mod mcp_proxy_client;
+mod permissions;
+pub mod pool;
 pub mod prefix_extract;
+pub mod replay;
+pub mod server;
 pub mod session_config;
+mod terminal;
 pub mod tool_output_compaction;
 pub mod transport;
 
@@ -13,9 +21,11 @@ pub use connection::{
     fork_session_via_cli,
 };
 pub use error::{AcpError, AcpResult};
+pub use pool::{AcpAgentPool, AcpPoolConfig, AcpPoolKey, PooledSession};
 pub use prefix_extract::{
     DEFAULT_PREFIX_BUDGET_TOKENS, ExtractedPrefix, PrefixConfig, PrefixExtractor,
 };
+pub use server::{AcpServeBackend, serve_stdio};
 pub use session_config::{McpServerConfig, SessionConfig};
 pub use tool_output_compaction::{ToolOutputCompactionConfig, ToolOutputCompactionState};
 pub use transport::{AcpOutput, AcpOutputIoOptions, AcpRunOptions, AcpSession};
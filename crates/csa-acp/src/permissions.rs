@@ -0,0 +1,275 @@
+//! Policy decisions for the agent's `session/request_permission` calls.
+//!
+//! Without a policy the client grants the first offered option, which is how
+//! CSA has always behaved. With one, requests are checked in a fixed order:
+//! `auto_deny` globs, then the tool's file restrictions, then `auto_approve`
+//! globs, and finally the configured mode. `ask_parent` rejects the request
+//! for this turn and leaves a `PermissionRequest` event for the orchestrator.
+
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use agent_client_protocol::{
+    PermissionOption, PermissionOptionKind, RequestPermissionOutcome, RequestPermissionRequest,
+    SelectedPermissionOutcome, ToolCallContent, ToolKind,
+};
+use csa_core::acp_permissions::{AcpPermissionMode, AcpPermissionPolicy};
+use glob::{MatchOptions, Pattern};
+use tracing::{debug, warn};
+
+use crate::client::SessionEvent;
+
+/// Engine shared between a connection and its client; `None` keeps the
+/// grant-first-option behavior.
+pub(crate) type SharedPermissionEngine = Rc<RefCell<Option<PermissionEngine>>>;
+
+/// Path globs only cross directories through `**`.
+const PATH_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PermissionDecision {
+    Approve,
+    Deny,
+    AskParent,
+}
+
+impl PermissionDecision {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Approve => "approved",
+            Self::Deny => "denied",
+            Self::AskParent => "ask_parent",
+        }
+    }
+}
+
+/// The parts of a permission request the policy looks at.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PermissionSubject {
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) kind: Option<ToolKind>,
+    pub(crate) paths: Vec<PathBuf>,
+    /// Paths of diffs without previous text, i.e. files the call creates.
+    pub(crate) created_paths: Vec<PathBuf>,
+}
+
+impl PermissionSubject {
+    pub(crate) fn from_request(request: &RequestPermissionRequest) -> Self {
+        let fields = &request.tool_call.fields;
+        let mut subject = Self {
+            id: request.tool_call.tool_call_id.0.to_string(),
+            title: fields.title.clone().unwrap_or_default(),
+            kind: fields.kind,
+            ..Self::default()
+        };
+        for location in fields.locations.iter().flatten() {
+            subject.push_path(&location.path);
+        }
+        for content in fields.content.iter().flatten() {
+            if let ToolCallContent::Diff(diff) = content {
+                subject.push_path(&diff.path);
+                if diff.old_text.is_none() {
+                    subject.created_paths.push(diff.path.clone());
+                }
+            }
+        }
+        subject
+    }
+
+    fn push_path(&mut self, path: &Path) {
+        if !self.paths.iter().any(|known| known == path) {
+            self.paths.push(path.to_path_buf());
+        }
+    }
+
+    pub(crate) fn into_event(self, decision: PermissionDecision) -> SessionEvent {
+        SessionEvent::PermissionRequest {
+            id: self.id,
+            title: self.title,
+            kind: self
+                .kind
+                .map(|kind| format!("{kind:?}"))
+                .unwrap_or_default(),
+            paths: self
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+            decision: decision.as_str().to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct PermissionEngine {
+    policy: AcpPermissionPolicy,
+    auto_approve: Vec<Pattern>,
+    auto_deny: Vec<Pattern>,
+}
+
+impl PermissionEngine {
+    pub(crate) fn new(policy: AcpPermissionPolicy) -> Self {
+        let auto_approve = compile_globs(&policy.config.auto_approve, "auto_approve");
+        let auto_deny = compile_globs(&policy.config.auto_deny, "auto_deny");
+        Self {
+            policy,
+            auto_approve,
+            auto_deny,
+        }
+    }
+
+    pub(crate) fn decide(&self, subject: &PermissionSubject) -> PermissionDecision {
+        if self.matches_any(&self.auto_deny, subject) {
+            debug!(title = %subject.title, "ACP permission denied by auto_deny");
+            return PermissionDecision::Deny;
+        }
+        if let Some(reason) = self.restriction_violation(subject) {
+            debug!(title = %subject.title, reason, "ACP permission denied by tool restrictions");
+            return PermissionDecision::Deny;
+        }
+        if self.matches_any(&self.auto_approve, subject) {
+            return PermissionDecision::Approve;
+        }
+        match self.policy.config.mode {
+            AcpPermissionMode::Approve => PermissionDecision::Approve,
+            AcpPermissionMode::Deny => PermissionDecision::Deny,
+            AcpPermissionMode::AskParent => PermissionDecision::AskParent,
+        }
+    }
+
+    /// Why a client-side read of `path` is refused, if it is.
+    pub(crate) fn file_read_denial(&self, path: &Path) -> Option<&'static str> {
+        self.path_matches_any(&self.auto_deny, path)
+            .then_some("path matches [acp.permissions] auto_deny")
+    }
+
+    /// Why a client-side write of `path` is refused, if it is.
+    pub(crate) fn file_write_denial(&self, path: &Path, creates: bool) -> Option<&'static str> {
+        if let Some(reason) = self.file_read_denial(path) {
+            Some(reason)
+        } else if creates && !self.policy.allow_write_new_files {
+            Some("writing new files is not allowed for this tool")
+        } else if !creates && !self.policy.allow_edit_existing_files {
+            Some("editing existing files is not allowed for this tool")
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn project_root(&self) -> Option<&Path> {
+        self.policy.project_root.as_deref()
+    }
+
+    fn matches_any(&self, patterns: &[Pattern], subject: &PermissionSubject) -> bool {
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches(&subject.title))
+            || subject
+                .paths
+                .iter()
+                .any(|path| self.path_matches_any(patterns, path))
+    }
+
+    fn path_matches_any(&self, patterns: &[Pattern], path: &Path) -> bool {
+        let candidates = self.path_candidates(path);
+        patterns.iter().any(|pattern| {
+            candidates
+                .iter()
+                .any(|candidate| pattern.matches_path_with(candidate, PATH_MATCH_OPTIONS))
+        })
+    }
+
+    /// The path as given, plus its absolute and project-relative forms.
+    fn path_candidates(&self, path: &Path) -> Vec<PathBuf> {
+        let mut candidates = vec![path.to_path_buf()];
+        if let Some(root) = self.policy.project_root.as_deref() {
+            if path.is_absolute() {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    candidates.push(relative.to_path_buf());
+                }
+            } else {
+                candidates.push(root.join(path));
+            }
+        }
+        candidates
+    }
+
+    fn restriction_violation(&self, subject: &PermissionSubject) -> Option<&'static str> {
+        if !matches!(
+            subject.kind,
+            Some(ToolKind::Edit | ToolKind::Delete | ToolKind::Move)
+        ) {
+            return None;
+        }
+        subject.paths.iter().find_map(|path| {
+            let creates = subject.created_paths.contains(path) || !self.resolve(path).exists();
+            if creates && !self.policy.allow_write_new_files {
+                Some("writing new files is not allowed for this tool")
+            } else if !creates && !self.policy.allow_edit_existing_files {
+                Some("editing existing files is not allowed for this tool")
+            } else {
+                None
+            }
+        })
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match self.policy.project_root.as_deref() {
+            Some(root) if path.is_relative() => root.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+}
+
+fn compile_globs(globs: &[String], field: &str) -> Vec<Pattern> {
+    globs
+        .iter()
+        .filter_map(|glob| match Pattern::new(glob) {
+            Ok(pattern) => Some(pattern),
+            Err(error) => {
+                warn!(glob, field, %error, "ignoring invalid [acp.permissions] glob");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Pick the option matching `decision`, preferring the once-only variant.
+/// Without a matching option the request is cancelled, which agents treat
+/// as a rejection.
+pub(crate) fn select_outcome(
+    options: &[PermissionOption],
+    decision: PermissionDecision,
+) -> RequestPermissionOutcome {
+    let preferred: &[PermissionOptionKind] = match decision {
+        PermissionDecision::Approve => &[
+            PermissionOptionKind::AllowOnce,
+            PermissionOptionKind::AllowAlways,
+        ],
+        PermissionDecision::Deny | PermissionDecision::AskParent => &[
+            PermissionOptionKind::RejectOnce,
+            PermissionOptionKind::RejectAlways,
+        ],
+    };
+    preferred
+        .iter()
+        .find_map(|kind| options.iter().find(|option| option.kind == *kind))
+        .map(|option| {
+            RequestPermissionOutcome::Selected(SelectedPermissionOutcome::new(
+                option.option_id.clone(),
+            ))
+        })
+        .unwrap_or(RequestPermissionOutcome::Cancelled)
+}
+
+#[cfg(test)]
+#[path = "permissions_tests.rs"]
+mod tests;
@@ -0,0 +1,206 @@
+use super::*;
+use agent_client_protocol::{Diff, ToolCallLocation, ToolCallUpdate, ToolCallUpdateFields};
+use csa_core::acp_permissions::AcpPermissionsConfig;
+
+fn policy(root: &Path, mode: AcpPermissionMode) -> AcpPermissionPolicy {
+    AcpPermissionPolicy {
+        config: AcpPermissionsConfig {
+            mode,
+            auto_approve: Vec::new(),
+            auto_deny: Vec::new(),
+        },
+        allow_edit_existing_files: true,
+        allow_write_new_files: true,
+        project_root: Some(root.to_path_buf()),
+    }
+}
+
+fn edit(path: PathBuf) -> PermissionSubject {
+    PermissionSubject {
+        id: "call-1".to_string(),
+        title: format!("Edit {}", path.display()),
+        kind: Some(ToolKind::Edit),
+        paths: vec![path],
+        created_paths: Vec::new(),
+    }
+}
+
+fn execute(command: &str) -> PermissionSubject {
+    PermissionSubject {
+        id: "call-2".to_string(),
+        title: command.to_string(),
+        kind: Some(ToolKind::Execute),
+        ..PermissionSubject::default()
+    }
+}
+
+fn options() -> Vec<PermissionOption> {
+    vec![
+        PermissionOption::new("allow-always", "Always", PermissionOptionKind::AllowAlways),
+        PermissionOption::new("allow", "Allow", PermissionOptionKind::AllowOnce),
+        PermissionOption::new("reject", "Reject", PermissionOptionKind::RejectOnce),
+    ]
+}
+
+fn selected_id(outcome: RequestPermissionOutcome) -> Option<String> {
+    match outcome {
+        RequestPermissionOutcome::Selected(selected) => Some(selected.option_id.0.to_string()),
+        _ => None,
+    }
+}
+
+#[test]
+fn mode_applies_when_no_glob_matches() {
+    let root = tempfile::tempdir().expect("tempdir");
+    for (mode, expected) in [
+        (AcpPermissionMode::Approve, PermissionDecision::Approve),
+        (AcpPermissionMode::Deny, PermissionDecision::Deny),
+        (AcpPermissionMode::AskParent, PermissionDecision::AskParent),
+    ] {
+        let engine = PermissionEngine::new(policy(root.path(), mode));
+        assert_eq!(engine.decide(&execute("cargo build")), expected);
+    }
+}
+
+#[test]
+fn auto_deny_wins_over_auto_approve() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let mut policy = policy(root.path(), AcpPermissionMode::Deny);
+    policy.config.auto_approve = vec!["src/**".to_string(), "cargo test*".to_string()];
+    policy.config.auto_deny = vec!["**/secrets/**".to_string()];
+    let engine = PermissionEngine::new(policy);
+
+    assert_eq!(
+        engine.decide(&edit(root.path().join("src/lib.rs"))),
+        PermissionDecision::Approve
+    );
+    assert_eq!(
+        engine.decide(&edit(root.path().join("src/secrets/key.rs"))),
+        PermissionDecision::Deny
+    );
+    assert_eq!(
+        engine.decide(&execute("cargo test -p csa-acp")),
+        PermissionDecision::Approve
+    );
+    assert_eq!(
+        engine.decide(&edit(root.path().join("docs/a.md"))),
+        PermissionDecision::Deny
+    );
+}
+
+#[test]
+fn single_star_path_globs_do_not_cross_directories() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let mut policy = policy(root.path(), AcpPermissionMode::Deny);
+    policy.config.auto_approve = vec!["src/*".to_string()];
+    let engine = PermissionEngine::new(policy);
+
+    assert_eq!(
+        engine.decide(&edit(PathBuf::from("src/lib.rs"))),
+        PermissionDecision::Approve
+    );
+    assert_eq!(
+        engine.decide(&edit(root.path().join("src/nested/mod.rs"))),
+        PermissionDecision::Deny
+    );
+}
+
+#[test]
+fn tool_restrictions_deny_before_auto_approve() {
+    let root = tempfile::tempdir().expect("tempdir");
+    std::fs::write(root.path().join("existing.rs"), "").expect("write file");
+    let mut policy = policy(root.path(), AcpPermissionMode::Approve);
+    policy.config.auto_approve = vec!["**".to_string()];
+    policy.allow_edit_existing_files = false;
+    let engine = PermissionEngine::new(policy.clone());
+
+    assert_eq!(
+        engine.decide(&edit(root.path().join("existing.rs"))),
+        PermissionDecision::Deny
+    );
+    assert_eq!(
+        engine.decide(&edit(root.path().join("new.rs"))),
+        PermissionDecision::Approve
+    );
+    assert_eq!(
+        engine.decide(&execute("ls existing.rs")),
+        PermissionDecision::Approve
+    );
+
+    policy.allow_edit_existing_files = true;
+    policy.allow_write_new_files = false;
+    let engine = PermissionEngine::new(policy);
+    let mut create = edit(root.path().join("existing.rs"));
+    create.created_paths = create.paths.clone();
+    assert_eq!(engine.decide(&create), PermissionDecision::Deny);
+    assert_eq!(
+        engine.decide(&edit(root.path().join("existing.rs"))),
+        PermissionDecision::Approve
+    );
+}
+
+#[test]
+fn invalid_globs_are_ignored() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let mut policy = policy(root.path(), AcpPermissionMode::Deny);
+    policy.config.auto_approve = vec!["[".to_string(), "git status".to_string()];
+    let engine = PermissionEngine::new(policy);
+
+    assert_eq!(
+        engine.decide(&execute("git status")),
+        PermissionDecision::Approve
+    );
+}
+
+#[test]
+fn select_outcome_prefers_once_only_options() {
+    assert_eq!(
+        selected_id(select_outcome(&options(), PermissionDecision::Approve)).as_deref(),
+        Some("allow")
+    );
+    assert_eq!(
+        selected_id(select_outcome(&options(), PermissionDecision::AskParent)).as_deref(),
+        Some("reject")
+    );
+    let allow_only = &options()[..2];
+    assert!(matches!(
+        select_outcome(allow_only, PermissionDecision::Deny),
+        RequestPermissionOutcome::Cancelled
+    ));
+}
+
+#[test]
+fn subject_collects_locations_and_created_diff_paths() {
+    let fields = ToolCallUpdateFields::new()
+        .kind(ToolKind::Edit)
+        .title("Write notes")
+        .locations(vec![ToolCallLocation::new("/repo/notes.md")])
+        .content(vec![
+            ToolCallContent::Diff(Diff::new("/repo/notes.md", "hello")),
+            ToolCallContent::Diff(Diff::new("/repo/lib.rs", "new").old_text("old")),
+        ]);
+    let request = RequestPermissionRequest::new(
+        "session-1",
+        ToolCallUpdate::new("call-9", fields),
+        options(),
+    );
+
+    let subject = PermissionSubject::from_request(&request);
+    assert_eq!(subject.id, "call-9");
+    assert_eq!(
+        subject.paths,
+        [
+            PathBuf::from("/repo/notes.md"),
+            PathBuf::from("/repo/lib.rs")
+        ]
+    );
+    assert_eq!(subject.created_paths, [PathBuf::from("/repo/notes.md")]);
+
+    match subject.into_event(PermissionDecision::AskParent) {
+        SessionEvent::PermissionRequest { kind, decision, .. } => {
+            assert_eq!(kind, "Edit");
+            assert_eq!(decision, "ask_parent");
+        }
+        other => panic!("unexpected event: {other:?}"),
+    }
+}
@@ -0,0 +1,275 @@
+//! Long-lived agent processes shared by several CSA sessions.
+//!
+//! Starting an agent such as `claude-code-acp` per sub-agent call costs
+//! seconds of startup and a full Node.js heap each time. The pool keeps
+//! agent processes alive per tool and opens further ACP sessions over an
+//! existing connection while it has a free session slot. It only pays off in
+//! a process that outlives one CSA session, such as `csa acp-serve`.
+//!
+//! Every session on an agent shares the process environment, so callers
+//! spawn agents without session-scoped variables and pass those in the
+//! `session/new` meta of each session instead.
+//!
+//! Each leased session registers its own event channels on the connection,
+//! so concurrent turns do not see each other's output, and a timed-out turn
+//! is cancelled with `session/cancel` rather than by killing the process the
+//! other sessions still use.
+//!
+//! Connections hold `Rc` state and a `LocalSet`, so a pool lives on one
+//! thread and is not `Send`.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    path::PathBuf,
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::{
+    connection::{AcpConnection, AcpConnectionOptions, PromptIoOptions, PromptResult},
+    error::{AcpError, AcpResult},
+    transport::{AcpSessionStart, open_session},
+};
+
+/// Default number of concurrent ACP sessions per agent process.
+pub const DEFAULT_MAX_SESSIONS_PER_AGENT: usize = 4;
+/// Default number of agent processes per pool key.
+pub const DEFAULT_MAX_AGENTS_PER_KEY: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcpPoolConfig {
+    /// Session slots of one agent process.
+    pub max_sessions_per_agent: usize,
+    /// Agent processes started for one key before leases are refused.
+    pub max_agents_per_key: usize,
+}
+
+impl Default for AcpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_sessions_per_agent: DEFAULT_MAX_SESSIONS_PER_AGENT,
+            max_agents_per_key: DEFAULT_MAX_AGENTS_PER_KEY,
+        }
+    }
+}
+
+/// Agents are shared between calls that would spawn the same process.
+///
+/// The environment is not part of the key: an agent keeps the environment of
+/// the call that started it, and session-scoped variables travel in the
+/// `session/new` meta.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AcpPoolKey {
+    pub tool_name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub working_dir: PathBuf,
+}
+
+struct PooledAgent {
+    connection: AcpConnection,
+    /// Leased session slots, including leases still opening their session.
+    active: Cell<usize>,
+}
+
+impl PooledAgent {
+    fn release_slot(&self) {
+        self.active.set(self.active.get().saturating_sub(1));
+    }
+}
+
+/// Pool of agent processes keyed by [`AcpPoolKey`].
+pub struct AcpAgentPool {
+    config: AcpPoolConfig,
+    agents: RefCell<HashMap<AcpPoolKey, Vec<Rc<PooledAgent>>>>,
+}
+
+impl AcpAgentPool {
+    pub fn new(config: AcpPoolConfig) -> Self {
+        Self {
+            config,
+            agents: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Lease a session slot for `key` and open the ACP session described by
+    /// `session_start` on it, starting a new agent with `env` when every
+    /// running one is full.
+    pub async fn open_session(
+        &self,
+        key: &AcpPoolKey,
+        env: &HashMap<String, String>,
+        options: AcpConnectionOptions,
+        session_start: AcpSessionStart<'_>,
+    ) -> AcpResult<PooledSession> {
+        let agent = match self.reserve_slot(key) {
+            Some(agent) => agent,
+            None => self.spawn_agent(key, env, options).await?,
+        };
+        match open_session(&agent.connection, &session_start, &key.working_dir).await {
+            Ok(session_id) => {
+                agent.connection.register_session(&session_id);
+                tracing::debug!(
+                    tool = %key.tool_name,
+                    session_id = %session_id,
+                    active_sessions = agent.active.get(),
+                    "opened pooled ACP session"
+                );
+                Ok(PooledSession { agent, session_id })
+            }
+            Err(error) => {
+                agent.release_slot();
+                Err(error)
+            }
+        }
+    }
+
+    /// Running agents for `key`.
+    pub fn agent_count(&self, key: &AcpPoolKey) -> usize {
+        self.agents.borrow().get(key).map_or(0, Vec::len)
+    }
+
+    /// Leased session slots across the agents for `key`.
+    pub fn active_sessions(&self, key: &AcpPoolKey) -> usize {
+        self.agents.borrow().get(key).map_or(0, |agents| {
+            agents.iter().map(|agent| agent.active.get()).sum()
+        })
+    }
+
+    /// Kill every agent that has no leased session.
+    pub async fn shutdown_idle(&self) {
+        let idle: Vec<Rc<PooledAgent>> = {
+            let mut agents = self.agents.borrow_mut();
+            let mut idle = Vec::new();
+            for pooled in agents.values_mut() {
+                pooled.retain(|agent| {
+                    let keep = agent.active.get() > 0;
+                    if !keep {
+                        idle.push(Rc::clone(agent));
+                    }
+                    keep
+                });
+            }
+            agents.retain(|_, pooled| !pooled.is_empty());
+            idle
+        };
+        for agent in idle {
+            let _ = agent.connection.kill().await;
+        }
+    }
+
+    /// Take a slot on the least loaded live agent for `key`, dropping agents
+    /// whose process has exited.
+    fn reserve_slot(&self, key: &AcpPoolKey) -> Option<Rc<PooledAgent>> {
+        let mut agents = self.agents.borrow_mut();
+        let pooled = agents.get_mut(key)?;
+        pooled.retain(|agent| agent.connection.is_running());
+        let loads: Vec<usize> = pooled.iter().map(|agent| agent.active.get()).collect();
+        let index = pick_agent(&loads, self.config.max_sessions_per_agent)?;
+        let agent = Rc::clone(&pooled[index]);
+        agent.active.set(agent.active.get() + 1);
+        Some(agent)
+    }
+
+    async fn spawn_agent(
+        &self,
+        key: &AcpPoolKey,
+        env: &HashMap<String, String>,
+        options: AcpConnectionOptions,
+    ) -> AcpResult<Rc<PooledAgent>> {
+        let running = self.agent_count(key);
+        if running >= self.config.max_agents_per_key {
+            return Err(AcpError::SessionFailed(format!(
+                "agent pool for '{}' is full: {running} agents x {} sessions in use",
+                key.tool_name, self.config.max_sessions_per_agent
+            )));
+        }
+        let connection = AcpConnection::spawn_with_options(
+            &key.command,
+            &key.args,
+            &key.working_dir,
+            env,
+            options,
+        )
+        .await?;
+        connection.initialize().await?;
+        let agent = Rc::new(PooledAgent {
+            connection,
+            active: Cell::new(1),
+        });
+        self.agents
+            .borrow_mut()
+            .entry(key.clone())
+            .or_default()
+            .push(Rc::clone(&agent));
+        tracing::info!(
+            tool = %key.tool_name,
+            agents = running + 1,
+            "started pooled ACP agent"
+        );
+        Ok(agent)
+    }
+}
+
+/// Index of the least loaded agent with a free slot.
+fn pick_agent(loads: &[usize], max_sessions_per_agent: usize) -> Option<usize> {
+    loads
+        .iter()
+        .enumerate()
+        .filter(|&(_, &load)| load < max_sessions_per_agent)
+        .min_by_key(|&(_, &load)| load)
+        .map(|(index, _)| index)
+}
+
+/// A session leased from an [`AcpAgentPool`]. Dropping it frees the slot;
+/// the agent process keeps running for later leases.
+pub struct PooledSession {
+    agent: Rc<PooledAgent>,
+    session_id: String,
+}
+
+impl PooledSession {
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn connection(&self) -> &AcpConnection {
+        &self.agent.connection
+    }
+
+    pub async fn prompt_with_io(
+        &self,
+        prompt: &str,
+        idle_timeout: Duration,
+        initial_response_timeout: Option<Duration>,
+        io: PromptIoOptions<'_>,
+    ) -> AcpResult<PromptResult> {
+        self.agent
+            .connection
+            .prompt_with_io(
+                &self.session_id,
+                prompt,
+                idle_timeout,
+                initial_response_timeout,
+                io,
+            )
+            .await
+    }
+
+    /// Cancel this session's in-flight turn without touching other sessions.
+    pub async fn cancel(&self) -> AcpResult<()> {
+        self.agent.connection.cancel_session(&self.session_id).await
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        self.agent.connection.unregister_session(&self.session_id);
+        self.agent.release_slot();
+    }
+}
+
+#[cfg(test)]
+#[path = "pool_tests.rs"]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+
+fn key(tool_name: &str) -> AcpPoolKey {
+    AcpPoolKey {
+        tool_name: tool_name.to_string(),
+        command: "/nonexistent/csa-test-acp-agent".to_string(),
+        args: Vec::new(),
+        working_dir: std::env::temp_dir(),
+    }
+}
+
+#[test]
+fn pick_agent_prefers_the_least_loaded_agent_with_a_free_slot() {
+    assert_eq!(pick_agent(&[], 4), None);
+    assert_eq!(pick_agent(&[3, 1, 2], 4), Some(1));
+    assert_eq!(pick_agent(&[4, 4, 3], 4), Some(2));
+    assert_eq!(pick_agent(&[4, 4], 4), None);
+    assert_eq!(pick_agent(&[0], 0), None);
+}
+
+#[tokio::test]
+async fn failed_spawn_leaves_no_agent_or_slot_behind() {
+    let pool = AcpAgentPool::new(AcpPoolConfig::default());
+    let key = key("codex");
+
+    let result = pool
+        .open_session(
+            &key,
+            &HashMap::new(),
+            AcpConnectionOptions::default(),
+            AcpSessionStart::default(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AcpError::SpawnFailed(_))));
+    assert_eq!(pool.agent_count(&key), 0);
+    assert_eq!(pool.active_sessions(&key), 0);
+}
+
+#[tokio::test]
+async fn pool_refuses_leases_beyond_its_agent_limit() {
+    let pool = AcpAgentPool::new(AcpPoolConfig {
+        max_sessions_per_agent: 1,
+        max_agents_per_key: 0,
+    });
+
+    let Err(err) = pool
+        .open_session(
+            &key("claude-code"),
+            &HashMap::new(),
+            AcpConnectionOptions::default(),
+            AcpSessionStart::default(),
+        )
+        .await
+    else {
+        panic!("an empty agent budget must refuse the lease");
+    };
+
+    assert!(
+        err.to_string()
+            .contains("agent pool for 'claude-code' is full: 0 agents x 1 sessions in use")
+    );
+}
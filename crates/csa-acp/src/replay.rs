@@ -0,0 +1,266 @@
+//! Replay of a frame capture against an agent.
+//!
+//! The client frames of a capture are written to the agent in their recorded
+//! order. Before each one, the replay waits for the agent requests and
+//! responses that preceded it in the capture. Notifications are not waited
+//! for, since their number varies between runs. The agent is either a real
+//! command or a mock that plays the captured agent frames back by the same
+//! rule, which makes a client-side issue reproducible without the agent that
+//! triggered it. Redacted values are replayed as the `<redacted>`
+//! placeholder.
+
+use std::{collections::HashMap, fmt, path::Path, process::Stdio, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+
+use crate::{
+    capture::{CapturedFrame, FrameDirection},
+    connection::AcpConnection,
+};
+
+/// Buffer of each direction of the in-process mock agent.
+const MOCK_PIPE_BYTES: usize = 64 * 1024;
+
+/// What the client frames are replayed against.
+#[derive(Debug, Clone, Copy)]
+pub enum ReplayAgent<'a> {
+    /// Answer with the agent frames of the capture itself.
+    Mock,
+    /// Spawn a real agent.
+    Command {
+        command: &'a str,
+        args: &'a [String],
+        working_dir: &'a Path,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayReport {
+    /// Client frames written to the agent.
+    pub sent: usize,
+    /// Frames the agent sent during the replay, in order.
+    pub received: Vec<Value>,
+    /// Captured agent requests and responses that did not arrive in time.
+    pub missing: Vec<String>,
+    /// Responses that failed where the captured one succeeded, or the other
+    /// way round.
+    pub mismatched: Vec<String>,
+    /// Why the replay stopped before every client frame was sent.
+    pub error: Option<String>,
+}
+
+impl ReplayReport {
+    /// Whether the agent behaved as captured.
+    pub fn matches_capture(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty() && self.error.is_none()
+    }
+}
+
+/// Replay the client frames of `frames` against `agent`, waiting at most
+/// `timeout` for each captured agent request or response.
+pub async fn replay_capture(
+    frames: &[CapturedFrame],
+    agent: ReplayAgent<'_>,
+    timeout: Duration,
+) -> anyhow::Result<ReplayReport> {
+    match agent {
+        ReplayAgent::Mock => {
+            let (client_io, agent_io) = tokio::io::duplex(MOCK_PIPE_BYTES);
+            let (client_reader, client_writer) = tokio::io::split(client_io);
+            let (agent_reader, agent_writer) = tokio::io::split(agent_io);
+            let (report, _) = tokio::join!(
+                play(
+                    frames,
+                    FrameDirection::ClientToAgent,
+                    client_reader,
+                    client_writer,
+                    timeout,
+                ),
+                play(
+                    frames,
+                    FrameDirection::AgentToClient,
+                    agent_reader,
+                    agent_writer,
+                    timeout,
+                ),
+            );
+            Ok(report)
+        }
+        ReplayAgent::Command {
+            command,
+            args,
+            working_dir,
+        } => {
+            let mut cmd =
+                AcpConnection::build_cmd_base(command, args, working_dir, &HashMap::new());
+            cmd.stderr(Stdio::inherit());
+            let mut child = cmd
+                .spawn()
+                .with_context(|| format!("failed to spawn ACP agent `{command}`"))?;
+            let stdin = child.stdin.take().context("agent stdin is not piped")?;
+            let stdout = child.stdout.take().context("agent stdout is not piped")?;
+            let report = play(
+                frames,
+                FrameDirection::ClientToAgent,
+                stdout,
+                stdin,
+                timeout,
+            )
+            .await;
+            let _ = child.start_kill();
+            let _ = child.wait().await;
+            Ok(report)
+        }
+    }
+}
+
+/// How a request or response is recognised when it comes back.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FrameKey {
+    Request { method: String, id: String },
+    Response { id: String },
+}
+
+impl FrameKey {
+    /// `None` for notifications and lines that are not JSON-RPC.
+    fn of(frame: &Value) -> Option<Self> {
+        let id = frame.get("id")?.to_string();
+        Some(match frame.get("method").and_then(Value::as_str) {
+            Some(method) => Self::Request {
+                method: method.to_string(),
+                id,
+            },
+            None => Self::Response { id },
+        })
+    }
+}
+
+impl fmt::Display for FrameKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request { method, id } => write!(f, "request {method} (id {id})"),
+            Self::Response { id } => write!(f, "response to id {id}"),
+        }
+    }
+}
+
+fn is_error(frame: &Value) -> bool {
+    frame.get("error").is_some()
+}
+
+/// The side of the conversation the replay listens to.
+struct Peer<R> {
+    lines: Lines<BufReader<R>>,
+    received: Vec<Value>,
+    /// Requests and responses seen so far, with whether each was an error.
+    seen: HashMap<FrameKey, bool>,
+    closed: bool,
+}
+
+impl<R: AsyncRead + Unpin> Peer<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            lines: BufReader::new(reader).lines(),
+            received: Vec::new(),
+            seen: HashMap::new(),
+            closed: false,
+        }
+    }
+
+    async fn wait_for(&mut self, key: &FrameKey, timeout: Duration) -> Option<bool> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if let Some(error) = self.seen.get(key) {
+                return Some(*error);
+            }
+            if self.closed {
+                return None;
+            }
+            match tokio::time::timeout_at(deadline, self.lines.next_line()).await {
+                Err(_) => return None,
+                Ok(Ok(Some(line))) => self.accept(&line),
+                Ok(Ok(None) | Err(_)) => self.closed = true,
+            }
+        }
+    }
+
+    fn accept(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        let frame = serde_json::from_str(line).unwrap_or_else(|_| Value::String(line.to_string()));
+        if let Some(key) = FrameKey::of(&frame) {
+            self.seen.insert(key, is_error(&frame));
+        }
+        self.received.push(frame);
+    }
+}
+
+/// Write the frames going in `direction` and wait for the other side's
+/// requests and responses in between.
+async fn play<R, W>(
+    frames: &[CapturedFrame],
+    direction: FrameDirection,
+    reader: R,
+    mut writer: W,
+    timeout: Duration,
+) -> ReplayReport
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut peer = Peer::new(reader);
+    let mut report = ReplayReport::default();
+    let mut expected = Vec::new();
+    for frame in frames {
+        if frame.direction != direction {
+            if let Some(key) = FrameKey::of(&frame.frame) {
+                expected.push((key, is_error(&frame.frame)));
+            }
+            continue;
+        }
+        settle(&mut peer, &mut expected, &mut report, timeout).await;
+        let mut line = frame.frame.to_string();
+        line.push('\n');
+        let written = match writer.write_all(line.as_bytes()).await {
+            Ok(()) => writer.flush().await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = written {
+            report.error = Some(format!(
+                "failed to write frame {}: {error}",
+                report.sent + 1
+            ));
+            break;
+        }
+        report.sent += 1;
+    }
+    settle(&mut peer, &mut expected, &mut report, timeout).await;
+    // Closing our side lets the other one see the end of the conversation.
+    drop(writer);
+    report.received = peer.received;
+    report
+}
+
+async fn settle<R: AsyncRead + Unpin>(
+    peer: &mut Peer<R>,
+    expected: &mut Vec<(FrameKey, bool)>,
+    report: &mut ReplayReport,
+    timeout: Duration,
+) {
+    for (key, captured_error) in expected.drain(..) {
+        match peer.wait_for(&key, timeout).await {
+            None => report.missing.push(key.to_string()),
+            Some(error) if error != captured_error => report.mismatched.push(key.to_string()),
+            Some(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "replay_tests.rs"]
+mod tests;
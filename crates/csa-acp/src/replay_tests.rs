@@ -0,0 +1,94 @@
+use super::*;
+use serde_json::json;
+
+fn frame(direction: FrameDirection, frame: Value) -> CapturedFrame {
+    CapturedFrame {
+        timestamp_ms: 1,
+        elapsed_ms: 0,
+        direction,
+        frame,
+    }
+}
+
+fn client(frame: Value) -> CapturedFrame {
+    self::frame(FrameDirection::ClientToAgent, frame)
+}
+
+fn agent(frame: Value) -> CapturedFrame {
+    self::frame(FrameDirection::AgentToClient, frame)
+}
+
+/// initialize, a prompt, and a permission request answered mid-turn.
+fn capture() -> Vec<CapturedFrame> {
+    vec![
+        client(json!({ "jsonrpc": "2.0", "id": 0, "method": "initialize" })),
+        agent(json!({ "jsonrpc": "2.0", "id": 0, "result": {} })),
+        client(json!({ "jsonrpc": "2.0", "id": 1, "method": "session/prompt" })),
+        agent(json!({ "jsonrpc": "2.0", "method": "session/update" })),
+        agent(json!({ "jsonrpc": "2.0", "id": 0, "method": "session/request_permission" })),
+        client(json!({ "jsonrpc": "2.0", "id": 0, "result": { "outcome": "selected" } })),
+        agent(json!({ "jsonrpc": "2.0", "id": 1, "result": { "stopReason": "end_turn" } })),
+    ]
+}
+
+#[test]
+fn frame_keys_tell_requests_from_responses() {
+    let request = FrameKey::of(&json!({ "id": 0, "method": "initialize" })).unwrap();
+    let response = FrameKey::of(&json!({ "id": 0, "result": {} })).unwrap();
+    assert_ne!(request, response);
+    assert_eq!(request.to_string(), "request initialize (id 0)");
+    assert_eq!(response.to_string(), "response to id 0");
+    assert!(FrameKey::of(&json!({ "method": "session/update" })).is_none());
+}
+
+#[tokio::test]
+async fn mock_agent_reproduces_the_captured_conversation() {
+    let frames = capture();
+    let report = replay_capture(&frames, ReplayAgent::Mock, Duration::from_secs(5))
+        .await
+        .expect("replay");
+
+    assert!(report.matches_capture(), "{report:?}");
+    assert_eq!(report.sent, 3);
+    let agent_frames: Vec<_> = frames
+        .iter()
+        .filter(|frame| frame.direction == FrameDirection::AgentToClient)
+        .map(|frame| frame.frame.clone())
+        .collect();
+    assert_eq!(report.received, agent_frames);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn agent_that_stops_answering_is_reported() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let args = vec![
+        "-c".to_string(),
+        "read line; echo '{\"jsonrpc\":\"2.0\",\"id\":0,\"error\":{\"code\":-32603}}'; \
+         exec cat >/dev/null"
+            .to_string(),
+    ];
+    let report = replay_capture(
+        &capture(),
+        ReplayAgent::Command {
+            command: "sh",
+            args: &args,
+            working_dir: dir.path(),
+        },
+        Duration::from_millis(500),
+    )
+    .await
+    .expect("replay");
+
+    assert!(!report.matches_capture());
+    assert_eq!(report.sent, 3);
+    assert!(report.error.is_none());
+    assert_eq!(report.mismatched, ["response to id 0"]);
+    assert_eq!(
+        report.missing,
+        [
+            "request session/request_permission (id 0)",
+            "response to id 1"
+        ]
+    );
+}
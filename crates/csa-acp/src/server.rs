@@ -0,0 +1,229 @@
+//! Agent side of ACP, so editors such as Zed can drive CSA as their agent
+//! over stdio.
+//!
+//! This module only speaks the protocol. What a session or a prompt turn is
+//! belongs to the [`AcpServeBackend`] supplied by the binary. Text the
+//! backend emits while a turn runs is forwarded as `agent_message_chunk`
+//! updates, in order and before the turn's `PromptResponse`.
+
+use std::{cell::RefCell, collections::HashMap, path::PathBuf};
+
+use agent_client_protocol::{
+    Agent, AgentCapabilities, AgentSideConnection, AuthenticateRequest, AuthenticateResponse,
+    CancelNotification, Client, ContentBlock, ContentChunk, EmbeddedResourceResource,
+    Implementation, InitializeRequest, InitializeResponse, LoadSessionRequest, LoadSessionResponse,
+    NewSessionRequest, NewSessionResponse, PromptRequest, PromptResponse, ProtocolVersion,
+    SessionId, SessionNotification, SessionUpdate, StopReason, TextContent,
+};
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    task::LocalSet,
+};
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+use tracing::{debug, warn};
+
+use crate::error::{AcpError, AcpResult};
+
+/// What `csa acp-serve` does with sessions and prompts.
+#[async_trait::async_trait(?Send)]
+pub trait AcpServeBackend {
+    /// Start a session rooted at `cwd` and return its ID.
+    async fn new_session(&self, cwd: PathBuf) -> anyhow::Result<String>;
+
+    /// Check that `session_id` exists and can be continued from `cwd`.
+    async fn load_session(&self, session_id: &str, cwd: PathBuf) -> anyhow::Result<()>;
+
+    /// Run one prompt turn. Text sent on `output` reaches the editor while
+    /// the turn runs; the returned text is sent afterwards only when nothing
+    /// was streamed.
+    async fn prompt(
+        &self,
+        session_id: &str,
+        prompt: String,
+        output: mpsc::UnboundedSender<String>,
+    ) -> anyhow::Result<String>;
+}
+
+type QueuedUpdate = (SessionNotification, oneshot::Sender<()>);
+
+struct CsaAgent<B> {
+    backend: B,
+    updates: mpsc::UnboundedSender<QueuedUpdate>,
+    /// Cancel switches of turns in flight, by session ID.
+    cancels: RefCell<HashMap<String, watch::Sender<bool>>>,
+}
+
+impl<B: AcpServeBackend> CsaAgent<B> {
+    /// Queue a text chunk; the receiver resolves once it was written.
+    fn send_text(&self, session_id: &SessionId, text: String) -> oneshot::Receiver<()> {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        let notification = SessionNotification::new(
+            session_id.clone(),
+            SessionUpdate::AgentMessageChunk(ContentChunk::new(ContentBlock::Text(
+                TextContent::new(text),
+            ))),
+        );
+        if self.updates.send((notification, ack_tx)).is_err() {
+            warn!("ACP update writer stopped; dropping agent message chunk");
+        }
+        ack_rx
+    }
+}
+
+fn internal_error(error: anyhow::Error) -> agent_client_protocol::Error {
+    agent_client_protocol::Error::internal_error().data(format!("{error:#}"))
+}
+
+#[async_trait::async_trait(?Send)]
+impl<B: AcpServeBackend> Agent for CsaAgent<B> {
+    async fn initialize(
+        &self,
+        _args: InitializeRequest,
+    ) -> agent_client_protocol::Result<InitializeResponse> {
+        Ok(InitializeResponse::new(ProtocolVersion::LATEST)
+            .agent_capabilities(AgentCapabilities::new().load_session(true))
+            .agent_info(Implementation::new("csa", env!("CARGO_PKG_VERSION"))))
+    }
+
+    async fn authenticate(
+        &self,
+        _args: AuthenticateRequest,
+    ) -> agent_client_protocol::Result<AuthenticateResponse> {
+        // Tools authenticate with their own credentials; CSA has none to check.
+        Ok(AuthenticateResponse::default())
+    }
+
+    async fn new_session(
+        &self,
+        args: NewSessionRequest,
+    ) -> agent_client_protocol::Result<NewSessionResponse> {
+        let session_id = self
+            .backend
+            .new_session(args.cwd)
+            .await
+            .map_err(internal_error)?;
+        debug!(session_id = %session_id, "ACP client opened a CSA session");
+        Ok(NewSessionResponse::new(SessionId::new(session_id)))
+    }
+
+    async fn load_session(
+        &self,
+        args: LoadSessionRequest,
+    ) -> agent_client_protocol::Result<LoadSessionResponse> {
+        self.backend
+            .load_session(&args.session_id.0, args.cwd)
+            .await
+            .map_err(internal_error)?;
+        Ok(LoadSessionResponse::new())
+    }
+
+    async fn prompt(&self, args: PromptRequest) -> agent_client_protocol::Result<PromptResponse> {
+        let session_id = args.session_id.0.to_string();
+        let (cancel_tx, mut cancel_rx) = watch::channel(false);
+        self.cancels
+            .borrow_mut()
+            .insert(session_id.clone(), cancel_tx);
+
+        let (output_tx, mut output_rx) = mpsc::unbounded_channel();
+        let turn = self
+            .backend
+            .prompt(&session_id, prompt_text(&args.prompt), output_tx);
+        tokio::pin!(turn);
+        let mut last_ack = None;
+        let outcome = loop {
+            tokio::select! {
+                result = &mut turn => break Some(result),
+                Some(chunk) = output_rx.recv() => {
+                    last_ack = Some(self.send_text(&args.session_id, chunk));
+                }
+                _ = cancel_rx.changed() => break None,
+            }
+        };
+        while let Ok(chunk) = output_rx.try_recv() {
+            last_ack = Some(self.send_text(&args.session_id, chunk));
+        }
+        self.cancels.borrow_mut().remove(&session_id);
+
+        let stop_reason = match outcome {
+            None => StopReason::Cancelled,
+            Some(Ok(output)) => {
+                if last_ack.is_none() && !output.is_empty() {
+                    last_ack = Some(self.send_text(&args.session_id, output));
+                }
+                StopReason::EndTurn
+            }
+            Some(Err(error)) => return Err(internal_error(error)),
+        };
+        if let Some(ack) = last_ack {
+            let _ = ack.await;
+        }
+        Ok(PromptResponse::new(stop_reason))
+    }
+
+    async fn cancel(&self, args: CancelNotification) -> agent_client_protocol::Result<()> {
+        if let Some(cancel) = self.cancels.borrow().get(&*args.session_id.0) {
+            let _ = cancel.send(true);
+        }
+        Ok(())
+    }
+}
+
+/// Flatten prompt content into the text prompt CSA tools take. Resource
+/// links become their URI; binary content is dropped.
+pub(crate) fn prompt_text(blocks: &[ContentBlock]) -> String {
+    let parts: Vec<String> = blocks
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text(text) => Some(text.text.clone()),
+            ContentBlock::ResourceLink(link) => Some(link.uri.clone()),
+            ContentBlock::Resource(embedded) => match &embedded.resource {
+                EmbeddedResourceResource::TextResourceContents(contents) => Some(format!(
+                    "<resource uri=\"{}\">\n{}\n</resource>",
+                    contents.uri, contents.text
+                )),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    parts.join("\n")
+}
+
+/// Serve ACP on stdin/stdout until the client disconnects.
+pub async fn serve_stdio<B: AcpServeBackend + 'static>(backend: B) -> AcpResult<()> {
+    let local_set = LocalSet::new();
+    local_set
+        .run_until(async move {
+            let (updates_tx, mut updates_rx) = mpsc::unbounded_channel::<QueuedUpdate>();
+            let agent = CsaAgent {
+                backend,
+                updates: updates_tx,
+                cancels: RefCell::new(HashMap::new()),
+            };
+            let (connection, io_task) = AgentSideConnection::new(
+                agent,
+                tokio::io::stdout().compat_write(),
+                tokio::io::stdin().compat(),
+                |fut| {
+                    tokio::task::spawn_local(fut);
+                },
+            );
+            tokio::task::spawn_local(async move {
+                while let Some((notification, ack)) = updates_rx.recv().await {
+                    if let Err(error) = connection.session_notification(notification).await {
+                        warn!(error = %error, "failed to send ACP session update");
+                        break;
+                    }
+                    let _ = ack.send(());
+                }
+            });
+            io_task
+                .await
+                .map_err(|error| AcpError::ConnectionFailed(format!("ACP server I/O: {error}")))
+        })
+        .await
+}
+
+#[cfg(test)]
+#[path = "server_tests.rs"]
+mod tests;
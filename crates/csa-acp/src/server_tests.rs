@@ -0,0 +1,27 @@
+use super::*;
+use agent_client_protocol::{EmbeddedResource, ResourceLink, TextResourceContents};
+
+#[test]
+fn prompt_text_joins_text_links_and_embedded_resources() {
+    let blocks = vec![
+        ContentBlock::Text(TextContent::new("Review this file")),
+        ContentBlock::ResourceLink(ResourceLink::new("lib.rs", "file:///repo/src/lib.rs")),
+        ContentBlock::Resource(EmbeddedResource::new(
+            EmbeddedResourceResource::TextResourceContents(TextResourceContents::new(
+                "fn main() {}",
+                "file:///repo/src/main.rs",
+            )),
+        )),
+    ];
+
+    assert_eq!(
+        prompt_text(&blocks),
+        "Review this file\nfile:///repo/src/lib.rs\n\
+         <resource uri=\"file:///repo/src/main.rs\">\nfn main() {}\n</resource>"
+    );
+}
+
+#[test]
+fn prompt_text_of_empty_prompt_is_empty() {
+    assert_eq!(prompt_text(&[]), "");
+}
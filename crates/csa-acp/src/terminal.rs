@@ -0,0 +1,335 @@
+//! Client side of the ACP `terminal/*` methods.
+//!
+//! Commands an agent starts in a terminal run through `csa-process` with the
+//! isolation plan of the agent itself. When the agent has a cgroup, the
+//! commands join it, so they share the agent's memory and PID budget instead
+//! of each getting a full budget of their own; otherwise each command gets a
+//! scope of its own that is stopped when the agent releases the terminal.
+//! Output is kept up to the requested byte limit, dropping the oldest bytes
+//! first, and the full run is recorded as a `ToolCallOutput` event once the
+//! command exits.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::CString,
+    path::PathBuf,
+    process::ExitStatus,
+    rc::Rc,
+};
+
+use agent_client_protocol::{CreateTerminalRequest, TerminalExitStatus};
+use csa_process::{SandboxHandle, SpawnOptions};
+use csa_resource::isolation_plan::IsolationPlan;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt},
+    sync::watch,
+};
+use tracing::{debug, warn};
+
+use crate::{
+    client::{SessionEvent, SharedEvents},
+    connection::AcpConnection,
+};
+
+/// Output kept per terminal when the agent sets no `outputByteLimit`.
+const DEFAULT_OUTPUT_BYTE_LIMIT: usize = 1024 * 1024;
+
+pub(crate) type SharedTerminals = Rc<TerminalManager>;
+
+/// How the connection's agent was started; terminal commands inherit it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TerminalLaunch {
+    pub(crate) working_dir: PathBuf,
+    pub(crate) env: HashMap<String, String>,
+    pub(crate) sandbox: Option<TerminalSandbox>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TerminalSandbox {
+    pub(crate) isolation_plan: IsolationPlan,
+    pub(crate) tool_name: String,
+    pub(crate) session_id: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TerminalOutput {
+    bytes: Vec<u8>,
+    limit: usize,
+    truncated: bool,
+}
+
+impl TerminalOutput {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.bytes.extend_from_slice(chunk);
+        if self.bytes.len() <= self.limit {
+            return;
+        }
+        let mut cut = self.bytes.len() - self.limit;
+        // Never start the kept output in the middle of a UTF-8 sequence.
+        while cut < self.bytes.len() && (self.bytes[cut] & 0b1100_0000) == 0b1000_0000 {
+            cut += 1;
+        }
+        self.bytes.drain(..cut);
+        self.truncated = true;
+    }
+
+    pub(crate) fn text(&self) -> String {
+        String::from_utf8_lossy(&self.bytes).into_owned()
+    }
+
+    pub(crate) fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+struct Terminal {
+    pid: Option<u32>,
+    output: Rc<RefCell<TerminalOutput>>,
+    exit: watch::Receiver<Option<TerminalExitStatus>>,
+    /// Keeps the command's cgroup scope alive until the terminal is released.
+    _sandbox: SandboxHandle,
+}
+
+#[derive(Default)]
+pub(crate) struct TerminalManager {
+    launch: RefCell<TerminalLaunch>,
+    /// `cgroup.procs` of the agent's cgroup; commands are started in it.
+    agent_cgroup: RefCell<Option<CString>>,
+    terminals: RefCell<HashMap<String, Terminal>>,
+    next_id: Cell<u64>,
+}
+
+impl TerminalManager {
+    pub(crate) fn set_launch(&self, launch: TerminalLaunch) {
+        *self.launch.borrow_mut() = launch;
+    }
+
+    pub(crate) fn set_agent_cgroup(&self, procs_path: Option<CString>) {
+        *self.agent_cgroup.borrow_mut() = procs_path;
+    }
+
+    /// Start the requested command; runs on the connection's `LocalSet`.
+    pub(crate) async fn create(
+        &self,
+        request: CreateTerminalRequest,
+        events: SharedEvents,
+    ) -> anyhow::Result<String> {
+        let launch = self.launch.borrow().clone();
+        let cwd = request.cwd.map_or_else(
+            || launch.working_dir.clone(),
+            |cwd| launch.working_dir.join(cwd),
+        );
+        let mut env = launch.env.clone();
+        env.extend(
+            request
+                .env
+                .into_iter()
+                .map(|variable| (variable.name, variable.value)),
+        );
+        let build_cmd =
+            || AcpConnection::build_cmd_base(&request.command, &request.args, &cwd, &env);
+        let (mut child, handle) = self.spawn(build_cmd, launch.sandbox.as_ref()).await?;
+
+        let id = format!("term-{}", self.next_id.get());
+        self.next_id.set(self.next_id.get() + 1);
+        let command_line = std::iter::once(request.command.as_str())
+            .chain(request.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        debug!(terminal = %id, command = %command_line, "ACP agent started a terminal command");
+
+        let limit = request
+            .output_byte_limit
+            .map_or(DEFAULT_OUTPUT_BYTE_LIMIT, |limit| limit as usize);
+        let output = Rc::new(RefCell::new(TerminalOutput::new(limit)));
+        let (exit_tx, exit_rx) = watch::channel(None);
+        let pid = child.id();
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let task_output = output.clone();
+        let task_id = id.clone();
+        tokio::task::spawn_local(async move {
+            tokio::join!(pump(stdout, &task_output), pump(stderr, &task_output));
+            let exit = match child.wait().await {
+                Ok(status) => exit_status(status),
+                Err(error) => {
+                    warn!(terminal = %task_id, %error, "failed to wait for terminal command");
+                    TerminalExitStatus::new()
+                }
+            };
+            events.borrow_mut().push(SessionEvent::ToolCallOutput {
+                id: task_id,
+                title: Some(command_line),
+                status: describe_exit(&exit),
+                output: task_output.borrow().text(),
+            });
+            let _ = exit_tx.send(Some(exit));
+        });
+
+        self.terminals.borrow_mut().insert(
+            id.clone(),
+            Terminal {
+                pid,
+                output,
+                exit: exit_rx,
+                _sandbox: handle,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Start the command in the agent's cgroup, or in a scope of its own when
+    /// the agent has none or its cgroup cannot be joined.
+    async fn spawn(
+        &self,
+        build_cmd: impl Fn() -> tokio::process::Command,
+        sandbox: Option<&TerminalSandbox>,
+    ) -> anyhow::Result<(tokio::process::Child, SandboxHandle)> {
+        let agent_cgroup = self.agent_cgroup.borrow().clone();
+        if let (Some(sandbox), Some(procs_path)) = (sandbox, agent_cgroup) {
+            match csa_process::spawn_tool_in_cgroup(
+                build_cmd(),
+                None,
+                SpawnOptions::default(),
+                &sandbox.isolation_plan,
+                &procs_path,
+            )
+            .await
+            {
+                Ok(spawned) => return Ok(spawned),
+                Err(error) => warn!(
+                    %error,
+                    "cannot start terminal command in the agent's cgroup; giving it a scope of its own"
+                ),
+            }
+        }
+        self.spawn_in_own_scope(build_cmd(), sandbox).await
+    }
+
+    async fn spawn_in_own_scope(
+        &self,
+        cmd: tokio::process::Command,
+        sandbox: Option<&TerminalSandbox>,
+    ) -> anyhow::Result<(tokio::process::Child, SandboxHandle)> {
+        csa_process::spawn_tool_sandboxed(
+            cmd,
+            None,
+            SpawnOptions::default(),
+            sandbox.map(|sandbox| &sandbox.isolation_plan),
+            sandbox.map_or("acp-terminal", |sandbox| sandbox.tool_name.as_str()),
+            &sandbox.map_or_else(String::new, |sandbox| {
+                format!("{}-terminal-{}", sandbox.session_id, self.next_id.get())
+            }),
+        )
+        .await
+    }
+
+    /// Output so far, whether it was truncated, and the exit status once the
+    /// command has exited.
+    pub(crate) fn output(&self, id: &str) -> Option<(String, bool, Option<TerminalExitStatus>)> {
+        let terminals = self.terminals.borrow();
+        let terminal = terminals.get(id)?;
+        let output = terminal.output.borrow();
+        Some((
+            output.text(),
+            output.truncated(),
+            terminal.exit.borrow().clone(),
+        ))
+    }
+
+    pub(crate) async fn wait_for_exit(&self, id: &str) -> Option<TerminalExitStatus> {
+        let mut exit = self.terminals.borrow().get(id)?.exit.clone();
+        let status = exit.wait_for(Option::is_some).await.ok()?;
+        status.clone()
+    }
+
+    /// Kill the command's process group; the terminal stays readable.
+    pub(crate) fn kill(&self, id: &str) -> bool {
+        let terminals = self.terminals.borrow();
+        let Some(terminal) = terminals.get(id) else {
+            return false;
+        };
+        if terminal.exit.borrow().is_none() {
+            kill_process_group(terminal.pid);
+        }
+        true
+    }
+
+    /// Kill the command if it still runs and forget the terminal.
+    pub(crate) fn release(&self, id: &str) -> bool {
+        let killed = self.kill(id);
+        self.terminals.borrow_mut().remove(id);
+        killed
+    }
+}
+
+async fn pump<R: AsyncRead + Unpin>(reader: Option<R>, output: &RefCell<TerminalOutput>) {
+    let Some(mut reader) = reader else {
+        return;
+    };
+    let mut buf = [0u8; 8192];
+    loop {
+        match reader.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => output.borrow_mut().push(&buf[..read]),
+        }
+    }
+}
+
+fn exit_status(status: ExitStatus) -> TerminalExitStatus {
+    let mut exit = TerminalExitStatus::new();
+    exit.exit_code = status.code().map(|code| code as u32);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        exit.signal = status.signal().map(signal_name);
+    }
+    exit
+}
+
+fn describe_exit(exit: &TerminalExitStatus) -> String {
+    match (&exit.exit_code, &exit.signal) {
+        (Some(code), _) => format!("exited {code}"),
+        (None, Some(signal)) => format!("killed by {signal}"),
+        (None, None) => "exited".to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn signal_name(signal: i32) -> String {
+    match signal {
+        libc::SIGHUP => "SIGHUP".to_string(),
+        libc::SIGINT => "SIGINT".to_string(),
+        libc::SIGKILL => "SIGKILL".to_string(),
+        libc::SIGSEGV => "SIGSEGV".to_string(),
+        libc::SIGTERM => "SIGTERM".to_string(),
+        libc::SIGABRT => "SIGABRT".to_string(),
+        other => format!("signal {other}"),
+    }
+}
+
+fn kill_process_group(pid: Option<u32>) {
+    #[cfg(unix)]
+    if let Some(pid) = pid {
+        // Commands run in their own session (setsid), so the group ID is
+        // the PID and the whole subtree goes down with it.
+        // SAFETY: killpg only sends a signal; a stale group ID fails with ESRCH.
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+}
+
+#[cfg(test)]
+#[path = "terminal_tests.rs"]
+mod tests;
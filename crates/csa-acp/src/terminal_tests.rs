@@ -0,0 +1,91 @@
+use super::*;
+use agent_client_protocol::EnvVariable;
+use tokio::task::LocalSet;
+
+use crate::client::SessionEventStore;
+
+fn manager(dir: &std::path::Path) -> TerminalManager {
+    let manager = TerminalManager::default();
+    manager.set_launch(TerminalLaunch {
+        working_dir: dir.to_path_buf(),
+        ..TerminalLaunch::default()
+    });
+    manager
+}
+
+#[test]
+fn output_keeps_the_newest_bytes_on_char_boundaries() {
+    let mut output = TerminalOutput::new(4);
+    output.push(b"ab");
+    assert_eq!(output.text(), "ab");
+    assert!(!output.truncated());
+
+    output.push("cé".as_bytes());
+    assert_eq!(output.text(), "bcé");
+    output.push("ü".as_bytes());
+    assert_eq!(output.text(), "éü");
+    assert!(output.truncated());
+}
+
+#[tokio::test]
+async fn command_output_and_exit_status_are_reported() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manager = manager(dir.path());
+    let events: SharedEvents = Rc::new(RefCell::new(SessionEventStore::default()));
+    LocalSet::new()
+        .run_until(async {
+            let request = CreateTerminalRequest::new("session-1", "sh")
+                .args(vec![
+                    "-c".to_string(),
+                    "echo \"$GREETING\"; pwd; exit 3".to_string(),
+                ])
+                .env(vec![EnvVariable::new("GREETING", "hello")]);
+            let id = manager
+                .create(request, events.clone())
+                .await
+                .expect("create terminal");
+
+            let exit = manager.wait_for_exit(&id).await.expect("exit status");
+            assert_eq!(exit.exit_code, Some(3));
+            let (output, truncated, exit) = manager.output(&id).expect("terminal output");
+            assert!(output.starts_with("hello\n"), "{output}");
+            assert!(output.contains(&dir.path().display().to_string()));
+            assert!(!truncated);
+            assert!(exit.is_some());
+
+            assert!(manager.release(&id));
+            assert!(manager.output(&id).is_none());
+        })
+        .await;
+
+    match events.borrow().events().as_slice() {
+        [SessionEvent::ToolCallOutput { status, output, .. }] => {
+            assert_eq!(status, "exited 3");
+            assert!(output.starts_with("hello\n"));
+        }
+        other => panic!("unexpected events: {other:?}"),
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn kill_stops_a_running_command() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manager = manager(dir.path());
+    let events: SharedEvents = Rc::new(RefCell::new(SessionEventStore::default()));
+    LocalSet::new()
+        .run_until(async {
+            let request =
+                CreateTerminalRequest::new("session-1", "sleep").args(vec!["30".to_string()]);
+            let id = manager
+                .create(request, events.clone())
+                .await
+                .expect("create terminal");
+            assert!(manager.kill(&id));
+
+            let exit = manager.wait_for_exit(&id).await.expect("exit status");
+            assert_eq!(exit.signal.as_deref(), Some("SIGKILL"));
+            assert!(!manager.kill("term-missing"));
+        })
+        .await;
+}
@@ -1,12 +1,14 @@
 use std::time::Duration;
 use std::{collections::HashMap, path::Path};
 
+use csa_core::acp_permissions::AcpPermissionPolicy;
 use csa_process::{DEFAULT_SPOOL_KEEP_ROTATED, DEFAULT_SPOOL_MAX_BYTES};
 
 use crate::{
     client::SessionEvent,
     connection::{AcpConnection, PromptIoOptions},
     error::AcpResult,
+    pool::{AcpAgentPool, AcpPoolKey},
     tool_output_compaction::ToolOutputCompactionConfig,
 };
 
@@ -38,6 +40,9 @@ pub struct AcpOutput {
     /// Peak memory usage in MB from cgroup `memory.peak`.
     /// `None` when cgroup monitoring is unavailable.
     pub peak_memory_mb: Option<u64>,
+    /// CPU time and block I/O of the agent's cgroup scope.
+    /// `None` when the agent did not run in one.
+    pub usage: Option<csa_resource::child_usage::ChildUsage>,
 }
 
 #[derive(Debug, Clone)]
@@ -71,6 +76,10 @@ pub struct AcpRunOptions<'a> {
     pub init_timeout: Duration,
     pub termination_grace_period: Duration,
     pub io: AcpOutputIoOptions<'a>,
+    /// Policy for the agent's permission requests; `None` grants them.
+    pub permission_policy: Option<AcpPermissionPolicy>,
+    /// File the JSON-RPC frames are recorded to; `None` records nothing.
+    pub frame_capture: Option<&'a Path>,
 }
 
 impl Default for AcpRunOptions<'_> {
@@ -81,6 +90,8 @@ impl Default for AcpRunOptions<'_> {
             init_timeout: Duration::from_secs(120),
             termination_grace_period: Duration::from_secs(5),
             io: AcpOutputIoOptions::default(),
+            permission_policy: None,
+            frame_capture: None,
         }
     }
 }
@@ -94,6 +105,8 @@ pub struct AcpSessionCreate<'a> {
     pub session_start: AcpSessionStart<'a>,
     pub init_timeout: Duration,
     pub termination_grace_period: Duration,
+    /// File the JSON-RPC frames are recorded to, from `initialize` on.
+    pub frame_capture: Option<&'a Path>,
 }
 
 pub struct AcpSession {
@@ -111,6 +124,7 @@ impl AcpSession {
             session_start,
             init_timeout,
             termination_grace_period,
+            frame_capture,
         } = create;
         let connection = AcpConnection::spawn_with_options(
             command,
@@ -120,42 +134,15 @@ impl AcpSession {
             crate::connection::AcpConnectionOptions {
                 init_timeout,
                 termination_grace_period,
+                max_reconnects: crate::connection::DEFAULT_MAX_RECONNECTS,
             },
         )
         .await?;
+        if let Some(path) = frame_capture {
+            connection.capture_frames(path);
+        }
         connection.initialize().await?;
-
-        // Inject fork metadata into the meta map when present.
-        let meta = build_session_meta(
-            session_start.meta.clone(),
-            session_start.fork_session_id,
-            session_start.resume_at_message,
-        );
-
-        let session_id = if let Some(resume_id) = session_start.resume_session_id {
-            tracing::debug!(resume_session_id = resume_id, "loading ACP session");
-            match connection.load_session(resume_id, Some(working_dir)).await {
-                Ok(id) => {
-                    tracing::debug!(session_id = %id, "Resumed ACP session");
-                    id
-                }
-                Err(error) => {
-                    tracing::warn!(
-                        resume_session_id = resume_id,
-                        error = %error,
-                        "Failed to resume ACP session, creating new session"
-                    );
-                    connection
-                        .new_session(session_start.system_prompt, Some(working_dir), meta.clone())
-                        .await?
-                }
-            }
-        } else {
-            tracing::debug!("creating new ACP session");
-            connection
-                .new_session(session_start.system_prompt, Some(working_dir), meta)
-                .await?
-        };
+        let session_id = open_session(&connection, &session_start, working_dir).await?;
 
         Ok(Self {
             connection,
@@ -213,15 +200,17 @@ impl AcpSession {
             .await
     }
 
+    /// Prompt with explicit I/O options. An agent that crashes mid-turn is
+    /// respawned and the turn resumed in the same provider session.
     pub async fn prompt_with_idle_timeout_and_io(
-        &self,
+        &mut self,
         prompt: &str,
         idle_timeout: Duration,
         initial_response_timeout: Option<Duration>,
         io: PromptIoOptions<'_>,
     ) -> AcpResult<PromptResult> {
         self.connection
-            .prompt_with_io(
+            .prompt_with_reconnect(
                 &self.session_id,
                 prompt,
                 idle_timeout,
@@ -254,6 +243,8 @@ pub async fn run_prompt(
             init_timeout: Duration::from_secs(120),
             termination_grace_period: Duration::from_secs(5),
             io: AcpOutputIoOptions::default(),
+            permission_policy: None,
+            frame_capture: None,
         },
     )
     .await
@@ -269,7 +260,7 @@ pub async fn run_prompt_with_io(
     options: AcpRunOptions<'_>,
 ) -> AcpResult<AcpOutput> {
     let has_resume_session = session_start.resume_session_id.is_some();
-    let session = AcpSession::new(AcpSessionCreate {
+    let mut session = AcpSession::new(AcpSessionCreate {
         command,
         args,
         working_dir,
@@ -277,8 +268,12 @@ pub async fn run_prompt_with_io(
         session_start,
         init_timeout: options.init_timeout,
         termination_grace_period: options.termination_grace_period,
+        frame_capture: options.frame_capture,
     })
     .await?;
+    session
+        .connection()
+        .set_permission_policy(options.permission_policy);
     let result = match session
         .prompt_with_idle_timeout_and_io(
             prompt,
@@ -306,8 +301,94 @@ pub async fn run_prompt_with_io(
     // ACP processes may stay alive across prompts. If the prompt itself succeeded
     // (no error above), a still-running process is normal — default to exit_code=0.
     // Only report the actual exit code when the process has already exited (e.g., crash).
-    let mut exit_code = session.connection().exit_code().await?.unwrap_or(0);
-    let mut stderr = session.connection().stderr();
+    let exit_code = session.connection().exit_code().await?.unwrap_or(0);
+    let stderr = session.connection().stderr();
+
+    // Kill ACP process immediately for single-prompt usage (no session resumption).
+    // In session mode (resume_session_id is Some), the process stays alive for reuse.
+    if !has_resume_session {
+        let _ = session.connection().kill().await;
+    }
+
+    Ok(prompt_output(
+        result,
+        session.session_id(),
+        exit_code,
+        stderr,
+        options.idle_timeout,
+        options.initial_response_timeout,
+        "process killed",
+    ))
+}
+
+/// Run one prompt on a session leased from `pool`.
+///
+/// The agent process outlives the call so later calls with the same key skip
+/// its startup; a timed-out turn is cancelled with `session/cancel` instead
+/// of killing it. `env` is only used when a new agent has to be started.
+/// `options.frame_capture` is ignored because frames of a shared process
+/// cannot be attributed to one call.
+pub async fn run_pooled_prompt(
+    pool: &AcpAgentPool,
+    key: &AcpPoolKey,
+    env: &HashMap<String, String>,
+    session_start: AcpSessionStart<'_>,
+    prompt: &str,
+    options: AcpRunOptions<'_>,
+) -> AcpResult<AcpOutput> {
+    let session = pool
+        .open_session(
+            key,
+            env,
+            crate::connection::AcpConnectionOptions {
+                init_timeout: options.init_timeout,
+                termination_grace_period: options.termination_grace_period,
+                max_reconnects: crate::connection::DEFAULT_MAX_RECONNECTS,
+            },
+            session_start,
+        )
+        .await?;
+    session
+        .connection()
+        .set_permission_policy(options.permission_policy);
+    let result = session
+        .prompt_with_io(
+            prompt,
+            options.idle_timeout,
+            options.initial_response_timeout,
+            PromptIoOptions {
+                stream_stdout_to_stderr: options.io.stream_stdout_to_stderr,
+                output_spool: options.io.output_spool,
+                spool_max_bytes: options.io.spool_max_bytes,
+                keep_rotated_spool: options.io.keep_rotated_spool,
+                tool_output_compaction: options.io.tool_output_compaction,
+            },
+        )
+        .await?;
+    let exit_code = session.connection().exit_code().await?.unwrap_or(0);
+    let stderr = session.connection().stderr();
+    Ok(prompt_output(
+        result,
+        session.session_id(),
+        exit_code,
+        stderr,
+        options.idle_timeout,
+        options.initial_response_timeout,
+        "turn cancelled",
+    ))
+}
+
+/// Assemble the output of a finished turn, reporting a timed-out turn with
+/// exit code 137 and a note naming the timeout and `timeout_action`.
+fn prompt_output(
+    result: PromptResult,
+    session_id: &str,
+    mut exit_code: i32,
+    mut stderr: String,
+    idle_timeout: Duration,
+    initial_response_timeout: Option<Duration>,
+    timeout_action: &str,
+) -> AcpOutput {
     if result.timed_out {
         exit_code = 137;
         if !stderr.is_empty() && !stderr.ends_with('\n') {
@@ -315,12 +396,9 @@ pub async fn run_prompt_with_io(
         }
         let is_initial = result.exit_reason.as_deref() == Some("initial_response_timeout");
         let timeout_secs = if is_initial {
-            options
-                .initial_response_timeout
-                .unwrap_or(options.idle_timeout)
-                .as_secs()
+            initial_response_timeout.unwrap_or(idle_timeout).as_secs()
         } else {
-            options.idle_timeout.as_secs()
+            idle_timeout.as_secs()
         };
         let label = if is_initial {
             "initial response timeout"
@@ -328,27 +406,63 @@ pub async fn run_prompt_with_io(
             "idle timeout"
         };
         stderr.push_str(&format!(
-            "{label}: no ACP events/stderr for {timeout_secs}s; process killed",
+            "{label}: no ACP events/stderr for {timeout_secs}s; {timeout_action}",
         ));
         stderr.push('\n');
     }
 
-    // Kill ACP process immediately for single-prompt usage (no session resumption).
-    // In session mode (resume_session_id is Some), the process stays alive for reuse.
-    if !has_resume_session {
-        let _ = session.connection().kill().await;
-    }
-
-    Ok(AcpOutput {
+    AcpOutput {
         output: result.output,
         stderr,
         events: result.events,
-        session_id: session.session_id().to_string(),
+        session_id: session_id.to_string(),
         exit_code,
         exit_reason: result.exit_reason,
         metadata: result.metadata,
         peak_memory_mb: None,
-    })
+        usage: None,
+    }
+}
+
+/// Create the provider session described by `session_start` on an
+/// initialized connection, falling back to a new session when the resume
+/// target cannot be loaded.
+pub(crate) async fn open_session(
+    connection: &AcpConnection,
+    session_start: &AcpSessionStart<'_>,
+    working_dir: &Path,
+) -> AcpResult<String> {
+    // Inject fork metadata into the meta map when present.
+    let meta = build_session_meta(
+        session_start.meta.clone(),
+        session_start.fork_session_id,
+        session_start.resume_at_message,
+    );
+
+    if let Some(resume_id) = session_start.resume_session_id {
+        tracing::debug!(resume_session_id = resume_id, "loading ACP session");
+        match connection.load_session(resume_id, Some(working_dir)).await {
+            Ok(id) => {
+                tracing::debug!(session_id = %id, "Resumed ACP session");
+                Ok(id)
+            }
+            Err(error) => {
+                tracing::warn!(
+                    resume_session_id = resume_id,
+                    error = %error,
+                    "Failed to resume ACP session, creating new session"
+                );
+                connection
+                    .new_session(session_start.system_prompt, Some(working_dir), meta)
+                    .await
+            }
+        }
+    } else {
+        tracing::debug!("creating new ACP session");
+        connection
+            .new_session(session_start.system_prompt, Some(working_dir), meta)
+            .await
+    }
 }
 
 /// Merge fork metadata into the session meta map. Returns the (possibly new) meta.
@@ -1,3 +1,4 @@
+use csa_core::acp_permissions::AcpPermissionsConfig;
 use serde::{Deserialize, Serialize};
 
 /// ACP transport-specific configuration.
@@ -6,6 +7,9 @@ pub struct AcpConfig {
     /// Timeout for ACP initialization/session setup operations.
     #[serde(default = "default_acp_init_timeout_seconds")]
     pub init_timeout_seconds: u64,
+    /// Policy for agent permission requests (`[acp.permissions]`).
+    #[serde(default, skip_serializing_if = "AcpPermissionsConfig::is_default")]
+    pub permissions: AcpPermissionsConfig,
 }
 
 const fn default_acp_init_timeout_seconds() -> u64 {
@@ -16,6 +20,7 @@ impl Default for AcpConfig {
     fn default() -> Self {
         Self {
             init_timeout_seconds: default_acp_init_timeout_seconds(),
+            permissions: AcpPermissionsConfig::default(),
         }
     }
 }
@@ -24,5 +29,6 @@ impl AcpConfig {
     /// Returns true when all fields match defaults.
     pub fn is_default(&self) -> bool {
         self.init_timeout_seconds == default_acp_init_timeout_seconds()
+            && self.permissions.is_default()
     }
 }
@@ -413,6 +413,11 @@ impl ProjectConfig {
         self.tools.get(tool).map(|t| t.enabled).unwrap_or(true)
     }
 
+    /// Whether `tool` is registered as an executor plugin (`plugin = true`).
+    pub fn is_plugin_tool(&self, tool: &str) -> bool {
+        self.tools.get(tool).is_some_and(|t| t.plugin)
+    }
+
     /// Get the thinking budget lock for a tool from project config.
     pub fn thinking_lock(&self, tool: &str) -> Option<&str> {
         self.tools
@@ -4,6 +4,7 @@ use super::{
     reject_project_tier_policy, reject_removed_refs, strip_review_project_only_from_global,
     warn_deprecated_keys,
 };
+use crate::config_raw::parse_config_toml;
 use anyhow::{Context, Result};
 use std::path::Path;
 
@@ -31,7 +32,7 @@ impl ProjectConfig {
             crate::validate::validate_tool_transport_overrides_in_raw_config(&raw)
                 .with_context(|| format!("Invalid config: {}", path.display()))?;
         }
-        let mut config: Self = toml::from_str(content)
+        let mut config: Self = parse_config_toml(path, content)
             .with_context(|| format!("Failed to parse config: {}", path.display()))?;
         config.sanitize_filesystem_sandbox();
         crate::validate::validate_tool_transport_overrides(&config)?;
@@ -40,11 +41,11 @@ impl ProjectConfig {
 
     pub(super) fn parse_project_contents(path: &Path, content: &str) -> Result<Option<Self>> {
         let config_str = pruned_project_config_str(content.to_string(), path)?;
-        let raw: toml::Value = toml::from_str(&config_str)
+        let raw: toml::Value = parse_config_toml(path, &config_str)
             .with_context(|| format!("Failed to parse config: {}", path.display()))?;
         reject_project_convergence_completion_policy(None, &raw, &path.display().to_string())
             .with_context(|| format!("Invalid project config: {}", path.display()))?;
-        let mut config: Self = toml::from_str(&config_str)
+        let mut config: Self = parse_config_toml(path, &config_str)
             .with_context(|| format!("Failed to parse config: {}", path.display()))?;
         config.sanitize_filesystem_sandbox();
         crate::validate::validate_tool_transport_overrides(&config)?;
@@ -57,11 +58,12 @@ impl ProjectConfig {
         overlay_path: &Path,
         overlay_str: &str,
     ) -> Result<Option<Self>> {
-        let base_val: toml::Value = toml::from_str(base_str)
+        let base_val: toml::Value = parse_config_toml(base_path, base_str)
             .with_context(|| format!("Failed to parse user config: {}", base_path.display()))?;
-        let mut overlay_val: toml::Value = toml::from_str(overlay_str).with_context(|| {
-            format!("Failed to parse project config: {}", overlay_path.display())
-        })?;
+        let mut overlay_val: toml::Value = parse_config_toml(overlay_path, overlay_str)
+            .with_context(|| {
+                format!("Failed to parse project config: {}", overlay_path.display())
+            })?;
 
         warn_deprecated_keys(&base_val, &base_path.display().to_string());
         warn_deprecated_keys(&overlay_val, &overlay_path.display().to_string());
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use csa_core::error::AppError;
 use std::path::Path;
 
 use crate::config_merge::{reject_project_tier_policy, warn_deprecated_keys};
@@ -27,3 +28,19 @@ pub(crate) fn reject_removed_refs(raw: &toml::Value, path: &Path, label: &str) -
 pub(crate) fn prune_project_removed_refs(raw: &mut toml::Value, path: &Path) -> usize {
     crate::project_prune::prune_removed_project_refs_in_raw_config(raw, &path.display().to_string())
 }
+
+/// Deserialize a config file, reporting syntax and schema errors as a typed
+/// [`AppError::ConfigError`] so callers can classify them without matching
+/// message text.
+pub(crate) fn parse_config_toml<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    content: &str,
+) -> Result<T> {
+    toml::from_str(content).map_err(|error| {
+        AppError::ConfigError {
+            path: Some(path.display().to_string()),
+            message: error.to_string(),
+        }
+        .into()
+    })
+}
@@ -3,6 +3,18 @@ use std::collections::HashMap;
 
 use crate::config::EnforcementMode;
 
+/// Network access for sandboxed tool processes (`resources.network`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SandboxNetwork {
+    /// Host network (default).
+    #[default]
+    Full,
+    /// Private network namespace with loopback only; the tool cannot make
+    /// network calls.
+    None,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourcesConfig {
     /// Minimum physical MemAvailable in MB before refusing launch.
@@ -77,6 +89,22 @@ pub struct ResourcesConfig {
     /// Maximum number of PIDs for child tool process trees.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pids_max: Option<u32>,
+    /// CPU cap for child tool process trees as a percentage of one core
+    /// (`CPUQuota`; 200 = two cores).  Without cgroups this is approximated
+    /// by lowering the child's scheduling priority.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_max_percent: Option<u32>,
+    /// Relative CPU weight under contention (`CPUWeight`, 1-10000).
+    /// Only enforced inside cgroup scopes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_weight: Option<u32>,
+    /// Disk budget in MB for a sandboxed run: growth of the project root and
+    /// the session-private `TMPDIR` beyond this terminates the tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_max_mb: Option<u64>,
+    /// Network access for sandboxed runs: `"full"` (default) or `"none"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<SandboxNetwork>,
     /// Soft memory limit as a percentage of `memory_max_mb`.
     /// When current memory usage exceeds this threshold, the monitor sends
     /// SIGTERM to the process group.  Default: 70 (%).
@@ -85,6 +113,22 @@ pub struct ResourcesConfig {
     /// Polling interval for the memory monitor in seconds.  Default: 5.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_monitor_interval_seconds: Option<u64>,
+    /// Memory pressure threshold: percent of time stalled on memory
+    /// (`/proc/pressure/memory` `some avg10`) at which new spawns are delayed.
+    /// Unset disables memory PSI throttling.  Linux only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psi_memory_threshold: Option<f64>,
+    /// CPU pressure threshold (`/proc/pressure/cpu` `some avg10`, percent).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psi_cpu_threshold: Option<f64>,
+    /// Longest a spawn waits for pressure to subside before launching anyway.
+    /// Default: 300.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psi_max_spawn_delay_seconds: Option<u64>,
+    /// While pressure stays above a threshold, SIGSTOP the most recently
+    /// started running session until it subsides.  Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psi_pause_lowest_priority: Option<bool>,
 }
 
 fn default_min_mem() -> u64 {
@@ -203,8 +247,16 @@ impl Default for ResourcesConfig {
             memory_swap_max_mb: None,
             node_heap_limit_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: None,
             soft_limit_percent: None,
             memory_monitor_interval_seconds: None,
+            psi_memory_threshold: None,
+            psi_cpu_threshold: None,
+            psi_max_spawn_delay_seconds: None,
+            psi_pause_lowest_priority: None,
         }
     }
 }
@@ -229,8 +281,16 @@ impl ResourcesConfig {
             && self.memory_swap_max_mb.is_none()
             && self.node_heap_limit_mb.is_none()
             && self.pids_max.is_none()
+            && self.cpu_max_percent.is_none()
+            && self.cpu_weight.is_none()
+            && self.disk_max_mb.is_none()
+            && self.network.is_none()
             && self.soft_limit_percent.is_none()
             && self.memory_monitor_interval_seconds.is_none()
+            && self.psi_memory_threshold.is_none()
+            && self.psi_cpu_threshold.is_none()
+            && self.psi_max_spawn_delay_seconds.is_none()
+            && self.psi_pause_lowest_priority.is_none()
     }
 }
 
@@ -264,6 +324,28 @@ mod tests {
         assert!(cfg.error_marker_scan);
     }
 
+    #[test]
+    fn network_parses_none_and_full() {
+        let cfg: ResourcesConfig = toml::from_str("network = \"none\"").expect("network none");
+        assert_eq!(cfg.network, Some(SandboxNetwork::None));
+        assert!(!cfg.is_default());
+        let cfg: ResourcesConfig = toml::from_str("network = \"full\"").expect("network full");
+        assert_eq!(cfg.network, Some(SandboxNetwork::Full));
+        assert!(toml::from_str::<ResourcesConfig>("network = \"host\"").is_err());
+    }
+
+    #[test]
+    fn psi_thresholds_parse_and_leave_default() {
+        let cfg: ResourcesConfig = toml::from_str(
+            "psi_memory_threshold = 20.0\npsi_cpu_threshold = 80\npsi_pause_lowest_priority = true",
+        )
+        .expect("psi keys");
+        assert_eq!(cfg.psi_memory_threshold, Some(20.0));
+        assert_eq!(cfg.psi_cpu_threshold, Some(80.0));
+        assert_eq!(cfg.psi_pause_lowest_priority, Some(true));
+        assert!(!cfg.is_default());
+    }
+
     #[test]
     fn hook_bypass_scan_absent_deserializes_to_true() {
         let cfg: ResourcesConfig = toml::from_str("").expect("empty [resources] table");
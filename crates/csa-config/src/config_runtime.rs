@@ -204,6 +204,11 @@ impl ProjectConfig {
             .or_else(|| default_node_heap_limit_mb_for_tool(tool))
     }
 
+    /// GPU memory (MB) declared by `tools.<tool>.gpu_memory_mb`.
+    pub fn tool_gpu_memory_mb(&self, tool: &str) -> Option<u64> {
+        self.tools.get(tool).and_then(|t| t.gpu_memory_mb)
+    }
+
     /// Resolve pids_max from project resources config.
     pub fn sandbox_pids_max(&self) -> Option<u32> {
         self.resources.pids_max
@@ -57,6 +57,11 @@ pub struct ToolConfig {
     /// Per-tool Node.js heap size limit (MB). Takes precedence over project resources.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub node_heap_limit_mb: Option<u64>,
+    /// GPU memory (MB) a run of this tool needs, e.g. for a local model.
+    /// When set, a run is only admitted if a GPU has this much VRAM free,
+    /// and its peak VRAM is recorded in usage stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_memory_mb: Option<u64>,
     /// Deprecated: use `setting_sources` instead.
     /// When `true`, equivalent to `setting_sources = []` (load nothing).
     /// When `false` or absent, no override.
@@ -121,6 +126,11 @@ pub struct ToolConfig {
     /// session in this project when true.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fast_mode: Option<bool>,
+    /// Run this tool through the external executor plugin protocol instead of
+    /// a built-in executor. The executable lives at
+    /// `~/.config/cli-sub-agent/executors/<name>`; see [`plugin_executable_path`].
+    #[serde(default)]
+    pub plugin: bool,
 }
 
 impl Default for ToolConfig {
@@ -133,6 +143,7 @@ impl Default for ToolConfig {
             memory_max_mb: None,
             memory_swap_max_mb: None,
             node_heap_limit_mb: None,
+            gpu_memory_mb: None,
             lean_mode: None,
             setting_sources: None,
             default_model: None,
@@ -146,6 +157,7 @@ impl Default for ToolConfig {
             api_key: None,
             filesystem_sandbox: None,
             fast_mode: None,
+            plugin: false,
         }
     }
 }
@@ -160,6 +172,24 @@ impl ToolConfig {
     }
 }
 
+/// Directory under the user config dir holding executor plugin executables.
+pub const PLUGIN_EXECUTORS_DIR: &str = "executors";
+
+/// Location of the executable for the executor plugin `name`.
+///
+/// Returns `None` when the user config dir cannot be resolved.
+pub fn plugin_executable_path(name: &str) -> Option<PathBuf> {
+    crate::paths::config_dir().map(|dir| dir.join(PLUGIN_EXECUTORS_DIR).join(name))
+}
+
+/// Plugin names become file names and `[tools.<name>]` keys, so keep them to
+/// lowercase ASCII letters, digits, `-` and `_`, starting with a letter.
+pub fn is_valid_plugin_tool_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|first| first.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolRestrictions {
     /// When false, the tool may not modify existing tracked files.
@@ -18,6 +18,15 @@ pub(crate) fn register_configured_specs(
 
     for (tier_name, tier) in &config.tiers {
         for (index, spec) in tier.models.iter().enumerate() {
+            // Executor plugins declare their own models; the catalog only
+            // covers built-in tools.
+            if spec
+                .split('/')
+                .next()
+                .is_some_and(|tool| config.is_plugin_tool(tool))
+            {
+                continue;
+            }
             let key = format!("tiers.{tier_name}.models[{index}]");
             let provenance = sources.provenance(&key, &["tiers", tier_name.as_str(), "models"]);
             register_full_spec(catalog, spec, provenance.clone(), &key)?;
@@ -49,7 +49,7 @@ impl GcConfig {
         if let Some(path) = user_path.as_deref().filter(|p| p.exists()) {
             let content = std::fs::read_to_string(path)
                 .with_context(|| format!("Failed to read user config: {}", path.display()))?;
-            let raw: toml::Value = toml::from_str(&content)
+            let raw: toml::Value = crate::config_raw::parse_config_toml(path, &content)
                 .with_context(|| format!("Failed to parse user config: {}", path.display()))?;
             merged = Some(raw);
         }
@@ -58,9 +58,10 @@ impl GcConfig {
             let content = std::fs::read_to_string(&project_path).with_context(|| {
                 format!("Failed to read project config: {}", project_path.display())
             })?;
-            let raw: toml::Value = toml::from_str(&content).with_context(|| {
-                format!("Failed to parse project config: {}", project_path.display())
-            })?;
+            let raw: toml::Value = crate::config_raw::parse_config_toml(&project_path, &content)
+                .with_context(|| {
+                    format!("Failed to parse project config: {}", project_path.display())
+                })?;
             merged = Some(match merged {
                 Some(base) => merge_toml_values(base, raw),
                 None => raw,
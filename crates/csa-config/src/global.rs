@@ -10,7 +10,7 @@ pub use crate::global_kv_cache::{
     DEFAULT_KV_CACHE_FREQUENT_POLL_SECS, DEFAULT_KV_CACHE_LONG_POLL_SECS, KvCacheConfig,
     KvCacheValueSource, LEGACY_SESSION_WAIT_FALLBACK_SECS, ProviderTtls, ResolvedKvCacheValue,
 };
-use crate::mcp::McpServerConfig;
+use crate::mcp::{McpFilter, McpServerConfig};
 use crate::memory::MemoryConfig;
 pub use crate::tool_selection::ToolSelection;
 use csa_core::types::ToolName;
@@ -67,9 +67,15 @@ pub struct GlobalConfig {
     /// MCP hub unix socket for shared proxy mode.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mcp_proxy_socket: Option<String>,
+    /// Per-consumer-tool MCP filters enforced by the hub; project filters narrow further.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mcp_filters: HashMap<String, McpFilter>,
     /// Tool name aliases (`cx` → `codex`). Project-level wins.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub tool_aliases: HashMap<String, String>,
+    /// Tools registered on top of the built-ins (`[tool_registry.<id>]`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tool_registry: HashMap<String, crate::ToolRegistryEntry>,
     /// `csa run` behavior defaults; project config overrides through the merged project view.
     #[serde(default)]
     pub run: crate::config::RunConfig,
@@ -97,6 +103,12 @@ pub struct GlobalConfig {
         skip_serializing_if = "crate::config_filesystem_sandbox::FilesystemSandboxConfig::is_default"
     )]
     pub filesystem_sandbox: crate::config_filesystem_sandbox::FilesystemSandboxConfig,
+    /// Weave package manager settings.
+    #[serde(default, skip_serializing_if = "WeaveConfig::is_default")]
+    pub weave: WeaveConfig,
+    /// Prometheus metrics endpoint of long-running components.
+    #[serde(default, skip_serializing_if = "MetricsConfig::is_default")]
+    pub metrics: MetricsConfig,
     /// Experimental feature flags.
     #[serde(default)]
     pub experimental: ExperimentalConfig,
@@ -122,7 +134,9 @@ impl Default for GlobalConfig {
             hooks: GlobalHooksConfig::default(),
             mcp: GlobalMcpConfig::default(),
             mcp_proxy_socket: None,
+            mcp_filters: HashMap::new(),
             tool_aliases: HashMap::new(),
+            tool_registry: HashMap::new(),
             run: crate::config::RunConfig::default(),
             execution: crate::config::ExecutionConfig::default(),
             kv_cache: KvCacheConfig::default(),
@@ -132,6 +146,8 @@ impl Default for GlobalConfig {
             acp: crate::AcpConfig::default(),
             filesystem_sandbox: crate::config_filesystem_sandbox::FilesystemSandboxConfig::default(
             ),
+            weave: WeaveConfig::default(),
+            metrics: MetricsConfig::default(),
             experimental: ExperimentalConfig::default(),
         }
     }
@@ -299,6 +315,64 @@ impl SessionWaitConfig {
     }
 }
 
+/// `[weave]`: settings for the weave package manager.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WeaveConfig {
+    /// Git URL of the package index used by `weave search` and by
+    /// `weave install <name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// HTTPS access tokens for private package repositories, keyed by host
+    /// (e.g. `"github.com"`). Tokens in the OS keyring take precedence.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tokens: HashMap<String, String>,
+}
+
+impl WeaveConfig {
+    pub fn is_default(&self) -> bool {
+        self.registry.is_none() && self.tokens.is_empty()
+    }
+}
+
+const DEFAULT_METRICS_LISTEN: &str = "127.0.0.1:9464";
+
+fn default_metrics_listen() -> String {
+    DEFAULT_METRICS_LISTEN.to_string()
+}
+
+/// `[metrics]`: Prometheus metrics served by the MCP hub.
+///
+/// When enabled, csa runs also spool run, failover and slot-wait events to
+/// [`MetricsConfig::events_path`] for the endpoint to count.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MetricsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the `/metrics` listener binds.
+    #[serde(default = "default_metrics_listen")]
+    pub listen: String,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_metrics_listen(),
+        }
+    }
+}
+
+impl MetricsConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Spool the current user's csa processes append metric events to.
+    pub fn events_path() -> Option<PathBuf> {
+        crate::paths::session_state_dir().map(|dir| dir.join("metrics").join("events.jsonl"))
+    }
+}
+
 /// Pre-flight checks that run before session creation.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PreflightConfig {
@@ -451,6 +525,53 @@ pub struct GlobalMcpConfig {
     /// MCP servers available to all tool sessions.
     #[serde(default)]
     pub servers: Vec<McpServerConfig>,
+    /// Have the MCP hub append every tool call to `<state_dir>/mcp-hub/requests.jsonl`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_log: bool,
+    /// Tool calls the MCP hub may answer from its cache; the first matching rule wins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cache: Vec<McpCacheRule>,
+    /// Max tool calls per second the MCP hub forwards to each named server.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rate_limits: HashMap<String, u32>,
+    /// Built-in `csa` tools the MCP hub serves.
+    #[serde(default, skip_serializing_if = "McpCsaToolsConfig::is_default")]
+    pub csa_tools: McpCsaToolsConfig,
+}
+
+/// MCP hub built-in `csa` tools (`[mcp.csa_tools]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpCsaToolsConfig {
+    /// Serve `csa_run` and `csa_review`, which start agents. Off by default,
+    /// and never served over HTTP.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_agents: bool,
+    /// Projects the tools may target besides the one the hub was started in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<PathBuf>,
+}
+
+impl McpCsaToolsConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Opt-in MCP hub response caching for matching tools (`[[mcp.cache]]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpCacheRule {
+    /// Glob patterns over `<server>.<tool>` names; `!` excludes.
+    pub tools: Vec<String>,
+    /// How long a successful result is reused for identical arguments.
+    pub ttl_secs: u64,
+}
+
+impl McpCacheRule {
+    /// Whether results of `tool` on `server` are cached under this rule.
+    pub fn matches(&self, server: &str, tool: &str) -> bool {
+        !self.tools.is_empty()
+            && crate::mcp_tool_pattern::patterns_allow(&self.tools, &format!("{server}.{tool}"))
+    }
 }
 
 /// Returns the heterogeneous counterpart tool for model-diversity enforcement.
@@ -65,8 +65,11 @@ impl GlobalConfig {
             )
             .with_context(|| format!("Invalid global config: {}", path.display()))?;
         }
-        let config: Self = toml::from_str(content)
+        let config: Self = crate::config_raw::parse_config_toml(path, content)
             .with_context(|| format!("Failed to parse global config: {}", path.display()))?;
+        config
+            .tool_registry()
+            .with_context(|| format!("Invalid global config: {}", path.display()))?;
         Ok(config.sanitized(Some(path)))
     }
 
@@ -1,4 +1,5 @@
 use super::*;
+use csa_core::types::ReviewFailOn;
 
 /// Configuration for the code review workflow.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
@@ -91,6 +92,10 @@ pub struct ReviewConfig {
     /// Standard review is read-only by default; `csa review --fix` stays writable.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub readonly_sandbox: Option<bool>,
+    /// Default `csa review --fail-on` policy: derive the exit code from the
+    /// finding severities (`critical`, `high` or `any`) instead of the verdict.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<ReviewFailOn>,
 }
 
 const fn default_gate_timeout_secs() -> u64 {
@@ -125,6 +130,7 @@ impl Default for ReviewConfig {
             gate_commands: Vec::new(),
             gate_timeout_secs: default_gate_timeout_secs(),
             readonly_sandbox: None,
+            fail_on: None,
         }
     }
 }
@@ -145,6 +151,7 @@ impl ReviewConfig {
             && self.gate_commands.is_empty()
             && self.gate_timeout_secs == default_gate_timeout_secs()
             && self.readonly_sandbox.is_none()
+            && self.fail_on.is_none()
     }
 
     /// Returns the effective gate steps, preferring `gate_commands` over legacy
@@ -189,6 +189,7 @@ other = 270
 # url = "https://mcp.example.com/mcp"
 # # headers = { Authorization = "Bearer ..." }
 # # allow_insecure = false  # Set true for http:// (not recommended)
+# # tool_renames = { search = "remote_search" }  # Names exposed by the MCP hub
 #
 # Legacy format (auto-detected as stdio, backward-compatible):
 # [[mcp.servers]]
@@ -196,8 +197,31 @@ other = 270
 # command = "npx"
 # args = ["-y", "@anthropic/deepwiki-mcp"]
 #
+# Log every MCP hub tool call to <state_dir>/mcp-hub/requests.jsonl.
+# [mcp]
+# request_log = true
+#
+# Let the MCP hub reuse results of read-only lookups for identical arguments,
+# and cap the tool calls it forwards to a server per second.
+# [[mcp.cache]]
+# tools = ["github.search_*", "github.get_*"]
+# ttl_secs = 300
+# [mcp.rate_limits]
+# github = 5
+#
 # Optional shared MCP hub socket path.
 # mcp_proxy_socket = "/run/user/1000/cli-sub-agent/mcp-hub.sock"
+#
+# Tools the MCP hub exposes to each consumer tool. Patterns match
+# "<server>.<tool>"; "!" denies. Project [mcp_filters] can only narrow these.
+# [mcp_filters.gemini-cli]
+# tools = ["github.*", "!github.delete_*"]
+#
+# Serve Prometheus metrics (runs per tool, failovers, slot waits, MCP proxy
+# latencies, memory store size) from the MCP hub at http://<listen>/metrics.
+# [metrics]
+# enabled = true
+# listen = "127.0.0.1:9464"
 
 # Execution tuning. Project-level [execution] overrides these values.
 # [execution]
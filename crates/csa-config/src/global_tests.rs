@@ -129,6 +129,23 @@ memory_warn_mb = 8192
     assert_eq!(config.session_wait.memory_warn_mb, Some(8192));
 }
 
+#[test]
+fn test_weave_registry_parses_and_is_omitted_when_unset() {
+    let config: GlobalConfig = toml::from_str(
+        r#"
+[weave]
+registry = "https://github.com/acme/weave-index"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.weave.registry.as_deref(),
+        Some("https://github.com/acme/weave-index")
+    );
+    let serialized = toml::to_string(&GlobalConfig::default()).unwrap();
+    assert!(!serialized.contains("[weave]"), "{serialized}");
+}
+
 #[test]
 fn test_resolve_session_wait_long_poll_seconds_uses_configured_kv_cache_value() {
     let dir = tempfile::tempdir().unwrap();
@@ -452,4 +469,43 @@ fn test_build_execution_env_drops_user_configured_subtree_pin_spoof() {
     assert_eq!(env.get("OTHER_VAR").map(String::as_str), Some("keep"));
 }
 
+#[test]
+fn test_mcp_cache_rules_and_rate_limits_parse() {
+    let config: GlobalConfig = toml::from_str(
+        r#"
+[[mcp.cache]]
+tools = ["github.search_*", "!github.search_issues"]
+ttl_secs = 300
+
+[mcp.rate_limits]
+github = 5
+
+[mcp.csa_tools]
+allow_agents = true
+projects = ["/work/other"]
+"#,
+    )
+    .unwrap();
+
+    let rule = &config.mcp.cache[0];
+    assert_eq!(rule.ttl_secs, 300);
+    assert!(rule.matches("github", "search_code"));
+    assert!(!rule.matches("github", "search_issues"));
+    assert!(!rule.matches("gitlab", "search_code"));
+    assert!(
+        !McpCacheRule {
+            tools: Vec::new(),
+            ttl_secs: 60
+        }
+        .matches("github", "search_code")
+    );
+    assert_eq!(config.mcp.rate_limits.get("github"), Some(&5));
+    assert!(config.mcp.csa_tools.allow_agents);
+    assert_eq!(
+        config.mcp.csa_tools.projects,
+        [PathBuf::from("/work/other")]
+    );
+    assert!(GlobalConfig::default().mcp.csa_tools.is_default());
+}
+
 include!("global_tests_split.rs");
@@ -1,3 +1,17 @@
+#[test]
+fn test_review_config_fail_on_round_trips_and_counts_as_non_default() {
+    let config: GlobalConfig = toml::from_str("[review]\nfail_on = \"high\"\n").unwrap();
+    assert_eq!(
+        config.review.fail_on,
+        Some(csa_core::types::ReviewFailOn::High)
+    );
+    assert!(!config.review.is_default());
+    let toml_str = toml::to_string(&config.review).unwrap();
+    assert!(toml_str.contains("fail_on = \"high\""), "{toml_str}");
+
+    assert!(toml::from_str::<GlobalConfig>("[review]\nfail_on = \"medium\"\n").is_err());
+}
+
 #[test]
 fn test_parse_toml() {
     let toml_str = r#"
@@ -196,6 +210,7 @@ fn test_gate_mode_serde_roundtrip_all_variants() {
             gate_commands: vec![],
             gate_timeout_secs: ReviewConfig::default_gate_timeout(),
             readonly_sandbox: None,
+            fail_on: None,
         };
         let toml = toml::to_string(&review).unwrap();
         let parsed: ReviewConfig = toml::from_str(&toml).unwrap();
@@ -0,0 +1,87 @@
+//! `[tool_registry.<id>]`: tools registered from global config on top of the
+//! built-in tool registry.
+
+use std::sync::OnceLock;
+
+use anyhow::{Result, anyhow};
+use csa_core::tool_registry::{ToolCapabilities, ToolDescriptor, ToolRegistry};
+use csa_core::types::ModelFamily;
+use serde::{Deserialize, Serialize};
+
+use crate::GlobalConfig;
+
+/// One config-registered tool.
+///
+/// Custom tools are never automatic routing candidates; tiers reach them
+/// through model specs (see executor plugins).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolRegistryEntry {
+    /// Extra names accepted for this tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Executable probed on PATH by availability checks and `csa doctor`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+    /// Provider family (`claude`, `gemini`, `openai`, `other`).
+    #[serde(default = "default_family")]
+    pub family: ModelFamily,
+    /// Shown when the binary is missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_hint: Option<String>,
+}
+
+fn default_family() -> ModelFamily {
+    ModelFamily::Other
+}
+
+impl GlobalConfig {
+    /// Built-in tools plus the `[tool_registry]` entries.
+    pub fn tool_registry(&self) -> Result<ToolRegistry> {
+        let mut registry = ToolRegistry::builtin();
+        let mut ids: Vec<&String> = self.tool_registry.keys().collect();
+        ids.sort();
+        for id in ids {
+            let entry = &self.tool_registry[id];
+            if let Some(name) = std::iter::once(id)
+                .chain(&entry.aliases)
+                .find(|name| !crate::is_valid_plugin_tool_name(name))
+            {
+                anyhow::bail!(
+                    "Invalid [tool_registry.{id}]: '{name}' must be lowercase letters, digits, \
+                     '-' or '_', starting with a letter"
+                );
+            }
+            registry
+                .register(ToolDescriptor {
+                    id: id.clone(),
+                    aliases: entry.aliases.clone(),
+                    binary: entry.binary.clone(),
+                    family: entry.family,
+                    capabilities: ToolCapabilities::default(),
+                    install_hint: entry.install_hint.clone(),
+                    builtin: None,
+                })
+                .map_err(|err| anyhow!("Invalid [tool_registry.{id}]: {err}"))?;
+        }
+        Ok(registry)
+    }
+}
+
+/// Process-wide registry from the user's global config, loaded once.
+///
+/// Falls back to the built-in tools when the global config cannot be loaded.
+pub fn tool_registry() -> &'static ToolRegistry {
+    static REGISTRY: OnceLock<ToolRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        GlobalConfig::load()
+            .and_then(|config| config.tool_registry())
+            .unwrap_or_else(|err| {
+                tracing::warn!(error = %err, "Using built-in tool registry");
+                ToolRegistry::builtin()
+            })
+    })
+}
+
+#[cfg(test)]
+#[path = "global_tool_registry_tests.rs"]
+mod tests;
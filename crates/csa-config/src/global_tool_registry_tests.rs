@@ -0,0 +1,48 @@
+use super::*;
+use std::path::Path;
+
+fn load(toml: &str) -> Result<GlobalConfig> {
+    GlobalConfig::load_from_captured_source(Some(Path::new("config.toml")), Some(toml))
+}
+
+#[test]
+fn tool_registry_entries_extend_the_builtins() {
+    let config = load(
+        r#"
+[tool_registry.aider]
+aliases = ["ad"]
+binary = "aider"
+family = "openai"
+install_hint = "pipx install aider-chat"
+"#,
+    )
+    .unwrap();
+
+    let registry = config.tool_registry().unwrap();
+    let aider = registry.get("ad").unwrap();
+    assert_eq!(aider.id, "aider");
+    assert_eq!(aider.binary.as_deref(), Some("aider"));
+    assert_eq!(aider.family, ModelFamily::OpenAI);
+    assert_eq!(
+        aider.install_hint.as_deref(),
+        Some("pipx install aider-chat")
+    );
+    assert!(!aider.capabilities.routing_candidate);
+    assert!(registry.get("codex").is_some());
+}
+
+#[test]
+fn tool_registry_defaults_family_and_rejects_bad_entries_at_load() {
+    let config = load("[tool_registry.mytool]\n").unwrap();
+    assert_eq!(
+        config.tool_registry().unwrap().family("mytool"),
+        Some(ModelFamily::Other)
+    );
+
+    let err = load("[tool_registry.codex]\nbinary = \"codex2\"\n").unwrap_err();
+    assert!(format!("{err:#}").contains("reserved by the built-in tool 'codex'"));
+    let err = load("[tool_registry.mine]\naliases = [\"My Tool\"]\n").unwrap_err();
+    assert!(format!("{err:#}").contains("'My Tool' must be lowercase"));
+    let err = load("[tool_registry.mine]\nfamily = \"mistral\"\n").unwrap_err();
+    assert!(format!("{err:#}").contains("Failed to parse global config"));
+}
@@ -8,6 +8,10 @@ use crate::config::{
     CURRENT_SCHEMA_VERSION, ProjectConfig, ProjectMeta, ResourcesConfig, TierConfig, TierStrategy,
     ToolConfig,
 };
+use crate::init_probe::INIT_TOOLS;
+pub use crate::init_probe::{ToolProbe, host_memory_mb, probe_tools};
+pub use crate::init_template::StackTemplate;
+use crate::init_template::propose_resources;
 
 /// Load tool names explicitly disabled (`enabled = false`) in the global config.
 ///
@@ -47,13 +51,10 @@ pub fn detect_installed_tools() -> Vec<&'static str> {
 /// This avoids relying on the process-global `PATH` environment variable,
 /// making it safe to call from parallel tests.
 fn detect_installed_tools_in_paths(paths: &OsStr) -> Vec<&'static str> {
-    let tools = ["opencode", "codex", "claude"];
-    let names = ["opencode", "codex", "claude-code"];
-    tools
+    INIT_TOOLS
         .iter()
-        .zip(names.iter())
-        .filter(|(exec, _)| which::which_in(exec, Some(paths), ".").is_ok())
-        .map(|(_, name)| *name)
+        .filter(|(_, exec)| which::which_in(exec, Some(paths), ".").is_ok())
+        .map(|(name, _)| *name)
         .collect()
 }
 
@@ -162,6 +163,16 @@ fn required_shipped_model_default(key: &str) -> String {
         .unwrap_or_else(|| panic!("shipped model policy is missing defaults.{key}"))
 }
 
+/// Everything `csa init` decided before writing, so the wizard can show it.
+#[derive(Debug, Clone)]
+pub struct InitPlan {
+    pub config: ProjectConfig,
+    /// Probe results; empty for minimal configs, which skip probing.
+    pub tools: Vec<ToolProbe>,
+    pub host_memory_mb: Option<u64>,
+    pub template: Option<StackTemplate>,
+}
+
 /// Initialize project configuration.
 /// If non_interactive is true, generate default config with detected tools.
 /// If minimal is true, generate only [project] + [tools] with no tiers/resources.
@@ -171,17 +182,49 @@ pub fn init_project(
     _non_interactive: bool,
     minimal: bool,
 ) -> Result<ProjectConfig> {
-    let config_path = ProjectConfig::config_path(project_root);
-    if config_path.exists() {
-        bail!("Configuration already exists at {}", config_path.display());
-    }
+    let plan = plan_init(project_root, minimal, None)?;
+    write_init_plan(project_root, &plan)?;
+    Ok(plan.config)
+}
+
+/// Build the project config without writing it.
+///
+/// Full configs probe the installed tools and host memory; `template`
+/// additionally applies stack-specific resource limits.
+pub fn plan_init(
+    project_root: &Path,
+    minimal: bool,
+    template: Option<StackTemplate>,
+) -> Result<InitPlan> {
+    ensure_uninitialized(project_root)?;
 
     let project_name = project_root
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "unnamed".to_string());
+    let project = ProjectMeta {
+        name: project_name,
+        created_at: Utc::now(),
+        max_recursion_depth: 5,
+    };
+
+    if minimal {
+        // Minimal config: only [project] section, rely on global config / defaults for the rest
+        return Ok(InitPlan {
+            config: project_config(project, ResourcesConfig::default(), HashMap::new()),
+            tools: Vec::new(),
+            host_memory_mb: None,
+            template,
+        });
+    }
 
-    let installed = detect_installed_tools();
+    let probes = probe_tools();
+    let installed: Vec<&str> = probes
+        .iter()
+        .filter(|probe| probe.installed())
+        .map(|probe| probe.tool)
+        .collect();
+    let host_memory_mb = host_memory_mb();
 
     // Load global config to discover tools the user has explicitly disabled.
     // Global `enabled = false` is a hard override — init must not re-enable them.
@@ -190,7 +233,7 @@ pub fn init_project(
     let mut tools = HashMap::new();
 
     // Supported tools with default config
-    for tool_name in &["opencode", "codex", "claude-code"] {
+    for (tool_name, _) in &INIT_TOOLS {
         let is_usable =
             installed.contains(tool_name) && !globally_disabled.contains(&tool_name.to_string());
         tools.insert(
@@ -211,79 +254,63 @@ pub fn init_project(
         );
     }
 
-    let config = if minimal {
-        // Minimal config: only [project] section, rely on global config / defaults for the rest
-        ProjectConfig {
-            schema_version: CURRENT_SCHEMA_VERSION,
-            project: ProjectMeta {
-                name: project_name,
-                created_at: Utc::now(),
-                max_recursion_depth: 5,
-            },
-            resources: ResourcesConfig::default(),
-            acp: Default::default(),
-            github: None,
-            session: Default::default(),
-            memory: Default::default(),
-            tools: HashMap::new(),
-            review: None,
-            debate: None,
-            tiers: HashMap::new(),
-            tier_mapping: HashMap::new(),
-            aliases: HashMap::new(),
-            tool_aliases: HashMap::new(),
-            preferences: None,
-            hooks: Default::default(),
-            run: Default::default(),
-            execution: Default::default(),
-            session_wait: None,
-            preflight: Default::default(),
-            vcs: Default::default(),
-            tool_state_dirs: HashMap::new(),
-            filesystem_sandbox: Default::default(),
-        }
-    } else {
-        ProjectConfig {
-            schema_version: CURRENT_SCHEMA_VERSION,
-            project: ProjectMeta {
-                name: project_name,
-                created_at: Utc::now(),
-                max_recursion_depth: 5,
-            },
-            resources: ResourcesConfig {
-                min_free_memory_mb: 4096,
-                idle_timeout_seconds: 250,
-                ..Default::default()
-            },
-            acp: Default::default(),
-            github: None,
-            session: Default::default(),
-            memory: Default::default(),
-            tools,
-            review: None,
-            debate: None,
-            tiers: build_smart_tiers(&installed, &globally_disabled),
-            tier_mapping: default_tier_mapping(),
-            aliases: HashMap::new(),
-            tool_aliases: HashMap::new(),
-            preferences: None,
-            hooks: Default::default(),
-            run: Default::default(),
-            execution: Default::default(),
-            session_wait: None,
-            preflight: Default::default(),
-            vcs: Default::default(),
-            tool_state_dirs: HashMap::new(),
-            filesystem_sandbox: Default::default(),
-        }
-    };
+    let mut config = project_config(project, propose_resources(host_memory_mb, template), tools);
+    config.tiers = build_smart_tiers(&installed, &globally_disabled);
+    config.tier_mapping = default_tier_mapping();
+    Ok(InitPlan {
+        config,
+        tools: probes,
+        host_memory_mb,
+        template,
+    })
+}
 
-    config.save(project_root)?;
+/// Write a planned config and add `.csa/` to `.gitignore`.
+pub fn write_init_plan(project_root: &Path, plan: &InitPlan) -> Result<()> {
+    // The wizard may have waited on the user since planning.
+    ensure_uninitialized(project_root)?;
+    plan.config.save(project_root)?;
+    update_gitignore(project_root)
+}
 
-    // Update .gitignore if it exists
-    update_gitignore(project_root)?;
+fn ensure_uninitialized(project_root: &Path) -> Result<()> {
+    let config_path = ProjectConfig::config_path(project_root);
+    if config_path.exists() {
+        bail!("Configuration already exists at {}", config_path.display());
+    }
+    Ok(())
+}
 
-    Ok(config)
+fn project_config(
+    project: ProjectMeta,
+    resources: ResourcesConfig,
+    tools: HashMap<String, ToolConfig>,
+) -> ProjectConfig {
+    ProjectConfig {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        project,
+        resources,
+        acp: Default::default(),
+        github: None,
+        session: Default::default(),
+        memory: Default::default(),
+        tools,
+        review: None,
+        debate: None,
+        tiers: HashMap::new(),
+        tier_mapping: HashMap::new(),
+        aliases: HashMap::new(),
+        tool_aliases: HashMap::new(),
+        preferences: None,
+        hooks: Default::default(),
+        run: Default::default(),
+        execution: Default::default(),
+        session_wait: None,
+        preflight: Default::default(),
+        vcs: Default::default(),
+        tool_state_dirs: HashMap::new(),
+        filesystem_sandbox: Default::default(),
+    }
 }
 
 /// Build default tier mapping for common task types.
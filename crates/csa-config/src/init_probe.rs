@@ -0,0 +1,141 @@
+//! Host and tool probing for `csa init --full`: installed tool binaries, their
+//! versions, the models a tool can list cheaply, and physical memory.
+
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Tools `csa init` configures, with the binary each one runs.
+pub(crate) const INIT_TOOLS: [(&str, &str); 3] = [
+    ("opencode", "opencode"),
+    ("codex", "codex"),
+    ("claude-code", "claude"),
+];
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// What `csa init` found out about one tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolProbe {
+    pub tool: &'static str,
+    pub binary: &'static str,
+    /// Resolved binary path; `None` when the tool is not installed.
+    pub path: Option<PathBuf>,
+    pub version: Option<String>,
+    /// Models reported by the tool itself. Empty when the tool has no cheap
+    /// local listing.
+    pub models: Vec<String>,
+}
+
+impl ToolProbe {
+    pub fn installed(&self) -> bool {
+        self.path.is_some()
+    }
+}
+
+/// Probe every init tool on the process `PATH`.
+pub fn probe_tools() -> Vec<ToolProbe> {
+    let system_path = std::env::var_os("PATH").unwrap_or_default();
+    probe_tools_in_paths(&system_path)
+}
+
+pub(crate) fn probe_tools_in_paths(paths: &OsStr) -> Vec<ToolProbe> {
+    INIT_TOOLS
+        .iter()
+        .map(|&(tool, binary)| {
+            let path = which::which_in(binary, Some(paths), ".").ok();
+            let version = path
+                .as_deref()
+                .and_then(|path| run_probe(path, &["--version"]))
+                .and_then(|stdout| {
+                    stdout
+                        .lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty())
+                        .map(str::to_string)
+                });
+            let models = match (tool, path.as_deref()) {
+                // `opencode models` reads the local provider registry.
+                ("opencode", Some(path)) => run_probe(path, &["models"])
+                    .map(|stdout| parse_model_list(&stdout))
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            };
+            ToolProbe {
+                tool,
+                binary,
+                path,
+                version,
+                models,
+            }
+        })
+        .collect()
+}
+
+/// One `provider/model` per line; anything else (banners, blank lines) is
+/// ignored.
+pub(crate) fn parse_model_list(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.contains(char::is_whitespace) && line.contains('/'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Run `binary args` and return its stdout, or `None` if it fails or does not
+/// finish within [`PROBE_TIMEOUT`].
+fn run_probe(binary: &Path, args: &[&str]) -> Option<String> {
+    let mut child = Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // Drain stdout on a thread so a long listing cannot fill the pipe while
+    // we poll for exit.
+    let mut stdout = child.stdout.take()?;
+    let reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        stdout.read_to_string(&mut buf).map(|_| buf)
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() < PROBE_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+    let stdout = reader.join().ok()?.ok()?;
+    status.success().then_some(stdout)
+}
+
+/// Total physical memory in MB, if the platform reports it.
+pub fn host_memory_mb() -> Option<u64> {
+    // SAFETY: sysconf has no preconditions and only reads system constants.
+    let (pages, page_size) = unsafe {
+        (
+            libc::sysconf(libc::_SC_PHYS_PAGES),
+            libc::sysconf(libc::_SC_PAGESIZE),
+        )
+    };
+    if pages <= 0 || page_size <= 0 {
+        return None;
+    }
+    Some((pages as u64).saturating_mul(page_size as u64) / (1024 * 1024))
+}
+
+#[cfg(test)]
+#[path = "init_probe_tests.rs"]
+mod tests;
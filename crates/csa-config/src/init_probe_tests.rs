@@ -0,0 +1,55 @@
+use super::*;
+use tempfile::tempdir;
+
+fn mock_binary(dir: &Path, name: &str, script: &str) {
+    use std::os::unix::fs::PermissionsExt;
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn probe_reports_versions_and_opencode_models() {
+    let dir = tempdir().unwrap();
+    mock_binary(dir.path(), "codex", r#"echo "codex-cli 0.45.0""#);
+    mock_binary(
+        dir.path(),
+        "opencode",
+        r#"if [ "$1" = models ]; then printf 'anthropic/claude-sonnet-4\nopenai/gpt-5\n'; else echo 0.9.1; fi"#,
+    );
+    let probes = probe_tools_in_paths(dir.path().as_os_str());
+
+    let by_tool = |tool: &str| probes.iter().find(|probe| probe.tool == tool).unwrap();
+    assert_eq!(
+        by_tool("codex").version.as_deref(),
+        Some("codex-cli 0.45.0")
+    );
+    assert!(by_tool("codex").models.is_empty());
+    assert_eq!(by_tool("opencode").version.as_deref(), Some("0.9.1"));
+    assert_eq!(
+        by_tool("opencode").models,
+        ["anthropic/claude-sonnet-4", "openai/gpt-5"]
+    );
+    assert!(!by_tool("claude-code").installed());
+}
+
+#[test]
+fn failing_version_probe_still_counts_as_installed() {
+    let dir = tempdir().unwrap();
+    mock_binary(dir.path(), "claude", "exit 1");
+    let probes = probe_tools_in_paths(dir.path().as_os_str());
+    let claude = probes
+        .iter()
+        .find(|probe| probe.tool == "claude-code")
+        .unwrap();
+    assert!(claude.installed());
+    assert_eq!(claude.version, None);
+}
+
+#[test]
+fn model_list_skips_banner_lines() {
+    assert_eq!(
+        parse_model_list("Available models:\n\n  openai/gpt-5  \nanthropic/claude-opus-4\n"),
+        ["openai/gpt-5", "anthropic/claude-opus-4"]
+    );
+}
@@ -0,0 +1,98 @@
+//! Stack templates (`csa init --from <template>`) and host-sized resource
+//! proposals for generated project configs.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::config::ResourcesConfig;
+
+/// Project template for a common stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackTemplate {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+impl StackTemplate {
+    pub const NAMES: [&'static str; 4] = ["rust", "node", "python", "go"];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Node => "node",
+            Self::Python => "python",
+            Self::Go => "go",
+        }
+    }
+
+    /// Share of host memory a tool's process tree may use (1/N).
+    fn memory_divisor(self) -> u64 {
+        match self {
+            // rustc and the linker are the heaviest thing CSA launches.
+            Self::Rust => 3,
+            Self::Node | Self::Python | Self::Go => 4,
+        }
+    }
+}
+
+impl Display for StackTemplate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StackTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "rust" => Ok(Self::Rust),
+            "node" => Ok(Self::Node),
+            "python" => Ok(Self::Python),
+            "go" => Ok(Self::Go),
+            other => Err(format!(
+                "unknown template '{other}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Resource limits for a full project config.
+///
+/// With a known host size, the free-memory floor is 1/8 of RAM (1-4 GiB) and
+/// each tool may use 1/4 of RAM (1/3 for Rust), capped at 32 GiB. Without
+/// one, the fixed defaults apply and no memory cap is set.
+pub(crate) fn propose_resources(
+    host_memory_mb: Option<u64>,
+    template: Option<StackTemplate>,
+) -> ResourcesConfig {
+    let mut resources = ResourcesConfig {
+        min_free_memory_mb: 4096,
+        idle_timeout_seconds: 250,
+        ..Default::default()
+    };
+    if let Some(total) = host_memory_mb {
+        resources.min_free_memory_mb = (total / 8).clamp(1024, 4096);
+        let divisor = template.map_or(4, StackTemplate::memory_divisor);
+        resources.memory_max_mb = Some((total / divisor).clamp(2048, 32768));
+    }
+    match template {
+        Some(StackTemplate::Rust) => {
+            // Cold `cargo build`/link steps can run for minutes without output.
+            resources.idle_timeout_seconds = 600;
+            resources.disk_max_mb = Some(20480);
+        }
+        Some(StackTemplate::Node) => {
+            resources.node_heap_limit_mb = Some(
+                resources
+                    .memory_max_mb
+                    .map_or(4096, |max| (max / 2).min(8192)),
+            );
+        }
+        Some(StackTemplate::Python | StackTemplate::Go) | None => {}
+    }
+    resources
+}
@@ -533,3 +533,48 @@ fn test_build_smart_tiers_all_disabled() {
     // Should fall back to codex defaults (existing behavior for no tools)
     assert_eq!(tiers.len(), 3);
 }
+
+#[test]
+fn test_propose_resources_scales_with_host_memory() {
+    let unknown = propose_resources(None, None);
+    assert_eq!(unknown.min_free_memory_mb, 4096);
+    assert_eq!(unknown.memory_max_mb, None);
+
+    let small = propose_resources(Some(8192), None);
+    assert_eq!(small.min_free_memory_mb, 1024);
+    assert_eq!(small.memory_max_mb, Some(2048));
+
+    let large = propose_resources(Some(262_144), None);
+    assert_eq!(large.min_free_memory_mb, 4096);
+    assert_eq!(large.memory_max_mb, Some(32768));
+}
+
+#[test]
+fn test_propose_resources_applies_stack_template() {
+    let rust = propose_resources(Some(49_152), Some(StackTemplate::Rust));
+    assert_eq!(rust.memory_max_mb, Some(16384));
+    assert_eq!(rust.idle_timeout_seconds, 600);
+    assert_eq!(rust.disk_max_mb, Some(20480));
+
+    let node = propose_resources(Some(32_768), Some(StackTemplate::Node));
+    assert_eq!(node.memory_max_mb, Some(8192));
+    assert_eq!(node.node_heap_limit_mb, Some(4096));
+
+    assert_eq!("Python".parse(), Ok(StackTemplate::Python));
+    assert!("cobol".parse::<StackTemplate>().is_err());
+}
+
+#[test]
+fn test_plan_init_does_not_write_until_requested() {
+    let dir = tempdir().unwrap();
+    let plan = plan_init(dir.path(), false, Some(StackTemplate::Rust)).unwrap();
+    assert_eq!(plan.template, Some(StackTemplate::Rust));
+    assert_eq!(plan.tools.len(), INIT_TOOLS.len());
+    assert_eq!(plan.config.resources.idle_timeout_seconds, 600);
+    assert!(!ProjectConfig::config_path(dir.path()).exists());
+
+    write_init_plan(dir.path(), &plan).unwrap();
+    let saved = ProjectConfig::load(dir.path()).unwrap().unwrap();
+    assert_eq!(saved.resources.disk_max_mb, Some(20480));
+    assert!(write_init_plan(dir.path(), &plan).is_err());
+}
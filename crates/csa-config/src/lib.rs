@@ -22,14 +22,19 @@ mod global_env;
 mod global_impl;
 mod global_kv_cache;
 mod global_template;
+mod global_tool_registry;
 pub mod init;
+mod init_probe;
+mod init_template;
 pub mod mcp;
+mod mcp_tool_pattern;
 pub mod memory;
 pub mod migrate;
 pub mod paths;
 pub mod project_profile;
 mod project_prune;
 pub mod provider_detection;
+pub mod shared_state;
 pub mod tool_selection;
 pub mod validate;
 pub mod weave_lock;
@@ -48,9 +53,12 @@ pub use config_session::{
 };
 pub type MergedConfig = ProjectConfig;
 pub use config_filesystem_sandbox::FilesystemSandboxConfig;
-pub use config_resources::ResourcesConfig;
+pub use config_resources::{ResourcesConfig, SandboxNetwork};
 pub use config_runtime::{DefaultSandboxOptions, default_sandbox_for_tool};
-pub use config_tool::{TransportKind, default_transport_for_tool};
+pub use config_tool::{
+    PLUGIN_EXECUTORS_DIR, TransportKind, default_transport_for_tool, is_valid_plugin_tool_name,
+    plugin_executable_path,
+};
 pub use convergence_completion_policy::{
     ConvergenceCompletionPolicy, EffectiveConvergenceCompletionPolicy,
     ProjectConvergenceCompletionPolicy, parse_project_convergence_completion_policy,
@@ -67,17 +75,25 @@ pub use global::{
     DEFAULT_KV_CACHE_FREQUENT_POLL_SECS, DEFAULT_KV_CACHE_LONG_POLL_SECS, ExecutionEnvOptions,
     ExperimentalConfig, GateMode, GateStep, GithubConfig, GlobalConfig, GlobalHooksConfig,
     GlobalMcpConfig, KvCacheConfig, KvCacheValueSource, LEGACY_SESSION_WAIT_FALLBACK_SECS,
-    PreflightConfig, ProviderTtls, ResolvedKvCacheValue, RetryConfig, ReviewConfig,
-    SessionWaitConfig, StateDirConfig, StateDirOnExceed, TierPolicyConfig, ToolSelection,
-    default_tool_state_dirs, ensure_default_tool_state_dirs,
+    McpCacheRule, McpCsaToolsConfig, MetricsConfig, PreflightConfig, ProviderTtls,
+    ResolvedKvCacheValue, RetryConfig, ReviewConfig, SessionWaitConfig, StateDirConfig,
+    StateDirOnExceed, TierPolicyConfig, ToolSelection, WeaveConfig, default_tool_state_dirs,
+    ensure_default_tool_state_dirs,
 };
 pub use global_caller_hints::{
     CallerHintsConfig, DEFAULT_CODEX_SESSION_WAIT_MCP_INTERNAL_TIMEOUT_SEC,
     DEFAULT_CODEX_SESSION_WAIT_MCP_TOOL_TIMEOUT_SEC, DEFAULT_CODEX_SESSION_WAIT_YIELD_MS,
 };
-pub use init::{detect_installed_tools, init_project};
+pub use global_tool_registry::{ToolRegistryEntry, tool_registry};
+pub use init::{
+    InitPlan, StackTemplate, ToolProbe, detect_installed_tools, init_project, plan_init,
+    write_init_plan,
+};
 pub use mcp::{McpFilter, McpRegistry, McpServerConfig, McpTransport};
-pub use memory::{MemoryBackend, MemoryConfig, MemoryEphemeralConfig, MemoryLlmConfig};
+pub use memory::{
+    MemoryBackend, MemoryConfig, MemoryConflictPolicy, MemoryEphemeralConfig, MemoryIndexBackend,
+    MemoryLlmConfig, MemorySecretPolicy,
+};
 pub use migrate::{Migration, MigrationRegistry, MigrationStep, Version, default_registry};
 pub use paths::{APP_NAME, LEGACY_APP_NAME};
 pub use project_profile::{ProjectProfile, detect_project_profile};
@@ -75,6 +75,9 @@ pub struct McpServerConfig {
     /// Per-server memory limit override (MB).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_max_mb: Option<u64>,
+    /// Names the MCP hub exposes this server's tools under (`tool = "new_name"`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tool_renames: HashMap<String, String>,
 }
 
 impl McpTransport {
@@ -135,6 +138,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             #[serde(default)]
             stateful: bool,
             memory_max_mb: Option<u64>,
+            #[serde(default)]
+            tool_renames: HashMap<String, String>,
         }
 
         let raw = Raw::deserialize(deserializer)?;
@@ -209,6 +214,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             transport,
             stateful: raw.stateful,
             memory_max_mb: raw.memory_max_mb,
+            tool_renames: raw.tool_renames,
         })
     }
 }
@@ -237,7 +243,7 @@ impl McpRegistry {
     pub fn load_from_path(path: &Path) -> Result<Self> {
         let raw = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read MCP config: {}", path.display()))?;
-        toml::from_str::<Self>(&raw)
+        crate::config_raw::parse_config_toml::<Self>(path, &raw)
             .with_context(|| format!("Failed to parse MCP config: {}", path.display()))
     }
 
@@ -269,10 +275,10 @@ impl McpRegistry {
     }
 }
 
-/// MCP filter for per-skill server selection.
+/// MCP filter for server and tool selection.
 ///
-/// Used by skill manifests to control which MCP servers are available.
-/// Full implementation in Task #265; this defines the interface.
+/// Used by skill manifests to control which MCP servers are available, and by
+/// `[mcp_filters.<tool>]` to limit the tools the MCP hub shows a consumer tool.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct McpFilter {
     /// Only include these servers (empty = include all).
@@ -281,6 +287,12 @@ pub struct McpFilter {
     /// Exclude these servers (applied after include).
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Glob patterns over `<server>.<tool>` names; `!` marks a deny pattern.
+    ///
+    /// A tool is allowed when it matches an allow pattern (or there are none)
+    /// and matches no deny pattern, e.g. `["github.*", "!github.delete_*"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
 }
 
 impl McpFilter {
@@ -300,6 +312,17 @@ impl McpFilter {
             .cloned()
             .collect()
     }
+
+    /// Whether `tool` of `server` passes both the server and tool filters.
+    pub fn allows_tool(&self, server: &str, tool: &str) -> bool {
+        if !self.include.is_empty() && !self.include.iter().any(|name| name == server) {
+            return false;
+        }
+        if self.exclude.iter().any(|name| name == server) {
+            return false;
+        }
+        crate::mcp_tool_pattern::patterns_allow(&self.tools, &format!("{server}.{tool}"))
+    }
 }
 
 #[cfg(test)]
@@ -350,6 +373,7 @@ env = { MEMORY_DIR = "~/.claude/memory" }
                     },
                     stateful: false,
                     memory_max_mb: None,
+                    tool_renames: HashMap::new(),
                 },
                 McpServerConfig {
                     name: "memory".to_string(),
@@ -362,6 +386,7 @@ env = { MEMORY_DIR = "~/.claude/memory" }
                     },
                     stateful: false,
                     memory_max_mb: None,
+                    tool_renames: HashMap::new(),
                 },
             ],
         };
@@ -600,6 +625,7 @@ allow_insecure = true
             },
             stateful: false,
             memory_max_mb: None,
+            tool_renames: HashMap::new(),
         }
     }
 
@@ -642,6 +668,7 @@ allow_insecure = true
         let filter = McpFilter {
             include: vec!["a".to_string(), "c".to_string()],
             exclude: vec![],
+            tools: vec![],
         };
         let result = filter.apply(&servers);
         assert_eq!(result.len(), 2);
@@ -655,6 +682,7 @@ allow_insecure = true
         let filter = McpFilter {
             include: vec![],
             exclude: vec!["b".to_string()],
+            tools: vec![],
         };
         let result = filter.apply(&servers);
         assert_eq!(result.len(), 2);
@@ -668,6 +696,7 @@ allow_insecure = true
         let filter = McpFilter {
             include: vec!["a".to_string(), "b".to_string()],
             exclude: vec!["b".to_string()],
+            tools: vec![],
         };
         let result = filter.apply(&servers);
         assert_eq!(result.len(), 1);
@@ -682,6 +711,22 @@ allow_insecure = true
         assert_eq!(result.len(), 2);
     }
 
+    #[test]
+    fn test_filter_allows_tool_by_server_and_pattern() {
+        let filter: McpFilter = toml::from_str(
+            r#"
+exclude = ["shell"]
+tools = ["github.*", "shell.*", "!github.delete_*"]
+"#,
+        )
+        .unwrap();
+        assert!(filter.allows_tool("github", "create_issue"));
+        assert!(!filter.allows_tool("github", "delete_repo"));
+        assert!(!filter.allows_tool("shell", "exec"));
+        assert!(!filter.allows_tool("memory", "store"));
+        assert!(McpFilter::default().allows_tool("memory", "store"));
+    }
+
     #[test]
     fn test_roundtrip_serialize_tagged_stdio() {
         let config = McpServerConfig {
@@ -693,6 +738,7 @@ allow_insecure = true
             },
             stateful: false,
             memory_max_mb: None,
+            tool_renames: HashMap::new(),
         };
 
         let serialized = toml::to_string(&config).unwrap();
@@ -711,6 +757,7 @@ allow_insecure = true
             },
             stateful: false,
             memory_max_mb: Some(1024),
+            tool_renames: HashMap::new(),
         };
 
         let serialized = toml::to_string(&config).unwrap();
@@ -718,4 +765,26 @@ allow_insecure = true
         assert!(serialized.contains("url = \"https://mcp.example.com\""));
         assert!(serialized.contains("memory_max_mb = 1024"));
     }
+
+    #[test]
+    fn test_parse_tool_renames() {
+        let registry: McpRegistry = toml::from_str(
+            r#"
+[[servers]]
+name = "github"
+type = "http"
+url = "https://mcp.example.com"
+tool_renames = { search = "github_search" }
+"#,
+        )
+        .unwrap();
+
+        let renames = &registry.servers[0].tool_renames;
+        assert_eq!(
+            renames.get("search").map(String::as_str),
+            Some("github_search")
+        );
+        let serialized = toml::to_string(&registry).unwrap();
+        assert!(serialized.contains("github_search"));
+    }
 }
@@ -0,0 +1,93 @@
+//! Glob matching for MCP tool filter patterns.
+//!
+//! Patterns match qualified `<server>.<tool>` names. `*` matches any run of
+//! characters (including `.`), `?` matches exactly one, and a leading `!`
+//! turns the pattern into a deny rule.
+
+/// Whether `qualified` passes `patterns`.
+///
+/// Deny patterns always win. Without allow patterns everything not denied
+/// passes; otherwise at least one allow pattern must match.
+pub(crate) fn patterns_allow(patterns: &[String], qualified: &str) -> bool {
+    let mut has_allow = false;
+    let mut allowed = false;
+    for pattern in patterns {
+        let pattern = pattern.trim();
+        if let Some(deny) = pattern.strip_prefix('!') {
+            if glob_match(deny.trim(), qualified) {
+                return false;
+            }
+        } else if !pattern.is_empty() {
+            has_allow = true;
+            allowed = allowed || glob_match(pattern, qualified);
+        }
+    }
+    allowed || !has_allow
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name index it is currently absorbing up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    backtrack = Some((star_p, star_n + 1));
+                    p = star_p;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, patterns_allow};
+
+    fn patterns(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn glob_supports_star_and_question_mark() {
+        assert!(glob_match("github.*", "github.create_issue"));
+        assert!(glob_match("*.read_*", "fs.read_file"));
+        assert!(glob_match("github.get_?", "github.get_x"));
+        assert!(glob_match("*", "anything.at_all"));
+        assert!(!glob_match("github.*", "gitlab.create_issue"));
+        assert!(!glob_match("github.get_?", "github.get_xy"));
+        assert!(!glob_match("github", "github.create_issue"));
+    }
+
+    #[test]
+    fn deny_patterns_override_allow_patterns() {
+        let rules = patterns(&["github.*", "!github.delete_*"]);
+        assert!(patterns_allow(&rules, "github.create_issue"));
+        assert!(!patterns_allow(&rules, "github.delete_repo"));
+        assert!(!patterns_allow(&rules, "memory.store"));
+    }
+
+    #[test]
+    fn deny_only_patterns_allow_everything_else() {
+        let rules = patterns(&["!*.delete_*", "!shell.*"]);
+        assert!(patterns_allow(&rules, "github.create_issue"));
+        assert!(!patterns_allow(&rules, "github.delete_repo"));
+        assert!(!patterns_allow(&rules, "shell.exec"));
+        assert!(patterns_allow(&[], "shell.exec"));
+    }
+}
@@ -20,6 +20,66 @@ impl fmt::Display for MemoryBackend {
     }
 }
 
+/// Full-text index implementation used for memory search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryIndexBackend {
+    /// Tantivy segment directory (`memory/index/`).
+    #[default]
+    Tantivy,
+    /// SQLite FTS5 database (`memory/index.sqlite`) with per-entry upserts.
+    Sqlite,
+}
+
+impl fmt::Display for MemoryIndexBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tantivy => write!(f, "tantivy"),
+            Self::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// What to do when a memory entry contains secret-like content at write time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemorySecretPolicy {
+    /// Replace matched secrets with `[REDACTED]` and persist the rest.
+    #[default]
+    Mask,
+    /// Refuse to persist the entry at all.
+    Reject,
+}
+
+impl fmt::Display for MemorySecretPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mask => write!(f, "mask"),
+            Self::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+/// How consolidation treats facts that assign different values to one subject.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MemoryConflictPolicy {
+    /// Keep both facts and list them under `csa memory conflicts`.
+    #[default]
+    Flag,
+    /// Keep the most recent fact and mark the older ones superseded.
+    PreferRecent,
+}
+
+impl fmt::Display for MemoryConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => write!(f, "flag"),
+            Self::PreferRecent => write!(f, "prefer-recent"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct MemoryConfig {
@@ -34,6 +94,12 @@ pub struct MemoryConfig {
     pub inject_token_budget: u32,
     /// Entry count threshold to trigger consolidation suggestion.
     pub consolidation_threshold: u32,
+    /// Write-time handling of entries that match secret patterns.
+    pub secret_policy: MemorySecretPolicy,
+    /// Search index implementation for the legacy backend.
+    pub index_backend: MemoryIndexBackend,
+    /// Resolution applied to contradictory facts during consolidation.
+    pub conflict_policy: MemoryConflictPolicy,
     /// LLM API configuration for memory operations.
     pub llm: MemoryLlmConfig,
     /// Ephemeral fallback configuration.
@@ -48,6 +114,9 @@ impl Default for MemoryConfig {
             inject: false,
             inject_token_budget: 2000,
             consolidation_threshold: 100,
+            secret_policy: MemorySecretPolicy::default(),
+            index_backend: MemoryIndexBackend::default(),
+            conflict_policy: MemoryConflictPolicy::default(),
             llm: MemoryLlmConfig::default(),
             ephemeral: MemoryEphemeralConfig::default(),
         }
@@ -61,6 +130,9 @@ impl MemoryConfig {
             && !self.inject
             && self.inject_token_budget == 2000
             && self.consolidation_threshold == 100
+            && self.secret_policy == MemorySecretPolicy::default()
+            && self.index_backend == MemoryIndexBackend::default()
+            && self.conflict_policy == MemoryConflictPolicy::default()
             && self.llm.is_default()
             && self.ephemeral.is_default()
     }
@@ -170,7 +242,10 @@ fn mask_api_key(api_key: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{MemoryBackend, MemoryConfig, MemoryLlmConfig};
+    use super::{
+        MemoryBackend, MemoryConfig, MemoryConflictPolicy, MemoryIndexBackend, MemoryLlmConfig,
+        MemorySecretPolicy,
+    };
     use crate::ProjectConfig;
 
     #[derive(Debug, serde::Deserialize)]
@@ -187,10 +262,41 @@ mod tests {
         assert!(!parsed.memory.inject);
         assert_eq!(parsed.memory.inject_token_budget, 2000);
         assert_eq!(parsed.memory.consolidation_threshold, 100);
+        assert_eq!(parsed.memory.secret_policy, MemorySecretPolicy::Mask);
         assert!(!parsed.memory.llm.enabled);
         assert!(!parsed.memory.ephemeral.enabled);
     }
 
+    #[test]
+    fn test_memory_secret_policy_parses_reject() {
+        let parsed: MemoryEnvelope =
+            toml::from_str("[memory]\nsecret_policy = \"reject\"\n").unwrap();
+        assert_eq!(parsed.memory.secret_policy, MemorySecretPolicy::Reject);
+        assert!(!parsed.memory.is_default());
+    }
+
+    #[test]
+    fn test_memory_index_backend_parses_sqlite() {
+        let parsed: MemoryEnvelope = toml::from_str("[memory]\n").unwrap();
+        assert_eq!(parsed.memory.index_backend, MemoryIndexBackend::Tantivy);
+
+        let parsed: MemoryEnvelope =
+            toml::from_str("[memory]\nindex_backend = \"sqlite\"\n").unwrap();
+        assert_eq!(parsed.memory.index_backend, MemoryIndexBackend::Sqlite);
+        assert!(!parsed.memory.is_default());
+    }
+
+    #[test]
+    fn test_memory_conflict_policy_parses_prefer_recent() {
+        let parsed: MemoryEnvelope =
+            toml::from_str("[memory]\nconflict_policy = \"prefer-recent\"\n").unwrap();
+        assert_eq!(
+            parsed.memory.conflict_policy,
+            MemoryConflictPolicy::PreferRecent
+        );
+        assert!(!parsed.memory.is_default());
+    }
+
     #[test]
     fn test_memory_config_full() {
         let toml = r#"
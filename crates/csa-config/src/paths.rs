@@ -4,6 +4,12 @@ use std::path::{Path, PathBuf};
 pub const APP_NAME: &str = "cli-sub-agent";
 /// Legacy XDG app name kept for backward-compatible reads and migration.
 pub const LEGACY_APP_NAME: &str = "csa";
+/// Relocates all CSA state (sessions, todos, memory, slots, locks) and the
+/// runtime directory, which becomes `$CSA_STATE_DIR/run` (per user in
+/// shared-state mode, see [`state_runtime_dir`]).
+pub const STATE_DIR_ENV: &str = "CSA_STATE_DIR";
+/// Relocates the global config directory.
+pub const CONFIG_DIR_ENV: &str = "CSA_CONFIG_DIR";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct XdgPathPair {
@@ -12,6 +18,24 @@ pub struct XdgPathPair {
     pub legacy_path: PathBuf,
 }
 
+/// Directory named by a non-empty `var`, made absolute so a later chdir
+/// does not move it.
+fn dir_override(var: &str) -> Option<PathBuf> {
+    let value = std::env::var_os(var).filter(|value| !value.is_empty())?;
+    let path = PathBuf::from(value);
+    Some(std::path::absolute(&path).unwrap_or(path))
+}
+
+/// `CSA_STATE_DIR`, when set.
+pub fn state_dir_override() -> Option<PathBuf> {
+    dir_override(STATE_DIR_ENV)
+}
+
+/// `CSA_CONFIG_DIR`, when set.
+pub fn config_dir_override() -> Option<PathBuf> {
+    dir_override(CONFIG_DIR_ENV)
+}
+
 fn project_config_dir(app_name: &str) -> Option<PathBuf> {
     directories::ProjectDirs::from("", "", app_name).map(|dirs| dirs.config_dir().to_path_buf())
 }
@@ -41,7 +65,7 @@ fn runtime_dir_for_name(app_name: &str, runtime_root: Option<&str>, uid: u32) ->
     PathBuf::from("/tmp").join(format!("{app_name}-{uid}"))
 }
 
-fn effective_uid() -> u32 {
+pub(crate) fn effective_uid() -> u32 {
     #[cfg(unix)]
     {
         // SAFETY: `geteuid` has no preconditions and returns caller effective UID.
@@ -56,6 +80,9 @@ fn effective_uid() -> u32 {
 /// Resolve config directory for reads:
 /// prefer canonical path, fallback to legacy if canonical does not exist.
 pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = config_dir_override() {
+        return Some(dir);
+    }
     let new_path = project_config_dir(APP_NAME)?;
     let legacy_path = project_config_dir(LEGACY_APP_NAME)?;
     Some(choose_read_path(new_path, legacy_path))
@@ -63,12 +90,15 @@ pub fn config_dir() -> Option<PathBuf> {
 
 /// Canonical config directory for writes (always new path).
 pub fn config_dir_write() -> Option<PathBuf> {
-    project_config_dir(APP_NAME)
+    config_dir_override().or_else(|| project_config_dir(APP_NAME))
 }
 
 /// Resolve state directory for reads:
 /// prefer canonical path, fallback to legacy if canonical does not exist.
 pub fn state_dir() -> Option<PathBuf> {
+    if let Some(dir) = state_dir_override() {
+        return Some(dir);
+    }
     let new_path = project_state_dir(APP_NAME)?;
     let legacy_path = project_state_dir(LEGACY_APP_NAME)?;
     Some(choose_read_path(new_path, legacy_path))
@@ -76,12 +106,53 @@ pub fn state_dir() -> Option<PathBuf> {
 
 /// Canonical state directory for writes (always new path).
 pub fn state_dir_write() -> Option<PathBuf> {
-    project_state_dir(APP_NAME)
+    state_dir_override().or_else(|| project_state_dir(APP_NAME))
+}
+
+/// Root of the current user's session trees: the state directory, or its
+/// `users/<user>/` subdirectory in shared-state mode (see
+/// [`crate::shared_state`]). Nothing is created; writers call
+/// [`create_session_state_dir`] first.
+pub fn session_state_dir() -> Option<PathBuf> {
+    let state_dir = state_dir_write()?;
+    if !crate::shared_state::enabled() {
+        return Some(state_dir);
+    }
+    Some(crate::shared_state::user_state_dir(&state_dir))
+}
+
+/// Create [`session_state_dir`] before writing under it. In shared-state mode
+/// the shared `users/` directory is created first and the user's own
+/// directory `rwx------`, which a plain `create_dir_all` of a subdirectory
+/// would leave readable by the group.
+pub fn create_session_state_dir() -> std::io::Result<()> {
+    let Some(state_dir) = state_dir_write() else {
+        return Ok(());
+    };
+    if !crate::shared_state::enabled() {
+        return Ok(());
+    }
+    crate::shared_state::create_dir_all(&state_dir.join(crate::shared_state::USERS_DIR))?;
+    crate::shared_state::create_private_dir(&crate::shared_state::user_state_dir(&state_dir))
+}
+
+/// Runtime directory under a `CSA_STATE_DIR` override. Shared-state mode puts
+/// it in the current user's `users/<user>/` tree, since daemon sockets and
+/// pid files must not collide between users.
+fn state_runtime_dir(state_dir: &Path) -> PathBuf {
+    if crate::shared_state::enabled() {
+        crate::shared_state::user_state_dir(state_dir).join("run")
+    } else {
+        state_dir.join("run")
+    }
 }
 
 /// Resolve runtime directory for reads:
 /// prefer canonical path, fallback to legacy if canonical does not exist.
 pub fn runtime_dir() -> PathBuf {
+    if let Some(dir) = state_dir_override() {
+        return state_runtime_dir(&dir);
+    }
     let runtime_root = std::env::var("XDG_RUNTIME_DIR").ok();
     let uid = effective_uid();
     let new_path = runtime_dir_for_name(APP_NAME, runtime_root.as_deref(), uid);
@@ -91,15 +162,28 @@ pub fn runtime_dir() -> PathBuf {
 
 /// Canonical runtime directory for writes (always new path).
 pub fn runtime_dir_write() -> PathBuf {
+    if let Some(dir) = state_dir_override() {
+        return state_runtime_dir(&dir);
+    }
     let runtime_root = std::env::var("XDG_RUNTIME_DIR").ok();
     runtime_dir_for_name(APP_NAME, runtime_root.as_deref(), effective_uid())
 }
 
+/// Legacy config directory; `None` under `CSA_CONFIG_DIR`, which replaces
+/// both layouts.
 pub fn legacy_config_dir() -> Option<PathBuf> {
+    if config_dir_override().is_some() {
+        return None;
+    }
     project_config_dir(LEGACY_APP_NAME)
 }
 
+/// Legacy state directory; `None` under `CSA_STATE_DIR`, which replaces
+/// both layouts.
 pub fn legacy_state_dir() -> Option<PathBuf> {
+    if state_dir_override().is_some() {
+        return None;
+    }
     project_state_dir(LEGACY_APP_NAME)
 }
 
@@ -166,11 +250,13 @@ pub fn xdg_path_pairs() -> Vec<XdgPathPair> {
             legacy_path,
         });
     }
-    pairs.push(XdgPathPair {
-        label: "runtime",
-        new_path: runtime_dir_write(),
-        legacy_path: legacy_runtime_dir(),
-    });
+    if state_dir_override().is_none() {
+        pairs.push(XdgPathPair {
+            label: "runtime",
+            new_path: runtime_dir_write(),
+            legacy_path: legacy_runtime_dir(),
+        });
+    }
     pairs
 }
 
@@ -204,8 +290,8 @@ pub fn legacy_paths_requiring_migration() -> Vec<XdgPathPair> {
 
 #[cfg(test)]
 mod tests {
-    use super::{APP_NAME, LEGACY_APP_NAME, choose_read_path, runtime_dir_for_name};
-    use std::path::PathBuf;
+    use super::*;
+    use serial_test::serial;
 
     #[test]
     fn choose_read_path_prefers_new_when_present() {
@@ -241,4 +327,82 @@ mod tests {
         let path = runtime_dir_for_name(LEGACY_APP_NAME, None, 1234);
         assert_eq!(path, PathBuf::from("/tmp").join("csa-1234"));
     }
+
+    #[test]
+    #[serial]
+    fn overrides_replace_xdg_and_legacy_directories() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let state = temp.path().join("state");
+        let config = temp.path().join("config");
+        // SAFETY: test-scoped env mutation, serialized and reverted below.
+        unsafe {
+            std::env::set_var(STATE_DIR_ENV, &state);
+            std::env::set_var(CONFIG_DIR_ENV, &config);
+        }
+
+        assert_eq!(state_dir(), Some(state.clone()));
+        assert_eq!(state_dir_write(), Some(state.clone()));
+        assert_eq!(legacy_state_dir(), None);
+        assert_eq!(runtime_dir_write(), state.join("run"));
+        assert_eq!(config_dir(), Some(config.clone()));
+        assert_eq!(config_dir_write(), Some(config));
+        assert_eq!(legacy_config_dir(), None);
+        assert!(xdg_path_pairs().is_empty());
+
+        // SAFETY: see above.
+        unsafe {
+            std::env::set_var(STATE_DIR_ENV, "");
+            std::env::remove_var(CONFIG_DIR_ENV);
+        }
+        assert_eq!(state_dir_override(), None);
+        assert_ne!(state_dir_write(), Some(state));
+        // SAFETY: see above.
+        unsafe { std::env::remove_var(STATE_DIR_ENV) };
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn shared_state_scopes_sessions_per_user_under_a_shared_users_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        // SAFETY: test-scoped env mutation, serialized and reverted below.
+        unsafe {
+            std::env::set_var(STATE_DIR_ENV, temp.path());
+            std::env::set_var(crate::shared_state::SHARED_STATE_ENV, "1");
+        }
+        let session_dir = session_state_dir();
+        let runtime = runtime_dir_write();
+        let users = temp.path().join(crate::shared_state::USERS_DIR);
+        assert!(!users.exists(), "path lookups must not create directories");
+        create_session_state_dir().expect("create session state dir");
+        // SAFETY: see above.
+        unsafe { std::env::remove_var(crate::shared_state::SHARED_STATE_ENV) };
+        let unshared = session_state_dir();
+        // SAFETY: see above.
+        unsafe { std::env::remove_var(STATE_DIR_ENV) };
+
+        assert_eq!(
+            session_dir,
+            Some(crate::shared_state::user_state_dir(temp.path()))
+        );
+        let session_dir = session_dir.expect("session dir");
+        assert_eq!(session_dir.parent(), Some(users.as_path()));
+        assert_eq!(runtime, session_dir.join("run"));
+        let mode = |path: &Path| {
+            std::fs::metadata(path)
+                .expect("state dir")
+                .permissions()
+                .mode()
+                & 0o7777
+        };
+        assert_eq!(mode(&users), 0o2770);
+        assert_eq!(
+            mode(&session_dir),
+            0o700,
+            "other users must not read sessions"
+        );
+        assert_eq!(unshared, Some(temp.path().to_path_buf()));
+    }
 }
@@ -0,0 +1,257 @@
+//! Shared-state mode for build servers where several users point
+//! `CSA_STATE_DIR` at one group-owned directory.
+//!
+//! With `CSA_SHARED_STATE=1` the state dir splits in two:
+//!
+//! - Per-user, under `users/<user>/` (created `rwx------`, see
+//!   [`crate::paths::create_session_state_dir`]): sessions and the todos under
+//!   them, the runtime dir, memories, the metrics spool, MCP hub request
+//!   logs, checklists and peak-memory statistics.
+//! - Shared, created through [`create_dir_all`] and [`share_file`]: slots,
+//!   project locks and the merge-guard audit log, so concurrency limits and
+//!   merge records span every user.
+//!
+//! Shared directories are created `rwxrws---` and shared files `rw-rw----`
+//! whatever the caller's umask, so any group member can reuse what another
+//! created first.
+
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Enables shared-state mode when set to `1`, `true`, `yes` or `on`.
+pub const SHARED_STATE_ENV: &str = "CSA_SHARED_STATE";
+
+/// Directory under the state dir holding one session tree per user.
+pub const USERS_DIR: &str = "users";
+
+#[cfg(unix)]
+const SHARED_DIR_MODE: u32 = 0o2770;
+#[cfg(unix)]
+const SHARED_FILE_MODE: u32 = 0o660;
+#[cfg(unix)]
+const PRIVATE_DIR_MODE: u32 = 0o700;
+
+/// Whether shared-state mode is on.
+pub fn enabled() -> bool {
+    std::env::var(SHARED_STATE_ENV).is_ok_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// Login name of the effective user, recorded in lock diagnostics and
+/// session metadata. Falls back to `$USER`, then `uid-<uid>`.
+pub fn current_user() -> &'static str {
+    static USER: OnceLock<String> = OnceLock::new();
+    USER.get_or_init(|| {
+        let uid = crate::paths::effective_uid();
+        passwd_name(uid)
+            .or_else(|| std::env::var("USER").ok().filter(|user| !user.is_empty()))
+            .unwrap_or_else(|| format!("uid-{uid}"))
+    })
+}
+
+#[cfg(unix)]
+fn passwd_name(uid: u32) -> Option<String> {
+    let mut buf = vec![0 as libc::c_char; 4096];
+    // SAFETY: `passwd` is plain data; getpwuid_r fills it in on success.
+    let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    // SAFETY: all pointers are valid for the call and `buf.len()` is the
+    // capacity of `buf`, which backs the strings written into `pwd`.
+    let ret = unsafe { libc::getpwuid_r(uid, &mut pwd, buf.as_mut_ptr(), buf.len(), &mut result) };
+    if ret != 0 || result.is_null() || pwd.pw_name.is_null() {
+        return None;
+    }
+    // SAFETY: on success `pw_name` is a NUL-terminated string inside `buf`.
+    let name = unsafe { std::ffi::CStr::from_ptr(pwd.pw_name) };
+    name.to_str()
+        .ok()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(not(unix))]
+fn passwd_name(_uid: u32) -> Option<String> {
+    None
+}
+
+/// `user` as a single path component.
+fn user_dir_name(user: &str) -> String {
+    let name: String = user
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.trim_matches('.').is_empty() {
+        "unknown".to_string()
+    } else {
+        name
+    }
+}
+
+/// The current user's directory under `state_dir`.
+pub fn user_state_dir(state_dir: &Path) -> PathBuf {
+    state_dir
+        .join(USERS_DIR)
+        .join(user_dir_name(current_user()))
+}
+
+/// Create `path`, the current user's directory under `users/`, as
+/// `rwx------` so other group members cannot read its sessions. An existing
+/// directory is tightened too, in case a subdirectory write created it first.
+pub fn create_private_dir(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        match fs::DirBuilder::new().mode(PRIVATE_DIR_MODE).create(path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists && path.is_dir() => {
+                if fs::metadata(path)?.permissions().mode() & 0o7777 == PRIVATE_DIR_MODE {
+                    return Ok(());
+                }
+            }
+            Err(err) => return Err(err),
+        }
+        fs::set_permissions(path, fs::Permissions::from_mode(PRIVATE_DIR_MODE))
+    }
+    #[cfg(not(unix))]
+    fs::create_dir_all(path)
+}
+
+/// `fs::create_dir_all`, except that in shared-state mode every directory it
+/// creates is group-writable and setgid, so files inside keep the group.
+pub fn create_dir_all(path: &Path) -> io::Result<()> {
+    if !enabled() {
+        return fs::create_dir_all(path);
+    }
+    create_shared_dir_all(path)
+}
+
+#[cfg(unix)]
+fn create_shared_dir_all(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if path.is_dir() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        create_shared_dir_all(parent)?;
+    }
+    match fs::create_dir(path) {
+        // The umask only applies at creation; chmod sets the full mode.
+        Ok(()) => fs::set_permissions(path, fs::Permissions::from_mode(SHARED_DIR_MODE)),
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists && path.is_dir() => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(not(unix))]
+fn create_shared_dir_all(path: &Path) -> io::Result<()> {
+    fs::create_dir_all(path)
+}
+
+/// In shared-state mode, make a slot or lock file the current user owns
+/// group read-writable. Files owned by others are left as they are, since
+/// only the owner may change their mode.
+pub fn share_file(file: &File) -> io::Result<()> {
+    if !enabled() {
+        return Ok(());
+    }
+    share_owned_file(file)
+}
+
+#[cfg(unix)]
+fn share_owned_file(file: &File) -> io::Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let metadata = file.metadata()?;
+    if metadata.uid() != crate::paths::effective_uid()
+        || metadata.mode() & SHARED_FILE_MODE == SHARED_FILE_MODE
+    {
+        return Ok(());
+    }
+    file.set_permissions(fs::Permissions::from_mode(SHARED_FILE_MODE))
+}
+
+#[cfg(not(unix))]
+fn share_owned_file(_file: &File) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn mode(path: &Path) -> u32 {
+        fs::metadata(path).expect("metadata").permissions().mode() & 0o7777
+    }
+
+    #[test]
+    fn user_dir_name_is_a_single_safe_component() {
+        assert_eq!(user_dir_name("alice"), "alice");
+        assert_eq!(user_dir_name("DOMAIN\\bob smith"), "DOMAIN_bob_smith");
+        assert_eq!(user_dir_name("../x"), ".._x");
+        assert_eq!(user_dir_name(".."), "unknown");
+        assert!(!current_user().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn shared_mode_creates_group_writable_dirs_and_files_despite_umask() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = temp.path().join("slots").join("codex");
+        let file_path = dir.join("slot-00.lock");
+
+        // The umask is process-wide, so the shared-mode half runs in a child.
+        let output = std::process::Command::new(std::env::current_exe().expect("test binary"))
+            .arg("shared_state_umask_child_entrypoint")
+            .arg("--nocapture")
+            .env(SHARED_STATE_ENV, "1")
+            .env("CSA_SHARED_STATE_TEST_DIR", &dir)
+            .output()
+            .expect("run child test process");
+        let child_stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success() && !child_stdout.contains("running 0 tests"),
+            "child process failed\nstdout:\n{}\nstderr:\n{}",
+            child_stdout,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(mode(&temp.path().join("slots")), 0o2770);
+        assert_eq!(mode(&dir), 0o2770);
+        assert_eq!(mode(&file_path), 0o660);
+
+        let private = temp.path().join("private");
+        create_dir_all(&private).expect("create private dir");
+        assert_eq!(mode(&private) & 0o2000, 0, "setgid only in shared mode");
+    }
+
+    #[test]
+    fn shared_state_umask_child_entrypoint() {
+        let Some(dir) = std::env::var_os("CSA_SHARED_STATE_TEST_DIR") else {
+            return;
+        };
+        let dir = PathBuf::from(dir);
+        // SAFETY: `umask` has no preconditions; this child process exits after the test.
+        unsafe { libc::umask(0o077) };
+
+        create_dir_all(&dir).expect("create shared dirs");
+        let file = File::create(dir.join("slot-00.lock")).expect("create file");
+        share_file(&file).expect("share file");
+    }
+}
@@ -5,14 +5,10 @@ use crate::config::{FORK_PREFIX_BUDGET_MAX_TOKENS, FORK_PREFIX_BUDGET_MIN_TOKENS
 use crate::global::ToolSelection;
 use crate::{EffectiveConfig, EffectiveModelCatalog, TransportKind};
 
-const KNOWN_TOOLS: &[&str] = &[
-    "opencode",
-    "codex",
-    "claude-code",
-    "openai-compat",
-    "hermes",
-    "antigravity-cli",
-];
+fn is_known_tool(name: &str) -> bool {
+    csa_core::tool_registry::supported_tool_ids().any(|id| id == name)
+}
+
 const LEGAL_TRANSPORT_VALUES: &str = "auto, acp, cli, tmux";
 
 /// Validate a project configuration file.
@@ -61,7 +57,7 @@ pub(crate) fn validate_tool_transport_overrides_in_raw_config(raw: &toml::Value)
     };
 
     for (tool_name, tool_value) in tools {
-        if !KNOWN_TOOLS.contains(&tool_name.as_str()) {
+        if !is_known_tool(tool_name) {
             continue;
         }
 
@@ -160,7 +156,7 @@ fn removed_gemini_cli_reference_error(source: &str, path: &str, value: &str) ->
 
 pub(crate) fn validate_tool_transport_overrides(config: &ProjectConfig) -> Result<()> {
     for (tool_name, tool_config) in &config.tools {
-        if !KNOWN_TOOLS.contains(&tool_name.as_str()) {
+        if !is_known_tool(tool_name) {
             continue;
         }
         if let Some(transport) = tool_config.transport {
@@ -238,6 +234,25 @@ fn validate_resources(config: &ProjectConfig) -> Result<()> {
                  Tool processes need at least 10 PIDs for process trees."
         );
     }
+    if let Some(percent) = config.resources.cpu_max_percent
+        && percent == 0
+    {
+        bail!(
+            "resources.cpu_max_percent must be >= 1 (got 0). \
+             Omit the key to leave CPU unthrottled."
+        );
+    }
+    if let Some(weight) = config.resources.cpu_weight
+        && !(1..=10000).contains(&weight)
+    {
+        bail!("resources.cpu_weight must be 1-10000 (got {weight}).");
+    }
+    if config.resources.disk_max_mb == Some(0) {
+        bail!(
+            "resources.disk_max_mb must be >= 1 (got 0). \
+             Omit the key to disable the disk budget."
+        );
+    }
     if let Some(percent) = config.resources.soft_limit_percent
         && (percent == 0 || percent > 100)
     {
@@ -246,6 +261,22 @@ fn validate_resources(config: &ProjectConfig) -> Result<()> {
              0 silently disables the memory monitor."
         );
     }
+    for (key, threshold) in [
+        (
+            "psi_memory_threshold",
+            config.resources.psi_memory_threshold,
+        ),
+        ("psi_cpu_threshold", config.resources.psi_cpu_threshold),
+    ] {
+        if let Some(threshold) = threshold
+            && !(threshold > 0.0 && threshold <= 100.0)
+        {
+            bail!(
+                "resources.{key} must be in (0, 100] (got {threshold}). \
+                 Omit the key to disable pressure throttling."
+            );
+        }
+    }
     if let Some(interval) = config.resources.memory_monitor_interval_seconds
         && interval == 0
     {
@@ -277,8 +308,16 @@ fn validate_acp(config: &ProjectConfig) -> Result<()> {
 
 fn validate_tools(config: &ProjectConfig) -> Result<()> {
     for (tool_name, tool_config) in &config.tools {
-        if !KNOWN_TOOLS.contains(&tool_name.as_str()) {
-            bail!("Unknown tool '{tool_name}'. Known tools: {KNOWN_TOOLS:?}");
+        if tool_config.plugin {
+            validate_plugin_tool(tool_name, tool_config)?;
+            continue;
+        }
+        if !is_known_tool(tool_name) {
+            bail!(
+                "Unknown tool '{tool_name}'. Known tools: {:?}. \
+                 Set `plugin = true` to register an executor plugin.",
+                csa_core::tool_registry::supported_tool_ids().collect::<Vec<_>>()
+            );
         }
         if let Some(transport) = tool_config.transport {
             validate_tool_transport_override(tool_name, transport)?;
@@ -320,6 +359,25 @@ fn validate_tools(config: &ProjectConfig) -> Result<()> {
     Ok(())
 }
 
+fn validate_plugin_tool(tool_name: &str, tool_config: &crate::ToolConfig) -> Result<()> {
+    if csa_core::tool_registry::builtin_tool(tool_name).is_some() {
+        bail!("tools.{tool_name}.plugin = true: '{tool_name}' is a built-in tool name");
+    }
+    if !crate::is_valid_plugin_tool_name(tool_name) {
+        bail!(
+            "tools.{tool_name}.plugin = true: plugin names must use lowercase letters, digits, \
+             '-' and '_', starting with a letter"
+        );
+    }
+    if tool_config.transport.is_some() || tool_config.tmux_mode || tool_config.codex_auto_trust {
+        bail!(
+            "tools.{tool_name}: transport, tmux_mode and codex_auto_trust do not apply to \
+             executor plugins"
+        );
+    }
+    Ok(())
+}
+
 fn validate_tool_tmux_mode(tool_name: &str, tool_config: &crate::ToolConfig) -> Result<()> {
     if !tool_config.tmux_mode {
         return Ok(());
@@ -511,7 +569,7 @@ fn validate_tiers(config: &ProjectConfig, catalog: &EffectiveModelCatalog) -> Re
             bail!("Tier '{tier_name}' must have at least one model");
         }
         for model_spec in &tier_config.models {
-            validate_model_spec(tier_name, model_spec, catalog)?;
+            validate_model_spec(config, tier_name, model_spec, catalog)?;
         }
         // Validate budget constraints
         if let Some(budget) = tier_config.token_budget
@@ -582,6 +640,7 @@ fn warn_unknown_tool_priority(config: &ProjectConfig) {
 }
 
 fn validate_model_spec(
+    config: &ProjectConfig,
     tier_name: &str,
     model_spec: &str,
     catalog: &EffectiveModelCatalog,
@@ -604,6 +663,11 @@ fn validate_model_spec(
             csa_core::types::removed_tool_error("gemini-cli")
         );
     }
+    // Plugins declare their own models in the handshake, so the catalog has
+    // nothing to check them against.
+    if config.is_plugin_tool(tool_part) {
+        return Ok(());
+    }
     let known_tools: Vec<&str> = crate::global::all_known_tools()
         .iter()
         .map(|t| t.as_str())
@@ -647,3 +711,7 @@ mod tests_model_catalog;
 #[cfg(test)]
 #[path = "validate_tests_transport.rs"]
 mod tests_transport;
+
+#[cfg(test)]
+#[path = "validate_tests_plugin.rs"]
+mod tests_plugin;
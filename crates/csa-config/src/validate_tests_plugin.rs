@@ -0,0 +1,67 @@
+use super::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn write_raw_project_config(dir: &Path, config_toml: &str) -> PathBuf {
+    let config_dir = dir.join(".csa");
+    fs::create_dir_all(&config_dir).unwrap();
+    let config_path = config_dir.join("config.toml");
+    fs::write(&config_path, config_toml).unwrap();
+    config_path
+}
+
+#[test]
+fn plugin_tools_and_their_tier_specs_validate() {
+    let dir = tempdir().unwrap();
+    let config_path = write_raw_project_config(
+        dir.path(),
+        r#"
+[tools.local-llm]
+plugin = true
+
+[tiers.tier-1-quick]
+description = "quick"
+models = ["local-llm/ollama/qwen3-coder/default", "codex/openai/gpt-5.5/xhigh"]
+"#,
+    );
+
+    let result = validate_config_with_paths(None, &config_path);
+    assert!(result.is_ok(), "plugin config should validate: {result:?}");
+}
+
+#[test]
+fn unregistered_tool_names_still_fail_with_plugin_hint() {
+    let dir = tempdir().unwrap();
+    let config_path = write_raw_project_config(
+        dir.path(),
+        r#"
+[tools.local-llm]
+enabled = true
+"#,
+    );
+
+    let message = format!(
+        "{:#}",
+        validate_config_with_paths(None, &config_path).unwrap_err()
+    );
+    assert!(message.contains("Unknown tool 'local-llm'"), "{message}");
+    assert!(message.contains("plugin = true"), "{message}");
+}
+
+#[test]
+fn plugin_registration_rejects_builtin_and_unsafe_names() {
+    for (tool, expected) in [
+        ("codex", "built-in tool name"),
+        ("Local.LLM", "lowercase letters"),
+    ] {
+        let dir = tempdir().unwrap();
+        let config_path =
+            write_raw_project_config(dir.path(), &format!("[tools.\"{tool}\"]\nplugin = true\n"));
+        let message = format!(
+            "{:#}",
+            validate_config_with_paths(None, &config_path).unwrap_err()
+        );
+        assert!(message.contains(expected), "{tool}: {message}");
+    }
+}
@@ -283,6 +283,86 @@ fn test_validate_pids_max_at_minimum() {
     assert!(result.is_ok(), "pids_max 10 should be valid");
 }
 
+#[test]
+fn test_validate_cpu_and_disk_limits_ranges() {
+    let dir = tempdir().unwrap();
+
+    let mut config = ProjectConfig {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        project: ProjectMeta {
+            name: "test".to_string(),
+            created_at: Utc::now(),
+            max_recursion_depth: 5,
+        },
+        resources: ResourcesConfig {
+            cpu_max_percent: Some(0),
+            ..Default::default()
+        },
+        acp: Default::default(),
+        tools: HashMap::new(),
+        review: None,
+        debate: None,
+        tiers: HashMap::new(),
+        tier_mapping: HashMap::new(),
+        aliases: HashMap::new(),
+        tool_aliases: HashMap::new(),
+        preferences: None,
+        github: None,
+        session: Default::default(),
+        memory: Default::default(),
+        hooks: Default::default(),
+        run: Default::default(),
+        execution: Default::default(),
+        session_wait: None,
+        preflight: Default::default(),
+        vcs: Default::default(),
+        tool_state_dirs: HashMap::new(),
+        filesystem_sandbox: Default::default(),
+    };
+
+    config.save(dir.path()).unwrap();
+    let config_path = dir.path().join(".csa").join("config.toml");
+    let err = validate_config_with_paths(None, &config_path).unwrap_err();
+    assert!(err.to_string().contains("cpu_max_percent must be >= 1"));
+
+    config.resources.cpu_max_percent = Some(250);
+    config.resources.cpu_weight = Some(20_000);
+    config.save(dir.path()).unwrap();
+    let err = validate_config_with_paths(None, &config_path).unwrap_err();
+    assert!(err.to_string().contains("cpu_weight must be 1-10000"));
+
+    config.resources.cpu_weight = Some(50);
+    config.resources.disk_max_mb = Some(0);
+    config.save(dir.path()).unwrap();
+    let err = validate_config_with_paths(None, &config_path).unwrap_err();
+    assert!(err.to_string().contains("disk_max_mb must be >= 1"));
+
+    config.resources.disk_max_mb = Some(2048);
+    config.save(dir.path()).unwrap();
+    assert!(validate_config_with_paths(None, &config_path).is_ok());
+
+    config.resources.psi_memory_threshold = Some(0.0);
+    config.save(dir.path()).unwrap();
+    let err = validate_config_with_paths(None, &config_path).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("psi_memory_threshold must be in (0, 100]")
+    );
+
+    config.resources.psi_memory_threshold = Some(20.0);
+    config.resources.psi_cpu_threshold = Some(150.0);
+    config.save(dir.path()).unwrap();
+    let err = validate_config_with_paths(None, &config_path).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("psi_cpu_threshold must be in (0, 100]")
+    );
+
+    config.resources.psi_cpu_threshold = Some(80.0);
+    config.save(dir.path()).unwrap();
+    assert!(validate_config_with_paths(None, &config_path).is_ok());
+}
+
 #[test]
 fn test_validate_node_heap_limit_mb_too_low_in_resources() {
     let dir = tempdir().unwrap();
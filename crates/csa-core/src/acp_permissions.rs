@@ -0,0 +1,110 @@
+//! Policy vocabulary for ACP permission requests (file writes, command
+//! execution) sent by agents mid-turn.
+//!
+//! `csa-config` reads it from `[acp.permissions]`; `csa-acp` evaluates it.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Decision for a request that no pattern matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcpPermissionMode {
+    /// Grant the request (CSA's historical behavior).
+    #[default]
+    Approve,
+    /// Reject the request.
+    Deny,
+    /// Reject the request for now and surface it to the orchestrator as a
+    /// `permission_request` event.
+    AskParent,
+}
+
+impl AcpPermissionMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Approve => "approve",
+            Self::Deny => "deny",
+            Self::AskParent => "ask_parent",
+        }
+    }
+}
+
+/// `[acp.permissions]` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcpPermissionsConfig {
+    #[serde(default)]
+    pub mode: AcpPermissionMode,
+    /// Globs approved without asking, matched against the request's paths
+    /// (absolute, and relative to the project root) and its title, which is
+    /// the command line for exec requests.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_approve: Vec<String>,
+    /// Globs always rejected; checked before `auto_approve`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_deny: Vec<String>,
+}
+
+impl AcpPermissionsConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Everything a session's permission decisions depend on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcpPermissionPolicy {
+    pub config: AcpPermissionsConfig,
+    /// From the tool's `ToolRestrictions`.
+    pub allow_edit_existing_files: bool,
+    /// From the tool's `ToolRestrictions`.
+    pub allow_write_new_files: bool,
+    /// Base for relative globs and for deciding whether a path is new.
+    pub project_root: Option<PathBuf>,
+}
+
+impl AcpPermissionPolicy {
+    /// Whether the policy grants every request, as CSA did before policies.
+    pub fn is_permissive(&self) -> bool {
+        self.config.is_default() && self.allow_edit_existing_files && self.allow_write_new_files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_config_parses_modes_and_globs() {
+        let config: AcpPermissionsConfig = toml::from_str(
+            r#"
+mode = "ask_parent"
+auto_approve = ["src/**", "cargo test*"]
+auto_deny = ["**/.env"]
+"#,
+        )
+        .expect("parse");
+        assert_eq!(config.mode, AcpPermissionMode::AskParent);
+        assert_eq!(config.auto_approve, ["src/**", "cargo test*"]);
+        assert_eq!(config.auto_deny, ["**/.env"]);
+        assert!(!config.is_default());
+
+        let empty: AcpPermissionsConfig = toml::from_str("").expect("parse empty");
+        assert!(empty.is_default());
+        assert_eq!(empty.mode.as_str(), "approve");
+    }
+
+    #[test]
+    fn restrictions_make_a_policy_non_permissive() {
+        let mut policy = AcpPermissionPolicy {
+            config: AcpPermissionsConfig::default(),
+            allow_edit_existing_files: true,
+            allow_write_new_files: true,
+            project_root: None,
+        };
+        assert!(policy.is_permissive());
+        policy.allow_write_new_files = false;
+        assert!(!policy.is_permissive());
+    }
+}
@@ -8,6 +8,9 @@ pub struct AuditManifest {
     pub meta: ManifestMeta,
     #[serde(default)]
     pub files: BTreeMap<String, FileEntry>,
+    /// Weave packages recorded from `weave.lock`, keyed by package name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub skills: BTreeMap<String, SkillEntry>,
 }
 
 /// Manifest metadata
@@ -24,6 +27,11 @@ pub struct ManifestMeta {
     /// When set, blog_path can be auto-computed as `{mirror_dir}/{relative_source_path}.md`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mirror_dir: Option<String>,
+    /// Fingerprint of the key that signs this manifest, set by `csa audit sign`.
+    /// It tells the loader a signature is required even when the OS keyring
+    /// cannot be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
 }
 
 fn default_project_root() -> String {
@@ -44,6 +52,17 @@ pub struct FileEntry {
     pub approved_by: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub approved_at: Option<String>,
+    /// `size:mtime_ns:ctime_ns:inode` of the file when `hash` was computed;
+    /// `audit diff --changed-only` reuses `hash` while the stat matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stat: Option<String>,
+}
+
+/// Locked weave package as recorded at scan time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkillEntry {
+    pub repo: String,
+    pub commit: String,
 }
 
 /// Audit status for a tracked file
@@ -78,8 +97,10 @@ impl AuditManifest {
                 updated_at: now,
                 last_scanned_at: None,
                 mirror_dir: None,
+                signing_key: None,
             },
             files: BTreeMap::new(),
+            skills: BTreeMap::new(),
         }
     }
 }
@@ -100,6 +121,7 @@ mod tests {
                 auditor: Some("auditor-a".to_string()),
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
         files.insert(
@@ -111,6 +133,7 @@ mod tests {
                 auditor: Some("auditor-b".to_string()),
                 approved_by: Some("human".to_string()),
                 approved_at: Some("2026-02-19T00:00:00Z".to_string()),
+                stat: None,
             },
         );
 
@@ -122,8 +145,16 @@ mod tests {
                 updated_at: "2026-02-19T00:01:00Z".to_string(),
                 last_scanned_at: Some("2026-02-19T00:02:00Z".to_string()),
                 mirror_dir: None,
+                signing_key: None,
             },
             files,
+            skills: BTreeMap::from([(
+                "my-skill".to_string(),
+                SkillEntry {
+                    repo: "https://github.com/user/my-skill.git".to_string(),
+                    commit: "abcdef1234".to_string(),
+                },
+            )]),
         };
 
         let toml = toml::to_string_pretty(&manifest).expect("manifest should serialize");
@@ -204,6 +235,7 @@ hash = "sha256:abc"
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
         manifest.files.insert(
@@ -215,6 +247,7 @@ hash = "sha256:abc"
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
         manifest.files.insert(
@@ -226,6 +259,7 @@ hash = "sha256:abc"
                 auditor: None,
                 approved_by: None,
                 approved_at: None,
+                stat: None,
             },
         );
 
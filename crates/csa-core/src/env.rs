@@ -34,6 +34,10 @@ pub const CSA_SESSION_ID_ENV_KEY: &str = "CSA_SESSION_ID";
 /// Current CSA recursion depth inherited by a nested CSA process.
 pub const CSA_DEPTH_ENV_KEY: &str = "CSA_DEPTH";
 
+/// Signed session chain and depth (`v1.<depth>.<ids>.<hmac>`) inherited by a
+/// nested CSA process; a valid token is the floor for [`CSA_DEPTH_ENV_KEY`].
+pub const CSA_LINEAGE_ENV_KEY: &str = "CSA_LINEAGE";
+
 /// Project root inherited by a nested CSA process.
 pub const CSA_PROJECT_ROOT_ENV_KEY: &str = "CSA_PROJECT_ROOT";
 
@@ -279,6 +283,7 @@ pub const CSA_PARENT_SESSION_DIR_ENV_KEY: &str = "CSA_PARENT_SESSION_DIR";
 pub const STARTUP_SUBTREE_ENV_KEYS: &[&str] = &[
     CSA_SESSION_ID_ENV_KEY,
     CSA_DEPTH_ENV_KEY,
+    CSA_LINEAGE_ENV_KEY,
     CSA_PROJECT_ROOT_ENV_KEY,
     CSA_SESSION_DIR_ENV_KEY,
     CSA_PARENT_SESSION_ENV_KEY,
@@ -1,7 +1,55 @@
-#[derive(thiserror::Error, Debug)]
+//! Typed errors csa crates return across crate boundaries.
+//!
+//! Callers classify failures by downcasting an `anyhow::Error` chain to
+//! [`AppError`] instead of matching message text. Every variant serializes as
+//! `{"kind": "<snake_case variant>", "context": ...}` for JSON output and maps
+//! to a process exit code via [`AppError::exit_code`].
+
+use serde::Serialize;
+
+#[derive(thiserror::Error, Debug, Serialize)]
+#[serde(tag = "kind", content = "context", rename_all = "snake_case")]
 pub enum AppError {
-    #[error("Session locked by PID {0}")]
-    SessionLocked(u32),
+    #[error(
+        "Session locked by PID {pid} (lock_path: {lock_path}, pid_status: {pid_status}, \
+         pid_start_time_ticks: {pid_start_time_ticks:?}, tool: {tool}, reason: {reason}, \
+         acquired: {acquired_at}{}{}{})",
+        optional_detail("holder_session_id", .holder_session_id),
+        optional_detail("resource_path", .resource_path),
+        optional_detail("user", .user)
+    )]
+    LockContention {
+        pid: u32,
+        lock_path: String,
+        /// `alive`, or `dead_or_recycled` when the holder PID no longer runs
+        /// the process that took the lock.
+        pid_status: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pid_start_time_ticks: Option<u64>,
+        tool: String,
+        reason: String,
+        acquired_at: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        holder_session_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        resource_path: Option<String>,
+        /// User that took the lock, when recorded.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+    },
+
+    /// A session lock is held by a holder whose diagnostic could not be read.
+    #[error("Session is locked (unable to read diagnostic info)")]
+    LockHeld { lock_path: String },
+
+    /// A config file failed to parse or validate. Displays only `message` so
+    /// it reads naturally under a "Failed to parse ..." context.
+    #[error("{message}")]
+    ConfigError {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        message: String,
+    },
 
     #[error("Invalid session ID '{0}': expected ULID format (26 chars Crockford Base32)")]
     InvalidSessionId(String),
@@ -46,16 +94,105 @@ pub enum AppError {
         /// (tool_name, free_slots, max_slots) for alternative tools.
         alternatives: Vec<(String, u32, u32)>,
     },
+
+    #[error("Tool '{tool}' produced no output within its idle timeout")]
+    IdleTimeout {
+        tool: String,
+        /// Effective idle window, when the caller knows it.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        idle_secs: Option<u64>,
+    },
+
+    /// The sandbox around a tool could not be set up.
+    #[error("{message}")]
+    SandboxFailure { tool: String, message: String },
+
+    /// A tool broke its transport protocol (ACP handshake, session or prompt).
+    #[error("{message}")]
+    ProtocolError { tool: String, message: String },
+}
+
+impl AppError {
+    /// [`AppError::ConfigError`] that does not come from a config file, such
+    /// as an unknown tool or a tier policy violation.
+    pub fn config(message: impl Into<String>) -> Self {
+        AppError::ConfigError {
+            path: None,
+            message: message.into(),
+        }
+    }
+
+    /// JSON form for `--format json` error output: the serialized variant
+    /// plus its rendered message and exit code.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_else(|_| serde_json::json!({}));
+        if let serde_json::Value::Object(fields) = &mut value {
+            fields.insert("message".into(), self.to_string().into());
+            fields.insert("exit_code".into(), self.exit_code().code().into());
+        }
+        value
+    }
+}
+
+fn optional_detail(name: &str, value: &Option<String>) -> String {
+    value
+        .as_deref()
+        .map(|value| format!(", {name}: {value}"))
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn lock_contention(pid: u32) -> AppError {
+        AppError::LockContention {
+            pid,
+            lock_path: "/tmp/s/locks/codex.lock".into(),
+            pid_status: "alive".into(),
+            pid_start_time_ticks: Some(77),
+            tool: "codex".into(),
+            reason: "run".into(),
+            acquired_at: "2026-01-02T03:04:05Z".into(),
+            holder_session_id: None,
+            resource_path: Some("/repo".into()),
+            user: Some("alice".into()),
+        }
+    }
+
+    #[test]
+    fn test_display_lock_contention() {
+        assert_eq!(
+            lock_contention(1234).to_string(),
+            "Session locked by PID 1234 (lock_path: /tmp/s/locks/codex.lock, pid_status: alive, \
+             pid_start_time_ticks: Some(77), tool: codex, reason: run, \
+             acquired: 2026-01-02T03:04:05Z, resource_path: /repo, user: alice)"
+        );
+    }
+
     #[test]
-    fn test_display_session_locked() {
-        let err = AppError::SessionLocked(1234);
-        assert_eq!(err.to_string(), "Session locked by PID 1234");
+    fn test_json_carries_kind_context_message_and_exit_code() {
+        let json = lock_contention(42).to_json();
+        assert_eq!(json["kind"], "lock_contention");
+        assert_eq!(json["context"]["pid"], 42);
+        assert_eq!(json["context"]["resource_path"], "/repo");
+        assert_eq!(json["context"]["user"], "alice");
+        assert!(json["context"].get("holder_session_id").is_none());
+        assert_eq!(json["exit_code"], 75);
+        assert!(
+            json["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("Session locked by PID 42 ")
+        );
+
+        let json = AppError::ToolNotInstalled("codex".into()).to_json();
+        assert_eq!(json["kind"], "tool_not_installed");
+        assert_eq!(json["context"], "codex");
+
+        let json = AppError::ProjectRootNotFound.to_json();
+        assert_eq!(json["kind"], "project_root_not_found");
+        assert!(json.get("context").is_none());
     }
 
     #[test]
@@ -175,8 +312,8 @@ mod tests {
     #[test]
     fn test_display_boundary_values() {
         // Zero PID
-        let err = AppError::SessionLocked(0);
-        assert_eq!(err.to_string(), "Session locked by PID 0");
+        let err = lock_contention(0);
+        assert!(err.to_string().starts_with("Session locked by PID 0 "));
 
         // Max depth values
         let err = AppError::MaxDepthExceeded {
@@ -0,0 +1,172 @@
+//! Process exit codes shared by every `csa` command.
+
+use crate::error::AppError;
+
+/// Exit status of a `csa` process.
+///
+/// Codes 1 and 2 keep their historical review/debate meaning. The remaining
+/// codes follow `sysexits.h`, which keeps them clear of small tool exit codes
+/// and of signal exits (`128 + N`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ExitCode {
+    Success = 0,
+    /// A review or debate verdict reported blocking findings.
+    ReviewFindings = 1,
+    /// The tool failed: non-zero exit, crash, rate limit, or no usable verdict.
+    ToolFailure = 2,
+    /// The command line names something that is malformed or does not
+    /// exist: a session ID or prefix, or a `--cd` directory.
+    UsageError = 64,
+    /// Every slot for the tool was occupied and no slot became free.
+    SlotExhausted = 69,
+    /// Any other CSA error; a bug rather than a rejected request.
+    InternalError = 70,
+    /// A session or resource lock is held by another process.
+    LockContention = 75,
+    /// Configuration is invalid or rejects the request (tier or branch
+    /// policy, depth limit, unknown or disabled tool).
+    ConfigError = 78,
+}
+
+impl ExitCode {
+    pub const fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<ExitCode> for i32 {
+    fn from(code: ExitCode) -> Self {
+        code.code()
+    }
+}
+
+impl AppError {
+    /// Exit code a `csa` process reports when it fails with this error.
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            AppError::LockContention { .. } | AppError::LockHeld { .. } => ExitCode::LockContention,
+            AppError::SlotExhausted { .. } => ExitCode::SlotExhausted,
+            AppError::ToolExecError(_)
+            | AppError::RateLimited { .. }
+            | AppError::TierExhausted { .. }
+            | AppError::IdleTimeout { .. }
+            | AppError::ProtocolError { .. } => ExitCode::ToolFailure,
+            AppError::ConfigError { .. }
+            | AppError::ToolNotInstalled(_)
+            | AppError::ToolDisabled(_)
+            | AppError::MaxDepthExceeded { .. } => ExitCode::ConfigError,
+            AppError::InvalidSessionId(_)
+            | AppError::SessionNotFound(_)
+            | AppError::AmbiguousSessionPrefix(_)
+            | AppError::ProjectRootNotFound => ExitCode::UsageError,
+            AppError::ParentSessionViolation
+            | AppError::InsufficientMemory { .. }
+            | AppError::SandboxFailure { .. } => ExitCode::InternalError,
+        }
+    }
+}
+
+/// Exit code table appended to `csa --help`.
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0   success
+  1   review/debate reported blocking findings
+  2   tool failure (non-zero exit, failed gate, rate limit, no verdict)
+  64  usage error (malformed or unknown session ID, missing --cd directory)
+  69  all tool slots occupied
+  70  internal error (any other failure)
+  75  session or resource lock held by another process
+  78  configuration error (invalid config, tier/branch policy, depth limit, unknown tool)
+  124 run timed out; 128+N interrupted by signal N";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let codes = [
+            ExitCode::Success,
+            ExitCode::ReviewFindings,
+            ExitCode::ToolFailure,
+            ExitCode::UsageError,
+            ExitCode::SlotExhausted,
+            ExitCode::InternalError,
+            ExitCode::LockContention,
+            ExitCode::ConfigError,
+        ]
+        .map(ExitCode::code);
+        for (i, code) in codes.iter().enumerate() {
+            assert!(!codes[i + 1..].contains(code), "duplicate exit code {code}");
+            assert!(
+                EXIT_CODES_HELP.contains(&format!("\n  {code} ")),
+                "exit code {code} missing from help"
+            );
+        }
+    }
+
+    #[test]
+    fn app_errors_map_to_their_category() {
+        assert_eq!(
+            AppError::LockHeld {
+                lock_path: "/tmp/s/locks/codex.lock".into(),
+            }
+            .exit_code(),
+            ExitCode::LockContention
+        );
+        assert_eq!(
+            AppError::config("Unknown tool: bogus").exit_code(),
+            ExitCode::ConfigError
+        );
+        assert_eq!(
+            AppError::ConfigError {
+                path: Some(".csa/config.toml".into()),
+                message: "expected a table".into(),
+            }
+            .exit_code(),
+            ExitCode::ConfigError
+        );
+        assert_eq!(
+            AppError::IdleTimeout {
+                tool: "codex".into(),
+                idle_secs: Some(120),
+            }
+            .exit_code(),
+            ExitCode::ToolFailure
+        );
+        assert_eq!(
+            AppError::ProtocolError {
+                tool: "claude-code".into(),
+                message: "ACP initialization failed".into(),
+            }
+            .exit_code(),
+            ExitCode::ToolFailure
+        );
+        assert_eq!(
+            AppError::SlotExhausted {
+                tool: "codex".into(),
+                max: 2,
+                alternatives: Vec::new(),
+            }
+            .exit_code(),
+            ExitCode::SlotExhausted
+        );
+        assert_eq!(
+            AppError::RateLimited {
+                tool: "codex".into(),
+                message: "429".into(),
+            }
+            .exit_code(),
+            ExitCode::ToolFailure
+        );
+        assert_eq!(
+            AppError::ToolDisabled("codex".into()).exit_code(),
+            ExitCode::ConfigError
+        );
+        assert_eq!(
+            AppError::SessionNotFound("01J".into()).exit_code(),
+            ExitCode::UsageError
+        );
+    }
+}
@@ -1,13 +1,17 @@
+pub mod acp_permissions;
 pub mod audit;
 pub mod checklist;
 pub mod consensus;
 pub mod env;
 pub mod error;
+pub mod exit_code;
 pub mod gemini;
+pub mod metrics;
 pub mod model_catalog;
 pub mod redact;
 pub mod spec_validate;
 pub mod thinking_budget;
+pub mod tool_registry;
 pub mod transport_events;
 pub mod types;
 pub mod vcs;
@@ -0,0 +1,631 @@
+//! Prometheus metrics shared by csa processes and long-running components.
+//!
+//! csa runs are short-lived processes nothing can scrape, so once
+//! [`enable_spool`] is called (`[metrics] enabled = true`) they append
+//! [`MetricEvent`]s as JSON lines to a spool file. A long-running component
+//! such as the MCP hub tails the spool with a [`SpoolReader`], folds the
+//! events into a [`Registry`] next to its own measurements, and serves
+//! [`Registry::render`] in the Prometheus text exposition format.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Spool size at which writers move it aside to `<spool>.1`.
+const MAX_SPOOL_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Histogram buckets, in seconds, for run durations and slot waits.
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.1, 1.0, 5.0, 15.0, 30.0, 60.0, 300.0, 900.0, 1800.0, 3600.0,
+];
+
+/// Histogram buckets, in seconds, for MCP proxy call latencies.
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Something a csa process did that a metrics endpoint counts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum MetricEvent {
+    RunStarted {
+        tool: String,
+    },
+    RunCompleted {
+        tool: String,
+        success: bool,
+        duration_secs: f64,
+    },
+    /// A run moved from one tool to another after a rate limit.
+    Failover {
+        from: String,
+        to: String,
+    },
+    /// Time spent waiting for a free tool slot.
+    SlotWait {
+        tool: String,
+        wait_secs: f64,
+        timed_out: bool,
+    },
+}
+
+static SPOOL: OnceLock<PathBuf> = OnceLock::new();
+
+/// Make [`record`] append to the spool at `path` for the rest of the process.
+pub fn enable_spool(path: PathBuf) {
+    let _ = SPOOL.set(path);
+}
+
+/// Append `event` to the spool when enabled. Metrics never fail a run, so
+/// write errors are dropped.
+pub fn record(event: &MetricEvent) {
+    if let Some(path) = SPOOL.get() {
+        let _ = append_event(path, event);
+    }
+}
+
+/// Append `event` as one JSON line to the spool at `path`, first moving a
+/// full spool aside.
+///
+/// Writers hold an exclusive lock on `<spool>.lock` while they check the size,
+/// rotate and write, so two writers never both rotate (the second would
+/// replace `<spool>.1` with a nearly empty spool) and nothing is written to a
+/// spool after it moved aside.
+pub fn append_event(path: &Path, event: &MetricEvent) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+    let _lock = lock_spool(path)?;
+    if std::fs::metadata(path).is_ok_and(|meta| meta.len() >= MAX_SPOOL_BYTES) {
+        std::fs::rename(path, rotated_path(path))?;
+    }
+    // One O_APPEND write per event keeps lines whole even without the lock.
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// `<spool>.1`, where a full spool is moved.
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+/// Exclusive `flock(2)` on `<spool>.lock`, released when the file is dropped.
+#[cfg(unix)]
+fn lock_spool(path: &Path) -> std::io::Result<File> {
+    use std::os::fd::AsRawFd;
+
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?;
+    // SAFETY: `file` owns a valid descriptor for the duration of the call.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn lock_spool(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Records [`MetricEvent::RunStarted`] when created and
+/// [`MetricEvent::RunCompleted`] when dropped; a run not marked with
+/// [`RunTimer::finish`] counts as failed.
+#[derive(Debug)]
+pub struct RunTimer {
+    tool: String,
+    started: Instant,
+    success: bool,
+}
+
+impl RunTimer {
+    pub fn start(tool: &str) -> Self {
+        record(&MetricEvent::RunStarted {
+            tool: tool.to_string(),
+        });
+        Self {
+            tool: tool.to_string(),
+            started: Instant::now(),
+            success: false,
+        }
+    }
+
+    pub fn finish(mut self, success: bool) {
+        self.success = success;
+    }
+}
+
+impl Drop for RunTimer {
+    fn drop(&mut self) {
+        record(&MetricEvent::RunCompleted {
+            tool: std::mem::take(&mut self.tool),
+            success: self.success,
+            duration_secs: self.started.elapsed().as_secs_f64(),
+        });
+    }
+}
+
+/// Tails a spool, returning each event once.
+#[derive(Debug)]
+pub struct SpoolReader {
+    path: PathBuf,
+    offset: u64,
+    /// Identity of the file `offset` belongs to, to notice rotation.
+    identity: Option<FileIdentity>,
+}
+
+impl SpoolReader {
+    /// Reader of events appended after now; counters start at zero with the
+    /// process serving them, as Prometheus expects.
+    pub fn from_end(path: PathBuf) -> Self {
+        let meta = std::fs::metadata(&path).ok();
+        Self {
+            offset: meta.as_ref().map_or(0, |meta| meta.len()),
+            identity: meta.as_ref().and_then(file_identity),
+            path,
+        }
+    }
+
+    /// Events appended since the last call. When the spool was rotated, the
+    /// rest of the previous spool is read from `<spool>.1` first and the new
+    /// spool from its start.
+    pub fn read_new(&mut self) -> Vec<MetricEvent> {
+        let Ok(file) = File::open(&self.path) else {
+            return Vec::new();
+        };
+        let Ok(meta) = file.metadata() else {
+            return Vec::new();
+        };
+        let identity = file_identity(&meta);
+        let mut events = Vec::new();
+        if self.identity.is_some() && identity != self.identity {
+            if let Ok(previous) = File::open(rotated_path(&self.path))
+                && previous
+                    .metadata()
+                    .is_ok_and(|meta| file_identity(&meta) == self.identity)
+            {
+                self.read_lines(previous, &mut events);
+            }
+            self.offset = 0;
+        } else if meta.len() < self.offset {
+            // Rotated where file identities are unavailable.
+            self.offset = 0;
+        }
+        self.identity = identity;
+        self.read_lines(file, &mut events);
+        events
+    }
+
+    /// Push the complete lines of `file` after `offset` onto `events`.
+    fn read_lines(&mut self, mut file: File, events: &mut Vec<MetricEvent>) {
+        if file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return;
+        }
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                // A line without its newline is still being written.
+                Ok(read) if read > 0 && line.ends_with('\n') => {
+                    self.offset += read as u64;
+                    if let Ok(event) = serde_json::from_str(&line) {
+                        events.push(event);
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+/// Device and inode of a file; they change when a spool is rotated even if
+/// the new spool is already longer than the old read offset.
+type FileIdentity = (u64, u64);
+
+#[cfg(unix)]
+fn file_identity(meta: &std::fs::Metadata) -> Option<FileIdentity> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_meta: &std::fs::Metadata) -> Option<FileIdentity> {
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+#[derive(Debug)]
+enum Series {
+    Value(f64),
+    Histogram {
+        buckets: &'static [f64],
+        counts: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+#[derive(Debug)]
+struct Family {
+    help: &'static str,
+    kind: Kind,
+    series: BTreeMap<Vec<(&'static str, String)>, Series>,
+}
+
+/// Counters, gauges and histograms keyed by metric name and label values.
+#[derive(Debug, Default)]
+pub struct Registry {
+    families: Mutex<BTreeMap<&'static str, Family>>,
+}
+
+impl Registry {
+    /// Add `by` to a counter.
+    pub fn inc(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&'static str, &str)],
+        by: f64,
+    ) {
+        self.update(name, help, Kind::Counter, labels, |series| {
+            if let Series::Value(value) = series {
+                *value += by;
+            }
+        });
+    }
+
+    /// Set a gauge.
+    pub fn set(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&'static str, &str)],
+        to: f64,
+    ) {
+        self.update(name, help, Kind::Gauge, labels, |series| {
+            if let Series::Value(value) = series {
+                *value = to;
+            }
+        });
+    }
+
+    /// Record one observation in a histogram with upper bounds `buckets`.
+    pub fn observe(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&'static str, &str)],
+        buckets: &'static [f64],
+        value: f64,
+    ) {
+        let mut families = self.lock();
+        let series = families
+            .entry(name)
+            .or_insert_with(|| Family {
+                help,
+                kind: Kind::Histogram,
+                series: BTreeMap::new(),
+            })
+            .series
+            .entry(owned_labels(labels))
+            .or_insert_with(|| Series::Histogram {
+                buckets,
+                counts: vec![0; buckets.len()],
+                sum: 0.0,
+                count: 0,
+            });
+        if let Series::Histogram {
+            buckets,
+            counts,
+            sum,
+            count,
+        } = series
+        {
+            if let Some(bucket) = buckets.iter().position(|bound| value <= *bound) {
+                counts[bucket] += 1;
+            }
+            *sum += value;
+            *count += 1;
+        }
+    }
+
+    /// Fold a spooled event into the run metrics.
+    pub fn apply(&self, event: &MetricEvent) {
+        match event {
+            MetricEvent::RunStarted { tool } => self.inc(
+                "csa_runs_started_total",
+                "csa runs started, by tool.",
+                &[("tool", tool)],
+                1.0,
+            ),
+            MetricEvent::RunCompleted {
+                tool,
+                success,
+                duration_secs,
+            } => {
+                let outcome = if *success { "success" } else { "failure" };
+                self.inc(
+                    "csa_runs_completed_total",
+                    "csa runs completed, by tool and outcome.",
+                    &[("tool", tool), ("outcome", outcome)],
+                    1.0,
+                );
+                self.observe(
+                    "csa_run_duration_seconds",
+                    "Wall time of completed csa runs.",
+                    &[("tool", tool)],
+                    DURATION_BUCKETS,
+                    *duration_secs,
+                );
+            }
+            MetricEvent::Failover { from, to } => self.inc(
+                "csa_failovers_total",
+                "Runs moved to another tool after a rate limit.",
+                &[("from", from), ("to", to)],
+                1.0,
+            ),
+            MetricEvent::SlotWait {
+                tool,
+                wait_secs,
+                timed_out,
+            } => {
+                self.observe(
+                    "csa_slot_wait_seconds",
+                    "Time spent waiting for a free tool slot.",
+                    &[("tool", tool)],
+                    DURATION_BUCKETS,
+                    *wait_secs,
+                );
+                if *timed_out {
+                    self.inc(
+                        "csa_slot_wait_timeouts_total",
+                        "Slot waits that gave up without a slot.",
+                        &[("tool", tool)],
+                        1.0,
+                    );
+                }
+            }
+        }
+    }
+
+    /// All metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let families = self.lock();
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            let kind = match family.kind {
+                Kind::Counter => "counter",
+                Kind::Gauge => "gauge",
+                Kind::Histogram => "histogram",
+            };
+            let _ = writeln!(out, "# HELP {name} {}", family.help);
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Value(value) => {
+                        let _ = writeln!(out, "{name}{} {value}", format_labels(labels, None));
+                    }
+                    Series::Histogram {
+                        buckets,
+                        counts,
+                        sum,
+                        count,
+                    } => {
+                        let mut cumulative = 0;
+                        for (bound, bucket_count) in buckets.iter().zip(counts) {
+                            cumulative += bucket_count;
+                            let le = bound.to_string();
+                            let labels = format_labels(labels, Some(&le));
+                            let _ = writeln!(out, "{name}_bucket{labels} {cumulative}");
+                        }
+                        let inf = format_labels(labels, Some("+Inf"));
+                        let _ = writeln!(out, "{name}_bucket{inf} {count}");
+                        let labels = format_labels(labels, None);
+                        let _ = writeln!(out, "{name}_sum{labels} {sum}");
+                        let _ = writeln!(out, "{name}_count{labels} {count}");
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn update(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        kind: Kind,
+        labels: &[(&'static str, &str)],
+        apply: impl FnOnce(&mut Series),
+    ) {
+        let mut families = self.lock();
+        let series = families
+            .entry(name)
+            .or_insert_with(|| Family {
+                help,
+                kind,
+                series: BTreeMap::new(),
+            })
+            .series
+            .entry(owned_labels(labels))
+            .or_insert(Series::Value(0.0));
+        apply(series);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<&'static str, Family>> {
+        self.families
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn owned_labels(labels: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+    labels
+        .iter()
+        .map(|(key, value)| (*key, (*value).to_string()))
+        .collect()
+}
+
+fn format_labels(labels: &[(&'static str, String)], le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+        .collect();
+    pairs.extend(le.map(|le| format!("le=\"{le}\"")));
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_gauges_and_cumulative_histograms() {
+        let registry = Registry::default();
+        registry.apply(&MetricEvent::RunStarted {
+            tool: "codex".to_string(),
+        });
+        for (success, secs) in [(true, 12.0), (true, 40.0), (false, 7200.0)] {
+            registry.apply(&MetricEvent::RunCompleted {
+                tool: "codex".to_string(),
+                success,
+                duration_secs: secs,
+            });
+        }
+        registry.set(
+            "csa_memory_store_bytes",
+            "Size of the memory store.",
+            &[],
+            2048.0,
+        );
+        registry.inc(
+            "csa_mcp_proxy_calls_total",
+            "Calls.",
+            &[("server", "a\"b\\c")],
+            1.0,
+        );
+
+        let text = registry.render();
+        assert!(
+            text.contains(
+                "# TYPE csa_runs_started_total counter\ncsa_runs_started_total{tool=\"codex\"} 1\n"
+            ),
+            "{text}"
+        );
+        assert!(text.contains("csa_runs_completed_total{tool=\"codex\",outcome=\"failure\"} 1\n"));
+        assert!(text.contains("csa_runs_completed_total{tool=\"codex\",outcome=\"success\"} 2\n"));
+        assert!(text.contains("# TYPE csa_run_duration_seconds histogram\n"));
+        assert!(text.contains("csa_run_duration_seconds_bucket{tool=\"codex\",le=\"5\"} 0\n"));
+        assert!(text.contains("csa_run_duration_seconds_bucket{tool=\"codex\",le=\"15\"} 1\n"));
+        assert!(text.contains("csa_run_duration_seconds_bucket{tool=\"codex\",le=\"3600\"} 2\n"));
+        assert!(text.contains("csa_run_duration_seconds_bucket{tool=\"codex\",le=\"+Inf\"} 3\n"));
+        assert!(text.contains("csa_run_duration_seconds_sum{tool=\"codex\"} 7252\n"));
+        assert!(text.contains("csa_run_duration_seconds_count{tool=\"codex\"} 3\n"));
+        assert!(
+            text.contains("# TYPE csa_memory_store_bytes gauge\ncsa_memory_store_bytes 2048\n")
+        );
+        assert!(text.contains("csa_mcp_proxy_calls_total{server=\"a\\\"b\\\\c\"} 1\n"));
+    }
+
+    #[test]
+    fn spool_reader_returns_new_complete_lines_once() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("metrics").join("events.jsonl");
+        let failover = MetricEvent::Failover {
+            from: "codex".to_string(),
+            to: "claude-code".to_string(),
+        };
+        append_event(&path, &failover).expect("append");
+
+        let mut reader = SpoolReader::from_end(path.clone());
+        assert!(reader.read_new().is_empty());
+
+        let wait = MetricEvent::SlotWait {
+            tool: "codex".to_string(),
+            wait_secs: 3.5,
+            timed_out: false,
+        };
+        append_event(&path, &wait).expect("append");
+        let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+        file.write_all(b"{\"event\":\"run_sta").expect("partial");
+        assert_eq!(reader.read_new(), [wait]);
+        file.write_all(b"rted\",\"tool\":\"codex\"}\nnot json\n")
+            .expect("rest");
+        assert_eq!(
+            reader.read_new(),
+            [MetricEvent::RunStarted {
+                tool: "codex".to_string()
+            }]
+        );
+
+        // A rotated spool is read again from its start.
+        std::fs::remove_file(&path).expect("remove");
+        append_event(&path, &failover).expect("append");
+        assert_eq!(reader.read_new(), [failover]);
+    }
+
+    #[test]
+    fn spool_reader_finishes_the_rotated_spool_before_the_new_one() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("events.jsonl");
+        let started = |tool: &str| MetricEvent::RunStarted {
+            tool: tool.to_string(),
+        };
+        append_event(&path, &started("before")).expect("append");
+        let mut reader = SpoolReader::from_end(path.clone());
+
+        append_event(&path, &started("old-tail")).expect("append");
+        std::fs::rename(&path, rotated_path(&path)).expect("rotate");
+        // The new spool outgrows the old read offset before the next scrape.
+        for tool in ["new-1", "new-2", "new-3"] {
+            append_event(&path, &started(tool)).expect("append");
+        }
+
+        assert_eq!(
+            reader.read_new(),
+            [
+                started("old-tail"),
+                started("new-1"),
+                started("new-2"),
+                started("new-3")
+            ]
+        );
+        assert!(reader.read_new().is_empty());
+    }
+}
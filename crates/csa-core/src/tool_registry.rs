@@ -0,0 +1,246 @@
+//! Data-driven tool registry.
+//!
+//! [`BUILTIN_TOOLS`] describes every tool CSA knows natively; [`ToolName`] is a
+//! thin handle onto those entries. A [`ToolRegistry`] starts from the built-ins
+//! and accepts further tools registered from global config, so tool facts
+//! (binary, family, install hint) live in one table instead of per-call-site
+//! matches.
+
+use crate::types::{ModelFamily, PromptTransport, ROUTING_CANDIDATE_TOOLS, ToolName};
+
+pub const GEMINI_CLI_INSTALL_HINT: &str = "Install: npm install -g @google/gemini-cli";
+pub const OPENCODE_INSTALL_HINT: &str = "Install: go install github.com/sst/opencode@latest";
+pub const CLAUDE_CODE_ACP_INSTALL_HINT: &str =
+    "Install ACP adapter: npm install -g @zed-industries/claude-code-acp";
+pub const OPENAI_COMPAT_INSTALL_HINT: &str =
+    "Configure [tools.openai-compat] with base_url and api_key in config.toml";
+pub const HERMES_INSTALL_HINT: &str = "Install Hermes and ensure `hermes` is on PATH";
+pub const ANTIGRAVITY_CLI_INSTALL_HINT: &str =
+    "Install: go install google.dev/antigravity@latest (requires Go 1.22+)";
+
+const ARGV_ONLY: &[PromptTransport] = &[PromptTransport::Argv];
+const ARGV_AND_STDIN: &[PromptTransport] = &[PromptTransport::Argv, PromptTransport::Stdin];
+
+/// Static description of a built-in tool.
+#[derive(Debug)]
+pub struct BuiltinTool {
+    pub name: ToolName,
+    /// Canonical CLI-facing id.
+    pub id: &'static str,
+    /// Short names accepted wherever the id is.
+    pub aliases: &'static [&'static str],
+    /// Executable probed on PATH. Codex and Claude Code list their CLI
+    /// transport binary; `None` for HTTP-only tools.
+    pub binary: Option<&'static str>,
+    pub family: ModelFamily,
+    pub prompt_transports: &'static [PromptTransport],
+    pub install_hint: Option<&'static str>,
+    /// Kept so old names produce the removal error instead of "unknown tool".
+    pub removed: bool,
+}
+
+pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
+    BuiltinTool {
+        name: ToolName::GeminiCli,
+        id: "gemini-cli",
+        aliases: &["gemini"],
+        binary: Some("gemini"),
+        family: ModelFamily::Gemini,
+        prompt_transports: ARGV_AND_STDIN,
+        install_hint: Some(GEMINI_CLI_INSTALL_HINT),
+        removed: true,
+    },
+    BuiltinTool {
+        name: ToolName::Opencode,
+        id: "opencode",
+        aliases: &[],
+        binary: Some("opencode"),
+        family: ModelFamily::Other,
+        prompt_transports: ARGV_ONLY,
+        install_hint: Some(OPENCODE_INSTALL_HINT),
+        removed: false,
+    },
+    BuiltinTool {
+        name: ToolName::Codex,
+        id: "codex",
+        aliases: &[],
+        binary: Some("codex"),
+        family: ModelFamily::OpenAI,
+        prompt_transports: ARGV_AND_STDIN,
+        install_hint: None,
+        removed: false,
+    },
+    BuiltinTool {
+        name: ToolName::ClaudeCode,
+        id: "claude-code",
+        aliases: &["claude"],
+        binary: Some("claude"),
+        family: ModelFamily::Claude,
+        prompt_transports: ARGV_AND_STDIN,
+        install_hint: Some(CLAUDE_CODE_ACP_INSTALL_HINT),
+        removed: false,
+    },
+    BuiltinTool {
+        name: ToolName::OpenaiCompat,
+        id: "openai-compat",
+        aliases: &[],
+        binary: None,
+        family: ModelFamily::Other,
+        // HTTP-only: prompt transport is irrelevant, but callers that check
+        // capabilities expect stdin to be accepted.
+        prompt_transports: ARGV_AND_STDIN,
+        install_hint: Some(OPENAI_COMPAT_INSTALL_HINT),
+        removed: false,
+    },
+    BuiltinTool {
+        name: ToolName::Hermes,
+        id: "hermes",
+        aliases: &[],
+        binary: Some("hermes"),
+        family: ModelFamily::Other,
+        prompt_transports: ARGV_AND_STDIN,
+        install_hint: Some(HERMES_INSTALL_HINT),
+        removed: false,
+    },
+    BuiltinTool {
+        name: ToolName::AntigravityCli,
+        id: "antigravity-cli",
+        aliases: &["antigravity"],
+        binary: Some("antigravity"),
+        family: ModelFamily::Gemini,
+        prompt_transports: ARGV_AND_STDIN,
+        install_hint: Some(ANTIGRAVITY_CLI_INSTALL_HINT),
+        removed: false,
+    },
+];
+
+/// Look up a built-in tool (including removed ones) by id or alias.
+pub fn builtin_tool(id_or_alias: &str) -> Option<&'static BuiltinTool> {
+    BUILTIN_TOOLS
+        .iter()
+        .find(|tool| tool.id == id_or_alias || tool.aliases.contains(&id_or_alias))
+}
+
+/// Look up a built-in tool (including removed ones) by canonical id only.
+pub fn builtin_tool_by_id(id: &str) -> Option<&'static BuiltinTool> {
+    BUILTIN_TOOLS.iter().find(|tool| tool.id == id)
+}
+
+/// Ids of the built-in tools that are still supported, in table order.
+pub fn supported_tool_ids() -> impl Iterator<Item = &'static str> {
+    BUILTIN_TOOLS
+        .iter()
+        .filter(|tool| !tool.removed)
+        .map(|tool| tool.id)
+}
+
+/// What a tool can do, as far as routing and prompt delivery are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ToolCapabilities {
+    /// Accepts the prompt on stdin (every tool accepts argv).
+    pub stdin_prompt: bool,
+    /// Eligible for automatic routing and general fallback.
+    pub routing_candidate: bool,
+}
+
+/// Owned registry entry, for built-in and config-registered tools alike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolDescriptor {
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub binary: Option<String>,
+    pub family: ModelFamily,
+    pub capabilities: ToolCapabilities,
+    pub install_hint: Option<String>,
+    /// The enum handle, for built-in tools only.
+    pub builtin: Option<ToolName>,
+}
+
+impl From<&BuiltinTool> for ToolDescriptor {
+    fn from(tool: &BuiltinTool) -> Self {
+        Self {
+            id: tool.id.to_string(),
+            aliases: tool.aliases.iter().map(|alias| alias.to_string()).collect(),
+            binary: tool.binary.map(str::to_string),
+            family: tool.family,
+            capabilities: ToolCapabilities {
+                stdin_prompt: tool.prompt_transports.contains(&PromptTransport::Stdin),
+                routing_candidate: ROUTING_CANDIDATE_TOOLS.contains(&tool.name),
+            },
+            install_hint: tool.install_hint.map(str::to_string),
+            builtin: Some(tool.name),
+        }
+    }
+}
+
+/// Registry of supported tools: the built-ins plus any registered extras.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolRegistry {
+    tools: Vec<ToolDescriptor>,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl ToolRegistry {
+    /// Registry seeded with the supported built-in tools.
+    pub fn builtin() -> Self {
+        Self {
+            tools: BUILTIN_TOOLS
+                .iter()
+                .filter(|tool| !tool.removed)
+                .map(ToolDescriptor::from)
+                .collect(),
+        }
+    }
+
+    /// Add a tool. Ids and aliases must not collide with any registered or
+    /// built-in (including removed) name.
+    pub fn register(&mut self, descriptor: ToolDescriptor) -> Result<(), String> {
+        let names = std::iter::once(&descriptor.id).chain(&descriptor.aliases);
+        for name in names {
+            if let Some(builtin) = builtin_tool(name) {
+                return Err(format!(
+                    "tool '{name}' is reserved by the built-in tool '{}'",
+                    builtin.id
+                ));
+            }
+            if let Some(existing) = self.get(name) {
+                return Err(format!(
+                    "tool '{name}' is already registered as '{}'",
+                    existing.id
+                ));
+            }
+        }
+        self.tools.push(descriptor);
+        Ok(())
+    }
+
+    /// Look up a tool by id or alias.
+    pub fn get(&self, id_or_alias: &str) -> Option<&ToolDescriptor> {
+        self.tools.iter().find(|tool| {
+            tool.id == id_or_alias || tool.aliases.iter().any(|alias| alias == id_or_alias)
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ToolDescriptor> {
+        self.tools.iter()
+    }
+
+    /// Tools registered on top of the built-ins.
+    pub fn custom(&self) -> impl Iterator<Item = &ToolDescriptor> {
+        self.tools.iter().filter(|tool| tool.builtin.is_none())
+    }
+
+    /// Provider family of a registered tool (quota-pool grouping).
+    pub fn family(&self, id_or_alias: &str) -> Option<ModelFamily> {
+        self.get(id_or_alias).map(|tool| tool.family)
+    }
+}
+
+#[cfg(test)]
+#[path = "tool_registry_tests.rs"]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use crate::types::PRIMARY_TOOL_NAMES;
+
+fn custom(id: &str, aliases: &[&str]) -> ToolDescriptor {
+    ToolDescriptor {
+        id: id.to_string(),
+        aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        binary: Some(id.to_string()),
+        family: ModelFamily::Other,
+        capabilities: ToolCapabilities::default(),
+        install_hint: None,
+        builtin: None,
+    }
+}
+
+#[test]
+fn every_tool_name_has_exactly_one_builtin_entry() {
+    for tool in BUILTIN_TOOLS {
+        assert_eq!(
+            BUILTIN_TOOLS.iter().filter(|t| t.name == tool.name).count(),
+            1,
+            "{}",
+            tool.id
+        );
+        assert_eq!(tool.name.builtin().id, tool.id);
+        assert_eq!(builtin_tool(tool.id).unwrap().name, tool.name);
+        for alias in tool.aliases {
+            assert_eq!(builtin_tool(alias).unwrap().name, tool.name);
+        }
+    }
+    for name in PRIMARY_TOOL_NAMES {
+        assert!(ToolName::from_id(name).is_some(), "{name}");
+    }
+    for tool in ROUTING_CANDIDATE_TOOLS {
+        assert!(!tool.builtin().removed, "{tool}");
+    }
+}
+
+#[test]
+fn builtin_registry_skips_removed_tools_and_resolves_aliases() {
+    let registry = ToolRegistry::builtin();
+
+    assert!(registry.get("gemini-cli").is_none());
+    assert_eq!(ToolName::from_id("gemini-cli"), None);
+    assert_eq!(registry.get("claude").unwrap().id, "claude-code");
+    assert_eq!(ToolName::from_id("claude"), None);
+    let antigravity = registry.get("antigravity-cli").unwrap();
+    assert_eq!(antigravity.builtin, Some(ToolName::AntigravityCli));
+    assert!(!antigravity.capabilities.routing_candidate);
+    assert!(!registry.get("opencode").unwrap().capabilities.stdin_prompt);
+    assert_eq!(registry.custom().count(), 0);
+}
+
+#[test]
+fn register_adds_custom_tools_and_rejects_collisions() {
+    let mut registry = ToolRegistry::builtin();
+    registry.register(custom("aider", &["ad"])).unwrap();
+
+    assert_eq!(registry.get("ad").unwrap().id, "aider");
+    assert_eq!(registry.family("aider"), Some(ModelFamily::Other));
+    assert_eq!(
+        registry.custom().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+        ["aider"]
+    );
+
+    let err = registry.register(custom("codex", &[])).unwrap_err();
+    assert!(
+        err.contains("reserved by the built-in tool 'codex'"),
+        "{err}"
+    );
+    let err = registry.register(custom("mine", &["gemini"])).unwrap_err();
+    assert!(err.contains("built-in tool 'gemini-cli'"), "{err}");
+    let err = registry.register(custom("ad", &[])).unwrap_err();
+    assert!(err.contains("already registered as 'aider'"), "{err}");
+}
@@ -78,5 +78,20 @@ pub enum SessionEvent {
         output: String,
     },
     PlanUpdate(String),
+    /// An agent permission request and the policy decision taken on it.
+    PermissionRequest {
+        id: String,
+        title: String,
+        kind: String,
+        paths: Vec<String>,
+        decision: String,
+    },
+    /// A file read or write the agent made through the client, with its
+    /// outcome (`ok`, `denied` or `error`).
+    FileAccess {
+        path: String,
+        operation: String,
+        status: String,
+    },
     Other(String),
 }
@@ -4,6 +4,10 @@ use chrono::{DateTime, Utc};
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
+use crate::tool_registry::{
+    BUILTIN_TOOLS, BuiltinTool, builtin_tool, builtin_tool_by_id, supported_tool_ids,
+};
+
 /// AI tool selection
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
 pub enum ToolName {
@@ -34,7 +38,7 @@ pub const ROUTING_CANDIDATE_TOOLS: &[ToolName] = &[
 ];
 
 pub fn is_removed_tool_name(name: &str) -> bool {
-    matches!(name, "gemini-cli" | "gemini")
+    builtin_tool(name).is_some_and(|tool| tool.removed)
 }
 
 pub fn removed_tool_error(name: &str) -> String {
@@ -46,29 +50,29 @@ pub fn removed_tool_error(name: &str) -> String {
 }
 
 impl ToolName {
+    /// Registry entry describing this tool.
+    pub fn builtin(&self) -> &'static BuiltinTool {
+        BUILTIN_TOOLS
+            .iter()
+            .find(|tool| tool.name == *self)
+            .expect("every ToolName has a BUILTIN_TOOLS entry")
+    }
+
+    /// Parse a canonical id of a supported tool (aliases not accepted).
+    pub fn from_id(id: &str) -> Option<Self> {
+        builtin_tool_by_id(id)
+            .filter(|tool| !tool.removed)
+            .map(|tool| tool.name)
+    }
+
     /// Returns the CLI-facing name for this tool
     pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::GeminiCli => "gemini-cli",
-            Self::Opencode => "opencode",
-            Self::Codex => "codex",
-            Self::ClaudeCode => "claude-code",
-            Self::OpenaiCompat => "openai-compat",
-            Self::Hermes => "hermes",
-            Self::AntigravityCli => "antigravity-cli",
-        }
+        self.builtin().id
     }
 
     /// Returns the model family for this tool
     pub fn model_family(&self) -> ModelFamily {
-        match self {
-            Self::ClaudeCode => ModelFamily::Claude,
-            Self::GeminiCli | Self::AntigravityCli => ModelFamily::Gemini,
-            Self::Codex => ModelFamily::OpenAI,
-            Self::Opencode => ModelFamily::Other,
-            Self::OpenaiCompat => ModelFamily::Other,
-            Self::Hermes => ModelFamily::Other,
-        }
+        self.builtin().family
     }
 
     /// Returns prompt transport channels supported by this tool.
@@ -84,22 +88,9 @@ pub enum PromptTransport {
     Stdin,
 }
 
-const PROMPT_TRANSPORT_ARGV_ONLY: &[PromptTransport] = &[PromptTransport::Argv];
-const PROMPT_TRANSPORT_ARGV_AND_STDIN: &[PromptTransport] =
-    &[PromptTransport::Argv, PromptTransport::Stdin];
-
 /// Prompt transport capabilities for each tool.
 pub fn prompt_transport_capabilities(tool: &ToolName) -> &'static [PromptTransport] {
-    match tool {
-        ToolName::Codex => PROMPT_TRANSPORT_ARGV_AND_STDIN,
-        ToolName::GeminiCli | ToolName::AntigravityCli => PROMPT_TRANSPORT_ARGV_AND_STDIN,
-        ToolName::ClaudeCode => PROMPT_TRANSPORT_ARGV_AND_STDIN,
-        ToolName::Hermes => PROMPT_TRANSPORT_ARGV_AND_STDIN,
-        ToolName::Opencode => PROMPT_TRANSPORT_ARGV_ONLY,
-        // OpenAI-compat is HTTP-only; prompt transport is irrelevant (no CLI process).
-        // Return Stdin to satisfy callers that check capabilities.
-        ToolName::OpenaiCompat => PROMPT_TRANSPORT_ARGV_AND_STDIN,
-    }
+    tool.builtin().prompt_transports
 }
 
 impl std::fmt::Display for ToolName {
@@ -109,7 +100,8 @@ impl std::fmt::Display for ToolName {
 }
 
 /// Model family for heterogeneous diversity enforcement.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ModelFamily {
     Claude,
     Gemini,
@@ -137,13 +129,7 @@ impl std::fmt::Display for ModelFamily {
 /// Legacy Gemini-family session records and `antigravity-cli` both consume the
 /// Google/Gemini quota pool.
 pub fn provider_for_tool_name(tool: &str) -> Option<ModelFamily> {
-    match tool {
-        "gemini-cli" | "antigravity-cli" | "antigravity" | "gemini" => Some(ModelFamily::Gemini),
-        "claude-code" | "claude" => Some(ModelFamily::Claude),
-        "codex" => Some(ModelFamily::OpenAI),
-        "opencode" | "openai-compat" | "hermes" => Some(ModelFamily::Other),
-        _ => None,
-    }
+    builtin_tool(tool).map(|tool| tool.family)
 }
 
 /// One step in a quota/rate-limit failover chain: which tool/spec was tried and why it was skipped.
@@ -184,19 +170,13 @@ impl std::str::FromStr for ToolArg {
         match s {
             "auto" => Ok(Self::Auto),
             "any-available" => Ok(Self::AnyAvailable),
-            "gemini-cli" | "gemini" => Err(removed_tool_error(s)),
-            // Canonical tool names
-            "opencode" => Ok(Self::Specific(ToolName::Opencode)),
-            "codex" => Ok(Self::Specific(ToolName::Codex)),
-            "claude-code" => Ok(Self::Specific(ToolName::ClaudeCode)),
-            "openai-compat" => Ok(Self::Specific(ToolName::OpenaiCompat)),
-            "hermes" => Ok(Self::Specific(ToolName::Hermes)),
-            "antigravity-cli" => Ok(Self::Specific(ToolName::AntigravityCli)),
-            // Built-in aliases for common short names
-            "claude" => Ok(Self::Specific(ToolName::ClaudeCode)),
-            "antigravity" => Ok(Self::Specific(ToolName::AntigravityCli)),
-            // Unknown string — store for config-based resolution
-            other => Ok(Self::Alias(other.to_string())),
+            // Canonical tool names and built-in short aliases
+            _ => match builtin_tool(s) {
+                Some(tool) if tool.removed => Err(removed_tool_error(s)),
+                Some(tool) => Ok(Self::Specific(tool.name)),
+                // Unknown string — store for config-based resolution
+                None => Ok(Self::Alias(s.to_string())),
+            },
         }
     }
 }
@@ -204,7 +184,7 @@ impl std::str::FromStr for ToolArg {
 impl ToolArg {
     /// Resolve a config-based alias to a concrete `ToolArg`.
     ///
-    /// Built-in aliases (`gemini`, `claude`) are already resolved in `from_str`.
+    /// Built-in aliases (`claude`, `antigravity`) are already resolved in `from_str`.
     /// This method handles user-defined aliases from `[tool_aliases]` in config.
     /// Non-alias variants pass through unchanged.
     pub fn resolve_alias(self, tool_aliases: &HashMap<String, String>) -> Result<Self, String> {
@@ -217,15 +197,16 @@ impl ToolArg {
                     match resolved {
                         Self::Alias(ref inner) => Err(format!(
                             "tool alias '{alias}' maps to '{inner}' which is not a valid tool \
-                             name. Valid targets: opencode, codex, claude-code, openai-compat, hermes, antigravity-cli"
+                             name. Valid targets: {}",
+                            supported_tool_list()
                         )),
                         other => Ok(other),
                     }
                 } else {
                     Err(format!(
-                        "unknown tool '{alias}'. Valid values: auto, any-available, \
-                         opencode, codex, claude-code, openai-compat, hermes, antigravity-cli. \
-                         Or define it in [tool_aliases] in config."
+                        "unknown tool '{alias}'. Valid values: auto, any-available, {}. \
+                         Or define it in [tool_aliases] in config.",
+                        supported_tool_list()
                     ))
                 }
             }
@@ -264,6 +245,11 @@ impl std::fmt::Display for ToolArg {
     }
 }
 
+/// Comma-separated ids of the supported built-in tools, for error messages.
+pub fn supported_tool_list() -> String {
+    supported_tool_ids().collect::<Vec<_>>().join(", ")
+}
+
 /// Resolved tool selection strategy used by the execution pipeline.
 #[derive(Clone, Debug)]
 pub enum ToolSelectionStrategy {
@@ -343,6 +329,34 @@ impl std::str::FromStr for ReviewDecision {
     }
 }
 
+/// Lowest finding severity that fails a review (`csa review --fail-on`, `[review] fail_on`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewFailOn {
+    /// Fail only on critical findings.
+    Critical,
+    /// Fail on critical and high findings.
+    High,
+    /// Fail on any finding.
+    Any,
+}
+
+impl ReviewFailOn {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Any => "any",
+        }
+    }
+}
+
+impl std::fmt::Display for ReviewFailOn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[cfg(test)]
 #[path = "types_tests.rs"]
 mod tests;
@@ -573,6 +573,9 @@ impl Executor {
     fn inject_csa_owned_env(&self, cmd: &mut Command, session: &MetaSessionState) {
         cmd.env("CSA_SESSION_ID", &session.meta_session_id);
         cmd.env("CSA_DEPTH", (session.genealogy.depth + 1).to_string());
+        if let Some(token) = csa_session::child_lineage_token(session) {
+            cmd.env(csa_core::env::CSA_LINEAGE_ENV_KEY, token);
+        }
         cmd.env("CSA_PROJECT_ROOT", &session.project_path);
         cmd.env(csa_core::env::CSA_INTERNAL_INVOCATION_ENV_KEY, "1");
         Self::inject_session_path_env(cmd, session);
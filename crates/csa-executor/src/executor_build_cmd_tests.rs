@@ -26,7 +26,6 @@ fn make_test_session() -> MetaSessionState {
         turn_count: 0,
         token_budget: None,
         sandbox_info: None,
-
         termination_reason: None,
         is_seed_candidate: false,
         git_head_at_creation: None,
@@ -37,6 +36,7 @@ fn make_test_session() -> MetaSessionState {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
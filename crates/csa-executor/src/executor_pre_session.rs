@@ -90,6 +90,7 @@ mod tests {
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
+            ..Default::default()
         }
     }
 
@@ -37,6 +37,7 @@ fn make_test_session() -> MetaSessionState {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
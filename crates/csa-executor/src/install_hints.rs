@@ -1,27 +1,18 @@
-//! Shared install hints for executor-facing tool availability messages.
+//! Install hints for executor-facing tool availability messages.
+//!
+//! Built-in hints live in the csa-core tool registry; this module adds the
+//! transport-specific ones.
+
+pub use csa_core::tool_registry::{
+    ANTIGRAVITY_CLI_INSTALL_HINT, CLAUDE_CODE_ACP_INSTALL_HINT, GEMINI_CLI_INSTALL_HINT,
+    HERMES_INSTALL_HINT, OPENAI_COMPAT_INSTALL_HINT, OPENCODE_INSTALL_HINT,
+};
 
-pub const GEMINI_CLI_INSTALL_HINT: &str = "Install: npm install -g @google/gemini-cli";
-pub const OPENCODE_INSTALL_HINT: &str = "Install: go install github.com/sst/opencode@latest";
-pub const CLAUDE_CODE_ACP_INSTALL_HINT: &str =
-    "Install ACP adapter: npm install -g @zed-industries/claude-code-acp";
 pub const CLAUDE_CODE_CLI_INSTALL_HINT: &str =
     "Install Claude Code CLI and ensure `claude` is on PATH";
-pub const OPENAI_COMPAT_INSTALL_HINT: &str =
-    "Configure [tools.openai-compat] with base_url and api_key in config.toml";
-pub const HERMES_INSTALL_HINT: &str = "Install Hermes and ensure `hermes` is on PATH";
-pub const ANTIGRAVITY_CLI_INSTALL_HINT: &str =
-    "Install: go install google.dev/antigravity@latest (requires Go 1.22+)";
 
 pub fn install_hint_for_known_tool(tool_name: &str) -> Option<&'static str> {
-    match tool_name {
-        "gemini-cli" => Some(GEMINI_CLI_INSTALL_HINT),
-        "opencode" => Some(OPENCODE_INSTALL_HINT),
-        "claude-code" => Some(CLAUDE_CODE_ACP_INSTALL_HINT),
-        "openai-compat" => Some(OPENAI_COMPAT_INSTALL_HINT),
-        "hermes" => Some(HERMES_INSTALL_HINT),
-        "antigravity-cli" => Some(ANTIGRAVITY_CLI_INSTALL_HINT),
-        _ => None,
-    }
+    csa_core::tool_registry::builtin_tool_by_id(tool_name).and_then(|tool| tool.install_hint)
 }
 
 #[cfg(test)]
@@ -13,6 +13,7 @@ pub mod install_hints;
 mod lefthook_guard;
 pub mod logging;
 pub mod model_spec;
+pub mod plugin;
 pub mod session_config;
 pub mod session_id;
 pub mod transport;
@@ -41,12 +42,15 @@ pub use install_hints::{
 };
 pub use logging::create_session_log_writer;
 pub use model_spec::{ModelSpec, ThinkingBudget};
+pub use plugin::{
+    PLUGIN_PROTOCOL_VERSION, PluginCapabilities, PluginExecutor, PluginOutcome, PluginRequest,
+};
 pub use session_config::{
     McpServerConfig as AcpMcpServerConfig, SessionConfig, ToolOutputCompactionConfig,
 };
 pub use session_id::{extract_session_id, extract_session_id_from_transport};
 #[cfg(feature = "acp")]
-pub use transport::AcpTransport;
+pub use transport::{AcpTransport, enable_acp_agent_pool};
 pub use transport::{
     CODEX_EXEC_INITIAL_STALL_REASON, ClaudeCodeCliTransport,
     DEFAULT_CODEX_INITIAL_RESPONSE_TIMEOUT_SECONDS, GEMINI_OAUTH_PROMPT_FATAL_MARKER,
@@ -0,0 +1,382 @@
+//! Executor plugins: external tools driven over a JSON-lines protocol.
+//!
+//! A plugin is an executable at `~/.config/cli-sub-agent/executors/<name>`,
+//! registered with `[tools.<name>] plugin = true`. Each run is one process:
+//!
+//! 1. csa writes `{"type":"hello","protocol_version":1,"tool":"<name>"}`.
+//! 2. The plugin replies `{"type":"capabilities","protocol_version":1,...}`.
+//! 3. csa writes one `{"type":"execute",...}` request and closes stdin.
+//! 4. The plugin streams `output`, `session` and `usage` messages and ends
+//!    with `done` (or `error`).
+//!
+//! Stdout is reserved for protocol messages; diagnostics belong on stderr.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use csa_session::state::{MetaSessionState, TokenUsage};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+
+/// Protocol version spoken by this csa build.
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// How long a plugin may take to answer `hello`.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Grace period for the process to exit after `done`/`error`.
+const EXIT_GRACE: Duration = Duration::from_secs(5);
+
+/// Cap on retained stderr, which is only used for diagnostics.
+const STDERR_TAIL_BYTES: usize = 16 * 1024;
+
+/// What a plugin declared in its handshake.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct PluginCapabilities {
+    pub protocol_version: u32,
+    /// Accepts `session_id` in `execute` to continue a previous session.
+    #[serde(default)]
+    pub resume: bool,
+    /// Honors the `thinking` field of `execute`.
+    #[serde(default)]
+    pub thinking: bool,
+    /// Models the plugin accepts; empty means any.
+    #[serde(default)]
+    pub models: Vec<String>,
+}
+
+/// One prompt to run through a plugin.
+#[derive(Debug, Clone)]
+pub struct PluginRequest<'a> {
+    pub prompt: &'a str,
+    pub model: Option<&'a str>,
+    pub thinking: Option<&'a str>,
+    /// Plugin-side session to continue, from a previous `session` message.
+    pub resume_session_id: Option<&'a str>,
+    pub working_dir: &'a Path,
+    pub timeout: Option<Duration>,
+}
+
+/// What a plugin run produced.
+#[derive(Debug, Clone, Default)]
+pub struct PluginOutcome {
+    pub capabilities: PluginCapabilities,
+    pub output: String,
+    pub exit_code: i32,
+    pub summary: Option<String>,
+    pub provider_session_id: Option<String>,
+    pub token_usage: Option<TokenUsage>,
+    pub stderr: String,
+    /// `resume_session_id` was sent to a plugin that supports resume.
+    pub resumed: bool,
+    pub timed_out: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HostMessage<'a> {
+    Hello {
+        protocol_version: u32,
+        tool: &'a str,
+    },
+    Execute {
+        prompt: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thinking: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<&'a str>,
+        working_dir: &'a Path,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PluginMessage {
+    Capabilities(PluginCapabilities),
+    Output {
+        text: String,
+    },
+    Session {
+        id: String,
+    },
+    Usage(TokenUsage),
+    Done {
+        exit_code: i32,
+        #[serde(default)]
+        summary: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// A registered executor plugin.
+#[derive(Debug, Clone)]
+pub struct PluginExecutor {
+    name: String,
+    executable: PathBuf,
+}
+
+impl PluginExecutor {
+    pub fn new(name: impl Into<String>, executable: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            executable: executable.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn executable(&self) -> &Path {
+        &self.executable
+    }
+
+    /// Environment csa hands every child so nested `csa` calls see their
+    /// session, depth and lineage, mirroring the built-in executors.
+    pub fn session_env(&self, session: &MetaSessionState) -> Vec<(String, String)> {
+        let mut env = vec![
+            (
+                "CSA_SESSION_ID".to_string(),
+                session.meta_session_id.clone(),
+            ),
+            (
+                "CSA_DEPTH".to_string(),
+                (session.genealogy.depth + 1).to_string(),
+            ),
+            ("CSA_PROJECT_ROOT".to_string(), session.project_path.clone()),
+            (
+                csa_core::env::CSA_INTERNAL_INVOCATION_ENV_KEY.to_string(),
+                "1".to_string(),
+            ),
+            ("CSA_TOOL".to_string(), self.name.clone()),
+        ];
+        if let Some(token) = csa_session::child_lineage_token(session) {
+            env.push((csa_core::env::CSA_LINEAGE_ENV_KEY.to_string(), token));
+        }
+        if let Ok(dir) = csa_session::manager::get_session_dir(
+            Path::new(&session.project_path),
+            &session.meta_session_id,
+        ) {
+            env.push((
+                "CSA_SESSION_DIR".to_string(),
+                dir.to_string_lossy().into_owned(),
+            ));
+        }
+        if let Ok(current_tool) = std::env::var("CSA_TOOL") {
+            env.push(("CSA_PARENT_TOOL".to_string(), current_tool));
+        }
+        if let Some(parent) = &session.genealogy.parent_session_id {
+            env.push(("CSA_PARENT_SESSION".to_string(), parent.clone()));
+        }
+        env
+    }
+
+    /// Run one request: handshake, send `execute`, and stream messages until
+    /// `done`/`error`. `on_output` sees each `output` chunk as it arrives.
+    pub async fn execute(
+        &self,
+        request: &PluginRequest<'_>,
+        env: &[(String, String)],
+        mut on_output: impl FnMut(&str),
+    ) -> Result<PluginOutcome> {
+        let mut cmd = Command::new(&self.executable);
+        cmd.current_dir(request.working_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for key in crate::CHILD_PROCESS_STRIPPED_ENV_VARS {
+            cmd.env_remove(key);
+        }
+        cmd.envs(env.iter().map(|(key, value)| (key, value)));
+        let mut child = cmd.spawn().with_context(|| {
+            format!(
+                "Failed to start executor plugin '{}' at {}",
+                self.name,
+                self.executable.display()
+            )
+        })?;
+
+        let stderr_task = child.stderr.take().map(|mut stderr| {
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let _ = stderr.read_to_end(&mut buf).await;
+                let start = buf.len().saturating_sub(STDERR_TAIL_BYTES);
+                String::from_utf8_lossy(&buf[start..]).into_owned()
+            })
+        });
+        let mut stdin = child.stdin.take().context("plugin stdin unavailable")?;
+        let mut lines =
+            BufReader::new(child.stdout.take().context("plugin stdout unavailable")?).lines();
+
+        write_message(
+            &mut stdin,
+            &HostMessage::Hello {
+                protocol_version: PLUGIN_PROTOCOL_VERSION,
+                tool: &self.name,
+            },
+        )
+        .await?;
+        let capabilities = match tokio::time::timeout(HANDSHAKE_TIMEOUT, lines.next_line()).await {
+            Err(_) => bail!(
+                "Executor plugin '{}' did not answer the handshake within {}s",
+                self.name,
+                HANDSHAKE_TIMEOUT.as_secs()
+            ),
+            Ok(line) => match parse_message(&self.name, line?)? {
+                Some(PluginMessage::Capabilities(capabilities)) => capabilities,
+                _ => bail!(
+                    "Executor plugin '{}' must answer hello with a capabilities message",
+                    self.name
+                ),
+            },
+        };
+        if capabilities.protocol_version != PLUGIN_PROTOCOL_VERSION {
+            bail!(
+                "Executor plugin '{}' speaks protocol version {}, csa supports {}",
+                self.name,
+                capabilities.protocol_version,
+                PLUGIN_PROTOCOL_VERSION
+            );
+        }
+        if let Some(model) = request.model
+            && !capabilities.models.is_empty()
+            && !capabilities.models.iter().any(|known| known == model)
+        {
+            bail!(
+                "Executor plugin '{}' does not support model '{model}' (supported: {})",
+                self.name,
+                capabilities.models.join(", ")
+            );
+        }
+
+        let resumed = request.resume_session_id.is_some() && capabilities.resume;
+        write_message(
+            &mut stdin,
+            &HostMessage::Execute {
+                prompt: request.prompt,
+                model: request.model,
+                thinking: request.thinking.filter(|_| capabilities.thinking),
+                session_id: request.resume_session_id.filter(|_| resumed),
+                working_dir: request.working_dir,
+            },
+        )
+        .await?;
+        drop(stdin);
+
+        let mut outcome = PluginOutcome {
+            capabilities,
+            resumed,
+            ..Default::default()
+        };
+        let stream = async {
+            while let Some(line) = lines.next_line().await? {
+                match parse_message(&self.name, Some(line))? {
+                    None | Some(PluginMessage::Capabilities(_)) => {}
+                    Some(PluginMessage::Output { text }) => {
+                        on_output(&text);
+                        outcome.output.push_str(&text);
+                    }
+                    Some(PluginMessage::Session { id }) => outcome.provider_session_id = Some(id),
+                    Some(PluginMessage::Usage(usage)) => outcome.token_usage = Some(usage),
+                    Some(PluginMessage::Done { exit_code, summary }) => {
+                        return Ok(Some((exit_code, summary)));
+                    }
+                    Some(PluginMessage::Error { message }) => return Ok(Some((1, Some(message)))),
+                }
+            }
+            Ok::<_, anyhow::Error>(None)
+        };
+        let finished = match request.timeout {
+            Some(limit) => tokio::time::timeout(limit, stream).await.ok(),
+            None => Some(stream.await),
+        };
+        match finished {
+            Some(Ok(Some((exit_code, summary)))) => {
+                outcome.exit_code = exit_code;
+                outcome.summary = summary;
+                reap(&mut child, EXIT_GRACE).await;
+            }
+            Some(Ok(None)) => {
+                let status = child.wait().await?;
+                outcome.exit_code = match status.code() {
+                    Some(0) | None => 1,
+                    Some(code) => code,
+                };
+                outcome.summary = Some(format!(
+                    "executor plugin '{}' exited ({status}) without a done message",
+                    self.name
+                ));
+            }
+            Some(Err(err)) => {
+                reap(&mut child, Duration::ZERO).await;
+                return Err(err);
+            }
+            None => {
+                reap(&mut child, Duration::ZERO).await;
+                outcome.timed_out = true;
+                outcome.exit_code = 124;
+                outcome.summary = Some(format!(
+                    "executor plugin '{}' timed out after {}s",
+                    self.name,
+                    request.timeout.unwrap_or_default().as_secs()
+                ));
+            }
+        }
+        // A lingering grandchild can hold stderr open; do not wait on it forever.
+        if let Some(task) = stderr_task
+            && let Ok(Ok(stderr)) = tokio::time::timeout(EXIT_GRACE, task).await
+        {
+            outcome.stderr = stderr;
+        }
+        Ok(outcome)
+    }
+}
+
+async fn write_message(
+    stdin: &mut tokio::process::ChildStdin,
+    message: &HostMessage<'_>,
+) -> Result<()> {
+    let mut line = serde_json::to_vec(message).context("Failed to encode plugin message")?;
+    line.push(b'\n');
+    stdin
+        .write_all(&line)
+        .await
+        .context("Failed to write to executor plugin stdin")?;
+    stdin
+        .flush()
+        .await
+        .context("Failed to flush executor plugin stdin")
+}
+
+/// Blank lines are ignored; anything else must be a protocol message.
+fn parse_message(plugin: &str, line: Option<String>) -> Result<Option<PluginMessage>> {
+    let Some(line) = line else {
+        bail!("Executor plugin '{plugin}' closed stdout during the handshake");
+    };
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(trimmed).map(Some).with_context(|| {
+        format!("Executor plugin '{plugin}' sent an invalid protocol line: {trimmed}")
+    })
+}
+
+/// Give the process `grace` to exit on its own, then kill it.
+async fn reap(child: &mut Child, grace: Duration) {
+    if tokio::time::timeout(grace, child.wait()).await.is_err() {
+        let _ = child.kill().await;
+    }
+}
+
+#[cfg(test)]
+#[path = "plugin_tests.rs"]
+mod tests;
@@ -0,0 +1,136 @@
+use super::*;
+use std::os::unix::fs::PermissionsExt;
+
+/// Write an executable shell plugin into `dir`.
+fn write_plugin(dir: &Path, body: &str) -> PluginExecutor {
+    let path = dir.join("echo-plugin");
+    std::fs::write(&path, format!("#!/bin/sh\n{body}")).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    PluginExecutor::new("echo-plugin", path)
+}
+
+fn request<'a>(dir: &'a Path, resume: Option<&'a str>) -> PluginRequest<'a> {
+    PluginRequest {
+        prompt: "say hi",
+        model: Some("small"),
+        thinking: Some("high"),
+        resume_session_id: resume,
+        working_dir: dir,
+        timeout: Some(Duration::from_secs(10)),
+    }
+}
+
+const HANDSHAKE: &str = r#"read hello
+echo '{"type":"capabilities","protocol_version":1,"resume":true,"models":["small"]}'
+read execute
+"#;
+
+#[tokio::test]
+async fn streams_output_session_usage_and_done() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plugin = write_plugin(
+        tmp.path(),
+        &format!(
+            "{HANDSHAKE}\
+             echo \"$execute\" >&2\n\
+             echo '{{\"type\":\"output\",\"text\":\"hello \"}}'\n\
+             echo ''\n\
+             echo '{{\"type\":\"session\",\"id\":\"thread-7\"}}'\n\
+             echo '{{\"type\":\"output\",\"text\":\"world\"}}'\n\
+             echo '{{\"type\":\"usage\",\"input_tokens\":12,\"output_tokens\":3}}'\n\
+             echo '{{\"type\":\"done\",\"exit_code\":0,\"summary\":\"greeted\"}}'\n"
+        ),
+    );
+
+    let mut streamed = Vec::new();
+    let outcome = plugin
+        .execute(&request(tmp.path(), Some("thread-6")), &[], |chunk| {
+            streamed.push(chunk.to_string())
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(streamed, ["hello ", "world"]);
+    assert_eq!(outcome.output, "hello world");
+    assert_eq!(outcome.exit_code, 0);
+    assert_eq!(outcome.summary.as_deref(), Some("greeted"));
+    assert_eq!(outcome.provider_session_id.as_deref(), Some("thread-7"));
+    assert_eq!(outcome.token_usage.unwrap().input_tokens, Some(12));
+    assert!(outcome.resumed);
+    assert!(outcome.capabilities.resume);
+    // The plugin did not declare `thinking`, so it is not forwarded.
+    assert!(outcome.stderr.contains(r#""session_id":"thread-6""#));
+    assert!(!outcome.stderr.contains("thinking"));
+}
+
+#[tokio::test]
+async fn handshake_rejects_wrong_version_and_unsupported_model() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plugin = write_plugin(
+        tmp.path(),
+        "read hello\necho '{\"type\":\"capabilities\",\"protocol_version\":9}'\n",
+    );
+    let err = plugin
+        .execute(&request(tmp.path(), None), &[], |_| {})
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("protocol version 9"), "{err}");
+
+    let plugin = write_plugin(
+        tmp.path(),
+        "read hello\necho '{\"type\":\"capabilities\",\"protocol_version\":1,\"models\":[\"big\"]}'\n",
+    );
+    let err = plugin
+        .execute(&request(tmp.path(), None), &[], |_| {})
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("does not support model 'small'"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn error_messages_and_missing_done_fail_the_run() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plugin = write_plugin(
+        tmp.path(),
+        &format!("{HANDSHAKE}echo '{{\"type\":\"error\",\"message\":\"quota exhausted\"}}'\n"),
+    );
+    let outcome = plugin
+        .execute(&request(tmp.path(), None), &[], |_| {})
+        .await
+        .unwrap();
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.summary.as_deref(), Some("quota exhausted"));
+
+    let plugin = write_plugin(
+        tmp.path(),
+        &format!("{HANDSHAKE}echo '{{\"type\":\"output\",\"text\":\"partial\"}}'\nexit 0\n"),
+    );
+    let outcome = plugin
+        .execute(&request(tmp.path(), None), &[], |_| {})
+        .await
+        .unwrap();
+    assert_eq!(outcome.exit_code, 1);
+    assert_eq!(outcome.output, "partial");
+    assert!(outcome.summary.unwrap().contains("without a done message"));
+}
+
+#[tokio::test]
+async fn invalid_lines_and_timeouts_are_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plugin = write_plugin(tmp.path(), &format!("{HANDSHAKE}echo 'debug: starting'\n"));
+    let err = plugin
+        .execute(&request(tmp.path(), None), &[], |_| {})
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid protocol line"), "{err}");
+
+    let plugin = write_plugin(tmp.path(), &format!("{HANDSHAKE}exec sleep 30\n"));
+    let mut slow = request(tmp.path(), None);
+    slow.timeout = Some(Duration::from_millis(200));
+    let outcome = plugin.execute(&slow, &[], |_| {}).await.unwrap();
+    assert!(outcome.timed_out);
+    assert_eq!(outcome.exit_code, 124);
+}
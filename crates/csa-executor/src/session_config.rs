@@ -33,4 +33,7 @@ pub struct SessionConfig {
     pub mcp_proxy_socket: Option<String>,
     #[serde(skip)]
     pub tool_output_compaction: Option<ToolOutputCompactionConfig>,
+    /// Policy for ACP permission requests; `None` grants them.
+    #[serde(skip)]
+    pub acp_permissions: Option<csa_core::acp_permissions::AcpPermissionPolicy>,
 }
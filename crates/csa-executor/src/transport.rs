@@ -54,8 +54,15 @@ pub use transport_meta::PeakMemoryContext;
 #[cfg(feature = "acp")]
 #[path = "transport_acp_sandbox.rs"]
 mod transport_acp_sandbox;
+#[path = "transport_memory_checkpoint.rs"]
+mod transport_memory_checkpoint;
 #[cfg(feature = "acp")]
 use transport_acp_sandbox::{build_summary, run_acp_sandboxed};
+#[cfg(feature = "acp")]
+#[path = "transport_acp_pool.rs"]
+mod transport_acp_pool;
+#[cfg(feature = "acp")]
+pub use transport_acp_pool::enable_acp_agent_pool;
 #[path = "transport_gemini_helpers.rs"]
 mod transport_gemini_helpers;
 pub use crate::transport_gemini_oauth::{
@@ -116,7 +123,9 @@ pub use transport_cli::ClaudeCodeCliTransport;
 #[cfg(feature = "acp")]
 mod transport_acp_payload_debug;
 #[cfg(feature = "acp")]
-use transport_acp_payload_debug::{AcpPayloadDebugRequest, maybe_write_acp_payload_debug};
+use transport_acp_payload_debug::{
+    AcpPayloadDebugRequest, acp_capture_path, maybe_write_acp_payload_debug,
+};
 #[path = "transport_codex_exec_stall.rs"]
 mod transport_codex_exec_stall;
 #[path = "transport_legacy_codex_exec_stall.rs"]
@@ -164,6 +173,7 @@ pub(crate) fn build_ephemeral_meta_session(work_dir: &Path) -> MetaSessionState
         token_budget: None,
         sandbox_info: None,
         termination_reason: None,
+        last_model: None,
         is_seed_candidate: false,
         git_head_at_creation: None,
         pre_session_porcelain: None,
@@ -172,6 +182,8 @@ pub(crate) fn build_ephemeral_meta_session(work_dir: &Path) -> MetaSessionState
         spec_id: None,
         vcs_identity: None,
         identity_version: 1,
+        pressure_events: Vec::new(),
+        user: None,
         fork_call_timestamps: Vec::new(),
     }
 }
@@ -223,6 +235,20 @@ impl AcpTransport {
         }
     }
 
+    /// Upper bound on how long an interrupted turn takes to end: the
+    /// `session/cancel` grace, then SIGTERM and the default termination grace.
+    pub const INTERRUPT_WAIT: std::time::Duration = csa_acp::interrupt::CANCEL_GRACE
+        .saturating_add(std::time::Duration::from_secs(
+            csa_process::DEFAULT_TERMINATION_GRACE_PERIOD_SECS + 1,
+        ));
+
+    /// Cancel every in-flight ACP turn with `session/cancel`, escalating to
+    /// signals for agents that do not stop within the cancel grace. Returns
+    /// whether any turn was in flight.
+    pub fn interrupt_in_flight_prompts() -> bool {
+        csa_acp::interrupt::interrupt_prompts() > 0
+    }
+
     fn acp_command_for_tool(tool_name: &str) -> (String, Vec<String>) {
         // ACP adapters: @zed-industries/{codex,claude-code}-acp via npm;
         // gemini-cli has native ACP mode via `gemini --acp`.
@@ -373,6 +399,7 @@ impl AcpTransport {
             session_meta: session_meta.as_ref(),
             prompt: &prompt,
         });
+        let frame_capture = acp_capture_path(&env, session_dir.as_deref());
         let stream_stdout_to_stderr =
             should_stream_acp_stdout_to_stderr(options.stream_mode, options.output_spool);
         let output_spool = options.output_spool.map(std::path::Path::to_path_buf);
@@ -411,7 +438,12 @@ impl AcpTransport {
             output_spool_max_bytes,
             output_spool_keep_rotated,
             tool_output_compaction,
+            permission_policy: self
+                .session_config
+                .as_ref()
+                .and_then(|config| config.acp_permissions.clone()),
             acp_payload_debug_path,
+            frame_capture,
             gemini_classification_env,
             gemini_env_allowlist_applied,
             memory_max_mb: options
@@ -466,6 +498,7 @@ impl AcpTransport {
             model_completed,
             terminal_reason,
             peak_memory_mb: output.peak_memory_mb,
+            child_usage: output.usage,
             ..Default::default()
         };
         if let Some(warning_summary) = gemini_warning_summary.as_deref() {
@@ -521,6 +554,28 @@ fn convert_acp_event(event: csa_acp::SessionEvent) -> csa_core::transport_events
         csa_acp::SessionEvent::PlanUpdate(text) => {
             csa_core::transport_events::SessionEvent::PlanUpdate(text)
         }
+        csa_acp::SessionEvent::PermissionRequest {
+            id,
+            title,
+            kind,
+            paths,
+            decision,
+        } => csa_core::transport_events::SessionEvent::PermissionRequest {
+            id,
+            title,
+            kind,
+            paths,
+            decision,
+        },
+        csa_acp::SessionEvent::FileAccess {
+            path,
+            operation,
+            status,
+        } => csa_core::transport_events::SessionEvent::FileAccess {
+            path,
+            operation,
+            status,
+        },
         csa_acp::SessionEvent::Other(text) => csa_core::transport_events::SessionEvent::Other(text),
     }
 }
@@ -7,6 +7,8 @@ use serde_json::Value;
 
 pub(crate) const ACP_PAYLOAD_DEBUG_ENV: &str = "CSA_DEBUG_ACP_PAYLOAD";
 const ACP_PAYLOAD_DEBUG_REL_PATH: &str = "output/acp-payload-debug.json";
+pub(crate) const ACP_CAPTURE_ENV: &str = "CSA_ACP_CAPTURE";
+const ACP_CAPTURE_REL_PATH: &str = "output/acp-capture.jsonl";
 
 pub(super) struct AcpPayloadDebugRequest<'a> {
     pub(super) env: &'a HashMap<String, String>,
@@ -40,16 +42,31 @@ fn debug_flag_enabled(value: &str) -> bool {
     matches!(normalized.as_str(), "1" | "true" | "yes" | "on")
 }
 
-fn acp_payload_debug_enabled(env: &HashMap<String, String>) -> bool {
-    if let Some(value) = env.get(ACP_PAYLOAD_DEBUG_ENV) {
+fn env_flag_enabled(env: &HashMap<String, String>, name: &str) -> bool {
+    if let Some(value) = env.get(name) {
         return debug_flag_enabled(value);
     }
 
-    std::env::var(ACP_PAYLOAD_DEBUG_ENV)
+    std::env::var(name)
         .map(|value| debug_flag_enabled(&value))
         .unwrap_or(false)
 }
 
+fn acp_payload_debug_enabled(env: &HashMap<String, String>) -> bool {
+    env_flag_enabled(env, ACP_PAYLOAD_DEBUG_ENV)
+}
+
+/// Where the ACP frame capture goes when `CSA_ACP_CAPTURE` is on.
+pub(super) fn acp_capture_path(
+    env: &HashMap<String, String>,
+    session_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    if !env_flag_enabled(env, ACP_CAPTURE_ENV) {
+        return None;
+    }
+    Some(session_dir?.join(ACP_CAPTURE_REL_PATH))
+}
+
 fn redact_env_value(value: &mut Value) {
     match value {
         Value::Object(map) => {
@@ -0,0 +1,208 @@
+//! Process-wide pool of unsandboxed ACP agents.
+//!
+//! The pool is off unless a long-lived host such as `csa acp-serve` turns it
+//! on with [`enable_acp_agent_pool`]; a one-shot `csa run` would start and
+//! drop the agent anyway.
+//!
+//! [`csa_acp::AcpAgentPool`] holds `Rc` state, so it lives on a dedicated
+//! thread with its own current-thread runtime. Attempts hand their prompt
+//! to that thread and wait for the output; agents outlive the attempt and
+//! serve later attempts of any CSA session with the same pool key. Agents
+//! without a leased session are killed once no prompt arrived for
+//! [`POOL_IDLE_SHUTDOWN`].
+//!
+//! Agents are spawned without the `CSA_*` session contract variables. Those
+//! go to `_meta.claudeCode.options.env` of each `session/new`, which
+//! `claude-code-acp` applies to that session's Claude Code process, so only
+//! `claude-code` runs are pooled.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use csa_acp::transport::AcpOutput;
+use csa_acp::{AcpAgentPool, AcpPoolConfig, AcpPoolKey, AcpResult};
+use tokio::sync::{mpsc, oneshot};
+
+use super::AcpPromptRunRequest;
+
+/// How long idle agents are kept after the last prompt.
+const POOL_IDLE_SHUTDOWN: Duration = Duration::from_secs(60);
+
+type PoolJob = (AcpPromptRunRequest, oneshot::Sender<AcpResult<AcpOutput>>);
+
+static POOL_JOBS: OnceLock<Option<mpsc::UnboundedSender<PoolJob>>> = OnceLock::new();
+static POOL_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Let unsandboxed `claude-code` attempts of this process share pooled
+/// agents. Meant for processes that serve many CSA sessions.
+pub fn enable_acp_agent_pool() {
+    POOL_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether `request` may run on a pooled agent. Sandboxed runs need a
+/// scope of their own, frame capture needs a process of its own, and only
+/// `claude-code-acp` takes session-scoped variables from the session meta.
+pub(super) fn is_poolable(request: &AcpPromptRunRequest) -> bool {
+    request.tool_name == "claude-code"
+        && request.sandbox_plan.is_none()
+        && request.frame_capture.is_none()
+}
+
+/// Pool key of `request`: everything that decides which process is spawned.
+pub(super) fn pool_key(request: &AcpPromptRunRequest) -> AcpPoolKey {
+    AcpPoolKey {
+        tool_name: request.tool_name.clone(),
+        command: request.acp_command.clone(),
+        args: request.acp_args.clone(),
+        working_dir: request.working_dir.clone(),
+    }
+}
+
+fn is_session_scoped_env_key(key: &str) -> bool {
+    key.starts_with("CSA_") || key.starts_with("_CSA_")
+}
+
+/// Split the environment of `request` into the part a shared agent process
+/// is spawned with and the session-scoped part.
+fn split_session_env(
+    request: &AcpPromptRunRequest,
+) -> (
+    HashMap<String, String>,
+    serde_json::Map<String, serde_json::Value>,
+) {
+    let mut process_env = HashMap::new();
+    let mut session_env = serde_json::Map::new();
+    for (key, value) in &request.env {
+        if is_session_scoped_env_key(key) {
+            session_env.insert(key.clone(), serde_json::Value::String(value.clone()));
+        } else {
+            process_env.insert(key.clone(), value.clone());
+        }
+    }
+    (process_env, session_env)
+}
+
+/// Session meta of `request` with `session_env` added as
+/// `claudeCode.options.env`.
+fn pooled_session_meta(
+    request: &AcpPromptRunRequest,
+    session_env: serde_json::Map<String, serde_json::Value>,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let mut meta = request.session_meta.clone().unwrap_or_default();
+    if !session_env.is_empty() {
+        let options = meta
+            .entry("claudeCode")
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
+            .as_object_mut()
+            .and_then(|claude_code| {
+                claude_code
+                    .entry("options")
+                    .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
+                    .as_object_mut()
+            });
+        if let Some(options) = options {
+            options.insert("env".to_string(), serde_json::Value::Object(session_env));
+        }
+    }
+    (!meta.is_empty()).then_some(meta)
+}
+
+/// Run `request` on the pool thread, or return `None` when the pool is off
+/// or unavailable and the caller has to spawn a dedicated agent.
+pub(super) async fn run_pooled(request: AcpPromptRunRequest) -> Option<AcpResult<AcpOutput>> {
+    if !POOL_ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let jobs = POOL_JOBS.get_or_init(start_pool_thread).as_ref()?;
+    let (reply, output) = oneshot::channel();
+    jobs.send((request, reply)).ok()?;
+    output.await.ok()
+}
+
+fn start_pool_thread() -> Option<mpsc::UnboundedSender<PoolJob>> {
+    let (jobs, receiver) = mpsc::unbounded_channel();
+    match std::thread::Builder::new()
+        .name("csa-acp-pool".to_string())
+        .spawn(move || run_pool_thread(receiver))
+    {
+        Ok(_) => Some(jobs),
+        Err(error) => {
+            tracing::warn!("failed to start the ACP agent pool thread: {error}");
+            None
+        }
+    }
+}
+
+fn run_pool_thread(mut jobs: mpsc::UnboundedReceiver<PoolJob>) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            tracing::warn!("failed to build the ACP agent pool runtime: {error}");
+            return;
+        }
+    };
+    let local_set = tokio::task::LocalSet::new();
+    local_set.block_on(&runtime, async move {
+        let pool = Rc::new(AcpAgentPool::new(AcpPoolConfig::default()));
+        loop {
+            match tokio::time::timeout(POOL_IDLE_SHUTDOWN, jobs.recv()).await {
+                Ok(Some((request, reply))) => {
+                    let pool = Rc::clone(&pool);
+                    tokio::task::spawn_local(async move {
+                        let _ = reply.send(run_pooled_prompt(&pool, &request).await);
+                    });
+                }
+                Ok(None) => break,
+                Err(_) => pool.shutdown_idle().await,
+            }
+        }
+        pool.shutdown_idle().await;
+    });
+}
+
+async fn run_pooled_prompt(
+    pool: &AcpAgentPool,
+    request: &AcpPromptRunRequest,
+) -> AcpResult<AcpOutput> {
+    let (process_env, session_env) = split_session_env(request);
+    csa_acp::transport::run_pooled_prompt(
+        pool,
+        &pool_key(request),
+        &process_env,
+        csa_acp::transport::AcpSessionStart {
+            system_prompt: request.system_prompt.as_deref(),
+            resume_session_id: request.resume_session_id.as_deref(),
+            meta: pooled_session_meta(request, session_env),
+            ..Default::default()
+        },
+        &request.prompt,
+        csa_acp::transport::AcpRunOptions {
+            idle_timeout: Duration::from_secs(request.idle_timeout_seconds),
+            initial_response_timeout: request
+                .initial_response_timeout_seconds
+                .map(Duration::from_secs),
+            init_timeout: Duration::from_secs(request.acp_init_timeout_seconds),
+            termination_grace_period: Duration::from_secs(request.termination_grace_period_seconds),
+            io: csa_acp::transport::AcpOutputIoOptions {
+                stream_stdout_to_stderr: request.stream_stdout_to_stderr,
+                output_spool: request.output_spool.as_deref(),
+                spool_max_bytes: request.output_spool_max_bytes,
+                keep_rotated_spool: request.output_spool_keep_rotated,
+                tool_output_compaction: request.tool_output_compaction.clone(),
+            },
+            permission_policy: request.permission_policy.clone(),
+            frame_capture: None,
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+#[path = "transport_acp_pool_tests.rs"]
+mod tests;
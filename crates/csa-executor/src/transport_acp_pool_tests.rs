@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use csa_resource::isolation_plan::{EnforcementMode, IsolationPlanBuilder};
+
+use super::super::AcpTransport;
+use super::*;
+
+/// ACP agent that answers every prompt with its own PID, the CSA session ID
+/// its `session/new` meta carried, and the one in its process environment.
+const PID_AGENT_SCRIPT: &str = r##"n=0
+while IFS= read -r line; do
+  id=$(printf '%s\n' "$line" | sed -n 's/^.*"id":\([0-9][0-9]*\).*$/\1/p')
+  case "$line" in
+    *'"method":"initialize"'*)
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":1,"agentCapabilities":{}}}\n' "$id" ;;
+    *'"method":"session/new"'*)
+      n=$((n + 1))
+      owner=$(printf '%s\n' "$line" | sed -n 's/^.*"CSA_SESSION_ID":"\([^"]*\)".*$/\1/p')
+      eval "owner_$n=\${owner:-none}"
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"sessionId":"pooled-%s"}}\n' "$id" "$n" ;;
+    *'"method":"session/prompt"'*)
+      sid=$(printf '%s\n' "$line" | sed -n 's/^.*"sessionId":"\([^"]*\)".*$/\1/p')
+      eval "owner=\$owner_${sid#pooled-}"
+      printf '{"jsonrpc":"2.0","method":"session/update","params":{"sessionId":"%s","update":{"sessionUpdate":"agent_message_chunk","content":{"type":"text","text":"agent %s meta %s env %s"}}}}\n' "$sid" "$$" "$owner" "${CSA_SESSION_ID:-unset}"
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"stopReason":"end_turn"}}\n' "$id" ;;
+  esac
+done
+"##;
+
+fn pid_agent_request(dir: &Path, session_id: &str) -> AcpPromptRunRequest {
+    let script = dir.join("pid-agent.sh");
+    std::fs::write(&script, PID_AGENT_SCRIPT).expect("write agent script");
+    AcpPromptRunRequest {
+        tool_name: "claude-code".to_string(),
+        acp_command: "sh".to_string(),
+        acp_args: vec![script.display().to_string()],
+        prompt: "which process are you?".to_string(),
+        working_dir: dir.to_path_buf(),
+        env: HashMap::from([("CSA_SESSION_ID".to_string(), session_id.to_string())]),
+        system_prompt: None,
+        resume_session_id: None,
+        session_meta: None,
+        sandbox_plan: None,
+        sandbox_tool_name: None,
+        sandbox_session_id: None,
+        sandbox_best_effort: false,
+        idle_timeout_seconds: 30,
+        initial_response_timeout_seconds: None,
+        acp_init_timeout_seconds: 30,
+        termination_grace_period_seconds: 1,
+        stream_stdout_to_stderr: false,
+        output_spool: None,
+        output_spool_max_bytes: csa_process::DEFAULT_SPOOL_MAX_BYTES,
+        output_spool_keep_rotated: false,
+        tool_output_compaction: None,
+        permission_policy: None,
+        acp_payload_debug_path: None,
+        frame_capture: None,
+        gemini_classification_env: None,
+        gemini_env_allowlist_applied: String::new(),
+        memory_max_mb: None,
+    }
+}
+
+async fn agent_reply(request: AcpPromptRunRequest) -> String {
+    enable_acp_agent_pool();
+    tokio::time::timeout(
+        Duration::from_secs(60),
+        AcpTransport::run_acp_prompt(request),
+    )
+    .await
+    .expect("pooled prompt timed out")
+    .expect("pooled prompt")
+    .output
+    .trim()
+    .to_string()
+}
+
+/// Split a reply of [`PID_AGENT_SCRIPT`] into PID, meta session ID and
+/// process-env session ID.
+fn reply_parts(reply: &str) -> (String, String, String) {
+    match reply.split(' ').collect::<Vec<_>>().as_slice() {
+        ["agent", pid, "meta", meta, "env", env] => {
+            (pid.to_string(), meta.to_string(), env.to_string())
+        }
+        _ => panic!("unexpected reply: {reply}"),
+    }
+}
+
+#[tokio::test]
+async fn attempts_of_one_session_reuse_the_pooled_agent() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let request = pid_agent_request(dir.path(), "01POOLREUSE");
+
+    let first = agent_reply(request.clone()).await;
+    let second = agent_reply(request).await;
+
+    assert_eq!(reply_parts(&first).0, reply_parts(&second).0);
+}
+
+#[tokio::test]
+async fn two_sessions_lease_one_agent_process() {
+    let dir = tempfile::tempdir().expect("tempdir");
+
+    let first = agent_reply(pid_agent_request(dir.path(), "01POOLSESSIONA")).await;
+    let second = agent_reply(pid_agent_request(dir.path(), "01POOLSESSIONB")).await;
+
+    let (first_pid, first_meta, first_env) = reply_parts(&first);
+    let (second_pid, second_meta, second_env) = reply_parts(&second);
+    assert_eq!(first_pid, second_pid, "both sessions must lease one agent");
+    assert_eq!(first_meta, "01POOLSESSIONA");
+    assert_eq!(second_meta, "01POOLSESSIONB");
+    assert_eq!(
+        (first_env.as_str(), second_env.as_str()),
+        ("unset", "unset"),
+        "session IDs must not leak into the shared process env"
+    );
+}
+
+#[test]
+fn session_env_joins_the_claude_code_options_of_the_meta() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut request = pid_agent_request(dir.path(), "01POOLMETA");
+    request
+        .env
+        .insert("PATH".to_string(), "/usr/bin".to_string());
+    request.session_meta = Some(
+        serde_json::json!({"claudeCode": {"options": {"settingSources": ["project"]}}})
+            .as_object()
+            .cloned()
+            .expect("meta object"),
+    );
+
+    let (process_env, session_env) = split_session_env(&request);
+    let meta = pooled_session_meta(&request, session_env).expect("meta");
+
+    assert_eq!(
+        process_env,
+        HashMap::from([("PATH".to_string(), "/usr/bin".to_string())])
+    );
+    assert_eq!(
+        serde_json::Value::Object(meta),
+        serde_json::json!({"claudeCode": {"options": {
+            "settingSources": ["project"],
+            "env": {"CSA_SESSION_ID": "01POOLMETA"},
+        }}})
+    );
+}
+
+#[test]
+fn sandboxed_captured_and_other_tool_runs_are_not_pooled() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let request = pid_agent_request(dir.path(), "01POOLCAPTURE");
+    assert!(is_poolable(&request));
+
+    let mut codex = request.clone();
+    codex.tool_name = "codex".to_string();
+    assert!(!is_poolable(&codex));
+
+    let mut captured = request.clone();
+    captured.frame_capture = Some(dir.path().join("frames.jsonl"));
+    assert!(!is_poolable(&captured));
+
+    let mut sandboxed = request;
+    sandboxed.sandbox_plan = Some(
+        IsolationPlanBuilder::new(EnforcementMode::Off)
+            .build()
+            .expect("isolation plan"),
+    );
+    assert!(!is_poolable(&sandboxed));
+}
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 use std::future::Future;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use csa_resource::isolation_plan::IsolationPlan;
 
-use super::transport_meta::start_memory_monitor;
+use super::transport_meta::{
+    MACOS_MEMORY_MONITOR_LABEL, append_disk_budget_notice, start_disk_monitor, start_memory_monitor,
+};
 
 const SUMMARY_MAX_CHARS: usize = 200;
 
@@ -45,17 +47,23 @@ pub(super) async fn run_acp_sandboxed(
     output_spool_max_bytes: u64,
     output_spool_keep_rotated: bool,
     tool_output_compaction: Option<csa_acp::ToolOutputCompactionConfig>,
+    permission_policy: Option<csa_core::acp_permissions::AcpPermissionPolicy>,
+    frame_capture: Option<&Path>,
 ) -> AcpSandboxedResult {
     use csa_acp::AcpConnection;
-    use csa_acp::connection::{AcpConnectionOptions, AcpSandboxRequest, AcpSpawnRequest};
+    use csa_acp::connection::{
+        AcpConnectionOptions, AcpSandboxRequest, AcpSpawnRequest, DEFAULT_MAX_RECONNECTS,
+    };
 
     let diagnostic_path =
         super::transport_meta::memory_soft_limit_diagnostic_path(working_dir, session_id);
     if let Some(path) = diagnostic_path.as_deref() {
         csa_resource::memory_monitor::clear_soft_limit_diagnostic(path);
     }
+    let memory_checkpoint =
+        super::transport_memory_checkpoint::memory_checkpoint(working_dir, session_id);
 
-    let (connection, sandbox_handle) = match AcpConnection::spawn_sandboxed(
+    let (mut connection, sandbox_handle) = match AcpConnection::spawn_sandboxed(
         AcpSpawnRequest {
             command,
             args,
@@ -64,6 +72,7 @@ pub(super) async fn run_acp_sandboxed(
             options: AcpConnectionOptions {
                 init_timeout,
                 termination_grace_period,
+                max_reconnects: DEFAULT_MAX_RECONNECTS,
             },
         },
         Some(AcpSandboxRequest {
@@ -85,27 +94,29 @@ pub(super) async fn run_acp_sandboxed(
             };
         }
     };
+    connection.set_permission_policy(permission_policy);
+    if let Some(path) = frame_capture {
+        connection.capture_frames(path);
+    }
 
     // Start memory monitor immediately after spawn, before initialize()/session
     // setup, so cold-start memory usage is also tracked.
-    let memory_monitor = sandbox_handle
-        .scope_name()
-        .zip(connection.child_pid())
-        .and_then(|(scope, pid)| {
-            start_memory_monitor(
-                scope,
-                pid,
-                isolation_plan,
-                termination_grace_period,
-                diagnostic_path.clone(),
-            )
-        });
+    let mut monitors = AcpMonitors {
+        isolation_plan,
+        grace_period: termination_grace_period,
+        diagnostic_path,
+        memory_checkpoint,
+        memory: None,
+        disk: None,
+        disk_breach: None,
+    };
+    monitors.start(&sandbox_handle, &connection);
 
     // Inner block: all fallible operations after spawn. peak_memory_mb is
     // captured regardless of success or failure.
     let inner_result = run_acp_sandboxed_inner(
-        &connection,
-        memory_monitor,
+        &mut connection,
+        &mut monitors,
         system_prompt,
         resume_session_id,
         meta,
@@ -121,6 +132,11 @@ pub(super) async fn run_acp_sandboxed(
     )
     .await;
 
+    let disk_breach = monitors.stop_disk().await;
+    if let Some(breach) = &disk_breach {
+        tracing::error!(tool = tool_name, "{breach}");
+    }
+
     let exit_signal = match &inner_result {
         Err(csa_acp::AcpError::ProcessExited { signal, .. }) => *signal,
         _ => None,
@@ -129,9 +145,22 @@ pub(super) async fn run_acp_sandboxed(
     // Capture peak memory and check for OOM BEFORE the sandbox handle is
     // dropped (which stops the cgroup scope). Note: `run_acp_sandboxed` is
     // called inside `spawn_blocking`, so synchronous systemctl queries are
-    // acceptable here.
-    let peak_memory_mb = sandbox_handle.memory_peak_mb();
-    let oom_diagnosis = sandbox_handle.oom_diagnosis_with_signal(exit_signal);
+    // acceptable here. A respawned agent ran in a scope of its own, which
+    // holds the process that failed.
+    let respawned_sandbox = connection.respawned_sandbox();
+    let peak_memory_mb = sandbox_handle
+        .memory_peak_mb()
+        .max(respawned_sandbox.and_then(|handle| handle.memory_peak_mb()));
+    let usage = match (
+        sandbox_handle.usage(),
+        respawned_sandbox.and_then(|handle| handle.usage()),
+    ) {
+        (Some(first), Some(respawned)) => Some(first.combined(&respawned)),
+        (first, respawned) => first.or(respawned),
+    };
+    let oom_diagnosis = respawned_sandbox
+        .unwrap_or(&sandbox_handle)
+        .oom_diagnosis_with_signal(exit_signal);
     if let Some(ref hint) = oom_diagnosis {
         tracing::error!(tool = tool_name, "{hint}");
     }
@@ -179,6 +208,12 @@ pub(super) async fn run_acp_sandboxed(
                 ));
                 stderr.push('\n');
             }
+            if let Some(breach) = &disk_breach {
+                append_disk_budget_notice(&mut stderr, breach);
+                if exit_code == 0 {
+                    exit_code = 1;
+                }
+            }
 
             Ok(csa_acp::transport::AcpOutput {
                 output: prompt_result.output,
@@ -189,6 +224,7 @@ pub(super) async fn run_acp_sandboxed(
                 exit_reason: prompt_result.exit_reason,
                 metadata: prompt_result.metadata,
                 peak_memory_mb,
+                usage,
             })
         }
         Err(e) => {
@@ -206,6 +242,15 @@ pub(super) async fn run_acp_sandboxed(
                     signal: Some(9),
                     stderr,
                 })
+            } else if let Some(breach) = &disk_breach {
+                let mut stderr = connection.stderr();
+                append_disk_budget_notice(&mut stderr, breach);
+                stderr.push_str(&format!("original error: {e}\n"));
+                Err(csa_acp::AcpError::ProcessExited {
+                    code: 1,
+                    signal: None,
+                    stderr,
+                })
             } else {
                 Err(e)
             }
@@ -224,8 +269,8 @@ pub(super) async fn run_acp_sandboxed(
 /// and session ID so the caller can capture peak memory regardless of outcome.
 #[allow(clippy::too_many_arguments)]
 async fn run_acp_sandboxed_inner(
-    connection: &csa_acp::AcpConnection,
-    memory_monitor: Option<csa_resource::memory_monitor::MemoryMonitorHandle>,
+    connection: &mut csa_acp::AcpConnection,
+    monitors: &mut AcpMonitors<'_>,
     system_prompt: Option<&str>,
     resume_session_id: Option<&str>,
     meta: Option<serde_json::Map<String, serde_json::Value>>,
@@ -265,21 +310,34 @@ async fn run_acp_sandboxed_inner(
             .await?
     };
 
-    let result = connection
-        .prompt_with_io(
-            &acp_session_id,
-            prompt,
-            idle_timeout,
-            initial_response_timeout,
-            csa_acp::connection::PromptIoOptions {
-                stream_stdout_to_stderr,
-                output_spool,
-                spool_max_bytes: output_spool_max_bytes,
-                keep_rotated_spool: output_spool_keep_rotated,
-                tool_output_compaction,
-            },
-        )
-        .await;
+    // A crashed agent is respawned into a scope of its own, so the monitors
+    // move to the new process before the turn is resent.
+    let mut turn = prompt.to_string();
+    let result = loop {
+        let attempt = connection
+            .prompt_or_respawn(
+                &acp_session_id,
+                &turn,
+                idle_timeout,
+                initial_response_timeout,
+                csa_acp::connection::PromptIoOptions {
+                    stream_stdout_to_stderr,
+                    output_spool,
+                    spool_max_bytes: output_spool_max_bytes,
+                    keep_rotated_spool: output_spool_keep_rotated,
+                    tool_output_compaction: tool_output_compaction.clone(),
+                },
+            )
+            .await;
+        match attempt {
+            Ok(csa_acp::connection::PromptAttempt::Finished(result)) => break Ok(result),
+            Ok(csa_acp::connection::PromptAttempt::Respawned) => {
+                monitors.restart(connection).await;
+                turn = csa_acp::connection::resume_prompt(prompt);
+            }
+            Err(error) => break Err(error),
+        }
+    };
 
     kill_after_fresh_sandboxed_prompt_error(result.is_err(), resume_session_id, || async {
         let _ = connection.kill().await;
@@ -287,13 +345,73 @@ async fn run_acp_sandboxed_inner(
     .await;
 
     // Stop memory monitor before capturing peak memory (done by caller).
-    if let Some(monitor) = memory_monitor {
-        monitor.stop().await;
-    }
+    monitors.stop_memory().await;
 
     result.map(|r| (r, acp_session_id))
 }
 
+/// Memory and disk monitors of the sandboxed agent process.
+struct AcpMonitors<'a> {
+    isolation_plan: &'a IsolationPlan,
+    grace_period: Duration,
+    diagnostic_path: Option<PathBuf>,
+    memory_checkpoint: Option<csa_resource::memory_monitor::PreKillCheckpoint>,
+    memory: Option<csa_resource::memory_monitor::MemoryMonitorHandle>,
+    disk: Option<csa_resource::disk_monitor::DiskMonitorHandle>,
+    /// Breach a replaced disk monitor acted on.
+    disk_breach: Option<csa_resource::disk_monitor::DiskBudgetExceeded>,
+}
+
+impl AcpMonitors<'_> {
+    /// Watch the agent of `connection`, which runs in `sandbox`.
+    fn start(&mut self, sandbox: &csa_acp::AcpSandboxHandle, connection: &csa_acp::AcpConnection) {
+        let Some(pid) = connection.child_pid() else {
+            return;
+        };
+        let memory_scope = match sandbox {
+            csa_acp::AcpSandboxHandle::None => None,
+            handle => handle.scope_name().or(MACOS_MEMORY_MONITOR_LABEL),
+        };
+        self.memory = memory_scope.and_then(|scope| {
+            start_memory_monitor(
+                scope,
+                pid,
+                self.isolation_plan,
+                self.grace_period,
+                self.diagnostic_path.clone(),
+                self.memory_checkpoint.clone(),
+            )
+        });
+        self.disk = start_disk_monitor(pid, self.isolation_plan, self.grace_period);
+    }
+
+    /// Move the monitors to the agent `connection` respawned.
+    async fn restart(&mut self, connection: &csa_acp::AcpConnection) {
+        self.stop_memory().await;
+        if let Some(monitor) = self.disk.take() {
+            self.disk_breach = self.disk_breach.take().or(monitor.stop().await);
+        }
+        let Some(sandbox) = connection.respawned_sandbox() else {
+            return;
+        };
+        self.start(sandbox, connection);
+    }
+
+    async fn stop_memory(&mut self) {
+        if let Some(monitor) = self.memory.take() {
+            monitor.stop().await;
+        }
+    }
+
+    async fn stop_disk(&mut self) -> Option<csa_resource::disk_monitor::DiskBudgetExceeded> {
+        let breach = match self.disk.take() {
+            Some(monitor) => monitor.stop().await,
+            None => None,
+        };
+        self.disk_breach.take().or(breach)
+    }
+}
+
 fn sandboxed_prompt_error_should_kill_session(
     prompt_failed: bool,
     resume_session_id: Option<&str>,
@@ -22,12 +22,43 @@ struct AcpPromptRunRequest {
     output_spool_max_bytes: u64,
     output_spool_keep_rotated: bool,
     tool_output_compaction: Option<csa_acp::ToolOutputCompactionConfig>,
+    permission_policy: Option<csa_core::acp_permissions::AcpPermissionPolicy>,
     acp_payload_debug_path: Option<std::path::PathBuf>,
+    frame_capture: Option<std::path::PathBuf>,
     gemini_classification_env: Option<HashMap<String, String>>,
     gemini_env_allowlist_applied: String,
     memory_max_mb: Option<u64>,
 }
 
+/// Typed cause for an ACP failure the orchestrator can act on: a sandbox that
+/// could not be set up, a provider rate limit, or a tool breaking the
+/// protocol. Crashes and plain spawn errors stay untyped. `message` is kept
+/// verbatim because retry and quota classifiers still read the rendered chain.
+fn typed_acp_failure(
+    tool_name: &str,
+    error: &csa_acp::AcpError,
+    sandbox_spawn_failed: bool,
+    message: &str,
+) -> Option<csa_core::error::AppError> {
+    let (tool, message) = (tool_name.to_string(), message.to_string());
+    if sandbox_spawn_failed {
+        Some(csa_core::error::AppError::SandboxFailure { tool, message })
+    } else if csa_core::gemini::detect_rate_limit_pattern(&message).is_some() {
+        Some(csa_core::error::AppError::RateLimited { tool, message })
+    } else if error.is_protocol_error() {
+        Some(csa_core::error::AppError::ProtocolError { tool, message })
+    } else {
+        None
+    }
+}
+
+fn acp_failure(tool_name: &str, error: &csa_acp::AcpError, message: String) -> anyhow::Error {
+    match typed_acp_failure(tool_name, error, false, &message) {
+        Some(typed) => typed.into(),
+        None => anyhow!(message),
+    }
+}
+
 #[derive(Debug)]
 struct GeminiAcpMcpRetryOutcome<T> {
     value: T,
@@ -74,6 +105,8 @@ impl AcpTransport {
                         request.output_spool_max_bytes,
                         request.output_spool_keep_rotated,
                         request.tool_output_compaction.clone(),
+                        request.permission_policy.clone(),
+                        request.frame_capture.as_deref(),
                     ));
                     match sr {
                         transport_acp_sandbox::AcpSandboxedResult {
@@ -126,17 +159,43 @@ impl AcpTransport {
                                             .tool_output_compaction
                                             .clone(),
                                     },
+                                    permission_policy: request.permission_policy.clone(),
+                                    frame_capture: request.frame_capture.as_deref(),
                                 },
                             ))
-                            .map_err(|e| anyhow!("ACP transport (unsandboxed fallback) failed: {e}"))
+                            .map_err(|e| {
+                                acp_failure(
+                                    &request.tool_name,
+                                    &e,
+                                    format!("ACP transport (unsandboxed fallback) failed: {e}"),
+                                )
+                            })
                         }
                         transport_acp_sandbox::AcpSandboxedResult {
                             result: Err(e),
                             peak_memory_mb,
-                            ..
-                        } => Err(PeakMemoryContext(peak_memory_mb)
-                            .into_anyhow(format!("sandboxed ACP: {e}"))),
+                            sandbox_spawn_failed,
+                        } => {
+                            let peak = anyhow::Error::new(PeakMemoryContext(peak_memory_mb));
+                            let message = format!("sandboxed ACP: {e}");
+                            Err(match typed_acp_failure(
+                                &request.tool_name,
+                                &e,
+                                sandbox_spawn_failed,
+                                &message,
+                            ) {
+                                Some(typed) => peak.context(typed),
+                                None => peak.context(message),
+                            })
+                        }
                     }
+                } else if let Some(result) = transport_acp_pool::is_poolable(&request)
+                    .then(|| rt.block_on(transport_acp_pool::run_pooled(request.clone())))
+                    .flatten()
+                {
+                    result.map_err(|e| {
+                        acp_failure(&request.tool_name, &e, format!("ACP transport failed: {e}"))
+                    })
                 } else {
                     rt.block_on(csa_acp::transport::run_prompt_with_io(
                         &request.acp_command,
@@ -170,9 +229,13 @@ impl AcpTransport {
                                 keep_rotated_spool: request.output_spool_keep_rotated,
                                 tool_output_compaction: request.tool_output_compaction.clone(),
                             },
+                            permission_policy: request.permission_policy.clone(),
+                            frame_capture: request.frame_capture.as_deref(),
                         },
                     ))
-                    .map_err(|e| anyhow!("ACP transport failed: {e}"))
+                    .map_err(|e| {
+                        acp_failure(&request.tool_name, &e, format!("ACP transport failed: {e}"))
+                    })
                 }
             })
             .await
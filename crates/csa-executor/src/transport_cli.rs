@@ -59,6 +59,12 @@ use tokio::process::Command;
 
 use crate::executor::Executor;
 
+#[path = "transport_cli_env.rs"]
+mod transport_cli_env;
+use transport_cli_env::{
+    CLI_TRANSPORT_CSA_OWNED_ENV_VARS, CLI_TRANSPORT_STRIPPED_ENV_VARS, inject_cli_session_env,
+};
+
 use super::{
     ResolvedTimeout, SandboxTransportConfig, Transport, TransportCapabilities, TransportMode,
     TransportOptions, TransportResult,
@@ -416,71 +422,6 @@ fn claude_model_args(executor: &Executor) -> Vec<String> {
     out
 }
 
-/// Env strip list for the CLI transport. Mirrors `Executor::STRIPPED_ENV_VARS`;
-/// startup subtree keys are scrubbed through `csa_core::env` (#1750).
-const CLI_TRANSPORT_STRIPPED_ENV_VARS: &[&str] = &[
-    "CLAUDECODE",
-    "CLAUDE_CODE_ENTRYPOINT",
-    "LEFTHOOK",
-    "LEFTHOOK_SKIP",
-    "CSA_DAEMON_SESSION_DIR",
-    csa_core::env::CSA_GIT_PUSH_ALLOWED_ENV_KEY,
-    csa_core::env::CSA_RUN_GIT_PUSH_AUTHORIZED_ENV_KEY,
-    csa_session::RESULT_TOML_PATH_CONTRACT_ENV,
-];
-
-const CLI_TRANSPORT_CSA_OWNED_ENV_VARS: &[&str] = &[
-    "CSA_SESSION_ID",
-    "CSA_DEPTH",
-    "CSA_PROJECT_ROOT",
-    "CSA_TOOL",
-    "CSA_PARENT_TOOL",
-    "CSA_PARENT_SESSION",
-    "CSA_SESSION_DIR",
-    "CSA_PARENT_SESSION_DIR",
-    "CSA_DAEMON_SESSION_DIR",
-    csa_session::RESULT_TOML_PATH_CONTRACT_ENV,
-];
-fn inject_cli_session_env(cmd: &mut Command, session: &MetaSessionState) {
-    cmd.env("CSA_SESSION_ID", &session.meta_session_id);
-    cmd.env("CSA_DEPTH", (session.genealogy.depth + 1).to_string());
-    cmd.env("CSA_PROJECT_ROOT", &session.project_path);
-    cmd.env(csa_core::env::CSA_INTERNAL_INVOCATION_ENV_KEY, "1");
-    cmd.env("CSA_TOOL", "claude-code");
-    if let Ok(current_tool) = std::env::var("CSA_TOOL") {
-        cmd.env("CSA_PARENT_TOOL", current_tool);
-    }
-    if let Some(parent_session_id) = session.genealogy.parent_session_id.as_deref() {
-        cmd.env("CSA_PARENT_SESSION", parent_session_id);
-    }
-    if let Ok(session_dir) = csa_session::manager::get_session_dir(
-        Path::new(&session.project_path),
-        &session.meta_session_id,
-    ) {
-        cmd.env(
-            "CSA_SESSION_DIR",
-            session_dir.to_string_lossy().into_owned(),
-        );
-        cmd.env(
-            csa_session::RESULT_TOML_PATH_CONTRACT_ENV,
-            csa_session::next_turn_contract_result_path(&session_dir, session.turn_count)
-                .to_string_lossy()
-                .into_owned(),
-        );
-    }
-    if let Some(parent_session_id) = session.genealogy.parent_session_id.as_deref()
-        && let Ok(parent_dir) = csa_session::manager::get_session_dir(
-            Path::new(&session.project_path),
-            parent_session_id,
-        )
-    {
-        cmd.env(
-            "CSA_PARENT_SESSION_DIR",
-            parent_dir.to_string_lossy().into_owned(),
-        );
-    }
-}
-
 /// Result of parsing a `claude --output-format stream-json` byte stream.
 #[derive(Debug, Default)]
 struct StreamParseResult {
@@ -18,7 +18,7 @@ use crate::{LegacyTransport, TransportFactory};
 use csa_resource::filesystem_sandbox::FilesystemCapability;
 use csa_resource::isolation_plan::IsolationPlan;
 use csa_resource::sandbox::ResourceCapability;
-use csa_session::state::{ContextStatus, Genealogy, MetaSessionState, SessionPhase, TaskContext};
+use csa_session::state::MetaSessionState;
 use std::collections::HashMap as StdHashMap;
 
 fn make_executor() -> Executor {
@@ -48,6 +48,10 @@ fn make_test_isolation_plan() -> IsolationPlan {
         memory_max_mb: Some(2048),
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -62,29 +66,10 @@ fn make_test_session() -> MetaSessionState {
         meta_session_id: "01HCLITEST000000000000000".to_string(),
         description: Some("cli transport test".to_string()),
         project_path: "/tmp/test-project".to_string(),
-        branch: None,
         created_at: now,
         last_accessed: now,
-        csa_version: None,
-        genealogy: Genealogy::default(),
-        tools: StdHashMap::new(),
-        context_status: ContextStatus::default(),
-        total_token_usage: None,
-        phase: SessionPhase::Active,
-        task_context: TaskContext::default(),
-        turn_count: 0,
-        token_budget: None,
-        sandbox_info: None,
-        termination_reason: None,
-        is_seed_candidate: false,
-        git_head_at_creation: None,
-        pre_session_porcelain: None,
-        last_return_packet: None,
-        change_id: None,
-        spec_id: None,
-        fork_call_timestamps: Vec::new(),
-        vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
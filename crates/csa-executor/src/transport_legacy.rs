@@ -227,6 +227,10 @@ impl LegacyTransport {
         if let Some(path) = diagnostic_path.as_deref() {
             csa_resource::memory_monitor::clear_soft_limit_diagnostic(path);
         }
+        let memory_checkpoint = transport_memory_checkpoint::memory_checkpoint(
+            Path::new(&session.project_path),
+            diagnostic_session_id,
+        );
 
         let spawn_options = SpawnOptions {
             stdin_write_timeout: std::time::Duration::from_secs(
@@ -288,19 +292,33 @@ impl LegacyTransport {
         let child_pid = child.id();
 
         // Start memory monitor for legacy transport (mirrors ACP path).
-        let memory_monitor = if let csa_process::SandboxHandle::Cgroup(ref guard) = sandbox_handle {
+        let memory_scope = match sandbox_handle {
+            csa_process::SandboxHandle::Cgroup(ref guard) => Some(guard.scope_name()),
+            csa_process::SandboxHandle::CgroupDirect(ref guard) => Some(guard.scope_name()),
+            csa_process::SandboxHandle::None => None,
+            _ => transport_meta::MACOS_MEMORY_MONITOR_LABEL,
+        };
+        let memory_monitor = if let Some(scope_name) = memory_scope {
             isolation_plan.and_then(|plan| {
                 transport_meta::start_memory_monitor(
-                    guard.scope_name(),
+                    scope_name,
                     child.id().unwrap_or(0),
                     plan,
                     std::time::Duration::from_secs(options.termination_grace_period_seconds),
                     diagnostic_path.clone(),
+                    memory_checkpoint.clone(),
                 )
             })
         } else {
             None
         };
+        let disk_monitor = isolation_plan.and_then(|plan| {
+            transport_meta::start_disk_monitor(
+                child.id().unwrap_or(0),
+                plan,
+                std::time::Duration::from_secs(options.termination_grace_period_seconds),
+            )
+        });
 
         let mut execution = wait_and_capture_with_idle_timeout(
             child,
@@ -318,10 +336,31 @@ impl LegacyTransport {
         if let Some(monitor) = memory_monitor {
             monitor.stop().await;
         }
+        if let Some(monitor) = disk_monitor
+            && let Some(breach) = monitor.stop().await
+        {
+            tracing::error!(tool = tool_name, "{breach}");
+            transport_meta::append_disk_budget_notice(&mut execution.stderr_output, &breach);
+            execution.summary = breach.to_string();
+            if execution.exit_code == 0 {
+                execution.exit_code = 1;
+            }
+        }
 
-        // Read peak memory from cgroup before sandbox_handle is dropped.
-        if let csa_process::SandboxHandle::Cgroup(ref guard) = sandbox_handle {
-            execution.peak_memory_mb = guard.memory_peak_mb();
+        // Read peak memory and usage from cgroup before sandbox_handle is dropped.
+        let cgroup_peak_mb = match sandbox_handle {
+            csa_process::SandboxHandle::Cgroup(ref guard) => {
+                execution.child_usage = guard.usage();
+                Some(guard.memory_peak_mb())
+            }
+            csa_process::SandboxHandle::CgroupDirect(ref guard) => {
+                execution.child_usage = guard.usage();
+                Some(guard.memory_peak_mb())
+            }
+            _ => None,
+        };
+        if let Some(peak_memory_mb) = cgroup_peak_mb {
+            execution.peak_memory_mb = peak_memory_mb;
             if let Some(peak) = execution.peak_memory_mb {
                 tracing::info!(
                     tool = tool_name,
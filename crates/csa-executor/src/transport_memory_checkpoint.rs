@@ -0,0 +1,112 @@
+//! Pre-kill checkpoint handed to the memory monitor.
+//!
+//! Before the monitor terminates a tool at its soft limit, the sections the
+//! tool has written to `output.log` so far are persisted and a partial return
+//! packet describing the out-of-memory stop is saved, so the session ends as
+//! "out-of-memory (partial results saved)" rather than an opaque signal kill.
+
+use std::path::Path;
+
+use csa_resource::memory_monitor::{
+    MemorySoftLimitKillDiagnostic, OOM_PARTIAL_RESULTS_LABEL, PreKillCheckpoint,
+};
+use csa_session::{ReturnPacket, ReturnStatus};
+use tracing::warn;
+
+/// Build the checkpoint for `session_id`, clearing any partial packet left by
+/// an earlier run of the same session.
+pub(super) fn memory_checkpoint(
+    project_root: &Path,
+    session_id: &str,
+) -> Option<PreKillCheckpoint> {
+    if session_id.trim().is_empty() {
+        return None;
+    }
+    let session_dir = csa_session::manager::get_session_dir(project_root, session_id).ok()?;
+    if let Err(err) = csa_session::clear_partial_return_packet(&session_dir) {
+        warn!(error = %err, "Failed to clear stale partial return packet");
+    }
+    Some(PreKillCheckpoint::new(move |event| {
+        save_partial_results(&session_dir, event)
+    }))
+}
+
+fn save_partial_results(session_dir: &Path, event: &MemorySoftLimitKillDiagnostic) -> bool {
+    let output_log = session_dir.join("output.log");
+    if output_log.is_file()
+        && let Err(err) = csa_session::persist_structured_output_from_file(session_dir, &output_log)
+    {
+        warn!(error = %err, "Failed to persist output sections before memory soft-limit stop");
+    }
+    let saved = csa_session::write_partial_return_packet(session_dir, &oom_return_packet(event))
+        .and_then(|()| csa_session::merge_partial_return_packet(session_dir));
+    match saved {
+        Ok(_) => true,
+        Err(err) => {
+            warn!(error = %err, "Failed to save partial return packet before memory soft-limit stop");
+            false
+        }
+    }
+}
+
+fn oom_return_packet(event: &MemorySoftLimitKillDiagnostic) -> ReturnPacket {
+    ReturnPacket {
+        status: ReturnStatus::Failure,
+        exit_code: 128 + event.signal,
+        summary: format!(
+            "{OOM_PARTIAL_RESULTS_LABEL}: memory reached {} MB of the {} MB limit",
+            event.current_mb, event.memory_max_mb
+        ),
+        next_actions: vec![
+            "Continue from the saved output sections instead of restarting the task.".to_string(),
+            "Raise resources.memory_max_mb or tools.<tool>.memory_max_mb, or split the task."
+                .to_string(),
+        ],
+        error_context: Some(format!(
+            "CSA memory monitor stopped the tool at {}% of memory_max ({} MB threshold) in {}",
+            event.soft_limit_percent, event.threshold_mb, event.scope_name
+        )),
+        ..ReturnPacket::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_partial_results_persists_sections_and_packet() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("output.log"),
+            "<!-- CSA:SECTION:summary -->\nhalfway\n<!-- CSA:SECTION:summary:END -->\n",
+        )
+        .unwrap();
+        let event = MemorySoftLimitKillDiagnostic {
+            kill_hint: csa_resource::memory_monitor::MEMORY_SOFT_LIMIT_KILL_HINT.to_string(),
+            signal: 15,
+            current_mb: 7200,
+            threshold_mb: 7000,
+            memory_max_mb: 8192,
+            soft_limit_percent: 85,
+            scope_name: "csa-codex-01KTEST.scope".to_string(),
+            partial_results_saved: false,
+        };
+
+        assert!(save_partial_results(tmp.path(), &event));
+
+        assert_eq!(
+            csa_session::read_section(tmp.path(), "summary")
+                .unwrap()
+                .as_deref(),
+            Some("halfway")
+        );
+        let packet = csa_session::read_section(tmp.path(), csa_session::RETURN_PACKET_SECTION_ID)
+            .unwrap()
+            .expect("partial return packet section");
+        let packet = csa_session::parse_return_packet(&packet).unwrap();
+        assert_eq!(packet.status, ReturnStatus::Failure);
+        assert_eq!(packet.exit_code, 143);
+        assert!(packet.summary.starts_with(OOM_PARTIAL_RESULTS_LABEL));
+    }
+}
@@ -30,6 +30,8 @@ const CSA_SESSION_ID_ENV: &str = "CSA_SESSION_ID";
 #[cfg(feature = "acp")]
 const CSA_DEPTH_ENV: &str = "CSA_DEPTH";
 #[cfg(feature = "acp")]
+const CSA_LINEAGE_ENV: &str = csa_core::env::CSA_LINEAGE_ENV_KEY;
+#[cfg(feature = "acp")]
 const CSA_PROJECT_ROOT_ENV: &str = "CSA_PROJECT_ROOT";
 #[cfg(feature = "acp")]
 const CSA_INTERNAL_INVOCATION_ENV: &str = csa_core::env::CSA_INTERNAL_INVOCATION_ENV_KEY;
@@ -49,6 +51,7 @@ const CSA_FS_SANDBOXED_ENV: &str = "CSA_FS_SANDBOXED";
 const CSA_OWNED_ENV_KEYS: &[&str] = &[
     CSA_SESSION_ID_ENV,
     CSA_DEPTH_ENV,
+    CSA_LINEAGE_ENV,
     CSA_PROJECT_ROOT_ENV,
     CSA_INTERNAL_INVOCATION_ENV,
     CSA_TOOL_ENV,
@@ -209,6 +212,9 @@ impl AcpTransport {
             CSA_DEPTH_ENV.to_string(),
             (session.genealogy.depth + 1).to_string(),
         );
+        if let Some(token) = csa_session::child_lineage_token(session) {
+            env.insert(CSA_LINEAGE_ENV.to_string(), token);
+        }
         env.insert(
             CSA_PROJECT_ROOT_ENV.to_string(),
             session.project_path.clone(),
@@ -334,16 +340,26 @@ impl PeakMemoryContext {
     }
 }
 
+/// Scope label for the memory monitor on macOS, where there are no cgroup
+/// scopes and usage is sampled from the child's process group instead.
+pub(super) const MACOS_MEMORY_MONITOR_LABEL: Option<&str> = if cfg!(target_os = "macos") {
+    Some("process-group")
+} else {
+    None
+};
+
 /// Start a memory monitor given cgroup scope details and isolation plan parameters.
 ///
-/// Shared by both ACP and legacy transport paths.  Returns `None` when
-/// monitoring is not applicable (no cgroup, zero max, etc.).
+/// Shared by both ACP and legacy transport paths.  `scope_name` is a systemd
+/// scope or, for directly managed cgroups, the cgroup directory.  Returns
+/// `None` when monitoring is not applicable (no cgroup, zero max, etc.).
 pub(super) fn start_memory_monitor(
     scope_name: &str,
     pid: u32,
     isolation_plan: &IsolationPlan,
     grace_period: std::time::Duration,
     diagnostic_path: Option<PathBuf>,
+    checkpoint: Option<csa_resource::memory_monitor::PreKillCheckpoint>,
 ) -> Option<csa_resource::memory_monitor::MemoryMonitorHandle> {
     if let Some(path) = diagnostic_path.as_deref() {
         csa_resource::memory_monitor::clear_soft_limit_diagnostic(path);
@@ -365,9 +381,48 @@ pub(super) fn start_memory_monitor(
         interval: std::time::Duration::from_secs(interval_secs),
         grace_period,
         diagnostic_path,
+        checkpoint,
     })
 }
 
+/// Disk usage is walked rather than read from a counter, so poll less often
+/// than the memory monitor.
+const DISK_MONITOR_INTERVAL_SECS: u64 = 15;
+
+/// Start the disk budget monitor when the isolation plan carries a budget.
+///
+/// Shared by both ACP and legacy transport paths.  Unlike the memory monitor
+/// this does not need a cgroup scope: usage is measured on the host paths.
+pub(super) fn start_disk_monitor(
+    pid: u32,
+    isolation_plan: &IsolationPlan,
+    grace_period: std::time::Duration,
+) -> Option<csa_resource::disk_monitor::DiskMonitorHandle> {
+    let max_mb = isolation_plan.disk_max_mb.unwrap_or(0);
+    if pid == 0 || max_mb == 0 {
+        return None;
+    }
+    csa_resource::disk_monitor::start(csa_resource::disk_monitor::DiskMonitorConfig {
+        paths: isolation_plan.disk_budget_paths(),
+        pgid: pid as i32,
+        disk_max_bytes: max_mb * 1024 * 1024,
+        interval: std::time::Duration::from_secs(DISK_MONITOR_INTERVAL_SECS),
+        grace_period,
+    })
+}
+
+/// Append a disk budget breach to `stderr` so it reaches the session result.
+pub(super) fn append_disk_budget_notice(
+    stderr: &mut String,
+    breach: &csa_resource::disk_monitor::DiskBudgetExceeded,
+) {
+    if !stderr.is_empty() && !stderr.ends_with('\n') {
+        stderr.push('\n');
+    }
+    stderr.push_str(&breach.to_string());
+    stderr.push('\n');
+}
+
 pub(super) fn memory_soft_limit_diagnostic_path(
     project_root: &Path,
     session_id: &str,
@@ -446,12 +501,15 @@ mod tests {
             token_budget: None,
             sandbox_info: None,
             termination_reason: None,
+            last_model: None,
             is_seed_candidate: false,
             git_head_at_creation: None,
             pre_session_porcelain: None,
             last_return_packet: None,
             change_id: None,
             spec_id: None,
+            pressure_events: Vec::new(),
+            user: None,
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
@@ -433,6 +433,10 @@ fn test_ensure_gemini_runtime_home_writable_path_adds_runtime_home_under_tmp() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -465,6 +469,10 @@ fn test_ensure_gemini_runtime_home_writable_path_skips_when_parent_is_bound() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -500,6 +508,10 @@ fn test_ensure_gemini_runtime_home_writable_path_is_noop_without_runtime_home()
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -569,6 +581,10 @@ fn test_apply_gemini_sandbox_runtime_env_overrides_pins_runtime_paths_and_clears
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -708,6 +724,10 @@ fn test_apply_gemini_sandbox_runtime_env_overrides_preserves_phase2_api_key() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -750,6 +770,10 @@ fn test_apply_gemini_sandbox_runtime_env_overrides_scrubs_startup_subtree_keys()
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
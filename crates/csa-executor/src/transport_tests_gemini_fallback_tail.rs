@@ -27,6 +27,10 @@ async fn test_execute_best_effort_sandbox_fallback_preserves_attempt_model_overr
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
@@ -128,6 +128,10 @@ async fn test_execute_fails_fast_when_shared_npm_cache_bind_cannot_be_added() {
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
@@ -236,6 +240,10 @@ async fn test_legacy_execute_fails_fast_when_shared_npm_cache_bind_cannot_be_add
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
@@ -344,6 +352,10 @@ async fn test_execute_fails_fast_when_shared_npm_cache_path_violates_writable_al
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
@@ -459,6 +471,10 @@ async fn test_legacy_execute_fails_fast_when_shared_npm_cache_path_violates_writ
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
@@ -582,6 +598,10 @@ async fn test_execute_fails_fast_when_symlinked_shared_npm_cache_resolves_outsid
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
@@ -40,6 +40,10 @@ async fn test_legacy_execute_fails_fast_when_symlinked_shared_npm_cache_resolves
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: csa_resource::NetworkMode::Full,
             readonly_project_root: false,
             user_daemon_ipc: false,
             project_root: None,
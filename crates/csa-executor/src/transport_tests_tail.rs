@@ -270,9 +270,9 @@ fn test_acp_build_env_propagates_extra_env() {
         last_return_packet: None,
         change_id: None,
         spec_id: None,
-        fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let mut extra = HashMap::new();
@@ -324,9 +324,9 @@ fn test_acp_build_env_includes_csa_session_dir() {
         last_return_packet: None,
         change_id: None,
         spec_id: None,
-        fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let env = transport.build_env(&session, None, None, false);
@@ -386,9 +386,9 @@ fn test_acp_build_env_reserved_session_paths_override_extra_env() {
         last_return_packet: None,
         change_id: None,
         spec_id: None,
-        fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let mut extra = HashMap::new();
@@ -540,9 +540,9 @@ fn build_test_meta_session(project_path: &str) -> MetaSessionState {
         last_return_packet: None,
         change_id: None,
         spec_id: None,
-        fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
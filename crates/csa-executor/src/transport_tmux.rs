@@ -492,6 +492,9 @@ impl TmuxTransport {
                     "CSA_DEPTH".into(),
                     (session.genealogy.depth + 1).to_string(),
                 );
+                if let Some(token) = csa_session::child_lineage_token(session) {
+                    env.insert(csa_core::env::CSA_LINEAGE_ENV_KEY.into(), token);
+                }
                 env.insert("CSA_PROJECT_ROOT".into(), session.project_path.clone());
                 env.insert(
                     csa_core::env::CSA_INTERNAL_INVOCATION_ENV_KEY.into(),
@@ -52,7 +52,8 @@ fn emit_merge_completed_event_inner(
     let state_dir =
         csa_config::paths::state_dir().context("cannot determine CSA state directory")?;
     let events_dir = state_dir.join(EVENTS_DIR_NAME);
-    fs::create_dir_all(&events_dir)
+    // Merge records span every user in shared-state mode.
+    csa_config::shared_state::create_dir_all(&events_dir)
         .with_context(|| format!("failed to create events dir: {}", events_dir.display()))?;
 
     let event = MergeAuditEvent {
@@ -69,6 +70,8 @@ fn emit_merge_completed_event_inner(
         .append(true)
         .open(&log_path)
         .with_context(|| format!("failed to open audit log: {}", log_path.display()))?;
+    csa_config::shared_state::share_file(&file)
+        .with_context(|| format!("failed to share audit log: {}", log_path.display()))?;
 
     let mut line = serde_json::to_string(&event).context("failed to serialize audit event")?;
     line.push('\n');
@@ -14,6 +14,9 @@
 ///   directive for passing cumulative review → `pr-bot` chaining
 /// - `MergeCompleted` — fired from `gh` wrapper when merge_guard allows merge;
 ///   persisted to JSONL audit log for traceability
+/// - `AuditAlert` — fired by `csa audit watch` for out-of-band edits to tracked files
+/// - `SkillInstalled` / `SkillUpgraded` / `SkillRemoved` — fired by `weave install`,
+///   `weave update`/`weave upgrade` and `weave remove` after `weave.lock` is saved
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HookEvent {
     /// Before the first user message is sent to the resolved transport.
@@ -55,6 +58,24 @@ pub enum HookEvent {
     /// Observational audit event persisted to JSONL for traceability.
     /// Template vars: `{pr_number}`, `{head_sha}`, `{marker_path}`.
     MergeCompleted,
+    /// When `csa audit watch` sees a manifest-tracked file change while no
+    /// csa session is running in the project.
+    /// Observational: alert-only, never blocks.
+    /// Template vars: `{path}`, `{change}`, `{expected_hash}`, `{actual_hash}`,
+    /// `{project_root}`.
+    AuditAlert,
+    /// After `weave install` locks a package.
+    /// Observational: audit or announce capability changes on this machine.
+    /// Template vars: `{name}`, `{version}`, `{commit}`, `{repo}`, `{source}`,
+    /// `{project_root}`.
+    SkillInstalled,
+    /// After `weave update` or `weave upgrade` moves a package to a new commit.
+    /// Observational. Template vars: those of `SkillInstalled`, plus
+    /// `{old_version}` and `{old_commit}`.
+    SkillUpgraded,
+    /// After `weave remove` drops a package from `weave.lock`.
+    /// Observational. Template vars: those of `SkillInstalled`.
+    SkillRemoved,
 }
 
 impl HookEvent {
@@ -77,6 +98,10 @@ impl HookEvent {
             HookEvent::PostEdit => "post_edit",
             HookEvent::PostReview => "post_review",
             HookEvent::MergeCompleted => "merge_completed",
+            HookEvent::AuditAlert => "audit_alert",
+            HookEvent::SkillInstalled => "skill_installed",
+            HookEvent::SkillUpgraded => "skill_upgraded",
+            HookEvent::SkillRemoved => "skill_removed",
         }
     }
 
@@ -141,7 +166,11 @@ impl HookEvent {
             | HookEvent::PreSession
             | HookEvent::PreRun
             | HookEvent::PostRun
-            | HookEvent::MergeCompleted => None,
+            | HookEvent::MergeCompleted
+            | HookEvent::AuditAlert
+            | HookEvent::SkillInstalled
+            | HookEvent::SkillUpgraded
+            | HookEvent::SkillRemoved => None,
         }
     }
 }
@@ -164,6 +193,10 @@ mod tests {
         assert_eq!(HookEvent::PostEdit.as_config_key(), "post_edit");
         assert_eq!(HookEvent::PostReview.as_config_key(), "post_review");
         assert_eq!(HookEvent::MergeCompleted.as_config_key(), "merge_completed");
+        assert_eq!(HookEvent::AuditAlert.as_config_key(), "audit_alert");
+        assert_eq!(HookEvent::SkillInstalled.as_config_key(), "skill_installed");
+        assert_eq!(HookEvent::SkillUpgraded.as_config_key(), "skill_upgraded");
+        assert_eq!(HookEvent::SkillRemoved.as_config_key(), "skill_removed");
     }
 
     #[test]
@@ -180,6 +213,10 @@ mod tests {
         assert!(HookEvent::PreRun.builtin_command().is_none());
         assert!(HookEvent::PostRun.builtin_command().is_none());
         assert!(HookEvent::MergeCompleted.builtin_command().is_none());
+        assert!(HookEvent::AuditAlert.builtin_command().is_none());
+        assert!(HookEvent::SkillInstalled.builtin_command().is_none());
+        assert!(HookEvent::SkillUpgraded.builtin_command().is_none());
+        assert!(HookEvent::SkillRemoved.builtin_command().is_none());
     }
 
     #[test]
@@ -207,6 +244,10 @@ mod tests {
             HookEvent::PostEdit,
             HookEvent::PostReview,
             HookEvent::MergeCompleted,
+            HookEvent::AuditAlert,
+            HookEvent::SkillInstalled,
+            HookEvent::SkillUpgraded,
+            HookEvent::SkillRemoved,
         ];
 
         let mut seen_keys = std::collections::HashSet::new();
@@ -218,8 +259,8 @@ mod tests {
                 "Duplicate config key: {key} (from {event:?})"
             );
         }
-        // Ensure we covered all 8 variants
-        assert_eq!(seen_keys.len(), 8, "Expected 8 unique config keys");
+        // Ensure we covered all 12 variants
+        assert_eq!(seen_keys.len(), 12, "Expected 12 unique config keys");
     }
 
     #[test]
@@ -262,6 +303,17 @@ mod tests {
         assert!(!HookEvent::MergeCompleted.is_gatekeeping());
     }
 
+    #[test]
+    fn test_skill_lifecycle_events_are_observational() {
+        for event in [
+            HookEvent::SkillInstalled,
+            HookEvent::SkillUpgraded,
+            HookEvent::SkillRemoved,
+        ] {
+            assert!(!event.is_gatekeeping());
+        }
+    }
+
     /// Verify config keys match the expected snake_case convention.
     #[test]
     fn test_config_keys_are_snake_case() {
@@ -274,6 +326,10 @@ mod tests {
             HookEvent::PostEdit,
             HookEvent::PostReview,
             HookEvent::MergeCompleted,
+            HookEvent::AuditAlert,
+            HookEvent::SkillInstalled,
+            HookEvent::SkillUpgraded,
+            HookEvent::SkillRemoved,
         ];
 
         for event in &all_events {
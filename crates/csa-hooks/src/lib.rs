@@ -9,6 +9,9 @@
 //! - `PostRun`: After a tool execution finishes
 //! - `PostEdit`: After PostRun when `.rs` files changed (observational clippy check)
 //! - `MergeCompleted`: After merge_guard allows a merge to proceed (audit event)
+//! - `AuditAlert`: When `csa audit watch` sees an out-of-band edit to a tracked file
+//! - `SkillInstalled` / `SkillUpgraded` / `SkillRemoved`: When `weave` installs,
+//!   moves or removes a package
 //!
 //! ## Configuration Priority
 //!
@@ -58,6 +61,7 @@ mod git_guard_tests;
 pub mod guard;
 pub mod mempal_capture;
 pub mod merge_guard;
+pub mod notify;
 pub mod policy;
 pub mod pre_session;
 pub mod runner;
@@ -82,6 +86,10 @@ pub use merge_guard::{
     MarkerStatus, default_install_dir, detect_installed_guard, ensure_guard_dir, gh_wrapper_script,
     inject_merge_guard_env, install_merge_guard, is_merge_guard_enabled, verify_pr_bot_marker,
 };
+pub use notify::{
+    NotifyHookConfig, NotifyOn, SessionNotification, WebhookFormat, WebhookTarget,
+    dispatch_session_notification, load_global_notify_hook_config, parse_notify_hook_config,
+};
 pub use policy::FailPolicy;
 pub use pre_session::{
     PreSessionHookConfig, PreSessionHookContext, PreSessionHookInvocation,
@@ -251,7 +251,7 @@ if [ -f "${MARKER_FILE}" ]; then
   "${REAL_GH}" "$@" || MERGE_EXIT=$?
   if [ "${MERGE_EXIT}" -eq 0 ]; then
     # Audit event: emitted AFTER successful merge, BEFORE sync.
-    EVENTS_DIR="${XDG_STATE_HOME:-$HOME/.local/state}/cli-sub-agent/events"
+    EVENTS_DIR="${CSA_STATE_DIR:-${XDG_STATE_HOME:-$HOME/.local/state}/cli-sub-agent}/events"
     mkdir -p "${EVENTS_DIR}" 2>/dev/null || true
     AUDIT_TS="$(date -u +%Y-%m-%dT%H:%M:%SZ 2>/dev/null || echo "unknown")"
     printf '{"event":"MergeCompleted","pr_number":%s,"head_sha":"%s","marker_path":"%s","timestamp":"%s"}\n' \
@@ -0,0 +1,485 @@
+//! Built-in completion notification targets (desktop and webhooks).
+//!
+//! Configured only in the global `~/.config/cli-sub-agent/config.toml` as
+//! `[hooks.notify]`. Notifications are best-effort: a failing target is
+//! logged at WARN and never affects the session outcome.
+
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_NOTIFY_TIMEOUT_SECONDS: u64 = 10;
+
+fn default_true() -> bool {
+    true
+}
+
+const fn default_timeout_seconds() -> u64 {
+    DEFAULT_NOTIFY_TIMEOUT_SECONDS
+}
+
+fn default_on() -> Vec<NotifyOn> {
+    vec![NotifyOn::Success, NotifyOn::Failure]
+}
+
+/// Session outcomes that trigger a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyOn {
+    Success,
+    Failure,
+}
+
+/// Payload shape for a webhook target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The raw [`SessionNotification`] as JSON.
+    #[default]
+    Json,
+    /// Slack incoming webhook (`{"text": ...}`).
+    Slack,
+    /// Discord webhook (`{"content": ...}`).
+    Discord,
+}
+
+/// One `[[hooks.notify.webhooks]]` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookTarget {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+/// Global-only `[hooks.notify]` configuration from
+/// `~/.config/cli-sub-agent/config.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotifyHookConfig {
+    /// Whether notifications are enabled when configured.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Outcomes to notify about (default: both).
+    #[serde(default = "default_on")]
+    pub on: Vec<NotifyOn>,
+    /// Show a desktop notification (`notify-send` on Linux, `osascript` on macOS).
+    #[serde(default = "default_true")]
+    pub desktop: bool,
+    /// Webhooks to POST to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookTarget>,
+    /// Skip sessions shorter than this many seconds.
+    #[serde(default)]
+    pub min_duration_seconds: u64,
+    /// Per-target timeout in seconds.
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for NotifyHookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            on: default_on(),
+            desktop: true,
+            webhooks: Vec::new(),
+            min_duration_seconds: 0,
+            timeout_seconds: default_timeout_seconds(),
+        }
+    }
+}
+
+/// What a completion notification reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionNotification {
+    pub session_id: String,
+    pub tool: String,
+    pub project_root: String,
+    pub succeeded: bool,
+    /// Session status (`success`, `failure`, `timeout`, ...) or review verdict.
+    pub verdict: String,
+    pub exit_code: i32,
+    pub duration_secs: u64,
+}
+
+impl SessionNotification {
+    fn outcome(&self) -> NotifyOn {
+        if self.succeeded {
+            NotifyOn::Success
+        } else {
+            NotifyOn::Failure
+        }
+    }
+
+    fn title(&self) -> String {
+        let short_id = self.session_id.get(..11).unwrap_or(&self.session_id);
+        let outcome = if self.succeeded { "finished" } else { "failed" };
+        format!("csa {short_id} {outcome}")
+    }
+
+    fn body(&self) -> String {
+        let project = Path::new(&self.project_root)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.project_root.clone());
+        format!(
+            "{} · {} (exit {}) · {} · {}",
+            self.tool,
+            self.verdict,
+            self.exit_code,
+            format_duration(self.duration_secs),
+            project
+        )
+    }
+}
+
+impl NotifyHookConfig {
+    /// Return true when `notification` passes the outcome and duration filters.
+    pub fn should_notify(&self, notification: &SessionNotification) -> bool {
+        self.enabled
+            && (self.desktop || !self.webhooks.is_empty())
+            && self.on.contains(&notification.outcome())
+            && notification.duration_secs >= self.min_duration_seconds
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlobalHooksEnvelope {
+    #[serde(default)]
+    hooks: Option<GlobalHooksTable>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlobalHooksTable {
+    #[serde(default)]
+    notify: Option<NotifyHookConfig>,
+}
+
+/// Parse `[hooks.notify]` from a TOML string.
+pub fn parse_notify_hook_config(
+    content: &str,
+) -> Result<Option<NotifyHookConfig>, toml::de::Error> {
+    let envelope: GlobalHooksEnvelope = toml::from_str(content)?;
+    Ok(envelope.hooks.and_then(|hooks| hooks.notify))
+}
+
+/// Load `[hooks.notify]` from the global CSA config.
+pub fn load_global_notify_hook_config() -> Option<NotifyHookConfig> {
+    let path = crate::pre_session::global_pre_session_config_path()?;
+    let content = std::fs::read_to_string(&path).ok()?;
+    match parse_notify_hook_config(&content) {
+        Ok(config) => config,
+        Err(error) => {
+            tracing::warn!(
+                path = %path.display(),
+                error = %error,
+                "Failed to parse notify hook config"
+            );
+            None
+        }
+    }
+}
+
+/// Send `notification` to every configured target, logging failures.
+pub fn dispatch_session_notification(
+    config: &NotifyHookConfig,
+    notification: &SessionNotification,
+) {
+    if !config.should_notify(notification) {
+        return;
+    }
+    let timeout = Duration::from_secs(config.timeout_seconds);
+    if config.desktop {
+        match desktop_command(notification) {
+            Some(command) => {
+                if let Err(error) = run_target(command, None, timeout) {
+                    tracing::warn!(error = %error, "Desktop notification failed");
+                }
+            }
+            None => tracing::debug!("No desktop notifier available (notify-send/osascript)"),
+        }
+    }
+    for webhook in &config.webhooks {
+        if let Err(error) = post_webhook(webhook, notification, timeout) {
+            tracing::warn!(error = %error, "Webhook notification failed");
+        }
+    }
+}
+
+fn desktop_command(notification: &SessionNotification) -> Option<Command> {
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(&notification.body()),
+            applescript_string(&notification.title())
+        );
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(script);
+        return Some(command);
+    }
+    let program = which::which("notify-send").ok()?;
+    let mut command = Command::new(program);
+    command
+        .arg("--app-name=csa")
+        .arg(if notification.succeeded {
+            "--urgency=normal"
+        } else {
+            "--urgency=critical"
+        })
+        .arg(notification.title())
+        .arg(notification.body());
+    Some(command)
+}
+
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn webhook_payload(format: WebhookFormat, notification: &SessionNotification) -> serde_json::Value {
+    let message = format!("{}\n{}", notification.title(), notification.body());
+    match format {
+        WebhookFormat::Json => serde_json::to_value(notification).unwrap_or_default(),
+        WebhookFormat::Slack => serde_json::json!({ "text": message }),
+        WebhookFormat::Discord => serde_json::json!({ "content": message }),
+    }
+}
+
+fn post_webhook(
+    webhook: &WebhookTarget,
+    notification: &SessionNotification,
+    timeout: Duration,
+) -> Result<()> {
+    let curl = which::which("curl").context("curl is required for webhook notifications")?;
+    let payload = serde_json::to_string(&webhook_payload(webhook.format, notification))?;
+    let config = curl_config(&webhook.url, &payload);
+    run_target(
+        webhook_command(curl, timeout),
+        Some(config.as_bytes()),
+        timeout,
+    )
+}
+
+/// curl reading its URL and body from `--config -`; webhook URLs embed
+/// secrets, so they must not show up in the process list.
+fn webhook_command(curl: impl AsRef<std::ffi::OsStr>, timeout: Duration) -> Command {
+    let mut command = Command::new(curl);
+    command
+        .arg("--silent")
+        .arg("--show-error")
+        .arg("--fail")
+        .arg("--max-time")
+        .arg(timeout.as_secs().max(1).to_string())
+        .arg("--header")
+        .arg("Content-Type: application/json")
+        .arg("--config")
+        .arg("-");
+    command
+}
+
+fn curl_config(url: &str, payload: &str) -> String {
+    let quote = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        "url = \"{}\"\ndata-binary = \"{}\"\n",
+        quote(url),
+        quote(payload)
+    )
+}
+
+fn run_target(mut command: Command, stdin: Option<&[u8]>, timeout: Duration) -> Result<()> {
+    command
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .spawn()
+        .with_context(|| format!("failed to spawn {program}"))?;
+    if let (Some(payload), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        match pipe.write_all(payload) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+            Err(err) => return Err(err).context("failed to write notification payload"),
+        }
+    }
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait()? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => bail!("{program} exited with code {}", status.code().unwrap_or(-1)),
+            None if start.elapsed() >= timeout => {
+                #[cfg(unix)]
+                {
+                    // SAFETY: negative PID targets the process group created above.
+                    unsafe {
+                        libc::kill(-(child.id() as i32), libc::SIGKILL);
+                    }
+                }
+                #[cfg(not(unix))]
+                {
+                    let _ = child.kill();
+                }
+                let _ = child.wait();
+                bail!("{program} timed out after {}s", timeout.as_secs());
+            }
+            None => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(succeeded: bool, duration_secs: u64) -> SessionNotification {
+        SessionNotification {
+            session_id: "01JNOTIFY00000000000000000".to_string(),
+            tool: "codex".to_string(),
+            project_root: "/work/my-project".to_string(),
+            succeeded,
+            verdict: if succeeded { "success" } else { "failure" }.to_string(),
+            exit_code: if succeeded { 0 } else { 1 },
+            duration_secs,
+        }
+    }
+
+    #[test]
+    fn parse_notify_config_from_global_hooks_table() {
+        let config = parse_notify_hook_config(
+            r#"
+[hooks]
+auto_setup_review_gate = true
+
+[hooks.notify]
+on = ["failure"]
+desktop = false
+min_duration_seconds = 120
+
+[[hooks.notify.webhooks]]
+url = "https://hooks.slack.com/services/T/B/X"
+format = "slack"
+
+[[hooks.notify.webhooks]]
+url = "https://example.com/csa"
+"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.on, vec![NotifyOn::Failure]);
+        assert!(!config.desktop);
+        assert_eq!(config.min_duration_seconds, 120);
+        assert_eq!(config.timeout_seconds, DEFAULT_NOTIFY_TIMEOUT_SECONDS);
+        assert_eq!(config.webhooks[0].format, WebhookFormat::Slack);
+        assert_eq!(config.webhooks[1].format, WebhookFormat::Json);
+
+        assert!(parse_notify_hook_config("[hooks]\n").unwrap().is_none());
+        assert!(parse_notify_hook_config("").unwrap().is_none());
+    }
+
+    #[test]
+    fn should_notify_applies_outcome_and_duration_filters() {
+        let config = NotifyHookConfig {
+            on: vec![NotifyOn::Failure],
+            min_duration_seconds: 60,
+            ..Default::default()
+        };
+        assert!(config.should_notify(&notification(false, 60)));
+        assert!(!config.should_notify(&notification(false, 59)));
+        assert!(!config.should_notify(&notification(true, 600)));
+
+        let no_targets = NotifyHookConfig {
+            desktop: false,
+            ..Default::default()
+        };
+        assert!(!no_targets.should_notify(&notification(true, 600)));
+        let disabled = NotifyHookConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(!disabled.should_notify(&notification(true, 600)));
+    }
+
+    #[test]
+    fn webhook_payloads_match_target_format() {
+        let failed = notification(false, 754);
+        let slack = webhook_payload(WebhookFormat::Slack, &failed);
+        assert_eq!(
+            slack["text"],
+            "csa 01JNOTIFY00 failed\ncodex · failure (exit 1) · 12m34s · my-project"
+        );
+        let discord = webhook_payload(WebhookFormat::Discord, &failed);
+        assert_eq!(discord["content"], slack["text"]);
+        let json = webhook_payload(WebhookFormat::Json, &failed);
+        assert_eq!(json["session_id"], "01JNOTIFY00000000000000000");
+        assert_eq!(json["duration_secs"], 754);
+        assert_eq!(json["succeeded"], false);
+    }
+
+    #[test]
+    fn helpers_format_durations_and_escape_applescript() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(3_725), "1h02m");
+        assert_eq!(
+            applescript_string(r#"say "hi" \ bye"#),
+            r#""say \"hi\" \\ bye""#
+        );
+    }
+
+    #[test]
+    fn webhook_url_and_payload_go_through_the_curl_config() {
+        let command = webhook_command("curl", Duration::from_secs(10));
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args[args.len() - 2..], ["--config", "-"]);
+
+        let config = curl_config("https://hooks.example.com/T/x\"y", r#"{"a":"b\\c"}"#);
+        assert_eq!(
+            config,
+            r#"url = "https://hooks.example.com/T/x\"y"
+data-binary = "{\"a\":\"b\\\\c\"}"
+"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_target_reports_failure_and_timeout() {
+        assert!(run_target(Command::new("true"), None, Duration::from_secs(5)).is_ok());
+        assert!(
+            run_target(
+                Command::new("cat"),
+                Some(b"payload"),
+                Duration::from_secs(5)
+            )
+            .is_ok()
+        );
+        let err = run_target(Command::new("false"), None, Duration::from_secs(5)).unwrap_err();
+        assert!(err.to_string().contains("exited with code 1"));
+        let mut sleep = Command::new("sleep");
+        sleep.arg("5");
+        let err = run_target(sleep, None, Duration::from_millis(200)).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+}
@@ -18,6 +18,8 @@ pub use worktree::{
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use csa_config::shared_state;
+use csa_core::error::AppError;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs::{self, File, OpenOptions};
@@ -26,7 +28,7 @@ use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::{Path, PathBuf};
 
 /// Diagnostic information written to lock files
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub(crate) struct LockDiagnostic {
     pub(crate) pid: u32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -38,6 +40,8 @@ pub(crate) struct LockDiagnostic {
     pub(crate) holder_session_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) resource_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) user: Option<String>,
 }
 
 /// Session lock guard backed by `flock(2)`.
@@ -114,7 +118,7 @@ pub(crate) fn acquire_lock_at_path_with_metadata(
     resource_path: Option<&Path>,
 ) -> Result<SessionLock> {
     if let Some(parent) = lock_path.parent() {
-        fs::create_dir_all(parent)
+        shared_state::create_dir_all(parent)
             .with_context(|| format!("Failed to create locks directory: {}", parent.display()))?;
     }
 
@@ -130,10 +134,9 @@ pub(crate) fn acquire_lock_at_path_with_metadata(
 
     // Lock is held by another process, try to read diagnostic info.
     let diagnostic = read_lock_diagnostic(lock_path)?;
-    let error_msg = diagnostic
+    let contention = diagnostic
         .as_ref()
-        .map(|diagnostic| format_lock_diagnostic(lock_path, diagnostic))
-        .unwrap_or_else(|| "Session is locked (unable to read diagnostic info)".to_string());
+        .map(|diagnostic| lock_contention(lock_path, diagnostic));
 
     // Resource-scoped locks such as the worktree write lock layer their own
     // stale recovery on top of this primitive. The generic session-lock
@@ -162,7 +165,13 @@ pub(crate) fn acquire_lock_at_path_with_metadata(
         }
     }
 
-    Err(anyhow::anyhow!(error_msg))
+    Err(match contention {
+        Some(contention) => contention.into(),
+        None => AppError::LockHeld {
+            lock_path: lock_path.display().to_string(),
+        }
+        .into(),
+    })
 }
 
 fn try_acquire_lock_at_path(
@@ -179,6 +188,7 @@ fn try_acquire_lock_at_path(
         .truncate(false)
         .open(lock_path)
         .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
+    share_lock_file(&file, lock_path);
 
     let fd = file.as_raw_fd();
 
@@ -204,6 +214,7 @@ fn try_acquire_lock_at_path(
             reason: reason.to_string(),
             holder_session_id: holder_session_id.map(ToString::to_string),
             resource_path: resource_path.map(|path| path.display().to_string()),
+            user: Some(shared_state::current_user().to_string()),
         };
 
         let json =
@@ -223,6 +234,18 @@ fn try_acquire_lock_at_path(
     }
 }
 
+/// Let other users of a shared state dir reuse a lock file this user created.
+/// Failure only costs them the lock file, so it is logged, not returned.
+pub(crate) fn share_lock_file(file: &File, path: &Path) {
+    if let Err(error) = shared_state::share_file(file) {
+        tracing::warn!(
+            path = %path.display(),
+            error = %error,
+            "failed to make lock file group-writable for shared state"
+        );
+    }
+}
+
 pub(crate) fn set_fd_cloexec(fd: RawFd, path: &Path) -> Result<()> {
     // SAFETY: `fd` is owned by a live `File`; F_GETFD reads descriptor flags.
     let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
@@ -304,35 +327,24 @@ fn clear_stale_lock_file(lock_path: &Path) -> bool {
     }
 }
 
-fn format_lock_diagnostic(lock_path: &Path, diagnostic: &LockDiagnostic) -> String {
-    let holder = diagnostic
-        .holder_session_id
-        .as_deref()
-        .map(|session| format!(", holder_session_id: {session}"))
-        .unwrap_or_default();
-    let resource = diagnostic
-        .resource_path
-        .as_deref()
-        .map(|path| format!(", resource_path: {path}"))
-        .unwrap_or_default();
+fn lock_contention(lock_path: &Path, diagnostic: &LockDiagnostic) -> AppError {
     let pid_status = if is_pid_dead(diagnostic.pid, diagnostic.pid_start_time_ticks) {
         "dead_or_recycled"
     } else {
         "alive"
     };
-    format!(
-        "Session locked by PID {} (lock_path: {}, pid_status: {}, \
-         pid_start_time_ticks: {:?}, tool: {}, reason: {}, acquired: {}{}{})",
-        diagnostic.pid,
-        lock_path.display(),
-        pid_status,
-        diagnostic.pid_start_time_ticks,
-        diagnostic.tool_name,
-        diagnostic.reason,
-        diagnostic.acquired_at,
-        holder,
-        resource
-    )
+    AppError::LockContention {
+        pid: diagnostic.pid,
+        lock_path: lock_path.display().to_string(),
+        pid_status: pid_status.to_string(),
+        pid_start_time_ticks: diagnostic.pid_start_time_ticks,
+        tool: diagnostic.tool_name.clone(),
+        reason: diagnostic.reason.clone(),
+        acquired_at: diagnostic.acquired_at.to_string(),
+        holder_session_id: diagnostic.holder_session_id.clone(),
+        resource_path: diagnostic.resource_path.clone(),
+        user: diagnostic.user.clone(),
+    }
 }
 
 /// Acquire a non-blocking exclusive lock for a session and tool.
@@ -374,19 +386,16 @@ pub fn acquire_parent_fork_lock(
     acquire_lock_at_path(&lock_path, &lock_name, reason)
 }
 
+/// CSA state directory, honoring `CSA_STATE_DIR`.
 fn resolve_state_root() -> Result<PathBuf> {
-    let base_dirs = directories::BaseDirs::new()
-        .ok_or_else(|| anyhow::anyhow!("could not determine platform base directories"))?;
-    Ok(base_dirs
-        .state_dir()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| base_dirs.data_local_dir().to_path_buf()))
+    csa_config::paths::state_dir_write()
+        .ok_or_else(|| anyhow::anyhow!("could not determine the CSA state directory"))
 }
 
 /// Acquire a per-project resource lock for cross-session coordination.
 ///
 /// Lock path:
-/// `${XDG_STATE_HOME:-$HOME/.local/state}/cli-sub-agent/<resource_kind>-locks/<sha256(canonical(project_root))>/<tool_name>.lock`
+/// `${CSA_STATE_DIR:-${XDG_STATE_HOME:-$HOME/.local/state}/cli-sub-agent}/<resource_kind>-locks/<sha256(canonical(project_root))>/<tool_name>.lock`
 ///
 /// Used when two CSA sessions in the same project_root must serialize on a
 /// shared resource (e.g., the underlying jj or git repository).
@@ -431,7 +440,6 @@ pub(crate) fn project_resource_lock_path(
     let safe_kind = sanitize_lock_component(kind);
     let safe_tool = sanitize_lock_component(tool);
     let lock_path = resolve_state_root()?
-        .join("cli-sub-agent")
         .join(format!("{safe_kind}-locks"))
         .join(digest)
         .join(format!("{safe_tool}.lock"));
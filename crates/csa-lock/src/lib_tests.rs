@@ -108,7 +108,8 @@ fn test_second_lock_fails() {
     let result = acquire_lock(session_dir, "test-tool", "second reason");
     assert!(result.is_err(), "Second lock should fail");
 
-    let err_msg = result.unwrap_err().to_string();
+    let err = result.unwrap_err();
+    let err_msg = err.to_string();
     assert!(
         err_msg.contains("Session locked by PID"),
         "Error message should contain PID info"
@@ -117,6 +118,19 @@ fn test_second_lock_fails() {
         err_msg.contains("reason: first reason"),
         "Error message should contain reason info"
     );
+    match err.downcast_ref::<AppError>() {
+        Some(AppError::LockContention {
+            pid,
+            tool,
+            pid_status,
+            ..
+        }) => {
+            assert_eq!(*pid, std::process::id());
+            assert_eq!(tool, "test-tool");
+            assert_eq!(pid_status, "alive");
+        }
+        other => panic!("expected typed lock contention, got {other:?}"),
+    }
 }
 
 #[test]
@@ -502,6 +516,7 @@ fn write_session_lock_diagnostic(
         reason: reason.to_string(),
         holder_session_id: holder_session_id.map(ToString::to_string),
         resource_path: resource_path.map(|path| path.display().to_string()),
+        ..Default::default()
     };
     fs::write(lock_path, serde_json::to_string(&diagnostic).unwrap())
         .expect("write lock diagnostic");
@@ -0,0 +1,537 @@
+use super::*;
+use tempfile::tempdir;
+
+#[test]
+fn test_acquire_slot_succeeds() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let result = try_acquire_slot(slots_dir, "test-tool", 3, None).unwrap();
+    assert!(matches!(result, SlotAcquireResult::Acquired(_)));
+
+    if let SlotAcquireResult::Acquired(slot) = result {
+        assert_eq!(slot.tool_name(), "test-tool");
+        assert_eq!(slot.slot_index(), 0);
+    }
+}
+
+#[test]
+fn test_acquire_multiple_slots() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let slot0 = try_acquire_slot(slots_dir, "test-tool", 3, None).unwrap();
+    assert!(matches!(slot0, SlotAcquireResult::Acquired(_)));
+
+    let slot1 = try_acquire_slot(slots_dir, "test-tool", 3, None).unwrap();
+    assert!(matches!(slot1, SlotAcquireResult::Acquired(_)));
+
+    let slot2 = try_acquire_slot(slots_dir, "test-tool", 3, None).unwrap();
+    assert!(matches!(slot2, SlotAcquireResult::Acquired(_)));
+
+    // Fourth should be exhausted
+    let slot3 = try_acquire_slot(slots_dir, "test-tool", 3, None).unwrap();
+    assert!(matches!(slot3, SlotAcquireResult::Exhausted(_)));
+
+    if let SlotAcquireResult::Exhausted(status) = slot3 {
+        assert_eq!(status.max_slots, 3);
+        assert_eq!(status.occupied, 3);
+        assert_eq!(status.free(), 0);
+    }
+}
+
+#[test]
+fn test_held_slots_lists_live_holders_only() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let _a0 = try_acquire_slot(slots_dir, "tool-a", 2, Some("01SESSIONA")).unwrap();
+    let a1 = try_acquire_slot(slots_dir, "tool-a", 2, None).unwrap();
+    let _b0 = try_acquire_slot(slots_dir, "tool-b", 1, Some("01SESSIONB")).unwrap();
+    drop(a1);
+
+    let held = held_slots(slots_dir);
+    let summary: Vec<_> = held
+        .iter()
+        .map(|slot| {
+            (
+                slot.tool_name.as_str(),
+                slot.slot_index,
+                slot.session_id.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("tool-a", 0, Some("01SESSIONA")),
+            ("tool-b", 0, Some("01SESSIONB"))
+        ]
+    );
+    assert!(held.iter().all(|slot| slot.pid == std::process::id()));
+    assert!(held_slots(&dir.path().join("missing")).is_empty());
+}
+
+#[test]
+fn test_different_tools_independent() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let _slot_a = try_acquire_slot(slots_dir, "tool-a", 1, None).unwrap();
+    let slot_b = try_acquire_slot(slots_dir, "tool-b", 1, None).unwrap();
+
+    // tool-a full, but tool-b should still work
+    assert!(matches!(slot_b, SlotAcquireResult::Acquired(_)));
+}
+
+#[test]
+fn test_slot_diagnostic_written() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let result = try_acquire_slot(slots_dir, "test-tool", 3, Some("session-123")).unwrap();
+    assert!(matches!(result, SlotAcquireResult::Acquired(_)));
+
+    // Read the slot file
+    let slot_path = slots_dir.join("test-tool/slot-00.lock");
+    let content = fs::read_to_string(&slot_path).unwrap();
+    let diag: SlotDiagnostic = serde_json::from_str(&content).unwrap();
+
+    assert_eq!(diag.pid, std::process::id());
+    assert_eq!(
+        diag.pid_start_time_ticks,
+        crate::process_start_time_ticks(std::process::id())
+    );
+    assert_eq!(diag.tool_name, "test-tool");
+    assert_eq!(diag.slot_index, 0);
+    assert_eq!(diag.session_id.as_deref(), Some("session-123"));
+}
+
+#[test]
+fn test_slot_fd_sets_cloexec() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let result = try_acquire_slot(slots_dir, "codex", 1, Some("session-123")).unwrap();
+    let SlotAcquireResult::Acquired(slot) = result else {
+        panic!("expected acquired slot");
+    };
+
+    assert_fd_cloexec(slot.file.as_raw_fd());
+}
+
+#[test]
+fn test_slot_recovers_dead_pid_when_flock_released() {
+    // When the holder PID is dead, the kernel has already released the flock.
+    // The on-disk diagnostic still shows the dead PID. try_acquire_slot should
+    // detect the dead PID, retry flock on the same fd, and succeed.
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+    let tool_dir = slots_dir.join("codex");
+    fs::create_dir_all(&tool_dir).unwrap();
+    let slot_path = tool_dir.join("slot-00.lock");
+
+    // Write a stale diagnostic with a definitely-dead PID.
+    // No ManualSlotFlock — the flock is free because the dead process released it.
+    write_slot_diagnostic(
+        &slot_path,
+        i32::MAX as u32,
+        None,
+        "codex",
+        0,
+        Some("01STALE"),
+    );
+
+    let result = try_acquire_slot(slots_dir, "codex", 1, Some("01FRESH")).unwrap();
+    let SlotAcquireResult::Acquired(slot) = result else {
+        panic!("dead PID diagnostic with free flock should be reclaimed");
+    };
+    assert_eq!(slot.slot_index(), 0);
+
+    let content = fs::read_to_string(&slot_path).unwrap();
+    let diag: SlotDiagnostic = serde_json::from_str(&content).unwrap();
+    assert_eq!(diag.pid, std::process::id());
+    assert_eq!(diag.tool_name, "codex");
+    assert_eq!(diag.slot_index, 0);
+    assert_eq!(diag.session_id.as_deref(), Some("01FRESH"));
+}
+
+#[test]
+fn test_slot_dead_pid_with_held_flock_does_not_steal() {
+    // If the diagnostic PID is dead BUT another live process now holds the flock
+    // (e.g., it acquired between our first flock attempt and our dead-PID check),
+    // we must NOT steal the slot. The retry flock should fail.
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+    let tool_dir = slots_dir.join("codex");
+    fs::create_dir_all(&tool_dir).unwrap();
+    let slot_path = tool_dir.join("slot-00.lock");
+    write_slot_diagnostic(
+        &slot_path,
+        i32::MAX as u32,
+        None,
+        "codex",
+        0,
+        Some("01STALE"),
+    );
+    // A live process holds the flock now.
+    let _live_flock = ManualSlotFlock::acquire(&slot_path);
+
+    let result = try_acquire_slot(slots_dir, "codex", 1, Some("01FRESH")).unwrap();
+    match result {
+        SlotAcquireResult::Exhausted(status) => {
+            assert_eq!(status.occupied, 1);
+        }
+        SlotAcquireResult::Acquired(_) => {
+            panic!("must not steal slot held by live flock even if diagnostic PID is dead");
+        }
+    }
+}
+
+#[test]
+fn test_slot_usage_empty() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let usage = slot_usage(slots_dir, &[("tool-a", 3), ("tool-b", 2)]);
+    assert_eq!(usage.len(), 2);
+    assert_eq!(usage[0].occupied, 0);
+    assert_eq!(usage[1].occupied, 0);
+}
+
+#[test]
+fn test_format_slot_diagnostic() {
+    let status = SlotStatus {
+        tool_name: "codex".to_string(),
+        max_slots: 3,
+        occupied: 3,
+    };
+    let all_usage = vec![
+        status.clone(),
+        SlotStatus {
+            tool_name: "opencode".to_string(),
+            max_slots: 2,
+            occupied: 1,
+        },
+        SlotStatus {
+            tool_name: "claude-code".to_string(),
+            max_slots: 1,
+            occupied: 0,
+        },
+    ];
+
+    let msg = format_slot_diagnostic("codex", &status, &all_usage);
+    assert!(msg.contains("codex: all 3 slots occupied"));
+    assert!(msg.contains("opencode (1 free)"));
+    assert!(msg.contains("claude-code (1 free)"));
+    assert!(msg.contains("--wait to block"));
+}
+
+#[test]
+fn test_slot_path_construction() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let result = try_acquire_slot(slots_dir, "my-tool", 3, None).unwrap();
+    if let SlotAcquireResult::Acquired(slot) = result {
+        let expected = slots_dir.join("my-tool").join("slot-00.lock");
+        assert_eq!(slot.slot_path, expected);
+    } else {
+        panic!("expected Acquired");
+    }
+}
+
+#[test]
+fn test_slot_path_index_padding() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    // Acquire first slot so the second goes to index 1
+    let _s0 = try_acquire_slot(slots_dir, "pad-tool", 10, None).unwrap();
+    let result = try_acquire_slot(slots_dir, "pad-tool", 10, None).unwrap();
+    if let SlotAcquireResult::Acquired(slot) = result {
+        let expected = slots_dir.join("pad-tool").join("slot-01.lock");
+        assert_eq!(slot.slot_path, expected);
+    } else {
+        panic!("expected Acquired at index 1");
+    }
+}
+
+#[test]
+fn test_slot_usage_all_free_returns_zero() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    // Create the tool directories but don't acquire any locks
+    fs::create_dir_all(slots_dir.join("alpha")).unwrap();
+    fs::create_dir_all(slots_dir.join("beta")).unwrap();
+
+    let usage = slot_usage(slots_dir, &[("alpha", 5), ("beta", 2)]);
+    assert_eq!(usage.len(), 2);
+    for s in &usage {
+        assert_eq!(s.occupied, 0, "{} should have 0 occupied", s.tool_name);
+        assert_eq!(s.free(), s.max_slots);
+    }
+}
+
+#[test]
+fn test_slot_status_free_saturating() {
+    // Ensure `free()` never underflows even with bad data
+    let status = SlotStatus {
+        tool_name: "x".to_string(),
+        max_slots: 0,
+        occupied: 5,
+    };
+    assert_eq!(status.free(), 0);
+}
+
+#[test]
+fn test_acquire_slot_blocking_timeout() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    // Exhaust the single available slot
+    let _held = try_acquire_slot(slots_dir, "busy", 1, None).unwrap();
+
+    let start = std::time::Instant::now();
+    let result = acquire_slot_blocking(slots_dir, "busy", 1, Duration::from_millis(300), None);
+
+    assert!(result.is_err(), "should timeout when all slots held");
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Timed out"), "error: {err}");
+    // Verify we actually waited (at least ~200ms given poll backoff)
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+fn test_acquire_slot_blocking_immediate_success() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    // No slots held — should succeed without blocking
+    let slot = acquire_slot_blocking(
+        slots_dir,
+        "fast-tool",
+        2,
+        Duration::from_secs(5),
+        Some("sess-1"),
+    )
+    .unwrap();
+    assert_eq!(slot.tool_name(), "fast-tool");
+    assert_eq!(slot.slot_index(), 0);
+}
+
+#[test]
+fn test_format_slot_diagnostic_zero_slots() {
+    let status = SlotStatus {
+        tool_name: "empty".to_string(),
+        max_slots: 0,
+        occupied: 0,
+    };
+    let all_usage = vec![status.clone()];
+    let msg = format_slot_diagnostic("empty", &status, &all_usage);
+    assert!(msg.contains("empty: all 0 slots occupied"));
+    // No alternatives line because no other tools
+    assert!(!msg.contains("alternatives:"));
+}
+
+#[test]
+fn test_format_slot_diagnostic_all_tools_full() {
+    let status_a = SlotStatus {
+        tool_name: "a".to_string(),
+        max_slots: 2,
+        occupied: 2,
+    };
+    let status_b = SlotStatus {
+        tool_name: "b".to_string(),
+        max_slots: 1,
+        occupied: 1,
+    };
+    let all_usage = vec![status_a.clone(), status_b];
+    let msg = format_slot_diagnostic("a", &status_a, &all_usage);
+    // No alternatives since b is also full
+    assert!(!msg.contains("alternatives:"));
+    assert!(msg.contains("--wait to block"));
+}
+
+#[test]
+fn test_try_acquire_slot_with_session_id_none() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let result = try_acquire_slot(slots_dir, "sid-tool", 1, None).unwrap();
+    if let SlotAcquireResult::Acquired(_slot) = &result {
+        let content = fs::read_to_string(slots_dir.join("sid-tool/slot-00.lock")).unwrap();
+        let diag: SlotDiagnostic = serde_json::from_str(&content).unwrap();
+        assert!(diag.session_id.is_none());
+    } else {
+        panic!("expected Acquired");
+    }
+}
+
+#[test]
+fn test_exhausted_returns_correct_status() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let _s = try_acquire_slot(slots_dir, "one", 1, None).unwrap();
+    let result = try_acquire_slot(slots_dir, "one", 1, None).unwrap();
+    match result {
+        SlotAcquireResult::Exhausted(st) => {
+            assert_eq!(st.tool_name, "one");
+            assert_eq!(st.max_slots, 1);
+            assert_eq!(st.occupied, 1);
+            assert_eq!(st.free(), 0);
+        }
+        _ => panic!("expected Exhausted"),
+    }
+}
+
+#[test]
+fn test_release_slot_allows_reacquire_when_max_concurrent_is_one() {
+    let dir = tempdir().unwrap();
+    let slots_dir = dir.path();
+
+    let mut first = match try_acquire_slot(slots_dir, "single", 1, None).unwrap() {
+        SlotAcquireResult::Acquired(slot) => slot,
+        SlotAcquireResult::Exhausted(_) => panic!("expected slot acquisition"),
+    };
+
+    first
+        .release_slot()
+        .expect("explicit release should succeed");
+
+    let second = try_acquire_slot(slots_dir, "single", 1, None).unwrap();
+    assert!(
+        matches!(second, SlotAcquireResult::Acquired(_)),
+        "slot should be reacquired after explicit release"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_try_acquire_slot_self_heals_dangling_symlink() {
+    let dir = tempdir().unwrap();
+    let tool_dir = dir.path().join("codex");
+    fs::create_dir_all(&tool_dir).unwrap();
+    // Create a self-referencing (dangling) symlink in slot 0.
+    std::os::unix::fs::symlink("slot-00.lock", tool_dir.join("slot-00.lock")).unwrap();
+
+    // Self-heal removes the symlink and creates a regular file — slot 0 acquired.
+    let result = try_acquire_slot(dir.path(), "codex", 2, None).unwrap();
+    match result {
+        SlotAcquireResult::Acquired(slot) => assert_eq!(slot.slot_index(), 0),
+        SlotAcquireResult::Exhausted(_) => panic!("expected slot acquisition"),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_try_acquire_slot_self_heals_all_dangling_symlinks() {
+    let dir = tempdir().unwrap();
+    let tool_dir = dir.path().join("codex");
+    fs::create_dir_all(&tool_dir).unwrap();
+    std::os::unix::fs::symlink("slot-00.lock", tool_dir.join("slot-00.lock")).unwrap();
+    std::os::unix::fs::symlink("slot-01.lock", tool_dir.join("slot-01.lock")).unwrap();
+
+    // Both dangling symlinks are cleaned up — slot 0 acquired.
+    let result = try_acquire_slot(dir.path(), "codex", 2, None).unwrap();
+    match result {
+        SlotAcquireResult::Acquired(slot) => assert_eq!(slot.slot_index(), 0),
+        SlotAcquireResult::Exhausted(_) => panic!("expected slot acquisition after self-heal"),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_try_acquire_slot_errors_with_diagnostic_on_permission_denied() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let tool_dir = dir.path().join("perm-tool");
+    fs::create_dir_all(&tool_dir).unwrap();
+
+    // Create a slot file and make it unreadable/unwritable.
+    let slot_path = tool_dir.join("slot-00.lock");
+    fs::write(&slot_path, "").unwrap();
+    fs::set_permissions(&slot_path, fs::Permissions::from_mode(0o000)).unwrap();
+
+    let result = try_acquire_slot(dir.path(), "perm-tool", 1, None);
+    // Restore permissions for cleanup.
+    let _ = fs::set_permissions(&slot_path, fs::Permissions::from_mode(0o644));
+
+    let err = match result {
+        Err(e) => e,
+        Ok(_) => panic!("expected error for permission-denied slot file"),
+    };
+    let msg = err.to_string();
+    assert!(
+        msg.contains("Slot file open failure for 'perm-tool'"),
+        "error should mention tool name: {msg}"
+    );
+    assert!(
+        msg.contains("slot-00.lock"),
+        "error should include file path: {msg}"
+    );
+    assert!(msg.contains("Hint:"), "error should include hint: {msg}");
+}
+
+fn write_slot_diagnostic(
+    slot_path: &Path,
+    pid: u32,
+    pid_start_time_ticks: Option<u64>,
+    tool_name: &str,
+    slot_index: u32,
+    session_id: Option<&str>,
+) {
+    let diagnostic = SlotDiagnostic {
+        pid,
+        pid_start_time_ticks,
+        tool_name: tool_name.to_string(),
+        slot_index,
+        acquired_at: Utc::now(),
+        session_id: session_id.map(ToString::to_string),
+        ..Default::default()
+    };
+    fs::write(slot_path, serde_json::to_string(&diagnostic).unwrap())
+        .expect("write slot diagnostic");
+}
+
+struct ManualSlotFlock {
+    file: File,
+}
+
+impl ManualSlotFlock {
+    fn acquire(slot_path: &Path) -> Self {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(slot_path)
+            .expect("open slot file for manual flock");
+        // SAFETY: `file` owns a valid fd, and LOCK_EX | LOCK_NB requests a
+        // non-blocking advisory lock for the stale-slot setup.
+        let ret = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+        assert_eq!(ret, 0, "manual stale slot flock should acquire lock");
+        Self { file }
+    }
+}
+
+impl Drop for ManualSlotFlock {
+    fn drop(&mut self) {
+        // SAFETY: `file` owns a valid fd; unlock before close for deterministic cleanup.
+        unsafe {
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+fn assert_fd_cloexec(fd: std::os::unix::io::RawFd) {
+    // SAFETY: `fd` is owned by a live slot guard in the calling test.
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+    assert_ne!(flags, -1, "F_GETFD should succeed");
+    assert_ne!(
+        flags & libc::FD_CLOEXEC,
+        0,
+        "slot fd should be marked close-on-exec"
+    );
+}
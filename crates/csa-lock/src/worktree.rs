@@ -184,7 +184,7 @@ pub fn worktree_write_lock_is_held_by_session(
 /// Acquire a fail-fast exclusive write lock for a canonical git worktree root.
 ///
 /// Lock path:
-/// `${XDG_STATE_HOME:-$HOME/.local/state}/cli-sub-agent/worktree-write-locks/<sha256(canonical(worktree_root))>/exclusive.lock`
+/// `${CSA_STATE_DIR:-${XDG_STATE_HOME:-$HOME/.local/state}/cli-sub-agent}/worktree-write-locks/<sha256(canonical(worktree_root))>/exclusive.lock`
 ///
 /// If the lock holder is one of `ancestor_session_ids`, the current session is
 /// allowed to proceed only while that ancestor is still live. This prevents
@@ -280,12 +280,10 @@ pub fn acquire_worktree_write_lock(
                 ) {
                     Ok(lock) => return Ok(lock),
                     Err(retry_error) => {
-                        anyhow::bail!(
+                        return Err(lock_error.context(format!(
                             "concurrent write session blocked: stale lock detected but retry \
-                             failed: {}. {}",
-                            retry_error,
-                            lock_error
-                        );
+                             failed: {retry_error}"
+                        )));
                     }
                 }
             }
@@ -318,12 +316,10 @@ pub fn acquire_worktree_write_lock(
                 ) {
                     Ok(lock) => return Ok(lock),
                     Err(retry_error) => {
-                        anyhow::bail!(
+                        return Err(lock_error.context(format!(
                             "concurrent write session blocked: terminal holder session detected \
-                             and flock is free, but retry failed: {}. {}",
-                            retry_error,
-                            lock_error
-                        );
+                             and flock is free, but retry failed: {retry_error}"
+                        )));
                     }
                 }
             }
@@ -355,12 +351,10 @@ pub fn acquire_worktree_write_lock(
                     ) {
                         Ok(lock) => return Ok(lock),
                         Err(retry_error) => {
-                            anyhow::bail!(
+                            return Err(lock_error.context(format!(
                                 "concurrent write session blocked: SIGTERM sent to stale holder, \
-                                 flock released, but retry failed: {}. {}",
-                                retry_error,
-                                lock_error
-                            );
+                                 flock released, but retry failed: {retry_error}"
+                            )));
                         }
                     }
                 } else {
@@ -377,14 +371,14 @@ pub fn acquire_worktree_write_lock(
                 .as_ref()
                 .and_then(|diag| diag.holder_session_id.as_deref())
                 .unwrap_or("unknown");
-            anyhow::bail!(
+            // Keep the typed lock contention as the cause for callers.
+            Err(lock_error.context(format!(
                 "concurrent write session blocked: worktree '{}' is already locked by session {}. \
-                 {}. Run write-capable CSA sessions for this repository sequentially; \
+                 Run write-capable CSA sessions for this repository sequentially; \
                  wait for the holder to finish or stop it before starting another writer.",
                 canonical_root.display(),
                 holder,
-                lock_error
-            )
+            )))
         }
     }
 }
@@ -427,6 +427,7 @@ fn overwrite_worktree_lock_diagnostic(
         ),
         holder_session_id: Some(session_id.to_string()),
         resource_path: Some(worktree_root.display().to_string()),
+        ..Default::default()
     };
     fs::write(lock_path, serde_json::to_string(&diagnostic).unwrap())
         .expect("overwrite lock diagnostic");
@@ -0,0 +1,239 @@
+//! Opt-in caching of tool call results.
+//!
+//! Tools matching a `[[mcp.cache]]` rule have successful results reused for
+//! identical arguments until the rule's TTL expires, so many sub-agents
+//! asking the same lookup hit the downstream service once. Entries are keyed
+//! by a SHA-256 over server, tool, project root, canonical (key-sorted)
+//! arguments and `_meta`, and shared across consumers of the same project;
+//! tool filters are checked before the cache.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use csa_config::McpCacheRule;
+use rmcp::model::{CallToolRequestParams, CallToolResult, JsonObject};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Entries kept at most; expired ones are pruned first, then the ones
+/// closest to expiry.
+const MAX_CACHE_ENTRIES: usize = 1024;
+
+pub(crate) type CacheKey = [u8; 32];
+
+#[derive(Debug)]
+struct CacheEntry {
+    expires_at: Instant,
+    result: CallToolResult,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CallCache {
+    rules: Vec<McpCacheRule>,
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+}
+
+impl CallCache {
+    pub(crate) fn new(rules: Vec<McpCacheRule>) -> Self {
+        Self {
+            rules,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// How long results of `tool` on `server` are cached, if at all.
+    pub(crate) fn ttl_for(&self, server: &str, tool: &str) -> Option<Duration> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(server, tool))
+            .map(|rule| Duration::from_secs(rule.ttl_secs))
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Key of a call of `tool` on `server` made for `project_root`. The
+    /// `_meta` progress token differs per call and is left out.
+    pub(crate) fn key(
+        server: &str,
+        tool: &str,
+        project_root: Option<&Path>,
+        request: &CallToolRequestParams,
+    ) -> CacheKey {
+        let mut hasher = Sha256::new();
+        hasher.update(server.as_bytes());
+        hasher.update([0]);
+        hasher.update(tool.as_bytes());
+        hasher.update([0]);
+        if let Some(project_root) = project_root {
+            hasher.update(project_root.as_os_str().as_encoded_bytes());
+        }
+        hasher.update([0]);
+        if let Some(arguments) = &request.arguments {
+            hash_object(&mut hasher, arguments);
+        }
+        hasher.update([0]);
+        if let Some(meta) = &request.meta {
+            let mut meta = meta.0.clone();
+            meta.remove("progressToken");
+            if !meta.is_empty() {
+                hash_object(&mut hasher, &meta);
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<CallToolResult> {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.result.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: CacheKey, ttl: Duration, result: CallToolResult) {
+        let now = Instant::now();
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if entries.len() >= MAX_CACHE_ENTRIES && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= MAX_CACHE_ENTRIES
+                && let Some(soonest) = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(key, _)| *key)
+            {
+                entries.remove(&soonest);
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                expires_at: now + ttl,
+                result,
+            },
+        );
+    }
+}
+
+/// Hash `value` so that objects differing only in key order hash alike.
+fn hash_value(hasher: &mut Sha256, value: &Value) {
+    match value {
+        Value::Object(object) => hash_object(hasher, object),
+        Value::Array(items) => {
+            hasher.update(b"[");
+            for item in items {
+                hash_value(hasher, item);
+                hasher.update(b",");
+            }
+            hasher.update(b"]");
+        }
+        scalar => hasher.update(scalar.to_string().as_bytes()),
+    }
+}
+
+fn hash_object(hasher: &mut Sha256, object: &JsonObject) {
+    let mut keys: Vec<&String> = object.keys().collect();
+    keys.sort();
+    hasher.update(b"{");
+    for key in keys {
+        hasher.update(Value::String(key.clone()).to_string().as_bytes());
+        hasher.update(b":");
+        hash_value(hasher, &object[key]);
+        hasher.update(b",");
+    }
+    hasher.update(b"}");
+}
+
+#[cfg(test)]
+mod tests {
+    use rmcp::model::{Content, Meta};
+    use serde_json::json;
+
+    use super::*;
+
+    fn rule(tools: &[&str], ttl_secs: u64) -> McpCacheRule {
+        McpCacheRule {
+            tools: tools.iter().map(|tool| tool.to_string()).collect(),
+            ttl_secs,
+        }
+    }
+
+    fn arguments(value: Value) -> JsonObject {
+        value.as_object().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn first_matching_rule_sets_the_ttl() {
+        let cache = CallCache::new(vec![
+            rule(&["github.search_issues"], 0),
+            rule(&["github.search_*"], 300),
+        ]);
+        assert_eq!(
+            cache.ttl_for("github", "search_code"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(cache.ttl_for("github", "search_issues"), None);
+        assert_eq!(cache.ttl_for("gitlab", "search_code"), None);
+    }
+
+    fn call(value: Value) -> CallToolRequestParams {
+        CallToolRequestParams::new("search").with_arguments(arguments(value))
+    }
+
+    #[test]
+    fn keys_ignore_argument_order() {
+        let a = call(json!({"q": "rmcp", "page": {"size": 10, "n": 1}}));
+        let b = call(json!({"page": {"n": 1, "size": 10}, "q": "rmcp"}));
+        let c = call(json!({"q": "rmcp", "page": {"size": 10, "n": 2}}));
+        let key = CallCache::key("github", "search", None, &a);
+        assert_eq!(key, CallCache::key("github", "search", None, &b));
+        assert_ne!(key, CallCache::key("github", "search", None, &c));
+        assert_ne!(key, CallCache::key("gitlab", "search", None, &a));
+        let bare = CallToolRequestParams::new("search");
+        assert_ne!(
+            CallCache::key("github", "search", None, &bare),
+            CallCache::key("githubsearch", "", None, &bare)
+        );
+    }
+
+    #[test]
+    fn keys_separate_projects_and_meta_but_not_progress_tokens() {
+        let request = call(json!({"q": "rmcp"}));
+        let key = CallCache::key("github", "search", Some(Path::new("/a")), &request);
+        assert_ne!(
+            key,
+            CallCache::key("github", "search", Some(Path::new("/b")), &request)
+        );
+        assert_ne!(key, CallCache::key("github", "search", None, &request));
+
+        let with_meta = |meta: Value| {
+            let mut request = request.clone();
+            request.meta = Some(Meta(arguments(meta)));
+            CallCache::key("github", "search", Some(Path::new("/a")), &request)
+        };
+        assert_ne!(key, with_meta(json!({"tenant": "x"})));
+        assert_eq!(key, with_meta(json!({"progressToken": 7})));
+    }
+
+    #[test]
+    fn entries_expire() {
+        let cache = CallCache::default();
+        let key = CallCache::key("github", "search", None, &call(json!({})));
+        let result = CallToolResult::success(vec![Content::text("hit")]);
+        cache.insert(key, Duration::from_secs(60), result.clone());
+        assert_eq!(cache.get(&key), Some(result.clone()));
+
+        cache.insert(key, Duration::ZERO, result);
+        assert_eq!(cache.get(&key), None);
+    }
+}
@@ -1,9 +1,16 @@
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
-use csa_config::{GlobalConfig, McpServerConfig, paths};
+use csa_config::{
+    GlobalConfig, McpCacheRule, McpCsaToolsConfig, McpFilter, McpServerConfig, paths,
+};
+
+use crate::http_auth::HttpTokens;
+use crate::metrics::default_request_log_path;
+use crate::tool_filter::ToolFilters;
 
 const DEFAULT_HTTP_BIND: &str = "127.0.0.1";
 const DEFAULT_HTTP_PORT: u16 = 0;
@@ -20,12 +27,30 @@ pub(crate) struct HubConfig {
     pub(crate) mcp_servers: Vec<McpServerConfig>,
     pub(crate) mcp_whitelist: Vec<String>,
     pub(crate) mcp_blacklist: Vec<String>,
+    /// Global `[mcp_filters]`; sessions add those of their own project.
+    pub(crate) mcp_filters: HashMap<String, McpFilter>,
+    /// Filters for peers outside a session, from the global config only.
+    pub(crate) tool_filters: ToolFilters,
     pub(crate) http_bind: String,
     pub(crate) http_port: u16,
+    /// Bearer tokens required by the HTTP endpoint; only loaded by `serve`.
+    pub(crate) http_tokens: HttpTokens,
     pub(crate) max_connections: usize,
     pub(crate) max_requests_per_sec: u32,
     pub(crate) max_request_body_bytes: usize,
     pub(crate) request_timeout_secs: u64,
+    /// Where to log every tool call; set by `[mcp] request_log = true`.
+    pub(crate) request_log: Option<PathBuf>,
+    /// `[[mcp.cache]]` rules for the response cache.
+    pub(crate) cache_rules: Vec<McpCacheRule>,
+    /// `[mcp.rate_limits]`: server name to max forwarded calls per second.
+    pub(crate) rate_limits: HashMap<String, u32>,
+    /// `[mcp.csa_tools]`: which built-in `csa` tools are served, and where.
+    pub(crate) csa_tools: McpCsaToolsConfig,
+    /// Address of the Prometheus endpoint; set by `[metrics] enabled = true`.
+    pub(crate) metrics_listen: Option<String>,
+    /// csa binary behind the built-in `csa` tools; only set by `load()`.
+    pub(crate) csa_program: Option<PathBuf>,
 }
 
 impl HubConfig {
@@ -38,7 +63,7 @@ impl HubConfig {
         let cwd = std::env::current_dir().context("failed to resolve current working directory")?;
         let project_root = discover_project_root(&cwd);
         let (mcp_whitelist, mcp_blacklist) = load_project_mcp_visibility(&project_root)?;
-        Ok(Self::from_parts(
+        let mut cfg = Self::from_parts(
             &global,
             project_root,
             socket_override,
@@ -46,7 +71,9 @@ impl HubConfig {
             http_port_override,
             mcp_whitelist,
             mcp_blacklist,
-        ))
+        );
+        cfg.csa_program = std::env::current_exe().ok();
+        Ok(cfg)
     }
 
     #[cfg(test)]
@@ -89,12 +116,28 @@ impl HubConfig {
             mcp_servers: global.mcp_servers().to_vec(),
             mcp_whitelist,
             mcp_blacklist,
+            mcp_filters: global.mcp_filters.clone(),
+            tool_filters: ToolFilters::new(&global.mcp_filters, HashMap::new()),
             http_bind: http_bind_override.unwrap_or_else(|| DEFAULT_HTTP_BIND.to_string()),
             http_port: http_port_override.unwrap_or(DEFAULT_HTTP_PORT),
+            http_tokens: HttpTokens::default(),
             max_connections: DEFAULT_MAX_CONNECTIONS,
             max_requests_per_sec: DEFAULT_MAX_REQUESTS_PER_SEC,
             max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
             request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            request_log: global
+                .mcp
+                .request_log
+                .then(default_request_log_path)
+                .flatten(),
+            cache_rules: global.mcp.cache.clone(),
+            rate_limits: global.mcp.rate_limits.clone(),
+            csa_tools: global.mcp.csa_tools.clone(),
+            metrics_listen: global
+                .metrics
+                .enabled
+                .then(|| global.metrics.listen.clone()),
+            csa_program: None,
         }
     }
 
@@ -137,6 +180,29 @@ fn load_project_mcp_visibility(project_root: &Path) -> Result<(Vec<String>, Vec<
     ))
 }
 
+/// `[mcp_filters]` of the project at `project_root`.
+pub(crate) fn load_project_mcp_filters(project_root: &Path) -> Result<HashMap<String, McpFilter>> {
+    let path = project_root.join(".csa").join("config.toml");
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read project config: {}", path.display()))?;
+    let value: toml::Value = toml::from_str(&raw)
+        .with_context(|| format!("failed to parse project config: {}", path.display()))?;
+    let Some(filters) = value.get("mcp_filters") else {
+        return Ok(HashMap::new());
+    };
+
+    filters.clone().try_into().with_context(|| {
+        format!(
+            "invalid [mcp_filters] in project config: {}",
+            path.display()
+        )
+    })
+}
+
 fn read_string_list(value: &toml::Value, key: &str) -> Vec<String> {
     let Some(items) = value.get(key).and_then(toml::Value::as_array) else {
         return Vec::new();
@@ -178,7 +244,8 @@ fn socket_path_from_runtime_dir(runtime_dir: Option<&str>, uid: u32) -> PathBuf
 mod tests {
     use super::{
         DEFAULT_HTTP_BIND, DEFAULT_HTTP_PORT, HubConfig, discover_project_root,
-        load_project_mcp_visibility, pid_path_for_socket, socket_path_from_runtime_dir,
+        load_project_mcp_filters, load_project_mcp_visibility, pid_path_for_socket,
+        socket_path_from_runtime_dir,
     };
 
     #[test]
@@ -305,4 +372,24 @@ mcp_blacklist = ["memory"]
         assert_eq!(whitelist, vec!["repomix", "deepwiki"]);
         assert_eq!(blacklist, vec!["memory"]);
     }
+
+    #[test]
+    fn load_project_mcp_filters_reads_consumer_tables() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let project_root = tmp.path().join("project");
+        std::fs::create_dir_all(project_root.join(".csa")).expect("create .csa");
+        std::fs::write(
+            project_root.join(".csa/config.toml"),
+            r#"
+[mcp_filters.codex]
+tools = ["github.*", "!github.delete_*"]
+"#,
+        )
+        .expect("write config");
+
+        let filters = load_project_mcp_filters(&project_root).expect("load filters");
+        let codex = &filters["codex"];
+        assert!(codex.allows_tool("github", "create_issue"));
+        assert!(!codex.allows_tool("github", "delete_repo"));
+    }
 }
@@ -0,0 +1,205 @@
+//! Hot reload of `[[mcp.servers]]` from the global config.
+//!
+//! The hub polls the global config file and, when it changes, applies the new
+//! server list to the running registry: added servers become available,
+//! removed ones are stopped and edited ones restarted. Connected clients get
+//! `notifications/tools/list_changed` so they re-list tools without
+//! reconnecting. Other hub settings still need a restart.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use csa_config::{GlobalConfig, McpServerConfig, paths};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::proxy::ProxyRouter;
+use crate::registry::{McpRegistry, RegistryChanges};
+use crate::skill_writer::{SkillRefreshNotifier, SkillRefreshSignal};
+
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Modification time and size of a file; `None` while it does not exist.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Notices edits of a file by polling its metadata.
+#[derive(Debug)]
+pub(crate) struct FileWatcher {
+    path: PathBuf,
+    stamp: FileStamp,
+}
+
+impl FileWatcher {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        let stamp = file_stamp(&path);
+        Self { path, stamp }
+    }
+
+    /// Whether the file was modified, created or removed since the last poll.
+    pub(crate) fn poll(&mut self) -> bool {
+        let stamp = file_stamp(&self.path);
+        if stamp == self.stamp {
+            return false;
+        }
+        self.stamp = stamp;
+        true
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ConfigReloadHandle {
+    join_handle: JoinHandle<()>,
+}
+
+impl ConfigReloadHandle {
+    pub(crate) async fn shutdown(self) {
+        self.join_handle.abort();
+        let _ = self.join_handle.await;
+    }
+}
+
+/// Watch the global config, or `None` without a config directory.
+pub(crate) fn spawn_config_reload_task(
+    registry: Arc<McpRegistry>,
+    router: Arc<ProxyRouter>,
+    skill_notify_tx: SkillRefreshNotifier,
+) -> Option<ConfigReloadHandle> {
+    let path = paths::config_dir()?.join("config.toml");
+    tracing::debug!(config = %path.display(), "watching global config for MCP server changes");
+    let mut watcher = FileWatcher::new(path);
+    let join_handle = tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(CONFIG_POLL_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            if !watcher.poll() {
+                continue;
+            }
+            let servers = match GlobalConfig::load() {
+                Ok(global) => global.mcp_servers().to_vec(),
+                Err(error) => {
+                    tracing::warn!(
+                        error = %error,
+                        "global config is invalid; keeping the current MCP servers"
+                    );
+                    continue;
+                }
+            };
+            apply_server_configs(&registry, &router, &skill_notify_tx, servers).await;
+        }
+    });
+    Some(ConfigReloadHandle { join_handle })
+}
+
+/// Apply `servers` to the registry and tell clients when their tools changed.
+pub(crate) async fn apply_server_configs(
+    registry: &McpRegistry,
+    router: &ProxyRouter,
+    skill_notify_tx: &SkillRefreshNotifier,
+    servers: Vec<McpServerConfig>,
+) -> RegistryChanges {
+    let changes = registry.reload(servers).await;
+    if changes.is_empty() {
+        return changes;
+    }
+    tracing::info!(
+        added = ?changes.added,
+        removed = ?changes.removed,
+        restarted = ?changes.restarted,
+        "reloaded MCP servers from config"
+    );
+    router.tools_changed().await;
+    skill_notify_tx.notify(SkillRefreshSignal::ToolsListChanged { server: None });
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use anyhow::Result;
+    use csa_config::McpTransport;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn server(name: &str, command: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            transport: McpTransport::Stdio {
+                command: command.to_string(),
+                args: Vec::new(),
+                env: HashMap::new(),
+            },
+            stateful: false,
+            memory_max_mb: None,
+            tool_renames: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn watcher_notices_creation_edits_and_removal() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let path = temp.path().join("config.toml");
+        let mut watcher = FileWatcher::new(path.clone());
+        assert!(!watcher.poll());
+
+        std::fs::write(&path, "[mcp]\n")?;
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        std::fs::write(&path, "[mcp]\nrequest_log = true\n")?;
+        assert!(watcher.poll());
+
+        std::fs::remove_file(&path)?;
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reload_notifies_clients_only_when_servers_change() -> Result<()> {
+        let registry = Arc::new(McpRegistry::new(vec![
+            server("keep", "keep-mcp"),
+            server("edit", "old-mcp"),
+            server("drop", "drop-mcp"),
+        ]));
+        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+        let mut list_changed = router.subscribe_list_changed();
+        let (signal_tx, mut signal_rx) = mpsc::channel(1);
+        let notifier = SkillRefreshNotifier::new(signal_tx);
+
+        let servers = vec![
+            server("keep", "keep-mcp"),
+            server("edit", "new-mcp"),
+            server("add", "add-mcp"),
+        ];
+        let changes = apply_server_configs(&registry, &router, &notifier, servers.clone()).await;
+        assert_eq!(
+            changes,
+            RegistryChanges {
+                added: vec!["add".to_string()],
+                removed: vec!["drop".to_string()],
+                restarted: vec!["edit".to_string()],
+            }
+        );
+        let mut names = registry.server_names();
+        names.sort();
+        assert_eq!(names, ["add", "edit", "keep"]);
+        assert!(list_changed.try_recv().is_ok());
+        assert!(signal_rx.try_recv().is_ok());
+
+        let unchanged = apply_server_configs(&registry, &router, &notifier, servers).await;
+        assert!(unchanged.is_empty());
+        assert!(list_changed.try_recv().is_err());
+
+        registry.shutdown_all().await?;
+        Ok(())
+    }
+}
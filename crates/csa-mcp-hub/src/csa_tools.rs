@@ -0,0 +1,432 @@
+//! Built-in `csa` tools served by the hub itself.
+//!
+//! Alongside the configured backends the hub exposes a `csa` server whose
+//! tools drive the csa binary the hub runs from, so MCP clients can start
+//! runs and reviews, inspect sessions and manage TODO plans without shelling
+//! out and parsing text. Listing tools return csa's `--format json` output as
+//! structured content; `csa_run` and `csa_review` start daemon sessions and
+//! return their session IDs. Those two start agents, so they are only served
+//! when `[mcp.csa_tools] allow_agents` is set and never over HTTP. Every tool
+//! is confined to the hub's project and the `projects` listed there.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use csa_config::McpCsaToolsConfig;
+use rmcp::model::{CallToolRequestParams, CallToolResult, Content, JsonObject, Tool};
+use serde_json::{Value, json};
+
+/// Server name the built-in tools are listed and filtered under.
+pub(crate) const SERVER_NAME: &str = "csa";
+
+/// Tools that start agents.
+const AGENT_TOOLS: [&str; 2] = ["csa_run", "csa_review"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+    Json,
+    Text,
+    /// A daemon session was started; stdout holds its ID.
+    SessionId,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Invocation {
+    args: Vec<String>,
+    output: Output,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CsaTools {
+    program: PathBuf,
+    default_project_root: PathBuf,
+    /// Canonical roots of the projects the tools may target.
+    projects: Vec<PathBuf>,
+    allow_agents: bool,
+}
+
+impl CsaTools {
+    pub(crate) fn new(
+        program: PathBuf,
+        default_project_root: PathBuf,
+        config: &McpCsaToolsConfig,
+    ) -> Self {
+        let projects = std::iter::once(&default_project_root)
+            .chain(&config.projects)
+            .filter_map(|root| match root.canonicalize() {
+                Ok(root) => Some(root),
+                Err(error) => {
+                    tracing::warn!(
+                        project = %root.display(),
+                        %error,
+                        "ignoring unresolvable [mcp.csa_tools] project"
+                    );
+                    None
+                }
+            })
+            .collect();
+        Self {
+            program,
+            default_project_root,
+            projects,
+            allow_agents: config.allow_agents,
+        }
+    }
+
+    /// These tools without `csa_run` and `csa_review`, for clients that may
+    /// not start agents.
+    pub(crate) fn without_agents(&self) -> Self {
+        Self {
+            allow_agents: false,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn tools(&self) -> Vec<Tool> {
+        let mut tools = Self::all_tools();
+        if !self.allow_agents {
+            tools.retain(|tool| !AGENT_TOOLS.contains(&tool.name.as_ref()));
+        }
+        tools
+    }
+
+    fn all_tools() -> Vec<Tool> {
+        let project_root = json!({
+            "type": "string",
+            "description": "Absolute root of a project the hub serves (defaults to the hub's project)"
+        });
+        let session_id = json!({
+            "type": "string",
+            "description": "Session ULID or unique prefix"
+        });
+        let tool = json!({
+            "type": "string",
+            "description": "Tool to run (codex, claude-code, gemini-cli, opencode); defaults to csa routing"
+        });
+        let tier = json!({
+            "type": "string",
+            "description": "Tier from [tiers] used for tool/model selection"
+        });
+        vec![
+            tool_def(
+                "csa_run",
+                "Start a csa run in the background and return its session ID. \
+                 Poll csa_session_show for the result.",
+                json!({
+                    "prompt": {"type": "string", "description": "Task prompt"},
+                    "tool": tool,
+                    "tier": tier,
+                    "description": {"type": "string", "description": "Session description"},
+                    "project_root": project_root,
+                }),
+                &["prompt"],
+            ),
+            tool_def(
+                "csa_review",
+                "Start a csa review in the background and return its session ID. \
+                 Reviews uncommitted changes unless a scope is given.",
+                json!({
+                    "branch": {"type": "string", "description": "Review the diff against this base branch"},
+                    "commit": {"type": "string", "description": "Review a single commit"},
+                    "range": {"type": "string", "description": "Review a commit range (base..head)"},
+                    "files": {"type": "string", "description": "Review these files (glob or comma-separated)"},
+                    "context": {"type": "string", "description": "Path to a TODO plan or design doc for context"},
+                    "tool": tool,
+                    "tier": tier,
+                    "project_root": project_root,
+                }),
+                &[],
+            ),
+            tool_def(
+                "csa_session_list",
+                "List csa sessions of a project, most recent first.",
+                json!({
+                    "limit": {"type": "integer", "minimum": 1, "description": "Return only the N most recent sessions"},
+                    "status": {"type": "string", "description": "Filter by status (active, retired, failed, error)"},
+                    "tool": {"type": "string", "description": "Filter by tool (comma-separated)"},
+                    "project_root": project_root,
+                }),
+                &[],
+            ),
+            tool_def(
+                "csa_session_show",
+                "Show the result of a csa session: status, exit code, summary and artifacts.",
+                json!({
+                    "session_id": session_id,
+                    "project_root": project_root,
+                }),
+                &["session_id"],
+            ),
+            tool_def(
+                "csa_todo_list",
+                "List TODO plans of a project.",
+                json!({
+                    "status": {"type": "string", "description": "Filter by status (draft, debating, approved, implementing, done)"},
+                    "project_root": project_root,
+                }),
+                &[],
+            ),
+            tool_def(
+                "csa_todo_show",
+                "Show a TODO plan's TODO.md, or its spec.toml criteria.",
+                json!({
+                    "timestamp": {"type": "string", "description": "Plan timestamp (defaults to the latest plan)"},
+                    "spec": {"type": "boolean", "description": "Show spec.toml instead of TODO.md"},
+                    "project_root": project_root,
+                }),
+                &[],
+            ),
+            tool_def(
+                "csa_todo_create",
+                "Create a TODO plan and return its timestamp and path.",
+                json!({
+                    "title": {"type": "string", "description": "Plan title"},
+                    "branch": {"type": "string", "description": "Git branch to associate (defaults to the current branch)"},
+                    "project_root": project_root,
+                }),
+                &["title"],
+            ),
+        ]
+    }
+
+    pub(crate) async fn call(
+        &self,
+        request: &CallToolRequestParams,
+        project_root: Option<PathBuf>,
+    ) -> Result<CallToolResult> {
+        let project_root = project_root.unwrap_or_else(|| self.default_project_root.clone());
+        let empty = JsonObject::new();
+        let arguments = request.arguments.as_ref().unwrap_or(&empty);
+        let invocation = self
+            .check_call(&request.name, &project_root)
+            .and_then(|()| invocation_for(&request.name, arguments, &project_root));
+        let invocation = match invocation {
+            Ok(invocation) => invocation,
+            Err(error) => return Ok(error_result(format!("{error:#}"))),
+        };
+
+        let output = tokio::process::Command::new(&self.program)
+            .args(&invocation.args)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .with_context(|| format!("failed to run {}", self.program.display()))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let detail = if stderr.trim().is_empty() {
+                stdout.trim()
+            } else {
+                stderr.trim()
+            };
+            return Ok(error_result(format!(
+                "{} failed ({}): {detail}",
+                request.name, output.status
+            )));
+        }
+
+        Ok(match invocation.output {
+            Output::Json => match serde_json::from_str::<Value>(&stdout) {
+                Ok(value @ Value::Object(_)) => CallToolResult::structured(value),
+                Ok(value) => CallToolResult::structured(json!({ "items": value })),
+                Err(_) => CallToolResult::success(vec![Content::text(stdout.into_owned())]),
+            },
+            Output::Text => CallToolResult::success(vec![Content::text(stdout.into_owned())]),
+            Output::SessionId => match stdout.lines().map(str::trim).find(|l| !l.is_empty()) {
+                Some(session_id) => CallToolResult::structured(json!({ "session_id": session_id })),
+                None => error_result(format!("{} did not report a session ID", request.name)),
+            },
+        })
+    }
+
+    /// Refuse agent tools unless allowed, and projects the hub does not serve.
+    fn check_call(&self, name: &str, project_root: &Path) -> Result<()> {
+        if !self.allow_agents && AGENT_TOOLS.contains(&name) {
+            bail!(
+                "{name} is not available: [mcp.csa_tools] allow_agents is off or the client is remote"
+            );
+        }
+        if !project_root.is_absolute() {
+            bail!(
+                "project_root must be an absolute path: {}",
+                project_root.display()
+            );
+        }
+        let resolved = project_root
+            .canonicalize()
+            .with_context(|| format!("project_root not found: {}", project_root.display()))?;
+        if !self
+            .projects
+            .iter()
+            .any(|project| resolved.starts_with(project))
+        {
+            bail!(
+                "project_root is not a project this hub serves: {}; add it to [mcp.csa_tools] projects",
+                project_root.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn tool_def(
+    name: &'static str,
+    description: &'static str,
+    properties: Value,
+    required: &[&str],
+) -> Tool {
+    let schema = json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    let Value::Object(schema) = schema else {
+        unreachable!("schema literal is an object");
+    };
+    Tool::new(name, description, Arc::new(schema))
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(message)])
+}
+
+/// The csa command line for a tool call.
+fn invocation_for(name: &str, arguments: &JsonObject, project_root: &Path) -> Result<Invocation> {
+    let args = Args(arguments);
+    let cd = project_root.to_string_lossy().into_owned();
+    let mut command: Vec<String> = Vec::new();
+    let output = match name {
+        "csa_run" => {
+            command.extend(["run".into(), "--cd".into(), cd]);
+            args.push_flag(&mut command, "tool", "--tool")?;
+            args.push_flag(&mut command, "tier", "--tier")?;
+            args.push_flag(&mut command, "description", "--description")?;
+            command.extend(["--prompt".into(), args.required("prompt")?]);
+            Output::SessionId
+        }
+        "csa_review" => {
+            command.extend(["review".into(), "--cd".into(), cd]);
+            let scopes = ["branch", "commit", "range", "files"];
+            match scopes
+                .iter()
+                .filter(|scope| args.0.contains_key(**scope))
+                .count()
+            {
+                0 => command.push("--diff".into()),
+                1 => {
+                    for scope in scopes {
+                        args.push_flag(&mut command, scope, &format!("--{scope}"))?;
+                    }
+                }
+                _ => bail!("give at most one of branch, commit, range and files"),
+            }
+            args.push_flag(&mut command, "context", "--context")?;
+            args.push_flag(&mut command, "tool", "--tool")?;
+            args.push_flag(&mut command, "tier", "--tier")?;
+            Output::SessionId
+        }
+        "csa_session_list" => {
+            command.extend(json_command(&["session", "list"], cd));
+            if let Some(limit) = args.integer("limit")? {
+                command.extend(["--limit".into(), limit.to_string()]);
+            }
+            args.push_flag(&mut command, "status", "--status")?;
+            args.push_flag(&mut command, "tool", "--tool")?;
+            Output::Json
+        }
+        "csa_session_show" => {
+            command.extend([
+                "session".into(),
+                "result".into(),
+                "--json".into(),
+                "--cd".into(),
+                cd,
+                "--session".into(),
+                args.required("session_id")?,
+            ]);
+            Output::Json
+        }
+        "csa_todo_list" => {
+            command.extend(json_command(&["todo", "list"], cd));
+            args.push_flag(&mut command, "status", "--status")?;
+            Output::Json
+        }
+        "csa_todo_show" => {
+            command.extend(["todo".into(), "show".into(), "--cd".into(), cd]);
+            args.push_flag(&mut command, "timestamp", "--timestamp")?;
+            if args.boolean("spec")? {
+                command.push("--spec".into());
+            }
+            Output::Text
+        }
+        "csa_todo_create" => {
+            command.extend(json_command(&["todo", "create"], cd));
+            args.push_flag(&mut command, "branch", "--branch")?;
+            command.extend(["--".into(), args.required("title")?]);
+            Output::Json
+        }
+        _ => bail!("unknown csa tool: {name}"),
+    };
+    Ok(Invocation {
+        args: command,
+        output,
+    })
+}
+
+fn json_command(subcommand: &[&str], cd: String) -> Vec<String> {
+    let mut command = vec!["--format".to_string(), "json".to_string()];
+    command.extend(subcommand.iter().map(|part| part.to_string()));
+    command.extend(["--cd".to_string(), cd]);
+    command
+}
+
+struct Args<'a>(&'a JsonObject);
+
+impl Args<'_> {
+    fn string(&self, key: &str) -> Result<Option<String>> {
+        match self.0.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(_) => bail!("{key} must be a string"),
+        }
+    }
+
+    fn required(&self, key: &str) -> Result<String> {
+        self.string(key)?
+            .filter(|value| !value.trim().is_empty())
+            .with_context(|| format!("missing required argument: {key}"))
+    }
+
+    fn integer(&self, key: &str) -> Result<Option<u64>> {
+        match self.0.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => value
+                .as_u64()
+                .map(Some)
+                .with_context(|| format!("{key} must be a positive integer")),
+        }
+    }
+
+    fn boolean(&self, key: &str) -> Result<bool> {
+        match self.0.get(key) {
+            None | Some(Value::Null) => Ok(false),
+            Some(value) => value
+                .as_bool()
+                .with_context(|| format!("{key} must be a boolean")),
+        }
+    }
+
+    fn push_flag(&self, command: &mut Vec<String>, key: &str, flag: &str) -> Result<()> {
+        if let Some(value) = self.string(key)? {
+            command.extend([flag.to_string(), value]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "csa_tools_tests.rs"]
+mod tests;
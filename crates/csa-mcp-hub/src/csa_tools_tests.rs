@@ -0,0 +1,284 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use rmcp::model::CallToolRequestParams;
+use serde_json::json;
+
+use super::*;
+
+fn object(value: Value) -> JsonObject {
+    value.as_object().cloned().unwrap_or_default()
+}
+
+fn args_for(name: &str, arguments: Value) -> Result<Vec<String>> {
+    invocation_for(name, &object(arguments), Path::new("/work/project")).map(|inv| inv.args)
+}
+
+/// Stand-in csa binary: starts "sessions", prints its argv as JSON for
+/// `--format json` commands and fails on anything else.
+fn write_fake_csa(dir: &Path) -> Result<PathBuf> {
+    let path = dir.join("csa");
+    fs::write(
+        &path,
+        r#"#!/bin/sh
+case "$1" in
+  run|review) echo "01JTESTSESSION" ;;
+  --format) printf '[{"argv":"%s"}]\n' "$*" ;;
+  *) echo "unknown command: $1" >&2; exit 2 ;;
+esac
+"#,
+    )?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&path, perms)?;
+    }
+    Ok(path)
+}
+
+#[test]
+fn tool_schemas_cover_every_tool() {
+    let tools = CsaTools::all_tools();
+    let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(
+        names,
+        [
+            "csa_run",
+            "csa_review",
+            "csa_session_list",
+            "csa_session_show",
+            "csa_todo_list",
+            "csa_todo_show",
+            "csa_todo_create",
+        ]
+    );
+    for tool in &tools {
+        let properties = tool.input_schema["properties"]
+            .as_object()
+            .expect("properties");
+        assert!(properties.contains_key("project_root"), "{}", tool.name);
+        for key in tool.input_schema["required"].as_array().expect("required") {
+            let key = key.as_str().expect("required name");
+            assert!(properties.contains_key(key), "{}: {key}", tool.name);
+        }
+        let required = tool.input_schema["required"].as_array();
+        if required.is_some_and(Vec::is_empty) {
+            assert!(args_for(&tool.name, json!({})).is_ok(), "{}", tool.name);
+        }
+    }
+}
+
+#[test]
+fn commands_map_arguments_to_flags() -> Result<()> {
+    assert_eq!(
+        args_for("csa_run", json!({"prompt": "fix it", "tier": "tier-2"}))?,
+        [
+            "run",
+            "--cd",
+            "/work/project",
+            "--tier",
+            "tier-2",
+            "--prompt",
+            "fix it"
+        ]
+    );
+    assert_eq!(
+        args_for("csa_review", json!({}))?,
+        ["review", "--cd", "/work/project", "--diff"]
+    );
+    assert_eq!(
+        args_for(
+            "csa_review",
+            json!({"range": "main..HEAD", "tool": "codex"})
+        )?,
+        [
+            "review",
+            "--cd",
+            "/work/project",
+            "--range",
+            "main..HEAD",
+            "--tool",
+            "codex"
+        ]
+    );
+    assert_eq!(
+        args_for("csa_session_list", json!({"limit": 5, "status": "active"}))?,
+        [
+            "--format",
+            "json",
+            "session",
+            "list",
+            "--cd",
+            "/work/project",
+            "--limit",
+            "5",
+            "--status",
+            "active"
+        ]
+    );
+    assert_eq!(
+        args_for("csa_todo_create", json!({"title": "--not-a-flag"}))?,
+        [
+            "--format",
+            "json",
+            "todo",
+            "create",
+            "--cd",
+            "/work/project",
+            "--",
+            "--not-a-flag"
+        ]
+    );
+    assert_eq!(
+        args_for("csa_todo_show", json!({"spec": true}))?,
+        ["todo", "show", "--cd", "/work/project", "--spec"]
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    let error = args_for("csa_run", json!({})).expect_err("prompt is required");
+    assert!(error.to_string().contains("prompt"), "{error}");
+    let error = args_for("csa_review", json!({"branch": "main", "commit": "abc"}))
+        .expect_err("one scope only");
+    assert!(error.to_string().contains("at most one"), "{error}");
+    let error = args_for("csa_session_list", json!({"limit": "ten"})).expect_err("integer");
+    assert!(error.to_string().contains("limit"), "{error}");
+}
+
+#[test]
+fn agent_tools_are_opt_in_and_never_served_to_http_clients() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let names = |tools: &CsaTools| -> Vec<String> {
+        tools
+            .tools()
+            .iter()
+            .map(|tool| tool.name.to_string())
+            .collect()
+    };
+    let program = temp.path().join("csa");
+
+    let default = CsaTools::new(
+        program.clone(),
+        temp.path().to_path_buf(),
+        &McpCsaToolsConfig::default(),
+    );
+    assert!(
+        !names(&default)
+            .iter()
+            .any(|name| AGENT_TOOLS.contains(&name.as_str()))
+    );
+    let error = default
+        .check_call("csa_run", temp.path())
+        .expect_err("agent tools are off by default");
+    assert!(error.to_string().contains("allow_agents"), "{error}");
+
+    let allowed = CsaTools::new(
+        program,
+        temp.path().to_path_buf(),
+        &McpCsaToolsConfig {
+            allow_agents: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(names(&allowed).len(), CsaTools::all_tools().len());
+    allowed.check_call("csa_review", temp.path())?;
+    let remote = allowed.without_agents();
+    assert_eq!(names(&remote), names(&default));
+    assert!(remote.check_call("csa_review", temp.path()).is_err());
+    Ok(())
+}
+
+#[test]
+fn project_root_is_confined_to_served_projects() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let hub_project = temp.path().join("hub");
+    let listed = temp.path().join("listed");
+    let other = temp.path().join("other");
+    for dir in [&hub_project, &listed, &other] {
+        fs::create_dir_all(dir.join("sub"))?;
+    }
+    let tools = CsaTools::new(
+        temp.path().join("csa"),
+        hub_project.clone(),
+        &McpCsaToolsConfig {
+            projects: vec![listed.clone()],
+            ..Default::default()
+        },
+    );
+
+    tools.check_call("csa_todo_list", &hub_project)?;
+    tools.check_call("csa_todo_list", &listed.join("sub"))?;
+    let error = tools
+        .check_call("csa_todo_list", &other)
+        .expect_err("unlisted project");
+    assert!(
+        error.to_string().contains("[mcp.csa_tools] projects"),
+        "{error}"
+    );
+    let error = tools
+        .check_call("csa_todo_list", &hub_project.join("sub/../../other"))
+        .expect_err("escape via ..");
+    assert!(error.to_string().contains("not a project"), "{error}");
+    let error = tools
+        .check_call("csa_todo_list", Path::new("hub"))
+        .expect_err("relative root");
+    assert!(error.to_string().contains("absolute"), "{error}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn call_returns_structured_output() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let other = tempfile::tempdir()?;
+    let tools = CsaTools::new(
+        write_fake_csa(temp.path())?,
+        temp.path().to_path_buf(),
+        &McpCsaToolsConfig {
+            allow_agents: true,
+            projects: vec![other.path().to_path_buf()],
+        },
+    );
+
+    let run = tools
+        .call(
+            &CallToolRequestParams::new("csa_run").with_arguments(object(json!({"prompt": "hi"}))),
+            None,
+        )
+        .await?;
+    assert_eq!(
+        run.structured_content,
+        Some(json!({"session_id": "01JTESTSESSION"}))
+    );
+
+    let list = tools
+        .call(
+            &CallToolRequestParams::new("csa_todo_list"),
+            Some(other.path().to_path_buf()),
+        )
+        .await?;
+    let argv = list.structured_content.expect("structured")["items"][0]["argv"].clone();
+    assert_eq!(
+        argv,
+        format!("--format json todo list --cd {}", other.path().display())
+    );
+
+    let show = tools
+        .call(
+            &CallToolRequestParams::new("csa_session_show")
+                .with_arguments(object(json!({"session_id": "01J"}))),
+            None,
+        )
+        .await?;
+    assert_eq!(show.is_error, Some(true));
+    let message = show.content[0].as_text().map(|t| t.text.as_str());
+    assert!(
+        message.is_some_and(|text| text.contains("unknown command: session")),
+        "{message:?}"
+    );
+    Ok(())
+}
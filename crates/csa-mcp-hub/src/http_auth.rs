@@ -0,0 +1,210 @@
+//! Bearer-token authentication for the MCP hub HTTP endpoint.
+//!
+//! The Unix socket is guarded by filesystem permissions and peer uid checks;
+//! the HTTP endpoint has neither, so clients reaching it from containers or
+//! other hosts present a per-client token in `Authorization: Bearer`. Tokens
+//! live in a TOML file that must not be readable by other users:
+//!
+//! ```toml
+//! [[client]]
+//! name = "devcontainer"
+//! token = "..."
+//! tool = "codex"  # consumer tool for `[mcp_filters]`; defaults to `name`
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use csa_config::paths;
+use serde::Deserialize;
+
+const TOKENS_FILE_NAME: &str = "mcp-hub-tokens.toml";
+
+/// Shortest token accepted; anything shorter is trivially guessable.
+const MIN_TOKEN_LEN: usize = 16;
+
+#[derive(Debug, Clone, Deserialize)]
+struct TokenEntry {
+    name: String,
+    token: String,
+    #[serde(default)]
+    tool: Option<String>,
+}
+
+impl TokenEntry {
+    fn consumer(&self) -> &str {
+        self.tool.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Consumer tool of an authenticated HTTP request, stored in its extensions.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpConsumer(pub(crate) String);
+
+#[derive(Debug, Default, Deserialize)]
+struct TokensFile {
+    #[serde(default)]
+    client: Vec<TokenEntry>,
+}
+
+/// Clients allowed to use the HTTP endpoint. Empty means no authentication.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HttpTokens {
+    clients: Vec<TokenEntry>,
+    source: Option<PathBuf>,
+}
+
+impl HttpTokens {
+    /// Load `path_override`, or the default tokens file when it exists.
+    pub(crate) fn load(path_override: Option<PathBuf>) -> Result<Self> {
+        match path_override {
+            Some(path) => Self::load_file(&path),
+            None => match default_tokens_path().filter(|path| path.exists()) {
+                Some(path) => Self::load_file(&path),
+                None => Ok(Self::default()),
+            },
+        }
+    }
+
+    pub(crate) fn load_file(path: &Path) -> Result<Self> {
+        check_private(path)?;
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read mcp-hub tokens: {}", path.display()))?;
+        let file: TokensFile = toml::from_str(&raw)
+            .with_context(|| format!("failed to parse mcp-hub tokens: {}", path.display()))?;
+
+        for (index, entry) in file.client.iter().enumerate() {
+            if entry.name.trim().is_empty() {
+                bail!(
+                    "mcp-hub token #{} in {} has no name",
+                    index + 1,
+                    path.display()
+                );
+            }
+            if entry.token.len() < MIN_TOKEN_LEN {
+                bail!(
+                    "mcp-hub token for '{}' in {} is shorter than {MIN_TOKEN_LEN} characters",
+                    entry.name,
+                    path.display()
+                );
+            }
+            if file.client[..index]
+                .iter()
+                .any(|other| other.name == entry.name || other.token == entry.token)
+            {
+                bail!(
+                    "mcp-hub token for '{}' in {} duplicates an earlier name or token",
+                    entry.name,
+                    path.display()
+                );
+            }
+        }
+
+        Ok(Self {
+            clients: file.client,
+            source: Some(path.to_path_buf()),
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// File the tokens were loaded from.
+    pub(crate) fn source(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    fn entry_for(&self, token: &str) -> Option<&TokenEntry> {
+        // Every entry is compared so the time taken does not reveal which one matched.
+        self.clients.iter().fold(None, |found, entry| {
+            let matches = constant_time_eq(entry.token.as_bytes(), token.as_bytes());
+            found.or(matches.then_some(entry))
+        })
+    }
+}
+
+pub(crate) fn default_tokens_path() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join(TOKENS_FILE_NAME))
+}
+
+#[cfg(unix)]
+fn check_private(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("failed to stat mcp-hub tokens: {}", path.display()))?;
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        bail!(
+            "mcp-hub tokens file {} is accessible by other users (mode {mode:o}); run `chmod 600` on it",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Axum middleware rejecting requests without a known bearer token.
+pub(crate) async fn require_token(
+    State(tokens): State<Arc<HttpTokens>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if tokens.is_empty() {
+        return next.run(request).await;
+    }
+
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    match presented.and_then(|token| tokens.entry_for(token)) {
+        Some(client) => {
+            tracing::debug!(
+                client = %client.name,
+                consumer = %client.consumer(),
+                method = %request.method(),
+                "mcp-hub HTTP request"
+            );
+            request
+                .extensions_mut()
+                .insert(HttpConsumer(client.consumer().to_string()));
+            next.run(request).await
+        }
+        None => {
+            tracing::warn!(
+                has_token = presented.is_some(),
+                "rejecting mcp-hub HTTP request: missing or unknown bearer token"
+            );
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                "missing or invalid mcp-hub token",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "http_auth_tests.rs"]
+mod tests;
@@ -0,0 +1,132 @@
+use super::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const TOKEN_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaa";
+const TOKEN_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbb";
+
+fn write_tokens(dir: &Path, contents: &str) -> PathBuf {
+    let path = dir.join(TOKENS_FILE_NAME);
+    std::fs::write(&path, contents).expect("write tokens");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .expect("chmod tokens");
+    }
+    path
+}
+
+fn two_clients(dir: &Path) -> PathBuf {
+    write_tokens(
+        dir,
+        &format!(
+            "[[client]]\nname = \"devcontainer\"\ntoken = \"{TOKEN_A}\"\n\n\
+             [[client]]\nname = \"ci\"\ntoken = \"{TOKEN_B}\"\n"
+        ),
+    )
+}
+
+fn client_for<'a>(tokens: &'a HttpTokens, token: &str) -> Option<&'a str> {
+    tokens.entry_for(token).map(|entry| entry.name.as_str())
+}
+
+#[test]
+fn tokens_identify_their_client() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let tokens = HttpTokens::load_file(&two_clients(tmp.path())).expect("load tokens");
+
+    assert!(!tokens.is_empty());
+    assert_eq!(client_for(&tokens, TOKEN_A), Some("devcontainer"));
+    assert_eq!(client_for(&tokens, TOKEN_B), Some("ci"));
+    assert_eq!(client_for(&tokens, "cccccccccccccccccccccccc"), None);
+    assert_eq!(client_for(&tokens, ""), None);
+}
+
+#[test]
+fn missing_override_file_is_an_error() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let result = HttpTokens::load(Some(tmp.path().join("absent.toml")));
+    assert!(result.is_err());
+}
+
+#[test]
+fn short_and_duplicate_tokens_are_rejected() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let short = write_tokens(tmp.path(), "[[client]]\nname = \"a\"\ntoken = \"short\"\n");
+    let error = HttpTokens::load_file(&short).unwrap_err().to_string();
+    assert!(error.contains("shorter than"), "{error}");
+
+    let duplicate = write_tokens(
+        tmp.path(),
+        &format!(
+            "[[client]]\nname = \"a\"\ntoken = \"{TOKEN_A}\"\n\n\
+             [[client]]\nname = \"b\"\ntoken = \"{TOKEN_A}\"\n"
+        ),
+    );
+    let error = HttpTokens::load_file(&duplicate).unwrap_err().to_string();
+    assert!(error.contains("duplicates"), "{error}");
+}
+
+#[cfg(unix)]
+#[test]
+fn readable_tokens_file_is_rejected() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let path = two_clients(tmp.path());
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).expect("chmod");
+
+    let error = HttpTokens::load_file(&path).unwrap_err().to_string();
+    assert!(error.contains("chmod 600"), "{error}");
+}
+
+async fn status_for(tokens: HttpTokens, authorization: Option<&str>) -> String {
+    let app = axum::Router::new()
+        .route("/mcp", axum::routing::post(|| async { "ok" }))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(tokens),
+            require_token,
+        ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    let auth_line = authorization
+        .map(|value| format!("Authorization: {value}\r\n"))
+        .unwrap_or_default();
+    let request = format!(
+        "POST /mcp HTTP/1.1\r\nHost: {addr}\r\n{auth_line}Content-Length: 0\r\nConnection: close\r\n\r\n"
+    );
+    stream.write_all(request.as_bytes()).await.expect("write");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.expect("read");
+    server.abort();
+
+    response.lines().next().unwrap_or_default().to_string()
+}
+
+#[tokio::test]
+async fn middleware_requires_a_known_bearer_token() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let tokens = HttpTokens::load_file(&two_clients(tmp.path())).expect("load tokens");
+
+    let accepted = status_for(tokens.clone(), Some(&format!("Bearer {TOKEN_B}"))).await;
+    assert!(accepted.contains("200"), "{accepted}");
+
+    let missing = status_for(tokens.clone(), None).await;
+    assert!(missing.contains("401"), "{missing}");
+
+    let wrong = status_for(tokens, Some("Bearer cccccccccccccccccccccccc")).await;
+    assert!(wrong.contains("401"), "{wrong}");
+}
+
+#[tokio::test]
+async fn middleware_is_open_without_tokens() {
+    let status = status_for(HttpTokens::default(), None).await;
+    assert!(status.contains("200"), "{status}");
+}
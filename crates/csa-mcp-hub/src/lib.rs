@@ -1,12 +1,23 @@
 //! Shared MCP hub implementation used by the csa CLI wrapper.
 
+mod call_cache;
 mod config;
+mod config_reload;
+mod csa_tools;
+mod http_auth;
+mod metrics;
+mod metrics_endpoint;
 mod proxy;
+mod rate_limit;
 mod registry;
 mod serve;
 mod skill_writer;
 mod socket;
+mod tool_filter;
+mod tool_names;
+mod tool_skills;
 
 pub use serve::{
-    handle_gen_skill_command, handle_serve_command, handle_status_command, handle_stop_command,
+    HubSession, handle_gen_skill_command, handle_serve_command, handle_status_command,
+    handle_stop_command, open_session,
 };
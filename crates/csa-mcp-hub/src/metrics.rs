@@ -0,0 +1,358 @@
+//! Per-tool call metrics and the optional request log.
+//!
+//! Every forwarded `tools/call` is counted per `(server, tool)` with its
+//! outcome and latency. `hub/stats` returns the totals for
+//! `csa mcp-hub status --stats`; with `[mcp] request_log = true` each call is
+//! also appended as one JSON line to `<state_dir>/mcp-hub/requests.jsonl`.
+//! The same calls feed the Prometheus series served by `[metrics]`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use csa_config::paths;
+use csa_core::metrics::{LATENCY_BUCKETS, Registry};
+use serde::Serialize;
+
+const REQUEST_LOG_DIR: &str = "mcp-hub";
+const REQUEST_LOG_FILE: &str = "requests.jsonl";
+
+/// Latency samples kept per tool; percentiles cover the most recent calls.
+const LATENCY_SAMPLES: usize = 1024;
+
+/// How a forwarded tool call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CallOutcome {
+    Ok,
+    /// The server answered with `isError: true`.
+    ToolError,
+    /// Forwarding failed or the server is down.
+    Failed,
+    TimedOut,
+    /// Answered from the hub's response cache.
+    Cached,
+}
+
+impl CallOutcome {
+    fn is_error(self) -> bool {
+        !matches!(self, Self::Ok | Self::Cached)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::ToolError => "tool_error",
+            Self::Failed => "failed",
+            Self::TimedOut => "timed_out",
+            Self::Cached => "cached",
+        }
+    }
+}
+
+/// One forwarded tool call.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CallRecord<'a> {
+    pub(crate) consumer: Option<&'a str>,
+    pub(crate) server: &'a str,
+    pub(crate) tool: &'a str,
+    pub(crate) outcome: CallOutcome,
+    pub(crate) latency_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct ToolCounters {
+    calls: u64,
+    errors: u64,
+    latencies_ms: VecDeque<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ToolStats {
+    pub(crate) tool: String,
+    pub(crate) calls: u64,
+    pub(crate) errors: u64,
+    pub(crate) p50_ms: u64,
+    pub(crate) p95_ms: u64,
+    pub(crate) p99_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ServerStats {
+    pub(crate) server: String,
+    pub(crate) calls: u64,
+    pub(crate) errors: u64,
+    pub(crate) tools: Vec<ToolStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct StatsSnapshot {
+    pub(crate) since: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) request_log: Option<PathBuf>,
+    pub(crate) servers: Vec<ServerStats>,
+}
+
+#[derive(Debug)]
+pub(crate) struct HubMetrics {
+    since: DateTime<Utc>,
+    counters: Mutex<BTreeMap<(String, String), ToolCounters>>,
+    request_log: Option<RequestLog>,
+    prometheus: Registry,
+}
+
+impl Default for HubMetrics {
+    fn default() -> Self {
+        Self {
+            since: Utc::now(),
+            counters: Mutex::default(),
+            request_log: None,
+            prometheus: Registry::default(),
+        }
+    }
+}
+
+impl HubMetrics {
+    /// Metrics that also append every call to the log at `path`.
+    pub(crate) fn with_request_log(path: &Path) -> Result<Self> {
+        Ok(Self {
+            request_log: Some(RequestLog::open(path)?),
+            ..Self::default()
+        })
+    }
+
+    pub(crate) fn record(&self, call: &CallRecord<'_>) {
+        {
+            let mut counters = self
+                .counters
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let entry = counters
+                .entry((call.server.to_string(), call.tool.to_string()))
+                .or_default();
+            entry.calls += 1;
+            if call.outcome.is_error() {
+                entry.errors += 1;
+            }
+            if entry.latencies_ms.len() == LATENCY_SAMPLES {
+                entry.latencies_ms.pop_front();
+            }
+            entry.latencies_ms.push_back(call.latency_ms);
+        }
+
+        self.prometheus.inc(
+            "csa_mcp_proxy_calls_total",
+            "Tool calls handled by the MCP hub, by outcome.",
+            &[
+                ("server", call.server),
+                ("tool", call.tool),
+                ("outcome", call.outcome.as_str()),
+            ],
+            1.0,
+        );
+        self.prometheus.observe(
+            "csa_mcp_proxy_call_duration_seconds",
+            "Latency of tool calls handled by the MCP hub.",
+            &[("server", call.server), ("tool", call.tool)],
+            LATENCY_BUCKETS,
+            call.latency_ms as f64 / 1000.0,
+        );
+
+        if let Some(log) = &self.request_log
+            && let Err(error) = log.append(call)
+        {
+            tracing::warn!(error = %error, "failed to write mcp-hub request log");
+        }
+    }
+
+    /// Prometheus series of the hub, shared with the run metrics it serves.
+    pub(crate) fn prometheus(&self) -> &Registry {
+        &self.prometheus
+    }
+
+    pub(crate) fn snapshot(&self) -> StatsSnapshot {
+        let counters = self
+            .counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut servers: Vec<ServerStats> = Vec::new();
+        for ((server, tool), counter) in counters.iter() {
+            let mut sorted: Vec<u64> = counter.latencies_ms.iter().copied().collect();
+            sorted.sort_unstable();
+            let stats = ToolStats {
+                tool: tool.clone(),
+                calls: counter.calls,
+                errors: counter.errors,
+                p50_ms: percentile(&sorted, 50),
+                p95_ms: percentile(&sorted, 95),
+                p99_ms: percentile(&sorted, 99),
+            };
+            // Keys are sorted, so a server's tools are contiguous.
+            match servers.last_mut() {
+                Some(last) if last.server == *server => {
+                    last.calls += stats.calls;
+                    last.errors += stats.errors;
+                    last.tools.push(stats);
+                }
+                _ => servers.push(ServerStats {
+                    server: server.clone(),
+                    calls: stats.calls,
+                    errors: stats.errors,
+                    tools: vec![stats],
+                }),
+            }
+        }
+        StatsSnapshot {
+            since: self.since,
+            request_log: self.request_log.as_ref().map(|log| log.path.clone()),
+            servers,
+        }
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (sorted.len() * pct).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+pub(crate) fn latency_ms(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+pub(crate) fn default_request_log_path() -> Option<PathBuf> {
+    paths::session_state_dir().map(|dir| dir.join(REQUEST_LOG_DIR).join(REQUEST_LOG_FILE))
+}
+
+#[derive(Debug)]
+struct RequestLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+#[derive(Serialize)]
+struct RequestLogLine<'a> {
+    timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    call: &'a CallRecord<'a>,
+}
+
+impl RequestLog {
+    fn open(path: &Path) -> Result<Self> {
+        paths::create_session_state_dir()
+            .context("failed to create the session state directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create request log dir: {}", parent.display())
+            })?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open request log: {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, call: &CallRecord<'_>) -> Result<()> {
+        let mut line = serde_json::to_string(&RequestLogLine {
+            timestamp: Utc::now(),
+            call,
+        })
+        .context("failed to serialize request log line")?;
+        line.push('\n');
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("failed to append to {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call<'a>(server: &'a str, tool: &'a str, outcome: CallOutcome, ms: u64) -> CallRecord<'a> {
+        CallRecord {
+            consumer: Some("codex"),
+            server,
+            tool,
+            outcome,
+            latency_ms: ms,
+        }
+    }
+
+    #[test]
+    fn snapshot_groups_tools_by_server() {
+        let metrics = HubMetrics::default();
+        for ms in 1..=100 {
+            metrics.record(&call("github", "search", CallOutcome::Ok, ms));
+        }
+        metrics.record(&call("github", "create_issue", CallOutcome::ToolError, 7));
+        metrics.record(&call("fs", "read", CallOutcome::TimedOut, 30_000));
+
+        let snapshot = metrics.snapshot();
+        let names: Vec<&str> = snapshot.servers.iter().map(|s| s.server.as_str()).collect();
+        assert_eq!(names, ["fs", "github"]);
+
+        let github = &snapshot.servers[1];
+        assert_eq!((github.calls, github.errors), (101, 1));
+        let search = &github.tools[1];
+        assert_eq!(search.tool, "search");
+        assert_eq!((search.p50_ms, search.p95_ms, search.p99_ms), (50, 95, 99));
+        assert_eq!(snapshot.servers[0].tools[0].errors, 1);
+    }
+
+    #[test]
+    fn latency_samples_are_bounded() {
+        let metrics = HubMetrics::default();
+        for _ in 0..LATENCY_SAMPLES {
+            metrics.record(&call("fs", "read", CallOutcome::Ok, 1_000));
+        }
+        for _ in 0..LATENCY_SAMPLES {
+            metrics.record(&call("fs", "read", CallOutcome::Ok, 5));
+        }
+
+        let read = &metrics.snapshot().servers[0].tools[0];
+        assert_eq!(read.calls, 2 * LATENCY_SAMPLES as u64);
+        assert_eq!(read.p99_ms, 5);
+    }
+
+    #[test]
+    fn request_log_appends_json_lines() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("nested").join(REQUEST_LOG_FILE);
+        let metrics = HubMetrics::with_request_log(&path).expect("open log");
+        metrics.record(&call("github", "search", CallOutcome::Ok, 12));
+        metrics.record(&call("github", "search", CallOutcome::Failed, 3));
+
+        let contents = std::fs::read_to_string(&path).expect("read log");
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["consumer"], "codex");
+        assert_eq!(lines[0]["tool"], "search");
+        assert_eq!(lines[0]["latency_ms"], 12);
+        assert_eq!(lines[1]["outcome"], "failed");
+        assert!(lines[1]["timestamp"].as_str().is_some());
+        assert_eq!(
+            metrics.snapshot().request_log.as_deref(),
+            Some(path.as_path())
+        );
+    }
+}
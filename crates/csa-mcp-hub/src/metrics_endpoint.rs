@@ -0,0 +1,215 @@
+//! Prometheus endpoint enabled by `[metrics] enabled = true`.
+//!
+//! `GET /metrics` serves the hub's MCP proxy series, the run, failover and
+//! slot-wait events csa processes spooled since the hub started, and the size
+//! of the memory store.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use csa_config::{MetricsConfig, paths};
+use csa_core::metrics::SpoolReader;
+use tokio_util::sync::CancellationToken;
+
+use crate::metrics::HubMetrics;
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Where csa-memory keeps its default store.
+fn memory_store_path() -> Option<PathBuf> {
+    paths::session_state_dir().map(|dir| dir.join("memory").join("memories.jsonl"))
+}
+
+/// Everything a scrape reports.
+#[derive(Clone)]
+pub(crate) struct Scrape {
+    metrics: Arc<HubMetrics>,
+    spool: Option<Arc<Mutex<SpoolReader>>>,
+    memory_store: Option<PathBuf>,
+}
+
+impl Scrape {
+    pub(crate) fn new(metrics: Arc<HubMetrics>) -> Self {
+        Self {
+            metrics,
+            spool: MetricsConfig::events_path()
+                .map(|path| Arc::new(Mutex::new(SpoolReader::from_end(path)))),
+            memory_store: memory_store_path(),
+        }
+    }
+
+    fn render(&self) -> String {
+        let registry = self.metrics.prometheus();
+        if let Some(spool) = &self.spool {
+            let mut spool = spool
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for event in spool.read_new() {
+                registry.apply(&event);
+            }
+        }
+        if let Some(path) = &self.memory_store {
+            let (bytes, entries) = memory_store_size(path);
+            registry.set(
+                "csa_memory_store_bytes",
+                "Size of the csa memory store.",
+                &[],
+                bytes as f64,
+            );
+            registry.set(
+                "csa_memory_store_entries",
+                "Entries in the csa memory store.",
+                &[],
+                entries as f64,
+            );
+        }
+        registry.render()
+    }
+}
+
+/// Bytes and JSON lines of the memory store; zero when it does not exist.
+fn memory_store_size(path: &Path) -> (u64, usize) {
+    match std::fs::read(path) {
+        Ok(content) => (
+            content.len() as u64,
+            content.iter().filter(|byte| **byte == b'\n').count(),
+        ),
+        Err(_) => (0, 0),
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MetricsEndpoint {
+    pub(crate) addr: SocketAddr,
+    shutdown: CancellationToken,
+    server_task: tokio::task::JoinHandle<()>,
+}
+
+impl MetricsEndpoint {
+    pub(crate) async fn start(listen: &str, scrape: Scrape) -> Result<Self> {
+        let listener = tokio::net::TcpListener::bind(listen)
+            .await
+            .with_context(|| format!("failed to bind metrics endpoint at {listen}"))?;
+        let addr = listener
+            .local_addr()
+            .context("failed to resolve local metrics address")?;
+
+        let app = axum::Router::new().route(
+            METRICS_PATH,
+            axum::routing::get(move || {
+                let scrape = scrape.clone();
+                async move {
+                    // Reading the spool and memory store is blocking file I/O.
+                    let body = tokio::task::spawn_blocking(move || scrape.render())
+                        .await
+                        .unwrap_or_default();
+                    ([(axum::http::header::CONTENT_TYPE, CONTENT_TYPE)], body)
+                }
+            }),
+        );
+        let shutdown = CancellationToken::new();
+        let server_shutdown = shutdown.clone();
+        let server_task = tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    server_shutdown.cancelled().await;
+                })
+                .await
+            {
+                tracing::warn!(error = %error, "metrics endpoint stopped with error");
+            }
+        });
+
+        Ok(Self {
+            addr,
+            shutdown,
+            server_task,
+        })
+    }
+
+    pub(crate) fn url(&self) -> String {
+        format!("http://{}{METRICS_PATH}", self.addr)
+    }
+
+    pub(crate) async fn shutdown(self) {
+        self.shutdown.cancel();
+        if let Err(error) = self.server_task.await {
+            tracing::debug!(error = %error, "metrics endpoint join failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{CallOutcome, CallRecord};
+    use csa_core::metrics::{MetricEvent, append_event};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn get(addr: SocketAddr) -> String {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .expect("request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("response");
+        response
+    }
+
+    #[tokio::test]
+    async fn scrape_reports_proxy_calls_spooled_runs_and_memory_size() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let spool = tmp.path().join("events.jsonl");
+        let memory_store = tmp.path().join("memories.jsonl");
+        std::fs::write(&memory_store, "{\"id\":1}\n{\"id\":2}\n").expect("memory");
+        let metrics = Arc::new(HubMetrics::default());
+        metrics.record(&CallRecord {
+            consumer: None,
+            server: "github",
+            tool: "search",
+            outcome: CallOutcome::Ok,
+            latency_ms: 30,
+        });
+        let scrape = Scrape {
+            metrics,
+            spool: Some(Arc::new(Mutex::new(SpoolReader::from_end(spool.clone())))),
+            memory_store: Some(memory_store),
+        };
+        append_event(
+            &spool,
+            &MetricEvent::Failover {
+                from: "codex".to_string(),
+                to: "claude-code".to_string(),
+            },
+        )
+        .expect("spool");
+
+        let endpoint = MetricsEndpoint::start("127.0.0.1:0", scrape)
+            .await
+            .expect("start");
+        let response = get(endpoint.addr).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("content-type: text/plain; version=0.0.4"));
+        assert!(response.contains(
+            "csa_mcp_proxy_calls_total{server=\"github\",tool=\"search\",outcome=\"ok\"} 1\n"
+        ));
+        assert!(response.contains(
+            "csa_mcp_proxy_call_duration_seconds_bucket{server=\"github\",tool=\"search\",le=\"0.05\"} 1\n"
+        ));
+        assert!(response.contains("csa_failovers_total{from=\"codex\",to=\"claude-code\"} 1\n"));
+        assert!(response.contains("csa_memory_store_bytes 18\n"));
+        assert!(response.contains("csa_memory_store_entries 2\n"));
+
+        // Spooled events are counted once.
+        let response = get(endpoint.addr).await;
+        assert!(response.contains("csa_failovers_total{from=\"codex\",to=\"claude-code\"} 1\n"));
+        endpoint.shutdown().await;
+    }
+}
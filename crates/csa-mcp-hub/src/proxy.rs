@@ -1,8 +1,9 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use csa_config::McpFilter;
 use rmcp::model::{
     CallToolRequestParams, CallToolResult, ListToolsResult, PaginatedRequestParams,
     ServerCapabilities, ServerInfo,
@@ -11,16 +12,28 @@ use rmcp::service::RequestContext;
 use rmcp::{ErrorData as McpError, RoleServer, ServerHandler};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, broadcast};
 use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
 
-use crate::registry::{McpRegistry, ToolCallRoute};
+use crate::call_cache::CallCache;
+use crate::csa_tools::{self, CsaTools};
+use crate::http_auth::HttpConsumer;
+use crate::metrics::{self, CallOutcome, CallRecord, HubMetrics};
+use crate::rate_limit::ServerRateLimits;
+use crate::registry::{HealthState, McpRegistry, ToolCallRoute};
+use crate::tool_filter::{self, ToolFilters};
+use crate::tool_names::{self, ToolCollision};
+
+const LIST_CHANGED_CAPACITY: usize = 16;
 
 /// Cached metadata for a single MCP tool, stored alongside its routing info.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ToolDescriptor {
     pub(crate) server_name: String,
+    /// Name of the tool on its server; differs from the exposed name when
+    /// renamed or namespaced.
+    pub(crate) tool_name: String,
     pub(crate) description: Option<String>,
     pub(crate) input_schema: Value,
 }
@@ -37,7 +50,20 @@ pub(crate) struct ToolSummary {
 pub(crate) struct ProxyRouter {
     registry: Arc<McpRegistry>,
     pub(crate) tool_cache: Arc<RwLock<HashMap<String, ToolDescriptor>>>,
+    /// Names several servers claimed in the last `tools/list`.
+    tool_collisions: Arc<RwLock<Vec<ToolCollision>>>,
     request_timeout: Duration,
+    tool_filters: Arc<ToolFilters>,
+    /// Consumer tool of a socket connection; HTTP requests carry their own.
+    consumer: Option<Arc<str>>,
+    /// Project of a session connection, for calls that name no `project_root`.
+    project_root: Option<Arc<Path>>,
+    metrics: Arc<HubMetrics>,
+    call_cache: Arc<CallCache>,
+    rate_limits: Arc<ServerRateLimits>,
+    csa_tools: Option<Arc<CsaTools>>,
+    /// Fires when the set of tools changed, e.g. after a registry reload.
+    list_changed: broadcast::Sender<()>,
 }
 
 impl ProxyRouter {
@@ -45,33 +71,139 @@ impl ProxyRouter {
         Self {
             registry,
             tool_cache: Arc::new(RwLock::new(HashMap::new())),
+            tool_collisions: Arc::default(),
             request_timeout,
+            tool_filters: Arc::default(),
+            consumer: None,
+            project_root: None,
+            metrics: Arc::default(),
+            call_cache: Arc::default(),
+            rate_limits: Arc::default(),
+            csa_tools: None,
+            list_changed: broadcast::channel(LIST_CHANGED_CAPACITY).0,
+        }
+    }
+
+    pub(crate) fn with_metrics(mut self, metrics: Arc<HubMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub(crate) fn with_call_cache(mut self, call_cache: CallCache) -> Self {
+        self.call_cache = Arc::new(call_cache);
+        self
+    }
+
+    pub(crate) fn with_rate_limits(mut self, rate_limits: ServerRateLimits) -> Self {
+        self.rate_limits = Arc::new(rate_limits);
+        self
+    }
+
+    /// Serve the built-in `csa` tools, unless a configured server is already
+    /// named `csa`.
+    pub(crate) fn with_csa_tools(mut self, builtin: CsaTools) -> Self {
+        if self
+            .registry
+            .server_names()
+            .iter()
+            .any(|name| name == csa_tools::SERVER_NAME)
+        {
+            tracing::warn!("MCP server 'csa' is configured; built-in csa tools are disabled");
+            return self;
+        }
+        self.csa_tools = Some(Arc::new(builtin));
+        self
+    }
+
+    pub(crate) fn with_tool_filters(mut self, tool_filters: ToolFilters) -> Self {
+        self.tool_filters = Arc::new(tool_filters);
+        self
+    }
+
+    /// A router for HTTP clients, which may not start agents.
+    pub(crate) fn for_http(&self) -> Self {
+        Self {
+            csa_tools: self
+                .csa_tools
+                .as_deref()
+                .map(|builtin| Arc::new(builtin.without_agents())),
+            ..self.clone()
         }
     }
 
+    /// A router sharing this one's backends that serves `consumer`.
+    pub(crate) fn for_consumer(&self, consumer: Option<String>) -> Self {
+        Self {
+            consumer: consumer.map(Arc::from),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn with_project_root(mut self, project_root: PathBuf) -> Self {
+        self.project_root = Some(Arc::from(project_root));
+        self
+    }
+
+    pub(crate) fn subscribe_list_changed(&self) -> broadcast::Receiver<()> {
+        self.list_changed.subscribe()
+    }
+
+    /// Drop cached tool routes and tell subscribed clients to re-list tools.
+    pub(crate) async fn tools_changed(&self) {
+        self.tool_cache.write().await.clear();
+        self.tool_collisions.write().await.clear();
+        let _ = self.list_changed.send(());
+    }
+
+    fn connection_filters(&self) -> &[McpFilter] {
+        self.tool_filters.for_consumer(self.consumer.as_deref())
+    }
+
+    fn request_consumer<'a>(&'a self, context: &'a RequestContext<RoleServer>) -> Option<&'a str> {
+        self.consumer.as_deref().or_else(|| {
+            context
+                .extensions
+                .get::<axum::http::request::Parts>()
+                .and_then(|parts| parts.extensions.get::<HttpConsumer>())
+                .map(|consumer| consumer.0.as_str())
+        })
+    }
+
+    fn request_filters(&self, context: &RequestContext<RoleServer>) -> &[McpFilter] {
+        self.tool_filters
+            .for_consumer(self.request_consumer(context))
+    }
+
     pub(crate) async fn status_payload(&self) -> Value {
         let servers = self.registry.server_names();
         let tools_cached = self.tool_cache.read().await.len();
+        let health = self.registry.health_report().await;
+        let degraded = health
+            .iter()
+            .any(|report| report.state == HealthState::Down);
+        let collisions = self.tool_collisions.read().await.clone();
         json!({
             "running": true,
             "servers": servers,
             "toolsCached": tools_cached,
+            "degraded": degraded,
+            "health": health,
+            "toolCollisions": collisions,
         })
     }
 
+    pub(crate) fn stats_payload(&self) -> Value {
+        json!(self.metrics.snapshot())
+    }
+
     async fn list_tools_internal(&self) -> Result<ListToolsResult, McpError> {
         use rmcp::model::Tool;
         use tokio::task::JoinSet;
 
-        // Sort server names alphabetically for deterministic duplicate resolution:
-        // if two servers expose the same tool name, the alphabetically-later server wins.
-        let mut server_names = self.registry.server_names();
-        server_names.sort();
-
         // Query all backends concurrently.
         let mut join_set: JoinSet<(String, Result<Vec<Tool>, String>)> = JoinSet::new();
 
-        for server in server_names.clone() {
+        for server in self.registry.server_names() {
             let registry = Arc::clone(&self.registry);
             let request_timeout = self.request_timeout;
             join_set.spawn(async move {
@@ -96,7 +228,7 @@ impl ProxyRouter {
         }
 
         // Collect all results keyed by server name.
-        let mut results_by_server: HashMap<String, Vec<Tool>> = HashMap::new();
+        let mut results_by_server: BTreeMap<String, Vec<Tool>> = BTreeMap::new();
         while let Some(result) = join_set.join_next().await {
             match result {
                 Ok((server, Ok(server_tools))) => {
@@ -111,98 +243,202 @@ impl ProxyRouter {
             }
         }
 
-        // Aggregate in sorted server order for deterministic duplicate resolution.
-        let mut tools = Vec::new();
-        let mut cache = HashMap::new();
+        if let Some(builtin) = &self.csa_tools {
+            results_by_server.insert(csa_tools::SERVER_NAME.to_string(), builtin.tools());
+        }
 
-        for server in &server_names {
-            let Some(server_tools) = results_by_server.remove(server) else {
-                continue;
-            };
-            for tool in server_tools {
-                let name = tool.name.to_string();
-                if let Some(existing) = cache.get(&name) {
-                    let existing: &ToolDescriptor = existing;
-                    tracing::warn!(
-                        tool = %name,
-                        previous_server = %existing.server_name,
-                        new_server = %server,
-                        "duplicate tool name: alphabetically-later server overrides previous"
-                    );
-                }
-                cache.insert(
-                    name,
-                    ToolDescriptor {
-                        server_name: server.clone(),
-                        description: tool.description.as_ref().map(|d| d.to_string()),
-                        input_schema: Value::Object(tool.input_schema.as_ref().clone()),
-                    },
-                );
-                tools.push(tool);
+        // Servers that did not answer still claim the tools they last listed.
+        let mut down = BTreeSet::new();
+        for server in self.registry.server_names() {
+            if !results_by_server.contains_key(&server) {
+                let tools = self.registry.last_listed_tools(&server);
+                results_by_server.insert(server.clone(), tools);
+                down.insert(server);
             }
         }
+        let (exposed, collisions) =
+            tool_names::resolve(results_by_server, &down, &self.registry.tool_renames());
+        for collision in &collisions {
+            tracing::warn!(
+                tool = %collision.name,
+                servers = collision.tools.len(),
+                "duplicate MCP tool name; exposing it namespaced per server"
+            );
+        }
+
+        let mut tools = Vec::with_capacity(exposed.len());
+        let mut cache = HashMap::with_capacity(exposed.len());
+        for entry in exposed {
+            cache.insert(
+                entry.tool.name.to_string(),
+                ToolDescriptor {
+                    server_name: entry.server,
+                    tool_name: entry.upstream,
+                    description: entry.tool.description.as_ref().map(|d| d.to_string()),
+                    input_schema: Value::Object(entry.tool.input_schema.as_ref().clone()),
+                },
+            );
+            tools.push(entry.tool);
+        }
 
         *self.tool_cache.write().await = cache;
+        *self.tool_collisions.write().await = collisions;
         Ok(ListToolsResult::with_all_items(tools))
     }
 
+    async fn list_tools_filtered(
+        &self,
+        filters: &[McpFilter],
+    ) -> Result<ListToolsResult, McpError> {
+        let mut result = self.list_tools_internal().await?;
+        if !filters.is_empty() {
+            let cache = self.tool_cache.read().await;
+            result.tools.retain(|tool| {
+                cache.get(tool.name.as_ref()).is_some_and(|descriptor| {
+                    tool_filter::allows(filters, &descriptor.server_name, &descriptor.tool_name)
+                })
+            });
+        }
+        Ok(result)
+    }
+
     async fn call_tool_internal(
         &self,
         request: CallToolRequestParams,
+        consumer: Option<&str>,
     ) -> Result<CallToolResult, McpError> {
-        let tool_name = request.name.as_ref();
-        let mut server = self.lookup_tool_owner(tool_name).await;
+        let filters = self.tool_filters.for_consumer(consumer);
+        let tool_name = request.name.to_string();
+        let mut descriptor = self.cached_descriptor(&tool_name).await;
 
-        if server.is_none() {
+        if descriptor.is_none() {
             self.list_tools_internal().await?;
-            server = self.lookup_tool_owner(tool_name).await;
+            descriptor = self.cached_descriptor(&tool_name).await;
         }
 
-        let Some(server_name) = server else {
+        let Some(ToolDescriptor {
+            server_name,
+            tool_name: upstream_name,
+            ..
+        }) = descriptor
+        else {
             return Err(McpError::invalid_params(
                 format!("unknown MCP tool: {tool_name}"),
                 None,
             ));
         };
+        if !tool_filter::allows(filters, &server_name, &upstream_name) {
+            tracing::warn!(
+                tool = %tool_name,
+                server = %server_name,
+                "MCP tool call denied by consumer filter"
+            );
+            return Err(McpError::invalid_params(
+                format!("MCP tool '{tool_name}' is not available to this client"),
+                None,
+            ));
+        }
+
+        let started = Instant::now();
+        let cache_ttl = self.call_cache.ttl_for(&server_name, &upstream_name);
+        let cache_key = cache_ttl.map(|_| {
+            let project_root = call_route_from_request(&request).project_root;
+            let project_root = project_root.as_deref().or(self.project_root.as_deref());
+            CallCache::key(&server_name, &upstream_name, project_root, &request)
+        });
+        let cached = cache_key.as_ref().and_then(|key| self.call_cache.get(key));
+        let (outcome, result) = match cached {
+            Some(response) => (CallOutcome::Cached, Ok(response)),
+            None => {
+                let mut request = request;
+                request.name = upstream_name.clone().into();
+                let (outcome, result) = self.forward_call(&server_name, request).await;
+                if let (CallOutcome::Ok, Ok(response), Some(key), Some(ttl)) =
+                    (outcome, &result, cache_key, cache_ttl)
+                {
+                    self.call_cache.insert(key, ttl, response.clone());
+                }
+                (outcome, result)
+            }
+        };
+        self.metrics.record(&CallRecord {
+            consumer,
+            server: &server_name,
+            tool: &upstream_name,
+            outcome,
+            latency_ms: metrics::latency_ms(started.elapsed()),
+        });
+        result
+    }
 
+    /// Forward a call to its server, waiting out the server's rate limit.
+    async fn forward_call(
+        &self,
+        server_name: &str,
+        request: CallToolRequestParams,
+    ) -> (CallOutcome, Result<CallToolResult, McpError>) {
         let route = call_route_from_request(&request);
         let cancellation = CancellationToken::new();
-        match timeout(
-            self.request_timeout,
-            self.registry
-                .call_tool(&server_name, request, route, cancellation.clone()),
-        )
-        .await
-        {
-            Ok(Ok(response)) => Ok(response),
-            Ok(Err(error)) => Err(McpError::internal_error(
-                format!("forwarding to MCP server '{server_name}' failed: {error}"),
-                None,
-            )),
-            Err(_) => {
-                cancellation.cancel();
+        let forward = async {
+            self.rate_limits.acquire(server_name).await;
+            match &self.csa_tools {
+                Some(builtin) if server_name == csa_tools::SERVER_NAME => {
+                    builtin.call(&request, route.project_root).await
+                }
+                _ => {
+                    self.registry
+                        .call_tool(server_name, request, route, cancellation.clone())
+                        .await
+                }
+            }
+        };
+        match timeout(self.request_timeout, forward).await {
+            Ok(Ok(response)) => {
+                let outcome = if response.is_error == Some(true) {
+                    CallOutcome::ToolError
+                } else {
+                    CallOutcome::Ok
+                };
+                (outcome, Ok(response))
+            }
+            Ok(Err(error)) => (
+                CallOutcome::Failed,
                 Err(McpError::internal_error(
-                    format!(
-                        "forwarding to MCP server '{server_name}' timed out after {}s",
-                        self.request_timeout.as_secs()
-                    ),
+                    format!("forwarding to MCP server '{server_name}' failed: {error}"),
                     None,
-                ))
+                )),
+            ),
+            Err(_) => {
+                cancellation.cancel();
+                (
+                    CallOutcome::TimedOut,
+                    Err(McpError::internal_error(
+                        format!(
+                            "forwarding to MCP server '{server_name}' timed out after {}s",
+                            self.request_timeout.as_secs()
+                        ),
+                        None,
+                    )),
+                )
             }
         }
     }
 
-    async fn lookup_tool_owner(&self, tool_name: &str) -> Option<String> {
-        self.tool_cache
-            .read()
-            .await
-            .get(tool_name)
-            .map(|desc| desc.server_name.clone())
+    async fn cached_descriptor(&self, tool_name: &str) -> Option<ToolDescriptor> {
+        self.tool_cache.read().await.get(tool_name).cloned()
     }
 
     /// Look up the full cached descriptor for a tool by name.
     pub(crate) async fn get_tool_descriptor(&self, tool_name: &str) -> Option<ToolDescriptor> {
-        self.tool_cache.read().await.get(tool_name).cloned()
+        let filters = self.connection_filters();
+        self.tool_cache
+            .read()
+            .await
+            .get(tool_name)
+            .filter(|descriptor| {
+                tool_filter::allows(filters, &descriptor.server_name, &descriptor.tool_name)
+            })
+            .cloned()
     }
 
     /// Case-insensitive substring search over cached tools.
@@ -217,6 +453,7 @@ impl ProxyRouter {
         let query_truncated: String = query.chars().take(MAX_QUERY_LEN).collect();
         let query_lower = query_truncated.to_lowercase();
 
+        let filters = self.connection_filters();
         let cache = self.tool_cache.read().await;
         let mut results = Vec::new();
 
@@ -224,6 +461,9 @@ impl ProxyRouter {
             if results.len() >= limit {
                 break;
             }
+            if !tool_filter::allows(filters, &descriptor.server_name, &descriptor.tool_name) {
+                continue;
+            }
             let name_lower = name.to_lowercase();
             let desc_lower = descriptor
                 .description
@@ -282,312 +522,33 @@ impl ServerHandler for ProxyRouter {
     async fn list_tools(
         &self,
         _request: Option<PaginatedRequestParams>,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
-        self.list_tools_internal().await
+        self.list_tools_filtered(self.request_filters(&context))
+            .await
     }
 
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
-        self.call_tool_internal(request).await
+        self.call_tool_internal(request, self.request_consumer(&context))
+            .await
     }
 
     fn get_info(&self) -> ServerInfo {
         let mut info = ServerInfo::default();
         info.server_info.name = "csa-mcp-hub".to_string();
         info.server_info.version = env!("CARGO_PKG_VERSION").to_string();
-        info.capabilities = ServerCapabilities::builder().enable_tools().build();
+        info.capabilities = ServerCapabilities::builder()
+            .enable_tools()
+            .enable_tool_list_changed()
+            .build();
         info
     }
 }
 
 #[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
-    use std::fs;
-    use std::sync::Arc;
-    use std::time::Duration;
-
-    use anyhow::Result;
-    use csa_config::{McpServerConfig, McpTransport};
-    use rmcp::model::CallToolRequestParams;
-    use serde_json::json;
-
-    use crate::proxy::{ProxyRouter, ToolDescriptor};
-    use crate::registry::McpRegistry;
-
-    fn write_script(dir: &std::path::Path) -> Result<std::path::PathBuf> {
-        let path = dir.join("mock-mcp.sh");
-        fs::write(
-            &path,
-            r#"#!/bin/sh
-while IFS= read -r line; do
-  id=$(printf '%s\n' "$line" | sed -n 's/.*"id"[ ]*:[ ]*\([^,}]*\).*/\1/p')
-  case "$line" in
-    *\"initialize\"*)
-      printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{"tools":{}},"serverInfo":{"name":"mock","version":"0.1.0"}}}\n' "$id"
-      ;;
-    *\"notifications/initialized\"*)
-      ;;
-    *\"tools/list\"*)
-      printf '{"jsonrpc":"2.0","id":%s,"result":{"tools":[{"name":"echo_tool","description":"echo","inputSchema":{"type":"object","properties":{}}}]}}\n' "$id"
-      ;;
-    *\"tools/call\"*)
-      printf '{"jsonrpc":"2.0","id":%s,"result":{"content":[{"type":"text","text":"pong"}]}}\n' "$id"
-      ;;
-  esac
-done
-"#,
-        )?;
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&path)?.permissions();
-            perms.set_mode(0o755);
-            fs::set_permissions(&path, perms)?;
-        }
-
-        Ok(path)
-    }
-
-    /// Write a mock MCP server that registers two tools, one with a duplicate name.
-
-    #[tokio::test]
-    async fn tools_list_and_call_are_forwarded() -> Result<()> {
-        let temp = tempfile::tempdir()?;
-        let script = write_script(temp.path())?;
-
-        let registry = Arc::new(McpRegistry::new(vec![McpServerConfig {
-            name: "mock".to_string(),
-            transport: McpTransport::Stdio {
-                command: "sh".to_string(),
-                args: vec![script.to_string_lossy().into_owned()],
-                env: HashMap::new(),
-            },
-            stateful: false,
-            memory_max_mb: None,
-        }]));
-        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
-
-        let list_response = router.list_tools_internal().await?;
-        assert_eq!(list_response.tools[0].name.as_ref(), "echo_tool");
-
-        let call_response = router
-            .call_tool_internal(
-                CallToolRequestParams::new("echo_tool").with_arguments(
-                    json!({"value":"ping"})
-                        .as_object()
-                        .cloned()
-                        .unwrap_or_default(),
-                ),
-            )
-            .await?;
-
-        assert_eq!(
-            call_response.content[0].as_text().map(|t| t.text.as_str()),
-            Some("pong")
-        );
-
-        registry.shutdown_all().await?;
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn tool_descriptor_cache_populated_after_list() -> Result<()> {
-        let temp = tempfile::tempdir()?;
-        let script = write_script(temp.path())?;
-
-        let registry = Arc::new(McpRegistry::new(vec![McpServerConfig {
-            name: "mock".to_string(),
-            transport: McpTransport::Stdio {
-                command: "sh".to_string(),
-                args: vec![script.to_string_lossy().into_owned()],
-                env: HashMap::new(),
-            },
-            stateful: false,
-            memory_max_mb: None,
-        }]));
-        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
-
-        // Cache should be empty before list
-        assert!(router.get_tool_descriptor("echo_tool").await.is_none());
-
-        router.list_tools_internal().await?;
-
-        // Cache should be populated after list
-        let descriptor = router
-            .get_tool_descriptor("echo_tool")
-            .await
-            .expect("echo_tool should be cached");
-        assert_eq!(descriptor.server_name, "mock");
-        assert_eq!(descriptor.description.as_deref(), Some("echo"));
-        assert_eq!(
-            descriptor.input_schema,
-            json!({"type": "object", "properties": {}})
-        );
-
-        registry.shutdown_all().await?;
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn tool_descriptor_duplicate_name_last_wins() {
-        // Test cache overwrite behavior directly — avoids flaky server_names()
-        // iteration order from McpRegistry (HashMap-backed, non-deterministic).
-        let registry = Arc::new(McpRegistry::new(Vec::new()));
-        let router = ProxyRouter::new(registry, Duration::from_secs(5));
-
-        {
-            let mut cache = router.tool_cache.write().await;
-            cache.insert(
-                "echo_tool".to_string(),
-                ToolDescriptor {
-                    server_name: "first-server".to_string(),
-                    description: Some("original echo".to_string()),
-                    input_schema: json!({"type": "object"}),
-                },
-            );
-            // Overwrite with second server — last insert wins
-            cache.insert(
-                "echo_tool".to_string(),
-                ToolDescriptor {
-                    server_name: "second-server".to_string(),
-                    description: Some("duplicate echo".to_string()),
-                    input_schema: json!({"type": "object"}),
-                },
-            );
-        }
-
-        let descriptor = router
-            .get_tool_descriptor("echo_tool")
-            .await
-            .expect("echo_tool should be cached");
-        assert_eq!(descriptor.server_name, "second-server");
-        assert_eq!(descriptor.description.as_deref(), Some("duplicate echo"));
-    }
-
-    #[tokio::test]
-    async fn tool_descriptor_resolve_returns_server_name() -> Result<()> {
-        let temp = tempfile::tempdir()?;
-        let script = write_script(temp.path())?;
-
-        let registry = Arc::new(McpRegistry::new(vec![McpServerConfig {
-            name: "mock".to_string(),
-            transport: McpTransport::Stdio {
-                command: "sh".to_string(),
-                args: vec![script.to_string_lossy().into_owned()],
-                env: HashMap::new(),
-            },
-            stateful: false,
-            memory_max_mb: None,
-        }]));
-        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
-
-        router.list_tools_internal().await?;
-
-        // resolve_tool (via lookup_tool_owner) should still return server_name
-        let owner = router.lookup_tool_owner("echo_tool").await;
-        assert_eq!(owner.as_deref(), Some("mock"));
-
-        // Unknown tool should return None
-        let unknown = router.lookup_tool_owner("nonexistent").await;
-        assert!(unknown.is_none());
-
-        registry.shutdown_all().await?;
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn tool_search_empty_cache_returns_empty() {
-        let registry = Arc::new(McpRegistry::new(Vec::new()));
-        let router = ProxyRouter::new(registry, Duration::from_secs(5));
-
-        let results = router.tool_search("anything", 10).await;
-        assert!(results.is_empty());
-    }
-
-    #[tokio::test]
-    async fn tool_search_matches_name_case_insensitive() -> Result<()> {
-        let temp = tempfile::tempdir()?;
-        let script = write_script(temp.path())?;
-
-        let registry = Arc::new(McpRegistry::new(vec![McpServerConfig {
-            name: "mock".to_string(),
-            transport: McpTransport::Stdio {
-                command: "sh".to_string(),
-                args: vec![script.to_string_lossy().into_owned()],
-                env: HashMap::new(),
-            },
-            stateful: false,
-            memory_max_mb: None,
-        }]));
-        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
-        router.list_tools_internal().await?;
-
-        // Case-insensitive match on name
-        let results = router.tool_search("ECHO", 10).await;
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].name, "echo_tool");
-        assert_eq!(results[0].server_name, "mock");
-
-        registry.shutdown_all().await?;
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn tool_search_no_match_returns_empty() -> Result<()> {
-        let temp = tempfile::tempdir()?;
-        let script = write_script(temp.path())?;
-
-        let registry = Arc::new(McpRegistry::new(vec![McpServerConfig {
-            name: "mock".to_string(),
-            transport: McpTransport::Stdio {
-                command: "sh".to_string(),
-                args: vec![script.to_string_lossy().into_owned()],
-                env: HashMap::new(),
-            },
-            stateful: false,
-            memory_max_mb: None,
-        }]));
-        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
-        router.list_tools_internal().await?;
-
-        let results = router.tool_search("nonexistent_xyz", 10).await;
-        assert!(results.is_empty());
-
-        registry.shutdown_all().await?;
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn tool_search_query_truncated_at_256() -> Result<()> {
-        let temp = tempfile::tempdir()?;
-        let script = write_script(temp.path())?;
-
-        let registry = Arc::new(McpRegistry::new(vec![McpServerConfig {
-            name: "mock".to_string(),
-            transport: McpTransport::Stdio {
-                command: "sh".to_string(),
-                args: vec![script.to_string_lossy().into_owned()],
-                env: HashMap::new(),
-            },
-            stateful: false,
-            memory_max_mb: None,
-        }]));
-        let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
-        router.list_tools_internal().await?;
-
-        // A very long query should not panic and should be silently truncated
-        let long_query = "x".repeat(1000);
-        let results = router.tool_search(&long_query, 10).await;
-        // "x" repeated doesn't match "echo_tool" so empty
-        assert!(results.is_empty());
-
-        registry.shutdown_all().await?;
-        Ok(())
-    }
-}
+#[path = "proxy_tests.rs"]
+mod tests;
@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use csa_config::{McpCacheRule, McpFilter, McpServerConfig, McpTransport};
+use rmcp::model::CallToolRequestParams;
+use serde_json::json;
+
+use crate::call_cache::CallCache;
+use crate::proxy::ProxyRouter;
+use crate::registry::McpRegistry;
+use crate::tool_filter::ToolFilters;
+
+fn write_script(dir: &std::path::Path) -> Result<std::path::PathBuf> {
+    let path = dir.join("mock-mcp.sh");
+    fs::write(
+        &path,
+        r#"#!/bin/sh
+while IFS= read -r line; do
+  id=$(printf '%s\n' "$line" | sed -n 's/.*"id"[ ]*:[ ]*\([^,}]*\).*/\1/p')
+  case "$line" in
+    *\"initialize\"*)
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{"tools":{}},"serverInfo":{"name":"mock","version":"0.1.0"}}}\n' "$id"
+      ;;
+    *\"notifications/initialized\"*)
+      ;;
+    *\"tools/list\"*)
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"tools":[{"name":"echo_tool","description":"echo","inputSchema":{"type":"object","properties":{}}}]}}\n' "$id"
+      ;;
+    *\"tools/call\"*)
+      echo call >> "$0.calls"
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"content":[{"type":"text","text":"pong"}]}}\n' "$id"
+      ;;
+  esac
+done
+"#,
+    )?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&path, perms)?;
+    }
+
+    Ok(path)
+}
+
+fn mock_server(name: &str, script: &std::path::Path) -> McpServerConfig {
+    McpServerConfig {
+        name: name.to_string(),
+        transport: McpTransport::Stdio {
+            command: "sh".to_string(),
+            args: vec![script.to_string_lossy().into_owned()],
+            env: HashMap::new(),
+        },
+        stateful: false,
+        memory_max_mb: None,
+        tool_renames: HashMap::new(),
+    }
+}
+
+#[tokio::test]
+async fn tools_list_and_call_are_forwarded() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+
+    let list_response = router.list_tools_internal().await?;
+    assert_eq!(list_response.tools[0].name.as_ref(), "echo_tool");
+
+    let call_response = router
+        .call_tool_internal(
+            CallToolRequestParams::new("echo_tool").with_arguments(
+                json!({"value":"ping"})
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            Some("codex"),
+        )
+        .await?;
+
+    assert_eq!(
+        call_response.content[0].as_text().map(|t| t.text.as_str()),
+        Some("pong")
+    );
+    let stats = router.stats_payload();
+    assert_eq!(stats["servers"][0]["server"], "mock");
+    assert_eq!(stats["servers"][0]["tools"][0]["tool"], "echo_tool");
+    assert_eq!(stats["servers"][0]["tools"][0]["calls"], 1);
+    assert_eq!(stats["servers"][0]["tools"][0]["errors"], 0);
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn consumer_filters_hide_and_block_tools() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let deny_echo = McpFilter {
+        tools: vec!["!mock.echo_*".to_string()],
+        ..McpFilter::default()
+    };
+    let filters = ToolFilters::new(
+        &HashMap::from([("codex".to_string(), deny_echo)]),
+        HashMap::new(),
+    );
+    let router =
+        ProxyRouter::new(registry.clone(), Duration::from_secs(5)).with_tool_filters(filters);
+    let codex = router.for_consumer(Some("codex".to_string()));
+    let codex_filters = codex.connection_filters().to_vec();
+
+    let listed = codex.list_tools_filtered(&codex_filters).await?;
+    assert!(listed.tools.is_empty());
+    assert!(codex.get_tool_descriptor("echo_tool").await.is_none());
+    assert!(codex.tool_search("echo", 10).await.is_empty());
+    let denied = codex
+        .call_tool_internal(CallToolRequestParams::new("echo_tool"), Some("codex"))
+        .await;
+    assert!(denied.is_err());
+
+    let other = router.for_consumer(Some("claude-code".to_string()));
+    assert_eq!(other.list_tools_filtered(&[]).await?.tools.len(), 1);
+    assert!(other.get_tool_descriptor("echo_tool").await.is_some());
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tool_descriptor_cache_populated_after_list() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+
+    // Cache should be empty before list
+    assert!(router.get_tool_descriptor("echo_tool").await.is_none());
+
+    router.list_tools_internal().await?;
+
+    // Cache should be populated after list
+    let descriptor = router
+        .get_tool_descriptor("echo_tool")
+        .await
+        .expect("echo_tool should be cached");
+    assert_eq!(descriptor.server_name, "mock");
+    assert_eq!(descriptor.description.as_deref(), Some("echo"));
+    assert_eq!(
+        descriptor.input_schema,
+        json!({"type": "object", "properties": {}})
+    );
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn duplicate_tool_names_are_namespaced_per_server() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![
+        mock_server("first", &script),
+        mock_server("second", &script),
+    ]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+
+    let list_response = router.list_tools_internal().await?;
+    let names: Vec<&str> = list_response
+        .tools
+        .iter()
+        .map(|t| t.name.as_ref())
+        .collect();
+    assert_eq!(names, ["first__echo_tool", "second__echo_tool"]);
+
+    let descriptor = router
+        .get_tool_descriptor("second__echo_tool")
+        .await
+        .expect("namespaced tool should be cached");
+    assert_eq!(descriptor.server_name, "second");
+    assert_eq!(descriptor.tool_name, "echo_tool");
+
+    let call_response = router
+        .call_tool_internal(CallToolRequestParams::new("second__echo_tool"), None)
+        .await?;
+    assert_eq!(
+        call_response.content[0].as_text().map(|t| t.text.as_str()),
+        Some("pong")
+    );
+    assert_eq!(
+        router.stats_payload()["servers"][0]["tools"][0]["tool"],
+        "echo_tool"
+    );
+
+    let status = router.status_payload().await;
+    assert_eq!(status["toolCollisions"][0]["name"], "echo_tool");
+    assert_eq!(
+        status["toolCollisions"][0]["tools"][1]["exposed_as"],
+        "second__echo_tool"
+    );
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn names_stay_namespaced_while_a_colliding_server_is_down() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![
+        mock_server("first", &script),
+        mock_server("second", &script),
+    ]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+    router.list_tools_internal().await?;
+
+    // `second` now fails to start; its last tools keep `first` namespaced.
+    registry
+        .reload(vec![
+            mock_server("first", &script),
+            mock_server("second", &temp.path().join("missing.sh")),
+        ])
+        .await;
+    let list_response = router.list_tools_internal().await?;
+    let names: Vec<&str> = list_response
+        .tools
+        .iter()
+        .map(|t| t.name.as_ref())
+        .collect();
+    assert_eq!(names, ["first__echo_tool"]);
+
+    // Removing the server from the config releases its names.
+    registry.reload(vec![mock_server("first", &script)]).await;
+    let list_response = router.list_tools_internal().await?;
+    assert_eq!(list_response.tools[0].name.as_ref(), "echo_tool");
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn cached_results_skip_the_server_until_they_expire() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+    let calls_log = temp.path().join("mock-mcp.sh.calls");
+    let calls = || fs::read_to_string(&calls_log).map_or(0, |log| log.lines().count());
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5)).with_call_cache(
+        CallCache::new(vec![McpCacheRule {
+            tools: vec!["mock.echo_*".to_string()],
+            ttl_secs: 60,
+        }]),
+    );
+    let call = |value: &str| {
+        CallToolRequestParams::new("echo_tool").with_arguments(
+            json!({"value": value})
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+        )
+    };
+
+    router.call_tool_internal(call("a"), None).await?;
+    let cached = router.call_tool_internal(call("a"), None).await?;
+    assert_eq!(
+        cached.content[0].as_text().map(|t| t.text.as_str()),
+        Some("pong")
+    );
+    assert_eq!(calls(), 1);
+
+    router.call_tool_internal(call("b"), None).await?;
+    assert_eq!(calls(), 2);
+    let stats = router.stats_payload();
+    assert_eq!(stats["servers"][0]["calls"], 3);
+    assert_eq!(stats["servers"][0]["errors"], 0);
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn renamed_tools_are_exposed_under_their_new_name() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let mut renamed = mock_server("first", &script);
+    renamed.tool_renames = HashMap::from([("echo_tool".to_string(), "echo".to_string())]);
+    let registry = Arc::new(McpRegistry::new(vec![
+        renamed,
+        mock_server("second", &script),
+    ]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+
+    let list_response = router.list_tools_internal().await?;
+    let names: Vec<&str> = list_response
+        .tools
+        .iter()
+        .map(|t| t.name.as_ref())
+        .collect();
+    assert_eq!(names, ["echo", "echo_tool"]);
+    assert_eq!(router.status_payload().await["toolCollisions"], json!([]));
+
+    router
+        .call_tool_internal(CallToolRequestParams::new("echo"), None)
+        .await?;
+    assert_eq!(router.stats_payload()["servers"][0]["server"], "first");
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tool_descriptor_resolve_returns_server_name() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+
+    router.list_tools_internal().await?;
+
+    // cached_descriptor should still return server_name
+    let descriptor = router.cached_descriptor("echo_tool").await;
+    assert_eq!(
+        descriptor.map(|d| (d.server_name, d.tool_name)),
+        Some(("mock".to_string(), "echo_tool".to_string()))
+    );
+
+    // Unknown tool should return None
+    let unknown = router.cached_descriptor("nonexistent").await;
+    assert!(unknown.is_none());
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tool_search_empty_cache_returns_empty() {
+    let registry = Arc::new(McpRegistry::new(Vec::new()));
+    let router = ProxyRouter::new(registry, Duration::from_secs(5));
+
+    let results = router.tool_search("anything", 10).await;
+    assert!(results.is_empty());
+}
+
+#[tokio::test]
+async fn tool_search_matches_name_case_insensitive() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+    router.list_tools_internal().await?;
+
+    // Case-insensitive match on name
+    let results = router.tool_search("ECHO", 10).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "echo_tool");
+    assert_eq!(results[0].server_name, "mock");
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tool_search_no_match_returns_empty() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+    router.list_tools_internal().await?;
+
+    let results = router.tool_search("nonexistent_xyz", 10).await;
+    assert!(results.is_empty());
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tool_search_query_truncated_at_256() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script = write_script(temp.path())?;
+
+    let registry = Arc::new(McpRegistry::new(vec![mock_server("mock", &script)]));
+    let router = ProxyRouter::new(registry.clone(), Duration::from_secs(5));
+    router.list_tools_internal().await?;
+
+    // A very long query should not panic and should be silently truncated
+    let long_query = "x".repeat(1000);
+    let results = router.tool_search(&long_query, 10).await;
+    // "x" repeated doesn't match "echo_tool" so empty
+    assert!(results.is_empty());
+
+    registry.shutdown_all().await?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Token-bucket rate limiting.
+//!
+//! Client connections use a [`TokenBucket`] to cap their request rate.
+//! [`ServerRateLimits`] throttles the tool calls the hub forwards to each
+//! server listed under `[mcp.rate_limits]`: calls over the limit wait for a
+//! token instead of failing, bounded by the request timeout.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(max_requests_per_sec: u32) -> Self {
+        let refill_per_sec = f64::from(max_requests_per_sec.max(1));
+        Self {
+            capacity: refill_per_sec,
+            tokens: refill_per_sec,
+            refill_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    pub(crate) fn try_consume(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Time until the next token is available.
+    fn time_to_next_token(&self) -> Duration {
+        Duration::from_secs_f64(((1.0 - self.tokens) / self.refill_per_sec).max(0.0))
+    }
+}
+
+/// Per-server forwarding limits from `[mcp.rate_limits]`.
+#[derive(Debug, Default)]
+pub(crate) struct ServerRateLimits {
+    buckets: HashMap<String, Mutex<TokenBucket>>,
+}
+
+impl ServerRateLimits {
+    pub(crate) fn new(limits: &HashMap<String, u32>) -> Self {
+        Self {
+            buckets: limits
+                .iter()
+                .map(|(server, per_sec)| (server.clone(), Mutex::new(TokenBucket::new(*per_sec))))
+                .collect(),
+        }
+    }
+
+    /// Wait until a call to `server` may be forwarded.
+    pub(crate) async fn acquire(&self, server: &str) {
+        let Some(bucket) = self.buckets.get(server) else {
+            return;
+        };
+        loop {
+            let wait = {
+                let mut bucket = bucket
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                if bucket.try_consume() {
+                    return;
+                }
+                bucket.time_to_next_token()
+            };
+            tracing::debug!(server = %server, wait_ms = wait.as_millis(), "MCP call rate limited");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let mut limiter = TokenBucket::new(2);
+        assert!(limiter.try_consume());
+        assert!(limiter.try_consume());
+        assert!(!limiter.try_consume());
+        std::thread::sleep(Duration::from_millis(600));
+        assert!(limiter.try_consume());
+    }
+
+    #[tokio::test]
+    async fn server_limits_delay_calls_over_the_rate() {
+        let limits = ServerRateLimits::new(&HashMap::from([("github".to_string(), 4)]));
+        let started = Instant::now();
+        for _ in 0..6 {
+            limits.acquire("github").await;
+        }
+        // Four calls pass at once; the next two wait ~250ms each.
+        assert!(started.elapsed() >= Duration::from_millis(400));
+
+        let started = Instant::now();
+        for _ in 0..100 {
+            limits.acquire("unlimited").await;
+        }
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -1,8 +1,14 @@
+#[path = "registry_health.rs"]
+mod registry_health;
 #[cfg(feature = "transport-http-client")]
 #[path = "registry_http.rs"]
 mod registry_http;
+#[path = "registry_known_tools.rs"]
+mod registry_known_tools;
 #[path = "registry_pool.rs"]
 mod registry_pool;
+#[path = "registry_reload.rs"]
+mod registry_reload;
 
 use anyhow::{Context, Result, anyhow};
 use csa_config::McpServerConfig;
@@ -10,12 +16,12 @@ use csa_process::{SandboxHandle, SpawnOptions, spawn_tool_sandboxed};
 use csa_resource::isolation_plan::{EnforcementMode, IsolationPlanBuilder};
 use csa_resource::{ResourceCapability, SandboxConfig, apply_rlimits, detect_resource_capability};
 use rmcp::RoleClient;
-use rmcp::model::{CallToolRequestParams, CallToolResult, Tool};
+use rmcp::model::{CallToolRequestParams, CallToolResult, ClientRequest, Tool};
 use rmcp::service::{RunningService, ServiceExt};
 use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::sync::{mpsc, oneshot};
@@ -28,9 +34,12 @@ pub(crate) use registry_http::{preflight_ssrf_check, validate_http_url};
 #[cfg(all(feature = "transport-http-client", test))]
 pub(crate) use registry_http::{is_ssrf_dangerous_ip, parse_host_port};
 
+use registry_health::{HEALTH_CHECK_INTERVAL, HealthTracker};
+pub(crate) use registry_health::{HealthState, ServerHealthReport};
 #[cfg(test)]
 use registry_pool::LeaseTracker;
 use registry_pool::StatefulServerPool;
+pub(crate) use registry_reload::RegistryChanges;
 
 const RESTART_BACKOFF_INITIAL_MS: u64 = 100;
 const RESTART_BACKOFF_MAX_MS: u64 = 30_000;
@@ -68,10 +77,20 @@ impl PoolKey {
 }
 
 pub(crate) struct McpRegistry {
-    servers: HashMap<String, ServerEntry>,
-    transport_labels: HashMap<String, String>,
+    /// Swapped as a whole by [`McpRegistry::reload`]; entries are cloned out
+    /// so no lock is held across a downstream request.
+    servers: RwLock<ServerSet>,
 }
 
+#[derive(Default)]
+struct ServerSet {
+    entries: HashMap<String, ServerEntry>,
+    configs: HashMap<String, McpServerConfig>,
+    /// Tools each server last listed, kept while it is down.
+    known_tools: HashMap<String, Vec<Tool>>,
+}
+
+#[derive(Clone)]
 enum ServerEntry {
     Stateless(Arc<ServerQueueHandle>),
     Stateful(Arc<StatefulServerPool>),
@@ -79,35 +98,46 @@ enum ServerEntry {
 
 impl McpRegistry {
     pub(crate) fn new(configs: Vec<McpServerConfig>) -> Self {
-        let mut servers = HashMap::new();
-        let mut transport_labels = HashMap::new();
+        let mut servers = ServerSet::default();
         for config in configs {
-            let label = config.transport.label().to_string();
-            let name = config.name.clone();
-            let entry = if config.stateful {
-                ServerEntry::Stateful(Arc::new(StatefulServerPool::new(config)))
-            } else {
-                ServerEntry::Stateless(Arc::new(ServerQueueHandle::spawn(config, None)))
-            };
-            servers.insert(name.clone(), entry);
-            transport_labels.insert(name, label);
+            servers.insert(config);
         }
         Self {
-            servers,
-            transport_labels,
+            servers: RwLock::new(servers),
         }
     }
 
+    fn read_servers(&self) -> RwLockReadGuard<'_, ServerSet> {
+        self.servers
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn entry(&self, server_name: &str) -> Result<ServerEntry> {
+        self.read_servers()
+            .entries
+            .get(server_name)
+            .cloned()
+            .with_context(|| format!("unknown MCP server: {server_name}"))
+    }
+
     pub(crate) fn server_names(&self) -> Vec<String> {
-        self.servers.keys().cloned().collect()
+        self.read_servers().entries.keys().cloned().collect()
     }
 
     /// Returns the transport label (stdio/http/sse) for a server.
-    pub(crate) fn transport_label(&self, server_name: &str) -> &str {
-        self.transport_labels
-            .get(server_name)
-            .map(String::as_str)
-            .unwrap_or("stdio")
+    pub(crate) fn transport_label(&self, server_name: &str) -> &'static str {
+        self.read_servers().transport_label(server_name)
+    }
+
+    /// Configured `tool_renames` keyed by server name.
+    pub(crate) fn tool_renames(&self) -> HashMap<String, HashMap<String, String>> {
+        self.read_servers()
+            .configs
+            .iter()
+            .filter(|(_, config)| !config.tool_renames.is_empty())
+            .map(|(name, config)| (name.clone(), config.tool_renames.clone()))
+            .collect()
     }
 
     pub(crate) async fn list_tools(
@@ -115,15 +145,11 @@ impl McpRegistry {
         server_name: &str,
         cancellation: CancellationToken,
     ) -> Result<Vec<Tool>> {
-        let entry = self
-            .servers
-            .get(server_name)
-            .with_context(|| format!("unknown MCP server: {server_name}"))?;
-
-        match entry {
+        let tools = match self.entry(server_name)? {
             ServerEntry::Stateless(queue) => queue.list_tools(cancellation).await,
             ServerEntry::Stateful(pool) => pool.list_tools(cancellation).await,
-        }
+        };
+        self.remember_tools(server_name, tools)
     }
 
     pub(crate) async fn call_tool(
@@ -133,25 +159,62 @@ impl McpRegistry {
         route: ToolCallRoute,
         cancellation: CancellationToken,
     ) -> Result<CallToolResult> {
-        let entry = self
-            .servers
-            .get(server_name)
-            .with_context(|| format!("unknown MCP server: {server_name}"))?;
-
-        match entry {
+        match self.entry(server_name)? {
             ServerEntry::Stateless(queue) => queue.call_tool(request, cancellation).await,
             ServerEntry::Stateful(pool) => pool.call_tool(request, route, cancellation).await,
         }
     }
 
+    /// Health of every server, sorted by name.
+    pub(crate) async fn health_report(&self) -> Vec<ServerHealthReport> {
+        let servers: Vec<(String, &'static str, ServerEntry)> = {
+            let servers = self.read_servers();
+            servers
+                .entries
+                .iter()
+                .map(|(name, entry)| (name.clone(), servers.transport_label(name), entry.clone()))
+                .collect()
+        };
+        let mut reports = Vec::with_capacity(servers.len());
+        for (name, transport, entry) in servers {
+            reports.push(match entry {
+                ServerEntry::Stateless(queue) => queue.health.report(&name, transport),
+                ServerEntry::Stateful(pool) => pool.health_report(transport).await,
+            });
+        }
+        reports.sort_by(|a, b| a.name.cmp(&b.name));
+        reports
+    }
+
     pub(crate) async fn shutdown_all(&self) -> Result<()> {
-        for entry in self.servers.values() {
+        let entries: Vec<ServerEntry> = self.read_servers().entries.values().cloned().collect();
+        for entry in entries {
             entry.shutdown().await?;
         }
         Ok(())
     }
 }
 
+impl ServerSet {
+    fn insert(&mut self, config: McpServerConfig) {
+        let name = config.name.clone();
+        let entry = if config.stateful {
+            ServerEntry::Stateful(Arc::new(StatefulServerPool::new(config.clone())))
+        } else {
+            ServerEntry::Stateless(Arc::new(ServerQueueHandle::spawn(config.clone(), None)))
+        };
+        self.entries.insert(name.clone(), entry);
+        self.configs.insert(name, config);
+    }
+
+    fn transport_label(&self, server_name: &str) -> &'static str {
+        self.configs
+            .get(server_name)
+            .map(|config| config.transport.label())
+            .unwrap_or("stdio")
+    }
+}
+
 impl ServerEntry {
     async fn shutdown(&self) -> Result<()> {
         match self {
@@ -165,6 +228,7 @@ impl ServerEntry {
 struct ServerQueueHandle {
     server_name: String,
     sender: mpsc::Sender<QueueCommand>,
+    health: HealthTracker,
 }
 
 enum QueueCommandKind {
@@ -190,11 +254,28 @@ impl ServerQueueHandle {
         let server_name = config.name.clone();
         let (sender, mut receiver) = mpsc::channel::<QueueCommand>(REQUEST_QUEUE_CAPACITY);
         let queue_server_name = server_name.clone();
+        let health = HealthTracker::default();
+        let worker_health = health.clone();
 
         tokio::spawn(async move {
-            let mut server = ManagedServer::new(config);
-
-            while let Some(command) = receiver.recv().await {
+            let mut server = ManagedServer::new(config, worker_health);
+            let mut health_ticks = tokio::time::interval_at(
+                tokio::time::Instant::now() + HEALTH_CHECK_INTERVAL,
+                HEALTH_CHECK_INTERVAL,
+            );
+            health_ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                let command = tokio::select! {
+                    command = receiver.recv() => command,
+                    _ = health_ticks.tick() => {
+                        server.health_check().await;
+                        continue;
+                    }
+                };
+                let Some(command) = command else {
+                    break;
+                };
                 match command.kind {
                     QueueCommandKind::Shutdown => {
                         let _ = command.response.send(Ok(QueueResponse::Shutdown));
@@ -234,6 +315,7 @@ impl ServerQueueHandle {
         Self {
             server_name,
             sender,
+            health,
         }
     }
 
@@ -320,18 +402,32 @@ struct ManagedServer {
     config: McpServerConfig,
     transport: Option<BackendTransport>,
     restart_backoff: Duration,
+    health: HealthTracker,
 }
 
 impl ManagedServer {
-    fn new(config: McpServerConfig) -> Self {
+    fn new(config: McpServerConfig, health: HealthTracker) -> Self {
         Self {
             config,
             transport: None,
             restart_backoff: Duration::from_millis(RESTART_BACKOFF_INITIAL_MS),
+            health,
+        }
+    }
+
+    /// Fail fast while a crashed server waits for its scheduled restart
+    /// instead of stalling the request behind the backoff.
+    fn check_not_waiting_for_restart(&self) -> Result<()> {
+        if self.transport.is_none()
+            && let Some(error) = self.health.pending_restart_error()
+        {
+            anyhow::bail!("MCP server '{}' is down: {error}", self.config.name);
         }
+        Ok(())
     }
 
     async fn list_tools(&mut self) -> Result<Vec<Tool>> {
+        self.check_not_waiting_for_restart()?;
         let mut last_err: Option<anyhow::Error> = None;
 
         for _ in 0..3 {
@@ -341,6 +437,7 @@ impl ManagedServer {
                     error = %error,
                     "MCP spawn/list_tools failed, restarting"
                 );
+                self.health.mark_down(format!("{error:#}"), None);
                 last_err = Some(error);
                 self.restart_after_failure().await?;
                 continue;
@@ -357,6 +454,7 @@ impl ManagedServer {
                             error = %error,
                             "MCP list_tools failed, restarting"
                         );
+                        self.health.mark_down(error.to_string(), None);
                         last_err = Some(anyhow!(error));
                         self.restart_after_failure().await?;
                     }
@@ -368,6 +466,7 @@ impl ManagedServer {
     }
 
     async fn call_tool(&mut self, request: CallToolRequestParams) -> Result<CallToolResult> {
+        self.check_not_waiting_for_restart()?;
         let mut last_err: Option<anyhow::Error> = None;
 
         for _ in 0..3 {
@@ -377,6 +476,7 @@ impl ManagedServer {
                     error = %error,
                     "MCP spawn/call_tool failed, restarting"
                 );
+                self.health.mark_down(format!("{error:#}"), None);
                 last_err = Some(error);
                 self.restart_after_failure().await?;
                 continue;
@@ -393,6 +493,7 @@ impl ManagedServer {
                             error = %error,
                             "MCP call_tool failed, restarting"
                         );
+                        self.health.mark_down(error.to_string(), None);
                         last_err = Some(anyhow!(error));
                         self.restart_after_failure().await?;
                     }
@@ -404,11 +505,23 @@ impl ManagedServer {
     }
 
     async fn ensure_running(&mut self) -> Result<()> {
+        if let Some(reason) = self
+            .transport
+            .as_mut()
+            .and_then(BackendTransport::exit_reason)
+        {
+            tracing::warn!(server = %self.config.name, reason = %reason, "MCP server stopped");
+            self.health.mark_down(reason, None);
+            if let Some(transport) = self.transport.take() {
+                transport.shutdown().await;
+            }
+        }
         if self.transport.is_some() {
             return Ok(());
         }
 
         self.transport = Some(BackendTransport::connect(&self.config).await?);
+        self.health.mark_healthy();
         Ok(())
     }
 
@@ -487,6 +600,27 @@ impl BackendTransport {
         }
     }
 
+    /// Why the backend is gone, if it is: the exit status of a stdio child or
+    /// a closed transport.
+    fn exit_reason(&mut self) -> Option<String> {
+        if let Self::Stdio { child, .. } = self
+            && let Ok(Some(status)) = child.try_wait()
+        {
+            return Some(format!("process exited with {status}"));
+        }
+        self.service()
+            .is_transport_closed()
+            .then(|| "transport closed".to_string())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.service()
+            .send_request(ClientRequest::PingRequest(Default::default()))
+            .await
+            .map(|_| ())
+            .map_err(|error| anyhow!(error))
+    }
+
     /// Graceful shutdown adapting to transport type.
     async fn shutdown(self) {
         match self {
@@ -530,11 +664,14 @@ impl BackendTransport {
             memory_max_mb: config.memory_max_mb.unwrap_or(MCP_SANDBOX_MEMORY_MAX_MB),
             memory_swap_max_mb: MCP_SANDBOX_MEMORY_SWAP_MAX_MB,
             pids_max: MCP_SANDBOX_PIDS_MAX,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            network: csa_resource::NetworkMode::Full,
         };
 
         let capability = detect_resource_capability();
         let (mut child, sandbox) = match capability {
-            ResourceCapability::CgroupV2 => {
+            ResourceCapability::CgroupV2 | ResourceCapability::CgroupDirect => {
                 let child = spawn_with_rlimit_interactive(cmd, &sandbox_config)
                     .with_context(|| format!("failed to sandbox MCP server '{}'", config.name))?;
                 (child, None)
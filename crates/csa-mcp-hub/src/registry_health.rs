@@ -0,0 +1,310 @@
+//! Health tracking for downstream MCP servers.
+//!
+//! Each server queue owns a [`HealthTracker`] that its worker updates on every
+//! connect, request and periodic ping, and restarts crashed stdio servers with
+//! exponential backoff. `hub/status` reports the snapshots so
+//! `csa mcp-hub status` can say which servers are down and why.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{BackendTransport, ManagedServer, RESTART_BACKOFF_INITIAL_MS, RESTART_BACKOFF_MAX_MS};
+
+/// How often a running server is pinged.
+pub(super) const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// How long a ping may take before the server counts as hung.
+pub(super) const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HealthState {
+    /// Not started yet; servers are spawned on first use.
+    Idle,
+    Healthy,
+    Down,
+}
+
+impl HealthState {
+    /// Rank for picking the worst state of several pool instances.
+    fn severity(self) -> u8 {
+        match self {
+            Self::Idle => 0,
+            Self::Healthy => 1,
+            Self::Down => 2,
+        }
+    }
+}
+
+/// Health of one server as reported by `hub/status`.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ServerHealthReport {
+    pub(crate) name: String,
+    pub(crate) transport: String,
+    pub(crate) state: HealthState,
+    /// Why the server is down, or the last failure it recovered from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    pub(crate) restarts: u32,
+    pub(crate) consecutive_failures: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) last_checked_at: Option<DateTime<Utc>>,
+    /// Seconds until the next automatic restart attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) next_restart_in_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+struct HealthSnapshot {
+    state: HealthState,
+    error: Option<String>,
+    restarts: u32,
+    consecutive_failures: u32,
+    last_checked_at: Option<DateTime<Utc>>,
+    next_restart_at: Option<Instant>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct HealthTracker(Arc<Mutex<HealthSnapshot>>);
+
+impl Default for HealthTracker {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(HealthSnapshot {
+            state: HealthState::Idle,
+            error: None,
+            restarts: 0,
+            consecutive_failures: 0,
+            last_checked_at: None,
+            next_restart_at: None,
+        })))
+    }
+}
+
+impl HealthTracker {
+    fn update(&self, apply: impl FnOnce(&mut HealthSnapshot)) {
+        let mut snapshot = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        snapshot.last_checked_at = Some(Utc::now());
+        apply(&mut snapshot);
+    }
+
+    pub(super) fn mark_healthy(&self) {
+        self.update(|snapshot| {
+            if snapshot.state == HealthState::Down {
+                snapshot.restarts += 1;
+            }
+            snapshot.state = HealthState::Healthy;
+            snapshot.consecutive_failures = 0;
+            snapshot.next_restart_at = None;
+        });
+    }
+
+    /// Record a failure; `retry_in` schedules the next automatic restart.
+    pub(super) fn mark_down(&self, error: impl Into<String>, retry_in: Option<Duration>) {
+        let error = error.into();
+        self.update(|snapshot| {
+            snapshot.state = HealthState::Down;
+            snapshot.error = Some(error);
+            snapshot.consecutive_failures += 1;
+            snapshot.next_restart_at = retry_in.map(|delay| Instant::now() + delay);
+        });
+    }
+
+    /// The failure to report while the server waits for its next restart.
+    pub(super) fn pending_restart_error(&self) -> Option<String> {
+        let snapshot = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let next_restart_at = snapshot.next_restart_at?;
+        let remaining = next_restart_at.checked_duration_since(Instant::now())?;
+        Some(format!(
+            "{}; next restart in {}s",
+            snapshot.error.as_deref().unwrap_or("server is down"),
+            remaining.as_secs().max(1)
+        ))
+    }
+
+    /// Whether an automatic restart is due.
+    pub(super) fn restart_due(&self) -> bool {
+        let snapshot = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        snapshot.state == HealthState::Down
+            && snapshot
+                .next_restart_at
+                .is_some_and(|at| at <= Instant::now())
+    }
+
+    pub(super) fn report(&self, name: &str, transport: &str) -> ServerHealthReport {
+        let snapshot = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        ServerHealthReport {
+            name: name.to_string(),
+            transport: transport.to_string(),
+            state: snapshot.state,
+            error: snapshot.error,
+            restarts: snapshot.restarts,
+            consecutive_failures: snapshot.consecutive_failures,
+            last_checked_at: snapshot.last_checked_at,
+            next_restart_in_secs: snapshot
+                .next_restart_at
+                .filter(|_| snapshot.state == HealthState::Down)
+                .map(|at| at.saturating_duration_since(Instant::now()).as_secs()),
+        }
+    }
+}
+
+/// Combine the reports of a stateful server's pool instances into one,
+/// keeping the worst state and summing restarts.
+pub(super) fn merge_reports(
+    name: &str,
+    transport: &str,
+    reports: impl IntoIterator<Item = ServerHealthReport>,
+) -> ServerHealthReport {
+    let mut merged = HealthTracker::default().report(name, transport);
+    for report in reports {
+        merged.restarts += report.restarts;
+        if report.state.severity() > merged.state.severity() {
+            merged.state = report.state;
+            merged.error = report.error;
+            merged.consecutive_failures = report.consecutive_failures;
+            merged.next_restart_in_secs = report.next_restart_in_secs;
+        }
+        merged.last_checked_at = merged.last_checked_at.max(report.last_checked_at);
+    }
+    merged
+}
+
+impl ManagedServer {
+    /// Periodic probe: ping a running server and restart a crashed stdio
+    /// server once its backoff has elapsed. Idle servers are left alone.
+    pub(super) async fn health_check(&mut self) {
+        let Some(transport) = self.transport.as_mut() else {
+            if self.health.restart_due() {
+                self.try_restart().await;
+            }
+            return;
+        };
+
+        let failure = match transport.exit_reason() {
+            Some(reason) => Some(reason),
+            None => match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, transport.ping()).await {
+                Ok(Ok(())) => None,
+                Ok(Err(error)) => Some(format!("ping failed: {error:#}")),
+                Err(_) => Some(format!(
+                    "ping timed out after {}s",
+                    HEALTH_CHECK_TIMEOUT.as_secs()
+                )),
+            },
+        };
+
+        match failure {
+            None => {
+                self.health.mark_healthy();
+                self.restart_backoff = Duration::from_millis(RESTART_BACKOFF_INITIAL_MS);
+            }
+            Some(reason) => {
+                tracing::warn!(server = %self.config.name, reason = %reason, "MCP health check failed");
+                if let Some(transport) = self.transport.take() {
+                    transport.shutdown().await;
+                }
+                self.schedule_restart(reason);
+            }
+        }
+    }
+
+    async fn try_restart(&mut self) {
+        match BackendTransport::connect(&self.config).await {
+            Ok(transport) => {
+                tracing::info!(server = %self.config.name, "MCP server restarted");
+                self.transport = Some(transport);
+                self.health.mark_healthy();
+                self.restart_backoff = Duration::from_millis(RESTART_BACKOFF_INITIAL_MS);
+            }
+            Err(error) => {
+                tracing::warn!(server = %self.config.name, error = %error, "MCP server restart failed");
+                self.schedule_restart(format!("restart failed: {error:#}"));
+            }
+        }
+    }
+
+    /// Mark the server down. Stdio servers are restarted by the health check
+    /// after the current backoff; remote servers reconnect on next use.
+    fn schedule_restart(&mut self, reason: String) {
+        let is_stdio = matches!(
+            self.config.transport,
+            csa_config::McpTransport::Stdio { .. }
+        );
+        if is_stdio {
+            self.health.mark_down(reason, Some(self.restart_backoff));
+            self.restart_backoff =
+                (self.restart_backoff * 2).min(Duration::from_millis(RESTART_BACKOFF_MAX_MS));
+        } else {
+            self.health.mark_down(reason, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_reports_failures_and_recovery() {
+        let tracker = HealthTracker::default();
+        assert_eq!(tracker.report("mock", "stdio").state, HealthState::Idle);
+
+        tracker.mark_down(
+            "process exited with exit status: 1",
+            Some(Duration::from_secs(60)),
+        );
+        let report = tracker.report("mock", "stdio");
+        assert_eq!(report.state, HealthState::Down);
+        assert_eq!(report.consecutive_failures, 1);
+        assert!(report.next_restart_in_secs.is_some());
+        assert!(!tracker.restart_due());
+        let pending = tracker.pending_restart_error().expect("restart pending");
+        assert!(pending.starts_with("process exited"), "{pending}");
+
+        tracker.mark_down("ping timed out", Some(Duration::ZERO));
+        assert!(tracker.restart_due());
+
+        tracker.mark_healthy();
+        let report = tracker.report("mock", "stdio");
+        assert_eq!(report.state, HealthState::Healthy);
+        assert_eq!(report.restarts, 1);
+        assert_eq!(report.consecutive_failures, 0);
+        assert_eq!(report.next_restart_in_secs, None);
+        assert_eq!(report.error.as_deref(), Some("ping timed out"));
+        assert!(tracker.pending_restart_error().is_none());
+    }
+
+    #[test]
+    fn merged_report_keeps_the_worst_pool_instance() {
+        let healthy = HealthTracker::default();
+        healthy.mark_healthy();
+        let down = HealthTracker::default();
+        down.mark_down("transport closed", None);
+
+        let merged = merge_reports(
+            "stateful",
+            "stdio",
+            [healthy.report("a", "stdio"), down.report("b", "stdio")],
+        );
+        assert_eq!(merged.name, "stateful");
+        assert_eq!(merged.state, HealthState::Down);
+        assert_eq!(merged.error.as_deref(), Some("transport closed"));
+
+        let idle = merge_reports("stateful", "stdio", []);
+        assert_eq!(idle.state, HealthState::Idle);
+    }
+}
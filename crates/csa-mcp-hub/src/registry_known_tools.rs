@@ -0,0 +1,39 @@
+//! Tools each server last listed.
+//!
+//! Which tools get namespaced depends on the names all configured servers
+//! claim (see `tool_names`). A server that is down still claims the tools it
+//! last listed, so an outage does not rename the tools of other servers.
+
+use anyhow::Result;
+use rmcp::model::Tool;
+
+use super::McpRegistry;
+
+impl McpRegistry {
+    pub(super) fn remember_tools(
+        &self,
+        server_name: &str,
+        tools: Result<Vec<Tool>>,
+    ) -> Result<Vec<Tool>> {
+        let tools = tools?;
+        let mut servers = self
+            .servers
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if servers.configs.contains_key(server_name) {
+            servers
+                .known_tools
+                .insert(server_name.to_string(), tools.clone());
+        }
+        Ok(tools)
+    }
+
+    /// The tools `server_name` listed last; empty if it never answered.
+    pub(crate) fn last_listed_tools(&self, server_name: &str) -> Vec<Tool> {
+        self.read_servers()
+            .known_tools
+            .get(server_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
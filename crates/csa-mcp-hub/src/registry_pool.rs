@@ -13,6 +13,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 
+use super::registry_health::{ServerHealthReport, merge_reports};
 use super::{
     DEFAULT_MAX_ACTIVE_POOLS, DEFAULT_MAX_WARM_POOLS, DEFAULT_WARM_TTL_SECS, PoolKey,
     ServerQueueHandle, ToolCallRoute,
@@ -51,6 +52,19 @@ impl StatefulServerPool {
         queue.list_tools(cancellation).await
     }
 
+    /// Health of all pool instances, merged into one report.
+    pub(super) async fn health_report(&self, transport: &str) -> ServerHealthReport {
+        let inner = self.inner.lock().await;
+        merge_reports(
+            &self.server_name,
+            transport,
+            inner
+                .queues
+                .values()
+                .map(|queue| queue.health.report(&self.server_name, transport)),
+        )
+    }
+
     async fn default_queue(&self) -> Arc<ServerQueueHandle> {
         let default_key = PoolKey {
             project_root: PathBuf::from("/"),
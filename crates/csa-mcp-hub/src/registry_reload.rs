@@ -0,0 +1,84 @@
+//! Applying an edited server list to a running registry.
+//!
+//! The hub watches the global config and hands every new `[[mcp.servers]]`
+//! list to [`McpRegistry::reload`]. Servers whose config is unchanged keep
+//! running; only added, removed and edited servers are started or stopped,
+//! so connected clients keep their sessions.
+
+use std::collections::HashMap;
+
+use csa_config::McpServerConfig;
+use serde::Serialize;
+
+use super::McpRegistry;
+
+/// Servers a reload started, stopped or restarted, each sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct RegistryChanges {
+    pub(crate) added: Vec<String>,
+    pub(crate) removed: Vec<String>,
+    pub(crate) restarted: Vec<String>,
+}
+
+impl RegistryChanges {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.restarted.is_empty()
+    }
+}
+
+impl McpRegistry {
+    /// Bring the registry in line with `configs`.
+    ///
+    /// New servers are registered (and spawned on first use like at startup),
+    /// removed ones are shut down and servers whose config changed are
+    /// replaced. A replaced server's shutdown is queued behind the calls it
+    /// already accepted, so in-flight calls still complete.
+    pub(crate) async fn reload(&self, configs: Vec<McpServerConfig>) -> RegistryChanges {
+        let mut wanted: HashMap<String, McpServerConfig> = configs
+            .into_iter()
+            .map(|config| (config.name.clone(), config))
+            .collect();
+        let mut changes = RegistryChanges::default();
+        let mut stopped = Vec::new();
+        {
+            let mut servers = self
+                .servers
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let current: Vec<String> = servers.configs.keys().cloned().collect();
+            for name in current {
+                let unchanged = wanted
+                    .get(&name)
+                    .is_some_and(|config| servers.configs.get(&name) == Some(config));
+                if unchanged {
+                    wanted.remove(&name);
+                    continue;
+                }
+                servers.configs.remove(&name);
+                stopped.extend(servers.entries.remove(&name));
+                if wanted.contains_key(&name) {
+                    changes.restarted.push(name);
+                } else {
+                    servers.known_tools.remove(&name);
+                    changes.removed.push(name);
+                }
+            }
+            for (name, config) in wanted {
+                if !changes.restarted.contains(&name) {
+                    changes.added.push(name);
+                }
+                servers.insert(config);
+            }
+        }
+
+        for entry in stopped {
+            if let Err(error) = entry.shutdown().await {
+                tracing::warn!(error = %error, "failed to stop MCP server removed by reload");
+            }
+        }
+        changes.added.sort();
+        changes.removed.sort();
+        changes.restarted.sort();
+        changes
+    }
+}
@@ -10,7 +10,8 @@ use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
 
-use super::{LeaseTracker, McpRegistry, PoolKey, StatefulServerPool, ToolCallRoute};
+use super::registry_health::{HealthState, HealthTracker};
+use super::{LeaseTracker, ManagedServer, McpRegistry, PoolKey, StatefulServerPool, ToolCallRoute};
 
 fn write_script(dir: &std::path::Path, body: &str) -> Result<std::path::PathBuf> {
     let path = dir.join("mock-mcp.sh");
@@ -35,6 +36,7 @@ fn stateless_config(script_path: &std::path::Path) -> McpServerConfig {
         },
         stateful: false,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     }
 }
 
@@ -48,6 +50,7 @@ fn stateful_config(script_path: &std::path::Path) -> McpServerConfig {
         },
         stateful: true,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     }
 }
 
@@ -110,12 +113,11 @@ done
     Ok(())
 }
 
-#[tokio::test]
-async fn registry_restarts_server_after_crash() -> Result<()> {
-    let temp = tempfile::tempdir()?;
-    let stamp = temp.path().join("first-list.stamp");
-    let script_path = write_script(
-        temp.path(),
+/// A server that exits right after answering its first `tools/list`.
+fn write_crash_once_script(dir: &std::path::Path) -> Result<std::path::PathBuf> {
+    let stamp = dir.join("first-list.stamp");
+    write_script(
+        dir,
         &format!(
             r#"#!/bin/sh
 stamp="{}"
@@ -139,7 +141,13 @@ done
 "#,
             stamp.to_string_lossy()
         ),
-    )?;
+    )
+}
+
+#[tokio::test]
+async fn registry_restarts_server_after_crash() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script_path = write_crash_once_script(temp.path())?;
 
     let registry = McpRegistry::new(vec![McpServerConfig {
         name: "flaky".to_string(),
@@ -150,6 +158,7 @@ done
         },
         stateful: false,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     }]);
 
     let first = registry
@@ -162,10 +171,56 @@ done
         .await?;
     assert_eq!(second[0].name.as_ref(), "echo_tool");
 
+    let health = registry.health_report().await;
+    assert_eq!(health[0].state, HealthState::Healthy);
+    assert_eq!(health[0].restarts, 1);
+    assert!(health[0].error.is_some());
+
     registry.shutdown_all().await?;
     Ok(())
 }
 
+#[tokio::test]
+async fn health_check_restarts_exited_stdio_server_after_backoff() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let script_path = write_crash_once_script(temp.path())?;
+    let health = HealthTracker::default();
+    let mut server = ManagedServer::new(stateless_config(&script_path), health.clone());
+
+    server.list_tools().await?;
+    // Long enough that the fail-fast check below runs inside the backoff.
+    server.restart_backoff = Duration::from_secs(1);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while health.report("mock", "stdio").state != HealthState::Down {
+        assert!(Instant::now() < deadline, "exit was never detected");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        server.health_check().await;
+    }
+    let report = health.report("mock", "stdio");
+    assert!(
+        report
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("exited")
+                || error.contains("closed")
+                || error.contains("ping")),
+        "{report:?}"
+    );
+    assert!(report.next_restart_in_secs.is_some());
+
+    let error = server.list_tools().await.unwrap_err().to_string();
+    assert!(error.contains("is down"), "{error}");
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    server.health_check().await;
+    assert_eq!(health.report("mock", "stdio").state, HealthState::Healthy);
+    assert_eq!(health.report("mock", "stdio").restarts, 1);
+    assert_eq!(server.list_tools().await?[0].name.as_ref(), "echo_tool");
+
+    server.shutdown().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn queue_cancellation_does_not_wait_for_head_of_line() -> Result<()> {
     let temp = tempfile::tempdir()?;
@@ -342,6 +397,7 @@ done
         },
         stateful: true,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     }]);
 
     let result = registry
@@ -513,6 +569,7 @@ async fn registry_tracks_transport_labels() {
         },
         stateful: false,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     };
     let http_config = McpServerConfig {
         name: "remote-mcp".to_string(),
@@ -523,6 +580,7 @@ async fn registry_tracks_transport_labels() {
         },
         stateful: false,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     };
     let sse_config = McpServerConfig {
         name: "sse-mcp".to_string(),
@@ -533,6 +591,7 @@ async fn registry_tracks_transport_labels() {
         },
         stateful: false,
         memory_max_mb: None,
+        tool_renames: HashMap::new(),
     };
 
     let registry = McpRegistry::new(vec![stdio_config, http_config, sse_config]);
@@ -2,26 +2,36 @@ use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use axum::extract::DefaultBodyLimit;
+use rmcp::RoleServer;
+use rmcp::service::Peer;
 use rmcp::transport::streamable_http_server::{
     StreamableHttpServerConfig, StreamableHttpService, session::never::NeverSessionManager,
 };
 use serde_json::{Value, json};
 use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
-use tokio::sync::Semaphore;
+use tokio::sync::{Semaphore, broadcast};
 use tokio_util::sync::CancellationToken;
 
+use crate::call_cache::CallCache;
 use crate::config::HubConfig;
+use crate::config_reload::spawn_config_reload_task;
+use crate::csa_tools::CsaTools;
+use crate::http_auth;
+use crate::metrics::HubMetrics;
+use crate::metrics_endpoint::{MetricsEndpoint, Scrape};
 use crate::proxy::ProxyRouter;
+use crate::rate_limit::{ServerRateLimits, TokenBucket};
 use crate::registry::McpRegistry;
 use crate::skill_writer::{
     SkillRefreshNotifier, SkillRefreshSignal, parse_tools_list_changed_signal,
     spawn_skill_sync_task,
 };
 use crate::socket;
+use crate::tool_skills::{ToolSkillGenerator, ToolSkillMode};
 
 const MCP_PATH: &str = "/mcp";
 
@@ -30,10 +40,22 @@ mod control;
 #[cfg(test)]
 use control::send_control_request;
 pub use control::{
-    handle_gen_skill_command, handle_serve_command, handle_status_command, handle_stop_command,
+    HubSession, handle_gen_skill_command, handle_serve_command, handle_status_command,
+    handle_stop_command, open_session,
 };
 
+#[path = "serve_sessions.rs"]
+mod sessions;
+use sessions::{SessionIdentity, SessionRequest, SessionSockets};
+
 pub(crate) async fn run_hub(cfg: HubConfig, systemd_activation: bool) -> Result<()> {
+    let metrics = Arc::new(match &cfg.request_log {
+        Some(path) => HubMetrics::with_request_log(path)?,
+        None => HubMetrics::default(),
+    });
+    // Under `CSA_STATE_DIR` the socket and pid file live in the user's state tree.
+    csa_config::paths::create_session_state_dir()
+        .context("failed to create the session state directory")?;
     let mut activated_by_systemd = false;
     let listener = if systemd_activation {
         if let Some(listener) = socket::bind_systemd_activated_listener()? {
@@ -49,15 +71,37 @@ pub(crate) async fn run_hub(cfg: HubConfig, systemd_activation: bool) -> Result<
     write_pid_file(&cfg.pid_path).await?;
 
     let registry = Arc::new(McpRegistry::new(cfg.mcp_servers.clone()));
-    let router = Arc::new(ProxyRouter::new(registry.clone(), cfg.request_timeout()));
+    let mut router = ProxyRouter::new(registry.clone(), cfg.request_timeout())
+        .with_tool_filters(cfg.tool_filters.clone())
+        .with_metrics(metrics.clone())
+        .with_call_cache(CallCache::new(cfg.cache_rules.clone()))
+        .with_rate_limits(ServerRateLimits::new(&cfg.rate_limits));
+    if let Some(program) = &cfg.csa_program {
+        router = router.with_csa_tools(CsaTools::new(
+            program.clone(),
+            cfg.project_root.clone(),
+            &cfg.csa_tools,
+        ));
+    }
+    let router = Arc::new(router);
     let http_endpoint = HttpEndpoint::start(&cfg, router.clone()).await?;
+    let metrics_endpoint = match &cfg.metrics_listen {
+        Some(listen) => Some(MetricsEndpoint::start(listen, Scrape::new(metrics)).await?),
+        None => None,
+    };
     let skill_sync = spawn_skill_sync_task(cfg.clone(), registry.clone());
     let skill_notify_tx = skill_sync.notifier();
+    let tool_skills = Arc::new(ToolSkillGenerator::new(&cfg, registry.clone()));
+    let config_reload =
+        spawn_config_reload_task(registry.clone(), router.clone(), skill_notify_tx.clone());
     let next_client_id = Arc::new(AtomicU64::new(1));
     let max_connections = cfg.max_connections.max(1);
     let connection_slots = Arc::new(Semaphore::new(max_connections));
     let connection_policy = ConnectionPolicy::from_config(&cfg);
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+    let (session_sockets, mut session_clients) =
+        SessionSockets::new(&cfg.socket_path, cfg.mcp_filters.clone());
+    session_sockets.clear().await?;
 
     println!(
         "mcp-hub listening on unix://{} and http://{}{}",
@@ -65,10 +109,76 @@ pub(crate) async fn run_hub(cfg: HubConfig, systemd_activation: bool) -> Result<
         http_endpoint.addr,
         MCP_PATH
     );
+    let auth_header = if cfg.http_tokens.is_empty() {
+        ""
+    } else {
+        " --header \"Authorization: Bearer <token>\""
+    };
     println!(
-        "claude mcp add --transport http csa-hub http://{}{}",
+        "claude mcp add --transport http csa-hub http://{}{}{auth_header}",
         http_endpoint.addr, MCP_PATH
     );
+    if let Some(path) = &cfg.request_log {
+        println!("logging tool calls to {}", path.display());
+    }
+    if let Some(endpoint) = &metrics_endpoint {
+        println!("serving metrics at {}", endpoint.url());
+    }
+
+    let spawn_client = |stream: tokio::net::UnixStream, session: Option<SessionIdentity>| {
+        let permit = match connection_slots.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                tracing::warn!(
+                    max_connections,
+                    "rejecting mcp-hub connection: connection limit reached"
+                );
+                return;
+            }
+        };
+
+        let client_id = next_client_id.fetch_add(1, Ordering::Relaxed);
+        // Only connections on the main socket may open session sockets.
+        let control = HubControl {
+            shutdown_tx: shutdown_tx.clone(),
+            sessions: session.is_none().then(|| session_sockets.clone()),
+        };
+        let client_router = Arc::new(match session {
+            Some(SessionIdentity {
+                consumer,
+                tool_filters,
+                project_root,
+            }) => {
+                tracing::debug!(client_id, consumer = %consumer, "mcp-hub client connected");
+                router
+                    .for_consumer(Some(consumer))
+                    .with_tool_filters(tool_filters)
+                    .with_project_root(project_root)
+            }
+            None => {
+                tracing::debug!(client_id, "mcp-hub client connected");
+                router.for_consumer(None)
+            }
+        });
+        let client_skill_notify_tx = skill_notify_tx.clone();
+        let client_tool_skills = tool_skills.clone();
+        tokio::spawn(async move {
+            let _permit = permit;
+            if let Err(error) = handle_client_connection(
+                stream,
+                client_id,
+                client_router,
+                control,
+                connection_policy,
+                client_skill_notify_tx,
+                client_tool_skills,
+            )
+            .await
+            {
+                tracing::warn!(client_id, error = %error, "mcp-hub client connection failed");
+            }
+        });
+    };
 
     loop {
         tokio::select! {
@@ -82,47 +192,28 @@ pub(crate) async fn run_hub(cfg: HubConfig, systemd_activation: bool) -> Result<
             }
             accept_result = listener.accept() => {
                 let (stream, _addr) = accept_result.context("failed to accept mcp-hub client")?;
-                let permit = match connection_slots.clone().try_acquire_owned() {
-                    Ok(permit) => permit,
-                    Err(_) => {
-                        tracing::warn!(
-                            max_connections,
-                            "rejecting mcp-hub connection: connection limit reached"
-                        );
-                        continue;
-                    }
-                };
-
-                let client_id = next_client_id.fetch_add(1, Ordering::Relaxed);
-                let client_router = router.clone();
-                let client_shutdown_tx = shutdown_tx.clone();
-                let client_skill_notify_tx = skill_notify_tx.clone();
-                tokio::spawn(async move {
-                    let _permit = permit;
-                    if let Err(error) = handle_client_connection(
-                        stream,
-                        client_id,
-                        client_router,
-                        client_shutdown_tx,
-                        connection_policy,
-                        client_skill_notify_tx,
-                    )
-                    .await
-                    {
-                        tracing::warn!(client_id, error = %error, "mcp-hub client connection failed");
-                    }
-                });
+                spawn_client(stream, None);
+            }
+            Some((stream, identity)) = session_clients.recv() => {
+                spawn_client(stream, Some(identity));
             }
         }
     }
 
+    if let Some(config_reload) = config_reload {
+        config_reload.shutdown().await;
+    }
     skill_sync.shutdown().await;
     http_endpoint.shutdown().await;
+    if let Some(endpoint) = metrics_endpoint {
+        endpoint.shutdown().await;
+    }
     registry.shutdown_all().await?;
     cleanup_pid_file(&cfg.pid_path).await?;
     if !activated_by_systemd {
         socket::cleanup_socket_file(&cfg.socket_path).await?;
     }
+    session_sockets.clear().await?;
 
     Ok(())
 }
@@ -144,6 +235,18 @@ impl HttpEndpoint {
                     cfg.http_bind, cfg.http_port
                 )
             })?;
+        if !bind_addr.ip().is_loopback() && cfg.http_tokens.is_empty() {
+            anyhow::bail!(
+                "refusing to serve mcp-hub HTTP on non-loopback address {bind_addr} without \
+                 client tokens; add them to {} or pass --http-tokens",
+                http_auth::default_tokens_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| "the mcp-hub tokens file".to_string())
+            );
+        }
+        if let Some(source) = cfg.http_tokens.source() {
+            tracing::info!(tokens = %source.display(), "mcp-hub HTTP endpoint requires bearer tokens");
+        }
 
         let listener = tokio::net::TcpListener::bind(bind_addr)
             .await
@@ -156,7 +259,7 @@ impl HttpEndpoint {
         let session_manager = Arc::new(NeverSessionManager::default());
         let mcp_service = StreamableHttpService::new(
             {
-                let hub_service = (*router).clone();
+                let hub_service = router.for_http();
                 move || Ok(hub_service.clone())
             },
             session_manager,
@@ -168,7 +271,11 @@ impl HttpEndpoint {
 
         let app = axum::Router::new()
             .route(MCP_PATH, axum::routing::any_service(mcp_service))
-            .layer(DefaultBodyLimit::max(cfg.max_request_body_bytes));
+            .layer(DefaultBodyLimit::max(cfg.max_request_body_bytes))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(cfg.http_tokens.clone()),
+                http_auth::require_token,
+            ));
         let server_shutdown = shutdown.clone();
         let server_task = tokio::spawn(async move {
             if let Err(error) = axum::serve(listener, app)
@@ -196,6 +303,23 @@ impl HttpEndpoint {
     }
 }
 
+/// Hub-wide controls a client connection may use.
+#[derive(Debug, Clone)]
+struct HubControl {
+    shutdown_tx: tokio::sync::watch::Sender<bool>,
+    /// Set for connections on the main socket, which may open sessions.
+    sessions: Option<SessionSockets>,
+}
+
+impl From<tokio::sync::watch::Sender<bool>> for HubControl {
+    fn from(shutdown_tx: tokio::sync::watch::Sender<bool>) -> Self {
+        Self {
+            shutdown_tx,
+            sessions: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ConnectionPolicy {
     max_requests_per_sec: u32,
@@ -215,51 +339,19 @@ impl ConnectionPolicy {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct TokenBucket {
-    capacity: f64,
-    tokens: f64,
-    refill_per_sec: f64,
-    last_refill: Instant,
-}
-
-impl TokenBucket {
-    fn new(max_requests_per_sec: u32) -> Self {
-        let refill_per_sec = f64::from(max_requests_per_sec.max(1));
-        Self {
-            capacity: refill_per_sec,
-            tokens: refill_per_sec,
-            refill_per_sec,
-            last_refill: Instant::now(),
-        }
-    }
-
-    fn try_consume(&mut self) -> bool {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
-        self.last_refill = now;
-        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
-        if self.tokens >= 1.0 {
-            self.tokens -= 1.0;
-            true
-        } else {
-            false
-        }
-    }
-}
-
 async fn handle_client_connection(
     stream: tokio::net::UnixStream,
     client_id: u64,
     router: Arc<ProxyRouter>,
-    shutdown_tx: tokio::sync::watch::Sender<bool>,
+    control: HubControl,
     policy: ConnectionPolicy,
     skill_notify_tx: SkillRefreshNotifier,
+    tool_skills: Arc<ToolSkillGenerator>,
 ) -> Result<()> {
-    let peer_uid = stream
+    let peer_cred = stream
         .peer_cred()
-        .context("failed to read peer credentials")?
-        .uid();
+        .context("failed to read peer credentials")?;
+    let peer_uid = peer_cred.uid();
     let (read_half, mut write_half) = stream.into_split();
     let mut reader = BufReader::new(read_half);
     let mut limiter = TokenBucket::new(policy.max_requests_per_sec);
@@ -324,6 +416,12 @@ async fn handle_client_connection(
         return Ok(());
     }
 
+    if method == Some("hub/stats") {
+        let result = router.stats_payload();
+        write_json_line(&mut write_half, &jsonrpc_result(request_id, result)).await?;
+        return Ok(());
+    }
+
     if method == Some("hub/stop") {
         if peer_uid != policy.current_uid {
             write_json_line(
@@ -337,7 +435,7 @@ async fn handle_client_connection(
             .await?;
             return Ok(());
         }
-        let _ = shutdown_tx.send(true);
+        let _ = control.shutdown_tx.send(true);
         write_json_line(
             &mut write_half,
             &jsonrpc_result(request_id, json!({"stopping": true})),
@@ -346,6 +444,43 @@ async fn handle_client_connection(
         return Ok(());
     }
 
+    if method == Some("hub/open-session") {
+        let Some(sessions) = control.sessions.filter(|_| peer_uid == policy.current_uid) else {
+            write_json_line(
+                &mut write_half,
+                &jsonrpc_error(
+                    request_id,
+                    -32004,
+                    "permission denied: sessions are opened by the hub owner on the hub socket"
+                        .to_string(),
+                ),
+            )
+            .await?;
+            return Ok(());
+        };
+        let param = |name: &str| {
+            first_message
+                .pointer(&format!("/params/{name}"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        };
+        let request = SessionRequest {
+            consumer: param("tool"),
+            project_root: param("projectRoot"),
+            holder_pid: peer_cred.pid().and_then(|pid| u32::try_from(pid).ok()),
+        };
+        let response = match sessions.open(request, reader).await {
+            Ok(socket_path) => jsonrpc_result(request_id, json!({"socketPath": socket_path})),
+            Err(error) => jsonrpc_error(
+                request_id,
+                -32602,
+                format!("failed to open mcp-hub session: {error:#}"),
+            ),
+        };
+        write_json_line(&mut write_half, &response).await?;
+        return Ok(());
+    }
+
     if method == Some("hub/gen-skill") {
         if peer_uid != policy.current_uid {
             write_json_line(
@@ -361,11 +496,24 @@ async fn handle_client_connection(
         }
 
         skill_notify_tx.notify(SkillRefreshSignal::RegenerateAll);
-        write_json_line(
-            &mut write_half,
-            &jsonrpc_result(request_id, json!({"queued": true})),
-        )
-        .await?;
+        let update = first_message
+            .pointer("/params/update")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let mode = if update {
+            ToolSkillMode::UpdateInPlace
+        } else {
+            ToolSkillMode::Regenerate
+        };
+        let response = match tool_skills.generate(mode).await {
+            Ok(report) => jsonrpc_result(request_id, json!({"queued": true, "toolSkills": report})),
+            Err(error) => jsonrpc_error(
+                request_id,
+                -32603,
+                format!("failed to generate tool skills: {error:#}"),
+            ),
+        };
+        write_json_line(&mut write_half, &response).await?;
         return Ok(());
     }
 
@@ -513,7 +661,10 @@ async fn handle_client_connection(
 
     let running =
         rmcp::service::serve_directly((*router).clone(), (prefill_read, write_half), None);
+    let list_changed_task =
+        spawn_list_changed_forwarder(running.peer().clone(), router.subscribe_list_changed());
     let waiting_result = running.waiting().await;
+    list_changed_task.abort();
     copy_task
         .await
         .context("failed to join MCP stream forwarding task")?;
@@ -521,6 +672,25 @@ async fn handle_client_connection(
     Ok(())
 }
 
+/// Relay tool list changes to a connected client until it disconnects.
+fn spawn_list_changed_forwarder(
+    peer: Peer<RoleServer>,
+    mut list_changed: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match list_changed.recv().await {
+                Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+            if let Err(error) = peer.notify_tool_list_changed().await {
+                tracing::debug!(error = %error, "failed to send tools/list_changed to client");
+                break;
+            }
+        }
+    })
+}
+
 async fn write_json_line<W: AsyncWrite + Unpin>(writer: &mut W, value: &Value) -> Result<()> {
     let payload = serde_json::to_string(value).context("failed to serialize JSON-RPC payload")?;
     writer
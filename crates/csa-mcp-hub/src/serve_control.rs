@@ -6,10 +6,15 @@ use std::process::Stdio;
 use anyhow::{Context, Result, bail};
 use serde_json::{Value, json};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
 
 use crate::config::{HubConfig, default_socket_path};
+use crate::http_auth::HttpTokens;
 use crate::skill_writer::regenerate_routing_skill_once;
 use crate::socket;
+use crate::tool_skills::ToolSkillMode;
+
+const SESSION_OPEN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
 pub async fn handle_serve_command(
     background: bool,
@@ -17,6 +22,7 @@ pub async fn handle_serve_command(
     socket_override: Option<String>,
     http_bind_override: Option<String>,
     http_port_override: Option<u16>,
+    http_tokens_override: Option<String>,
     systemd_activation: bool,
 ) -> Result<()> {
     if background && !foreground {
@@ -24,21 +30,23 @@ pub async fn handle_serve_command(
             socket_override.as_deref(),
             http_bind_override.as_deref(),
             http_port_override,
+            http_tokens_override.as_deref(),
             systemd_activation,
         )?;
         println!("mcp-hub started in background (pid={pid})");
         return Ok(());
     }
 
-    let cfg = HubConfig::load(
+    let mut cfg = HubConfig::load(
         socket_override.map(PathBuf::from),
         http_bind_override,
         http_port_override,
     )?;
+    cfg.http_tokens = HttpTokens::load(http_tokens_override.map(PathBuf::from))?;
     super::run_hub(cfg, systemd_activation).await
 }
 
-pub async fn handle_status_command(socket_override: Option<String>) -> Result<()> {
+pub async fn handle_status_command(socket_override: Option<String>, stats: bool) -> Result<()> {
     let socket_path = socket_override
         .map(PathBuf::from)
         .unwrap_or_else(default_socket_path);
@@ -52,6 +60,29 @@ pub async fn handle_status_command(socket_override: Option<String>) -> Result<()
                     socket_path.display(),
                     servers
                 );
+                let down = down_server_lines(result);
+                if !down.is_empty() {
+                    println!("degraded: {} server(s) down", down.len());
+                    for line in down {
+                        println!("  {line}");
+                    }
+                }
+                let collisions = collision_lines(result);
+                if !collisions.is_empty() {
+                    println!("tool name collisions (exposed namespaced):");
+                    for line in collisions {
+                        println!("  {line}");
+                    }
+                }
+                if stats {
+                    let response = send_control_request(&socket_path, "hub/stats").await?;
+                    let stats = response
+                        .get("result")
+                        .with_context(|| format!("mcp-hub returned no stats: {response}"))?;
+                    for line in stats_lines(stats) {
+                        println!("{line}");
+                    }
+                }
             } else {
                 println!(
                     "mcp-hub responded at {}, but status payload was empty",
@@ -67,6 +98,117 @@ pub async fn handle_status_command(socket_override: Option<String>) -> Result<()
     Ok(())
 }
 
+/// One line per server the status payload reports as down.
+pub(super) fn down_server_lines(status: &Value) -> Vec<String> {
+    let Some(health) = status.get("health").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    health
+        .iter()
+        .filter(|report| report["state"] == "down")
+        .map(|report| {
+            let mut line = format!(
+                "{} ({}): {}",
+                report["name"].as_str().unwrap_or("?"),
+                report["transport"].as_str().unwrap_or("?"),
+                report["error"].as_str().unwrap_or("unknown error"),
+            );
+            if let Some(secs) = report["next_restart_in_secs"].as_u64() {
+                line.push_str(&format!("; restarting in {secs}s"));
+            }
+            let restarts = report["restarts"].as_u64().unwrap_or(0);
+            if restarts > 0 {
+                line.push_str(&format!("; restarted {restarts} time(s)"));
+            }
+            line
+        })
+        .collect()
+}
+
+/// One line per tool name several servers claimed, listing the namespaced
+/// names it is exposed under.
+pub(super) fn collision_lines(status: &Value) -> Vec<String> {
+    let Some(collisions) = status.get("toolCollisions").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    collisions
+        .iter()
+        .map(|collision| {
+            let exposed: Vec<String> = collision["tools"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|tool| {
+                    format!(
+                        "{} ({}.{})",
+                        tool["exposed_as"].as_str().unwrap_or("?"),
+                        tool["server"].as_str().unwrap_or("?"),
+                        tool["tool"].as_str().unwrap_or("?"),
+                    )
+                })
+                .collect();
+            format!(
+                "{}: {}",
+                collision["name"].as_str().unwrap_or("?"),
+                exposed.join(", ")
+            )
+        })
+        .collect()
+}
+
+/// Per-server totals followed by one row per tool.
+pub(super) fn stats_lines(stats: &Value) -> Vec<String> {
+    let mut lines = vec![format!(
+        "tool calls since {}",
+        stats["since"].as_str().unwrap_or("hub start")
+    )];
+    if let Some(path) = stats["request_log"].as_str() {
+        lines.push(format!("request log: {path}"));
+    }
+    let servers = stats["servers"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    if servers.is_empty() {
+        lines.push("no tool calls yet".to_string());
+        return lines;
+    }
+    for server in servers {
+        let (calls, errors) = counts(server);
+        lines.push(format!(
+            "{}: {calls} calls, {errors} errors ({})",
+            server["server"].as_str().unwrap_or("?"),
+            error_rate(calls, errors)
+        ));
+        for tool in server["tools"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+            let (calls, errors) = counts(tool);
+            lines.push(format!(
+                "  {:<32} {calls:>6} calls {:>6} err  p50 {}ms  p95 {}ms  p99 {}ms",
+                tool["tool"].as_str().unwrap_or("?"),
+                error_rate(calls, errors),
+                tool["p50_ms"].as_u64().unwrap_or(0),
+                tool["p95_ms"].as_u64().unwrap_or(0),
+                tool["p99_ms"].as_u64().unwrap_or(0),
+            ));
+        }
+    }
+    lines
+}
+
+fn counts(entry: &Value) -> (u64, u64) {
+    (
+        entry["calls"].as_u64().unwrap_or(0),
+        entry["errors"].as_u64().unwrap_or(0),
+    )
+}
+
+fn error_rate(calls: u64, errors: u64) -> String {
+    if calls == 0 {
+        return "0.0%".to_string();
+    }
+    format!("{:.1}%", errors as f64 * 100.0 / calls as f64)
+}
+
 pub async fn handle_stop_command(socket_override: Option<String>) -> Result<()> {
     let socket_path = socket_override
         .map(PathBuf::from)
@@ -84,12 +226,13 @@ pub async fn handle_stop_command(socket_override: Option<String>) -> Result<()>
     Ok(())
 }
 
-pub async fn handle_gen_skill_command(socket_override: Option<String>) -> Result<()> {
+pub async fn handle_gen_skill_command(socket_override: Option<String>, update: bool) -> Result<()> {
     let socket_path = socket_override
         .map(PathBuf::from)
         .unwrap_or_else(default_socket_path);
 
-    match send_control_request(&socket_path, "hub/gen-skill").await {
+    let params = json!({ "update": update });
+    match send_control_request_with_params(&socket_path, "hub/gen-skill", params).await {
         Ok(response) => {
             if response.get("error").is_some() {
                 bail!("mcp-hub returned an error while regenerating skill: {response}");
@@ -98,23 +241,98 @@ pub async fn handle_gen_skill_command(socket_override: Option<String>) -> Result
                 "requested routing-guide skill regeneration via running hub at {}",
                 socket_path.display()
             );
+            let report = response
+                .pointer("/result/toolSkills")
+                .unwrap_or(&Value::Null);
+            for line in tool_skill_lines(report) {
+                println!("{line}");
+            }
             Ok(())
         }
         Err(_) => {
             let cfg = HubConfig::load(None, None, None)?;
-            regenerate_routing_skill_once(cfg).await?;
+            let mode = if update {
+                ToolSkillMode::UpdateInPlace
+            } else {
+                ToolSkillMode::Regenerate
+            };
+            let report = regenerate_routing_skill_once(cfg, mode).await?;
             println!("generated routing-guide skill via one-shot mcp-hub run");
+            for line in tool_skill_lines(&json!(report)) {
+                println!("{line}");
+            }
             Ok(())
         }
     }
 }
 
+/// Summary of a `toolSkills` report from `hub/gen-skill`.
+pub(super) fn tool_skill_lines(report: &Value) -> Vec<String> {
+    let names = |key: &str| -> Vec<String> {
+        report
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    if report.is_null() {
+        return Vec::new();
+    }
+
+    let written = names("written");
+    let removed = names("removed");
+    let unchanged = report.get("unchanged").and_then(Value::as_u64).unwrap_or(0);
+    let links = report
+        .get("linksCreated")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let mut lines = vec![format!(
+        "tool skills: {} written, {unchanged} unchanged, {} removed, {links} new links",
+        written.len(),
+        removed.len()
+    )];
+    let unreachable = names("unreachable");
+    if !unreachable.is_empty() {
+        lines.push(format!(
+            "  servers not answering (skills kept on --update): {}",
+            unreachable.join(", ")
+        ));
+    }
+    for error in names("linkErrors") {
+        lines.push(format!("  link error: {error}"));
+    }
+    lines
+}
+
 pub(super) async fn send_control_request(socket_path: &Path, method: &str) -> Result<Value> {
+    send_control_request_with_params(socket_path, method, json!({})).await
+}
+
+async fn send_control_request_with_params(
+    socket_path: &Path,
+    method: &str,
+    params: Value,
+) -> Result<Value> {
     let mut stream = socket::connect(socket_path).await?;
+    exchange_control_request(&mut stream, method, params).await
+}
+
+async fn exchange_control_request(
+    stream: &mut UnixStream,
+    method: &str,
+    params: Value,
+) -> Result<Value> {
     let request = json!({
         "jsonrpc": "2.0",
         "id": 1,
         "method": method,
+        "params": params,
     });
 
     let payload = serde_json::to_string(&request).context("failed to serialize control request")?;
@@ -144,10 +362,54 @@ pub(super) async fn send_control_request(socket_path: &Path, method: &str) -> Re
     serde_json::from_str(line.trim()).context("failed to parse control response")
 }
 
+/// Hub session socket for one agent run. The hub removes the socket once
+/// this is dropped.
+#[derive(Debug)]
+pub struct HubSession {
+    socket_path: PathBuf,
+    _lease: UnixStream,
+}
+
+impl HubSession {
+    /// Socket the agent connects to; the hub serves it as the session's tool.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+}
+
+/// Open a session socket on the hub at `hub_socket` for consumer `tool`
+/// running in `project_root`, whose `[mcp_filters]` the session applies.
+pub async fn open_session(
+    hub_socket: &Path,
+    tool: &str,
+    project_root: &Path,
+) -> Result<HubSession> {
+    let mut lease = socket::connect(hub_socket).await?;
+    let response = tokio::time::timeout(
+        SESSION_OPEN_TIMEOUT,
+        exchange_control_request(
+            &mut lease,
+            "hub/open-session",
+            json!({"tool": tool, "projectRoot": project_root}),
+        ),
+    )
+    .await
+    .context("timed out opening mcp-hub session")??;
+    let socket_path = response
+        .pointer("/result/socketPath")
+        .and_then(Value::as_str)
+        .with_context(|| format!("mcp-hub refused to open a session: {response}"))?;
+    Ok(HubSession {
+        socket_path: PathBuf::from(socket_path),
+        _lease: lease,
+    })
+}
+
 fn spawn_background(
     socket_override: Option<&str>,
     http_bind_override: Option<&str>,
     http_port_override: Option<u16>,
+    http_tokens_override: Option<&str>,
     systemd_activation: bool,
 ) -> Result<u32> {
     let exe = std::env::current_exe().context("failed to resolve current executable")?;
@@ -162,6 +424,9 @@ fn spawn_background(
     if let Some(http_port) = http_port_override {
         cmd.arg("--http-port").arg(http_port.to_string());
     }
+    if let Some(http_tokens) = http_tokens_override {
+        cmd.arg("--http-tokens").arg(http_tokens);
+    }
     if systemd_activation {
         cmd.arg("--systemd-activation");
     }
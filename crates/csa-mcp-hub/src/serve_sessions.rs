@@ -0,0 +1,412 @@
+//! Per-session sockets identifying the consumer tool of a hub client.
+//!
+//! csa opens a session with `hub/open-session` before it spawns an agent. The
+//! hub binds a socket whose name is a fresh random token and serves every
+//! connection on it with the identity fixed when the token was issued: the
+//! consumer tool and the `[mcp_filters]` of the session's project. Nothing
+//! the agent reports about itself is trusted. The socket lives as long as
+//! the connection that opened it, which csa holds for the run.
+//!
+//! Where the process tree is readable, the token is also bound to the csa
+//! process holding the lease: only that process and its descendants may
+//! connect, and a session opened from inside another session's run keeps
+//! the enclosing session's identity, so an agent cannot trade its filters
+//! for another tool's by opening a session of its own.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result, bail};
+use csa_config::McpFilter;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::net::UnixStream;
+use tokio::sync::mpsc;
+
+use crate::config::load_project_mcp_filters;
+use crate::socket;
+use crate::tool_filter::ToolFilters;
+
+/// Random bytes in a session token.
+const TOKEN_BYTES: usize = 32;
+/// Ancestors walked before a process is treated as unrelated.
+const MAX_ANCESTRY_DEPTH: usize = 64;
+
+/// Identity every connection on a session socket is served as.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionIdentity {
+    pub(crate) consumer: String,
+    pub(crate) tool_filters: ToolFilters,
+    pub(crate) project_root: PathBuf,
+}
+
+/// Connection accepted on a session socket, with its session's identity.
+pub(crate) type SessionClient = (UnixStream, SessionIdentity);
+
+/// Token and identity of each open session, by the PID holding its lease.
+type Leases = HashMap<u32, Vec<(String, SessionIdentity)>>;
+
+/// What csa asked for in `hub/open-session`.
+#[derive(Debug)]
+pub(crate) struct SessionRequest<'a> {
+    pub(crate) consumer: &'a str,
+    pub(crate) project_root: &'a str,
+    /// PID of the csa process holding the lease, when the peer reported one.
+    pub(crate) holder_pid: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SessionSockets {
+    dir: PathBuf,
+    clients: mpsc::UnboundedSender<SessionClient>,
+    global_filters: Arc<HashMap<String, McpFilter>>,
+    /// Open sessions by the PID holding their lease.
+    leases: Arc<Mutex<Leases>>,
+}
+
+impl SessionSockets {
+    /// Session sockets for the hub listening on `socket_path`, filtered by
+    /// the global `[mcp_filters]` plus those of each session's project.
+    pub(crate) fn new(
+        socket_path: &Path,
+        global_filters: HashMap<String, McpFilter>,
+    ) -> (Self, mpsc::UnboundedReceiver<SessionClient>) {
+        let (clients, receiver) = mpsc::unbounded_channel();
+        let sessions = Self {
+            dir: socket_path.with_extension("sessions"),
+            clients,
+            global_filters: Arc::new(global_filters),
+            leases: Arc::default(),
+        };
+        (sessions, receiver)
+    }
+
+    /// Remove sockets left behind by a previous hub.
+    pub(crate) async fn clear(&self) -> Result<()> {
+        match tokio::fs::remove_dir_all(&self.dir).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error).with_context(|| {
+                format!("failed to remove session sockets: {}", self.dir.display())
+            }),
+        }
+    }
+
+    /// Bind a session socket for `request` and return its path. Connections
+    /// to it are handed to the hub until `lease` reaches end of file.
+    pub(crate) async fn open<R>(&self, request: SessionRequest<'_>, lease: R) -> Result<PathBuf>
+    where
+        R: AsyncBufRead + Unpin + Send + 'static,
+    {
+        let identity = self.identity_for(&request)?;
+        let token = new_token()?;
+        let socket_path = self.dir.join(format!("{token}.sock"));
+        let listener = socket::bind_listener(&socket_path).await?;
+        let holder_pid = request.holder_pid;
+        if let Some(pid) = holder_pid {
+            lock_leases(&self.leases)
+                .entry(pid)
+                .or_default()
+                .push((token.clone(), identity.clone()));
+        }
+        let clients = self.clients.clone();
+        let leases = Arc::clone(&self.leases);
+        let path = socket_path.clone();
+        tokio::spawn(async move {
+            let accept_loop = async {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, _addr)) => {
+                            if !peer_belongs_to_lease(&stream, holder_pid) {
+                                tracing::warn!(
+                                    consumer = %identity.consumer,
+                                    "rejecting mcp-hub session client outside the session's run"
+                                );
+                                continue;
+                            }
+                            if clients.send((stream, identity.clone())).is_err() {
+                                break;
+                            }
+                        }
+                        Err(error) => {
+                            tracing::warn!(error = %error, "failed to accept mcp-hub session client");
+                            break;
+                        }
+                    }
+                }
+            };
+            tokio::select! {
+                () = accept_loop => {}
+                () = wait_for_eof(lease) => {}
+            }
+            if let Some(pid) = holder_pid {
+                release_lease(&leases, pid, &token);
+            }
+            if let Err(error) = socket::cleanup_socket_file(&path).await {
+                tracing::debug!(error = %error, "failed to remove mcp-hub session socket");
+            }
+        });
+        Ok(socket_path)
+    }
+
+    /// Identity of a new session: the enclosing session's when the holder
+    /// runs inside one, otherwise the requested tool with the filters of
+    /// the requested project.
+    fn identity_for(&self, request: &SessionRequest<'_>) -> Result<SessionIdentity> {
+        if request.consumer.trim().is_empty() {
+            bail!("missing required parameter: tool");
+        }
+        if let Some(enclosing) = request
+            .holder_pid
+            .map(|pid| self.enclosing_identity(pid))
+            .transpose()?
+            .flatten()
+        {
+            tracing::debug!(
+                requested = request.consumer,
+                consumer = %enclosing.consumer,
+                "nested mcp-hub session keeps the enclosing session's identity"
+            );
+            return Ok(enclosing);
+        }
+        let project_root = Path::new(request.project_root);
+        if !project_root.is_absolute() {
+            bail!("missing or relative parameter: projectRoot");
+        }
+        let project_filters = load_project_mcp_filters(project_root)?;
+        Ok(SessionIdentity {
+            consumer: request.consumer.to_string(),
+            tool_filters: ToolFilters::new(&self.global_filters, project_filters),
+            project_root: project_root.to_path_buf(),
+        })
+    }
+
+    /// Identity of the nearest session held by an ancestor of `pid`.
+    fn enclosing_identity(&self, pid: u32) -> Result<Option<SessionIdentity>> {
+        let Some(ancestors) = ancestors(pid) else {
+            return Ok(None);
+        };
+        let leases = lock_leases(&self.leases);
+        let Some(held) = ancestors.iter().find_map(|ancestor| leases.get(ancestor)) else {
+            return Ok(None);
+        };
+        let (_, first) = &held[0];
+        if held
+            .iter()
+            .any(|(_, identity)| identity.consumer != first.consumer)
+        {
+            bail!("cannot tell which of its parent's hub sessions process {pid} belongs to");
+        }
+        Ok(Some(first.clone()))
+    }
+}
+
+fn lock_leases(leases: &Mutex<Leases>) -> std::sync::MutexGuard<'_, Leases> {
+    leases
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn release_lease(leases: &Mutex<Leases>, pid: u32, token: &str) {
+    let mut leases = lock_leases(leases);
+    if let Some(held) = leases.get_mut(&pid) {
+        held.retain(|(held_token, _)| held_token != token);
+        if held.is_empty() {
+            leases.remove(&pid);
+        }
+    }
+}
+
+/// Whether the peer of `stream` is the lease holder or one of its
+/// descendants. Peers are accepted when either side cannot be identified.
+fn peer_belongs_to_lease(stream: &UnixStream, holder_pid: Option<u32>) -> bool {
+    let Some(holder) = holder_pid else {
+        return true;
+    };
+    let Some(peer) = stream
+        .peer_cred()
+        .ok()
+        .and_then(|cred| cred.pid())
+        .and_then(|pid| u32::try_from(pid).ok())
+    else {
+        return true;
+    };
+    peer == holder || ancestors(peer).is_none_or(|ancestors| ancestors.contains(&holder))
+}
+
+/// Ancestors of `pid`, nearest first, or `None` where the process tree is
+/// not readable.
+fn ancestors(pid: u32) -> Option<Vec<u32>> {
+    if !Path::new("/proc/self/stat").exists() {
+        return None;
+    }
+    let mut ancestors = Vec::new();
+    let mut current = pid;
+    while ancestors.len() < MAX_ANCESTRY_DEPTH {
+        match parent_pid(current) {
+            Some(parent) if parent > 1 => {
+                ancestors.push(parent);
+                current = parent;
+            }
+            _ => break,
+        }
+    }
+    Some(ancestors)
+}
+
+fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name may contain spaces and parentheses; fields resume
+    // after the last ')': state, then the parent PID.
+    let fields = &stat[stat.rfind(')')? + 1..];
+    fields.split_whitespace().nth(1)?.parse().ok()
+}
+
+async fn wait_for_eof<R: AsyncBufRead + Unpin>(mut lease: R) {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match lease.read_line(&mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+}
+
+fn new_token() -> Result<String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::fill(&mut bytes)
+        .map_err(|error| anyhow::anyhow!("failed to generate session token: {error}"))?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::tool_filter::allows;
+
+    fn project_with_codex_filter() -> Result<tempfile::TempDir> {
+        let temp = tempfile::tempdir()?;
+        std::fs::create_dir_all(temp.path().join(".csa"))?;
+        std::fs::write(
+            temp.path().join(".csa/config.toml"),
+            "[mcp_filters.codex]\ntools = [\"github.*\"]\n",
+        )?;
+        Ok(temp)
+    }
+
+    fn request<'a>(consumer: &'a str, project: &'a Path, holder_pid: u32) -> SessionRequest<'a> {
+        SessionRequest {
+            consumer,
+            project_root: project.to_str().expect("utf-8 temp path"),
+            holder_pid: Some(holder_pid),
+        }
+    }
+
+    #[tokio::test]
+    async fn session_socket_serves_consumer_until_lease_closes() -> Result<()> {
+        let temp = project_with_codex_filter()?;
+        let (sessions, mut clients) =
+            SessionSockets::new(&temp.path().join("hub.sock"), HashMap::new());
+        let (lease, holder) = UnixStream::pair()?;
+        let socket_path = sessions
+            .open(
+                request("codex", temp.path(), std::process::id()),
+                tokio::io::BufReader::new(lease),
+            )
+            .await?;
+        assert_eq!(
+            socket_path.parent(),
+            Some(temp.path().join("hub.sessions").as_path())
+        );
+
+        let _client = UnixStream::connect(&socket_path).await?;
+        let (_stream, identity) = clients.recv().await.context("no session client")?;
+        assert_eq!(identity.consumer, "codex");
+        let filters = identity.tool_filters.for_consumer(Some("codex"));
+        assert!(allows(filters, "github", "create_issue"));
+        assert!(!allows(filters, "memory", "store"));
+
+        drop(holder);
+        for _ in 0..50 {
+            if !socket_path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(!socket_path.exists());
+        assert!(lock_leases(&sessions.leases).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nested_sessions_keep_the_enclosing_identity() -> Result<()> {
+        let temp = project_with_codex_filter()?;
+        let (sessions, _clients) =
+            SessionSockets::new(&temp.path().join("hub.sock"), HashMap::new());
+        let parent = parent_pid(std::process::id()).context("no parent process")?;
+        let (outer_lease, _outer_holder) = UnixStream::pair()?;
+        sessions
+            .open(
+                request("codex", temp.path(), parent),
+                tokio::io::BufReader::new(outer_lease),
+            )
+            .await?;
+
+        let nested = sessions.identity_for(&request(
+            "claude-code",
+            Path::new("/nonexistent"),
+            std::process::id(),
+        ))?;
+
+        assert_eq!(nested.consumer, "codex");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn session_socket_rejects_peers_outside_the_run() -> Result<()> {
+        let temp = project_with_codex_filter()?;
+        let (sessions, mut clients) =
+            SessionSockets::new(&temp.path().join("hub.sock"), HashMap::new());
+        let mut unrelated = std::process::Command::new("sleep").arg("30").spawn()?;
+        let (lease, _holder) = UnixStream::pair()?;
+        let socket_path = sessions
+            .open(
+                request("codex", temp.path(), unrelated.id()),
+                tokio::io::BufReader::new(lease),
+            )
+            .await?;
+
+        let _client = UnixStream::connect(&socket_path).await?;
+        let accepted = tokio::time::timeout(Duration::from_millis(200), clients.recv()).await;
+        unrelated.kill()?;
+        unrelated.wait()?;
+
+        assert!(accepted.is_err(), "a peer outside the run must be rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn sessions_require_an_absolute_project_root() {
+        let (sessions, _clients) = SessionSockets::new(Path::new("/tmp/hub.sock"), HashMap::new());
+        let error = sessions
+            .identity_for(&SessionRequest {
+                consumer: "codex",
+                project_root: "relative/project",
+                holder_pid: None,
+            })
+            .expect_err("relative project root must be refused");
+        assert!(error.to_string().contains("projectRoot"));
+    }
+
+    #[test]
+    fn tokens_are_unique_hex() -> Result<()> {
+        let token = new_token()?;
+        assert_eq!(token.len(), TOKEN_BYTES * 2);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, new_token()?);
+        Ok(())
+    }
+}
@@ -5,8 +5,21 @@ use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
 use super::send_control_request;
+use crate::config::HubConfig;
 use crate::proxy::ProxyRouter;
 use crate::registry::McpRegistry;
+use crate::tool_skills::ToolSkillGenerator;
+
+/// Tool skill generator over no servers, writing under `project_root`.
+fn tool_skills(project_root: &std::path::Path) -> Arc<ToolSkillGenerator> {
+    let mut cfg =
+        HubConfig::from_global_config(&csa_config::GlobalConfig::default(), None, None, None);
+    cfg.project_root = project_root.to_path_buf();
+    Arc::new(ToolSkillGenerator::new(
+        &cfg,
+        Arc::new(McpRegistry::new(Vec::new())),
+    ))
+}
 
 #[tokio::test]
 async fn send_control_request_round_trip() -> Result<()> {
@@ -42,13 +55,85 @@ async fn send_control_request_round_trip() -> Result<()> {
 }
 
 #[test]
-fn token_bucket_refills_over_time() {
-    let mut limiter = super::TokenBucket::new(2);
-    assert!(limiter.try_consume());
-    assert!(limiter.try_consume());
-    assert!(!limiter.try_consume());
-    std::thread::sleep(Duration::from_millis(600));
-    assert!(limiter.try_consume());
+fn status_lists_down_servers_with_reason() {
+    let status = json!({
+        "running": true,
+        "degraded": true,
+        "health": [
+            {"name": "fs", "transport": "stdio", "state": "healthy", "restarts": 0},
+            {
+                "name": "github",
+                "transport": "stdio",
+                "state": "down",
+                "error": "process exited with exit status: 1",
+                "restarts": 2,
+                "next_restart_in_secs": 4
+            },
+            {"name": "search", "transport": "http", "state": "down", "error": "transport closed", "restarts": 0}
+        ]
+    });
+
+    assert_eq!(
+        super::control::down_server_lines(&status),
+        vec![
+            "github (stdio): process exited with exit status: 1; restarting in 4s; restarted 2 time(s)",
+            "search (http): transport closed",
+        ]
+    );
+    assert!(super::control::down_server_lines(&json!({"running": true})).is_empty());
+}
+
+#[test]
+fn status_lists_tool_name_collisions() {
+    let status = json!({
+        "running": true,
+        "toolCollisions": [{
+            "name": "search",
+            "tools": [
+                {"server": "github", "tool": "search", "exposed_as": "github__search"},
+                {"server": "gitlab", "tool": "search", "exposed_as": "gitlab__search"}
+            ]
+        }]
+    });
+
+    assert_eq!(
+        super::control::collision_lines(&status),
+        vec!["search: github__search (github.search), gitlab__search (gitlab.search)"]
+    );
+    assert!(super::control::collision_lines(&json!({"running": true})).is_empty());
+}
+
+#[test]
+fn stats_lines_show_error_rates_and_latency() {
+    let stats = json!({
+        "since": "2026-10-16T08:00:00Z",
+        "servers": [{
+            "server": "github",
+            "calls": 4,
+            "errors": 1,
+            "tools": [{
+                "tool": "search",
+                "calls": 4,
+                "errors": 1,
+                "p50_ms": 120,
+                "p95_ms": 480,
+                "p99_ms": 510
+            }]
+        }]
+    });
+
+    let lines = super::control::stats_lines(&stats);
+    assert_eq!(lines[0], "tool calls since 2026-10-16T08:00:00Z");
+    assert_eq!(lines[1], "github: 4 calls, 1 errors (25.0%)");
+    assert!(lines[2].starts_with("  search "), "{}", lines[2]);
+    assert!(
+        lines[2].ends_with("4 calls  25.0% err  p50 120ms  p95 480ms  p99 510ms"),
+        "{}",
+        lines[2]
+    );
+
+    let empty = super::control::stats_lines(&json!({"since": "now", "servers": []}));
+    assert_eq!(empty.last().map(String::as_str), Some("no tool calls yet"));
 }
 
 #[tokio::test]
@@ -67,14 +152,16 @@ async fn large_first_frame_is_processed_without_deadlock() -> Result<()> {
     };
     let (skill_notify_tx, _skill_notify_rx) = tokio::sync::mpsc::channel(1);
     let skill_notify_tx = super::SkillRefreshNotifier::new(skill_notify_tx);
+    let project = tempfile::tempdir()?;
 
     let server_task = tokio::spawn(super::handle_client_connection(
         server,
         1,
         router,
-        shutdown_tx,
+        shutdown_tx.into(),
         policy,
         skill_notify_tx,
+        tool_skills(project.path()),
     ));
 
     let (client_read, mut client_write) = client.into_split();
@@ -127,14 +214,16 @@ async fn control_plane_round_trip(
     };
     let (skill_notify_tx, _skill_notify_rx) = tokio::sync::mpsc::channel(1);
     let skill_notify_tx = super::SkillRefreshNotifier::new(skill_notify_tx);
+    let project = tempfile::tempdir()?;
 
     let server_task = tokio::spawn(super::handle_client_connection(
         server,
         1,
         router,
-        shutdown_tx,
+        shutdown_tx.into(),
         policy,
         skill_notify_tx,
+        tool_skills(project.path()),
     ));
 
     let (client_read, mut client_write) = client.into_split();
@@ -164,6 +253,24 @@ async fn control_plane_round_trip(
     Ok(response)
 }
 
+#[tokio::test]
+async fn hub_gen_skill_reports_tool_skills() -> Result<()> {
+    let router = Arc::new(ProxyRouter::new(
+        Arc::new(McpRegistry::new(Vec::new())),
+        Duration::from_secs(5),
+    ));
+
+    let response =
+        control_plane_round_trip(router, "hub/gen-skill", json!({"update": true})).await?;
+
+    assert_eq!(response["result"]["queued"], true);
+    let report = &response["result"]["toolSkills"];
+    assert_eq!(report["written"], json!([]));
+    assert_eq!(report["unchanged"], 0);
+    assert_eq!(report["linkErrors"], json!([]));
+    Ok(())
+}
+
 #[tokio::test]
 async fn hub_search_tools_returns_empty_on_empty_cache() -> Result<()> {
     let router = Arc::new(ProxyRouter::new(
@@ -195,6 +302,7 @@ async fn hub_search_tools_finds_cached_tool() -> Result<()> {
             "my_search_tool".to_string(),
             ToolDescriptor {
                 server_name: "test-server".to_string(),
+                tool_name: "my_search_tool".to_string(),
                 description: Some("searches things".to_string()),
                 input_schema: json!({"type": "object"}),
             },
@@ -203,6 +311,7 @@ async fn hub_search_tools_finds_cached_tool() -> Result<()> {
             "other_tool".to_string(),
             ToolDescriptor {
                 server_name: "test-server".to_string(),
+                tool_name: "other_tool".to_string(),
                 description: Some("does other things".to_string()),
                 input_schema: json!({"type": "object"}),
             },
@@ -238,6 +347,7 @@ async fn hub_get_tool_schema_returns_schema() -> Result<()> {
             "schema_tool".to_string(),
             ToolDescriptor {
                 server_name: "test-server".to_string(),
+                tool_name: "schema_tool".to_string(),
                 description: Some("a tool with schema".to_string()),
                 input_schema: json!({
                     "type": "object",
@@ -299,3 +409,17 @@ async fn hub_get_tool_schema_missing_param() -> Result<()> {
     assert_eq!(response["error"]["code"], -32602);
     Ok(())
 }
+
+#[tokio::test]
+async fn hub_open_session_is_refused_off_the_hub_socket() -> Result<()> {
+    let router = Arc::new(ProxyRouter::new(
+        Arc::new(McpRegistry::new(Vec::new())),
+        Duration::from_secs(5),
+    ));
+
+    let response =
+        control_plane_round_trip(router, "hub/open-session", json!({"tool": "codex"})).await?;
+
+    assert_eq!(response["error"]["code"], -32004);
+    Ok(())
+}
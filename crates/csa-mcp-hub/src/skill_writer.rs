@@ -18,6 +18,8 @@ use tokio_util::sync::CancellationToken;
 
 use crate::config::HubConfig;
 use crate::registry::McpRegistry;
+use crate::tool_filter::VisibilityFilter;
+use crate::tool_skills::{ToolSkillGenerator, ToolSkillMode, ToolSkillReport};
 
 const ROUTING_SKILL_NAME: &str = "mcp-hub-routing-guide";
 const STARTUP_LIST_RETRIES: u32 = 20;
@@ -108,7 +110,11 @@ pub(crate) fn spawn_skill_sync_task(cfg: HubConfig, registry: Arc<McpRegistry>)
     }
 }
 
-pub(crate) async fn regenerate_routing_skill_once(cfg: HubConfig) -> Result<()> {
+/// Regenerate the routing guide and per-tool skills without a running hub.
+pub(crate) async fn regenerate_routing_skill_once(
+    cfg: HubConfig,
+    tool_skill_mode: ToolSkillMode,
+) -> Result<ToolSkillReport> {
     let registry = Arc::new(McpRegistry::new(cfg.mcp_servers.clone()));
     let writer = SkillWriter::new(
         cfg.project_root.clone(),
@@ -122,8 +128,11 @@ pub(crate) async fn regenerate_routing_skill_once(cfg: HubConfig) -> Result<()>
     )
     .await;
     writer.regenerate(snapshots, true).await?;
+    let report = ToolSkillGenerator::new(&cfg, registry.clone())
+        .generate(tool_skill_mode)
+        .await;
     registry.shutdown_all().await?;
-    Ok(())
+    report
 }
 
 pub(crate) fn parse_tools_list_changed_signal(payload: &str) -> Option<SkillRefreshSignal> {
@@ -202,7 +211,7 @@ async fn run_skill_sync_loop(
     Ok(())
 }
 
-async fn collect_snapshots(
+pub(crate) async fn collect_snapshots(
     registry: &McpRegistry,
     attempts: u32,
     retry_delay: Duration,
@@ -391,36 +400,6 @@ impl SkillWriter {
     }
 }
 
-#[derive(Debug, Clone)]
-struct VisibilityFilter {
-    include: Option<HashSet<String>>,
-    exclude: HashSet<String>,
-}
-
-impl VisibilityFilter {
-    fn new(whitelist: Vec<String>, blacklist: Vec<String>) -> Self {
-        let include = if whitelist.is_empty() {
-            None
-        } else {
-            Some(whitelist.into_iter().collect())
-        };
-
-        Self {
-            include,
-            exclude: blacklist.into_iter().collect(),
-        }
-    }
-
-    fn allows(&self, name: &str) -> bool {
-        if let Some(include) = &self.include
-            && !include.contains(name)
-        {
-            return false;
-        }
-        !self.exclude.contains(name)
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct RegistryFile {
     #[serde(default)]
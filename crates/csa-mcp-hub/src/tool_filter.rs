@@ -0,0 +1,158 @@
+//! Per-consumer-tool filtering of the tools the hub exposes.
+//!
+//! Filters are keyed by consumer tool name (`codex`, `gemini-cli`, ...) and
+//! come from `[mcp_filters.<tool>]` in the global config and the project's
+//! `.csa/config.toml`. A tool is visible only when every filter for the
+//! consumer allows it, so a project can narrow the global rules but never
+//! widen them. Filters under `"*"` apply to every identified consumer.
+//!
+//! A consumer is identified only by the session socket csa opened for it or
+//! by its HTTP bearer token. Once any filter is configured, peers the hub
+//! cannot identify see no tools at all.
+
+use std::collections::{HashMap, HashSet};
+
+use csa_config::McpFilter;
+
+/// Filter key applying to all consumers.
+const ANY_CONSUMER: &str = "*";
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ToolFilters {
+    by_consumer: HashMap<String, Vec<McpFilter>>,
+    /// Filters for unidentified peers: deny everything when any are configured.
+    unidentified: Vec<McpFilter>,
+}
+
+impl ToolFilters {
+    pub(crate) fn new(
+        global: &HashMap<String, McpFilter>,
+        project: HashMap<String, McpFilter>,
+    ) -> Self {
+        let mut by_consumer: HashMap<String, Vec<McpFilter>> = HashMap::new();
+        for (consumer, filter) in global
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .chain(project)
+        {
+            by_consumer.entry(consumer).or_default().push(filter);
+        }
+        if let Some(shared) = by_consumer.get(ANY_CONSUMER).cloned() {
+            for (consumer, filters) in &mut by_consumer {
+                if consumer != ANY_CONSUMER {
+                    filters.extend(shared.iter().cloned());
+                }
+            }
+        }
+        let unidentified = if by_consumer.is_empty() {
+            Vec::new()
+        } else {
+            vec![McpFilter {
+                tools: vec!["!*".to_string()],
+                ..McpFilter::default()
+            }]
+        };
+        Self {
+            by_consumer,
+            unidentified,
+        }
+    }
+
+    /// Filters applying to `consumer`, or to any consumer when it has none.
+    /// Unidentified peers get filters denying every tool.
+    pub(crate) fn for_consumer(&self, consumer: Option<&str>) -> &[McpFilter] {
+        let Some(consumer) = consumer else {
+            return &self.unidentified;
+        };
+        self.by_consumer
+            .get(consumer)
+            .or_else(|| self.by_consumer.get(ANY_CONSUMER))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+pub(crate) fn allows(filters: &[McpFilter], server: &str, tool: &str) -> bool {
+    filters
+        .iter()
+        .all(|filter| filter.allows_tool(server, tool))
+}
+
+/// Server-level `mcp_whitelist` / `mcp_blacklist` visibility of MCP servers.
+#[derive(Debug, Clone)]
+pub(crate) struct VisibilityFilter {
+    include: Option<HashSet<String>>,
+    exclude: HashSet<String>,
+}
+
+impl VisibilityFilter {
+    pub(crate) fn new(whitelist: Vec<String>, blacklist: Vec<String>) -> Self {
+        let include = if whitelist.is_empty() {
+            None
+        } else {
+            Some(whitelist.into_iter().collect())
+        };
+
+        Self {
+            include,
+            exclude: blacklist.into_iter().collect(),
+        }
+    }
+
+    pub(crate) fn allows(&self, name: &str) -> bool {
+        if let Some(include) = &self.include
+            && !include.contains(name)
+        {
+            return false;
+        }
+        !self.exclude.contains(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(tools: &[&str]) -> McpFilter {
+        McpFilter {
+            tools: tools.iter().map(|tool| tool.to_string()).collect(),
+            ..McpFilter::default()
+        }
+    }
+
+    #[test]
+    fn project_filters_can_only_narrow_global_ones() {
+        let global = HashMap::from([("codex".to_string(), filter(&["github.*"]))]);
+        let project = HashMap::from([("codex".to_string(), filter(&["!github.delete_*", "*"]))]);
+        let filters = ToolFilters::new(&global, project);
+
+        let codex = filters.for_consumer(Some("codex"));
+        assert!(allows(codex, "github", "create_issue"));
+        assert!(!allows(codex, "github", "delete_repo"));
+        assert!(!allows(codex, "memory", "store"));
+        assert!(filters.for_consumer(Some("claude-code")).is_empty());
+        assert!(!allows(filters.for_consumer(None), "memory", "store"));
+    }
+
+    #[test]
+    fn unidentified_consumers_see_every_tool_only_without_filters() {
+        let filters = ToolFilters::new(&HashMap::new(), HashMap::new());
+        assert!(filters.for_consumer(None).is_empty());
+    }
+
+    #[test]
+    fn wildcard_filters_apply_to_every_consumer() {
+        let global = HashMap::from([
+            ("*".to_string(), filter(&["!*.delete_*"])),
+            ("codex".to_string(), filter(&["github.*"])),
+        ]);
+        let filters = ToolFilters::new(&global, HashMap::new());
+
+        let codex = filters.for_consumer(Some("codex"));
+        assert!(allows(codex, "github", "create_issue"));
+        assert!(!allows(codex, "github", "delete_repo"));
+        let other = filters.for_consumer(Some("claude-code"));
+        assert!(allows(other, "memory", "store"));
+        assert!(!allows(other, "memory", "delete_all"));
+        assert!(!allows(filters.for_consumer(None), "memory", "store"));
+    }
+}
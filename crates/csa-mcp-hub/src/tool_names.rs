@@ -0,0 +1,239 @@
+//! Names the hub exposes downstream tools under.
+//!
+//! Tool names must be unique across all servers behind the hub. A server's
+//! `tool_renames` apply first; a name still claimed by more than one tool is
+//! then namespaced as `<server>__<tool>` for every claimant, so no server
+//! silently shadows another. The collisions are reported by `hub/status`.
+//!
+//! Servers that are down take part with the tools they last listed but are
+//! not exposed, so names only change when the configured servers or their
+//! tools do, not when a server goes down.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use rmcp::model::Tool;
+use serde::Serialize;
+
+pub(crate) const NAMESPACE_SEPARATOR: &str = "__";
+
+/// A downstream tool under the name the hub exposes it as.
+#[derive(Debug, Clone)]
+pub(crate) struct ExposedTool {
+    pub(crate) server: String,
+    /// The tool's name on its server.
+    pub(crate) upstream: String,
+    /// The tool with `name` set to the exposed name.
+    pub(crate) tool: Tool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct CollidingTool {
+    pub(crate) server: String,
+    pub(crate) tool: String,
+    pub(crate) exposed_as: String,
+}
+
+/// A name claimed by tools of several servers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct ToolCollision {
+    pub(crate) name: String,
+    pub(crate) tools: Vec<CollidingTool>,
+}
+
+pub(crate) fn namespaced(server: &str, tool: &str) -> String {
+    format!("{server}{NAMESPACE_SEPARATOR}{tool}")
+}
+
+/// Assign exposed names to the tools of each server.
+///
+/// The result only depends on the tool lists and renames, not on the order
+/// servers answered in. Tools of the servers in `down` claim their names but
+/// are left out of the exposed tools.
+pub(crate) fn resolve(
+    servers: BTreeMap<String, Vec<Tool>>,
+    down: &BTreeSet<String>,
+    renames: &HashMap<String, HashMap<String, String>>,
+) -> (Vec<ExposedTool>, Vec<ToolCollision>) {
+    let mut claimed: Vec<(String, ExposedTool)> = Vec::new();
+    for (server, tools) in servers {
+        let server_renames = renames.get(&server);
+        for tool in tools {
+            let upstream = tool.name.to_string();
+            let preferred = server_renames
+                .and_then(|renames| renames.get(&upstream))
+                .cloned()
+                .unwrap_or_else(|| upstream.clone());
+            claimed.push((
+                preferred,
+                ExposedTool {
+                    server: server.clone(),
+                    upstream,
+                    tool,
+                },
+            ));
+        }
+    }
+
+    let mut claimants: BTreeMap<&str, usize> = BTreeMap::new();
+    for (preferred, _) in &claimed {
+        *claimants.entry(preferred.as_str()).or_default() += 1;
+    }
+    let colliding: BTreeSet<String> = claimants
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    let mut collisions: BTreeMap<String, Vec<CollidingTool>> = BTreeMap::new();
+    let mut exposed = Vec::with_capacity(claimed.len());
+    let mut taken: HashMap<String, String> = HashMap::new();
+    for (preferred, mut entry) in claimed {
+        let name = if colliding.contains(&preferred) {
+            let name = namespaced(&entry.server, &entry.upstream);
+            collisions
+                .entry(preferred)
+                .or_default()
+                .push(CollidingTool {
+                    server: entry.server.clone(),
+                    tool: entry.upstream.clone(),
+                    exposed_as: name.clone(),
+                });
+            name
+        } else {
+            preferred
+        };
+        // A namespaced name can still clash with a tool literally named so.
+        if let Some(owner) = taken.get(&name) {
+            tracing::warn!(
+                tool = %name,
+                server = %entry.server,
+                owner = %owner,
+                "MCP tool name already taken; tool is not exposed"
+            );
+            continue;
+        }
+        taken.insert(name.clone(), entry.server.clone());
+        if down.contains(&entry.server) {
+            continue;
+        }
+        entry.tool.name = name.into();
+        exposed.push(entry);
+    }
+
+    let collisions = collisions
+        .into_iter()
+        .map(|(name, tools)| ToolCollision { name, tools })
+        .collect();
+    (exposed, collisions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn tool(name: &'static str) -> Tool {
+        Tool::new(name, "test tool", Arc::new(serde_json::Map::new()))
+    }
+
+    fn servers(entries: &[(&str, &[&'static str])]) -> BTreeMap<String, Vec<Tool>> {
+        entries
+            .iter()
+            .map(|(server, tools)| (server.to_string(), tools.iter().map(|t| tool(t)).collect()))
+            .collect()
+    }
+
+    fn names(exposed: &[ExposedTool]) -> Vec<(&str, &str, &str)> {
+        exposed
+            .iter()
+            .map(|e| (e.tool.name.as_ref(), e.server.as_str(), e.upstream.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn colliding_tools_are_namespaced_for_every_server() {
+        let (exposed, collisions) = resolve(
+            servers(&[("gitlab", &["search", "merge"]), ("github", &["search"])]),
+            &BTreeSet::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(
+            names(&exposed),
+            [
+                ("github__search", "github", "search"),
+                ("gitlab__search", "gitlab", "search"),
+                ("merge", "gitlab", "merge"),
+            ]
+        );
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].name, "search");
+        let servers: Vec<&str> = collisions[0]
+            .tools
+            .iter()
+            .map(|t| t.server.as_str())
+            .collect();
+        assert_eq!(servers, ["github", "gitlab"]);
+    }
+
+    #[test]
+    fn renames_resolve_or_cause_collisions() {
+        let renames = HashMap::from([
+            (
+                "github".to_string(),
+                HashMap::from([("search".to_string(), "code_search".to_string())]),
+            ),
+            (
+                "gitlab".to_string(),
+                HashMap::from([("merge".to_string(), "fetch".to_string())]),
+            ),
+        ]);
+        let (exposed, collisions) = resolve(
+            servers(&[
+                ("github", &["search"]),
+                ("gitlab", &["search", "merge"]),
+                ("web", &["fetch"]),
+            ]),
+            &BTreeSet::new(),
+            &renames,
+        );
+        assert_eq!(
+            names(&exposed),
+            [
+                ("code_search", "github", "search"),
+                ("search", "gitlab", "search"),
+                ("gitlab__merge", "gitlab", "merge"),
+                ("web__fetch", "web", "fetch"),
+            ]
+        );
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].name, "fetch");
+    }
+
+    #[test]
+    fn namespaced_name_clashing_with_a_literal_tool_is_dropped() {
+        let (exposed, _) = resolve(
+            servers(&[("a", &["x"]), ("b", &["x", "a__x"])]),
+            &BTreeSet::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(names(&exposed), [("a__x", "a", "x"), ("b__x", "b", "x")]);
+    }
+
+    #[test]
+    fn down_servers_keep_other_names_stable_but_are_not_exposed() {
+        let all = servers(&[("github", &["search"]), ("gitlab", &["search", "merge"])]);
+        let (up, _) = resolve(all.clone(), &BTreeSet::new(), &HashMap::new());
+        let down = BTreeSet::from(["github".to_string()]);
+        let (exposed, collisions) = resolve(all, &down, &HashMap::new());
+        assert_eq!(
+            names(&exposed),
+            [
+                ("gitlab__search", "gitlab", "search"),
+                ("merge", "gitlab", "merge"),
+            ]
+        );
+        assert_eq!(names(&exposed), names(&up)[1..]);
+        assert_eq!(collisions.len(), 1);
+    }
+}
@@ -0,0 +1,430 @@
+//! Per-tool skills generated from live MCP introspection.
+//!
+//! `csa mcp-hub gen-skill` lists the tools of every visible server and writes
+//! one skill per tool, with its argument schema and an example call, into
+//! `.csa/mcp-skills/<skill>/SKILL.md`. weave's link machinery then links the
+//! skills into `.claude/skills/`, `.codex/skills/` and `.agents/skills/`.
+//! Skills are named `mcp-<server>-<tool>` and call tools by the name the hub
+//! exposes them as.
+//!
+//! A full run rewrites every skill and drops those of tools that are gone.
+//! An update-in-place run only rewrites the generated section of each skill,
+//! keeps notes added below it, and keeps the skills of servers that did not
+//! answer.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::config::HubConfig;
+use crate::registry::McpRegistry;
+use crate::skill_writer::{McpServerSnapshot, collect_snapshots};
+use crate::tool_filter::VisibilityFilter;
+use crate::tool_names::{self, ExposedTool};
+
+const SKILL_PREFIX: &str = "mcp-";
+/// Opens the generated section; followed by the server name and ` -->`.
+const GENERATED_BEGIN: &str = "<!-- csa-mcp-hub:generated server=";
+const GENERATED_END: &str = "<!-- csa-mcp-hub:generated:end -->";
+const LIST_ATTEMPTS: u32 = 3;
+const LIST_RETRY_DELAY: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToolSkillMode {
+    /// Rewrite every skill from the tools listed now.
+    Regenerate,
+    /// Rewrite generated sections only, keeping notes and unreachable servers.
+    UpdateInPlace,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ToolSkillReport {
+    pub(crate) written: Vec<String>,
+    pub(crate) unchanged: usize,
+    pub(crate) removed: Vec<String>,
+    /// Servers whose `tools/list` failed.
+    pub(crate) unreachable: Vec<String>,
+    pub(crate) links_created: usize,
+    pub(crate) link_errors: Vec<String>,
+}
+
+/// Where generated tool skills live before being linked.
+pub(crate) fn tool_skills_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".csa").join("mcp-skills")
+}
+
+#[derive(Clone)]
+pub(crate) struct ToolSkillGenerator {
+    project_root: PathBuf,
+    visibility: VisibilityFilter,
+    registry: Arc<McpRegistry>,
+}
+
+impl ToolSkillGenerator {
+    pub(crate) fn new(cfg: &HubConfig, registry: Arc<McpRegistry>) -> Self {
+        Self {
+            project_root: cfg.project_root.clone(),
+            visibility: VisibilityFilter::new(cfg.mcp_whitelist.clone(), cfg.mcp_blacklist.clone()),
+            registry,
+        }
+    }
+
+    pub(crate) async fn generate(&self, mode: ToolSkillMode) -> Result<ToolSkillReport> {
+        let mut snapshots =
+            collect_snapshots(self.registry.as_ref(), LIST_ATTEMPTS, LIST_RETRY_DELAY).await;
+        for snapshot in &mut snapshots {
+            if snapshot.status != "ready" {
+                snapshot.tools = self.registry.last_listed_tools(&snapshot.name);
+            }
+        }
+        let plan = SkillPlan::new(snapshots, &self.registry.tool_renames(), &self.visibility);
+        let project_root = self.project_root.clone();
+        tokio::task::spawn_blocking(move || write_tool_skills(&project_root, &plan, mode))
+            .await
+            .context("tool skill writer panicked")?
+    }
+}
+
+#[derive(Debug)]
+struct ToolSkill {
+    name: String,
+    content: String,
+}
+
+/// The skills to write for one round of introspection.
+#[derive(Debug)]
+struct SkillPlan {
+    skills: Vec<ToolSkill>,
+    unreachable: Vec<String>,
+}
+
+impl SkillPlan {
+    fn new(
+        snapshots: Vec<McpServerSnapshot>,
+        renames: &HashMap<String, HashMap<String, String>>,
+        visibility: &VisibilityFilter,
+    ) -> Self {
+        let mut unreachable = Vec::new();
+        let mut servers = BTreeMap::new();
+        for snapshot in snapshots {
+            if snapshot.status != "ready" {
+                unreachable.push(snapshot.name.clone());
+            }
+            servers.insert(snapshot.name, snapshot.tools);
+        }
+        unreachable.sort();
+
+        // Resolve across all servers so names match what `tools/list` exposes;
+        // unreachable servers claim the tools they last listed.
+        let down = unreachable.iter().cloned().collect();
+        let (exposed, _) = tool_names::resolve(servers, &down, renames);
+        let mut seen = HashSet::new();
+        let mut skills = Vec::new();
+        for entry in exposed {
+            if !visibility.allows(&entry.server) {
+                continue;
+            }
+            let name = skill_name(&entry.server, &entry.upstream);
+            if !seen.insert(name.clone()) {
+                tracing::warn!(
+                    skill = %name,
+                    tool = %entry.upstream,
+                    server = %entry.server,
+                    "tool skill name already used by another tool; skipping"
+                );
+                continue;
+            }
+            skills.push(ToolSkill {
+                content: render_tool_skill(&name, &entry),
+                name,
+            });
+        }
+        Self {
+            skills,
+            unreachable,
+        }
+    }
+}
+
+fn write_tool_skills(
+    project_root: &Path,
+    plan: &SkillPlan,
+    mode: ToolSkillMode,
+) -> Result<ToolSkillReport> {
+    let source_root = tool_skills_dir(project_root);
+    std::fs::create_dir_all(&source_root)
+        .with_context(|| format!("failed to create {}", source_root.display()))?;
+    let mut report = ToolSkillReport {
+        unreachable: plan.unreachable.clone(),
+        ..ToolSkillReport::default()
+    };
+
+    let current: HashSet<&str> = plan.skills.iter().map(|s| s.name.as_str()).collect();
+    let entries = std::fs::read_dir(&source_root)
+        .with_context(|| format!("failed to read {}", source_root.display()))?;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if current.contains(name.as_str()) {
+            continue;
+        }
+        // Only skills this module wrote are removed.
+        let Some(server) = generated_server(&entry.path().join("SKILL.md")) else {
+            continue;
+        };
+        if mode == ToolSkillMode::UpdateInPlace && plan.unreachable.contains(&server) {
+            continue;
+        }
+        std::fs::remove_dir_all(entry.path())
+            .with_context(|| format!("failed to remove {}", entry.path().display()))?;
+        report.removed.push(name);
+    }
+    report.removed.sort();
+
+    for skill in &plan.skills {
+        let path = source_root.join(&skill.name).join("SKILL.md");
+        let existing = match std::fs::read_to_string(&path) {
+            Ok(existing) => Some(existing),
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => {
+                return Err(error).with_context(|| format!("failed to read {}", path.display()));
+            }
+        };
+        let content = match (mode, existing.as_deref()) {
+            (ToolSkillMode::UpdateInPlace, Some(existing)) => {
+                format!("{}{}", skill.content, notes_after_generated(existing))
+            }
+            _ => skill.content.clone(),
+        };
+        if existing.as_deref() == Some(content.as_str()) {
+            report.unchanged += 1;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        report.written.push(skill.name.clone());
+    }
+
+    weave::link::remove_stale_generated_links(project_root, &source_root)?;
+    let links = weave::link::link_generated_skills(project_root, &source_root, false)?;
+    report.links_created = links.unique_created_count();
+    report.link_errors = links.errors.iter().map(ToString::to_string).collect();
+    Ok(report)
+}
+
+/// Server named in the generated section of `path`, if this module wrote it.
+fn generated_server(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    content.lines().find_map(|line| {
+        line.strip_prefix(GENERATED_BEGIN)
+            .and_then(|rest| rest.strip_suffix(" -->"))
+            .map(str::to_string)
+    })
+}
+
+/// Whatever follows the generated section, i.e. notes added by hand.
+fn notes_after_generated(existing: &str) -> &str {
+    existing
+        .find(GENERATED_END)
+        .map(|at| &existing[at + GENERATED_END.len()..])
+        .map(|rest| rest.strip_prefix('\n').unwrap_or(rest))
+        .unwrap_or("")
+}
+
+fn skill_name(server: &str, tool: &str) -> String {
+    format!("{SKILL_PREFIX}{}-{}", slug(server), slug(tool))
+}
+
+fn slug(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for ch in name.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "tool".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn render_tool_skill(name: &str, entry: &ExposedTool) -> String {
+    let exposed = entry.tool.name.as_ref();
+    let description = entry.tool.description.as_deref().unwrap_or("").trim();
+    let summary = description.lines().next().unwrap_or("").trim();
+    let use_when = if summary.is_empty() {
+        format!(
+            "calling MCP tool {} of server {}",
+            entry.upstream, entry.server
+        )
+    } else {
+        summary.trim_end_matches('.').to_string()
+    };
+    let schema = Value::Object(entry.tool.input_schema.as_ref().clone());
+
+    let mut lines = vec![
+        "---".to_string(),
+        format!("name: {name}"),
+        format!(
+            "description: {}",
+            Value::String(format!(
+                "Use when: {use_when} (MCP tool `{exposed}` via csa-mcp-hub)"
+            ))
+        ),
+        "---".to_string(),
+        format!("{GENERATED_BEGIN}{} -->", entry.server),
+        format!("# {exposed}"),
+        String::new(),
+        format!(
+            "Tool `{}` of MCP server `{}`, called through csa-mcp-hub as `{exposed}`.",
+            entry.upstream, entry.server
+        ),
+        String::new(),
+    ];
+    if !description.is_empty() {
+        lines.push(description.to_string());
+        lines.push(String::new());
+    }
+
+    lines.push("## Arguments".to_string());
+    lines.push(String::new());
+    let arguments = argument_rows(&schema);
+    if arguments.is_empty() {
+        lines.push("None.".to_string());
+    } else {
+        lines.push("| Name | Type | Required | Description |".to_string());
+        lines.push("|------|------|----------|-------------|".to_string());
+        for row in &arguments {
+            lines.push(format!(
+                "| `{}` | {} | {} | {} |",
+                row.name,
+                row.type_text,
+                if row.required { "yes" } else { "no" },
+                table_cell(&row.description)
+            ));
+        }
+    }
+    lines.push(String::new());
+
+    let example_arguments: Map<String, Value> = arguments
+        .iter()
+        .filter(|row| row.required)
+        .map(|row| (row.name.clone(), example_value(&row.name, &row.schema)))
+        .collect();
+    let example = json!({"name": exposed, "arguments": example_arguments});
+    lines.push("## Example".to_string());
+    lines.push(String::new());
+    lines.push("`tools/call` parameters:".to_string());
+    lines.push(String::new());
+    lines.push("```json".to_string());
+    lines.push(serde_json::to_string_pretty(&example).unwrap_or_default());
+    lines.push("```".to_string());
+    lines.push(GENERATED_END.to_string());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+struct ArgumentRow {
+    name: String,
+    type_text: String,
+    required: bool,
+    description: String,
+    schema: Value,
+}
+
+/// Arguments of `schema`, required ones first, each group sorted by name.
+fn argument_rows(schema: &Value) -> Vec<ArgumentRow> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: HashSet<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let mut rows: Vec<ArgumentRow> = properties
+        .iter()
+        .map(|(name, property)| ArgumentRow {
+            name: name.clone(),
+            type_text: schema_types(property).join(" \\| "),
+            required: required.contains(name.as_str()),
+            description: property
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string(),
+            schema: property.clone(),
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.required
+            .cmp(&a.required)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    rows
+}
+
+fn schema_types(schema: &Value) -> Vec<String> {
+    match schema.get("type") {
+        Some(Value::String(single)) => vec![single.clone()],
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => vec!["any".to_string()],
+    }
+}
+
+/// A plausible value for an argument: its default, first enum value, or a
+/// placeholder of its type.
+fn example_value(name: &str, schema: &Value) -> Value {
+    if let Some(default) = schema.get("default") {
+        return default.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+    let types = schema_types(schema);
+    let primary = types
+        .iter()
+        .find(|kind| kind.as_str() != "null")
+        .map(String::as_str);
+    match primary {
+        Some("integer" | "number") => json!(1),
+        Some("boolean") => json!(true),
+        Some("array") => json!([]),
+        Some("object") => json!({}),
+        _ => Value::String(format!("<{name}>")),
+    }
+}
+
+fn table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+#[path = "tool_skills_tests.rs"]
+mod tests;
@@ -0,0 +1,172 @@
+use std::fs;
+
+use rmcp::model::Tool;
+
+use super::*;
+
+fn tool(name: &'static str, description: &'static str, schema: Value) -> Tool {
+    let schema = schema.as_object().cloned().unwrap_or_default();
+    Tool::new(name, description, Arc::new(schema))
+}
+
+fn snapshot(name: &str, status: &str, tools: Vec<Tool>) -> McpServerSnapshot {
+    McpServerSnapshot {
+        name: name.to_string(),
+        status: status.to_string(),
+        transport_type: "stdio".to_string(),
+        tools,
+    }
+}
+
+fn search_tool() -> Tool {
+    tool(
+        "search_issues",
+        "Search issues by query.\nSupports GitHub search syntax.",
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string", "description": "Search | query"},
+                "state": {"type": "string", "enum": ["open", "closed"]},
+                "limit": {"type": ["integer", "null"], "default": 30},
+                "labels": {"type": "array"}
+            },
+            "required": ["query", "state"]
+        }),
+    )
+}
+
+fn plan(snapshots: Vec<McpServerSnapshot>) -> SkillPlan {
+    SkillPlan::new(
+        snapshots,
+        &HashMap::new(),
+        &VisibilityFilter::new(Vec::new(), Vec::new()),
+    )
+}
+
+#[test]
+fn skills_document_arguments_and_an_example_call() {
+    let plan = plan(vec![snapshot("GitHub", "ready", vec![search_tool()])]);
+    assert_eq!(plan.skills.len(), 1);
+    let skill = &plan.skills[0];
+    assert_eq!(skill.name, "mcp-github-search-issues");
+
+    let content = &skill.content;
+    assert!(content.starts_with("---\nname: mcp-github-search-issues\n"));
+    assert!(
+        content.contains(
+            "description: \"Use when: Search issues by query (MCP tool `search_issues` via csa-mcp-hub)\""
+        ),
+        "{content}"
+    );
+    assert!(content.contains("| `query` | string | yes | Search \\| query |"));
+    assert!(content.contains("| `state` | string | yes |  |"));
+    assert!(content.contains("| `limit` | integer \\| null | no |  |"));
+    let query_row = content.find("| `query`").expect("query row");
+    let labels_row = content.find("| `labels`").expect("labels row");
+    assert!(query_row < labels_row, "required arguments come first");
+    assert!(content.contains("\"query\": \"<query>\""));
+    assert!(content.contains("\"state\": \"open\""));
+    assert!(
+        !content.contains("\"limit\""),
+        "optional args stay out of the example"
+    );
+    assert_eq!(
+        generated_server_of(content),
+        Some("GitHub".to_string()),
+        "marker names the server"
+    );
+}
+
+fn generated_server_of(content: &str) -> Option<String> {
+    let temp = tempfile::tempdir().ok()?;
+    let path = temp.path().join("SKILL.md");
+    fs::write(&path, content).ok()?;
+    generated_server(&path)
+}
+
+#[test]
+fn colliding_tools_use_their_exposed_names() {
+    let plan = plan(vec![
+        snapshot("github", "ready", vec![search_tool()]),
+        snapshot("gitlab", "ready", vec![search_tool()]),
+    ]);
+    let names: Vec<&str> = plan.skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["mcp-github-search-issues", "mcp-gitlab-search-issues"]
+    );
+    assert!(
+        plan.skills[0]
+            .content
+            .contains("called through csa-mcp-hub as `github__search_issues`")
+    );
+}
+
+#[test]
+fn update_in_place_keeps_notes_and_unreachable_servers() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let project = temp.path();
+    let fetch = tool("fetch", "Fetch a URL", json!({"type": "object"}));
+    let first = plan(vec![
+        snapshot("github", "ready", vec![search_tool()]),
+        snapshot("web", "ready", vec![fetch.clone()]),
+    ]);
+    let report = write_tool_skills(project, &first, ToolSkillMode::Regenerate)?;
+    assert_eq!(report.written.len(), 2);
+    assert!(report.link_errors.is_empty(), "{:?}", report.link_errors);
+    assert!(
+        project
+            .join(".claude/skills/mcp-web-fetch/SKILL.md")
+            .is_file()
+    );
+
+    let github_skill = tool_skills_dir(project).join("mcp-github-search-issues/SKILL.md");
+    let notes = "\n## Notes\n\nPrefer `state: open`.\n";
+    let mut content = fs::read_to_string(&github_skill)?;
+    content.push_str(notes);
+    fs::write(&github_skill, &content)?;
+
+    // github changed its description, web is down.
+    let mut changed = search_tool();
+    changed.description = Some("Find issues.".into());
+    let second = plan(vec![
+        snapshot("github", "ready", vec![changed]),
+        snapshot("web", "error", Vec::new()),
+    ]);
+    let report = write_tool_skills(project, &second, ToolSkillMode::UpdateInPlace)?;
+    assert_eq!(report.written, ["mcp-github-search-issues"]);
+    assert!(report.removed.is_empty());
+    assert_eq!(report.unreachable, ["web"]);
+    let updated = fs::read_to_string(&github_skill)?;
+    assert!(updated.contains("Find issues."));
+    assert!(updated.ends_with(notes), "{updated}");
+    assert!(
+        project
+            .join(".claude/skills/mcp-web-fetch/SKILL.md")
+            .is_file()
+    );
+
+    let report = write_tool_skills(project, &second, ToolSkillMode::UpdateInPlace)?;
+    assert!(report.written.is_empty());
+    assert_eq!(report.unchanged, 1);
+
+    let report = write_tool_skills(project, &second, ToolSkillMode::Regenerate)?;
+    assert_eq!(report.removed, ["mcp-web-fetch"]);
+    assert!(!fs::read_to_string(&github_skill)?.contains("## Notes"));
+    assert!(fs::symlink_metadata(project.join(".claude/skills/mcp-web-fetch")).is_err());
+    Ok(())
+}
+
+#[test]
+fn hand_written_skills_in_the_directory_are_left_alone() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    let project = temp.path();
+    let custom = tool_skills_dir(project).join("my-notes");
+    fs::create_dir_all(&custom)?;
+    fs::write(custom.join("SKILL.md"), "---\nname: my-notes\n---\n")?;
+
+    let report = write_tool_skills(project, &plan(Vec::new()), ToolSkillMode::Regenerate)?;
+    assert!(report.removed.is_empty());
+    assert!(custom.join("SKILL.md").is_file());
+    Ok(())
+}
@@ -0,0 +1,264 @@
+//! Detection and resolution of contradictory memory facts.
+//!
+//! A fact is treated as a claim only when it is an explicit key/value pair,
+//! `key: value` or `key = value`, with a short identifier-like key and a short
+//! value. Prose such as "the build is slow" is never a claim. Two active
+//! entries of the same project that assign different values to exactly the
+//! same key conflict.
+//!
+//! Resolution never deletes anything: a losing fact moves to the entry's
+//! [`MemoryProvenance::superseded`](crate::MemoryProvenance) list together with
+//! the entry that superseded it.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{MemoryEntry, SupersededFact};
+
+/// Keys with more words than this are prose, not claims.
+const MAX_KEY_WORDS: usize = 4;
+/// Values with more words than this are prose, not claims.
+const MAX_VALUE_WORDS: usize = 3;
+
+/// Claims grouped by `(project, normalized subject)`, each with its normalized value.
+type ClaimMap = HashMap<(Option<String>, String), Vec<(String, ConflictSide)>>;
+
+/// One side of a contradiction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictSide {
+    pub entry_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub fact: String,
+    pub value: String,
+}
+
+/// Facts that assign different values to the same subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FactConflict {
+    pub project: Option<String>,
+    pub subject: String,
+    /// Sides ordered oldest first; the last side is the most recent claim.
+    pub sides: Vec<ConflictSide>,
+}
+
+impl FactConflict {
+    pub fn most_recent(&self) -> Option<&ConflictSide> {
+        self.sides.last()
+    }
+}
+
+/// Split an explicit `key: value` or `key = value` fact into a normalized
+/// `(subject, value)` claim. Anything else is prose and yields `None`.
+fn parse_claim(fact: &str) -> Option<(String, String)> {
+    let fact = fact.trim().trim_end_matches('.');
+    let (subject, value) = [": ", " = "]
+        .iter()
+        .filter_map(|sep| fact.find(sep).map(|at| (at, sep.len())))
+        .min()
+        .map(|(at, len)| (&fact[..at], &fact[at + len..]))?;
+    let subject = normalize(subject);
+    let value = normalize(value);
+    if !is_key(&subject) || value.is_empty() || value.split(' ').count() > MAX_VALUE_WORDS {
+        return None;
+    }
+    Some((subject, value))
+}
+
+/// Whether a normalized subject reads like a configuration key rather than
+/// the start of a sentence.
+fn is_key(subject: &str) -> bool {
+    !subject.is_empty()
+        && subject.split(' ').count() <= MAX_KEY_WORDS
+        && subject
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, ' ' | '_' | '-' | '.' | '/'))
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|ch: char| ch == '`' || ch == '"' || ch == '\'')
+        .to_lowercase()
+}
+
+/// Find contradictory facts across `entries`.
+///
+/// Entries are compared only within the same project. Results are sorted by
+/// project and subject so output is stable.
+pub fn detect_conflicts(entries: &[MemoryEntry]) -> Vec<FactConflict> {
+    let mut claims = ClaimMap::new();
+    for entry in entries {
+        for fact in &entry.facts {
+            let Some((subject, value)) = parse_claim(fact) else {
+                continue;
+            };
+            claims
+                .entry((entry.project.clone(), subject))
+                .or_default()
+                .push((
+                    value.clone(),
+                    ConflictSide {
+                        entry_id: entry.id.to_string(),
+                        timestamp: entry.timestamp,
+                        fact: fact.clone(),
+                        value,
+                    },
+                ));
+        }
+    }
+
+    let mut conflicts: Vec<FactConflict> = claims
+        .into_iter()
+        .filter_map(|((project, subject), mut sides)| {
+            let first = &sides.first()?.0;
+            if sides.iter().all(|(value, _)| value == first) {
+                return None;
+            }
+            sides.sort_by_key(|(_, side)| side.timestamp);
+            Some(FactConflict {
+                project,
+                subject,
+                sides: sides.into_iter().map(|(_, side)| side).collect(),
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| (&a.project, &a.subject).cmp(&(&b.project, &b.subject)));
+    conflicts
+}
+
+/// Mark every fact that lost to a more recent claim on the same subject as
+/// superseded.
+///
+/// Losing facts move from `facts` to `provenance.superseded`, naming the entry
+/// that holds the most recent claim; nothing is deleted. Returns the number of
+/// facts superseded. Facts agreeing with the most recent value are kept.
+pub fn resolve_prefer_recent(entries: &mut [MemoryEntry], conflicts: &[FactConflict]) -> usize {
+    let now = Utc::now();
+    let mut superseded = 0;
+    for conflict in conflicts {
+        let Some(latest) = conflict.most_recent() else {
+            continue;
+        };
+        for side in conflict.sides.iter().filter(|s| s.value != latest.value) {
+            let Some(entry) = entries
+                .iter_mut()
+                .find(|entry| entry.id.to_string() == side.entry_id)
+            else {
+                continue;
+            };
+            let Some(position) = entry.facts.iter().position(|fact| fact == &side.fact) else {
+                continue;
+            };
+            let fact = entry.facts.remove(position);
+            entry.provenance.superseded.push(SupersededFact {
+                fact,
+                superseded_by: latest.entry_id.clone(),
+                at: now,
+            });
+            superseded += 1;
+        }
+    }
+    superseded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryProvenance, MemorySource};
+    use chrono::Duration;
+    use ulid::Ulid;
+
+    fn make_entry(project: &str, age_days: i64, facts: &[&str]) -> MemoryEntry {
+        MemoryEntry {
+            id: Ulid::new(),
+            timestamp: Utc::now() - Duration::days(age_days),
+            project: Some(project.to_string()),
+            tool: None,
+            session_id: None,
+            tags: Vec::new(),
+            content: String::new(),
+            facts: facts.iter().map(|fact| (*fact).to_string()).collect(),
+            source: MemorySource::PostRun,
+            valid_from: None,
+            valid_until: None,
+            provenance: MemoryProvenance::default(),
+        }
+    }
+
+    #[test]
+    fn test_parse_claim_accepts_common_forms() {
+        assert_eq!(
+            parse_claim("Default port: 8080"),
+            Some(("default port".to_string(), "8080".to_string()))
+        );
+        assert_eq!(
+            parse_claim("MSRV = 1.85"),
+            Some(("msrv".to_string(), "1.85".to_string()))
+        );
+        assert_eq!(
+            parse_claim("lock_backend = flock."),
+            Some(("lock_backend".to_string(), "flock".to_string()))
+        );
+        assert_eq!(parse_claim("The lock backend is flock."), None);
+        assert_eq!(parse_claim("refactored the scheduler"), None);
+    }
+
+    #[test]
+    fn test_detect_conflicts_same_subject_different_value() {
+        let old = make_entry("p", 3, &["Default port: 8080"]);
+        let new = make_entry("p", 1, &["default port: 9090"]);
+        let agreeing = make_entry("p", 2, &["MSRV = 1.85"]);
+        let agreeing_again = make_entry("p", 0, &["msrv = 1.85"]);
+        let other_project = make_entry("q", 0, &["default port: 7070"]);
+
+        let conflicts = detect_conflicts(&[
+            new.clone(),
+            old.clone(),
+            agreeing,
+            agreeing_again,
+            other_project,
+        ]);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.subject, "default port");
+        assert_eq!(conflict.sides[0].entry_id, old.id.to_string());
+        assert_eq!(
+            conflict.most_recent().map(|side| side.value.as_str()),
+            Some("9090")
+        );
+    }
+
+    #[test]
+    fn test_prose_is_never_flagged() {
+        let entries = [
+            make_entry("p", 3, &["the build is slow"]),
+            make_entry("p", 1, &["the build is green on CI"]),
+            make_entry("p", 2, &["Note: the build was flaky on Monday"]),
+            make_entry("p", 0, &["Note: the release checklist now covers docs"]),
+            make_entry("p", 2, &["When the cache is cold, the first build: slow"]),
+            make_entry("p", 0, &["When the cache is cold, the first build: fast"]),
+        ];
+        assert!(detect_conflicts(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_prefer_recent_supersedes_stale_facts() {
+        let old = make_entry("p", 3, &["Default port: 8080", "uses tokio"]);
+        let new = make_entry("p", 1, &["default port: 9090"]);
+        let new_id = new.id.to_string();
+        let mut entries = vec![old, new];
+        let conflicts = detect_conflicts(&entries);
+
+        assert_eq!(resolve_prefer_recent(&mut entries, &conflicts), 1);
+        assert_eq!(entries[0].facts, vec!["uses tokio".to_string()]);
+        let superseded = &entries[0].provenance.superseded;
+        assert_eq!(superseded.len(), 1);
+        assert_eq!(superseded[0].fact, "Default port: 8080");
+        assert_eq!(superseded[0].superseded_by, new_id);
+        assert_eq!(entries[1].facts, vec!["default port: 9090".to_string()]);
+        assert!(detect_conflicts(&entries).is_empty());
+    }
+}
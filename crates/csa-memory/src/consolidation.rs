@@ -2,10 +2,12 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 use chrono::Utc;
+use csa_config::MemoryConflictPolicy;
 use ulid::Ulid;
 
+use crate::conflicts::{FactConflict, detect_conflicts, resolve_prefer_recent};
 use crate::llm_client::MemoryLlmClient;
-use crate::{MemoryEntry, MemoryIndex, MemorySource, MemoryStore};
+use crate::{MemoryEntry, MemoryIndex, MemoryProvenance, MemorySource, MemoryStore};
 
 /// Plan for consolidation, returned by dry-run.
 #[derive(Debug)]
@@ -16,6 +18,8 @@ pub struct ConsolidationPlan {
     pub groups_to_merge: Vec<MergeGroup>,
     pub total_before: usize,
     pub total_after_estimate: usize,
+    /// Contradictory facts found among active entries.
+    pub conflicts: Vec<FactConflict>,
 }
 
 #[derive(Debug)]
@@ -36,6 +40,7 @@ pub async fn plan_consolidation(
     threshold: u32,
 ) -> Result<ConsolidationPlan> {
     let entries = store.load_all()?;
+    let conflicts = detect_conflicts(&entries);
     let global_min = threshold.max(4) as usize;
     let per_project_min = (threshold as usize / 2).max(3);
     if entries.len() < global_min {
@@ -44,6 +49,7 @@ pub async fn plan_consolidation(
             groups_to_merge: Vec::new(),
             total_before: entries.len(),
             total_after_estimate: entries.len(),
+            conflicts,
         });
     }
 
@@ -61,6 +67,7 @@ pub async fn plan_consolidation(
         groups_to_merge: Vec::new(),
         total_before: by_project.values().map(Vec::len).sum(),
         total_after_estimate: by_project.values().map(Vec::len).sum(),
+        conflicts,
     };
 
     for project_entries in by_project.values() {
@@ -107,20 +114,32 @@ pub async fn plan_consolidation(
 }
 
 /// Execute consolidation (apply plan effects to store and index).
+///
+/// Conflicting facts are left in place under [`MemoryConflictPolicy::Flag`].
+/// Under [`MemoryConflictPolicy::PreferRecent`] the stale ones are marked
+/// superseded by the most recent claim (see [`resolve_prefer_recent`]).
 pub async fn execute_consolidation(
     store: &MemoryStore,
     index: Option<&MemoryIndex>,
     client: &dyn MemoryLlmClient,
     threshold: u32,
+    conflict_policy: MemoryConflictPolicy,
 ) -> Result<ConsolidationPlan> {
     let plan = plan_consolidation(store, client, threshold).await?;
-    if plan.groups_to_merge.is_empty() && plan.entries_to_expire.is_empty() {
+    let resolve_conflicts =
+        conflict_policy == MemoryConflictPolicy::PreferRecent && !plan.conflicts.is_empty();
+    if plan.groups_to_merge.is_empty() && plan.entries_to_expire.is_empty() && !resolve_conflicts {
         return Ok(plan);
     }
 
     let mut entries = store.load_all()?;
     let now = Utc::now();
 
+    if resolve_conflicts {
+        let superseded = resolve_prefer_recent(&mut entries, &plan.conflicts);
+        tracing::info!(superseded, "Marked stale facts superseded by newer memory");
+    }
+
     for entry_id in &plan.entries_to_expire {
         if let Some(entry) = entries
             .iter_mut()
@@ -162,6 +181,10 @@ pub async fn execute_consolidation(
             source: MemorySource::Consolidated,
             valid_from: Some(now),
             valid_until: None,
+            provenance: MemoryProvenance {
+                derived_from: group.source_ids.clone(),
+                ..MemoryProvenance::default()
+            },
         };
         entries.push(consolidated);
     }
@@ -188,9 +211,11 @@ mod tests {
     use chrono::Utc;
     use ulid::Ulid;
 
-    use crate::{Fact, MemoryEntry, MemoryLlmClient, MemorySource, MemoryStore, NoopClient};
+    use crate::{
+        Fact, MemoryEntry, MemoryLlmClient, MemoryProvenance, MemorySource, MemoryStore, NoopClient,
+    };
 
-    use super::{execute_consolidation, plan_consolidation};
+    use super::{MemoryConflictPolicy, execute_consolidation, plan_consolidation};
 
     fn make_test_store() -> MemoryStore {
         let dir =
@@ -211,6 +236,7 @@ mod tests {
             source: MemorySource::PostRun,
             valid_from: Some(Utc::now()),
             valid_until: None,
+            provenance: MemoryProvenance::default(),
         }
     }
 
@@ -242,7 +268,8 @@ mod tests {
             store.append(&make_entry(format!("entry-{idx}"), "project-a"))?;
         }
 
-        let plan = execute_consolidation(&store, None, &client, 10).await?;
+        let plan =
+            execute_consolidation(&store, None, &client, 10, MemoryConflictPolicy::Flag).await?;
         assert_eq!(plan.groups_to_merge.len(), 1);
 
         let raw_file = store.base_dir().join("memories.jsonl");
@@ -298,7 +325,8 @@ mod tests {
             store.append(&make_entry(format!("entry-{idx}"), "project-a"))?;
         }
 
-        let plan = execute_consolidation(&store, None, &client, 10).await?;
+        let plan =
+            execute_consolidation(&store, None, &client, 10, MemoryConflictPolicy::Flag).await?;
         assert_eq!(plan.groups_to_merge.len(), 1);
         assert_eq!(plan.groups_to_merge[0].merged_content_preview.len(), 200);
         assert_eq!(plan.groups_to_merge[0].full_summary, full_summary);
@@ -310,6 +338,45 @@ mod tests {
             .find(|entry| matches!(entry.source, MemorySource::Consolidated))
             .expect("consolidated entry should exist");
         assert_eq!(consolidated.content, full_summary);
+        assert_eq!(consolidated.provenance.derived_from.len(), 10);
+
+        fs::remove_dir_all(store.base_dir()).ok();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_consolidation_prefers_recent_conflicting_fact() -> Result<()> {
+        let store = make_test_store();
+        let client = NoopClient;
+
+        let mut old = make_entry("old".to_string(), "project-a");
+        old.timestamp = Utc::now() - chrono::Duration::days(2);
+        old.facts = vec!["default port: 8080".to_string()];
+        let mut new = make_entry("new".to_string(), "project-a");
+        new.facts = vec!["default port: 9090".to_string()];
+        store.append(&old)?;
+        store.append(&new)?;
+
+        let plan = plan_consolidation(&store, &client, 10).await?;
+        assert_eq!(plan.conflicts.len(), 1);
+        execute_consolidation(&store, None, &client, 10, MemoryConflictPolicy::Flag).await?;
+        assert_eq!(store.load_all()?[0].facts.len(), 1);
+
+        let plan = execute_consolidation(
+            &store,
+            None,
+            &client,
+            10,
+            MemoryConflictPolicy::PreferRecent,
+        )
+        .await?;
+        assert_eq!(plan.conflicts.len(), 1);
+        let entries = store.load_all()?;
+        let old_after = entries.iter().find(|entry| entry.id == old.id).unwrap();
+        assert!(old_after.facts.is_empty());
+        assert_eq!(old_after.provenance.superseded.len(), 1);
+        let new_after = entries.iter().find(|entry| entry.id == new.id).unwrap();
+        assert_eq!(new_after.facts, vec!["default port: 9090".to_string()]);
 
         fs::remove_dir_all(store.base_dir()).ok();
         Ok(())
@@ -15,6 +15,9 @@ pub struct MemoryEntry {
     pub source: MemorySource,
     pub valid_from: Option<DateTime<Utc>>,
     pub valid_until: Option<DateTime<Utc>>,
+    /// Where the entry came from beyond `session_id`. Absent in legacy lines.
+    #[serde(default, skip_serializing_if = "MemoryProvenance::is_empty")]
+    pub provenance: MemoryProvenance,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,3 +26,99 @@ pub enum MemorySource {
     Manual,
     Consolidated,
 }
+
+/// Links from a memory entry back to the artifacts that produced it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryProvenance {
+    /// Review finding or todo item the entry was distilled from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finding_id: Option<String>,
+    /// Repository-relative files touched by the producing session.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Entry IDs merged into this one by consolidation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived_from: Vec<String>,
+    /// Facts of this entry that a more recent claim contradicted. Kept here
+    /// instead of being deleted so a wrong resolution can be undone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub superseded: Vec<SupersededFact>,
+}
+
+/// A fact set aside by conflict resolution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupersededFact {
+    pub fact: String,
+    /// ID of the entry holding the claim that won.
+    pub superseded_by: String,
+    pub at: DateTime<Utc>,
+}
+
+impl MemoryProvenance {
+    pub fn is_empty(&self) -> bool {
+        self.finding_id.is_none()
+            && self.files.is_empty()
+            && self.derived_from.is_empty()
+            && self.superseded.is_empty()
+    }
+}
+
+impl MemoryEntry {
+    /// One-line provenance summary for CLI output, or `None` when nothing is known.
+    pub fn provenance_summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(session_id) = &self.session_id {
+            parts.push(format!("session {session_id}"));
+        }
+        if let Some(finding_id) = &self.provenance.finding_id {
+            parts.push(format!("finding {finding_id}"));
+        }
+        if !self.provenance.files.is_empty() {
+            parts.push(format!("files {}", self.provenance.files.join(", ")));
+        }
+        if !self.provenance.derived_from.is_empty() {
+            parts.push(format!(
+                "merged from {} entries",
+                self.provenance.derived_from.len()
+            ));
+        }
+        if !self.provenance.superseded.is_empty() {
+            parts.push(format!(
+                "{} superseded facts",
+                self.provenance.superseded.len()
+            ));
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_line_without_provenance_round_trips() {
+        let line = r#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FAV","timestamp":"2026-01-01T00:00:00Z","project":null,"tool":null,"session_id":"01SESSION","tags":[],"content":"x","facts":[],"source":"Manual","valid_from":null,"valid_until":null}"#;
+        let entry: MemoryEntry = serde_json::from_str(line).unwrap();
+        assert!(entry.provenance.is_empty());
+        assert!(
+            !serde_json::to_string(&entry)
+                .unwrap()
+                .contains("provenance")
+        );
+        assert_eq!(
+            entry.provenance_summary().as_deref(),
+            Some("session 01SESSION")
+        );
+    }
+
+    #[test]
+    fn test_provenance_summary_lists_links() {
+        let line = r#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FAV","timestamp":"2026-01-01T00:00:00Z","project":null,"tool":null,"session_id":null,"tags":[],"content":"x","facts":[],"source":"Consolidated","valid_from":null,"valid_until":null,"provenance":{"finding_id":"F-12","files":["src/a.rs","src/b.rs"],"derived_from":["01A","01B"]}}"#;
+        let entry: MemoryEntry = serde_json::from_str(line).unwrap();
+        assert_eq!(
+            entry.provenance_summary().as_deref(),
+            Some("finding F-12; files src/a.rs, src/b.rs; merged from 2 entries")
+        );
+    }
+}
@@ -11,7 +11,11 @@ use tantivy::{
     schema::{DateOptions, Field, STORED, STRING, Schema, TEXT, Value},
 };
 
-use crate::MemoryEntry;
+use csa_config::MemoryIndexBackend;
+
+#[cfg(feature = "sqlite-index")]
+use crate::index_sqlite::SqliteIndex;
+use crate::{MemoryEntry, MemoryStore};
 
 const WRITER_HEAP_BYTES: usize = 50_000_000;
 const SNIPPET_MAX_CHARS: usize = 180;
@@ -23,7 +27,123 @@ pub struct SearchResult {
     pub snippet: String,
 }
 
+/// Full-text index over memory entries.
+///
+/// Backed by tantivy by default; `memory.index_backend = "sqlite"` selects an
+/// SQLite FTS5 database instead (requires the `sqlite-index` feature).
 pub struct MemoryIndex {
+    inner: IndexImpl,
+}
+
+enum IndexImpl {
+    Tantivy(TantivyIndex),
+    #[cfg(feature = "sqlite-index")]
+    Sqlite(SqliteIndex),
+}
+
+impl MemoryIndex {
+    /// Open the default (tantivy) index at `index_dir`.
+    pub fn open(index_dir: &Path) -> Result<Self> {
+        Ok(Self {
+            inner: IndexImpl::Tantivy(TantivyIndex::open(index_dir)?),
+        })
+    }
+
+    /// Open the index selected by `backend`.
+    ///
+    /// The SQLite database lives next to the tantivy directory
+    /// (`<index_dir>.sqlite`). When SQLite is unavailable the tantivy index is
+    /// used instead, so callers never lose search entirely.
+    pub fn open_with_backend(index_dir: &Path, backend: MemoryIndexBackend) -> Result<Self> {
+        match backend {
+            MemoryIndexBackend::Tantivy => Self::open(index_dir),
+            #[cfg(feature = "sqlite-index")]
+            MemoryIndexBackend::Sqlite => match SqliteIndex::open(&sqlite_path(index_dir)) {
+                Ok(index) => Ok(Self {
+                    inner: IndexImpl::Sqlite(index),
+                }),
+                Err(err) => {
+                    tracing::warn!(error = %err, "SQLite memory index unavailable; falling back to tantivy");
+                    Self::open(index_dir)
+                }
+            },
+            #[cfg(not(feature = "sqlite-index"))]
+            MemoryIndexBackend::Sqlite => {
+                tracing::warn!("csa-memory built without sqlite-index; falling back to tantivy");
+                Self::open(index_dir)
+            }
+        }
+    }
+
+    /// Open the configured index and migrate existing entries into it until it is seeded.
+    ///
+    /// Switching `memory.index_backend` to `sqlite` creates an empty database;
+    /// this seeds it from the JSONL store once so search results stay intact.
+    pub fn open_seeded(
+        index_dir: &Path,
+        backend: MemoryIndexBackend,
+        store: &MemoryStore,
+    ) -> Result<Self> {
+        let index = Self::open_with_backend(index_dir, backend)?;
+        if index.needs_migration()? {
+            let entries = store.load_all()?;
+            tracing::info!(
+                entries = entries.len(),
+                "Migrating memory entries into new SQLite index"
+            );
+            index.rebuild(&entries)?;
+        }
+        Ok(index)
+    }
+
+    /// Backend actually in use (may differ from the requested one after fallback).
+    pub fn backend(&self) -> MemoryIndexBackend {
+        match &self.inner {
+            IndexImpl::Tantivy(_) => MemoryIndexBackend::Tantivy,
+            #[cfg(feature = "sqlite-index")]
+            IndexImpl::Sqlite(_) => MemoryIndexBackend::Sqlite,
+        }
+    }
+
+    pub fn index_entry(&self, entry: &MemoryEntry) -> Result<()> {
+        match &self.inner {
+            IndexImpl::Tantivy(index) => index.index_entry(entry),
+            #[cfg(feature = "sqlite-index")]
+            IndexImpl::Sqlite(index) => index.index_entry(entry),
+        }
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        match &self.inner {
+            IndexImpl::Tantivy(index) => index.search(query, limit),
+            #[cfg(feature = "sqlite-index")]
+            IndexImpl::Sqlite(index) => index.search(query, limit),
+        }
+    }
+
+    pub fn rebuild(&self, entries: &[MemoryEntry]) -> Result<()> {
+        match &self.inner {
+            IndexImpl::Tantivy(index) => index.rebuild(entries),
+            #[cfg(feature = "sqlite-index")]
+            IndexImpl::Sqlite(index) => index.rebuild(entries),
+        }
+    }
+
+    fn needs_migration(&self) -> Result<bool> {
+        match &self.inner {
+            IndexImpl::Tantivy(_) => Ok(false),
+            #[cfg(feature = "sqlite-index")]
+            IndexImpl::Sqlite(index) => index.needs_seed(),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite-index")]
+fn sqlite_path(index_dir: &Path) -> PathBuf {
+    index_dir.with_extension("sqlite")
+}
+
+struct TantivyIndex {
     index: Index,
     reader: IndexReader,
     writer_path: PathBuf,
@@ -46,8 +166,8 @@ struct IndexFields {
     tags: Field,
 }
 
-impl MemoryIndex {
-    pub fn open(index_dir: &Path) -> Result<Self> {
+impl TantivyIndex {
+    fn open(index_dir: &Path) -> Result<Self> {
         fs::create_dir_all(index_dir).with_context(|| {
             format!("failed to create index directory: {}", index_dir.display())
         })?;
@@ -78,7 +198,7 @@ impl MemoryIndex {
         })
     }
 
-    pub fn index_entry(&self, entry: &MemoryEntry) -> Result<()> {
+    fn index_entry(&self, entry: &MemoryEntry) -> Result<()> {
         let mut writer = self.open_writer()?;
         writer
             .add_document(self.entry_document(entry))
@@ -90,7 +210,7 @@ impl MemoryIndex {
         Ok(())
     }
 
-    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
         if query.trim().is_empty() || limit == 0 {
             return Ok(Vec::new());
         }
@@ -129,7 +249,7 @@ impl MemoryIndex {
         Ok(results)
     }
 
-    pub fn rebuild(&self, entries: &[MemoryEntry]) -> Result<()> {
+    fn rebuild(&self, entries: &[MemoryEntry]) -> Result<()> {
         let mut writer = self.open_writer()?;
         writer
             .delete_all_documents()
@@ -228,7 +348,7 @@ fn build_schema() -> (Schema, IndexFields) {
     )
 }
 
-fn truncate_snippet(text: &str) -> String {
+pub(crate) fn truncate_snippet(text: &str) -> String {
     if text.chars().count() <= SNIPPET_MAX_CHARS {
         return text.to_string();
     }
@@ -244,7 +364,7 @@ mod tests {
     use chrono::Utc;
     use ulid::Ulid;
 
-    use crate::MemorySource;
+    use crate::{MemoryProvenance, MemorySource};
 
     fn make_index_dir() -> PathBuf {
         std::env::temp_dir().join(format!("csa-memory-index-test-{}", Ulid::new()))
@@ -263,6 +383,7 @@ mod tests {
             source: MemorySource::PostRun,
             valid_from: None,
             valid_until: None,
+            provenance: MemoryProvenance::default(),
         }
     }
 
@@ -360,4 +481,48 @@ mod tests {
         fs::remove_dir_all(index_dir).ok();
         Ok(())
     }
+
+    #[cfg(feature = "sqlite-index")]
+    #[test]
+    fn test_open_seeded_migrates_store_into_new_sqlite_index() -> Result<()> {
+        let base_dir = make_index_dir();
+        let store = MemoryStore::new(base_dir.clone());
+        let entry = make_entry("migrated sqlite memory", &[], &[]);
+        store.append(&entry)?;
+
+        let index_dir = base_dir.join("index");
+        let index = MemoryIndex::open_seeded(&index_dir, MemoryIndexBackend::Sqlite, &store)?;
+        assert_eq!(index.backend(), MemoryIndexBackend::Sqlite);
+        assert!(index_dir.with_extension("sqlite").is_file());
+
+        let results = index.search("migrated", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry_id, entry.id.to_string());
+
+        fs::remove_dir_all(base_dir).ok();
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite-index")]
+    #[test]
+    fn test_open_seeded_reseeds_sqlite_index_without_seed_marker() -> Result<()> {
+        let base_dir = make_index_dir();
+        let store = MemoryStore::new(base_dir.clone());
+        let entry = make_entry("stored before the index existed", &[], &[]);
+        store.append(&entry)?;
+
+        // An index file that exists but was never seeded, e.g. after a crash.
+        let index_dir = base_dir.join("index");
+        let partial = MemoryIndex::open_with_backend(&index_dir, MemoryIndexBackend::Sqlite)?;
+        partial.index_entry(&make_entry("indexed without seeding", &[], &[]))?;
+        drop(partial);
+
+        let index = MemoryIndex::open_seeded(&index_dir, MemoryIndexBackend::Sqlite, &store)?;
+        let results = index.search("stored", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry_id, entry.id.to_string());
+
+        fs::remove_dir_all(base_dir).ok();
+        Ok(())
+    }
 }
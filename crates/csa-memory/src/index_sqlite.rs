@@ -0,0 +1,262 @@
+//! SQLite FTS5 backend for [`crate::MemoryIndex`].
+//!
+//! Entries are upserted one row at a time, so capture and `csa memory add`
+//! only touch the affected row instead of rewriting a segment.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+
+use crate::MemoryEntry;
+use crate::index::{SearchResult, truncate_snippet};
+
+/// `PRAGMA user_version` once the index has been seeded from the JSONL store.
+const SEEDED_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE VIRTUAL TABLE IF NOT EXISTS memory_fts USING fts5(
+    ulid UNINDEXED,
+    project UNINDEXED,
+    tool UNINDEXED,
+    timestamp UNINDEXED,
+    content,
+    facts,
+    tags,
+    tokenize = 'unicode61'
+);
+";
+
+pub(crate) struct SqliteIndex {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteIndex {
+    pub(crate) fn open(db_path: &Path) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create index directory: {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(db_path)
+            .with_context(|| format!("failed to open sqlite index: {}", db_path.display()))?;
+        conn.execute_batch(SCHEMA)
+            .context("failed to initialize sqlite FTS5 schema")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// True until a [`SqliteIndex::rebuild`] has committed, so an interrupted
+    /// seed is retried on the next open.
+    pub(crate) fn needs_seed(&self) -> Result<bool> {
+        let version: i64 = self
+            .lock()?
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .context("failed to read sqlite index version")?;
+        Ok(version < SEEDED_VERSION)
+    }
+
+    pub(crate) fn index_entry(&self, entry: &MemoryEntry) -> Result<()> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .context("failed to start sqlite transaction")?;
+        upsert(&tx, entry)?;
+        tx.commit().context("failed to commit indexed entry")
+    }
+
+    pub(crate) fn rebuild(&self, entries: &[MemoryEntry]) -> Result<()> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .context("failed to start sqlite transaction")?;
+        tx.execute("DELETE FROM memory_fts", [])
+            .context("failed to clear sqlite index before rebuild")?;
+        for entry in entries {
+            upsert(&tx, entry)?;
+        }
+        tx.pragma_update(None, "user_version", SEEDED_VERSION)
+            .context("failed to mark sqlite index as seeded")?;
+        tx.commit().context("failed to commit rebuilt sqlite index")
+    }
+
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        let Some(match_expr) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT ulid, bm25(memory_fts), content, facts, tags
+                 FROM memory_fts WHERE memory_fts MATCH ?1
+                 ORDER BY bm25(memory_fts) LIMIT ?2",
+            )
+            .context("failed to prepare sqlite search")?;
+        let terms = query_terms(query);
+        let rows = stmt
+            .query_map(params![match_expr, limit as i64], |row| {
+                let entry_id: String = row.get(0)?;
+                let rank: f64 = row.get(1)?;
+                let fields: [String; 3] = [row.get(2)?, row.get(3)?, row.get(4)?];
+                Ok((entry_id, rank, fields))
+            })
+            .with_context(|| format!("failed to execute sqlite search: {query}"))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let (entry_id, rank, fields) = row.context("failed to read sqlite search row")?;
+            results.push(SearchResult {
+                entry_id,
+                // FTS5 bm25() is negative with lower = better; flip to match tantivy.
+                score: (-rank) as f32,
+                snippet: build_snippet(&terms, &fields),
+            });
+        }
+        Ok(results)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| anyhow::anyhow!("sqlite memory index mutex poisoned"))
+    }
+}
+
+fn upsert(conn: &Connection, entry: &MemoryEntry) -> Result<()> {
+    let entry_id = entry.id.to_string();
+    conn.execute("DELETE FROM memory_fts WHERE ulid = ?1", params![entry_id])
+        .context("failed to remove previous sqlite index row")?;
+    conn.execute(
+        "INSERT INTO memory_fts (ulid, project, tool, timestamp, content, facts, tags)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            entry_id,
+            entry.project.as_deref().unwrap_or_default(),
+            entry.tool.as_deref().unwrap_or_default(),
+            entry.timestamp.timestamp(),
+            entry.content,
+            entry.facts.join("\n"),
+            entry.tags.join(" "),
+        ],
+    )
+    .context("failed to insert sqlite index row")?;
+    Ok(())
+}
+
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split(|ch: char| !ch.is_alphanumeric() && ch != '_')
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Turn free text into an FTS5 expression: every term quoted and OR-ed, which
+/// mirrors tantivy's default query semantics and cannot trip FTS5 syntax errors.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms = query_terms(query);
+    if terms.is_empty() {
+        return None;
+    }
+    Some(
+        terms
+            .iter()
+            .map(|term| format!("\"{term}\""))
+            .collect::<Vec<_>>()
+            .join(" OR "),
+    )
+}
+
+fn build_snippet(terms: &[String], fields: &[String; 3]) -> String {
+    for text in fields.iter().filter(|text| !text.is_empty()) {
+        let lower = text.to_lowercase();
+        if terms.is_empty() || terms.iter().any(|term| lower.contains(term)) {
+            return truncate_snippet(text);
+        }
+    }
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryProvenance, MemorySource};
+    use chrono::Utc;
+    use ulid::Ulid;
+
+    fn make_db_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("csa-memory-sqlite-test-{}.sqlite", Ulid::new()))
+    }
+
+    fn make_entry(content: &str, facts: &[&str]) -> MemoryEntry {
+        MemoryEntry {
+            id: Ulid::new(),
+            timestamp: Utc::now(),
+            project: Some("test-project".to_string()),
+            tool: Some("codex".to_string()),
+            session_id: None,
+            tags: Vec::new(),
+            content: content.to_string(),
+            facts: facts.iter().map(|fact| (*fact).to_string()).collect(),
+            source: MemorySource::PostRun,
+            valid_from: None,
+            valid_until: None,
+            provenance: MemoryProvenance::default(),
+        }
+    }
+
+    #[test]
+    fn test_sqlite_index_ranks_and_upserts() -> Result<()> {
+        let db_path = make_db_path();
+        let index = SqliteIndex::open(&db_path)?;
+        assert!(index.needs_seed()?);
+
+        let low = make_entry("alpha beta", &[]);
+        let mut high = make_entry("alpha alpha alpha beta", &[]);
+        index.index_entry(&low)?;
+        index.index_entry(&high)?;
+
+        let results = index.search("alpha", 10)?;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].entry_id, high.id.to_string());
+        assert!(results[0].score > results[1].score);
+
+        high.content = "gamma only".to_string();
+        index.index_entry(&high)?;
+        let results = index.search("alpha", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry_id, low.id.to_string());
+
+        drop(index);
+        let index = SqliteIndex::open(&db_path)?;
+        assert!(index.needs_seed()?, "upserts alone do not seed the index");
+        index.rebuild(&[low])?;
+        drop(index);
+        assert!(!SqliteIndex::open(&db_path)?.needs_seed()?);
+        fs::remove_file(db_path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqlite_index_tolerates_fts_syntax_in_query() -> Result<()> {
+        let db_path = make_db_path();
+        let index = SqliteIndex::open(&db_path)?;
+        let entry = make_entry("no match in body", &["critical keyword lives in facts"]);
+        index.rebuild(std::slice::from_ref(&entry))?;
+
+        let results = index.search("keyword AND (\"unterminated", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].snippet, "critical keyword lives in facts");
+        assert!(index.search("  -- ", 10)?.is_empty());
+
+        fs::remove_file(db_path).ok();
+        Ok(())
+    }
+}
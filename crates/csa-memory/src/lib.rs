@@ -1,19 +1,32 @@
+mod conflicts;
 pub mod consolidation;
 mod entry;
 mod ephemeral_client;
 mod index;
+#[cfg(feature = "sqlite-index")]
+mod index_sqlite;
 mod llm_client;
 mod mempal_detect;
 mod noop_client;
+mod portable;
 mod resolve_backend;
+mod secret_filter;
 mod store;
 
+pub use conflicts::{ConflictSide, FactConflict, detect_conflicts, resolve_prefer_recent};
 pub use consolidation::{ConsolidationPlan, MergeGroup, execute_consolidation, plan_consolidation};
-pub use entry::{MemoryEntry, MemorySource};
+pub use entry::{MemoryEntry, MemoryProvenance, MemorySource, SupersededFact};
 pub use ephemeral_client::EphemeralClient;
 pub use index::{MemoryIndex, SearchResult};
 pub use llm_client::{ApiClient, Fact, MemoryLlmClient, ModelRotator};
 pub use mempal_detect::{MempalInfo, detect_mempal};
 pub use noop_client::NoopClient;
+pub use portable::{
+    MEMORY_EXPORT_SCHEMA_VERSION, MemoryExportHeader, MemoryImportSummary, export_entries,
+    import_entries, read_export,
+};
 pub use resolve_backend::resolve_backend;
-pub use store::{MemoryFilter, MemoryStore, append_entry, list_entries, quick_search};
+pub use secret_filter::screen_entry;
+pub use store::{
+    MemoryFilter, MemoryStore, append_entry, default_memory_base_dir, list_entries, quick_search,
+};
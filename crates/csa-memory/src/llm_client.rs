@@ -319,7 +319,7 @@ fn parse_completion_content(body: &str) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{MemorySource, NoopClient};
+    use crate::{MemoryProvenance, MemorySource, NoopClient};
     use chrono::Utc;
     use reqwest::header::HeaderValue;
     use ulid::Ulid;
@@ -408,6 +408,7 @@ mod tests {
             source: MemorySource::Manual,
             valid_from: None,
             valid_until: None,
+            provenance: MemoryProvenance::default(),
         };
         let summary = client
             .summarize(&[entry])
@@ -0,0 +1,239 @@
+//! Portable JSONL export/import for memory entries.
+//!
+//! An export is a header line followed by one [`MemoryEntry`] per line. The
+//! header carries a schema version so future importers can refuse files they
+//! do not understand instead of silently dropping fields. Entry IDs are kept
+//! as-is, which makes repeated imports of the same file idempotent.
+
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{MemoryEntry, MemoryStore};
+
+/// Current export schema version. Bump when the entry layout changes incompatibly.
+pub const MEMORY_EXPORT_SCHEMA_VERSION: u32 = 1;
+const EXPORT_FORMAT: &str = "csa-memory-export";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryExportHeader {
+    pub format: String,
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub entry_count: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryImportSummary {
+    /// Entries appended to the store, as persisted (after secret screening).
+    pub imported: Vec<MemoryEntry>,
+    /// Entries skipped because an entry with the same ID already exists.
+    pub skipped_existing: usize,
+    /// Entries skipped because their `valid_until` has passed.
+    pub skipped_expired: usize,
+}
+
+/// Write `entries` as a versioned JSONL export. Returns the number of entries written.
+pub fn export_entries(entries: &[MemoryEntry], writer: &mut impl Write) -> Result<usize> {
+    let header = MemoryExportHeader {
+        format: EXPORT_FORMAT.to_string(),
+        schema_version: MEMORY_EXPORT_SCHEMA_VERSION,
+        exported_at: Utc::now(),
+        entry_count: entries.len(),
+    };
+    let line = serde_json::to_string(&header).context("failed to serialize export header")?;
+    writeln!(writer, "{line}").context("failed to write export header")?;
+    for entry in entries {
+        let line = serde_json::to_string(entry).context("failed to serialize memory entry")?;
+        writeln!(writer, "{line}").context("failed to write memory entry")?;
+    }
+    writer.flush().context("failed to flush memory export")?;
+    Ok(entries.len())
+}
+
+/// Parse a JSONL export produced by [`export_entries`].
+///
+/// Unlike the live store, corrupt lines are an error: an import should either
+/// apply the whole file or nothing.
+pub fn read_export(reader: impl BufRead) -> Result<(MemoryExportHeader, Vec<MemoryEntry>)> {
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |text| !text.trim().is_empty()));
+
+    let Some((_, header_line)) = lines.next() else {
+        bail!("memory export is empty");
+    };
+    let header_line = header_line.context("failed to read export header")?;
+    let header: MemoryExportHeader = serde_json::from_str(&header_line).context(
+        "missing or invalid memory export header; was this file produced by `csa memory export`?",
+    )?;
+    if header.format != EXPORT_FORMAT {
+        bail!("unsupported memory export format: {}", header.format);
+    }
+    if header.schema_version > MEMORY_EXPORT_SCHEMA_VERSION {
+        bail!(
+            "memory export schema v{} is newer than supported v{}; upgrade csa before importing",
+            header.schema_version,
+            MEMORY_EXPORT_SCHEMA_VERSION
+        );
+    }
+
+    let mut entries = Vec::new();
+    for (idx, line) in lines {
+        let line = line.with_context(|| format!("failed to read export line {}", idx + 1))?;
+        let entry: MemoryEntry = serde_json::from_str(&line)
+            .with_context(|| format!("invalid memory entry on export line {}", idx + 1))?;
+        entries.push(entry);
+    }
+    if entries.len() != header.entry_count {
+        bail!(
+            "memory export is truncated: header declares {} entries, found {}",
+            header.entry_count,
+            entries.len()
+        );
+    }
+    Ok((header, entries))
+}
+
+/// Append entries whose IDs are not yet in `store`, preserving their IDs.
+///
+/// Entries are deduplicated by ID, expired ones are dropped and the rest are
+/// screened before anything is written, then appended in one write so a
+/// rejected entry cannot leave a partial import behind. With `dry_run`,
+/// nothing is written and `imported` lists what would be added.
+pub fn import_entries(
+    store: &MemoryStore,
+    entries: Vec<MemoryEntry>,
+    dry_run: bool,
+) -> Result<MemoryImportSummary> {
+    let mut known: HashSet<_> = store
+        .load_all()?
+        .into_iter()
+        .map(|entry| entry.id)
+        .collect();
+    let now = Utc::now();
+    let mut summary = MemoryImportSummary::default();
+    for entry in entries {
+        if entry.valid_until.is_some_and(|until| until <= now) {
+            summary.skipped_expired += 1;
+            continue;
+        }
+        if !known.insert(entry.id) {
+            summary.skipped_existing += 1;
+            continue;
+        }
+        summary.imported.push(store.screen(&entry)?);
+    }
+    if !dry_run {
+        store.append_all(&summary.imported)?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryProvenance, MemorySource};
+    use std::io::Cursor;
+    use ulid::Ulid;
+
+    fn make_store() -> MemoryStore {
+        MemoryStore::new(
+            std::env::temp_dir().join(format!("csa-memory-portable-test-{}", Ulid::new())),
+        )
+    }
+
+    fn make_entry(content: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: Ulid::new(),
+            timestamp: Utc::now(),
+            project: Some("shared".to_string()),
+            tool: Some("manual".to_string()),
+            session_id: None,
+            tags: vec!["kb".to_string()],
+            content: content.to_string(),
+            facts: Vec::new(),
+            source: MemorySource::Manual,
+            valid_from: None,
+            valid_until: None,
+            provenance: MemoryProvenance::default(),
+        }
+    }
+
+    #[test]
+    fn test_export_import_round_trip_preserves_ids_and_is_idempotent() -> Result<()> {
+        let entries = vec![make_entry("first"), make_entry("second")];
+        let mut buffer = Vec::new();
+        assert_eq!(export_entries(&entries, &mut buffer)?, 2);
+
+        let (header, parsed) = read_export(Cursor::new(&buffer))?;
+        assert_eq!(header.schema_version, MEMORY_EXPORT_SCHEMA_VERSION);
+        assert_eq!(parsed.len(), 2);
+
+        let store = make_store();
+        let summary = import_entries(&store, parsed.clone(), false)?;
+        assert_eq!(summary.imported.len(), 2);
+        let stored_ids: HashSet<_> = store.load_all()?.into_iter().map(|e| e.id).collect();
+        assert!(entries.iter().all(|entry| stored_ids.contains(&entry.id)));
+
+        let again = import_entries(&store, parsed, false)?;
+        assert!(again.imported.is_empty());
+        assert_eq!(again.skipped_existing, 2);
+        assert_eq!(store.load_all()?.len(), 2);
+
+        std::fs::remove_dir_all(store.base_dir()).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_skips_expired_and_duplicate_entries_and_is_all_or_nothing() -> Result<()> {
+        let live = make_entry("live");
+        let mut expired = make_entry("expired");
+        expired.valid_until = Some(Utc::now() - chrono::Duration::days(1));
+        let store = make_store();
+
+        let summary = import_entries(&store, vec![live.clone(), expired, live.clone()], false)?;
+        assert_eq!(summary.imported.len(), 1);
+        assert_eq!(summary.skipped_expired, 1);
+        assert_eq!(summary.skipped_existing, 1);
+        assert_eq!(store.load_all()?.len(), 1);
+
+        let strict = store
+            .clone()
+            .with_secret_policy(csa_config::MemorySecretPolicy::Reject);
+        let clean = make_entry("clean");
+        let leaky = make_entry("token rotated: OPENAI_API_KEY=providerfixture12345");
+        assert!(import_entries(&strict, vec![clean, leaky], false).is_err());
+        assert_eq!(
+            store.load_all()?.len(),
+            1,
+            "nothing from a rejected import lands"
+        );
+
+        std::fs::remove_dir_all(store.base_dir()).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_export_rejects_newer_schema_and_truncation() {
+        let newer = format!(
+            "{{\"format\":\"{EXPORT_FORMAT}\",\"schema_version\":{},\"exported_at\":\"2026-01-01T00:00:00Z\",\"entry_count\":0}}\n",
+            MEMORY_EXPORT_SCHEMA_VERSION + 1
+        );
+        let err = read_export(Cursor::new(newer)).unwrap_err();
+        assert!(err.to_string().contains("newer than supported"));
+
+        let truncated = format!(
+            "{{\"format\":\"{EXPORT_FORMAT}\",\"schema_version\":1,\"exported_at\":\"2026-01-01T00:00:00Z\",\"entry_count\":3}}\n"
+        );
+        let err = read_export(Cursor::new(truncated)).unwrap_err();
+        assert!(err.to_string().contains("truncated"));
+
+        let err = read_export(Cursor::new("")).unwrap_err();
+        assert!(err.to_string().contains("empty"));
+    }
+}
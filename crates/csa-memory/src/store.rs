@@ -4,13 +4,14 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use csa_config::MemorySecretPolicy;
 use regex::RegexBuilder;
 use tracing::warn;
 
 use crate::entry::MemoryEntry;
+use crate::secret_filter::screen_entry;
 
 const MEMORY_FILE_NAME: &str = "memories.jsonl";
-const APP_NAME: &str = "cli-sub-agent";
 
 #[derive(Debug, Clone, Default)]
 pub struct MemoryFilter {
@@ -24,6 +25,7 @@ pub struct MemoryFilter {
 pub struct MemoryStore {
     base_dir: PathBuf,
     file_path: PathBuf,
+    secret_policy: MemorySecretPolicy,
 }
 
 impl MemoryStore {
@@ -36,10 +38,42 @@ impl MemoryStore {
         Self {
             file_path: base_dir.join(MEMORY_FILE_NAME),
             base_dir,
+            secret_policy: MemorySecretPolicy::default(),
         }
     }
 
+    /// Override how secret-like content is handled on write (defaults to masking).
+    pub fn with_secret_policy(mut self, secret_policy: MemorySecretPolicy) -> Self {
+        self.secret_policy = secret_policy;
+        self
+    }
+
+    /// Screen `entry` with this store's secret policy without persisting it.
+    ///
+    /// Callers that also index the entry should index the returned copy so the
+    /// search index never sees content the JSONL store would not accept.
+    pub fn screen(&self, entry: &MemoryEntry) -> Result<MemoryEntry> {
+        screen_entry(entry, self.secret_policy)
+    }
+
     pub fn append(&self, entry: &MemoryEntry) -> Result<()> {
+        self.append_all(std::slice::from_ref(entry))
+    }
+
+    /// Append `entries` with a single write, after every one has been screened,
+    /// so a rejected entry leaves the store untouched.
+    pub fn append_all(&self, entries: &[MemoryEntry]) -> Result<()> {
+        let mut lines = String::new();
+        for entry in entries {
+            let entry = self.screen(entry)?;
+            lines.push_str(
+                &serde_json::to_string(&entry).context("failed to serialize memory entry")?,
+            );
+            lines.push('\n');
+        }
+        if lines.is_empty() {
+            return Ok(());
+        }
         self.ensure_storage_dir()?;
 
         let mut file = OpenOptions::new()
@@ -50,8 +84,8 @@ impl MemoryStore {
 
         set_file_mode_600(&self.file_path)?;
 
-        let line = serde_json::to_string(entry).context("failed to serialize memory entry")?;
-        writeln!(file, "{line}").context("failed to append memory entry")?;
+        file.write_all(lines.as_bytes())
+            .context("failed to append memory entry")?;
         file.flush()
             .context("failed to flush memory entry append")?;
 
@@ -73,7 +107,8 @@ impl MemoryStore {
 
         let mut writer = BufWriter::new(file);
         for entry in entries {
-            let line = serde_json::to_string(entry).context("failed to serialize memory entry")?;
+            let entry = self.screen(entry)?;
+            let line = serde_json::to_string(&entry).context("failed to serialize memory entry")?;
             writeln!(writer, "{line}").context("failed to write memory entry")?;
         }
         writer
@@ -186,6 +221,8 @@ impl MemoryStore {
     }
 
     fn ensure_storage_dir(&self) -> Result<()> {
+        csa_config::paths::create_session_state_dir()
+            .context("failed to create the session state directory")?;
         fs::create_dir_all(&self.base_dir)
             .with_context(|| format!("failed to create memory dir: {}", self.base_dir.display()))?;
 
@@ -214,25 +251,11 @@ pub fn list_entries(filter: MemoryFilter) -> Result<Vec<MemoryEntry>> {
     MemoryStore::default().list(filter)
 }
 
-fn default_memory_base_dir() -> PathBuf {
-    if let Some(project_dirs) = directories::ProjectDirs::from("", "", APP_NAME) {
-        return project_dirs
-            .state_dir()
-            .unwrap_or_else(|| project_dirs.data_local_dir())
-            .join("memory");
-    }
-
-    if let Some(base_dirs) = directories::BaseDirs::new() {
-        return base_dirs
-            .home_dir()
-            .join(".local")
-            .join("state")
-            .join(APP_NAME)
-            .join("memory");
-    }
-
-    std::env::temp_dir()
-        .join(format!("{APP_NAME}-state"))
+/// `memory/` under the current user's CSA state directory, honoring
+/// `CSA_STATE_DIR` and shared-state mode.
+pub fn default_memory_base_dir() -> PathBuf {
+    csa_config::paths::session_state_dir()
+        .unwrap_or_else(csa_config::paths::state_dir_fallback)
         .join("memory")
 }
 
@@ -263,7 +286,7 @@ fn set_file_mode_600(_path: &std::path::Path) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entry::MemorySource;
+    use crate::entry::{MemoryProvenance, MemorySource};
     use chrono::Duration;
     use ulid::Ulid;
 
@@ -292,6 +315,7 @@ mod tests {
             source: MemorySource::PostRun,
             valid_from: None,
             valid_until,
+            provenance: MemoryProvenance::default(),
         }
     }
 
@@ -314,6 +338,31 @@ mod tests {
         assert!(all.iter().any(|entry| entry.content == "third"));
     }
 
+    #[test]
+    fn test_append_masks_or_rejects_secrets_per_policy() {
+        let store = make_test_store();
+        let leaky = make_entry(
+            "token rotated: OPENAI_API_KEY=providerfixture12345",
+            None,
+            None,
+            &[],
+            &[],
+            None,
+        );
+
+        store.append(&leaky).unwrap();
+        let all = store.load_all().unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(!all[0].content.contains("providerfixture12345"));
+        assert!(all[0].content.contains("[REDACTED]"));
+
+        let strict = store.clone().with_secret_policy(MemorySecretPolicy::Reject);
+        assert!(strict.append(&leaky).is_err());
+        assert_eq!(store.load_all().unwrap().len(), 1);
+        assert!(strict.rewrite_all(std::slice::from_ref(&leaky)).is_err());
+        assert_eq!(store.load_all().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_quick_search() {
         let store = make_test_store();
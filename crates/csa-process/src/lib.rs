@@ -94,19 +94,28 @@ pub enum StreamMode {
 ///   `SIGTERM` to **all** processes in the scope.  CSA should let systemd
 ///   handle cleanup rather than sending signals directly when a scope is active.
 ///
+/// - **`CgroupDirect`**: The child runs in a CSA-created cgroup.  On drop,
+///   [`DirectCgroupGuard`] sends `SIGTERM`, then kills what remains and
+///   removes the cgroup.
+///
 /// - **`Rlimit`**: `RLIMIT_NPROC` was applied in the child's `pre_exec`.
 ///   This is a marker variant indicating rlimit-based PID isolation is active.
 ///
 /// - **`None`**: No sandbox active; signal handling is unchanged.
 ///
 /// [`CgroupScopeGuard`]: csa_resource::cgroup::CgroupScopeGuard
+/// [`DirectCgroupGuard`]: csa_resource::cgroup_direct::DirectCgroupGuard
 pub enum SandboxHandle {
     /// cgroup scope guard -- dropped to stop the scope.
     Cgroup(csa_resource::cgroup::CgroupScopeGuard),
+    /// Directly managed cgroup guard -- dropped to kill and remove the cgroup.
+    CgroupDirect(csa_resource::cgroup_direct::DirectCgroupGuard),
     /// Bubblewrap filesystem sandbox is active.
     Bwrap,
     /// Landlock LSM filesystem restrictions applied in child via `pre_exec`.
     Landlock,
+    /// macOS Seatbelt (`sandbox-exec`) filesystem sandbox is active.
+    SandboxExec,
     /// `RLIMIT_NPROC` was applied in child via `pre_exec`.
     Rlimit,
     /// No sandbox active.
@@ -161,24 +170,30 @@ impl Default for SpawnOptions {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(crate) enum PreExecPolicy {
     /// Call `setsid()` only — no additional resource enforcement.
     Setsid,
-    /// Call `setsid()` + apply `RLIMIT_NPROC` (and ignore memory_max_mb).
+    /// Call `setsid()` + apply `RLIMIT_NPROC` (and ignore memory_max_mb),
+    /// approximating `cpu_max_percent` with a lower scheduling priority.
     Rlimits {
         memory_max_mb: u64,
         pids_max: Option<u64>,
+        cpu_max_percent: Option<u32>,
     },
     /// Call `setsid()` + raise OOM score.  Used when no cgroup or rlimit is
     /// available, as a last-resort signal to the kernel OOM killer.
     OomAdj,
+    /// Call `setsid()` + join a CSA-managed cgroup by writing to its
+    /// `cgroup.procs`; limits are enforced by the cgroup itself.
+    JoinCgroup { procs_path: std::ffi::CString },
 }
 
 #[path = "lib_spawn.rs"]
 mod spawn;
 pub use spawn::{
-    spawn_tool, spawn_tool_sandboxed, spawn_tool_sandboxed_in_environment, spawn_tool_with_options,
+    spawn_tool, spawn_tool_in_cgroup, spawn_tool_sandboxed, spawn_tool_sandboxed_in_environment,
+    spawn_tool_with_options,
 };
 
 /// Wait for a spawned child process and capture its output.
@@ -42,6 +42,14 @@ pub struct ExecutionResult {
     /// `None` when cgroup monitoring is unavailable.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub peak_memory_mb: Option<u64>,
+    /// Peak GPU memory in MB, sampled only for tools that declare
+    /// `gpu_memory_mb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_vram_mb: Option<u64>,
+    /// CPU time and block I/O from the tool's cgroup scope; `None` when the
+    /// tool did not run in one.
+    #[serde(skip)]
+    pub child_usage: Option<csa_resource::child_usage::ChildUsage>,
     /// Whether the model turn reached a normal terminal state (e.g. ACP `end_turn`
     /// / `max_tokens`; legacy `turn.completed` / `subtype=success`).
     ///
@@ -41,7 +41,15 @@ pub async fn spawn_tool_with_options(
     stdin_data: Option<Vec<u8>>,
     spawn_options: SpawnOptions,
 ) -> Result<tokio::process::Child> {
-    spawn_tool_with_pre_exec(cmd, stdin_data, PreExecPolicy::Setsid, spawn_options, None).await
+    spawn_tool_with_pre_exec(
+        cmd,
+        stdin_data,
+        PreExecPolicy::Setsid,
+        spawn_options,
+        None,
+        false,
+    )
+    .await
 }
 
 async fn spawn_tool_with_pre_exec(
@@ -50,6 +58,7 @@ async fn spawn_tool_with_pre_exec(
     pre_exec_policy: PreExecPolicy,
     spawn_options: SpawnOptions,
     landlock_paths: Option<Vec<std::path::PathBuf>>,
+    private_network: bool,
 ) -> Result<tokio::process::Child> {
     cmd.stdout(std::process::Stdio::piped());
     cmd.stderr(std::process::Stdio::piped());
@@ -60,9 +69,10 @@ async fn spawn_tool_with_pre_exec(
     }
     cmd.kill_on_drop(true);
 
-    // Isolate child in its own process group, optionally apply rlimits,
-    // and optionally apply Landlock filesystem restrictions.
+    // Isolate child in its own process group, optionally enter a private
+    // network namespace, apply rlimits, and apply Landlock restrictions.
     // SAFETY: setsid() and setrlimit are async-signal-safe and run before exec.
+    //         enter_private_network() only issues raw syscalls without allocating.
     //         Landlock syscalls (landlock_create_ruleset, landlock_add_rule,
     //         landlock_restrict_self) are also safe in this context.
     #[cfg(unix)]
@@ -70,14 +80,29 @@ async fn spawn_tool_with_pre_exec(
         cmd.pre_exec(move || {
             libc::setsid();
 
+            // Join the cgroup first: a user namespace or Landlock would deny
+            // the write to cgroup.procs.
+            if let PreExecPolicy::JoinCgroup { procs_path } = &pre_exec_policy {
+                csa_resource::cgroup_direct::join_cgroup(procs_path)?;
+            }
+
+            // Network isolation must precede Landlock: the user-namespace
+            // fallback writes /proc/self/{uid,gid}_map.
+            if private_network {
+                csa_resource::network::enter_private_network()?;
+            }
+
             // Resource isolation (rlimits / OOM score).
-            match pre_exec_policy {
-                PreExecPolicy::Setsid => {}
+            match &pre_exec_policy {
+                PreExecPolicy::Setsid | PreExecPolicy::JoinCgroup { .. } => {}
                 PreExecPolicy::Rlimits {
                     memory_max_mb,
                     pids_max,
+                    cpu_max_percent,
                 } => {
-                    csa_resource::rlimit::apply_rlimits(memory_max_mb, pids_max)
+                    csa_resource::rlimit::apply_rlimits(*memory_max_mb, *pids_max)
+                        .map_err(std::io::Error::other)?;
+                    csa_resource::rlimit::apply_cpu_priority(*cpu_max_percent)
                         .map_err(std::io::Error::other)?;
                 }
                 PreExecPolicy::OomAdj => {
@@ -97,6 +122,7 @@ async fn spawn_tool_with_pre_exec(
     {
         let _ = pre_exec_policy;
         let _ = landlock_paths;
+        let _ = private_network;
     }
 
     let mut child = cmd.spawn().context("Failed to spawn command")?;
@@ -139,6 +165,10 @@ async fn spawn_tool_with_pre_exec(
 ///   scope via `systemd-run --user --scope`.  A [`CgroupScopeGuard`] is
 ///   returned that stops the scope on drop.
 ///
+/// - **CgroupDirect**: In containers without a systemd user session, a child
+///   of the delegated cgroup v2 subtree is created and the tool joins it from
+///   `pre_exec`.  A [`DirectCgroupGuard`] removes it on drop.
+///
 /// - **Setrlimit**: `RLIMIT_NPROC` is applied in the child via `pre_exec`.
 ///
 /// - **None**: Falls through to OOM score adjustment as a last resort.
@@ -149,14 +179,30 @@ async fn spawn_tool_with_pre_exec(
 ///   [`csa_resource::bwrap::from_isolation_plan()`], providing read-only root
 ///   with selective writable bind mounts.
 ///
-/// - **Landlock**: Reserved for Phase C (currently a no-op placeholder).
+/// - **Landlock**: [`csa_resource::apply_landlock_rules`] runs in `pre_exec`,
+///   limiting writes to the plan's writable paths (project root, session dir,
+///   tool config dirs).  Selected when bwrap is unavailable; needs neither
+///   systemd nor user namespaces.
+///
+/// - **SandboxExec** (macOS): The command is wrapped with `sandbox-exec(1)`
+///   via [`csa_resource::seatbelt::from_isolation_plan()`], whose generated
+///   profile denies writes outside the plan's writable paths.
 ///
 /// - **None**: No filesystem isolation applied.
 ///
+/// ## Network axis (`plan.network`)
+///
+/// `NetworkMode::None` maps to `--unshare-net` under bwrap, `(deny network*)`
+/// in the Seatbelt profile, `unshare --net` inside a cgroup scope (see
+/// [`csa_resource::network::unshare_net_command`]), and otherwise a network
+/// namespace entered in `pre_exec` (see
+/// [`csa_resource::network::enter_private_network`]).
+///
 /// When `isolation` is `None`, this delegates directly to [`spawn_tool`] with
 /// no overhead — behavior is identical to the unsandboxed path.
 ///
 /// [`CgroupScopeGuard`]: csa_resource::cgroup::CgroupScopeGuard
+/// [`DirectCgroupGuard`]: csa_resource::cgroup_direct::DirectCgroupGuard
 pub async fn spawn_tool_sandboxed(
     cmd: Command,
     stdin_data: Option<Vec<u8>>,
@@ -170,42 +216,13 @@ pub async fn spawn_tool_sandboxed(
         return Ok((child, SandboxHandle::None));
     };
 
-    // --- Filesystem axis: wrap the command if needed ---
-    //
-    // Landlock paths are captured here and applied in pre_exec later,
-    // since Landlock operates on the calling thread (not via a wrapper binary).
-    let mut landlock_paths: Option<Vec<std::path::PathBuf>> = None;
-
-    let cmd = match plan.filesystem {
-        FilesystemCapability::Bwrap => wrap_command_with_bwrap(cmd, plan),
-        FilesystemCapability::Landlock => {
-            debug!("Landlock filesystem isolation will be applied in pre_exec");
-            // Filter out project_root when readonly_project_root is set,
-            // mirroring the bwrap --ro-bind behavior.
-            let paths = if plan.readonly_project_root {
-                plan.writable_paths
-                    .iter()
-                    .filter(|p| plan.project_root.as_ref().is_none_or(|root| *p != root))
-                    .cloned()
-                    .collect()
-            } else {
-                plan.writable_paths.clone()
-            };
-            landlock_paths = Some(paths);
-            let mut cmd = cmd;
-            apply_plan_env_overrides(&mut cmd, plan);
-            cmd
-        }
-        FilesystemCapability::None => {
-            let mut cmd = cmd;
-            apply_plan_env_overrides(&mut cmd, plan);
-            cmd
-        }
-    };
-
+    let (cmd, landlock_paths) = apply_filesystem_sandbox(cmd, plan);
     let has_bwrap = plan.filesystem == FilesystemCapability::Bwrap;
+    let has_seatbelt = plan.filesystem == FilesystemCapability::SandboxExec;
 
     let has_landlock = landlock_paths.is_some();
+    // bwrap and the Seatbelt profile already cut the network; otherwise do it in pre_exec.
+    let private_network = plan.network.is_isolated() && !has_bwrap && !has_seatbelt;
 
     // --- Resource axis: apply resource isolation ---
     match plan.resource {
@@ -220,6 +237,24 @@ pub async fn spawn_tool_sandboxed(
                 FsSandboxParams {
                     _has_bwrap: has_bwrap,
                     landlock_paths,
+                    private_network,
+                    clean_environment: None,
+                },
+            )
+            .await
+        }
+        ResourceCapability::CgroupDirect => {
+            spawn_with_direct_cgroup(
+                cmd,
+                stdin_data,
+                spawn_options,
+                plan,
+                tool_name,
+                session_id,
+                FsSandboxParams {
+                    _has_bwrap: has_bwrap,
+                    landlock_paths,
+                    private_network,
                     clean_environment: None,
                 },
             )
@@ -232,14 +267,18 @@ pub async fn spawn_tool_sandboxed(
                 PreExecPolicy::Rlimits {
                     memory_max_mb: plan.memory_max_mb.unwrap_or(0),
                     pids_max: plan.pids_max.map(u64::from),
+                    cpu_max_percent: plan.cpu_max_percent,
                 },
                 spawn_options,
                 landlock_paths,
+                private_network,
             )
             .await?;
 
             let handle = if has_bwrap {
                 SandboxHandle::Bwrap
+            } else if has_seatbelt {
+                SandboxHandle::SandboxExec
             } else if has_landlock {
                 SandboxHandle::Landlock
             } else {
@@ -255,11 +294,14 @@ pub async fn spawn_tool_sandboxed(
                 PreExecPolicy::OomAdj,
                 spawn_options,
                 landlock_paths,
+                private_network,
             )
             .await?;
 
             let handle = if has_bwrap {
                 SandboxHandle::Bwrap
+            } else if has_seatbelt {
+                SandboxHandle::SandboxExec
             } else if has_landlock {
                 SandboxHandle::Landlock
             } else {
@@ -270,6 +312,79 @@ pub async fn spawn_tool_sandboxed(
     }
 }
 
+/// Spawn a tool under `plan`'s filesystem and network sandbox inside an
+/// existing cgroup, whose `cgroup.procs` is `procs_path`, instead of giving
+/// it a cgroup of its own. The tool then counts against that cgroup's memory,
+/// PID and CPU limits. `plan.resource` is ignored.
+pub async fn spawn_tool_in_cgroup(
+    cmd: Command,
+    stdin_data: Option<Vec<u8>>,
+    spawn_options: SpawnOptions,
+    plan: &IsolationPlan,
+    procs_path: &std::ffi::CStr,
+) -> Result<(tokio::process::Child, SandboxHandle)> {
+    let (cmd, landlock_paths) = apply_filesystem_sandbox(cmd, plan);
+    let handle = match plan.filesystem {
+        FilesystemCapability::Bwrap => SandboxHandle::Bwrap,
+        FilesystemCapability::SandboxExec => SandboxHandle::SandboxExec,
+        FilesystemCapability::Landlock => SandboxHandle::Landlock,
+        FilesystemCapability::None => SandboxHandle::None,
+    };
+    let private_network = plan.network.is_isolated()
+        && !matches!(
+            plan.filesystem,
+            FilesystemCapability::Bwrap | FilesystemCapability::SandboxExec
+        );
+    let child = spawn_tool_with_pre_exec(
+        cmd,
+        stdin_data,
+        PreExecPolicy::JoinCgroup {
+            procs_path: procs_path.to_owned(),
+        },
+        spawn_options,
+        landlock_paths,
+        private_network,
+    )
+    .await?;
+    Ok((child, handle))
+}
+
+/// Filesystem axis: wrap `cmd` with bwrap or sandbox-exec, or return the
+/// paths Landlock must allow in `pre_exec`.
+fn apply_filesystem_sandbox(
+    cmd: Command,
+    plan: &IsolationPlan,
+) -> (Command, Option<Vec<std::path::PathBuf>>) {
+    match plan.filesystem {
+        FilesystemCapability::Bwrap => (wrap_command_with_bwrap(cmd, plan), None),
+        FilesystemCapability::SandboxExec => (wrap_command_with_sandbox_exec(cmd, plan), None),
+        FilesystemCapability::Landlock => {
+            // Landlock operates on the calling thread (not via a wrapper
+            // binary), so it is applied in pre_exec later.
+            debug!("Landlock filesystem isolation will be applied in pre_exec");
+            // Filter out project_root when readonly_project_root is set,
+            // mirroring the bwrap --ro-bind behavior.
+            let paths = if plan.readonly_project_root {
+                plan.writable_paths
+                    .iter()
+                    .filter(|p| plan.project_root.as_ref().is_none_or(|root| *p != root))
+                    .cloned()
+                    .collect()
+            } else {
+                plan.writable_paths.clone()
+            };
+            let mut cmd = cmd;
+            apply_plan_env_overrides(&mut cmd, plan);
+            (cmd, Some(paths))
+        }
+        FilesystemCapability::None => {
+            let mut cmd = cmd;
+            apply_plan_env_overrides(&mut cmd, plan);
+            (cmd, None)
+        }
+    }
+}
+
 /// Spawn a tool from a cleared, deterministic environment.
 ///
 /// The effective environment is composed before wrapper construction and then
@@ -296,6 +411,7 @@ pub async fn spawn_tool_sandboxed_in_environment(
     let mut landlock_paths = None;
     let mut cmd = match plan.filesystem {
         FilesystemCapability::Bwrap => wrap_command_with_bwrap_required(cmd, plan, &effective)?,
+        FilesystemCapability::SandboxExec => wrap_command_with_sandbox_exec(cmd, plan),
         FilesystemCapability::Landlock => {
             let paths = if plan.readonly_project_root {
                 plan.writable_paths
@@ -315,7 +431,9 @@ pub async fn spawn_tool_sandboxed_in_environment(
     validate_program(cmd.as_std().get_program(), &effective)?;
 
     let has_bwrap = plan.filesystem == FilesystemCapability::Bwrap;
+    let has_seatbelt = plan.filesystem == FilesystemCapability::SandboxExec;
     let has_landlock = landlock_paths.is_some();
+    let private_network = plan.network.is_isolated() && !has_bwrap && !has_seatbelt;
     match plan.resource {
         ResourceCapability::CgroupV2 => {
             spawn_with_cgroup(
@@ -328,6 +446,24 @@ pub async fn spawn_tool_sandboxed_in_environment(
                 FsSandboxParams {
                     _has_bwrap: has_bwrap,
                     landlock_paths,
+                    private_network,
+                    clean_environment: Some(&effective),
+                },
+            )
+            .await
+        }
+        ResourceCapability::CgroupDirect => {
+            spawn_with_direct_cgroup(
+                cmd,
+                stdin_data,
+                spawn_options,
+                plan,
+                tool_name,
+                session_id,
+                FsSandboxParams {
+                    _has_bwrap: has_bwrap,
+                    landlock_paths,
+                    private_network,
                     clean_environment: Some(&effective),
                 },
             )
@@ -340,13 +476,17 @@ pub async fn spawn_tool_sandboxed_in_environment(
                 PreExecPolicy::Rlimits {
                     memory_max_mb: plan.memory_max_mb.unwrap_or(0),
                     pids_max: plan.pids_max.map(u64::from),
+                    cpu_max_percent: plan.cpu_max_percent,
                 },
                 spawn_options,
                 landlock_paths,
+                private_network,
             )
             .await?;
             let handle = if has_bwrap {
                 SandboxHandle::Bwrap
+            } else if has_seatbelt {
+                SandboxHandle::SandboxExec
             } else if has_landlock {
                 SandboxHandle::Landlock
             } else {
@@ -361,10 +501,13 @@ pub async fn spawn_tool_sandboxed_in_environment(
                 PreExecPolicy::OomAdj,
                 spawn_options,
                 landlock_paths,
+                private_network,
             )
             .await?;
             let handle = if has_bwrap {
                 SandboxHandle::Bwrap
+            } else if has_seatbelt {
+                SandboxHandle::SandboxExec
             } else if has_landlock {
                 SandboxHandle::Landlock
             } else {
@@ -432,6 +575,33 @@ fn wrap_command_with_bwrap(cmd: Command, plan: &IsolationPlan) -> Command {
     }
 }
 
+fn wrap_command_with_sandbox_exec(cmd: Command, plan: &IsolationPlan) -> Command {
+    let tool_binary = cmd.as_std().get_program().to_string_lossy().into_owned();
+    let tool_args: Vec<String> = cmd
+        .as_std()
+        .get_args()
+        .map(|argument| argument.to_string_lossy().into_owned())
+        .collect();
+
+    let Some(seatbelt_cmd) =
+        csa_resource::seatbelt::from_isolation_plan(plan, &tool_binary, &tool_args)
+    else {
+        warn!("sandbox-exec requested but from_isolation_plan returned None; proceeding without");
+        return cmd;
+    };
+    let mut wrapped = Command::from(seatbelt_cmd);
+    csa_core::env::scrub_subtree_contract_env_tokio(&mut wrapped);
+    scrub_git_push_authorization_env(&mut wrapped);
+    propagate_explicit_envs(&mut wrapped, &explicit_envs(&cmd));
+    // Plan overrides win over the tool's own env, as bwrap's --setenv does.
+    apply_plan_env_overrides(&mut wrapped, plan);
+    if let Some(dir) = cmd.as_std().get_current_dir() {
+        wrapped.current_dir(dir);
+    }
+    debug!("wrapped tool command with sandbox-exec filesystem sandbox");
+    wrapped
+}
+
 pub(crate) fn wrap_command_with_bwrap_required(
     cmd: Command,
     plan: &IsolationPlan,
@@ -461,6 +631,9 @@ pub(crate) fn wrap_command_with_bwrap_required(
 struct FsSandboxParams<'a> {
     _has_bwrap: bool,
     landlock_paths: Option<Vec<std::path::PathBuf>>,
+    /// Isolate the network outside bwrap and Seatbelt: in `pre_exec` for
+    /// direct cgroups, via `unshare --net` for systemd scopes.
+    private_network: bool,
     clean_environment: Option<&'a std::collections::BTreeMap<String, String>>,
 }
 
@@ -480,11 +653,11 @@ async fn spawn_with_cgroup(
         ));
     }
 
-    let cgroup_config = csa_resource::cgroup::SandboxConfig {
-        memory_max_mb: plan.memory_max_mb.unwrap_or(4096),
-        memory_swap_max_mb: plan.memory_swap_max_mb,
-        pids_max: plan.pids_max.or(Some(512)),
-    };
+    let mut cgroup_config = cgroup_sandbox_config(plan);
+    if !fs_sandbox.private_network {
+        // bwrap or Seatbelt already cut the network inside the scope.
+        cgroup_config.network = csa_resource::NetworkMode::Full;
+    }
 
     let mut tokio_cmd = if let Some(environment) = fs_sandbox.clean_environment {
         build_clean_cgroup_scope_command(
@@ -512,6 +685,59 @@ async fn spawn_with_cgroup(
     Ok((child, SandboxHandle::Cgroup(guard)))
 }
 
+/// Spawn inside a CSA-created child of the delegated cgroup v2 subtree.
+///
+/// Used in containers without a systemd user session.  The child joins the
+/// cgroup from `pre_exec`, so Landlock and network isolation still apply.
+async fn spawn_with_direct_cgroup(
+    cmd: Command,
+    stdin_data: Option<Vec<u8>>,
+    spawn_options: SpawnOptions,
+    plan: &IsolationPlan,
+    tool_name: &str,
+    session_id: &str,
+    fs_sandbox: FsSandboxParams<'_>,
+) -> Result<(tokio::process::Child, SandboxHandle)> {
+    let root = csa_resource::cgroup_direct::prepare_delegated_root()
+        .context("delegated cgroup v2 subtree is no longer usable")?;
+    let guard = csa_resource::cgroup_direct::DirectCgroupGuard::create(
+        &root,
+        tool_name,
+        session_id,
+        &cgroup_sandbox_config(plan),
+    )?;
+
+    let child = spawn_tool_with_pre_exec(
+        cmd,
+        stdin_data,
+        PreExecPolicy::JoinCgroup {
+            procs_path: guard.procs_path().to_owned(),
+        },
+        spawn_options,
+        fs_sandbox.landlock_paths,
+        fs_sandbox.private_network,
+    )
+    .await?;
+
+    debug!(
+        cgroup = %guard.scope_name(),
+        pid = child.id(),
+        "spawned tool inside directly managed cgroup"
+    );
+    Ok((child, SandboxHandle::CgroupDirect(guard)))
+}
+
+fn cgroup_sandbox_config(plan: &IsolationPlan) -> csa_resource::cgroup::SandboxConfig {
+    csa_resource::cgroup::SandboxConfig {
+        memory_max_mb: plan.memory_max_mb.unwrap_or(4096),
+        memory_swap_max_mb: plan.memory_swap_max_mb,
+        pids_max: plan.pids_max.or(Some(512)),
+        cpu_max_percent: plan.cpu_max_percent,
+        cpu_weight: plan.cpu_weight,
+        network: plan.network,
+    }
+}
+
 pub(crate) fn build_clean_cgroup_scope_command(
     original_cmd: &Command,
     tool_name: &str,
@@ -119,6 +119,9 @@ fn cgroup_outer_environment_is_reasserted_from_the_typed_map() {
             memory_max_mb: 512,
             memory_swap_max_mb: None,
             pids_max: Some(32),
+            cpu_max_percent: None,
+            cpu_weight: None,
+            network: csa_resource::NetworkMode::Full,
         },
         &effective,
     )
@@ -25,6 +25,10 @@ fn bwrap_plan() -> IsolationPlan {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -44,6 +48,10 @@ fn no_filesystem_wrapper_plan_with_tmpdir(tmpdir: &str) -> IsolationPlan {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: csa_resource::NetworkMode::Full,
         readonly_project_root: false,
         user_daemon_ipc: false,
         project_root: None,
@@ -91,6 +99,40 @@ async fn non_bwrap_spawn_applies_plan_env_overrides_over_explicit_env() {
     );
 }
 
+#[tokio::test]
+async fn spawn_in_cgroup_joins_before_exec_and_applies_plan_env() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    // A plain file stands in for `cgroup.procs`: the child writes "0" to it.
+    let procs = temp.path().join("cgroup.procs");
+    std::fs::write(&procs, "").expect("create procs stand-in");
+    let procs_path = std::ffi::CString::new(procs.to_str().expect("utf-8 path")).unwrap();
+    let plan = no_filesystem_wrapper_plan_with_tmpdir("/session-tmp");
+    let mut cmd = Command::new("/bin/sh");
+    cmd.arg("-c").arg("printf '%s' \"$TMPDIR\"");
+
+    let (child, _handle) =
+        spawn_tool_in_cgroup(cmd, None, SpawnOptions::default(), &plan, &procs_path)
+            .await
+            .expect("spawn should succeed");
+    let result = crate::wait_and_capture(child, crate::StreamMode::BufferOnly)
+        .await
+        .expect("wait should succeed");
+
+    assert_eq!(result.output, "/session-tmp");
+    assert_eq!(std::fs::read_to_string(&procs).unwrap(), "0");
+
+    let missing = std::ffi::CString::new(temp.path().join("gone").to_str().unwrap()).unwrap();
+    let spawned = spawn_tool_in_cgroup(
+        Command::new("/bin/true"),
+        None,
+        SpawnOptions::default(),
+        &plan,
+        &missing,
+    )
+    .await;
+    assert!(spawned.is_err(), "an unjoinable cgroup must fail the spawn");
+}
+
 #[test]
 fn bwrap_wrapper_scrubs_ambient_subtree_contract_env() {
     let original = Command::new("/usr/bin/tool");
@@ -151,6 +193,9 @@ fn cgroup_wrapper_scrubs_ambient_then_preserves_explicit_fresh_env() {
         memory_max_mb: 1024,
         memory_swap_max_mb: None,
         pids_max: Some(64),
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: csa_resource::NetworkMode::Full,
     };
 
     let wrapped = build_cgroup_scope_command(&original, "codex", "01KTEST", &config);
@@ -196,6 +241,9 @@ fn cgroup_wrapper_preserves_explicit_typed_git_push_authorization() {
         memory_max_mb: 1024,
         memory_swap_max_mb: None,
         pids_max: Some(64),
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: csa_resource::NetworkMode::Full,
     };
 
     let wrapped = build_cgroup_scope_command(&original, "codex", "01KTEST", &config);
@@ -40,7 +40,7 @@ pub async fn check_tool_installed(executable: &str) -> Result<()> {
         .context("Failed to execute 'which' command")?;
 
     if !output.status.success() {
-        anyhow::bail!("Tool '{executable}' is not installed or not in PATH");
+        return Err(csa_core::error::AppError::ToolNotInstalled(executable.to_string()).into());
     }
 
     Ok(())
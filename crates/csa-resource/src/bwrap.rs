@@ -17,6 +17,7 @@ pub struct BwrapCommandBuilder {
     readable_paths: Vec<PathBuf>,
     ro_binds: Vec<(PathBuf, PathBuf)>,
     env_vars: Vec<(String, String)>,
+    unshare_net: bool,
 }
 
 impl BwrapCommandBuilder {
@@ -29,6 +30,7 @@ impl BwrapCommandBuilder {
             readable_paths: Vec::new(),
             ro_binds: Vec::new(),
             env_vars: Vec::new(),
+            unshare_net: false,
         }
     }
 
@@ -70,6 +72,12 @@ impl BwrapCommandBuilder {
         self
     }
 
+    /// Run the tool in a private network namespace (`--unshare-net`).
+    pub fn with_unshare_net(&mut self) -> &mut Self {
+        self.unshare_net = true;
+        self
+    }
+
     /// Consume the builder and produce a ready-to-spawn [`Command`].
     pub fn build(&self) -> Command {
         self.build_with_home(std::env::var_os("HOME").as_deref().map(Path::new))
@@ -176,7 +184,11 @@ impl BwrapCommandBuilder {
         }
 
         // Namespace configuration
-        cmd.arg("--share-net");
+        cmd.arg(if self.unshare_net {
+            "--unshare-net"
+        } else {
+            "--share-net"
+        });
         cmd.arg("--unshare-pid");
         cmd.arg("--die-with-parent");
 
@@ -276,6 +288,10 @@ pub fn from_isolation_plan(
         builder.with_readable_path(path);
     }
 
+    if plan.network.is_isolated() {
+        builder.with_unshare_net();
+    }
+
     let mut env_overrides = plan.env_overrides.clone();
     csa_core::env::scrub_subtree_contract_env_map(&mut env_overrides);
     csa_core::env::strip_git_push_authorization_keys(&mut env_overrides);
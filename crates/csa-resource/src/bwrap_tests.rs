@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use super::*;
+use crate::network::NetworkMode;
 use crate::sandbox::ResourceCapability;
 
 /// Helper: extract the full argument list from a Command via Debug output.
@@ -105,6 +106,10 @@ fn test_bwrap_from_isolation_plan_bwrap() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: NetworkMode::Full,
         readonly_project_root: false,
         project_root: None,
         soft_limit_percent: None,
@@ -133,6 +138,10 @@ fn test_bwrap_from_isolation_plan_none() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: NetworkMode::Full,
         readonly_project_root: false,
         project_root: None,
         soft_limit_percent: None,
@@ -255,6 +264,10 @@ fn test_bwrap_from_isolation_plan_sets_tmpdir_override() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: NetworkMode::Full,
         readonly_project_root: false,
         project_root: Some(PathBuf::from("/project")),
         soft_limit_percent: None,
@@ -274,6 +287,18 @@ fn test_bwrap_from_isolation_plan_sets_tmpdir_override() {
     );
 }
 
+#[test]
+fn test_bwrap_unshare_net_replaces_share_net() {
+    let mut builder = BwrapCommandBuilder::new("/usr/bin/tool", &[]);
+    let shared = command_args(&builder.build());
+    assert!(shared.contains(&"--share-net".to_owned()));
+    assert!(!shared.contains(&"--unshare-net".to_owned()));
+
+    let isolated = command_args(&builder.with_unshare_net().build());
+    assert!(isolated.contains(&"--unshare-net".to_owned()));
+    assert!(!isolated.contains(&"--share-net".to_owned()));
+}
+
 #[test]
 fn test_bwrap_from_isolation_plan_scrubs_subtree_contract_env_overrides() {
     let mut env_overrides = HashMap::new();
@@ -304,6 +329,10 @@ fn test_bwrap_from_isolation_plan_scrubs_subtree_contract_env_overrides() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: NetworkMode::Full,
         readonly_project_root: false,
         project_root: Some(PathBuf::from("/project")),
         soft_limit_percent: None,
@@ -349,6 +378,10 @@ fn test_bwrap_readonly_project_root() {
         memory_max_mb: None,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        disk_max_mb: None,
+        network: NetworkMode::Full,
         readonly_project_root: true,
         project_root: Some(PathBuf::from("/project")),
         soft_limit_percent: None,
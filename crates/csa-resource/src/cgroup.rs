@@ -1,9 +1,12 @@
 //! Cgroup v2 scope guard for systemd-based resource isolation.
 //!
 //! Wraps child tool processes in systemd transient scopes via `systemd-run
-//! --user --scope`, applying `MemoryMax`, `MemorySwapMax`, and `TasksMax`
-//! properties.  The [`CgroupScopeGuard`] owns the scope's lifecycle and stops
-//! it on [`Drop`].
+//! --user --scope`, applying `MemoryMax`, `MemorySwapMax`, `TasksMax` and,
+//! when configured, `CPUQuota` / `CPUWeight` properties.  Network isolation
+//! runs the command under `unshare --net`, because transient scopes reject
+//! `PrivateNetwork=`.
+//! The [`CgroupScopeGuard`] owns the scope's lifecycle and stops it on
+//! [`Drop`].
 //!
 //! # Recursive isolation
 //!
@@ -25,11 +28,15 @@
 //! ```
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use tracing::{debug, warn};
 
+use crate::child_usage::ChildUsage;
+use crate::network::NetworkMode;
+
 // ---------------------------------------------------------------------------
 // SandboxConfig
 // ---------------------------------------------------------------------------
@@ -45,6 +52,15 @@ pub struct SandboxConfig {
     /// Maximum number of tasks/PIDs (`TasksMax`).  `None` keeps the systemd
     /// default (unlimited).
     pub pids_max: Option<u32>,
+    /// CPU time cap as a percentage of one core (`CPUQuota`); 200 allows two
+    /// full cores.  `None` leaves CPU unthrottled.
+    pub cpu_max_percent: Option<u32>,
+    /// Relative CPU share under contention (`CPUWeight`, 1-10000, systemd
+    /// default 100).  `None` keeps the systemd default.
+    pub cpu_weight: Option<u32>,
+    /// `NetworkMode::None` runs the command under `unshare --net` (loopback
+    /// only).
+    pub network: NetworkMode,
 }
 
 // ---------------------------------------------------------------------------
@@ -85,12 +101,16 @@ pub fn scope_unit_name(tool_name: &str, session_id: &str) -> String {
 /// # Example
 ///
 /// ```no_run
+/// use csa_resource::NetworkMode;
 /// use csa_resource::cgroup::{SandboxConfig, create_scope_command};
 ///
 /// let cfg = SandboxConfig {
 ///     memory_max_mb: 4096,
 ///     memory_swap_max_mb: Some(0),
 ///     pids_max: Some(512),
+///     cpu_max_percent: Some(200),
+///     cpu_weight: None,
+///     network: NetworkMode::Full,
 /// };
 /// let mut cmd = create_scope_command("claude-code", "01JEXAMPLE", &cfg);
 /// cmd.arg("claude-code").arg("--yolo");
@@ -117,8 +137,20 @@ fn populate_scope_command(
         cmd.args(["-p", &format!("TasksMax={pids}")]);
     }
 
+    if let Some(percent) = config.cpu_max_percent {
+        cmd.args(["-p", &format!("CPUQuota={percent}%")]);
+    }
+
+    if let Some(weight) = config.cpu_weight {
+        cmd.args(["-p", &format!("CPUWeight={weight}")]);
+    }
+
     // Separator: everything after "--" is the actual command the scope runs.
     cmd.arg("--");
+
+    if config.network.is_isolated() {
+        cmd.args(crate::network::unshare_net_command());
+    }
 }
 
 pub fn create_scope_command(tool_name: &str, session_id: &str, config: &SandboxConfig) -> Command {
@@ -268,6 +300,40 @@ impl CgroupScopeGuard {
         trimmed.parse::<u64>().ok()
     }
 
+    /// CPU time and block I/O of the processes that ran in this scope.
+    ///
+    /// Reads the stat files of the scope's `ControlGroup`; must be called
+    /// before [`Self::stop`] or [`Drop`].
+    pub fn usage(&self) -> Option<ChildUsage> {
+        ChildUsage::from_cgroup_dir(&self.cgroup_dir()?)
+    }
+
+    /// The scope's cgroup directory under `/sys/fs/cgroup`, from its
+    /// `ControlGroup` property. `None` once the scope is gone.
+    pub fn cgroup_dir(&self) -> Option<PathBuf> {
+        let output = Command::new("systemctl")
+            .args([
+                "--user",
+                "show",
+                &self.scope_name,
+                "--property=ControlGroup",
+                "--value",
+            ])
+            .stdin(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let control_group = String::from_utf8_lossy(&output.stdout);
+        let control_group = control_group.trim().trim_start_matches('/');
+        if control_group.is_empty() {
+            return None;
+        }
+        Some(Path::new("/sys/fs/cgroup").join(control_group))
+    }
+
     /// Query configured memory limit (in MB) for this scope.
     ///
     /// Uses `systemctl --user show <scope> --property=MemoryMax`.
@@ -368,33 +434,20 @@ impl CgroupScopeGuard {
         memory_swap_max_bytes: Option<u64>,
         inferred_from_sigkill: bool,
     ) -> String {
-        let peak = peak_bytes
-            .map(|bytes| format!("peak: {}MB", bytes_to_mb(bytes)))
-            .unwrap_or_else(|| "peak: unknown".to_string());
-        let limit_mb = memory_max_bytes
-            .map(bytes_to_mb)
-            .unwrap_or(self.configured_memory_max_mb);
-        let swap_mb = memory_swap_max_bytes
-            .map(bytes_to_mb)
-            .or(self.configured_memory_swap_max_mb);
-        let mut message = format!(
-            "process was {} ({peak}, limit: {limit_mb}MB, {}). \
-             Increase resources.memory_max_mb or tools.<tool>.memory_max_mb \
-             in .csa/config.toml",
+        format_oom_diagnosis(
+            peak_bytes,
+            memory_max_bytes
+                .map(bytes_to_mb)
+                .unwrap_or(self.configured_memory_max_mb),
+            memory_swap_max_bytes
+                .map(bytes_to_mb)
+                .or(self.configured_memory_swap_max_mb),
             if inferred_from_sigkill {
                 "likely OOM-killed after scope cleanup"
             } else {
                 "OOM-killed"
             },
-            format_swap_limit(swap_mb),
-        );
-        if swap_mb == Some(0) {
-            message.push_str(
-                " Swap is disabled; consider increasing resources.memory_swap_max_mb \
-                 or tools.<tool>.memory_swap_max_mb.",
-            );
-        }
-        message
+        )
     }
 
     /// Explicitly stop the scope, using the same cleanup path as [`Drop`].
@@ -488,6 +541,31 @@ fn bytes_to_mb(bytes: u64) -> u64 {
     bytes / 1024 / 1024
 }
 
+/// Shared OOM hint for cgroup guards: `verdict` is e.g. "OOM-killed".
+pub(crate) fn format_oom_diagnosis(
+    peak_bytes: Option<u64>,
+    limit_mb: u64,
+    swap_mb: Option<u64>,
+    verdict: &str,
+) -> String {
+    let peak = peak_bytes
+        .map(|bytes| format!("peak: {}MB", bytes_to_mb(bytes)))
+        .unwrap_or_else(|| "peak: unknown".to_string());
+    let mut message = format!(
+        "process was {verdict} ({peak}, limit: {limit_mb}MB, {}). \
+         Increase resources.memory_max_mb or tools.<tool>.memory_max_mb \
+         in .csa/config.toml",
+        format_swap_limit(swap_mb),
+    );
+    if swap_mb == Some(0) {
+        message.push_str(
+            " Swap is disabled; consider increasing resources.memory_swap_max_mb \
+             or tools.<tool>.memory_swap_max_mb.",
+        );
+    }
+    message
+}
+
 fn format_swap_limit(swap_mb: Option<u64>) -> String {
     match swap_mb {
         Some(swap) => format!("swap: {swap}MB"),
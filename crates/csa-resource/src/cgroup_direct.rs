@@ -0,0 +1,535 @@
+//! Direct cgroup v2 management for hosts without a systemd user session.
+//!
+//! Containers usually have no `systemd --user`, so `systemd-run --user
+//! --scope` is unavailable even when the container owns a delegated cgroup v2
+//! subtree.  In that case CSA manages the cgroup itself:
+//!
+//! 1. create `csa-<tool>-<session>` under the process's own cgroup,
+//! 2. write `memory.max`, `memory.swap.max`, `pids.max`, `cpu.max` and
+//!    `cpu.weight`,
+//! 3. move the child in from `pre_exec` ([`join_cgroup`]), before exec and
+//!    before Landlock would forbid writes under `/sys/fs/cgroup`.
+//!
+//! [`DirectCgroupGuard`] kills the remaining processes and removes the cgroup
+//! on drop, with the same lifetime semantics as
+//! [`CgroupScopeGuard`](crate::cgroup::CgroupScopeGuard).
+//!
+//! The subtree counts as delegated when its `cgroup.controllers` offers the
+//! `memory` and `pids` controllers and the directory is writable.  Probing
+//! ([`delegated_root`]) is read-only.  Because of cgroup v2's "no internal
+//! processes" rule, [`prepare_delegated_root`] first moves the processes in
+//! that cgroup (CSA itself included) into a `csa-supervisor` leaf and only
+//! then enables the controllers for its children.
+
+use std::ffi::{CStr, CString};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use tracing::{debug, warn};
+
+use crate::cgroup::{SandboxConfig, format_oom_diagnosis, scope_unit_name};
+use crate::child_usage::ChildUsage;
+
+const CGROUP_MOUNT: &str = "/sys/fs/cgroup";
+const REQUIRED_CONTROLLERS: [&str; 2] = ["memory", "pids"];
+/// Leaf cgroup the supervisor's processes move into before controllers are
+/// enabled for the delegated root.
+const SUPERVISOR_LEAF: &str = "csa-supervisor";
+const CPU_PERIOD_USEC: u64 = 100_000;
+const SIGKILL: i32 = 9;
+/// How long drop waits for processes to exit after each signal.
+const EXIT_WAIT: Duration = Duration::from_secs(1);
+
+/// Return the delegated cgroup directory new child cgroups are created under.
+///
+/// This is the caller's own cgroup (from `/proc/self/cgroup`), or its parent
+/// once the caller has moved into the [`SUPERVISOR_LEAF`], when the `memory`
+/// and `pids` controllers are delegated to it and it is writable.  The probe
+/// never writes to cgroupfs; see [`prepare_delegated_root`].
+pub fn delegated_root() -> Option<PathBuf> {
+    let root = own_root()?;
+    probe_root(&root).then_some(root)
+}
+
+/// Make the delegated root usable for child cgroups and return it.
+///
+/// Moves every process of the root cgroup into `<root>/csa-supervisor` and
+/// then enables `memory`, `pids` (and `cpu` when offered) for the root's
+/// children.  Idempotent: an already prepared root is returned unchanged.
+pub fn prepare_delegated_root() -> Result<PathBuf> {
+    let root = own_root().context("cannot determine own cgroup v2 path")?;
+    prepare_root(&root)?;
+    Ok(root)
+}
+
+fn own_root() -> Option<PathBuf> {
+    let own = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let relative = parse_own_cgroup(&own)?;
+    let cgroup = Path::new(CGROUP_MOUNT).join(relative.trim_start_matches('/'));
+    if cgroup
+        .file_name()
+        .is_some_and(|name| name == SUPERVISOR_LEAF)
+    {
+        return cgroup.parent().map(Path::to_path_buf);
+    }
+    Some(cgroup)
+}
+
+/// Extract the unified-hierarchy path (`0::<path>`) from `/proc/self/cgroup`.
+fn parse_own_cgroup(contents: &str) -> Option<&str> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(str::trim)
+}
+
+fn probe_root(root: &Path) -> bool {
+    let Ok(available) = std::fs::read_to_string(root.join("cgroup.controllers")) else {
+        return false;
+    };
+    REQUIRED_CONTROLLERS
+        .iter()
+        .all(|controller| has_word(&available, controller))
+        && is_writable(root)
+        && is_writable(&root.join("cgroup.subtree_control"))
+}
+
+fn is_writable(path: &Path) -> bool {
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: `path` is NUL-terminated and outlives the call.
+    unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+}
+
+fn prepare_root(root: &Path) -> Result<()> {
+    let available = std::fs::read_to_string(root.join("cgroup.controllers"))
+        .with_context(|| format!("failed to read controllers of {}", root.display()))?;
+    let enabled = std::fs::read_to_string(root.join("cgroup.subtree_control"))
+        .with_context(|| format!("failed to read subtree_control of {}", root.display()))?;
+    let missing: Vec<&str> = REQUIRED_CONTROLLERS
+        .into_iter()
+        .filter(|controller| !has_word(&enabled, controller))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if let Some(controller) = missing.iter().find(|c| !has_word(&available, c)) {
+        anyhow::bail!(
+            "cgroup controller '{controller}' not delegated to {}",
+            root.display()
+        );
+    }
+    move_into_supervisor_leaf(root)?;
+    for controller in missing {
+        std::fs::write(
+            root.join("cgroup.subtree_control"),
+            format!("+{controller}"),
+        )
+        .with_context(|| {
+            format!(
+                "failed to enable '{controller}' for children of {}",
+                root.display()
+            )
+        })?;
+    }
+    // cpu is optional: cpu.max / cpu.weight are skipped when it is missing.
+    if has_word(&available, "cpu") && !has_word(&enabled, "cpu") {
+        let _ = std::fs::write(root.join("cgroup.subtree_control"), "+cpu");
+    }
+    Ok(())
+}
+
+/// Move all processes of `root` into `<root>/csa-supervisor`, so `root` has
+/// no internal processes left when controllers are enabled for its children.
+fn move_into_supervisor_leaf(root: &Path) -> Result<()> {
+    let leaf = root.join(SUPERVISOR_LEAF);
+    if !leaf.exists() {
+        std::fs::create_dir(&leaf)
+            .with_context(|| format!("failed to create cgroup {}", leaf.display()))?;
+    }
+    let leaf_procs = leaf.join("cgroup.procs");
+    for pid in read_pids(&root.join("cgroup.procs")) {
+        match std::fs::write(&leaf_procs, pid.to_string()) {
+            Ok(()) => {}
+            // The process exited between listing and moving it.
+            Err(error) if error.raw_os_error() == Some(libc::ESRCH) => {}
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("failed to move pid {pid} into {}", leaf.display()));
+            }
+        }
+    }
+    debug!(cgroup = %leaf.display(), "moved delegated root processes into supervisor leaf");
+    Ok(())
+}
+
+fn read_pids(procs: &Path) -> Vec<i32> {
+    std::fs::read_to_string(procs)
+        .map(|procs| {
+            procs
+                .lines()
+                .filter_map(|pid| pid.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn has_word(list: &str, word: &str) -> bool {
+    list.split_whitespace().any(|entry| entry == word)
+}
+
+/// Move the calling process into the cgroup whose `cgroup.procs` is `procs_path`.
+///
+/// Intended for `pre_exec`: it only issues `open`/`write`/`close`.
+pub fn join_cgroup(procs_path: &CStr) -> std::io::Result<()> {
+    // SAFETY: `procs_path` is NUL-terminated and the buffer outlives the write.
+    unsafe {
+        let fd = libc::open(procs_path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // Writing "0" moves the writing process itself.
+        let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+        let result = if written == 1 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        };
+        libc::close(fd);
+        result
+    }
+}
+
+/// RAII guard for a CSA-created child cgroup.
+///
+/// Create it *before* spawning, pass [`Self::procs_path`] to the child's
+/// `pre_exec`, and keep it alive for the child's lifetime.  On drop the
+/// remaining processes get SIGTERM, then `cgroup.kill` (or SIGKILL), and the
+/// directory is removed.
+pub struct DirectCgroupGuard {
+    path: PathBuf,
+    path_label: String,
+    procs_path: CString,
+    configured_memory_max_mb: u64,
+    configured_memory_swap_max_mb: Option<u64>,
+}
+
+impl DirectCgroupGuard {
+    /// Create `csa-<tool>-<session>` under `root` and apply `config`'s limits.
+    ///
+    /// `config.network` is ignored here; network isolation happens in the
+    /// child's `pre_exec` like on the setrlimit path.
+    pub fn create(
+        root: &Path,
+        tool_name: &str,
+        session_id: &str,
+        config: &SandboxConfig,
+    ) -> Result<Self> {
+        let unit = scope_unit_name(tool_name, session_id);
+        let name = unit.strip_suffix(".scope").unwrap_or(&unit);
+        let path = root.join(name);
+        if path.exists() {
+            // A crashed run can leave an empty cgroup behind.
+            std::fs::remove_dir(&path).with_context(|| {
+                format!("stale cgroup {} exists and is still in use", path.display())
+            })?;
+        }
+        std::fs::create_dir(&path)
+            .with_context(|| format!("failed to create cgroup {}", path.display()))?;
+
+        let procs_path = CString::new(path.join("cgroup.procs").as_os_str().as_bytes())
+            .context("cgroup path contains a NUL byte")?;
+        let guard = Self {
+            path_label: path.to_string_lossy().into_owned(),
+            path,
+            procs_path,
+            configured_memory_max_mb: config.memory_max_mb,
+            configured_memory_swap_max_mb: config.memory_swap_max_mb,
+        };
+        guard.apply_limits(config)?;
+        debug!(cgroup = %guard.path_label, "direct cgroup created");
+        Ok(guard)
+    }
+
+    fn apply_limits(&self, config: &SandboxConfig) -> Result<()> {
+        self.write_control(
+            "memory.max",
+            &(config.memory_max_mb * 1024 * 1024).to_string(),
+        )?;
+        if let Some(swap) = config.memory_swap_max_mb {
+            // memory.swap.max is absent when swap accounting is disabled.
+            if self.path.join("memory.swap.max").exists() {
+                self.write_control("memory.swap.max", &(swap * 1024 * 1024).to_string())?;
+            }
+        }
+        if let Some(pids) = config.pids_max {
+            self.write_control("pids.max", &pids.to_string())?;
+        }
+        if let Some(percent) = config.cpu_max_percent {
+            let quota = u64::from(percent) * CPU_PERIOD_USEC / 100;
+            self.write_optional_control("cpu.max", &format!("{quota} {CPU_PERIOD_USEC}"));
+        }
+        if let Some(weight) = config.cpu_weight {
+            self.write_optional_control("cpu.weight", &weight.to_string());
+        }
+        Ok(())
+    }
+
+    fn write_control(&self, file: &str, value: &str) -> Result<()> {
+        std::fs::write(self.path.join(file), value)
+            .with_context(|| format!("failed to write {file}={value} in {}", self.path.display()))
+    }
+
+    fn write_optional_control(&self, file: &str, value: &str) {
+        if let Err(error) = self.write_control(file, value) {
+            warn!(%error, "cpu controller unavailable in delegated cgroup; limit not applied");
+        }
+    }
+
+    /// Path of the cgroup's `cgroup.procs`, for [`join_cgroup`] in `pre_exec`.
+    pub fn procs_path(&self) -> &CStr {
+        &self.procs_path
+    }
+
+    /// The cgroup directory, used as the monitor and telemetry label.
+    pub fn scope_name(&self) -> &str {
+        &self.path_label
+    }
+
+    /// Check whether the kernel OOM killer fired in this cgroup, falling back
+    /// to SIGKILL inference when `memory.events` is unreadable.
+    pub fn check_oom_killed_with_signal(&self, exit_signal: Option<i32>) -> bool {
+        match self.oom_kill_count() {
+            Some(count) => count > 0,
+            None => self.should_assume_oom(exit_signal),
+        }
+    }
+
+    /// Peak memory usage in MB (`memory.peak`, kernel 5.19+).
+    pub fn memory_peak_mb(&self) -> Option<u64> {
+        self.read_u64("memory.peak")
+            .map(|bytes| bytes / 1024 / 1024)
+    }
+
+    /// Current memory usage in bytes (`memory.current`).
+    pub fn memory_current_bytes(&self) -> Option<u64> {
+        self.read_u64("memory.current")
+    }
+
+    /// CPU time and block I/O of the processes that ran in this cgroup.
+    pub fn usage(&self) -> Option<ChildUsage> {
+        ChildUsage::from_cgroup_dir(&self.path)
+    }
+
+    /// Actionable OOM hint when the cgroup was OOM-killed.
+    pub fn oom_diagnosis_with_signal(&self, exit_signal: Option<i32>) -> Option<String> {
+        let verdict = match self.oom_kill_count() {
+            Some(count) if count > 0 => "OOM-killed",
+            Some(_) => return None,
+            None if self.should_assume_oom(exit_signal) => "likely OOM-killed",
+            None => return None,
+        };
+        Some(format_oom_diagnosis(
+            self.read_u64("memory.peak"),
+            self.configured_memory_max_mb,
+            self.configured_memory_swap_max_mb,
+            verdict,
+        ))
+    }
+
+    fn oom_kill_count(&self) -> Option<u64> {
+        let events = std::fs::read_to_string(self.path.join("memory.events")).ok()?;
+        parse_oom_kill_count(&events)
+    }
+
+    fn should_assume_oom(&self, exit_signal: Option<i32>) -> bool {
+        exit_signal == Some(SIGKILL) && self.configured_memory_max_mb > 0
+    }
+
+    fn read_u64(&self, file: &str) -> Option<u64> {
+        std::fs::read_to_string(self.path.join(file))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Processes in this cgroup and in any child cgroups, e.g. the
+    /// `csa-supervisor` leaf of a nested CSA.
+    fn pids(&self) -> Vec<i32> {
+        let mut pids = Vec::new();
+        for dir in subtree_dirs(&self.path) {
+            pids.extend(read_pids(&dir.join("cgroup.procs")));
+        }
+        pids
+    }
+
+    fn wait_until_empty(&self) -> bool {
+        let deadline = Instant::now() + EXIT_WAIT;
+        while Instant::now() < deadline {
+            if self.pids().is_empty() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        self.pids().is_empty()
+    }
+
+    fn signal_all(&self, signal: i32) {
+        for pid in self.pids() {
+            // SAFETY: kill() has no memory-safety preconditions.
+            unsafe {
+                libc::kill(pid, signal);
+            }
+        }
+    }
+
+    /// Explicitly tear down the cgroup, using the same path as [`Drop`].
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for DirectCgroupGuard {
+    fn drop(&mut self) {
+        self.signal_all(libc::SIGTERM);
+        if !self.wait_until_empty() {
+            // cgroup.kill (5.14+) also catches processes forked mid-teardown.
+            if std::fs::write(self.path.join("cgroup.kill"), "1").is_err() {
+                self.signal_all(libc::SIGKILL);
+            }
+            self.wait_until_empty();
+        }
+        // Children first: a nested CSA may have created its own leaves.
+        let result = subtree_dirs(&self.path)
+            .iter()
+            .rev()
+            .try_for_each(std::fs::remove_dir);
+        match result {
+            Ok(()) => debug!(cgroup = %self.path_label, "direct cgroup removed"),
+            Err(error) => warn!(cgroup = %self.path_label, %error, "failed to remove cgroup"),
+        }
+    }
+}
+
+/// `dir` followed by all of its descendant directories, parents first.
+fn subtree_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![dir.to_path_buf()];
+    let mut next = 0;
+    while next < dirs.len() {
+        if let Ok(entries) = std::fs::read_dir(&dirs[next]) {
+            dirs.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                    .map(|entry| entry.path()),
+            );
+        }
+        next += 1;
+    }
+    dirs
+}
+
+fn parse_oom_kill_count(events: &str) -> Option<u64> {
+    events
+        .lines()
+        .find_map(|line| line.strip_prefix("oom_kill "))
+        .and_then(|count| count.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_own_cgroup_picks_unified_entry() {
+        let contents = "12:pids:/legacy\n0::/docker/abc\n";
+        assert_eq!(parse_own_cgroup(contents), Some("/docker/abc"));
+        assert_eq!(parse_own_cgroup("0::/\n"), Some("/"));
+        assert_eq!(parse_own_cgroup("5:memory:/x\n"), None);
+    }
+
+    #[test]
+    fn test_parse_oom_kill_count() {
+        let events = "low 0\nhigh 0\nmax 3\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(parse_oom_kill_count(events), Some(1));
+        assert_eq!(parse_oom_kill_count("low 0\n"), None);
+    }
+
+    #[test]
+    fn test_has_word_matches_whole_controller_names() {
+        assert!(has_word("cpuset cpu io memory pids", "cpu"));
+        assert!(!has_word("cpuset io memory", "cpu"));
+    }
+
+    #[test]
+    fn test_probe_is_read_only_and_prepare_moves_processes_into_leaf() {
+        let root = tempfile::tempdir().unwrap();
+        let write =
+            |file: &str, value: &str| std::fs::write(root.path().join(file), value).unwrap();
+        write("cgroup.controllers", "cpu io memory pids");
+        write("cgroup.subtree_control", "");
+        write("cgroup.procs", "4242\n");
+
+        assert!(probe_root(root.path()));
+        assert!(!root.path().join(SUPERVISOR_LEAF).exists());
+        assert_eq!(
+            std::fs::read_to_string(root.path().join("cgroup.subtree_control")).unwrap(),
+            ""
+        );
+
+        prepare_root(root.path()).unwrap();
+        let leaf_procs = root.path().join(SUPERVISOR_LEAF).join("cgroup.procs");
+        assert_eq!(std::fs::read_to_string(leaf_procs).unwrap(), "4242");
+    }
+
+    #[test]
+    fn test_probe_rejects_root_without_required_controllers() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("cgroup.controllers"), "cpu io").unwrap();
+        std::fs::write(root.path().join("cgroup.subtree_control"), "").unwrap();
+        assert!(!probe_root(root.path()));
+        assert!(prepare_root(root.path()).is_err());
+    }
+
+    #[test]
+    fn test_guard_applies_limits_and_cleans_up_in_fake_hierarchy() {
+        let root = tempfile::tempdir().unwrap();
+        let config = SandboxConfig {
+            memory_max_mb: 64,
+            memory_swap_max_mb: Some(0),
+            pids_max: Some(32),
+            cpu_max_percent: Some(150),
+            cpu_weight: None,
+            network: crate::network::NetworkMode::Full,
+        };
+        // A plain directory stands in for cgroupfs: files are created on write.
+        let guard = DirectCgroupGuard::create(root.path(), "codex", "01JTEST", &config).unwrap();
+        let dir = root.path().join("csa-codex-01JTEST");
+        assert_eq!(guard.scope_name(), dir.to_string_lossy());
+        let read = |file: &str| std::fs::read_to_string(dir.join(file)).unwrap();
+        assert_eq!(read("memory.max"), (64 * 1024 * 1024).to_string());
+        assert_eq!(read("pids.max"), "32");
+        assert_eq!(read("cpu.max"), "150000 100000");
+        assert!(!dir.join("memory.swap.max").exists());
+
+        std::fs::write(dir.join("memory.events"), "oom_kill 2\n").unwrap();
+        assert!(guard.check_oom_killed_with_signal(None));
+        assert!(
+            guard
+                .oom_diagnosis_with_signal(None)
+                .unwrap()
+                .contains("OOM-killed")
+        );
+
+        // Real cgroupfs removes control files with the directory.
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            std::fs::remove_file(entry.unwrap().path()).unwrap();
+        }
+        drop(guard);
+        assert!(!dir.exists());
+    }
+}
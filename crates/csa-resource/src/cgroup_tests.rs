@@ -8,6 +8,9 @@ fn test_guard(tool_name: &str, session_id: &str) -> CgroupScopeGuard {
             memory_max_mb: 1024,
             memory_swap_max_mb: Some(256),
             pids_max: Some(32),
+            cpu_max_percent: None,
+            cpu_weight: None,
+            network: NetworkMode::Full,
         },
     )
 }
@@ -38,6 +41,9 @@ fn test_create_scope_command_full() {
         memory_max_mb: 4096,
         memory_swap_max_mb: Some(0),
         pids_max: Some(512),
+        cpu_max_percent: Some(150),
+        cpu_weight: Some(50),
+        network: NetworkMode::Full,
     };
     let cmd = create_scope_command("codex", "01JTEST", &cfg);
     let args: Vec<_> = cmd
@@ -52,6 +58,9 @@ fn test_create_scope_command_full() {
     assert!(args.contains(&"MemoryMax=4096M".to_string()));
     assert!(args.contains(&"MemorySwapMax=0M".to_string()));
     assert!(args.contains(&"TasksMax=512".to_string()));
+    assert!(args.contains(&"CPUQuota=150%".to_string()));
+    assert!(!args.contains(&"PrivateNetwork=yes".to_string()));
+    assert!(args.contains(&"CPUWeight=50".to_string()));
     assert!(args.contains(&"--".to_string()));
 }
 
@@ -61,6 +70,9 @@ fn test_create_scope_command_minimal() {
         memory_max_mb: 1024,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: NetworkMode::Full,
     };
     let cmd = create_scope_command("gemini-cli", "01JXY", &cfg);
     let args: Vec<_> = cmd
@@ -71,6 +83,7 @@ fn test_create_scope_command_minimal() {
     assert!(args.contains(&"MemoryMax=1024M".to_string()));
     assert!(!args.iter().any(|a| a.contains("MemorySwapMax")));
     assert!(!args.iter().any(|a| a.contains("TasksMax")));
+    assert!(!args.iter().any(|a| a.starts_with("CPU")));
 }
 
 #[test]
@@ -79,6 +92,9 @@ fn test_create_scope_command_separator_at_end() {
         memory_max_mb: 512,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: NetworkMode::Full,
     };
     let cmd = create_scope_command("t", "s", &cfg);
     let args: Vec<_> = cmd
@@ -89,12 +105,70 @@ fn test_create_scope_command_separator_at_end() {
     assert_eq!(args.last().unwrap(), "--");
 }
 
+#[test]
+fn test_create_scope_command_private_network() {
+    let cfg = SandboxConfig {
+        memory_max_mb: 512,
+        memory_swap_max_mb: None,
+        pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: NetworkMode::None,
+    };
+    let cmd = create_scope_command("codex", "01JNET", &cfg);
+    let args: Vec<_> = cmd
+        .get_args()
+        .map(|a| a.to_string_lossy().to_string())
+        .collect();
+
+    // Transient scopes reject PrivateNetwork=; the command runs under unshare.
+    assert!(!args.iter().any(|arg| arg.starts_with("PrivateNetwork")));
+    let separator = args.iter().position(|arg| arg == "--").unwrap();
+    assert_eq!(
+        &args[separator + 1..],
+        crate::network::unshare_net_command()
+    );
+}
+
+#[test]
+fn test_scope_with_private_network_sees_only_loopback() {
+    if !crate::sandbox::has_systemd_user_scope() {
+        eprintln!("systemd user scopes unavailable; skipping");
+        return;
+    }
+    let cfg = SandboxConfig {
+        memory_max_mb: 512,
+        memory_swap_max_mb: None,
+        pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: NetworkMode::None,
+    };
+    let output = create_scope_command("csa-test", &format!("net-{}", std::process::id()), &cfg)
+        .args(["cat", "/proc/self/net/dev"])
+        .output()
+        .expect("run systemd-run");
+
+    assert!(
+        output.status.success(),
+        "scope command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        crate::network::interface_names(&String::from_utf8_lossy(&output.stdout)),
+        ["lo"]
+    );
+}
+
 #[test]
 fn test_create_scope_command_with_env_keeps_secrets_off_command_line() {
     let cfg = SandboxConfig {
         memory_max_mb: 512,
         memory_swap_max_mb: None,
         pids_max: None,
+        cpu_max_percent: None,
+        cpu_weight: None,
+        network: NetworkMode::Full,
     };
     let env = HashMap::from([
         ("CSA_SUPPRESS_NOTIFY".to_string(), "1".to_string()),
@@ -194,6 +268,9 @@ fn test_oom_diagnosis_zero_swap_includes_hint() {
             memory_max_mb: 1024,
             memory_swap_max_mb: Some(0),
             pids_max: Some(32),
+            cpu_max_percent: None,
+            cpu_weight: None,
+            network: NetworkMode::Full,
         },
     );
     let diagnosis = guard
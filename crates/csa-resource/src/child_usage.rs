@@ -0,0 +1,77 @@
+//! CPU time and I/O of one sandboxed tool, read from its cgroup.
+//!
+//! The counters belong to the tool's own cgroup scope, so runs that share a
+//! CSA process (parallel reviewers, plan steps, hooks) never see each
+//! other's usage. Runs without a cgroup scope report no usage.
+
+use std::path::Path;
+use std::time::Duration;
+
+/// Cumulative resource counters of a cgroup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChildUsage {
+    pub user_time: Duration,
+    pub system_time: Duration,
+    /// Bytes read from block devices; `None` without the io controller.
+    pub read_bytes: Option<u64>,
+    /// Bytes written to block devices; `None` without the io controller.
+    pub write_bytes: Option<u64>,
+}
+
+impl ChildUsage {
+    /// Read `cpu.stat` and `io.stat` of the cgroup at `dir`. `None` when
+    /// `cpu.stat` is unreadable, e.g. after the cgroup was removed.
+    pub fn from_cgroup_dir(dir: &Path) -> Option<Self> {
+        let cpu_stat = std::fs::read_to_string(dir.join("cpu.stat")).ok()?;
+        let io_stat = std::fs::read_to_string(dir.join("io.stat")).ok();
+        Self::parse(&cpu_stat, io_stat.as_deref())
+    }
+
+    /// Parse cgroup v2 `cpu.stat` (`user_usec`, `system_usec`) and `io.stat`
+    /// (`rbytes=` / `wbytes=` summed over devices).
+    pub fn parse(cpu_stat: &str, io_stat: Option<&str>) -> Option<Self> {
+        let cpu_field = |name: &str| {
+            cpu_stat.lines().find_map(|line| {
+                let (key, value) = line.split_once(' ')?;
+                (key == name).then(|| value.trim().parse::<u64>().ok())?
+            })
+        };
+        let user_usec = cpu_field("user_usec")?;
+        let system_usec = cpu_field("system_usec")?;
+        let io_field = |name: &str| {
+            io_stat.map(|stat| {
+                stat.split_whitespace()
+                    .filter_map(|field| field.strip_prefix(name)?.parse::<u64>().ok())
+                    .sum()
+            })
+        };
+        Some(Self {
+            user_time: Duration::from_micros(user_usec),
+            system_time: Duration::from_micros(system_usec),
+            read_bytes: io_field("rbytes="),
+            write_bytes: io_field("wbytes="),
+        })
+    }
+
+    /// Usage of two scopes together, e.g. an agent and its respawn.
+    pub fn combined(&self, other: &Self) -> Self {
+        let add = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0).saturating_add(b.unwrap_or(0))),
+        };
+        Self {
+            user_time: self.user_time + other.user_time,
+            system_time: self.system_time + other.system_time,
+            read_bytes: add(self.read_bytes, other.read_bytes),
+            write_bytes: add(self.write_bytes, other.write_bytes),
+        }
+    }
+
+    pub fn cpu_seconds(&self) -> f64 {
+        (self.user_time + self.system_time).as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+#[path = "child_usage_tests.rs"]
+mod tests;
@@ -0,0 +1,40 @@
+use super::*;
+
+const CPU_STAT: &str = "usage_usec 5000000\nuser_usec 4000000\nsystem_usec 1000000\n\
+                        nr_periods 0\nnr_throttled 0\nthrottled_usec 0\n";
+const IO_STAT: &str = "8:0 rbytes=4096 wbytes=8192 rios=1 wios=2 dbytes=0 dios=0\n\
+                       259:0 rbytes=1024 wbytes=0 rios=1 wios=0 dbytes=0 dios=0\n";
+
+#[test]
+fn parse_reads_cpu_times_and_sums_io_over_devices() {
+    let usage = ChildUsage::parse(CPU_STAT, Some(IO_STAT)).expect("usage");
+    assert_eq!(usage.cpu_seconds(), 5.0);
+    assert_eq!(usage.read_bytes, Some(5120));
+    assert_eq!(usage.write_bytes, Some(8192));
+
+    let without_io = ChildUsage::parse(CPU_STAT, None).expect("usage");
+    assert_eq!(without_io.read_bytes, None);
+    assert_eq!(ChildUsage::parse("usage_usec 1\n", None), None);
+}
+
+#[test]
+fn from_cgroup_dir_reads_the_stat_files() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    assert_eq!(ChildUsage::from_cgroup_dir(dir.path()), None);
+    std::fs::write(dir.path().join("cpu.stat"), CPU_STAT).expect("write cpu.stat");
+
+    let usage = ChildUsage::from_cgroup_dir(dir.path()).expect("usage");
+    assert_eq!(usage.user_time, Duration::from_secs(4));
+    assert_eq!(usage.write_bytes, None);
+}
+
+#[test]
+fn combined_adds_both_scopes() {
+    let agent = ChildUsage::parse(CPU_STAT, Some(IO_STAT)).expect("usage");
+    let respawn = ChildUsage::parse(CPU_STAT, None).expect("usage");
+
+    let total = agent.combined(&respawn);
+    assert_eq!(total.cpu_seconds(), 10.0);
+    assert_eq!(total.read_bytes, Some(5120));
+    assert_eq!(respawn.combined(&respawn).write_bytes, None);
+}
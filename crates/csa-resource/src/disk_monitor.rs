@@ -0,0 +1,290 @@
+//! Background disk budget monitor for sandboxed runs.
+//!
+//! Snapshots the apparent size of the tracked directories (project root and
+//! the session-private `TMPDIR`) at start, then polls at a fixed interval.
+//! When the bytes written since the snapshot exceed the budget, the process
+//! group receives SIGTERM, escalating to SIGKILL after a grace period if the
+//! usage has not dropped back under the limit.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+pub const DISK_BUDGET_KILL_HINT: &str = "disk_budget_exceeded";
+
+/// Evidence that the disk monitor terminated a run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiskBudgetExceeded {
+    pub kill_hint: String,
+    /// Bytes written since the run started, in MB.
+    pub used_mb: u64,
+    pub disk_max_mb: u64,
+    pub paths: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for DiskBudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let paths: Vec<String> = self
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        write!(
+            f,
+            "disk budget exceeded: {} MB written (limit {} MB) under {}; process killed",
+            self.used_mb,
+            self.disk_max_mb,
+            paths.join(", ")
+        )
+    }
+}
+
+/// Configuration for the disk budget monitor.
+#[derive(Debug, Clone)]
+pub struct DiskMonitorConfig {
+    /// Directories whose growth counts against the budget.
+    pub paths: Vec<PathBuf>,
+    /// Process group ID to signal.
+    pub pgid: i32,
+    /// Allowed growth in bytes across all tracked paths.
+    pub disk_max_bytes: u64,
+    /// Polling interval.
+    pub interval: Duration,
+    /// Grace period between SIGTERM and SIGKILL.
+    pub grace_period: Duration,
+}
+
+/// Handle to a running disk monitor. Drop or call [`stop`](Self::stop) to cancel.
+pub struct DiskMonitorHandle {
+    cancel_tx: watch::Sender<bool>,
+    join: Option<tokio::task::JoinHandle<Option<DiskBudgetExceeded>>>,
+}
+
+impl DiskMonitorHandle {
+    /// Stop the monitor and return the breach it acted on, if any.
+    pub async fn stop(mut self) -> Option<DiskBudgetExceeded> {
+        let _ = self.cancel_tx.send(true);
+        match self.join.take() {
+            Some(join) => join.await.ok().flatten(),
+            None => None,
+        }
+    }
+}
+
+impl Drop for DiskMonitorHandle {
+    fn drop(&mut self) {
+        let _ = self.cancel_tx.send(true);
+    }
+}
+
+/// Start the background disk monitor.
+///
+/// Returns `None` when the budget is 0 or there is nothing to track.
+pub fn start(mut config: DiskMonitorConfig) -> Option<DiskMonitorHandle> {
+    config.paths = dedup_nested_paths(std::mem::take(&mut config.paths));
+    if config.disk_max_bytes == 0 || config.paths.is_empty() {
+        return None;
+    }
+    if config.interval.is_zero() {
+        config.interval = Duration::from_secs(1);
+    }
+
+    info!(
+        limit_mb = bytes_to_mb(config.disk_max_bytes),
+        paths = ?config.paths,
+        interval_s = config.interval.as_secs(),
+        "disk monitor started"
+    );
+
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    let join = tokio::spawn(monitor_loop(config, cancel_rx));
+    Some(DiskMonitorHandle {
+        cancel_tx,
+        join: Some(join),
+    })
+}
+
+async fn monitor_loop(
+    config: DiskMonitorConfig,
+    mut cancel_rx: watch::Receiver<bool>,
+) -> Option<DiskBudgetExceeded> {
+    let baseline = measure(&config.paths).await;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(config.interval) => {}
+            result = cancel_rx.changed() => {
+                if result.is_err() || *cancel_rx.borrow() {
+                    debug!("disk monitor cancelled");
+                    return None;
+                }
+            }
+        }
+
+        let growth = measure(&config.paths).await.saturating_sub(baseline);
+        if growth <= config.disk_max_bytes {
+            continue;
+        }
+
+        let breach = DiskBudgetExceeded {
+            kill_hint: DISK_BUDGET_KILL_HINT.to_string(),
+            used_mb: bytes_to_mb(growth),
+            disk_max_mb: bytes_to_mb(config.disk_max_bytes),
+            paths: config.paths.clone(),
+        };
+        warn!(
+            used_mb = breach.used_mb,
+            limit_mb = breach.disk_max_mb,
+            "disk budget exceeded, sending SIGTERM to process group"
+        );
+        send_signal(config.pgid, libc::SIGTERM);
+
+        tokio::select! {
+            _ = tokio::time::sleep(config.grace_period) => {}
+            result = cancel_rx.changed() => {
+                if result.is_err() || *cancel_rx.borrow() {
+                    return Some(breach);
+                }
+            }
+        }
+
+        if measure(&config.paths).await.saturating_sub(baseline) > config.disk_max_bytes {
+            warn!("still over disk budget after grace period, sending SIGKILL");
+            send_signal(config.pgid, libc::SIGKILL);
+        }
+        return Some(breach);
+    }
+}
+
+async fn measure(paths: &[PathBuf]) -> u64 {
+    let paths = paths.to_vec();
+    tokio::task::spawn_blocking(move || paths.iter().map(|path| dir_usage_bytes(path)).sum())
+        .await
+        .unwrap_or(0)
+}
+
+/// Total apparent size in bytes of regular files under `root`.
+///
+/// Symlinks are not followed and unreadable entries are skipped, so the walk
+/// never escapes the tracked tree or fails midway.
+pub fn dir_usage_bytes(root: &Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+    }
+    total
+}
+
+/// Drop paths nested under another tracked path so bytes are counted once.
+fn dedup_nested_paths(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    paths.sort();
+    paths.dedup();
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        if !kept.iter().any(|parent| path.starts_with(parent)) {
+            kept.push(path);
+        }
+    }
+    kept
+}
+
+fn send_signal(pgid: i32, signal: i32) {
+    // SAFETY: libc::kill with a negative pid signals the whole process group.
+    let result = unsafe { libc::kill(-pgid.abs(), signal) };
+    if result != 0 {
+        let errno = std::io::Error::last_os_error();
+        debug!(pgid, signal, %errno, "kill() returned error (process may already be gone)");
+    }
+}
+
+fn bytes_to_mb(bytes: u64) -> u64 {
+    bytes / 1024 / 1024
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir_usage_bytes_counts_nested_files_and_skips_symlinks() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join("nested")).unwrap();
+        std::fs::write(temp.path().join("a.bin"), vec![0u8; 1000]).unwrap();
+        std::fs::write(temp.path().join("nested/b.bin"), vec![0u8; 24]).unwrap();
+        std::os::unix::fs::symlink(temp.path().join("a.bin"), temp.path().join("link")).unwrap();
+
+        assert_eq!(dir_usage_bytes(temp.path()), 1024);
+        assert_eq!(dir_usage_bytes(&temp.path().join("missing")), 0);
+    }
+
+    #[test]
+    fn test_dedup_nested_paths_keeps_outermost() {
+        let paths = dedup_nested_paths(vec![
+            PathBuf::from("/work/project/tmp"),
+            PathBuf::from("/work/project"),
+            PathBuf::from("/state/session/tmp"),
+            PathBuf::from("/work/project"),
+        ]);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/state/session/tmp"),
+                PathBuf::from("/work/project")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_start_returns_none_without_budget() {
+        let config = DiskMonitorConfig {
+            paths: vec![PathBuf::from("/tmp")],
+            pgid: 1,
+            disk_max_bytes: 0,
+            interval: Duration::from_secs(1),
+            grace_period: Duration::from_secs(1),
+        };
+        assert!(start(config).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_monitor_reports_breach_after_growth() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let handle = start(DiskMonitorConfig {
+            paths: vec![temp.path().to_path_buf()],
+            pgid: child.id().unwrap() as i32,
+            disk_max_bytes: 1024 * 1024,
+            interval: Duration::from_millis(50),
+            grace_period: Duration::from_millis(50),
+        })
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        std::fs::write(temp.path().join("big.bin"), vec![0u8; 3 * 1024 * 1024]).unwrap();
+
+        let status = child.wait().await.unwrap();
+        let breach = handle.stop().await.expect("breach recorded");
+        assert!(!status.success());
+        assert_eq!(breach.used_mb, 3);
+        assert_eq!(breach.disk_max_mb, 1);
+        assert!(breach.to_string().starts_with("disk budget exceeded"));
+    }
+}
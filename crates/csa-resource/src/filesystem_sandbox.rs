@@ -1,7 +1,8 @@
 //! Filesystem sandbox capability detection.
 //!
 //! Probes the host environment to determine which filesystem isolation
-//! mechanism is available: bubblewrap (`bwrap`), Landlock LSM, or nothing.
+//! mechanism is available: bubblewrap (`bwrap`), Landlock LSM, macOS
+//! Seatbelt (`sandbox-exec`), or nothing.
 //! The result is cached for the lifetime of the process via `OnceLock`.
 
 use std::path::Path;
@@ -15,6 +16,8 @@ pub enum FilesystemCapability {
     Bwrap,
     /// Linux Landlock LSM — kernel-level filesystem access control.
     Landlock,
+    /// macOS Seatbelt via `sandbox-exec` with a generated profile.
+    SandboxExec,
     /// No usable filesystem isolation mechanism detected.
     None,
 }
@@ -24,6 +27,7 @@ impl std::fmt::Display for FilesystemCapability {
         match self {
             Self::Bwrap => write!(f, "Bwrap"),
             Self::Landlock => write!(f, "Landlock"),
+            Self::SandboxExec => write!(f, "SandboxExec"),
             Self::None => write!(f, "None"),
         }
     }
@@ -39,6 +43,14 @@ pub fn detect_filesystem_capability() -> FilesystemCapability {
 
 /// Perform the actual detection (called at most once).
 fn probe_capability() -> FilesystemCapability {
+    if cfg!(target_os = "macos") {
+        return if has_sandbox_exec() {
+            FilesystemCapability::SandboxExec
+        } else {
+            FilesystemCapability::None
+        };
+    }
+
     if has_bwrap() && has_usable_user_namespaces() {
         return FilesystemCapability::Bwrap;
     }
@@ -61,6 +73,14 @@ fn has_bwrap() -> bool {
         .is_ok_and(|s| s.success())
 }
 
+/// Check whether the macOS Seatbelt launcher is installed.
+///
+/// Apple marks `sandbox-exec` deprecated but still ships it (and relies on the
+/// same Seatbelt profiles internally), so its presence is the capability.
+fn has_sandbox_exec() -> bool {
+    Path::new(crate::seatbelt::SANDBOX_EXEC_PATH).exists()
+}
+
 /// Check whether unprivileged user namespaces are functional.
 ///
 /// Two checks are performed:
@@ -89,12 +109,13 @@ fn is_apparmor_userns_restricted() -> bool {
         .unwrap_or(false)
 }
 
-/// Check whether Landlock LSM is available on this kernel.
+/// Check whether Landlock LSM is available on this kernel (Linux 5.13+).
 ///
-/// Looks for the Landlock sysfs entry, which is present when the kernel
-/// was compiled with `CONFIG_SECURITY_LANDLOCK=y` and the LSM is active.
+/// Queries the ABI version via syscall rather than the securityfs entry so
+/// hosts without systemd, where securityfs is commonly unmounted, still get
+/// Landlock confinement.
 fn has_landlock() -> bool {
-    Path::new("/sys/kernel/security/landlock").exists()
+    crate::landlock::kernel_abi_version().is_some()
 }
 
 #[cfg(test)]
@@ -145,6 +166,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sandbox_exec_only_selected_on_macos() {
+        let result = probe_capability();
+        if cfg!(target_os = "macos") {
+            assert_eq!(
+                result == FilesystemCapability::SandboxExec,
+                has_sandbox_exec()
+            );
+        } else {
+            assert_ne!(result, FilesystemCapability::SandboxExec);
+        }
+    }
+
     #[test]
     fn test_capability_caching() {
         let first = detect_filesystem_capability();
@@ -156,6 +190,7 @@ mod tests {
     fn test_display_variants() {
         assert_eq!(FilesystemCapability::Bwrap.to_string(), "Bwrap");
         assert_eq!(FilesystemCapability::Landlock.to_string(), "Landlock");
+        assert_eq!(FilesystemCapability::SandboxExec.to_string(), "SandboxExec");
         assert_eq!(FilesystemCapability::None.to_string(), "None");
     }
 
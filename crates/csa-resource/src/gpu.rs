@@ -0,0 +1,297 @@
+//! GPU memory (VRAM) readings for tools that run local models.
+//!
+//! Devices are probed through the vendor CLIs, `nvidia-smi` first and then
+//! `rocm-smi`. Both are optional: on a host with neither, no snapshot is
+//! available and VRAM admission is skipped with a warning rather than
+//! refusing a run that may well fall back to the CPU.
+//!
+//! Peak VRAM of a run is the summed per-process usage of CSA's descendants
+//! where the driver reports it (NVIDIA), otherwise the growth of device-wide
+//! usage over the value seen at spawn, which also counts unrelated GPU users.
+
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// How often [`VramPeakMonitor`] samples usage.
+pub const VRAM_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+}
+
+/// Memory of one GPU, in MB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuDevice {
+    pub index: u32,
+    pub total_mb: u64,
+    pub used_mb: u64,
+}
+
+impl GpuDevice {
+    pub fn free_mb(&self) -> u64 {
+        self.total_mb.saturating_sub(self.used_mb)
+    }
+}
+
+/// Every GPU of the first vendor CLI that answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuSnapshot {
+    pub vendor: GpuVendor,
+    pub devices: Vec<GpuDevice>,
+}
+
+impl GpuSnapshot {
+    /// Query `nvidia-smi`, then `rocm-smi`. `None` when neither lists a device.
+    pub fn sample() -> Option<Self> {
+        if let Some(output) = run_probe(
+            "nvidia-smi",
+            &[
+                "--query-gpu=index,memory.total,memory.used",
+                "--format=csv,noheader,nounits",
+            ],
+        ) {
+            let devices = parse_nvidia_smi_memory(&output);
+            if !devices.is_empty() {
+                return Some(Self {
+                    vendor: GpuVendor::Nvidia,
+                    devices,
+                });
+            }
+        }
+        let output = run_probe("rocm-smi", &["--showmeminfo", "vram", "--csv"])?;
+        let devices = parse_rocm_smi_memory(&output);
+        (!devices.is_empty()).then_some(Self {
+            vendor: GpuVendor::Amd,
+            devices,
+        })
+    }
+
+    /// A run is placed on a single device, so the best device is what counts.
+    pub fn max_free_mb(&self) -> u64 {
+        self.devices
+            .iter()
+            .map(GpuDevice::free_mb)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn used_mb(&self) -> u64 {
+        self.devices.iter().map(|device| device.used_mb).sum()
+    }
+}
+
+fn run_probe(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Parse `nvidia-smi --query-gpu=index,memory.total,memory.used
+/// --format=csv,noheader,nounits` (values in MiB).
+pub fn parse_nvidia_smi_memory(output: &str) -> Vec<GpuDevice> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',').map(str::trim);
+            Some(GpuDevice {
+                index: fields.next()?.parse().ok()?,
+                total_mb: fields.next()?.parse().ok()?,
+                used_mb: fields.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Parse `rocm-smi --showmeminfo vram --csv` (values in bytes), locating the
+/// columns by header because their order differs between releases.
+pub fn parse_rocm_smi_memory(output: &str) -> Vec<GpuDevice> {
+    let mut lines = output.lines().filter(|line| line.contains(','));
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let column = |needle: &str| columns.iter().position(|name| name.contains(needle));
+    let (Some(total_col), Some(used_col)) = (column("Total Memory"), column("Used Memory")) else {
+        return Vec::new();
+    };
+    lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let index = fields.first()?.trim_start_matches("card").parse().ok()?;
+            let bytes = |col: usize| fields.get(col)?.parse::<u64>().ok();
+            Some(GpuDevice {
+                index,
+                total_mb: bytes(total_col)? / 1024 / 1024,
+                used_mb: bytes(used_col)? / 1024 / 1024,
+            })
+        })
+        .collect()
+}
+
+/// Parse `nvidia-smi --query-compute-apps=pid,used_memory
+/// --format=csv,noheader,nounits` into `(pid, used_mb)` pairs.
+pub fn parse_nvidia_compute_apps(output: &str) -> Vec<(u32, u64)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, used) = line.split_once(',')?;
+            Some((pid.trim().parse().ok()?, used.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Refuse a run that declares `required_mb` of VRAM when no device has that
+/// much free. Without a snapshot the requirement cannot be checked, so the
+/// run is admitted with a warning.
+pub fn check_vram_availability(
+    tool_name: &str,
+    required_mb: u64,
+    snapshot: Option<&GpuSnapshot>,
+) -> Result<()> {
+    let Some(snapshot) = snapshot else {
+        warn!(
+            tool = tool_name,
+            required_mb,
+            "gpu_memory_mb is set but no GPU was found via nvidia-smi or rocm-smi; \
+             skipping VRAM admission"
+        );
+        return Ok(());
+    };
+    let free_mb = snapshot.max_free_mb();
+    if free_mb < required_mb {
+        bail!(
+            "CSA: GPU memory admission denied — free_vram={free_mb}MB < \
+             required={required_mb}MB for '{tool_name}' (vendor={vendor:?}, devices={devices}). \
+             Wait for other GPU workloads to finish or lower tools.{tool_name}.gpu_memory_mb \
+             in .csa/config.toml.",
+            vendor = snapshot.vendor,
+            devices = snapshot.devices.len(),
+        );
+    }
+    info!(
+        tool = tool_name,
+        required_mb,
+        available_mb = free_mb,
+        "GPU memory admission granted"
+    );
+    Ok(())
+}
+
+/// Samples VRAM attributable to the process tree rooted at `root_pid`.
+#[derive(Debug, Clone, Copy)]
+struct VramSampler {
+    vendor: GpuVendor,
+    root_pid: u32,
+    baseline_used_mb: u64,
+}
+
+impl VramSampler {
+    fn sample_mb(&self) -> Option<u64> {
+        if self.vendor == GpuVendor::Nvidia
+            && let Some(output) = run_probe(
+                "nvidia-smi",
+                &[
+                    "--query-compute-apps=pid,used_memory",
+                    "--format=csv,noheader,nounits",
+                ],
+            )
+        {
+            return Some(
+                parse_nvidia_compute_apps(&output)
+                    .into_iter()
+                    .filter(|&(pid, _)| is_descendant_of(pid, self.root_pid))
+                    .map(|(_, used_mb)| used_mb)
+                    .sum(),
+            );
+        }
+        let snapshot = GpuSnapshot::sample()?;
+        Some(snapshot.used_mb().saturating_sub(self.baseline_used_mb))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_descendant_of(pid: u32, ancestor: u32) -> bool {
+    let mut current = pid;
+    // Bounded walk: a ppid cycle cannot happen, but a racing reparent can
+    // make the chain briefly inconsistent.
+    for _ in 0..64 {
+        if current == ancestor {
+            return true;
+        }
+        let Ok(stat) = std::fs::read_to_string(format!("/proc/{current}/stat")) else {
+            return false;
+        };
+        let Some(ppid) = stat
+            .rsplit_once(") ")
+            .and_then(|(_, rest)| rest.split_whitespace().nth(1))
+            .and_then(|field| field.parse::<u32>().ok())
+        else {
+            return false;
+        };
+        if ppid <= 1 {
+            return false;
+        }
+        current = ppid;
+    }
+    false
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_descendant_of(_pid: u32, _ancestor: u32) -> bool {
+    false
+}
+
+/// Background task tracking the peak VRAM of a run.
+pub struct VramPeakMonitor {
+    cancel_tx: watch::Sender<bool>,
+    join: tokio::task::JoinHandle<Option<u64>>,
+}
+
+impl VramPeakMonitor {
+    /// Start sampling for the tree rooted at `root_pid`. `None` when the host
+    /// has no GPU the vendor CLIs can see.
+    pub fn start(root_pid: u32) -> Option<Self> {
+        let snapshot = GpuSnapshot::sample()?;
+        let sampler = VramSampler {
+            vendor: snapshot.vendor,
+            root_pid,
+            baseline_used_mb: snapshot.used_mb(),
+        };
+        let (cancel_tx, mut cancel_rx) = watch::channel(false);
+        let join = tokio::spawn(async move {
+            let mut peak: Option<u64> = None;
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(VRAM_POLL_INTERVAL) => {}
+                    _ = cancel_rx.changed() => return peak,
+                }
+                let sample = tokio::task::spawn_blocking(move || sampler.sample_mb())
+                    .await
+                    .ok()
+                    .flatten();
+                if let Some(used_mb) = sample {
+                    peak = Some(peak.map_or(used_mb, |peak| peak.max(used_mb)));
+                }
+            }
+        });
+        Some(Self { cancel_tx, join })
+    }
+
+    /// Stop sampling and return the highest usage seen, in MB.
+    pub async fn stop(self) -> Option<u64> {
+        let _ = self.cancel_tx.send(true);
+        self.join.await.ok().flatten()
+    }
+}
+
+#[cfg(test)]
+#[path = "gpu_tests.rs"]
+mod tests;
@@ -0,0 +1,97 @@
+use super::*;
+
+fn snapshot(devices: &[(u64, u64)]) -> GpuSnapshot {
+    GpuSnapshot {
+        vendor: GpuVendor::Nvidia,
+        devices: devices
+            .iter()
+            .enumerate()
+            .map(|(index, &(total_mb, used_mb))| GpuDevice {
+                index: index as u32,
+                total_mb,
+                used_mb,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn parse_nvidia_smi_memory_reads_each_device() {
+    let devices = parse_nvidia_smi_memory("0, 24564, 1024\n1, 81920, 70000\nnot a gpu\n");
+    assert_eq!(
+        devices,
+        [
+            GpuDevice {
+                index: 0,
+                total_mb: 24_564,
+                used_mb: 1_024,
+            },
+            GpuDevice {
+                index: 1,
+                total_mb: 81_920,
+                used_mb: 70_000,
+            },
+        ]
+    );
+    assert!(parse_nvidia_smi_memory("").is_empty());
+}
+
+#[test]
+fn parse_rocm_smi_memory_locates_columns_by_header() {
+    let output = "\
+device,VRAM Total Used Memory (B),VRAM Total Memory (B)
+card0,1073741824,17163091968
+card1,0,34359738368
+";
+    let devices = parse_rocm_smi_memory(output);
+    assert_eq!(devices.len(), 2);
+    assert_eq!(devices[0].index, 0);
+    assert_eq!(devices[0].total_mb, 16_368);
+    assert_eq!(devices[0].used_mb, 1_024);
+    assert_eq!(devices[1].free_mb(), 32_768);
+
+    assert!(parse_rocm_smi_memory("WARNING: no devices\n").is_empty());
+}
+
+#[test]
+fn parse_nvidia_compute_apps_pairs_pid_and_usage() {
+    assert_eq!(
+        parse_nvidia_compute_apps("4242, 6144\n4343, 512\n[N/A], 1\n"),
+        [(4242, 6_144), (4343, 512)]
+    );
+}
+
+#[test]
+fn vram_admission_uses_the_best_single_device() {
+    let gpus = snapshot(&[(24_000, 20_000), (24_000, 8_000)]);
+    assert_eq!(gpus.max_free_mb(), 16_000);
+    assert_eq!(gpus.used_mb(), 28_000);
+
+    assert!(check_vram_availability("local", 12_000, Some(&gpus)).is_ok());
+    let err = check_vram_availability("local", 18_000, Some(&gpus)).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("free_vram=16000MB < required=18000MB")
+    );
+}
+
+#[test]
+fn vram_admission_without_gpu_info_is_skipped() {
+    assert!(check_vram_availability("local", 8_000, None).is_ok());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn descendants_are_found_through_the_parent_chain() {
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .expect("spawn child");
+    let is_child = is_descendant_of(child.id(), std::process::id());
+    let parent_is_child = is_descendant_of(std::process::id(), child.id());
+    child.kill().ok();
+    child.wait().ok();
+
+    assert!(is_child);
+    assert!(!parent_is_child);
+}
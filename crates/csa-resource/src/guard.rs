@@ -2,7 +2,7 @@ use std::path::Path;
 
 use anyhow::Result;
 use sysinfo::System;
-use tracing::warn;
+use tracing::{info, warn};
 
 /// Configuration for resource limits (mirrors csa-config's ResourcesConfig
 /// but duplicated here to avoid circular dependency).
@@ -36,6 +36,9 @@ pub struct SpawnMemoryAdmission {
     pub active_session_count: u64,
     /// Number of active sessions whose process tree RSS was sampled successfully.
     pub sampled_session_count: u64,
+    /// Time-decayed P95 of past peaks for this tool/model/task kind, when known.
+    /// Reported alongside the decision; it does not change `projected_spawn_mb`.
+    pub historical_p95_mb: Option<u64>,
 }
 
 /// Upper-bound inputs for a retry after host-memory admission denial.
@@ -189,6 +192,16 @@ impl ResourceGuard {
         )
     }
 
+    /// Check that a GPU has `required_mb` of free VRAM for a tool that
+    /// declares `gpu_memory_mb`.
+    pub fn check_gpu_memory(&self, tool_name: &str, required_mb: u64) -> Result<()> {
+        crate::gpu::check_vram_availability(
+            tool_name,
+            required_mb,
+            crate::gpu::GpuSnapshot::sample().as_ref(),
+        )
+    }
+
     /// Warn if configured cgroup limits exceed a percentage of total system RAM.
     ///
     /// Emits a `tracing::warn!` if `memory_max_mb + memory_swap_max_mb` exceeds
@@ -370,6 +383,10 @@ fn evaluate_memory_availability(
     if let Some((admission, retry_bounds, retry_note)) = admission_retry
         && admission.projected_spawn_mb > 0
     {
+        let historical_p95 = admission
+            .historical_p95_mb
+            .map(|p95| p95.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
         let required_available_mb = reserve_mb.saturating_add(admission.projected_spawn_mb);
         if available_phys_mb < required_available_mb {
             let message = format!(
@@ -377,8 +394,9 @@ fn evaluate_memory_availability(
                  required={required_available_mb}MB (reserve={reserve_mb}MB + \
                  projected_spawn={projected_spawn_mb}MB). active_sessions={active_sessions} \
                  sampled_sessions={sampled_sessions} active_session_rss_mb={active_rss} \
-                 active_session_projected_mb={active_projected} swap_available_mb={available_swap_mb} \
-                 combined_available_mb={available_combined_mb}. Pre-exec memory admission is \
+                 active_session_projected_mb={active_projected} historical_p95_mb={historical_p95} \
+                 swap_available_mb={available_swap_mb} combined_available_mb={available_combined_mb}. \
+                 Pre-exec memory admission is \
                  infrastructure/session-unavailable before provider launch, not a \
                  product/test/review failure. {retry_note}",
                 projected_spawn_mb = admission.projected_spawn_mb,
@@ -427,7 +445,8 @@ fn evaluate_memory_availability(
                  > host_safe_limit={host_safe_limit_mb}MB ({safe_num}/{safe_den} of total_ram={total_ram_mb}MB). \
                  active_sessions={active_sessions} sampled_sessions={sampled_sessions} \
                  active_session_rss_mb={active_rss} active_session_projected_mb={active_projected} \
-                 projected_spawn_mb={projected_spawn_mb} available_mb={available_phys_mb}. \
+                 projected_spawn_mb={projected_spawn_mb} historical_p95_mb={historical_p95} \
+                 available_mb={available_phys_mb}. \
                  Pre-exec memory admission is infrastructure/session-unavailable before provider \
                  launch, not a product/test/review failure. {retry_note}",
                 safe_num = ACTIVE_SESSION_SAFE_FRACTION_NUM,
@@ -481,6 +500,18 @@ fn evaluate_memory_availability(
                 "Active CSA session memory is near host admission limit"
             );
         }
+
+        info!(
+            tool = tool_name,
+            available_mb = available_phys_mb,
+            reserve_mb,
+            projected_spawn_mb = admission.projected_spawn_mb,
+            historical_p95_mb = admission.historical_p95_mb,
+            projected_active_mb,
+            host_safe_limit_mb,
+            active_sessions = admission.active_session_count,
+            "Host memory admission granted"
+        );
     }
 
     Ok(())
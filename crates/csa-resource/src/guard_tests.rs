@@ -175,6 +175,7 @@ fn test_evaluate_blocks_when_spawn_projection_exceeds_available_headroom() {
         active_session_projected_mb: 4096,
         active_session_count: 1,
         sampled_session_count: 1,
+        historical_p95_mb: None,
     };
 
     let result =
@@ -191,6 +192,7 @@ fn test_evaluate_blocks_when_spawn_projection_exceeds_available_headroom() {
     let msg = err.to_string();
     assert!(msg.contains("host memory admission denied"));
     assert!(msg.contains("projected_spawn=8192MB"));
+    assert!(msg.contains("historical_p95_mb=unknown"));
     assert!(msg.contains("infrastructure/session-unavailable"));
     assert!(msg.contains("Host admission uses physical MemAvailable only"));
     assert!(msg.contains("swap and combined memory are reported for diagnostics"));
@@ -208,6 +210,7 @@ fn test_evaluate_blocks_when_active_projection_exceeds_host_safe_limit() {
         active_session_projected_mb: 20_000,
         active_session_count: 3,
         sampled_session_count: 2,
+        historical_p95_mb: Some(6_500),
     };
 
     let result =
@@ -224,6 +227,7 @@ fn test_evaluate_blocks_when_active_projection_exceeds_host_safe_limit() {
     let msg = err.to_string();
     assert!(msg.contains("active-session memory admission denied"));
     assert!(msg.contains("projected_active=28192MB"));
+    assert!(msg.contains("historical_p95_mb=6500"));
     assert!(msg.contains("Retry upper bound: memory_max_mb <= 4000MB"));
     assert!(msg.contains("--memory-max-mb <MB>"));
     assert!(msg.contains("resources.memory_max_mb"));
@@ -237,6 +241,7 @@ fn test_evaluate_allows_safe_spawn_projection() {
         active_session_projected_mb: 4096,
         active_session_count: 1,
         sampled_session_count: 1,
+        historical_p95_mb: None,
     };
 
     let result = evaluate_memory_availability(
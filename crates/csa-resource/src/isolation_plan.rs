@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::filesystem_sandbox::FilesystemCapability;
+use crate::network::NetworkMode;
 use crate::sandbox::ResourceCapability;
 
 pub const DEFAULT_SANDBOX_TMPDIR: &str = "/tmp";
@@ -16,11 +17,13 @@ mod codex_paths;
 mod runtime_path;
 #[path = "isolation_plan_rust_env.rs"]
 mod rust_env;
+#[path = "isolation_plan_tool_defaults.rs"]
+mod tool_defaults;
 #[path = "isolation_plan_validation.rs"]
 mod validation;
+use runtime_path::is_sensitive_system_path;
 #[cfg(test)]
-use runtime_path::is_xdg_runtime_child_path;
-use runtime_path::{detect_superproject_root, home_dir, is_sensitive_system_path};
+use runtime_path::{detect_superproject_root, is_xdg_runtime_child_path};
 pub use validation::{
     canonicalize_through_existing_ancestors, resolve_writable_paths, validate_readable_paths,
     validate_writable_paths,
@@ -50,7 +53,7 @@ pub enum EnforcementMode {
 pub struct IsolationPlan {
     /// Resource-level capability (cgroup / setrlimit / none).
     pub resource: ResourceCapability,
-    /// Filesystem-level capability (bwrap / landlock / none).
+    /// Filesystem-level capability (bwrap / landlock / sandbox-exec / none).
     pub filesystem: FilesystemCapability,
     /// Paths the sandboxed process is allowed to write to.
     pub writable_paths: Vec<PathBuf>,
@@ -66,6 +69,16 @@ pub struct IsolationPlan {
     pub memory_swap_max_mb: Option<u64>,
     /// Maximum number of PIDs for cgroup `TasksMax` or `RLIMIT_NPROC`.
     pub pids_max: Option<u32>,
+    /// CPU time cap (percent of one core) for cgroup `CPUQuota`, approximated
+    /// by a lower scheduling priority under `Setrlimit`.
+    pub cpu_max_percent: Option<u32>,
+    /// Relative CPU share for cgroup `CPUWeight`.
+    pub cpu_weight: Option<u32>,
+    /// Disk budget in MB: how much the run may grow the project root and its
+    /// private `TMPDIR` before the disk monitor terminates it.
+    pub disk_max_mb: Option<u64>,
+    /// Network access for the tool; `None` confines it to loopback.
+    pub network: NetworkMode,
     /// When true, the project root is mounted read-only instead of read-write.
     pub readonly_project_root: bool,
     /// Project root path, used by bwrap to decide bind mount mode.
@@ -92,6 +105,23 @@ impl IsolationPlan {
     pub fn add_writable_dir_or_creatable_parent(&mut self, dir: &Path) -> bool {
         add_dir_or_creatable_parent(&mut self.writable_paths, dir)
     }
+
+    /// Directories whose growth counts against [`disk_max_mb`](Self::disk_max_mb):
+    /// the writable project root and a host-visible `TMPDIR`.
+    pub fn disk_budget_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if let Some(root) = &self.project_root
+            && !self.readonly_project_root
+        {
+            paths.push(root.clone());
+        }
+        if let Some(tmpdir) = self.env_overrides.get("TMPDIR")
+            && tmpdir != DEFAULT_SANDBOX_TMPDIR
+        {
+            paths.push(PathBuf::from(tmpdir));
+        }
+        paths
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -123,8 +153,13 @@ pub struct IsolationPlanBuilder {
     memory_max_mb: Option<u64>,
     memory_swap_max_mb: Option<u64>,
     pids_max: Option<u32>,
+    cpu_max_percent: Option<u32>,
+    cpu_weight: Option<u32>,
+    disk_max_mb: Option<u64>,
+    network: NetworkMode,
     readonly_project_root: bool,
     project_root: Option<PathBuf>,
+    session_tmpdir: Option<PathBuf>,
     soft_limit_percent: Option<u8>,
     memory_monitor_interval_seconds: Option<u64>,
     user_daemon_ipc: bool,
@@ -146,8 +181,13 @@ impl IsolationPlanBuilder {
             memory_max_mb: None,
             memory_swap_max_mb: None,
             pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: NetworkMode::Full,
             readonly_project_root: false,
             project_root: None,
+            session_tmpdir: None,
             soft_limit_percent: None,
             memory_monitor_interval_seconds: None,
             user_daemon_ipc: false,
@@ -202,6 +242,33 @@ impl IsolationPlanBuilder {
         self
     }
 
+    /// Set CPU limits (`CPUQuota` percent and `CPUWeight`).
+    pub fn with_cpu_limits(
+        mut self,
+        cpu_max_percent: Option<u32>,
+        cpu_weight: Option<u32>,
+    ) -> Self {
+        self.cpu_max_percent = cpu_max_percent;
+        self.cpu_weight = cpu_weight;
+        self
+    }
+
+    /// Set the disk budget in MB enforced by the disk monitor.
+    ///
+    /// Under bwrap this also moves `TMPDIR` from the private tmpfs `/tmp` to
+    /// the session-owned `tmp/` directory so temporary files are measured.
+    pub fn with_disk_limit(mut self, disk_max_mb: Option<u64>) -> Self {
+        self.disk_max_mb = disk_max_mb;
+        self
+    }
+
+    /// Set network access (`unshare --net`, `--unshare-net` or a pre-exec
+    /// network namespace, depending on the spawn path).
+    pub fn with_network(mut self, network: NetworkMode) -> Self {
+        self.network = network;
+        self
+    }
+
     /// Mount the project root as read-only instead of read-write.
     ///
     /// When enabled, the bwrap builder uses `--ro-bind` for the project root.
@@ -276,164 +343,6 @@ impl IsolationPlanBuilder {
         self
     }
 
-    fn apply_tool_defaults(
-        &mut self,
-        tool_name: &str,
-        project_root: &Path,
-        session_dir: &Path,
-        tool_state_dirs: Option<&HashMap<String, PathBuf>>,
-    ) {
-        self.project_root = Some(project_root.to_path_buf());
-        self.writable_paths.push(project_root.to_path_buf());
-        self.writable_paths.push(session_dir.to_path_buf());
-        let sandbox_tmpdir =
-            runtime_path::sandbox_tmpdir_for_capability(self.filesystem, session_dir);
-        self.env_overrides.insert(
-            "TMPDIR".to_string(),
-            sandbox_tmpdir.to_string_lossy().into_owned(),
-        );
-        if !matches!(self.filesystem, FilesystemCapability::Bwrap) {
-            add_dir_or_creatable_parent(&mut self.writable_paths, &sandbox_tmpdir);
-        }
-
-        // Submodule detection: if .git is a file (not a directory), the project
-        // root is inside a git submodule.  Walk up to find the superproject root
-        // (the nearest ancestor with a .git *directory*) and make the entire
-        // superproject writable so the agent can access .git/modules/ and other
-        // submodules.
-        if let Some(superproject) = detect_superproject_root(project_root) {
-            self.writable_paths.push(superproject);
-        }
-
-        if let Some(home) = home_dir() {
-            // Common writable paths needed by all tools:
-            // - XDG_STATE_HOME (~/.local/state): cargo compilation writes proc-macro
-            //   artifacts here; without write access tools get "Read-only file system
-            //   (os error 30)" on Rust compilation.
-            let xdg_state = std::env::var("XDG_STATE_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home.join(".local/state"));
-            // Only add paths that exist on the filesystem; bwrap --bind fails on
-            // nonexistent paths.
-            if xdg_state.exists() {
-                self.writable_paths.push(xdg_state);
-            }
-
-            // mise cache: tools launched via mise shims (rustc, cargo, node) write
-            // to ~/.cache/mise during startup and compilation. Without write access,
-            // mise-managed toolchains fail with "Read-only file system".
-            let mise_cache = home.join(".cache/mise");
-            if mise_cache.exists() {
-                self.writable_paths.push(mise_cache);
-            }
-
-            // Cargo home directory: cargo needs write access to registry/, git/,
-            // and .package-cache (lock file).
-            //
-            // When CARGO_HOME is explicitly set to a non-default location, we
-            // ONLY expose that directory — not ~/.cargo — to avoid leaking
-            // credentials/config from the real cargo home.  For cold starts
-            // where the directory doesn't exist yet, we add it anyway so bwrap
-            // can create it (the parent must exist).
-            let default_cargo_home = home.join(".cargo");
-            if let Ok(cargo_home_env) = std::env::var(csa_core::env::CARGO_HOME_ENV_KEY) {
-                let cargo_home =
-                    rust_env::resolve_rust_state_path(&cargo_home_env, &default_cargo_home);
-                if cargo_home == default_cargo_home {
-                    // CARGO_HOME points to the default — treat as if unset.
-                    add_dir_or_creatable_parent(&mut self.writable_paths, &default_cargo_home);
-                    // Override the env var when the original pointed at a
-                    // read-only system prefix (e.g. /usr/local) so the child
-                    // process uses the writable default instead (#2607).
-                    rust_env::insert_env_override_if_needed(
-                        &mut self.env_overrides,
-                        csa_core::env::CARGO_HOME_ENV_KEY,
-                        &cargo_home_env,
-                        &default_cargo_home,
-                    );
-                } else {
-                    // CARGO_HOME points elsewhere — only expose that directory.
-                    // Do NOT add ~/.cargo (may contain credentials/config).
-                    add_dir_or_creatable_parent(&mut self.writable_paths, &cargo_home);
-                }
-            } else {
-                add_dir_or_creatable_parent(&mut self.writable_paths, &default_cargo_home);
-            }
-
-            // RUSTUP_HOME: rustup needs write access for toolchain management
-            // (downloading components, updating toolchains). Same pattern as
-            // CARGO_HOME: when explicitly set elsewhere, don't expose ~/.rustup.
-            let default_rustup = home.join(".rustup");
-            if let Ok(rustup_home) = std::env::var(csa_core::env::RUSTUP_HOME_ENV_KEY) {
-                let rustup_path = rust_env::resolve_rust_state_path(&rustup_home, &default_rustup);
-                if rustup_path == default_rustup {
-                    add_dir_or_creatable_parent(&mut self.writable_paths, &default_rustup);
-                    // Same env override as CARGO_HOME (#2607).
-                    rust_env::insert_env_override_if_needed(
-                        &mut self.env_overrides,
-                        csa_core::env::RUSTUP_HOME_ENV_KEY,
-                        &rustup_home,
-                        &default_rustup,
-                    );
-                } else {
-                    // RUSTUP_HOME points elsewhere — only expose that directory.
-                    add_dir_or_creatable_parent(&mut self.writable_paths, &rustup_path);
-                }
-            } else {
-                add_dir_or_creatable_parent(&mut self.writable_paths, &default_rustup);
-            }
-
-            // RUSTUP_TOOLCHAIN: ambient value is inherited by the child
-            // process normally. Explicit tool configuration via extra_env
-            // overrides ambient values through build_merged_env(). We do not
-            // add RUSTUP_TOOLCHAIN to env_overrides here because bwrap
-            // --setenv is applied after the merged execution_env and would
-            // override configured values (#2661).
-
-            // Do not make mise-managed toolchain install dirs writable; only the
-            // registry/git cache subdirs above need write access.
-
-            // Expose existing CODEX_HOME for every sandboxed parent so nested
-            // Codex CSA children inherit a writable source path.
-            codex_paths::add_codex_home_for_tool(
-                tool_name,
-                &home,
-                tool_state_dirs,
-                &mut self.writable_paths,
-                &mut self.required_writable_dirs,
-            );
-
-            // Expose the claude home (~/.claude or $CLAUDE_CONFIG_DIR) writable
-            // for every sandboxed parent so a nested claude-code CSA child can
-            // create ~/.claude/session-env/<id> instead of hitting EROFS under a
-            // read-only HOME. Mirrors the codex helper above; see
-            // isolation_plan_claude.rs for the #1683 root cause and the ~/.codex
-            // symmetry justification.
-            claude_paths::add_claude_home_for_tool(
-                tool_name,
-                &home,
-                tool_state_dirs,
-                &mut self.writable_paths,
-                &mut self.required_writable_dirs,
-            );
-
-            match tool_name {
-                "gemini-cli" => [".gemini", ".config/gemini-cli"]
-                    .into_iter()
-                    .map(|rel| home.join(rel))
-                    .filter(|path| path.exists())
-                    .for_each(|path| self.writable_paths.push(path)),
-                "opencode" => {
-                    let p = home.join(".config/opencode");
-                    if p.exists() {
-                        self.writable_paths.push(p);
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
     /// Consume the builder and produce an [`IsolationPlan`].
     ///
     /// # Errors
@@ -491,6 +400,15 @@ impl IsolationPlanBuilder {
             );
         }
 
+        if self.disk_max_mb.is_some()
+            && self.filesystem == FilesystemCapability::Bwrap
+            && let Some(tmpdir) = self.session_tmpdir.take()
+        {
+            self.env_overrides
+                .insert("TMPDIR".to_string(), tmpdir.to_string_lossy().into_owned());
+            add_dir_or_creatable_parent(&mut self.writable_paths, &tmpdir);
+        }
+
         self.add_runtime_daemon_socket_readable_paths();
 
         codex_paths::validate_required_writable_dirs(
@@ -509,6 +427,10 @@ impl IsolationPlanBuilder {
             memory_max_mb: self.memory_max_mb,
             memory_swap_max_mb: self.memory_swap_max_mb,
             pids_max: self.pids_max,
+            cpu_max_percent: self.cpu_max_percent,
+            cpu_weight: self.cpu_weight,
+            disk_max_mb: self.disk_max_mb,
+            network: self.network,
             readonly_project_root: self.readonly_project_root,
             project_root: self.project_root,
             soft_limit_percent: self.soft_limit_percent,
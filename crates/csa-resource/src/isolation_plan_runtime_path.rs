@@ -17,7 +17,9 @@ pub(super) fn sandbox_tmpdir_for_capability(
 ) -> PathBuf {
     match filesystem {
         FilesystemCapability::Bwrap => PathBuf::from(DEFAULT_SANDBOX_TMPDIR),
-        FilesystemCapability::Landlock | FilesystemCapability::None => session_dir.join("tmp"),
+        FilesystemCapability::Landlock
+        | FilesystemCapability::SandboxExec
+        | FilesystemCapability::None => session_dir.join("tmp"),
     }
 }
 
@@ -45,6 +45,7 @@ fn non_bwrap_tool_defaults_create_writable_session_tmpdir() {
 
     for (filesystem, label) in [
         (FilesystemCapability::Landlock, "landlock"),
+        (FilesystemCapability::SandboxExec, "sandbox-exec"),
         (FilesystemCapability::None, "none"),
     ] {
         let session = temp.path().join(format!("session-{label}"));
@@ -73,3 +74,42 @@ fn non_bwrap_tool_defaults_create_writable_session_tmpdir() {
         std::fs::write(session_tmp.join("probe"), "ok").expect("TMPDIR should be writable");
     }
 }
+
+#[test]
+fn bwrap_disk_budget_moves_tmpdir_to_tracked_session_dir() {
+    let _guard = ENV_LOCK.lock().unwrap();
+    let temp = tempfile::tempdir().expect("tempdir");
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    let session = temp.path().join("session");
+    for dir in [&home, &project, &session] {
+        std::fs::create_dir_all(dir).expect("create dir");
+    }
+    let _home = ScopedEnvVar::set("HOME", &home);
+    let session_tmp = session.join("tmp");
+
+    let unbudgeted = IsolationPlanBuilder::new(EnforcementMode::BestEffort)
+        .with_filesystem_capability(FilesystemCapability::Bwrap)
+        .with_tool_defaults("codex", &project, &session)
+        .build()
+        .expect("should build isolation plan");
+    assert_eq!(
+        unbudgeted.env_overrides.get("TMPDIR").map(String::as_str),
+        Some(DEFAULT_SANDBOX_TMPDIR)
+    );
+    assert_eq!(unbudgeted.disk_budget_paths(), vec![project.clone()]);
+
+    let budgeted = IsolationPlanBuilder::new(EnforcementMode::BestEffort)
+        .with_filesystem_capability(FilesystemCapability::Bwrap)
+        .with_disk_limit(Some(512))
+        .with_tool_defaults("codex", &project, &session)
+        .build()
+        .expect("should build isolation plan");
+    assert_eq!(budgeted.disk_max_mb, Some(512));
+    assert_eq!(
+        budgeted.env_overrides.get("TMPDIR"),
+        Some(&session_tmp.to_string_lossy().into_owned())
+    );
+    assert!(budgeted.writable_paths.contains(&session_tmp));
+    assert_eq!(budgeted.disk_budget_paths(), vec![project, session_tmp]);
+}
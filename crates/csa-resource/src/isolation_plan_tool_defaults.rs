@@ -0,0 +1,180 @@
+//! Per-tool default writable paths and env overrides for the isolation plan.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::runtime_path::{self, detect_superproject_root, home_dir};
+use super::{
+    IsolationPlanBuilder, add_dir_or_creatable_parent, claude_paths, codex_paths, rust_env,
+};
+use crate::filesystem_sandbox::FilesystemCapability;
+
+impl IsolationPlanBuilder {
+    pub(super) fn apply_tool_defaults(
+        &mut self,
+        tool_name: &str,
+        project_root: &Path,
+        session_dir: &Path,
+        tool_state_dirs: Option<&HashMap<String, PathBuf>>,
+    ) {
+        self.project_root = Some(project_root.to_path_buf());
+        self.writable_paths.push(project_root.to_path_buf());
+        self.writable_paths.push(session_dir.to_path_buf());
+        self.session_tmpdir = Some(session_dir.join("tmp"));
+        let sandbox_tmpdir =
+            runtime_path::sandbox_tmpdir_for_capability(self.filesystem, session_dir);
+        self.env_overrides.insert(
+            "TMPDIR".to_string(),
+            sandbox_tmpdir.to_string_lossy().into_owned(),
+        );
+        if !matches!(self.filesystem, FilesystemCapability::Bwrap) {
+            add_dir_or_creatable_parent(&mut self.writable_paths, &sandbox_tmpdir);
+        }
+
+        // Submodule detection: if .git is a file (not a directory), the project
+        // root is inside a git submodule.  Walk up to find the superproject root
+        // (the nearest ancestor with a .git *directory*) and make the entire
+        // superproject writable so the agent can access .git/modules/ and other
+        // submodules.
+        if let Some(superproject) = detect_superproject_root(project_root) {
+            self.writable_paths.push(superproject);
+        }
+
+        if let Some(home) = home_dir() {
+            // Common writable paths needed by all tools:
+            // - XDG_STATE_HOME (~/.local/state): cargo compilation writes proc-macro
+            //   artifacts here; without write access tools get "Read-only file system
+            //   (os error 30)" on Rust compilation.
+            let xdg_state = std::env::var("XDG_STATE_HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| home.join(".local/state"));
+            // Only add paths that exist on the filesystem; bwrap --bind fails on
+            // nonexistent paths.
+            if xdg_state.exists() {
+                self.writable_paths.push(xdg_state);
+            }
+            // A relocated CSA state dir (`CSA_STATE_DIR`) must stay writable for
+            // nested csa calls, just like its default under XDG_STATE_HOME.
+            if let Some(csa_state) = std::env::var_os("CSA_STATE_DIR")
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .filter(|dir| dir.is_absolute() && dir.exists())
+            {
+                self.writable_paths.push(csa_state);
+            }
+
+            // mise cache: tools launched via mise shims (rustc, cargo, node) write
+            // to ~/.cache/mise during startup and compilation. Without write access,
+            // mise-managed toolchains fail with "Read-only file system".
+            let mise_cache = home.join(".cache/mise");
+            if mise_cache.exists() {
+                self.writable_paths.push(mise_cache);
+            }
+
+            // Cargo home directory: cargo needs write access to registry/, git/,
+            // and .package-cache (lock file).
+            //
+            // When CARGO_HOME is explicitly set to a non-default location, we
+            // ONLY expose that directory — not ~/.cargo — to avoid leaking
+            // credentials/config from the real cargo home.  For cold starts
+            // where the directory doesn't exist yet, we add it anyway so bwrap
+            // can create it (the parent must exist).
+            let default_cargo_home = home.join(".cargo");
+            if let Ok(cargo_home_env) = std::env::var(csa_core::env::CARGO_HOME_ENV_KEY) {
+                let cargo_home =
+                    rust_env::resolve_rust_state_path(&cargo_home_env, &default_cargo_home);
+                if cargo_home == default_cargo_home {
+                    // CARGO_HOME points to the default — treat as if unset.
+                    add_dir_or_creatable_parent(&mut self.writable_paths, &default_cargo_home);
+                    // Override the env var when the original pointed at a
+                    // read-only system prefix (e.g. /usr/local) so the child
+                    // process uses the writable default instead (#2607).
+                    rust_env::insert_env_override_if_needed(
+                        &mut self.env_overrides,
+                        csa_core::env::CARGO_HOME_ENV_KEY,
+                        &cargo_home_env,
+                        &default_cargo_home,
+                    );
+                } else {
+                    // CARGO_HOME points elsewhere — only expose that directory.
+                    // Do NOT add ~/.cargo (may contain credentials/config).
+                    add_dir_or_creatable_parent(&mut self.writable_paths, &cargo_home);
+                }
+            } else {
+                add_dir_or_creatable_parent(&mut self.writable_paths, &default_cargo_home);
+            }
+
+            // RUSTUP_HOME: rustup needs write access for toolchain management
+            // (downloading components, updating toolchains). Same pattern as
+            // CARGO_HOME: when explicitly set elsewhere, don't expose ~/.rustup.
+            let default_rustup = home.join(".rustup");
+            if let Ok(rustup_home) = std::env::var(csa_core::env::RUSTUP_HOME_ENV_KEY) {
+                let rustup_path = rust_env::resolve_rust_state_path(&rustup_home, &default_rustup);
+                if rustup_path == default_rustup {
+                    add_dir_or_creatable_parent(&mut self.writable_paths, &default_rustup);
+                    // Same env override as CARGO_HOME (#2607).
+                    rust_env::insert_env_override_if_needed(
+                        &mut self.env_overrides,
+                        csa_core::env::RUSTUP_HOME_ENV_KEY,
+                        &rustup_home,
+                        &default_rustup,
+                    );
+                } else {
+                    // RUSTUP_HOME points elsewhere — only expose that directory.
+                    add_dir_or_creatable_parent(&mut self.writable_paths, &rustup_path);
+                }
+            } else {
+                add_dir_or_creatable_parent(&mut self.writable_paths, &default_rustup);
+            }
+
+            // RUSTUP_TOOLCHAIN: ambient value is inherited by the child
+            // process normally. Explicit tool configuration via extra_env
+            // overrides ambient values through build_merged_env(). We do not
+            // add RUSTUP_TOOLCHAIN to env_overrides here because bwrap
+            // --setenv is applied after the merged execution_env and would
+            // override configured values (#2661).
+
+            // Do not make mise-managed toolchain install dirs writable; only the
+            // registry/git cache subdirs above need write access.
+
+            // Expose existing CODEX_HOME for every sandboxed parent so nested
+            // Codex CSA children inherit a writable source path.
+            codex_paths::add_codex_home_for_tool(
+                tool_name,
+                &home,
+                tool_state_dirs,
+                &mut self.writable_paths,
+                &mut self.required_writable_dirs,
+            );
+
+            // Expose the claude home (~/.claude or $CLAUDE_CONFIG_DIR) writable
+            // for every sandboxed parent so a nested claude-code CSA child can
+            // create ~/.claude/session-env/<id> instead of hitting EROFS under a
+            // read-only HOME. Mirrors the codex helper above; see
+            // isolation_plan_claude.rs for the #1683 root cause and the ~/.codex
+            // symmetry justification.
+            claude_paths::add_claude_home_for_tool(
+                tool_name,
+                &home,
+                tool_state_dirs,
+                &mut self.writable_paths,
+                &mut self.required_writable_dirs,
+            );
+
+            match tool_name {
+                "gemini-cli" => [".gemini", ".config/gemini-cli"]
+                    .into_iter()
+                    .map(|rel| home.join(rel))
+                    .filter(|path| path.exists())
+                    .for_each(|path| self.writable_paths.push(path)),
+                "opencode" => {
+                    let p = home.join(".config/opencode");
+                    if p.exists() {
+                        self.writable_paths.push(p);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -85,6 +85,33 @@ pub fn detect_abi() -> ABI {
     ABI::Unsupported
 }
 
+/// Ask the kernel for its Landlock ABI version.
+///
+/// Uses `landlock_create_ruleset(NULL, 0, LANDLOCK_CREATE_RULESET_VERSION)`
+/// instead of the securityfs entry, which is often not mounted on hosts
+/// without systemd (containers, minimal inits).  Returns `None` on kernels
+/// older than 5.13 or when Landlock is disabled at boot.
+#[cfg(target_os = "linux")]
+pub fn kernel_abi_version() -> Option<u32> {
+    const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1;
+    // SAFETY: with the VERSION flag the kernel ignores the (null) attr and
+    // size arguments and only returns the ABI number or -1 with errno.
+    let version = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    u32::try_from(version).ok().filter(|version| *version >= 1)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn kernel_abi_version() -> Option<u32> {
+    None
+}
+
 // ---------------------------------------------------------------------------
 // Internal helpers
 // ---------------------------------------------------------------------------
@@ -144,15 +171,11 @@ fn report_status(status: &RestrictionStatus) {
 
 /// Non-destructive ABI version probe.
 ///
-/// Checks the sysfs entry first, then uses `landlock_create_ruleset`
+/// Checks the kernel-reported version first, then uses `landlock_create_ruleset`
 /// version probing through the crate's `Ruleset` builder.
 #[cfg(target_os = "linux")]
 fn probe_abi_version() -> ABI {
-    use std::path::Path;
-
-    // Quick sysfs check — if the directory is missing, Landlock is not
-    // compiled into the kernel at all.
-    if !Path::new("/sys/kernel/security/landlock").exists() {
+    if kernel_abi_version().is_none() {
         return ABI::Unsupported;
     }
 
@@ -218,17 +241,17 @@ mod tests {
 
         // On any Linux host this should return a concrete value (possibly
         // Unsupported on old kernels / non-Linux CI).
-        if std::path::Path::new("/sys/kernel/security/landlock").exists() {
+        if let Some(version) = kernel_abi_version() {
             assert!(
                 abi != ABI::Unsupported,
-                "sysfs entry exists but ABI reported as Unsupported"
+                "kernel reports Landlock ABI v{version} but detect_abi() is Unsupported"
             );
             debug!("detected Landlock ABI: {abi:?}");
         } else {
             assert_eq!(
                 abi,
                 ABI::Unsupported,
-                "no sysfs entry but ABI reported as supported"
+                "kernel lacks Landlock but ABI reported as supported"
             );
         }
     }
@@ -16,6 +16,10 @@ pub fn detect_abi() -> ABI {
     ABI::Unsupported
 }
 
+pub fn kernel_abi_version() -> Option<u32> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,6 +27,7 @@ mod tests {
     #[test]
     fn detect_abi_reports_unsupported() {
         assert_eq!(detect_abi(), ABI::Unsupported);
+        assert_eq!(kernel_abi_version(), None);
     }
 
     #[test]
@@ -2,7 +2,11 @@
 
 pub mod bwrap;
 pub mod cgroup;
+pub mod cgroup_direct;
+pub mod child_usage;
+pub mod disk_monitor;
 pub mod filesystem_sandbox;
+pub mod gpu;
 pub mod guard;
 pub mod isolation_plan;
 #[cfg(target_os = "linux")]
@@ -13,8 +17,13 @@ pub mod landlock;
 pub mod memory_balloon;
 pub mod memory_monitor;
 pub mod memory_policy;
+pub mod network;
+pub mod pressure;
+pub mod process_memory;
 pub mod rlimit;
 pub mod sandbox;
+pub mod seatbelt;
+pub mod usage_stats;
 
 pub use bwrap::{BwrapCommandBuilder, from_isolation_plan};
 pub use cgroup::{
@@ -28,5 +37,10 @@ pub use guard::{
 };
 pub use isolation_plan::{EnforcementMode, IsolationPlan, IsolationPlanBuilder};
 pub use landlock::apply_landlock_rules;
+pub use network::NetworkMode;
 pub use rlimit::apply_rlimits;
-pub use sandbox::{ResourceCapability, detect_resource_capability, has_systemd_user_scope};
+pub use sandbox::{
+    ResourceCapability, SandboxCapability, detect_resource_capability, detect_sandbox_capability,
+    has_systemd_user_scope,
+};
+pub use usage_stats::{MemoryEstimate, UsageKey, UsageStats};
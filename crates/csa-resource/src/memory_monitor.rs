@@ -2,12 +2,17 @@
 //!
 //! Polls `MemoryCurrent` at a configurable interval and sends SIGTERM to the
 //! process group when usage exceeds `soft_limit_percent` of `MemoryMax`.
-//! After a grace period, escalates to SIGKILL.
+//! After a grace period, escalates to SIGKILL.  On macOS, which has no
+//! cgroups, usage is the process group's RSS sampled via `proc_pidinfo`.
+//!
+//! When a [`PreKillCheckpoint`] is configured, the process group is stopped
+//! while it runs so partial results can be saved from a quiescent child,
+//! then terminated gracefully.
 
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::sync::{LazyLock, Mutex};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
@@ -15,8 +20,13 @@ use tokio::sync::watch;
 use tracing::{debug, info, warn};
 
 pub const MEMORY_SOFT_LIMIT_KILL_HINT: &str = "memory_soft_limit";
+/// How a soft-limit stop is reported once the checkpoint saved partial results.
+pub const OOM_PARTIAL_RESULTS_LABEL: &str = "out-of-memory (partial results saved)";
 pub const MEMORY_SOFT_LIMIT_KILL_FILE_NAME: &str = "memory-soft-limit-kill.toml";
 const MEMORY_SOFT_LIMIT_DIAGNOSTIC_DIR: &str = "memory-soft-limit";
+/// Upper bound on a pre-kill checkpoint so a slow disk cannot keep the child
+/// stopped indefinitely.
+const CHECKPOINT_TIMEOUT: Duration = Duration::from_secs(10);
 
 static SOFT_LIMIT_DIAGNOSTICS: LazyLock<Mutex<HashMap<PathBuf, RecordedSoftLimitDiagnostic>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
@@ -37,6 +47,9 @@ pub struct MemorySoftLimitKillDiagnostic {
     pub memory_max_mb: u64,
     pub soft_limit_percent: u8,
     pub scope_name: String,
+    /// Whether the pre-kill checkpoint saved partial results before SIGTERM.
+    #[serde(default)]
+    pub partial_results_saved: bool,
 }
 
 impl MemorySoftLimitKillDiagnostic {
@@ -49,14 +62,39 @@ impl MemorySoftLimitKillDiagnostic {
             memory_max_mb: bytes_to_mb(config.memory_max_bytes),
             soft_limit_percent: config.soft_limit_percent,
             scope_name: config.scope_name.clone(),
+            partial_results_saved: false,
         }
     }
 }
 
+/// Best-effort work run after the soft limit is crossed and before SIGTERM,
+/// such as persisting the output parsed so far.  Returns whether partial
+/// results were saved.
+#[derive(Clone)]
+pub struct PreKillCheckpoint(Arc<dyn Fn(&MemorySoftLimitKillDiagnostic) -> bool + Send + Sync>);
+
+impl PreKillCheckpoint {
+    pub fn new(
+        checkpoint: impl Fn(&MemorySoftLimitKillDiagnostic) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(checkpoint))
+    }
+}
+
+impl std::fmt::Debug for PreKillCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PreKillCheckpoint(..)")
+    }
+}
+
 /// Configuration for the memory soft-limit monitor.
 #[derive(Debug, Clone)]
 pub struct MemoryMonitorConfig {
     /// Scope name to monitor (e.g. `csa-claude-code-01J....scope`).
+    ///
+    /// For a directly managed cgroup this is its absolute directory and usage
+    /// is read from `memory.current`.  On macOS this is only a label; usage
+    /// is sampled from `pgid`.
     pub scope_name: String,
     /// Process group ID (negative PID) to signal.
     pub pgid: i32,
@@ -75,6 +113,8 @@ pub struct MemoryMonitorConfig {
     /// as an in-process CSA-owned registry key and never create, remove, or trust
     /// a file at this location.
     pub diagnostic_path: Option<PathBuf>,
+    /// Run before SIGTERM while the process group is stopped.
+    pub checkpoint: Option<PreKillCheckpoint>,
 }
 
 /// Return the CSA supervisor-owned diagnostic registry key for a session directory.
@@ -179,7 +219,7 @@ async fn monitor_loop(
             }
         }
 
-        let current = match query_memory_current(&config.scope_name).await {
+        let current = match query_memory_current(&config).await {
             Some(bytes) => bytes,
             None => {
                 // Scope might be gone — stop monitoring.
@@ -195,20 +235,32 @@ async fn monitor_loop(
             continue;
         }
 
-        // Soft limit exceeded — record concrete evidence before sending SIGTERM.
-        let diagnostic =
+        // Soft limit exceeded — checkpoint, then record concrete evidence
+        // before sending SIGTERM.
+        let mut diagnostic =
             MemorySoftLimitKillDiagnostic::from_config(&config, current, threshold_bytes);
+        if let Some(checkpoint) = config.checkpoint.clone() {
+            // Freeze the group so it neither grows nor rewrites its output
+            // while the checkpoint reads it; SIGTERM stays pending until
+            // SIGCONT below.
+            send_signal(config.pgid, libc::SIGSTOP);
+            diagnostic.partial_results_saved = run_checkpoint(checkpoint, &diagnostic).await;
+        }
         let current_mb = diagnostic.current_mb;
         let threshold_mb = diagnostic.threshold_mb;
         warn!(
             scope = %config.scope_name,
             current_mb,
             threshold_mb,
+            partial_results_saved = diagnostic.partial_results_saved,
             "memory soft limit exceeded, sending SIGTERM to process group"
         );
         record_soft_limit_diagnostic(config.diagnostic_path.as_deref(), &diagnostic);
 
         send_signal(config.pgid, libc::SIGTERM);
+        if config.checkpoint.is_some() {
+            send_signal(config.pgid, libc::SIGCONT);
+        }
 
         // Wait grace period, then check again.
         tokio::select! {
@@ -221,7 +273,7 @@ async fn monitor_loop(
         }
 
         // Re-check: if still over threshold, escalate to SIGKILL.
-        if let Some(still_current) = query_memory_current(&config.scope_name).await
+        if let Some(still_current) = query_memory_current(&config).await
             && still_current >= threshold_bytes
         {
             warn!(
@@ -235,6 +287,28 @@ async fn monitor_loop(
     }
 }
 
+async fn run_checkpoint(
+    checkpoint: PreKillCheckpoint,
+    diagnostic: &MemorySoftLimitKillDiagnostic,
+) -> bool {
+    let diagnostic = diagnostic.clone();
+    let task = tokio::task::spawn_blocking(move || (checkpoint.0)(&diagnostic));
+    match tokio::time::timeout(CHECKPOINT_TIMEOUT, task).await {
+        Ok(Ok(saved)) => saved,
+        Ok(Err(err)) => {
+            warn!(error = %err, "pre-kill checkpoint failed");
+            false
+        }
+        Err(_) => {
+            warn!(
+                timeout_s = CHECKPOINT_TIMEOUT.as_secs(),
+                "pre-kill checkpoint timed out, terminating without it"
+            );
+            false
+        }
+    }
+}
+
 /// Record CSA-owned in-process evidence that the memory monitor initiated a
 /// soft-limit SIGTERM for `path`.
 ///
@@ -347,8 +421,27 @@ fn record_soft_limit_diagnostic(path: Option<&Path>, diagnostic: &MemorySoftLimi
     record_soft_limit_diagnostic_evidence(path, diagnostic);
 }
 
-/// Query `MemoryCurrent` (in bytes) for the given systemd scope.
-async fn query_memory_current(scope_name: &str) -> Option<u64> {
+/// Sum the resident memory of the monitored process group (macOS).
+#[cfg(target_os = "macos")]
+async fn query_memory_current(config: &MemoryMonitorConfig) -> Option<u64> {
+    let pgid = config.pgid;
+    tokio::task::spawn_blocking(move || crate::process_memory::process_group_rss_bytes(pgid))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Query `MemoryCurrent` (in bytes) for the given systemd scope, or read
+/// `memory.current` of a directly managed cgroup directory.
+#[cfg(not(target_os = "macos"))]
+async fn query_memory_current(config: &MemoryMonitorConfig) -> Option<u64> {
+    let scope_name = config.scope_name.as_str();
+    if Path::new(scope_name).is_absolute() {
+        let current = tokio::fs::read_to_string(Path::new(scope_name).join("memory.current"))
+            .await
+            .ok()?;
+        return current.trim().parse().ok();
+    }
     let output = tokio::process::Command::new("systemctl")
         .args([
             "--user",
@@ -404,6 +497,7 @@ mod tests {
             memory_max_mb: 1000,
             soft_limit_percent: 70,
             scope_name: "csa-codex-01J.scope".to_string(),
+            partial_results_saved: false,
         }
     }
 
@@ -417,6 +511,7 @@ mod tests {
             interval: Duration::from_secs(5),
             grace_period: Duration::from_secs(5),
             diagnostic_path: None,
+            checkpoint: None,
         };
         assert!(start(config).is_none());
     }
@@ -431,6 +526,7 @@ mod tests {
             interval: Duration::from_secs(5),
             grace_period: Duration::from_secs(5),
             diagnostic_path: None,
+            checkpoint: None,
         };
         assert!(start(config).is_none());
     }
@@ -445,6 +541,7 @@ mod tests {
             interval: Duration::from_secs(5),
             grace_period: Duration::from_secs(5),
             diagnostic_path: None,
+            checkpoint: None,
         };
         assert!(start(config).is_none());
     }
@@ -459,6 +556,7 @@ mod tests {
             interval: Duration::from_secs(5),
             grace_period: Duration::from_secs(5),
             diagnostic_path: None,
+            checkpoint: None,
         };
 
         let diagnostic =
@@ -471,6 +569,31 @@ mod tests {
         assert_eq!(diagnostic.memory_max_mb, 10);
         assert_eq!(diagnostic.soft_limit_percent, 70);
         assert_eq!(diagnostic.scope_name, "csa-codex-01J.scope");
+        assert!(!diagnostic.partial_results_saved);
+    }
+
+    #[tokio::test]
+    async fn run_checkpoint_reports_whether_partial_results_were_saved() {
+        let seen = Arc::new(Mutex::new(None));
+        let seen_in_checkpoint = Arc::clone(&seen);
+        let checkpoint = PreKillCheckpoint::new(move |diagnostic| {
+            *seen_in_checkpoint.lock().unwrap() = Some(diagnostic.current_mb);
+            true
+        });
+        assert!(run_checkpoint(checkpoint, &test_soft_limit_diagnostic()).await);
+        assert_eq!(*seen.lock().unwrap(), Some(900));
+
+        let failing = PreKillCheckpoint::new(|_| panic!("disk full"));
+        assert!(!run_checkpoint(failing, &test_soft_limit_diagnostic()).await);
+    }
+
+    #[test]
+    fn soft_limit_diagnostic_without_checkpoint_field_deserializes() {
+        let legacy = "kill_hint = \"memory_soft_limit\"\nsignal = 15\ncurrent_mb = 900\n\
+                      threshold_mb = 700\nmemory_max_mb = 1000\nsoft_limit_percent = 70\n\
+                      scope_name = \"csa-codex-01J.scope\"\n";
+        let diagnostic: MemorySoftLimitKillDiagnostic = toml::from_str(legacy).expect("parse");
+        assert!(!diagnostic.partial_results_saved);
     }
 
     #[test]
@@ -485,6 +608,7 @@ mod tests {
             memory_max_mb: 1000,
             soft_limit_percent: 70,
             scope_name: "csa-codex-01J.scope".to_string(),
+            partial_results_saved: false,
         };
 
         record_soft_limit_diagnostic(Some(&path), &diagnostic);
@@ -534,6 +658,7 @@ mod tests {
             memory_max_mb: 1000,
             soft_limit_percent: 70,
             scope_name: "csa-codex-01J.scope".to_string(),
+            partial_results_saved: false,
         };
         std::fs::write(
             &path,
@@ -559,6 +684,7 @@ mod tests {
             memory_max_mb: 1000,
             soft_limit_percent: 70,
             scope_name: "csa-codex-01J.scope".to_string(),
+            partial_results_saved: false,
         };
         record_soft_limit_diagnostic_evidence(&path, &diagnostic);
 
@@ -606,6 +732,7 @@ mod tests {
                 interval: Duration::from_secs(5),
                 grace_period: Duration::from_secs(5),
                 diagnostic_path: Some(path.clone()),
+                checkpoint: None,
             })
             .is_none(),
             "zero memory limit should skip monitor setup"
@@ -634,6 +761,7 @@ mod tests {
             memory_max_mb: 1000,
             soft_limit_percent: 70,
             scope_name: "csa-codex-01J.scope".to_string(),
+            partial_results_saved: false,
         };
         record_soft_limit_diagnostic_evidence(&path, &diagnostic);
         let stale_contents = "kill_hint = \"memory_soft_limit\"\n";
@@ -647,6 +775,7 @@ mod tests {
             interval: Duration::from_secs(3600),
             grace_period: Duration::from_secs(5),
             diagnostic_path: Some(path.clone()),
+            checkpoint: None,
         })
         .expect("monitor should start");
 
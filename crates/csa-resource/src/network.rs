@@ -0,0 +1,176 @@
+//! Network isolation for sandboxed tool processes.
+//!
+//! `NetworkMode::None` gives the child an empty network namespace (loopback
+//! only), so review-only or untrusted runs cannot reach the network.  The
+//! mechanism depends on the spawn path:
+//!
+//! - cgroup scope: the scope runs the tool under [`unshare_net_command`], since
+//!   transient scopes reject `PrivateNetwork=`
+//! - bwrap: `--unshare-net`
+//! - otherwise: [`enter_private_network`] from `pre_exec`, the in-process
+//!   equivalent of `unshare -n` (via an unprivileged user namespace when the
+//!   caller lacks `CAP_SYS_ADMIN`).
+
+/// Network access granted to a sandboxed tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NetworkMode {
+    /// Host network, no restriction.
+    #[default]
+    Full,
+    /// Private network namespace with loopback only.
+    None,
+}
+
+impl NetworkMode {
+    pub fn is_isolated(self) -> bool {
+        self == Self::None
+    }
+}
+
+/// `unshare(1)` prefix that runs the command after it in a fresh network
+/// namespace, for spawn paths without a `pre_exec` hook into the tool, such
+/// as `systemd-run --scope`.
+///
+/// Root unshares the namespace directly; other users go through a user
+/// namespace that maps them onto themselves, as [`enter_private_network`]
+/// does.
+pub fn unshare_net_command() -> &'static [&'static str] {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    if unsafe { libc::geteuid() } == 0 {
+        &["unshare", "--net", "--"]
+    } else {
+        &["unshare", "--user", "--map-current-user", "--net", "--"]
+    }
+}
+
+/// Move the calling process into a fresh network namespace.
+///
+/// Tries a plain `unshare(CLONE_NEWNET)` first.  Without privileges it falls
+/// back to `CLONE_NEWUSER | CLONE_NEWNET` and maps the caller's uid/gid onto
+/// themselves, so file ownership checks behave as before.
+///
+/// Intended for `pre_exec`: it performs no heap allocation.
+#[cfg(target_os = "linux")]
+pub fn enter_private_network() -> std::io::Result<()> {
+    // SAFETY: unshare/getuid/getgid take no pointers; failure is reported via errno.
+    unsafe {
+        if libc::unshare(libc::CLONE_NEWNET) == 0 {
+            return Ok(());
+        }
+        let uid = libc::getuid();
+        let gid = libc::getgid();
+        if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // Unprivileged gid_map writes require setgroups to be denied first.
+        write_proc_file(c"/proc/self/setgroups", b"deny")?;
+        let mut buf = [0u8; 40];
+        write_proc_file(c"/proc/self/uid_map", id_map_line(&mut buf, uid))?;
+        write_proc_file(c"/proc/self/gid_map", id_map_line(&mut buf, gid))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn enter_private_network() -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "network namespaces are only available on Linux",
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn write_proc_file(path: &std::ffi::CStr, contents: &[u8]) -> std::io::Result<()> {
+    // SAFETY: `path` is NUL-terminated and `contents` outlives the write call.
+    unsafe {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let written = libc::write(fd, contents.as_ptr().cast(), contents.len());
+        let result = if written == contents.len() as isize {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        };
+        libc::close(fd);
+        result
+    }
+}
+
+/// Format `"<id> <id> 1"` into `buf` without allocating.
+fn id_map_line(buf: &mut [u8; 40], id: u32) -> &[u8] {
+    let mut digits = [0u8; 10];
+    let mut len = 0;
+    let mut rest = id;
+    loop {
+        digits[len] = b'0' + (rest % 10) as u8;
+        len += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    let mut pos = 0;
+    for _ in 0..2 {
+        for &digit in digits[..len].iter().rev() {
+            buf[pos] = digit;
+            pos += 1;
+        }
+        buf[pos] = b' ';
+        pos += 1;
+    }
+    buf[pos] = b'1';
+    &buf[..=pos]
+}
+
+/// Interface names listed in `/proc/<pid>/net/dev` output.
+#[cfg(test)]
+pub(crate) fn interface_names(net_dev: &str) -> Vec<&str> {
+    net_dev
+        .lines()
+        .skip(2)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, _)| name.trim())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unshare_net_command_leaves_only_loopback() {
+        let (program, args) = unshare_net_command().split_first().unwrap();
+        let output = match std::process::Command::new(program)
+            .args(args)
+            .args(["cat", "/proc/self/net/dev"])
+            .output()
+        {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                eprintln!(
+                    "unshare unavailable here ({}); skipping",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+                return;
+            }
+            Err(err) => {
+                eprintln!("unshare not installed ({err}); skipping");
+                return;
+            }
+        };
+        assert_eq!(
+            interface_names(&String::from_utf8_lossy(&output.stdout)),
+            ["lo"]
+        );
+    }
+
+    #[test]
+    fn test_id_map_line_formats_identity_mapping() {
+        let mut buf = [0u8; 40];
+        assert_eq!(id_map_line(&mut buf, 0), b"0 0 1");
+        assert_eq!(id_map_line(&mut buf, 1000), b"1000 1000 1");
+        assert_eq!(id_map_line(&mut buf, u32::MAX), b"4294967295 4294967295 1");
+    }
+}
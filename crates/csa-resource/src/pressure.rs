@@ -0,0 +1,186 @@
+//! Pressure Stall Information (PSI) readings for adaptive throttling.
+//!
+//! `/proc/pressure/{memory,cpu}` report the share of wall time in which at
+//! least one task (`some`) or every task (`full`) was stalled on the
+//! resource, averaged over 10s/60s/300s windows. Unlike a free-memory
+//! snapshot, PSI rises as soon as the host starts thrashing, so it is used
+//! both to hold back new spawns and to pause a running session while it
+//! stays high.
+//!
+//! PSI is Linux-only (kernel 4.20+); elsewhere every reading is absent and
+//! no threshold is ever exceeded.
+
+use std::time::{Duration, Instant};
+
+/// Pressure must fall below this fraction of the threshold before throttling
+/// is lifted, so a reading hovering at the threshold does not flap.
+pub const PRESSURE_RESUME_RATIO: f64 = 0.75;
+
+/// Stall averages for one line (`some` or `full`) of a PSI file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PressureStall {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    /// Cumulative stall time in microseconds.
+    pub total_us: u64,
+}
+
+/// Parsed contents of one `/proc/pressure/<resource>` file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourcePressure {
+    pub some: PressureStall,
+    /// Absent for CPU on kernels older than 5.13.
+    pub full: Option<PressureStall>,
+}
+
+/// Parse the text of a PSI file.
+pub fn parse_pressure(text: &str) -> Option<ResourcePressure> {
+    let mut some = None;
+    let mut full = None;
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        let slot = match fields.next() {
+            Some("some") => &mut some,
+            Some("full") => &mut full,
+            _ => continue,
+        };
+        *slot = parse_stall(fields);
+    }
+    Some(ResourcePressure { some: some?, full })
+}
+
+fn parse_stall<'a>(fields: impl Iterator<Item = &'a str>) -> Option<PressureStall> {
+    let (mut avg10, mut avg60, mut avg300, mut total_us) = (None, None, None, None);
+    for field in fields {
+        let (name, value) = field.split_once('=')?;
+        match name {
+            "avg10" => avg10 = value.parse().ok(),
+            "avg60" => avg60 = value.parse().ok(),
+            "avg300" => avg300 = value.parse().ok(),
+            "total" => total_us = value.parse().ok(),
+            _ => {}
+        }
+    }
+    Some(PressureStall {
+        avg10: avg10?,
+        avg60: avg60?,
+        avg300: avg300?,
+        total_us: total_us?,
+    })
+}
+
+/// Read `/proc/pressure/<resource>` (`"memory"`, `"cpu"` or `"io"`).
+#[cfg(target_os = "linux")]
+pub fn read_pressure(resource: &str) -> Option<ResourcePressure> {
+    let text = std::fs::read_to_string(format!("/proc/pressure/{resource}")).ok()?;
+    parse_pressure(&text)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_pressure(_resource: &str) -> Option<ResourcePressure> {
+    None
+}
+
+/// The `some avg10` values throttling decisions are based on.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureReading {
+    pub memory_some_avg10: Option<f64>,
+    pub cpu_some_avg10: Option<f64>,
+}
+
+impl PressureReading {
+    /// Sample the host's current memory and CPU pressure.
+    pub fn sample() -> Self {
+        Self {
+            memory_some_avg10: read_pressure("memory").map(|pressure| pressure.some.avg10),
+            cpu_some_avg10: read_pressure("cpu").map(|pressure| pressure.some.avg10),
+        }
+    }
+}
+
+/// Percent-of-time thresholds on `some avg10`; `None` disables a resource.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureThresholds {
+    pub memory_some_avg10: Option<f64>,
+    pub cpu_some_avg10: Option<f64>,
+}
+
+impl PressureThresholds {
+    pub fn is_enabled(&self) -> bool {
+        self.memory_some_avg10.is_some() || self.cpu_some_avg10.is_some()
+    }
+
+    /// Describe the first threshold `reading` meets or crosses, if any.
+    pub fn exceeded(&self, reading: PressureReading) -> Option<String> {
+        [
+            ("memory", self.memory_some_avg10, reading.memory_some_avg10),
+            ("cpu", self.cpu_some_avg10, reading.cpu_some_avg10),
+        ]
+        .into_iter()
+        .find_map(|(resource, threshold, value)| {
+            let (threshold, value) = (threshold?, value?);
+            (value >= threshold)
+                .then(|| format!("{resource} some avg10={value:.1}% >= {threshold:.1}%"))
+        })
+    }
+
+    /// Whether every enabled resource has dropped below the resume level.
+    pub fn subsided(&self, reading: PressureReading) -> bool {
+        [
+            (self.memory_some_avg10, reading.memory_some_avg10),
+            (self.cpu_some_avg10, reading.cpu_some_avg10),
+        ]
+        .into_iter()
+        .all(|(threshold, value)| match (threshold, value) {
+            (Some(threshold), Some(value)) => value < threshold * PRESSURE_RESUME_RATIO,
+            _ => true,
+        })
+    }
+}
+
+/// Outcome of [`wait_for_pressure_relief`] when pressure was high.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PressureDelay {
+    /// Threshold that was crossed when the wait began.
+    pub reason: String,
+    pub waited: Duration,
+    /// `true` when `max_wait` elapsed before pressure subsided.
+    pub timed_out: bool,
+}
+
+/// Wait while pressure is above `thresholds`, polling `sample` every
+/// `poll_interval` for at most `max_wait`.
+///
+/// Returns `None` without sleeping when pressure is already acceptable.
+pub async fn wait_for_pressure_relief(
+    thresholds: PressureThresholds,
+    max_wait: Duration,
+    poll_interval: Duration,
+    mut sample: impl FnMut() -> PressureReading,
+) -> Option<PressureDelay> {
+    let reason = thresholds.exceeded(sample())?;
+    let started = Instant::now();
+    loop {
+        let waited = started.elapsed();
+        if waited >= max_wait {
+            return Some(PressureDelay {
+                reason,
+                waited,
+                timed_out: true,
+            });
+        }
+        tokio::time::sleep(poll_interval.min(max_wait - waited)).await;
+        if thresholds.subsided(sample()) {
+            return Some(PressureDelay {
+                reason,
+                waited: started.elapsed(),
+                timed_out: false,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "pressure_tests.rs"]
+mod tests;
@@ -0,0 +1,93 @@
+use super::*;
+
+const MEMORY_PSI: &str = "\
+some avg10=31.25 avg60=12.50 avg300=3.00 total=123456
+full avg10=10.00 avg60=4.00 avg300=1.00 total=65432
+";
+
+fn reading(memory: f64, cpu: f64) -> PressureReading {
+    PressureReading {
+        memory_some_avg10: Some(memory),
+        cpu_some_avg10: Some(cpu),
+    }
+}
+
+#[test]
+fn parse_pressure_reads_some_and_full_lines() {
+    let pressure = parse_pressure(MEMORY_PSI).unwrap();
+    assert_eq!(pressure.some.avg10, 31.25);
+    assert_eq!(pressure.some.avg300, 3.0);
+    assert_eq!(pressure.some.total_us, 123_456);
+    assert_eq!(pressure.full.unwrap().avg60, 4.0);
+
+    let cpu_only_some = parse_pressure("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").unwrap();
+    assert!(cpu_only_some.full.is_none());
+
+    assert!(parse_pressure("").is_none());
+    assert!(parse_pressure("some avg10=1.0 total=5\n").is_none());
+}
+
+#[test]
+fn thresholds_report_crossing_and_require_hysteresis_to_resume() {
+    let thresholds = PressureThresholds {
+        memory_some_avg10: Some(20.0),
+        cpu_some_avg10: None,
+    };
+    assert!(thresholds.is_enabled());
+    assert_eq!(
+        thresholds.exceeded(reading(31.25, 99.0)).as_deref(),
+        Some("memory some avg10=31.2% >= 20.0%")
+    );
+    // CPU is not configured, so its pressure is ignored.
+    assert!(thresholds.exceeded(reading(5.0, 99.0)).is_none());
+
+    // Below the threshold but above the resume level: still throttled.
+    assert!(!thresholds.subsided(reading(18.0, 0.0)));
+    assert!(thresholds.subsided(reading(14.0, 0.0)));
+    // A missing reading never holds throttling in place.
+    assert!(thresholds.subsided(PressureReading::default()));
+    assert!(!PressureThresholds::default().is_enabled());
+}
+
+#[tokio::test]
+async fn wait_for_pressure_relief_returns_immediately_when_calm() {
+    let thresholds = PressureThresholds {
+        memory_some_avg10: None,
+        cpu_some_avg10: Some(50.0),
+    };
+    let delay =
+        wait_for_pressure_relief(thresholds, Duration::from_secs(60), Duration::ZERO, || {
+            reading(90.0, 10.0)
+        })
+        .await;
+    assert!(delay.is_none());
+}
+
+#[tokio::test]
+async fn wait_for_pressure_relief_waits_until_subsided_or_timeout() {
+    let thresholds = PressureThresholds {
+        memory_some_avg10: Some(20.0),
+        cpu_some_avg10: None,
+    };
+    let mut samples = [40.0, 30.0, 16.0, 10.0].into_iter();
+    let delay =
+        wait_for_pressure_relief(thresholds, Duration::from_secs(60), Duration::ZERO, || {
+            reading(samples.next().unwrap_or(0.0), 0.0)
+        })
+        .await
+        .unwrap();
+    assert!(!delay.timed_out);
+    assert_eq!(delay.reason, "memory some avg10=40.0% >= 20.0%");
+    assert_eq!(samples.next(), None, "stopped at the first calm sample");
+
+    let delay = wait_for_pressure_relief(
+        thresholds,
+        Duration::from_millis(20),
+        Duration::from_millis(5),
+        || reading(50.0, 0.0),
+    )
+    .await
+    .unwrap();
+    assert!(delay.timed_out);
+    assert!(delay.waited >= Duration::from_millis(20));
+}
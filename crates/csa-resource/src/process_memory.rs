@@ -0,0 +1,126 @@
+//! Per-process-group memory sampling for hosts without cgroups.
+//!
+//! On macOS there is no `MemoryCurrent` to query, so the memory monitor sums
+//! the resident set size of every process in the tool's process group using
+//! `proc_listpids(PROC_PGRP_ONLY)` and `proc_pidinfo(PROC_PIDTASKINFO)`.
+//! On Linux the same figure comes from scanning `/proc/<pid>/stat` for the
+//! group and summing `/proc/<pid>/statm` resident pages; `csa top` uses it to
+//! show per-session RSS regardless of which resource backend is active.
+
+/// Resident memory in bytes of all processes in process group `pgid`.
+///
+/// Returns `None` when the group no longer exists, so callers can stop
+/// monitoring just like a vanished cgroup scope.
+#[cfg(target_os = "macos")]
+pub fn process_group_rss_bytes(pgid: i32) -> Option<u64> {
+    // Not exported by libc; value from <sys/proc_info.h>.
+    const PROC_PGRP_ONLY: u32 = 2;
+    const MAX_PIDS: usize = 4096;
+
+    let pgid = u32::try_from(pgid.checked_abs()?).ok()?;
+    let mut pids = vec![0 as libc::pid_t; MAX_PIDS];
+    let buffer_size = (pids.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int;
+    // SAFETY: `pids` is a writable buffer of exactly `buffer_size` bytes.
+    let filled =
+        unsafe { libc::proc_listpids(PROC_PGRP_ONLY, pgid, pids.as_mut_ptr().cast(), buffer_size) };
+    if filled <= 0 {
+        return None;
+    }
+    let count = filled as usize / std::mem::size_of::<libc::pid_t>();
+
+    let mut total = 0u64;
+    let mut seen = false;
+    for &pid in pids[..count].iter().filter(|pid| **pid > 0) {
+        if let Some(rss) = process_rss_bytes(pid) {
+            total += rss;
+            seen = true;
+        }
+    }
+    seen.then_some(total)
+}
+
+#[cfg(target_os = "linux")]
+pub fn process_group_rss_bytes(pgid: i32) -> Option<u64> {
+    let pgid = pgid.checked_abs()?;
+    // SAFETY: sysconf has no preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+
+    let mut total = 0u64;
+    let mut seen = false;
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(stat) = std::fs::read_to_string(format!("/proc/{pid}/stat")) else {
+            continue;
+        };
+        // Fields after the parenthesised comm: state, ppid, pgrp, ...
+        let Some((_, after_comm)) = stat.rsplit_once(") ") else {
+            continue;
+        };
+        let mut fields = after_comm.split_whitespace();
+        let state = fields.next();
+        let pgrp = fields.nth(1).and_then(|field| field.parse::<i32>().ok());
+        if pgrp != Some(pgid) || matches!(state, Some("Z" | "X")) {
+            continue;
+        }
+        let resident_pages = std::fs::read_to_string(format!("/proc/{pid}/statm"))
+            .ok()
+            .and_then(|statm| statm.split_whitespace().nth(1)?.parse::<u64>().ok());
+        if let Some(pages) = resident_pages {
+            total += pages * page_size;
+            seen = true;
+        }
+    }
+    seen.then_some(total)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn process_group_rss_bytes(_pgid: i32) -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn process_rss_bytes(pid: libc::pid_t) -> Option<u64> {
+    // SAFETY: proc_taskinfo is plain old data; all-zero is a valid value.
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    // SAFETY: `info` is a writable buffer of exactly `size` bytes.
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid,
+            libc::PROC_PIDTASKINFO,
+            0,
+            (&mut info as *mut libc::proc_taskinfo).cast(),
+            size,
+        )
+    };
+    (written == size).then_some(info.pti_resident_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_group_rss_matches_platform() {
+        // SAFETY: getpgrp has no preconditions.
+        let own_group = unsafe { libc::getpgrp() };
+        let rss = process_group_rss_bytes(own_group);
+        if cfg!(any(target_os = "macos", target_os = "linux")) {
+            assert!(rss.is_some_and(|bytes| bytes > 0));
+        } else {
+            assert_eq!(rss, None);
+        }
+    }
+
+    #[test]
+    fn test_process_group_rss_missing_group_is_none() {
+        // Above the kernel's PID_MAX_LIMIT, so no group can exist.
+        assert_eq!(process_group_rss_bytes(i32::MAX), None);
+    }
+}
@@ -1,7 +1,8 @@
 //! POSIX `setrlimit` enforcement for PID limits.
 //!
 //! Provides [`apply_rlimits`] which optionally sets `RLIMIT_NPROC` on the
-//! **current process**.  Intended to be called in a child process after fork
+//! **current process**, and [`apply_cpu_priority`] which approximates a CPU
+//! quota with `setpriority`.  Intended to be called in a child process after fork
 //! (e.g. via `Command::pre_exec`).
 //!
 //! Memory enforcement is handled by cgroup scopes (preferred) or
@@ -41,6 +42,51 @@ pub fn apply_rlimits(_memory_max_mb: u64, pids_max: Option<u64>) -> Result<()> {
     Ok(())
 }
 
+/// Highest (least favourable) nice value on Linux.
+const MAX_NICE: i32 = 19;
+
+/// Map a `CPUQuota`-style percentage to a nice value.
+///
+/// `setrlimit` cannot cap CPU bandwidth, so without a cgroup the best
+/// approximation is to yield to other work: the smaller the share, the higher
+/// the nice value.  Quotas of one full core or more are left at nice 0.
+pub fn cpu_percent_to_nice(cpu_max_percent: u32) -> i32 {
+    if cpu_max_percent >= 100 {
+        return 0;
+    }
+    let starved = 100 - cpu_max_percent as i32;
+    (starved * MAX_NICE / 100).clamp(1, MAX_NICE)
+}
+
+/// Lower the scheduling priority of the current process to approximate
+/// `cpu_max_percent` when no cgroup scope is available.
+///
+/// Like [`apply_rlimits`] this is meant for a `pre_exec` closure;
+/// `setpriority` is async-signal-safe.  Raising priority is never attempted,
+/// so an already-niced parent is left alone.
+pub fn apply_cpu_priority(cpu_max_percent: Option<u32>) -> Result<()> {
+    let Some(percent) = cpu_max_percent else {
+        return Ok(());
+    };
+    let nice = cpu_percent_to_nice(percent);
+    if nice == 0 {
+        return Ok(());
+    }
+
+    // SAFETY: setpriority is a well-defined POSIX syscall; `who = 0` targets
+    // the calling process.
+    let ret = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) };
+    if ret != 0 {
+        let err = std::io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::EACCES) {
+            // Already running at a higher nice value than requested.
+            return Ok(());
+        }
+        return Err(err).context(format!("setpriority(PRIO_PROCESS, {nice}) failed"));
+    }
+    Ok(())
+}
+
 /// Read current RLIMIT_NPROC soft limit.
 pub fn current_rlimit_nproc() -> Option<u64> {
     let mut rlim = libc::rlimit {
@@ -125,6 +171,21 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_cpu_percent_to_nice_scales_below_one_core() {
+        assert_eq!(cpu_percent_to_nice(100), 0);
+        assert_eq!(cpu_percent_to_nice(400), 0);
+        assert_eq!(cpu_percent_to_nice(50), 9);
+        assert_eq!(cpu_percent_to_nice(1), 18);
+        assert_eq!(cpu_percent_to_nice(99), 1);
+    }
+
+    #[test]
+    fn test_apply_cpu_priority_noop_without_limit() {
+        assert!(apply_cpu_priority(None).is_ok());
+        assert!(apply_cpu_priority(Some(100)).is_ok());
+    }
+
     #[test]
     fn test_apply_oom_score_adj_succeeds() {
         // On Linux with /proc mounted, this should succeed (or silently
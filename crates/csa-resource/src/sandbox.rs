@@ -1,19 +1,30 @@
 //! Sandbox capability detection.
 //!
 //! Probes the host environment to determine which resource isolation
-//! mechanism is available: cgroup v2 (via systemd user scope), POSIX
+//! mechanism is available: cgroup v2 (via systemd user scope), a delegated
+//! cgroup v2 subtree managed directly (containers without systemd), POSIX
 //! `setrlimit`, or nothing.  The result is cached for the lifetime of the
 //! process via `OnceLock`.
+//!
+//! [`detect_sandbox_capability`] combines this with the filesystem probe.
+//! Hosts without systemd user scopes drop to `setrlimit` for resources, but
+//! still get filesystem confinement from bwrap or, failing that, Landlock
+//! (kernel >= 5.13), which needs neither systemd nor user namespaces.
+//! On macOS the pair is `Setrlimit` plus `sandbox-exec` (Seatbelt).
 
 use std::path::Path;
 use std::process::Command;
 use std::sync::OnceLock;
 
+use crate::filesystem_sandbox::{FilesystemCapability, detect_filesystem_capability};
+
 /// Resource-isolation mechanism available on this host.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResourceCapability {
     /// cgroup v2 with systemd user-scope support (best isolation).
     CgroupV2,
+    /// cgroup v2 subtree written directly by CSA (no systemd user session).
+    CgroupDirect,
     /// POSIX `setrlimit` — PID limit only (`RLIMIT_NPROC`).
     Setrlimit,
     /// No usable isolation mechanism detected.
@@ -24,12 +35,20 @@ impl std::fmt::Display for ResourceCapability {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::CgroupV2 => write!(f, "CgroupV2"),
+            Self::CgroupDirect => write!(f, "CgroupDirect"),
             Self::Setrlimit => write!(f, "Setrlimit"),
             Self::None => write!(f, "None"),
         }
     }
 }
 
+impl ResourceCapability {
+    /// Whether memory/PID limits are enforced by a cgroup (either mode).
+    pub fn is_cgroup(self) -> bool {
+        matches!(self, Self::CgroupV2 | Self::CgroupDirect)
+    }
+}
+
 /// Process-wide cached probe result.
 static CAPABILITY: OnceLock<ResourceCapability> = OnceLock::new();
 
@@ -38,10 +57,33 @@ pub fn detect_resource_capability() -> ResourceCapability {
     *CAPABILITY.get_or_init(probe_capability)
 }
 
+/// Combined resource and filesystem sandbox capability of this host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxCapability {
+    pub resource: ResourceCapability,
+    pub filesystem: FilesystemCapability,
+}
+
+/// Detect both sandbox axes (each probe is cached independently).
+///
+/// Under Landlock the child is restricted to the isolation plan's writable
+/// paths: project root, session dir and tool config dirs.
+pub fn detect_sandbox_capability() -> SandboxCapability {
+    SandboxCapability {
+        resource: detect_resource_capability(),
+        filesystem: detect_filesystem_capability(),
+    }
+}
+
 /// Perform the actual detection (called at most once).
 fn probe_capability() -> ResourceCapability {
-    if has_cgroup_v2() && has_systemd_user_scope() {
-        return ResourceCapability::CgroupV2;
+    if has_cgroup_v2() {
+        if has_systemd_user_scope() {
+            return ResourceCapability::CgroupV2;
+        }
+        if crate::cgroup_direct::delegated_root().is_some() {
+            return ResourceCapability::CgroupDirect;
+        }
     }
 
     if has_setrlimit() {
@@ -108,9 +150,20 @@ mod tests {
         assert_eq!(first, second, "cached result must be stable");
     }
 
+    #[test]
+    fn test_detect_sandbox_capability_matches_axis_probes() {
+        let capability = detect_sandbox_capability();
+        assert_eq!(capability.resource, detect_resource_capability());
+        assert_eq!(capability.filesystem, detect_filesystem_capability());
+        if capability.filesystem == FilesystemCapability::Landlock {
+            assert!(crate::landlock::kernel_abi_version().is_some());
+        }
+    }
+
     #[test]
     fn test_display_variants() {
         assert_eq!(ResourceCapability::CgroupV2.to_string(), "CgroupV2");
+        assert_eq!(ResourceCapability::CgroupDirect.to_string(), "CgroupDirect");
         assert_eq!(ResourceCapability::Setrlimit.to_string(), "Setrlimit");
         assert_eq!(ResourceCapability::None.to_string(), "None");
     }
@@ -0,0 +1,180 @@
+//! macOS Seatbelt (`sandbox-exec`) profile generation.
+//!
+//! The macOS counterpart of bwrap: the tool is launched as
+//! `sandbox-exec -p <profile> <tool> <args...>`, where the profile allows
+//! everything by default but denies file writes outside the plan's writable
+//! paths and, for `NetworkMode::None`, all non-loopback networking.
+//!
+//! Profile generation is plain string building so it is testable on every
+//! platform; only running the wrapped command requires macOS.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::filesystem_sandbox::FilesystemCapability;
+use crate::isolation_plan::IsolationPlan;
+
+/// Location of the Seatbelt launcher on macOS.
+pub const SANDBOX_EXEC_PATH: &str = "/usr/bin/sandbox-exec";
+
+/// Device nodes tools routinely write to even when confined.
+const DEVICE_WRITE_PATHS: &[&str] = &["/dev/null", "/dev/zero", "/dev/tty", "/dev/dtracehelper"];
+
+/// Render the Seatbelt profile for `plan`.
+///
+/// When `readonly_project_root` is set the project root is left out of the
+/// writable set, mirroring bwrap's `--ro-bind`.
+pub fn render_profile(plan: &IsolationPlan) -> String {
+    let mut profile = String::from("(version 1)\n(allow default)\n(deny file-write*)\n");
+
+    profile.push_str("(allow file-write*\n");
+    for device in DEVICE_WRITE_PATHS {
+        profile.push_str(&format!("    (literal {})\n", quote(Path::new(device))));
+    }
+    profile.push_str("    (subpath \"/dev/fd\")\n");
+    for path in writable_paths(plan) {
+        profile.push_str(&format!("    (subpath {})\n", quote(&path)));
+    }
+    profile.push_str(")\n");
+
+    if plan.network.is_isolated() {
+        profile.push_str(
+            "(deny network*)\n\
+             (allow network* (local ip \"localhost:*\"))\n\
+             (allow network* (remote ip \"localhost:*\"))\n\
+             (allow network* (remote unix-socket))\n",
+        );
+    }
+    profile
+}
+
+/// Build a `sandbox-exec` [`Command`] from an [`IsolationPlan`].
+///
+/// Returns `Some(Command)` when `plan.filesystem == FilesystemCapability::SandboxExec`,
+/// `None` otherwise.
+pub fn from_isolation_plan(
+    plan: &IsolationPlan,
+    tool_binary: &str,
+    tool_args: &[String],
+) -> Option<Command> {
+    if plan.filesystem != FilesystemCapability::SandboxExec {
+        return None;
+    }
+
+    let mut cmd = Command::new(SANDBOX_EXEC_PATH);
+    cmd.arg("-p").arg(render_profile(plan));
+    cmd.arg(tool_binary).args(tool_args);
+
+    let mut env_overrides = plan.env_overrides.clone();
+    csa_core::env::scrub_subtree_contract_env_map(&mut env_overrides);
+    csa_core::env::strip_git_push_authorization_keys(&mut env_overrides);
+    cmd.envs(env_overrides);
+
+    Some(cmd)
+}
+
+/// Writable subpaths for the profile, each listed as given and, when it
+/// differs, canonicalized: Seatbelt matches the resolved path, so
+/// `/tmp/x` must also appear as `/private/tmp/x`.
+fn writable_paths(plan: &IsolationPlan) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for path in &plan.writable_paths {
+        let is_project_root = plan.project_root.as_ref().is_some_and(|root| path == root);
+        if plan.readonly_project_root && is_project_root {
+            continue;
+        }
+        paths.push(path.clone());
+        if let Ok(resolved) = std::fs::canonicalize(path)
+            && resolved != *path
+        {
+            paths.push(resolved);
+        }
+    }
+    paths.dedup();
+    paths
+}
+
+/// Quote a path as an SBPL string literal.
+fn quote(path: &Path) -> String {
+    let escaped = path
+        .to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkMode;
+    use crate::sandbox::ResourceCapability;
+    use std::collections::HashMap;
+
+    fn plan(writable: &[&str]) -> IsolationPlan {
+        IsolationPlan {
+            resource: ResourceCapability::Setrlimit,
+            filesystem: FilesystemCapability::SandboxExec,
+            writable_paths: writable.iter().map(PathBuf::from).collect(),
+            readable_paths: Vec::new(),
+            env_overrides: HashMap::from([("TMPDIR".to_string(), "/work/tmp".to_string())]),
+            degraded_reasons: Vec::new(),
+            memory_max_mb: None,
+            memory_swap_max_mb: None,
+            pids_max: None,
+            cpu_max_percent: None,
+            cpu_weight: None,
+            disk_max_mb: None,
+            network: NetworkMode::Full,
+            readonly_project_root: false,
+            project_root: Some(PathBuf::from("/work/project")),
+            soft_limit_percent: None,
+            memory_monitor_interval_seconds: None,
+            user_daemon_ipc: false,
+        }
+    }
+
+    #[test]
+    fn test_profile_denies_writes_outside_writable_paths() {
+        let profile = render_profile(&plan(&["/work/project", "/state/session"]));
+        assert!(profile.starts_with("(version 1)\n(allow default)\n(deny file-write*)\n"));
+        assert!(profile.contains("(subpath \"/work/project\")"));
+        assert!(profile.contains("(subpath \"/state/session\")"));
+        assert!(profile.contains("(literal \"/dev/null\")"));
+        assert!(!profile.contains("network"));
+    }
+
+    #[test]
+    fn test_profile_readonly_project_root_and_network_none() {
+        let mut plan = plan(&["/work/project", "/state/session"]);
+        plan.readonly_project_root = true;
+        plan.network = NetworkMode::None;
+        let profile = render_profile(&plan);
+        assert!(!profile.contains("/work/project"));
+        assert!(profile.contains("(subpath \"/state/session\")"));
+        assert!(profile.contains("(deny network*)"));
+        assert!(profile.contains("(allow network* (remote ip \"localhost:*\"))"));
+    }
+
+    #[test]
+    fn test_quote_escapes_sbpl_metacharacters() {
+        assert_eq!(quote(Path::new("/a \"b\"\\c")), "\"/a \\\"b\\\"\\\\c\"");
+    }
+
+    #[test]
+    fn test_from_isolation_plan_wraps_tool_only_for_sandbox_exec() {
+        let plan = plan(&["/work/project"]);
+        let cmd = from_isolation_plan(&plan, "claude", &["--print".to_string()]).unwrap();
+        assert_eq!(cmd.get_program(), SANDBOX_EXEC_PATH);
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(args[0], "-p");
+        assert_eq!(&args[2..], ["claude", "--print"]);
+        assert!(
+            cmd.get_envs()
+                .any(|(key, value)| key == "TMPDIR" && value == Some("/work/tmp".as_ref()))
+        );
+
+        let mut bwrap_plan = plan.clone();
+        bwrap_plan.filesystem = FilesystemCapability::Bwrap;
+        assert!(from_isolation_plan(&bwrap_plan, "claude", &[]).is_none());
+    }
+}
@@ -0,0 +1,367 @@
+//! Historical peak-memory statistics used to estimate a spawn's footprint.
+//!
+//! Every finished run records its peak memory under a `(tool, model,
+//! task_kind)` key, because a flash-tier model and a pro-tier model of the
+//! same tool can differ by gigabytes. Estimates are a time-decayed P95: each
+//! sample is weighted by `0.5^(age / half_life)`, so a single old outlier
+//! stops dominating once newer runs accumulate.
+//!
+//! When the exact key has too few samples the estimate falls back to the
+//! same tool and model across task kinds, then to the tool as a whole.
+
+use std::fs::{self, OpenOptions};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// File name of the persisted statistics under the CSA state directory.
+pub const USAGE_STATS_FILE: &str = "usage-stats.toml";
+
+/// Default half-life for sample weights.
+pub const DEFAULT_HALF_LIFE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Percentile reported by [`UsageStats::estimate`].
+pub const ESTIMATE_PERCENTILE: f64 = 0.95;
+
+/// Samples a key needs before it is trusted over a broader fallback key.
+pub const MIN_SAMPLES_FOR_ESTIMATE: usize = 3;
+
+/// Newest samples retained per key; older ones carry negligible weight anyway.
+const MAX_SAMPLES_PER_KEY: usize = 64;
+
+/// Identity that runs are grouped by.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct UsageKey {
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_kind: Option<String>,
+}
+
+impl UsageKey {
+    pub fn new(tool: &str, model: Option<&str>, task_kind: Option<&str>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            model: model.map(str::to_string),
+            task_kind: task_kind.map(str::to_string),
+        }
+    }
+
+    /// Keys consulted for an estimate, most specific first.
+    fn fallback_chain(&self) -> Vec<KeyFilter<'_>> {
+        let mut chain = vec![KeyFilter {
+            tool: &self.tool,
+            model: Some(self.model.as_deref()),
+            task_kind: Some(self.task_kind.as_deref()),
+        }];
+        if self.task_kind.is_some() {
+            chain.push(KeyFilter {
+                tool: &self.tool,
+                model: Some(self.model.as_deref()),
+                task_kind: None,
+            });
+        }
+        if self.model.is_some() || self.task_kind.is_some() {
+            chain.push(KeyFilter {
+                tool: &self.tool,
+                model: None,
+                task_kind: None,
+            });
+        }
+        chain
+    }
+}
+
+impl std::fmt::Display for UsageKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            self.tool,
+            self.model.as_deref().unwrap_or("*"),
+            self.task_kind.as_deref().unwrap_or("*")
+        )
+    }
+}
+
+/// Matches entries for one level of the fallback chain; `None` is a wildcard.
+#[derive(Debug, Clone, Copy)]
+struct KeyFilter<'a> {
+    tool: &'a str,
+    model: Option<Option<&'a str>>,
+    task_kind: Option<Option<&'a str>>,
+}
+
+impl KeyFilter<'_> {
+    fn matches(&self, key: &UsageKey) -> bool {
+        key.tool == self.tool
+            && self.model.is_none_or(|model| key.model.as_deref() == model)
+            && self
+                .task_kind
+                .is_none_or(|task_kind| key.task_kind.as_deref() == task_kind)
+    }
+
+    fn to_key(self) -> UsageKey {
+        UsageKey {
+            tool: self.tool.to_string(),
+            model: self.model.flatten().map(str::to_string),
+            task_kind: self.task_kind.flatten().map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct UsageSample {
+    peak_mb: u64,
+    /// Peak GPU memory, for runs of tools that declare `gpu_memory_mb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    peak_vram_mb: Option<u64>,
+    /// Unix seconds.
+    recorded_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct UsageEntry {
+    #[serde(flatten)]
+    key: UsageKey,
+    #[serde(default)]
+    samples: Vec<UsageSample>,
+}
+
+/// A decayed-P95 estimate and the key level it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryEstimate {
+    /// Key whose samples produced the estimate; wildcards print as `*`.
+    pub key: UsageKey,
+    pub p95_mb: u64,
+    pub sample_count: usize,
+    /// Sum of sample weights; low values mean the data is mostly stale.
+    pub effective_weight: f64,
+    /// Most recent sample for the key.
+    pub last_peak_mb: u64,
+    /// Decayed P95 of peak VRAM over the samples that recorded it.
+    pub vram_p95_mb: Option<u64>,
+}
+
+/// Peak-memory samples for every `(tool, model, task_kind)` seen so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageStats {
+    #[serde(default)]
+    entries: Vec<UsageEntry>,
+}
+
+impl UsageStats {
+    /// Load statistics from `path`; a missing file yields empty statistics.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse usage stats {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => {
+                Err(err).with_context(|| format!("Failed to read usage stats {}", path.display()))
+            }
+        }
+    }
+
+    /// Write statistics to `path` via a temp file and rename.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = toml::to_string(self).context("Failed to serialize usage stats")?;
+        let tmp_path = path.with_extension("toml.tmp");
+        fs::write(&tmp_path, content)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Append a sample to `path` under an exclusive lock so concurrent runs
+    /// finishing at the same time do not drop each other's samples.
+    pub fn record_to_file(
+        path: &Path,
+        key: UsageKey,
+        peak_mb: u64,
+        peak_vram_mb: Option<u64>,
+        recorded_at: SystemTime,
+    ) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let lock_path = path.with_extension("toml.lock");
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+        // SAFETY: the fd is owned by `lock_file` and stays open until it drops,
+        // which also releases the lock.
+        if unsafe { libc::flock(lock_file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(std::io::Error::last_os_error())
+                .with_context(|| format!("Failed to lock {}", lock_path.display()));
+        }
+
+        let mut stats = Self::load(path).unwrap_or_else(|err| {
+            tracing::warn!(error = %err, "Discarding unreadable usage stats");
+            Self::default()
+        });
+        stats.record_with_vram(key, peak_mb, peak_vram_mb, recorded_at);
+        stats.save(path)
+    }
+
+    /// Record one run's peak memory.
+    pub fn record(&mut self, key: UsageKey, peak_mb: u64, recorded_at: SystemTime) {
+        self.record_with_vram(key, peak_mb, None, recorded_at);
+    }
+
+    /// Record one run's peak memory together with its peak VRAM, if measured.
+    pub fn record_with_vram(
+        &mut self,
+        key: UsageKey,
+        peak_mb: u64,
+        peak_vram_mb: Option<u64>,
+        recorded_at: SystemTime,
+    ) {
+        let sample = UsageSample {
+            peak_mb,
+            peak_vram_mb,
+            recorded_at: unix_seconds(recorded_at),
+        };
+        let entry = match self.entries.iter().position(|entry| entry.key == key) {
+            Some(index) => &mut self.entries[index],
+            None => {
+                self.entries.push(UsageEntry {
+                    key,
+                    samples: Vec::new(),
+                });
+                self.entries.last_mut().expect("entry was just pushed")
+            }
+        };
+        entry.samples.push(sample);
+        if entry.samples.len() > MAX_SAMPLES_PER_KEY {
+            entry.samples.sort_by_key(|sample| sample.recorded_at);
+            let excess = entry.samples.len() - MAX_SAMPLES_PER_KEY;
+            entry.samples.drain(..excess);
+        }
+    }
+
+    /// Decayed-P95 estimate for `key`, walking the fallback chain until a
+    /// level has at least [`MIN_SAMPLES_FOR_ESTIMATE`] samples. The broadest
+    /// level is used with fewer samples when nothing better exists.
+    pub fn estimate(
+        &self,
+        key: &UsageKey,
+        now: SystemTime,
+        half_life: Duration,
+    ) -> Option<MemoryEstimate> {
+        let chain = key.fallback_chain();
+        let mut broadest = None;
+        for filter in &chain {
+            let samples: Vec<UsageSample> = self
+                .entries
+                .iter()
+                .filter(|entry| filter.matches(&entry.key))
+                .flat_map(|entry| entry.samples.iter().copied())
+                .collect();
+            if samples.len() >= MIN_SAMPLES_FOR_ESTIMATE {
+                return estimate_from(filter.to_key(), &samples, now, half_life);
+            }
+            broadest = Some((*filter, samples));
+        }
+        let (filter, samples) = broadest?;
+        estimate_from(filter.to_key(), &samples, now, half_life)
+    }
+
+    /// One estimate per recorded key, sorted by key, for reporting.
+    pub fn estimates(&self, now: SystemTime, half_life: Duration) -> Vec<MemoryEstimate> {
+        let mut estimates: Vec<MemoryEstimate> = self
+            .entries
+            .iter()
+            .filter_map(|entry| estimate_from(entry.key.clone(), &entry.samples, now, half_life))
+            .collect();
+        estimates.sort_by(|a, b| a.key.cmp(&b.key));
+        estimates
+    }
+}
+
+fn estimate_from(
+    key: UsageKey,
+    samples: &[UsageSample],
+    now: SystemTime,
+    half_life: Duration,
+) -> Option<MemoryEstimate> {
+    let now_secs = unix_seconds(now);
+    let half_life_secs = half_life.as_secs_f64().max(1.0);
+    let weight_of = |sample: &UsageSample| {
+        let age_secs = now_secs.saturating_sub(sample.recorded_at) as f64;
+        0.5f64.powf(age_secs / half_life_secs)
+    };
+    let mut weighted: Vec<(u64, f64)> = samples
+        .iter()
+        .map(|sample| (sample.peak_mb, weight_of(sample)))
+        .collect();
+    let effective_weight: f64 = weighted.iter().map(|(_, weight)| weight).sum();
+    if effective_weight == 0.0 {
+        // Every weight underflowed; stale data still beats no data, so fall
+        // back to an unweighted percentile and let `effective_weight` say so.
+        weighted.iter_mut().for_each(|(_, weight)| *weight = 1.0);
+    }
+    let p95_mb = weighted_percentile(&weighted, ESTIMATE_PERCENTILE)?;
+    let vram_weighted: Vec<(u64, f64)> = samples
+        .iter()
+        .filter_map(|sample| Some((sample.peak_vram_mb?, weight_of(sample))))
+        .collect();
+    let vram_p95_mb = weighted_percentile(&vram_weighted, ESTIMATE_PERCENTILE);
+    let last_peak_mb = samples
+        .iter()
+        .max_by_key(|sample| sample.recorded_at)?
+        .peak_mb;
+    Some(MemoryEstimate {
+        key,
+        p95_mb,
+        sample_count: samples.len(),
+        effective_weight,
+        last_peak_mb,
+        vram_p95_mb,
+    })
+}
+
+/// Smallest value whose cumulative weight reaches `quantile` of the total.
+pub fn weighted_percentile(samples: &[(u64, f64)], quantile: f64) -> Option<u64> {
+    let mut sorted: Vec<(u64, f64)> = samples
+        .iter()
+        .copied()
+        .filter(|(_, weight)| weight.is_finite() && *weight > 0.0)
+        .collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by_key(|(value, _)| *value);
+
+    let total: f64 = sorted.iter().map(|(_, weight)| weight).sum();
+    let target = total * quantile.clamp(0.0, 1.0);
+    let mut cumulative = 0.0;
+    for (value, weight) in &sorted {
+        cumulative += weight;
+        if cumulative >= target {
+            return Some(*value);
+        }
+    }
+    sorted.last().map(|(value, _)| *value)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+#[path = "usage_stats_tests.rs"]
+mod tests;
@@ -0,0 +1,141 @@
+use super::*;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn at(days_ago: u64) -> SystemTime {
+    now() - DAY * days_ago as u32
+}
+
+fn now() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(1_800_000_000)
+}
+
+#[test]
+fn test_weighted_percentile_equal_weights() {
+    let samples: Vec<(u64, f64)> = (1..=20).map(|mb| (mb * 100, 1.0)).collect();
+    assert_eq!(weighted_percentile(&samples, 0.95), Some(1900));
+    assert_eq!(weighted_percentile(&samples, 0.5), Some(1000));
+    assert_eq!(weighted_percentile(&[], 0.95), None);
+    assert_eq!(weighted_percentile(&[(10, 0.0)], 0.95), None);
+}
+
+#[test]
+fn test_old_outlier_decays_out_of_p95() {
+    let key = UsageKey::new("gemini-cli", Some("gemini-2.5-pro"), Some("run"));
+    let mut stats = UsageStats::default();
+    stats.record(key.clone(), 12_000, at(60));
+    for days_ago in 0..10 {
+        stats.record(key.clone(), 2_000 + days_ago, at(days_ago));
+    }
+
+    let decayed = stats.estimate(&key, now(), DEFAULT_HALF_LIFE).unwrap();
+    assert!(
+        decayed.p95_mb < 3_000,
+        "outlier still dominates: {decayed:?}"
+    );
+    assert_eq!(decayed.sample_count, 11);
+    assert_eq!(decayed.last_peak_mb, 2_000);
+    assert_eq!(decayed.vram_p95_mb, None);
+
+    // Without decay the 60-day-old outlier is the P95.
+    let undecayed = stats
+        .estimate(&key, now(), Duration::from_secs(u64::MAX / 4))
+        .unwrap();
+    assert_eq!(undecayed.p95_mb, 12_000);
+}
+
+#[test]
+fn test_models_of_same_tool_are_estimated_separately() {
+    let flash = UsageKey::new("gemini-cli", Some("gemini-2.5-flash"), Some("run"));
+    let pro = UsageKey::new("gemini-cli", Some("gemini-2.5-pro"), Some("run"));
+    let mut stats = UsageStats::default();
+    for _ in 0..3 {
+        stats.record(flash.clone(), 600, now());
+        stats.record(pro.clone(), 4_000, now());
+    }
+
+    assert_eq!(
+        stats
+            .estimate(&flash, now(), DEFAULT_HALF_LIFE)
+            .unwrap()
+            .p95_mb,
+        600
+    );
+    let pro_estimate = stats.estimate(&pro, now(), DEFAULT_HALF_LIFE).unwrap();
+    assert_eq!(pro_estimate.p95_mb, 4_000);
+    assert_eq!(pro_estimate.key, pro);
+}
+
+#[test]
+fn test_sparse_key_falls_back_to_broader_levels() {
+    let mut stats = UsageStats::default();
+    let review = UsageKey::new("codex", Some("gpt-5"), Some("review"));
+    stats.record(review.clone(), 3_000, now());
+    for _ in 0..3 {
+        stats.record(
+            UsageKey::new("codex", Some("gpt-5"), Some("run")),
+            1_500,
+            now(),
+        );
+    }
+
+    let estimate = stats.estimate(&review, now(), DEFAULT_HALF_LIFE).unwrap();
+    assert_eq!(estimate.key, UsageKey::new("codex", Some("gpt-5"), None));
+    assert_eq!(estimate.sample_count, 4);
+    assert_eq!(estimate.key.to_string(), "codex/gpt-5/*");
+
+    let unseen_model = UsageKey::new("codex", Some("gpt-5-mini"), Some("run"));
+    let estimate = stats
+        .estimate(&unseen_model, now(), DEFAULT_HALF_LIFE)
+        .unwrap();
+    assert_eq!(estimate.key, UsageKey::new("codex", None, None));
+
+    assert!(
+        stats
+            .estimate(
+                &UsageKey::new("opencode", None, None),
+                now(),
+                DEFAULT_HALF_LIFE
+            )
+            .is_none()
+    );
+}
+
+#[test]
+fn test_record_keeps_newest_samples() {
+    let key = UsageKey::new("claude-code", None, None);
+    let mut stats = UsageStats::default();
+    for index in 0..(MAX_SAMPLES_PER_KEY as u64 + 5) {
+        stats.record(
+            key.clone(),
+            index,
+            UNIX_EPOCH + Duration::from_secs(1_000 + index),
+        );
+    }
+    // Decades-old samples underflow to zero weight but still yield an estimate.
+    let estimates = stats.estimates(now(), DEFAULT_HALF_LIFE);
+    assert_eq!(estimates.len(), 1);
+    assert_eq!(estimates[0].sample_count, MAX_SAMPLES_PER_KEY);
+    assert_eq!(estimates[0].last_peak_mb, MAX_SAMPLES_PER_KEY as u64 + 4);
+    assert_eq!(estimates[0].effective_weight, 0.0);
+}
+
+#[test]
+fn test_record_to_file_round_trips() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("state").join(USAGE_STATS_FILE);
+    assert_eq!(UsageStats::load(&path).unwrap(), UsageStats::default());
+
+    let key = UsageKey::new("gemini-cli", Some("gemini-2.5-flash"), None);
+    UsageStats::record_to_file(&path, key.clone(), 700, None, now()).unwrap();
+    UsageStats::record_to_file(&path, key.clone(), 900, Some(6_000), now()).unwrap();
+
+    let stats = UsageStats::load(&path).unwrap();
+    let estimates = stats.estimates(now(), DEFAULT_HALF_LIFE);
+    assert_eq!(estimates.len(), 1);
+    assert_eq!(estimates[0].key, key);
+    assert_eq!(estimates[0].sample_count, 2);
+    assert_eq!(estimates[0].p95_mb, 900);
+    // Only the second run measured VRAM.
+    assert_eq!(estimates[0].vram_p95_mb, Some(6_000));
+}
@@ -0,0 +1,171 @@
+//! Benchmark results of `csa bench`, kept per project.
+//!
+//! Every task run of a benchmark is appended to
+//! `{project_state}/bench-history.toml`. Summaries aggregate the history per
+//! candidate (tool or tier) and resolved model spec: pass rate, mean latency,
+//! mean tokens and total cost.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const BENCH_HISTORY_FILE: &str = "bench-history.toml";
+
+/// Oldest records are dropped beyond this many.
+const MAX_BENCH_RECORDS: usize = 2000;
+
+/// One task run of one candidate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    pub recorded_at: DateTime<Utc>,
+    pub suite: String,
+    pub task: String,
+    /// `tool:<name>` or `tier:<name>`, as selected on the command line.
+    pub candidate: String,
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_spec: Option<String>,
+    pub passed: bool,
+    pub latency_secs: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Why the run failed before validation, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BenchHistory {
+    #[serde(default)]
+    pub records: Vec<BenchRecord>,
+}
+
+/// Aggregate of the records of one candidate and model spec.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchSummary {
+    pub candidate: String,
+    pub tool: String,
+    pub model_spec: Option<String>,
+    pub runs: usize,
+    pub passed: usize,
+    pub pass_rate: f64,
+    pub mean_latency_secs: f64,
+    /// Mean over the runs that reported tokens.
+    pub mean_total_tokens: Option<u64>,
+    /// Sum over the runs that reported a cost.
+    pub total_cost_usd: Option<f64>,
+}
+
+impl BenchHistory {
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Append `records` to the history at `path` under an exclusive lock so
+    /// concurrent benches do not drop each other's records.
+    pub fn append_to_file(path: &Path, records: &[BenchRecord]) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let lock_path = path.with_extension("toml.lock");
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+        // SAFETY: the fd is owned by `lock_file` and stays open until it drops,
+        // which also releases the lock.
+        if unsafe { libc::flock(lock_file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(std::io::Error::last_os_error())
+                .with_context(|| format!("Failed to lock {}", lock_path.display()));
+        }
+
+        let mut history = Self::load(path)?;
+        history.records.extend_from_slice(records);
+        let overflow = history.records.len().saturating_sub(MAX_BENCH_RECORDS);
+        history.records.drain(..overflow);
+        let content =
+            toml::to_string_pretty(&history).context("Failed to serialize bench history")?;
+        let tmp_path = path.with_extension("toml.tmp");
+        fs::write(&tmp_path, content)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Summaries per candidate and model spec, optionally limited to one suite.
+    pub fn summaries(&self, suite: Option<&str>) -> Vec<BenchSummary> {
+        let mut groups: BTreeMap<(&str, &str, Option<&str>), Vec<&BenchRecord>> = BTreeMap::new();
+        for record in &self.records {
+            if suite.is_some_and(|suite| record.suite != suite) {
+                continue;
+            }
+            groups
+                .entry((
+                    record.candidate.as_str(),
+                    record.tool.as_str(),
+                    record.model_spec.as_deref(),
+                ))
+                .or_default()
+                .push(record);
+        }
+        groups
+            .into_iter()
+            .map(|((candidate, tool, model_spec), records)| {
+                let runs = records.len();
+                let passed = records.iter().filter(|record| record.passed).count();
+                let tokens: Vec<u64> = records.iter().filter_map(|r| r.total_tokens).collect();
+                let costs: Vec<f64> = records
+                    .iter()
+                    .filter_map(|r| r.estimated_cost_usd)
+                    .collect();
+                BenchSummary {
+                    candidate: candidate.to_string(),
+                    tool: tool.to_string(),
+                    model_spec: model_spec.map(str::to_string),
+                    runs,
+                    passed,
+                    pass_rate: passed as f64 / runs as f64,
+                    mean_latency_secs: records.iter().map(|r| r.latency_secs).sum::<f64>()
+                        / runs as f64,
+                    mean_total_tokens: (!tokens.is_empty())
+                        .then(|| tokens.iter().sum::<u64>() / tokens.len() as u64),
+                    total_cost_usd: (!costs.is_empty()).then(|| costs.iter().sum()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Append `records` to the benchmark history of the project at `project_root`.
+pub fn record_bench_results(project_root: &Path, records: &[BenchRecord]) -> Result<()> {
+    BenchHistory::append_to_file(&bench_history_path(project_root)?, records)
+}
+
+/// Benchmark history of the project at `project_root`; empty if none was recorded.
+pub fn load_bench_history(project_root: &Path) -> Result<BenchHistory> {
+    BenchHistory::load(&bench_history_path(project_root)?)
+}
+
+fn bench_history_path(project_root: &Path) -> Result<std::path::PathBuf> {
+    Ok(csa_session::get_session_root(project_root)?.join(BENCH_HISTORY_FILE))
+}
+
+#[cfg(test)]
+#[path = "bench_history_tests.rs"]
+mod tests;
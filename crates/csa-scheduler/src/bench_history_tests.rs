@@ -0,0 +1,86 @@
+use super::*;
+
+fn record(candidate: &str, model_spec: &str, passed: bool, latency_secs: f64) -> BenchRecord {
+    BenchRecord {
+        recorded_at: Utc::now(),
+        suite: "smoke".to_string(),
+        task: "task".to_string(),
+        candidate: candidate.to_string(),
+        tool: "codex".to_string(),
+        model_spec: Some(model_spec.to_string()),
+        passed,
+        latency_secs,
+        total_tokens: Some(1000),
+        estimated_cost_usd: passed.then_some(0.25),
+        session_id: None,
+        error: None,
+    }
+}
+
+#[test]
+fn summaries_aggregate_per_candidate_and_model_spec() {
+    let spec = "codex/openai/gpt-5/high";
+    let mut other_suite = record("tier:fast", "codex/openai/gpt-5/low", true, 1.0);
+    other_suite.suite = "other".to_string();
+    let history = BenchHistory {
+        records: vec![
+            record("tool:codex", spec, true, 10.0),
+            record("tool:codex", spec, false, 20.0),
+            other_suite,
+        ],
+    };
+
+    let summaries = history.summaries(Some("smoke"));
+    assert_eq!(summaries.len(), 1);
+    let summary = &summaries[0];
+    assert_eq!(summary.candidate, "tool:codex");
+    assert_eq!((summary.runs, summary.passed), (2, 1));
+    assert_eq!(summary.pass_rate, 0.5);
+    assert_eq!(summary.mean_latency_secs, 15.0);
+    assert_eq!(summary.mean_total_tokens, Some(1000));
+    assert_eq!(summary.total_cost_usd, Some(0.25));
+
+    assert_eq!(history.summaries(None).len(), 2);
+}
+
+#[test]
+fn append_to_file_keeps_earlier_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("state").join(BENCH_HISTORY_FILE);
+    assert!(BenchHistory::load(&path).unwrap().records.is_empty());
+
+    let first = record("tool:codex", "codex/openai/gpt-5/high", true, 3.5);
+    BenchHistory::append_to_file(&path, std::slice::from_ref(&first)).unwrap();
+    let second = record("tier:fast", "codex/openai/gpt-5/low", false, 1.5);
+    BenchHistory::append_to_file(&path, std::slice::from_ref(&second)).unwrap();
+
+    let loaded = BenchHistory::load(&path).unwrap();
+    assert_eq!(loaded.records.len(), 2);
+    assert_eq!(loaded.records[0].candidate, first.candidate);
+    assert_eq!(loaded.records[1].passed, second.passed);
+}
+
+#[test]
+fn concurrent_appends_keep_every_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(BENCH_HISTORY_FILE);
+
+    std::thread::scope(|scope| {
+        for index in 0..8 {
+            let path = &path;
+            scope.spawn(move || {
+                let record = record(
+                    &format!("tool:{index}"),
+                    "codex/openai/gpt-5/high",
+                    true,
+                    1.0,
+                );
+                BenchHistory::append_to_file(path, &[record]).unwrap();
+            });
+        }
+    });
+
+    let loaded = BenchHistory::load(&path).unwrap();
+    assert_eq!(loaded.records.len(), 8);
+    assert_eq!(loaded.summaries(None).len(), 8);
+}
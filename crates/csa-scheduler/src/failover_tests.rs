@@ -108,6 +108,7 @@ fn make_session(tools: Vec<(&str, &str)>, compacted: bool) -> MetaSessionState {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
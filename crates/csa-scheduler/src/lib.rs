@@ -1,5 +1,7 @@
-//! Scheduler: tool selection (round-robin), session reuse, seed management, and 429 failover.
+//! Scheduler: tool selection (round-robin), session reuse, seed management, 429 failover,
+//! and benchmark history.
 
+pub mod bench_history;
 pub mod failover;
 #[cfg(test)]
 mod failover_tests;
@@ -8,6 +10,9 @@ pub mod rotation;
 pub mod seed_session;
 pub mod session_reuse;
 
+pub use bench_history::{
+    BenchHistory, BenchRecord, BenchSummary, load_bench_history, record_bench_results,
+};
 pub use csa_core::types::FallbackAttempt;
 pub use failover::{FailoverAction, FallbackChain, decide_failover};
 pub use rate_limit::{
@@ -157,6 +157,7 @@ mod tests {
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
+            ..Default::default()
         };
 
         let score = compute_relevance_score(&session, "default", "gemini-cli");
@@ -197,6 +198,7 @@ mod tests {
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
+            ..Default::default()
         };
 
         let score = compute_relevance_score(&session, "review", "gemini-cli");
@@ -237,6 +239,7 @@ mod tests {
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
+            ..Default::default()
         };
 
         let score = compute_relevance_score(&session, "fix", "gemini-cli");
@@ -277,6 +280,7 @@ mod tests {
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
+            ..Default::default()
         };
 
         let score = compute_relevance_score(&session, "deploy", "gemini-cli");
@@ -315,6 +319,7 @@ mod tests {
             fork_call_timestamps: Vec::new(),
             vcs_identity: None,
             identity_version: 1,
+            ..Default::default()
         };
 
         let score = compute_relevance_score(&session, "default", "gemini-cli");
@@ -13,8 +13,8 @@ pub struct ChecklistStore {
 
 impl ChecklistStore {
     pub fn new() -> Result<Self> {
-        let state_dir =
-            csa_config::paths::state_dir_write().context("Failed to determine state directory")?;
+        let state_dir = csa_config::paths::session_state_dir()
+            .context("Failed to determine state directory")?;
         Ok(Self {
             base_dir: state_dir.join("checklists"),
         })
@@ -65,6 +65,8 @@ impl ChecklistStore {
 
     pub fn save(&self, project_root: &Path, branch: &str, doc: &ChecklistDocument) -> Result<()> {
         let branch_dir = self.branch_dir(project_root, branch);
+        csa_config::paths::create_session_state_dir()
+            .context("Failed to create the session state directory")?;
         fs::create_dir_all(&branch_dir)
             .with_context(|| format!("Failed to create checklist dir: {}", branch_dir.display()))?;
         let lock_path = branch_dir.join("checklist.lock");
@@ -143,6 +145,8 @@ impl ChecklistStore {
         F: FnOnce(&mut csa_core::checklist::ChecklistItem),
     {
         let branch_dir = self.branch_dir(project_root, branch);
+        csa_config::paths::create_session_state_dir()
+            .context("Failed to create the session state directory")?;
         fs::create_dir_all(&branch_dir)
             .with_context(|| format!("Failed to create checklist dir: {}", branch_dir.display()))?;
         let lock_path = branch_dir.join("checklist.lock");
@@ -282,6 +282,7 @@ fn test_note_from_session_deterministic_tool_selection() {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let note = note_from_session(&session);
@@ -441,6 +442,7 @@ fn test_note_from_session() {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let note = note_from_session(&session);
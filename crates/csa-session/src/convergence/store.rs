@@ -128,7 +128,7 @@ impl ConvergenceLedgerStore {
                 canonical.display()
             )
         })?;
-        let state_dir = csa_config::paths::state_dir_write()
+        let state_dir = csa_config::paths::session_state_dir()
             .context("failed to determine convergence state directory")?;
         let secure_boundary = state_dir
             .parent()
@@ -351,6 +351,9 @@ impl ConvergenceLedgerStore {
         B: FnOnce(&SecureDirectory, &[ConvergenceLedgerEntry]) -> anyhow::Result<()>,
         P: FnOnce(&SecureDirectory, &Path, &[u8]) -> Result<(), AtomicPublishError>,
     {
+        csa_config::paths::create_session_state_dir()
+            .context("create session state directory")
+            .map_err(not_published)?;
         let directory = secure_fs::open_convergence_directory(
             &self.secure_boundary,
             &self.project_state_root,
@@ -227,6 +227,8 @@ fn event_type(event: &SessionEvent) -> &'static str {
         | SessionEvent::ToolCallCompleted { .. }
         | SessionEvent::ToolCallOutput { .. } => "tool_call",
         SessionEvent::PlanUpdate(_) => "plan",
+        SessionEvent::PermissionRequest { .. } => "permission",
+        SessionEvent::FileAccess { .. } => "fs",
         SessionEvent::Other(_) => "other",
     }
 }
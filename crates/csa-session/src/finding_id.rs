@@ -35,6 +35,16 @@ impl FindingId {
         Self(encoded[..FINDING_ID_LENGTH].to_string())
     }
 
+    /// Accept `value` only if it has the fid-v1 shape (26 base32 characters).
+    ///
+    /// Reviewer-local ids such as `f1` in `findings.toml` are not stable across
+    /// sessions and are rejected.
+    pub fn parse(value: &str) -> Option<Self> {
+        let is_fid_v1 = value.len() == FINDING_ID_LENGTH
+            && value.chars().all(|ch| matches!(ch, 'A'..='Z' | '2'..='7'));
+        is_fid_v1.then(|| Self(value.to_string()))
+    }
+
     /// Return the identifier as a borrowed string.
     pub fn as_str(&self) -> &str {
         &self.0
@@ -128,6 +138,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_accepts_only_fid_v1_values() {
+        let id = FindingId::compute("semgrep", "rule", "src/lib.rs", None, None, None, "00");
+        assert_eq!(FindingId::parse(id.as_str()), Some(id));
+        assert_eq!(FindingId::parse("f1"), None);
+        assert_eq!(FindingId::parse(&"a".repeat(26)), None);
+    }
+
     #[test]
     fn test_compute_is_deterministic_for_same_inputs() {
         let id1 = FindingId::compute(
@@ -0,0 +1,342 @@
+//! Whole-session removal planning and reclaimed-space accounting for `csa gc`.
+//!
+//! The planner is pure: callers supply each session's on-disk size and whether
+//! it is protected (Active, pinned or live), and get back the sessions to
+//! remove together with the category that selected them. Per-project and
+//! `--global` gc share it, so filters and size budgets behave identically.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use tracing::info;
+
+use crate::state::MetaSessionState;
+
+/// Which sessions gc may delete as a whole.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionGcPolicy {
+    /// Delete sessions last accessed at least this long ago.
+    pub max_age: Option<Duration>,
+    /// Only delete sessions that ran this tool. Empty sessions never match.
+    pub tool: Option<String>,
+    /// After age-based removal, delete the least recently accessed sessions
+    /// until the remaining ones fit in this many bytes.
+    pub max_total_size: Option<u64>,
+}
+
+impl SessionGcPolicy {
+    pub fn matches_tool(&self, session: &MetaSessionState) -> bool {
+        self.tool
+            .as_deref()
+            .is_none_or(|tool| session.tools.contains_key(tool))
+    }
+}
+
+/// What gc removed (or would remove), for per-category reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcCategory {
+    EmptySessions,
+    ExpiredSessions,
+    OverBudgetSessions,
+    OrphanDirs,
+    RuntimePayloads,
+    Transcripts,
+}
+
+impl GcCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::EmptySessions => "Empty sessions",
+            Self::ExpiredSessions => "Expired sessions",
+            Self::OverBudgetSessions => "Over-budget sessions",
+            Self::OrphanDirs => "Orphan directories",
+            Self::RuntimePayloads => "Runtime payloads",
+            Self::Transcripts => "Transcript files",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionRemoval {
+    pub session_id: String,
+    pub category: GcCategory,
+    pub bytes: u64,
+}
+
+/// Plan whole-session removals under `policy`.
+///
+/// Sessions without tools are empty; sessions older than `max_age` are
+/// expired. When `max_total_size` is set, the surviving sessions are then
+/// removed oldest-access first until the rest fit the budget. Protected
+/// sessions and sessions outside the tool filter are never selected, but
+/// still count toward the budget.
+pub fn plan_session_removals(
+    sessions: &[MetaSessionState],
+    policy: &SessionGcPolicy,
+    now: DateTime<Utc>,
+    mut size_of: impl FnMut(&MetaSessionState) -> u64,
+    mut is_protected: impl FnMut(&MetaSessionState) -> bool,
+) -> Vec<SessionRemoval> {
+    let mut removable = |session: &MetaSessionState| {
+        if !policy.matches_tool(session) {
+            return false;
+        }
+        if is_protected(session) {
+            info!(
+                session = %session.meta_session_id,
+                "Skipped whole-session delete for Active, pinned or live session"
+            );
+            return false;
+        }
+        true
+    };
+
+    let mut removals = Vec::new();
+    let mut kept = Vec::new();
+    for session in sessions {
+        let category = if session.tools.is_empty() {
+            Some(GcCategory::EmptySessions)
+        } else if policy
+            .max_age
+            .is_some_and(|max_age| now.signed_duration_since(session.last_accessed) >= max_age)
+        {
+            Some(GcCategory::ExpiredSessions)
+        } else {
+            None
+        };
+        match category {
+            Some(category) if removable(session) => removals.push(SessionRemoval {
+                session_id: session.meta_session_id.clone(),
+                category,
+                bytes: size_of(session),
+            }),
+            _ => kept.push(session),
+        }
+    }
+
+    if let Some(budget) = policy.max_total_size {
+        let mut sized: Vec<(&MetaSessionState, u64)> = kept
+            .into_iter()
+            .map(|session| (session, size_of(session)))
+            .collect();
+        let mut total: u64 = sized.iter().map(|(_, bytes)| bytes).sum();
+        sized.sort_by_key(|(session, _)| session.last_accessed);
+        for (session, bytes) in sized {
+            if total <= budget {
+                break;
+            }
+            if removable(session) {
+                total = total.saturating_sub(bytes);
+                removals.push(SessionRemoval {
+                    session_id: session.meta_session_id.clone(),
+                    category: GcCategory::OverBudgetSessions,
+                    bytes,
+                });
+            }
+        }
+    }
+    removals
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct GcTotals {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+/// Items and bytes reclaimed per category.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct GcReclaimed(BTreeMap<GcCategory, GcTotals>);
+
+impl GcReclaimed {
+    pub fn record(&mut self, category: GcCategory, bytes: u64) {
+        self.add(category, 1, bytes);
+    }
+
+    pub fn add(&mut self, category: GcCategory, count: u64, bytes: u64) {
+        let totals = self.0.entry(category).or_default();
+        totals.count = totals.count.saturating_add(count);
+        totals.bytes = totals.bytes.saturating_add(bytes);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (&category, totals) in &other.0 {
+            self.add(category, totals.count, totals.bytes);
+        }
+    }
+
+    pub fn get(&self, category: GcCategory) -> GcTotals {
+        self.0.get(&category).copied().unwrap_or_default()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.0
+            .values()
+            .fold(0u64, |sum, totals| sum.saturating_add(totals.bytes))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (GcCategory, GcTotals)> + '_ {
+        self.0.iter().map(|(&category, &totals)| (category, totals))
+    }
+}
+
+/// Parse a byte size such as `500M`, `2G` or `1048576`. Units are binary
+/// (`K` = 1024) and an optional trailing `B`/`iB` is accepted.
+pub fn parse_byte_size(value: &str) -> Result<u64, String> {
+    let trimmed = value.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let upper = upper
+        .strip_suffix("IB")
+        .or_else(|| upper.strip_suffix('B'))
+        .unwrap_or(&upper);
+    let (number, shift) = match upper.chars().last() {
+        Some('K') => (&upper[..upper.len() - 1], 10),
+        Some('M') => (&upper[..upper.len() - 1], 20),
+        Some('G') => (&upper[..upper.len() - 1], 30),
+        Some('T') => (&upper[..upper.len() - 1], 40),
+        _ => (upper, 0),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(1u64 << shift))
+        .ok_or_else(|| format!("invalid size '{trimmed}' (expected e.g. 500M, 2G, 1048576)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ToolState;
+    use chrono::TimeZone;
+
+    fn session(id: &str, tool: Option<&str>, days_ago: i64) -> MetaSessionState {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let mut session = MetaSessionState {
+            meta_session_id: id.to_string(),
+            last_accessed: now - Duration::days(days_ago),
+            ..MetaSessionState::default()
+        };
+        if let Some(tool) = tool {
+            session.tools.insert(
+                tool.to_string(),
+                ToolState {
+                    provider_session_id: None,
+                    last_action_summary: String::new(),
+                    last_exit_code: 0,
+                    updated_at: session.last_accessed,
+                    tool_version: None,
+                    token_usage: None,
+                },
+            );
+        }
+        session
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+    }
+
+    fn plan(sessions: &[MetaSessionState], policy: &SessionGcPolicy) -> Vec<(String, GcCategory)> {
+        plan_session_removals(
+            sessions,
+            policy,
+            now(),
+            |_| 100,
+            |s| s.meta_session_id == "pinned",
+        )
+        .into_iter()
+        .map(|removal| (removal.session_id, removal.category))
+        .collect()
+    }
+
+    #[test]
+    fn empty_and_expired_sessions_respect_tool_filter_and_protection() {
+        let sessions = [
+            session("empty", None, 0),
+            session("old-codex", Some("codex"), 10),
+            session("old-gemini", Some("gemini-cli"), 10),
+            session("fresh-codex", Some("codex"), 1),
+            session("pinned", Some("codex"), 30),
+        ];
+        let mut policy = SessionGcPolicy {
+            max_age: Some(Duration::days(7)),
+            ..SessionGcPolicy::default()
+        };
+        assert_eq!(
+            plan(&sessions, &policy),
+            [
+                ("empty".to_string(), GcCategory::EmptySessions),
+                ("old-codex".to_string(), GcCategory::ExpiredSessions),
+                ("old-gemini".to_string(), GcCategory::ExpiredSessions),
+            ]
+        );
+
+        policy.tool = Some("codex".to_string());
+        assert_eq!(
+            plan(&sessions, &policy),
+            [("old-codex".to_string(), GcCategory::ExpiredSessions)]
+        );
+    }
+
+    #[test]
+    fn size_budget_removes_least_recently_accessed_first() {
+        let sessions = [
+            session("newest", Some("codex"), 1),
+            session("pinned", Some("codex"), 9),
+            session("oldest", Some("codex"), 8),
+            session("middle", Some("codex"), 5),
+        ];
+        let policy = SessionGcPolicy {
+            max_total_size: Some(250),
+            ..SessionGcPolicy::default()
+        };
+        // 400 bytes total; the pinned session is skipped but still counted.
+        assert_eq!(
+            plan(&sessions, &policy),
+            [
+                ("oldest".to_string(), GcCategory::OverBudgetSessions),
+                ("middle".to_string(), GcCategory::OverBudgetSessions),
+            ]
+        );
+    }
+
+    #[test]
+    fn reclaimed_totals_merge_and_serialize_by_category() {
+        let mut reclaimed = GcReclaimed::default();
+        reclaimed.record(GcCategory::ExpiredSessions, 10);
+        let mut other = GcReclaimed::default();
+        other.add(GcCategory::ExpiredSessions, 2, 5);
+        other.record(GcCategory::OrphanDirs, 7);
+        reclaimed.merge(&other);
+
+        assert_eq!(
+            reclaimed.get(GcCategory::ExpiredSessions),
+            GcTotals {
+                count: 3,
+                bytes: 15
+            }
+        );
+        assert_eq!(reclaimed.total_bytes(), 22);
+        assert_eq!(
+            serde_json::to_value(&reclaimed).unwrap(),
+            serde_json::json!({
+                "expired_sessions": { "count": 3, "bytes": 15 },
+                "orphan_dirs": { "count": 1, "bytes": 7 },
+            })
+        );
+    }
+
+    #[test]
+    fn parse_byte_size_accepts_binary_units() {
+        assert_eq!(parse_byte_size("1048576"), Ok(1_048_576));
+        assert_eq!(parse_byte_size("500M"), Ok(500 << 20));
+        assert_eq!(parse_byte_size("2GiB"), Ok(2 << 30));
+        assert_eq!(parse_byte_size("4kb"), Ok(4096));
+        assert!(parse_byte_size("lots").is_err());
+        assert!(parse_byte_size("").is_err());
+    }
+}
@@ -66,7 +66,7 @@ fn session_roots_with_legacy(project_path: &Path) -> Result<Vec<PathBuf>> {
     let primary_root = get_session_root(project_path)?;
     let mut roots = vec![primary_root.clone()];
 
-    let Some(primary_state_dir) = csa_config::paths::state_dir_write() else {
+    let Some(primary_state_dir) = csa_config::paths::session_state_dir() else {
         return Ok(roots);
     };
     let Some(legacy_state_dir) = csa_config::paths::legacy_state_dir() else {
@@ -17,4 +17,12 @@ pub struct KillDiagnosticReport {
     pub soft_limit_percent: Option<u8>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scope_name: Option<String>,
+    /// Sections parsed so far and a partial return packet were saved before
+    /// CSA terminated the tool.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub partial_results_saved: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
@@ -11,18 +11,22 @@ pub mod convergence;
 pub mod cooldown;
 pub mod event_writer;
 pub mod finding_id;
+pub mod gc;
 pub mod genealogy;
 pub mod git;
 pub mod jj_journal;
 pub mod kill_diagnostics;
 pub mod large_diff_warning;
+pub mod lineage;
 pub mod manager;
 pub mod metadata;
 pub mod output_parser;
 pub mod output_section;
+pub mod pin;
 pub mod post_exec_gate_report;
 mod process_tree_memory;
 pub mod redact;
+pub mod resource_report;
 pub mod result;
 pub mod review_artifact;
 pub mod soft_fork;
@@ -144,27 +148,33 @@ pub use convergence::{
     verify_merge_attestation, verify_terminal_artifact_pair,
 };
 pub use state::{
-    ContextStatus, FixConvergenceMeta, Genealogy, MetaSessionState, PhaseEvent,
-    ResourceResolutionInfo, ResourceValueSource, ReviewSessionMeta, SandboxInfo, SessionPhase,
-    SourcedResourceValue, TaskContext, TokenUsage, ToolState, write_review_meta,
+    ContextStatus, FixConvergenceMeta, Genealogy, MetaSessionState, PhaseEvent, PressureAction,
+    PressureEvent, ResourceResolutionInfo, ResourceValueSource, ReviewSessionMeta, SandboxInfo,
+    SessionPhase, SourcedResourceValue, TaskContext, TokenUsage, ToolState, write_review_meta,
 };
 
 pub use metadata::SessionMetadata;
 
 pub use event_writer::{EventWriteStats, EventWriter};
 pub use finding_id::{FindingId, anchor_hash, normalize_path};
+pub use gc::{
+    GcCategory, GcReclaimed, GcTotals, SessionGcPolicy, SessionRemoval, parse_byte_size,
+    plan_session_removals,
+};
 pub use jj_journal::JjJournal;
 pub use kill_diagnostics::KillDiagnosticReport;
 pub use large_diff_warning::LargeDiffWarningReport;
 pub use output_parser::{
-    estimate_tokens, load_output_index, parse_return_packet, persist_structured_output,
-    persist_structured_output_from_file, read_all_sections, read_section,
-    validate_return_packet_path,
+    PARTIAL_RETURN_PACKET_FILE, add_return_packet_changed_files, clear_partial_return_packet,
+    estimate_tokens, load_output_index, merge_partial_return_packet, parse_return_packet,
+    persist_structured_output, persist_structured_output_from_file, read_all_sections,
+    read_section, validate_return_packet_path, write_partial_return_packet,
 };
 pub use output_section::{
     ChangedFile, FileAction, OutputIndex, OutputSection, RETURN_PACKET_MAX_SUMMARY_CHARS,
     RETURN_PACKET_SECTION_ID, ReturnPacket, ReturnPacketRef, ReturnStatus,
 };
+pub use pin::{PIN_MARKER_FILE, is_session_pinned, set_session_pinned};
 pub use post_exec_gate_report::{
     GATE_FAILURE_LOG_REL_PATH, GATE_OUTPUT_TAIL_MAX_BYTES, GATE_OUTPUT_TAIL_MAX_LINES,
     GATE_SUMMARY_LEAD, PostExecGateReport, bound_output_tail, parse_failing_step,
@@ -172,6 +182,9 @@ pub use post_exec_gate_report::{
 };
 pub use process_tree_memory::{SessionTreeMemorySampler, session_tree_rss_mb};
 pub use redact::{redact_event, redact_text_content};
+pub use resource_report::{
+    RESOURCE_REPORT_FILE_NAME, SessionResourceReport, write_resource_report,
+};
 pub use result::{
     MemorySoftLimitRecoveryDiagnostic, NO_PROVIDER_LAUNCH_ARTIFACT_PATH,
     NO_PROVIDER_LAUNCH_SCHEMA_VERSION, NoProviderLaunchDiagnostic,
@@ -180,9 +193,10 @@ pub use result::{
     write_no_provider_launch_diagnostic,
 };
 pub use review_artifact::{
-    Finding, FindingsFile, REVIEW_VERDICT_SCHEMA_VERSION, ReviewArtifact, ReviewDiffSize,
-    ReviewFinding, ReviewFindingFileRange, ReviewVerdictArtifact, Severity, SeveritySummary,
-    write_findings_toml, write_review_verdict,
+    Finding, FindingsFile, InfrastructureNote, InfrastructureNoteKind,
+    REVIEW_VERDICT_SCHEMA_VERSION, ReviewArtifact, ReviewDiffSize, ReviewFinding,
+    ReviewFindingFileRange, ReviewVerdictArtifact, Severity, SeveritySummary, write_findings_toml,
+    write_review_verdict,
 };
 pub use session_output_artifact::{publish_session_output_artifact, read_session_output_artifact};
 pub use soft_fork::{SoftForkContext, soft_fork_session};
@@ -191,18 +205,19 @@ pub use vcs_backends::{GitBackend, JjBackend, create_vcs_backend};
 // Re-export manager functions
 pub use manager::{
     CONTRACT_RESULT_ARTIFACT_PATH, LEGACY_USER_RESULT_ARTIFACT_PATH, RESULT_TOML_PATH_CONTRACT_ENV,
-    RepoWriteAudit, SaveOptions, SignalResultMetadata, clear_manager_sidecar, complete_session,
-    compute_repo_write_audit, contract_result_path, create_session, create_session_fresh,
-    create_session_with_daemon_env, decode_session_created_at, delete_session,
-    delete_session_from_root, detect_git_head, existing_next_turn_contract_result_artifact_path,
-    existing_turn_contract_result_artifact_path, find_sessions, get_session_dir,
-    get_session_dir_global, get_session_dir_global_durable, get_session_root,
-    is_manager_result_artifact_path, latest_manager_result_artifact_path, legacy_user_result_path,
-    list_all_project_session_roots, list_all_sessions, list_all_sessions_all_projects,
-    list_artifacts, list_sessions, list_sessions_from_root, list_sessions_from_root_readonly,
-    list_sessions_readonly, load_metadata, load_result, load_result_view, load_session,
-    load_session_global_exact, next_turn_contract_result_artifact_path,
-    next_turn_contract_result_path, observed_session_artifact, redact_result_sidecar_value,
+    RepoWriteAudit, SaveOptions, SignalResultMetadata, alias_session_root, clear_manager_sidecar,
+    complete_session, compute_repo_write_audit, contract_result_path, create_session,
+    create_session_fresh, create_session_with_daemon_env, decode_session_created_at,
+    delete_session, delete_session_from_root, detect_git_head,
+    existing_next_turn_contract_result_artifact_path, existing_turn_contract_result_artifact_path,
+    find_sessions, get_session_dir, get_session_dir_global, get_session_dir_global_durable,
+    get_session_root, is_manager_result_artifact_path, latest_manager_result_artifact_path,
+    legacy_user_result_path, list_all_project_session_roots, list_all_sessions,
+    list_all_sessions_all_projects, list_artifacts, list_sessions, list_sessions_from_root,
+    list_sessions_from_root_readonly, list_sessions_readonly, load_metadata, load_result,
+    load_result_view, load_session, load_session_global_exact,
+    next_turn_contract_result_artifact_path, next_turn_contract_result_path,
+    observed_session_artifact, redact_result_sidecar_value, remove_session_root_alias,
     render_redacted_result_sidecar, resolve_fork_source, resolve_resume_session, save_result,
     save_result_with_options, save_result_with_signal_metadata, save_session, save_session_in,
     turn_contract_result_artifact_path, turn_contract_result_path, update_last_accessed,
@@ -218,6 +233,9 @@ pub use manager::SessionResultView;
 
 // Re-export genealogy functions
 pub use genealogy::{find_children, list_sessions_tree, list_sessions_tree_filtered};
+pub use lineage::{
+    InheritedLineage, Lineage, LineageError, child_lineage_token, verify_inherited_lineage,
+};
 
 // Re-export validation functions
 pub use validate::{new_session_id, resolve_session_prefix, validate_session_id};
@@ -0,0 +1,438 @@
+//! Signed recursion lineage passed across process boundaries.
+//!
+//! `CSA_DEPTH` is advisory: a tool can drop it or rewrite it before invoking a
+//! nested `csa`. Next to it the executor passes `CSA_LINEAGE`, which holds the
+//! depth the child runs at and the session chain from the root session down to
+//! the spawning session. The token is authenticated with HMAC-SHA256 under a
+//! per-user key kept in the state directory. A nested `csa` verifies the token
+//! and never runs shallower than the token proves.
+//!
+//! The key is readable by the user's own processes. The token therefore guards
+//! against lost or casually rewritten variables, not a forger who reads the key.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result, bail};
+use data_encoding::HEXLOWER;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::state::{Genealogy, MetaSessionState};
+
+/// Key file under the CSA state directory.
+pub const LINEAGE_KEY_FILE: &str = "lineage.key";
+
+const TOKEN_VERSION: &str = "v1";
+const KEY_LEN: usize = 32;
+/// Upper bound on ancestor walks; far beyond any configured recursion depth.
+const MAX_CHAIN_LEN: usize = 64;
+/// Upper bound on the `state.toml` files one genealogy cycle check reads.
+const MAX_GENEALOGY_READS: usize = 256;
+
+/// Verified recursion lineage: the depth a process runs at and the session
+/// chain (root first) that spawned it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lineage {
+    pub depth: u32,
+    pub chain: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LineageError {
+    #[error("malformed lineage token")]
+    Malformed,
+    #[error("lineage token signature does not match")]
+    BadSignature,
+}
+
+impl Lineage {
+    /// `v1.<depth>.<id>,<id>,...` followed by `.<hex hmac>`.
+    pub fn encode(&self, key: &[u8]) -> String {
+        let payload = self.payload();
+        let mac = HEXLOWER.encode(&hmac_sha256(key, payload.as_bytes()));
+        format!("{payload}.{mac}")
+    }
+
+    pub fn decode(token: &str, key: &[u8]) -> Result<Self, LineageError> {
+        let (payload, mac) = token
+            .trim()
+            .rsplit_once('.')
+            .ok_or(LineageError::Malformed)?;
+        let mac = HEXLOWER
+            .decode(mac.as_bytes())
+            .map_err(|_| LineageError::BadSignature)?;
+        if !hmac_sha256_verify(key, payload.as_bytes(), &mac) {
+            return Err(LineageError::BadSignature);
+        }
+        let mut parts = payload.splitn(3, '.');
+        if parts.next() != Some(TOKEN_VERSION) {
+            return Err(LineageError::Malformed);
+        }
+        let depth = parts
+            .next()
+            .and_then(|depth| depth.parse().ok())
+            .ok_or(LineageError::Malformed)?;
+        let chain: Vec<String> = parts
+            .next()
+            .ok_or(LineageError::Malformed)?
+            .split(',')
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect();
+        if chain.len() > MAX_CHAIN_LEN {
+            return Err(LineageError::Malformed);
+        }
+        Ok(Self { depth, chain })
+    }
+
+    /// The session that spawned the process holding this lineage.
+    pub fn current_session(&self) -> Option<&str> {
+        self.chain.last().map(String::as_str)
+    }
+
+    /// Whether the session with full ID `session_id` is on the chain. A
+    /// prefix never matches; resolve it to one session first.
+    pub fn contains(&self, session_id: &str) -> bool {
+        let session_id = session_id.trim();
+        self.chain
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(session_id))
+    }
+
+    fn payload(&self) -> String {
+        format!("{TOKEN_VERSION}.{}.{}", self.depth, self.chain.join(","))
+    }
+}
+
+/// Result of reconciling the inherited `CSA_LINEAGE`, `CSA_DEPTH` and
+/// `CSA_SESSION_ID` at process startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InheritedLineage {
+    pub lineage: Lineage,
+    /// Set when the inherited variables disagreed or could not be verified.
+    pub warning: Option<String>,
+}
+
+/// Verify the inherited lineage with the user's key. See
+/// [`verify_inherited_lineage_with_key`].
+pub fn verify_inherited_lineage(
+    token: Option<&str>,
+    claimed_depth: u32,
+    claimed_session: Option<&str>,
+    session_dir: Option<&Path>,
+) -> InheritedLineage {
+    // Only touch (and possibly create) the key file when there is a token.
+    let key = token.and_then(|_| lineage_key());
+    verify_inherited_lineage_with_key(
+        token,
+        claimed_depth,
+        claimed_session,
+        session_dir,
+        key.as_deref(),
+    )
+}
+
+/// Work out the true depth and chain of a starting process.
+///
+/// A valid token whose chain ends at `claimed_session` wins. Without one, the
+/// chain is rebuilt from the spawning session's genealogy on disk
+/// (`session_dir`). Either way the depth is never lower than `claimed_depth`,
+/// so a lost token cannot reset recursion accounting.
+pub fn verify_inherited_lineage_with_key(
+    token: Option<&str>,
+    claimed_depth: u32,
+    claimed_session: Option<&str>,
+    session_dir: Option<&Path>,
+    key: Option<&[u8]>,
+) -> InheritedLineage {
+    let mut warning = None;
+    if let Some(token) = token.filter(|token| !token.trim().is_empty()) {
+        let decoded = match key {
+            Some(key) => Lineage::decode(token, key).map_err(|err| err.to_string()),
+            None => Err("no lineage key available".to_string()),
+        };
+        match decoded {
+            Ok(lineage) if lineage.current_session() == claimed_session => {
+                return raise_depth(lineage, claimed_depth, "signed lineage");
+            }
+            Ok(_) => {
+                warning = Some(
+                    "ignoring CSA_LINEAGE: its chain does not end at CSA_SESSION_ID".to_string(),
+                )
+            }
+            Err(err) => warning = Some(format!("ignoring CSA_LINEAGE: {err}")),
+        }
+    }
+
+    let (Some(session_id), Some(session_dir)) = (claimed_session, session_dir) else {
+        return InheritedLineage {
+            lineage: Lineage {
+                depth: claimed_depth,
+                chain: claimed_session.map(str::to_string).into_iter().collect(),
+            },
+            warning,
+        };
+    };
+    let rebuilt = read_genealogy(session_dir).and_then(|genealogy| {
+        let sessions_dir = session_dir
+            .parent()
+            .context("session directory has no parent")?;
+        let mut chain = ancestor_chain_in(
+            sessions_dir,
+            genealogy.parent_session_id.as_deref(),
+            session_id,
+        )?;
+        chain.push(session_id.to_string());
+        Ok(Lineage {
+            depth: genealogy.depth.saturating_add(1),
+            chain,
+        })
+    });
+    match rebuilt {
+        Ok(lineage) => {
+            let mut inherited = raise_depth(lineage, claimed_depth, "session genealogy");
+            inherited.warning = inherited.warning.or(warning);
+            inherited
+        }
+        Err(err) => InheritedLineage {
+            lineage: Lineage {
+                depth: claimed_depth,
+                chain: vec![session_id.to_string()],
+            },
+            warning: Some(format!(
+                "{}could not rebuild lineage of session {session_id}: {err:#}",
+                warning.map(|w| format!("{w}; ")).unwrap_or_default()
+            )),
+        },
+    }
+}
+
+fn raise_depth(mut lineage: Lineage, claimed_depth: u32, source: &str) -> InheritedLineage {
+    let warning = (claimed_depth < lineage.depth).then(|| {
+        format!(
+            "CSA_DEPTH={claimed_depth} is lower than the {source} depth {}; using {}",
+            lineage.depth, lineage.depth
+        )
+    });
+    lineage.depth = lineage.depth.max(claimed_depth);
+    InheritedLineage { lineage, warning }
+}
+
+/// Signed `CSA_LINEAGE` value for a tool spawned by `session`: the session's
+/// ancestor chain plus the session itself, at the session's child depth.
+///
+/// Returns `None` (and logs) when the key or the ancestor chain is unavailable.
+pub fn child_lineage_token(session: &MetaSessionState) -> Option<String> {
+    let key = lineage_key()?;
+    let session_dir =
+        crate::manager::get_session_dir(Path::new(&session.project_path), &session.meta_session_id)
+            .ok()?;
+    let sessions_dir = session_dir.parent()?;
+    let mut chain = match ancestor_chain_in(
+        sessions_dir,
+        session.genealogy.parent_session_id.as_deref(),
+        &session.meta_session_id,
+    ) {
+        Ok(chain) => chain,
+        Err(err) => {
+            tracing::warn!(session_id = %session.meta_session_id, error = %err, "Not passing CSA_LINEAGE");
+            return None;
+        }
+    };
+    chain.push(session.meta_session_id.clone());
+    Some(
+        Lineage {
+            depth: session.genealogy.depth.saturating_add(1),
+            chain,
+        }
+        .encode(&key),
+    )
+}
+
+/// Ancestors of `session_id` (root first), starting from `parent_id`, read
+/// from the `state.toml` files in `sessions_dir`.
+///
+/// The walk stops at the first ancestor whose state is missing or unreadable
+/// (for example a parent in another project). It fails when the parent links
+/// loop, including back to `session_id` itself.
+pub fn ancestor_chain_in(
+    sessions_dir: &Path,
+    parent_id: Option<&str>,
+    session_id: &str,
+) -> Result<Vec<String>> {
+    let mut chain: Vec<String> = Vec::new();
+    let mut next = parent_id.map(str::to_string);
+    while let Some(id) = next {
+        if id == session_id || chain.contains(&id) {
+            bail!("genealogy cycle through session {id} in the ancestry of {session_id}");
+        }
+        if chain.len() >= MAX_CHAIN_LEN {
+            bail!("genealogy of {session_id} is deeper than {MAX_CHAIN_LEN} sessions");
+        }
+        next = match read_genealogy(&sessions_dir.join(&id)) {
+            Ok(genealogy) => genealogy.parent_session_id,
+            Err(err) => {
+                tracing::debug!(session_id = %id, error = %err, "Ancestor state unavailable; ending walk");
+                None
+            }
+        };
+        chain.push(id);
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Reject a state whose parent or fork links would make it its own ancestor.
+///
+/// Only runs when the links differ from the ones already on disk, i.e. when
+/// a session is created, forked or re-parented. Both links of every ancestor
+/// are followed. Each ancestor's `state.toml` is read at most once, and the
+/// walk ends after `MAX_GENEALOGY_READS` sessions. Missing or unreadable
+/// ancestors end their branch of the walk.
+pub(crate) fn ensure_acyclic_genealogy(
+    sessions_dir: &Path,
+    state: &MetaSessionState,
+) -> Result<()> {
+    let session_id = state.meta_session_id.as_str();
+    if let Ok(stored) = read_genealogy(&sessions_dir.join(session_id))
+        && stored.parent_session_id == state.genealogy.parent_session_id
+        && stored.fork_of_session_id == state.genealogy.fork_of_session_id
+    {
+        return Ok(());
+    }
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut pending: Vec<String> = genealogy_links(&state.genealogy).collect();
+    while let Some(id) = pending.pop() {
+        if id == session_id {
+            bail!("genealogy cycle: session {session_id} would be its own parent or fork ancestor");
+        }
+        if !visited.insert(id.clone()) {
+            continue;
+        }
+        if visited.len() > MAX_GENEALOGY_READS {
+            tracing::warn!(
+                session_id,
+                "Genealogy spans more than {MAX_GENEALOGY_READS} sessions; ending cycle check"
+            );
+            break;
+        }
+        match read_genealogy(&sessions_dir.join(&id)) {
+            Ok(genealogy) => pending.extend(genealogy_links(&genealogy)),
+            Err(err) => {
+                tracing::debug!(session_id = %id, error = %err, "Ancestor state unavailable; ending walk");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The parent and fork-source IDs of a genealogy.
+fn genealogy_links(genealogy: &Genealogy) -> impl Iterator<Item = String> + '_ {
+    genealogy
+        .parent_session_id
+        .iter()
+        .chain(&genealogy.fork_of_session_id)
+        .cloned()
+}
+
+fn read_genealogy(session_dir: &Path) -> Result<Genealogy> {
+    #[derive(Deserialize)]
+    struct GenealogyOnly {
+        genealogy: Genealogy,
+    }
+    let path = session_dir.join("state.toml");
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let state: GenealogyOnly =
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(state.genealogy)
+}
+
+/// The per-user lineage key, created on first use. Cached per process.
+pub fn lineage_key() -> Option<Vec<u8>> {
+    static KEY: OnceLock<Option<Vec<u8>>> = OnceLock::new();
+    KEY.get_or_init(|| {
+        let dir = csa_config::paths::session_state_dir()?;
+        load_or_create_key(&dir)
+            .map_err(|err| tracing::warn!(error = %err, "Lineage key unavailable"))
+            .ok()
+    })
+    .clone()
+}
+
+fn load_or_create_key(dir: &Path) -> Result<Vec<u8>> {
+    let path = dir.join(LINEAGE_KEY_FILE);
+    if let Some(key) = read_key(&path)? {
+        return Ok(key);
+    }
+    csa_config::paths::create_session_state_dir()
+        .context("failed to create the session state directory")?;
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let key = random_key()?;
+    // Write the whole key to a private temp file and link it into place, so
+    // a concurrent reader never sees a created but still empty key file.
+    let mut temp = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("failed to create a temp file in {}", dir.display()))?;
+    temp.write_all(HEXLOWER.encode(&key).as_bytes())
+        .and_then(|()| temp.as_file().sync_all())
+        .with_context(|| format!("failed to write {}", temp.path().display()))?;
+    match temp.persist_noclobber(&path) {
+        Ok(_) => Ok(key),
+        // Another process created it first; use theirs.
+        Err(err) if err.error.kind() == ErrorKind::AlreadyExists => {
+            read_key(&path)?.with_context(|| format!("{} is empty", path.display()))
+        }
+        Err(err) => Err(err.error).with_context(|| format!("failed to create {}", path.display())),
+    }
+}
+
+fn read_key(path: &Path) -> Result<Option<Vec<u8>>> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+    };
+    if raw.trim().is_empty() {
+        return Ok(None);
+    }
+    let key = HEXLOWER
+        .decode(raw.trim().as_bytes())
+        .with_context(|| format!("{} is not a hex key", path.display()))?;
+    if key.len() != KEY_LEN {
+        bail!("{} must hold {KEY_LEN} bytes", path.display());
+    }
+    Ok(Some(key))
+}
+
+fn random_key() -> Result<Vec<u8>> {
+    let mut key = vec![0u8; KEY_LEN];
+    getrandom::fill(&mut key)
+        .map_err(|err| anyhow::anyhow!("failed to generate lineage key: {err}"))?;
+    Ok(key)
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key) else {
+        unreachable!("HMAC-SHA256 takes keys of any length");
+    };
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// Check `tag` against the HMAC-SHA256 of `message` in constant time.
+fn hmac_sha256_verify(key: &[u8], message: &[u8], tag: &[u8]) -> bool {
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key) else {
+        unreachable!("HMAC-SHA256 takes keys of any length");
+    };
+    mac.update(message);
+    mac.verify_slice(tag).is_ok()
+}
+
+#[cfg(test)]
+#[path = "lineage_tests.rs"]
+mod tests;
@@ -0,0 +1,214 @@
+use super::*;
+use tempfile::TempDir;
+
+const KEY: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+fn lineage(depth: u32, chain: &[&str]) -> Lineage {
+    Lineage {
+        depth,
+        chain: chain.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+/// Write a minimal `state.toml` with only the genealogy table.
+fn write_state(sessions_dir: &Path, id: &str, parent: Option<&str>, depth: u32) {
+    let dir = sessions_dir.join(id);
+    fs::create_dir_all(&dir).unwrap();
+    let parent = parent
+        .map(|parent| format!("parent_session_id = \"{parent}\"\n"))
+        .unwrap_or_default();
+    fs::write(
+        dir.join("state.toml"),
+        format!("[genealogy]\n{parent}depth = {depth}\n"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn hmac_matches_rfc4231_test_case_2() {
+    assert_eq!(
+        HEXLOWER.encode(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn key_is_created_once_and_reused() {
+    let tmp = TempDir::new().unwrap();
+    let first = load_or_create_key(tmp.path()).unwrap();
+    assert_eq!(first.len(), KEY_LEN);
+    assert_eq!(load_or_create_key(tmp.path()).unwrap(), first);
+    // Only the key file is left behind; no temp files.
+    let entries: Vec<_> = fs::read_dir(tmp.path()).unwrap().collect();
+    assert_eq!(entries.len(), 1);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(tmp.path().join(LINEAGE_KEY_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
+
+#[test]
+fn token_round_trips_and_rejects_tampering() {
+    let original = lineage(2, &["01ROOT", "01CHILD"]);
+    let token = original.encode(KEY);
+    assert_eq!(Lineage::decode(&token, KEY), Ok(original));
+
+    let shallower = token.replacen("v1.2.", "v1.0.", 1);
+    assert_eq!(
+        Lineage::decode(&shallower, KEY),
+        Err(LineageError::BadSignature)
+    );
+    assert_eq!(
+        Lineage::decode(&token, b"another key"),
+        Err(LineageError::BadSignature)
+    );
+    assert_eq!(
+        Lineage::decode("garbage", KEY),
+        Err(LineageError::Malformed)
+    );
+}
+
+#[test]
+fn contains_matches_full_ids_only() {
+    let lineage = lineage(2, &["01JROOT000", "01JCHILD00"]);
+    assert!(lineage.contains("01JCHILD00"));
+    assert!(lineage.contains("01jroot000"));
+    assert!(!lineage.contains("01JROOT"), "a prefix is not an ancestor");
+    assert!(!lineage.contains("01J"));
+    assert!(!lineage.contains("01JOTHER"));
+    assert!(!lineage.contains(" "));
+}
+
+#[test]
+fn valid_token_sets_floor_for_spoofed_depth() {
+    let token = lineage(3, &["01A", "01B", "01C"]).encode(KEY);
+
+    let lowered = verify_inherited_lineage_with_key(Some(&token), 0, Some("01C"), None, Some(KEY));
+    assert_eq!(lowered.lineage.depth, 3);
+    assert_eq!(lowered.lineage.chain, ["01A", "01B", "01C"]);
+    assert!(lowered.warning.unwrap().contains("CSA_DEPTH=0"));
+
+    let deeper = verify_inherited_lineage_with_key(Some(&token), 4, Some("01C"), None, Some(KEY));
+    assert_eq!(deeper.lineage.depth, 4);
+    assert_eq!(deeper.warning, None);
+}
+
+#[test]
+fn lost_or_mismatched_token_is_rebuilt_from_genealogy() {
+    let tmp = TempDir::new().unwrap();
+    let sessions = tmp.path();
+    write_state(sessions, "01ROOT", None, 0);
+    write_state(sessions, "01MID", Some("01ROOT"), 1);
+    write_state(sessions, "01LEAF", Some("01MID"), 2);
+    let leaf_dir = sessions.join("01LEAF");
+
+    let lost =
+        verify_inherited_lineage_with_key(None, 0, Some("01LEAF"), Some(&leaf_dir), Some(KEY));
+    assert_eq!(lost.lineage, lineage(3, &["01ROOT", "01MID", "01LEAF"]));
+    assert!(lost.warning.unwrap().contains("session genealogy depth 3"));
+
+    let replayed = lineage(1, &["01OTHER"]).encode(KEY);
+    let mismatched = verify_inherited_lineage_with_key(
+        Some(&replayed),
+        3,
+        Some("01LEAF"),
+        Some(&leaf_dir),
+        Some(KEY),
+    );
+    assert_eq!(mismatched.lineage.depth, 3);
+    assert!(
+        mismatched
+            .warning
+            .unwrap()
+            .contains("does not end at CSA_SESSION_ID")
+    );
+
+    let top_level = verify_inherited_lineage_with_key(None, 0, None, None, Some(KEY));
+    assert_eq!(top_level, InheritedLineage::default());
+}
+
+#[test]
+fn ancestor_walk_detects_cycles() {
+    let tmp = TempDir::new().unwrap();
+    let sessions = tmp.path();
+    write_state(sessions, "01A", Some("01B"), 1);
+    write_state(sessions, "01B", Some("01A"), 1);
+
+    let err = ancestor_chain_in(sessions, Some("01A"), "01B").unwrap_err();
+    assert!(err.to_string().contains("genealogy cycle"), "{err}");
+    // A missing ancestor ends the walk instead of failing it.
+    assert_eq!(
+        ancestor_chain_in(sessions, Some("01GONE"), "01NEW").unwrap(),
+        ["01GONE"]
+    );
+}
+
+#[test]
+fn save_rejects_genealogy_that_loops() {
+    let tmp = TempDir::new().unwrap();
+    let base = tmp.path();
+    let mut root = crate::manager::create_session_in(base, base, Some("root"), None, None).unwrap();
+    let mut child = crate::manager::create_session_in(
+        base,
+        base,
+        Some("child"),
+        Some(&root.meta_session_id),
+        None,
+    )
+    .unwrap();
+
+    root.genealogy.parent_session_id = Some(child.meta_session_id.clone());
+    let err = crate::manager::save_session_in(base, &root).unwrap_err();
+    assert!(err.to_string().contains("genealogy cycle"), "{err}");
+
+    child.genealogy.parent_session_id = Some(child.meta_session_id.clone());
+    assert!(crate::manager::save_session_in(base, &child).is_err());
+}
+
+#[test]
+fn save_rejects_fork_links_that_loop() {
+    let tmp = TempDir::new().unwrap();
+    let base = tmp.path();
+    let source = crate::manager::create_session_in(base, base, Some("source"), None, None).unwrap();
+    let mut fork = crate::manager::create_session_in(base, base, Some("fork"), None, None).unwrap();
+    fork.genealogy.fork_of_session_id = Some(source.meta_session_id.clone());
+    crate::manager::save_session_in(base, &fork).unwrap();
+
+    // source -> (fork of) fork -> (fork of) source.
+    let mut source = source;
+    source.genealogy.fork_of_session_id = Some(fork.meta_session_id.clone());
+    let err = crate::manager::save_session_in(base, &source).unwrap_err();
+    assert!(err.to_string().contains("genealogy cycle"), "{err}");
+
+    // A parent link into a fork chain that leads back is caught too.
+    source.genealogy.fork_of_session_id = None;
+    source.genealogy.parent_session_id = Some(fork.meta_session_id.clone());
+    assert!(crate::manager::save_session_in(base, &source).is_err());
+}
+
+#[test]
+fn save_at_the_end_of_a_long_chain_succeeds() {
+    let tmp = TempDir::new().unwrap();
+    let base = tmp.path();
+    let sessions = base.join("sessions");
+    let ids: Vec<String> = (0..MAX_GENEALOGY_READS + 44)
+        .map(|n| format!("01CHAIN{n:04}"))
+        .collect();
+    for (n, id) in ids.iter().enumerate() {
+        let parent = n.checked_sub(1).map(|prev| ids[prev].as_str());
+        write_state(&sessions, id, parent, n as u32);
+    }
+
+    let mut leaf = crate::manager::create_session_in(base, base, Some("leaf"), None, None).unwrap();
+    leaf.genealogy.parent_session_id = ids.last().cloned();
+    crate::manager::save_session_in(base, &leaf).unwrap();
+
+    // Later saves with unchanged links skip the walk entirely.
+    leaf.turn_count += 1;
+    crate::manager::save_session_in(base, &leaf).unwrap();
+}
@@ -37,12 +37,14 @@ use manager_daemon::{SessionIdStrategy, preassigned_daemon_session_id_from_env};
 pub use manager_legacy::decode_session_created_at;
 #[cfg(test)]
 use manager_paths::project_storage_key_from_path;
-pub use manager_paths::{get_session_dir, get_session_root};
+pub use manager_paths::{
+    alias_session_root, get_session_dir, get_session_root, remove_session_root_alias,
+};
 pub use manager_paths::{
     get_session_dir_global, get_session_dir_global_durable, list_all_project_session_roots,
 };
 use manager_paths::{get_session_dir_in, resolve_read_base_dir, resolve_write_base_dir};
-use manager_paths::{legacy_session_root, normalize_project_path};
+use manager_paths::{legacy_session_root, normalize_project_path, session_root_for_write};
 pub use manager_result::{
     CONTRACT_RESULT_ARTIFACT_PATH, LEGACY_USER_RESULT_ARTIFACT_PATH, RESULT_TOML_PATH_CONTRACT_ENV,
     SaveOptions, SessionResultView, SignalResultMetadata, clear_manager_sidecar,
@@ -80,7 +82,7 @@ pub fn create_session(
     parent_id: Option<&str>,
     tool: Option<&str>,
 ) -> Result<MetaSessionState> {
-    let base_dir = get_session_root(project_path)?;
+    let base_dir = session_root_for_write(project_path)?;
     create_session_in_with_strategy(
         &base_dir,
         project_path,
@@ -99,7 +101,7 @@ pub fn create_session_fresh(
     parent_id: Option<&str>,
     tool: Option<&str>,
 ) -> Result<MetaSessionState> {
-    let base_dir = get_session_root(project_path)?;
+    let base_dir = session_root_for_write(project_path)?;
     create_session_in_with_strategy(
         &base_dir,
         project_path,
@@ -239,6 +241,7 @@ fn create_session_in_with_strategy(
         token_budget: None,
         sandbox_info: None,
         termination_reason: None,
+        last_model: None,
         is_seed_candidate: false,
         git_head_at_creation: git_head,
         pre_session_porcelain,
@@ -247,6 +250,8 @@ fn create_session_in_with_strategy(
         spec_id: None,
         vcs_identity: identity,
         identity_version: 2,
+        pressure_events: Vec::new(),
+        user: Some(csa_config::shared_state::current_user().to_string()),
         fork_call_timestamps: Vec::new(),
     };
 
@@ -336,6 +341,7 @@ pub fn save_session(state: &MetaSessionState) -> Result<()> {
 
 ///// Save session state to an explicit base directory.
 pub fn save_session_in(base_dir: &Path, state: &MetaSessionState) -> Result<()> {
+    crate::lineage::ensure_acyclic_genealogy(&base_dir.join("sessions"), state)?;
     let session_dir = get_session_dir_in(base_dir, &state.meta_session_id);
     let state_path = session_dir.join(STATE_FILE_NAME);
 
@@ -2,23 +2,78 @@ use anyhow::{Context, Result};
 use csa_config::paths;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
 use tracing::warn;
 
-/// Get the session root directory for a project (`~/.local/state/cli-sub-agent/{project_path}`)
+/// `(alias, project root)` pairs registered by [`alias_session_root`].
+static SESSION_ROOT_ALIASES: Mutex<Vec<(PathBuf, PathBuf)>> = Mutex::new(Vec::new());
+
+/// Store the sessions of `alias` and the directories below it under the
+/// session root of `project_root` for the rest of this process, e.g. for a
+/// throwaway worktree of that project. Sessions keep `alias` as their
+/// `project_path`.
+pub fn alias_session_root(alias: &Path, project_root: &Path) {
+    let alias = normalize_project_path(alias);
+    let project_root = normalize_project_path(project_root);
+    let mut aliases = SESSION_ROOT_ALIASES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    aliases.retain(|(existing, _)| *existing != alias);
+    aliases.push((alias, project_root));
+}
+
+/// Undo [`alias_session_root`] for `alias`.
+pub fn remove_session_root_alias(alias: &Path) {
+    let alias = normalize_project_path(alias);
+    SESSION_ROOT_ALIASES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain(|(existing, _)| *existing != alias);
+}
+
+/// `project_path` with a registered alias prefix replaced by its project root.
+fn storage_project_path(project_path: &Path) -> PathBuf {
+    let aliases = SESSION_ROOT_ALIASES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    for (alias, project_root) in aliases.iter() {
+        if let Ok(rest) = project_path.strip_prefix(alias) {
+            if rest.as_os_str().is_empty() {
+                return project_root.clone();
+            }
+            return project_root.join(rest);
+        }
+    }
+    project_path.to_path_buf()
+}
+
+/// Get the session root directory for a project (`~/.local/state/cli-sub-agent/{project_path}`,
+/// under `users/<user>/` in shared-state mode)
 pub fn get_session_root(project_path: &Path) -> Result<PathBuf> {
-    let state_dir = paths::state_dir_write().context("Failed to determine project directories")?;
-    let normalized = normalize_project_path(project_path);
+    let state_dir =
+        paths::session_state_dir().context("Failed to determine project directories")?;
+    let normalized = storage_project_path(&normalize_project_path(project_path));
     Ok(state_dir.join(project_storage_key(&normalized)))
 }
 
+/// [`get_session_root`] for a new session, after creating the user's state
+/// directory (see [`paths::create_session_state_dir`]).
+pub(super) fn session_root_for_write(project_path: &Path) -> Result<PathBuf> {
+    paths::create_session_state_dir().context("Failed to create session state directory")?;
+    get_session_root(project_path)
+}
+
 pub(super) fn legacy_session_root(project_path: &Path) -> Option<PathBuf> {
-    let normalized = normalize_project_path(project_path);
+    let normalized = storage_project_path(&normalize_project_path(project_path));
     paths::legacy_state_dir().map(|state_dir| state_dir.join(project_storage_key(&normalized)))
 }
 
 fn session_roots_for_reads(project_path: &Path) -> Result<Vec<PathBuf>> {
-    let normalized = normalize_project_path(project_path);
-    let state_dir = paths::state_dir_write().context("Failed to determine project directories")?;
+    let normalized = storage_project_path(&normalize_project_path(project_path));
+    let project_path = storage_project_path(project_path);
+    let project_path = project_path.as_path();
+    let state_dir =
+        paths::session_state_dir().context("Failed to determine project directories")?;
     let mut roots = Vec::new();
 
     push_unique_root(
@@ -136,7 +191,7 @@ fn find_session_base_dir_under_matching(
 
 fn find_session_base_dir_anywhere(session_id: &str) -> Result<Option<PathBuf>> {
     let primary_state_dir =
-        paths::state_dir_write().context("Failed to determine project directories")?;
+        paths::session_state_dir().context("Failed to determine project directories")?;
     if let Some(base_dir) = find_session_base_dir_under(&primary_state_dir, session_id)? {
         return Ok(Some(base_dir));
     }
@@ -152,7 +207,7 @@ fn find_session_base_dir_anywhere(session_id: &str) -> Result<Option<PathBuf>> {
 
 fn find_durable_session_base_dir_anywhere(session_id: &str) -> Result<Option<PathBuf>> {
     let primary_state_dir =
-        paths::state_dir_write().context("Failed to determine project directories")?;
+        paths::session_state_dir().context("Failed to determine project directories")?;
     if let Some(base_dir) = find_durable_session_base_dir_under(&primary_state_dir, session_id)? {
         return Ok(Some(base_dir));
     }
@@ -356,7 +411,7 @@ pub fn list_all_project_session_roots() -> Result<Vec<(PathBuf, String)>> {
     let mut roots = Vec::new();
 
     let primary_state_dir =
-        paths::state_dir_write().context("Failed to determine project directories")?;
+        paths::session_state_dir().context("Failed to determine project directories")?;
     collect_project_roots_under(&primary_state_dir, &mut roots)?;
 
     if let Some(legacy_state_dir) = paths::legacy_state_dir() {
@@ -50,12 +50,15 @@ pub(super) fn recover_corrupt_session_state(
         token_budget: None,
         sandbox_info: None,
         termination_reason: None,
+        last_model: None,
         is_seed_candidate: false,
         git_head_at_creation: None,
         pre_session_porcelain: None,
         last_return_packet: None,
         change_id: None,
         spec_id: None,
+        pressure_events: Vec::new(),
+        user: None,
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
@@ -399,6 +399,7 @@ mod tests {
                 ),
                 ..Default::default()
             },
+            resources: None,
         };
         save_result_in(
             td.path(),
@@ -422,6 +422,35 @@ fn test_create_session_with_tool() {
     assert!(meta.tool_locked);
 }
 
+#[test]
+fn test_aliased_worktree_sessions_are_stored_under_the_project_root() {
+    let td = tempdir().unwrap();
+    let _xdg = ScopedXdgOverride::new(&td);
+    let project = td.path().join("project");
+    let worktree = td.path().join("worktree");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::create_dir_all(worktree.join("sub")).unwrap();
+
+    alias_session_root(&worktree, &project);
+    let state = create_session(&worktree, Some("isolated"), None, None).unwrap();
+    let project_root = get_session_root(&project).unwrap();
+    assert_eq!(get_session_root(&worktree).unwrap(), project_root);
+    assert_eq!(
+        get_session_root(&worktree.join("sub")).unwrap(),
+        get_session_root(&project.join("sub")).unwrap()
+    );
+    remove_session_root_alias(&worktree);
+
+    assert_ne!(get_session_root(&worktree).unwrap(), project_root);
+    let listed = list_sessions(&project, None).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].meta_session_id, state.meta_session_id);
+    assert_eq!(
+        Path::new(&listed[0].project_path),
+        worktree.canonicalize().unwrap()
+    );
+}
+
 include!("manager_tests_tail.rs");
 include!("manager_tests_tail_2.rs");
 include!("manager_tests_audit.rs");
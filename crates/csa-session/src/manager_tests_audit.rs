@@ -37,6 +37,7 @@ fn test_save_result_persists_manager_fields_sidecar() {
             ),
             ..Default::default()
         },
+        resources: None,
     };
 
     save_result_in(td.path(), &state.meta_session_id, &result, crate::SaveOptions::default())
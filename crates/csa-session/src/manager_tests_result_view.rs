@@ -454,6 +454,7 @@ fn test_save_result_with_empty_manager_fields_preserves_existing_sidecar() {
             ),
             ..Default::default()
         },
+        resources: None,
     };
     save_result_in(
         td.path(),
@@ -541,6 +542,7 @@ fn test_clear_manager_sidecar_removes_existing_sidecar() {
             ),
             ..Default::default()
         },
+        resources: None,
     };
     save_result_in(
         td.path(),
@@ -96,6 +96,7 @@ fn sidecar_write_failure_leaves_envelope_unchanged() {
             ),
             ..Default::default()
         },
+        resources: None,
     };
     save_result_in(
         td.path(),
@@ -196,6 +197,7 @@ fn sidecar_clear_failure_or_crash_leaves_envelope_consistent() {
             ),
             ..Default::default()
         },
+        resources: None,
     };
     save_result_in(
         td.path(),
@@ -275,6 +277,7 @@ fn sidecar_clear_happy_path_publishes_envelope_then_unlinks() {
             ),
             ..Default::default()
         },
+        resources: None,
     };
     save_result_in(
         td.path(),
@@ -0,0 +1,141 @@
+//! Files CSA saw the tool change, added to its return packet.
+//!
+//! Writes an ACP agent makes through CSA's client-side fs methods are known
+//! exactly, so they end up in the packet's `changed_files` even when the
+//! tool leaves them out of the packet it reports.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::{estimate_tokens, load_output_index};
+use crate::output_section::{ChangedFile, RETURN_PACKET_SECTION_ID, ReturnPacket};
+
+/// Add `changed` entries whose path is not listed yet to the persisted
+/// `return-packet` section.
+///
+/// Returns `Ok(false)` when nothing was added: there is no packet, it is not
+/// TOML, or it already lists every path.
+pub fn add_return_packet_changed_files(
+    session_dir: &Path,
+    changed: &[ChangedFile],
+) -> Result<bool> {
+    if changed.is_empty() {
+        return Ok(false);
+    }
+    let Some(mut index) = load_output_index(session_dir)? else {
+        return Ok(false);
+    };
+    let Some(section) = index
+        .sections
+        .iter_mut()
+        .find(|section| section.id == RETURN_PACKET_SECTION_ID)
+    else {
+        return Ok(false);
+    };
+    let Some(file_path) = section.file_path.clone() else {
+        return Ok(false);
+    };
+    let output_dir = session_dir.join("output");
+    let section_path = output_dir.join(&file_path);
+    let raw = fs::read_to_string(&section_path)
+        .with_context(|| format!("Failed to read section file: {}", section_path.display()))?;
+    let Ok(mut packet) = toml::from_str::<ReturnPacket>(&raw) else {
+        return Ok(false);
+    };
+
+    let before = packet.changed_files.len();
+    for file in changed {
+        if !packet
+            .changed_files
+            .iter()
+            .any(|known| known.path == file.path)
+        {
+            packet.changed_files.push(file.clone());
+        }
+    }
+    if packet.changed_files.len() == before {
+        return Ok(false);
+    }
+
+    let content = toml::to_string_pretty(&packet).context("Failed to serialize return packet")?;
+    fs::write(&section_path, &content)
+        .with_context(|| format!("Failed to write section file: {}", section_path.display()))?;
+    let token_estimate = estimate_tokens(&content);
+    index.total_tokens =
+        (index.total_tokens + token_estimate).saturating_sub(section.token_estimate);
+    section.token_estimate = token_estimate;
+
+    let index_path = output_dir.join("index.toml");
+    let index_toml = toml::to_string_pretty(&index).context("Failed to serialize output index")?;
+    fs::write(&index_path, &index_toml)
+        .with_context(|| format!("Failed to write index: {}", index_path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output_parser::{parse_return_packet, persist_structured_output, read_section};
+    use crate::output_section::FileAction;
+
+    fn changed(path: &str, action: FileAction) -> ChangedFile {
+        ChangedFile {
+            path: path.to_string(),
+            action,
+        }
+    }
+
+    #[test]
+    fn adds_unlisted_paths_to_the_return_packet() {
+        let tmp = tempfile::tempdir().unwrap();
+        persist_structured_output(
+            tmp.path(),
+            "<!-- CSA:SECTION:return-packet -->\nstatus = \"Success\"\nexit_code = 0\n\
+             summary = \"done\"\nchanged_files = [{ path = \"src/lib.rs\", action = \"Modify\" }]\n\
+             <!-- CSA:SECTION:return-packet:END -->\n",
+        )
+        .unwrap();
+
+        let added = add_return_packet_changed_files(
+            tmp.path(),
+            &[
+                changed("src/lib.rs", FileAction::Add),
+                changed("docs/notes.md", FileAction::Add),
+            ],
+        )
+        .unwrap();
+
+        assert!(added);
+        let section = read_section(tmp.path(), RETURN_PACKET_SECTION_ID)
+            .unwrap()
+            .unwrap();
+        let packet = parse_return_packet(&section).unwrap();
+        assert_eq!(packet.summary, "done");
+        assert_eq!(
+            packet.changed_files,
+            [
+                changed("src/lib.rs", FileAction::Modify),
+                changed("docs/notes.md", FileAction::Add),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_packet_or_known_paths_are_a_no_op() {
+        let tmp = tempfile::tempdir().unwrap();
+        let files = [changed("src/lib.rs", FileAction::Modify)];
+        assert!(!add_return_packet_changed_files(tmp.path(), &files).unwrap());
+
+        persist_structured_output(
+            tmp.path(),
+            "<!-- CSA:SECTION:return-packet -->\nstatus = \"Success\"\nexit_code = 0\n\
+             changed_files = [{ path = \"src/lib.rs\", action = \"Modify\" }]\n\
+             <!-- CSA:SECTION:return-packet:END -->\n",
+        )
+        .unwrap();
+        assert!(!add_return_packet_changed_files(tmp.path(), &files).unwrap());
+        assert!(!add_return_packet_changed_files(tmp.path(), &[]).unwrap());
+    }
+}
@@ -0,0 +1,147 @@
+//! Partial return packets saved when CSA terminates a tool early.
+//!
+//! The pre-kill checkpoint writes a packet describing the interruption to
+//! `partial-return-packet.toml` in the session dir. It is merged into the
+//! structured output index only when the tool never emitted a `return-packet`
+//! section of its own, so a fork-call parent always gets a packet back.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::{estimate_tokens, id_to_title, load_output_index, parse_return_packet};
+use crate::output_section::{OutputIndex, OutputSection, RETURN_PACKET_SECTION_ID, ReturnPacket};
+
+/// File name of the pending partial packet, relative to the session dir.
+pub const PARTIAL_RETURN_PACKET_FILE: &str = "partial-return-packet.toml";
+
+/// Save `packet` for a later [`merge_partial_return_packet`].
+pub fn write_partial_return_packet(session_dir: &Path, packet: &ReturnPacket) -> Result<()> {
+    let path = session_dir.join(PARTIAL_RETURN_PACKET_FILE);
+    let content = toml::to_string_pretty(packet).context("Failed to serialize return packet")?;
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Remove a partial packet left by an earlier run of the session.
+pub fn clear_partial_return_packet(session_dir: &Path) -> Result<()> {
+    let path = session_dir.join(PARTIAL_RETURN_PACKET_FILE);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("Failed to remove {}", path.display())),
+    }
+}
+
+/// Add the pending partial packet to `output/index.toml` as the
+/// `return-packet` section.
+///
+/// Returns `Ok(false)` when there is no partial packet or the tool already
+/// produced a return packet, which always takes precedence.
+pub fn merge_partial_return_packet(session_dir: &Path) -> Result<bool> {
+    let path = session_dir.join(PARTIAL_RETURN_PACKET_FILE);
+    if !path.is_file() {
+        return Ok(false);
+    }
+    let mut index = load_output_index(session_dir)?.unwrap_or(OutputIndex {
+        sections: Vec::new(),
+        total_tokens: 0,
+        total_lines: 0,
+    });
+    if index
+        .sections
+        .iter()
+        .any(|section| section.id == RETURN_PACKET_SECTION_ID)
+    {
+        return Ok(false);
+    }
+
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let packet = parse_return_packet(&raw)?;
+    let content = toml::to_string_pretty(&packet).context("Failed to serialize return packet")?;
+
+    let output_dir = session_dir.join("output");
+    fs::create_dir_all(&output_dir)
+        .with_context(|| format!("Failed to create output dir: {}", output_dir.display()))?;
+    let file_path = format!("{RETURN_PACKET_SECTION_ID}.md");
+    let section_path = output_dir.join(&file_path);
+    fs::write(&section_path, &content)
+        .with_context(|| format!("Failed to write section file: {}", section_path.display()))?;
+
+    // The packet is not part of output.log, so it gets an empty line span.
+    let token_estimate = estimate_tokens(&content);
+    index.sections.push(OutputSection {
+        id: RETURN_PACKET_SECTION_ID.to_string(),
+        title: id_to_title(RETURN_PACKET_SECTION_ID),
+        line_start: index.total_lines + 1,
+        line_end: index.total_lines,
+        token_estimate,
+        file_path: Some(file_path),
+    });
+    index.total_tokens += token_estimate;
+
+    let index_path = output_dir.join("index.toml");
+    let index_toml = toml::to_string_pretty(&index).context("Failed to serialize output index")?;
+    fs::write(&index_path, &index_toml)
+        .with_context(|| format!("Failed to write index: {}", index_path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output_parser::{persist_structured_output, read_section};
+    use crate::output_section::ReturnStatus;
+
+    fn partial_packet() -> ReturnPacket {
+        ReturnPacket {
+            status: ReturnStatus::Failure,
+            exit_code: 143,
+            summary: "out-of-memory (partial results saved)".to_string(),
+            ..ReturnPacket::default()
+        }
+    }
+
+    #[test]
+    fn merge_adds_partial_packet_after_parsed_sections() {
+        let tmp = tempfile::tempdir().unwrap();
+        persist_structured_output(
+            tmp.path(),
+            "<!-- CSA:SECTION:summary -->\nhalf done\n<!-- CSA:SECTION:summary:END -->\n",
+        )
+        .unwrap();
+        write_partial_return_packet(tmp.path(), &partial_packet()).unwrap();
+
+        assert!(merge_partial_return_packet(tmp.path()).unwrap());
+
+        let index = load_output_index(tmp.path()).unwrap().unwrap();
+        let ids: Vec<&str> = index.sections.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["summary", RETURN_PACKET_SECTION_ID]);
+        let section = read_section(tmp.path(), RETURN_PACKET_SECTION_ID)
+            .unwrap()
+            .unwrap();
+        let packet = parse_return_packet(&section).unwrap();
+        assert_eq!(packet.exit_code, 143);
+        assert_eq!(packet.summary, "out-of-memory (partial results saved)");
+    }
+
+    #[test]
+    fn tool_return_packet_wins_and_missing_packet_is_a_no_op() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(!merge_partial_return_packet(tmp.path()).unwrap());
+
+        persist_structured_output(
+            tmp.path(),
+            "<!-- CSA:SECTION:return-packet -->\nstatus = \"Success\"\nexit_code = 0\n\
+             summary = \"done\"\n<!-- CSA:SECTION:return-packet:END -->\n",
+        )
+        .unwrap();
+        write_partial_return_packet(tmp.path(), &partial_packet()).unwrap();
+        assert!(!merge_partial_return_packet(tmp.path()).unwrap());
+
+        clear_partial_return_packet(tmp.path()).unwrap();
+        clear_partial_return_packet(tmp.path()).unwrap();
+        assert!(!tmp.path().join(PARTIAL_RETURN_PACKET_FILE).exists());
+    }
+}
@@ -0,0 +1,412 @@
+use super::*;
+use std::fs;
+
+#[test]
+fn test_parse_no_markers_returns_full_section() {
+    let output = "line 1\nline 2\nline 3\n";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].id, "full");
+    assert_eq!(sections[0].line_start, 1);
+    assert_eq!(sections[0].line_end, 3);
+    assert!(sections[0].token_estimate > 0);
+}
+
+#[test]
+fn test_parse_empty_output() {
+    let sections = parse_sections("");
+    assert!(sections.is_empty());
+}
+
+#[test]
+fn test_parse_single_section_with_end_marker() {
+    let output = "preamble\n\
+                   <!-- CSA:SECTION:summary -->\n\
+                   This is the summary.\n\
+                   It has two lines.\n\
+                   <!-- CSA:SECTION:summary:END -->\n\
+                   postamble";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].id, "summary");
+    assert_eq!(sections[0].title, "Summary");
+    assert_eq!(sections[0].line_start, 3);
+    assert_eq!(sections[0].line_end, 4);
+}
+
+#[test]
+fn test_parse_multiple_sections() {
+    let output = "<!-- CSA:SECTION:intro -->\n\
+                   Hello world\n\
+                   <!-- CSA:SECTION:intro:END -->\n\
+                   <!-- CSA:SECTION:details -->\n\
+                   Some details here\n\
+                   <!-- CSA:SECTION:details:END -->";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].id, "intro");
+    assert_eq!(sections[1].id, "details");
+}
+
+#[test]
+fn test_parse_missing_end_marker_extends_to_eof() {
+    let output = "<!-- CSA:SECTION:analysis -->\n\
+                   Line A\n\
+                   Line B\n\
+                   Line C";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].id, "analysis");
+    assert_eq!(sections[0].line_start, 2);
+    assert_eq!(sections[0].line_end, 4);
+}
+
+#[test]
+fn test_parse_missing_end_closes_at_next_start() {
+    let output = "<!-- CSA:SECTION:first -->\n\
+                   content first\n\
+                   <!-- CSA:SECTION:second -->\n\
+                   content second";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].id, "first");
+    assert_eq!(sections[0].line_start, 2);
+    assert_eq!(sections[0].line_end, 2);
+    assert_eq!(sections[1].id, "second");
+    assert_eq!(sections[1].line_start, 4);
+    assert_eq!(sections[1].line_end, 4);
+}
+
+#[test]
+fn test_parse_orphan_end_marker_falls_back_to_full() {
+    let output = "some text\n\
+                   <!-- CSA:SECTION:ghost:END -->\n\
+                   more text";
+    let sections = parse_sections(output);
+    // Orphaned END markers produce no matched sections, so the parser falls
+    // back to a single "full" section to avoid losing the output content.
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].id, "full");
+    assert_eq!(sections[0].line_start, 1);
+    assert_eq!(sections[0].line_end, 3);
+}
+
+#[test]
+fn test_parse_duplicate_start_closes_first() {
+    let output = "<!-- CSA:SECTION:dup -->\n\
+                   first content\n\
+                   <!-- CSA:SECTION:dup -->\n\
+                   second content\n\
+                   <!-- CSA:SECTION:dup:END -->";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 2);
+    // First occurrence closed at second start
+    assert_eq!(sections[0].line_start, 2);
+    assert_eq!(sections[0].line_end, 2);
+    // Second occurrence closed by END
+    assert_eq!(sections[1].line_start, 4);
+    assert_eq!(sections[1].line_end, 4);
+    // Deduplicated file paths: first keeps original, second gets suffix
+    assert_eq!(sections[0].file_path.as_deref(), Some("dup.md"));
+    assert_eq!(sections[1].file_path.as_deref(), Some("dup-2.md"));
+}
+
+#[test]
+fn test_parse_whitespace_around_markers() {
+    let output = "  <!-- CSA:SECTION:padded -->  \n\
+                   content\n\
+                   \t<!-- CSA:SECTION:padded:END -->\t";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].id, "padded");
+}
+
+#[test]
+fn test_estimate_tokens() {
+    assert_eq!(estimate_tokens(""), 0);
+    assert_eq!(estimate_tokens("hello world foo bar"), 4 * 4 / 3); // 5 (integer division)
+}
+
+#[test]
+fn test_id_to_title() {
+    assert_eq!(id_to_title("summary"), "Summary");
+    assert_eq!(id_to_title("exec-plan"), "Exec Plan");
+    assert_eq!(id_to_title("code_review"), "Code Review");
+    assert_eq!(id_to_title("a-b_c"), "A B C");
+}
+
+#[test]
+fn test_persist_structured_output_no_markers() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "Hello world\nSecond line\n";
+    let index = persist_structured_output(tmp.path(), output).unwrap();
+
+    assert_eq!(index.sections.len(), 1);
+    assert_eq!(index.sections[0].id, "full");
+    assert_eq!(index.total_lines, 2);
+
+    // Verify index.toml written
+    let index_path = tmp.path().join("output/index.toml");
+    assert!(index_path.exists());
+    let loaded: OutputIndex = toml::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    assert_eq!(loaded, index);
+
+    // Verify section file written
+    let section_path = tmp.path().join("output/full.md");
+    assert!(section_path.exists());
+}
+
+#[test]
+fn test_persist_structured_output_with_markers() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "preamble\n\
+                   <!-- CSA:SECTION:summary -->\n\
+                   Summary content here.\n\
+                   <!-- CSA:SECTION:summary:END -->\n\
+                   <!-- CSA:SECTION:details -->\n\
+                   Detail line 1.\n\
+                   Detail line 2.\n\
+                   <!-- CSA:SECTION:details:END -->";
+    let index = persist_structured_output(tmp.path(), output).unwrap();
+
+    assert_eq!(index.sections.len(), 2);
+    assert_eq!(index.sections[0].id, "summary");
+    assert_eq!(index.sections[1].id, "details");
+
+    // Verify section files
+    let summary_content = fs::read_to_string(tmp.path().join("output/summary.md")).unwrap();
+    assert!(summary_content.contains("Summary content"));
+    let details_content = fs::read_to_string(tmp.path().join("output/details.md")).unwrap();
+    assert!(details_content.contains("Detail line 1"));
+
+    // Verify index round-trip
+    let index_toml = fs::read_to_string(tmp.path().join("output/index.toml")).unwrap();
+    let loaded: OutputIndex = toml::from_str(&index_toml).unwrap();
+    assert_eq!(loaded.sections.len(), 2);
+}
+
+#[test]
+fn test_persist_structured_output_duplicate_ids() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "<!-- CSA:SECTION:review -->\n\
+                   First review content\n\
+                   <!-- CSA:SECTION:review:END -->\n\
+                   <!-- CSA:SECTION:review -->\n\
+                   Second review content\n\
+                   <!-- CSA:SECTION:review:END -->";
+    let index = persist_structured_output(tmp.path(), output).unwrap();
+
+    assert_eq!(index.sections.len(), 2);
+    assert_eq!(index.sections[0].file_path.as_deref(), Some("review.md"));
+    assert_eq!(index.sections[1].file_path.as_deref(), Some("review-2.md"));
+
+    // Both files exist and have distinct content
+    let first = fs::read_to_string(tmp.path().join("output/review.md")).unwrap();
+    let second = fs::read_to_string(tmp.path().join("output/review-2.md")).unwrap();
+    assert!(first.contains("First review content"));
+    assert!(second.contains("Second review content"));
+}
+
+#[test]
+fn test_persist_structured_output_empty() {
+    let tmp = tempfile::tempdir().unwrap();
+    let index = persist_structured_output(tmp.path(), "").unwrap();
+
+    assert!(index.sections.is_empty());
+    assert_eq!(index.total_tokens, 0);
+    assert_eq!(index.total_lines, 0);
+
+    let index_path = tmp.path().join("output/index.toml");
+    assert!(index_path.exists());
+}
+
+// ── load_output_index tests ───────────────────────────────────────
+
+#[test]
+fn test_load_output_index_returns_none_when_missing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let result = load_output_index(tmp.path()).unwrap();
+    assert!(result.is_none());
+}
+
+#[test]
+fn test_load_output_index_returns_persisted_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "<!-- CSA:SECTION:summary -->\nHello\n<!-- CSA:SECTION:summary:END -->";
+    persist_structured_output(tmp.path(), output).unwrap();
+
+    let loaded = load_output_index(tmp.path()).unwrap().unwrap();
+    assert_eq!(loaded.sections.len(), 1);
+    assert_eq!(loaded.sections[0].id, "summary");
+}
+
+// ── read_section tests ────────────────────────────────────────────
+
+#[test]
+fn test_read_section_returns_none_when_no_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    let result = read_section(tmp.path(), "summary").unwrap();
+    assert!(result.is_none());
+}
+
+#[test]
+fn test_read_section_returns_none_for_unknown_id() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "<!-- CSA:SECTION:summary -->\nHello\n<!-- CSA:SECTION:summary:END -->";
+    persist_structured_output(tmp.path(), output).unwrap();
+
+    let result = read_section(tmp.path(), "nonexistent").unwrap();
+    assert!(result.is_none());
+}
+
+#[test]
+fn test_read_section_returns_content_for_valid_id() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output =
+        "<!-- CSA:SECTION:summary -->\nSummary content here\n<!-- CSA:SECTION:summary:END -->";
+    persist_structured_output(tmp.path(), output).unwrap();
+
+    let content = read_section(tmp.path(), "summary").unwrap().unwrap();
+    assert!(content.contains("Summary content here"));
+}
+
+#[test]
+fn test_read_section_with_multiple_sections() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "<!-- CSA:SECTION:intro -->\nIntro text\n<!-- CSA:SECTION:intro:END -->\n\
+                   <!-- CSA:SECTION:details -->\nDetail text\n<!-- CSA:SECTION:details:END -->";
+    persist_structured_output(tmp.path(), output).unwrap();
+
+    let intro = read_section(tmp.path(), "intro").unwrap().unwrap();
+    assert!(intro.contains("Intro text"));
+
+    let details = read_section(tmp.path(), "details").unwrap().unwrap();
+    assert!(details.contains("Detail text"));
+}
+
+// ── read_all_sections tests ───────────────────────────────────────
+
+#[test]
+fn test_read_all_sections_returns_empty_when_no_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    let result = read_all_sections(tmp.path()).unwrap();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_read_all_sections_returns_all_in_order() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "<!-- CSA:SECTION:summary -->\nSummary\n<!-- CSA:SECTION:summary:END -->\n\
+                   <!-- CSA:SECTION:details -->\nDetails\n<!-- CSA:SECTION:details:END -->\n\
+                   <!-- CSA:SECTION:conclusion -->\nConclusion\n<!-- CSA:SECTION:conclusion:END -->";
+    persist_structured_output(tmp.path(), output).unwrap();
+
+    let sections = read_all_sections(tmp.path()).unwrap();
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].0.id, "summary");
+    assert!(sections[0].1.contains("Summary"));
+    assert_eq!(sections[1].0.id, "details");
+    assert!(sections[1].1.contains("Details"));
+    assert_eq!(sections[2].0.id, "conclusion");
+    assert!(sections[2].1.contains("Conclusion"));
+}
+
+#[test]
+fn test_read_all_sections_with_no_markers_returns_full() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = "Line 1\nLine 2\nLine 3";
+    persist_structured_output(tmp.path(), output).unwrap();
+
+    let sections = read_all_sections(tmp.path()).unwrap();
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].0.id, "full");
+    assert!(sections[0].1.contains("Line 1"));
+}
+
+// ── empty section tests ────────────────────────────────────────
+
+#[test]
+fn test_parse_empty_section_preserves_empty_span() {
+    // Adjacent start/end markers with no content between them
+    let output = "preamble\n\
+                   <!-- CSA:SECTION:empty -->\n\
+                   <!-- CSA:SECTION:empty:END -->\n\
+                   postamble";
+    let sections = parse_sections(output);
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].id, "empty");
+    // Empty section: line_end < line_start
+    assert!(
+        sections[0].line_end < sections[0].line_start,
+        "Empty section should have line_end < line_start, got start={} end={}",
+        sections[0].line_start,
+        sections[0].line_end,
+    );
+    assert_eq!(sections[0].token_estimate, 0);
+}
+
+// ── section ID sanitization tests ──────────────────────────────
+
+#[test]
+fn test_sanitize_section_id_allows_safe_chars() {
+    assert_eq!(sanitize_section_id("summary"), "summary");
+    assert_eq!(sanitize_section_id("exec-plan"), "exec-plan");
+    assert_eq!(sanitize_section_id("code_review"), "code_review");
+    assert_eq!(sanitize_section_id("v1.2.3"), "v1.2.3");
+}
+
+#[test]
+fn test_sanitize_section_id_blocks_path_traversal() {
+    let sanitized = sanitize_section_id("../../outside");
+    assert!(
+        !sanitized.contains('/'),
+        "Sanitized ID should not contain '/': {sanitized}"
+    );
+    assert!(
+        !sanitized.contains(".."),
+        "Sanitized ID should not contain '..': {sanitized}"
+    );
+}
+
+#[test]
+fn test_sanitize_section_id_blocks_backslash() {
+    let sanitized = sanitize_section_id("..\\..\\outside");
+    assert!(
+        !sanitized.contains('\\'),
+        "Sanitized ID should not contain backslash: {sanitized}"
+    );
+    assert!(
+        !sanitized.contains(".."),
+        "Sanitized ID should not contain '..': {sanitized}"
+    );
+}
+
+#[test]
+fn test_persist_structured_output_sanitizes_section_ids() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output =
+        "<!-- CSA:SECTION:../../escape -->\nmalicious\n<!-- CSA:SECTION:../../escape:END -->";
+    let index = persist_structured_output(tmp.path(), output).unwrap();
+
+    assert_eq!(index.sections.len(), 1);
+    let section = &index.sections[0];
+    // ID should be sanitized
+    assert!(
+        !section.id.contains('/'),
+        "Section ID should be sanitized: {}",
+        section.id
+    );
+    assert!(
+        !section.id.contains(".."),
+        "Section ID should not contain '..': {}",
+        section.id
+    );
+    // File should be written inside output dir, not escaped
+    if let Some(ref fp) = section.file_path {
+        let section_path = tmp.path().join("output").join(fp);
+        assert!(
+            section_path.exists(),
+            "Section file should exist at safe path"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! Pinned sessions.
+//!
+//! A pinned session carries a marker file in its session directory; `csa gc`
+//! and `csa session clean` never delete it, whatever its age.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Marker file whose presence pins a session.
+pub const PIN_MARKER_FILE: &str = "pinned";
+
+pub fn is_session_pinned(session_dir: &Path) -> bool {
+    session_dir.join(PIN_MARKER_FILE).is_file()
+}
+
+/// Pin or unpin the session stored in `session_dir`.
+pub fn set_session_pinned(session_dir: &Path, pinned: bool) -> Result<()> {
+    let marker = session_dir.join(PIN_MARKER_FILE);
+    if pinned {
+        std::fs::write(&marker, b"")
+            .with_context(|| format!("Failed to write {}", marker.display()))
+    } else {
+        match std::fs::remove_file(&marker) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(err).with_context(|| format!("Failed to remove {}", marker.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pin_marker_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_session_pinned(dir.path()));
+        set_session_pinned(dir.path(), true).unwrap();
+        assert!(is_session_pinned(dir.path()));
+        set_session_pinned(dir.path(), false).unwrap();
+        set_session_pinned(dir.path(), false).unwrap();
+        assert!(!is_session_pinned(dir.path()));
+    }
+}
@@ -0,0 +1,79 @@
+//! Per-session resource report written to `resources.toml` at completion.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const RESOURCE_REPORT_FILE_NAME: &str = "resources.toml";
+
+/// What a session run consumed, so orchestrators can budget later delegations.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionResourceReport {
+    /// Peak RSS of the tool process tree (MB).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_rss_mb: Option<u64>,
+    /// Peak GPU memory (MB), sampled for tools that declare `gpu_memory_mb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_vram_mb: Option<u64>,
+    /// User plus system CPU time of the tool and the children it reaped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_seconds: Option<f64>,
+    /// Bytes read from block devices; page-cache hits are not counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io_read_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io_write_bytes: Option<u64>,
+    /// Resource isolation mode: "cgroup", "rlimit", or "none".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<String>,
+    /// Filesystem isolation mode: "bwrap", "landlock", "sandbox-exec", or "none".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesystem_sandbox: Option<String>,
+    /// Time spent waiting for a tool slot or for host pressure to subside.
+    #[serde(default)]
+    pub queue_wait_secs: u64,
+    /// Wall-clock duration of the tool run.
+    #[serde(default)]
+    pub wall_secs: u64,
+    /// Limits hit during the run, one line each.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub limit_violations: Vec<String>,
+}
+
+/// Write `report` to `{session_dir}/resources.toml`.
+pub fn write_resource_report(session_dir: &Path, report: &SessionResourceReport) -> Result<()> {
+    let path = session_dir.join(RESOURCE_REPORT_FILE_NAME);
+    let content = toml::to_string_pretty(report).context("Failed to serialize resource report")?;
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_resource_report_round_trips() {
+        let tmp = tempfile::tempdir().unwrap();
+        let report = SessionResourceReport {
+            peak_rss_mb: Some(2048),
+            peak_vram_mb: Some(512),
+            cpu_seconds: Some(12.5),
+            io_read_bytes: Some(4096),
+            io_write_bytes: Some(1 << 20),
+            sandbox_mode: Some("cgroup".to_string()),
+            filesystem_sandbox: Some("bwrap".to_string()),
+            queue_wait_secs: 30,
+            wall_secs: 600,
+            limit_violations: vec!["disk budget exceeded".to_string()],
+        };
+
+        write_resource_report(tmp.path(), &report).unwrap();
+
+        let raw = std::fs::read_to_string(tmp.path().join(RESOURCE_REPORT_FILE_NAME)).unwrap();
+        let loaded: SessionResourceReport = toml::from_str(&raw).unwrap();
+        assert_eq!(loaded, report);
+        let empty: SessionResourceReport = toml::from_str("").unwrap();
+        assert_eq!(empty, SessionResourceReport::default());
+    }
+}
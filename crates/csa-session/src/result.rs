@@ -469,6 +469,9 @@ pub struct SessionResult {
     /// pre-existing `result.toml` files (without it) still deserialize.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub post_exec_gate: Option<crate::post_exec_gate_report::PostExecGateReport>,
+    /// Resource usage of the run; the same report is written to `resources.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<crate::resource_report::SessionResourceReport>,
     /// Manager-facing data loaded from `output/result.toml` sidecars at read time.
     /// This is intentionally read-only metadata and is never serialized back into
     /// the runtime `result.toml` envelope.
@@ -19,6 +19,7 @@ fn session_result_kill_diagnostics_roundtrip() {
             memory_max_mb: Some(1000),
             soft_limit_percent: Some(70),
             scope_name: Some("csa-codex-01J.scope".to_string()),
+            partial_results_saved: false,
         }),
         ..Default::default()
     };
@@ -252,6 +252,7 @@ fn test_session_result_uncommitted_changes_roundtrip() {
         require_commit_recovery: None,
         memory_soft_limit_recovery: None,
         manager_fields: Default::default(),
+        resources: None,
     };
 
     let toml_str = toml::to_string_pretty(&result).expect("Serialize should succeed");
@@ -110,6 +110,36 @@ pub struct ReviewArtifact {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Kind of an [`InfrastructureNote`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum InfrastructureNoteKind {
+    /// Provider quota, capacity or rate-limit error.
+    Quota,
+    /// Provider authentication or permission error.
+    Auth,
+    /// Output of a CSA hook (`[csa-hook]` lines).
+    Hook,
+    /// Other failure of the review tool itself, such as degraded MCP servers.
+    ToolError,
+}
+
+/// Diagnostic of the review tool or harness captured with the review.
+///
+/// Infrastructure notes are a channel of their own: they explain why a
+/// review may be incomplete but never count as code findings, so they never
+/// change the severity counts or the decision of a verdict.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct InfrastructureNote {
+    pub kind: InfrastructureNoteKind,
+    pub message: String,
+}
+
+/// Verdict of one review.
+///
+/// `decision` and `severity_counts` are derived from the findings channel
+/// only; tool failure diagnostics go to `infrastructure_notes`.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct ReviewVerdictArtifact {
     pub schema_version: u32,
@@ -140,6 +170,8 @@ pub struct ReviewVerdictArtifact {
     pub large_diff_warning_changed_lines: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub no_provider_launch: Option<NoProviderLaunchDiagnostic>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub infrastructure_notes: Vec<InfrastructureNote>,
 }
 
 fn is_false(value: &bool) -> bool {
@@ -184,6 +216,7 @@ impl ReviewVerdictArtifact {
             large_diff_warning_threshold: None,
             large_diff_warning_changed_lines: None,
             no_provider_launch: None,
+            infrastructure_notes: Vec::new(),
         }
     }
 }
@@ -211,8 +244,9 @@ pub fn write_findings_toml(session_dir: &Path, artifact: &FindingsFile) -> std::
 #[cfg(test)]
 mod tests {
     use super::{
-        Finding, FindingsFile, REVIEW_VERDICT_SCHEMA_VERSION, ReviewArtifact, ReviewFinding,
-        ReviewFindingFileRange, ReviewVerdictArtifact, Severity, SeveritySummary,
+        Finding, FindingsFile, InfrastructureNote, InfrastructureNoteKind,
+        REVIEW_VERDICT_SCHEMA_VERSION, ReviewArtifact, ReviewFinding, ReviewFindingFileRange,
+        ReviewVerdictArtifact, Severity, SeveritySummary,
     };
     use chrono::Utc;
     use csa_core::types::ReviewDecision;
@@ -347,6 +381,33 @@ mod tests {
         assert_eq!(decoded.review_mode, None);
     }
 
+    #[test]
+    fn review_verdict_infrastructure_notes_stay_out_of_severity_counts() {
+        let mut artifact = ReviewVerdictArtifact::from_parts(
+            "01JABCDEF0123456789ABCDEFG",
+            ReviewDecision::Pass,
+            "CLEAN",
+            &[],
+            Vec::new(),
+        );
+        let json = serde_json::to_string(&artifact).expect("serialize verdict without notes");
+        assert!(!json.contains("infrastructure_notes"));
+
+        artifact.infrastructure_notes.push(InfrastructureNote {
+            kind: InfrastructureNoteKind::Quota,
+            message: "Error: 429 Too Many Requests".to_string(),
+        });
+        let json = serde_json::to_string(&artifact).expect("serialize verdict with notes");
+        assert!(json.contains("\"infrastructure_notes\":[{\"kind\":\"quota\""));
+        let decoded: ReviewVerdictArtifact =
+            serde_json::from_str(&json).expect("deserialize verdict with notes");
+        assert_eq!(decoded, artifact);
+        assert!(decoded.severity_counts.values().all(|count| *count == 0));
+
+        let unknown = r#"{"kind":"hook","message":"x","severity":"high"}"#;
+        assert!(serde_json::from_str::<InfrastructureNote>(unknown).is_err());
+    }
+
     #[test]
     fn review_verdict_artifact_review_mode_roundtrips() {
         let mut artifact = ReviewVerdictArtifact::from_parts(
@@ -45,6 +45,11 @@ pub struct MetaSessionState {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub csa_version: Option<String>,
 
+    /// OS user that created this session, for attribution in shared state
+    /// directories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
     /// Genealogy information (parent, depth)
     #[serde(default)]
     pub genealogy: Genealogy,
@@ -85,6 +90,10 @@ pub struct MetaSessionState {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub termination_reason: Option<String>,
 
+    /// Model of the latest tool run, when one was selected explicitly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_model: Option<String>,
+
     /// Whether this session is a seed candidate for future fork-from-seed.
     #[serde(default)]
     pub is_seed_candidate: bool,
@@ -124,6 +133,10 @@ pub struct MetaSessionState {
     #[serde(default = "default_identity_version")]
     pub identity_version: u8,
 
+    /// Throttling applied because host pressure (PSI) crossed a threshold.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pressure_events: Vec<PressureEvent>,
+
     /// In-memory fork-call timestamps for simple per-session rate limiting.
     ///
     /// This is intentionally runtime-only and is not persisted to state.toml.
@@ -151,6 +164,7 @@ impl Default for MetaSessionState {
             token_budget: None,
             sandbox_info: None,
             termination_reason: None,
+            last_model: None,
             is_seed_candidate: false,
             git_head_at_creation: None,
             pre_session_porcelain: None,
@@ -159,6 +173,8 @@ impl Default for MetaSessionState {
             spec_id: None,
             vcs_identity: None,
             identity_version: default_identity_version(),
+            pressure_events: Vec::new(),
+            user: None,
             fork_call_timestamps: Vec::new(),
         }
     }
@@ -206,6 +222,11 @@ pub struct ReviewSessionMeta {
     pub tool: String,
     /// Review scope (e.g., "uncommitted", "range:main...HEAD", "base:main").
     pub scope: String,
+    /// `--path` globs that limited the reviewed diff within `scope`.
+    ///
+    /// Empty for a review of the whole scope.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_filters: Vec<String>,
     /// Exit code of the review process.
     pub exit_code: i32,
     /// Whether `--fix` was enabled and a fix pass was attempted.
@@ -243,6 +264,12 @@ pub struct FixConvergenceMeta {
 }
 
 impl ReviewSessionMeta {
+    /// True when this review covered all of `scope`, not only the part of it
+    /// that matches `path_filters`.
+    pub fn covers_scope(&self, scope: &str) -> bool {
+        self.scope == scope && self.path_filters.is_empty()
+    }
+
     /// Returns true when review metadata represents an incomplete or failed
     /// reviewer execution that must not be treated as a clean verdict.
     pub fn requires_fail_closed_verdict(&self) -> bool {
@@ -302,7 +329,7 @@ pub struct SandboxInfo {
     /// Memory limit applied (MB), if any.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_max_mb: Option<u64>,
-    /// Filesystem isolation mode used: "bwrap", "landlock", or "none".
+    /// Filesystem isolation mode used: "bwrap", "landlock", "sandbox-exec", or "none".
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub filesystem_mode: Option<String>,
     /// Whether the project root was mounted read-only.
@@ -313,6 +340,31 @@ pub struct SandboxInfo {
     pub resource_resolution: Option<ResourceResolutionInfo>,
 }
 
+/// One adaptive-throttling action taken for a session under host pressure.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PressureEvent {
+    pub at: DateTime<Utc>,
+    pub action: PressureAction,
+    /// Threshold that was crossed, e.g. `memory some avg10=31.2% >= 20.0%`.
+    pub reason: String,
+    /// How long the spawn was delayed or the session paused, once known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureAction {
+    /// Spawn held back until pressure subsided.
+    SpawnDelayed,
+    /// Spawn held back for the full delay budget, then launched anyway.
+    SpawnDelayTimedOut,
+    /// Session's process group stopped with SIGSTOP.
+    Paused,
+    /// Session's process group continued with SIGCONT.
+    Resumed,
+}
+
 /// Source of a resource value resolved for one CSA child.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -29,6 +29,7 @@ fn sample_state_with_phase(phase: SessionPhase) -> MetaSessionState {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     }
 }
 
@@ -337,6 +338,7 @@ fn test_meta_session_state_toml_roundtrip() {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let toml_str = toml::to_string_pretty(&state).expect("Serialize should succeed");
@@ -490,6 +492,7 @@ fn test_meta_session_state_last_return_packet_roundtrip() {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let toml_str = toml::to_string_pretty(&state).expect("serialize");
@@ -706,6 +709,7 @@ fn test_meta_session_state_with_budget_roundtrip() {
         fork_call_timestamps: Vec::new(),
         vcs_identity: None,
         identity_version: 1,
+        ..Default::default()
     };
 
     let toml_str = toml::to_string_pretty(&state).expect("Serialize should succeed");
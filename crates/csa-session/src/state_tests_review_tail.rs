@@ -87,6 +87,7 @@ fn review_session_meta_serde_roundtrip() {
         failure_reason: None,
         tool: "claude-code".to_string(),
         scope: "range:main...HEAD".to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: true,
         fix_rounds: 2,
@@ -116,6 +117,7 @@ fn review_session_meta_write_and_read() {
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "uncommitted".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: false,
         fix_rounds: 0,
@@ -153,6 +155,7 @@ fn review_session_meta_overwrite_on_fix_round() {
         failure_reason: None,
         tool: "claude-code".to_string(),
         scope: "base:main".to_string(),
+        path_filters: Vec::new(),
         exit_code: 1,
         fix_attempted: false,
         fix_rounds: 0,
@@ -175,6 +178,7 @@ fn review_session_meta_overwrite_on_fix_round() {
         failure_reason: None,
         tool: "claude-code".to_string(),
         scope: "base:main".to_string(),
+        path_filters: Vec::new(),
         exit_code: 0,
         fix_attempted: true,
         fix_rounds: 1,
@@ -212,6 +216,8 @@ fn review_session_meta_missing_review_iterations_defaults_to_one() {
 
     let decoded: ReviewSessionMeta = serde_json::from_str(json).expect("parse");
     assert_eq!(decoded.review_iterations, 1);
+    assert!(decoded.path_filters.is_empty());
+    assert!(decoded.covers_scope("base:main"));
 }
 
 fn review_meta_with_decision(decision: &str, verdict: &str, exit_code: i32) -> ReviewSessionMeta {
@@ -226,6 +232,7 @@ fn review_meta_with_decision(decision: &str, verdict: &str, exit_code: i32) -> R
         failure_reason: None,
         tool: "codex".to_string(),
         scope: "base:main".to_string(),
+        path_filters: Vec::new(),
         exit_code,
         fix_attempted: false,
         fix_rounds: 0,
@@ -286,3 +293,14 @@ fn review_session_meta_rejects_fix_without_positive_convergence_sentinel() {
     assert!(!meta.fix_clean_converged());
     assert!(!meta.accepts_clean_review_verdict(ReviewDecision::Pass));
 }
+
+#[test]
+fn review_session_meta_with_path_filters_does_not_cover_its_scope() {
+    let mut meta = review_meta_with_decision("pass", "CLEAN", 0);
+    meta.path_filters = vec!["crates/foo/**".to_string()];
+
+    assert!(!meta.covers_scope("base:main"));
+    let json = serde_json::to_value(&meta).expect("serialize");
+    assert_eq!(json["scope"], "base:main");
+    assert_eq!(json["path_filters"][0], "crates/foo/**");
+}
@@ -6,7 +6,8 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 
 use crate::compiler::{compile, plan_to_toml};
-use crate::parser::parse_skill;
+use crate::parser::parse_skill_file;
+use crate::workspace::Workspace;
 
 /// Aggregated result of a batch compile run.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -103,6 +104,27 @@ pub fn compile_all(root: &Path) -> Result<BatchSummary> {
     })
 }
 
+/// Compile the `patterns/` directory of every workspace member that has one.
+pub fn compile_workspace(workspace: &Workspace) -> Result<BatchSummary> {
+    let mut summary = BatchSummary {
+        ok: 0,
+        failed: 0,
+        results: Vec::new(),
+    };
+    for member in &workspace.members {
+        let patterns = workspace.member_dir(member).join("patterns");
+        if !patterns.is_dir() {
+            continue;
+        }
+        eprintln!("compiling {} ({})", member.name, member.path);
+        let member_summary = compile_all(&patterns)?;
+        summary.ok += member_summary.ok;
+        summary.failed += member_summary.failed;
+        summary.results.extend(member_summary.results);
+    }
+    Ok(summary)
+}
+
 /// Find all `workflow.toml` files under `root`, sorted for deterministic output.
 fn find_workflow_tomls(root: &Path) -> Result<Vec<PathBuf>> {
     let mut plans = Vec::new();
@@ -149,9 +171,7 @@ fn compile_single(plan_path: &Path) -> Result<()> {
 
     match source {
         Some(src_path) => {
-            let content = std::fs::read_to_string(&src_path)
-                .with_context(|| format!("failed to read {}", src_path.display()))?;
-            let doc = parse_skill(&content)
+            let doc = parse_skill_file(&src_path)
                 .with_context(|| format!("failed to parse {}", src_path.display()))?;
             let plan = compile(&doc).context("compilation failed")?;
             // Verify TOML serialization round-trips.
@@ -323,6 +343,27 @@ on_fail = "abort"
         assert_eq!(summary.results.len(), 2);
     }
 
+    #[test]
+    fn compile_workspace_compiles_patterns_of_every_member() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            tmp.path().join(crate::workspace::WORKSPACE_MANIFEST),
+            "[workspace]\nmembers = [\"skills/*\"]\n",
+        )
+        .unwrap();
+        for member in ["alpha", "beta"] {
+            let pattern_dir = tmp.path().join("skills").join(member).join("patterns/p");
+            fs::create_dir_all(&pattern_dir).unwrap();
+            fs::write(pattern_dir.join("workflow.toml"), minimal_workflow_toml()).unwrap();
+        }
+        fs::create_dir_all(tmp.path().join("skills/no-patterns")).unwrap();
+
+        let workspace = Workspace::load(tmp.path()).unwrap();
+        let summary = compile_workspace(&workspace).expect("compile_workspace should succeed");
+        assert_eq!(summary.ok, 2);
+        assert_eq!(summary.failed, 0);
+    }
+
     #[test]
     fn find_workflow_tomls_returns_sorted_paths() {
         let tmp = tempfile::tempdir().expect("tempdir");
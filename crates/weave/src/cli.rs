@@ -75,13 +75,34 @@ pub enum Commands {
 
     /// Install a skill from a git repository or local path.
     Install {
-        /// Git URL or user/repo shorthand (mutually exclusive with --path).
+        /// Git URL, user/repo shorthand, or a package name from the configured
+        /// index, optionally followed by `@<ref>` or `@'<version req>'`
+        /// (mutually exclusive with --path).
         source: Option<String>,
 
         /// Install from a local directory instead of git.
         #[arg(long, value_name = "DIR", conflicts_with = "source")]
         path: Option<PathBuf>,
 
+        /// Pin to this branch; `update`/`upgrade` keep the locked commit
+        /// unless `--force` moves it to the branch head.
+        #[arg(long, value_name = "NAME", group = "pin", requires = "source")]
+        branch: Option<String>,
+
+        /// Pin to this tag.
+        #[arg(long, value_name = "NAME", group = "pin", requires = "source")]
+        tag: Option<String>,
+
+        /// Pin to this commit.
+        #[arg(long, value_name = "COMMIT", group = "pin", requires = "source")]
+        rev: Option<String>,
+
+        /// Install only this workspace member directory of the git source,
+        /// e.g. `skills/review`; it must be listed in the repository's
+        /// `weave-workspace.toml`.
+        #[arg(long, value_name = "PATH", requires = "source")]
+        member: Option<String>,
+
         /// Where to create skill symlinks: project (.claude/skills/), user
         /// (~/.claude/skills/), or none (skip linking).
         #[arg(long, default_value = "project")]
@@ -96,6 +117,25 @@ pub enum Commands {
         force_link: bool,
     },
 
+    /// Search the configured package index by name, description, or tag.
+    Search {
+        /// Case-insensitive search term.
+        term: String,
+    },
+
+    /// Store an HTTPS access token for a git host in the OS keyring.
+    ///
+    /// The token is read from stdin and sent when installing from
+    /// `https://<host>/...` sources. SSH sources use the SSH agent instead.
+    Auth {
+        /// Git host, e.g. `github.com` (include the port if the URLs do).
+        host: String,
+
+        /// Remove the stored token instead.
+        #[arg(long)]
+        remove: bool,
+    },
+
     /// Lock current skill dependencies.
     Lock,
 
@@ -119,9 +159,40 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Show what `weave upgrade` would change for an installed package.
+    ///
+    /// Lists the commits and changed files between the locked checkout and
+    /// the upstream commit its version requirement or pin resolves to.
+    Diff {
+        /// Package name as recorded in weave.lock.
+        name: String,
+    },
+
+    /// Remove a package from weave.lock and unlink its skills.
+    ///
+    /// The checkout stays in the global store until `weave gc`.
+    Remove {
+        /// Package name as recorded in weave.lock.
+        name: String,
+    },
+
     /// Audit installed skills for issues.
     Audit,
 
+    /// Print the dependency tree of installed packages.
+    ///
+    /// Edges come from the `[dependencies]` of each locked package's
+    /// `.skill.toml`; packages nothing depends on are shown as roots.
+    Tree {
+        /// Show only git repositories locked under more than one name.
+        #[arg(long, conflicts_with = "why")]
+        duplicates: bool,
+
+        /// Show which packages pull in <PKG>.
+        #[arg(long, value_name = "PKG")]
+        why: Option<String>,
+    },
+
     /// Check for broken symlinks in skill directories.
     Check {
         /// Directories to scan (default: .claude/skills, .codex/skills, .agents/skills, .gemini/skills).
@@ -145,7 +216,9 @@ pub enum Commands {
     /// Migrate from legacy .weave/lock.toml to weave.lock and global store.
     Migrate,
 
-    /// Garbage-collect unreferenced checkouts from the global package store.
+    /// Garbage-collect unreferenced checkouts from the global package store
+    /// and unreferenced repositories from the git cache, then prune the
+    /// cache's shared object pool.
     Gc {
         /// Print what would be removed without actually deleting.
         #[arg(long)]
@@ -154,23 +227,93 @@ pub enum Commands {
 
     /// Batch-compile all workflow.toml files in a directory tree.
     CompileAll {
-        /// Root directory to scan for workflow.toml files (default: patterns/).
-        #[arg(long, default_value = "patterns")]
+        /// Root directory to scan for workflow.toml files (default: the
+        /// `patterns/` of every workspace member inside a workspace, else
+        /// patterns/).
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Check skill files for undefined variables, unreachable steps, missing
+    /// `Tool:` hints, suspicious shell, and unbalanced blocks.
+    ///
+    /// Rule severities are read from the nearest `weave-lint.toml`. Exits
+    /// non-zero when any finding has severity `error`.
+    Lint {
+        /// Skill files or directories to scan for PATTERN.md (default:
+        /// patterns/ if present, else the current directory).
+        paths: Vec<PathBuf>,
+    },
+
+    /// Rewrite skill files in canonical form: frontmatter key order,
+    /// directive heading case, condition spacing, and hint syntax.
+    Fmt {
+        /// Skill files or directories to scan for PATTERN.md (default:
+        /// patterns/ if present, else the current directory).
+        paths: Vec<PathBuf>,
+
+        /// Report files that are not formatted instead of rewriting them;
+        /// exits non-zero if any are found.
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Release the skill package in the current directory.
+    ///
+    /// Lints, compiles and audits the package, then bumps `[skill] version`
+    /// in `.skill.toml`, commits it and creates an annotated `v<version>` tag
+    /// (`<member>-v<version>` for a workspace member).
+    Publish {
+        /// `major`, `minor`, `patch`, or an explicit version.
+        #[arg(default_value = "patch")]
+        bump: String,
+
+        /// Workspace member to release, by name or path (default: the member
+        /// containing the current directory).
+        #[arg(long, value_name = "NAME")]
+        member: Option<String>,
+
+        /// Push the release commit and tag to `origin`.
+        #[arg(long)]
+        push: bool,
+
+        /// Also list the package in the configured registry index.
+        #[arg(long, requires = "push")]
+        index: bool,
+
+        /// Validate and print the release without changing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Run a skill package's `tests/*.toml` fixtures against its compiled plans.
+    Test {
+        /// Package root containing the `tests/` directory.
+        #[arg(default_value = ".")]
         dir: PathBuf,
     },
 
-    /// Visualize a compiled workflow.toml as ASCII (default), Mermaid, or PNG.
+    /// Visualize a compiled workflow.toml as ASCII (default), Mermaid, SVG,
+    /// HTML, or PNG.
     Visualize {
         /// Input workflow.toml file path.
         plan: PathBuf,
 
-        /// Write PNG output to file.
-        #[arg(long, value_name = "FILE", conflicts_with = "mermaid")]
+        /// Write PNG output to file (requires Graphviz).
+        #[arg(long, value_name = "FILE", group = "visualize_format")]
         png: Option<PathBuf>,
 
         /// Print Mermaid flowchart to stdout.
-        #[arg(long, conflicts_with = "png")]
+        #[arg(long, group = "visualize_format")]
         mermaid: bool,
+
+        /// Print a standalone SVG diagram to stdout.
+        #[arg(long, group = "visualize_format")]
+        svg: bool,
+
+        /// Print an HTML page rendering the Mermaid flowchart via mermaid.js.
+        #[arg(long, group = "visualize_format")]
+        html: bool,
     },
 }
 
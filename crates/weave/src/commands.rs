@@ -0,0 +1,577 @@
+//! Handlers for the longer `weave` subcommands.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use weave::batch;
+use weave::check;
+use weave::link::{self, LinkScope};
+use weave::lint;
+use weave::package::{self, IndexEntry};
+use weave::publish::{self, Bump};
+use weave::requirements::{configured_mcp_servers, read_requirements};
+use weave::workspace::{self, Workspace};
+
+use crate::hooks::PackageHooks;
+
+/// Project root for package commands: the enclosing workspace root when the
+/// current directory is that root or one of its members (they all share its
+/// `weave.lock`), otherwise the current directory. Switching to the workspace
+/// root is reported on stderr.
+pub(crate) fn project_root() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("cannot determine CWD")?;
+    match workspace::lockfile_root(&cwd)? {
+        Some(root) if root != cwd => {
+            eprintln!(
+                "Using workspace root {} (its weave.lock is shared by all members)",
+                root.display()
+            );
+            Ok(root)
+        }
+        _ => Ok(cwd),
+    }
+}
+
+/// `weave compile-all`: `dir`, every workspace member's `patterns/`, or
+/// `patterns/`.
+pub(crate) fn compile_all(dir: Option<PathBuf>) -> Result<()> {
+    let summary = match dir {
+        Some(dir) => batch::compile_all(&dir)?,
+        None => {
+            let cwd = std::env::current_dir().context("cannot determine CWD")?;
+            match Workspace::discover(&cwd)? {
+                Some(ws) => batch::compile_workspace(&ws)?,
+                None => batch::compile_all(Path::new("patterns"))?,
+            }
+        }
+    };
+    let total = summary.ok + summary.failed;
+    if summary.failed > 0 {
+        eprintln!(
+            "{total} pattern(s) compiled: {} OK, {} FAILED",
+            summary.ok, summary.failed
+        );
+        std::process::exit(1);
+    }
+    eprintln!("{total} pattern(s) compiled: {} OK, 0 FAILED", summary.ok);
+    Ok(())
+}
+
+/// `weave lint`: print findings as text or a JSON array, exiting 1 when any
+/// has severity `error`.
+pub(crate) fn lint(paths: Vec<PathBuf>, json: bool) -> Result<()> {
+    let cwd = std::env::current_dir().context("cannot determine CWD")?;
+    let config = lint::LintConfig::discover(&cwd)?;
+    let files = skill_files(paths)?;
+
+    let mut report = Vec::new();
+    for file in &files {
+        for finding in lint::lint_file(file, &config)? {
+            report.push((file.display().to_string(), finding));
+        }
+    }
+    let errors = report
+        .iter()
+        .filter(|(_, f)| f.severity == lint::Severity::Error)
+        .count();
+
+    if json {
+        let entries: Vec<_> = report
+            .iter()
+            .map(|(file, f)| {
+                serde_json::json!({
+                    "file": file,
+                    "line": f.line,
+                    "rule": f.rule,
+                    "severity": f.severity,
+                    "message": f.message,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else {
+        for (file, f) in &report {
+            println!(
+                "{file}:{}: {}[{}]: {}",
+                f.line, f.severity, f.rule, f.message
+            );
+        }
+        eprintln!(
+            "{} file(s) linted: {} finding(s), {errors} error(s)",
+            files.len(),
+            report.len()
+        );
+    }
+    if errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// `weave fmt`: rewrite skill files in place, or with `check` list the
+/// unformatted ones and exit 1.
+pub(crate) fn fmt(paths: Vec<PathBuf>, check: bool) -> Result<()> {
+    let files = skill_files(paths)?;
+    let mut changed = 0usize;
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        let formatted = weave::fmt::format_skill(&content)
+            .with_context(|| format!("failed to format {}", file.display()))?;
+        if formatted == content {
+            continue;
+        }
+        changed += 1;
+        if check {
+            println!("{}", file.display());
+        } else {
+            std::fs::write(file, formatted)
+                .with_context(|| format!("failed to write {}", file.display()))?;
+        }
+    }
+    if check {
+        eprintln!("{} file(s) checked: {changed} need formatting", files.len());
+        if changed > 0 {
+            std::process::exit(1);
+        }
+    } else {
+        eprintln!("{} file(s) checked: {changed} reformatted", files.len());
+    }
+    Ok(())
+}
+
+/// `weave lock`, plus a check of member dependencies inside a workspace.
+pub(crate) fn lock(project_root: &Path) -> Result<()> {
+    let store_root = package::global_store_root()?;
+    let lockfile = package::lock(project_root, &store_root)?;
+    eprintln!("locked {} package(s)", lockfile.package.len());
+    for pkg in &lockfile.package {
+        let ver = pkg.version.as_deref().unwrap_or("-");
+        let commit_short = if pkg.commit.len() > 12 {
+            &pkg.commit[..12]
+        } else {
+            &pkg.commit
+        };
+        eprintln!("  {} {} ({})", pkg.name, ver, commit_short);
+    }
+    if let Some(ws) = Workspace::discover(project_root)? {
+        eprintln!("workspace: {} member(s)", ws.members.len());
+        for conflict in ws.dependency_conflicts(&lockfile) {
+            eprintln!("warning: {} {conflict}", conflict.dependent);
+        }
+    }
+    Ok(())
+}
+
+/// `weave upgrade`: upgrade every package, then relink and migrate links.
+pub(crate) fn upgrade(project_root: &Path, force: bool) -> Result<()> {
+    let cache_root = package::default_cache_root()?;
+    let store_root = package::global_store_root()?;
+    let results = package::upgrade(project_root, &cache_root, &store_root, force)?;
+    let hooks = PackageHooks::load(project_root);
+
+    let mut upgraded = 0u32;
+    let mut already_latest = 0u32;
+    let mut skipped = 0u32;
+
+    for entry in &results {
+        match &entry.status {
+            package::UpgradeStatus::Upgraded {
+                old_commit,
+                old_version,
+            } => {
+                upgraded += 1;
+                let old_short = &old_commit[..old_commit.len().min(12)];
+                let new_short = &entry.package.commit[..entry.package.commit.len().min(12)];
+                let old_ver = old_version.as_deref().unwrap_or("-");
+                let new_ver = entry.package.version.as_deref().unwrap_or("-");
+                eprintln!(
+                    "  upgraded {} ({old_ver} {old_short}) -> ({new_ver} {new_short})",
+                    entry.name
+                );
+                hooks.upgraded(&entry.package, old_commit, old_version.as_deref());
+            }
+            package::UpgradeStatus::AlreadyLatest => {
+                already_latest += 1;
+                let ver = entry.package.version.as_deref().unwrap_or("-");
+                eprintln!("  up-to-date {} ({ver})", entry.name);
+            }
+            package::UpgradeStatus::Skipped { reason } => {
+                skipped += 1;
+                eprintln!("  skipped {} ({reason})", entry.name);
+            }
+        }
+    }
+
+    eprintln!();
+    eprintln!(
+        "{} package(s): {upgraded} upgraded, {already_latest} up-to-date, {skipped} skipped",
+        results.len()
+    );
+    warn_version_conflicts(project_root, &store_root)?;
+
+    // Auto-link companion skills after upgrade.
+    if upgraded > 0 {
+        let removed = link::remove_stale_links(project_root, LinkScope::Project)?;
+        if !removed.is_empty() {
+            eprintln!("removed {} stale symlink(s)", removed.len());
+        }
+        report_stale_references(project_root, &removed);
+
+        let report = link::link_skills(project_root, LinkScope::Project, false)?;
+        let created = report.unique_created_count();
+        let link_skipped = report.unique_skipped_count();
+        if created > 0 || link_skipped > 0 {
+            eprintln!("linked {created} skill(s) ({link_skipped} already up-to-date)");
+        }
+        for name in report.unique_created_names() {
+            eprintln!("  + {name}");
+        }
+        if report.has_errors() {
+            for err in &report.errors {
+                eprintln!("warning: {err}");
+            }
+        }
+
+        relink_patterns(project_root, false)?;
+    }
+
+    // Migrate .gemini/skills/ → .agents/skills/ on every upgrade.
+    let migrate_result = check::migrate_gemini_skills(project_root)?;
+    if !migrate_result.missing_dir {
+        let moved = migrate_result.moved.len();
+        let removed = migrate_result.removed.len();
+        if moved > 0 || removed > 0 {
+            eprintln!();
+            eprintln!("gemini→agents migration: {moved} moved, {removed} duplicate(s) removed");
+            for entry in &migrate_result.moved {
+                eprintln!(
+                    "  → {} (from {})",
+                    entry.agents_path.display(),
+                    entry.gemini_path.display()
+                );
+            }
+        }
+        for f in migrate_result
+            .move_failures
+            .iter()
+            .chain(&migrate_result.remove_failures)
+        {
+            eprintln!("warning: {}: {}", f.path.display(), f.error);
+        }
+    }
+    Ok(())
+}
+
+/// `weave link sync`.
+pub(crate) fn link_sync(
+    project_root: &Path,
+    scope: LinkScope,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if scope == LinkScope::None {
+        bail!("--scope none is not valid for 'link sync'");
+    }
+
+    if dry_run {
+        // Dry-run: show what would be done without modifying anything.
+        let skills = link::discover_skills(project_root)?;
+        eprintln!("would link {} skill(s):", skills.len());
+        for skill in &skills {
+            eprintln!("  {} (from {})", skill.name, skill.package_name);
+        }
+
+        let stale = link::detect_stale_links(project_root, scope)?;
+        if stale.is_empty() {
+            eprintln!("no stale links detected");
+        } else {
+            eprintln!("would remove {} stale link(s):", stale.len());
+            for p in &stale {
+                eprintln!("  - {}", p.display());
+            }
+        }
+        eprintln!("(dry-run: no changes made)");
+        return Ok(());
+    }
+
+    // Remove stale links first.
+    let removed = link::remove_stale_links(project_root, scope)?;
+    if !removed.is_empty() {
+        eprintln!("removed {} stale symlink(s)", removed.len());
+        for p in &removed {
+            eprintln!("  - {}", p.display());
+        }
+    }
+    report_stale_references(project_root, &removed);
+
+    // Create/update links.
+    let report = link::link_skills(project_root, scope, force)?;
+
+    if report.has_errors() {
+        for err in &report.errors {
+            eprintln!("error: {err}");
+        }
+        bail!("{} error(s) during link sync", report.errors.len());
+    }
+
+    let created = report.unique_created_count();
+    let skipped = report.unique_skipped_count();
+    eprintln!(
+        "link sync: {created} created, {skipped} up-to-date, {} stale removed",
+        removed.len()
+    );
+
+    relink_patterns(project_root, force)
+}
+
+/// `weave publish`: validate the package, then commit and tag its release,
+/// optionally pushing it and listing it in the `registry` index.
+pub(crate) fn publish(
+    bump: &Bump,
+    member: Option<&str>,
+    push: bool,
+    registry: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    let cwd = std::env::current_dir().context("cannot determine CWD")?;
+    let (dir, member) = publish_target(&cwd, member)?;
+    if registry.is_some() && member.is_some() {
+        bail!(
+            "the package index cannot list workspace members; \
+             they are installed with `weave install <repo> --member <path>`"
+        );
+    }
+    let problems = validate_package(&dir)?;
+    if problems > 0 {
+        bail!("{problems} problem(s) found; nothing was published");
+    }
+    let release = publish::plan(&dir, member.as_deref(), bump)?;
+    publish::ensure_clean(&release.repo_root)?;
+    let from = release
+        .previous
+        .as_ref()
+        .map_or_else(|| "(unversioned)".to_string(), ToString::to_string);
+    eprintln!(
+        "{} {from} -> {} (tag {})",
+        release.name, release.version, release.tag
+    );
+    if dry_run {
+        return Ok(());
+    }
+
+    let commit = publish::release(&release)?;
+    eprintln!("committed {} and tagged {}", &commit[..8], release.tag);
+    if push {
+        publish::push(&release)?;
+        eprintln!("pushed {} to origin", release.tag);
+    }
+    if let Some(registry) = registry {
+        let description = std::fs::read_to_string(dir.join("SKILL.md"))
+            .ok()
+            .and_then(|content| weave::parser::parse_skill(&content).ok())
+            .and_then(|doc| doc.meta.description)
+            .unwrap_or_default();
+        let entry = IndexEntry {
+            name: release.name.clone(),
+            repo: publish::origin_url(&release.repo_root)?,
+            description,
+            tags: Vec::new(),
+        };
+        let work = tempfile::tempdir().context("failed to create a temporary directory")?;
+        let checkout = publish::clone_index(registry, work.path())?;
+        if publish::update_index(&checkout, &entry)? {
+            eprintln!("listed {} in {registry}", entry.name);
+        } else {
+            eprintln!("{registry} already lists {}", entry.name);
+        }
+    }
+    Ok(())
+}
+
+/// Directory and workspace member name of the package to publish: `member`
+/// of the enclosing workspace, the member containing `cwd`, or `cwd`.
+fn publish_target(cwd: &Path, member: Option<&str>) -> Result<(PathBuf, Option<String>)> {
+    let Some(ws) = Workspace::discover(cwd)? else {
+        if let Some(member) = member {
+            bail!("--member {member} given outside a workspace");
+        }
+        return Ok((cwd.to_path_buf(), None));
+    };
+    let found = match member {
+        Some(wanted) => Some(
+            ws.members
+                .iter()
+                .find(|m| m.name == wanted || m.path == wanted.trim_end_matches('/'))
+                .with_context(|| format!("workspace has no member '{wanted}'"))?,
+        ),
+        None => ws
+            .members
+            .iter()
+            .find(|m| cwd.starts_with(ws.member_dir(m))),
+    };
+    match found {
+        Some(m) => Ok((ws.member_dir(m), Some(m.name.clone()))),
+        // The workspace root may itself be a package.
+        None if cwd.join(".skill.toml").is_file() => Ok((cwd.to_path_buf(), None)),
+        None => bail!("run `weave publish` inside a workspace member or pass --member"),
+    }
+}
+
+/// Lint, compile and audit the package in `dir`, printing each problem.
+/// Returns the number of problems; lint warnings do not count.
+fn validate_package(dir: &Path) -> Result<usize> {
+    let mut problems = 0;
+    let config = lint::LintConfig::discover(dir)?;
+    for file in lint::find_skill_files(dir)? {
+        for f in lint::lint_file(&file, &config)? {
+            if f.severity == lint::Severity::Error {
+                eprintln!(
+                    "{}:{}: {}[{}]: {}",
+                    file.display(),
+                    f.line,
+                    f.severity,
+                    f.rule,
+                    f.message
+                );
+                problems += 1;
+            }
+        }
+    }
+    let patterns = dir.join("patterns");
+    if patterns.is_dir() {
+        problems += batch::compile_all(&patterns)?.failed;
+    }
+    for issue in package::audit_package(dir) {
+        eprintln!("  [!] {issue}");
+        problems += 1;
+    }
+    if let Err(err) = read_requirements(dir) {
+        eprintln!("error: {err:#}");
+        problems += 1;
+    }
+    Ok(problems)
+}
+
+/// Skill files named by `paths`, scanning directories for PATTERN.md; with
+/// no paths, `patterns/` if present, else the current directory.
+fn skill_files(paths: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    let paths = if paths.is_empty() {
+        let patterns = Path::new("patterns");
+        vec![if patterns.is_dir() {
+            patterns.to_path_buf()
+        } else {
+            PathBuf::from(".")
+        }]
+    } else {
+        paths
+    };
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(lint::find_skill_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Report `[dependencies]` requirements the locked packages do not satisfy.
+pub(crate) fn warn_version_conflicts(project_root: &Path, store_root: &Path) -> Result<()> {
+    for conflict in package::version_conflicts(project_root, store_root)? {
+        eprintln!("warning: {} {conflict}", conflict.dependent);
+    }
+    Ok(())
+}
+
+/// Report capabilities `pkg` declares in its `SKILL.md` frontmatter that
+/// this machine lacks. csa refuses to run a skill missing a tool or needing
+/// a newer csa, so these are worth fixing before the first run.
+pub(crate) fn warn_unmet_requirements(
+    project_root: &Path,
+    store_root: &Path,
+    pkg: &package::LockedPackage,
+) -> Result<()> {
+    let commit_key = if pkg.source_kind == package::SourceKind::Local {
+        "local"
+    } else {
+        pkg.commit.as_str()
+    };
+    let dir = package::package_dir(store_root, &pkg.name, commit_key)?;
+    let requirements = match read_requirements(&dir) {
+        Ok(requirements) => requirements,
+        Err(err) => {
+            eprintln!("warning: {}: {err:#}", pkg.name);
+            return Ok(());
+        }
+    };
+    if requirements.is_empty() {
+        return Ok(());
+    }
+    // weave and csa are released together, so weave's version is csa's.
+    let unmet = requirements.unmet(
+        env!("CARGO_PKG_VERSION"),
+        &configured_mcp_servers(project_root),
+    );
+    for requirement in &unmet {
+        eprintln!("warning: {} {requirement}", pkg.name);
+        eprintln!("  hint: {}", requirement.hint());
+    }
+    Ok(())
+}
+
+/// Remove stale pattern links and link the patterns of installed packages.
+fn relink_patterns(project_root: &Path, force: bool) -> Result<()> {
+    let stale_pats = link::remove_stale_pattern_links(project_root)?;
+    if !stale_pats.is_empty() {
+        eprintln!("removed {} stale pattern link(s)", stale_pats.len());
+    }
+    let pat_report = link::link_patterns(project_root, force)?;
+    let pat_created = pat_report.unique_created_count();
+    if pat_created > 0 {
+        eprintln!("linked {pat_created} pattern(s)");
+        for name in pat_report.unique_created_names() {
+            eprintln!("  + patterns/{name}");
+        }
+    }
+    for err in &pat_report.errors {
+        eprintln!("warning (pattern): {err}");
+    }
+    Ok(())
+}
+
+/// Scan project files for references to the skills behind `removed` links.
+fn report_stale_references(project_root: &Path, removed: &[PathBuf]) {
+    let removed_names: Vec<String> = {
+        let mut names = std::collections::HashSet::new();
+        for p in removed {
+            if let Some(n) = p.file_name() {
+                names.insert(n.to_string_lossy().to_string());
+            }
+        }
+        names.into_iter().collect()
+    };
+    if removed_names.is_empty() {
+        return;
+    }
+    let stale_refs = weave::stale_ref::scan_stale_skill_references(project_root, &removed_names);
+    if stale_refs.is_empty() {
+        return;
+    }
+    eprintln!();
+    eprintln!("warning: found stale references to removed skill(s):");
+    for r in &stale_refs {
+        eprintln!(
+            "  {}:{}: Skill '{}' was removed but is still referenced",
+            r.file.display(),
+            r.line,
+            r.skill_name,
+        );
+    }
+    eprintln!(
+        "  -> {} stale reference(s) found. Update these files to use the new skill name.",
+        stale_refs.len(),
+    );
+}
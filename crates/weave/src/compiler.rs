@@ -8,14 +8,15 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
 
-use crate::parser::{Block, SkillDocument, WorkspaceAccess};
+use crate::inputs::{VariableType, plan_variables};
+use crate::parser::{Block, RepeatMode, SkillDocument, WorkspaceAccess};
 
 // ---------------------------------------------------------------------------
 // Plan types
 // ---------------------------------------------------------------------------
 
 /// An executable plan produced by compiling a skill document.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ExecutionPlan {
     pub name: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -23,10 +24,24 @@ pub struct ExecutionPlan {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub variables: Vec<VariableDecl>,
     pub steps: Vec<PlanStep>,
+    /// Step groups from `## PARALLEL` blocks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parallel_groups: Vec<ParallelGroup>,
 }
 
-/// A single step in the execution plan.
+/// Steps that may run concurrently; the plan continues once all of them finish.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParallelGroup {
+    pub id: usize,
+    /// Member step ids, contiguous and in plan order.
+    pub steps: Vec<usize>,
+    /// Upper bound on concurrently running members; unbounded when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<u32>,
+}
+
+/// A single step in the execution plan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PlanStep {
     pub id: usize,
     pub title: String,
@@ -44,9 +59,23 @@ pub struct PlanStep {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub loop_var: Option<LoopSpec>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<RepeatSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace_access: Option<WorkspaceAccess>,
+    /// Plan variable bound to this step's output for later steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputBinding>,
+}
+
+/// Binds a step's output (or one section of it) to a plan variable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputBinding {
+    pub var: String,
+    /// Markdown heading whose section is captured instead of the full output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
 }
 
 /// How to handle a step failure.
@@ -70,6 +99,17 @@ pub struct LoopSpec {
     pub max_iterations: u32,
 }
 
+/// Repetition of a `WHILE`/`UNTIL` block, shared by all of its steps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepeatSpec {
+    /// Distinguishes adjacent blocks with the same condition.
+    pub id: usize,
+    pub mode: RepeatMode,
+    pub condition: String,
+    /// Mandatory guard; the block stops after this many iterations.
+    pub max_iterations: u32,
+}
+
 fn default_max_iterations() -> u32 {
     10
 }
@@ -80,6 +120,14 @@ pub struct VariableDecl {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default: Option<String>,
+    /// Type of an input declared in frontmatter `[variables]`.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<VariableType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The run must supply a value before the first step.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
 }
 
 /// A non-fatal warning produced during compilation.
@@ -130,6 +178,12 @@ static WORKSPACE_ACCESS_HINT_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?i)^Workspace\s+Access:\s*(read-only|mutating)\s*$").expect("valid regex")
 });
 
+/// Matches an `Output: VAR_NAME [from "Heading"]` line at the start of a step body.
+static OUTPUT_HINT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^Output:\s*([A-Za-z_][A-Za-z0-9_]*)(?:\s+from\s+"([^"]+)")?\s*$"#)
+        .expect("valid regex")
+});
+
 /// Matches a `MaxIterations: <n>` line at the start of a step body (FOR loops).
 static MAXITER_HINT_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?i)^MaxIterations:\s*(\d+)\s*$").expect("valid regex"));
@@ -154,23 +208,23 @@ pub fn compile_with_warnings(doc: &SkillDocument) -> Result<CompileOutput> {
     let mut ctx = CompileCtx::new();
     compile_blocks(&doc.body, &mut ctx)?;
 
-    let mut all_vars: Vec<String> = ctx.variables;
-    all_vars.sort();
-    all_vars.dedup();
-
-    let variables = all_vars
-        .into_iter()
-        .map(|name| VariableDecl {
-            name,
-            default: None,
-        })
+    // Variables bound by `Output:` hints are produced by the plan, not inputs.
+    let bound: Vec<&str> = ctx
+        .steps
+        .iter()
+        .filter_map(|step| step.output.as_ref().map(|o| o.var.as_str()))
         .collect();
+    warn_output_used_before_bound(&ctx.steps, &mut ctx.warnings);
+    let mut all_vars: Vec<String> = ctx.variables;
+    all_vars.retain(|name| !bound.contains(&name.as_str()));
+    let variables = plan_variables(all_vars, &doc.meta.variables)?;
 
     let plan = ExecutionPlan {
         name: doc.meta.name.clone(),
         description: doc.meta.description.clone().unwrap_or_default(),
         variables,
         steps: ctx.steps,
+        parallel_groups: ctx.parallel_groups,
     };
 
     Ok(CompileOutput {
@@ -187,6 +241,12 @@ struct CompileCtx {
     steps: Vec<PlanStep>,
     variables: Vec<String>,
     warnings: Vec<CompileWarning>,
+    parallel_groups: Vec<ParallelGroup>,
+    /// Set while compiling the body of a PARALLEL block.
+    in_parallel: bool,
+    /// Blocks compiled so far; also set while compiling a WHILE/UNTIL body.
+    repeat_blocks: usize,
+    in_repeat: bool,
     next_id: usize,
     /// Temporary storage for a MaxIterations hint found in a step body,
     /// consumed by `compile_for` when building the loop spec.
@@ -199,6 +259,10 @@ impl CompileCtx {
             steps: Vec::new(),
             variables: Vec::new(),
             warnings: Vec::new(),
+            parallel_groups: Vec::new(),
+            in_parallel: false,
+            repeat_blocks: 0,
+            in_repeat: false,
             next_id: 1,
             pending_max_iterations: None,
         }
@@ -242,6 +306,25 @@ fn compile_blocks(blocks: &[Block], ctx: &mut CompileCtx) -> Result<()> {
             } => {
                 compile_for(variable, collection, body, ctx)?;
             }
+            Block::Repeat {
+                mode,
+                condition,
+                max_iterations,
+                body,
+            } => {
+                compile_repeat(*mode, condition, *max_iterations, body, ctx)?;
+            }
+            Block::Parallel {
+                max_concurrency,
+                body,
+            } => {
+                compile_parallel(*max_concurrency, body, ctx)?;
+            }
+            Block::Matrix {
+                variable,
+                values,
+                body,
+            } => compile_blocks(&expand_matrix(variable, values, body), ctx)?,
             Block::Include { path } => {
                 compile_include(path, ctx);
             }
@@ -254,15 +337,16 @@ fn compile_blocks(blocks: &[Block], ctx: &mut CompileCtx) -> Result<()> {
 }
 
 /// Extracted step hints from the leading lines of a step body.
-struct StepHints {
-    tool: Option<String>,
-    tier: Option<String>,
-    session: Option<String>,
-    workspace_access: Option<WorkspaceAccess>,
-    on_fail: FailAction,
-    condition: Option<String>,
-    max_iterations: Option<u32>,
-    prompt: String,
+pub(crate) struct StepHints {
+    pub(crate) tool: Option<String>,
+    pub(crate) tier: Option<String>,
+    pub(crate) session: Option<String>,
+    pub(crate) workspace_access: Option<WorkspaceAccess>,
+    pub(crate) on_fail: FailAction,
+    pub(crate) condition: Option<String>,
+    pub(crate) max_iterations: Option<u32>,
+    pub(crate) output: Option<OutputBinding>,
+    pub(crate) prompt: String,
 }
 
 fn is_hint_preamble_line(line: &str) -> bool {
@@ -270,9 +354,9 @@ fn is_hint_preamble_line(line: &str) -> bool {
     trimmed.is_empty() || trimmed.starts_with('>')
 }
 
-/// Extract metadata hints (Tool, Tier, OnFail, MaxIterations) from the first
+/// Extract metadata hints (Tool, Tier, OnFail, Output, ...) from the first
 /// lines of a step body and return the remaining prompt text.
-fn extract_hints(body: &str) -> StepHints {
+pub(crate) fn extract_hints(body: &str) -> StepHints {
     let mut tool = None;
     let mut tier = None;
     let mut session = None;
@@ -280,6 +364,7 @@ fn extract_hints(body: &str) -> StepHints {
     let mut on_fail = FailAction::Abort;
     let mut condition = None;
     let mut max_iterations = None;
+    let mut output = None;
     let mut prompt_lines = Vec::new();
     let mut in_hints = true;
 
@@ -314,6 +399,13 @@ fn extract_hints(body: &str) -> StepHints {
                 max_iterations = caps[1].parse().ok();
                 continue;
             }
+            if let Some(caps) = OUTPUT_HINT_RE.captures(line) {
+                output = Some(OutputBinding {
+                    var: caps[1].to_string(),
+                    section: caps.get(2).map(|m| m.as_str().trim().to_string()),
+                });
+                continue;
+            }
             if is_hint_preamble_line(line) {
                 prompt_lines.push(line);
                 continue;
@@ -333,6 +425,7 @@ fn extract_hints(body: &str) -> StepHints {
         on_fail,
         condition,
         max_iterations,
+        output,
         prompt,
     }
 }
@@ -381,6 +474,7 @@ fn compile_step(title: &str, body: &str, variables: &[String], ctx: &mut Compile
     // compile_for if this step ends up inside a FOR block.
     ctx.pending_max_iterations = hints.max_iterations;
     if let Some(ref condition) = hints.condition {
+        check_condition(condition, &format!("step '{title}'"), ctx);
         ctx.collect_vars(condition);
     }
     if let Some(ref session) = hints.session {
@@ -399,16 +493,53 @@ fn compile_step(title: &str, body: &str, variables: &[String], ctx: &mut Compile
         loop_var: None,
         session: hints.session,
         workspace_access: hints.workspace_access,
+        repeat: None,
+        output: hints.output,
     });
     Ok(())
 }
 
+/// Warn when a step reads an `Output:` variable before any step binds it.
+fn warn_output_used_before_bound(steps: &[PlanStep], warnings: &mut Vec<CompileWarning>) {
+    for (binder_idx, binder) in steps.iter().enumerate() {
+        let Some(output) = &binder.output else {
+            continue;
+        };
+        let placeholder = format!("${{{}}}", output.var);
+        let bound_earlier = steps[..binder_idx]
+            .iter()
+            .any(|step| step.output.as_ref().is_some_and(|o| o.var == output.var));
+        if bound_earlier {
+            continue;
+        }
+        for step in &steps[..binder_idx] {
+            let reads = [
+                Some(&step.prompt),
+                step.condition.as_ref(),
+                step.session.as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|text| text.contains(&placeholder));
+            if reads {
+                warnings.push(CompileWarning {
+                    message: format!(
+                        "step {} uses {placeholder} before step {} binds it with `Output:`",
+                        step.id, binder.id
+                    ),
+                });
+            }
+        }
+    }
+}
+
 fn compile_if(
     condition: &str,
     then_blocks: &[Block],
     else_blocks: &[Block],
     ctx: &mut CompileCtx,
 ) -> Result<()> {
+    check_condition(condition, "IF block", ctx);
     ctx.collect_vars(condition);
 
     // Compile then-branch steps with the condition.
@@ -436,6 +567,8 @@ fn compile_if(
             loop_var: None,
             session: None,
             workspace_access: None,
+            repeat: None,
+            output: None,
         });
     }
 
@@ -514,6 +647,88 @@ fn compile_for(
     Ok(())
 }
 
+fn compile_repeat(
+    mode: RepeatMode,
+    condition: &str,
+    max_iterations: u32,
+    body: &[Block],
+    ctx: &mut CompileCtx,
+) -> Result<()> {
+    let keyword = mode.as_str().to_uppercase();
+    if ctx.in_repeat {
+        bail!("nested WHILE/UNTIL blocks are not supported ({keyword} {condition})");
+    }
+    check_condition(condition, &format!("{keyword} block"), ctx);
+    ctx.collect_vars(condition);
+
+    ctx.in_repeat = true;
+    let repeat_start = ctx.steps.len();
+    let compiled = compile_blocks(body, ctx);
+    ctx.in_repeat = false;
+    compiled?;
+
+    if repeat_start == ctx.steps.len() {
+        bail!("{keyword} block on `{condition}` has no compilable steps");
+    }
+    if max_iterations > MAX_ITERATIONS_WARN_THRESHOLD {
+        ctx.warnings.push(CompileWarning {
+            message: format!(
+                "{keyword} block on `{condition}`: max_iterations={max_iterations} exceeds \
+                 recommended threshold of {MAX_ITERATIONS_WARN_THRESHOLD} — possible misconfiguration"
+            ),
+        });
+    }
+
+    ctx.repeat_blocks += 1;
+    let spec = RepeatSpec {
+        id: ctx.repeat_blocks,
+        mode,
+        condition: condition.to_string(),
+        max_iterations,
+    };
+    for step in &mut ctx.steps[repeat_start..] {
+        step.repeat = Some(spec.clone());
+    }
+    Ok(())
+}
+
+fn compile_parallel(
+    max_concurrency: Option<u32>,
+    body: &[Block],
+    ctx: &mut CompileCtx,
+) -> Result<()> {
+    if ctx.in_parallel {
+        bail!("nested PARALLEL blocks are not supported");
+    }
+
+    ctx.in_parallel = true;
+    let group_start = ctx.steps.len();
+    let compiled = compile_blocks(body, ctx);
+    ctx.in_parallel = false;
+    compiled?;
+
+    let steps: Vec<usize> = ctx.steps[group_start..].iter().map(|s| s.id).collect();
+    if steps.is_empty() {
+        bail!("PARALLEL block has no compilable steps");
+    }
+    if steps.len() == 1 {
+        ctx.warnings.push(CompileWarning {
+            message: format!(
+                "PARALLEL block around step {} has a single step and runs sequentially",
+                steps[0]
+            ),
+        });
+    }
+
+    let id = ctx.parallel_groups.len() + 1;
+    ctx.parallel_groups.push(ParallelGroup {
+        id,
+        steps,
+        max_concurrency,
+    });
+    Ok(())
+}
+
 fn compile_include(path: &str, ctx: &mut CompileCtx) {
     let id = ctx.alloc_id();
     ctx.steps.push(PlanStep {
@@ -528,80 +743,26 @@ fn compile_include(path: &str, ctx: &mut CompileCtx) {
         loop_var: None,
         session: None,
         workspace_access: None,
+        repeat: None,
+        output: None,
     });
 }
 
 // ---------------------------------------------------------------------------
-// TOML serialization wrapper
+// TOML serialization
 // ---------------------------------------------------------------------------
 
-/// Wrapper for TOML serialization — uses `[workflow]` as primary key,
-/// with `[plan]` accepted as a legacy alias for backward compatibility.
-#[derive(Serialize, Deserialize)]
-struct WorkflowWrapper {
-    #[serde(alias = "plan")]
-    workflow: ExecutionPlan,
-}
+#[path = "compiler_conditions.rs"]
+mod conditions;
+use conditions::check_condition;
 
-/// Serialize an execution plan to TOML (uses `[workflow]` key).
-///
-/// Prompts for bash steps (or prompts containing backslashes) are emitted as
-/// TOML literal strings (`'''…'''`) so that backslash-heavy content is not
-/// mangled by escape processing.  If a prompt contains the literal-string
-/// delimiter `'''` or a carriage return (`\r`), we fall back to the default
-/// escaped basic string because TOML literal strings cannot represent those.
-pub fn plan_to_toml(plan: &ExecutionPlan) -> Result<String> {
-    let wrapper = WorkflowWrapper {
-        workflow: plan.clone(),
-    };
-    let pretty = toml::to_string_pretty(&wrapper)
-        .map_err(|e| anyhow::anyhow!("TOML serialization failed: {e}"))?;
-
-    // Post-process: convert prompts that benefit from literal strings.
-    let mut doc: toml_edit::DocumentMut = pretty
-        .parse()
-        .map_err(|e| anyhow::anyhow!("toml_edit parse failed: {e}"))?;
-
-    if let Some(workflow) = doc.get_mut("workflow")
-        && let Some(steps) = workflow.get_mut("steps")
-        && let Some(arr) = steps.as_array_of_tables_mut()
-    {
-        for (i, step) in arr.iter_mut().enumerate() {
-            let needs_literal = plan
-                .steps
-                .get(i)
-                .is_some_and(|s| s.tool.as_deref() == Some("bash") || s.prompt.contains('\\'));
-            if !needs_literal {
-                continue;
-            }
-            let text = &plan.steps[i].prompt;
-            // Literal strings cannot contain ''' or \r.
-            if text.contains("'''") || text.contains('\r') {
-                continue;
-            }
-            // Build a literal-string Value by parsing a tiny TOML snippet.
-            // toml_edit preserves the repr through parse, so the resulting
-            // Value carries the literal-string encoding.
-            let snippet = format!("v = '''\n{}'''", text);
-            if let Ok(tiny) = snippet.parse::<toml_edit::DocumentMut>()
-                && let Some(val) = tiny.get("v").and_then(|i| i.as_value()).cloned()
-                && let Some(prompt_item) = step.get_mut("prompt")
-            {
-                *prompt_item = toml_edit::Item::Value(val);
-            }
-        }
-    }
+#[path = "compiler_matrix.rs"]
+mod matrix;
+use matrix::expand_matrix;
 
-    Ok(doc.to_string())
-}
-
-/// Deserialize an execution plan from TOML.
-/// Accepts both `[workflow]` (current) and `[plan]` (legacy) as the top-level key.
-pub fn plan_from_toml(toml_str: &str) -> Result<ExecutionPlan> {
-    let wrapper: WorkflowWrapper = toml::from_str(toml_str)
-        .map_err(|e| anyhow::anyhow!("TOML deserialization failed: {e}"))?;
-    Ok(wrapper.workflow)
-}
+#[path = "compiler_toml.rs"]
+mod toml_io;
+pub use toml_io::{plan_from_toml, plan_to_toml};
 
 #[cfg(test)]
 #[path = "compiler_tests.rs"]
@@ -618,3 +779,15 @@ mod literal_tests;
 #[cfg(test)]
 #[path = "compiler_condition_tests.rs"]
 mod condition_tests;
+
+#[cfg(test)]
+#[path = "compiler_parallel_tests.rs"]
+mod parallel_tests;
+
+#[cfg(test)]
+#[path = "compiler_repeat_tests.rs"]
+mod repeat_tests;
+
+#[cfg(test)]
+#[path = "compiler_output_tests.rs"]
+mod output_tests;
@@ -29,3 +29,34 @@ fn test_compile_step_with_blank_condition_hint_treated_as_none() {
         assert_eq!(step.prompt, "Run the final gate.");
     }
 }
+
+#[test]
+fn test_compile_accepts_rich_conditions_and_warns_on_malformed_ones() {
+    let input = "---\nname = \"rich\"\n---\n\
+                 ## IF ${COUNT} > 3 || exists(${MARKER})\n## Big\nTool: bash\nCondition: env(CI) == true\nRun.\n## ENDIF\n";
+    let plan = compile(&parse_skill(input).unwrap()).unwrap();
+    assert_eq!(
+        plan.steps[0].condition.as_deref(),
+        Some("(${COUNT} > 3 || exists(${MARKER})) && (env(CI) == true)")
+    );
+    let vars: Vec<&str> = plan.variables.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(vars, ["COUNT", "MARKER"]);
+
+    for (header, body) in [
+        ("## IF ${A} & ${B}", "## X\nGo.\n## ENDIF\n"),
+        ("## WHILE (${A} MAX 3", "## X\nGo.\n## ENDWHILE\n"),
+        ("## Step\nCondition: size(${A})", "Go.\n"),
+    ] {
+        let input = format!("---\nname = \"bad\"\n---\n{header}\n{body}");
+        let output = compile_with_warnings(&parse_skill(&input).unwrap()).unwrap();
+        assert_eq!(output.warnings.len(), 1, "{header}");
+        assert!(
+            output.warnings[0].message.contains("invalid condition"),
+            "{header}: {}",
+            output.warnings[0].message
+        );
+        let toml = plan_to_toml(&output.plan).unwrap();
+        let err = plan_from_toml(&toml).unwrap_err();
+        assert!(format!("{err:#}").contains("invalid condition"), "{err:#}");
+    }
+}
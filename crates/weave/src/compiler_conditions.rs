@@ -0,0 +1,38 @@
+//! Condition checks for compiled plans.
+//!
+//! Conditions are parsed by [`crate::condition`]. The compiler warns about
+//! the ones the plan runner cannot evaluate, and loading a plan refuses them.
+
+use anyhow::{Context, Result};
+
+use super::{CompileCtx, CompileWarning, ExecutionPlan};
+use crate::condition::parse_condition;
+
+/// Warn about conditions the plan runner cannot evaluate; loading the
+/// compiled plan for a run fails until they are fixed.
+pub(super) fn check_condition(condition: &str, location: &str, ctx: &mut CompileCtx) {
+    if let Err(error) = parse_condition(condition) {
+        ctx.warnings.push(CompileWarning {
+            message: format!(
+                "{location}: invalid condition `{condition}` ({error:#}) — the plan cannot be \
+                 run until it is fixed"
+            ),
+        });
+    }
+}
+
+/// Reject a plan with a step or repeat condition the runner cannot parse.
+pub(super) fn check_plan_conditions(plan: &ExecutionPlan) -> Result<()> {
+    for step in &plan.steps {
+        let repeat = step.repeat.as_ref().map(|spec| &spec.condition);
+        for condition in step.condition.iter().chain(repeat) {
+            parse_condition(condition).with_context(|| {
+                format!(
+                    "step {} ({}): invalid condition `{condition}`",
+                    step.id, step.title
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
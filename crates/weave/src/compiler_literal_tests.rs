@@ -79,7 +79,9 @@ fn test_prompt_with_triple_quote_fallback() {
             loop_var: None,
             session: None,
             workspace_access: None,
+            ..Default::default()
         }],
+        ..Default::default()
     };
 
     let toml_str = plan_to_toml(&plan).unwrap();
@@ -0,0 +1,96 @@
+//! Compile-time expansion of `## MATRIX <var> IN [a, b]` blocks.
+//!
+//! The enclosed blocks are repeated once per value with every `${var}`
+//! replaced by the value. Step titles that do not mention the variable get a
+//! ` [value]` suffix so the expanded copies stay distinguishable.
+
+use crate::parser::{Block, extract_variables};
+
+/// Blocks of `body` repeated for each of `values`.
+pub(super) fn expand_matrix(variable: &str, values: &[String], body: &[Block]) -> Vec<Block> {
+    let placeholder = format!("${{{variable}}}");
+    values
+        .iter()
+        .flat_map(|value| {
+            let subst = |text: &str| text.replace(&placeholder, value);
+            body.iter()
+                .map(move |block| substitute(block, &subst, value))
+        })
+        .collect()
+}
+
+fn substitute(block: &Block, subst: &impl Fn(&str) -> String, value: &str) -> Block {
+    let blocks = |blocks: &[Block]| -> Vec<Block> {
+        blocks
+            .iter()
+            .map(|block| substitute(block, subst, value))
+            .collect()
+    };
+    match block {
+        Block::Step { title, body, .. } => {
+            let expanded = subst(title);
+            let title = if expanded == *title {
+                format!("{title} [{value}]")
+            } else {
+                expanded
+            };
+            let body = subst(body);
+            Block::Step {
+                title,
+                variables: extract_variables(&body),
+                body,
+            }
+        }
+        Block::If {
+            condition,
+            then_blocks,
+            else_blocks,
+        } => Block::If {
+            condition: subst(condition),
+            then_blocks: blocks(then_blocks),
+            else_blocks: blocks(else_blocks),
+        },
+        Block::For {
+            variable,
+            collection,
+            body,
+        } => Block::For {
+            variable: variable.clone(),
+            collection: subst(collection),
+            body: blocks(body),
+        },
+        Block::Repeat {
+            mode,
+            condition,
+            max_iterations,
+            body,
+        } => Block::Repeat {
+            mode: *mode,
+            condition: subst(condition),
+            max_iterations: *max_iterations,
+            body: blocks(body),
+        },
+        Block::Parallel {
+            max_concurrency,
+            body,
+        } => Block::Parallel {
+            max_concurrency: *max_concurrency,
+            body: blocks(body),
+        },
+        Block::Matrix {
+            variable,
+            values,
+            body,
+        } => Block::Matrix {
+            variable: variable.clone(),
+            values: values.clone(),
+            body: blocks(body),
+        },
+        Block::Include { path } => Block::Include { path: subst(path) },
+        Block::RawMarkdown(text) => Block::RawMarkdown(subst(text)),
+    }
+}
+
+#[cfg(test)]
+#[path = "compiler_matrix_tests.rs"]
+mod tests;
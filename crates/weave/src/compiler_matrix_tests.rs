@@ -0,0 +1,116 @@
+use crate::compiler::{ExecutionPlan, compile};
+use crate::parser::parse_skill;
+
+fn compile_doc(input: &str) -> anyhow::Result<ExecutionPlan> {
+    compile(&parse_skill(input)?)
+}
+
+#[test]
+fn matrix_expands_steps_per_value() {
+    let plan = compile_doc(
+        r#"---
+name = "compare"
+---
+## MATRIX tool IN [codex, gemini-cli]
+## Review with ${tool}
+Tool: ${tool}
+Review ${SCOPE} as ${tool}.
+## Summarize
+Tool: ${tool}
+List the findings.
+## ENDMATRIX
+## Compare
+Compare the reviews.
+"#,
+    )
+    .unwrap();
+
+    let steps: Vec<(&str, Option<&str>, &str)> = plan
+        .steps
+        .iter()
+        .map(|s| (s.title.as_str(), s.tool.as_deref(), s.prompt.as_str()))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            (
+                "Review with codex",
+                Some("codex"),
+                "Review ${SCOPE} as codex."
+            ),
+            ("Summarize [codex]", Some("codex"), "List the findings."),
+            (
+                "Review with gemini-cli",
+                Some("gemini-cli"),
+                "Review ${SCOPE} as gemini-cli."
+            ),
+            (
+                "Summarize [gemini-cli]",
+                Some("gemini-cli"),
+                "List the findings."
+            ),
+            ("Compare", None, "Compare the reviews."),
+        ]
+    );
+    let ids: Vec<usize> = plan.steps.iter().map(|s| s.id).collect();
+    assert_eq!(ids, [1, 2, 3, 4, 5]);
+
+    let vars: Vec<&str> = plan.variables.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(vars, ["SCOPE"]);
+}
+
+#[test]
+fn matrix_inside_parallel_fans_out_and_substitutes_conditions() {
+    let plan = compile_doc(
+        r#"---
+name = "fanout"
+---
+## PARALLEL
+## MATRIX tool IN [codex, "claude-code"]
+## IF ${tool} != ${SKIP_TOOL}
+## Check
+Tool: ${tool}
+Run the check.
+## ENDIF
+## ENDMATRIX
+## ENDPARALLEL
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(plan.parallel_groups.len(), 1);
+    assert_eq!(plan.parallel_groups[0].steps, [1, 2]);
+    assert_eq!(plan.steps[0].title, "Check [codex]");
+    assert_eq!(plan.steps[1].tool.as_deref(), Some("claude-code"));
+    assert_eq!(
+        plan.steps[1].condition.as_deref(),
+        Some("claude-code != ${SKIP_TOOL}")
+    );
+}
+
+#[test]
+fn matrix_header_errors() {
+    let parse = |header: &str| {
+        parse_skill(&format!(
+            "---\nname = \"m\"\n---\n{header}\n## A\nGo.\n## ENDMATRIX\n"
+        ))
+    };
+    let cases = [
+        ("## MATRIX tool IN codex, gemini-cli", "expects a list"),
+        ("## MATRIX tool IN [codex, ]", "empty value"),
+        ("## MATRIX tool IN [codex, codex]", "more than once"),
+        ("## MATRIX tool IN [${TOOLS}]", "must be literals"),
+    ];
+    for (header, expected) in cases {
+        let err = parse(header).unwrap_err();
+        assert!(err.to_string().contains(expected), "{header}: {err}");
+    }
+
+    let err = parse_skill("---\nname = \"m\"\n---\n## MATRIX t IN [a]\n## A\nGo.\n").unwrap_err();
+    assert!(err.to_string().contains("missing ## ENDMATRIX"), "{err}");
+    let err = parse_skill("---\nname = \"m\"\n---\n## A\nGo.\n## ENDMATRIX\n").unwrap_err();
+    assert!(
+        err.to_string().contains("without matching ## MATRIX"),
+        "{err}"
+    );
+}
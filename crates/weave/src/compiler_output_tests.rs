@@ -0,0 +1,65 @@
+use super::*;
+use crate::parser::parse_skill;
+
+#[test]
+fn test_compile_output_hint_binds_variable() {
+    let doc = parse_skill(
+        r#"---
+name = "review"
+---
+## Review
+Tool: codex
+Output: FINDINGS from "Findings"
+Review the diff and list findings under a `## Findings` heading.
+## Summarize
+Tool: bash
+Output: REPORT
+Summarize ${FINDINGS} for ${AUDIENCE}.
+## Fix
+Condition: ${FINDINGS}
+Fix ${FINDINGS}.
+"#,
+    )
+    .unwrap();
+    let output = compile_with_warnings(&doc).unwrap();
+    let plan = output.plan;
+
+    assert_eq!(
+        plan.steps[0].output,
+        Some(OutputBinding {
+            var: "FINDINGS".to_string(),
+            section: Some("Findings".to_string()),
+        })
+    );
+    assert_eq!(
+        plan.steps[0].prompt,
+        "Review the diff and list findings under a `## Findings` heading."
+    );
+    assert_eq!(plan.steps[1].output.as_ref().unwrap().section, None);
+    assert!(plan.steps[2].output.is_none());
+    // Bound variables are produced by the plan, so they are not inputs.
+    let names: Vec<&str> = plan.variables.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["AUDIENCE"]);
+    assert!(output.warnings.is_empty());
+
+    let toml = plan_to_toml(&plan).unwrap();
+    assert!(toml.contains("section = \"Findings\""), "{toml}");
+    assert_eq!(plan_from_toml(&toml).unwrap(), plan);
+}
+
+#[test]
+fn test_compile_warns_when_output_is_read_before_bound() {
+    let doc = parse_skill(
+        "---\nname = \"early\"\n---\n## Use\nPrint ${RESULT}.\n## Produce\nOutput: RESULT\nMake it.\n",
+    )
+    .unwrap();
+    let output = compile_with_warnings(&doc).unwrap();
+    assert_eq!(output.warnings.len(), 1);
+    assert!(
+        output.warnings[0]
+            .message
+            .contains("step 1 uses ${RESULT} before step 2 binds it"),
+        "{:?}",
+        output.warnings
+    );
+}
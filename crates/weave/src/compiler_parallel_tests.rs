@@ -0,0 +1,94 @@
+use super::*;
+use crate::parser::parse_skill;
+
+fn compile_doc(input: &str) -> Result<CompileOutput> {
+    compile_with_warnings(&parse_skill(input)?)
+}
+
+#[test]
+fn test_compile_parallel_block_records_group() {
+    let output = compile_doc(
+        r#"---
+name = "fanout"
+---
+## Prepare
+Collect the diff.
+## PARALLEL MAX 2
+## Review Security
+Tool: codex
+Check for vulnerabilities.
+## IF ${HAS_UI}
+## Review UI
+Tool: gemini-cli
+Check accessibility.
+## ENDIF
+## Review Style
+Check naming.
+## ENDPARALLEL
+## Merge
+Combine findings.
+"#,
+    )
+    .unwrap();
+    let plan = output.plan;
+
+    assert_eq!(plan.steps.len(), 5);
+    assert_eq!(
+        plan.parallel_groups,
+        vec![ParallelGroup {
+            id: 1,
+            steps: vec![2, 3, 4],
+            max_concurrency: Some(2),
+        }]
+    );
+    assert_eq!(plan.steps[2].condition.as_deref(), Some("${HAS_UI}"));
+    assert!(output.warnings.is_empty());
+}
+
+#[test]
+fn test_compile_parallel_groups_round_trip_through_toml() {
+    let plan = compile_doc(
+        "---\nname = \"p\"\n---\n## PARALLEL\n## A\nx\n## B\ny\n## ENDPARALLEL\n\
+         ## PARALLEL MAX 3\n## C\nz\n## D\nw\n## ENDPARALLEL\n",
+    )
+    .unwrap()
+    .plan;
+    assert_eq!(plan.parallel_groups.len(), 2);
+    assert_eq!(plan.parallel_groups[0].max_concurrency, None);
+    assert_eq!(plan.parallel_groups[1].steps, vec![3, 4]);
+
+    let toml = plan_to_toml(&plan).unwrap();
+    assert!(toml.contains("[[workflow.parallel_groups]]"), "{toml}");
+    assert_eq!(plan_from_toml(&toml).unwrap(), plan);
+}
+
+#[test]
+fn test_compile_plan_without_parallel_blocks_omits_groups() {
+    let plan = compile_doc("---\nname = \"seq\"\n---\n## A\nx\n")
+        .unwrap()
+        .plan;
+    assert!(plan.parallel_groups.is_empty());
+    assert!(!plan_to_toml(&plan).unwrap().contains("parallel_groups"));
+}
+
+#[test]
+fn test_compile_rejects_nested_and_empty_parallel_blocks() {
+    let nested = compile_doc(
+        "---\nname = \"p\"\n---\n## PARALLEL\n## PARALLEL\n## A\nx\n## ENDPARALLEL\n## ENDPARALLEL\n",
+    )
+    .unwrap_err();
+    assert!(nested.to_string().contains("nested PARALLEL"), "{nested}");
+
+    let empty = compile_doc("---\nname = \"p\"\n---\n## PARALLEL\nJust prose.\n## ENDPARALLEL\n")
+        .unwrap_err();
+    assert!(empty.to_string().contains("no compilable steps"), "{empty}");
+}
+
+#[test]
+fn test_compile_single_step_parallel_block_warns() {
+    let output =
+        compile_doc("---\nname = \"p\"\n---\n## PARALLEL\n## A\nx\n## ENDPARALLEL\n").unwrap();
+    assert_eq!(output.plan.parallel_groups[0].steps, vec![1]);
+    assert_eq!(output.warnings.len(), 1);
+    assert!(output.warnings[0].message.contains("single step"));
+}
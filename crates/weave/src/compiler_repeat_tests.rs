@@ -0,0 +1,79 @@
+use super::*;
+use crate::parser::parse_skill;
+
+fn compile_doc(input: &str) -> Result<CompileOutput> {
+    compile_with_warnings(&parse_skill(input)?)
+}
+
+#[test]
+fn test_compile_while_block_tags_steps() {
+    let output = compile_doc(
+        r#"---
+name = "retry"
+---
+## Run Tests
+Tool: bash
+Run the suite.
+## WHILE ${TESTS_FAILING} MAX 5
+## Fix Tests
+Tool: codex
+Fix the failing tests.
+## Rerun Tests
+Tool: bash
+Run the suite again.
+## ENDWHILE
+## UNTIL ${CI_GREEN} MAX 3
+## Poll CI
+Check CI.
+## ENDUNTIL
+"#,
+    )
+    .unwrap();
+    let plan = output.plan;
+
+    assert!(plan.steps[0].repeat.is_none());
+    let expected = RepeatSpec {
+        id: 1,
+        mode: RepeatMode::While,
+        condition: "${TESTS_FAILING}".to_string(),
+        max_iterations: 5,
+    };
+    assert_eq!(plan.steps[1].repeat.as_ref(), Some(&expected));
+    assert_eq!(plan.steps[2].repeat.as_ref(), Some(&expected));
+    let until = plan.steps[3].repeat.as_ref().unwrap();
+    assert_eq!((until.id, until.mode), (2, RepeatMode::Until));
+
+    let names: Vec<&str> = plan.variables.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["CI_GREEN", "TESTS_FAILING"]);
+    assert!(output.warnings.is_empty());
+
+    let toml = plan_to_toml(&plan).unwrap();
+    assert!(toml.contains("mode = \"while\""), "{toml}");
+    assert_eq!(plan_from_toml(&toml).unwrap(), plan);
+}
+
+#[test]
+fn test_compile_repeat_rejects_nesting_and_empty_body() {
+    let nested = compile_doc(
+        "---\nname = \"w\"\n---\n## WHILE ${A} MAX 2\n## UNTIL ${B} MAX 2\n## S\nx\n\
+         ## ENDUNTIL\n## ENDWHILE\n",
+    )
+    .unwrap_err();
+    assert!(
+        nested.to_string().contains("nested WHILE/UNTIL"),
+        "{nested}"
+    );
+
+    let empty = compile_doc("---\nname = \"w\"\n---\n## WHILE ${A} MAX 2\nprose\n## ENDWHILE\n")
+        .unwrap_err();
+    assert!(empty.to_string().contains("no compilable steps"), "{empty}");
+}
+
+#[test]
+fn test_compile_repeat_warns_on_high_guard() {
+    let output =
+        compile_doc("---\nname = \"w\"\n---\n## WHILE ${A} MAX 500\n## S\nx\n## ENDWHILE\n")
+            .unwrap();
+    assert_eq!(output.warnings.len(), 1);
+    assert!(output.warnings[0].message.contains("max_iterations=500"));
+}
@@ -0,0 +1,78 @@
+//! TOML serialization of execution plans.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::ExecutionPlan;
+use super::conditions::check_plan_conditions;
+
+/// Wrapper for TOML serialization — uses `[workflow]` as primary key,
+/// with `[plan]` accepted as a legacy alias for backward compatibility.
+#[derive(Serialize, Deserialize)]
+struct WorkflowWrapper {
+    #[serde(alias = "plan")]
+    workflow: ExecutionPlan,
+}
+
+/// Serialize an execution plan to TOML (uses `[workflow]` key).
+///
+/// Prompts for bash steps (or prompts containing backslashes) are emitted as
+/// TOML literal strings (`'''…'''`) so that backslash-heavy content is not
+/// mangled by escape processing.  If a prompt contains the literal-string
+/// delimiter `'''` or a carriage return (`\r`), we fall back to the default
+/// escaped basic string because TOML literal strings cannot represent those.
+pub fn plan_to_toml(plan: &ExecutionPlan) -> Result<String> {
+    let wrapper = WorkflowWrapper {
+        workflow: plan.clone(),
+    };
+    let pretty = toml::to_string_pretty(&wrapper)
+        .map_err(|e| anyhow::anyhow!("TOML serialization failed: {e}"))?;
+
+    // Post-process: convert prompts that benefit from literal strings.
+    let mut doc: toml_edit::DocumentMut = pretty
+        .parse()
+        .map_err(|e| anyhow::anyhow!("toml_edit parse failed: {e}"))?;
+
+    if let Some(workflow) = doc.get_mut("workflow")
+        && let Some(steps) = workflow.get_mut("steps")
+        && let Some(arr) = steps.as_array_of_tables_mut()
+    {
+        for (i, step) in arr.iter_mut().enumerate() {
+            let needs_literal = plan
+                .steps
+                .get(i)
+                .is_some_and(|s| s.tool.as_deref() == Some("bash") || s.prompt.contains('\\'));
+            if !needs_literal {
+                continue;
+            }
+            let text = &plan.steps[i].prompt;
+            // Literal strings cannot contain ''' or \r.
+            if text.contains("'''") || text.contains('\r') {
+                continue;
+            }
+            // Build a literal-string Value by parsing a tiny TOML snippet.
+            // toml_edit preserves the repr through parse, so the resulting
+            // Value carries the literal-string encoding.
+            let snippet = format!("v = '''\n{}'''", text);
+            if let Ok(tiny) = snippet.parse::<toml_edit::DocumentMut>()
+                && let Some(val) = tiny.get("v").and_then(|i| i.as_value()).cloned()
+                && let Some(prompt_item) = step.get_mut("prompt")
+            {
+                *prompt_item = toml_edit::Item::Value(val);
+            }
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Deserialize an execution plan from TOML.
+/// Accepts both `[workflow]` (current) and `[plan]` (legacy) as the top-level key.
+/// Plans with a condition the runner cannot parse are rejected: the condition
+/// would be false on every run and silently skip the steps it guards.
+pub fn plan_from_toml(toml_str: &str) -> Result<ExecutionPlan> {
+    let wrapper: WorkflowWrapper = toml::from_str(toml_str)
+        .map_err(|e| anyhow::anyhow!("TOML deserialization failed: {e}"))?;
+    check_plan_conditions(&wrapper.workflow)?;
+    Ok(wrapper.workflow)
+}
@@ -0,0 +1,488 @@
+//! Condition evaluation for plan step execution.
+//!
+//! Supports boolean expressions used in workflow IF/WHILE/UNTIL conditions
+//! and `Condition:` hints:
+//! - `${VAR}` → true when var is non-empty (and not "false"/"0")
+//! - `!expr`, `!(expr)` → logical NOT
+//! - `a && b`, `a || b` → logical AND / OR (`&&` binds tighter), with
+//!   parentheses for grouping
+//! - `a == b`, `a != b` → equality, numeric when both sides are numbers
+//! - `a < b`, `a <= b`, `a > b`, `a >= b` → numeric comparison
+//! - `exists(path)` → whether the path exists; relative paths are resolved
+//!   against the base directory of the evaluation (the project root)
+//! - `env(NAME)` → the value of environment variable `NAME`
+//!
+//! Operands are `${VAR}` references, bare words, numbers, or `"quoted"`
+//! strings; variables are substituted inside all of them. Expressions are
+//! tokenized before substitution, so variable values never act as operators.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::{Result, bail};
+use regex::Regex;
+
+/// Matches `${VAR_NAME}` placeholders.
+static VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid regex"));
+
+/// Evaluate a condition expression against the current variables, resolving
+/// relative `exists()` paths against the current directory.
+///
+/// Unresolved `${VAR}` references (where the var was not provided) evaluate to
+/// false, allowing workflows with optional condition variables to skip those
+/// steps cleanly.  Malformed expressions (unbalanced parens, empty) also
+/// evaluate to false (fail-closed).
+pub fn evaluate_condition(condition: &str, vars: &HashMap<String, String>) -> bool {
+    evaluate_condition_in(condition, vars, Path::new("."))
+}
+
+/// [`evaluate_condition`] with relative `exists()` paths resolved against
+/// `base_dir`. A malformed expression is logged as a warning, since the step
+/// it guards is skipped.
+pub fn evaluate_condition_in(
+    condition: &str,
+    vars: &HashMap<String, String>,
+    base_dir: &Path,
+) -> bool {
+    match parse_condition(condition) {
+        Ok(parsed) => parsed.evaluate_in(vars, base_dir),
+        Err(error) => {
+            tracing::warn!(%condition, "invalid condition evaluates to false: {error:#}");
+            false
+        }
+    }
+}
+
+/// Parse a condition expression.
+pub fn parse_condition(condition: &str) -> Result<Condition> {
+    let tokens = tokenize(condition)?;
+    if tokens.is_empty() {
+        bail!("empty condition");
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let parsed = parser.or()?;
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        bail!("unexpected {token} in condition");
+    }
+    Ok(parsed)
+}
+
+/// A parsed condition expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Or(Vec<Condition>),
+    And(Vec<Condition>),
+    Not(Box<Condition>),
+    Compare {
+        left: Operand,
+        op: CompareOp,
+        right: Operand,
+    },
+    /// A lone operand, tested for truthiness.
+    Value(Operand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Whitespace-separated terms; their values are joined with single spaces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operand(pub Vec<Term>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// Bare text, possibly containing `${VAR}` references.
+    Word(String),
+    /// Contents of a `"..."` or `'...'` string.
+    Quoted(String),
+    Call(Builtin, Operand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Builtin {
+    /// `exists(path)`: `true` when the path exists.
+    Exists,
+    /// `env(NAME)`: the environment variable's value; unresolved when unset.
+    Env,
+}
+
+impl Condition {
+    /// Evaluate with relative `exists()` paths resolved against `base_dir`.
+    pub fn evaluate_in(&self, vars: &HashMap<String, String>, base_dir: &Path) -> bool {
+        match self {
+            Self::Or(parts) => parts.iter().any(|part| part.evaluate_in(vars, base_dir)),
+            Self::And(parts) => parts.iter().all(|part| part.evaluate_in(vars, base_dir)),
+            Self::Not(inner) => !inner.evaluate_in(vars, base_dir),
+            Self::Compare { left, op, right } => {
+                match (left.resolve(vars, base_dir), right.resolve(vars, base_dir)) {
+                    (Some(left), Some(right)) => op.apply(left.trim(), right.trim()),
+                    _ => false,
+                }
+            }
+            Self::Value(operand) => operand
+                .resolve(vars, base_dir)
+                .is_some_and(|value| is_truthy(&value)),
+        }
+    }
+}
+
+impl CompareOp {
+    fn apply(self, left: &str, right: &str) -> bool {
+        let numbers = left.parse::<f64>().ok().zip(right.parse::<f64>().ok());
+        match (self, numbers) {
+            (Self::Eq, Some((l, r))) => l == r,
+            (Self::Ne, Some((l, r))) => l != r,
+            (Self::Eq, None) => left == right,
+            (Self::Ne, None) => left != right,
+            (Self::Lt, Some((l, r))) => l < r,
+            (Self::Le, Some((l, r))) => l <= r,
+            (Self::Gt, Some((l, r))) => l > r,
+            (Self::Ge, Some((l, r))) => l >= r,
+            // Ordering is only defined on numbers (fail-closed).
+            (Self::Lt | Self::Le | Self::Gt | Self::Ge, None) => false,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+impl Operand {
+    /// The operand's value, or `None` when a variable or environment
+    /// variable it references is unset.
+    fn resolve(&self, vars: &HashMap<String, String>, base_dir: &Path) -> Option<String> {
+        let values = self
+            .0
+            .iter()
+            .map(|term| match term {
+                Term::Word(text) | Term::Quoted(text) => substitute_vars(text, vars),
+                Term::Call(Builtin::Exists, arg) => {
+                    let path = arg.resolve(vars, base_dir)?;
+                    Some(base_dir.join(path.trim()).exists().to_string())
+                }
+                Term::Call(Builtin::Env, arg) => {
+                    std::env::var(arg.resolve(vars, base_dir)?.trim()).ok()
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(values.join(" "))
+    }
+}
+
+/// Truthy: non-empty and not literally "false" or "0".
+fn is_truthy(value: &str) -> bool {
+    let lower = value.trim().to_lowercase();
+    !lower.is_empty() && lower != "false" && lower != "0"
+}
+
+/// Substitute `${VAR}` placeholders for condition evaluation only, returning
+/// `None` when any referenced variable is unset.
+///
+/// Safety: this function does not execute shell code. The resolved value is
+/// consumed only by condition evaluation, so shell-injection concerns from
+/// bash script execution do not apply here.
+fn substitute_vars(template: &str, vars: &HashMap<String, String>) -> Option<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut last = 0;
+    for caps in VAR_RE.captures_iter(template) {
+        let whole = caps.get(0)?;
+        result.push_str(&template[last..whole.start()]);
+        result.push_str(vars.get(&caps[1])?);
+        last = whole.end();
+    }
+    result.push_str(&template[last..]);
+    Some(result)
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+impl fmt::Display for Condition {
+    /// Canonical text: single spaces around operators and parentheses around
+    /// every nested `&&`/`||` group.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, parts: &[Condition], sep: &str| {
+            for (idx, part) in parts.iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(sep)?;
+                }
+                match part {
+                    Self::Or(_) | Self::And(_) => write!(f, "({part})")?,
+                    _ => write!(f, "{part}")?,
+                }
+            }
+            Ok(())
+        };
+        match self {
+            Self::Or(parts) => join(f, parts, " || "),
+            Self::And(parts) => join(f, parts, " && "),
+            Self::Not(inner) => match inner.as_ref() {
+                Self::Value(_) => write!(f, "!{inner}"),
+                _ => write!(f, "!({inner})"),
+            },
+            Self::Compare { left, op, right } => write!(f, "{left} {} {right}", op.as_str()),
+            Self::Value(operand) => write!(f, "{operand}"),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, term) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            match term {
+                Term::Word(text) => f.write_str(text)?,
+                Term::Quoted(text) if text.contains('"') => write!(f, "'{text}'")?,
+                Term::Quoted(text) => write!(f, "\"{text}\"")?,
+                Term::Call(builtin, arg) => write!(f, "{}({arg})", builtin.name())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Builtin {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "exists" => Some(Self::Exists),
+            "env" => Some(Self::Env),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Exists => "exists",
+            Self::Env => "env",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+    Op(CompareOp),
+    Word(String),
+    Quoted(String),
+    /// A word immediately followed by `(`, e.g. `exists(`.
+    Call(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LParen => f.write_str("`(`"),
+            Self::RParen => f.write_str("`)`"),
+            Self::Not => f.write_str("`!`"),
+            Self::And => f.write_str("`&&`"),
+            Self::Or => f.write_str("`||`"),
+            Self::Op(op) => write!(f, "`{}`", op.as_str()),
+            Self::Word(text) | Self::Quoted(text) => write!(f, "operand `{text}`"),
+            Self::Call(name) => write!(f, "`{name}(`"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while let Some(ch) = rest.chars().next() {
+        let two = rest.get(..2).unwrap_or("");
+        let (token, len) = match (ch, two) {
+            (c, _) if c.is_whitespace() => {
+                rest = rest.trim_start();
+                continue;
+            }
+            (_, "&&") => (Token::And, 2),
+            (_, "||") => (Token::Or, 2),
+            (_, "==") => (Token::Op(CompareOp::Eq), 2),
+            (_, "!=") => (Token::Op(CompareOp::Ne), 2),
+            (_, "<=") => (Token::Op(CompareOp::Le), 2),
+            (_, ">=") => (Token::Op(CompareOp::Ge), 2),
+            ('<', _) => (Token::Op(CompareOp::Lt), 1),
+            ('>', _) => (Token::Op(CompareOp::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('&' | '|' | '=', _) => bail!("unexpected `{ch}` in condition (use `&&`, `||`, `==`)"),
+            ('"' | '\'', _) => {
+                let Some(end) = rest[1..].find(ch) else {
+                    bail!("unterminated string in condition");
+                };
+                (Token::Quoted(rest[1..1 + end].to_string()), end + 2)
+            }
+            _ => {
+                let len = word_len(rest);
+                let word = rest[..len].to_string();
+                if rest[len..].starts_with('(') {
+                    (Token::Call(word), len + 1)
+                } else {
+                    (Token::Word(word), len)
+                }
+            }
+        };
+        tokens.push(token);
+        rest = &rest[len..];
+    }
+    Ok(tokens)
+}
+
+/// Length of the bare word at the start of `input`; `${...}` references are
+/// kept whole.
+fn word_len(input: &str) -> usize {
+    let mut idx = 0;
+    while idx < input.len() {
+        let rest = &input[idx..];
+        if rest.starts_with("${")
+            && let Some(close) = rest.find('}')
+        {
+            idx += close + 1;
+            continue;
+        }
+        let ch = rest.chars().next().unwrap_or(' ');
+        if ch.is_whitespace() || "()!&|=<>\"'".contains(ch) {
+            break;
+        }
+        idx += ch.len_utf8();
+    }
+    idx
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Condition> {
+        let mut parts = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            parts.push(self.and()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Condition::Or(parts)
+        })
+    }
+
+    fn and(&mut self) -> Result<Condition> {
+        let mut parts = vec![self.unary()?];
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            parts.push(self.unary()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Condition::And(parts)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Condition> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(Condition::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.or()?;
+                self.expect_close()?;
+                Ok(inner)
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Condition> {
+        let left = self.operand()?;
+        let Some(Token::Op(op)) = self.peek().cloned() else {
+            return Ok(Condition::Value(left));
+        };
+        self.pos += 1;
+        let right = self.operand()?;
+        Ok(Condition::Compare { left, op, right })
+    }
+
+    fn operand(&mut self) -> Result<Operand> {
+        let mut terms = Vec::new();
+        loop {
+            match self.peek().cloned() {
+                Some(Token::Word(text)) => terms.push(Term::Word(text)),
+                Some(Token::Quoted(text)) => terms.push(Term::Quoted(text)),
+                Some(Token::Call(name)) => {
+                    let Some(builtin) = Builtin::from_name(&name) else {
+                        bail!("unknown function `{name}` in condition (expected exists or env)");
+                    };
+                    self.pos += 1;
+                    let arg = self.operand()?;
+                    self.expect_close()?;
+                    terms.push(Term::Call(builtin, arg));
+                    continue;
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        if terms.is_empty() {
+            match self.peek() {
+                Some(token) => bail!("expected an operand, found {token}"),
+                None => bail!("condition ends where an operand is expected"),
+            }
+        }
+        Ok(Operand(terms))
+    }
+
+    fn expect_close(&mut self) -> Result<()> {
+        match self.next() {
+            Some(Token::RParen) => Ok(()),
+            Some(token) => bail!("expected `)`, found {token}"),
+            None => bail!("unbalanced parentheses in condition"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "condition_tests.rs"]
+mod tests;
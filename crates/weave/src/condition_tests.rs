@@ -0,0 +1,297 @@
+use super::*;
+
+#[test]
+fn unset_var_is_false() {
+    let vars = HashMap::new();
+    assert!(!evaluate_condition("${UNSET}", &vars));
+}
+
+#[test]
+fn empty_var_is_false() {
+    let mut vars = HashMap::new();
+    vars.insert("EMPTY".into(), "".into());
+    assert!(!evaluate_condition("${EMPTY}", &vars));
+}
+
+#[test]
+fn false_literal_is_false() {
+    let mut vars = HashMap::new();
+    vars.insert("FLAG".into(), "false".into());
+    assert!(!evaluate_condition("${FLAG}", &vars));
+}
+
+#[test]
+fn zero_is_false() {
+    let mut vars = HashMap::new();
+    vars.insert("FLAG".into(), "0".into());
+    assert!(!evaluate_condition("${FLAG}", &vars));
+}
+
+#[test]
+fn nonempty_var_is_true() {
+    let mut vars = HashMap::new();
+    vars.insert("FLAG".into(), "yes".into());
+    assert!(evaluate_condition("${FLAG}", &vars));
+}
+
+#[test]
+fn negation() {
+    let mut vars = HashMap::new();
+    vars.insert("FLAG".into(), "yes".into());
+    assert!(!evaluate_condition("!(${FLAG})", &vars));
+
+    let empty_vars = HashMap::new();
+    assert!(evaluate_condition("!(${FLAG})", &empty_vars));
+}
+
+#[test]
+fn conjunction() {
+    let mut vars = HashMap::new();
+    vars.insert("A".into(), "yes".into());
+    vars.insert("B".into(), "yes".into());
+    assert!(evaluate_condition("(${A}) && (${B})", &vars));
+
+    let mut partial = HashMap::new();
+    partial.insert("A".into(), "yes".into());
+    assert!(!evaluate_condition("(${A}) && (${B})", &partial));
+}
+
+#[test]
+fn nested_not_and_and() {
+    // Pattern from dev2merge/dev-to-merge: (${BOT_HAS_ISSUES}) && (!(${COMMENT_IS_FALSE_POSITIVE}))
+    let mut vars = HashMap::new();
+    vars.insert("BOT_HAS_ISSUES".into(), "yes".into());
+    // COMMENT_IS_FALSE_POSITIVE not set → !(false) = true
+    assert!(evaluate_condition(
+        "(${BOT_HAS_ISSUES}) && (!(${COMMENT_IS_FALSE_POSITIVE}))",
+        &vars
+    ));
+
+    // Both unset → false && true = false
+    let empty = HashMap::new();
+    assert!(!evaluate_condition(
+        "(${BOT_HAS_ISSUES}) && (!(${COMMENT_IS_FALSE_POSITIVE}))",
+        &empty
+    ));
+}
+
+#[test]
+fn three_conjuncts_with_negation() {
+    // P1 regression: 3+ conjuncts broke the old `find(") && (")` logic.
+    let expr =
+        "(!(${COMMENT_IS_FALSE_POSITIVE})) && (${REVIEW_HAS_ISSUES}) && (!(${COMMENT_IS_STALE}))";
+
+    // All conditions met: !(unset=false)=true && yes=true && !(unset=false)=true → true
+    let mut vars = HashMap::new();
+    vars.insert("REVIEW_HAS_ISSUES".into(), "yes".into());
+    assert!(evaluate_condition(expr, &vars));
+
+    // Middle var unset → false
+    let empty = HashMap::new();
+    assert!(!evaluate_condition(expr, &empty));
+
+    // Negated var is truthy → !(true)=false → whole conjunction false
+    let mut fp_set = HashMap::new();
+    fp_set.insert("REVIEW_HAS_ISSUES".into(), "yes".into());
+    fp_set.insert("COMMENT_IS_FALSE_POSITIVE".into(), "yes".into());
+    assert!(!evaluate_condition(expr, &fp_set));
+}
+
+#[test]
+fn nested_conjunction() {
+    // Nested: (!(A)) && ((B) && (!(C)))
+    let expr = "(!(${A})) && ((${B}) && (!(${C})))";
+
+    let mut vars = HashMap::new();
+    vars.insert("B".into(), "yes".into());
+    // A unset → !(false)=true, B=true, C unset → !(false)=true → true
+    assert!(evaluate_condition(expr, &vars));
+
+    // C set → !(true)=false → inner conjunction false → whole false
+    let mut vars2 = HashMap::new();
+    vars2.insert("B".into(), "yes".into());
+    vars2.insert("C".into(), "yes".into());
+    assert!(!evaluate_condition(expr, &vars2));
+}
+
+#[test]
+fn malformed_expression_is_false() {
+    let vars = HashMap::new();
+    // Empty expression
+    assert!(!evaluate_condition("", &vars));
+    // Unbalanced parens — inner var unset, so base-case resolves to false
+    assert!(!evaluate_condition("((${A})", &vars));
+    // Unresolved variable reference → false
+    assert!(!evaluate_condition("${DOES_NOT_EXIST}", &vars));
+}
+
+#[test]
+fn unbalanced_parens_with_set_vars_is_false() {
+    // P1-1: Unbalanced parens must fail-closed even when variables resolve
+    let mut vars = HashMap::new();
+    vars.insert("A".into(), "yes".into());
+    // Extra opening paren
+    assert!(!evaluate_condition("((${A})", &vars));
+    // Extra closing paren
+    assert!(!evaluate_condition("(${A}))", &vars));
+    // Unbalanced in conjunction
+    assert!(!evaluate_condition("((${A}) && (${A})", &vars));
+}
+
+#[test]
+fn trailing_operator_is_false() {
+    // P1-1: Trailing && should fail-closed
+    let mut vars = HashMap::new();
+    vars.insert("A".into(), "yes".into());
+    assert!(!evaluate_condition("(${A}) && ", &vars));
+    assert!(!evaluate_condition(" && (${A})", &vars));
+}
+
+#[test]
+fn malformed_with_set_vars_fails_closed() {
+    // P1-2: Verify fail-closed exercises with *set* variables, not just unset
+    let mut vars = HashMap::new();
+    vars.insert("A".into(), "yes".into());
+    vars.insert("B".into(), "true".into());
+    // Leftover operator after substitution
+    assert!(!evaluate_condition("(${A}) && (${B}) && ", &vars));
+    // Unbalanced opening paren with conjunction
+    assert!(!evaluate_condition("((${A}) && (${B})", &vars));
+}
+
+#[test]
+fn empty_condition_is_false() {
+    let vars = HashMap::new();
+    assert!(!evaluate_condition("", &vars));
+    assert!(!evaluate_condition("   ", &vars));
+}
+
+fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn disjunction_binds_looser_than_conjunction() {
+    let expr = "${A} && ${B} || ${C}";
+    assert!(evaluate_condition(expr, &vars(&[("C", "yes")])));
+    assert!(evaluate_condition(expr, &vars(&[("A", "1"), ("B", "1")])));
+    assert!(!evaluate_condition(expr, &vars(&[("A", "1")])));
+
+    let grouped = "${A} && (${B} || ${C})";
+    assert!(!evaluate_condition(grouped, &vars(&[("C", "yes")])));
+    assert!(evaluate_condition(
+        grouped,
+        &vars(&[("A", "1"), ("C", "yes")])
+    ));
+}
+
+#[test]
+fn negation_applies_to_the_next_operand_or_group() {
+    assert!(evaluate_condition("!${A} || ${B}", &vars(&[])));
+    assert!(!evaluate_condition("!${A} || ${B}", &vars(&[("A", "1")])));
+    assert!(evaluate_condition("!(${A} || ${B})", &vars(&[])));
+    assert!(!evaluate_condition("!(${A} || ${B})", &vars(&[("B", "1")])));
+}
+
+#[test]
+fn comparisons_are_numeric_when_both_sides_are_numbers() {
+    let count = vars(&[("COUNT", "10"), ("MODE", "fast")]);
+    assert!(evaluate_condition("${COUNT} > 3", &count));
+    assert!(evaluate_condition("${COUNT} >= 10.0", &count));
+    assert!(!evaluate_condition("${COUNT} < 9", &count));
+    assert!(evaluate_condition("${COUNT} == 10.0", &count));
+    assert!(evaluate_condition("${MODE} == fast", &count));
+    assert!(evaluate_condition("${MODE} != \"slow mode\"", &count));
+    // Ordering non-numbers and comparing unset variables fail closed.
+    assert!(!evaluate_condition("${MODE} > 3", &count));
+    assert!(!evaluate_condition("${UNSET} != fast", &count));
+    assert!(evaluate_condition("!(${UNSET} == fast)", &count));
+}
+
+#[test]
+fn variable_values_are_not_parsed_as_operators() {
+    let injected = vars(&[("A", "x || true"), ("B", "")]);
+    assert!(!evaluate_condition("${B}", &injected));
+    assert!(evaluate_condition("${A} == 'x || true'", &injected));
+}
+
+#[test]
+fn builtins_read_the_filesystem_and_environment() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let dir = tmp.path().display().to_string();
+    let vars = vars(&[("DIR", &dir)]);
+    assert!(evaluate_condition("exists(${DIR})", &vars));
+    assert!(!evaluate_condition(
+        "exists(\"${DIR}/missing file\")",
+        &vars
+    ));
+    assert!(evaluate_condition(
+        "!exists(${DIR}/nope) && exists(${DIR})",
+        &vars
+    ));
+
+    // PATH is set in every test environment; the other name is not.
+    assert!(evaluate_condition("env(PATH)", &vars));
+    assert!(!evaluate_condition(
+        "env(WEAVE_CONDITION_TEST_UNSET_VAR)",
+        &vars
+    ));
+    assert!(evaluate_condition(
+        "env(WEAVE_CONDITION_TEST_UNSET_VAR) != x || ${DIR} != ''",
+        &vars
+    ));
+}
+
+#[test]
+fn exists_resolves_relative_paths_against_the_base_dir() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join("target")).unwrap();
+    let vars = vars(&[]);
+    assert!(evaluate_condition_in("exists(target)", &vars, tmp.path()));
+    assert!(!evaluate_condition_in(
+        "exists(target/missing)",
+        &vars,
+        tmp.path()
+    ));
+    let absolute = format!("exists({})", tmp.path().display());
+    assert!(evaluate_condition_in(
+        &absolute,
+        &vars,
+        std::path::Path::new("/nonexistent")
+    ));
+}
+
+#[test]
+fn parse_errors_are_reported() {
+    for (expr, message) in [
+        ("${A} & ${B}", "unexpected `&`"),
+        ("${A} = 1", "unexpected `=`"),
+        ("(${A}", "unbalanced parentheses"),
+        ("${A})", "unexpected `)`"),
+        ("${A} &&", "operand is expected"),
+        ("${A} == ", "operand is expected"),
+        ("size(${A}) > 1", "unknown function `size`"),
+        ("\"open", "unterminated string"),
+    ] {
+        let err = parse_condition(expr).unwrap_err();
+        assert!(err.to_string().contains(message), "{expr}: {err}");
+    }
+}
+
+#[test]
+fn display_is_canonical_and_round_trips() {
+    for (input, canonical) in [
+        ("(${A}) && (!(${B}))", "${A} && !${B}"),
+        ("${A}&&${B}||${C}", "(${A} && ${B}) || ${C}"),
+        ("!(${A}||${B})", "!(${A} || ${B})"),
+        ("${N}>=3 && exists( ${P} )", "${N} >= 3 && exists(${P})"),
+        ("${M} == 'a b'", "${M} == \"a b\""),
+    ] {
+        let parsed = parse_condition(input).unwrap();
+        assert_eq!(parsed.to_string(), canonical);
+        assert_eq!(parse_condition(canonical).unwrap(), parsed);
+    }
+}
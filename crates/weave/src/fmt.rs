@@ -0,0 +1,270 @@
+//! Canonical formatting of skill-lang files (`weave fmt`).
+//!
+//! The formatter rewrites only structure that has a canonical spelling and
+//! leaves prose and code blocks untouched:
+//!
+//! - frontmatter keys are ordered `name`, `description`, `allowed-tools`,
+//!   `tier`, `model`, `version`, then any others in their original order;
+//! - directive headings are upper-cased (`## endif` → `## ENDIF`) and
+//!   `## step 2:` prefixes become `## Step 2:`;
+//! - conditions use single spaces around binary operators (`&&`, `||`,
+//!   `==`, `>`, …) and none inside `(…)` or after `!`;
+//! - step hint keys use their canonical case and `OnFail:` values are
+//!   lower-case, with the implicit retry count spelled out.
+
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+
+/// Frontmatter keys in canonical order; unknown keys follow these.
+const FRONTMATTER_ORDER: &[&str] = &[
+    "name",
+    "description",
+    "allowed-tools",
+    "tier",
+    "model",
+    "version",
+];
+
+/// Step hint keys with their canonical spelling.
+const HINT_KEYS: &[&str] = &[
+    "Tool",
+    "Tier",
+    "OnFail",
+    "Condition",
+    "Session",
+    "Workspace Access",
+    "MaxIterations",
+    "Output",
+];
+
+/// Binary condition operators, longest first.
+const BINARY_OPS: &[&str] = &["&&", "||", "==", "!=", "<=", ">=", "<", ">"];
+
+/// Matches a `## <rest>` heading.
+static HEADING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+(\S.*?)\s*$").expect("valid regex"));
+
+/// Matches keyword-only directives in any case.
+static BARE_DIRECTIVE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(else|endif|endfor|endwhile|enduntil|endparallel|endmatrix)$")
+        .expect("valid regex")
+});
+
+static PARALLEL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^parallel(?:\s+max\s+(\d+))?$").expect("valid regex"));
+
+static FOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^for\s+(\w+)\s+in\s+(\$.*)$").expect("valid regex"));
+
+static MATRIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^matrix\s+(\w+)\s+in\s+(\[.*\])$").expect("valid regex"));
+
+/// `IF`/`WHILE`/`UNTIL` followed by something that reads as a condition, so
+/// step titles such as "If needed, fix" are left alone.
+static CONDITIONAL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(if|while|until)\s+([$!(].*)$").expect("valid regex"));
+
+static REPEAT_MAX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(.+?)\s+max\s+(\d+)$").expect("valid regex"));
+
+static INCLUDE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^INCLUDE\s+(.+)$").expect("valid regex"));
+
+static STEP_PREFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^step\s+(\d+)\s*:\s*(.+)$").expect("valid regex"));
+
+/// Matches a `Key: value` hint line.
+static HINT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z][A-Za-z ]*?):\s*(\S.*?)\s*$").expect("valid regex"));
+
+/// Format skill-lang source text into its canonical form.
+pub fn format_skill(content: &str) -> Result<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let open = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .filter(|&idx| lines[idx].trim_end() == "---");
+    let Some(open) = open else {
+        bail!("missing frontmatter: file must start with `---`");
+    };
+    let Some(close) = lines[open + 1..]
+        .iter()
+        .position(|line| line.trim_end() == "---")
+        .map(|idx| open + 1 + idx)
+    else {
+        bail!("missing closing `---` for frontmatter");
+    };
+
+    let mut out = String::from("---\n");
+    out.push_str(&format_frontmatter(&lines[open + 1..close].join("\n"))?);
+    out.push_str("---\n");
+
+    let mut in_fence = false;
+    let mut in_hints = false;
+    for line in &lines[close + 1..] {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            in_hints = false;
+        }
+        if in_fence || line.trim_start().starts_with("```") {
+            out.push_str(line);
+        } else if let Some(caps) = HEADING_RE.captures(line) {
+            let (heading, is_step) = format_heading(&caps[1]);
+            in_hints = is_step;
+            out.push_str(&heading);
+        } else if in_hints && let Some(hint) = format_hint(line) {
+            out.push_str(&hint);
+        } else {
+            let trimmed = line.trim();
+            in_hints &= trimmed.is_empty() || trimmed.starts_with('>');
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    let body_end = out.trim_end_matches('\n').len();
+    out.truncate(body_end);
+    out.push('\n');
+    Ok(out)
+}
+
+/// Normalize spacing in a condition expression.
+pub fn format_condition(condition: &str) -> String {
+    let mut out = String::with_capacity(condition.len());
+    let mut rest = condition.trim();
+    while let Some(first) = rest.chars().next() {
+        if let Some(end) = matches!(first, '"' | '\'')
+            .then(|| rest[1..].find(first))
+            .flatten()
+        {
+            // Quoted strings are copied verbatim.
+            out.push_str(&rest[..end + 2]);
+            rest = &rest[end + 2..];
+        } else if let Some(op) = BINARY_OPS.iter().find(|op| rest.starts_with(**op)) {
+            let kept = out.trim_end().len();
+            out.truncate(kept);
+            out.push_str(&format!(" {op} "));
+            rest = rest[op.len()..].trim_start();
+        } else if let Some(after) = rest.strip_prefix('!') {
+            out.push('!');
+            rest = after.trim_start();
+        } else if let Some(after) = rest.strip_prefix('(') {
+            out.push('(');
+            rest = after.trim_start();
+        } else if rest.starts_with(')') {
+            let kept = out.trim_end().len();
+            out.truncate(kept);
+            out.push(')');
+            rest = &rest[1..];
+        } else if rest.starts_with(char::is_whitespace) {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+            rest = rest.trim_start();
+        } else {
+            let len = rest
+                .find(|c: char| c.is_whitespace() || "&|!()=<>".contains(c))
+                .unwrap_or(rest.len())
+                .max(first.len_utf8());
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn format_frontmatter(toml: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = toml.parse().context("invalid frontmatter TOML")?;
+    let rank = |key: &str| {
+        FRONTMATTER_ORDER
+            .iter()
+            .position(|k| *k == key)
+            .unwrap_or(FRONTMATTER_ORDER.len())
+    };
+    doc.as_table_mut()
+        .sort_values_by(|a, _, b, _| rank(a.get()).cmp(&rank(b.get())));
+    let mut out = doc.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Canonical form of a `## ` heading and whether it starts a step.
+fn format_heading(text: &str) -> (String, bool) {
+    if BARE_DIRECTIVE_RE.is_match(text) {
+        return (format!("## {}", text.to_uppercase()), false);
+    }
+    if let Some(caps) = PARALLEL_RE.captures(text) {
+        return match caps.get(1) {
+            Some(max) => (format!("## PARALLEL MAX {}", max.as_str()), false),
+            None => ("## PARALLEL".to_string(), false),
+        };
+    }
+    if let Some(caps) = FOR_RE.captures(text) {
+        return (format!("## FOR {} IN {}", &caps[1], caps[2].trim()), false);
+    }
+    if let Some(caps) = MATRIX_RE.captures(text) {
+        let values: Vec<&str> = caps[2][1..caps[2].len() - 1]
+            .split(',')
+            .map(str::trim)
+            .collect();
+        return (
+            format!("## MATRIX {} IN [{}]", &caps[1], values.join(", ")),
+            false,
+        );
+    }
+    if let Some(caps) = CONDITIONAL_RE.captures(text) {
+        let keyword = caps[1].to_uppercase();
+        let header = &caps[2];
+        let formatted = match (keyword.as_str(), REPEAT_MAX_RE.captures(header)) {
+            ("WHILE" | "UNTIL", Some(max)) => {
+                format!("{} MAX {}", format_condition(&max[1]), &max[2])
+            }
+            _ => format_condition(header),
+        };
+        return (format!("## {keyword} {formatted}"), false);
+    }
+    if let Some(caps) = INCLUDE_RE.captures(text) {
+        return (format!("## INCLUDE {}", caps[1].trim()), false);
+    }
+    match STEP_PREFIX_RE.captures(text) {
+        Some(caps) => (format!("## Step {}: {}", &caps[1], &caps[2]), true),
+        None => (format!("## {text}"), true),
+    }
+}
+
+/// Canonical form of a step hint line, or `None` when `line` is not a hint.
+fn format_hint(line: &str) -> Option<String> {
+    let caps = HINT_RE.captures(line)?;
+    let key = caps[1].split_whitespace().collect::<Vec<_>>().join(" ");
+    let key = *HINT_KEYS.iter().find(|k| k.eq_ignore_ascii_case(&key))?;
+    let value = &caps[2];
+    let value = match key {
+        "OnFail" => format_on_fail(value),
+        "Condition" => format_condition(value),
+        "Workspace Access" => value.to_lowercase(),
+        _ => value.to_string(),
+    };
+    Some(format!("{key}: {value}"))
+}
+
+fn format_on_fail(value: &str) -> String {
+    let lower = value.to_lowercase();
+    let mut words = lower.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some("retry"), None, None) => "retry 3".to_string(),
+        (Some("retry"), Some(n), None) if n.parse::<u32>().is_ok() => format!("retry {n}"),
+        (Some("delegate"), target, None) => match target {
+            Some(target) => format!("delegate {target}"),
+            None => "delegate".to_string(),
+        },
+        (Some(action @ ("abort" | "skip")), None, None) => action.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "fmt_tests.rs"]
+mod tests;
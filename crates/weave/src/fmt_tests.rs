@@ -0,0 +1,89 @@
+use super::*;
+
+#[test]
+fn frontmatter_keys_are_put_in_canonical_order() {
+    let out = format_skill(
+        "---\nversion = \"1.0.0\"\nextra = true\nname = \"demo\"\ntier = \"tier-1\"\n\
+         description = \"Demo\"\n---\n\n# Demo\n",
+    )
+    .unwrap();
+    assert_eq!(
+        out,
+        "---\nname = \"demo\"\ndescription = \"Demo\"\ntier = \"tier-1\"\n\
+         version = \"1.0.0\"\nextra = true\n---\n\n# Demo\n"
+    );
+}
+
+#[test]
+fn directive_headings_and_conditions_are_normalized() {
+    let out = format_skill(
+        "---\nname = \"demo\"\n---\n\
+         ##  if ( ${A} )&&! ${B}\n## step 1:  Review\nGo.\n## else\n## Fix\nFix.\n## endif\n\
+         ## for file in ${FILES}\n## Each\n${file}\n## endfor\n\
+         ## until ${DONE} max 3\n## Poll\nWait.\n## enduntil\n\
+         ## parallel max 2\n## If needed, report\nDone.\n## endparallel\n\
+         ## matrix tool in [codex,gemini-cli]\n## Check\nTool: ${tool}\n## endmatrix\n",
+    )
+    .unwrap();
+    assert_eq!(
+        out,
+        "---\nname = \"demo\"\n---\n\
+         ## IF (${A}) && !${B}\n## Step 1: Review\nGo.\n## ELSE\n## Fix\nFix.\n## ENDIF\n\
+         ## FOR file IN ${FILES}\n## Each\n${file}\n## ENDFOR\n\
+         ## UNTIL ${DONE} MAX 3\n## Poll\nWait.\n## ENDUNTIL\n\
+         ## PARALLEL MAX 2\n## If needed, report\nDone.\n## ENDPARALLEL\n\
+         ## MATRIX tool IN [codex, gemini-cli]\n## Check\nTool: ${tool}\n## ENDMATRIX\n"
+    );
+}
+
+#[test]
+fn step_hints_are_normalized_only_before_the_prompt() {
+    let out = format_skill(
+        "---\nname = \"demo\"\n---\n\n## Build\n\ntool: bash\nonfail:  Retry\n\
+         condition: ${A}&&${B}\n> note\nworkspace access: Read-Only\n\n\
+         Prompt text.\nonfail: skip\n\n## Other\nONFAIL: Delegate Codex\nOnFail: skip please\n",
+    )
+    .unwrap();
+    assert_eq!(
+        out,
+        "---\nname = \"demo\"\n---\n\n## Build\n\nTool: bash\nOnFail: retry 3\n\
+         Condition: ${A} && ${B}\n> note\nWorkspace Access: read-only\n\n\
+         Prompt text.\nonfail: skip\n\n## Other\nOnFail: delegate codex\nOnFail: skip please\n"
+    );
+}
+
+#[test]
+fn code_blocks_are_left_untouched() {
+    let source = "---\nname = \"demo\"\n---\n\n## Run\nTool: bash\n\n```bash\n## endif\n\
+                  tool:  x\n```\n";
+    assert_eq!(format_skill(source).unwrap(), source);
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    let once = format_skill(
+        "---\ntier = \"t\"\nname = \"demo\"\n---\n## if ${A}\n## step 2: X\nonfail: retry  5\n## endif\n\n\n",
+    )
+    .unwrap();
+    assert!(once.ends_with("## ENDIF\n"));
+    assert_eq!(format_skill(&once).unwrap(), once);
+}
+
+#[test]
+fn missing_or_invalid_frontmatter_is_an_error() {
+    assert!(format_skill("## Step\n").is_err());
+    assert!(format_skill("---\nname = \"x\"\n## Step\n").is_err());
+    assert!(format_skill("---\nname = \n---\n").is_err());
+}
+
+#[test]
+fn comparison_and_disjunction_operators_are_spaced() {
+    assert_eq!(
+        format_condition("${A}||${N}>=3&&! exists( ${P} )"),
+        "${A} || ${N} >= 3 && !exists(${P})"
+    );
+    assert_eq!(
+        format_condition("${M}!=\"a  &&  b\"  ||  ${X}<1"),
+        "${M} != \"a  &&  b\" || ${X} < 1"
+    );
+}
@@ -0,0 +1,269 @@
+//! Skill test harness: run the `tests/*.toml` fixtures of a skill package.
+//!
+//! Each fixture compiles one skill source, checks the plan and its graph, then
+//! dry-runs the plan with fixture variables and mocked step outcomes. The dry
+//! run follows the csa plan runner: steps run in order, false conditions skip a
+//! step, FOR/WHILE/UNTIL steps are skipped, and a failing step stops the run
+//! unless its `OnFail` is `skip`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::compiler::{CompileOutput, ExecutionPlan, FailAction, compile_with_warnings};
+use crate::condition::evaluate_condition;
+use crate::parser::parse_skill_file;
+use crate::visualize::build_graph;
+
+/// Directory under the package root that holds test fixtures.
+pub const TESTS_DIR: &str = "tests";
+
+/// One `tests/*.toml` fixture.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillTest {
+    /// Skill source relative to the package root; defaults to the root
+    /// `PATTERN.md`, then `SKILL.md`.
+    #[serde(default)]
+    pub source: Option<PathBuf>,
+    /// Variables supplied to the dry run.
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+    /// Mocked outcomes keyed by step title; unmocked steps succeed silently.
+    #[serde(default)]
+    pub mock: BTreeMap<String, MockStep>,
+    #[serde(default)]
+    pub expect: Expectations,
+}
+
+/// Mocked result of one step.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockStep {
+    #[serde(default)]
+    pub fail: bool,
+    /// Value bound to the step's `Output:` variable and `STEP_<id>_OUTPUT`.
+    #[serde(default)]
+    pub output: Option<String>,
+}
+
+/// Assertions on the compiled plan and the dry run. Absent fields are not checked.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expectations {
+    /// Titles of all compiled steps, in plan order.
+    #[serde(default)]
+    pub steps: Option<Vec<String>>,
+    /// Input variables declared by the plan.
+    #[serde(default)]
+    pub variables: Option<Vec<String>>,
+    /// Number of compiler warnings.
+    #[serde(default)]
+    pub warnings: Option<usize>,
+    /// Graph edges that must exist, written `"S1 -> S2"` with visualize node ids.
+    #[serde(default)]
+    pub edges: Vec<String>,
+    /// Titles of the steps the dry run executes, in order.
+    #[serde(default)]
+    pub run: Option<Vec<String>>,
+    /// Title of the step that stops the dry run; `""` expects it to complete.
+    #[serde(default)]
+    pub aborted_at: Option<String>,
+}
+
+/// Result of one fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestOutcome {
+    pub path: PathBuf,
+    /// Failed assertions; empty when the fixture passed.
+    pub failures: Vec<String>,
+}
+
+impl TestOutcome {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Trace of a dry run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DryRun {
+    /// Titles of executed steps, in order.
+    pub run: Vec<String>,
+    /// Titles of steps skipped by conditions or unsupported loops.
+    pub skipped: Vec<String>,
+    pub aborted_at: Option<String>,
+    /// Variables after the run, including bound step outputs.
+    pub vars: HashMap<String, String>,
+}
+
+/// Fixture files under `<package_root>/tests`, sorted by path.
+pub fn discover_tests(package_root: &Path) -> Result<Vec<PathBuf>> {
+    let dir = package_root.join(TESTS_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut tests: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    tests.sort();
+    Ok(tests)
+}
+
+/// Run every fixture of the package at `package_root`.
+pub fn run_package_tests(package_root: &Path) -> Result<Vec<TestOutcome>> {
+    Ok(discover_tests(package_root)?
+        .into_iter()
+        .map(|path| {
+            let failures = match run_test_file(package_root, &path) {
+                Ok(failures) => failures,
+                Err(e) => vec![format!("{e:#}")],
+            };
+            TestOutcome { path, failures }
+        })
+        .collect())
+}
+
+/// Load, compile and check one fixture; returns the failed assertions.
+pub fn run_test_file(package_root: &Path, path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let test: SkillTest =
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    let source = match &test.source {
+        Some(source) => package_root.join(source),
+        None => default_source(package_root)?,
+    };
+    let doc = parse_skill_file(&source)
+        .with_context(|| format!("failed to parse {}", source.display()))?;
+    let output = compile_with_warnings(&doc)
+        .with_context(|| format!("failed to compile {}", source.display()))?;
+    Ok(check(&test, &output))
+}
+
+fn default_source(package_root: &Path) -> Result<PathBuf> {
+    ["PATTERN.md", "SKILL.md"]
+        .iter()
+        .map(|name| package_root.join(name))
+        .find(|path| path.is_file())
+        .with_context(|| {
+            format!(
+                "no PATTERN.md or SKILL.md in {}; set `source` in the test",
+                package_root.display()
+            )
+        })
+}
+
+/// Check `test`'s expectations against a compiled plan.
+pub fn check(test: &SkillTest, output: &CompileOutput) -> Vec<String> {
+    let plan = &output.plan;
+    let expect = &test.expect;
+    let mut failures = Vec::new();
+
+    let titles: Vec<String> = plan.steps.iter().map(|s| s.title.clone()).collect();
+    if let Some(steps) = &expect.steps {
+        compare(&mut failures, "steps", steps, &titles);
+    }
+    if let Some(variables) = &expect.variables {
+        let declared: Vec<String> = plan.variables.iter().map(|v| v.name.clone()).collect();
+        compare(&mut failures, "variables", variables, &declared);
+    }
+    if let Some(warnings) = expect.warnings
+        && warnings != output.warnings.len()
+    {
+        let messages: Vec<&str> = output.warnings.iter().map(|w| w.message.as_str()).collect();
+        failures.push(format!(
+            "warnings: expected {warnings}, got {}: {messages:?}",
+            output.warnings.len()
+        ));
+    }
+    if !expect.edges.is_empty() {
+        let graph = build_graph(plan);
+        for edge in &expect.edges {
+            let found = edge.split_once("->").is_some_and(|(from, to)| {
+                graph
+                    .edges
+                    .iter()
+                    .any(|e| e.from == from.trim() && e.to == to.trim())
+            });
+            if !found {
+                failures.push(format!("edges: no edge {edge}"));
+            }
+        }
+    }
+
+    for title in test.mock.keys() {
+        if !titles.contains(title) {
+            failures.push(format!("mock: no step titled '{title}'"));
+        }
+    }
+    let vars = test
+        .vars
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let trace = dry_run(plan, vars, &test.mock);
+    if let Some(run) = &expect.run {
+        compare(&mut failures, "run", run, &trace.run);
+    }
+    if let Some(aborted_at) = &expect.aborted_at {
+        let actual = trace.aborted_at.as_deref().unwrap_or_default();
+        if aborted_at != actual {
+            failures.push(format!(
+                "aborted_at: expected '{aborted_at}', got '{actual}'"
+            ));
+        }
+    }
+    failures
+}
+
+fn compare(failures: &mut Vec<String>, field: &str, expected: &[String], actual: &[String]) {
+    if expected != actual {
+        failures.push(format!("{field}: expected {expected:?}, got {actual:?}"));
+    }
+}
+
+/// Walk `plan` without executing anything, using `mock` for step outcomes.
+pub fn dry_run(
+    plan: &ExecutionPlan,
+    mut vars: HashMap<String, String>,
+    mock: &BTreeMap<String, MockStep>,
+) -> DryRun {
+    let mut trace = DryRun::default();
+    for step in &plan.steps {
+        let skip = step.loop_var.is_some()
+            || step.repeat.is_some()
+            || step
+                .condition
+                .as_ref()
+                .is_some_and(|condition| !evaluate_condition(condition, &vars));
+        if skip {
+            trace.skipped.push(step.title.clone());
+            continue;
+        }
+        trace.run.push(step.title.clone());
+        let outcome = mock.get(&step.title).cloned().unwrap_or_default();
+        if outcome.fail {
+            if step.on_fail == FailAction::Skip {
+                continue;
+            }
+            trace.aborted_at = Some(step.title.clone());
+            break;
+        }
+        let output = outcome.output.unwrap_or_default();
+        if let Some(binding) = &step.output {
+            vars.insert(binding.var.clone(), output.clone());
+        }
+        vars.insert(format!("STEP_{}_OUTPUT", step.id), output);
+    }
+    trace.vars = vars;
+    trace
+}
+
+#[cfg(test)]
+#[path = "harness_tests.rs"]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use crate::parser::parse_skill;
+
+const PATTERN: &str = r#"---
+name = "review"
+---
+## Review
+Tool: codex
+Output: FINDINGS
+Review ${SCOPE}.
+## IF ${FINDINGS}
+## Fix
+OnFail: skip
+Fix ${FINDINGS}.
+## ENDIF
+## Report
+Report on ${SCOPE}.
+"#;
+
+fn package(tests: &[(&str, &str)]) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("PATTERN.md"), PATTERN).unwrap();
+    std::fs::create_dir_all(tmp.path().join(TESTS_DIR)).unwrap();
+    for (name, content) in tests {
+        std::fs::write(tmp.path().join(TESTS_DIR).join(name), content).unwrap();
+    }
+    tmp
+}
+
+#[test]
+fn passing_fixture_checks_plan_graph_and_dry_run() {
+    let tmp = package(&[(
+        "findings.toml",
+        r#"
+[vars]
+SCOPE = "src/"
+
+[mock.Review]
+output = "unused import"
+
+[expect]
+steps = ["Review", "Fix", "Report"]
+variables = ["SCOPE"]
+warnings = 0
+edges = ["V -> S1", "S1 -> D1"]
+run = ["Review", "Fix", "Report"]
+aborted_at = ""
+"#,
+    )]);
+    let outcomes = run_package_tests(tmp.path()).unwrap();
+    assert_eq!(outcomes.len(), 1);
+    assert!(outcomes[0].passed(), "{:?}", outcomes[0].failures);
+}
+
+#[test]
+fn failing_assertions_are_reported() {
+    let tmp = package(&[
+        (
+            "a_clean.toml",
+            r#"
+[expect]
+run = ["Review", "Fix", "Report"]
+edges = ["S1 -> S9"]
+"#,
+        ),
+        (
+            "b_abort.toml",
+            r#"
+[mock.Review]
+fail = true
+[mock.Missing]
+fail = true
+
+[expect]
+aborted_at = ""
+"#,
+        ),
+        ("c_broken.toml", "unknown_key = 1\n"),
+        ("notes.md", "not a fixture\n"),
+    ]);
+    let outcomes = run_package_tests(tmp.path()).unwrap();
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(
+        outcomes[0].failures,
+        [
+            r#"edges: no edge S1 -> S9"#,
+            r#"run: expected ["Review", "Fix", "Report"], got ["Review", "Report"]"#,
+        ]
+    );
+    assert_eq!(
+        outcomes[1].failures,
+        [
+            "mock: no step titled 'Missing'",
+            "aborted_at: expected '', got 'Review'",
+        ]
+    );
+    assert!(outcomes[2].failures[0].contains("failed to parse"));
+}
+
+#[test]
+fn dry_run_skips_failed_steps_marked_skip_and_binds_outputs() {
+    let doc = parse_skill(PATTERN).unwrap();
+    let plan = compile_with_warnings(&doc).unwrap().plan;
+    let mock = BTreeMap::from([
+        (
+            "Review".to_string(),
+            MockStep {
+                fail: false,
+                output: Some("bug".to_string()),
+            },
+        ),
+        (
+            "Fix".to_string(),
+            MockStep {
+                fail: true,
+                output: None,
+            },
+        ),
+    ]);
+    let trace = dry_run(&plan, HashMap::new(), &mock);
+    assert_eq!(trace.run, ["Review", "Fix", "Report"]);
+    assert!(trace.skipped.is_empty());
+    assert_eq!(trace.aborted_at, None);
+    assert_eq!(trace.vars["FINDINGS"], "bug");
+    assert_eq!(trace.vars["STEP_1_OUTPUT"], "bug");
+    assert!(!trace.vars.contains_key("STEP_2_OUTPUT"));
+}
+
+#[test]
+fn missing_tests_dir_yields_no_outcomes() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(run_package_tests(tmp.path()).unwrap().is_empty());
+}
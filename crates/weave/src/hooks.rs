@@ -0,0 +1,149 @@
+//! csa-hooks lifecycle events for package changes.
+//!
+//! Hooks are configured like every other csa hook, under `[skill_installed]`,
+//! `[skill_upgraded]` and `[skill_removed]` in the global or project
+//! `hooks.toml`. They are observational: a failing hook is reported and the
+//! command carries on.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use csa_hooks::{HookEvent, HooksConfig};
+use weave::package::{LockedPackage, SourceKind};
+
+/// The hook configuration of one project.
+pub(crate) struct PackageHooks<'a> {
+    config: HooksConfig,
+    project_root: &'a Path,
+}
+
+impl<'a> PackageHooks<'a> {
+    pub(crate) fn load(project_root: &'a Path) -> Self {
+        let config = csa_hooks::load_hooks_config(
+            csa_session::get_session_root(project_root)
+                .ok()
+                .map(|session_root| session_root.join("hooks.toml"))
+                .as_deref(),
+            csa_hooks::global_hooks_path().as_deref(),
+            None,
+        );
+        Self {
+            config,
+            project_root,
+        }
+    }
+
+    pub(crate) fn installed(&self, pkg: &LockedPackage) {
+        self.fire(HookEvent::SkillInstalled, self.package_vars(pkg));
+    }
+
+    pub(crate) fn upgraded(
+        &self,
+        pkg: &LockedPackage,
+        old_commit: &str,
+        old_version: Option<&str>,
+    ) {
+        let mut vars = self.package_vars(pkg);
+        vars.insert("old_commit".to_string(), old_commit.to_string());
+        vars.insert(
+            "old_version".to_string(),
+            old_version.unwrap_or_default().to_string(),
+        );
+        self.fire(HookEvent::SkillUpgraded, vars);
+    }
+
+    pub(crate) fn removed(&self, pkg: &LockedPackage) {
+        self.fire(HookEvent::SkillRemoved, self.package_vars(pkg));
+    }
+
+    fn package_vars(&self, pkg: &LockedPackage) -> HashMap<String, String> {
+        let source = match pkg.source_kind {
+            SourceKind::Git => "git",
+            SourceKind::Local => "local",
+        };
+        HashMap::from([
+            ("name".to_string(), pkg.name.clone()),
+            (
+                "version".to_string(),
+                pkg.version.clone().unwrap_or_default(),
+            ),
+            ("commit".to_string(), pkg.commit.clone()),
+            ("repo".to_string(), pkg.repo.clone()),
+            ("source".to_string(), source.to_string()),
+            (
+                "project_root".to_string(),
+                self.project_root.display().to_string(),
+            ),
+        ])
+    }
+
+    fn fire(&self, event: HookEvent, vars: HashMap<String, String>) {
+        if let Err(err) = csa_hooks::run_hooks_for_event(event, &self.config, &vars) {
+            eprintln!("warning: {} hook failed: {err:#}", event.as_config_key());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use csa_hooks::HookConfig;
+
+    fn pkg(commit: &str, version: &str) -> LockedPackage {
+        LockedPackage {
+            name: "review-kit".to_string(),
+            repo: "https://github.com/acme/review-kit.git".to_string(),
+            commit: commit.to_string(),
+            version: Some(version.to_string()),
+            source_kind: SourceKind::Git,
+            requested_version: None,
+            resolved_ref: None,
+            checksum: None,
+            member: None,
+        }
+    }
+
+    #[test]
+    fn configured_hooks_receive_package_variables() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = tmp.path().join("events.log");
+        let command = |event: &str, vars: &str| HookConfig {
+            enabled: true,
+            command: Some(format!("echo {event} {vars} >> '{}'", log.display())),
+            timeout_secs: 10,
+            fail_policy: Default::default(),
+            waivers: Vec::new(),
+        };
+        let mut config = HooksConfig::default();
+        config.hooks.insert(
+            "skill_installed".to_string(),
+            command("installed", "{name} {version} {commit} {source}"),
+        );
+        config.hooks.insert(
+            "skill_upgraded".to_string(),
+            command(
+                "upgraded",
+                "{name} {old_version}:{old_commit} {version}:{commit}",
+            ),
+        );
+        config.hooks.insert(
+            "skill_removed".to_string(),
+            command("removed", "{name} {repo}"),
+        );
+        let hooks = PackageHooks {
+            config,
+            project_root: tmp.path(),
+        };
+
+        hooks.installed(&pkg("aaa", "1.0.0"));
+        hooks.upgraded(&pkg("bbb", "1.1.0"), "aaa", Some("1.0.0"));
+        hooks.removed(&pkg("bbb", "1.1.0"));
+
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "installed review-kit 1.0.0 aaa git\n\
+             upgraded review-kit 1.0.0:aaa 1.1.0:bbb\n\
+             removed review-kit https://github.com/acme/review-kit.git\n"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Typed workflow inputs declared in skill frontmatter.
+//!
+//! A pattern declares the values a run must supply under `[variables.NAME]`:
+//!
+//! ```toml
+//! [variables.TARGET_BRANCH]
+//! type = "string"            # string (default), bool, int, or path
+//! description = "Branch to merge into"
+//! default = "main"           # optional; without it the input is required
+//! ```
+//!
+//! Declarations are carried into the compiled plan's `variables`, where the
+//! runner checks them before executing the first step.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::compiler::{ExecutionPlan, VariableDecl};
+
+/// Value type of a declared input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    #[default]
+    String,
+    Bool,
+    Int,
+    Path,
+}
+
+impl VariableType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Path => "path",
+        }
+    }
+
+    /// Check that `value` is a valid value of this type.
+    pub fn check(self, value: &str) -> Result<()> {
+        match self {
+            Self::String => Ok(()),
+            Self::Bool if matches!(value, "true" | "false") => Ok(()),
+            Self::Bool => bail!("expected `true` or `false`, got '{value}'"),
+            Self::Int if value.parse::<i64>().is_ok() => Ok(()),
+            Self::Int => bail!("expected an integer, got '{value}'"),
+            Self::Path if !value.trim().is_empty() => Ok(()),
+            Self::Path => bail!("expected a non-empty path"),
+        }
+    }
+}
+
+impl fmt::Display for VariableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A `[variables.NAME]` frontmatter table.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VariableSpec {
+    #[serde(default, rename = "type")]
+    pub kind: VariableType,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Written as a TOML string, integer, or boolean.
+    #[serde(default, deserialize_with = "scalar_string")]
+    pub default: Option<String>,
+    /// Defaults to `true` when there is no `default`.
+    #[serde(default)]
+    pub required: Option<bool>,
+}
+
+fn scalar_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Scalar {
+        String(String),
+        Int(i64),
+        Bool(bool),
+    }
+    Ok(Some(match Scalar::deserialize(deserializer)? {
+        Scalar::String(s) => s,
+        Scalar::Int(n) => n.to_string(),
+        Scalar::Bool(b) => b.to_string(),
+    }))
+}
+
+/// Plan variables: every `referenced` name plus every declared input, sorted
+/// by name. Declared defaults must match their type.
+pub(crate) fn plan_variables(
+    referenced: Vec<String>,
+    declared: &BTreeMap<String, VariableSpec>,
+) -> Result<Vec<VariableDecl>> {
+    let mut names = referenced;
+    names.extend(declared.keys().cloned());
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let Some(spec) = declared.get(&name) else {
+                return Ok(VariableDecl {
+                    name,
+                    default: None,
+                    kind: None,
+                    description: None,
+                    required: false,
+                });
+            };
+            if !is_variable_name(&name) {
+                bail!("invalid variable name '{name}' in [variables]");
+            }
+            if let Some(default) = &spec.default
+                && let Err(err) = spec.kind.check(default)
+            {
+                bail!(
+                    "default of variable '{name}' is not a valid {}: {err}",
+                    spec.kind
+                );
+            }
+            Ok(VariableDecl {
+                required: spec.required.unwrap_or(spec.default.is_none()),
+                default: spec.default.clone(),
+                kind: Some(spec.kind),
+                description: spec.description.clone(),
+                name,
+            })
+        })
+        .collect()
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Required inputs of `plan` that have no value in `values`.
+pub fn missing_inputs<'a>(
+    plan: &'a ExecutionPlan,
+    values: &HashMap<String, String>,
+) -> Vec<&'a VariableDecl> {
+    plan.variables
+        .iter()
+        .filter(|decl| decl.required && !values.contains_key(&decl.name))
+        .collect()
+}
+
+/// Check supplied values of typed inputs against their declared types.
+pub fn check_inputs(plan: &ExecutionPlan, values: &HashMap<String, String>) -> Result<()> {
+    for decl in &plan.variables {
+        if let (Some(kind), Some(value)) = (decl.kind, values.get(&decl.name))
+            && let Err(err) = kind.check(value)
+        {
+            bail!("variable {} must be a {kind}: {err}", decl.name);
+        }
+    }
+    Ok(())
+}
+
+/// Machine-readable description of inputs a run still needs, printed when
+/// the runner cannot ask for them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MissingInputsReport {
+    pub error: &'static str,
+    pub workflow: String,
+    pub missing: Vec<MissingInput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MissingInput {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: VariableType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl MissingInputsReport {
+    pub fn new(plan: &ExecutionPlan, missing: &[&VariableDecl]) -> Self {
+        Self {
+            error: "missing_inputs",
+            workflow: plan.name.clone(),
+            missing: missing
+                .iter()
+                .map(|decl| MissingInput {
+                    name: decl.name.clone(),
+                    kind: decl.kind.unwrap_or_default(),
+                    description: decl.description.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "inputs_tests.rs"]
+mod tests;
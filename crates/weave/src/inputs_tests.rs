@@ -0,0 +1,120 @@
+use super::*;
+use crate::compiler::compile;
+use crate::parser::parse_skill;
+
+const PATTERN: &str = r#"---
+name = "merge"
+
+[variables.TARGET_BRANCH]
+description = "Branch to merge into"
+
+[variables.MAX_RETRIES]
+type = "int"
+default = 3
+
+[variables.DRY_RUN]
+type = "bool"
+required = false
+---
+## Merge
+Merge into ${TARGET_BRANCH} with ${STRATEGY}.
+"#;
+
+fn plan() -> ExecutionPlan {
+    compile(&parse_skill(PATTERN).unwrap()).unwrap()
+}
+
+#[test]
+fn declared_inputs_are_merged_into_plan_variables() {
+    let plan = plan();
+    let names: Vec<&str> = plan.variables.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["DRY_RUN", "MAX_RETRIES", "STRATEGY", "TARGET_BRANCH"]
+    );
+
+    let by_name = |name: &str| plan.variables.iter().find(|v| v.name == name).unwrap();
+    let target = by_name("TARGET_BRANCH");
+    assert_eq!(target.kind, Some(VariableType::String));
+    assert_eq!(target.description.as_deref(), Some("Branch to merge into"));
+    assert!(target.required);
+
+    let retries = by_name("MAX_RETRIES");
+    assert_eq!(retries.kind, Some(VariableType::Int));
+    assert_eq!(retries.default.as_deref(), Some("3"));
+    assert!(!retries.required);
+
+    assert!(!by_name("DRY_RUN").required);
+    let strategy = by_name("STRATEGY");
+    assert_eq!((strategy.kind, strategy.required), (None, false));
+}
+
+#[test]
+fn declarations_survive_the_toml_round_trip() {
+    let plan = plan();
+    let toml = crate::compiler::plan_to_toml(&plan).unwrap();
+    assert!(toml.contains("type = \"int\""), "{toml}");
+    assert!(toml.contains("required = true"), "{toml}");
+    assert_eq!(crate::compiler::plan_from_toml(&toml).unwrap(), plan);
+}
+
+#[test]
+fn missing_inputs_lists_required_inputs_without_values() {
+    let plan = plan();
+    let mut values = HashMap::new();
+    let missing: Vec<&str> = missing_inputs(&plan, &values)
+        .iter()
+        .map(|v| v.name.as_str())
+        .collect();
+    assert_eq!(missing, ["TARGET_BRANCH"]);
+
+    let report = MissingInputsReport::new(&plan, &missing_inputs(&plan, &values));
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        serde_json::json!({
+            "error": "missing_inputs",
+            "workflow": "merge",
+            "missing": [{
+                "name": "TARGET_BRANCH",
+                "type": "string",
+                "description": "Branch to merge into",
+            }],
+        })
+    );
+
+    values.insert("TARGET_BRANCH".to_string(), "main".to_string());
+    assert!(missing_inputs(&plan, &values).is_empty());
+}
+
+#[test]
+fn supplied_values_are_checked_against_types() {
+    let plan = plan();
+    let values = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+    assert!(check_inputs(&plan, &values(&[("MAX_RETRIES", "5"), ("DRY_RUN", "true")])).is_ok());
+    assert!(check_inputs(&plan, &values(&[("STRATEGY", "anything")])).is_ok());
+
+    let err = check_inputs(&plan, &values(&[("MAX_RETRIES", "many")])).unwrap_err();
+    assert!(
+        err.to_string().contains("MAX_RETRIES must be a int"),
+        "{err}"
+    );
+    assert!(check_inputs(&plan, &values(&[("DRY_RUN", "yes")])).is_err());
+}
+
+#[test]
+fn invalid_declarations_fail_to_compile() {
+    let compile_src = |frontmatter: &str| {
+        let src = format!("---\nname = \"x\"\n{frontmatter}\n---\n## Step\nGo.\n");
+        parse_skill(&src).and_then(|doc| compile(&doc))
+    };
+    let err = compile_src("[variables.N]\ntype = \"int\"\ndefault = \"lots\"").unwrap_err();
+    assert!(err.to_string().contains("default of variable 'N'"), "{err}");
+    assert!(compile_src("[variables.N]\ntype = \"float\"").is_err());
+    assert!(compile_src("[variables.N]\ndefualt = \"x\"").is_err());
+    assert!(compile_src("[variables.\"not-valid\"]\ndefault = \"x\"").is_err());
+}
@@ -1,9 +1,17 @@
 pub mod batch;
 pub mod check;
 pub mod compiler;
+pub mod condition;
+pub mod fmt;
+pub mod harness;
+pub mod inputs;
 pub mod link;
+pub mod lint;
 pub mod package;
 pub mod parser;
 pub(crate) mod path_utils;
+pub mod publish;
+pub mod requirements;
 pub mod stale_ref;
 pub mod visualize;
+pub mod workspace;
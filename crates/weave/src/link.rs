@@ -15,7 +15,8 @@ use tracing::{debug, warn};
 
 use crate::check::DEFAULT_LINK_DIRS;
 use crate::package::{
-    Lockfile, SourceKind, find_lockfile, global_store_root, load_lockfile, package_dir,
+    ChecksumMismatch, Lockfile, SourceKind, find_lockfile, global_store_root, load_lockfile,
+    package_dir, verify_checkout,
 };
 use crate::path_utils::{normalize_path, resolve_symlink_target};
 
@@ -88,6 +89,9 @@ pub enum LinkErrorKind {
     NotASymlink { path: PathBuf },
     /// Symlink exists but points to a different (non-weave-managed) target.
     ForeignSymlink { path: PathBuf, target: PathBuf },
+    /// The package's store checkout does not match its locked checksum; none
+    /// of its skills are linked.
+    ChecksumMismatch(ChecksumMismatch),
     /// I/O or other error.
     Io(String),
 }
@@ -119,6 +123,12 @@ impl std::fmt::Display for LinkError {
                 path.display(),
                 target.display()
             ),
+            LinkErrorKind::ChecksumMismatch(mismatch) => write!(
+                f,
+                "not linking skills of package '{}': {mismatch}. \
+                 Reinstall it to restore the checkout.",
+                self.name
+            ),
             LinkErrorKind::Io(msg) => write!(f, "cannot link skill '{}': {}", self.name, msg),
         }
     }
@@ -258,6 +268,11 @@ pub fn discover_skills(project_root: &Path) -> Result<Vec<DiscoveredSkill>> {
             continue;
         }
 
+        if let Some(mismatch) = verify_checkout(&store_root, pkg) {
+            warn!("skipping skills of '{}': {mismatch}", pkg.name);
+            continue;
+        }
+
         let patterns_dir = pkg_dir.join("patterns");
         if !patterns_dir.is_dir() {
             continue;
@@ -317,6 +332,16 @@ fn discover_skills_in_patterns(
 mod link_patterns_impl;
 pub use link_patterns_impl::{discover_patterns, link_patterns, remove_stale_pattern_links};
 
+#[path = "link_generated.rs"]
+mod link_generated_impl;
+pub use link_generated_impl::{
+    discover_generated_skills, link_generated_skills, remove_stale_generated_links,
+};
+
+#[path = "link_dirs.rs"]
+mod link_dirs_impl;
+use link_dirs_impl::link_into_skill_dirs;
+
 // ---------------------------------------------------------------------------
 // Pre-check (conflict detection)
 // ---------------------------------------------------------------------------
@@ -374,59 +399,31 @@ pub fn link_skills(project_root: &Path, scope: LinkScope, force: bool) -> Result
     }
 
     let store_root = global_store_root()?;
-    let base_dir = match scope {
-        LinkScope::Project => project_root.to_path_buf(),
-        LinkScope::User => {
-            let dirs = directories::BaseDirs::new().context("cannot determine home directory")?;
-            dirs.home_dir().to_path_buf()
-        }
-        LinkScope::None => unreachable!(),
-    };
-
-    let mut report = LinkReport::default();
-
-    for target_dir_name in DEFAULT_LINK_DIRS {
-        let target_dir = base_dir.join(target_dir_name);
-
-        // Decide whether to create this target directory.
-        // - The primary directory (.claude/skills/) is always created — it is
-        //   the standard discovery path and must exist for first-time setups.
-        // - Other tool directories are only created if their parent already
-        //   exists (e.g., create .codex/skills/ only if .codex/ is present).
-        let is_primary = *target_dir_name == DEFAULT_LINK_DIRS[0];
-        let should_create =
-            target_dir.is_dir() || is_primary || target_dir.parent().is_some_and(|p| p.is_dir());
-
-        if !should_create {
-            continue;
-        }
-
-        if !target_dir.exists() {
-            std::fs::create_dir_all(&target_dir)
-                .with_context(|| format!("cannot create {}", target_dir.display()))?;
-        }
-
-        for skill in &skills {
-            let link_path = target_dir.join(&skill.name);
-            let outcome = create_skill_link(
-                &link_path,
-                &skill.source_dir,
-                &target_dir,
-                &store_root,
-                skill,
-                force,
-            );
-
-            match outcome {
-                Ok(o) => report.outcomes.push(o),
-                Err(e) => report.errors.push(e),
-            }
-        }
-    }
-
+    let base_dir = scope_base_dir(project_root, scope)?;
+    let mut report = link_into_skill_dirs(&base_dir, &skills, &store_root, force)?;
+    report
+        .errors
+        .extend(checksum_errors(project_root, &store_root)?);
     Ok(report)
 }
 
+/// One error per locked package whose checkout fails checksum verification.
+fn checksum_errors(project_root: &Path, store_root: &Path) -> Result<Vec<LinkError>> {
+    let lockfile = match find_lockfile(project_root) {
+        Some(path) => load_lockfile(&path)?,
+        None => return Ok(Vec::new()),
+    };
+    Ok(lockfile
+        .package
+        .iter()
+        .filter_map(|pkg| verify_checkout(store_root, pkg))
+        .map(|mismatch| LinkError {
+            name: mismatch.package.clone(),
+            reason: LinkErrorKind::ChecksumMismatch(mismatch),
+        })
+        .collect())
+}
+
 /// Create or validate a single skill symlink.
 fn create_skill_link(
     link_path: &Path,
@@ -0,0 +1,64 @@
+//! Linking of a skill set into the per-tool skill directories.
+//!
+//! Shared by package skills and generated skills; only the root whose links
+//! count as managed differs between them.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::{DiscoveredSkill, LinkReport, create_skill_link};
+use crate::check::DEFAULT_LINK_DIRS;
+
+/// Link `skills` into every [`DEFAULT_LINK_DIRS`] entry under `base_dir`.
+///
+/// Existing links resolving into `managed_root` are replaced freely.
+pub(super) fn link_into_skill_dirs(
+    base_dir: &Path,
+    skills: &[DiscoveredSkill],
+    managed_root: &Path,
+    force: bool,
+) -> Result<LinkReport> {
+    let mut report = LinkReport::default();
+
+    for target_dir_name in DEFAULT_LINK_DIRS {
+        let target_dir = base_dir.join(target_dir_name);
+
+        // Decide whether to create this target directory.
+        // - The primary directory (.claude/skills/) is always created — it is
+        //   the standard discovery path and must exist for first-time setups.
+        // - Other tool directories are only created if their parent already
+        //   exists (e.g., create .codex/skills/ only if .codex/ is present).
+        let is_primary = *target_dir_name == DEFAULT_LINK_DIRS[0];
+        let should_create =
+            target_dir.is_dir() || is_primary || target_dir.parent().is_some_and(|p| p.is_dir());
+
+        if !should_create {
+            continue;
+        }
+
+        if !target_dir.exists() {
+            std::fs::create_dir_all(&target_dir)
+                .with_context(|| format!("cannot create {}", target_dir.display()))?;
+        }
+
+        for skill in skills {
+            let link_path = target_dir.join(&skill.name);
+            let outcome = create_skill_link(
+                &link_path,
+                &skill.source_dir,
+                &target_dir,
+                managed_root,
+                skill,
+                force,
+            );
+
+            match outcome {
+                Ok(o) => report.outcomes.push(o),
+                Err(e) => report.errors.push(e),
+            }
+        }
+    }
+
+    Ok(report)
+}
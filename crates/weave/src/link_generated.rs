@@ -0,0 +1,97 @@
+//! Linking of skills generated outside the package store.
+//!
+//! Tools such as `csa mcp-hub gen-skill` write skills into a directory they
+//! own (`<source_root>/<name>/SKILL.md`). These functions expose them in the
+//! same skill directories as package skills. Links resolving into the source
+//! root are managed by it, the way links into the global store are managed
+//! by weave: they are replaced freely and removed once their skill is gone.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+use super::{DiscoveredSkill, LinkReport, is_stale_link, link_into_skill_dirs, remove_symlink};
+use crate::check::DEFAULT_LINK_DIRS;
+
+/// Discover the skills directly under `source_root`.
+///
+/// A missing `source_root` has no skills.
+pub fn discover_generated_skills(source_root: &Path) -> Result<Vec<DiscoveredSkill>> {
+    let entries = match std::fs::read_dir(source_root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("cannot read {}", source_root.display()));
+        }
+    };
+
+    let owner = source_root.display().to_string();
+    let mut skills = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let dir = entry.path();
+        if dir.join("SKILL.md").is_file()
+            && let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string())
+        {
+            skills.push(DiscoveredSkill {
+                name,
+                package_name: owner.clone(),
+                source_dir: dir,
+            });
+        }
+    }
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(skills)
+}
+
+/// Link every skill under `source_root` into the project's skill directories.
+///
+/// `.claude/skills/` is always created; the other directories only when
+/// their tool directory (e.g. `.codex/`) exists. `force` overwrites links
+/// and files not managed by `source_root`.
+pub fn link_generated_skills(
+    project_root: &Path,
+    source_root: &Path,
+    force: bool,
+) -> Result<LinkReport> {
+    let skills = discover_generated_skills(source_root)?;
+    if skills.is_empty() {
+        return Ok(LinkReport::default());
+    }
+    link_into_skill_dirs(project_root, &skills, source_root, force)
+}
+
+/// Remove project skill links into `source_root` whose skill no longer exists.
+pub fn remove_stale_generated_links(
+    project_root: &Path,
+    source_root: &Path,
+) -> Result<Vec<PathBuf>> {
+    let skills = discover_generated_skills(source_root)?;
+    let skill_names: HashSet<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+    let skill_source_dirs: HashSet<PathBuf> = skills
+        .iter()
+        .filter_map(|s| s.source_dir.canonicalize().ok())
+        .collect();
+
+    let mut removed = Vec::new();
+    for dir_name in DEFAULT_LINK_DIRS {
+        let dir = project_root.join(dir_name);
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !is_stale_link(&path, source_root, &skill_names, &skill_source_dirs) {
+                continue;
+            }
+            match remove_symlink(&path) {
+                Ok(()) => removed.push(path),
+                Err(e) => warn!("failed to remove stale symlink {}: {e}", path.display()),
+            }
+        }
+    }
+
+    Ok(removed)
+}
@@ -12,7 +12,9 @@ use super::{
     DiscoveredPattern, DiscoveredSkill, LinkOutcome, LinkReport, create_skill_link,
     is_weave_managed_path,
 };
-use crate::package::{SourceKind, find_lockfile, global_store_root, load_lockfile, package_dir};
+use crate::package::{
+    SourceKind, find_lockfile, global_store_root, load_lockfile, package_dir, verify_checkout,
+};
 use crate::path_utils::resolve_symlink_target;
 
 /// Discover all pattern directories (containing workflow.toml) across installed packages.
@@ -42,6 +44,10 @@ pub fn discover_patterns(project_root: &Path) -> Result<Vec<DiscoveredPattern>>
         if !patterns_dir.is_dir() {
             continue;
         }
+        if let Some(mismatch) = verify_checkout(&store_root, pkg) {
+            warn!("skipping patterns of '{}': {mismatch}", pkg.name);
+            continue;
+        }
         let entries = match std::fs::read_dir(&patterns_dir) {
             Ok(e) => e,
             Err(_) => continue,
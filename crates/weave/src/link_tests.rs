@@ -643,3 +643,71 @@ fn is_weave_managed_rejects_outside() {
     std::fs::create_dir_all(&outside).unwrap();
     assert!(!is_weave_managed_path(&outside, &store));
 }
+
+// ---------------------------------------------------------------------------
+// Generated skill tests
+// ---------------------------------------------------------------------------
+
+#[cfg(unix)]
+#[test]
+fn generated_skills_are_linked_and_stale_links_removed() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let source_root = project.join(".csa").join("mcp-skills");
+    for name in ["mcp-github-search", "mcp-web-fetch"] {
+        let dir = source_root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), format!("---\nname: {name}\n---\n")).unwrap();
+    }
+    std::fs::create_dir_all(source_root.join("not-a-skill")).unwrap();
+    std::fs::create_dir_all(project.join(".codex")).unwrap();
+
+    let report = link_generated_skills(project, &source_root, false).unwrap();
+    assert!(!report.has_errors());
+    assert_eq!(
+        report.unique_created_names(),
+        ["mcp-github-search", "mcp-web-fetch"]
+    );
+    for dir in [".claude/skills", ".codex/skills"] {
+        let link = project.join(dir).join("mcp-web-fetch");
+        assert!(link.join("SKILL.md").is_file(), "{}", link.display());
+    }
+    assert!(!project.join(".agents/skills").exists());
+
+    let again = link_generated_skills(project, &source_root, false).unwrap();
+    assert_eq!(again.unique_skipped_count(), 2);
+
+    std::fs::remove_dir_all(source_root.join("mcp-web-fetch")).unwrap();
+    let removed = remove_stale_generated_links(project, &source_root).unwrap();
+    assert_eq!(removed.len(), 2);
+    assert!(
+        std::fs::symlink_metadata(project.join(".claude/skills/mcp-web-fetch")).is_err(),
+        "stale link should be gone"
+    );
+    assert!(project.join(".claude/skills/mcp-github-search").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn generated_links_do_not_replace_foreign_entries() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let source_root = project.join("generated");
+    std::fs::create_dir_all(source_root.join("shared")).unwrap();
+    std::fs::write(
+        source_root.join("shared/SKILL.md"),
+        "---\nname: shared\n---\n",
+    )
+    .unwrap();
+    let user_skill = project.join(".claude/skills/shared");
+    std::fs::create_dir_all(&user_skill).unwrap();
+
+    let report = link_generated_skills(project, &source_root, false).unwrap();
+    assert!(report.has_errors());
+    assert!(user_skill.is_dir());
+    assert!(
+        remove_stale_generated_links(project, &source_root)
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,442 @@
+//! Static analysis of skill-lang files (`weave lint`).
+//!
+//! Checks run on the source text so every finding carries a line number:
+//!
+//! - `parse-error`: missing or invalid frontmatter.
+//! - `unbalanced-block`: IF/ELSE/ENDIF, FOR, WHILE, UNTIL, PARALLEL and
+//!   MATRIX directives that do not pair up.
+//! - `undefined-variable`: a `${VAR}` that is not declared in frontmatter
+//!   `[variables]`, documented outside the steps, bound by `Output:` or `CSA_VAR:`, a `STEP_<n>_OUTPUT`, a FOR or
+//!   MATRIX variable in scope, or assigned in a shell block.
+//! - `unreachable-step`: steps after a top-level bash step that always exits
+//!   non-zero and aborts the plan.
+//! - `missing-tool`: steps with a shell code block but no `Tool:` hint, which
+//!   are sent to a model instead of being executed.
+//! - `suspicious-shell`: downloads piped into a shell, broad recursive
+//!   deletes, `eval`, `sudo` and similar.
+//!
+//! Rule severities can be overridden per project in `weave-lint.toml`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::parser::{LineKind, RepeatMode, classify_line, parse_frontmatter};
+
+/// File name of the per-project lint configuration.
+pub const LINT_CONFIG_FILE: &str = "weave-lint.toml";
+
+/// A lint check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    ParseError,
+    UnbalancedBlock,
+    UndefinedVariable,
+    UnreachableStep,
+    MissingTool,
+    SuspiciousShell,
+}
+
+impl LintRule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ParseError => "parse-error",
+            Self::UnbalancedBlock => "unbalanced-block",
+            Self::UndefinedVariable => "undefined-variable",
+            Self::UnreachableStep => "unreachable-step",
+            Self::MissingTool => "missing-tool",
+            Self::SuspiciousShell => "suspicious-shell",
+        }
+    }
+
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Self::ParseError | Self::UnbalancedBlock => Severity::Error,
+            Self::UndefinedVariable
+            | Self::UnreachableStep
+            | Self::MissingTool
+            | Self::SuspiciousShell => Severity::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for LintRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a finding is reported; `off` disables the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Parsed `weave-lint.toml`.
+///
+/// ```toml
+/// [rules]
+/// undefined-variable = "error"
+/// suspicious-shell = "off"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<LintRule, Severity>,
+}
+
+impl LintConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Config from the nearest `weave-lint.toml` at or above `start`, or the
+    /// defaults.
+    pub fn discover(start: &Path) -> Result<Self> {
+        match start
+            .ancestors()
+            .map(|dir| dir.join(LINT_CONFIG_FILE))
+            .find(|path| path.is_file())
+        {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn severity(&self, rule: LintRule) -> Severity {
+        self.rules
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+}
+
+/// One problem found in a skill file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: Severity,
+    /// 1-based line in the linted file.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Lint the skill file at `path`.
+pub fn lint_file(path: &Path, config: &LintConfig) -> Result<Vec<LintFinding>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(lint_source(&content, config))
+}
+
+/// Lint skill-lang source text, returning findings ordered by line.
+pub fn lint_source(content: &str, config: &LintConfig) -> Vec<LintFinding> {
+    let mut lint = Findings {
+        config,
+        findings: Vec::new(),
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(body_start) = body_start(&lines) else {
+        lint.push(
+            LintRule::ParseError,
+            1,
+            "missing frontmatter: file must start with a `---` delimited TOML block".to_string(),
+        );
+        return lint.findings;
+    };
+    let inputs = match parse_frontmatter(content) {
+        Ok((meta, _)) => meta.variables.into_keys().collect(),
+        Err(err) => {
+            lint.push(LintRule::ParseError, 1, format!("{err:#}"));
+            Vec::new()
+        }
+    };
+
+    let mut scan = scan_body(&lines, body_start, &mut lint);
+    scan.inputs = inputs;
+    check_steps(&scan, &mut lint);
+    check_variables(&scan, &mut lint);
+    lint.findings.sort_by_key(|f| (f.line, f.rule));
+    lint.findings
+}
+
+/// Skill files under `root`: every `PATTERN.md`, skipping hidden directories
+/// and `target/`.
+pub fn find_skill_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    walk(root, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.file_type()?.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                walk(&entry.path(), files)?;
+            }
+        } else if name == "PATTERN.md" {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Scanning
+// ---------------------------------------------------------------------------
+
+struct Findings<'c> {
+    config: &'c LintConfig,
+    findings: Vec<LintFinding>,
+}
+
+impl Findings<'_> {
+    fn push(&mut self, rule: LintRule, line: usize, message: String) {
+        let severity = self.config.severity(rule);
+        if severity != Severity::Off {
+            self.findings.push(LintFinding {
+                rule,
+                severity,
+                line,
+                message,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    If,
+    For,
+    While,
+    Until,
+    Parallel,
+    Matrix,
+}
+
+impl BlockKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            Self::If => "IF",
+            Self::For => "FOR",
+            Self::While => "WHILE",
+            Self::Until => "UNTIL",
+            Self::Parallel => "PARALLEL",
+            Self::Matrix => "MATRIX",
+        }
+    }
+}
+
+struct OpenBlock<'a> {
+    kind: BlockKind,
+    line: usize,
+    for_var: Option<&'a str>,
+    has_else: bool,
+}
+
+/// A `## <title>` step and its body lines.
+struct Step<'a> {
+    title: &'a str,
+    line: usize,
+    body: Vec<(usize, &'a str)>,
+    /// Inside an IF, loop or PARALLEL block.
+    nested: bool,
+    for_vars: Vec<&'a str>,
+}
+
+/// A directive expression (IF condition, FOR collection, loop condition).
+struct Expr<'a> {
+    line: usize,
+    text: &'a str,
+    for_vars: Vec<&'a str>,
+}
+
+#[derive(Default)]
+struct Scan<'a> {
+    steps: Vec<Step<'a>>,
+    exprs: Vec<Expr<'a>>,
+    /// Markdown outside steps, where authors document plan inputs.
+    prose: Vec<&'a str>,
+    /// Inputs declared in frontmatter `[variables]`.
+    inputs: Vec<String>,
+}
+
+/// Index of the first body line after the closing frontmatter delimiter.
+fn body_start(lines: &[&str]) -> Option<usize> {
+    let open = lines.iter().position(|line| !line.trim().is_empty())?;
+    if !lines[open].starts_with("---") {
+        return None;
+    }
+    let close = lines[open + 1..]
+        .iter()
+        .position(|line| line.starts_with("---"))?;
+    Some(open + 1 + close + 1)
+}
+
+fn scan_body<'a>(lines: &[&'a str], start: usize, lint: &mut Findings<'_>) -> Scan<'a> {
+    let mut scan = Scan::default();
+    let mut stack: Vec<OpenBlock<'a>> = Vec::new();
+    let mut current: Option<Step<'a>> = None;
+    let for_vars = |stack: &[OpenBlock<'a>]| -> Vec<&'a str> {
+        stack.iter().filter_map(|b| b.for_var).collect()
+    };
+
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        let line_no = idx + 1;
+        let kind = classify_line(line);
+        if let LineKind::Text(text) = kind {
+            match current.as_mut() {
+                Some(step) => step.body.push((line_no, text)),
+                None => scan.prose.push(text),
+            }
+            continue;
+        }
+        scan.steps.extend(current.take());
+        let open = |kind, for_var| OpenBlock {
+            kind,
+            line: line_no,
+            for_var,
+            has_else: false,
+        };
+        match kind {
+            LineKind::Step(title) => {
+                current = Some(Step {
+                    title,
+                    line: line_no,
+                    body: Vec::new(),
+                    nested: !stack.is_empty(),
+                    for_vars: for_vars(&stack),
+                });
+            }
+            LineKind::If(text) => {
+                scan.exprs.push(Expr {
+                    line: line_no,
+                    text,
+                    for_vars: for_vars(&stack),
+                });
+                stack.push(open(BlockKind::If, None));
+            }
+            LineKind::For { var, collection } => {
+                scan.exprs.push(Expr {
+                    line: line_no,
+                    text: collection,
+                    for_vars: for_vars(&stack),
+                });
+                stack.push(open(BlockKind::For, Some(var)));
+            }
+            LineKind::Repeat(mode, text) => {
+                scan.exprs.push(Expr {
+                    line: line_no,
+                    text,
+                    for_vars: for_vars(&stack),
+                });
+                let kind = match mode {
+                    RepeatMode::While => BlockKind::While,
+                    RepeatMode::Until => BlockKind::Until,
+                };
+                stack.push(open(kind, None));
+            }
+            LineKind::Parallel(_) => stack.push(open(BlockKind::Parallel, None)),
+            LineKind::Matrix { var, .. } => stack.push(open(BlockKind::Matrix, Some(var))),
+            LineKind::Else => match stack.last_mut() {
+                Some(block) if block.kind == BlockKind::If && !block.has_else => {
+                    block.has_else = true;
+                }
+                Some(block) if block.kind == BlockKind::If => lint.push(
+                    LintRule::UnbalancedBlock,
+                    line_no,
+                    format!("second `## ELSE` for the `## IF` at line {}", block.line),
+                ),
+                _ => lint.push(
+                    LintRule::UnbalancedBlock,
+                    line_no,
+                    "`## ELSE` outside an `## IF` block".to_string(),
+                ),
+            },
+            LineKind::EndIf => close(&mut stack, BlockKind::If, line_no, lint),
+            LineKind::EndFor => close(&mut stack, BlockKind::For, line_no, lint),
+            LineKind::EndRepeat(RepeatMode::While) => {
+                close(&mut stack, BlockKind::While, line_no, lint);
+            }
+            LineKind::EndRepeat(RepeatMode::Until) => {
+                close(&mut stack, BlockKind::Until, line_no, lint);
+            }
+            LineKind::EndParallel => close(&mut stack, BlockKind::Parallel, line_no, lint),
+            LineKind::EndMatrix => close(&mut stack, BlockKind::Matrix, line_no, lint),
+            LineKind::Include(_) | LineKind::Text(_) => {}
+        }
+    }
+    scan.steps.extend(current);
+    for block in stack {
+        lint.push(
+            LintRule::UnbalancedBlock,
+            block.line,
+            format!("`## {}` is never closed", block.keyword()),
+        );
+    }
+    scan
+}
+
+/// Pop the innermost open `kind` block, reporting blocks left open inside it.
+fn close(stack: &mut Vec<OpenBlock<'_>>, kind: BlockKind, line: usize, lint: &mut Findings<'_>) {
+    let keyword = kind.keyword();
+    let Some(pos) = stack.iter().rposition(|block| block.kind == kind) else {
+        lint.push(
+            LintRule::UnbalancedBlock,
+            line,
+            format!("`## END{keyword}` has no matching `## {keyword}`"),
+        );
+        return;
+    };
+    for block in stack.drain(pos + 1..) {
+        lint.push(
+            LintRule::UnbalancedBlock,
+            block.line,
+            format!(
+                "`## {}` is not closed before `## END{keyword}` at line {line}",
+                block.keyword()
+            ),
+        );
+    }
+    stack.pop();
+}
+
+impl OpenBlock<'_> {
+    fn keyword(&self) -> &'static str {
+        self.kind.keyword()
+    }
+}
+
+#[path = "lint_checks.rs"]
+mod checks;
+use checks::{check_steps, check_variables};
+
+#[cfg(test)]
+#[path = "lint_tests.rs"]
+mod tests;
@@ -0,0 +1,236 @@
+//! Per-step and variable checks for `weave lint`.
+
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{Findings, LintRule, Scan, Step};
+use crate::compiler::{FailAction, extract_hints};
+
+/// Matches `${VAR_NAME}` placeholders.
+static VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid regex"));
+
+/// Matches a variable documented as a list item: `` - `${VAR}`: ... ``.
+static DOC_ITEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*[-*]\s+`\$\{([A-Za-z_][A-Za-z0-9_]*)\}`").expect("valid regex")
+});
+
+/// Matches a `CSA_VAR:NAME=value` marker a step prints to set a variable.
+static CSA_VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"CSA_VAR:([A-Za-z_][A-Za-z0-9_]*)=").expect("valid regex"));
+
+/// Matches shell assignments: `NAME=`, `export NAME=`, `${NAME:=default}`,
+/// `for NAME in` and `read NAME`.
+static SHELL_ASSIGN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?x)
+        (?:^|[\s;&|(])(?:(?:export|local|readonly|declare(?:\s+-\w+)?)\s+)?([A-Za-z_][A-Za-z0-9_]*)\+?=
+        | \$\{([A-Za-z_][A-Za-z0-9_]*):?=
+        | \bfor\s+([A-Za-z_][A-Za-z0-9_]*)\s+in\b
+        | \bread\s+(?:-\w+\s+)*([A-Za-z_][A-Za-z0-9_]*)",
+    )
+    .expect("valid regex")
+});
+
+/// Matches `STEP_<n>_OUTPUT` / `STEP_<n>_SESSION`, injected for earlier steps.
+static STEP_VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^STEP_(\d+)_(?:OUTPUT|SESSION)$").expect("valid regex"));
+
+/// Matches a line that unconditionally exits non-zero.
+static EXIT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^exit\s+([0-9]+)\s*(?:[;#].*)?$").expect("valid regex"));
+
+/// Environment variables every shell step can read.
+const ENV_VARS: &[&str] = &["HOME", "PATH", "PWD", "USER", "TMPDIR", "SHELL"];
+
+/// Shell patterns worth a second look, with the reason they are flagged.
+static SUSPICIOUS_SHELL: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (
+            r"\b(?:curl|wget)\b[^|]*\|\s*(?:sudo\s+)?(?:ba|z)?sh\b",
+            "pipes a download into a shell",
+        ),
+        (
+            r#"\brm\s+-[a-zA-Z]*[rR][a-zA-Z]*\s+(?:-\S+\s+)*["']?(?:/|~|\$\{?[A-Za-z_][A-Za-z0-9_]*\}?)["']?(?:\s|$|/\*)"#,
+            "recursively deletes a root, home or variable path",
+        ),
+        (r"(?:^|[\s;&|(])eval\s", "uses `eval`"),
+        (r"(?:^|[\s;&|(])sudo\s", "uses `sudo`"),
+        (r"\bchmod\s+(?:-R\s+)?0?777\b", "makes files world-writable"),
+        (
+            r"\bgit\s+push\b.*(?:\s--force(?:\s|$)|\s-f\b)",
+            "force-pushes without `--force-with-lease`",
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, reason)| (Regex::new(pattern).expect("valid regex"), reason))
+    .collect()
+});
+
+/// `missing-tool`, `suspicious-shell` and `unreachable-step`.
+pub(super) fn check_steps(scan: &Scan<'_>, lint: &mut Findings<'_>) {
+    let mut aborted_by: Option<&Step<'_>> = None;
+    for step in &scan.steps {
+        if let Some(abort) = aborted_by.take() {
+            lint.push(
+                LintRule::UnreachableStep,
+                step.line,
+                format!(
+                    "step '{}' is unreachable: step '{}' at line {} always exits non-zero and aborts",
+                    step.title, abort.title, abort.line
+                ),
+            );
+        }
+
+        let body = body_text(step);
+        let hints = extract_hints(&body);
+        let shell = shell_lines(step);
+        if hints.tool.is_none() && !shell.is_empty() {
+            lint.push(
+                LintRule::MissingTool,
+                step.line,
+                format!(
+                    "step '{}' has a shell block but no `Tool:` hint, so it is sent to a model instead of run",
+                    step.title
+                ),
+            );
+        }
+        for &(line, text) in &step.body {
+            if let Some((_, reason)) = SUSPICIOUS_SHELL.iter().find(|(re, _)| re.is_match(text)) {
+                lint.push(
+                    LintRule::SuspiciousShell,
+                    line,
+                    format!("step '{}' {reason}: `{}`", step.title, text.trim()),
+                );
+            }
+        }
+
+        let runs_bash = hints
+            .tool
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("bash"));
+        let always_fails = shell
+            .iter()
+            .any(|(_, text)| EXIT_RE.captures(text).is_some_and(|caps| &caps[1] != "0"));
+        if !step.nested
+            && runs_bash
+            && always_fails
+            && hints.condition.is_none()
+            && hints.on_fail == FailAction::Abort
+        {
+            aborted_by = Some(step);
+        }
+    }
+}
+
+/// `undefined-variable`: report each unknown `${VAR}` once, at its first use.
+pub(super) fn check_variables(scan: &Scan<'_>, lint: &mut Findings<'_>) {
+    let defined = defined_variables(scan);
+    let is_defined = |name: &str| {
+        defined.contains(name)
+            || ENV_VARS.contains(&name)
+            || STEP_VAR_RE
+                .captures(name)
+                .and_then(|caps| caps[1].parse::<usize>().ok())
+                .is_some_and(|n| (1..=scan.steps.len()).contains(&n))
+    };
+
+    let mut uses: Vec<(usize, &str, &[&str])> = Vec::new();
+    for expr in &scan.exprs {
+        uses.push((expr.line, expr.text, &expr.for_vars));
+    }
+    for step in &scan.steps {
+        uses.push((step.line, step.title, &step.for_vars));
+        for &(line, text) in &step.body {
+            uses.push((line, text, &step.for_vars));
+        }
+    }
+    uses.sort_by_key(|(line, _, _)| *line);
+
+    let mut reported = BTreeSet::new();
+    for (line, text, for_vars) in uses {
+        for caps in VAR_RE.captures_iter(text) {
+            let name = caps.get(1).map_or("", |m| m.as_str());
+            if for_vars.contains(&name) || is_defined(name) || !reported.insert(name) {
+                continue;
+            }
+            lint.push(
+                LintRule::UndefinedVariable,
+                line,
+                format!(
+                    "`${{{name}}}` is never documented, bound by `Output:`/`CSA_VAR:`, or assigned"
+                ),
+            );
+        }
+    }
+}
+
+/// Variables declared in frontmatter or prose, documented in a step, or set
+/// by a step.
+fn defined_variables(scan: &Scan<'_>) -> BTreeSet<String> {
+    let mut defined: BTreeSet<String> = scan.inputs.iter().cloned().collect();
+    let mut capture = |re: &Regex, text: &str| {
+        for caps in re.captures_iter(text) {
+            defined.extend(
+                caps.iter()
+                    .skip(1)
+                    .flatten()
+                    .map(|m| m.as_str().to_string()),
+            );
+        }
+    };
+    for text in &scan.prose {
+        capture(&VAR_RE, text);
+    }
+    for step in &scan.steps {
+        for &(_, text) in &step.body {
+            capture(&DOC_ITEM_RE, text);
+            capture(&CSA_VAR_RE, text);
+        }
+        for (_, text) in shell_lines(step) {
+            capture(&SHELL_ASSIGN_RE, text);
+            // `: "${A}" "${B}"` is the idiom for declaring plan inputs.
+            if text.trim_start().starts_with(": ") {
+                capture(&VAR_RE, text);
+            }
+        }
+    }
+    defined.extend(
+        scan.steps
+            .iter()
+            .filter_map(|step| extract_hints(&body_text(step)).output)
+            .map(|output| output.var),
+    );
+    defined
+}
+
+fn body_text(step: &Step<'_>) -> String {
+    step.body
+        .iter()
+        .map(|(_, text)| *text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lines inside the step's bash/sh/shell/zsh code fences.
+fn shell_lines<'a>(step: &Step<'a>) -> Vec<(usize, &'a str)> {
+    let mut lines = Vec::new();
+    let mut in_shell: Option<bool> = None;
+    for &(line, text) in &step.body {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("```") {
+            in_shell = match in_shell {
+                Some(_) => None,
+                None => Some(matches!(
+                    trimmed.trim_start_matches('`').trim(),
+                    "bash" | "sh" | "shell" | "zsh"
+                )),
+            };
+        } else if in_shell == Some(true) {
+            lines.push((line, text));
+        }
+    }
+    lines
+}
@@ -1,4 +1,6 @@
 mod cli;
+mod commands;
+mod hooks;
 
 use std::io::Read;
 use std::path::PathBuf;
@@ -6,13 +8,13 @@ use std::path::PathBuf;
 use anyhow::{Context, Result, bail};
 use clap::Parser;
 
-use cli::{Cli, Commands, LinkAction};
-use weave::batch;
+use cli::{Cli, Commands, Format, LinkAction};
 use weave::check;
-use weave::compiler::{compile, plan_from_toml, plan_to_toml};
+use weave::compiler::{compile_with_warnings, plan_from_toml, plan_to_toml};
+use weave::harness;
 use weave::link::{self, LinkScope};
 use weave::package;
-use weave::parser::parse_skill;
+use weave::parser::parse_skill_file;
 use weave::visualize::{self, VisualizeResult, VisualizeTarget};
 
 fn main() -> Result<()> {
@@ -47,18 +49,22 @@ fn main() -> Result<()> {
 
     match cli.command {
         Commands::Compile { input, output } => {
-            let content = std::fs::read_to_string(&input)
-                .with_context(|| format!("failed to read {}", input.display()))?;
             let plan = if input
                 .file_name()
                 .is_some_and(|name| name == "workflow.toml")
             {
+                let content = std::fs::read_to_string(&input)
+                    .with_context(|| format!("failed to read {}", input.display()))?;
                 plan_from_toml(&content)
                     .with_context(|| format!("failed to parse {}", input.display()))?
             } else {
-                let doc = parse_skill(&content)
+                let doc = parse_skill_file(&input)
                     .with_context(|| format!("failed to parse {}", input.display()))?;
-                compile(&doc).context("compilation failed")?
+                let output = compile_with_warnings(&doc).context("compilation failed")?;
+                for warning in &output.warnings {
+                    eprintln!("warning: {}", warning.message);
+                }
+                output.plan
             };
             let toml_str = plan_to_toml(&plan)?;
 
@@ -70,27 +76,71 @@ fn main() -> Result<()> {
                 print!("{toml_str}");
             }
         }
-        Commands::CompileAll { dir } => {
-            let summary = batch::compile_all(&dir)?;
-            let total = summary.ok + summary.failed;
-            if summary.failed > 0 {
+        Commands::CompileAll { dir } => commands::compile_all(dir)?,
+        Commands::Fmt { paths, check } => commands::fmt(paths, check)?,
+        Commands::Lint { paths } => commands::lint(paths, matches!(cli.format, Format::Json))?,
+        Commands::Publish {
+            bump,
+            member,
+            push,
+            index,
+            dry_run,
+        } => {
+            let registry = if index {
+                Some(configured_registry()?.context(
+                    "no package index configured; set `registry` under [weave] in the global config",
+                )?)
+            } else {
+                None
+            };
+            commands::publish(
+                &bump.parse()?,
+                member.as_deref(),
+                push,
+                registry.as_deref(),
+                dry_run,
+            )?;
+        }
+        Commands::Test { dir } => {
+            let outcomes = harness::run_package_tests(&dir)?;
+            if outcomes.is_empty() {
                 eprintln!(
-                    "{total} pattern(s) compiled: {} OK, {} FAILED",
-                    summary.ok, summary.failed
+                    "no tests found under {}",
+                    dir.join(harness::TESTS_DIR).display()
                 );
+                return Ok(());
+            }
+            let total = outcomes.len();
+            let mut failed = 0usize;
+            for (i, outcome) in outcomes.iter().enumerate() {
+                let label = outcome.path.display();
+                if outcome.passed() {
+                    eprintln!("[{}/{}] {label} ... OK", i + 1, total);
+                } else {
+                    failed += 1;
+                    eprintln!("[{}/{}] {label} ... FAILED", i + 1, total);
+                    for failure in &outcome.failures {
+                        eprintln!("  - {failure}");
+                    }
+                }
+            }
+            eprintln!("{total} test(s): {} OK, {failed} FAILED", total - failed);
+            if failed > 0 {
                 std::process::exit(1);
-            } else {
-                eprintln!("{total} pattern(s) compiled: {} OK, 0 FAILED", summary.ok);
             }
         }
         Commands::Install {
             source,
             path,
+            branch,
+            tag,
+            rev,
+            member,
             link_scope,
             no_link,
             force_link,
         } => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
+            let project_root = commands::project_root()?;
 
             let scope: LinkScope = if no_link {
                 LinkScope::None
@@ -119,15 +169,49 @@ fn main() -> Result<()> {
                 let store_root = package::global_store_root()?;
                 let pkg = package::install_from_local(&local_path, &project_root, &store_root)?;
                 eprintln!("installed {} (local) -> {}/", pkg.name, pkg.name);
+                hooks::PackageHooks::load(&project_root).installed(&pkg);
+                commands::warn_unmet_requirements(&project_root, &store_root, &pkg)?;
             } else if let Some(git_source) = source {
                 let cache_root = package::default_cache_root()?;
                 let store_root = package::global_store_root()?;
-                let pkg = package::install(&git_source, &project_root, &cache_root, &store_root)?;
+                let registry = if package::is_index_name(&git_source) {
+                    configured_registry()?
+                } else {
+                    None
+                };
+                let git_source =
+                    package::resolve_index_source(&git_source, registry.as_deref(), &cache_root)?;
+                let pin = [
+                    (package::PinKind::Branch, branch),
+                    (package::PinKind::Tag, tag),
+                    (package::PinKind::Rev, rev),
+                ]
+                .into_iter()
+                .find_map(|(kind, target)| {
+                    Some(package::Pin {
+                        kind,
+                        target: target?,
+                    })
+                });
+                let pkg = package::install(
+                    &git_source,
+                    pin.as_ref(),
+                    member.as_deref(),
+                    &project_root,
+                    &cache_root,
+                    &store_root,
+                )?;
                 let commit_short = &pkg.commit[..pkg.commit.len().min(8)];
                 eprintln!(
                     "installed {} ({}) -> {}/{}/",
                     pkg.name, commit_short, pkg.name, commit_short
                 );
+                if let Some(pin) = pkg.pin() {
+                    eprintln!("pinned {} to {pin}", pkg.name);
+                }
+                hooks::PackageHooks::load(&project_root).installed(&pkg);
+                commands::warn_version_conflicts(&project_root, &store_root)?;
+                commands::warn_unmet_requirements(&project_root, &store_root, &pkg)?;
             } else {
                 bail!("either <SOURCE> or --path <DIR> is required");
             }
@@ -169,25 +253,77 @@ fn main() -> Result<()> {
                 }
             }
         }
-        Commands::Lock => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
-            let store_root = package::global_store_root()?;
-            let lockfile = package::lock(&project_root, &store_root)?;
-            eprintln!("locked {} package(s)", lockfile.package.len());
-            for pkg in &lockfile.package {
-                let ver = pkg.version.as_deref().unwrap_or("-");
-                let commit_short = if pkg.commit.len() > 12 {
-                    &pkg.commit[..12]
+        Commands::Auth { host, remove } => {
+            if remove {
+                if package::remove_token(&host)? {
+                    eprintln!("removed token for {host}");
                 } else {
-                    &pkg.commit
+                    eprintln!("no token stored for {host}");
+                }
+            } else {
+                if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+                    eprintln!("Paste the access token for {host}, then press Enter:");
+                }
+                let mut token = String::new();
+                std::io::stdin()
+                    .read_line(&mut token)
+                    .context("failed to read token from stdin")?;
+                let token = token.trim();
+                if token.is_empty() {
+                    bail!("no token given on stdin");
+                }
+                package::store_token(&host, token)?;
+                eprintln!("stored token for {host} in the OS keyring");
+            }
+        }
+        Commands::Search { term } => {
+            let registry = configured_registry()?.context(
+                "no package index configured; set `registry` under [weave] in the global config",
+            )?;
+            let index = package::fetch_index(&package::default_cache_root()?, &registry)?;
+            let hits = index.search(&term);
+            if hits.is_empty() {
+                eprintln!("no packages match '{term}'");
+            }
+            for entry in hits {
+                let tags = if entry.tags.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", entry.tags.join(", "))
                 };
-                eprintln!("  {} {} ({})", pkg.name, ver, commit_short);
+                println!("{}  {}{tags}", entry.name, entry.description);
+                println!("    {}", entry.repo);
+            }
+        }
+        Commands::Lock => commands::lock(&commands::project_root()?)?,
+        Commands::Tree { duplicates, why } => {
+            let project_root = commands::project_root()?;
+            let store_root = package::global_store_root()?;
+            let graph = package::dependency_graph(&project_root, &store_root)?;
+            let rendered = match why.as_deref() {
+                Some(name) => graph.render_why(name)?,
+                None if duplicates => graph.render_duplicates(),
+                None => graph.render_tree(),
+            };
+            if rendered.is_empty() {
+                eprintln!(
+                    "{}",
+                    if duplicates {
+                        "no duplicate packages"
+                    } else {
+                        "no packages installed"
+                    }
+                );
             }
+            print!("{rendered}");
         }
         Commands::Update { name, force } => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
+            let project_root = commands::project_root()?;
             let cache_root = package::default_cache_root()?;
             let store_root = package::global_store_root()?;
+            let previous = package::load_project_lockfile(&project_root)
+                .map(|lockfile| lockfile.package)
+                .unwrap_or_default();
             let updated = package::update(
                 name.as_deref(),
                 &project_root,
@@ -203,153 +339,42 @@ fn main() -> Result<()> {
                 };
                 eprintln!("updated {} -> {}", pkg.name, commit_short);
             }
-        }
-        Commands::Upgrade { force } => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
-            let cache_root = package::default_cache_root()?;
-            let store_root = package::global_store_root()?;
-            let results = package::upgrade(&project_root, &cache_root, &store_root, force)?;
-
-            let mut upgraded = 0u32;
-            let mut already_latest = 0u32;
-            let mut skipped = 0u32;
-
-            for entry in &results {
-                match &entry.status {
-                    package::UpgradeStatus::Upgraded {
-                        old_commit,
-                        old_version,
-                    } => {
-                        upgraded += 1;
-                        let old_short = &old_commit[..old_commit.len().min(12)];
-                        let new_short = &entry.package.commit[..entry.package.commit.len().min(12)];
-                        let old_ver = old_version.as_deref().unwrap_or("-");
-                        let new_ver = entry.package.version.as_deref().unwrap_or("-");
-                        eprintln!(
-                            "  upgraded {} ({old_ver} {old_short}) -> ({new_ver} {new_short})",
-                            entry.name
-                        );
-                    }
-                    package::UpgradeStatus::AlreadyLatest => {
-                        already_latest += 1;
-                        let ver = entry.package.version.as_deref().unwrap_or("-");
-                        eprintln!("  up-to-date {} ({ver})", entry.name);
-                    }
-                    package::UpgradeStatus::Skipped { reason } => {
-                        skipped += 1;
-                        eprintln!("  skipped {} ({reason})", entry.name);
-                    }
-                }
-            }
-
-            eprintln!();
-            eprintln!(
-                "{} package(s): {upgraded} upgraded, {already_latest} up-to-date, {skipped} skipped",
-                results.len()
-            );
-
-            // Auto-link companion skills after upgrade.
-            if upgraded > 0 {
-                let removed = link::remove_stale_links(&project_root, LinkScope::Project)?;
-                if !removed.is_empty() {
-                    eprintln!("removed {} stale symlink(s)", removed.len());
-                }
-
-                // Scan project files for stale references to removed skills.
-                let removed_skill_names: Vec<String> = {
-                    let mut names = std::collections::HashSet::new();
-                    for p in &removed {
-                        if let Some(n) = p.file_name() {
-                            names.insert(n.to_string_lossy().to_string());
-                        }
-                    }
-                    names.into_iter().collect()
-                };
-
-                if !removed_skill_names.is_empty() {
-                    let stale_refs = weave::stale_ref::scan_stale_skill_references(
-                        &project_root,
-                        &removed_skill_names,
-                    );
-                    if !stale_refs.is_empty() {
-                        eprintln!();
-                        eprintln!("warning: found stale references to removed skill(s):");
-                        for r in &stale_refs {
-                            eprintln!(
-                                "  {}:{}: Skill '{}' was removed but is still referenced",
-                                r.file.display(),
-                                r.line,
-                                r.skill_name,
-                            );
-                        }
-                        eprintln!(
-                            "  -> {} stale reference(s) found. Update these files to use the new skill name.",
-                            stale_refs.len(),
-                        );
-                    }
-                }
-
-                let report = link::link_skills(&project_root, LinkScope::Project, false)?;
-                let created = report.unique_created_count();
-                let link_skipped = report.unique_skipped_count();
-                if created > 0 || link_skipped > 0 {
-                    eprintln!("linked {created} skill(s) ({link_skipped} already up-to-date)");
-                }
-                for name in report.unique_created_names() {
-                    eprintln!("  + {name}");
-                }
-                if report.has_errors() {
-                    for err in &report.errors {
-                        eprintln!("warning: {err}");
-                    }
-                }
-
-                let stale_pats = link::remove_stale_pattern_links(&project_root)?;
-                if !stale_pats.is_empty() {
-                    eprintln!("removed {} stale pattern link(s)", stale_pats.len());
-                }
-                let pat_report = link::link_patterns(&project_root, false)?;
-                let pat_created = pat_report.unique_created_count();
-                if pat_created > 0 {
-                    eprintln!("linked {pat_created} pattern(s)");
-                    for name in pat_report.unique_created_names() {
-                        eprintln!("  + patterns/{name}");
-                    }
-                }
-                for err in &pat_report.errors {
-                    eprintln!("warning (pattern): {err}");
-                }
-            }
-
-            // Migrate .gemini/skills/ → .agents/skills/ on every upgrade.
-            let migrate_result = check::migrate_gemini_skills(&project_root)?;
-            if !migrate_result.missing_dir {
-                let moved = migrate_result.moved.len();
-                let removed = migrate_result.removed.len();
-                if moved > 0 || removed > 0 {
-                    eprintln!();
-                    eprintln!(
-                        "gemini→agents migration: {moved} moved, {removed} duplicate(s) removed"
-                    );
-                    for entry in &migrate_result.moved {
-                        eprintln!(
-                            "  → {} (from {})",
-                            entry.agents_path.display(),
-                            entry.gemini_path.display()
-                        );
-                    }
-                }
-                for f in migrate_result
-                    .move_failures
+            let hooks = hooks::PackageHooks::load(&project_root);
+            for pkg in &updated {
+                if let Some(old) = previous
                     .iter()
-                    .chain(&migrate_result.remove_failures)
+                    .find(|old| old.name == pkg.name && old.commit != pkg.commit)
                 {
-                    eprintln!("warning: {}: {}", f.path.display(), f.error);
+                    hooks.upgraded(pkg, &old.commit, old.version.as_deref());
                 }
             }
+            commands::warn_version_conflicts(&project_root, &store_root)?;
+        }
+        Commands::Upgrade { force } => {
+            commands::upgrade(&commands::project_root()?, force)?;
+        }
+        Commands::Diff { name } => {
+            let diff = package::diff(
+                &name,
+                &commands::project_root()?,
+                &package::default_cache_root()?,
+                &package::global_store_root()?,
+            )?;
+            print!("{}", diff.render());
+        }
+        Commands::Remove { name } => {
+            let project_root = commands::project_root()?;
+            let pkg = package::remove(&project_root, &name)?;
+            eprintln!("removed {} from weave.lock", pkg.name);
+            let unlinked = link::remove_stale_links(&project_root, LinkScope::Project)?;
+            for path in &unlinked {
+                eprintln!("  - {}", path.display());
+            }
+            hooks::PackageHooks::load(&project_root).removed(&pkg);
+            eprintln!("run `weave gc` to delete its checkout");
         }
         Commands::Audit => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
+            let project_root = commands::project_root()?;
             let store_root = package::global_store_root()?;
             let results = package::audit(&project_root, &store_root)?;
             if results.is_empty() {
@@ -412,30 +437,44 @@ fn main() -> Result<()> {
             }
         }
         Commands::Gc { dry_run } => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
+            let project_root = commands::project_root()?;
             let store_root = package::global_store_root()?;
             let result = package::gc(&project_root, &store_root, dry_run)?;
-            if result.removed.is_empty() {
-                eprintln!("nothing to collect — all checkouts are referenced");
-            } else if dry_run {
-                for entry in &result.removed {
-                    eprintln!("  would remove {entry}");
-                }
+            let keep: Vec<String> = configured_registry()?.into_iter().collect();
+            let cache = package::gc_cache(
+                &project_root,
+                &package::default_cache_root()?,
+                &keep,
+                dry_run,
+            )?;
+            let (verb, freeing) = if dry_run {
+                ("would remove", "freeing")
+            } else {
+                ("removed", "freed")
+            };
+            for entry in &result.removed {
+                eprintln!("  {verb} {entry}");
+            }
+            for entry in &cache.removed {
+                eprintln!("  {verb} cached repository {entry}");
+            }
+            if !result.removed.is_empty() {
                 eprintln!(
-                    "would remove {} unreferenced checkout(s), freeing ~{} bytes",
+                    "{verb} {} unreferenced checkout(s), {freeing} ~{} bytes",
                     result.removed.len(),
                     result.freed_bytes
                 );
-            } else {
-                for entry in &result.removed {
-                    eprintln!("  removed {entry}");
-                }
+            }
+            if !cache.removed.is_empty() || cache.freed_bytes > 0 {
                 eprintln!(
-                    "removed {} unreferenced checkout(s), freed ~{} bytes",
-                    result.removed.len(),
-                    result.freed_bytes
+                    "{verb} {} unreferenced cached repository(ies) and pruned the object pool, {freeing} ~{} bytes",
+                    cache.removed.len(),
+                    cache.freed_bytes
                 );
             }
+            if result.removed.is_empty() && cache.removed.is_empty() && cache.freed_bytes == 0 {
+                eprintln!("nothing to collect — all checkouts are referenced");
+            }
         }
         Commands::Check { dirs, fix } => {
             let project_root = std::env::current_dir().context("cannot determine CWD")?;
@@ -537,9 +576,19 @@ fn main() -> Result<()> {
                 std::process::exit(1);
             }
         }
-        Commands::Visualize { plan, png, mermaid } => {
+        Commands::Visualize {
+            plan,
+            png,
+            mermaid,
+            svg,
+            html,
+        } => {
             let target = if mermaid {
                 VisualizeTarget::Mermaid
+            } else if svg {
+                VisualizeTarget::Svg
+            } else if html {
+                VisualizeTarget::Html
             } else if let Some(output) = png {
                 VisualizeTarget::Png(output)
             } else {
@@ -566,116 +615,14 @@ fn main() -> Result<()> {
             }
         }
         Commands::Link { action } => {
-            let project_root = std::env::current_dir().context("cannot determine CWD")?;
+            let project_root = commands::project_root()?;
 
             match action {
                 LinkAction::Sync {
                     scope,
                     force,
                     dry_run,
-                } => {
-                    let scope: LinkScope = scope.into();
-
-                    if scope == LinkScope::None {
-                        bail!("--scope none is not valid for 'link sync'");
-                    }
-
-                    if dry_run {
-                        // Dry-run: show what would be done without modifying anything.
-                        let skills = link::discover_skills(&project_root)?;
-                        eprintln!("would link {} skill(s):", skills.len());
-                        for skill in &skills {
-                            eprintln!("  {} (from {})", skill.name, skill.package_name);
-                        }
-
-                        let stale = link::detect_stale_links(&project_root, scope)?;
-                        if stale.is_empty() {
-                            eprintln!("no stale links detected");
-                        } else {
-                            eprintln!("would remove {} stale link(s):", stale.len());
-                            for p in &stale {
-                                eprintln!("  - {}", p.display());
-                            }
-                        }
-                        eprintln!("(dry-run: no changes made)");
-                        return Ok(());
-                    }
-
-                    // Remove stale links first.
-                    let removed = link::remove_stale_links(&project_root, scope)?;
-                    if !removed.is_empty() {
-                        eprintln!("removed {} stale symlink(s)", removed.len());
-                        for p in &removed {
-                            eprintln!("  - {}", p.display());
-                        }
-                    }
-
-                    // Scan for stale references to removed skills.
-                    let removed_names: Vec<String> = {
-                        let mut names = std::collections::HashSet::new();
-                        for p in &removed {
-                            if let Some(n) = p.file_name() {
-                                names.insert(n.to_string_lossy().to_string());
-                            }
-                        }
-                        names.into_iter().collect()
-                    };
-                    if !removed_names.is_empty() {
-                        let stale_refs = weave::stale_ref::scan_stale_skill_references(
-                            &project_root,
-                            &removed_names,
-                        );
-                        if !stale_refs.is_empty() {
-                            eprintln!();
-                            eprintln!("warning: found stale references to removed skill(s):");
-                            for r in &stale_refs {
-                                eprintln!(
-                                    "  {}:{}: Skill '{}' was removed but is still referenced",
-                                    r.file.display(),
-                                    r.line,
-                                    r.skill_name,
-                                );
-                            }
-                            eprintln!(
-                                "  -> {} stale reference(s) found. Update these files to use the new skill name.",
-                                stale_refs.len(),
-                            );
-                        }
-                    }
-
-                    // Create/update links.
-                    let report = link::link_skills(&project_root, scope, force)?;
-
-                    if report.has_errors() {
-                        for err in &report.errors {
-                            eprintln!("error: {err}");
-                        }
-                        bail!("{} error(s) during link sync", report.errors.len());
-                    }
-
-                    let created = report.unique_created_count();
-                    let skipped = report.unique_skipped_count();
-                    eprintln!(
-                        "link sync: {created} created, {skipped} up-to-date, {} stale removed",
-                        removed.len()
-                    );
-
-                    let stale_pats = link::remove_stale_pattern_links(&project_root)?;
-                    if !stale_pats.is_empty() {
-                        eprintln!("removed {} stale pattern link(s)", stale_pats.len());
-                    }
-                    let pat_report = link::link_patterns(&project_root, force)?;
-                    let pat_created = pat_report.unique_created_count();
-                    if pat_created > 0 {
-                        eprintln!("linked {pat_created} pattern(s)");
-                        for name in pat_report.unique_created_names() {
-                            eprintln!("  + patterns/{name}");
-                        }
-                    }
-                    for err in &pat_report.errors {
-                        eprintln!("warning (pattern): {err}");
-                    }
-                }
+                } => commands::link_sync(&project_root, scope.into(), force, dry_run)?,
             }
         }
     }
@@ -683,6 +630,11 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Package index URL from `[weave] registry` in the global config.
+fn configured_registry() -> Result<Option<String>> {
+    Ok(csa_config::GlobalConfig::load()?.weave.registry)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -692,4 +644,26 @@ mod tests {
         let cli = Cli::try_parse_from(["weave", "clean-gemini-skills"]).unwrap();
         assert!(matches!(cli.command, Commands::CleanGeminiSkills));
     }
+
+    #[test]
+    fn install_accepts_one_pin_flag() {
+        let cli = Cli::try_parse_from(["weave", "install", "user/repo", "--tag", "v1.0"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Install { tag: Some(ref tag), branch: None, rev: None, .. } if tag == "v1.0"
+        ));
+        assert!(
+            Cli::try_parse_from([
+                "weave",
+                "install",
+                "user/repo",
+                "--tag",
+                "v1",
+                "--rev",
+                "abc"
+            ])
+            .is_err()
+        );
+        assert!(Cli::try_parse_from(["weave", "install", "--branch", "main"]).is_err());
+    }
 }
@@ -1,8 +1,9 @@
 //! Weave git-native package management.
 //!
-//! Skills are distributed as git repositories. Weave clones them into a
-//! content-addressable cache (`~/.cache/weave/git/<url-hash>/`) and checks
-//! out the requested revision into the global package store at
+//! Skills are distributed as git repositories. Weave shallow-clones them into
+//! a content-addressable cache (`~/.cache/weave/git/<url-hash>/`) whose
+//! objects live in a shared pool, and checks out the requested revision into
+//! the global package store at
 //! `~/.local/share/weave/packages/<name>/<commit-prefix>/`.
 
 use std::path::{Path, PathBuf};
@@ -10,18 +11,30 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 
+use crate::workspace::normalize_member_path;
+
 #[path = "package_git.rs"]
 mod package_git;
 pub(crate) use package_git::detect_skill_md_case_mismatch;
 use package_git::{
     checkout_to, copy_dir_recursive, ensure_cached, legacy_lockfile_path, read_version,
-    resolve_commit,
 };
 pub use package_git::{
     default_cache_root, find_lockfile, global_store_root, is_checkout_valid, load_project_lockfile,
     lockfile_path,
 };
 
+#[path = "package_source.rs"]
+mod package_source;
+pub use package_source::{InstallSource, Pin, PinKind, parse_source};
+
+#[path = "package_auth.rs"]
+mod package_auth;
+
+#[path = "package_cache.rs"]
+mod package_cache;
+pub use package_auth::{GitFailure, GitFailureKind, remove_token, store_token};
+
 /// Root structure of the lockfile (`weave.lock`).
 ///
 /// The lock file may also contain CSA version/migration tracking sections
@@ -61,7 +74,7 @@ pub enum SourceKind {
 }
 
 /// A single locked dependency.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct LockedPackage {
     pub name: String,
     pub repo: String,
@@ -78,96 +91,48 @@ pub struct LockedPackage {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub requested_version: Option<String>,
     /// The git ref that was resolved during install (branch, tag, or commit
-    /// hash before full resolution). Absent means HEAD was used.
+    /// hash before full resolution). Absent means HEAD was used. Installs
+    /// pinned with `--branch`/`--tag` record `refs/heads/<name>` or
+    /// `refs/tags/<name>`, and `--rev` records the full commit.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resolved_ref: Option<String>,
+    /// `sha256:` digest of the store checkout, verified by audit and before
+    /// linking. Absent in lockfiles written before checksums were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Workspace member directory inside `repo` that was installed; absent
+    /// when the whole repository is the package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
 }
 
-/// Parsed install source — a git URL with an optional ref and skill name.
-#[derive(Debug, Clone, PartialEq)]
-pub struct InstallSource {
-    /// Canonical git URL (https).
-    pub url: String,
-    /// Git ref to checkout (branch/tag/commit). None means HEAD.
-    pub git_ref: Option<String>,
-    /// Skill name (last path segment without `.git`).
-    pub name: String,
-}
-
-/// Parse a source string into an `InstallSource`.
-///
-/// Accepted formats:
-/// - `user/repo` → `https://github.com/user/repo.git`
-/// - `github.com/user/repo` → `https://github.com/user/repo.git`
-/// - `https://github.com/user/repo` → as-is with `.git` suffix
-/// - `https://github.com/user/repo@v1.0` → with ref
-/// - `https://github.com/user/repo#branch` → with ref
-pub fn parse_source(source: &str) -> Result<InstallSource> {
-    let (url_part, git_ref) = if let Some((url, r)) = source.rsplit_once('@') {
-        (url.to_string(), Some(r.to_string()))
-    } else if let Some((url, r)) = source.rsplit_once('#') {
-        (url.to_string(), Some(r.to_string()))
-    } else {
-        (source.to_string(), None)
-    };
-
-    let url = normalize_url(&url_part)?;
-    let name = extract_name(&url)?;
-
-    Ok(InstallSource { url, git_ref, name })
-}
-
-/// Normalize various URL formats to canonical https git URL.
-fn normalize_url(input: &str) -> Result<String> {
-    // Already a full URL
-    if input.starts_with("https://") || input.starts_with("http://") {
-        let url = if input.ends_with(".git") {
-            input.to_string()
+impl LockedPackage {
+    /// What the package is pinned to (`branch main`, `tag v1.2`,
+    /// `rev 1a2b3c4d`, or the `@ref` it was installed with); `None` when it
+    /// follows the default branch or a version requirement.
+    pub fn pin(&self) -> Option<String> {
+        let requested = self.requested_version.as_deref()?;
+        if is_version_req(requested) {
+            return None;
+        }
+        let resolved = self.resolved_ref.as_deref().unwrap_or_default();
+        Some(if resolved.starts_with("refs/heads/") {
+            format!("branch {requested}")
+        } else if resolved.starts_with("refs/tags/") {
+            format!("tag {requested}")
+        } else if !self.commit.is_empty() && resolved == self.commit {
+            format!("rev {}", &self.commit[..self.commit.len().min(8)])
         } else {
-            format!("{input}.git")
-        };
-        return Ok(url);
+            requested.to_string()
+        })
     }
 
-    // domain/user/repo format (e.g., github.com/user/repo)
-    if input.contains('.') && input.contains('/') {
-        let url = if input.ends_with(".git") {
-            format!("https://{input}")
-        } else {
-            format!("https://{input}.git")
-        };
-        return Ok(url);
-    }
-
-    // user/repo shorthand → GitHub
-    let parts: Vec<&str> = input.split('/').collect();
-    if parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-        return Ok(format!("https://github.com/{}/{}.git", parts[0], parts[1]));
-    }
-
-    bail!("cannot parse source: '{input}' (expected user/repo, domain/user/repo, or full URL)")
-}
-
-/// Extract the skill name from a git URL (last path segment minus `.git`).
-fn extract_name(url: &str) -> Result<String> {
-    let path = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))
-        .unwrap_or(url);
-
-    let last_segment = path
-        .trim_end_matches('/')
-        .rsplit('/')
-        .next()
-        .context("empty URL path")?;
-
-    let name = last_segment.strip_suffix(".git").unwrap_or(last_segment);
-
-    if name.is_empty() {
-        bail!("could not extract skill name from URL: {url}");
+    /// Ref that `update`/`upgrade --force` re-resolves for a pinned package.
+    fn pinned_ref(&self) -> Option<&str> {
+        self.resolved_ref
+            .as_deref()
+            .or(self.requested_version.as_deref())
     }
-
-    Ok(name.to_string())
 }
 
 // ---------------------------------------------------------------------------
@@ -219,37 +184,69 @@ pub fn package_dir(store_root: &Path, name: &str, commit: &str) -> Result<PathBu
 /// Skips checkout when the destination already contains a valid checkout
 /// (content-addressed idempotency).
 ///
+/// With `pin`, the install follows that branch, tag, or commit instead of
+/// the default branch; `source` must not carry its own `@ref` then. With
+/// `member`, only that workspace member directory of the repository is
+/// checked out, and the package is named after it.
+///
 /// Returns the locked package entry.
 pub fn install(
     source: &str,
+    pin: Option<&Pin>,
+    member: Option<&str>,
     project_root: &Path,
     cache_root: &Path,
     store_root: &Path,
 ) -> Result<LockedPackage> {
     let src = parse_source(source)?;
+    let member = member.map(normalize_member_path).transpose()?;
+    let name = match &member {
+        Some(path) => path.rsplit('/').next().unwrap_or(path).to_string(),
+        None => src.name,
+    };
+    let lock_path = lockfile_path(project_root);
+    let mut lockfile = load_project_lockfile(project_root).unwrap_or_default();
+    let (requested, spec) = match pin {
+        Some(_) if src.git_ref.is_some() => {
+            bail!("'{source}' already names a ref; use either `@<ref>` or --branch/--tag/--rev")
+        }
+        Some(pin) => (Some(pin.target.clone()), Some(pin.git_ref())),
+        None => (src.git_ref.clone(), src.git_ref),
+    };
     let cas = ensure_cached(cache_root, &src.url)?;
-    let commit = resolve_commit(&cas, src.git_ref.as_deref())?;
-
-    let dest = package_dir(store_root, &src.name, &commit)?;
-    if !is_checkout_valid(&dest) {
-        checkout_to(&cas, &commit, &dest)?;
+    let (commit, mut resolved_ref) =
+        resolve_spec(&cas, &name, spec.as_deref(), &lockfile, store_root)?;
+    if pin.is_some_and(|pin| pin.kind == PinKind::Rev) {
+        resolved_ref = Some(commit.clone());
+    }
+    if let Some(path) = &member {
+        check_member(&cas, &commit, path)?;
     }
 
+    let dest = package_dir(store_root, &name, &commit)?;
+    let locked = lockfile
+        .package
+        .iter()
+        .find(|p| p.name == name && p.commit == commit)
+        .and_then(|p| p.checksum.as_deref());
+    let tree = member_tree(&commit, member.as_deref());
+    let checksum = ensure_checkout(&cas, &tree, &dest, locked)?;
+
     let version = read_version(&dest);
 
     let pkg = LockedPackage {
-        name: src.name,
+        name,
         repo: src.url,
         commit,
         version,
         source_kind: SourceKind::Git,
-        requested_version: src.git_ref.clone(),
-        resolved_ref: src.git_ref,
+        requested_version: requested,
+        resolved_ref,
+        checksum: Some(checksum),
+        member,
     };
 
     // Update the lockfile with this package.
-    let lock_path = lockfile_path(project_root);
-    let mut lockfile = load_project_lockfile(project_root).unwrap_or_default();
     upsert_package(&mut lockfile, &pkg);
     save_lockfile(&lock_path, &lockfile)?;
 
@@ -375,6 +372,8 @@ pub fn install_from_local(
         source_kind: SourceKind::Local,
         requested_version: None,
         resolved_ref: None,
+        checksum: Some(checkout_checksum(&dest)?),
+        member: None,
     };
 
     // Update the lockfile.
@@ -416,6 +415,11 @@ pub fn lock(project_root: &Path, store_root: &Path) -> Result<Lockfile> {
             let checkout = package_dir(store_root, &pkg.name, commit_key)?;
             if checkout.is_dir() {
                 updated.version = read_version(&checkout);
+                // Keep recorded checksums so relocking cannot bless a
+                // modified checkout.
+                if updated.checksum.is_none() {
+                    updated.checksum = Some(checkout_checksum(&checkout)?);
+                }
             }
         }
         packages.push(updated);
@@ -473,37 +477,44 @@ pub fn update(
             continue; // Skip entries without a known repo.
         }
 
-        // Skip pinned dependencies unless --force is used.
-        if pkg.requested_version.is_some() && !force {
+        // Skip pinned dependencies unless --force is used; version
+        // requirements re-resolve within their range.
+        let constrained = pkg.requested_version.as_deref().is_some_and(is_version_req);
+        if pkg.requested_version.is_some() && !constrained && !force {
             eprintln!(
                 "skipping {} (pinned to {} — use --force to override)",
                 pkg.name,
-                pkg.requested_version.as_deref().unwrap_or("?")
+                pkg.pin().as_deref().unwrap_or("?")
             );
             continue;
         }
 
         let cas = ensure_cached(cache_root, &pkg.repo)?;
 
-        // When --force is used on pinned deps, resolve from the configured ref
-        // (branch/tag name) instead of the previously resolved commit hash,
-        // so we can advance past immutable pinned refs.
-        let resolve_ref = if force {
+        // When --force is used on pinned deps, re-resolve the pinned ref so
+        // branch pins advance to the branch head.
+        let resolve_ref = if constrained {
             pkg.requested_version.as_deref()
+        } else if force {
+            pkg.pinned_ref()
         } else {
             pkg.resolved_ref.as_deref()
         };
-        let new_commit = resolve_commit(&cas, resolve_ref)?;
+        let (new_commit, resolved_ref) =
+            resolve_spec(&cas, &pkg.name, resolve_ref, &lockfile, store_root)?;
 
         if new_commit != pkg.commit {
             let dest = package_dir(store_root, &pkg.name, &new_commit)?;
-            if !is_checkout_valid(&dest) {
-                checkout_to(&cas, &new_commit, &dest)?;
-            }
+            let tree = member_tree(&new_commit, pkg.member.as_deref());
+            let checksum = ensure_checkout(&cas, &tree, &dest, None)?;
 
             let version = read_version(&dest);
             lockfile.package[idx].commit = new_commit;
             lockfile.package[idx].version = version;
+            lockfile.package[idx].checksum = Some(checksum);
+            if constrained {
+                lockfile.package[idx].resolved_ref = resolved_ref;
+            }
         }
 
         updated.push(lockfile.package[idx].clone());
@@ -583,13 +594,14 @@ pub fn upgrade(
             continue;
         }
 
-        if pkg.requested_version.is_some() && !force {
+        let constrained = pkg.requested_version.as_deref().is_some_and(is_version_req);
+        if pkg.requested_version.is_some() && !constrained && !force {
             results.push(UpgradeEntry {
                 name: pkg.name.clone(),
                 status: UpgradeStatus::Skipped {
                     reason: format!(
                         "pinned to {} — use --force to override",
-                        pkg.requested_version.as_deref().unwrap_or("?")
+                        pkg.pin().as_deref().unwrap_or("?")
                     ),
                 },
                 package: pkg.clone(),
@@ -599,28 +611,33 @@ pub fn upgrade(
 
         let cas = ensure_cached(cache_root, &pkg.repo)?;
 
-        // When --force is used on pinned deps, resolve from the configured ref
-        // (branch/tag name) instead of the previously resolved commit hash,
-        // so we can advance past immutable pinned refs.
-        let resolve_ref = if force {
+        // When --force is used on pinned deps, re-resolve the pinned ref so
+        // branch pins advance to the branch head.
+        let resolve_ref = if constrained {
             pkg.requested_version.as_deref()
+        } else if force {
+            pkg.pinned_ref()
         } else {
             pkg.resolved_ref.as_deref()
         };
-        let new_commit = resolve_commit(&cas, resolve_ref)?;
+        let (new_commit, resolved_ref) =
+            resolve_spec(&cas, &pkg.name, resolve_ref, &lockfile, store_root)?;
 
         if new_commit != pkg.commit {
             let old_commit = pkg.commit.clone();
             let old_version = pkg.version.clone();
 
             let dest = package_dir(store_root, &pkg.name, &new_commit)?;
-            if !is_checkout_valid(&dest) {
-                checkout_to(&cas, &new_commit, &dest)?;
-            }
+            let tree = member_tree(&new_commit, pkg.member.as_deref());
+            let checksum = ensure_checkout(&cas, &tree, &dest, None)?;
 
             let version = read_version(&dest);
             lockfile.package[idx].commit = new_commit;
             lockfile.package[idx].version = version;
+            lockfile.package[idx].checksum = Some(checksum);
+            if constrained {
+                lockfile.package[idx].resolved_ref = resolved_ref;
+            }
 
             results.push(UpgradeEntry {
                 name: lockfile.package[idx].name.clone(),
@@ -653,11 +670,47 @@ pub use package_migrate::{LegacyDir, MigrateResult, migrate};
 
 #[path = "package_audit.rs"]
 mod package_audit;
-pub use package_audit::{AuditIssue, AuditResult, audit};
+pub use package_audit::{AuditIssue, AuditResult, audit, audit_package};
+
+#[path = "package_drift.rs"]
+mod package_drift;
+pub use package_drift::{
+    DriftIssue, LinkDrift, LinkDriftIssue, PackageDrift, checkout_drift, link_drift, package_drift,
+};
 
 #[path = "package_gc.rs"]
 mod package_gc;
-pub use package_gc::{GcResult, gc};
+pub use package_gc::{GcResult, gc, gc_cache, remove};
+
+#[path = "package_diff.rs"]
+mod package_diff;
+pub use package_diff::{DiffCommit, FileChange, PackageDiff, diff};
+
+#[path = "package_version.rs"]
+mod package_version;
+use package_version::resolve_spec;
+pub use package_version::{VersionConflict, is_version_req, version_conflicts, version_satisfies};
+
+#[path = "package_graph.rs"]
+mod package_graph;
+pub use package_graph::{DependencyEdge, DependencyGraph, DuplicatePackage, dependency_graph};
+
+#[path = "package_integrity.rs"]
+mod package_integrity;
+use package_integrity::ensure_checkout;
+pub use package_integrity::{
+    CHECKSUM_PREFIX, ChecksumMismatch, checkout_checksum, verify_checkout,
+};
+
+#[path = "package_workspace.rs"]
+mod package_workspace;
+use package_workspace::{check_member, member_tree};
+
+#[path = "package_index.rs"]
+mod package_index;
+pub use package_index::{
+    INDEX_FILE, IndexEntry, PackageIndex, fetch_index, is_index_name, resolve_index_source,
+};
 
 /// Load a lockfile from disk.
 pub fn load_lockfile(path: &Path) -> Result<Lockfile> {
@@ -708,3 +761,35 @@ mod audit_tests;
 #[cfg(test)]
 #[path = "package_tests_migrate.rs"]
 mod migrate_tests;
+
+#[cfg(test)]
+#[path = "package_tests_drift.rs"]
+mod drift_tests;
+
+#[cfg(test)]
+#[path = "package_tests_index.rs"]
+mod index_tests;
+
+#[cfg(test)]
+#[path = "package_tests_version.rs"]
+mod version_tests;
+
+#[cfg(test)]
+#[path = "package_tests_graph.rs"]
+mod graph_tests;
+
+#[cfg(test)]
+#[path = "package_tests_integrity.rs"]
+mod integrity_tests;
+
+#[cfg(test)]
+#[path = "package_tests_workspace.rs"]
+mod workspace_tests;
+
+#[cfg(test)]
+#[path = "package_tests_auth.rs"]
+mod auth_tests;
+
+#[cfg(test)]
+#[path = "package_tests_cache.rs"]
+mod cache_tests;
@@ -6,7 +6,10 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
-use super::{SourceKind, detect_skill_md_case_mismatch, load_project_lockfile, package_dir};
+use super::{
+    ChecksumMismatch, SourceKind, VersionConflict, detect_skill_md_case_mismatch,
+    load_project_lockfile, package_dir, verify_checkout, version_conflicts,
+};
 
 /// Audit result for a single package.
 #[derive(Debug)]
@@ -44,6 +47,10 @@ pub enum AuditIssue {
         /// Pattern name.
         pattern: String,
     },
+    /// A `[dependencies]` requirement is not met by the locked versions.
+    VersionConflict(VersionConflict),
+    /// The store checkout does not match the checksum in `weave.lock`.
+    ChecksumMismatch(ChecksumMismatch),
 }
 
 impl std::fmt::Display for AuditIssue {
@@ -74,6 +81,8 @@ impl std::fmt::Display for AuditIssue {
                      patterns/{pattern}/skills/{pattern}/SKILL.md"
                 )
             }
+            Self::VersionConflict(conflict) => write!(f, "{conflict}"),
+            Self::ChecksumMismatch(mismatch) => write!(f, "{mismatch}"),
         }
     }
 }
@@ -83,6 +92,7 @@ impl std::fmt::Display for AuditIssue {
 /// Checks packages in the lockfile against the global store at `store_root`.
 pub fn audit(project_root: &Path, store_root: &Path) -> Result<Vec<AuditResult>> {
     let lockfile = load_project_lockfile(project_root).unwrap_or_default();
+    let conflicts = version_conflicts(project_root, store_root)?;
 
     let mut results = Vec::new();
 
@@ -110,25 +120,29 @@ pub fn audit(project_root: &Path, store_root: &Path) -> Result<Vec<AuditResult>>
 
             if !dep_path.is_dir() {
                 issues.push(AuditIssue::MissingFromDeps);
-            } else if !dep_path.join("SKILL.md").is_file() {
-                // Distinguish case-mismatch from truly missing.
-                if let Some(found) = detect_skill_md_case_mismatch(&dep_path) {
-                    issues.push(AuditIssue::CaseMismatchSkillMd { found });
-                } else {
-                    issues.push(AuditIssue::MissingSkillMd);
-                }
             }
 
             if pkg.repo.is_empty() && pkg.source_kind != SourceKind::Local {
                 issues.push(AuditIssue::UnknownRepo);
             }
 
-            // Check for companion skills in patterns.
             if dep_path.is_dir() {
-                check_companion_skills(&dep_path, &mut issues);
+                issues.extend(audit_package(&dep_path));
+            }
+
+            if let Some(mismatch) = verify_checkout(store_root, pkg) {
+                issues.push(AuditIssue::ChecksumMismatch(mismatch));
             }
         }
 
+        issues.extend(
+            conflicts
+                .iter()
+                .filter(|conflict| conflict.dependent == pkg.name)
+                .cloned()
+                .map(AuditIssue::VersionConflict),
+        );
+
         if !issues.is_empty() {
             results.push(AuditResult {
                 name: pkg.name.clone(),
@@ -140,6 +154,22 @@ pub fn audit(project_root: &Path, store_root: &Path) -> Result<Vec<AuditResult>>
     Ok(results)
 }
 
+/// Audit the package checked out at `dir`: a `SKILL.md` entry point and a
+/// companion skill for every pattern.
+pub fn audit_package(dir: &Path) -> Vec<AuditIssue> {
+    let mut issues = Vec::new();
+    if !dir.join("SKILL.md").is_file() {
+        // Distinguish case-mismatch from truly missing.
+        if let Some(found) = detect_skill_md_case_mismatch(dir) {
+            issues.push(AuditIssue::CaseMismatchSkillMd { found });
+        } else {
+            issues.push(AuditIssue::MissingSkillMd);
+        }
+    }
+    check_companion_skills(dir, &mut issues);
+    issues
+}
+
 /// Check that each pattern in a package has a companion skill.
 ///
 /// A companion skill is at `patterns/<name>/skills/<name>/SKILL.md` and serves
@@ -0,0 +1,292 @@
+//! Credentials and failure classification for package git operations.
+//!
+//! SSH sources authenticate through the user's SSH agent; ssh runs in batch
+//! mode so a missing key fails instead of prompting. HTTPS sources get an
+//! access token from the OS keyring (service `weave`, user = host) or from
+//! `[weave.tokens]` in the global config. The token reaches git through
+//! `GIT_CONFIG_*` environment variables, so it never appears on the command
+//! line or in the cached repository's config.
+
+use std::fmt;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use data_encoding::BASE64;
+
+use super::package_source::is_ssh_url;
+
+const KEYRING_SERVICE: &str = "weave";
+
+/// Broad cause of a failed clone or fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitFailureKind {
+    /// Credentials were missing or rejected.
+    Auth,
+    /// The repository does not exist (or is hidden from these credentials).
+    NotFound,
+    /// The remote could not be reached.
+    Network,
+    Other,
+}
+
+impl fmt::Display for GitFailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GitFailureKind::Auth => "authentication failed",
+            GitFailureKind::NotFound => "repository not found",
+            GitFailureKind::Network => "network error",
+            GitFailureKind::Other => "git error",
+        })
+    }
+}
+
+/// A failed `git clone` or `git fetch` of a package repository.
+#[derive(Debug)]
+pub struct GitFailure {
+    pub kind: GitFailureKind,
+    /// `"clone"` or `"fetch"`.
+    pub operation: &'static str,
+    pub url: String,
+    /// What git printed on stderr.
+    pub stderr: String,
+    /// Whether an access token was sent with the request.
+    pub token_sent: bool,
+}
+
+impl GitFailure {
+    fn hint(&self) -> Option<String> {
+        let ssh = is_ssh_url(&self.url);
+        let host = https_host(&self.url);
+        match self.kind {
+            GitFailureKind::Auth if ssh => Some(
+                "load a key with access into your SSH agent (`ssh-add -l` lists loaded keys) \
+                 and make sure the host is in ~/.ssh/known_hosts"
+                    .to_string(),
+            ),
+            GitFailureKind::Auth => host.map(|host| {
+                if self.token_sent {
+                    format!(
+                        "the token for {host} was rejected; replace it with `weave auth {host}`"
+                    )
+                } else {
+                    format!(
+                        "store an access token with `weave auth {host}` or under \
+                         [weave.tokens] in the global config"
+                    )
+                }
+            }),
+            GitFailureKind::NotFound if ssh => Some(
+                "check the repository URL; private repositories also report this when the \
+                 SSH key has no access"
+                    .to_string(),
+            ),
+            GitFailureKind::NotFound => Some(match host {
+                Some(host) if !self.token_sent => format!(
+                    "check the repository URL; private repositories also report this without \
+                     credentials (`weave auth {host}`)"
+                ),
+                _ => "check the repository URL".to_string(),
+            }),
+            GitFailureKind::Network => {
+                Some("check your network connection and proxy settings".to_string())
+            }
+            GitFailureKind::Other => None,
+        }
+    }
+}
+
+impl fmt::Display for GitFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let url = without_userinfo(&self.url);
+        write!(f, "git {} of {url} failed: {}", self.operation, self.kind)?;
+        let stderr = self.stderr.replace(&self.url, &url);
+        for line in stderr.lines().filter(|line| !line.trim().is_empty()) {
+            write!(f, "\n  {}", line.trim_end())?;
+        }
+        if let Some(hint) = self.hint() {
+            write!(f, "\nhint: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GitFailure {}
+
+/// `url` without a `user:password@` part, which may hold a credential.
+pub(super) fn without_userinfo(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    match rest[..authority_end].rsplit_once('@') {
+        Some((_, host)) => format!("{scheme}://{host}{}", &rest[authority_end..]),
+        None => url.to_string(),
+    }
+}
+
+/// Classify a clone/fetch failure from git's stderr.
+///
+/// Patterns follow the messages git and its transports print, not loose
+/// words, so e.g. a missing branch is not reported as a missing repository.
+pub(super) fn classify_git_failure(stderr: &str) -> GitFailureKind {
+    const AUTH: &[&str] = &[
+        "fatal: authentication failed for '",
+        "could not read username for '",
+        "could not read password for '",
+        "terminal prompts disabled",
+        "permission denied (publickey",
+        "permission denied, please try again",
+        "invalid username or password",
+        "host key verification failed",
+        "the requested url returned error: 401",
+        "the requested url returned error: 403",
+    ];
+    const NOT_FOUND: &[&str] = &[
+        "remote: repository not found",
+        "error: repository not found",
+        "does not appear to be a git repository",
+        "the requested url returned error: 404",
+    ];
+    const NETWORK: &[&str] = &[
+        "could not resolve host: ",
+        "could not resolve hostname ",
+        "connection refused",
+        "connection timed out",
+        "operation timed out",
+        "connection reset by peer",
+        "network is unreachable",
+        "failed to connect to ",
+        "ssl certificate problem",
+        "gnutls_handshake() failed",
+        "gnutls recv error",
+        "openssl ssl_connect",
+        "ssl_error_syscall",
+        "tls connection was non-properly terminated",
+        "early eof",
+        "unexpected disconnect while reading sideband packet",
+    ];
+
+    let stderr = stderr.to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|p| stderr.contains(p));
+    if matches(AUTH) {
+        GitFailureKind::Auth
+    } else if matches(NOT_FOUND) || names_missing_repository(&stderr) {
+        GitFailureKind::NotFound
+    } else if matches(NETWORK) {
+        GitFailureKind::Network
+    } else {
+        GitFailureKind::Other
+    }
+}
+
+/// Whether a line reads `repository '<url>' not found` or
+/// `repository '<path>' does not exist`.
+fn names_missing_repository(stderr: &str) -> bool {
+    stderr.lines().any(|line| {
+        line.split_once("repository '").is_some_and(|(_, rest)| {
+            rest.rsplit_once('\'')
+                .is_some_and(|(_, tail)| matches!(tail.trim(), "not found" | "does not exist"))
+        })
+    })
+}
+
+/// A `git` command for a clone or fetch of `url`: it never prompts, and
+/// carries an access token for HTTPS hosts that have one. Returns whether a
+/// token was attached.
+pub(super) fn git_remote_command(url: &str) -> (Command, bool) {
+    let mut command = Command::new("git");
+    command.env("GIT_TERMINAL_PROMPT", "0").stdin(Stdio::null());
+    if is_ssh_url(url) {
+        // Respect an explicit ssh override; otherwise never prompt for
+        // passphrases or host keys.
+        if std::env::var_os("GIT_SSH_COMMAND").is_none() && std::env::var_os("GIT_SSH").is_none() {
+            command.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+        }
+        return (command, false);
+    }
+    let Some(host) = https_host(url) else {
+        return (command, false);
+    };
+    let Some(token) = lookup_token(host) else {
+        return (command, false);
+    };
+    let existing = std::env::var("GIT_CONFIG_COUNT").ok();
+    command.envs(token_config_env(existing.as_deref(), host, &token));
+    (command, true)
+}
+
+/// `GIT_CONFIG_*` variables adding an `Authorization` header for `host`,
+/// appended after any config entries already passed through the environment.
+pub(super) fn token_config_env(
+    existing_count: Option<&str>,
+    host: &str,
+    token: &str,
+) -> Vec<(String, String)> {
+    let index: usize = existing_count.and_then(|n| n.parse().ok()).unwrap_or(0);
+    let credentials = BASE64.encode(format!("x-access-token:{token}").as_bytes());
+    vec![
+        ("GIT_CONFIG_COUNT".to_string(), (index + 1).to_string()),
+        (
+            format!("GIT_CONFIG_KEY_{index}"),
+            format!("http.https://{host}/.extraHeader"),
+        ),
+        (
+            format!("GIT_CONFIG_VALUE_{index}"),
+            format!("Authorization: Basic {credentials}"),
+        ),
+    ]
+}
+
+/// Host (with port, if any) of an `https://` URL. Tokens are never sent over
+/// plain HTTP.
+pub(super) fn https_host(url: &str) -> Option<&str> {
+    let authority = url.strip_prefix("https://")?.split('/').next()?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    (!host.is_empty()).then_some(host)
+}
+
+/// Token for `host` from the OS keyring, falling back to `[weave.tokens]`.
+fn lookup_token(host: &str) -> Option<String> {
+    match keyring_token(host) {
+        Ok(Some(token)) => return Some(token),
+        Ok(None) => {}
+        Err(err) => tracing::debug!(error = %err, host, "OS keyring unavailable"),
+    }
+    match csa_config::GlobalConfig::load() {
+        Ok(config) => config.weave.tokens.get(host).cloned(),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load global config; no token for {host}");
+            None
+        }
+    }
+}
+
+fn keyring_entry(host: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, host).context("failed to open OS keyring entry")
+}
+
+fn keyring_token(host: &str) -> Result<Option<String>> {
+    match keyring_entry(host)?.get_password() {
+        Ok(token) => Ok(Some(token.trim().to_string()).filter(|token| !token.is_empty())),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(err).context("failed to read token from OS keyring"),
+    }
+}
+
+/// Store an HTTPS access token for `host` in the OS keyring.
+pub fn store_token(host: &str, token: &str) -> Result<()> {
+    keyring_entry(host)?
+        .set_password(token)
+        .with_context(|| format!("failed to store token for {host} in OS keyring"))
+}
+
+/// Remove the keyring token for `host`. Returns false if there was none.
+pub fn remove_token(host: &str) -> Result<bool> {
+    match keyring_entry(host)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(err) => Err(err).with_context(|| format!("failed to remove token for {host}")),
+    }
+}
@@ -0,0 +1,335 @@
+//! Shallow source caches backed by a shared object pool.
+//!
+//! Each git source gets its own bare repository under
+//! `<cache>/git/<safe-url>/`, fetched with `--depth 1` (branch and tag tips
+//! only). After each fetch its objects move into one pool,
+//! `<cache>/git/pool.git`, which every source borrows from through
+//! `objects/info/alternates`, so forks and mirrors of the same history are
+//! stored once. The pool has no refs of its own: sources disagree on where
+//! their shallow history ends, so only a source can tell which objects it
+//! reaches. `weave gc` therefore keeps exactly the pool objects some cached
+//! source reaches and drops the rest.
+//!
+//! A commit that is not a branch or tag tip is fetched on demand: by hash
+//! when the full hash is known, otherwise by unshallowing the cache.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result, bail};
+
+use super::package_auth::git_remote_command;
+use super::package_git::git_failure;
+
+/// Directory name of the object pool inside `<cache>/git/`.
+pub(super) const POOL_DIR: &str = "pool.git";
+
+/// Everything a source cache tracks: all branches and tags.
+const SOURCE_REFSPECS: [&str; 2] = ["+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"];
+
+/// Pool path as seen from `<cas>/objects`.
+const POOL_ALTERNATE: &str = "../../pool.git/objects";
+
+/// Pool packs younger than this survive `weave gc`, so a concurrent install
+/// cannot lose objects it has not yet referenced.
+const POOL_PRUNE_GRACE: Duration = Duration::from_secs(60 * 60);
+
+fn git(dir: &Path) -> Command {
+    let mut command = Command::new("git");
+    command.current_dir(dir);
+    command
+}
+
+/// Run a local git command, failing with its stderr.
+fn run(command: &mut Command, what: &str) -> Result<Output> {
+    let output = command
+        .output()
+        .with_context(|| format!("failed to run {what}"))?;
+    if !output.status.success() {
+        bail!(
+            "{what} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output)
+}
+
+fn pool_for(cas: &Path) -> Result<PathBuf> {
+    Ok(cas
+        .parent()
+        .context("cache repository has no parent directory")?
+        .join(POOL_DIR))
+}
+
+/// Whether the cache at `cas` holds truncated history.
+pub(super) fn is_shallow(cas: &Path) -> bool {
+    cas.join("shallow").is_file()
+}
+
+/// Create the cache for `url` at `cas` (an empty directory) and fetch the
+/// tips of its branches and tags.
+pub(super) fn clone_shallow(cas: &Path, url: &str) -> Result<()> {
+    ensure_pool(&pool_for(cas)?)?;
+    run(git(cas).args(["init", "--bare", "--quiet"]), "git init")?;
+    link_pool(cas)?;
+    run(
+        git(cas).args(["config", "remote.origin.url", url]),
+        "git config",
+    )?;
+    if let Some(head) = remote_head(cas, url)? {
+        run(
+            git(cas).args(["symbolic-ref", "HEAD", &head]),
+            "git symbolic-ref",
+        )?;
+    }
+    fetch(cas, url, "clone", &["--depth", "1"], &SOURCE_REFSPECS)?;
+    share_objects_or_warn(cas);
+    Ok(())
+}
+
+/// Fetch new branch and tag tips into an existing cache.
+pub(super) fn update(cas: &Path, url: &str) -> Result<()> {
+    let depth: &[&str] = if is_shallow(cas) {
+        &["--depth", "1"]
+    } else {
+        &[]
+    };
+    fetch(cas, url, "fetch", depth, &SOURCE_REFSPECS)?;
+    share_objects_or_warn(cas);
+    Ok(())
+}
+
+/// Fetch what a shallow cache lacks to resolve `rev`: just that commit when
+/// `rev` is a full hash the remote will serve, else the complete history.
+pub(super) fn deepen_for(cas: &Path, rev: &str) -> Result<()> {
+    if rev.len() == 40 && rev.chars().all(|c| c.is_ascii_hexdigit()) {
+        let refspec = format!("{rev}:refs/weave/commits/{rev}");
+        let url = origin_url(cas)?;
+        if fetch(cas, &url, "fetch", &["--depth", "1"], &[&refspec]).is_ok() {
+            share_objects_or_warn(cas);
+            return Ok(());
+        }
+    }
+    unshallow(cas)
+}
+
+/// Fetch the complete history of a shallow cache, e.g. to list the commits
+/// between two revisions.
+pub(super) fn unshallow(cas: &Path) -> Result<()> {
+    if !is_shallow(cas) {
+        return Ok(());
+    }
+    fetch(
+        cas,
+        &origin_url(cas)?,
+        "fetch",
+        &["--unshallow"],
+        &SOURCE_REFSPECS,
+    )?;
+    share_objects_or_warn(cas);
+    Ok(())
+}
+
+fn fetch(
+    cas: &Path,
+    url: &str,
+    operation: &'static str,
+    args: &[&str],
+    refspecs: &[&str],
+) -> Result<()> {
+    let (mut command, token_sent) = git_remote_command(url);
+    let output = command
+        .args(["fetch", "--quiet"])
+        .args(args)
+        .arg("origin")
+        .args(refspecs)
+        .current_dir(cas)
+        .output()
+        .context("failed to run git fetch")?;
+    if !output.status.success() {
+        return Err(git_failure(operation, url, &output.stderr, token_sent).into());
+    }
+    Ok(())
+}
+
+/// The branch the remote's HEAD points at, e.g. `refs/heads/main`.
+fn remote_head(cas: &Path, url: &str) -> Result<Option<String>> {
+    let (mut command, token_sent) = git_remote_command(url);
+    let output = command
+        .args(["ls-remote", "--symref", "origin", "HEAD"])
+        .current_dir(cas)
+        .output()
+        .context("failed to run git ls-remote")?;
+    if !output.status.success() {
+        return Err(git_failure("clone", url, &output.stderr, token_sent).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("ref: ")?.strip_suffix("\tHEAD"))
+        .map(str::to_string))
+}
+
+fn origin_url(cas: &Path) -> Result<String> {
+    let output = run(
+        git(cas).args(["config", "--get", "remote.origin.url"]),
+        "git config",
+    )?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn ensure_pool(pool: &Path) -> Result<()> {
+    if pool.join("HEAD").is_file() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(pool)
+        .with_context(|| format!("failed to create {}", pool.display()))?;
+    run(git(pool).args(["init", "--bare", "--quiet"]), "git init")?;
+    Ok(())
+}
+
+/// Register the pool as an alternate object store of `cas`. Idempotent, so
+/// caches created before the pool existed join it on their next fetch.
+fn link_pool(cas: &Path) -> Result<()> {
+    let path = cas.join("objects").join("info").join("alternates");
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    if existing.lines().any(|line| line == POOL_ALTERNATE) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(path.parent().context("alternates path has no parent")?)?;
+    std::fs::write(&path, format!("{existing}{POOL_ALTERNATE}\n"))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Move the objects `cas` holds itself into the pool.
+pub(super) fn share_objects(cas: &Path) -> Result<()> {
+    let pool = pool_for(cas)?;
+    ensure_pool(&pool)?;
+    link_pool(cas)?;
+    let stats = run(git(cas).args(["count-objects", "-v"]), "git count-objects")?;
+    let own_objects = String::from_utf8_lossy(&stats.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(": ")?;
+            matches!(key, "count" | "in-pack").then(|| value.parse::<u64>().unwrap_or(0))
+        })
+        .sum::<u64>();
+    if own_objects == 0 {
+        return Ok(());
+    }
+    run(
+        git(cas)
+            .args(["pack-objects", "--all", "--local", "--quiet"])
+            .arg(pool.join("objects").join("pack").join("pack"))
+            .stdin(Stdio::null()),
+        "git pack-objects into the object pool",
+    )?;
+    run(
+        git(cas).args(["repack", "-a", "-d", "-l", "--quiet"]),
+        "git repack",
+    )?;
+    Ok(())
+}
+
+/// Sharing only saves space; a cache that could not be shared keeps its own
+/// objects and stays usable.
+fn share_objects_or_warn(cas: &Path) {
+    if let Err(err) = share_objects(cas) {
+        tracing::warn!(cache = %cas.display(), "failed to share objects with the pool: {err:#}");
+    }
+}
+
+/// Rewrite the pool to hold only objects reachable from `caches`, then
+/// delete its older packs.
+pub(super) fn prune_pool(git_dir: &Path, caches: &[PathBuf]) -> Result<()> {
+    let pool = git_dir.join(POOL_DIR);
+    if !pool.join("HEAD").is_file() {
+        return Ok(());
+    }
+    let mut reachable = String::new();
+    for cas in caches {
+        let output = run(
+            git(cas).args(["rev-list", "--objects", "--all"]),
+            "git rev-list",
+        )
+        .with_context(|| format!("refusing to prune the object pool: {}", cas.display()))?;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            reachable.extend(line.split(' ').next());
+            reachable.push('\n');
+        }
+    }
+    // The list also names objects the caches hold themselves; keep only the
+    // ones the pool has.
+    let pooled: String = String::from_utf8_lossy(&pipe(
+        git(&pool).args(["cat-file", "--batch-check=%(objectname) %(objecttype)"]),
+        &reachable,
+        "git cat-file",
+    )?)
+    .lines()
+    .filter(|line| !line.ends_with(" missing"))
+    .filter_map(|line| Some(format!("{}\n", line.split_once(' ')?.0)))
+    .collect();
+
+    let pack_dir = pool.join("objects").join("pack");
+    let kept = if pooled.is_empty() {
+        None
+    } else {
+        let output = pipe(
+            git(&pool)
+                .args(["pack-objects", "--quiet"])
+                .arg(pack_dir.join("pack")),
+            &pooled,
+            "git pack-objects",
+        )?;
+        Some(format!("pack-{}", String::from_utf8_lossy(&output).trim()))
+    };
+
+    let cutoff = SystemTime::now() - POOL_PRUNE_GRACE;
+    for entry in std::fs::read_dir(&pack_dir)
+        .with_context(|| format!("failed to read {}", pack_dir.display()))?
+    {
+        let path = entry?.path();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let old = path
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| modified < cutoff);
+        if old && stem.starts_with("pack-") && Some(stem.as_ref()) != kept.as_deref() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("failed to remove {}", path.display()))?;
+        }
+    }
+    run(
+        git(&pool).args(["prune-packed", "--quiet"]),
+        "git prune-packed",
+    )?;
+    Ok(())
+}
+
+/// Run `command` with `input` on stdin, returning its stdout.
+fn pipe(command: &mut Command, input: &str, what: &str) -> Result<Vec<u8>> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {what}"))?;
+    let mut stdin = child.stdin.take().context("no stdin")?;
+    let input = input.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("failed to run {what}"))?;
+    writer
+        .join()
+        .map_err(|_| anyhow::anyhow!("{what}: stdin writer panicked"))?
+        .with_context(|| format!("failed to write to {what}"))?;
+    if !output.status.success() {
+        bail!(
+            "{what} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
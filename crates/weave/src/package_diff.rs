@@ -0,0 +1,264 @@
+//! `weave diff`: what upgrading an installed package would change.
+//!
+//! The locked commit is compared with the commit `weave upgrade` would move
+//! to: the newest tag satisfying a version requirement, the head of a pinned
+//! branch, or the upstream default branch.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use super::package_cache::unshallow;
+use super::package_git::{ensure_cached, resolve_commit};
+use super::package_version::resolve_spec;
+use super::{SourceKind, is_version_req, load_project_lockfile};
+
+/// Changes between a locked checkout and its upstream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageDiff {
+    pub name: String,
+    /// Locked commit.
+    pub from: String,
+    pub from_version: Option<String>,
+    /// Upstream commit `weave upgrade` would lock.
+    pub to: String,
+    pub to_version: Option<String>,
+    /// Pin of the package, when `weave upgrade` needs `--force` to apply the
+    /// changes.
+    pub pin: Option<String>,
+    /// Upstream commits not in the locked checkout, newest first.
+    pub commits: Vec<DiffCommit>,
+    /// Locked commits no longer on the upstream side (history rewritten or
+    /// a downgrade), newest first.
+    pub dropped: Vec<DiffCommit>,
+    pub files: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffCommit {
+    pub commit: String,
+    pub summary: String,
+}
+
+/// A changed file, relative to the package root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Git status letter: `A`dded, `M`odified, `D`eleted, or `T`ype change.
+    pub status: char,
+    pub path: String,
+    /// Added and removed lines; `None` for binary files.
+    pub lines: Option<(u64, u64)>,
+}
+
+/// Compare the locked checkout of `name` with the upstream commit
+/// `weave upgrade` would move it to.
+pub fn diff(
+    name: &str,
+    project_root: &Path,
+    cache_root: &Path,
+    store_root: &Path,
+) -> Result<PackageDiff> {
+    let lockfile = load_project_lockfile(project_root)
+        .context("no lockfile found — run `weave install` first")?;
+    let pkg = lockfile
+        .package
+        .iter()
+        .find(|p| p.name == name)
+        .with_context(|| format!("package '{name}' not found in lockfile"))?;
+    if pkg.source_kind == SourceKind::Local {
+        bail!("{name} is installed from a local path; there is no upstream to compare with");
+    }
+    if pkg.repo.is_empty() || pkg.commit.is_empty() {
+        bail!("{name} has no locked git commit");
+    }
+
+    let cas = ensure_cached(cache_root, &pkg.repo)?;
+    let constrained = pkg.requested_version.as_deref().is_some_and(is_version_req);
+    let spec = if constrained {
+        pkg.requested_version.as_deref()
+    } else {
+        pkg.pinned_ref()
+    };
+    let (to, _) = resolve_spec(&cas, name, spec, &lockfile, store_root)?;
+    let from = resolve_commit(&cas, Some(&pkg.commit))?;
+
+    let member = pkg.member.as_deref();
+    let (commits, dropped, files) = if from == to {
+        Default::default()
+    } else {
+        // Commit lists need the history between the two revisions.
+        unshallow(&cas)?;
+        (
+            log(&cas, &to, &from, member)?,
+            log(&cas, &from, &to, member)?,
+            changed_files(&cas, &from, &to, member)?,
+        )
+    };
+
+    Ok(PackageDiff {
+        name: name.to_string(),
+        from_version: pkg.version.clone(),
+        to_version: version_at(&cas, &to, member),
+        pin: pkg.pin(),
+        from,
+        to,
+        commits,
+        dropped,
+        files,
+    })
+}
+
+fn git(cas: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cas)
+        .output()
+        .with_context(|| format!("failed to run git {}", args[0]))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Commits reachable from `tip` but not from `base`, touching `member`.
+fn log(cas: &Path, tip: &str, base: &str, member: Option<&str>) -> Result<Vec<DiffCommit>> {
+    let range = format!("{base}..{tip}");
+    let mut args = vec!["log", "--format=%H%x09%s", &range, "--"];
+    args.extend(member);
+    Ok(String::from_utf8_lossy(&git(cas, &args)?)
+        .lines()
+        .filter_map(|line| {
+            let (commit, summary) = line.split_once('\t')?;
+            Some(DiffCommit {
+                commit: commit.to_string(),
+                summary: summary.to_string(),
+            })
+        })
+        .collect())
+}
+
+fn changed_files(
+    cas: &Path,
+    from: &str,
+    to: &str,
+    member: Option<&str>,
+) -> Result<Vec<FileChange>> {
+    let relative = member.map(|member| format!("--relative={member}"));
+    let diff = |format: &str| -> Result<Vec<u8>> {
+        let mut args = vec!["diff", "--no-renames", "-z", format];
+        args.extend(relative.as_deref());
+        args.extend([from, to]);
+        git(cas, &args)
+    };
+
+    // --name-status -z: `<status>\0<path>\0`
+    let name_status = diff("--name-status")?;
+    let mut fields = name_status.split(|byte| *byte == 0);
+    let mut files = Vec::new();
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        let Some(&status) = status.first() else { break };
+        files.push(FileChange {
+            status: char::from(status),
+            path: String::from_utf8_lossy(path).into_owned(),
+            lines: None,
+        });
+    }
+
+    // --numstat -z: `<added>\t<removed>\t<path>\0`, `-` counts for binaries
+    for record in diff("--numstat")?.split(|byte| *byte == 0) {
+        let record = String::from_utf8_lossy(record);
+        let mut parts = record.splitn(3, '\t');
+        let (Some(added), Some(removed), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if let (Some(file), Ok(added), Ok(removed)) = (
+            files.iter_mut().find(|file| file.path == path),
+            added.parse(),
+            removed.parse(),
+        ) {
+            file.lines = Some((added, removed));
+        }
+    }
+    Ok(files)
+}
+
+/// `version` from the package's `.skill.toml` at `commit`.
+fn version_at(cas: &Path, commit: &str, member: Option<&str>) -> Option<String> {
+    let path = match member {
+        Some(member) => format!("{commit}:{member}/.skill.toml"),
+        None => format!("{commit}:.skill.toml"),
+    };
+    let content = git(cas, &["show", &path]).ok()?;
+    toml::from_str::<crate::parser::SkillConfig>(&String::from_utf8_lossy(&content))
+        .ok()?
+        .skill
+        .version
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+fn label(version: Option<&str>, commit: &str) -> String {
+    match version {
+        Some(version) => format!("{version} ({})", short(commit)),
+        None => format!("({})", short(commit)),
+    }
+}
+
+impl PackageDiff {
+    pub fn is_empty(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// Human-readable summary: header, commits, then changed files.
+    pub fn render(&self) -> String {
+        let from = label(self.from_version.as_deref(), &self.from);
+        if self.is_empty() {
+            return format!("{} {from} is up to date\n", self.name);
+        }
+        let mut out = format!(
+            "{} {from} -> {}\n",
+            self.name,
+            label(self.to_version.as_deref(), &self.to)
+        );
+        if let Some(pin) = &self.pin {
+            let _ = writeln!(
+                out,
+                "pinned to {pin} — `weave upgrade` applies this only with --force"
+            );
+        }
+        for (heading, commits) in [
+            ("new upstream commit(s)", &self.commits),
+            ("locked commit(s) no longer upstream", &self.dropped),
+        ] {
+            if commits.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n{} {heading}:", commits.len());
+            for commit in commits {
+                let _ = writeln!(out, "  {} {}", short(&commit.commit), commit.summary);
+            }
+        }
+        let _ = writeln!(out, "\n{} file(s) changed:", self.files.len());
+        for file in &self.files {
+            let lines = match file.lines {
+                Some((added, removed)) => format!(" (+{added} -{removed})"),
+                None => " (binary)".to_string(),
+            };
+            let _ = writeln!(out, "  {} {}{lines}", file.status, file.path);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+#[path = "package_tests_diff.rs"]
+mod tests;
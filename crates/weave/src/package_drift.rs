@@ -0,0 +1,325 @@
+//! Detect drift of installed packages and skill links from `weave.lock`.
+//!
+//! Checkouts carry no `.git`, so each file is compared against the locked
+//! commit's tree in the CAS cache by git blob hash. Local-source packages have
+//! no commit to compare against and are skipped.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+use super::package_git::cas_dir_for;
+use super::{Lockfile, SourceKind, find_lockfile, load_lockfile, member_tree, package_dir};
+use crate::check::DEFAULT_CHECK_DIRS;
+use crate::path_utils::{normalize_path, resolve_symlink_target};
+
+/// Git mode of symlink tree entries; these are checked for presence only.
+const SYMLINK_MODE: &str = "120000";
+
+/// Drift found in one locked package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDrift {
+    pub name: String,
+    /// Commit recorded in `weave.lock`.
+    pub commit: String,
+    pub issues: Vec<DriftIssue>,
+}
+
+/// A single difference between a checkout and its locked commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftIssue {
+    /// No checkout for the locked commit in the global store.
+    MissingCheckout,
+    /// The locked commit is not in the CAS cache, so files cannot be compared.
+    BaselineUnavailable(String),
+    /// File content differs from the locked commit.
+    Modified(String),
+    /// File from the locked commit is missing from the checkout.
+    Missing(String),
+    /// File in the checkout that the locked commit does not contain.
+    Added(String),
+}
+
+impl std::fmt::Display for DriftIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCheckout => write!(f, "no checkout in the global package store"),
+            Self::BaselineUnavailable(reason) => write!(f, "cannot verify: {reason}"),
+            Self::Modified(path) => write!(f, "modified: {path}"),
+            Self::Missing(path) => write!(f, "missing: {path}"),
+            Self::Added(path) => write!(f, "added: {path}"),
+        }
+    }
+}
+
+/// A skill symlink that does not match `weave.lock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkDrift {
+    pub link: PathBuf,
+    pub issue: LinkDriftIssue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkDriftIssue {
+    /// Points into the store but the target is gone.
+    Broken { target: PathBuf },
+    /// Points at a checkout of a different commit than the locked one.
+    WrongCommit {
+        package: String,
+        linked: String,
+        locked: String,
+    },
+    /// Points at a package that is not in `weave.lock`.
+    Unlocked { package: String },
+}
+
+impl std::fmt::Display for LinkDriftIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Broken { target } => write!(f, "broken link to {}", target.display()),
+            Self::WrongCommit {
+                package,
+                linked,
+                locked,
+            } => write!(
+                f,
+                "links '{package}' at {linked}, but weave.lock pins {locked}"
+            ),
+            Self::Unlocked { package } => write!(f, "links '{package}', which is not locked"),
+        }
+    }
+}
+
+/// Compare every locked git package's checkout with its locked commit.
+///
+/// Only packages with at least one issue are returned.
+pub fn package_drift(
+    project_root: &Path,
+    store_root: &Path,
+    cache_root: &Path,
+) -> Result<Vec<PackageDrift>> {
+    let lockfile = locked_packages(project_root)?;
+    let mut drifted = Vec::new();
+    for pkg in &lockfile.package {
+        if pkg.source_kind == SourceKind::Local || pkg.commit.is_empty() {
+            continue;
+        }
+        let checkout = package_dir(store_root, &pkg.name, &pkg.commit)?;
+        let issues = if checkout.is_dir() {
+            let tree = member_tree(&pkg.commit, pkg.member.as_deref());
+            checkout_drift(&cas_dir_for(cache_root, &pkg.repo), &tree, &checkout)?
+        } else {
+            vec![DriftIssue::MissingCheckout]
+        };
+        if !issues.is_empty() {
+            drifted.push(PackageDrift {
+                name: pkg.name.clone(),
+                commit: pkg.commit.clone(),
+                issues,
+            });
+        }
+    }
+    Ok(drifted)
+}
+
+/// The project's lockfile; a project without one locks nothing. A lockfile
+/// that cannot be read fails the check instead of hiding every package.
+fn locked_packages(project_root: &Path) -> Result<Lockfile> {
+    match find_lockfile(project_root) {
+        Some(path) => load_lockfile(&path),
+        None => Ok(Lockfile::default()),
+    }
+}
+
+/// Diff `checkout` against the tree of `commit` in the bare repo `cas_dir`.
+pub fn checkout_drift(cas_dir: &Path, commit: &str, checkout: &Path) -> Result<Vec<DriftIssue>> {
+    let expected = match locked_tree(cas_dir, commit) {
+        Ok(expected) => expected,
+        Err(err) => return Ok(vec![DriftIssue::BaselineUnavailable(format!("{err:#}"))]),
+    };
+    let mut files = Vec::new();
+    let mut symlinks = Vec::new();
+    collect_files(checkout, checkout, &mut files, &mut symlinks)?;
+    let mut actual = blob_hashes(checkout, &files)?;
+    // Symlinks are not hashed; an empty hash never matches a regular file.
+    actual.extend(symlinks.into_iter().map(|path| (path, String::new())));
+
+    let mut issues = Vec::new();
+    for (path, (mode, hash)) in &expected {
+        match actual.get(path) {
+            None => issues.push(DriftIssue::Missing(path.clone())),
+            Some(_) if mode == SYMLINK_MODE => {}
+            Some(found) if found != hash => issues.push(DriftIssue::Modified(path.clone())),
+            Some(_) => {}
+        }
+    }
+    issues.extend(
+        actual
+            .keys()
+            .filter(|path| !expected.contains_key(*path))
+            .map(|path| DriftIssue::Added(path.clone())),
+    );
+    Ok(issues)
+}
+
+/// Check skill symlinks under the project's skill directories against the lock.
+pub fn link_drift(project_root: &Path, store_root: &Path) -> Result<Vec<LinkDrift>> {
+    let lockfile = locked_packages(project_root)?;
+    let store = normalize_path(store_root);
+    let mut drifted = Vec::new();
+    for dir_name in DEFAULT_CHECK_DIRS {
+        let Ok(entries) = std::fs::read_dir(project_root.join(dir_name)) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let link = entry.path();
+            let Ok(target) = std::fs::read_link(&link) else {
+                continue;
+            };
+            let resolved = normalize_path(&resolve_symlink_target(
+                link.parent().unwrap_or(Path::new(".")),
+                &target,
+            ));
+            let Ok(relative) = resolved.strip_prefix(&store) else {
+                continue;
+            };
+            let mut components = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy());
+            let (Some(package), Some(linked)) = (components.next(), components.next()) else {
+                continue;
+            };
+            let issue = match lockfile.package.iter().find(|pkg| pkg.name == package) {
+                None => Some(LinkDriftIssue::Unlocked {
+                    package: package.to_string(),
+                }),
+                Some(pkg) => {
+                    let locked = locked_store_key(pkg);
+                    if linked != locked {
+                        Some(LinkDriftIssue::WrongCommit {
+                            package: package.to_string(),
+                            linked: linked.to_string(),
+                            locked: locked.to_string(),
+                        })
+                    } else if !resolved.exists() {
+                        Some(LinkDriftIssue::Broken {
+                            target: resolved.clone(),
+                        })
+                    } else {
+                        None
+                    }
+                }
+            };
+            if let Some(issue) = issue {
+                drifted.push(LinkDrift { link, issue });
+            }
+        }
+    }
+    drifted.sort_by(|a, b| a.link.cmp(&b.link));
+    Ok(drifted)
+}
+
+/// Store directory name for a locked package (see [`package_dir`]).
+fn locked_store_key(pkg: &super::LockedPackage) -> &str {
+    if pkg.source_kind == SourceKind::Local {
+        "local"
+    } else {
+        &pkg.commit[..pkg.commit.len().min(8)]
+    }
+}
+
+/// `path -> (mode, blob hash)` for every file in `commit`.
+fn locked_tree(cas_dir: &Path, commit: &str) -> Result<BTreeMap<String, (String, String)>> {
+    if !cas_dir.join("HEAD").is_file() {
+        bail!("no cached clone at {}", cas_dir.display());
+    }
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "-z", "--full-tree", commit])
+        .current_dir(cas_dir)
+        .output()
+        .context("failed to run git ls-tree")?;
+    if !output.status.success() {
+        bail!("commit {commit} is not in the cached clone");
+    }
+    let mut tree = BTreeMap::new();
+    for record in output.stdout.split(|byte| *byte == 0) {
+        let record = String::from_utf8_lossy(record);
+        // `<mode> <type> <hash>\t<path>`
+        let Some((meta, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let mut fields = meta.split(' ');
+        if let (Some(mode), Some("blob"), Some(hash)) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            tree.insert(path.to_string(), (mode.to_string(), hash.to_string()));
+        }
+    }
+    Ok(tree)
+}
+
+/// Relative, `/`-separated paths of the regular files and symlinks under `dir`.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<String>,
+    symlinks: &mut Vec<String>,
+) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, files, symlinks)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let parts: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            let out = if file_type.is_symlink() {
+                &mut *symlinks
+            } else {
+                &mut *files
+            };
+            out.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// Git blob hashes of `files` (relative to `dir`), as stored without filters.
+fn blob_hashes(dir: &Path, files: &[String]) -> Result<BTreeMap<String, String>> {
+    if files.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let mut child = Command::new("git")
+        .args(["hash-object", "--no-filters", "--stdin-paths"])
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("failed to run git hash-object")?;
+    {
+        let mut stdin = child.stdin.take().context("git hash-object has no stdin")?;
+        for file in files {
+            writeln!(stdin, "{file}").context("failed to write to git hash-object")?;
+        }
+    }
+    let output = child.wait_with_output().context("git hash-object failed")?;
+    if !output.status.success() {
+        bail!(
+            "git hash-object failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let hashes = String::from_utf8_lossy(&output.stdout);
+    Ok(files
+        .iter()
+        .cloned()
+        .zip(hashes.lines().map(str::to_string))
+        .collect())
+}
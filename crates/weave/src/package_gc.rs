@@ -1,13 +1,20 @@
-//! Garbage-collect unreferenced checkouts from the global package store.
+//! Garbage-collect unreferenced checkouts from the global package store and
+//! unreferenced repositories from the git cache, and remove packages from
+//! the lockfile so their checkouts become unreferenced.
 //!
 //! Split from `package.rs` to stay under the monolith-file limit.
 
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
-use super::{SourceKind, find_lockfile, load_lockfile};
+use super::package_cache::{POOL_DIR, prune_pool};
+use super::package_git::cas_dir_for;
+use super::{
+    LockedPackage, SourceKind, find_lockfile, load_lockfile, load_project_lockfile, lockfile_path,
+    save_lockfile,
+};
 
 /// Result of a `weave gc` operation.
 #[derive(Debug, PartialEq)]
@@ -102,14 +109,8 @@ pub fn gc(project_root: &Path, store_root: &Path, dry_run: bool) -> Result<GcRes
 /// never treat a corrupt lockfile as "no references" to avoid deleting
 /// valid checkouts). If no lockfile exists at all, returns an empty set.
 fn build_reference_set(project_root: &Path) -> Result<HashSet<String>> {
-    let lockfile = match find_lockfile(project_root) {
-        Some(path) => load_lockfile(&path)
-            .with_context(|| format!("refusing to gc: corrupt lockfile at {}", path.display()))?,
-        None => return Ok(HashSet::new()),
-    };
-
     let mut refs = HashSet::new();
-    for pkg in &lockfile.package {
+    for pkg in &locked_packages(project_root)? {
         let commit_key = if pkg.source_kind == SourceKind::Local {
             "local".to_string()
         } else if pkg.commit.is_empty() {
@@ -126,6 +127,102 @@ fn build_reference_set(project_root: &Path) -> Result<HashSet<String>> {
     Ok(refs)
 }
 
+/// Packages of the project's lockfile; none without one, an error for a
+/// corrupt one.
+fn locked_packages(project_root: &Path) -> Result<Vec<LockedPackage>> {
+    match find_lockfile(project_root) {
+        Some(path) => Ok(load_lockfile(&path)
+            .with_context(|| format!("refusing to gc: corrupt lockfile at {}", path.display()))?
+            .package),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Garbage-collect the git cache.
+///
+/// Removes cached source repositories that neither the project's lockfile
+/// nor `keep` (e.g. the package index URL) refers to, then prunes objects no
+/// remaining source reaches from the shared object pool. `removed` lists the
+/// cache directory names.
+pub fn gc_cache(
+    project_root: &Path,
+    cache_root: &Path,
+    keep: &[String],
+    dry_run: bool,
+) -> Result<GcResult> {
+    let git_dir = cache_root.join("git");
+    let referenced: HashSet<PathBuf> = locked_packages(project_root)?
+        .iter()
+        .filter(|pkg| pkg.source_kind == SourceKind::Git)
+        .map(|pkg| pkg.repo.as_str())
+        .chain(keep.iter().map(String::as_str))
+        .map(|url| cas_dir_for(cache_root, url))
+        .collect();
+
+    let mut removed = Vec::new();
+    let mut freed_bytes: u64 = 0;
+    if !git_dir.is_dir() {
+        return Ok(GcResult {
+            removed,
+            freed_bytes,
+        });
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(&git_dir)
+        .with_context(|| format!("failed to read git cache at {}", git_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir() && !path.ends_with(POOL_DIR))
+        .collect();
+    entries.sort();
+    let mut kept = Vec::new();
+    for cas in entries {
+        if referenced.contains(&cas) {
+            if cas.join("HEAD").is_file() {
+                kept.push(cas);
+            }
+            continue;
+        }
+        freed_bytes += dir_size(&cas);
+        if !dry_run {
+            std::fs::remove_dir_all(&cas)
+                .with_context(|| format!("failed to remove {}", cas.display()))?;
+        }
+        removed.push(
+            cas.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+        );
+    }
+
+    if !dry_run {
+        let pool = git_dir.join(POOL_DIR);
+        let before = dir_size(&pool);
+        prune_pool(&git_dir, &kept)?;
+        freed_bytes += before.saturating_sub(dir_size(&pool));
+    }
+
+    Ok(GcResult {
+        removed,
+        freed_bytes,
+    })
+}
+
+/// Drop `name` from the project lockfile, returning its entry. The checkout
+/// stays in the global store until `weave gc` finds it unreferenced.
+pub fn remove(project_root: &Path, name: &str) -> Result<LockedPackage> {
+    let mut lockfile = load_project_lockfile(project_root)
+        .context("no lockfile found — run `weave install` first")?;
+    let idx = lockfile
+        .package
+        .iter()
+        .position(|p| p.name == name)
+        .with_context(|| format!("package '{name}' not found in lockfile"))?;
+    let pkg = lockfile.package.remove(idx);
+    save_lockfile(&lockfile_path(project_root), &lockfile)?;
+    Ok(pkg)
+}
+
 /// Recursively compute the total size of a directory in bytes.
 fn dir_size(path: &Path) -> u64 {
     let mut total: u64 = 0;
@@ -182,6 +279,7 @@ mod tests {
             source_kind: SourceKind::Git,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         }
     }
 
@@ -194,9 +292,40 @@ mod tests {
             source_kind: SourceKind::Local,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn test_remove_drops_package_and_leaves_checkout_for_gc() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("project");
+        let store = tmp.path().join("store");
+        std::fs::create_dir_all(&project).unwrap();
+        create_checkout(&store, "alpha", "aabbccdd");
+        write_lockfile(
+            &project,
+            vec![
+                make_pkg("alpha", "aabbccddee112233"),
+                make_local_pkg("beta"),
+            ],
+        );
+
+        let removed = remove(&project, "alpha").unwrap();
+        assert_eq!(removed.commit, "aabbccddee112233");
+        let names: Vec<_> = locked_packages(&project)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, ["beta"]);
+        assert!(store.join("alpha").join("aabbccdd").is_dir());
+        assert!(remove(&project, "alpha").is_err());
+
+        let result = gc(&project, &store, false).unwrap();
+        assert_eq!(result.removed, ["alpha/aabbccdd"]);
+    }
+
     #[test]
     fn test_gc_removes_unreferenced_checkout() {
         let tmp = tempfile::tempdir().unwrap();
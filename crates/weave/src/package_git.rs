@@ -1,6 +1,7 @@
 //! Git operations and filesystem helpers for weave package management.
 //!
-//! Extracted from `package.rs` — contains CAS cache management, git clone/fetch,
+//! Extracted from `package.rs` — contains CAS cache management, git clone/fetch
+//! (see `package_cache.rs` for the shallow caches and their object pool),
 //! commit resolution, checkout, directory copying, and SKILL.md detection.
 
 use std::path::{Path, PathBuf};
@@ -8,6 +9,9 @@ use std::process::Command;
 
 use anyhow::{Context, Result, bail};
 
+use super::package_auth::{GitFailure, classify_git_failure};
+use super::package_cache;
+
 // ---------------------------------------------------------------------------
 // CAS cache
 // ---------------------------------------------------------------------------
@@ -63,62 +67,91 @@ pub fn is_checkout_valid(dir: &Path) -> bool {
 // Git operations
 // ---------------------------------------------------------------------------
 
-/// Clone or fetch a bare repository in the CAS cache. Returns the CAS path.
+/// Clone or fetch a shallow bare repository in the CAS cache. Returns the CAS
+/// path.
+///
+/// Failures are reported as a [`GitFailure`] classified as auth, not-found,
+/// or network.
 pub(super) fn ensure_cached(cache_root: &Path, url: &str) -> Result<PathBuf> {
     let cas = cas_dir_for(cache_root, url);
 
     if cas.join("HEAD").is_file() {
-        // Already cloned — fetch updates.
-        // In a bare repo `git fetch --all` updates FETCH_HEAD but does NOT
-        // advance local branch refs.  Use an explicit refspec so that
-        // `resolve_commit(cas, None)` (which resolves HEAD → main) sees the
-        // latest remote commit.
-        let status = Command::new("git")
-            .args(["fetch", "--quiet", "origin", "+refs/heads/*:refs/heads/*"])
-            .current_dir(&cas)
-            .status()
-            .context("failed to run git fetch")?;
-        if !status.success() {
-            bail!("git fetch failed in {}", cas.display());
-        }
+        // Already cloned — fetch updates. Branch and tag tips are fetched
+        // into refs/heads and refs/tags (not FETCH_HEAD) so that
+        // `resolve_commit(cas, None)` sees the latest remote commit.
+        package_cache::update(&cas, url)?;
     } else {
-        // Fresh bare clone.
         std::fs::create_dir_all(&cas)
             .with_context(|| format!("failed to create {}", cas.display()))?;
-        let status = Command::new("git")
-            .args(["clone", "--bare", "--quiet", url])
-            .arg(&cas)
-            .status()
-            .context("failed to run git clone")?;
-        if !status.success() {
+        if let Err(err) = package_cache::clone_shallow(&cas, url) {
             // Clean up failed clone.
             let _ = std::fs::remove_dir_all(&cas);
-            bail!("git clone failed for {url}");
+            return Err(err);
         }
     }
 
     Ok(cas)
 }
 
-/// Resolve a git ref to a full commit hash.
+pub(super) fn git_failure(
+    operation: &'static str,
+    url: &str,
+    stderr: &[u8],
+    token_sent: bool,
+) -> GitFailure {
+    let stderr = String::from_utf8_lossy(stderr).into_owned();
+    GitFailure {
+        kind: classify_git_failure(&stderr),
+        operation,
+        url: url.to_string(),
+        stderr,
+        token_sent,
+    }
+}
+
+/// Resolve a git ref to a full commit hash, peeling annotated tags.
+///
+/// A shallow cache is deepened once when the ref is not among the fetched
+/// tips.
 pub(super) fn resolve_commit(cas_dir: &Path, git_ref: Option<&str>) -> Result<String> {
     let ref_spec = git_ref.unwrap_or("HEAD");
+    if let Some(commit) = rev_parse_commit(cas_dir, ref_spec)? {
+        return Ok(commit);
+    }
+    if package_cache::is_shallow(cas_dir) {
+        package_cache::deepen_for(cas_dir, ref_spec)?;
+        if let Some(commit) = rev_parse_commit(cas_dir, ref_spec)? {
+            return Ok(commit);
+        }
+    }
+    bail!("git ref '{ref_spec}' does not resolve to a commit in the cached repository");
+}
+
+fn rev_parse_commit(cas_dir: &Path, ref_spec: &str) -> Result<Option<String>> {
     let output = Command::new("git")
-        .args(["rev-parse", ref_spec])
+        .args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{ref_spec}^{{commit}}"),
+        ])
         .current_dir(cas_dir)
         .output()
         .context("failed to run git rev-parse")?;
-    if !output.status.success() {
-        bail!(
-            "git rev-parse {ref_spec} failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        );
-    }
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
 }
 
 /// Checkout a specific commit from a bare repo into a target directory.
 pub(super) fn checkout_to(cas_dir: &Path, commit: &str, dest: &Path) -> Result<()> {
+    // Locked commits may predate the tips a shallow cache holds. `commit`
+    // may be a `<commit>:<member>` tree-ish.
+    resolve_commit(
+        cas_dir,
+        Some(commit.split_once(':').map_or(commit, |(c, _)| c)),
+    )?;
     if dest.exists() {
         std::fs::remove_dir_all(dest)
             .with_context(|| format!("failed to remove existing {}", dest.display()))?;
@@ -162,10 +195,13 @@ pub(super) fn checkout_to(cas_dir: &Path, commit: &str, dest: &Path) -> Result<(
 
 /// Try to read a version from a `.skill.toml` in the checked-out directory.
 pub(super) fn read_version(dep_dir: &Path) -> Option<String> {
-    let config_path = dep_dir.join(".skill.toml");
-    let content = std::fs::read_to_string(&config_path).ok()?;
-    let config: crate::parser::SkillConfig = toml::from_str(&content).ok()?;
-    config.skill.version
+    read_skill_config(dep_dir)?.skill.version
+}
+
+/// Parse the `.skill.toml` manifest of a checkout, if it has a valid one.
+pub(super) fn read_skill_config(dep_dir: &Path) -> Option<crate::parser::SkillConfig> {
+    let content = std::fs::read_to_string(dep_dir.join(".skill.toml")).ok()?;
+    toml::from_str(&content).ok()
 }
 
 // ---------------------------------------------------------------------------
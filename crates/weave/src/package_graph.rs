@@ -0,0 +1,240 @@
+//! Dependency graph of the installed weave packages.
+//!
+//! Nodes are the packages locked in `weave.lock`; edges are the
+//! `[dependencies]` declared in each locked checkout's `.skill.toml`. A
+//! dependency that is declared but not locked is kept as an edge to a missing
+//! node. Locked packages no other package depends on are the project's direct
+//! dependencies.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+use super::package_git::read_skill_config;
+use super::package_version::locked_checkout;
+use super::{LockedPackage, Lockfile, SourceKind, load_project_lockfile};
+
+/// Installed packages and the dependencies they declare.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    /// Locked packages keyed by name.
+    pub packages: BTreeMap<String, LockedPackage>,
+    /// Declared dependencies keyed by dependent package name.
+    pub edges: BTreeMap<String, Vec<DependencyEdge>>,
+}
+
+/// One `[dependencies]` entry of a locked package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyEdge {
+    pub package: String,
+    /// Semver requirement on `package`.
+    pub requirement: String,
+}
+
+/// A git repository locked under more than one package name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicatePackage {
+    pub repo: String,
+    pub names: Vec<String>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from `lockfile`, reading manifests from the store.
+    pub fn from_lockfile(lockfile: &Lockfile, store_root: &Path) -> Self {
+        let mut graph = Self::default();
+        for pkg in &lockfile.package {
+            let edges = locked_checkout(store_root, pkg)
+                .and_then(|dir| read_skill_config(&dir))
+                .map(|config| {
+                    config
+                        .dependencies
+                        .into_iter()
+                        .map(|(package, spec)| DependencyEdge {
+                            package,
+                            requirement: spec.version,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            graph.edges.insert(pkg.name.clone(), edges);
+            graph.packages.insert(pkg.name.clone(), pkg.clone());
+        }
+        graph
+    }
+
+    pub fn dependencies(&self, name: &str) -> &[DependencyEdge] {
+        self.edges.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Packages declaring a dependency on `name`, with their requirement.
+    pub fn dependents(&self, name: &str) -> Vec<(&str, &str)> {
+        self.edges
+            .iter()
+            .flat_map(|(dependent, edges)| {
+                edges
+                    .iter()
+                    .filter(move |edge| edge.package == name)
+                    .map(move |edge| (dependent.as_str(), edge.requirement.as_str()))
+            })
+            .collect()
+    }
+
+    /// Locked packages that no other locked package depends on.
+    pub fn roots(&self) -> Vec<&str> {
+        let required: BTreeSet<&str> = self
+            .edges
+            .values()
+            .flatten()
+            .map(|edge| edge.package.as_str())
+            .collect();
+        self.packages
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !required.contains(name))
+            .collect()
+    }
+
+    /// Git repositories locked under several names, e.g. a fork installed
+    /// next to its upstream at another commit.
+    pub fn duplicates(&self) -> Vec<DuplicatePackage> {
+        let mut by_repo: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for pkg in self.packages.values() {
+            if pkg.source_kind == SourceKind::Git {
+                by_repo.entry(&pkg.repo).or_default().push(pkg.name.clone());
+            }
+        }
+        by_repo
+            .into_iter()
+            .filter(|(_, names)| names.len() > 1)
+            .map(|(repo, names)| DuplicatePackage {
+                repo: repo.to_string(),
+                names,
+            })
+            .collect()
+    }
+
+    /// Every package under the direct dependencies. Subtrees already shown
+    /// are marked `(*)`; packages only reachable through a cycle are listed
+    /// as extra roots.
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        let mut seen = BTreeSet::new();
+        let roots = self.roots();
+        let cyclic = self
+            .packages
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !roots.contains(name));
+        for root in roots.iter().copied().chain(cyclic) {
+            if seen.insert(root) {
+                self.write_tree(&mut out, root, false, &mut seen);
+            }
+        }
+        out
+    }
+
+    /// Inverted tree: the chains of dependents that pull in `name`.
+    pub fn render_why<'a>(&'a self, name: &'a str) -> Result<String> {
+        if !self.packages.contains_key(name) && self.dependents(name).is_empty() {
+            bail!("package '{name}' is not installed or required by any installed package");
+        }
+        let mut out = String::new();
+        self.write_inverted(&mut out, name);
+        Ok(out)
+    }
+
+    /// Duplicated repositories, each name followed by what pulls it in.
+    pub fn render_duplicates(&self) -> String {
+        let mut out = String::new();
+        for duplicate in self.duplicates() {
+            let _ = writeln!(out, "{}", duplicate.repo);
+            for name in &duplicate.names {
+                self.write_inverted(&mut out, name);
+            }
+        }
+        out
+    }
+
+    fn write_inverted<'a>(&'a self, out: &mut String, name: &'a str) {
+        self.write_tree(out, name, true, &mut BTreeSet::new());
+    }
+
+    /// `(package, requirement)` pairs below `name`: its dependencies, or its
+    /// dependents when `inverted`.
+    fn children(&self, name: &str, inverted: bool) -> Vec<(&str, &str)> {
+        if inverted {
+            return self.dependents(name);
+        }
+        self.dependencies(name)
+            .iter()
+            .map(|edge| (edge.package.as_str(), edge.requirement.as_str()))
+            .collect()
+    }
+
+    fn write_tree<'a>(
+        &'a self,
+        out: &mut String,
+        root: &'a str,
+        inverted: bool,
+        seen: &mut BTreeSet<&'a str>,
+    ) {
+        let _ = writeln!(out, "{}", self.label(root));
+        self.write_children(out, root, inverted, seen, &mut vec![root], "");
+    }
+
+    fn write_children<'a>(
+        &'a self,
+        out: &mut String,
+        name: &'a str,
+        inverted: bool,
+        seen: &mut BTreeSet<&'a str>,
+        path: &mut Vec<&'a str>,
+        prefix: &str,
+    ) {
+        let kids = self.children(name, inverted);
+        for (i, (child, requirement)) in kids.iter().enumerate() {
+            let (branch, indent) = if i + 1 == kids.len() {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            let _ = write!(out, "{prefix}{branch}{} [{requirement}]", self.label(child));
+            if path.contains(child) {
+                out.push_str(" (cycle)\n");
+            } else if !seen.insert(child) && !self.children(child, inverted).is_empty() {
+                out.push_str(" (*)\n");
+            } else {
+                out.push('\n');
+                path.push(child);
+                let prefix = format!("{prefix}{indent}");
+                self.write_children(out, child, inverted, seen, path, &prefix);
+                path.pop();
+            }
+        }
+    }
+
+    fn label(&self, name: &str) -> String {
+        let Some(pkg) = self.packages.get(name) else {
+            return format!("{name} (not installed)");
+        };
+        let version = pkg.version.as_deref().unwrap_or("-");
+        match pkg.source_kind {
+            SourceKind::Local => format!("{name} {version} (local)"),
+            SourceKind::Git => {
+                let commit = &pkg.commit[..pkg.commit.len().min(8)];
+                match pkg.pin() {
+                    Some(pin) => format!("{name} {version} ({commit}) [{pin}]"),
+                    None => format!("{name} {version} ({commit})"),
+                }
+            }
+        }
+    }
+}
+
+/// Dependency graph of the packages locked in the project's `weave.lock`.
+pub fn dependency_graph(project_root: &Path, store_root: &Path) -> Result<DependencyGraph> {
+    let lockfile = load_project_lockfile(project_root)?;
+    Ok(DependencyGraph::from_lockfile(&lockfile, store_root))
+}
@@ -0,0 +1,115 @@
+//! Package index: a git repository whose `index.toml` lists package metadata.
+//!
+//! The index is fetched into the CAS cache like any package. It backs
+//! `weave search` and lets `weave install <name>` resolve a bare package name
+//! to its git source.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::package_git::ensure_cached;
+use super::package_source::is_ssh_url;
+
+/// File at the root of the index repository.
+pub const INDEX_FILE: &str = "index.toml";
+
+/// Parsed `index.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PackageIndex {
+    #[serde(default)]
+    pub package: Vec<IndexEntry>,
+}
+
+/// One package listed in the index.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IndexEntry {
+    pub name: String,
+    /// Any source accepted by `weave install` (`user/repo`, URL, ...).
+    pub repo: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl PackageIndex {
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).with_context(|| format!("failed to parse {INDEX_FILE}"))
+    }
+
+    /// Entries whose name, description or tags contain `term`, ignoring case.
+    /// Exact name matches come first; an empty term matches everything.
+    pub fn search(&self, term: &str) -> Vec<&IndexEntry> {
+        let term = term.trim().to_lowercase();
+        let mut hits: Vec<&IndexEntry> = self
+            .package
+            .iter()
+            .filter(|entry| {
+                entry.name.to_lowercase().contains(&term)
+                    || entry.description.to_lowercase().contains(&term)
+                    || entry.tags.iter().any(|t| t.to_lowercase().contains(&term))
+            })
+            .collect();
+        hits.sort_by_key(|entry| (entry.name.to_lowercase() != term, entry.name.clone()));
+        hits
+    }
+
+    pub fn find(&self, name: &str) -> Option<&IndexEntry> {
+        self.package.iter().find(|entry| entry.name == name)
+    }
+}
+
+/// Fetch the index repository at `url` and read `index.toml` from its HEAD.
+pub fn fetch_index(cache_root: &Path, url: &str) -> Result<PackageIndex> {
+    let cas = ensure_cached(cache_root, url)
+        .with_context(|| format!("failed to fetch package index {url}"))?;
+    let output = Command::new("git")
+        .args(["show", &format!("HEAD:{INDEX_FILE}")])
+        .current_dir(&cas)
+        .output()
+        .context("failed to run git show")?;
+    if !output.status.success() {
+        bail!("package index {url} has no {INDEX_FILE} at HEAD");
+    }
+    PackageIndex::parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether `source` is a bare package name (optionally `name@ref`) rather
+/// than a git source.
+pub fn is_index_name(source: &str) -> bool {
+    let name = source.split(['@', '#']).next().unwrap_or(source);
+    !name.is_empty() && !name.contains(['/', ':', '.']) && !is_ssh_url(source)
+}
+
+/// Resolve an install source through the index at `registry` when it is a
+/// bare package name; other sources are returned unchanged.
+pub fn resolve_index_source(
+    source: &str,
+    registry: Option<&str>,
+    cache_root: &Path,
+) -> Result<String> {
+    if !is_index_name(source) {
+        return Ok(source.to_string());
+    }
+    let (name, git_ref) = match source.split_once(['@', '#']) {
+        Some((name, git_ref)) => (name, Some(git_ref)),
+        None => (source, None),
+    };
+    let Some(registry) = registry else {
+        bail!(
+            "'{name}' is not a git source and no package index is configured; \
+             set `registry` under [weave] in the global config or pass user/repo"
+        );
+    };
+    let index = fetch_index(cache_root, registry)?;
+    let entry = index
+        .find(name)
+        .with_context(|| format!("package '{name}' not found in index {registry}"))?;
+    Ok(match git_ref {
+        Some(git_ref) => format!("{}@{git_ref}", entry.repo),
+        None => entry.repo.clone(),
+    })
+}
@@ -256,6 +256,7 @@ fn lockfile_roundtrip_with_version_pinning() {
             source_kind: SourceKind::Git,
             requested_version: Some("v1.0.0".to_string()),
             resolved_ref: Some("v1.0.0".to_string()),
+            ..Default::default()
         },
         LockedPackage {
             name: "unpinned".to_string(),
@@ -265,6 +266,7 @@ fn lockfile_roundtrip_with_version_pinning() {
             source_kind: SourceKind::Git,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
         LockedPackage {
             name: "branch-pinned".to_string(),
@@ -274,6 +276,7 @@ fn lockfile_roundtrip_with_version_pinning() {
             source_kind: SourceKind::Git,
             requested_version: Some("main".to_string()),
             resolved_ref: Some("main".to_string()),
+            ..Default::default()
         },
     ]);
 
@@ -323,6 +326,7 @@ fn version_fields_omitted_from_toml_when_none() {
         source_kind: SourceKind::Git,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
 
     let serialized = toml::to_string_pretty(&lockfile).unwrap();
@@ -346,6 +350,7 @@ fn version_fields_present_in_toml_when_set() {
         source_kind: SourceKind::Git,
         requested_version: Some("v2.0".to_string()),
         resolved_ref: Some("v2.0".to_string()),
+        ..Default::default()
     }]);
 
     let serialized = toml::to_string_pretty(&lockfile).unwrap();
@@ -378,6 +383,7 @@ fn lock_preserves_requested_version_from_existing_lockfile() {
         source_kind: SourceKind::Git,
         requested_version: Some("v1.0".to_string()),
         resolved_ref: Some("v1.0".to_string()),
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &initial).unwrap();
@@ -0,0 +1,167 @@
+//! Content hashes of store checkouts.
+//!
+//! `weave.lock` records a `sha256:` digest of every checkout when it is
+//! installed, updated or locked. Audit and linking recompute it so a checkout
+//! modified or corrupted in the global store is reported instead of being
+//! linked into agent skill directories.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use super::package_git::checkout_to;
+use super::package_version::locked_checkout;
+use super::{LockedPackage, is_checkout_valid};
+
+/// Prefix of the digests stored in `LockedPackage::checksum`.
+pub const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// A checkout whose content no longer matches its locked checksum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub package: String,
+    pub expected: String,
+    /// Digest of the checkout as found; `None` when it could not be read.
+    pub actual: Option<String>,
+}
+
+impl std::fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.actual {
+            Some(actual) => write!(
+                f,
+                "checkout content changed: weave.lock has {}, store has {actual}",
+                self.expected
+            ),
+            None => write!(
+                f,
+                "checkout is unreadable; weave.lock expects {}",
+                self.expected
+            ),
+        }
+    }
+}
+
+/// Digest of every file under `dir` except `.git/`.
+///
+/// Entries are hashed in path order with their relative path, kind
+/// (file, executable, symlink) and content, so renames, mode flips and
+/// added or removed files all change the digest.
+pub fn checkout_checksum(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(dir, Path::new(""), &mut files)?;
+    files.sort();
+    let mut hasher = Sha256::new();
+    for rel in &files {
+        let path = dir.join(rel);
+        let meta = std::fs::symlink_metadata(&path)
+            .with_context(|| format!("failed to stat {}", path.display()))?;
+        let (kind, content) = if meta.file_type().is_symlink() {
+            let target = std::fs::read_link(&path)
+                .with_context(|| format!("failed to read link {}", path.display()))?;
+            (b'L', target.to_string_lossy().into_owned().into_bytes())
+        } else {
+            let content = std::fs::read(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            (if is_executable(&meta) { b'X' } else { b'F' }, content)
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        hasher.update([kind]);
+        hasher.update(rel.as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+    Ok(format!("{CHECKSUM_PREFIX}{:x}", hasher.finalize()))
+}
+
+/// Compare the store checkout of `pkg` with its locked checksum.
+///
+/// Packages without a recorded checksum, or whose checkout is missing (which
+/// audit reports separately), are not checked.
+pub fn verify_checkout(store_root: &Path, pkg: &LockedPackage) -> Option<ChecksumMismatch> {
+    let expected = pkg.checksum.as_ref()?;
+    let dir = locked_checkout(store_root, pkg).filter(|dir| dir.is_dir())?;
+    let actual = checkout_checksum(&dir).ok();
+    (actual.as_ref() != Some(expected)).then(|| ChecksumMismatch {
+        package: pkg.name.clone(),
+        expected: expected.clone(),
+        actual,
+    })
+}
+
+/// Check out `commit` into `dest` unless a valid checkout is already there,
+/// and return its checksum. An existing checkout is kept only when it matches
+/// `locked`, or, without a locked checksum, a fresh checkout of `commit`;
+/// otherwise it is replaced with that fresh checkout.
+pub(super) fn ensure_checkout(
+    cas_dir: &Path,
+    commit: &str,
+    dest: &Path,
+    locked: Option<&str>,
+) -> Result<String> {
+    if let Some(locked) = locked
+        && is_checkout_valid(dest)
+        && checkout_checksum(dest).is_ok_and(|checksum| checksum == locked)
+    {
+        return Ok(locked.to_string());
+    }
+
+    let parent = dest
+        .parent()
+        .with_context(|| format!("{} has no parent directory", dest.display()))?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("failed to create {}", parent.display()))?;
+    let fresh = tempfile::Builder::new()
+        .prefix(".checkout-")
+        .tempdir_in(parent)
+        .with_context(|| format!("failed to create a temp dir in {}", parent.display()))?;
+    let fresh_dir = fresh.path().join("tree");
+    checkout_to(cas_dir, commit, &fresh_dir)?;
+    let checksum = checkout_checksum(&fresh_dir)?;
+
+    if locked.is_none()
+        && is_checkout_valid(dest)
+        && checkout_checksum(dest).is_ok_and(|existing| existing == checksum)
+    {
+        return Ok(checksum);
+    }
+    if dest.exists() {
+        std::fs::remove_dir_all(dest)
+            .with_context(|| format!("failed to remove existing {}", dest.display()))?;
+    }
+    std::fs::rename(&fresh_dir, dest)
+        .with_context(|| format!("failed to move checkout to {}", dest.display()))?;
+    Ok(checksum)
+}
+
+fn collect_files(root: &Path, rel: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let dir = root.join(rel);
+    for entry in
+        std::fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let path = rel.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &std::fs::Metadata) -> bool {
+    false
+}
@@ -83,6 +83,7 @@ fn migrate_skips_local_and_migrates_git() {
             source_kind: SourceKind::Local,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
         LockedPackage {
             name: "git-dep".to_string(),
@@ -92,6 +93,7 @@ fn migrate_skips_local_and_migrates_git() {
             source_kind: SourceKind::Git,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
     ]);
     save_lockfile(&legacy, &lockfile).unwrap();
@@ -0,0 +1,138 @@
+//! Parsing of `weave install` git sources.
+
+use anyhow::{Context, Result, bail};
+
+/// Parsed install source — a git URL with an optional ref and skill name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstallSource {
+    /// Canonical git URL (https or SSH).
+    pub url: String,
+    /// Git ref to checkout (branch/tag/commit). None means HEAD.
+    pub git_ref: Option<String>,
+    /// Skill name (last path segment without `.git`).
+    pub name: String,
+}
+
+/// Kind of git ref selected by `weave install --branch/--tag/--rev`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinKind {
+    Branch,
+    Tag,
+    Rev,
+}
+
+/// An explicit ref to pin an install to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    pub kind: PinKind,
+    /// Branch or tag name, or a commit-ish for `Rev`.
+    pub target: String,
+}
+
+impl Pin {
+    /// Ref to resolve in the cached repository; branches and tags are fully
+    /// qualified so a branch and a tag of the same name cannot be confused.
+    pub fn git_ref(&self) -> String {
+        match self.kind {
+            PinKind::Branch => format!("refs/heads/{}", self.target),
+            PinKind::Tag => format!("refs/tags/{}", self.target),
+            PinKind::Rev => self.target.clone(),
+        }
+    }
+}
+
+/// Parse a source string into an `InstallSource`.
+///
+/// Accepted formats:
+/// - `user/repo` → `https://github.com/user/repo.git`
+/// - `github.com/user/repo` → `https://github.com/user/repo.git`
+/// - `https://github.com/user/repo` → as-is with `.git` suffix
+/// - `https://github.com/user/repo@v1.0` → with ref
+/// - `https://github.com/user/repo#branch` → with ref
+/// - `git@github.com:user/repo` / `ssh://git@host/user/repo` → SSH, as-is
+///   with `.git` suffix (the `user@` part is not a ref separator)
+pub fn parse_source(source: &str) -> Result<InstallSource> {
+    let (prefix, rest) = source.split_at(ssh_path_start(source).unwrap_or(0));
+    let (url_part, git_ref) = if let Some((url, r)) = rest.rsplit_once('@') {
+        (format!("{prefix}{url}"), Some(r.to_string()))
+    } else if let Some((url, r)) = rest.rsplit_once('#') {
+        (format!("{prefix}{url}"), Some(r.to_string()))
+    } else {
+        (source.to_string(), None)
+    };
+
+    let url = normalize_url(&url_part)?;
+    let name = extract_name(&url)?;
+
+    Ok(InstallSource { url, git_ref, name })
+}
+
+/// Byte offset where the repository path of an SSH source begins, i.e. just
+/// past `ssh://[user@]host` or the `user@host:` of the scp-like form.
+fn ssh_path_start(source: &str) -> Option<usize> {
+    if let Some(rest) = source.strip_prefix("ssh://") {
+        return Some("ssh://".len() + rest.find('/')?);
+    }
+    let (host, _) = source.split_once(':')?;
+    (host.contains('@') && !host.contains('/')).then_some(host.len() + 1)
+}
+
+/// Whether `url` is reached over SSH rather than HTTP(S).
+pub(super) fn is_ssh_url(url: &str) -> bool {
+    url.starts_with("ssh://") || (!url.contains("://") && ssh_path_start(url).is_some())
+}
+
+/// Normalize various URL formats to canonical https git URL.
+fn normalize_url(input: &str) -> Result<String> {
+    // Already a full URL
+    if input.starts_with("https://") || input.starts_with("http://") || is_ssh_url(input) {
+        let url = if input.ends_with(".git") {
+            input.to_string()
+        } else {
+            format!("{input}.git")
+        };
+        return Ok(url);
+    }
+
+    // domain/user/repo format (e.g., github.com/user/repo)
+    if input.contains('.') && input.contains('/') {
+        let url = if input.ends_with(".git") {
+            format!("https://{input}")
+        } else {
+            format!("https://{input}.git")
+        };
+        return Ok(url);
+    }
+
+    // user/repo shorthand → GitHub
+    let parts: Vec<&str> = input.split('/').collect();
+    if parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty() {
+        return Ok(format!("https://github.com/{}/{}.git", parts[0], parts[1]));
+    }
+
+    bail!(
+        "cannot parse source: '{input}' (expected user/repo, domain/user/repo, git@host:user/repo, or full URL)"
+    )
+}
+
+/// Extract the skill name from a git URL (last path segment minus `.git`).
+fn extract_name(url: &str) -> Result<String> {
+    let path = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    let last_segment = path
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .context("empty URL path")?;
+
+    let name = last_segment.strip_suffix(".git").unwrap_or(last_segment);
+
+    if name.is_empty() {
+        bail!("could not extract skill name from URL: {url}");
+    }
+
+    Ok(name.to_string())
+}
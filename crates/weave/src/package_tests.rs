@@ -1,7 +1,7 @@
 use std::process::Command;
 use tempfile::TempDir;
 
-use super::package_git::cas_dir_for;
+use super::package_git::{cas_dir_for, resolve_commit};
 use super::*;
 
 #[test]
@@ -47,6 +47,19 @@ fn parse_source_with_hash_ref() {
     assert_eq!(src.git_ref, Some("main".to_string()));
 }
 
+#[test]
+fn parse_source_ssh() {
+    let src = parse_source("git@github.com:acme/private-kit@v1.0").unwrap();
+    assert_eq!(src.url, "git@github.com:acme/private-kit.git");
+    assert_eq!(src.name, "private-kit");
+    assert_eq!(src.git_ref, Some("v1.0".to_string()));
+
+    let src = parse_source("ssh://git@git.example.com:2222/team/kit.git").unwrap();
+    assert_eq!(src.url, "ssh://git@git.example.com:2222/team/kit.git");
+    assert_eq!(src.name, "kit");
+    assert!(src.git_ref.is_none());
+}
+
 #[test]
 fn parse_source_invalid() {
     assert!(parse_source("").is_err());
@@ -64,6 +77,7 @@ fn lockfile_round_trip() {
             source_kind: SourceKind::default(),
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
         LockedPackage {
             name: "review".to_string(),
@@ -73,6 +87,7 @@ fn lockfile_round_trip() {
             source_kind: SourceKind::default(),
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
     ]);
 
@@ -94,6 +109,7 @@ fn upsert_adds_new_package() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
 
     let new_pkg = LockedPackage {
@@ -104,6 +120,7 @@ fn upsert_adds_new_package() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     };
 
     upsert_package(&mut lockfile, &new_pkg);
@@ -121,6 +138,7 @@ fn upsert_updates_existing_package() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
 
     let updated = LockedPackage {
@@ -131,6 +149,7 @@ fn upsert_updates_existing_package() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     };
 
     upsert_package(&mut lockfile, &updated);
@@ -279,6 +298,7 @@ fn lock_reads_from_legacy_and_writes_to_new() {
         source_kind: SourceKind::Git,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     save_lockfile(&legacy, &initial).unwrap();
     let result = lock(tmp.path(), &store).unwrap();
@@ -347,6 +367,7 @@ fn lock_preserves_existing_lockfile_entries() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &initial).unwrap();
@@ -367,6 +388,7 @@ fn source_kind_serde_roundtrip() {
             source_kind: SourceKind::Git,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
         LockedPackage {
             name: "from-local".to_string(),
@@ -376,6 +398,7 @@ fn source_kind_serde_roundtrip() {
             source_kind: SourceKind::Local,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
     ]);
 
@@ -416,6 +439,7 @@ fn lock_preserves_source_kind_from_existing_lockfile() {
         source_kind: SourceKind::Local,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &initial).unwrap();
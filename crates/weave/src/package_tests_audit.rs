@@ -26,6 +26,7 @@ fn audit_detects_missing_dep() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
@@ -56,6 +57,7 @@ fn audit_detects_missing_skill_md() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
@@ -87,6 +89,7 @@ fn audit_detects_unknown_repo() {
         source_kind: SourceKind::Git, // Git source with empty repo → UnknownRepo
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
@@ -124,6 +127,7 @@ fn audit_detects_case_mismatch_skill_md() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
@@ -161,6 +165,7 @@ fn audit_correct_skill_md_no_case_issue() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
@@ -184,6 +189,7 @@ fn audit_neither_skill_md_variant_is_missing() {
         source_kind: SourceKind::default(),
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
@@ -216,6 +222,7 @@ fn audit_skips_unknown_repo_for_local_source() {
         source_kind: SourceKind::Local,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     let lp = lockfile_path(tmp.path());
     save_lockfile(&lp, &lockfile).unwrap();
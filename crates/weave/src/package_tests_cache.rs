@@ -0,0 +1,186 @@
+//! Shallow cache and object pool tests for the `package` module.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use super::package_cache::{POOL_DIR, is_shallow};
+use super::package_git::{checkout_to, ensure_cached, resolve_commit};
+use super::*;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.email=t@example.com", "-c", "user.name=T"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Commit `SKILL.md` once per entry of `versions`, returning the commits.
+fn commit_versions(repo: &Path, versions: &[&str]) -> Vec<String> {
+    if !repo.join(".git").exists() {
+        std::fs::create_dir_all(repo).unwrap();
+        git(repo, &["init", "--quiet", "--initial-branch=main"]);
+    }
+    versions
+        .iter()
+        .map(|version| {
+            std::fs::write(repo.join("SKILL.md"), format!("# skill {version}\n")).unwrap();
+            git(repo, &["add", "."]);
+            git(repo, &["commit", "--quiet", "-m", version]);
+            git(repo, &["rev-parse", "HEAD"])
+        })
+        .collect()
+}
+
+/// Objects stored in `cas` itself rather than borrowed from the pool.
+fn own_objects(cas: &Path) -> (String, String) {
+    let stats = git(cas, &["count-objects", "-v"]);
+    let field = |name: &str| {
+        stats
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .unwrap()
+            .trim()
+            .to_string()
+    };
+    (field("count:"), field("in-pack:"))
+}
+
+#[test]
+fn shallow_cache_fetches_tips_and_deepens_on_demand() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("repo");
+    let commits = commit_versions(&repo, &["c0", "c1", "c2", "c3"]);
+    git(&repo, &["tag", "v1", &commits[1]]);
+
+    let cas = ensure_cached(&tmp.path().join("cache"), repo.to_str().unwrap()).unwrap();
+    assert!(is_shallow(&cas));
+    assert_eq!(resolve_commit(&cas, None).unwrap(), commits[3]);
+    assert_eq!(resolve_commit(&cas, Some("v1")).unwrap(), commits[1]);
+
+    // A full hash that is not a tip is fetched on its own.
+    assert_eq!(resolve_commit(&cas, Some(&commits[2])).unwrap(), commits[2]);
+    assert!(is_shallow(&cas));
+
+    // An abbreviated hash needs the whole history.
+    assert_eq!(
+        resolve_commit(&cas, Some(&commits[0][..10])).unwrap(),
+        commits[0]
+    );
+    assert!(!is_shallow(&cas));
+
+    let dest = tmp.path().join("checkout");
+    checkout_to(&cas, &commits[0], &dest).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+        "# skill c0\n"
+    );
+    assert!(resolve_commit(&cas, Some("no-such-branch")).is_err());
+}
+
+#[test]
+fn forks_borrow_shared_history_from_the_pool() {
+    let tmp = TempDir::new().unwrap();
+    let cache = tmp.path().join("cache");
+    let upstream = tmp.path().join("upstream");
+    commit_versions(&upstream, &["c0", "c1"]);
+    let fork = tmp.path().join("fork");
+    git(
+        tmp.path(),
+        &["clone", "--quiet", upstream.to_str().unwrap(), "fork"],
+    );
+    let fork_head = commit_versions(&fork, &["fork"]).remove(0);
+
+    let upstream_cas = ensure_cached(&cache, upstream.to_str().unwrap()).unwrap();
+    let fork_cas = ensure_cached(&cache, fork.to_str().unwrap()).unwrap();
+
+    for cas in [&upstream_cas, &fork_cas] {
+        assert_eq!(own_objects(cas), ("0".to_string(), "0".to_string()));
+    }
+    let pool = cache.join("git").join(POOL_DIR);
+    assert_eq!(git(&pool, &["cat-file", "-t", &fork_head]), "commit");
+
+    let dest = tmp.path().join("checkout");
+    checkout_to(&fork_cas, &fork_head, &dest).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+        "# skill fork\n"
+    );
+}
+
+#[test]
+fn gc_cache_removes_unreferenced_repositories_and_prunes_the_pool() {
+    let tmp = TempDir::new().unwrap();
+    let cache = tmp.path().join("cache");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let used = tmp.path().join("used");
+    let unused = tmp.path().join("unused");
+    let index = tmp.path().join("index");
+    let used_head = commit_versions(&used, &["used"]).remove(0);
+    let unused_head = commit_versions(&unused, &["unused"]).remove(0);
+    commit_versions(&index, &["index"]);
+    let [used_cas, unused_cas, index_cas] =
+        [&used, &unused, &index].map(|repo| ensure_cached(&cache, repo.to_str().unwrap()).unwrap());
+
+    let pkg = LockedPackage {
+        name: "used".to_string(),
+        repo: used.to_str().unwrap().to_string(),
+        commit: used_head.clone(),
+        version: None,
+        source_kind: SourceKind::Git,
+        ..Default::default()
+    };
+    save_lockfile(
+        &lockfile_path(&project),
+        &Lockfile::with_packages(vec![pkg]),
+    )
+    .unwrap();
+    let keep = [index.to_str().unwrap().to_string()];
+    let unused_key = unused_cas
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+
+    let dry = gc_cache(&project, &cache, &keep, true).unwrap();
+    assert_eq!(dry.removed, std::slice::from_ref(&unused_key));
+    assert!(unused_cas.is_dir());
+
+    // Pool packs written within the grace period are never pruned.
+    let pool = cache.join("git").join(POOL_DIR);
+    let packs = pool.join("objects").join("pack");
+    for entry in std::fs::read_dir(&packs).unwrap() {
+        let status = Command::new("touch")
+            .args(["-d", "2 hours ago"])
+            .arg(entry.unwrap().path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    let result = gc_cache(&project, &cache, &keep, false).unwrap();
+    assert_eq!(result.removed, [unused_key]);
+    assert!(result.freed_bytes > 0);
+    assert!(!unused_cas.exists());
+    assert!(used_cas.is_dir() && index_cas.is_dir());
+
+    let pooled = |commit: &str| {
+        Command::new("git")
+            .args(["cat-file", "-e", commit])
+            .current_dir(&pool)
+            .status()
+            .unwrap()
+            .success()
+    };
+    assert!(!pooled(&unused_head));
+    assert!(pooled(&used_head));
+
+    let dest = tmp.path().join("checkout");
+    checkout_to(&used_cas, &used_head, &dest).unwrap();
+    assert!(dest.join("SKILL.md").is_file());
+}
@@ -0,0 +1,146 @@
+//! Tests for `weave diff`.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use super::*;
+use crate::package::{LockedPackage, Lockfile, lockfile_path, save_lockfile};
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.email=t@example.com", "-c", "user.name=T"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Commit a release of `lib` and tag it `v<version>`.
+fn release(repo: &Path, version: &str, extra: &[(&str, &[u8])]) -> String {
+    std::fs::write(
+        repo.join(".skill.toml"),
+        format!("[skill]\nname = \"lib\"\nversion = \"{version}\"\n"),
+    )
+    .unwrap();
+    std::fs::write(repo.join("SKILL.md"), format!("# lib {version}\n")).unwrap();
+    for (path, content) in extra {
+        std::fs::write(repo.join(path), content).unwrap();
+    }
+    git(repo, &["add", "-A"]);
+    git(
+        repo,
+        &["commit", "--quiet", "-m", &format!("release {version}")],
+    );
+    git(repo, &["tag", &format!("v{version}")]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+fn lock(project: &Path, repo: &Path, commit: &str, requested: &str, resolved: &str) {
+    let pkg = LockedPackage {
+        name: "lib".to_string(),
+        repo: repo.to_str().unwrap().to_string(),
+        commit: commit.to_string(),
+        version: Some("1.0.0".to_string()),
+        source_kind: SourceKind::Git,
+        requested_version: Some(requested.to_string()),
+        resolved_ref: Some(resolved.to_string()),
+        ..Default::default()
+    };
+    save_lockfile(&lockfile_path(project), &Lockfile::with_packages(vec![pkg])).unwrap();
+}
+
+#[test]
+fn diff_lists_commits_and_files_up_to_the_newest_matching_release() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("lib");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&repo).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+    git(&repo, &["init", "--quiet", "--initial-branch=main"]);
+    let v1 = release(&repo, "1.0.0", &[("old.md", b"old\n")]);
+    std::fs::remove_file(repo.join("old.md")).unwrap();
+    let v1_1 = release(&repo, "1.1.0", &[("logo.png", b"\x89PNG\0\x01")]);
+    release(&repo, "2.0.0", &[]);
+    lock(&project, &repo, &v1, "^1", "v1.0.0");
+
+    let cache = tmp.path().join("cache");
+    let store = tmp.path().join("store");
+    let result = diff("lib", &project, &cache, &store).unwrap();
+    assert_eq!(result.from, v1);
+    assert_eq!(result.to, v1_1);
+    assert_eq!(result.to_version.as_deref(), Some("1.1.0"));
+    assert_eq!(result.pin, None);
+    assert_eq!(
+        result.commits,
+        [DiffCommit {
+            commit: v1_1.clone(),
+            summary: "release 1.1.0".to_string(),
+        }]
+    );
+    assert!(result.dropped.is_empty());
+    assert_eq!(
+        result.files,
+        [
+            FileChange {
+                status: 'M',
+                path: ".skill.toml".to_string(),
+                lines: Some((1, 1)),
+            },
+            FileChange {
+                status: 'M',
+                path: "SKILL.md".to_string(),
+                lines: Some((1, 1)),
+            },
+            FileChange {
+                status: 'A',
+                path: "logo.png".to_string(),
+                lines: None,
+            },
+            FileChange {
+                status: 'D',
+                path: "old.md".to_string(),
+                lines: Some((0, 1)),
+            },
+        ]
+    );
+    let text = result.render();
+    assert!(
+        text.starts_with(&format!(
+            "lib 1.0.0 ({}) -> 1.1.0 ({})",
+            &v1[..8],
+            &v1_1[..8]
+        )),
+        "{text}"
+    );
+    assert!(text.contains("1 new upstream commit(s)"), "{text}");
+    assert!(text.contains("  A logo.png (binary)"), "{text}");
+
+    // Once locked at the newest match there is nothing to show.
+    lock(&project, &repo, &v1_1, "^1", "v1.1.0");
+    let result = diff("lib", &project, &cache, &store).unwrap();
+    assert!(result.is_empty());
+    assert!(result.render().ends_with("is up to date\n"));
+}
+
+#[test]
+fn diff_of_pinned_branch_follows_branch_head_and_notes_the_pin() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("lib");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&repo).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+    git(&repo, &["init", "--quiet", "--initial-branch=main"]);
+    let v1 = release(&repo, "1.0.0", &[]);
+    let v2 = release(&repo, "2.0.0", &[]);
+    lock(&project, &repo, &v1, "main", "refs/heads/main");
+
+    let result = diff("lib", &project, &tmp.path().join("cache"), tmp.path()).unwrap();
+    assert_eq!(result.to, v2);
+    assert_eq!(result.pin.as_deref(), Some("branch main"));
+    assert!(result.render().contains("only with --force"));
+
+    assert!(diff("missing", &project, &tmp.path().join("cache"), tmp.path()).is_err());
+}
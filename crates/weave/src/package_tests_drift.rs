@@ -0,0 +1,187 @@
+//! Drift-detection tests for the `package` module.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use super::*;
+
+fn run_git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn locked(name: &str, repo: &str, commit: &str) -> LockedPackage {
+    LockedPackage {
+        name: name.to_string(),
+        repo: repo.to_string(),
+        commit: commit.to_string(),
+        version: None,
+        source_kind: SourceKind::Git,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn package_drift_reports_modified_missing_and_added_files() {
+    let tmp = TempDir::new().unwrap();
+    let work = tmp.path().join("work");
+    let cache = tmp.path().join("cache");
+    let store = tmp.path().join("store");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(work.join("patterns/p/skills/p")).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    run_git(&work, &["init", "--quiet"]);
+    run_git(&work, &["config", "user.email", "test@example.com"]);
+    run_git(&work, &["config", "user.name", "Test User"]);
+    std::fs::write(work.join("SKILL.md"), "# skill\n").unwrap();
+    std::fs::write(work.join("patterns/p/skills/p/SKILL.md"), "# p\n").unwrap();
+    std::fs::write(work.join("notes.md"), "notes\n").unwrap();
+    run_git(&work, &["add", "."]);
+    run_git(&work, &["commit", "--quiet", "-m", "initial"]);
+    let commit = run_git(&work, &["rev-parse", "HEAD"]);
+
+    let repo = work.to_str().unwrap();
+    let cas = ensure_cached(&cache, repo).unwrap();
+    let checkout = package_dir(&store, "skill", &commit).unwrap();
+    checkout_to(&cas, &commit, &checkout).unwrap();
+    save_lockfile(
+        &lockfile_path(&project),
+        &Lockfile::with_packages(vec![locked("skill", repo, &commit)]),
+    )
+    .unwrap();
+
+    assert!(package_drift(&project, &store, &cache).unwrap().is_empty());
+
+    std::fs::write(checkout.join("patterns/p/skills/p/SKILL.md"), "# evil\n").unwrap();
+    std::fs::remove_file(checkout.join("notes.md")).unwrap();
+    std::fs::write(checkout.join("extra.sh"), "echo hi\n").unwrap();
+
+    let drift = package_drift(&project, &store, &cache).unwrap();
+    assert_eq!(drift.len(), 1);
+    assert_eq!(drift[0].commit, commit);
+    assert_eq!(
+        drift[0].issues,
+        vec![
+            DriftIssue::Missing("notes.md".to_string()),
+            DriftIssue::Modified("patterns/p/skills/p/SKILL.md".to_string()),
+            DriftIssue::Added("extra.sh".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn package_drift_without_cached_clone_cannot_verify() {
+    let tmp = TempDir::new().unwrap();
+    let store = tmp.path().join("store");
+    let checkout = package_dir(&store, "skill", "abcdef1234").unwrap();
+    std::fs::create_dir_all(&checkout).unwrap();
+    std::fs::write(checkout.join("SKILL.md"), "# skill\n").unwrap();
+    save_lockfile(
+        &lockfile_path(tmp.path()),
+        &Lockfile::with_packages(vec![
+            locked("skill", "https://example.com/skill.git", "abcdef1234"),
+            locked("ghost", "https://example.com/ghost.git", "1234567890"),
+        ]),
+    )
+    .unwrap();
+
+    let drift = package_drift(tmp.path(), &store, &tmp.path().join("cache")).unwrap();
+    assert_eq!(drift.len(), 2);
+    assert!(matches!(
+        drift[0].issues.as_slice(),
+        [DriftIssue::BaselineUnavailable(_)]
+    ));
+    assert_eq!(drift[1].issues, vec![DriftIssue::MissingCheckout]);
+}
+
+#[cfg(unix)]
+#[test]
+fn link_drift_flags_stale_unlocked_and_broken_links() {
+    let tmp = TempDir::new().unwrap();
+    let store = tmp.path().join("store");
+    let skills = tmp.path().join(".claude/skills");
+    std::fs::create_dir_all(&skills).unwrap();
+    for dir in [
+        "pkg/abcdef12/patterns/a",
+        "pkg/00000000/patterns/b",
+        "other/11111111",
+    ] {
+        std::fs::create_dir_all(store.join(dir)).unwrap();
+    }
+    save_lockfile(
+        &lockfile_path(tmp.path()),
+        &Lockfile::with_packages(vec![locked(
+            "pkg",
+            "https://example.com/pkg.git",
+            "abcdef1234",
+        )]),
+    )
+    .unwrap();
+
+    let link = |name: &str, target: &str| {
+        std::os::unix::fs::symlink(store.join(target), skills.join(name)).unwrap();
+    };
+    link("ok", "pkg/abcdef12/patterns/a");
+    link("stale", "pkg/00000000/patterns/b");
+    link("unlocked", "other/11111111");
+    link("broken", "pkg/abcdef12/patterns/gone");
+    std::os::unix::fs::symlink("/elsewhere", skills.join("foreign")).unwrap();
+
+    let drift = link_drift(tmp.path(), &store).unwrap();
+    let by_name: Vec<(String, &LinkDriftIssue)> = drift
+        .iter()
+        .map(|d| {
+            (
+                d.link.file_name().unwrap().to_string_lossy().to_string(),
+                &d.issue,
+            )
+        })
+        .collect();
+    assert_eq!(by_name.len(), 3, "{by_name:?}");
+    assert!(matches!(by_name[0], (ref n, LinkDriftIssue::Broken { .. }) if n == "broken"));
+    assert_eq!(
+        by_name[1].1,
+        &LinkDriftIssue::WrongCommit {
+            package: "pkg".to_string(),
+            linked: "00000000".to_string(),
+            locked: "abcdef12".to_string(),
+        }
+    );
+    assert_eq!(
+        by_name[2].1,
+        &LinkDriftIssue::Unlocked {
+            package: "other".to_string()
+        }
+    );
+}
+
+#[test]
+fn drift_checks_fail_on_an_unreadable_lockfile() {
+    let tmp = TempDir::new().unwrap();
+    let store = tmp.path().join("store");
+    let cache = tmp.path().join("cache");
+    // No lockfile locks nothing.
+    assert!(
+        package_drift(tmp.path(), &store, &cache)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(link_drift(tmp.path(), &store).unwrap().is_empty());
+
+    std::fs::write(lockfile_path(tmp.path()), "[[package]\nname = ").unwrap();
+    let err = package_drift(tmp.path(), &store, &cache).unwrap_err();
+    assert!(format!("{err:#}").contains("failed to parse"), "{err:#}");
+    assert!(link_drift(tmp.path(), &store).is_err());
+}
@@ -0,0 +1,200 @@
+//! Dependency-graph tests for the `package` module.
+
+use tempfile::TempDir;
+
+use super::*;
+
+fn git_pkg(name: &str, repo: &str, commit: &str, version: &str) -> LockedPackage {
+    LockedPackage {
+        name: name.to_string(),
+        repo: repo.to_string(),
+        commit: commit.to_string(),
+        version: Some(version.to_string()),
+        source_kind: SourceKind::Git,
+        ..Default::default()
+    }
+}
+
+fn edge(package: &str, requirement: &str) -> DependencyEdge {
+    DependencyEdge {
+        package: package.to_string(),
+        requirement: requirement.to_string(),
+    }
+}
+
+/// app -> lib -> util, tool -> util, and lib-fork locked from lib's repo.
+fn sample_graph() -> DependencyGraph {
+    let packages = [
+        git_pkg("app", "acme/app", "aaaaaaaa11", "1.0.0"),
+        git_pkg("lib", "acme/lib", "bbbbbbbb22", "1.2.0"),
+        git_pkg("lib-fork", "acme/lib", "cccccccc33", "1.3.0"),
+        git_pkg("tool", "acme/tool", "dddddddd44", "0.1.0"),
+        git_pkg("util", "acme/util", "eeeeeeee55", "0.3.1"),
+    ];
+    let mut graph = DependencyGraph::default();
+    for pkg in packages {
+        graph.edges.insert(pkg.name.clone(), Vec::new());
+        graph.packages.insert(pkg.name.clone(), pkg);
+    }
+    graph.edges.insert(
+        "app".to_string(),
+        vec![edge("lib", "^1.2"), edge("missing", "^2")],
+    );
+    graph
+        .edges
+        .insert("lib".to_string(), vec![edge("util", ">=0.3")]);
+    graph
+        .edges
+        .insert("tool".to_string(), vec![edge("util", "^0.3")]);
+    graph
+}
+
+#[test]
+fn roots_are_packages_nothing_depends_on() {
+    let graph = sample_graph();
+    assert_eq!(graph.roots(), ["app", "lib-fork", "tool"]);
+    assert_eq!(
+        graph.dependents("util"),
+        [("lib", ">=0.3"), ("tool", "^0.3")]
+    );
+}
+
+#[test]
+fn render_tree_nests_transitive_dependencies() {
+    let rendered = sample_graph().render_tree();
+    assert_eq!(
+        rendered,
+        "\
+app 1.0.0 (aaaaaaaa)
+├── lib 1.2.0 (bbbbbbbb) [^1.2]
+│   └── util 0.3.1 (eeeeeeee) [>=0.3]
+└── missing (not installed) [^2]
+lib-fork 1.3.0 (cccccccc)
+tool 0.1.0 (dddddddd)
+└── util 0.3.1 (eeeeeeee) [^0.3]
+"
+    );
+}
+
+#[test]
+fn render_tree_marks_cycles_and_lists_cyclic_packages() {
+    let mut graph = DependencyGraph::default();
+    for name in ["a", "b"] {
+        graph
+            .packages
+            .insert(name.to_string(), git_pkg(name, name, "0123456789", "1.0.0"));
+    }
+    graph.edges.insert("a".to_string(), vec![edge("b", "^1")]);
+    graph.edges.insert("b".to_string(), vec![edge("a", "^1")]);
+    assert!(graph.roots().is_empty());
+    assert_eq!(
+        graph.render_tree(),
+        "\
+a 1.0.0 (01234567)
+└── b 1.0.0 (01234567) [^1]
+    └── a 1.0.0 (01234567) [^1] (cycle)
+"
+    );
+}
+
+#[test]
+fn render_why_walks_dependents_up_to_the_roots() {
+    let graph = sample_graph();
+    assert_eq!(
+        graph.render_why("util").unwrap(),
+        "\
+util 0.3.1 (eeeeeeee)
+├── lib 1.2.0 (bbbbbbbb) [>=0.3]
+│   └── app 1.0.0 (aaaaaaaa) [^1.2]
+└── tool 0.1.0 (dddddddd) [^0.3]
+"
+    );
+    assert_eq!(
+        graph.render_why("missing").unwrap(),
+        "missing (not installed)\n└── app 1.0.0 (aaaaaaaa) [^2]\n"
+    );
+    let err = graph.render_why("nope").unwrap_err();
+    assert!(err.to_string().contains("not installed or required"));
+}
+
+#[test]
+fn duplicates_group_names_locked_from_the_same_repo() {
+    let graph = sample_graph();
+    assert_eq!(
+        graph.duplicates(),
+        [DuplicatePackage {
+            repo: "acme/lib".to_string(),
+            names: vec!["lib".to_string(), "lib-fork".to_string()],
+        }]
+    );
+    assert_eq!(
+        graph.render_duplicates(),
+        "\
+acme/lib
+lib 1.2.0 (bbbbbbbb)
+└── app 1.0.0 (aaaaaaaa) [^1.2]
+lib-fork 1.3.0 (cccccccc)
+"
+    );
+}
+
+#[test]
+fn dependency_graph_reads_manifests_of_locked_checkouts() {
+    let tmp = TempDir::new().unwrap();
+    let project = tmp.path().join("project");
+    let store = tmp.path().join("store");
+    let dir = package_dir(&store, "app", "local").unwrap();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(
+        dir.join(".skill.toml"),
+        "[skill]\nname = \"app\"\n\n[dependencies]\nlib = { version = \"^1\" }\n",
+    )
+    .unwrap();
+    let app = LockedPackage {
+        source_kind: SourceKind::Local,
+        commit: String::new(),
+        version: None,
+        ..git_pkg("app", "", "", "")
+    };
+    save_lockfile(
+        &lockfile_path(&project),
+        &Lockfile::with_packages(vec![app]),
+    )
+    .unwrap();
+
+    let graph = dependency_graph(&project, &store).unwrap();
+    assert_eq!(graph.dependencies("app"), [edge("lib", "^1")]);
+    assert_eq!(graph.roots(), ["app"]);
+    assert!(graph.render_tree().contains("app - (local)"));
+}
+
+#[test]
+fn pinned_packages_show_their_pin() {
+    let commit = "0123456789abcdef0123456789abcdef01234567";
+    let pinned = |requested: &str, resolved: &str| LockedPackage {
+        requested_version: Some(requested.to_string()),
+        resolved_ref: Some(resolved.to_string()),
+        ..git_pkg("pkg", "https://example.com/pkg.git", commit, "1.0.0")
+    };
+    assert_eq!(
+        pinned("main", "refs/heads/main").pin().as_deref(),
+        Some("branch main")
+    );
+    assert_eq!(
+        pinned("v1.0", "refs/tags/v1.0").pin().as_deref(),
+        Some("tag v1.0")
+    );
+    assert_eq!(
+        pinned("0123456", commit).pin().as_deref(),
+        Some("rev 01234567")
+    );
+    assert_eq!(pinned("v1.0", "v1.0").pin().as_deref(), Some("v1.0"));
+    assert_eq!(pinned("^1", "v1.0.0").pin(), None);
+
+    let graph = DependencyGraph::from_lockfile(
+        &Lockfile::with_packages(vec![pinned("v1.0", "refs/tags/v1.0")]),
+        Path::new("/nonexistent"),
+    );
+    assert_eq!(graph.render_tree(), "pkg 1.0.0 (01234567) [tag v1.0]\n");
+}
@@ -0,0 +1,110 @@
+//! Package index tests for the `package` module.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use super::*;
+
+const INDEX: &str = r#"
+[[package]]
+name = "review-kit"
+repo = "acme/review-kit"
+description = "Code review patterns"
+tags = ["review", "security"]
+
+[[package]]
+name = "commit"
+repo = "https://example.com/acme/commit.git"
+description = "Conventional commits with a security review step"
+"#;
+
+fn index_repo(dir: &Path) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join(INDEX_FILE), INDEX).unwrap();
+    for args in [
+        &["init", "--quiet"][..],
+        &["add", "."],
+        &[
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "user.name=Test User",
+            "commit",
+            "--quiet",
+            "-m",
+            "index",
+        ],
+    ] {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+}
+
+#[test]
+fn search_matches_name_description_and_tags() {
+    let index = PackageIndex::parse(INDEX).unwrap();
+    let names = |term: &str| -> Vec<String> {
+        index
+            .search(term)
+            .into_iter()
+            .map(|e| e.name.clone())
+            .collect()
+    };
+    assert_eq!(names("SECURITY"), ["commit", "review-kit"]);
+    assert_eq!(names("commit"), ["commit"]);
+    assert_eq!(names("conventional"), ["commit"]);
+    assert_eq!(names("review"), ["commit", "review-kit"]);
+    assert_eq!(names("review-kit"), ["review-kit"]);
+    assert_eq!(names("").len(), 2);
+    assert!(names("deploy").is_empty());
+}
+
+#[test]
+fn is_index_name_distinguishes_names_from_git_sources() {
+    assert!(is_index_name("review-kit"));
+    assert!(is_index_name("review-kit@v1.2.0"));
+    assert!(!is_index_name("acme/review-kit"));
+    assert!(!is_index_name("github.com/acme/review-kit"));
+    assert!(!is_index_name("git@github.com:acme/review-kit"));
+    assert!(!is_index_name("https://example.com/acme/review-kit.git"));
+    assert!(!is_index_name("@v1"));
+}
+
+#[test]
+fn resolve_index_source_looks_up_bare_names() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("index");
+    index_repo(&repo);
+    let registry = repo.to_str().unwrap();
+    let cache = tmp.path().join("cache");
+
+    assert_eq!(
+        resolve_index_source("review-kit@v1.2.0", Some(registry), &cache).unwrap(),
+        "acme/review-kit@v1.2.0"
+    );
+    assert_eq!(
+        resolve_index_source("commit", Some(registry), &cache).unwrap(),
+        "https://example.com/acme/commit.git"
+    );
+    assert_eq!(
+        resolve_index_source("acme/other", None, &cache).unwrap(),
+        "acme/other"
+    );
+    let missing = resolve_index_source("deploy", Some(registry), &cache).unwrap_err();
+    assert!(
+        missing.to_string().contains("not found in index"),
+        "{missing}"
+    );
+    let unconfigured = resolve_index_source("commit", None, &cache).unwrap_err();
+    assert!(
+        unconfigured
+            .to_string()
+            .contains("no package index is configured"),
+        "{unconfigured}"
+    );
+}
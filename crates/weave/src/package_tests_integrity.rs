@@ -0,0 +1,160 @@
+//! Checkout checksum tests for the `package` module.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use super::package_git::{ensure_cached, resolve_commit};
+use super::package_integrity::ensure_checkout;
+use super::*;
+
+fn local_skill(dir: &Path) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("SKILL.md"), "# Skill\n").unwrap();
+    std::fs::write(dir.join("notes.txt"), "notes\n").unwrap();
+}
+
+#[test]
+fn checkout_checksum_tracks_content_names_and_new_files() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("pkg");
+    local_skill(&dir);
+    let original = checkout_checksum(&dir).unwrap();
+    assert!(original.starts_with(CHECKSUM_PREFIX));
+    assert_eq!(checkout_checksum(&dir).unwrap(), original);
+
+    std::fs::create_dir_all(dir.join(".git")).unwrap();
+    std::fs::write(dir.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+    assert_eq!(
+        checkout_checksum(&dir).unwrap(),
+        original,
+        ".git is ignored"
+    );
+
+    std::fs::write(dir.join("notes.txt"), "edited\n").unwrap();
+    let edited = checkout_checksum(&dir).unwrap();
+    assert_ne!(edited, original);
+
+    std::fs::rename(dir.join("notes.txt"), dir.join("moved.txt")).unwrap();
+    assert_ne!(checkout_checksum(&dir).unwrap(), edited);
+
+    std::fs::write(dir.join("extra.md"), "").unwrap();
+    assert_ne!(checkout_checksum(&dir).unwrap(), edited);
+}
+
+#[test]
+fn audit_reports_modified_checkout_and_lock_keeps_checksum() {
+    let tmp = TempDir::new().unwrap();
+    let project = tmp.path().join("project");
+    let store = tmp.path().join("store");
+    std::fs::create_dir_all(&project).unwrap();
+    let src = tmp.path().join("my-skill");
+    local_skill(&src);
+
+    let pkg = install_from_local(&src, &project, &store).unwrap();
+    let recorded = pkg.checksum.clone().expect("install records a checksum");
+    assert!(verify_checkout(&store, &pkg).is_none());
+    assert!(audit(&project, &store).unwrap().is_empty());
+
+    let checkout = package_dir(&store, "my-skill", "local").unwrap();
+    std::fs::write(checkout.join("SKILL.md"), "# Injected\n").unwrap();
+
+    let mismatch = verify_checkout(&store, &pkg).expect("modified checkout is detected");
+    assert_eq!(mismatch.expected, recorded);
+    assert!(mismatch.actual.is_some_and(|actual| actual != recorded));
+    let results = audit(&project, &store).unwrap();
+    assert!(
+        results[0]
+            .issues
+            .iter()
+            .any(|issue| matches!(issue, AuditIssue::ChecksumMismatch(_)))
+    );
+
+    let relocked = lock(&project, &store).unwrap();
+    assert_eq!(
+        relocked.package[0].checksum.as_deref(),
+        Some(recorded.as_str())
+    );
+}
+
+#[test]
+fn lock_records_checksum_for_entries_without_one() {
+    let tmp = TempDir::new().unwrap();
+    let project = tmp.path().join("project");
+    let store = tmp.path().join("store");
+    std::fs::create_dir_all(&project).unwrap();
+    let src = tmp.path().join("my-skill");
+    local_skill(&src);
+    let mut pkg = install_from_local(&src, &project, &store).unwrap();
+    let recorded = pkg.checksum.take();
+    save_lockfile(
+        &lockfile_path(&project),
+        &Lockfile::with_packages(vec![pkg]),
+    )
+    .unwrap();
+
+    let relocked = lock(&project, &store).unwrap();
+    assert_eq!(relocked.package[0].checksum, recorded);
+}
+
+#[test]
+fn ensure_checkout_replaces_checkout_that_no_longer_matches() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("repo");
+    local_skill(&repo);
+    for args in [
+        &["init", "--quiet"][..],
+        &[
+            "-c",
+            "user.email=t@example.com",
+            "-c",
+            "user.name=T",
+            "add",
+            ".",
+        ],
+        &[
+            "-c",
+            "user.email=t@example.com",
+            "-c",
+            "user.name=T",
+            "commit",
+            "--quiet",
+            "-m",
+            "init",
+        ],
+    ] {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+    let cas = ensure_cached(&tmp.path().join("cache"), repo.to_str().unwrap()).unwrap();
+    let commit = resolve_commit(&cas, None).unwrap();
+    let dest = tmp.path().join("store/repo").join(&commit[..8]);
+
+    let checksum = ensure_checkout(&cas, &commit, &dest, None).unwrap();
+    std::fs::write(dest.join("SKILL.md"), "# Injected\n").unwrap();
+
+    let restored = ensure_checkout(&cas, &commit, &dest, Some(&checksum)).unwrap();
+    assert_eq!(restored, checksum);
+    assert_eq!(
+        std::fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+        "# Skill\n"
+    );
+
+    // Without a locked checksum the checkout is compared with the commit.
+    std::fs::write(dest.join("SKILL.md"), "# Injected\n").unwrap();
+    let unlocked = ensure_checkout(&cas, &commit, &dest, None).unwrap();
+    assert_eq!(unlocked, checksum);
+    assert_eq!(
+        std::fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+        "# Skill\n"
+    );
+    let leftovers: Vec<_> = std::fs::read_dir(dest.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(leftovers, vec![std::ffi::OsString::from(&commit[..8])]);
+}
@@ -141,6 +141,7 @@ fn migrate_creates_weave_lock_from_legacy() {
         source_kind: SourceKind::Local,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     save_lockfile(&legacy, &lockfile).unwrap();
 
@@ -174,6 +175,7 @@ fn migrate_skips_valid_checkout_in_global_store() {
         source_kind: SourceKind::Git,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     save_lockfile(&legacy, &lockfile).unwrap();
     let result = migrate(tmp.path(), &cache, &store).unwrap();
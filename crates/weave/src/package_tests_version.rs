@@ -0,0 +1,188 @@
+//! Version-constraint tests for the `package` module.
+
+use std::process::Command;
+
+use semver::Version;
+use tempfile::TempDir;
+
+use super::package_git::ensure_cached;
+use super::package_version::{release_version, resolve_spec};
+use super::*;
+
+fn run_git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Repo with releases `v1.0.0`, `v1.5.0` (annotated) and `v2.0.0`; returns
+/// the repo path and the commit of each release.
+fn release_repo(dir: &Path) -> Vec<String> {
+    std::fs::create_dir_all(dir).unwrap();
+    run_git(dir, &["init", "--quiet"]);
+    run_git(dir, &["config", "user.email", "test@example.com"]);
+    run_git(dir, &["config", "user.name", "Test"]);
+    let mut commits = Vec::new();
+    for version in ["1.0.0", "1.5.0", "2.0.0"] {
+        std::fs::write(dir.join("SKILL.md"), format!("# lib {version}\n")).unwrap();
+        std::fs::write(
+            dir.join(".skill.toml"),
+            format!("[skill]\nname = \"lib\"\nversion = \"{version}\"\n"),
+        )
+        .unwrap();
+        run_git(dir, &["add", "."]);
+        run_git(dir, &["commit", "--quiet", "-m", version]);
+        let tag = format!("v{version}");
+        if version == "1.5.0" {
+            run_git(dir, &["tag", "-a", &tag, "-m", &tag]);
+        } else {
+            run_git(dir, &["tag", &tag]);
+        }
+        commits.push(run_git(dir, &["rev-parse", "HEAD"]));
+    }
+    commits
+}
+
+fn locked(name: &str, repo: &str, commit: &str, requested: Option<&str>) -> LockedPackage {
+    LockedPackage {
+        name: name.to_string(),
+        repo: repo.to_string(),
+        commit: commit.to_string(),
+        version: Some("1.0.0".to_string()),
+        source_kind: SourceKind::Git,
+        requested_version: requested.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+/// Local-source package `app` whose manifest requires `lib` at `requirement`.
+fn local_dependent(store: &Path, requirement: &str) -> LockedPackage {
+    let dir = package_dir(store, "app", "local").unwrap();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join(".skill.toml"),
+        format!(
+            "[skill]\nname = \"app\"\n\n[dependencies]\nlib = {{ version = \"{requirement}\" }}\n"
+        ),
+    )
+    .unwrap();
+    LockedPackage {
+        name: "app".to_string(),
+        repo: String::new(),
+        commit: String::new(),
+        version: None,
+        source_kind: SourceKind::Local,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn is_version_req_requires_an_operator_or_comma() {
+    assert!(is_version_req(">=1.2, <2"));
+    assert!(is_version_req("^1.2"));
+    assert!(is_version_req("~1.2.3"));
+    assert!(is_version_req("*"));
+    assert!(!is_version_req("v1.2.0"));
+    assert!(!is_version_req("1.2"));
+    assert!(!is_version_req("main"));
+    assert!(!is_version_req(">=banana"));
+}
+
+#[test]
+fn upgrade_moves_constrained_package_to_newest_matching_tag() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("lib");
+    let commits = release_repo(&repo);
+    let project = tmp.path().join("project");
+    let cache = tmp.path().join("cache");
+    let store = tmp.path().join("store");
+    std::fs::create_dir_all(&project).unwrap();
+    save_lockfile(
+        &lockfile_path(&project),
+        &Lockfile::with_packages(vec![locked(
+            "lib",
+            repo.to_str().unwrap(),
+            &commits[0],
+            Some(">=1.0, <2"),
+        )]),
+    )
+    .unwrap();
+
+    let results = upgrade(&project, &cache, &store, false).unwrap();
+    assert!(matches!(results[0].status, UpgradeStatus::Upgraded { .. }));
+    let pkg = &results[0].package;
+    assert_eq!(pkg.commit, commits[1]);
+    assert_eq!(pkg.resolved_ref.as_deref(), Some("v1.5.0"));
+    assert_eq!(pkg.version.as_deref(), Some("1.5.0"));
+    assert_eq!(pkg.requested_version.as_deref(), Some(">=1.0, <2"));
+}
+
+#[test]
+fn incompatible_requirements_are_reported() {
+    let tmp = TempDir::new().unwrap();
+    let repo = tmp.path().join("lib");
+    let commits = release_repo(&repo);
+    let project = tmp.path().join("project");
+    let cache = tmp.path().join("cache");
+    let store = tmp.path().join("store");
+    std::fs::create_dir_all(&project).unwrap();
+    let lockfile = Lockfile::with_packages(vec![
+        locked("lib", repo.to_str().unwrap(), &commits[0], None),
+        local_dependent(&store, ">=2"),
+    ]);
+    save_lockfile(&lockfile_path(&project), &lockfile).unwrap();
+
+    let cas = ensure_cached(&cache, repo.to_str().unwrap()).unwrap();
+    let (commit, tag) = resolve_spec(&cas, "lib", Some("^2"), &lockfile, &store).unwrap();
+    assert_eq!(
+        (commit.as_str(), tag.as_deref()),
+        (commits[2].as_str(), Some("v2.0.0"))
+    );
+    let err = resolve_spec(&cas, "lib", Some("<2"), &lockfile, &store).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no release tag of 'lib' satisfies every requirement: \
+         requested requires <2; app requires >=2"
+    );
+
+    let conflicts = version_conflicts(&project, &store).unwrap();
+    let audited = audit(&project, &store).unwrap();
+    let app = audited.iter().find(|r| r.name == "app").unwrap();
+    assert!(
+        app.issues
+            .iter()
+            .any(|issue| matches!(issue, AuditIssue::VersionConflict(c) if c.package == "lib"))
+    );
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].dependent, "app");
+    assert_eq!(
+        conflicts[0].to_string(),
+        "requires lib >=2, but 1.0.0 is locked"
+    );
+}
+
+#[test]
+fn release_tags_may_carry_the_member_name() {
+    assert_eq!(
+        release_version("v1.2.0", "lint"),
+        Some(Version::new(1, 2, 0))
+    );
+    assert_eq!(
+        release_version("1.2.0", "lint"),
+        Some(Version::new(1, 2, 0))
+    );
+    assert_eq!(
+        release_version("lint-v1.2.0", "lint"),
+        Some(Version::new(1, 2, 0))
+    );
+    assert_eq!(release_version("review-v1.2.0", "lint"), None);
+}
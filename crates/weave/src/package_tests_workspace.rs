@@ -0,0 +1,69 @@
+//! Workspace member tests for the `package` module.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use super::package_git::{ensure_cached, resolve_commit};
+use super::package_integrity::ensure_checkout;
+use super::package_workspace::{check_member, member_tree};
+use super::*;
+use crate::workspace::WORKSPACE_MANIFEST;
+
+/// Bare-repo cache of a workspace with `skills/review` and `skills/commit`.
+fn cached_workspace(tmp: &Path) -> (PathBuf, String) {
+    let repo = tmp.join("repo");
+    for member in ["review", "commit"] {
+        let dir = repo.join("skills").join(member);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), format!("# {member}\n")).unwrap();
+    }
+    std::fs::write(
+        repo.join(WORKSPACE_MANIFEST),
+        "[workspace]\nmembers = [\"skills/*\"]\n",
+    )
+    .unwrap();
+    let identity = ["-c", "user.email=t@example.com", "-c", "user.name=T"];
+    for args in [
+        vec!["init", "--quiet"],
+        [&identity[..], &["add", "."]].concat(),
+        [&identity[..], &["commit", "--quiet", "-m", "init"]].concat(),
+    ] {
+        let status = Command::new("git")
+            .args(&args)
+            .current_dir(&repo)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+    let cas = ensure_cached(&tmp.join("cache"), repo.to_str().unwrap()).unwrap();
+    let commit = resolve_commit(&cas, None).unwrap();
+    (cas, commit)
+}
+
+#[test]
+fn check_member_requires_a_listed_member() {
+    let tmp = TempDir::new().unwrap();
+    let (cas, commit) = cached_workspace(tmp.path());
+
+    check_member(&cas, &commit, "skills/review").unwrap();
+    let err = check_member(&cas, &commit, "docs").unwrap_err();
+    assert!(err.to_string().contains("not a workspace member"));
+}
+
+#[test]
+fn member_checkout_contains_only_the_member_directory() {
+    let tmp = TempDir::new().unwrap();
+    let (cas, commit) = cached_workspace(tmp.path());
+    let dest = tmp.path().join("store/review").join(&commit[..8]);
+
+    let tree = member_tree(&commit, Some("skills/review"));
+    let checksum = ensure_checkout(&cas, &tree, &dest, None).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+        "# review\n"
+    );
+    assert!(!dest.join(WORKSPACE_MANIFEST).exists());
+    assert_eq!(checksum, checkout_checksum(&dest).unwrap());
+    assert!(checkout_drift(&cas, &tree, &dest).unwrap().is_empty());
+}
@@ -3,6 +3,7 @@
 use std::process::Command;
 use tempfile::TempDir;
 
+use super::package_git::resolve_commit;
 use super::*;
 
 // ---------------------------------------------------------------------------
@@ -86,6 +87,7 @@ fn manual_install(
         source_kind: SourceKind::Git,
         requested_version: requested_version.map(|s| s.to_string()),
         resolved_ref: requested_version.map(|s| s.to_string()),
+        ..Default::default()
     };
     let lock_path = lockfile_path(project_root);
     let mut lockfile = load_lockfile(&lock_path).unwrap_or_default();
@@ -155,6 +157,7 @@ fn upgrade_skips_local_packages() {
         source_kind: SourceKind::Local,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     save_lockfile(&lockfile_path(&project), &lf).unwrap();
 
@@ -369,6 +372,7 @@ fn upgrade_mixed_packages() {
             source_kind: SourceKind::Local,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
         LockedPackage {
             name: "no-repo".to_string(),
@@ -378,6 +382,7 @@ fn upgrade_mixed_packages() {
             source_kind: SourceKind::Git,
             requested_version: None,
             resolved_ref: None,
+            ..Default::default()
         },
     ]);
     save_lockfile(&lockfile_path(&project), &lf).unwrap();
@@ -414,6 +419,7 @@ fn upgrade_skips_empty_repo_packages() {
         source_kind: SourceKind::Git,
         requested_version: None,
         resolved_ref: None,
+        ..Default::default()
     }]);
     save_lockfile(&lockfile_path(&project), &lf).unwrap();
 
@@ -459,3 +465,66 @@ fn upgrade_preserves_lockfile_versions_section() {
     );
     assert_eq!(before, after, "no-op upgrade must not rewrite weave.lock");
 }
+
+#[test]
+fn branch_and_tag_pins_are_skipped_and_forced_by_kind() {
+    let tmp = TempDir::new().unwrap();
+    let (work, remote) = setup_git_repo(tmp.path());
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let cache = tmp.path().join("cache");
+    let store = tmp.path().join("store");
+
+    // An annotated tag on v1 plus a branch pin, recorded as `--tag`/`--branch` do.
+    let status = Command::new("git")
+        .args(["tag", "-a", "v1.0.0", "-m", "v1"])
+        .current_dir(&work)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = Command::new("git")
+        .args(["push", "--quiet", "origin", "v1.0.0"])
+        .env("CSA_GIT_PUSH_ALLOWED", "true")
+        .current_dir(&work)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let first = manual_install(&remote, &cache, &store, &project, "test-skill", None);
+    let tagged = manual_install(&remote, &cache, &store, &project, "tagged", None);
+    assert_eq!(first.commit, tagged.commit);
+    let lock_path = lockfile_path(&project);
+    let mut lockfile = load_lockfile(&lock_path).unwrap();
+    for (pkg, target, resolved) in [
+        (0, "main", "refs/heads/main"),
+        (1, "v1.0.0", "refs/tags/v1.0.0"),
+    ] {
+        lockfile.package[pkg].requested_version = Some(target.to_string());
+        lockfile.package[pkg].resolved_ref = Some(resolved.to_string());
+    }
+    save_lockfile(&lock_path, &lockfile).unwrap();
+
+    let new_commit = push_new_version(&work, "2.0.0");
+
+    let results = upgrade(&project, &cache, &store, false).unwrap();
+    let reasons: Vec<String> = results
+        .iter()
+        .map(|entry| match &entry.status {
+            UpgradeStatus::Skipped { reason } => reason.clone(),
+            other => panic!("expected skip, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            "pinned to branch main — use --force to override",
+            "pinned to tag v1.0.0 — use --force to override",
+        ]
+    );
+
+    // --force follows the branch head but stays on the tag's peeled commit.
+    let results = upgrade(&project, &cache, &store, true).unwrap();
+    assert!(matches!(results[0].status, UpgradeStatus::Upgraded { .. }));
+    assert_eq!(results[0].package.commit, new_commit);
+    assert_eq!(results[1].status, UpgradeStatus::AlreadyLatest);
+    assert_eq!(results[1].package.commit, first.commit);
+}
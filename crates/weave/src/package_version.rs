@@ -0,0 +1,197 @@
+//! Semver constraints on weave packages.
+//!
+//! A package can be installed with a version requirement instead of a git ref
+//! (`weave install user/repo@'>=1.2, <2'`), and skills can declare
+//! requirements on other packages under `[dependencies]` in `.skill.toml`.
+//! Requirements resolve to the newest release tag (`v1.2.3` or `1.2.3`) that
+//! satisfies all of them.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use semver::{Version, VersionReq};
+
+use super::package_git::resolve_commit;
+use super::package_graph::DependencyGraph;
+use super::{LockedPackage, Lockfile, SourceKind, load_project_lockfile, package_dir};
+
+/// A dependency requirement that the locked packages do not satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConflict {
+    /// Package whose `.skill.toml` declares the requirement.
+    pub dependent: String,
+    /// Required package.
+    pub package: String,
+    pub requirement: String,
+    /// Locked version of `package`; `None` when it is not installed or has none.
+    pub locked: Option<String>,
+    pub installed: bool,
+}
+
+impl std::fmt::Display for VersionConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "requires {} {}, but ", self.package, self.requirement)?;
+        match (&self.locked, self.installed) {
+            (_, false) => write!(f, "it is not installed"),
+            (Some(version), true) => write!(f, "{version} is locked"),
+            (None, true) => write!(f, "the locked checkout has no version"),
+        }
+    }
+}
+
+/// Whether an install ref is a version requirement rather than a git ref.
+///
+/// Only requirements with an operator or a comma qualify, so tags and
+/// branches such as `v1.2` or `main` keep their git meaning.
+pub fn is_version_req(spec: &str) -> bool {
+    let spec = spec.trim();
+    (spec.starts_with(['^', '~', '=', '<', '>', '*']) || spec.contains(','))
+        && VersionReq::parse(spec).is_ok()
+}
+
+/// Resolve `spec` for package `name` to `(commit, resolved_ref)`.
+///
+/// A version requirement resolves to the newest tag satisfying it and every
+/// requirement other locked packages place on `name`; anything else is
+/// resolved as a plain git ref.
+pub(super) fn resolve_spec(
+    cas_dir: &Path,
+    name: &str,
+    spec: Option<&str>,
+    lockfile: &Lockfile,
+    store_root: &Path,
+) -> Result<(String, Option<String>)> {
+    let Some(req) = spec.filter(|spec| is_version_req(spec)) else {
+        return Ok((resolve_commit(cas_dir, spec)?, spec.map(str::to_string)));
+    };
+    let mut requirements = vec![("requested".to_string(), req.trim().to_string())];
+    requirements.extend(dependent_requirements(lockfile, store_root, name));
+    let tag = newest_matching_tag(cas_dir, name, &requirements)?;
+    let commit = resolve_commit(cas_dir, Some(&format!("refs/tags/{tag}^{{commit}}")))?;
+    Ok((commit, Some(tag)))
+}
+
+/// Newest tag of the repo in `cas_dir` satisfying all `(who, requirement)` pairs.
+fn newest_matching_tag(
+    cas_dir: &Path,
+    name: &str,
+    requirements: &[(String, String)],
+) -> Result<String> {
+    let reqs = requirements
+        .iter()
+        .map(|(who, req)| {
+            VersionReq::parse(req)
+                .with_context(|| format!("{who}: invalid version requirement '{req}' on {name}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let best = list_tags(cas_dir)?
+        .into_iter()
+        .filter_map(|tag| Some((release_version(&tag, name)?, tag)))
+        .filter(|(version, _)| reqs.iter().all(|req| req.matches(version)))
+        .max_by(|a, b| a.0.cmp(&b.0));
+    match best {
+        Some((_, tag)) => Ok(tag),
+        None => {
+            let wanted: Vec<String> = requirements
+                .iter()
+                .map(|(who, req)| format!("{who} requires {req}"))
+                .collect();
+            bail!(
+                "no release tag of '{name}' satisfies every requirement: {}",
+                wanted.join("; ")
+            )
+        }
+    }
+}
+
+/// Requirements on `name` declared by the other locked packages' manifests.
+fn dependent_requirements(
+    lockfile: &Lockfile,
+    store_root: &Path,
+    name: &str,
+) -> Vec<(String, String)> {
+    DependencyGraph::from_lockfile(lockfile, store_root)
+        .dependents(name)
+        .into_iter()
+        .filter(|(dependent, _)| *dependent != name)
+        .map(|(dependent, req)| (dependent.to_string(), req.to_string()))
+        .collect()
+}
+
+/// Check every `[dependencies]` requirement of the locked packages against
+/// the versions in `weave.lock`.
+pub fn version_conflicts(project_root: &Path, store_root: &Path) -> Result<Vec<VersionConflict>> {
+    let lockfile = load_project_lockfile(project_root).unwrap_or_default();
+    let graph = DependencyGraph::from_lockfile(&lockfile, store_root);
+    let mut conflicts = Vec::new();
+    for (dependent, edges) in &graph.edges {
+        for edge in edges {
+            let locked = graph.packages.get(&edge.package);
+            let version = locked.and_then(|p| p.version.clone());
+            if !version_satisfies(&edge.requirement, version.as_deref()) {
+                conflicts.push(VersionConflict {
+                    dependent: dependent.clone(),
+                    package: edge.package.clone(),
+                    requirement: edge.requirement.clone(),
+                    locked: version,
+                    installed: locked.is_some(),
+                });
+            }
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Whether `version` satisfies `requirement`; a missing version or an
+/// unparsable requirement never does.
+pub fn version_satisfies(requirement: &str, version: Option<&str>) -> bool {
+    match (
+        VersionReq::parse(requirement),
+        version.and_then(tag_version),
+    ) {
+        (Ok(req), Some(version)) => req.matches(&version),
+        _ => false,
+    }
+}
+
+pub(super) fn locked_checkout(store_root: &Path, pkg: &LockedPackage) -> Option<PathBuf> {
+    let key = match pkg.source_kind {
+        SourceKind::Local => "local",
+        SourceKind::Git if pkg.commit.is_empty() => return None,
+        SourceKind::Git => &pkg.commit,
+    };
+    package_dir(store_root, &pkg.name, key).ok()
+}
+
+/// Version named by a release tag of `name`: `v1.2.3`, `1.2.3`, or
+/// `name-v1.2.3` as `weave publish` tags workspace members.
+pub(super) fn release_version(tag: &str, name: &str) -> Option<Version> {
+    let unprefixed = tag
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('-'));
+    tag_version(unprefixed.unwrap_or(tag))
+}
+
+/// Semver of a release tag or version string, with an optional `v` prefix.
+fn tag_version(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+fn list_tags(cas_dir: &Path) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["tag", "--list"])
+        .current_dir(cas_dir)
+        .output()
+        .context("failed to run git tag")?;
+    if !output.status.success() {
+        bail!(
+            "git tag failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
@@ -0,0 +1,40 @@
+//! Installing single members of workspace repositories from git.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use crate::workspace::{WORKSPACE_MANIFEST, WorkspaceManifest};
+
+/// Tree-ish to check out: the whole `commit`, or only the `member` directory.
+pub(super) fn member_tree(commit: &str, member: Option<&str>) -> String {
+    match member {
+        Some(path) => format!("{commit}:{path}"),
+        None => commit.to_string(),
+    }
+}
+
+/// Fail unless the workspace manifest of `commit` lists `member`.
+pub(super) fn check_member(cas_dir: &Path, commit: &str, member: &str) -> Result<()> {
+    let short = &commit[..commit.len().min(8)];
+    let output = Command::new("git")
+        .args(["show", &format!("{commit}:{WORKSPACE_MANIFEST}")])
+        .current_dir(cas_dir)
+        .output()
+        .context("failed to run git show")?;
+    if !output.status.success() {
+        bail!(
+            "repository has no {WORKSPACE_MANIFEST} at {short}; cannot install --member {member}"
+        );
+    }
+    let manifest = WorkspaceManifest::parse(&String::from_utf8_lossy(&output.stdout))
+        .with_context(|| format!("failed to parse {WORKSPACE_MANIFEST} at {short}"))?;
+    if !manifest.includes(member) {
+        bail!(
+            "'{member}' is not a workspace member at {short} (members: {})",
+            manifest.workspace.members.join(", ")
+        );
+    }
+    Ok(())
+}
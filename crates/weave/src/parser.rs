@@ -7,8 +7,11 @@
 use anyhow::{Context, Result, bail};
 use regex::Regex;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
+use crate::inputs::VariableSpec;
+
 // ---------------------------------------------------------------------------
 // AST types
 // ---------------------------------------------------------------------------
@@ -36,6 +39,9 @@ pub struct SkillMeta {
     pub model: Option<String>,
     #[serde(default)]
     pub version: Option<String>,
+    /// Typed run inputs from `[variables.NAME]` tables.
+    #[serde(default)]
+    pub variables: BTreeMap<String, VariableSpec>,
 }
 
 /// Configuration from a `.skill.toml` sidecar file.
@@ -44,6 +50,19 @@ pub struct SkillConfig {
     pub skill: SkillConfigMeta,
     #[serde(default)]
     pub agent: Option<AgentConfig>,
+    /// Other weave packages this skill needs, keyed by package name.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, DependencySpec>,
+}
+
+/// `[dependencies]` entry of `.skill.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DependencySpec {
+    /// Semver requirement on the dependency's release tags, e.g. `>=1.2, <2`.
+    pub version: String,
+    /// Where the dependency is published; informational.
+    #[serde(default)]
+    pub repo: Option<String>,
 }
 
 /// `[skill]` section of `.skill.toml`.
@@ -123,12 +142,51 @@ pub enum Block {
         collection: String,
         body: Vec<Block>,
     },
+    /// Steps repeated while (or until) `condition` holds, at most
+    /// `max_iterations` times.
+    Repeat {
+        mode: RepeatMode,
+        condition: String,
+        max_iterations: u32,
+        body: Vec<Block>,
+    },
+    /// Steps that may run concurrently, at most `max_concurrency` at a time.
+    Parallel {
+        max_concurrency: Option<u32>,
+        body: Vec<Block>,
+    },
+    /// Steps expanded once per value at compile time, with `${variable}`
+    /// replaced by the value.
+    Matrix {
+        variable: String,
+        values: Vec<String>,
+        body: Vec<Block>,
+    },
     Include {
         path: String,
     },
     RawMarkdown(String),
 }
 
+/// When a `WHILE`/`UNTIL` block checks its condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepeatMode {
+    /// Checked before each iteration; runs zero or more times.
+    While,
+    /// Checked after each iteration; runs at least once.
+    Until,
+}
+
+impl RepeatMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::While => "while",
+            Self::Until => "until",
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Regex patterns
 // ---------------------------------------------------------------------------
@@ -151,6 +209,28 @@ static FOR_RE: LazyLock<Regex> =
 
 static ENDFOR_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^##\s+ENDFOR\s*$").unwrap());
 
+static REPEAT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+(WHILE|UNTIL)\s+(.+)$").unwrap());
+
+static ENDREPEAT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+END(WHILE|UNTIL)\s*$").unwrap());
+
+/// Trailing `MAX <n>` guard on a `WHILE`/`UNTIL` header.
+static REPEAT_MAX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(.+?)\s+MAX\s+(\d+)$").unwrap());
+
+static PARALLEL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+PARALLEL(?:\s+MAX\s+(\d+))?\s*$").unwrap());
+
+static ENDPARALLEL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+ENDPARALLEL\s*$").unwrap());
+
+static MATRIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+MATRIX\s+(\w+)\s+IN\s+(.+)$").unwrap());
+
+static ENDMATRIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^##\s+ENDMATRIX\s*$").unwrap());
+
 static INCLUDE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^##\s+INCLUDE\s+(.+)$").unwrap());
 
@@ -194,7 +274,7 @@ pub fn parse_skill(content: &str) -> Result<SkillDocument> {
 // ---------------------------------------------------------------------------
 
 /// Split frontmatter from body and parse the TOML metadata.
-fn parse_frontmatter(content: &str) -> Result<(SkillMeta, &str)> {
+pub(crate) fn parse_frontmatter(content: &str) -> Result<(SkillMeta, &str)> {
     let trimmed = content.trim_start();
     if !trimmed.starts_with("---") {
         bail!("missing frontmatter: file must start with `---`");
@@ -225,12 +305,26 @@ fn parse_frontmatter(content: &str) -> Result<(SkillMeta, &str)> {
 
 /// Intermediate line classification.
 #[derive(Debug)]
-enum LineKind<'a> {
+pub(crate) enum LineKind<'a> {
     If(&'a str),
     Else,
     EndIf,
-    For { var: &'a str, collection: &'a str },
+    For {
+        var: &'a str,
+        collection: &'a str,
+    },
     EndFor,
+    /// `WHILE`/`UNTIL` header text after the keyword, guard included.
+    Repeat(RepeatMode, &'a str),
+    EndRepeat(RepeatMode),
+    /// Raw `MAX` value, validated when the block is parsed.
+    Parallel(Option<&'a str>),
+    EndParallel,
+    Matrix {
+        var: &'a str,
+        values: &'a str,
+    },
+    EndMatrix,
     Include(&'a str),
     Step(&'a str),
     Text(&'a str),
@@ -242,7 +336,7 @@ fn cap_str<'a>(caps: &regex::Captures<'a>, group: usize) -> Option<&'a str> {
     caps.get(group).map(|m| m.as_str())
 }
 
-fn classify_line(line: &str) -> LineKind<'_> {
+pub(crate) fn classify_line(line: &str) -> LineKind<'_> {
     let trimmed = line.trim_end();
 
     if let Some(caps) = IF_RE.captures(trimmed)
@@ -267,6 +361,30 @@ fn classify_line(line: &str) -> LineKind<'_> {
     if ENDFOR_RE.is_match(trimmed) {
         return LineKind::EndFor;
     }
+    if let Some(caps) = REPEAT_RE.captures(trimmed)
+        && let (Some(keyword), Some(header)) = (cap_str(&caps, 1), cap_str(&caps, 2))
+    {
+        return LineKind::Repeat(repeat_mode(keyword), header);
+    }
+    if let Some(caps) = ENDREPEAT_RE.captures(trimmed)
+        && let Some(keyword) = cap_str(&caps, 1)
+    {
+        return LineKind::EndRepeat(repeat_mode(keyword));
+    }
+    if let Some(caps) = PARALLEL_RE.captures(trimmed) {
+        return LineKind::Parallel(cap_str(&caps, 1));
+    }
+    if ENDPARALLEL_RE.is_match(trimmed) {
+        return LineKind::EndParallel;
+    }
+    if let Some(caps) = MATRIX_RE.captures(trimmed)
+        && let (Some(var), Some(values)) = (cap_str(&caps, 1), cap_str(&caps, 2))
+    {
+        return LineKind::Matrix { var, values };
+    }
+    if ENDMATRIX_RE.is_match(trimmed) {
+        return LineKind::EndMatrix;
+    }
     if let Some(caps) = INCLUDE_RE.captures(trimmed)
         && let Some(path) = cap_str(&caps, 1)
     {
@@ -281,8 +399,59 @@ fn classify_line(line: &str) -> LineKind<'_> {
     LineKind::Text(line)
 }
 
+fn repeat_mode(keyword: &str) -> RepeatMode {
+    if keyword == "UNTIL" {
+        RepeatMode::Until
+    } else {
+        RepeatMode::While
+    }
+}
+
+/// Split a `WHILE`/`UNTIL` header into its condition and mandatory guard.
+fn parse_repeat_header(mode: RepeatMode, header: &str) -> Result<(String, u32)> {
+    let keyword = mode.as_str().to_uppercase();
+    let caps = REPEAT_MAX_RE.captures(header.trim()).with_context(|| {
+        format!("{keyword} block requires an iteration guard: `## {keyword} <condition> MAX <n>`")
+    })?;
+    let raw = &caps[2];
+    let max_iterations = raw
+        .parse::<u32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| format!("invalid {keyword} MAX `{raw}`: expected a positive integer"))?;
+    Ok((caps[1].trim().to_string(), max_iterations))
+}
+
+/// Parse the `[a, b, "c d"]` value list of a `MATRIX` header.
+fn parse_matrix_values(var: &str, raw: &str) -> Result<Vec<String>> {
+    let list = raw
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .with_context(|| format!("MATRIX {var} expects a list of values: `[a, b]`"))?;
+    let mut values = Vec::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        let value = item
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .unwrap_or(item);
+        if value.is_empty() {
+            bail!("MATRIX {var} has an empty value in `{raw}`");
+        }
+        if VAR_RE.is_match(value) {
+            bail!("MATRIX {var} values must be literals, got `{value}`");
+        }
+        if values.iter().any(|v| v == value) {
+            bail!("MATRIX {var} lists `{value}` more than once");
+        }
+        values.push(value.to_string());
+    }
+    Ok(values)
+}
+
 /// Extract unique `${VAR}` placeholders from text.
-fn extract_variables(text: &str) -> Vec<String> {
+pub(crate) fn extract_variables(text: &str) -> Vec<String> {
     let mut vars: Vec<String> = VAR_RE
         .captures_iter(text)
         .map(|c| c[1].to_string())
@@ -292,8 +461,8 @@ fn extract_variables(text: &str) -> Vec<String> {
     vars
 }
 
-/// Maximum nesting depth for IF/FOR blocks to prevent stack overflow on
-/// adversarial input.
+/// Maximum nesting depth for IF/FOR/WHILE/PARALLEL/MATRIX blocks to prevent stack
+/// overflow on adversarial input.
 const MAX_NESTING_DEPTH: usize = 64;
 
 /// Parse body lines into blocks. Operates recursively for nested structures.
@@ -313,6 +482,10 @@ fn parse_body(body: &str) -> Result<Vec<Block>> {
 const STOP_ELSE: &str = "ELSE";
 const STOP_ENDIF: &str = "ENDIF";
 const STOP_ENDFOR: &str = "ENDFOR";
+const STOP_ENDPARALLEL: &str = "ENDPARALLEL";
+const STOP_ENDWHILE: &str = "ENDWHILE";
+const STOP_ENDUNTIL: &str = "ENDUNTIL";
+const STOP_ENDMATRIX: &str = "ENDMATRIX";
 
 /// Parse a sequence of blocks, stopping when a line matches one of `stop_on`.
 ///
@@ -327,7 +500,7 @@ fn parse_blocks<'a>(
     if depth > MAX_NESTING_DEPTH {
         bail!(
             "nesting depth exceeds maximum ({MAX_NESTING_DEPTH}): \
-             too many nested IF/FOR blocks"
+             too many nested IF/FOR/WHILE/PARALLEL/MATRIX blocks"
         );
     }
     let mut blocks: Vec<Block> = Vec::new();
@@ -342,6 +515,10 @@ fn parse_blocks<'a>(
             LineKind::Else => stop_on.contains(&STOP_ELSE),
             LineKind::EndIf => stop_on.contains(&STOP_ENDIF),
             LineKind::EndFor => stop_on.contains(&STOP_ENDFOR),
+            LineKind::EndRepeat(RepeatMode::While) => stop_on.contains(&STOP_ENDWHILE),
+            LineKind::EndRepeat(RepeatMode::Until) => stop_on.contains(&STOP_ENDUNTIL),
+            LineKind::EndParallel => stop_on.contains(&STOP_ENDPARALLEL),
+            LineKind::EndMatrix => stop_on.contains(&STOP_ENDMATRIX),
             _ => false,
         };
         if is_stop {
@@ -409,6 +586,87 @@ fn parse_blocks<'a>(
                 continue;
             }
 
+            LineKind::Repeat(mode, header) => {
+                flush_raw(&mut raw_buf, &mut blocks);
+                let (condition, max_iterations) = parse_repeat_header(mode, header)?;
+                let (stop, keyword) = match mode {
+                    RepeatMode::While => (STOP_ENDWHILE, "WHILE"),
+                    RepeatMode::Until => (STOP_ENDUNTIL, "UNTIL"),
+                };
+                pos += 1;
+
+                let (body_blocks, rest) = parse_blocks(lines, pos, &[stop], depth + 1)?;
+
+                if rest.first().is_some_and(
+                    |l| matches!(classify_line(l), LineKind::EndRepeat(end) if end == mode),
+                ) {
+                    pos = lines.len() - rest.len() + 1;
+                } else {
+                    bail!("unclosed {keyword} block: missing ## END{keyword}");
+                }
+
+                blocks.push(Block::Repeat {
+                    mode,
+                    condition,
+                    max_iterations,
+                    body: body_blocks,
+                });
+                continue;
+            }
+
+            LineKind::Parallel(max) => {
+                flush_raw(&mut raw_buf, &mut blocks);
+                let max_concurrency = max
+                    .map(|raw| {
+                        raw.parse::<u32>().ok().filter(|n| *n > 0).with_context(|| {
+                            format!("invalid PARALLEL MAX `{raw}`: expected a positive integer")
+                        })
+                    })
+                    .transpose()?;
+                pos += 1;
+
+                let (body_blocks, rest) = parse_blocks(lines, pos, &[STOP_ENDPARALLEL], depth + 1)?;
+
+                if rest
+                    .first()
+                    .is_some_and(|l| ENDPARALLEL_RE.is_match(l.trim_end()))
+                {
+                    pos = lines.len() - rest.len() + 1;
+                } else {
+                    bail!("unclosed PARALLEL block: missing ## ENDPARALLEL");
+                }
+
+                blocks.push(Block::Parallel {
+                    max_concurrency,
+                    body: body_blocks,
+                });
+                continue;
+            }
+
+            LineKind::Matrix { var, values } => {
+                flush_raw(&mut raw_buf, &mut blocks);
+                let values = parse_matrix_values(var, values)?;
+                pos += 1;
+
+                let (body_blocks, rest) = parse_blocks(lines, pos, &[STOP_ENDMATRIX], depth + 1)?;
+
+                if rest
+                    .first()
+                    .is_some_and(|l| ENDMATRIX_RE.is_match(l.trim_end()))
+                {
+                    pos = lines.len() - rest.len() + 1;
+                } else {
+                    bail!("unclosed MATRIX block: missing ## ENDMATRIX");
+                }
+
+                blocks.push(Block::Matrix {
+                    variable: var.to_string(),
+                    values,
+                    body: body_blocks,
+                });
+                continue;
+            }
+
             LineKind::Include(path) => {
                 flush_raw(&mut raw_buf, &mut blocks);
                 blocks.push(Block::Include {
@@ -457,6 +715,16 @@ fn parse_blocks<'a>(
             LineKind::EndFor => {
                 bail!("unexpected ## ENDFOR without matching ## FOR");
             }
+            LineKind::EndRepeat(mode) => {
+                let keyword = mode.as_str().to_uppercase();
+                bail!("unexpected ## END{keyword} without matching ## {keyword}");
+            }
+            LineKind::EndParallel => {
+                bail!("unexpected ## ENDPARALLEL without matching ## PARALLEL");
+            }
+            LineKind::EndMatrix => {
+                bail!("unexpected ## ENDMATRIX without matching ## MATRIX");
+            }
 
             LineKind::Text(t) => {
                 if !raw_buf.is_empty() {
@@ -482,6 +750,14 @@ fn flush_raw(buf: &mut String, blocks: &mut Vec<Block>) {
     buf.clear();
 }
 
+#[path = "parser_include.rs"]
+mod include;
+pub use include::{MAX_INCLUDE_DEPTH, expand_includes, parse_skill_file};
+
 #[cfg(test)]
 #[path = "parser_tests.rs"]
 mod tests;
+
+#[cfg(test)]
+#[path = "parser_include_tests.rs"]
+mod include_tests;
@@ -0,0 +1,147 @@
+//! `@include` expansion for skill-lang files.
+//!
+//! A line `@include relative/path.md` is replaced by the contents of that
+//! file (minus any frontmatter) before parsing, so shared step libraries can
+//! be reused across skills. Paths resolve against the including file's
+//! directory and must stay inside the package root: the nearest ancestor of
+//! the top-level file holding a `.skill.toml` or `.git`. Lines inside code
+//! fences are never treated as directives.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use super::{MAX_INPUT_BYTES, SkillDocument, parse_skill};
+
+/// Maximum nesting of `@include` directives.
+pub const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Read, expand `@include` directives in, and parse the skill file at `path`.
+pub fn parse_skill_file(path: &Path) -> Result<SkillDocument> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let expanded = expand_includes(&content, path, &package_root(path)?)?;
+    parse_skill(&expanded)
+}
+
+/// Expand the `@include` directives in `content`, which was read from
+/// `path`. Included files must resolve inside `root`.
+pub fn expand_includes(content: &str, path: &Path, root: &Path) -> Result<String> {
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", root.display()))?;
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    let mut out = String::with_capacity(content.len());
+    expand(content, &path, &root, &mut vec![path.clone()], &mut out)?;
+    Ok(out)
+}
+
+fn expand(
+    content: &str,
+    path: &Path,
+    root: &Path,
+    stack: &mut Vec<PathBuf>,
+    out: &mut String,
+) -> Result<()> {
+    let dir = path.parent().unwrap_or(root);
+    let mut in_fence = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let target = (!in_fence)
+            .then(|| line.trim_end().strip_prefix("@include "))
+            .flatten()
+            .map(str::trim);
+        let Some(target) = target else {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        };
+
+        let included =
+            resolve(dir, target, root).with_context(|| format!("in {}", display(path, root)))?;
+        if let Some(start) = stack.iter().position(|p| *p == included) {
+            let cycle: Vec<String> = stack[start..]
+                .iter()
+                .chain([&included])
+                .map(|p| display(p, root))
+                .collect();
+            bail!("@include cycle: {}", cycle.join(" -> "));
+        }
+        if stack.len() > MAX_INCLUDE_DEPTH {
+            bail!(
+                "@include nesting exceeds {MAX_INCLUDE_DEPTH} levels at {}",
+                display(&included, root)
+            );
+        }
+        let text = std::fs::read_to_string(&included)
+            .with_context(|| format!("failed to read {}", included.display()))?;
+        stack.push(included.clone());
+        expand(strip_frontmatter(&text), &included, root, stack, out)?;
+        stack.pop();
+        if out.len() > MAX_INPUT_BYTES {
+            bail!("input with includes exceeds maximum size ({MAX_INPUT_BYTES} bytes)");
+        }
+    }
+    Ok(())
+}
+
+/// Canonical path of `target` relative to `dir`, which must stay in `root`.
+fn resolve(dir: &Path, target: &str, root: &Path) -> Result<PathBuf> {
+    if target.is_empty() {
+        bail!("@include needs a path");
+    }
+    if Path::new(target).is_absolute() {
+        bail!("@include path '{target}' must be relative");
+    }
+    let joined = dir.join(target);
+    let resolved = joined
+        .canonicalize()
+        .with_context(|| format!("@include '{target}': cannot resolve {}", joined.display()))?;
+    if !resolved.starts_with(root) {
+        bail!("@include '{target}' resolves outside the package root");
+    }
+    if !resolved.is_file() {
+        bail!("@include '{target}' is not a file");
+    }
+    Ok(resolved)
+}
+
+/// Body of an included file, without a leading `---` frontmatter block.
+fn strip_frontmatter(text: &str) -> &str {
+    let trimmed = text.trim_start();
+    let Some(after) = trimmed.strip_prefix("---") else {
+        return text;
+    };
+    match after.find("\n---") {
+        Some(idx) => {
+            let rest = &after[idx + 4..];
+            rest.split_once('\n').map_or("", |(_, body)| body)
+        }
+        None => text,
+    }
+}
+
+/// Nearest ancestor of `path` containing `.skill.toml` or `.git`, else the
+/// file's own directory.
+fn package_root(path: &Path) -> Result<PathBuf> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new("/"));
+    Ok(dir
+        .ancestors()
+        .find(|d| d.join(".skill.toml").exists() || d.join(".git").exists())
+        .unwrap_or(dir)
+        .to_path_buf())
+}
+
+fn display(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
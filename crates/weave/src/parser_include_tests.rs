@@ -0,0 +1,117 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+use super::*;
+
+/// A package root (marked by `.skill.toml`) with the given files.
+fn package(files: &[(&str, &str)]) -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    fs::write(tmp.path().join(".skill.toml"), "[skill]\nname = \"pkg\"\n").unwrap();
+    for (path, content) in files {
+        let path = tmp.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    tmp
+}
+
+fn step_titles(doc: &SkillDocument) -> Vec<&str> {
+    doc.body
+        .iter()
+        .filter_map(|block| match block {
+            Block::Step { title, .. } => Some(title.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn include_inlines_shared_steps_relative_to_the_including_file() {
+    let tmp = package(&[
+        (
+            "patterns/review/PATTERN.md",
+            "---\nname = \"review\"\n---\n## Start\nGo.\n@include ../../lib/checks.md\n## End\nDone.\n",
+        ),
+        (
+            "lib/checks.md",
+            "---\nname = \"checks\"\n---\n## Lint\nRun lint.\n@include nested/test.md\n",
+        ),
+        ("lib/nested/test.md", "## Test\nRun ${SUITE}.\n"),
+    ]);
+
+    let doc = parse_skill_file(&tmp.path().join("patterns/review/PATTERN.md")).unwrap();
+    assert_eq!(doc.meta.name, "review");
+    assert_eq!(step_titles(&doc), ["Start", "Lint", "Test", "End"]);
+}
+
+#[test]
+fn include_inside_code_fences_is_left_alone() {
+    let tmp = package(&[(
+        "PATTERN.md",
+        "---\nname = \"x\"\n---\n## Show\n```markdown\n@include missing.md\n```\n",
+    )]);
+    let doc = parse_skill_file(&tmp.path().join("PATTERN.md")).unwrap();
+    assert_eq!(step_titles(&doc), ["Show"]);
+}
+
+#[test]
+fn include_cycles_are_reported_with_the_chain() {
+    let tmp = package(&[
+        ("PATTERN.md", "---\nname = \"x\"\n---\n@include a.md\n"),
+        ("a.md", "## A\n@include b.md\n"),
+        ("b.md", "## B\n@include a.md\n"),
+    ]);
+    let err = parse_skill_file(&tmp.path().join("PATTERN.md")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("@include cycle: a.md -> b.md -> a.md"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn include_depth_is_limited() {
+    let mut files = vec![(
+        "PATTERN.md".to_string(),
+        "---\nname = \"x\"\n---\n@include 0.md\n".to_string(),
+    )];
+    for level in 0..=MAX_INCLUDE_DEPTH {
+        files.push((
+            format!("{level}.md"),
+            format!("## L{level}\n@include {}.md\n", level + 1),
+        ));
+    }
+    files.push((
+        format!("{}.md", MAX_INCLUDE_DEPTH + 1),
+        "## Last\n".to_string(),
+    ));
+    let refs: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_str()))
+        .collect();
+    let tmp = package(&refs);
+
+    let err = parse_skill_file(&tmp.path().join("PATTERN.md")).unwrap_err();
+    assert!(err.to_string().contains("nesting exceeds"), "{err:#}");
+}
+
+#[test]
+fn include_must_stay_inside_the_package() {
+    let outer = TempDir::new().unwrap();
+    fs::write(outer.path().join("secret.md"), "## Secret\n").unwrap();
+    let pkg = outer.path().join("pkg");
+    fs::create_dir_all(&pkg).unwrap();
+    fs::write(pkg.join(".skill.toml"), "[skill]\nname = \"pkg\"\n").unwrap();
+
+    for (directive, message) in [
+        ("@include ../secret.md", "outside the package root"),
+        ("@include /etc/hosts", "must be relative"),
+        ("@include missing.md", "cannot resolve"),
+    ] {
+        let source = pkg.join("PATTERN.md");
+        fs::write(&source, format!("---\nname = \"x\"\n---\n{directive}\n")).unwrap();
+        let err = parse_skill_file(&source).unwrap_err();
+        assert!(format!("{err:#}").contains(message), "{directive}: {err:#}");
+    }
+}
@@ -345,6 +345,149 @@ Step content.
     assert!(matches!(&doc.body[1], Block::Step { .. }));
 }
 
+// -- WHILE / UNTIL blocks ---------------------------------------------------
+
+#[test]
+fn test_parse_while_block_with_guard() {
+    let input = r#"---
+name = "retry"
+---
+## WHILE ${TESTS_FAILING} MAX 5
+## Fix Tests
+Tool: codex
+Fix the failing tests.
+## ENDWHILE
+"#;
+    let doc = parse_skill(input).unwrap();
+    assert_eq!(doc.body.len(), 1);
+    match &doc.body[0] {
+        Block::Repeat {
+            mode,
+            condition,
+            max_iterations,
+            body,
+        } => {
+            assert_eq!(*mode, RepeatMode::While);
+            assert_eq!(condition, "${TESTS_FAILING}");
+            assert_eq!(*max_iterations, 5);
+            assert!(matches!(&body[0], Block::Step { title, .. } if title == "Fix Tests"));
+        }
+        other => panic!("expected Repeat, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_until_block() {
+    let input =
+        "---\nname = \"u\"\n---\n## UNTIL (${A}) && (${B}) MAX 3\n## Poll\nx\n## ENDUNTIL\n";
+    let doc = parse_skill(input).unwrap();
+    assert!(matches!(
+        &doc.body[0],
+        Block::Repeat {
+            mode: RepeatMode::Until,
+            condition,
+            max_iterations: 3,
+            ..
+        } if condition == "(${A}) && (${B})"
+    ));
+}
+
+#[test]
+fn test_error_on_while_without_guard() {
+    let input = "---\nname = \"w\"\n---\n## WHILE ${X}\n## A\nx\n## ENDWHILE\n";
+    let err = parse_skill(input).unwrap_err();
+    assert!(
+        err.to_string().contains("requires an iteration guard"),
+        "unexpected error: {err}"
+    );
+
+    let zero = "---\nname = \"w\"\n---\n## UNTIL ${X} MAX 0\n## A\nx\n## ENDUNTIL\n";
+    let err = parse_skill(zero).unwrap_err();
+    assert!(err.to_string().contains("invalid UNTIL MAX `0`"), "{err}");
+}
+
+#[test]
+fn test_error_on_mismatched_repeat_terminator() {
+    let input = "---\nname = \"w\"\n---\n## WHILE ${X} MAX 2\n## A\nx\n## ENDUNTIL\n";
+    let err = parse_skill(input).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unexpected ## ENDUNTIL without matching ## UNTIL"),
+        "unexpected error: {err}"
+    );
+}
+
+// -- PARALLEL blocks --------------------------------------------------------
+
+#[test]
+fn test_parse_parallel_block_with_max() {
+    let input = r#"---
+name = "fanout"
+---
+## PARALLEL MAX 2
+## Review Security
+Tool: codex
+Check for vulnerabilities.
+## Review Style
+Tool: gemini-cli
+Check naming.
+## ENDPARALLEL
+## Merge
+Combine findings.
+"#;
+    let doc = parse_skill(input).unwrap();
+    assert_eq!(doc.body.len(), 2);
+    match &doc.body[0] {
+        Block::Parallel {
+            max_concurrency,
+            body,
+        } => {
+            assert_eq!(*max_concurrency, Some(2));
+            assert_eq!(body.len(), 2);
+            assert!(matches!(&body[1], Block::Step { title, .. } if title == "Review Style"));
+        }
+        other => panic!("expected Parallel, got {other:?}"),
+    }
+    assert!(matches!(&doc.body[1], Block::Step { title, .. } if title == "Merge"));
+}
+
+#[test]
+fn test_parse_parallel_block_without_max_is_unbounded() {
+    let input = "---\nname = \"p\"\n---\n## PARALLEL\n## A\nx\n## B\ny\n## ENDPARALLEL\n";
+    let doc = parse_skill(input).unwrap();
+    assert!(matches!(
+        &doc.body[0],
+        Block::Parallel {
+            max_concurrency: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_error_on_invalid_parallel_max() {
+    let input = "---\nname = \"p\"\n---\n## PARALLEL MAX 0\n## A\nx\n## ENDPARALLEL\n";
+    let err = parse_skill(input).unwrap_err();
+    assert!(
+        err.to_string().contains("invalid PARALLEL MAX `0`"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn test_error_on_unclosed_parallel() {
+    let input = "---\nname = \"p\"\n---\n## PARALLEL\n## A\nx\n";
+    let err = parse_skill(input).unwrap_err();
+    assert!(err.to_string().contains("missing ## ENDPARALLEL"));
+}
+
+#[test]
+fn test_error_on_stray_endparallel() {
+    let input = "---\nname = \"p\"\n---\n## A\nx\n## ENDPARALLEL\n";
+    let err = parse_skill(input).unwrap_err();
+    assert!(err.to_string().contains("unexpected ## ENDPARALLEL"));
+}
+
 // -- Edge cases -------------------------------------------------------------
 
 #[test]
@@ -0,0 +1,198 @@
+//! Capability requirements a skill declares in its `SKILL.md` frontmatter.
+//!
+//! ```yaml
+//! ---
+//! name: release-notes
+//! requires-tools: [gh, jq]
+//! requires-mcp-servers:
+//!   - github
+//! min-csa-version: 0.1.1100
+//! ---
+//! ```
+//!
+//! Tools are executables that must be on `PATH`; MCP servers must be
+//! configured for csa (globally or in `.csa/mcp.toml`). TOML frontmatter
+//! (`requires-tools = ["gh"]`) is read the same way.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use semver::Version;
+
+/// What a skill needs from the machine it runs on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Requirements {
+    /// Executables that must be on `PATH`.
+    pub tools: Vec<String>,
+    /// MCP servers that must be configured for csa.
+    pub mcp_servers: Vec<String>,
+    /// Oldest csa release the skill works with.
+    pub min_csa_version: Option<Version>,
+}
+
+/// A requirement the current environment does not meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unmet {
+    Tool(String),
+    McpServer(String),
+    CsaVersion { required: Version, found: Version },
+}
+
+impl fmt::Display for Unmet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tool(tool) => write!(f, "requires `{tool}`, which is not on PATH"),
+            Self::McpServer(server) => {
+                write!(f, "requires MCP server `{server}`, which is not configured")
+            }
+            Self::CsaVersion { required, found } => {
+                write!(f, "requires csa >= {required}, but this is {found}")
+            }
+        }
+    }
+}
+
+impl Unmet {
+    /// How to satisfy the requirement.
+    pub fn hint(&self) -> String {
+        match self {
+            Self::Tool(tool) => format!("install `{tool}` or add its directory to PATH"),
+            Self::McpServer(server) => format!(
+                "add a `[[servers]]` entry named \"{server}\" to .csa/mcp.toml, \
+                 or a `[[mcp.servers]]` entry to the global csa config"
+            ),
+            Self::CsaVersion { .. } => "upgrade with `csa self-update`".to_string(),
+        }
+    }
+}
+
+impl Requirements {
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty() && self.mcp_servers.is_empty() && self.min_csa_version.is_none()
+    }
+
+    /// Requirements not met by `csa_version`, the configured `mcp_servers`
+    /// and the executables on `PATH`.
+    pub fn unmet(&self, csa_version: &str, mcp_servers: &[String]) -> Vec<Unmet> {
+        let mut unmet: Vec<Unmet> = self
+            .tools
+            .iter()
+            .filter(|tool| which::which(tool).is_err())
+            .map(|tool| Unmet::Tool(tool.clone()))
+            .collect();
+        unmet.extend(
+            self.mcp_servers
+                .iter()
+                .filter(|server| !mcp_servers.contains(server))
+                .map(|server| Unmet::McpServer(server.clone())),
+        );
+        if let (Some(required), Ok(found)) = (&self.min_csa_version, Version::parse(csa_version))
+            && found < *required
+        {
+            unmet.push(Unmet::CsaVersion {
+                required: required.clone(),
+                found,
+            });
+        }
+        unmet
+    }
+}
+
+/// Parse the requirement keys of a `SKILL.md` frontmatter block; a document
+/// without frontmatter has no requirements.
+pub fn parse_requirements(skill_md: &str) -> Result<Requirements> {
+    let mut requirements = Requirements::default();
+    let Some(frontmatter) = frontmatter(skill_md) else {
+        return Ok(requirements);
+    };
+    let mut lines = frontmatter.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once([':', '=']) else {
+            continue;
+        };
+        let key = key.trim();
+        if !matches!(
+            key,
+            "requires-tools" | "requires-mcp-servers" | "min-csa-version"
+        ) {
+            continue;
+        }
+        let value = value.trim();
+        let mut items = if value.is_empty() {
+            // YAML block sequence on the following indented lines.
+            let mut items = Vec::new();
+            while let Some(item) = lines
+                .peek()
+                .and_then(|next| next.trim_start().strip_prefix('-'))
+            {
+                items.push(unquote(item).to_string());
+                lines.next();
+            }
+            items
+        } else {
+            value
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(unquote)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        match key {
+            "requires-tools" => requirements.tools.append(&mut items),
+            "requires-mcp-servers" => requirements.mcp_servers.append(&mut items),
+            _ => {
+                let version = unquote(value);
+                let version = version.strip_prefix(">=").unwrap_or(version).trim();
+                requirements.min_csa_version = Some(
+                    Version::parse(version)
+                        .with_context(|| format!("invalid min-csa-version '{version}'"))?,
+                );
+            }
+        }
+    }
+    Ok(requirements)
+}
+
+/// Requirements of the skill in `dir`; none when it has no `SKILL.md`.
+pub fn read_requirements(dir: &Path) -> Result<Requirements> {
+    let path = dir.join("SKILL.md");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => parse_requirements(&content)
+            .with_context(|| format!("invalid requirements in {}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Requirements::default()),
+        Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Names of the MCP servers csa would start for `project_root`: the global
+/// config's servers merged with the project's `.csa/mcp.toml`.
+pub fn configured_mcp_servers(project_root: &Path) -> Vec<String> {
+    let global = csa_config::GlobalConfig::load()
+        .map(|config| config.mcp_servers().to_vec())
+        .unwrap_or_default();
+    let merged = match csa_config::McpRegistry::load(project_root) {
+        Ok(Some(project)) => csa_config::McpRegistry::merge(&global, &project).servers,
+        _ => global,
+    };
+    merged.into_iter().map(|server| server.name).collect()
+}
+
+fn frontmatter(content: &str) -> Option<&str> {
+    let rest = content.trim_start().strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    Some(&rest[..end])
+}
+
+fn unquote(item: &str) -> &str {
+    item.trim().trim_matches(|c| c == '"' || c == '\'').trim()
+}
+
+#[cfg(test)]
+#[path = "requirements_tests.rs"]
+mod tests;
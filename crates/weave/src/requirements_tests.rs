@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn parses_yaml_inline_and_block_lists() {
+    let skill = "---\nname: notes\nallowed-tools: Bash, Read\nrequires-tools: [gh, \"jq\"]\n\
+                 requires-mcp-servers:\n  - github\n  - 'deepwiki'\nmin-csa-version: \">=0.1.1100\"\n\
+                 triggers:\n  - notes\n---\n\n# Notes\nrequires-tools: [ignored]\n";
+    let requirements = parse_requirements(skill).unwrap();
+    assert_eq!(requirements.tools, ["gh", "jq"]);
+    assert_eq!(requirements.mcp_servers, ["github", "deepwiki"]);
+    assert_eq!(requirements.min_csa_version, Some(Version::new(0, 1, 1100)));
+}
+
+#[test]
+fn parses_toml_frontmatter_and_tolerates_missing_frontmatter() {
+    let pattern =
+        "---\nname = \"notes\"\nrequires-tools = [\"gh\"]\nmin-csa-version = \"0.2.0\"\n---\n";
+    let requirements = parse_requirements(pattern).unwrap();
+    assert_eq!(requirements.tools, ["gh"]);
+    assert_eq!(requirements.min_csa_version, Some(Version::new(0, 2, 0)));
+
+    assert!(parse_requirements("# No frontmatter\n").unwrap().is_empty());
+    let err = parse_requirements("---\nmin-csa-version: soon\n---\n").unwrap_err();
+    assert!(err.to_string().contains("invalid min-csa-version 'soon'"));
+}
+
+#[test]
+fn unmet_reports_missing_tools_servers_and_old_csa() {
+    let requirements = Requirements {
+        tools: vec!["sh".to_string(), "weave-no-such-tool".to_string()],
+        mcp_servers: vec!["github".to_string(), "deepwiki".to_string()],
+        min_csa_version: Some(Version::new(0, 2, 0)),
+    };
+    let unmet = requirements.unmet("0.1.1118", &["github".to_string()]);
+    assert_eq!(
+        unmet,
+        [
+            Unmet::Tool("weave-no-such-tool".to_string()),
+            Unmet::McpServer("deepwiki".to_string()),
+            Unmet::CsaVersion {
+                required: Version::new(0, 2, 0),
+                found: Version::new(0, 1, 1118),
+            },
+        ]
+    );
+    assert_eq!(
+        unmet[0].to_string(),
+        "requires `weave-no-such-tool`, which is not on PATH"
+    );
+    assert!(unmet[1].hint().contains(".csa/mcp.toml"));
+    let servers = ["github".to_string(), "deepwiki".to_string()];
+    assert_eq!(requirements.unmet("0.2.0", &servers).len(), 1);
+}
@@ -1,6 +1,9 @@
 use crate::compiler::{ExecutionPlan, FailAction};
 
-use super::{common_prefix_len, format_fail_action, step_condition_atoms};
+use super::{
+    common_prefix_len, format_fail_action, format_fork_label, format_repeat_label,
+    step_condition_atoms, step_groups,
+};
 
 const DEFAULT_COLUMNS: usize = 100;
 const MIN_COLUMNS: usize = 60;
@@ -26,8 +29,35 @@ pub fn render_ascii_with_width(plan: &ExecutionPlan, width: usize) -> String {
         lines.extend(render_variables_box(plan, width));
     }
 
+    let groups = step_groups(plan);
     let mut prev_atoms = Vec::new();
-    for step in &plan.steps {
+    for (idx, step) in plan.steps.iter().enumerate() {
+        let repeat_id = |i: usize| {
+            plan.steps
+                .get(i)
+                .and_then(|s| s.repeat.as_ref())
+                .map(|r| r.id)
+        };
+        let repeat = step.repeat.as_ref();
+        if let Some(spec) = repeat
+            && (idx == 0 || repeat_id(idx - 1) != Some(spec.id))
+        {
+            lines.push(clamp_line(
+                format!("↻ {}", format_repeat_label(spec)),
+                width,
+            ));
+        }
+        let group = groups.get(&step.id);
+        if let Some(group) = group
+            && group.steps.first() == Some(&step.id)
+        {
+            let indent = "  ".repeat(common_prefix_len(&prev_atoms, &step_condition_atoms(step)));
+            lines.push(clamp_line(
+                format!("{indent}╦ {}", format_fork_label(group.max_concurrency)),
+                width,
+            ));
+        }
+
         let atoms = step_condition_atoms(step);
         let common = common_prefix_len(&prev_atoms, &atoms);
 
@@ -69,6 +99,16 @@ pub fn render_ascii_with_width(plan: &ExecutionPlan, width: usize) -> String {
             ));
         }
         lines.push(clamp_line(format!("{indent}└─"), width));
+        if let Some(group) = group
+            && group.steps.last() == Some(&step.id)
+        {
+            lines.push(clamp_line(format!("{indent}╩ join"), width));
+        }
+        if let Some(spec) = repeat
+            && repeat_id(idx + 1) != Some(spec.id)
+        {
+            lines.push(clamp_line(format!("↻ end {}", spec.mode.as_str()), width));
+        }
         prev_atoms = atoms;
     }
 
@@ -130,10 +170,16 @@ mod tests {
                 VariableDecl {
                     name: "APP".to_string(),
                     default: None,
+                    kind: None,
+                    description: None,
+                    required: false,
                 },
                 VariableDecl {
                     name: "ENV".to_string(),
                     default: None,
+                    kind: None,
+                    description: None,
+                    required: false,
                 },
             ],
             steps: vec![
@@ -149,6 +195,7 @@ mod tests {
                     loop_var: None,
                     session: None,
                     workspace_access: None,
+                    ..Default::default()
                 },
                 PlanStep {
                     id: 2,
@@ -162,6 +209,7 @@ mod tests {
                     loop_var: None,
                     session: None,
                     workspace_access: None,
+                    ..Default::default()
                 },
                 PlanStep {
                     id: 3,
@@ -179,8 +227,10 @@ mod tests {
                     }),
                     session: None,
                     workspace_access: None,
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         }
     }
 
@@ -237,4 +287,52 @@ visual layout demo
   └─"#;
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_render_ascii_parallel_group_markers() {
+        let doc = crate::parser::parse_skill(
+            "---\nname = \"p\"\n---\n## PARALLEL MAX 2\n## A\nOne.\n## B\nTwo.\n## ENDPARALLEL\n## C\nThree.\n",
+        )
+        .unwrap();
+        let plan = crate::compiler::compile(&doc).unwrap();
+
+        let output = render_ascii_with_width(&plan, 80);
+        let expected = r#"Plan: p
+╦ parallel (max 2)
+┌─ [1] A [none]
+│ One.
+└─
+┌─ [2] B [none]
+│ Two.
+└─
+╩ join
+┌─ [3] C [none]
+│ Three.
+└─"#;
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_render_ascii_repeat_block_markers() {
+        let doc = crate::parser::parse_skill(
+            "---\nname = \"r\"\n---\n## UNTIL ${GREEN} MAX 3\n## Poll\nCheck CI.\n## ENDUNTIL\n## Done\nReport.\n",
+        )
+        .unwrap();
+        let plan = crate::compiler::compile(&doc).unwrap();
+
+        let output = render_ascii_with_width(&plan, 80);
+        let expected = r#"Plan: r
+┌ Variables ───────────────────────────────────────────────────────────────────┐
+│GREEN                                                                         │
+└──────────────────────────────────────────────────────────────────────────────┘
+↻ until ${GREEN} (max 3)
+┌─ [1] Poll [none]
+│ Check CI.
+└─
+↻ end until
+┌─ [2] Done [none]
+│ Report.
+└─"#;
+        assert_eq!(output, expected);
+    }
 }
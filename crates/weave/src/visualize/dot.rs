@@ -6,7 +6,7 @@ use anyhow::{Context, Result, bail};
 
 use crate::compiler::ExecutionPlan;
 
-use super::{VizEdgeKind, VizNodeKind, build_graph};
+use super::{VizEdgeKind, VizNodeKind, build_graph, format_fork_label};
 
 pub fn to_dot(plan: &ExecutionPlan) -> String {
     let graph = build_graph(plan);
@@ -40,6 +40,10 @@ pub fn to_dot(plan: &ExecutionPlan) -> String {
                 "circle",
                 label.clone().unwrap_or_else(|| "join".to_string()),
             ),
+            VizNodeKind::Fork {
+                group: _,
+                max_concurrency,
+            } => ("trapezium", format_fork_label(*max_concurrency)),
         };
         out.push_str(&format!(
             "  {} [shape={}, label=\"{}\"];\n",
@@ -62,6 +66,14 @@ pub fn to_dot(plan: &ExecutionPlan) -> String {
                     .unwrap_or_else(|| "unknown".to_string());
                 format!(" [style=\"dashed\", label=\"on_fail: {label}\"]")
             }
+            VizEdgeKind::LoopBack => {
+                let label = edge
+                    .label
+                    .as_deref()
+                    .map(escape_dot_label)
+                    .unwrap_or_default();
+                format!(" [style=\"dotted\", label=\"{label}\"]")
+            }
         };
         out.push_str(&format!("  {} -> {}{};\n", edge.from, edge.to, attrs));
     }
@@ -142,6 +154,7 @@ mod tests {
                     loop_var: None,
                     session: None,
                     workspace_access: None,
+                    ..Default::default()
                 },
                 PlanStep {
                     id: 2,
@@ -155,8 +168,10 @@ mod tests {
                     loop_var: None,
                     session: None,
                     workspace_access: None,
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let dot = to_dot(&plan);
@@ -0,0 +1,55 @@
+//! Standalone HTML page rendering the Mermaid flowchart with mermaid.js.
+
+use crate::compiler::ExecutionPlan;
+
+use super::mermaid::render_mermaid;
+use super::svg::escape_xml;
+
+/// mermaid.js ES module served from the jsDelivr CDN.
+pub const MERMAID_CDN_URL: &str =
+    "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs";
+
+pub fn render_html(plan: &ExecutionPlan) -> String {
+    let title = escape_xml(&plan.name);
+    let source = escape_xml(&render_mermaid(plan));
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n\
+         </head>\n\
+         <body>\n\
+         <h1>{title}</h1>\n\
+         <pre class=\"mermaid\">\n{source}\n</pre>\n\
+         <script type=\"module\">\n\
+         import mermaid from \"{MERMAID_CDN_URL}\";\n\
+         mermaid.initialize({{ startOnLoad: true }});\n\
+         </script>\n\
+         </body>\n\
+         </html>\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::parser::parse_skill;
+
+    #[test]
+    fn test_render_html_embeds_escaped_mermaid_source() {
+        let doc = parse_skill(
+            "---\nname = \"a<b>\"\n---\n## IF ${N} > 1\n## Build\nTool: codex\nGo.\n## ENDIF\n",
+        )
+        .expect("parse should succeed");
+        let plan = compile(&doc).expect("compile should succeed");
+
+        let html = render_html(&plan);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>a&lt;b&gt;</title>"));
+        assert!(html.contains("<pre class=\"mermaid\">\nflowchart TD\n"));
+        assert!(html.contains("D1{&quot;${N} &gt; 1?&quot;}"), "{html}");
+        assert!(html.contains(MERMAID_CDN_URL));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::compiler::ExecutionPlan;
 
-use super::{VizEdgeKind, VizNodeKind, build_graph};
+use super::{VizEdgeKind, VizNodeKind, build_graph, format_fork_label};
 
 pub fn render_mermaid(plan: &ExecutionPlan) -> String {
     let graph = build_graph(plan);
@@ -37,6 +37,13 @@ pub fn render_mermaid(plan: &ExecutionPlan) -> String {
                 let escaped = escape_label(title);
                 lines.push(format!("  {}((\"{}\"))", node.id, escaped));
             }
+            VizNodeKind::Fork {
+                group: _,
+                max_concurrency,
+            } => {
+                let label = escape_label(&format_fork_label(*max_concurrency));
+                lines.push(format!("  {}[/\"{}\"\\]", node.id, label));
+            }
         }
     }
 
@@ -57,6 +64,10 @@ pub fn render_mermaid(plan: &ExecutionPlan) -> String {
                     .unwrap_or_else(|| "unknown".to_string());
                 format!("  {} -->|on_fail: {}| {}", edge.from, label, edge.to)
             }
+            VizEdgeKind::LoopBack => {
+                let label = edge.label.as_deref().map(escape_label).unwrap_or_default();
+                format!("  {} -.->|{}| {}", edge.from, label, edge.to)
+            }
         };
         lines.push(edge_text);
     }
@@ -133,11 +144,64 @@ No tests available.
                 loop_var: None,
                 session: None,
                 workspace_access: None,
+                ..Default::default()
             }],
+            ..Default::default()
         };
 
         let output = render_mermaid(&plan);
         assert!(output.contains("Say \\\"hi\\\" / greet"));
         assert!(output.contains("[co\\\\dex]"));
     }
+
+    #[test]
+    fn test_render_mermaid_parallel_group() {
+        let plan = compile_doc(
+            r#"---
+name = "fanout"
+---
+## PARALLEL MAX 2
+## A
+Tool: codex
+One.
+## B
+Tool: gemini-cli
+Two.
+## ENDPARALLEL
+"#,
+        );
+
+        let output = render_mermaid(&plan);
+        assert!(output.contains("P1[/\"parallel (max 2)\"\\]"), "{output}");
+        assert!(output.contains("PJ1((\"join\"))"));
+        assert!(output.contains("P1 --> S1"));
+        assert!(output.contains("S2 --> PJ1"));
+    }
+
+    #[test]
+    fn test_render_mermaid_while_loop_back_edge() {
+        let plan = compile_doc(
+            r#"---
+name = "retry"
+---
+## WHILE ${TESTS_FAILING} MAX 5
+## Fix
+Tool: codex
+Fix tests.
+## Rerun
+Tool: bash
+Run tests.
+## ENDWHILE
+## Report
+Summarize.
+"#,
+        );
+
+        let output = render_mermaid(&plan);
+        assert!(
+            output.contains("S2 -.->|while ${TESTS_FAILING} (max 5)| S1"),
+            "{output}"
+        );
+        assert!(output.contains("S2 --> S3"));
+    }
 }
@@ -3,16 +3,23 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
-use crate::compiler::{ExecutionPlan, FailAction, PlanStep, plan_from_toml};
+use crate::compiler::{
+    ExecutionPlan, FailAction, ParallelGroup, PlanStep, RepeatSpec, plan_from_toml,
+};
+use crate::condition::{Condition, parse_condition};
 
 pub mod ascii;
 pub mod dot;
+pub mod html;
 pub mod mermaid;
+pub mod svg;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VisualizeTarget {
     Ascii,
     Mermaid,
+    Svg,
+    Html,
     Png(PathBuf),
 }
 
@@ -53,6 +60,11 @@ pub enum VizNodeKind {
         depth: usize,
         label: Option<String>,
     },
+    /// Start of a parallel step group; its members rejoin at a `Join` node.
+    Fork {
+        group: usize,
+        max_concurrency: Option<u32>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,6 +81,8 @@ pub enum VizEdgeKind {
     BranchYes,
     BranchNo,
     OnFail,
+    /// From the last step of a WHILE/UNTIL block back to its first step.
+    LoopBack,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -124,19 +138,38 @@ pub fn build_graph(plan: &ExecutionPlan) -> VizGraph {
         });
     }
 
+    let groups = step_groups(plan);
+    // Steps in a parallel group are entered through its fork node and left
+    // through its join node.
+    let entry_node = |step: &PlanStep| match groups.get(&step.id) {
+        Some(group) => fork_node_id(group.id),
+        None => step_node_id(step.id),
+    };
+    let exit_node = |step: &PlanStep| match groups.get(&step.id) {
+        Some(group) => parallel_join_node_id(group.id),
+        None => step_node_id(step.id),
+    };
+
     if let Some(first) = plan.steps.first() {
         // Only add a direct entry edge when the first step is unconditional.
         // Conditional first steps are reached through their decision nodes.
         if step_condition_atoms(first).is_empty() {
             graph.edges.push(VizEdge {
                 from: entry_id.clone(),
-                to: step_node_id(first.id),
+                to: entry_node(first),
                 kind: VizEdgeKind::Normal,
                 label: None,
             });
         }
     }
     for pair in plan.steps.windows(2) {
+        let same_group = groups
+            .get(&pair[0].id)
+            .zip(groups.get(&pair[1].id))
+            .is_some_and(|(left, right)| left.id == right.id);
+        if same_group {
+            continue;
+        }
         let left_atoms = step_condition_atoms(&pair[0]);
         let right_atoms = step_condition_atoms(&pair[1]);
         // Only add sequential edges between steps that share the same
@@ -144,16 +177,18 @@ pub fn build_graph(plan: &ExecutionPlan) -> VizGraph {
         // condition must NOT be linked sequentially.
         if left_atoms == right_atoms {
             graph.edges.push(VizEdge {
-                from: step_node_id(pair[0].id),
-                to: step_node_id(pair[1].id),
+                from: exit_node(&pair[0]),
+                to: entry_node(&pair[1]),
                 kind: VizEdgeKind::Normal,
                 label: None,
             });
         }
     }
 
+    add_parallel_nodes(&mut graph, plan);
     add_decision_edges(&mut graph, plan, &entry_id);
     add_join_nodes(&mut graph, plan);
+    add_repeat_edges(&mut graph, plan);
     add_on_fail_edges(&mut graph, plan);
 
     dedupe_edges(&mut graph);
@@ -169,18 +204,33 @@ fn add_decision_edges(graph: &mut VizGraph, plan: &ExecutionPlan, entry_id: &str
     // the most recent unconditional step (or the graph entry node) is the
     // predecessor.
     let mut predecessor_map: HashMap<String, String> = HashMap::new();
+    // Inside a parallel group the fork is the predecessor; after it, the join.
+    let groups = step_groups(plan);
     let mut last_unconditional: String = entry_id.to_string();
     for step in &plan.steps {
+        let group = groups.get(&step.id);
+        if let Some(group) = group
+            && group.steps.first() == Some(&step.id)
+        {
+            last_unconditional = fork_node_id(group.id);
+        }
         let atoms = step_condition_atoms(step);
         if atoms.is_empty() {
             // Unconditional step — update the running predecessor.
-            last_unconditional = step_node_id(step.id);
+            if group.is_none() {
+                last_unconditional = step_node_id(step.id);
+            }
         } else if let Some(first_atom) = atoms.first() {
             let top_key = decision_key(&[], &first_atom.expr);
             predecessor_map
                 .entry(top_key)
                 .or_insert_with(|| last_unconditional.clone());
         }
+        if let Some(group) = group
+            && group.steps.last() == Some(&step.id)
+        {
+            last_unconditional = parallel_join_node_id(group.id);
+        }
     }
 
     for step in &plan.steps {
@@ -288,6 +338,75 @@ fn add_join_nodes(graph: &mut VizGraph, plan: &ExecutionPlan) {
     }
 }
 
+fn add_parallel_nodes(graph: &mut VizGraph, plan: &ExecutionPlan) {
+    let steps: HashMap<usize, &PlanStep> = plan.steps.iter().map(|s| (s.id, s)).collect();
+    for group in &plan.parallel_groups {
+        let fork_id = fork_node_id(group.id);
+        let join_id = parallel_join_node_id(group.id);
+        graph.nodes.push(VizNode {
+            id: fork_id.clone(),
+            kind: VizNodeKind::Fork {
+                group: group.id,
+                max_concurrency: group.max_concurrency,
+            },
+        });
+        graph.nodes.push(VizNode {
+            id: join_id.clone(),
+            kind: VizNodeKind::Join {
+                depth: 0,
+                label: Some("join".to_string()),
+            },
+        });
+
+        let members: Vec<&PlanStep> = group
+            .steps
+            .iter()
+            .filter_map(|id| steps.get(id).copied())
+            .collect();
+        let group_atoms = members
+            .first()
+            .map(|step| step_condition_atoms(step))
+            .unwrap_or_default();
+        for member in members {
+            // Members under their own IF are reached through decision nodes.
+            if step_condition_atoms(member) == group_atoms {
+                graph.edges.push(VizEdge {
+                    from: fork_id.clone(),
+                    to: step_node_id(member.id),
+                    kind: VizEdgeKind::Normal,
+                    label: None,
+                });
+            }
+            graph.edges.push(VizEdge {
+                from: step_node_id(member.id),
+                to: join_id.clone(),
+                kind: VizEdgeKind::Normal,
+                label: None,
+            });
+        }
+    }
+}
+
+fn add_repeat_edges(graph: &mut VizGraph, plan: &ExecutionPlan) {
+    for block in plan
+        .steps
+        .chunk_by(|a, b| a.repeat.as_ref().map(|r| r.id) == b.repeat.as_ref().map(|r| r.id))
+    {
+        let (Some(first), Some(last)) = (block.first(), block.last()) else {
+            continue;
+        };
+        let Some(spec) = &first.repeat else {
+            continue;
+        };
+        graph.edges.push(VizEdge {
+            from: step_node_id(last.id),
+            to: step_node_id(first.id),
+            kind: VizEdgeKind::LoopBack,
+            label: Some(format_repeat_label(spec)),
+        });
+    }
+}
+
 fn add_on_fail_edges(graph: &mut VizGraph, plan: &ExecutionPlan) {
     let mut fail_join_counter = 0usize;
     for step in &plan.steps {
@@ -327,6 +446,38 @@ fn step_node_id(step_id: usize) -> String {
     format!("S{step_id}")
 }
 
+fn fork_node_id(group_id: usize) -> String {
+    format!("P{group_id}")
+}
+
+fn parallel_join_node_id(group_id: usize) -> String {
+    format!("PJ{group_id}")
+}
+
+/// Parallel group of each grouped step, keyed by step id.
+pub(crate) fn step_groups(plan: &ExecutionPlan) -> HashMap<usize, &ParallelGroup> {
+    plan.parallel_groups
+        .iter()
+        .flat_map(|group| group.steps.iter().map(move |id| (*id, group)))
+        .collect()
+}
+
+pub(crate) fn format_repeat_label(spec: &RepeatSpec) -> String {
+    format!(
+        "{} {} (max {})",
+        spec.mode.as_str(),
+        spec.condition,
+        spec.max_iterations
+    )
+}
+
+pub(crate) fn format_fork_label(max_concurrency: Option<u32>) -> String {
+    match max_concurrency {
+        Some(max) => format!("parallel (max {max})"),
+        None => "parallel".to_string(),
+    }
+}
+
 fn branch_kind(truthy: bool) -> VizEdgeKind {
     if truthy {
         VizEdgeKind::BranchYes
@@ -425,24 +576,39 @@ fn strip_wrapping_parens(input: &str) -> &str {
 }
 
 fn parse_condition_atoms(condition: &str) -> Vec<ConditionAtom> {
-    split_top_level_and(condition)
-        .into_iter()
-        .map(|raw| {
-            let trimmed = strip_wrapping_parens(&raw);
-            if let Some(rest) = trimmed.strip_prefix('!') {
-                ConditionAtom {
-                    expr: strip_wrapping_parens(rest).to_string(),
-                    truthy: false,
-                }
-            } else {
-                ConditionAtom {
-                    expr: trimmed.to_string(),
-                    truthy: true,
-                }
+    let Ok(parsed) = parse_condition(condition) else {
+        return split_top_level_and(condition)
+            .into_iter()
+            .map(|raw| ConditionAtom {
+                expr: strip_wrapping_parens(&raw).to_string(),
+                truthy: true,
+            })
+            .filter(|atom| !atom.expr.is_empty())
+            .collect();
+    };
+    let mut atoms = Vec::new();
+    push_conjuncts(parsed, &mut atoms);
+    atoms
+}
+
+/// One atom per top-level conjunct; `||` groups and comparisons stay whole so
+/// each renders as a single decision node.
+fn push_conjuncts(condition: Condition, atoms: &mut Vec<ConditionAtom>) {
+    match condition {
+        Condition::And(parts) => {
+            for part in parts {
+                push_conjuncts(part, atoms);
             }
-        })
-        .filter(|atom| !atom.expr.is_empty())
-        .collect()
+        }
+        Condition::Not(inner) => atoms.push(ConditionAtom {
+            expr: inner.to_string(),
+            truthy: false,
+        }),
+        other => atoms.push(ConditionAtom {
+            expr: other.to_string(),
+            truthy: true,
+        }),
+    }
 }
 
 pub(crate) fn step_condition_atoms(step: &PlanStep) -> Vec<ConditionAtom> {
@@ -478,6 +644,16 @@ pub fn render_mermaid(plan: &ExecutionPlan) -> String {
     mermaid::render_mermaid(plan)
 }
 
+/// Render an execution plan as a standalone SVG document.
+pub fn render_svg(plan: &ExecutionPlan) -> String {
+    svg::render_svg(plan)
+}
+
+/// Render an execution plan as an HTML page that draws the Mermaid flowchart.
+pub fn render_html(plan: &ExecutionPlan) -> String {
+    html::render_html(plan)
+}
+
 /// Render an execution plan as PNG.
 pub fn render_png(plan: &ExecutionPlan, output: &Path) -> Result<()> {
     #[cfg(feature = "visualize-png-dot")]
@@ -513,6 +689,8 @@ pub fn visualize_plan_toml(
     match target {
         VisualizeTarget::Ascii => Ok(VisualizeResult::Stdout(render_ascii(&plan))),
         VisualizeTarget::Mermaid => Ok(VisualizeResult::Stdout(render_mermaid(&plan))),
+        VisualizeTarget::Svg => Ok(VisualizeResult::Stdout(render_svg(&plan))),
+        VisualizeTarget::Html => Ok(VisualizeResult::Stdout(render_html(&plan))),
         VisualizeTarget::Png(output) => {
             render_png(&plan, &output)?;
             Ok(VisualizeResult::FileWritten(output))
@@ -521,186 +699,4 @@ pub fn visualize_plan_toml(
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::compiler::compile;
-    use crate::parser::parse_skill;
-
-    fn compile_doc(markdown: &str) -> ExecutionPlan {
-        let doc = parse_skill(markdown).expect("parse should succeed");
-        compile(&doc).expect("compile should succeed")
-    }
-
-    fn find_decision_node<'a>(graph: &'a VizGraph, condition: &str) -> Option<&'a VizNode> {
-        graph.nodes.iter().find(|node| {
-            matches!(
-                &node.kind,
-                VizNodeKind::Decision {
-                    condition: c,
-                    depth: _
-                } if c == condition
-            )
-        })
-    }
-
-    #[test]
-    fn test_build_graph_linear_plan() {
-        let plan = compile_doc(
-            r#"---
-name = "linear"
----
-## Build
-Tool: codex
-Build project.
-
-## Test
-Tool: claude-code
-Run tests.
-"#,
-        );
-        let graph = build_graph(&plan);
-
-        assert!(
-            graph
-                .nodes
-                .iter()
-                .all(|n| !matches!(n.kind, VizNodeKind::Decision { .. }))
-        );
-        assert!(
-            graph
-                .edges
-                .iter()
-                .any(|e| e.from == "S1" && e.to == "S2" && e.kind == VizEdgeKind::Normal)
-        );
-    }
-
-    #[test]
-    fn test_build_graph_single_if_else() {
-        let plan = compile_doc(
-            r#"---
-name = "if-else"
----
-## IF has_tests
-## Run Tests
-Tool: codex
-Run unit tests.
-## ELSE
-## Skip Tests
-No tests available.
-## ENDIF
-"#,
-        );
-        let graph = build_graph(&plan);
-
-        let decision = find_decision_node(&graph, "has_tests").expect("missing decision");
-        assert!(graph.edges.iter().any(|e| {
-            e.from == decision.id && e.to == "S1" && e.kind == VizEdgeKind::BranchYes
-        }));
-        assert!(
-            graph.edges.iter().any(|e| {
-                e.from == decision.id && e.to == "S2" && e.kind == VizEdgeKind::BranchNo
-            })
-        );
-    }
-
-    #[test]
-    fn test_build_graph_nested_if_in_else() {
-        let plan = compile_doc(
-            r#"---
-name = "nested"
----
-## IF ${USER_APPROVES}
-## Apply Plan
-Proceed.
-## ELSE
-## IF ${USER_MODIFIES}
-## Resume
-Resume with edits.
-## ELSE
-## Abandon
-Stop.
-## ENDIF
-## ENDIF
-"#,
-        );
-        let graph = build_graph(&plan);
-
-        let outer = find_decision_node(&graph, "${USER_APPROVES}").expect("missing outer");
-        let inner = find_decision_node(&graph, "${USER_MODIFIES}").expect("missing inner");
-
-        assert!(graph.edges.iter().any(|e| {
-            e.from == outer.id && e.to == inner.id && e.kind == VizEdgeKind::BranchNo
-        }));
-        assert!(
-            graph
-                .edges
-                .iter()
-                .any(|e| e.from == inner.id && e.to == "S2" && e.kind == VizEdgeKind::BranchYes)
-        );
-        assert!(
-            graph
-                .edges
-                .iter()
-                .any(|e| e.from == inner.id && e.to == "S3" && e.kind == VizEdgeKind::BranchNo)
-        );
-    }
-
-    #[test]
-    fn test_build_graph_if_inside_for_preserves_loop_labels() {
-        let plan = compile_doc(
-            r#"---
-name = "if-for"
----
-## FOR item IN ${ITEMS}
-## IF ${IS_VALID}
-## Process Item
-Tool: codex
-OnFail: retry 2
-Process ${item}.
-## ELSE
-## Skip Item
-OnFail: skip
-Skip ${item}.
-## ENDIF
-## ENDFOR
-"#,
-        );
-
-        let graph = build_graph(&plan);
-        let process = graph
-            .nodes
-            .iter()
-            .find(|node| {
-                matches!(
-                    node.kind,
-                    VizNodeKind::Step {
-                        step_id: 1,
-                        title: _,
-                        tool: _,
-                        loop_label: _
-                    }
-                )
-            })
-            .expect("missing step");
-
-        match &process.kind {
-            VizNodeKind::Step { loop_label, .. } => {
-                assert_eq!(loop_label.as_deref(), Some("item in ${ITEMS}"));
-            }
-            _ => unreachable!("unexpected node kind"),
-        }
-
-        assert!(
-            graph
-                .edges
-                .iter()
-                .any(|e| e.kind == VizEdgeKind::OnFail && e.from == "S1")
-        );
-        assert!(
-            graph
-                .edges
-                .iter()
-                .any(|e| e.kind == VizEdgeKind::OnFail && e.from == "S2")
-        );
-    }
-}
+mod tests;
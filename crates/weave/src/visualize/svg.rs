@@ -0,0 +1,391 @@
+//! Native SVG rendering of a [`VizGraph`](super::VizGraph), no Graphviz required.
+//!
+//! Nodes are placed in layers by longest path from the entry (loop-back
+//! edges are ignored for ranking), ordered within a layer by creation order,
+//! and each layer is centered horizontally. Edges pointing down are drawn
+//! straight; edges pointing back up are drawn as curves on the right.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::compiler::ExecutionPlan;
+
+use super::{VizEdge, VizEdgeKind, VizNode, VizNodeKind, build_graph, format_fork_label};
+
+const CHAR_WIDTH: i64 = 7;
+const LINE_HEIGHT: i64 = 16;
+const PADDING: i64 = 12;
+const H_GAP: i64 = 40;
+const V_GAP: i64 = 50;
+const MARGIN: i64 = 20;
+/// Horizontal reach of back-edge curves beyond the wider endpoint.
+const BACK_EDGE_BULGE: i64 = 60;
+
+/// Center position and size of a laid-out node.
+#[derive(Debug, Clone, Copy)]
+struct Placed {
+    x: i64,
+    y: i64,
+    w: i64,
+    h: i64,
+    rank: usize,
+}
+
+pub fn render_svg(plan: &ExecutionPlan) -> String {
+    let graph = build_graph(plan);
+    // ROOT is a synthetic entry point, not drawn (as in Mermaid output).
+    let nodes: Vec<&VizNode> = graph.nodes.iter().filter(|n| n.id != "ROOT").collect();
+    let edges: Vec<&VizEdge> = graph
+        .edges
+        .iter()
+        .filter(|e| e.from != "ROOT" && e.to != "ROOT")
+        .collect();
+
+    let (placed, width, height) = layout(&nodes, &edges);
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"12\">\n"
+    );
+    out.push_str(
+        "  <defs>\n    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+         markerWidth=\"8\" markerHeight=\"8\" orient=\"auto-start-reverse\">\n      \
+         <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#333\"/>\n    </marker>\n  </defs>\n",
+    );
+    out.push_str(&format!(
+        "  <rect width=\"{width}\" height=\"{height}\" fill=\"white\"/>\n"
+    ));
+
+    for edge in &edges {
+        if let (Some(from), Some(to)) =
+            (placed.get(edge.from.as_str()), placed.get(edge.to.as_str()))
+        {
+            out.push_str(&render_edge(edge, from, to));
+        }
+    }
+    for node in &nodes {
+        if let Some(at) = placed.get(node.id.as_str()) {
+            out.push_str(&render_node(node, at));
+        }
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn layout<'a>(nodes: &[&'a VizNode], edges: &[&VizEdge]) -> (HashMap<&'a str, Placed>, i64, i64) {
+    let ranks = ranks(nodes, edges);
+    let max_rank = ranks.values().copied().max().unwrap_or(0);
+    let mut layers: Vec<Vec<&VizNode>> = vec![Vec::new(); max_rank + 1];
+    for node in nodes {
+        layers[ranks[node.id.as_str()]].push(node);
+    }
+
+    let sizes: HashMap<&str, (i64, i64)> = nodes
+        .iter()
+        .map(|n| (n.id.as_str(), node_size(n)))
+        .collect();
+    let layer_width = |layer: &[&VizNode]| {
+        layer.iter().map(|n| sizes[n.id.as_str()].0).sum::<i64>()
+            + H_GAP * (layer.len().saturating_sub(1) as i64)
+    };
+    let content_width = layers.iter().map(|l| layer_width(l)).max().unwrap_or(0);
+
+    let mut placed = HashMap::new();
+    let mut y = MARGIN;
+    for (rank, layer) in layers.iter().enumerate() {
+        let row_height = layer
+            .iter()
+            .map(|n| sizes[n.id.as_str()].1)
+            .max()
+            .unwrap_or(0);
+        let mut x = MARGIN + (content_width - layer_width(layer)) / 2;
+        for node in layer {
+            let (w, h) = sizes[node.id.as_str()];
+            placed.insert(
+                node.id.as_str(),
+                Placed {
+                    x: x + w / 2,
+                    y: y + row_height / 2,
+                    w,
+                    h,
+                    rank,
+                },
+            );
+            x += w + H_GAP;
+        }
+        y += row_height + V_GAP;
+    }
+
+    let width = content_width + 2 * MARGIN + BACK_EDGE_BULGE;
+    let height = (y - V_GAP + MARGIN).max(2 * MARGIN);
+    (placed, width, height)
+}
+
+/// Longest-path layer of each node over forward edges (Kahn's algorithm);
+/// nodes left on a cycle keep the deepest rank seen so far.
+fn ranks<'a>(nodes: &[&'a VizNode], edges: &[&VizEdge]) -> HashMap<&'a str, usize> {
+    let mut rank: HashMap<&str, usize> = nodes.iter().map(|n| (n.id.as_str(), 0)).collect();
+    let forward: Vec<&VizEdge> = edges
+        .iter()
+        .copied()
+        .filter(|e| e.kind != VizEdgeKind::LoopBack)
+        .filter(|e| rank.contains_key(e.from.as_str()) && rank.contains_key(e.to.as_str()))
+        .collect();
+    let mut indegree: HashMap<&str, usize> = HashMap::new();
+    for edge in &forward {
+        *indegree.entry(edge.to.as_str()).or_default() += 1;
+    }
+
+    let mut queue: VecDeque<&str> = nodes
+        .iter()
+        .map(|n| n.id.as_str())
+        .filter(|id| !indegree.contains_key(id))
+        .collect();
+    while let Some(id) = queue.pop_front() {
+        for edge in forward.iter().filter(|e| e.from == id) {
+            let next = rank[id] + 1;
+            let to = edge.to.as_str();
+            let slot = rank.get_mut(to).expect("edge target is ranked");
+            *slot = (*slot).max(next);
+            let remaining = indegree.get_mut(to).expect("edge target has indegree");
+            *remaining -= 1;
+            if *remaining == 0 {
+                queue.push_back(to);
+            }
+        }
+    }
+
+    nodes
+        .iter()
+        .map(|n| (n.id.as_str(), rank[n.id.as_str()]))
+        .collect()
+}
+
+fn node_lines(node: &VizNode) -> Vec<String> {
+    match &node.kind {
+        VizNodeKind::VariableHeader { names } => vec![format!("Variables: {}", names.join(", "))],
+        VizNodeKind::Step {
+            step_id,
+            title,
+            tool,
+            loop_label,
+        } => {
+            let mut lines = vec![
+                format!("{step_id}. {title}"),
+                format!("[{}]", tool.as_deref().unwrap_or("none")),
+            ];
+            if let Some(loop_label) = loop_label {
+                lines.push(format!("loop: {loop_label}"));
+            }
+            lines
+        }
+        VizNodeKind::Decision {
+            condition,
+            depth: _,
+        } => vec![format!("{condition}?")],
+        VizNodeKind::Join { depth: _, label } => {
+            vec![label.clone().unwrap_or_else(|| "join".to_string())]
+        }
+        VizNodeKind::Fork {
+            group: _,
+            max_concurrency,
+        } => vec![format_fork_label(*max_concurrency)],
+    }
+}
+
+fn node_size(node: &VizNode) -> (i64, i64) {
+    let lines = node_lines(node);
+    let chars = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as i64;
+    let w = chars * CHAR_WIDTH + 2 * PADDING;
+    let h = lines.len() as i64 * LINE_HEIGHT + PADDING;
+    match node.kind {
+        // Diamonds need room for the text inside their inscribed rectangle.
+        VizNodeKind::Decision { .. } => (w + w / 2, h * 2),
+        VizNodeKind::Join { .. } => {
+            let d = w.max(h);
+            (d, d)
+        }
+        VizNodeKind::Fork { .. } => (w + 2 * PADDING, h),
+        _ => (w, h),
+    }
+}
+
+fn render_node(node: &VizNode, at: &Placed) -> String {
+    let (left, top) = (at.x - at.w / 2, at.y - at.h / 2);
+    let (right, bottom) = (left + at.w, top + at.h);
+    let style = "fill=\"#f8f8f8\" stroke=\"#333\"";
+    let shape = match node.kind {
+        VizNodeKind::VariableHeader { .. } => format!(
+            "<rect x=\"{left}\" y=\"{top}\" width=\"{}\" height=\"{}\" rx=\"8\" {style}/>",
+            at.w, at.h
+        ),
+        VizNodeKind::Step { .. } => format!(
+            "<rect x=\"{left}\" y=\"{top}\" width=\"{}\" height=\"{}\" {style}/>",
+            at.w, at.h
+        ),
+        VizNodeKind::Decision { .. } => format!(
+            "<polygon points=\"{x},{top} {right},{y} {x},{bottom} {left},{y}\" {style}/>",
+            x = at.x,
+            y = at.y
+        ),
+        VizNodeKind::Join { .. } => format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {style}/>",
+            at.x,
+            at.y,
+            at.w / 2
+        ),
+        VizNodeKind::Fork { .. } => format!(
+            "<polygon points=\"{},{top} {},{top} {right},{bottom} {left},{bottom}\" {style}/>",
+            left + PADDING,
+            right - PADDING
+        ),
+    };
+
+    let lines = node_lines(node);
+    let first_baseline = at.y - (lines.len() as i64 - 1) * LINE_HEIGHT / 2 + 4;
+    let mut out = format!("  <g id=\"{}\">\n    {shape}\n", escape_xml(&node.id));
+    for (idx, line) in lines.iter().enumerate() {
+        out.push_str(&format!(
+            "    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            at.x,
+            first_baseline + idx as i64 * LINE_HEIGHT,
+            escape_xml(line)
+        ));
+    }
+    out.push_str("  </g>\n");
+    out
+}
+
+fn render_edge(edge: &VizEdge, from: &Placed, to: &Placed) -> String {
+    let dash = match edge.kind {
+        VizEdgeKind::OnFail => " stroke-dasharray=\"6 3\"",
+        VizEdgeKind::LoopBack => " stroke-dasharray=\"2 3\"",
+        _ => "",
+    };
+    let (path, label_x, label_y) = if to.rank > from.rank {
+        let (x1, y1) = (from.x, from.y + from.h / 2);
+        let (x2, y2) = (to.x, to.y - to.h / 2);
+        (
+            format!("M {x1} {y1} L {x2} {y2}"),
+            (x1 + x2) / 2 + 4,
+            (y1 + y2) / 2,
+        )
+    } else {
+        let (x1, x2) = (from.x + from.w / 2, to.x + to.w / 2);
+        let bulge = x1.max(x2) + BACK_EDGE_BULGE;
+        (
+            format!(
+                "M {x1} {y1} C {bulge} {y1}, {bulge} {y2}, {x2} {y2}",
+                y1 = from.y,
+                y2 = to.y
+            ),
+            bulge - BACK_EDGE_BULGE / 4,
+            (from.y + to.y) / 2,
+        )
+    };
+
+    let mut out = format!(
+        "  <path d=\"{path}\" fill=\"none\" stroke=\"#333\"{dash} marker-end=\"url(#arrow)\"/>\n"
+    );
+    if let Some(label) = edge_label(edge) {
+        out.push_str(&format!(
+            "  <text x=\"{label_x}\" y=\"{label_y}\" font-size=\"10\" fill=\"#555\">{}</text>\n",
+            escape_xml(&label)
+        ));
+    }
+    out
+}
+
+fn edge_label(edge: &VizEdge) -> Option<String> {
+    match edge.kind {
+        VizEdgeKind::Normal => None,
+        VizEdgeKind::BranchYes => Some("Yes".to_string()),
+        VizEdgeKind::BranchNo => Some("No".to_string()),
+        VizEdgeKind::OnFail => Some(format!(
+            "on_fail: {}",
+            edge.label.as_deref().unwrap_or("unknown")
+        )),
+        VizEdgeKind::LoopBack => edge.label.clone(),
+    }
+}
+
+pub(crate) fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::parser::parse_skill;
+
+    fn compile_doc(markdown: &str) -> ExecutionPlan {
+        let doc = parse_skill(markdown).expect("parse should succeed");
+        compile(&doc).expect("compile should succeed")
+    }
+
+    #[test]
+    fn test_render_svg_draws_nodes_in_layers() {
+        let plan = compile_doc(
+            r#"---
+name = "svg-demo"
+---
+## Build
+Tool: codex
+Build project.
+
+## IF ${HAS_TESTS} && ${A} < 3
+## Test
+OnFail: retry 2
+Run tests.
+## ENDIF
+"#,
+        );
+
+        let svg = render_svg(&plan);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(">1. Build</text>"));
+        assert!(svg.contains(">[codex]</text>"));
+        assert!(svg.contains("<polygon"), "decision diamond: {svg}");
+        assert!(svg.contains("${A} &lt; 3?"), "escaped condition: {svg}");
+        assert!(svg.contains(">on_fail: retry:2</text>"));
+        assert!(!svg.contains("id=\"ROOT\""));
+
+        let y_of = |id: &str| {
+            let graph = build_graph(&plan);
+            let nodes: Vec<&VizNode> = graph.nodes.iter().filter(|n| n.id != "ROOT").collect();
+            let edges: Vec<&VizEdge> = graph.edges.iter().collect();
+            let (placed, _, _) = layout(&nodes, &edges);
+            placed[id].y
+        };
+        assert!(y_of("S1") < y_of("D1"));
+        assert!(y_of("D1") < y_of("D2"));
+        assert!(y_of("D2") < y_of("S2"));
+    }
+
+    #[test]
+    fn test_render_svg_loop_back_edge_curves_upward() {
+        let plan = compile_doc(
+            r#"---
+name = "retry"
+---
+## WHILE ${TESTS_FAILING} MAX 5
+## Fix
+Fix tests.
+## Rerun
+Run tests.
+## ENDWHILE
+"#,
+        );
+
+        let svg = render_svg(&plan);
+        assert!(svg.contains(" C "), "loop-back edge is a curve: {svg}");
+        assert!(svg.contains("stroke-dasharray=\"2 3\""));
+        assert!(svg.contains(">while ${TESTS_FAILING} (max 5)</text>"));
+    }
+}
@@ -0,0 +1,270 @@
+use super::*;
+use crate::compiler::compile;
+use crate::parser::parse_skill;
+
+fn compile_doc(markdown: &str) -> ExecutionPlan {
+    let doc = parse_skill(markdown).expect("parse should succeed");
+    compile(&doc).expect("compile should succeed")
+}
+
+fn find_decision_node<'a>(graph: &'a VizGraph, condition: &str) -> Option<&'a VizNode> {
+    graph.nodes.iter().find(|node| {
+        matches!(
+            &node.kind,
+            VizNodeKind::Decision {
+                condition: c,
+                depth: _
+            } if c == condition
+        )
+    })
+}
+
+#[test]
+fn test_build_graph_linear_plan() {
+    let plan = compile_doc(
+        r#"---
+name = "linear"
+---
+## Build
+Tool: codex
+Build project.
+
+## Test
+Tool: claude-code
+Run tests.
+"#,
+    );
+    let graph = build_graph(&plan);
+
+    assert!(
+        graph
+            .nodes
+            .iter()
+            .all(|n| !matches!(n.kind, VizNodeKind::Decision { .. }))
+    );
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.from == "S1" && e.to == "S2" && e.kind == VizEdgeKind::Normal)
+    );
+}
+
+#[test]
+fn test_build_graph_single_if_else() {
+    let plan = compile_doc(
+        r#"---
+name = "if-else"
+---
+## IF has_tests
+## Run Tests
+Tool: codex
+Run unit tests.
+## ELSE
+## Skip Tests
+No tests available.
+## ENDIF
+"#,
+    );
+    let graph = build_graph(&plan);
+
+    let decision = find_decision_node(&graph, "has_tests").expect("missing decision");
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| { e.from == decision.id && e.to == "S1" && e.kind == VizEdgeKind::BranchYes })
+    );
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| { e.from == decision.id && e.to == "S2" && e.kind == VizEdgeKind::BranchNo })
+    );
+}
+
+#[test]
+fn test_build_graph_nested_if_in_else() {
+    let plan = compile_doc(
+        r#"---
+name = "nested"
+---
+## IF ${USER_APPROVES}
+## Apply Plan
+Proceed.
+## ELSE
+## IF ${USER_MODIFIES}
+## Resume
+Resume with edits.
+## ELSE
+## Abandon
+Stop.
+## ENDIF
+## ENDIF
+"#,
+    );
+    let graph = build_graph(&plan);
+
+    let outer = find_decision_node(&graph, "${USER_APPROVES}").expect("missing outer");
+    let inner = find_decision_node(&graph, "${USER_MODIFIES}").expect("missing inner");
+
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| { e.from == outer.id && e.to == inner.id && e.kind == VizEdgeKind::BranchNo })
+    );
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.from == inner.id && e.to == "S2" && e.kind == VizEdgeKind::BranchYes)
+    );
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.from == inner.id && e.to == "S3" && e.kind == VizEdgeKind::BranchNo)
+    );
+}
+
+#[test]
+fn test_build_graph_if_inside_for_preserves_loop_labels() {
+    let plan = compile_doc(
+        r#"---
+name = "if-for"
+---
+## FOR item IN ${ITEMS}
+## IF ${IS_VALID}
+## Process Item
+Tool: codex
+OnFail: retry 2
+Process ${item}.
+## ELSE
+## Skip Item
+OnFail: skip
+Skip ${item}.
+## ENDIF
+## ENDFOR
+"#,
+    );
+
+    let graph = build_graph(&plan);
+    let process = graph
+        .nodes
+        .iter()
+        .find(|node| {
+            matches!(
+                node.kind,
+                VizNodeKind::Step {
+                    step_id: 1,
+                    title: _,
+                    tool: _,
+                    loop_label: _
+                }
+            )
+        })
+        .expect("missing step");
+
+    match &process.kind {
+        VizNodeKind::Step { loop_label, .. } => {
+            assert_eq!(loop_label.as_deref(), Some("item in ${ITEMS}"));
+        }
+        _ => unreachable!("unexpected node kind"),
+    }
+
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.kind == VizEdgeKind::OnFail && e.from == "S1")
+    );
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.kind == VizEdgeKind::OnFail && e.from == "S2")
+    );
+}
+
+#[test]
+fn test_build_graph_parallel_group_forks_and_joins() {
+    let plan = compile_doc(
+        r#"---
+name = "fanout"
+---
+## Prepare
+Collect the diff.
+## PARALLEL MAX 2
+## Review Security
+Tool: codex
+Check for vulnerabilities.
+## Review Style
+Tool: gemini-cli
+Check naming.
+## ENDPARALLEL
+## Merge
+Combine findings.
+"#,
+    );
+    let graph = build_graph(&plan);
+    let has_edge = |from: &str, to: &str| {
+        graph
+            .edges
+            .iter()
+            .any(|e| e.from == from && e.to == to && e.kind == VizEdgeKind::Normal)
+    };
+
+    assert!(graph.nodes.iter().any(|n| n.id == "P1"
+        && n.kind
+            == VizNodeKind::Fork {
+                group: 1,
+                max_concurrency: Some(2)
+            }));
+    assert!(has_edge("S1", "P1"));
+    assert!(has_edge("P1", "S2") && has_edge("P1", "S3"));
+    assert!(has_edge("S2", "PJ1") && has_edge("S3", "PJ1"));
+    assert!(has_edge("PJ1", "S4"));
+    // Group members are not chained sequentially.
+    assert!(!has_edge("S2", "S3"));
+    assert!(!has_edge("S1", "S2") && !has_edge("S3", "S4"));
+}
+
+#[test]
+fn test_build_graph_disjunction_renders_one_decision_per_branch() {
+    let plan = compile_doc(
+        r#"---
+name = "or-else"
+---
+## IF !${CI} || ${COUNT} > 3
+## Local
+Run locally.
+## ELSE
+## Remote
+Run remotely.
+## ENDIF
+"#,
+    );
+    let graph = build_graph(&plan);
+
+    let decisions: Vec<&VizNode> = graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(n.kind, VizNodeKind::Decision { .. }))
+        .collect();
+    assert_eq!(decisions.len(), 1, "{:?}", graph.nodes);
+    let decision = find_decision_node(&graph, "!${CI} || ${COUNT} > 3").expect("missing decision");
+    for (to, kind) in [
+        ("S1", VizEdgeKind::BranchYes),
+        ("S2", VizEdgeKind::BranchNo),
+    ] {
+        assert!(
+            graph
+                .edges
+                .iter()
+                .any(|e| e.from == decision.id && e.to == to && e.kind == kind),
+            "{:?}",
+            graph.edges
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Workspaces: one repository holding several skill packages.
+//!
+//! A `weave-workspace.toml` at the repository root lists the member package
+//! directories:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["skills/*", "tools/lint"]
+//! ```
+//!
+//! An entry ending in `/*` includes every non-hidden subdirectory. Members
+//! share the workspace root's `weave.lock`, `weave compile-all` compiles the
+//! patterns of every member, and `weave install <repo> --member <path>`
+//! installs a single member from git.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::package::{Lockfile, VersionConflict, version_satisfies};
+use crate::parser::{DependencySpec, SkillConfig};
+
+/// File name of the workspace manifest.
+pub const WORKSPACE_MANIFEST: &str = "weave-workspace.toml";
+
+/// Parsed `weave-workspace.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct WorkspaceManifest {
+    pub workspace: WorkspaceSection,
+}
+
+/// `[workspace]` section of `weave-workspace.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct WorkspaceSection {
+    /// Member directories relative to the workspace root; `dir/*` globs
+    /// every subdirectory of `dir`.
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+impl WorkspaceManifest {
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(content).context("invalid workspace manifest")?;
+        for entry in &manifest.workspace.members {
+            let (prefix, _) = split_glob(entry);
+            if !prefix.is_empty() {
+                normalize_member_path(prefix)?;
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Whether the member at `path` is listed, directly or through a glob.
+    pub fn includes(&self, path: &str) -> bool {
+        let Ok(path) = normalize_member_path(path) else {
+            return false;
+        };
+        let parent = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+        self.workspace
+            .members
+            .iter()
+            .any(|entry| match split_glob(entry) {
+                (prefix, true) => normalize_prefix(prefix) == parent,
+                (literal, false) => normalize_member_path(literal).is_ok_and(|m| m == path),
+            })
+    }
+}
+
+/// A loaded workspace and its expanded members.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// One member package of a workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceMember {
+    /// Package name: the member's directory name, as for `weave install --path`.
+    pub name: String,
+    /// `/`-separated path relative to the workspace root.
+    pub path: String,
+    /// `[skill] version` from the member's `.skill.toml`.
+    pub version: Option<String>,
+    /// `[dependencies]` from the member's `.skill.toml`.
+    pub dependencies: BTreeMap<String, DependencySpec>,
+}
+
+impl Workspace {
+    /// Load the workspace whose manifest is at `root`.
+    pub fn load(root: &Path) -> Result<Self> {
+        let manifest_path = root.join(WORKSPACE_MANIFEST);
+        let content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        let manifest = WorkspaceManifest::parse(&content)
+            .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+
+        let mut paths = Vec::new();
+        for entry in &manifest.workspace.members {
+            match split_glob(entry) {
+                (prefix, true) => paths.extend(subdirectories(root, &normalize_prefix(prefix))?),
+                (literal, false) => {
+                    let path = normalize_member_path(literal)?;
+                    if !root.join(&path).is_dir() {
+                        bail!("workspace member '{path}' is not a directory");
+                    }
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+        paths.dedup();
+
+        let mut members: Vec<WorkspaceMember> = Vec::with_capacity(paths.len());
+        for path in paths {
+            let member = load_member(root, path)?;
+            if let Some(other) = members.iter().find(|m| m.name == member.name) {
+                bail!(
+                    "workspace members '{}' and '{}' share the package name '{}'",
+                    other.path,
+                    member.path,
+                    member.name
+                );
+            }
+            members.push(member);
+        }
+        Ok(Self {
+            root: root.to_path_buf(),
+            members,
+        })
+    }
+
+    /// Load the workspace enclosing `start`, if any.
+    pub fn discover(start: &Path) -> Result<Option<Self>> {
+        find_workspace_root(start)
+            .map(|root| Self::load(&root))
+            .transpose()
+    }
+
+    pub fn member_dir(&self, member: &WorkspaceMember) -> PathBuf {
+        self.root.join(&member.path)
+    }
+
+    /// Member `[dependencies]` that neither a sibling member nor a package
+    /// locked in `lockfile` satisfies.
+    pub fn dependency_conflicts(&self, lockfile: &Lockfile) -> Vec<VersionConflict> {
+        let mut conflicts = Vec::new();
+        for member in &self.members {
+            for (package, spec) in &member.dependencies {
+                let version = match self.members.iter().find(|m| &m.name == package) {
+                    Some(sibling) => Some(sibling.version.clone()),
+                    None => lockfile
+                        .package
+                        .iter()
+                        .find(|p| &p.name == package)
+                        .map(|p| p.version.clone()),
+                };
+                let locked = version.clone().flatten();
+                if !version_satisfies(&spec.version, locked.as_deref()) {
+                    conflicts.push(VersionConflict {
+                        dependent: member.name.clone(),
+                        package: package.clone(),
+                        requirement: spec.version.clone(),
+                        locked,
+                        installed: version.is_some(),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// Nearest ancestor of `start` (inclusive) containing a workspace manifest.
+pub fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(WORKSPACE_MANIFEST).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Workspace root whose shared `weave.lock` governs `dir`: the enclosing
+/// workspace when `dir` is its root or lies inside one of its members. A
+/// directory below a workspace that belongs to no member keeps its own lock.
+pub fn lockfile_root(dir: &Path) -> Result<Option<PathBuf>> {
+    let Some(ws) = Workspace::discover(dir)? else {
+        return Ok(None);
+    };
+    let governed = dir == ws.root
+        || ws
+            .members
+            .iter()
+            .any(|member| dir.starts_with(ws.member_dir(member)));
+    Ok(governed.then_some(ws.root))
+}
+
+/// Validate a member path and return it `/`-separated without `.` segments
+/// or trailing slashes. Absolute paths and `..` are rejected.
+pub fn normalize_member_path(path: &str) -> Result<String> {
+    let mut segments = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => bail!("invalid workspace member path '{path}': must be relative without '..'"),
+        }
+    }
+    if segments.is_empty() {
+        bail!("invalid workspace member path '{path}': empty");
+    }
+    Ok(segments.join("/"))
+}
+
+/// `(prefix, is_glob)` of a members entry; `skills/*` is `("skills", true)`.
+fn split_glob(entry: &str) -> (&str, bool) {
+    if entry == "*" {
+        return ("", true);
+    }
+    match entry.strip_suffix("/*") {
+        Some(prefix) => (prefix, true),
+        None => (entry, false),
+    }
+}
+
+fn normalize_prefix(prefix: &str) -> String {
+    normalize_member_path(prefix).unwrap_or_default()
+}
+
+/// Non-hidden subdirectories of `root/prefix` as member paths.
+fn subdirectories(root: &Path, prefix: &str) -> Result<Vec<String>> {
+    let dir = root.join(prefix);
+    let mut paths = Vec::new();
+    for entry in
+        std::fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || !entry.file_type()?.is_dir() {
+            continue;
+        }
+        paths.push(if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        });
+    }
+    Ok(paths)
+}
+
+fn load_member(root: &Path, path: String) -> Result<WorkspaceMember> {
+    let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+    let config_path = root.join(&path).join(".skill.toml");
+    let config = match std::fs::read_to_string(&config_path) {
+        Ok(content) => Some(
+            toml::from_str::<SkillConfig>(&content)
+                .with_context(|| format!("failed to parse {}", config_path.display()))?,
+        ),
+        Err(_) => None,
+    };
+    let (version, dependencies) = config
+        .map(|config| (config.skill.version, config.dependencies))
+        .unwrap_or_default();
+    Ok(WorkspaceMember {
+        name,
+        path,
+        version,
+        dependencies,
+    })
+}
+
+#[cfg(test)]
+#[path = "workspace_tests.rs"]
+mod tests;
@@ -0,0 +1,148 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+use super::*;
+use crate::package::{LockedPackage, SourceKind};
+
+fn write_member(root: &Path, path: &str, skill_toml: Option<&str>) {
+    let dir = root.join(path);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("SKILL.md"), "# Skill\n").unwrap();
+    if let Some(content) = skill_toml {
+        fs::write(dir.join(".skill.toml"), content).unwrap();
+    }
+}
+
+fn workspace_root(members: &str) -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join(WORKSPACE_MANIFEST),
+        format!("[workspace]\nmembers = {members}\n"),
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn load_expands_globs_and_reads_member_manifests() {
+    let tmp = workspace_root(r#"["skills/*", "./tools/lint/"]"#);
+    write_member(
+        tmp.path(),
+        "skills/review",
+        Some("[skill]\nname = \"review\"\nversion = \"1.2.0\"\n"),
+    );
+    write_member(tmp.path(), "skills/commit", None);
+    fs::create_dir_all(tmp.path().join("skills/.hidden")).unwrap();
+    write_member(tmp.path(), "tools/lint", None);
+
+    let ws = Workspace::load(tmp.path()).unwrap();
+    let paths: Vec<&str> = ws.members.iter().map(|m| m.path.as_str()).collect();
+    assert_eq!(paths, ["skills/commit", "skills/review", "tools/lint"]);
+    assert_eq!(ws.members[1].name, "review");
+    assert_eq!(ws.members[1].version.as_deref(), Some("1.2.0"));
+    assert_eq!(ws.member_dir(&ws.members[2]), tmp.path().join("tools/lint"));
+}
+
+#[test]
+fn load_rejects_missing_escaping_and_duplicate_members() {
+    let missing = workspace_root(r#"["skills/none"]"#);
+    let err = Workspace::load(missing.path()).unwrap_err();
+    assert!(format!("{err:#}").contains("is not a directory"));
+
+    let escaping = workspace_root(r#"["../outside"]"#);
+    let err = Workspace::load(escaping.path()).unwrap_err();
+    assert!(format!("{err:#}").contains("without '..'"));
+
+    let duplicate = workspace_root(r#"["a/*", "b/*"]"#);
+    write_member(duplicate.path(), "a/review", None);
+    write_member(duplicate.path(), "b/review", None);
+    let err = Workspace::load(duplicate.path()).unwrap_err();
+    assert!(err.to_string().contains("share the package name 'review'"));
+}
+
+#[test]
+fn manifest_includes_listed_and_globbed_members() {
+    let manifest =
+        WorkspaceManifest::parse("[workspace]\nmembers = [\"skills/*\", \"tools/lint\"]\n")
+            .unwrap();
+    assert!(manifest.includes("skills/review"));
+    assert!(manifest.includes("./skills/review/"));
+    assert!(manifest.includes("tools/lint"));
+    assert!(!manifest.includes("skills/review/nested"));
+    assert!(!manifest.includes("tools"));
+    assert!(!manifest.includes("../skills/review"));
+}
+
+#[test]
+fn discover_finds_the_enclosing_workspace() {
+    let tmp = workspace_root(r#"["skills/*"]"#);
+    write_member(tmp.path(), "skills/review", None);
+    let nested = tmp.path().join("skills/review");
+
+    assert_eq!(find_workspace_root(&nested), Some(tmp.path().to_path_buf()));
+    let ws = Workspace::discover(&nested).unwrap().unwrap();
+    assert_eq!(ws.members.len(), 1);
+
+    let outside = TempDir::new().unwrap();
+    assert!(Workspace::discover(outside.path()).unwrap().is_none());
+}
+
+#[test]
+fn lockfile_root_covers_the_root_and_members_only() {
+    let tmp = workspace_root(r#"["skills/*"]"#);
+    write_member(tmp.path(), "skills/review", None);
+    let unrelated = tmp.path().join("scratch/project");
+    fs::create_dir_all(&unrelated).unwrap();
+    let root = Some(tmp.path().to_path_buf());
+
+    assert_eq!(lockfile_root(tmp.path()).unwrap(), root);
+    assert_eq!(
+        lockfile_root(&tmp.path().join("skills/review")).unwrap(),
+        root
+    );
+    assert_eq!(lockfile_root(&unrelated).unwrap(), None);
+    let outside = TempDir::new().unwrap();
+    assert_eq!(lockfile_root(outside.path()).unwrap(), None);
+}
+
+#[test]
+fn dependency_conflicts_check_siblings_before_the_lockfile() {
+    let tmp = workspace_root(r#"["skills/*"]"#);
+    write_member(
+        tmp.path(),
+        "skills/app",
+        Some(
+            "[skill]\nname = \"app\"\n\n[dependencies]\n\
+             lib = { version = \"^1\" }\nutil = { version = \"^2\" }\n\
+             gone = { version = \"*\" }\n",
+        ),
+    );
+    write_member(
+        tmp.path(),
+        "skills/lib",
+        Some("[skill]\nname = \"lib\"\nversion = \"1.4.0\"\n"),
+    );
+    let ws = Workspace::load(tmp.path()).unwrap();
+    let lockfile = Lockfile::with_packages(vec![LockedPackage {
+        name: "util".to_string(),
+        repo: "https://example.com/util.git".to_string(),
+        commit: "0123456789".to_string(),
+        version: Some("1.0.0".to_string()),
+        source_kind: SourceKind::Git,
+        ..Default::default()
+    }]);
+
+    let conflicts = ws.dependency_conflicts(&lockfile);
+    let summary: Vec<String> = conflicts
+        .iter()
+        .map(|c| format!("{} {c}", c.dependent))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "app requires gone *, but it is not installed",
+            "app requires util ^2, but 1.0.0 is locked",
+        ]
+    );
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::process::Command;
+
+fn weave_cmd() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_weave"))
+}
+
+#[test]
+fn test_subcommand_reports_fixture_results_and_fails_on_mismatch() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("SKILL.md"),
+        "---\nname = \"hello\"\n---\n## Greet\nTool: bash\nSay hello to ${NAME}.\n",
+    )
+    .unwrap();
+    fs::create_dir_all(tmp.path().join("tests")).unwrap();
+    fs::write(
+        tmp.path().join("tests/ok.toml"),
+        "[expect]\nsteps = [\"Greet\"]\nvariables = [\"NAME\"]\n",
+    )
+    .unwrap();
+
+    let output = weave_cmd()
+        .arg("test")
+        .arg(tmp.path())
+        .output()
+        .expect("run weave test");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("1 test(s): 1 OK, 0 FAILED"), "{stderr}");
+
+    fs::write(
+        tmp.path().join("tests/wrong.toml"),
+        "[expect]\nsteps = [\"Wave\"]\n",
+    )
+    .unwrap();
+    let output = weave_cmd()
+        .arg("test")
+        .arg(tmp.path())
+        .output()
+        .expect("run weave test");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{stderr}");
+    assert!(stderr.contains("wrong.toml ... FAILED"), "{stderr}");
+    assert!(
+        stderr.contains(r#"steps: expected ["Wave"], got ["Greet"]"#),
+        "{stderr}"
+    );
+}
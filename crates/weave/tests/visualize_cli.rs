@@ -88,7 +88,9 @@ fn visualize_reads_plan_from_stdin_when_dash_is_used() {
             loop_var: None,
             session: None,
             workspace_access: None,
+            ..Default::default()
         }],
+        ..Default::default()
     };
     let plan_toml = plan_to_toml(&plan).expect("serialize plan toml");
 
@@ -124,6 +126,64 @@ fn visualize_reads_plan_from_stdin_when_dash_is_used() {
     );
 }
 
+#[test]
+fn visualize_svg_and_html_print_to_stdout() {
+    use weave::compiler::{ExecutionPlan, FailAction, PlanStep, plan_to_toml};
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let plan_path = tmp.path().join("demo.plan.toml");
+    let plan = ExecutionPlan {
+        name: "demo".to_string(),
+        description: String::new(),
+        variables: Vec::new(),
+        steps: vec![PlanStep {
+            id: 1,
+            title: "Build".to_string(),
+            tool: Some("codex".to_string()),
+            prompt: "Build project".to_string(),
+            tier: None,
+            depends_on: Vec::new(),
+            on_fail: FailAction::Abort,
+            condition: None,
+            loop_var: None,
+            session: None,
+            workspace_access: None,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    fs::write(
+        &plan_path,
+        plan_to_toml(&plan).expect("serialize plan toml"),
+    )
+    .expect("write plan toml");
+
+    for (flag, expected) in [
+        ("--svg", "<svg xmlns="),
+        ("--html", "<pre class=\"mermaid\">"),
+    ] {
+        let output = weave_cmd()
+            .arg("visualize")
+            .arg(&plan_path)
+            .arg(flag)
+            .output()
+            .expect("run weave visualize");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "{flag} should succeed: {stderr}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(expected), "{flag} output: {stdout}");
+        assert!(stdout.contains("1. Build"), "{flag} output: {stdout}");
+    }
+
+    let output = weave_cmd()
+        .arg("visualize")
+        .arg(&plan_path)
+        .args(["--svg", "--html"])
+        .output()
+        .expect("run weave visualize with conflicting flags");
+    assert!(!output.status.success());
+}
+
 #[cfg(feature = "visualize-png-dot")]
 #[test]
 fn visualize_png_writes_file_when_dot_is_available() {
@@ -151,7 +211,9 @@ fn visualize_png_writes_file_when_dot_is_available() {
             loop_var: None,
             session: None,
             workspace_access: None,
+            ..Default::default()
         }],
+        ..Default::default()
     };
 
     weave::visualize::render_png(&plan, &png).expect("png rendering should succeed");